@@ -0,0 +1,250 @@
+//! Allow-listing of tokens before transfers
+//!
+//! An [`AssetAllowList`] names the assets transfers may move, keyed by their
+//! identifier on the chain they live on: a pallet-assets ID such as `1984`,
+//! or an ERC-20 contract address. Each entry records the symbol and decimals
+//! the asset is expected to have. Adapters check the list before building a
+//! transfer and compare the entry against the asset's on-chain metadata, so
+//! a look-alike token at another ID, or an allowed ID whose metadata no
+//! longer matches, is refused with an [`AssetPolicyError`].
+//!
+//! ```rust
+//! use apex_sdk_core::asset_policy::{AssetAllowList, AssetPolicyError};
+//!
+//! let allow_list = AssetAllowList::new()
+//!     .with_asset("1984", "USDt", 6)
+//!     .with_asset("0xdAC17F958D2ee523a2206206994597C13D831ec7", "USDT", 6);
+//!
+//! assert!(allow_list.verify("1984", "USDt", 6).is_ok());
+//! assert_eq!(
+//!     allow_list.verify("1985", "USDt", 6),
+//!     Err(AssetPolicyError::NotAllowed("1985".to_string()))
+//! );
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Metadata an allowed asset must have
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpectedAsset {
+    /// Ticker symbol, compared exactly
+    pub symbol: String,
+    /// Number of decimals in a display amount
+    pub decimals: u8,
+}
+
+/// Transfer refused by an [`AssetAllowList`]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AssetPolicyError {
+    /// The asset has no allow-list entry
+    #[error("Asset {0} is not on the allow-list")]
+    NotAllowed(String),
+
+    /// On-chain symbol differs from the allow-list entry
+    #[error("Asset {asset} has symbol {actual:?}, expected {expected:?}")]
+    SymbolMismatch {
+        /// Asset identifier
+        asset: String,
+        /// Symbol in the allow-list
+        expected: String,
+        /// Symbol on chain
+        actual: String,
+    },
+
+    /// On-chain decimals differ from the allow-list entry
+    #[error("Asset {asset} has {actual} decimals, expected {expected}")]
+    DecimalsMismatch {
+        /// Asset identifier
+        asset: String,
+        /// Decimals in the allow-list
+        expected: u8,
+        /// Decimals on chain
+        actual: u8,
+    },
+}
+
+impl crate::HasErrorCode for AssetPolicyError {
+    fn code(&self) -> crate::ErrorCode {
+        crate::ErrorCode::new(match self {
+            AssetPolicyError::NotAllowed(_) => 1140,
+            AssetPolicyError::SymbolMismatch { .. } => 1141,
+            AssetPolicyError::DecimalsMismatch { .. } => 1142,
+        })
+    }
+}
+
+impl From<AssetPolicyError> for crate::SdkError {
+    fn from(err: AssetPolicyError) -> Self {
+        crate::SdkError::TransactionError(format!("Asset policy violation: {}", err))
+    }
+}
+
+/// Assets transfers may move, with their expected metadata
+///
+/// Identifiers are matched case-insensitively, so checksummed and lowercase
+/// contract addresses are the same entry. The list serializes as a map from
+/// identifier to [`ExpectedAsset`], to be kept in configuration files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    from = "BTreeMap<String, ExpectedAsset>",
+    into = "BTreeMap<String, ExpectedAsset>"
+)]
+pub struct AssetAllowList {
+    assets: BTreeMap<String, ExpectedAsset>,
+}
+
+impl From<BTreeMap<String, ExpectedAsset>> for AssetAllowList {
+    fn from(assets: BTreeMap<String, ExpectedAsset>) -> Self {
+        Self {
+            assets: assets
+                .into_iter()
+                .map(|(id, expected)| (normalize(&id), expected))
+                .collect(),
+        }
+    }
+}
+
+impl From<AssetAllowList> for BTreeMap<String, ExpectedAsset> {
+    fn from(allow_list: AssetAllowList) -> Self {
+        allow_list.assets
+    }
+}
+
+impl AssetAllowList {
+    /// Create an empty list, which allows nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow an asset with the given symbol and decimals
+    pub fn with_asset(
+        mut self,
+        id: impl AsRef<str>,
+        symbol: impl Into<String>,
+        decimals: u8,
+    ) -> Self {
+        self.allow(id, symbol, decimals);
+        self
+    }
+
+    /// Allow an asset, replacing its entry if it has one
+    pub fn allow(&mut self, id: impl AsRef<str>, symbol: impl Into<String>, decimals: u8) {
+        self.assets.insert(
+            normalize(id.as_ref()),
+            ExpectedAsset {
+                symbol: symbol.into(),
+                decimals,
+            },
+        );
+    }
+
+    /// Remove an asset; returns its entry if it had one
+    pub fn revoke(&mut self, id: &str) -> Option<ExpectedAsset> {
+        self.assets.remove(&normalize(id))
+    }
+
+    /// Entry of an asset
+    pub fn get(&self, id: &str) -> Option<&ExpectedAsset> {
+        self.assets.get(&normalize(id))
+    }
+
+    /// Number of allowed assets
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    /// Whether the list allows nothing
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    /// Entry of an asset, or [`AssetPolicyError::NotAllowed`]
+    pub fn check_allowed(&self, id: &str) -> Result<&ExpectedAsset, AssetPolicyError> {
+        self.get(id)
+            .ok_or_else(|| AssetPolicyError::NotAllowed(id.to_string()))
+    }
+
+    /// Check an asset's on-chain symbol and decimals against its entry
+    pub fn verify(&self, id: &str, symbol: &str, decimals: u8) -> Result<(), AssetPolicyError> {
+        let expected = self.check_allowed(id)?;
+        if expected.symbol != symbol {
+            return Err(AssetPolicyError::SymbolMismatch {
+                asset: id.to_string(),
+                expected: expected.symbol.clone(),
+                actual: symbol.to_string(),
+            });
+        }
+        if expected.decimals != decimals {
+            return Err(AssetPolicyError::DecimalsMismatch {
+                asset: id.to_string(),
+                expected: expected.decimals,
+                actual: decimals,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn normalize(id: &str) -> String {
+    id.trim().to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HasErrorCode;
+
+    const USDT: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+
+    #[test]
+    fn test_verify_checks_entry_and_metadata() {
+        let allow_list = AssetAllowList::new().with_asset(USDT, "USDT", 6);
+
+        // Contract addresses match regardless of checksum casing
+        assert!(allow_list.verify(&USDT.to_lowercase(), "USDT", 6).is_ok());
+
+        // A look-alike symbol with a Cyrillic "Т"
+        let err = allow_list.verify(USDT, "USDТ", 6).unwrap_err();
+        assert!(matches!(err, AssetPolicyError::SymbolMismatch { .. }));
+        assert_eq!(err.code().get(), 1141);
+
+        assert_eq!(
+            allow_list.verify(USDT, "USDT", 18),
+            Err(AssetPolicyError::DecimalsMismatch {
+                asset: USDT.to_string(),
+                expected: 6,
+                actual: 18,
+            })
+        );
+        assert!(matches!(
+            allow_list.verify("1984", "USDT", 6),
+            Err(AssetPolicyError::NotAllowed(_))
+        ));
+    }
+
+    #[test]
+    fn test_allow_list_serializes_as_map() {
+        let mut allow_list = AssetAllowList::new().with_asset("1984", "USDt", 6);
+        let json = serde_json::to_value(&allow_list).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "1984": { "symbol": "USDt", "decimals": 6 } })
+        );
+        assert_eq!(
+            serde_json::from_value::<AssetAllowList>(json).unwrap(),
+            allow_list
+        );
+
+        let loaded: AssetAllowList = serde_json::from_str(&format!(
+            r#"{{"{}": {{"symbol": "USDT", "decimals": 6}}}}"#,
+            USDT
+        ))
+        .unwrap();
+        assert!(loaded.get(&USDT.to_lowercase()).is_some());
+
+        assert!(allow_list.revoke("1984").is_some());
+        assert!(allow_list.is_empty());
+    }
+}
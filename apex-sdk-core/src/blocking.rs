@@ -0,0 +1,316 @@
+//! Bounded pool for blocking work
+//!
+//! CPU-heavy or blocking calls (system probing, key derivation, signature
+//! verification) must not run on the async executor, where they stall every
+//! other task on the worker thread. [`BlockingPool`] moves them onto tokio's
+//! blocking threads while capping how many run at once, and tracks how many
+//! are waiting so a saturated pool shows up in metrics.
+//!
+//! ```rust
+//! use apex_sdk_core::blocking::BlockingPool;
+//!
+//! # async fn example() -> Result<(), apex_sdk_core::blocking::BlockingError> {
+//! let pool = BlockingPool::global();
+//! let sum = pool.run(|| (0..1_000u64).sum::<u64>()).await?;
+//! assert_eq!(sum, 499_500);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::metrics::{Metric, MetricType, MetricsCollector};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use thiserror::Error;
+use tokio::sync::Semaphore;
+
+/// Default number of blocking tasks allowed to run at once
+pub const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
+static GLOBAL: OnceLock<Arc<BlockingPool>> = OnceLock::new();
+
+/// Error running a task on a [`BlockingPool`]
+#[derive(Error, Debug)]
+pub enum BlockingError {
+    #[error("Blocking task in pool '{pool}' panicked: {message}")]
+    Panicked { pool: String, message: String },
+
+    #[error("Blocking task in pool '{0}' was cancelled")]
+    Cancelled(String),
+}
+
+impl crate::HasErrorCode for BlockingError {
+    fn code(&self) -> crate::ErrorCode {
+        crate::ErrorCode::new(match self {
+            BlockingError::Panicked { .. } => 1120,
+            BlockingError::Cancelled(_) => 1121,
+        })
+    }
+}
+
+impl From<BlockingError> for crate::SdkError {
+    fn from(err: BlockingError) -> Self {
+        crate::SdkError::ProviderError(err.to_string())
+    }
+}
+
+/// Snapshot of a pool's load
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockingPoolStats {
+    /// Tasks waiting for a free slot
+    pub queued: usize,
+    /// Tasks currently running
+    pub active: usize,
+    /// Tasks finished since the pool was created
+    pub completed: u64,
+    /// Maximum number of concurrently running tasks
+    pub max_concurrency: usize,
+}
+
+/// Concurrency-limited wrapper around `tokio::task::spawn_blocking`
+#[derive(Debug)]
+pub struct BlockingPool {
+    name: String,
+    max_concurrency: usize,
+    permits: Arc<Semaphore>,
+    counters: Arc<Counters>,
+    metrics: Option<MetricsCollector>,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    queued: AtomicUsize,
+    active: AtomicUsize,
+    completed: AtomicU64,
+}
+
+impl BlockingPool {
+    /// Create a pool running at most `max_concurrency` tasks at once
+    pub fn new(name: impl Into<String>, max_concurrency: usize) -> Self {
+        let max_concurrency = max_concurrency.max(1);
+        Self {
+            name: name.into(),
+            max_concurrency,
+            permits: Arc::new(Semaphore::new(max_concurrency)),
+            counters: Arc::new(Counters::default()),
+            metrics: None,
+        }
+    }
+
+    /// Record queue depth and active task gauges into a metrics collector
+    pub fn with_metrics(mut self, metrics: MetricsCollector) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Shared process-wide pool
+    ///
+    /// Created with [`DEFAULT_MAX_CONCURRENCY`] on first use unless
+    /// [`BlockingPool::set_global`] installed one before.
+    pub fn global() -> Arc<BlockingPool> {
+        GLOBAL
+            .get_or_init(|| Arc::new(BlockingPool::new("global", DEFAULT_MAX_CONCURRENCY)))
+            .clone()
+    }
+
+    /// Install the process-wide pool
+    ///
+    /// Returns the pool back if the global pool was already initialized.
+    pub fn set_global(pool: Arc<BlockingPool>) -> Result<(), Arc<BlockingPool>> {
+        GLOBAL.set(pool)
+    }
+
+    /// Pool name, used as the `pool` metric label
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Current load of the pool
+    pub fn stats(&self) -> BlockingPoolStats {
+        BlockingPoolStats {
+            queued: self.counters.queued.load(Ordering::Relaxed),
+            active: self.counters.active.load(Ordering::Relaxed),
+            completed: self.counters.completed.load(Ordering::Relaxed),
+            max_concurrency: self.max_concurrency,
+        }
+    }
+
+    /// Run `f` on a blocking thread once a slot is free
+    ///
+    /// If the returned future is dropped while `f` is running, `f` still runs
+    /// to completion and keeps its slot until then.
+    pub async fn run<F, R>(&self, f: F) -> Result<R, BlockingError>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let permit = {
+            let _queued = QueueGuard::enter(self);
+            self.permits
+                .clone()
+                .acquire_owned()
+                .await
+                .map_err(|_| BlockingError::Cancelled(self.name.clone()))?
+        };
+
+        self.counters.active.fetch_add(1, Ordering::Relaxed);
+        self.record_load();
+
+        let counters = self.counters.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            let _active = ActiveGuard(counters);
+            f()
+        })
+        .await;
+        self.record_load();
+
+        result.map_err(|e| {
+            if e.is_panic() {
+                BlockingError::Panicked {
+                    pool: self.name.clone(),
+                    message: panic_message(e.into_panic()),
+                }
+            } else {
+                BlockingError::Cancelled(self.name.clone())
+            }
+        })
+    }
+
+    fn record_load(&self) {
+        let Some(metrics) = &self.metrics else {
+            return;
+        };
+        let stats = self.stats();
+        metrics.record(
+            Metric::new(
+                MetricType::QueueDepth,
+                "blocking_queue_depth",
+                stats.queued as f64,
+            )
+            .with_label("pool", &self.name)
+            .with_help("Blocking tasks waiting for a free slot"),
+        );
+        metrics.record(
+            Metric::new(
+                MetricType::QueueDepth,
+                "blocking_active_tasks",
+                stats.active as f64,
+            )
+            .with_label("pool", &self.name)
+            .with_help("Blocking tasks currently running"),
+        );
+    }
+}
+
+/// Counts a task as queued until it gets a slot or is cancelled
+struct QueueGuard<'a> {
+    pool: &'a BlockingPool,
+}
+
+impl<'a> QueueGuard<'a> {
+    fn enter(pool: &'a BlockingPool) -> Self {
+        pool.counters.queued.fetch_add(1, Ordering::Relaxed);
+        pool.record_load();
+        Self { pool }
+    }
+}
+
+impl Drop for QueueGuard<'_> {
+    fn drop(&mut self) {
+        self.pool.counters.queued.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Marks a running task finished, also when it panics
+struct ActiveGuard(Arc<Counters>);
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::Relaxed);
+        self.0.completed.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_run_returns_result() {
+        let pool = BlockingPool::new("test", 2);
+        assert_eq!(pool.run(|| 21 * 2).await.unwrap(), 42);
+
+        let stats = pool.stats();
+        assert_eq!(stats.completed, 1);
+        assert_eq!(stats.active, 0);
+        assert_eq!(stats.queued, 0);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_is_bounded() {
+        let pool = Arc::new(BlockingPool::new("bounded", 1));
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+
+        let first = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.run(move || release_rx.recv().unwrap()).await }
+        });
+        while pool.stats().active == 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        let second = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.run(|| 7).await }
+        });
+        while pool.stats().queued == 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(pool.stats().active, 1);
+
+        release_tx.send(()).unwrap();
+        first.await.unwrap().unwrap();
+        assert_eq!(second.await.unwrap().unwrap(), 7);
+        assert_eq!(pool.stats().completed, 2);
+        assert_eq!(pool.stats().queued, 0);
+    }
+
+    #[tokio::test]
+    async fn test_panic_is_reported() {
+        let pool = BlockingPool::new("panics", 1);
+        let err = pool
+            .run(|| -> u8 { panic!("derivation failed") })
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("derivation failed"));
+        assert_eq!(pool.stats().active, 0);
+        assert_eq!(pool.run(|| 1).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_record_queue_depth() {
+        let metrics = MetricsCollector::new();
+        let pool = BlockingPool::new("metered", 1).with_metrics(metrics.clone());
+        pool.run(|| ()).await.unwrap();
+
+        let recorded = metrics.get_metrics();
+        let depth = recorded
+            .iter()
+            .find(|m| m.name == "blocking_queue_depth")
+            .unwrap();
+        assert_eq!(depth.metric_type, MetricType::QueueDepth);
+        assert_eq!(depth.labels.get("pool").unwrap(), "metered");
+        assert!(recorded.iter().any(|m| m.name == "blocking_active_tasks"));
+    }
+}
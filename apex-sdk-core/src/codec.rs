@@ -0,0 +1,982 @@
+//! # Interchange Codecs
+//!
+//! Encodings for data that leaves the process: webhook payloads, keystore
+//! backups and other interchange files. [`Codec`] encodes and decodes any
+//! serde type with one of three backends:
+//!
+//! | Codec   | Content type               | Self-describing |
+//! |---------|----------------------------|-----------------|
+//! | `scale` | `application/octet-stream` | no              |
+//! | `json`  | `application/json`         | yes             |
+//! | `cbor`  | `application/cbor`         | yes             |
+//!
+//! The SCALE backend follows the `parity-scale-codec` layout for the serde
+//! data model: fixed-width little-endian integers, compact-prefixed strings,
+//! sequences and maps, `0`/`1`-prefixed options, one-byte enum variant
+//! indices and struct fields concatenated in declaration order. Floats are
+//! written as their little-endian IEEE 754 bits. Because SCALE is not
+//! self-describing, types that rely on `deserialize_any` (untagged enums,
+//! `serde_json::Value`, flattened structs) or that skip fields conditionally
+//! cannot be decoded from it.
+//!
+//! ```rust
+//! use apex_sdk_core::codec::Codec;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Transfer {
+//!     to: String,
+//!     amount: u128,
+//! }
+//!
+//! let transfer = Transfer { to: "5Grw".into(), amount: 1_000 };
+//! for codec in [Codec::Scale, Codec::Json, Codec::Cbor] {
+//!     let bytes = codec.encode(&transfer).unwrap();
+//!     assert_eq!(codec.decode::<Transfer>(&bytes).unwrap(), transfer);
+//! }
+//!
+//! let codec: Codec = "cbor".parse().unwrap();
+//! assert_eq!(codec.content_type(), "application/cbor");
+//! ```
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Errors returned by [`Codec`]
+#[derive(Error, Debug)]
+pub enum CodecError {
+    #[error("Failed to encode {codec}: {message}")]
+    Encode { codec: Codec, message: String },
+    #[error("Failed to decode {codec}: {message}")]
+    Decode { codec: Codec, message: String },
+}
+
+impl crate::HasErrorCode for CodecError {
+    fn code(&self) -> crate::ErrorCode {
+        crate::ErrorCode::new(match self {
+            CodecError::Encode { .. } => 1110,
+            CodecError::Decode { .. } => 1111,
+        })
+    }
+}
+
+/// Serialization format for interchange data
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Codec {
+    /// SCALE, as used on Substrate chains
+    Scale,
+    /// JSON
+    #[default]
+    Json,
+    /// CBOR (RFC 8949)
+    Cbor,
+}
+
+impl Codec {
+    /// All codecs
+    pub const ALL: [Codec; 3] = [Codec::Scale, Codec::Json, Codec::Cbor];
+
+    /// MIME type of encoded payloads
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Scale => "application/octet-stream",
+            Self::Json => "application/json",
+            Self::Cbor => "application/cbor",
+        }
+    }
+
+    /// Conventional file extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Scale => "scale",
+            Self::Json => "json",
+            Self::Cbor => "cbor",
+        }
+    }
+
+    /// Encode `value`
+    pub fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        let result = match self {
+            Self::Scale => {
+                let mut serializer = ScaleSerializer { output: Vec::new() };
+                value
+                    .serialize(&mut serializer)
+                    .map(|()| serializer.output)
+                    .map_err(|e| e.to_string())
+            }
+            Self::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+            Self::Cbor => {
+                let mut output = Vec::new();
+                ciborium::into_writer(value, &mut output)
+                    .map(|()| output)
+                    .map_err(|e| e.to_string())
+            }
+        };
+        result.map_err(|message| CodecError::Encode {
+            codec: *self,
+            message,
+        })
+    }
+
+    /// Decode a `T` from `bytes`, rejecting trailing data
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, CodecError> {
+        let result = match self {
+            Self::Scale => {
+                let mut deserializer = ScaleDeserializer { input: bytes };
+                T::deserialize(&mut deserializer)
+                    .and_then(|value| {
+                        if deserializer.input.is_empty() {
+                            Ok(value)
+                        } else {
+                            Err(ScaleError(format!(
+                                "{} trailing bytes",
+                                deserializer.input.len()
+                            )))
+                        }
+                    })
+                    .map_err(|e| e.to_string())
+            }
+            Self::Json => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+            Self::Cbor => ciborium::from_reader(bytes).map_err(|e| e.to_string()),
+        };
+        result.map_err(|message| CodecError::Decode {
+            codec: *self,
+            message,
+        })
+    }
+}
+
+impl fmt::Display for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scale => write!(f, "scale"),
+            Self::Json => write!(f, "json"),
+            Self::Cbor => write!(f, "cbor"),
+        }
+    }
+}
+
+impl FromStr for Codec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "scale" => Ok(Self::Scale),
+            "json" => Ok(Self::Json),
+            "cbor" => Ok(Self::Cbor),
+            other => Err(format!(
+                "Unknown codec '{}'. Expected one of: scale, json, cbor",
+                other
+            )),
+        }
+    }
+}
+
+/// Error raised by the SCALE serializer and deserializer
+#[derive(Debug)]
+struct ScaleError(String);
+
+impl fmt::Display for ScaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ScaleError {}
+
+impl ser::Error for ScaleError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+impl de::Error for ScaleError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Append the SCALE compact encoding of `value`
+fn write_compact(output: &mut Vec<u8>, value: u64) {
+    match value {
+        0..=0x3f => output.push((value as u8) << 2),
+        0x40..=0x3fff => output.extend_from_slice(&(((value as u16) << 2) | 0b01).to_le_bytes()),
+        0x4000..=0x3fff_ffff => {
+            output.extend_from_slice(&(((value as u32) << 2) | 0b10).to_le_bytes())
+        }
+        _ => {
+            let bytes = value.to_le_bytes();
+            let len = 8 - (value.leading_zeros() / 8) as usize;
+            output.push((((len - 4) as u8) << 2) | 0b11);
+            output.extend_from_slice(&bytes[..len]);
+        }
+    }
+}
+
+struct ScaleSerializer {
+    output: Vec<u8>,
+}
+
+impl ScaleSerializer {
+    fn write_len(&mut self, len: Option<usize>) -> Result<(), ScaleError> {
+        let len = len.ok_or_else(|| ScaleError("sequence length must be known".to_string()))?;
+        write_compact(&mut self.output, len as u64);
+        Ok(())
+    }
+
+    fn write_variant(&mut self, index: u32) -> Result<(), ScaleError> {
+        let index = u8::try_from(index)
+            .map_err(|_| ScaleError(format!("variant index {} exceeds 255", index)))?;
+        self.output.push(index);
+        Ok(())
+    }
+}
+
+impl ser::Serializer for &mut ScaleSerializer {
+    type Ok = ();
+    type Error = ScaleError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), ScaleError> {
+        self.output.push(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), ScaleError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), ScaleError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), ScaleError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), ScaleError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), ScaleError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), ScaleError> {
+        self.output.push(v);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), ScaleError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), ScaleError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), ScaleError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), ScaleError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), ScaleError> {
+        self.output.extend_from_slice(&v.to_bits().to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), ScaleError> {
+        self.output.extend_from_slice(&v.to_bits().to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), ScaleError> {
+        self.serialize_u32(v as u32)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), ScaleError> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), ScaleError> {
+        write_compact(&mut self.output, v.len() as u64);
+        self.output.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), ScaleError> {
+        self.output.push(0);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), ScaleError> {
+        self.output.push(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), ScaleError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), ScaleError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), ScaleError> {
+        self.write_variant(variant_index)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), ScaleError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), ScaleError> {
+        self.write_variant(variant_index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, ScaleError> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, ScaleError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, ScaleError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, ScaleError> {
+        self.write_variant(variant_index)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, ScaleError> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, ScaleError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, ScaleError> {
+        self.write_variant(variant_index)?;
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for &mut ScaleSerializer {
+    type Ok = ();
+    type Error = ScaleError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ScaleError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), ScaleError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut ScaleSerializer {
+    type Ok = ();
+    type Error = ScaleError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ScaleError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), ScaleError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut ScaleSerializer {
+    type Ok = ();
+    type Error = ScaleError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ScaleError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), ScaleError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut ScaleSerializer {
+    type Ok = ();
+    type Error = ScaleError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ScaleError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), ScaleError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for &mut ScaleSerializer {
+    type Ok = ();
+    type Error = ScaleError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), ScaleError> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ScaleError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), ScaleError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut ScaleSerializer {
+    type Ok = ();
+    type Error = ScaleError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), ScaleError> {
+        value.serialize(&mut **self)
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), ScaleError> {
+        Err(ScaleError(format!(
+            "field '{}' is skipped, which SCALE cannot represent",
+            key
+        )))
+    }
+
+    fn end(self) -> Result<(), ScaleError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut ScaleSerializer {
+    type Ok = ();
+    type Error = ScaleError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), ScaleError> {
+        value.serialize(&mut **self)
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), ScaleError> {
+        Err(ScaleError(format!(
+            "field '{}' is skipped, which SCALE cannot represent",
+            key
+        )))
+    }
+
+    fn end(self) -> Result<(), ScaleError> {
+        Ok(())
+    }
+}
+
+struct ScaleDeserializer<'de> {
+    input: &'de [u8],
+}
+
+impl<'de> ScaleDeserializer<'de> {
+    fn take(&mut self, len: usize) -> Result<&'de [u8], ScaleError> {
+        if self.input.len() < len {
+            return Err(ScaleError("unexpected end of input".to_string()));
+        }
+        let (head, tail) = self.input.split_at(len);
+        self.input = tail;
+        Ok(head)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], ScaleError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn read_compact(&mut self) -> Result<u64, ScaleError> {
+        let first = self.take_array::<1>()?[0];
+        match first & 0b11 {
+            0b00 => Ok(u64::from(first >> 2)),
+            0b01 => {
+                let second = self.take_array::<1>()?[0];
+                Ok(u64::from(u16::from_le_bytes([first, second]) >> 2))
+            }
+            0b10 => {
+                let rest = self.take_array::<3>()?;
+                Ok(u64::from(
+                    u32::from_le_bytes([first, rest[0], rest[1], rest[2]]) >> 2,
+                ))
+            }
+            _ => {
+                let len = usize::from(first >> 2) + 4;
+                if len > 8 {
+                    return Err(ScaleError(format!(
+                        "compact integer of {} bytes is too large",
+                        len
+                    )));
+                }
+                let mut bytes = [0u8; 8];
+                bytes[..len].copy_from_slice(self.take(len)?);
+                Ok(u64::from_le_bytes(bytes))
+            }
+        }
+    }
+
+    fn read_len(&mut self) -> Result<usize, ScaleError> {
+        let len = self.read_compact()?;
+        usize::try_from(len).map_err(|_| ScaleError(format!("length {} is too large", len)))
+    }
+
+    fn read_bytes(&mut self) -> Result<&'de [u8], ScaleError> {
+        let len = self.read_len()?;
+        self.take(len)
+    }
+}
+
+/// Sequence access for a known number of elements
+struct Counted<'a, 'de> {
+    de: &'a mut ScaleDeserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de> SeqAccess<'de> for Counted<'_, 'de> {
+    type Error = ScaleError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, ScaleError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Counted<'_, 'de> {
+    type Error = ScaleError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, ScaleError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, ScaleError> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+macro_rules! deserialize_le {
+    ($method:ident, $visit:ident, $ty:ty) => {
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+            visitor.$visit(<$ty>::from_le_bytes(self.take_array()?))
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &mut ScaleDeserializer<'de> {
+    type Error = ScaleError;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ScaleError> {
+        Err(ScaleError(
+            "SCALE is not self-describing; the type must not need deserialize_any".to_string(),
+        ))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        match self.take_array::<1>()?[0] {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            other => Err(ScaleError(format!("invalid bool byte {}", other))),
+        }
+    }
+
+    deserialize_le!(deserialize_i8, visit_i8, i8);
+    deserialize_le!(deserialize_i16, visit_i16, i16);
+    deserialize_le!(deserialize_i32, visit_i32, i32);
+    deserialize_le!(deserialize_i64, visit_i64, i64);
+    deserialize_le!(deserialize_i128, visit_i128, i128);
+    deserialize_le!(deserialize_u8, visit_u8, u8);
+    deserialize_le!(deserialize_u16, visit_u16, u16);
+    deserialize_le!(deserialize_u32, visit_u32, u32);
+    deserialize_le!(deserialize_u64, visit_u64, u64);
+    deserialize_le!(deserialize_u128, visit_u128, u128);
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        visitor.visit_f32(f32::from_bits(u32::from_le_bytes(self.take_array()?)))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        visitor.visit_f64(f64::from_bits(u64::from_le_bytes(self.take_array()?)))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        let code = u32::from_le_bytes(self.take_array()?);
+        let c = char::from_u32(code)
+            .ok_or_else(|| ScaleError(format!("invalid char code {:#x}", code)))?;
+        visitor.visit_char(c)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        let bytes = self.read_bytes()?;
+        let s = std::str::from_utf8(bytes).map_err(|e| ScaleError(e.to_string()))?;
+        visitor.visit_borrowed_str(s)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        visitor.visit_borrowed_bytes(self.read_bytes()?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        match self.take_array::<1>()?[0] {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            other => Err(ScaleError(format!("invalid option byte {}", other))),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ScaleError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ScaleError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        let remaining = self.read_len()?;
+        visitor.visit_seq(Counted {
+            de: self,
+            remaining,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ScaleError> {
+        visitor.visit_seq(Counted {
+            de: self,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ScaleError> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScaleError> {
+        let remaining = self.read_len()?;
+        visitor.visit_map(Counted {
+            de: self,
+            remaining,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ScaleError> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ScaleError> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ScaleError> {
+        Err(ScaleError(
+            "SCALE does not encode field or variant names".to_string(),
+        ))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ScaleError> {
+        Err(ScaleError(
+            "SCALE is not self-describing; unknown data cannot be skipped".to_string(),
+        ))
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut ScaleDeserializer<'de> {
+    type Error = ScaleError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), ScaleError> {
+        let index = u32::from(self.take_array::<1>()?[0]);
+        let variant = seed.deserialize(IntoDeserializer::<ScaleError>::into_deserializer(index))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut ScaleDeserializer<'de> {
+    type Error = ScaleError;
+
+    fn unit_variant(self) -> Result<(), ScaleError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, ScaleError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ScaleError> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ScaleError> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parity_scale_codec::{Compact, Encode};
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode)]
+    enum Kind {
+        Transfer,
+        Remark(Vec<u8>),
+        Batch { calls: u32, atomic: bool },
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode)]
+    struct Request {
+        nonce: u64,
+        amount: u128,
+        signer: String,
+        tip: Option<u32>,
+        kinds: Vec<Kind>,
+        pair: (u8, i16),
+    }
+
+    fn sample() -> Request {
+        Request {
+            nonce: 7,
+            amount: 10_000_000_000,
+            signer: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+            tip: Some(500),
+            kinds: vec![
+                Kind::Transfer,
+                Kind::Remark(vec![1, 2, 3]),
+                Kind::Batch {
+                    calls: 3,
+                    atomic: true,
+                },
+            ],
+            pair: (255, -2),
+        }
+    }
+
+    #[test]
+    fn test_codec_roundtrip() {
+        let request = sample();
+        for codec in Codec::ALL {
+            let bytes = codec.encode(&request).unwrap();
+            assert_eq!(
+                codec.decode::<Request>(&bytes).unwrap(),
+                request,
+                "{}",
+                codec
+            );
+        }
+
+        let mut map = BTreeMap::new();
+        map.insert("threshold".to_string(), 2.5f64);
+        let bytes = Codec::Scale.encode(&map).unwrap();
+        assert_eq!(
+            Codec::Scale
+                .decode::<BTreeMap<String, f64>>(&bytes)
+                .unwrap(),
+            map
+        );
+
+        assert_eq!("CBOR".parse::<Codec>().unwrap(), Codec::Cbor);
+        assert!("xml".parse::<Codec>().is_err());
+        assert_eq!(Codec::default(), Codec::Json);
+    }
+
+    #[test]
+    fn test_scale_matches_parity_codec() {
+        let request = sample();
+        assert_eq!(Codec::Scale.encode(&request).unwrap(), request.encode());
+
+        for len in [
+            0u64,
+            63,
+            64,
+            16_383,
+            16_384,
+            (1 << 30) - 1,
+            1 << 30,
+            u64::MAX,
+        ] {
+            let mut ours = Vec::new();
+            write_compact(&mut ours, len);
+            assert_eq!(ours, Compact(len).encode(), "compact {}", len);
+
+            let mut de = ScaleDeserializer { input: &ours };
+            assert_eq!(de.read_compact().unwrap(), len);
+        }
+    }
+
+    #[test]
+    fn test_scale_rejects_malformed_input() {
+        let bytes = Codec::Scale.encode(&sample()).unwrap();
+        assert!(Codec::Scale
+            .decode::<Request>(&bytes[..bytes.len() - 1])
+            .is_err());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(Codec::Scale.decode::<Request>(&trailing).is_err());
+
+        assert!(Codec::Scale.decode::<bool>(&[2]).is_err());
+        assert!(Codec::Scale.decode::<serde_json::Value>(&[0]).is_err());
+
+        #[derive(Serialize)]
+        struct Sparse {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            note: Option<String>,
+        }
+        assert!(Codec::Scale.encode(&Sparse { note: None }).is_err());
+    }
+}
@@ -0,0 +1,443 @@
+//! # Fault Injection
+//!
+//! Injectable RPC faults for failover drills and chaos testing.
+//!
+//! A [`FaultInjector`] decides, per RPC call, whether to delay it, fail it
+//! before it is sent, or drop its response after it was sent. Rates are set per
+//! endpoint in a [`FaultConfig`], which can be loaded from JSON or TOML, so
+//! resilience features (failover, retries, circuit breakers) can be exercised
+//! in staging without touching the nodes.
+//!
+//! Every injected fault is recorded as an `injected_faults_total` metric whose
+//! `category` label is `injected`, so dashboards can tell injected failures
+//! from real ones.
+//!
+//! This module is only available with the `fault-injection` feature and must
+//! not be enabled in production builds.
+//!
+//! ```rust
+//! use apex_sdk_core::fault::{FaultConfig, FaultInjector, FaultRule};
+//! use std::time::Duration;
+//!
+//! let config = FaultConfig::new()
+//!     .with_seed(42)
+//!     .with_endpoint(
+//!         "wss://rpc.example.com",
+//!         FaultRule::new()
+//!             .with_error_rate(0.1)
+//!             .with_delay(0.2, Duration::from_millis(500)),
+//!     );
+//!
+//! let injector = FaultInjector::new(config).unwrap();
+//! let fault = injector.decide("wss://rpc.example.com", "chain_getHeader");
+//! ```
+
+use crate::metrics::{Metric, MetricType, MetricsCollector};
+use crate::SdkError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Kind of injected fault
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FaultKind {
+    /// The call is delayed before it is sent
+    Delay,
+    /// The call fails without being sent
+    Error,
+    /// The call is sent but its response is discarded
+    Drop,
+}
+
+impl fmt::Display for FaultKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Delay => write!(f, "delay"),
+            Self::Error => write!(f, "error"),
+            Self::Drop => write!(f, "drop"),
+        }
+    }
+}
+
+/// Fault to apply to a single call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Wait before sending the call
+    Delay(Duration),
+    /// Fail without sending the call
+    Error,
+    /// Send the call, then discard the response and fail
+    Drop,
+}
+
+impl Fault {
+    /// Get the fault kind
+    pub fn kind(&self) -> FaultKind {
+        match self {
+            Self::Delay(_) => FaultKind::Delay,
+            Self::Error => FaultKind::Error,
+            Self::Drop => FaultKind::Drop,
+        }
+    }
+}
+
+/// Error returned for calls failed or dropped by the injector
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Injected {kind} fault on {method} at {endpoint}")]
+pub struct InjectedFault {
+    /// Endpoint the call was made to
+    pub endpoint: String,
+    /// RPC method
+    pub method: String,
+    /// Kind of fault
+    pub kind: FaultKind,
+}
+
+/// Fault rates for an endpoint
+///
+/// Rates are probabilities between 0 and 1; their sum must not exceed 1.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FaultRule {
+    /// Probability of delaying a call
+    #[serde(default)]
+    pub delay_rate: f64,
+    /// Delay applied to delayed calls, in milliseconds
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// Probability of failing a call
+    #[serde(default)]
+    pub error_rate: f64,
+    /// Probability of dropping a call's response
+    #[serde(default)]
+    pub drop_rate: f64,
+    /// Only inject faults into these methods; all methods when empty
+    #[serde(default)]
+    pub methods: Vec<String>,
+}
+
+impl FaultRule {
+    /// Create a rule that injects nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delay a fraction of calls
+    pub fn with_delay(mut self, rate: f64, delay: Duration) -> Self {
+        self.delay_rate = rate;
+        self.delay_ms = delay.as_millis() as u64;
+        self
+    }
+
+    /// Fail a fraction of calls
+    pub fn with_error_rate(mut self, rate: f64) -> Self {
+        self.error_rate = rate;
+        self
+    }
+
+    /// Drop the response of a fraction of calls
+    pub fn with_drop_rate(mut self, rate: f64) -> Self {
+        self.drop_rate = rate;
+        self
+    }
+
+    /// Restrict faults to the given methods
+    pub fn with_methods<I, S>(mut self, methods: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.methods = methods.into_iter().map(Into::into).collect();
+        self
+    }
+
+    fn applies_to(&self, method: &str) -> bool {
+        self.methods.is_empty() || self.methods.iter().any(|m| m == method)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let rates = [self.delay_rate, self.error_rate, self.drop_rate];
+        if rates.iter().any(|r| !(0.0..=1.0).contains(r)) {
+            return Err("Fault rates must be between 0 and 1".to_string());
+        }
+        if rates.iter().sum::<f64>() > 1.0 {
+            return Err("Fault rates must not sum to more than 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Fault injection configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FaultConfig {
+    /// Seed for reproducible drills; seeded from the clock when unset
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Rule for endpoints without a specific rule
+    #[serde(default)]
+    pub default_rule: Option<FaultRule>,
+    /// Rules by endpoint URL
+    #[serde(default)]
+    pub endpoints: HashMap<String, FaultRule>,
+}
+
+impl FaultConfig {
+    /// Create a configuration that injects nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a fixed random seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set the rule for endpoints without a specific rule
+    pub fn with_default_rule(mut self, rule: FaultRule) -> Self {
+        self.default_rule = Some(rule);
+        self
+    }
+
+    /// Set the rule for an endpoint
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>, rule: FaultRule) -> Self {
+        self.endpoints.insert(endpoint.into(), rule);
+        self
+    }
+
+    /// Get the rule applying to an endpoint
+    pub fn rule_for(&self, endpoint: &str) -> Option<&FaultRule> {
+        self.endpoints.get(endpoint).or(self.default_rule.as_ref())
+    }
+
+    /// Check that all rates are valid
+    pub fn validate(&self) -> Result<(), SdkError> {
+        self.default_rule
+            .iter()
+            .map(|rule| ("default", rule))
+            .chain(self.endpoints.iter().map(|(e, rule)| (e.as_str(), rule)))
+            .try_for_each(|(endpoint, rule)| {
+                rule.validate()
+                    .map_err(|e| SdkError::ConfigError(format!("{} ({})", e, endpoint)))
+            })
+    }
+}
+
+/// Decides which RPC calls to fault and records injected faults
+#[derive(Debug)]
+pub struct FaultInjector {
+    config: FaultConfig,
+    rng: Mutex<u64>,
+    counts: Mutex<HashMap<(String, FaultKind), u64>>,
+    metrics: Option<MetricsCollector>,
+}
+
+impl FaultInjector {
+    /// Create an injector, validating the configuration
+    pub fn new(config: FaultConfig) -> Result<Self, SdkError> {
+        config.validate()?;
+        let seed = config.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as u64
+        });
+
+        Ok(Self {
+            config,
+            rng: Mutex::new(seed),
+            counts: Mutex::new(HashMap::new()),
+            metrics: None,
+        })
+    }
+
+    /// Record injected faults into a metrics collector
+    pub fn with_metrics(mut self, metrics: MetricsCollector) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &FaultConfig {
+        &self.config
+    }
+
+    /// Decide the fault for a call, recording it if one is injected
+    pub fn decide(&self, endpoint: &str, method: &str) -> Option<Fault> {
+        let rule = self.config.rule_for(endpoint)?;
+        if !rule.applies_to(method) {
+            return None;
+        }
+
+        let roll = self.next_f64();
+        let fault = if roll < rule.error_rate {
+            Fault::Error
+        } else if roll < rule.error_rate + rule.drop_rate {
+            Fault::Drop
+        } else if roll < rule.error_rate + rule.drop_rate + rule.delay_rate {
+            Fault::Delay(Duration::from_millis(rule.delay_ms))
+        } else {
+            return None;
+        };
+
+        self.record(endpoint, method, fault.kind());
+        Some(fault)
+    }
+
+    /// Error for a failed or dropped call
+    pub fn error(&self, endpoint: &str, method: &str, kind: FaultKind) -> InjectedFault {
+        InjectedFault {
+            endpoint: endpoint.to_string(),
+            method: method.to_string(),
+            kind,
+        }
+    }
+
+    /// Number of faults of a kind injected for an endpoint
+    pub fn injected_count(&self, endpoint: &str, kind: FaultKind) -> u64 {
+        self.counts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&(endpoint.to_string(), kind))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn record(&self, endpoint: &str, method: &str, kind: FaultKind) {
+        tracing::debug!(
+            endpoint = %endpoint,
+            operation = %method,
+            fault = %kind,
+            "Injecting RPC fault"
+        );
+
+        *self
+            .counts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry((endpoint.to_string(), kind))
+            .or_insert(0) += 1;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record(
+                Metric::new(MetricType::ErrorRate, "injected_faults_total", 1.0)
+                    .with_label("error_type", format!("injected_{}", kind))
+                    .with_label("operation", method)
+                    .with_label("category", "injected")
+                    .with_label("severity", "none")
+                    .with_label("endpoint", endpoint)
+                    .with_help("Faults injected by the fault injection layer"),
+            );
+        }
+    }
+
+    /// SplitMix64 step mapped to `[0, 1)`
+    fn next_f64(&self) -> f64 {
+        let mut state = self
+            .rng
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENDPOINT: &str = "wss://rpc.example.com";
+
+    fn injector(rule: FaultRule) -> FaultInjector {
+        FaultInjector::new(
+            FaultConfig::new()
+                .with_seed(7)
+                .with_endpoint(ENDPOINT, rule),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rates_are_respected() {
+        let injector = injector(FaultRule::new().with_error_rate(0.25));
+        let errors = (0..10_000)
+            .filter(|_| injector.decide(ENDPOINT, "chain_getHeader") == Some(Fault::Error))
+            .count();
+
+        assert!((2_000..3_000).contains(&errors), "errors: {}", errors);
+        assert_eq!(
+            injector.injected_count(ENDPOINT, FaultKind::Error),
+            errors as u64
+        );
+    }
+
+    #[test]
+    fn test_other_endpoints_and_methods_are_untouched() {
+        let injector = injector(
+            FaultRule::new()
+                .with_drop_rate(1.0)
+                .with_methods(["author_submitExtrinsic"]),
+        );
+
+        assert_eq!(
+            injector.decide("wss://other.example.com", "author_submitExtrinsic"),
+            None
+        );
+        assert_eq!(injector.decide(ENDPOINT, "chain_getHeader"), None);
+        assert_eq!(
+            injector.decide(ENDPOINT, "author_submitExtrinsic"),
+            Some(Fault::Drop)
+        );
+    }
+
+    #[test]
+    fn test_seed_makes_drills_reproducible() {
+        let rule = FaultRule::new()
+            .with_error_rate(0.3)
+            .with_delay(0.3, Duration::from_millis(100));
+        let a = injector(rule.clone());
+        let b = injector(rule);
+        for _ in 0..50 {
+            assert_eq!(a.decide(ENDPOINT, "m"), b.decide(ENDPOINT, "m"));
+        }
+    }
+
+    #[test]
+    fn test_invalid_rates_are_rejected() {
+        let config = FaultConfig::new()
+            .with_default_rule(FaultRule::new().with_error_rate(0.6).with_drop_rate(0.6));
+        assert!(FaultInjector::new(config).is_err());
+    }
+
+    #[test]
+    fn test_injected_faults_are_labelled() {
+        let metrics = MetricsCollector::new();
+        let injector =
+            injector(FaultRule::new().with_error_rate(1.0)).with_metrics(metrics.clone());
+        injector.decide(ENDPOINT, "state_getStorage");
+
+        let recorded = metrics.get_metrics();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].name, "injected_faults_total");
+        assert_eq!(recorded[0].labels["category"], "injected");
+        assert_eq!(recorded[0].labels["error_type"], "injected_error");
+    }
+
+    #[test]
+    fn test_config_from_json() {
+        let config: FaultConfig = serde_json::from_str(
+            r#"{"seed": 1, "endpoints": {"wss://a": {"drop_rate": 0.1, "delay_rate": 0.2, "delay_ms": 250}}}"#,
+        )
+        .unwrap();
+        let rule = config.rule_for("wss://a").unwrap();
+        assert_eq!(rule.delay_ms, 250);
+        assert!(config.rule_for("wss://b").is_none());
+    }
+}
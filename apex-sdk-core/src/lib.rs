@@ -20,17 +20,17 @@
 //!
 //! ```rust,no_run
 //! use apex_sdk_core::ChainAdapter;
-//! use apex_sdk_types::{Address, TransactionStatus};
+//! use apex_sdk_types::{Address, TransactionStatus, TxHash};
 //!
 //! async fn check_transaction<T: ChainAdapter>(
 //!     adapter: &T,
-//!     tx_hash: &str
+//!     tx_hash: &TxHash
 //! ) -> Result<TransactionStatus, String> {
 //!     adapter.get_transaction_status(tx_hash).await
 //! }
 //! ```
 
-use apex_sdk_types::{Address, TransactionStatus};
+use apex_sdk_types::{Address, TransactionStatus, TxHash};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -45,14 +45,46 @@ pub mod pipeline;
 /// Metrics collection and monitoring
 pub mod metrics;
 
+/// Bounded pool for blocking work
+pub mod blocking;
+
+/// Readiness conditions for warm-up gating
+pub mod readiness;
+
+/// Retention policies and GC for persistent stores
+pub mod retention;
+
+/// Allow-list and metadata checks for token transfers
+pub mod asset_policy;
+
+/// Standard structured logging field names
+pub mod log_fields;
+
+/// Redaction of addresses and amounts in logs and metrics
+pub mod privacy;
+
 /// Golden vectors for encoding verification
 pub mod golden_vectors;
 
+/// SCALE, JSON and CBOR codecs for interchange data
+pub mod codec;
+
+/// Backoff hints parsed from provider throttle responses
+pub mod throttle;
+
+/// RPC fault injection for resilience testing
+#[cfg(feature = "fault-injection")]
+pub mod fault;
+
+pub use apex_sdk_types::{ErrorCode, HasErrorCode};
+pub use asset_policy::{AssetAllowList, AssetPolicyError};
+pub use codec::{Codec, CodecError};
 pub use golden_vectors::{
     load_default_golden_vectors, verify_golden_vector, ChainType, GoldenVector, GoldenVectorSet,
 };
 pub use metrics::{MetricType, MetricsCollector};
 pub use pipeline::{TransactionPipeline, TransactionResult};
+pub use throttle::ThrottleHint;
 
 /// Unified error taxonomy for the SDK
 #[derive(Error, Debug)]
@@ -71,11 +103,24 @@ pub enum SdkError {
     NotImplemented(String),
 }
 
+impl HasErrorCode for SdkError {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::new(match self {
+            SdkError::ProviderError(_) => 1100,
+            SdkError::SignerError(_) => 1101,
+            SdkError::TransactionError(_) => 1102,
+            SdkError::NetworkError(_) => 1103,
+            SdkError::ConfigError(_) => 1104,
+            SdkError::NotImplemented(_) => 1105,
+        })
+    }
+}
+
 /// Trait for blockchain adapters
 #[async_trait]
 pub trait ChainAdapter: Send + Sync {
     /// Get the transaction status
-    async fn get_transaction_status(&self, tx_hash: &str) -> Result<TransactionStatus, String>;
+    async fn get_transaction_status(&self, tx_hash: &TxHash) -> Result<TransactionStatus, String>;
 
     /// Validate an address for this chain
     fn validate_address(&self, address: &Address) -> bool;
@@ -142,6 +187,34 @@ pub struct BlockInfo {
     pub event_count: Option<u32>,
     #[serde(default)]
     pub is_finalized: bool,
+    /// Summary of each extrinsic, in block order
+    #[serde(default)]
+    pub extrinsics: Vec<BlockExtrinsic>,
+    /// Account that authored the block, when the chain's consensus digest
+    /// identifies one
+    #[serde(default)]
+    pub author: Option<String>,
+}
+
+/// Summary of an extrinsic in a [`BlockInfo`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockExtrinsic {
+    /// Index of the extrinsic in the block
+    pub index: u32,
+    /// Hash of the extrinsic
+    pub hash: String,
+    /// Pallet name
+    pub pallet: String,
+    /// Call name
+    pub call: String,
+    /// Whether the extrinsic is signed
+    pub signed: bool,
+    /// Whether the extrinsic succeeded, if the block's events were read
+    #[serde(default)]
+    pub success: Option<bool>,
+    /// Number of events the extrinsic emitted, if the block's events were read
+    #[serde(default)]
+    pub event_count: Option<u32>,
 }
 
 /// Detailed block information with extrinsics and events
@@ -187,6 +260,150 @@ pub struct BlockEvent {
     pub event: String,
 }
 
+/// Default number of blocks searched after [`TxSearch::since`]'s block: the
+/// default mortality period of a Substrate transaction
+pub const DEFAULT_TX_SEARCH_WINDOW: u32 = 64;
+
+/// Blocks to search when looking up a transaction by hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxSearch {
+    /// The `n` most recent blocks, counting back from the best block
+    Depth(u32),
+    /// Block `block` and the `window - 1` blocks after it
+    ///
+    /// Use the best block number when the transaction was submitted as
+    /// `block` and its mortality period as `window`: a transaction not
+    /// included by then can no longer be.
+    Since {
+        /// First block to search
+        block: u64,
+        /// Number of blocks to search
+        window: u32,
+    },
+}
+
+impl TxSearch {
+    /// Search `block` and the [`DEFAULT_TX_SEARCH_WINDOW`] blocks from it
+    pub fn since(block: u64) -> Self {
+        Self::Since {
+            block,
+            window: DEFAULT_TX_SEARCH_WINDOW,
+        }
+    }
+
+    /// First and last block to search, given the best block number
+    ///
+    /// `None` if the search starts after the best block.
+    pub fn range(&self, best: u64) -> Option<(u64, u64)> {
+        match *self {
+            Self::Depth(0) | Self::Since { window: 0, .. } => None,
+            Self::Depth(depth) => Some((best.saturating_sub(depth as u64 - 1), best)),
+            Self::Since { block, .. } if block > best => None,
+            Self::Since { block, window } => {
+                Some((block, best.min(block.saturating_add(window as u64 - 1))))
+            }
+        }
+    }
+
+    /// Whether every block this search covers is at or below `best`, so a
+    /// transaction not found in them was dropped rather than still pending
+    pub fn is_complete(&self, best: u64) -> bool {
+        match *self {
+            Self::Depth(_) => true,
+            Self::Since { block, window } => {
+                block.saturating_add((window as u64).saturating_sub(1)) <= best
+            }
+        }
+    }
+}
+
+/// Block and extrinsic that included a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxInclusion {
+    /// Number of the including block
+    pub block_number: u64,
+    /// Hash of the including block
+    pub block_hash: String,
+    /// Index of the extrinsic in the block
+    pub extrinsic_index: u32,
+    /// Blocks on top of the including block, up to the best block
+    pub confirmations: u32,
+    /// Whether the extrinsic succeeded, or `None` if its events could not
+    /// be read, as on a node that pruned the block's state
+    pub success: Option<bool>,
+    /// Dispatch error of a failed extrinsic
+    pub error: Option<String>,
+}
+
+/// Outcome of looking up a transaction by hash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxLookup {
+    /// Included in a finalized block
+    Finalized(TxInclusion),
+    /// Included in a block that is not finalized yet
+    InBlock(TxInclusion),
+    /// Not in any searched block, but the search reaches past the best
+    /// block, so it may still be included
+    Pending,
+    /// Not in any of blocks `from..=to`, which cover the whole search
+    Dropped {
+        /// First block searched
+        from: u64,
+        /// Last block searched
+        to: u64,
+    },
+}
+
+impl TxLookup {
+    /// Block and extrinsic that included the transaction, if found
+    pub fn inclusion(&self) -> Option<&TxInclusion> {
+        match self {
+            Self::Finalized(inclusion) | Self::InBlock(inclusion) => Some(inclusion),
+            Self::Pending | Self::Dropped { .. } => None,
+        }
+    }
+
+    /// Status of transaction `hash` with this outcome
+    ///
+    /// Failed extrinsics are [`TxStatus::Failed`](apex_sdk_types::TxStatus)
+    /// and dropped transactions [`Unknown`](apex_sdk_types::TxStatus), as
+    /// they may be in a block outside the search.
+    pub fn to_status(&self, hash: String) -> TransactionStatus {
+        let inclusion = match self {
+            Self::Pending => return TransactionStatus::pending(hash),
+            Self::Dropped { .. } => return TransactionStatus::unknown(hash),
+            Self::Finalized(inclusion) | Self::InBlock(inclusion) => inclusion,
+        };
+
+        if inclusion.success == Some(false) {
+            let error = inclusion.error.clone().unwrap_or_else(|| {
+                format!(
+                    "Extrinsic {}-{} failed",
+                    inclusion.block_number, inclusion.extrinsic_index
+                )
+            });
+            return TransactionStatus {
+                block_number: Some(inclusion.block_number),
+                block_hash: Some(inclusion.block_hash.clone()),
+                ..TransactionStatus::failed(hash, error)
+            };
+        }
+
+        let status = match self {
+            Self::Finalized(_) => TransactionStatus::finalized,
+            _ => TransactionStatus::confirmed,
+        };
+        status(
+            hash,
+            inclusion.block_number,
+            inclusion.block_hash.clone(),
+            None,
+            None,
+            Some(inclusion.confirmations),
+        )
+    }
+}
+
 /// Signer trait for signing transactions
 #[async_trait]
 pub trait Signer: Send + Sync {
@@ -213,26 +430,33 @@ pub trait NonceManager: Send + Sync {
 #[async_trait]
 pub trait Broadcaster: Send + Sync {
     /// Broadcast a signed transaction
-    async fn broadcast(&self, signed_tx: &[u8]) -> Result<String, SdkError>;
+    async fn broadcast(&self, signed_tx: &[u8]) -> Result<TxHash, SdkError>;
 }
 
+/// Called with the `0x`-prefixed hash of a signed transaction just before it
+/// is broadcast
+///
+/// Lets callers persist the hash first, e.g. in a write-ahead journal. An
+/// error aborts the broadcast.
+pub type BroadcastHook = std::sync::Arc<dyn Fn(&str) -> Result<(), SdkError> + Send + Sync>;
+
 /// Receipt watcher trait for tracking transaction status
 #[async_trait]
 pub trait ReceiptWatcher: Send + Sync {
     /// Wait for a transaction receipt with default confirmation strategy
-    async fn wait_for_receipt(&self, tx_hash: &str) -> Result<TransactionStatus, SdkError>;
+    async fn wait_for_receipt(&self, tx_hash: &TxHash) -> Result<TransactionStatus, SdkError>;
 
     /// Wait for a transaction receipt with custom confirmation strategy
     async fn wait_for_receipt_with_strategy(
         &self,
-        tx_hash: &str,
+        tx_hash: &TxHash,
         strategy: &ConfirmationStrategy,
     ) -> Result<TransactionStatus, SdkError>;
 
     /// Get current transaction status without waiting
     async fn get_receipt_status(
         &self,
-        tx_hash: &str,
+        tx_hash: &TxHash,
     ) -> Result<Option<TransactionStatus>, SdkError>;
 }
 
@@ -255,6 +479,20 @@ pub enum ConfirmationStrategy {
     Immediate,
 }
 
+impl ConfirmationStrategy {
+    /// Maximum time to wait, or `None` when the strategy sets no limit
+    /// (`Immediate`, or a `timeout_secs` of 0)
+    pub fn timeout(&self) -> Option<std::time::Duration> {
+        let secs = match self {
+            Self::BlockConfirmations { timeout_secs, .. } | Self::Finalized { timeout_secs } => {
+                *timeout_secs
+            }
+            Self::Immediate => 0,
+        };
+        (secs > 0).then(|| std::time::Duration::from_secs(secs))
+    }
+}
+
 impl Default for ConfirmationStrategy {
     fn default() -> Self {
         Self::BlockConfirmations {
@@ -0,0 +1,60 @@
+//! # Standard Log Fields
+//!
+//! Field names shared by every SDK crate when emitting `tracing` events.
+//!
+//! Events carry context as structured fields rather than interpolated text, so
+//! log pipelines (Loki, ELK) can filter on them directly:
+//!
+//! ```rust
+//! use tracing::info;
+//!
+//! let tx_hash = "0xabc";
+//! info!(
+//!     chain = "polkadot",
+//!     operation = "transfer",
+//!     tx_hash = %tx_hash,
+//!     "Transaction finalized"
+//! );
+//! ```
+//!
+//! `tracing` macros take field names as identifiers, so the constants below
+//! document the vocabulary and are used by consumers (formatters, filters)
+//! rather than at the call sites.
+
+/// Chain or network name (e.g. `polkadot`, `westend`)
+pub const CHAIN: &str = "chain";
+
+/// RPC endpoint URL
+pub const ENDPOINT: &str = "endpoint";
+
+/// Transaction or extrinsic hash
+pub const TX_HASH: &str = "tx_hash";
+
+/// Address of the signing wallet
+pub const WALLET: &str = "wallet";
+
+/// SDK operation name (e.g. `transfer`, `batch`, `contract_call`)
+pub const OPERATION: &str = "operation";
+
+/// Identifier of the enclosing span
+pub const SPAN_ID: &str = "span_id";
+
+/// All standard fields, in output order
+pub const ALL: [&str; 6] = [CHAIN, ENDPOINT, TX_HASH, WALLET, OPERATION, SPAN_ID];
+
+/// Check whether a field name is one of the standard fields
+pub fn is_standard(name: &str) -> bool {
+    ALL.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_standard() {
+        assert!(is_standard("tx_hash"));
+        assert!(is_standard(OPERATION));
+        assert!(!is_standard("message"));
+    }
+}
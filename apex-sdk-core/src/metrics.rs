@@ -27,6 +27,10 @@ pub enum MetricType {
     NonceMetrics,
     /// Fee estimation accuracy
     FeeEstimationAccuracy,
+    /// Work queue depth
+    QueueDepth,
+    /// Records purged and sizes of retained stores
+    Retention,
 }
 
 /// A single metric data point
@@ -63,8 +67,16 @@ impl Metric {
     }
 
     /// Add a label to the metric
+    ///
+    /// Address and amount labels are redacted when privacy mode is enabled
+    /// (see [`crate::privacy`]).
     pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.labels.insert(key.into(), value.into());
+        let key = key.into();
+        let value = match crate::privacy::redactor() {
+            Some(redactor) => redactor.redact(&key, &value.into()).into_owned(),
+            None => value.into(),
+        };
+        self.labels.insert(key, value);
         self
     }
 
@@ -4,7 +4,7 @@ use crate::{
     BlockInfo, Broadcaster, ChainAdapter, ConfirmationStrategy, FeeEstimator, NonceManager,
     Provider as CoreProvider, ReceiptWatcher, SdkError, Signer,
 };
-use apex_sdk_types::{Address, TransactionStatus};
+use apex_sdk_types::{Address, TransactionStatus, TxHash};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -16,7 +16,7 @@ pub struct MockChainAdapter {
     /// Chain name
     pub name: String,
     /// Transaction statuses
-    pub tx_statuses: Arc<Mutex<HashMap<String, TransactionStatus>>>,
+    pub tx_statuses: Arc<Mutex<HashMap<TxHash, TransactionStatus>>>,
 }
 
 impl MockChainAdapter {
@@ -29,15 +29,15 @@ impl MockChainAdapter {
     }
 
     /// Set the status for a transaction hash
-    pub fn set_transaction_status(&self, tx_hash: &str, status: TransactionStatus) {
+    pub fn set_transaction_status(&self, tx_hash: TxHash, status: TransactionStatus) {
         let mut statuses = self.tx_statuses.lock().unwrap();
-        statuses.insert(tx_hash.to_string(), status);
+        statuses.insert(tx_hash, status);
     }
 }
 
 #[async_trait]
 impl ChainAdapter for MockChainAdapter {
-    async fn get_transaction_status(&self, tx_hash: &str) -> Result<TransactionStatus, String> {
+    async fn get_transaction_status(&self, tx_hash: &TxHash) -> Result<TransactionStatus, String> {
         let statuses = self.tx_statuses.lock().unwrap();
         Ok(statuses
             .get(tx_hash)
@@ -122,6 +122,8 @@ impl CoreProvider for MockProvider {
             extrinsic_count: 1,
             event_count: Some(3),
             is_finalized: block_number > 100, // Mock: blocks > 100 are finalized
+            extrinsics: Vec::new(),
+            author: None,
         })
     }
 
@@ -244,10 +246,11 @@ impl Default for MockBroadcaster {
 
 #[async_trait]
 impl Broadcaster for MockBroadcaster {
-    async fn broadcast(&self, signed_tx: &[u8]) -> Result<String, SdkError> {
+    async fn broadcast(&self, signed_tx: &[u8]) -> Result<TxHash, SdkError> {
         // Generate a mock transaction hash based on the signed transaction
-        let hash = format!("0x{:064x}", signed_tx.len());
-        Ok(hash)
+        let mut hash = [0u8; 32];
+        hash[24..].copy_from_slice(&(signed_tx.len() as u64).to_be_bytes());
+        Ok(TxHash::new(hash))
     }
 }
 
@@ -277,14 +280,14 @@ impl Default for MockReceiptWatcher {
 
 #[async_trait]
 impl ReceiptWatcher for MockReceiptWatcher {
-    async fn wait_for_receipt(&self, tx_hash: &str) -> Result<TransactionStatus, SdkError> {
+    async fn wait_for_receipt(&self, tx_hash: &TxHash) -> Result<TransactionStatus, SdkError> {
         self.wait_for_receipt_with_strategy(tx_hash, &ConfirmationStrategy::default())
             .await
     }
 
     async fn wait_for_receipt_with_strategy(
         &self,
-        tx_hash: &str,
+        tx_hash: &TxHash,
         _strategy: &ConfirmationStrategy,
     ) -> Result<TransactionStatus, SdkError> {
         // Simulate some processing time
@@ -308,7 +311,7 @@ impl ReceiptWatcher for MockReceiptWatcher {
 
     async fn get_receipt_status(
         &self,
-        tx_hash: &str,
+        tx_hash: &TxHash,
     ) -> Result<Option<TransactionStatus>, SdkError> {
         if self.should_succeed {
             Ok(Some(TransactionStatus {
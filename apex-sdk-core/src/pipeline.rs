@@ -5,9 +5,9 @@
 
 use crate::{
     Broadcaster, ConfirmationStrategy, FeeEstimator, NonceManager, Provider, ReceiptWatcher,
-    RetryConfig, SdkError, SdkLog, Signer, TimeoutConfig,
+    RetryConfig, SdkError, SdkLog, Signer, ThrottleHint, TimeoutConfig,
 };
-use apex_sdk_types::{Address, ChainType, TransactionStatus};
+use apex_sdk_types::{Address, ChainType, TransactionStatus, TxHash};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tokio::time::timeout;
@@ -201,9 +201,9 @@ where
     }
 
     /// Broadcast transaction with retry logic
-    async fn broadcast_with_retry(&self, signed_tx: &[u8]) -> Result<String, SdkError> {
+    async fn broadcast_with_retry(&self, signed_tx: &[u8]) -> Result<TxHash, SdkError> {
         self.with_retry("broadcast", || async {
-            let result: Result<String, SdkError> = timeout(
+            let result: Result<TxHash, SdkError> = timeout(
                 Duration::from_secs(self.timeout_config.rpc_timeout_secs),
                 self.broadcaster.broadcast(signed_tx),
             )
@@ -217,7 +217,7 @@ where
     /// Wait for confirmation with retry logic
     async fn wait_for_confirmation_with_retry(
         &self,
-        tx_hash: &str,
+        tx_hash: &TxHash,
     ) -> Result<TransactionStatus, SdkError> {
         let result: Result<TransactionStatus, SdkError> = timeout(
             Duration::from_secs(self.timeout_config.operation_timeout_secs),
@@ -253,6 +253,19 @@ where
                         return Err(err);
                     }
 
+                    // Wait as long as a throttling provider asked, up to the
+                    // maximum delay
+                    let max_delay = Duration::from_millis(self.retry_config.max_delay_ms);
+                    if let Some(wait) = ThrottleHint::parse(&err.to_string())
+                        .and_then(|hint| hint.retry_after_capped(max_delay))
+                    {
+                        let wait_ms = wait.as_millis() as u64;
+                        self.log_retry_attempt(operation, attempt, &err, wait_ms)
+                            .await;
+                        tokio::time::sleep(wait).await;
+                        continue;
+                    }
+
                     self.log_retry_attempt(operation, attempt, &err, delay)
                         .await;
                     tokio::time::sleep(Duration::from_millis(delay)).await;
@@ -578,7 +591,7 @@ where
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionResult {
     /// Transaction hash
-    pub hash: String,
+    pub hash: TxHash,
     /// Nonce used
     pub nonce: u64,
     /// Estimated fee
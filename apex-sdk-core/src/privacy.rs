@@ -0,0 +1,288 @@
+//! # Privacy Mode
+//!
+//! Redaction of addresses and amounts in SDK-emitted logs and metric labels.
+//!
+//! Privacy mode is process-wide: the telemetry initializer (or the application)
+//! installs a [`Redactor`] with [`set_redactor`], and formatters and
+//! [`Metric::with_label`](crate::metrics::Metric::with_label) consult it when
+//! writing values. Only structured fields are redacted; interpolated message
+//! text is written as-is, which is why SDK crates log addresses and amounts as
+//! fields (see [`log_fields`](crate::log_fields)).
+//!
+//! | Mode       | Addresses              | Amounts            |
+//! |------------|------------------------|--------------------|
+//! | `off`      | unchanged              | unchanged          |
+//! | `truncate` | `5Grwva…utQY`          | `~10^12`           |
+//! | `hash`     | `h:3f9a0c4d1e2b7a65`   | `~10^12`           |
+//!
+//! Amounts are bucketed by order of magnitude in both modes: they come from a
+//! small domain, so a hash would be trivially reversible.
+//!
+//! ```rust
+//! use apex_sdk_core::privacy::{PrivacyMode, Redactor};
+//!
+//! let redactor = Redactor::new(PrivacyMode::Truncate);
+//! assert_eq!(
+//!     redactor.redact("wallet", "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+//!     "5Grwva…utQY"
+//! );
+//! assert_eq!(redactor.redact("amount", "1500000000000"), "~10^12");
+//! assert_eq!(redactor.redact("chain", "polkadot"), "polkadot");
+//! ```
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+/// Field and label names treated as addresses
+pub const ADDRESS_FIELDS: &[&str] = &[
+    "wallet",
+    "address",
+    "account",
+    "from",
+    "to",
+    "sender",
+    "recipient",
+    "beneficiary",
+    "dest",
+    "signer",
+    "contract",
+    "owner",
+    "admin",
+];
+
+/// Field and label names treated as amounts
+pub const AMOUNT_FIELDS: &[&str] = &["amount", "value", "balance"];
+
+/// Characters kept at the start of a truncated address
+const PREFIX_LEN: usize = 6;
+
+/// Characters kept at the end of a truncated address
+const SUFFIX_LEN: usize = 4;
+
+/// Hex characters kept from the hash digest
+const HASH_LEN: usize = 16;
+
+/// How sensitive values are written to logs and metric labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivacyMode {
+    /// Values are written unchanged
+    #[default]
+    Off,
+    /// Addresses keep their first and last characters
+    Truncate,
+    /// Addresses are replaced by a salted hash, stable for a given salt
+    Hash,
+}
+
+impl PrivacyMode {
+    /// Check whether any redaction is applied
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, Self::Off)
+    }
+}
+
+impl fmt::Display for PrivacyMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Truncate => write!(f, "truncate"),
+            Self::Hash => write!(f, "hash"),
+        }
+    }
+}
+
+impl FromStr for PrivacyMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" | "none" | "false" | "0" => Ok(Self::Off),
+            "truncate" => Ok(Self::Truncate),
+            "hash" => Ok(Self::Hash),
+            other => Err(format!("Unknown privacy mode: {}", other)),
+        }
+    }
+}
+
+/// Redacts address and amount values according to a [`PrivacyMode`]
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    mode: PrivacyMode,
+    salt: String,
+}
+
+impl Redactor {
+    /// Create a redactor for the given mode
+    pub fn new(mode: PrivacyMode) -> Self {
+        Self {
+            mode,
+            salt: String::new(),
+        }
+    }
+
+    /// Set the salt mixed into address hashes
+    ///
+    /// Without a salt, hashed addresses can be recovered by hashing candidate
+    /// addresses; use a per-deployment secret in production.
+    pub fn with_salt(mut self, salt: impl Into<String>) -> Self {
+        self.salt = salt.into();
+        self
+    }
+
+    /// Get the privacy mode
+    pub fn mode(&self) -> PrivacyMode {
+        self.mode
+    }
+
+    /// Check whether a field name holds an address
+    pub fn is_address_field(name: &str) -> bool {
+        ADDRESS_FIELDS.contains(&name)
+    }
+
+    /// Check whether a field name holds an amount
+    pub fn is_amount_field(name: &str) -> bool {
+        AMOUNT_FIELDS.contains(&name)
+    }
+
+    /// Check whether a field name is redacted by this redactor
+    pub fn is_sensitive(&self, name: &str) -> bool {
+        self.mode.is_enabled() && (Self::is_address_field(name) || Self::is_amount_field(name))
+    }
+
+    /// Redact a field value based on its name
+    ///
+    /// Fields that are neither addresses nor amounts are returned unchanged.
+    pub fn redact<'a>(&self, name: &str, value: &'a str) -> Cow<'a, str> {
+        if !self.mode.is_enabled() {
+            Cow::Borrowed(value)
+        } else if Self::is_address_field(name) {
+            Cow::Owned(self.redact_address(value))
+        } else if Self::is_amount_field(name) {
+            Cow::Owned(self.redact_amount(value))
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+
+    /// Redact an address
+    pub fn redact_address(&self, address: &str) -> String {
+        match self.mode {
+            PrivacyMode::Off => address.to_string(),
+            PrivacyMode::Truncate => {
+                let chars: Vec<char> = address.chars().collect();
+                if chars.len() <= PREFIX_LEN + SUFFIX_LEN + 2 {
+                    return address.to_string();
+                }
+                let prefix: String = chars[..PREFIX_LEN].iter().collect();
+                let suffix: String = chars[chars.len() - SUFFIX_LEN..].iter().collect();
+                format!("{}…{}", prefix, suffix)
+            }
+            PrivacyMode::Hash => {
+                let mut hasher = Sha256::new();
+                hasher.update(self.salt.as_bytes());
+                hasher.update(address.as_bytes());
+                let digest = hex::encode(hasher.finalize());
+                format!("h:{}", &digest[..HASH_LEN])
+            }
+        }
+    }
+
+    /// Redact an amount to its order of magnitude
+    ///
+    /// Values that are not plain integers are replaced entirely.
+    pub fn redact_amount(&self, amount: &str) -> String {
+        if !self.mode.is_enabled() {
+            return amount.to_string();
+        }
+
+        let digits = amount.trim_start_matches('0');
+        if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+            "[redacted]".to_string()
+        } else if digits.is_empty() {
+            "0".to_string()
+        } else {
+            format!("~10^{}", digits.len() - 1)
+        }
+    }
+}
+
+static REDACTOR: RwLock<Option<Arc<Redactor>>> = RwLock::new(None);
+
+/// Install the process-wide redactor
+///
+/// Replaces any previously installed redactor. Installing a redactor with
+/// [`PrivacyMode::Off`] disables redaction.
+pub fn set_redactor(redactor: Redactor) {
+    let redactor = redactor.mode.is_enabled().then(|| Arc::new(redactor));
+    if let Ok(mut current) = REDACTOR.write() {
+        *current = redactor;
+    }
+}
+
+/// Get the process-wide redactor, if privacy mode is enabled
+pub fn redactor() -> Option<Arc<Redactor>> {
+    REDACTOR.read().ok().and_then(|current| current.clone())
+}
+
+/// Redact a value with the process-wide redactor
+pub fn redact<'a>(name: &str, value: &'a str) -> Cow<'a, str> {
+    match redactor() {
+        Some(redactor) => Cow::Owned(redactor.redact(name, value).into_owned()),
+        None => Cow::Borrowed(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    #[test]
+    fn test_off_leaves_values_unchanged() {
+        let redactor = Redactor::new(PrivacyMode::Off);
+        assert_eq!(redactor.redact("wallet", ALICE), ALICE);
+        assert_eq!(redactor.redact("amount", "1000"), "1000");
+    }
+
+    #[test]
+    fn test_truncate() {
+        let redactor = Redactor::new(PrivacyMode::Truncate);
+        assert_eq!(redactor.redact("to", ALICE), "5Grwva…utQY");
+        assert_eq!(redactor.redact("wallet", "0xabc"), "0xabc");
+        assert_eq!(redactor.redact("tx_hash", "0xdeadbeef"), "0xdeadbeef");
+    }
+
+    #[test]
+    fn test_hash_is_salted_and_stable() {
+        let redactor = Redactor::new(PrivacyMode::Hash).with_salt("deployment-a");
+        let first = redactor.redact_address(ALICE);
+        assert_eq!(first, redactor.redact_address(ALICE));
+        assert!(first.starts_with("h:"));
+        assert_eq!(first.len(), 2 + HASH_LEN);
+
+        let other = Redactor::new(PrivacyMode::Hash).with_salt("deployment-b");
+        assert_ne!(first, other.redact_address(ALICE));
+    }
+
+    #[test]
+    fn test_amount_buckets() {
+        let redactor = Redactor::new(PrivacyMode::Hash);
+        assert_eq!(redactor.redact_amount("1500000000000"), "~10^12");
+        assert_eq!(redactor.redact_amount("7"), "~10^0");
+        assert_eq!(redactor.redact_amount("0"), "0");
+        assert_eq!(redactor.redact_amount("1.5 DOT"), "[redacted]");
+    }
+
+    #[test]
+    fn test_mode_from_str() {
+        assert_eq!("Hash".parse::<PrivacyMode>().unwrap(), PrivacyMode::Hash);
+        assert_eq!("off".parse::<PrivacyMode>().unwrap(), PrivacyMode::Off);
+        assert!("scramble".parse::<PrivacyMode>().is_err());
+    }
+}
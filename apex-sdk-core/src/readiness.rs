@@ -0,0 +1,201 @@
+//! Readiness conditions
+//!
+//! A process is ready to serve once the conditions it depends on hold, e.g. a
+//! chain adapter is connected and its runtime metadata is loaded. The SDK marks
+//! the standard conditions below as it warms up; applications register which
+//! of them (and any of their own) are required, and readiness probes such as
+//! the metrics server's `/ready` endpoint report whether all required
+//! conditions are met.
+//!
+//! ```rust
+//! use apex_sdk_core::readiness::{Readiness, ADAPTER_CONNECTED, METADATA_LOADED};
+//!
+//! let readiness = Readiness::new()
+//!     .with_requirement(ADAPTER_CONNECTED)
+//!     .with_requirement(METADATA_LOADED);
+//! assert!(!readiness.is_ready());
+//!
+//! readiness.mark_ready(ADAPTER_CONNECTED);
+//! readiness.mark_ready(METADATA_LOADED);
+//! assert!(readiness.is_ready());
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// At least one chain adapter is connected
+pub const ADAPTER_CONNECTED: &str = "adapter_connected";
+
+/// Runtime metadata of the Substrate chain is loaded
+pub const METADATA_LOADED: &str = "metadata_loaded";
+
+/// Signing keys are unlocked and available
+pub const KEYSTORE_UNLOCKED: &str = "keystore_unlocked";
+
+/// State of one readiness condition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadinessCondition {
+    /// Condition name
+    pub name: String,
+    /// Whether readiness depends on this condition
+    pub required: bool,
+    /// Whether the condition currently holds
+    pub ready: bool,
+    /// Why the condition does not hold, or other detail
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Snapshot of all readiness conditions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadinessReport {
+    /// Whether every required condition holds
+    pub ready: bool,
+    /// Conditions ordered by name
+    pub conditions: Vec<ReadinessCondition>,
+}
+
+impl ReadinessReport {
+    /// Required conditions that do not hold
+    pub fn pending(&self) -> impl Iterator<Item = &ReadinessCondition> {
+        self.conditions.iter().filter(|c| c.required && !c.ready)
+    }
+}
+
+/// Shared set of readiness conditions
+///
+/// Clones share the same conditions, so the SDK and the readiness probe can
+/// each hold one.
+#[derive(Debug, Clone, Default)]
+pub struct Readiness {
+    conditions: Arc<Mutex<BTreeMap<String, ReadinessCondition>>>,
+}
+
+impl Readiness {
+    /// Create a readiness set without requirements, which is always ready
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require a condition; it is not ready until marked so
+    pub fn with_requirement(self, name: impl Into<String>) -> Self {
+        self.require(name);
+        self
+    }
+
+    /// Require a condition; it is not ready until marked so
+    ///
+    /// Keeps the current state if the condition was already marked.
+    pub fn require(&self, name: impl Into<String>) {
+        let name = name.into();
+        self.lock()
+            .entry(name.clone())
+            .or_insert_with(|| ReadinessCondition {
+                name,
+                required: true,
+                ready: false,
+                message: None,
+            })
+            .required = true;
+    }
+
+    /// Mark a condition as holding
+    pub fn mark_ready(&self, name: impl Into<String>) {
+        self.set(name.into(), true, None);
+    }
+
+    /// Mark a condition as not holding, with the reason
+    pub fn mark_not_ready(&self, name: impl Into<String>, reason: impl Into<String>) {
+        self.set(name.into(), false, Some(reason.into()));
+    }
+
+    /// Whether every required condition holds
+    pub fn is_ready(&self) -> bool {
+        self.lock().values().all(|c| c.ready || !c.required)
+    }
+
+    /// Current state of all conditions
+    pub fn report(&self) -> ReadinessReport {
+        let conditions: Vec<ReadinessCondition> = self.lock().values().cloned().collect();
+        ReadinessReport {
+            ready: conditions.iter().all(|c| c.ready || !c.required),
+            conditions,
+        }
+    }
+
+    fn set(&self, name: String, ready: bool, message: Option<String>) {
+        let mut conditions = self.lock();
+        let condition = conditions
+            .entry(name.clone())
+            .or_insert_with(|| ReadinessCondition {
+                name,
+                required: false,
+                ready,
+                message: None,
+            });
+        condition.ready = ready;
+        condition.message = message;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, ReadinessCondition>> {
+        self.conditions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ready_without_requirements() {
+        let readiness = Readiness::new();
+        assert!(readiness.is_ready());
+
+        readiness.mark_not_ready("optional", "not needed");
+        assert!(readiness.is_ready());
+    }
+
+    #[test]
+    fn test_required_conditions_gate_readiness() {
+        let readiness = Readiness::new()
+            .with_requirement(ADAPTER_CONNECTED)
+            .with_requirement(KEYSTORE_UNLOCKED);
+        let probe = readiness.clone();
+
+        readiness.mark_ready(ADAPTER_CONNECTED);
+        assert!(!probe.is_ready());
+        let report = probe.report();
+        assert!(!report.ready);
+        assert_eq!(
+            report
+                .pending()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>(),
+            vec![KEYSTORE_UNLOCKED]
+        );
+
+        readiness.mark_ready(KEYSTORE_UNLOCKED);
+        assert!(probe.is_ready());
+
+        readiness.mark_not_ready(ADAPTER_CONNECTED, "connection lost");
+        let report = probe.report();
+        assert!(!report.ready);
+        assert_eq!(
+            report.conditions[0].message.as_deref(),
+            Some("connection lost")
+        );
+    }
+
+    #[test]
+    fn test_require_keeps_existing_state() {
+        let readiness = Readiness::new();
+        readiness.mark_ready(METADATA_LOADED);
+        readiness.require(METADATA_LOADED);
+
+        assert!(readiness.is_ready());
+        assert!(readiness.report().conditions[0].required);
+    }
+}
@@ -0,0 +1,424 @@
+//! Retention of persisted records
+//!
+//! Stores that grow with every block or transaction, such as the block
+//! indexer's database or the intent journal, implement [`RetentionStore`].
+//! A [`RetentionGc`] applies a [`RetentionPolicy`] to each registered store
+//! on an interval, compacts stores that had records removed, and records the
+//! number of purged records and the resulting store sizes.
+//!
+//! ```rust,no_run
+//! use apex_sdk_core::retention::{RetentionGc, RetentionPolicy, RetentionStore};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # fn example(store: Arc<dyn RetentionStore>) {
+//! let gc = Arc::new(RetentionGc::new().with_interval(Duration::from_secs(600)).with_store(
+//!     store,
+//!     RetentionPolicy::new()
+//!         .with_max_age(Duration::from_secs(30 * 24 * 3600))
+//!         .with_max_size(512 * 1024 * 1024),
+//! ));
+//! let handle = gc.clone().start_background();
+//! # }
+//! ```
+
+use crate::metrics::{Metric, MetricType, MetricsCollector};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tracing::warn;
+
+/// Interval between GC runs unless configured otherwise
+pub const DEFAULT_GC_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Error raised by a store while purging or compacting
+#[derive(Error, Debug)]
+#[error("Retention of '{store}' failed: {message}")]
+pub struct RetentionError {
+    /// Name of the store
+    pub store: String,
+    /// What went wrong
+    pub message: String,
+}
+
+impl RetentionError {
+    /// Create an error for `store`
+    pub fn new(store: impl Into<String>, message: impl ToString) -> Self {
+        Self {
+            store: store.into(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl crate::HasErrorCode for RetentionError {
+    fn code(&self) -> crate::ErrorCode {
+        crate::ErrorCode::new(1130)
+    }
+}
+
+impl From<RetentionError> for crate::SdkError {
+    fn from(err: RetentionError) -> Self {
+        crate::SdkError::ProviderError(err.to_string())
+    }
+}
+
+/// How long and how much a store keeps
+///
+/// A policy without limits keeps everything.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Records older than this are purged
+    pub max_age: Option<Duration>,
+    /// Oldest records are purged until the store is at most this many bytes
+    pub max_size: Option<u64>,
+}
+
+impl RetentionPolicy {
+    /// Create a policy that keeps everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Purge records older than `max_age`
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Purge the oldest records while the store exceeds `bytes`
+    pub fn with_max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Whether the policy never purges anything
+    pub fn is_unbounded(&self) -> bool {
+        self.max_age.is_none() && self.max_size.is_none()
+    }
+
+    /// Unix timestamp in seconds before which records are expired at `now`
+    pub fn cutoff(&self, now: SystemTime) -> Option<u64> {
+        let now = now.duration_since(UNIX_EPOCH).ok()?.as_secs();
+        self.max_age.map(|age| now.saturating_sub(age.as_secs()))
+    }
+
+    /// Whether the store exceeds the size limit at `size_bytes`
+    pub fn exceeds_size(&self, size_bytes: u64) -> bool {
+        self.max_size.is_some_and(|max| size_bytes > max)
+    }
+}
+
+/// Outcome of one purge
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PurgeReport {
+    /// Records removed
+    pub purged: u64,
+    /// Size of the store after the purge, in bytes
+    pub size_bytes: u64,
+}
+
+/// A persistent store whose records can be expired
+#[async_trait]
+pub trait RetentionStore: Send + Sync {
+    /// Name used in statistics and metric labels
+    fn name(&self) -> &str;
+
+    /// Remove records older than the policy's max age, then the oldest
+    /// remaining records until the store fits its max size
+    async fn purge(&self, policy: &RetentionPolicy) -> Result<PurgeReport, RetentionError>;
+
+    /// Reclaim space freed by a purge
+    ///
+    /// Called after a purge that removed records. Stores that release space
+    /// on delete keep the default, which does nothing.
+    async fn compact(&self) -> Result<(), RetentionError> {
+        Ok(())
+    }
+}
+
+/// Retention history of one store
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionStats {
+    /// Store name
+    pub store: String,
+    /// GC runs over the store
+    pub runs: u64,
+    /// Records purged by the latest run
+    pub last_purged: u64,
+    /// Records purged since the GC was created
+    pub purged_total: u64,
+    /// Size of the store after the latest successful run, in bytes
+    pub size_bytes: u64,
+    /// Unix timestamp of the latest run, in seconds
+    pub last_run: Option<u64>,
+    /// Error of the latest run, if it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Applies retention policies to stores, once or on an interval
+pub struct RetentionGc {
+    stores: Vec<(Arc<dyn RetentionStore>, RetentionPolicy)>,
+    interval: Duration,
+    stats: Mutex<BTreeMap<String, RetentionStats>>,
+    metrics: Option<MetricsCollector>,
+}
+
+impl RetentionGc {
+    /// Create a GC without stores, running every [`DEFAULT_GC_INTERVAL`]
+    pub fn new() -> Self {
+        Self {
+            stores: Vec::new(),
+            interval: DEFAULT_GC_INTERVAL,
+            stats: Mutex::new(BTreeMap::new()),
+            metrics: None,
+        }
+    }
+
+    /// Run every `interval` when started in the background
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Apply `policy` to `store` on every run
+    pub fn with_store(mut self, store: Arc<dyn RetentionStore>, policy: RetentionPolicy) -> Self {
+        self.stores.push((store, policy));
+        self
+    }
+
+    /// Record purged records and store sizes into a metrics collector
+    pub fn with_metrics(mut self, metrics: MetricsCollector) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Purge and compact every store once
+    ///
+    /// A failing store does not stop the others; its error is kept in its
+    /// statistics. Returns the statistics after the run.
+    pub async fn run_once(&self) -> Vec<RetentionStats> {
+        for (store, policy) in &self.stores {
+            let result = match store.purge(policy).await {
+                Ok(report) if report.purged > 0 => store.compact().await.map(|_| report),
+                other => other,
+            };
+            if let Err(e) = &result {
+                warn!(operation = "retention_gc", store = store.name(), error = %e, "Retention run failed");
+            }
+            self.update_stats(store.name(), result);
+        }
+        self.stats()
+    }
+
+    /// Run on the configured interval until the task is aborted
+    pub fn start_background(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                self.run_once().await;
+            }
+        })
+    }
+
+    /// Statistics per store, ordered by name
+    pub fn stats(&self) -> Vec<RetentionStats> {
+        self.stats
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    fn update_stats(&self, name: &str, result: Result<PurgeReport, RetentionError>) {
+        let snapshot = {
+            let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+            let entry = stats
+                .entry(name.to_string())
+                .or_insert_with(|| RetentionStats {
+                    store: name.to_string(),
+                    ..Default::default()
+                });
+            entry.runs += 1;
+            entry.last_run = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs());
+            match result {
+                Ok(report) => {
+                    entry.last_purged = report.purged;
+                    entry.purged_total += report.purged;
+                    entry.size_bytes = report.size_bytes;
+                    entry.last_error = None;
+                }
+                Err(e) => {
+                    entry.last_purged = 0;
+                    entry.last_error = Some(e.message);
+                }
+            }
+            entry.clone()
+        };
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record(
+                Metric::new(
+                    MetricType::Retention,
+                    "retention_purged_records",
+                    snapshot.purged_total as f64,
+                )
+                .with_label("store", name)
+                .with_help("Records purged by retention since start"),
+            );
+            metrics.record(
+                Metric::new(
+                    MetricType::Retention,
+                    "retention_store_bytes",
+                    snapshot.size_bytes as f64,
+                )
+                .with_label("store", name)
+                .with_help("Size of a retained store after the latest GC run"),
+            );
+        }
+    }
+}
+
+impl Default for RetentionGc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records keyed by unix timestamp, each one `RECORD_SIZE` bytes
+    struct VecStore {
+        records: Mutex<Vec<u64>>,
+        compactions: Mutex<u32>,
+    }
+
+    const RECORD_SIZE: u64 = 10;
+
+    impl VecStore {
+        fn new(records: Vec<u64>) -> Self {
+            Self {
+                records: Mutex::new(records),
+                compactions: Mutex::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RetentionStore for VecStore {
+        fn name(&self) -> &str {
+            "vec"
+        }
+
+        async fn purge(&self, policy: &RetentionPolicy) -> Result<PurgeReport, RetentionError> {
+            let mut records = self.records.lock().unwrap();
+            let before = records.len();
+            if let Some(cutoff) = policy.cutoff(SystemTime::now()) {
+                records.retain(|&ts| ts >= cutoff);
+            }
+            while policy.exceeds_size(records.len() as u64 * RECORD_SIZE) {
+                records.remove(0);
+            }
+            Ok(PurgeReport {
+                purged: (before - records.len()) as u64,
+                size_bytes: records.len() as u64 * RECORD_SIZE,
+            })
+        }
+
+        async fn compact(&self) -> Result<(), RetentionError> {
+            *self.compactions.lock().unwrap() += 1;
+            Ok(())
+        }
+    }
+
+    struct FailingStore;
+
+    #[async_trait]
+    impl RetentionStore for FailingStore {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        async fn purge(&self, _policy: &RetentionPolicy) -> Result<PurgeReport, RetentionError> {
+            Err(RetentionError::new("failing", "disk unavailable"))
+        }
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn test_policy_limits() {
+        let policy = RetentionPolicy::new();
+        assert!(policy.is_unbounded());
+        assert_eq!(policy.cutoff(SystemTime::now()), None);
+        assert!(!policy.exceeds_size(u64::MAX));
+
+        let policy = policy
+            .with_max_age(Duration::from_secs(60))
+            .with_max_size(100);
+        let at = UNIX_EPOCH + Duration::from_secs(1_000);
+        assert_eq!(policy.cutoff(at), Some(940));
+        assert!(policy.exceeds_size(101));
+        assert!(!policy.exceeds_size(100));
+    }
+
+    #[tokio::test]
+    async fn test_run_once_purges_and_compacts() {
+        let now = now();
+        let store = Arc::new(VecStore::new(vec![now - 7200, now - 10, now - 5, now]));
+        let metrics = MetricsCollector::new();
+        let gc = RetentionGc::new()
+            .with_store(
+                store.clone(),
+                RetentionPolicy::new()
+                    .with_max_age(Duration::from_secs(3600))
+                    .with_max_size(20),
+            )
+            .with_store(Arc::new(FailingStore), RetentionPolicy::new())
+            .with_metrics(metrics.clone());
+
+        let stats = gc.run_once().await;
+        assert_eq!(stats.len(), 2);
+        let failing = &stats[0];
+        assert_eq!(failing.store, "failing");
+        assert_eq!(failing.last_error.as_deref(), Some("disk unavailable"));
+
+        let vec = &stats[1];
+        assert_eq!(vec.last_purged, 2);
+        assert_eq!(vec.size_bytes, 20);
+        assert_eq!(*store.records.lock().unwrap(), vec![now - 5, now]);
+        assert_eq!(*store.compactions.lock().unwrap(), 1);
+
+        // Nothing left to purge, so no compaction either
+        let stats = gc.run_once().await;
+        assert_eq!(stats[1].runs, 2);
+        assert_eq!(stats[1].last_purged, 0);
+        assert_eq!(stats[1].purged_total, 2);
+        assert_eq!(*store.compactions.lock().unwrap(), 1);
+
+        let purged = metrics
+            .get_metrics()
+            .into_iter()
+            .rfind(|m| m.name == "retention_purged_records" && m.labels["store"] == "vec")
+            .unwrap();
+        assert_eq!(purged.metric_type, MetricType::Retention);
+        assert_eq!(purged.value, 2.0);
+    }
+}
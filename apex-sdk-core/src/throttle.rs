@@ -0,0 +1,372 @@
+//! Throttle hints from provider errors
+//!
+//! RPC providers say how long to back off in different ways: an HTTP
+//! `Retry-After` header, a `backoff_seconds` or `retryAfter` field in the
+//! JSON-RPC error data, or a sentence such as "try again in 2s". Errors reach
+//! the SDK as text, so [`ThrottleHint::parse`] looks for each form in an error
+//! message and normalizes it. Retry loops and rate limiters wait the hinted
+//! time instead of their own backoff when a hint gives one; retry loops cap
+//! it at their maximum delay.
+//!
+//! ```rust
+//! use apex_sdk_core::throttle::ThrottleHint;
+//! use std::time::Duration;
+//!
+//! let error = r#"RPC error: {"code":-32005,"message":"limit exceeded","data":{"backoff_seconds":30}}"#;
+//! let hint = ThrottleHint::parse(error).unwrap();
+//! assert_eq!(hint.retry_after, Some(Duration::from_secs(30)));
+//! assert_eq!(hint.code, Some(-32005));
+//! ```
+
+use serde_json::Value;
+use std::time::Duration;
+
+/// JSON-RPC "limit exceeded" error code (EIP-1474)
+pub const LIMIT_EXCEEDED_CODE: i64 = -32005;
+
+/// HTTP status for rate-limited requests
+pub const TOO_MANY_REQUESTS: i64 = 429;
+
+/// Error data fields holding a wait in seconds
+const SECONDS_FIELDS: [&str; 6] = [
+    "retry_after",
+    "retryAfter",
+    "retry_after_seconds",
+    "retryAfterSeconds",
+    "backoff_seconds",
+    "backoffSeconds",
+];
+
+/// Error data fields holding a wait in milliseconds
+const MILLIS_FIELDS: [&str; 4] = ["retry_after_ms", "retryAfterMs", "backoff_ms", "backoffMs"];
+
+/// Phrases introducing a wait, e.g. "try again in 2s"
+const WAIT_PHRASES: [&str; 4] = ["try again in", "try again after", "retry in", "retry after"];
+
+/// Phrases that mark a message as a throttle response
+const THROTTLE_PHRASES: [&str; 5] = [
+    "too many requests",
+    "rate limit",
+    "rate-limit",
+    "request limit",
+    "limit exceeded",
+];
+
+/// Backoff a provider asked for, normalized across providers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ThrottleHint {
+    /// How long to wait before the next request, if the provider said
+    pub retry_after: Option<Duration>,
+    /// JSON-RPC error code or HTTP status of the throttle response
+    pub code: Option<i64>,
+}
+
+impl ThrottleHint {
+    /// Throttle hint in an error message, or `None` if the error is not a
+    /// throttle response
+    ///
+    /// A message counts as throttled when it has a rate-limit code (`429`,
+    /// `-32005`), a wait, or a phrase such as "too many requests". Waits are
+    /// taken from JSON error data first, then a `Retry-After` header, then
+    /// a sentence such as "retry after 5 seconds".
+    pub fn parse(message: &str) -> Option<Self> {
+        let mut hint = Self::default();
+
+        for object in json_objects(message) {
+            if hint.code.is_none() {
+                hint.code = rpc_code(&object).filter(|code| is_throttle_code(*code));
+            }
+            if hint.retry_after.is_none() {
+                hint.retry_after = json_wait(&object);
+            }
+        }
+
+        if hint.retry_after.is_none() {
+            hint.retry_after = header_wait(message).or_else(|| phrase_wait(message));
+        }
+        if hint.code.is_none() && has_status(message, TOO_MANY_REQUESTS) {
+            hint.code = Some(TOO_MANY_REQUESTS);
+        }
+
+        let lower = message.to_lowercase();
+        let throttled = hint.retry_after.is_some()
+            || hint.code.is_some()
+            || THROTTLE_PHRASES.iter().any(|phrase| lower.contains(phrase));
+        throttled.then_some(hint)
+    }
+
+    /// Wait given by a `Retry-After` header value: delay seconds or an
+    /// HTTP date
+    pub fn from_retry_after(value: &str) -> Option<Duration> {
+        let value = value.trim();
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+
+        let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+        let wait = date.signed_duration_since(chrono::Utc::now());
+        Some(wait.to_std().unwrap_or_default())
+    }
+
+    /// Hinted wait, capped at `max`
+    ///
+    /// A provider asking for longer than `max`, e.g. `Retry-After: 3600`, is
+    /// logged and waited for `max` only.
+    pub fn retry_after_capped(&self, max: Duration) -> Option<Duration> {
+        let wait = self.retry_after?;
+        if wait > max {
+            tracing::warn!(
+                hinted = ?wait,
+                max = ?max,
+                "Throttle hint exceeds the maximum delay; waiting the maximum"
+            );
+            return Some(max);
+        }
+        Some(wait)
+    }
+
+    /// Time to wait: the hinted wait, or `fallback` if the provider gave none
+    pub fn backoff(&self, fallback: Duration) -> Duration {
+        self.retry_after.unwrap_or(fallback)
+    }
+}
+
+/// Wait to use before retrying after `error`: the provider's hint if it gave
+/// one, otherwise `fallback`
+pub fn retry_delay(error: &impl std::fmt::Display, fallback: Duration) -> Duration {
+    match ThrottleHint::parse(&error.to_string()) {
+        Some(hint) => hint.backoff(fallback),
+        None => fallback,
+    }
+}
+
+fn is_throttle_code(code: i64) -> bool {
+    code == LIMIT_EXCEEDED_CODE || code == TOO_MANY_REQUESTS
+}
+
+/// JSON objects embedded in a message, outermost first
+fn json_objects(message: &str) -> Vec<Value> {
+    let mut objects = Vec::new();
+    let mut start = 0;
+
+    while let Some(offset) = message[start..].find('{') {
+        let from = start + offset;
+        let mut stream = serde_json::Deserializer::from_str(&message[from..]).into_iter::<Value>();
+        match stream.next() {
+            Some(Ok(object @ Value::Object(_))) => {
+                objects.push(object);
+                start = from + stream.byte_offset();
+            }
+            _ => start = from + 1,
+        }
+    }
+
+    objects
+}
+
+/// `code` of a JSON-RPC error object or response
+fn rpc_code(object: &Value) -> Option<i64> {
+    object
+        .get("code")
+        .or_else(|| object.get("error").and_then(|error| error.get("code")))
+        .and_then(Value::as_i64)
+}
+
+/// First wait field anywhere in `value`
+fn json_wait(value: &Value) -> Option<Duration> {
+    match value {
+        Value::Object(map) => {
+            let seconds = SECONDS_FIELDS
+                .iter()
+                .find_map(|field| map.get(*field).and_then(number))
+                .map(seconds_saturating);
+            let millis = || {
+                MILLIS_FIELDS
+                    .iter()
+                    .find_map(|field| map.get(*field).and_then(number))
+                    .map(|ms| seconds_saturating(ms / 1000.0))
+            };
+            seconds
+                .or_else(millis)
+                .or_else(|| map.values().find_map(json_wait))
+        }
+        Value::Array(values) => values.iter().find_map(json_wait),
+        _ => None,
+    }
+}
+
+/// Non-negative number, or a string holding one
+fn number(value: &Value) -> Option<f64> {
+    let number = match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }?;
+    (number.is_finite() && number >= 0.0).then_some(number)
+}
+
+/// Wait from a `Retry-After: <value>` header quoted in the message
+fn header_wait(message: &str) -> Option<Duration> {
+    let lower = message.to_ascii_lowercase();
+    let at = lower.find("retry-after")? + "retry-after".len();
+    let value = message[at..]
+        .trim_start_matches(|c: char| c == ':' || c == '=' || c == '"' || c.is_whitespace());
+    let end = value.find(['\n', '"', '}', ';']).unwrap_or(value.len());
+    let value = &value[..end];
+
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    if digits > 0 {
+        return value[..digits].parse().ok().map(Duration::from_secs);
+    }
+    ThrottleHint::from_retry_after(value)
+}
+
+/// Wait from a sentence such as "try again in 500ms"
+fn phrase_wait(message: &str) -> Option<Duration> {
+    let lower = message.to_lowercase();
+    WAIT_PHRASES.iter().find_map(|phrase| {
+        let at = lower.find(phrase)? + phrase.len();
+        parse_duration(lower[at..].trim_start())
+    })
+}
+
+/// Number with an optional unit; seconds when there is none
+fn parse_duration(text: &str) -> Option<Duration> {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let amount: f64 = text[..end].parse().ok()?;
+    let unit: String = text[end..]
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+
+    let seconds = match unit.as_str() {
+        "ms" | "millisecond" | "milliseconds" => amount / 1000.0,
+        "m" | "min" | "mins" | "minute" | "minutes" => amount * 60.0,
+        _ => amount,
+    };
+    Some(seconds_saturating(seconds))
+}
+
+/// Duration of `seconds`, clamped to [`Duration::MAX`] when a provider hints
+/// a wait too long to represent
+fn seconds_saturating(seconds: f64) -> Duration {
+    Duration::try_from_secs_f64(seconds).unwrap_or(Duration::MAX)
+}
+
+/// Whether `status` appears in `message` as a number of its own
+fn has_status(message: &str, status: i64) -> bool {
+    let status = status.to_string();
+    message.match_indices(&status).any(|(at, _)| {
+        let before = message[..at].chars().next_back();
+        let after = message[at + status.len()..].chars().next();
+        !before.is_some_and(|c| c.is_ascii_digit() || c == '-')
+            && !after.is_some_and(|c| c.is_ascii_digit())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_provider_responses() {
+        let infura = r#"RPC error: {"code":-32005,"message":"daily request count exceeded, request rate limited","data":{"rate":{"allowed_rps":1,"backoff_seconds":30,"current_rps":1.3}}}"#;
+        assert_eq!(
+            ThrottleHint::parse(infura),
+            Some(ThrottleHint {
+                retry_after: Some(Duration::from_secs(30)),
+                code: Some(LIMIT_EXCEEDED_CODE),
+            })
+        );
+
+        let header = "HTTP error 429 with body: Too Many Requests\nRetry-After: 12";
+        let hint = ThrottleHint::parse(header).unwrap();
+        assert_eq!(hint.retry_after, Some(Duration::from_secs(12)));
+        assert_eq!(hint.code, Some(TOO_MANY_REQUESTS));
+
+        let millis = r#"{"jsonrpc":"2.0","error":{"code":429,"message":"slow down","data":{"retryAfterMs":250}}}"#;
+        let hint = ThrottleHint::parse(millis).unwrap();
+        assert_eq!(hint.retry_after, Some(Duration::from_millis(250)));
+        assert_eq!(hint.code, Some(TOO_MANY_REQUESTS));
+
+        let sentence = "Rate limit reached, please try again in 1.5 seconds";
+        assert_eq!(
+            ThrottleHint::parse(sentence).unwrap().retry_after,
+            Some(Duration::from_millis(1500))
+        );
+
+        // Throttled, but without a wait
+        let bare = ThrottleHint::parse("too many requests").unwrap();
+        assert_eq!(bare.retry_after, None);
+        assert_eq!(bare.backoff(Duration::from_secs(2)), Duration::from_secs(2));
+        assert_eq!(bare.retry_after_capped(Duration::from_secs(2)), None);
+    }
+
+    #[test]
+    fn test_retry_after_capped() {
+        let hint = ThrottleHint::parse("HTTP error 429\nRetry-After: 3600").unwrap();
+        assert_eq!(
+            hint.retry_after_capped(Duration::from_secs(30)),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            hint.retry_after_capped(Duration::from_secs(7200)),
+            Some(Duration::from_secs(3600))
+        );
+    }
+
+    #[test]
+    fn test_parse_clamps_oversized_waits() {
+        let seconds = r#"{"code":-32005,"data":{"backoff_seconds":1e300}}"#;
+        assert_eq!(
+            ThrottleHint::parse(seconds).unwrap().retry_after,
+            Some(Duration::MAX)
+        );
+
+        let millis = r#"{"code":429,"data":{"retryAfterMs":"1e308"}}"#;
+        assert_eq!(
+            ThrottleHint::parse(millis).unwrap().retry_after,
+            Some(Duration::MAX)
+        );
+
+        let sentence = format!("rate limited, try again in {} minutes", "9".repeat(400));
+        let hint = ThrottleHint::parse(&sentence).unwrap();
+        assert_eq!(hint.retry_after, Some(Duration::MAX));
+        assert_eq!(
+            hint.retry_after_capped(Duration::from_secs(30)),
+            Some(Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn test_parse_ignores_other_errors() {
+        assert_eq!(ThrottleHint::parse("Connection refused"), None);
+        assert_eq!(ThrottleHint::parse("Block 14290 not found"), None);
+        assert_eq!(
+            ThrottleHint::parse(r#"{"code":-32000,"message":"nonce too low"}"#),
+            None
+        );
+        assert_eq!(
+            retry_delay(&"invalid params", Duration::from_millis(100)),
+            Duration::from_millis(100)
+        );
+    }
+
+    #[test]
+    fn test_retry_after_header_values() {
+        assert_eq!(
+            ThrottleHint::from_retry_after(" 120 "),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            ThrottleHint::from_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(ThrottleHint::from_retry_after("soon"), None);
+    }
+}
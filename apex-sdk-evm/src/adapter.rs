@@ -0,0 +1,676 @@
+use crate::fees::{Eip1559Fees, FeeStrategy, FEE_HISTORY_BLOCKS};
+use crate::nonce::EvmNonceManager;
+use crate::transaction::TransactionExecutor;
+use crate::{Error, Result};
+use alloy::consensus::{Transaction as _, TxEnvelope};
+use alloy::eips::eip2718::Decodable2718;
+use alloy::network::ReceiptResponse;
+use alloy::primitives::{Address as EvmAddress, B256};
+use alloy::providers::{Provider, RootProvider};
+use alloy::rpc::types::BlockNumberOrTag;
+use alloy::signers::local::PrivateKeySigner;
+use apex_sdk_core::{
+    AssetAllowList, BlockInfo, Broadcaster, ChainAdapter, ConfirmationStrategy, NonceManager,
+    Provider as CoreProvider, ReceiptWatcher, SdkError,
+};
+use apex_sdk_types::{Address, Chain, TransactionStatus, TxHash, TxStatus};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Intrinsic gas of a transaction without call data
+const BASE_TRANSACTION_GAS: u64 = 21_000;
+
+/// Chains named by their EIP-155 chain ID
+const KNOWN_CHAINS: [Chain; 10] = [
+    Chain::Ethereum,
+    Chain::BinanceSmartChain,
+    Chain::Polygon,
+    Chain::Avalanche,
+    Chain::Arbitrum,
+    Chain::Optimism,
+    Chain::ZkSync,
+    Chain::Base,
+    Chain::Moonbeam,
+    Chain::Astar,
+];
+
+/// Adapter for Ethereum-compatible chains over JSON-RPC
+pub struct EvmAdapter {
+    provider: RootProvider,
+    endpoint: Option<String>,
+    chain_id: u64,
+    chain_name: String,
+    signer: Option<PrivateKeySigner>,
+    nonces: Arc<EvmNonceManager>,
+    fee_strategy: FeeStrategy,
+    poll_interval: Duration,
+    asset_allow_list: Option<AssetAllowList>,
+    /// Span and error recording for adapter calls
+    #[cfg(feature = "observability")]
+    pub(crate) instrumentation: Option<apex_sdk_metrics::ChainInstrumentation>,
+}
+
+impl EvmAdapter {
+    /// Connect to an HTTP(S) or WebSocket JSON-RPC endpoint
+    pub async fn connect(endpoint: &str) -> Result<Self> {
+        info!(
+            endpoint = %endpoint,
+            operation = "connect",
+            "Connecting to EVM node"
+        );
+
+        let provider = RootProvider::connect(endpoint)
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to connect: {}", e)))?;
+        let mut adapter = Self::from_provider(provider).await?;
+        adapter.endpoint = Some(endpoint.to_string());
+
+        debug!(chain = %adapter.chain_name, chain_id = adapter.chain_id, "Connected");
+        Ok(adapter)
+    }
+
+    /// Create an adapter from an alloy provider, fetching its chain ID
+    pub async fn from_provider(provider: RootProvider) -> Result<Self> {
+        let chain_id = provider
+            .get_chain_id()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch chain ID: {}", e)))?;
+        let chain_name = KNOWN_CHAINS
+            .iter()
+            .find(|chain| chain.chain_id() == Some(chain_id))
+            .map(|chain| chain.name().to_string())
+            .unwrap_or_else(|| format!("EVM chain {}", chain_id));
+
+        Ok(Self {
+            provider,
+            endpoint: None,
+            chain_id,
+            chain_name,
+            signer: None,
+            nonces: Arc::new(EvmNonceManager::new()),
+            fee_strategy: FeeStrategy::default(),
+            poll_interval: Duration::from_millis(500),
+            asset_allow_list: None,
+            #[cfg(feature = "observability")]
+            instrumentation: None,
+        })
+    }
+
+    /// Sign transactions with a local private key
+    pub fn with_signer(mut self, signer: PrivateKeySigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Set how aggressively transactions bid for inclusion
+    pub fn with_fee_strategy(mut self, strategy: FeeStrategy) -> Self {
+        self.fee_strategy = strategy;
+        self
+    }
+
+    /// Set the initial interval between receipt polls
+    ///
+    /// The interval doubles after every poll, up to five seconds.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Only transfer ERC-20 tokens on `allow_list` whose on-chain symbol and
+    /// decimals match their entry
+    ///
+    /// Entries are keyed by contract address.
+    pub fn with_asset_allow_list(mut self, allow_list: AssetAllowList) -> Self {
+        self.asset_allow_list = Some(allow_list);
+        self
+    }
+
+    /// Record balance queries, transaction submissions and RPC requests as
+    /// spans and errors in `facade`, labelled with this chain and endpoint
+    #[cfg(feature = "observability")]
+    pub fn with_observability(mut self, facade: apex_sdk_metrics::ObservabilityFacade) -> Self {
+        self.instrumentation = Some(apex_sdk_metrics::ChainInstrumentation::new(
+            facade,
+            self.chain_name.clone(),
+            self.endpoint.as_deref(),
+        ));
+        self
+    }
+
+    /// Get the underlying alloy provider
+    pub fn provider(&self) -> &RootProvider {
+        &self.provider
+    }
+
+    /// Get the endpoint URL, if connected through [`EvmAdapter::connect`]
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoint.as_deref()
+    }
+
+    /// Get the EIP-155 chain ID
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Get the address transactions are signed with
+    pub fn signer_address(&self) -> Option<Address> {
+        self.signer
+            .as_ref()
+            .map(|signer| Address::evm(signer.address().to_checksum(None)))
+    }
+
+    /// Get the nonce manager shared by this adapter's transaction executors
+    pub fn nonce_manager(&self) -> Arc<EvmNonceManager> {
+        self.nonces.clone()
+    }
+
+    /// Get the allow-list token transfers are checked against
+    pub fn asset_allow_list(&self) -> Option<&AssetAllowList> {
+        self.asset_allow_list.as_ref()
+    }
+
+    /// Get the fee strategy
+    pub fn fee_strategy(&self) -> FeeStrategy {
+        self.fee_strategy
+    }
+
+    /// Create a transaction executor signing with the configured signer
+    pub fn transaction_executor(&self) -> Result<TransactionExecutor<'_>> {
+        let signer = self.signer.as_ref().ok_or_else(|| {
+            Error::Signer("No signer configured; use EvmAdapter::with_signer".to_string())
+        })?;
+        Ok(TransactionExecutor::new(self, signer))
+    }
+
+    /// Estimate EIP-1559 fees for the next block
+    pub async fn estimate_fees(&self) -> Result<Eip1559Fees> {
+        observed!(self, FeeEstimation, "estimate_fees", async {
+            let history = self
+                .provider
+                .get_fee_history(
+                    FEE_HISTORY_BLOCKS,
+                    BlockNumberOrTag::Latest,
+                    &[self.fee_strategy.reward_percentile()],
+                )
+                .await?;
+            Eip1559Fees::from_fee_history(&history)
+        })
+    }
+
+    /// Get the native balance of an address
+    pub async fn get_balance(&self, address: &Address) -> Result<u128> {
+        let address = parse_address(address)?;
+        observed!(self, BalanceQuery, "get_balance", async {
+            let balance = self.provider.get_balance(address).await?;
+            u128::try_from(balance)
+                .map_err(|_| Error::Other(format!("Balance {} exceeds u128", balance)))
+        })
+    }
+
+    /// Get transaction status by hash
+    pub async fn get_transaction_status(&self, tx_hash: &TxHash) -> Result<TransactionStatus> {
+        observed!(
+            self,
+            RpcRequest,
+            "get_transaction_status",
+            self.query_transaction_status(tx_hash)
+        )
+    }
+
+    async fn query_transaction_status(&self, tx_hash: &TxHash) -> Result<TransactionStatus> {
+        let hash = B256::from(tx_hash.into_bytes());
+
+        let Some(receipt) = self.provider.get_transaction_receipt(hash).await? else {
+            // Known to the node but not mined yet
+            return Ok(match self.provider.get_transaction_by_hash(hash).await? {
+                Some(_) => TransactionStatus {
+                    status: TxStatus::InMempool,
+                    ..TransactionStatus::pending(tx_hash.to_string())
+                },
+                None => TransactionStatus::unknown(tx_hash.to_string()),
+            });
+        };
+
+        let block_number = receipt.block_number().unwrap_or_default();
+        let block_hash = receipt
+            .block_hash()
+            .map(|hash| format!("{:#x}", hash))
+            .unwrap_or_default();
+        let latest = self.provider.get_block_number().await?;
+        let confirmations =
+            u32::try_from(latest.saturating_sub(block_number) + 1).unwrap_or(u32::MAX);
+
+        if !receipt.status() {
+            return Ok(TransactionStatus {
+                block_number: Some(block_number),
+                block_hash: Some(block_hash),
+                gas_used: Some(receipt.gas_used()),
+                effective_gas_price: Some(receipt.effective_gas_price()),
+                confirmations: Some(confirmations),
+                ..TransactionStatus::failed(tx_hash.to_string(), "Transaction reverted".to_string())
+            });
+        }
+
+        let finalized = self.finalized_block_number().await;
+        let status = if finalized.is_some_and(|finalized| finalized >= block_number) {
+            TransactionStatus::finalized
+        } else {
+            TransactionStatus::confirmed
+        };
+        Ok(status(
+            tx_hash.to_string(),
+            block_number,
+            block_hash,
+            Some(receipt.gas_used()),
+            Some(receipt.effective_gas_price()),
+            Some(confirmations),
+        ))
+    }
+
+    /// Number of the latest finalized block, if the node reports one
+    async fn finalized_block_number(&self) -> Option<u64> {
+        match self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Finalized)
+            .await
+        {
+            Ok(block) => block.map(|block| block.header.number),
+            Err(e) => {
+                debug!(error = %e, "Node does not report finalized blocks");
+                None
+            }
+        }
+    }
+
+    /// Broadcast an EIP-2718 encoded signed transaction
+    pub(crate) async fn send_raw(&self, signed_tx: &[u8]) -> Result<TxHash> {
+        observed!(self, TransactionSubmit, "broadcast", async {
+            let pending = self.provider.send_raw_transaction(signed_tx).await?;
+            Ok::<_, Error>(TxHash::new(pending.tx_hash().0))
+        })
+    }
+
+    /// Fallback polling with exponential backoff
+    async fn wait_for_receipt_polling(
+        &self,
+        tx_hash: &TxHash,
+        strategy: &ConfirmationStrategy,
+    ) -> std::result::Result<TransactionStatus, SdkError> {
+        let timeout = match strategy {
+            ConfirmationStrategy::BlockConfirmations { timeout_secs, .. }
+            | ConfirmationStrategy::Finalized { timeout_secs } => {
+                Duration::from_secs(*timeout_secs)
+            }
+            ConfirmationStrategy::Immediate => Duration::from_secs(30),
+        };
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut poll_interval = self.poll_interval;
+        let max_poll_interval = Duration::from_secs(5);
+
+        while tokio::time::Instant::now() < deadline {
+            match self.get_transaction_status(tx_hash).await {
+                Ok(status) => {
+                    let is_satisfied = status.status == TxStatus::Failed
+                        || match strategy {
+                            ConfirmationStrategy::Immediate => {
+                                !matches!(status.status, TxStatus::Pending | TxStatus::Unknown)
+                            }
+                            ConfirmationStrategy::Finalized { .. } => {
+                                status.status == TxStatus::Finalized
+                            }
+                            ConfirmationStrategy::BlockConfirmations {
+                                confirmations: required,
+                                ..
+                            } => status
+                                .confirmations
+                                .is_some_and(|confirmations| confirmations >= *required),
+                        };
+                    if is_satisfied {
+                        return Ok(status);
+                    }
+                }
+                Err(e) => debug!(tx_hash = %tx_hash, error = %e, "Receipt poll failed"),
+            }
+
+            tokio::time::sleep(poll_interval).await;
+            poll_interval = std::cmp::min(poll_interval * 2, max_poll_interval);
+        }
+
+        Err(SdkError::NetworkError(format!(
+            "Timeout waiting for transaction {} after {:?}",
+            tx_hash, timeout
+        )))
+    }
+}
+
+/// Parse an SDK address as an EVM address
+pub(crate) fn parse_address(address: &Address) -> Result<EvmAddress> {
+    match address {
+        Address::Evm(addr) => addr
+            .parse()
+            .map_err(|e| Error::InvalidAddress(format!("{}: {}", addr, e))),
+        other => Err(Error::InvalidAddress(format!(
+            "Expected an EVM address, got {}",
+            other
+        ))),
+    }
+}
+
+/// Gas limit charged for `input` before execution
+fn intrinsic_gas(input: &[u8]) -> u64 {
+    input.iter().fold(BASE_TRANSACTION_GAS, |gas, byte| {
+        gas + if *byte == 0 { 4 } else { 16 }
+    })
+}
+
+#[async_trait]
+impl CoreProvider for EvmAdapter {
+    async fn get_block_number(&self) -> std::result::Result<u64, SdkError> {
+        observed!(self, RpcRequest, "get_block_number", async {
+            Ok::<_, Error>(self.provider.get_block_number().await?)
+        })
+        .map_err(Into::into)
+    }
+
+    async fn get_balance(&self, address: &Address) -> std::result::Result<u128, SdkError> {
+        self.get_balance(address).await.map_err(Into::into)
+    }
+
+    async fn get_transaction_count(&self, address: &Address) -> std::result::Result<u64, SdkError> {
+        let address = parse_address(address)?;
+        observed!(self, NonceRetrieval, "get_transaction_count", async {
+            Ok::<_, Error>(
+                self.provider
+                    .get_transaction_count(address)
+                    .pending()
+                    .await?,
+            )
+        })
+        .map_err(Into::into)
+    }
+
+    /// Maximum fee of `tx`
+    ///
+    /// `tx` is either an EIP-2718 encoded transaction, whose gas limit is
+    /// used, or call data, charged its intrinsic gas.
+    async fn estimate_fee(&self, tx: &[u8]) -> std::result::Result<u128, SdkError> {
+        let gas_limit = match TxEnvelope::decode_2718(&mut &tx[..]) {
+            Ok(envelope) => envelope.gas_limit(),
+            Err(_) => intrinsic_gas(tx),
+        };
+        let fees = self.estimate_fees().await?;
+        Ok(fees.max_cost(gas_limit))
+    }
+
+    async fn get_block(&self, block_number: u64) -> std::result::Result<BlockInfo, SdkError> {
+        let block = observed!(self, BlockQuery, "get_block", async {
+            Ok::<_, Error>(
+                self.provider
+                    .get_block_by_number(BlockNumberOrTag::Number(block_number))
+                    .await?,
+            )
+        })?
+        .ok_or_else(|| SdkError::ProviderError(format!("Block {} not found", block_number)))?;
+
+        let is_finalized = self
+            .finalized_block_number()
+            .await
+            .is_some_and(|finalized| finalized >= block_number);
+        let transactions: Vec<String> = block
+            .transactions
+            .hashes()
+            .map(|hash| format!("{:#x}", hash))
+            .collect();
+
+        Ok(BlockInfo {
+            number: block.header.number,
+            hash: format!("{:#x}", block.header.hash),
+            parent_hash: format!("{:#x}", block.header.parent_hash),
+            timestamp: block.header.timestamp,
+            state_root: Some(format!("{:#x}", block.header.state_root)),
+            extrinsics_root: Some(format!("{:#x}", block.header.transactions_root)),
+            extrinsic_count: transactions.len() as u32,
+            transactions,
+            event_count: None,
+            is_finalized,
+            extrinsics: Vec::new(),
+            author: Some(format!("{:#x}", block.header.beneficiary)),
+        })
+    }
+
+    async fn health_check(&self) -> std::result::Result<(), SdkError> {
+        CoreProvider::get_block_number(self).await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl ChainAdapter for EvmAdapter {
+    async fn get_transaction_status(
+        &self,
+        tx_hash: &TxHash,
+    ) -> std::result::Result<TransactionStatus, String> {
+        self.get_transaction_status(tx_hash)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    fn validate_address(&self, address: &Address) -> bool {
+        parse_address(address).is_ok()
+    }
+
+    fn chain_name(&self) -> &str {
+        &self.chain_name
+    }
+}
+
+#[async_trait]
+impl NonceManager for EvmAdapter {
+    async fn get_next_nonce(&self, address: &Address) -> std::result::Result<u64, SdkError> {
+        let address = parse_address(address)?;
+        self.nonces
+            .peek(&self.provider, address)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+#[async_trait]
+impl Broadcaster for EvmAdapter {
+    async fn broadcast(&self, signed_tx: &[u8]) -> std::result::Result<TxHash, SdkError> {
+        if signed_tx.is_empty() {
+            return Err(SdkError::TransactionError(
+                "Cannot broadcast empty transaction".to_string(),
+            ));
+        }
+
+        TxEnvelope::decode_2718(&mut &signed_tx[..]).map_err(|e| {
+            SdkError::TransactionError(format!("Invalid signed transaction: {}", e))
+        })?;
+
+        let tx_hash = self
+            .send_raw(signed_tx)
+            .await
+            .map_err(|e| SdkError::TransactionError(format!("Broadcast failed: {}", e)))?;
+
+        info!(
+            chain = %self.chain_name,
+            operation = "broadcast",
+            tx_hash = %tx_hash,
+            "Transaction broadcast successful"
+        );
+        Ok(tx_hash)
+    }
+}
+
+#[async_trait]
+impl ReceiptWatcher for EvmAdapter {
+    async fn wait_for_receipt(
+        &self,
+        tx_hash: &TxHash,
+    ) -> std::result::Result<TransactionStatus, SdkError> {
+        self.wait_for_receipt_with_strategy(tx_hash, &ConfirmationStrategy::default())
+            .await
+    }
+
+    async fn wait_for_receipt_with_strategy(
+        &self,
+        tx_hash: &TxHash,
+        strategy: &ConfirmationStrategy,
+    ) -> std::result::Result<TransactionStatus, SdkError> {
+        debug!("Waiting for receipt with strategy: {:?}", strategy);
+        self.wait_for_receipt_polling(tx_hash, strategy).await
+    }
+
+    async fn get_receipt_status(
+        &self,
+        tx_hash: &TxHash,
+    ) -> std::result::Result<Option<TransactionStatus>, SdkError> {
+        let status = self.get_transaction_status(tx_hash).await?;
+        Ok(match status.status {
+            TxStatus::Unknown => None,
+            _ => Some(status),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{U256, U64};
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+    use serde_json::json;
+
+    const TX_HASH: &str = "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060";
+
+    async fn mocked(chain_id: u64) -> (EvmAdapter, Asserter) {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(chain_id));
+        let adapter = EvmAdapter::from_provider(provider).await.unwrap();
+        (adapter, asserter)
+    }
+
+    fn receipt(status: &str, block_number: u64) -> serde_json::Value {
+        json!({
+            "type": "0x2",
+            "status": status,
+            "cumulativeGasUsed": "0x5208",
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "transactionHash": TX_HASH,
+            "transactionIndex": "0x0",
+            "blockHash": format!("0x{}", "ab".repeat(32)),
+            "blockNumber": format!("{:#x}", block_number),
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x3b9aca00",
+            "from": "0x1111111111111111111111111111111111111111",
+            "to": "0x2222222222222222222222222222222222222222",
+            "contractAddress": null
+        })
+    }
+
+    #[tokio::test]
+    async fn test_chain_name_from_chain_id() {
+        let (adapter, _) = mocked(137).await;
+        assert_eq!(adapter.chain_id(), 137);
+        assert_eq!(ChainAdapter::chain_name(&adapter), "Polygon");
+
+        let (adapter, _) = mocked(31337).await;
+        assert_eq!(ChainAdapter::chain_name(&adapter), "EVM chain 31337");
+    }
+
+    #[tokio::test]
+    async fn test_validate_address() {
+        let (adapter, _) = mocked(1).await;
+        assert!(
+            adapter.validate_address(&Address::evm("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"))
+        );
+        assert!(!adapter.validate_address(&Address::evm("0x1234")));
+        assert!(!adapter.validate_address(&Address::substrate(
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_get_balance() {
+        let (adapter, asserter) = mocked(1).await;
+        asserter.push_success(&U256::from(1_500u64));
+        let balance = adapter
+            .get_balance(&Address::evm("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"))
+            .await
+            .unwrap();
+        assert_eq!(balance, 1_500);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_status() {
+        let (adapter, asserter) = mocked(1).await;
+
+        // Not mined, unknown to the node
+        asserter.push_success(&serde_json::Value::Null);
+        asserter.push_success(&serde_json::Value::Null);
+        let status = adapter
+            .get_transaction_status(&TX_HASH.parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(status.status, TxStatus::Unknown);
+
+        // Mined 3 blocks ago, not finalized
+        asserter.push_success(&receipt("0x1", 100));
+        asserter.push_success(&U64::from(102));
+        asserter.push_success(&serde_json::Value::Null);
+        let status = adapter
+            .get_transaction_status(&TX_HASH.parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(status.status, TxStatus::Confirmed);
+        assert_eq!(status.block_number, Some(100));
+        assert_eq!(status.confirmations, Some(3));
+        assert_eq!(status.gas_used, Some(21_000));
+
+        // Reverted
+        asserter.push_success(&receipt("0x0", 100));
+        asserter.push_success(&U64::from(100));
+        let status = adapter
+            .get_transaction_status(&TX_HASH.parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(status.status, TxStatus::Failed);
+        assert_eq!(status.confirmations, Some(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_for_block_confirmations() {
+        let (adapter, asserter) = mocked(1).await;
+        let strategy = ConfirmationStrategy::BlockConfirmations {
+            confirmations: 2,
+            timeout_secs: 60,
+        };
+
+        // Not yet seen, then 1 confirmation, then 2
+        asserter.push_success(&serde_json::Value::Null);
+        asserter.push_success(&serde_json::Value::Null);
+        for latest in [100u64, 101] {
+            asserter.push_success(&receipt("0x1", 100));
+            asserter.push_success(&U64::from(latest));
+            asserter.push_success(&serde_json::Value::Null);
+        }
+
+        let status = adapter
+            .wait_for_receipt_with_strategy(&TX_HASH.parse().unwrap(), &strategy)
+            .await
+            .unwrap();
+        assert_eq!(status.confirmations, Some(2));
+    }
+
+    #[test]
+    fn test_intrinsic_gas() {
+        assert_eq!(intrinsic_gas(&[]), 21_000);
+        assert_eq!(intrinsic_gas(&[0, 1, 2]), 21_000 + 4 + 16 + 16);
+    }
+}
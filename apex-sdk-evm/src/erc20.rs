@@ -0,0 +1,177 @@
+//! ERC-20 tokens
+//!
+//! [`EvmAdapter::token_metadata`] reads a token's symbol and decimals, and
+//! [`TransactionExecutor::transfer_token`] sends a `transfer`. With an
+//! [`AssetAllowList`] attached to the adapter, keyed by contract address,
+//! transfers are refused with [`Error::AssetPolicy`] unless the token is on
+//! the list and its symbol and decimals match its entry.
+//!
+//! [`TransactionExecutor::transfer_token`]: crate::TransactionExecutor::transfer_token
+//! [`AssetAllowList`]: apex_sdk_core::AssetAllowList
+
+use crate::adapter::parse_address;
+use crate::{Error, EvmAdapter, Result};
+use alloy::primitives::{Address as EvmAddress, Bytes, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use apex_sdk_types::Address;
+use serde::{Deserialize, Serialize};
+
+sol! {
+    /// The parts of the ERC-20 interface the SDK uses
+    interface IERC20 {
+        function symbol() external view returns (string);
+        function decimals() external view returns (uint8);
+        function balanceOf(address owner) external view returns (uint256);
+        function transfer(address to, uint256 amount) external returns (bool);
+    }
+}
+
+/// Symbol and decimals of an ERC-20 token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    /// Ticker symbol
+    pub symbol: String,
+    /// Number of decimals in a display amount
+    pub decimals: u8,
+}
+
+/// Call data of `transfer(to, amount)`
+pub(crate) fn transfer_data(to: EvmAddress, amount: u128) -> Vec<u8> {
+    IERC20::transferCall {
+        to,
+        amount: U256::from(amount),
+    }
+    .abi_encode()
+}
+
+impl EvmAdapter {
+    /// Read a token's symbol and decimals
+    ///
+    /// Tokens that return their symbol as `bytes32`, which predates the
+    /// standard, fail to decode.
+    pub async fn token_metadata(&self, token: &Address) -> Result<TokenMetadata> {
+        let token = parse_address(token)?;
+        observed!(self, RpcRequest, "token_metadata", async {
+            let symbol = self.view(token, IERC20::symbolCall {}).await?;
+            let decimals = self.view(token, IERC20::decimalsCall {}).await?;
+            Ok(TokenMetadata { symbol, decimals })
+        })
+    }
+
+    /// Token balance of `owner`, in the token's smallest unit
+    pub async fn token_balance(&self, token: &Address, owner: &Address) -> Result<u128> {
+        let token = parse_address(token)?;
+        let owner = parse_address(owner)?;
+        observed!(self, BalanceQuery, "token_balance", async {
+            let balance = self.view(token, IERC20::balanceOfCall { owner }).await?;
+            u128::try_from(balance)
+                .map_err(|_| Error::Other(format!("Balance {} exceeds u128", balance)))
+        })
+    }
+
+    /// Check a token against the allow-list, if one is set
+    ///
+    /// Fails with [`Error::AssetPolicy`] if the token is not on the list or
+    /// its on-chain symbol or decimals differ from its entry.
+    pub async fn verify_token(&self, token: &Address) -> Result<()> {
+        let Some(allow_list) = self.asset_allow_list() else {
+            return Ok(());
+        };
+        let id = token.to_string();
+        allow_list.check_allowed(&id)?;
+
+        let metadata = self.token_metadata(token).await?;
+        allow_list.verify(&id, &metadata.symbol, metadata.decimals)?;
+        Ok(())
+    }
+
+    /// `eth_call` a view function and decode its return value
+    async fn view<C: SolCall>(&self, token: EvmAddress, call: C) -> Result<C::Return> {
+        let request = TransactionRequest::default()
+            .to(token)
+            .input(Bytes::from(call.abi_encode()).into());
+        let output = self.provider().call(request).await?;
+        C::abi_decode_returns(&output).map_err(|e| {
+            Error::Rpc(format!(
+                "Unexpected {} result from {}: {}",
+                C::SIGNATURE,
+                token,
+                e
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U64;
+    use alloy::providers::ProviderBuilder;
+    use alloy::sol_types::SolValue;
+    use alloy::transports::mock::Asserter;
+    use apex_sdk_core::{AssetAllowList, AssetPolicyError};
+
+    const USDT: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+
+    async fn mocked() -> (EvmAdapter, Asserter) {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(1));
+        let adapter = EvmAdapter::from_provider(provider).await.unwrap();
+        (adapter, asserter)
+    }
+
+    fn push_metadata(asserter: &Asserter, symbol: &str, decimals: u8) {
+        asserter.push_success(&Bytes::from(symbol.to_string().abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(decimals).abi_encode()));
+    }
+
+    #[tokio::test]
+    async fn test_token_metadata() {
+        let (adapter, asserter) = mocked().await;
+        push_metadata(&asserter, "USDT", 6);
+
+        let metadata = adapter.token_metadata(&Address::evm(USDT)).await.unwrap();
+        assert_eq!(
+            metadata,
+            TokenMetadata {
+                symbol: "USDT".to_string(),
+                decimals: 6,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_token_against_allow_list() {
+        let (adapter, asserter) = mocked().await;
+        let adapter =
+            adapter.with_asset_allow_list(AssetAllowList::new().with_asset(USDT, "USDT", 6));
+
+        push_metadata(&asserter, "USDT", 6);
+        adapter
+            .verify_token(&Address::evm(USDT.to_lowercase()))
+            .await
+            .unwrap();
+
+        // A token whose metadata changed
+        push_metadata(&asserter, "USDT", 18);
+        assert!(matches!(
+            adapter.verify_token(&Address::evm(USDT)).await,
+            Err(Error::AssetPolicy(
+                AssetPolicyError::DecimalsMismatch { .. }
+            ))
+        ));
+
+        // A look-alike is refused without querying it
+        let lookalike = Address::evm("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        assert!(matches!(
+            adapter.verify_token(&lookalike).await,
+            Err(Error::AssetPolicy(AssetPolicyError::NotAllowed(_)))
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+//! EIP-1559 fee estimation
+//!
+//! Fees are derived from `eth_feeHistory`: the priority fee is the median of
+//! the tips paid at a strategy-dependent percentile over recent blocks, and
+//! the max fee leaves room for the base fee to double before the transaction
+//! is included.
+
+use crate::{Error, Result};
+use alloy::rpc::types::FeeHistory;
+use serde::{Deserialize, Serialize};
+
+/// Number of recent blocks sampled for fee estimation
+pub const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// Priority fee used when recent blocks paid no tips (1 gwei)
+pub const DEFAULT_PRIORITY_FEE: u128 = 1_000_000_000;
+
+/// How aggressively to bid for inclusion
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeeStrategy {
+    /// Tip like the cheapest 10% of recent transactions
+    Slow,
+    /// Tip like the median recent transaction
+    #[default]
+    Standard,
+    /// Tip like the most generous 10% of recent transactions
+    Fast,
+}
+
+impl FeeStrategy {
+    /// Percentile of recent tips to match
+    pub fn reward_percentile(self) -> f64 {
+        match self {
+            FeeStrategy::Slow => 10.0,
+            FeeStrategy::Standard => 50.0,
+            FeeStrategy::Fast => 90.0,
+        }
+    }
+}
+
+/// EIP-1559 fee parameters for a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Eip1559Fees {
+    /// Base fee expected for the next block
+    pub base_fee_per_gas: u128,
+    /// Tip paid to the block producer
+    pub max_priority_fee_per_gas: u128,
+    /// Upper bound on the total fee per gas
+    pub max_fee_per_gas: u128,
+}
+
+impl Eip1559Fees {
+    /// Derive fees from an `eth_feeHistory` response requested with a single
+    /// reward percentile
+    pub fn from_fee_history(history: &FeeHistory) -> Result<Self> {
+        let base_fee_per_gas = history
+            .next_block_base_fee()
+            .filter(|base_fee| *base_fee > 0)
+            .ok_or_else(|| {
+                Error::FeeEstimation("Chain does not report an EIP-1559 base fee".to_string())
+            })?;
+
+        let mut tips: Vec<u128> = history
+            .reward
+            .iter()
+            .flatten()
+            .filter_map(|rewards| rewards.first().copied())
+            .filter(|tip| *tip > 0)
+            .collect();
+        tips.sort_unstable();
+        let max_priority_fee_per_gas = tips
+            .get(tips.len() / 2)
+            .copied()
+            .unwrap_or(DEFAULT_PRIORITY_FEE);
+
+        Ok(Self {
+            base_fee_per_gas,
+            max_priority_fee_per_gas,
+            max_fee_per_gas: base_fee_per_gas
+                .saturating_mul(2)
+                .saturating_add(max_priority_fee_per_gas),
+        })
+    }
+
+    /// Highest fee a transaction using `gas_limit` can be charged
+    pub fn max_cost(&self, gas_limit: u64) -> u128 {
+        self.max_fee_per_gas.saturating_mul(gas_limit as u128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(base_fees: Vec<u128>, rewards: Vec<Vec<u128>>) -> FeeHistory {
+        FeeHistory {
+            base_fee_per_gas: base_fees,
+            reward: Some(rewards),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fees_from_history() {
+        let fees = Eip1559Fees::from_fee_history(&history(
+            vec![10, 12, 20],
+            vec![vec![3], vec![0], vec![1], vec![2]],
+        ))
+        .unwrap();
+
+        assert_eq!(fees.base_fee_per_gas, 20);
+        assert_eq!(fees.max_priority_fee_per_gas, 2);
+        assert_eq!(fees.max_fee_per_gas, 42);
+        assert_eq!(fees.max_cost(21_000), 42 * 21_000);
+    }
+
+    #[test]
+    fn test_default_tip_without_rewards() {
+        let fees = Eip1559Fees::from_fee_history(&history(vec![7, 7], vec![])).unwrap();
+        assert_eq!(fees.max_priority_fee_per_gas, DEFAULT_PRIORITY_FEE);
+    }
+
+    #[test]
+    fn test_legacy_chain_rejected() {
+        let result = Eip1559Fees::from_fee_history(&history(vec![0, 0], vec![]));
+        assert!(matches!(result, Err(Error::FeeEstimation(_))));
+    }
+}
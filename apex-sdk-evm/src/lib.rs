@@ -0,0 +1,136 @@
+//! # Apex SDK EVM Adapter
+//!
+//! Adapter for Ethereum-compatible chains over JSON-RPC, built on alloy.
+//! It includes support for:
+//! - HTTP and WebSocket endpoints
+//! - EIP-1559 fee estimation from the node's fee history
+//! - Local nonce tracking for back-to-back submissions
+//! - Signing with a local private key and raw transaction broadcast
+//! - Receipt polling with block-confirmation and finality strategies
+//! - ERC-20 metadata, balances and transfers checked against an asset allow-list
+//!
+//! ```rust,no_run
+//! use apex_sdk_evm::{EvmAdapter, PrivateKeySigner};
+//! use apex_sdk_types::Address;
+//!
+//! # async fn example() -> apex_sdk_evm::Result<()> {
+//! let signer: PrivateKeySigner =
+//!     "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+//!         .parse()
+//!         .unwrap();
+//! let adapter = EvmAdapter::connect("https://ethereum-sepolia-rpc.publicnode.com")
+//!     .await?
+//!     .with_signer(signer);
+//!
+//! let to = Address::evm("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+//! let tx_hash = adapter.transaction_executor()?.transfer(&to, 1_000).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use apex_sdk_core::{AssetPolicyError, ErrorCode, HasErrorCode, SdkError};
+use thiserror::Error;
+
+/// Run an adapter call through the attached observability instrumentation, if any
+macro_rules! observed {
+    ($adapter:expr, $operation:ident, $name:literal, $call:expr) => {{
+        #[cfg(feature = "observability")]
+        let result = match &$adapter.instrumentation {
+            Some(instrumentation) => {
+                instrumentation
+                    .observe(
+                        apex_sdk_metrics::profiling::OperationType::$operation,
+                        $name,
+                        $call,
+                    )
+                    .await
+            }
+            None => $call.await,
+        };
+        #[cfg(not(feature = "observability"))]
+        let result = $call.await;
+        result
+    }};
+}
+
+pub mod adapter;
+pub mod erc20;
+pub mod fees;
+pub mod nonce;
+pub mod transaction;
+
+pub use adapter::EvmAdapter;
+pub use alloy::signers::local::PrivateKeySigner;
+pub use erc20::TokenMetadata;
+pub use fees::{Eip1559Fees, FeeStrategy};
+pub use nonce::EvmNonceManager;
+pub use transaction::TransactionExecutor;
+
+/// EVM adapter error
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Connection error: {0}")]
+    Connection(String),
+
+    #[error("RPC error: {0}")]
+    Rpc(String),
+
+    #[error("Transaction error: {0}")]
+    Transaction(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Signer error: {0}")]
+    Signer(String),
+
+    #[error("Fee estimation error: {0}")]
+    FeeEstimation(String),
+
+    #[error("Asset policy violation: {0}")]
+    AssetPolicy(#[from] AssetPolicyError),
+
+    #[error("Other error: {0}")]
+    Other(String),
+}
+
+impl HasErrorCode for Error {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::new(match self {
+            Error::Connection(_) => 1400,
+            Error::Rpc(_) => 1401,
+            Error::Transaction(_) => 1402,
+            Error::InvalidAddress(_) => 1403,
+            Error::Signer(_) => 1404,
+            Error::FeeEstimation(_) => 1405,
+            Error::AssetPolicy(_) => 1406,
+            Error::Other(_) => 1499,
+        })
+    }
+}
+
+impl From<alloy::transports::TransportError> for Error {
+    fn from(err: alloy::transports::TransportError) -> Self {
+        match err {
+            alloy::transports::RpcError::Transport(_) => Error::Connection(err.to_string()),
+            err => Error::Rpc(err.to_string()),
+        }
+    }
+}
+
+impl From<Error> for SdkError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Connection(msg) => SdkError::NetworkError(msg),
+            Error::Rpc(msg) => SdkError::ProviderError(msg),
+            Error::Transaction(msg) => SdkError::TransactionError(msg),
+            Error::InvalidAddress(msg) => SdkError::ConfigError(msg),
+            Error::Signer(msg) => SdkError::SignerError(msg),
+            Error::FeeEstimation(msg) => SdkError::ProviderError(msg),
+            Error::AssetPolicy(err) => err.into(),
+            Error::Other(msg) => SdkError::ProviderError(msg),
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
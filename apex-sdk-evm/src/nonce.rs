@@ -0,0 +1,86 @@
+//! Nonce management
+//!
+//! The pending transaction count reported by a node lags behind submissions
+//! made moments earlier, so sending several transactions back to back with
+//! on-chain nonces alone reuses nonces. [`EvmNonceManager`] hands out nonces
+//! from a local counter per account, resynchronized with the node whenever it
+//! reports a higher count.
+
+use crate::Result;
+use alloy::primitives::Address;
+use alloy::providers::Provider;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+/// Per-account nonce tracker
+#[derive(Debug, Default)]
+pub struct EvmNonceManager {
+    next: Mutex<HashMap<Address, u64>>,
+}
+
+impl EvmNonceManager {
+    /// Create a nonce manager without tracked accounts
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Next nonce for `address`, without reserving it
+    pub async fn peek<P: Provider>(&self, provider: &P, address: Address) -> Result<u64> {
+        let next = self.next.lock().await;
+        let on_chain = provider.get_transaction_count(address).pending().await?;
+        Ok(next
+            .get(&address)
+            .map_or(on_chain, |local| (*local).max(on_chain)))
+    }
+
+    /// Reserve the next nonce for `address`
+    ///
+    /// Concurrent callers receive distinct nonces.
+    pub async fn reserve<P: Provider>(&self, provider: &P, address: Address) -> Result<u64> {
+        let mut next = self.next.lock().await;
+        let on_chain = provider.get_transaction_count(address).pending().await?;
+        let nonce = next
+            .get(&address)
+            .map_or(on_chain, |local| (*local).max(on_chain));
+        next.insert(address, nonce + 1);
+        Ok(nonce)
+    }
+
+    /// Forget the local counter for `address`
+    ///
+    /// Call after a submission failed, so the nonce it reserved is reused.
+    pub async fn reset(&self, address: Address) {
+        self.next.lock().await.remove(&address);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U64;
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+
+    #[tokio::test]
+    async fn test_reserve_increments_past_pending_count() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        let nonces = EvmNonceManager::new();
+        let account = Address::repeat_byte(0x11);
+
+        for count in [5u64, 5, 5, 9, 9] {
+            asserter.push_success(&U64::from(count));
+        }
+
+        assert_eq!(nonces.reserve(&provider, account).await.unwrap(), 5);
+        assert_eq!(nonces.reserve(&provider, account).await.unwrap(), 6);
+        assert_eq!(nonces.peek(&provider, account).await.unwrap(), 7);
+        // The node caught up with transactions sent elsewhere
+        assert_eq!(nonces.reserve(&provider, account).await.unwrap(), 9);
+
+        nonces.reset(account).await;
+        assert_eq!(nonces.reserve(&provider, account).await.unwrap(), 9);
+    }
+}
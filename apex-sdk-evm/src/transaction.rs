@@ -0,0 +1,287 @@
+//! Transaction execution
+//!
+//! [`TransactionExecutor`] turns a transfer, contract call or deployment into
+//! a signed EIP-1559 transaction: it estimates fees and gas, reserves a nonce
+//! from the adapter's [`EvmNonceManager`](crate::EvmNonceManager), signs with
+//! the adapter's signer and broadcasts the encoded transaction. ERC-20
+//! transfers are checked against the adapter's asset allow-list first.
+
+use crate::adapter::parse_address;
+use crate::erc20::transfer_data;
+use crate::{Error, EvmAdapter, Result};
+use alloy::eips::eip2718::Encodable2718;
+use alloy::network::{EthereumWallet, TransactionBuilder};
+use alloy::primitives::{Bytes, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::signers::local::PrivateKeySigner;
+use apex_sdk_core::BroadcastHook;
+use apex_sdk_types::{Address, TxHash};
+use tracing::{debug, info};
+
+/// Signs and broadcasts transactions for an [`EvmAdapter`]
+pub struct TransactionExecutor<'a> {
+    adapter: &'a EvmAdapter,
+    signer: &'a PrivateKeySigner,
+    broadcast_hook: Option<BroadcastHook>,
+}
+
+impl<'a> TransactionExecutor<'a> {
+    pub(crate) fn new(adapter: &'a EvmAdapter, signer: &'a PrivateKeySigner) -> Self {
+        Self {
+            adapter,
+            signer,
+            broadcast_hook: None,
+        }
+    }
+
+    /// Pass the hash of every signed transaction to `hook` before it is
+    /// broadcast
+    ///
+    /// If the hook fails, the transaction is not broadcast.
+    pub fn with_broadcast_hook(mut self, hook: BroadcastHook) -> Self {
+        self.broadcast_hook = Some(hook);
+        self
+    }
+
+    /// Transfer `value` wei to `to`
+    ///
+    /// Returns the transaction hash.
+    pub async fn transfer(&self, to: &Address, value: u128) -> Result<TxHash> {
+        let request = TransactionRequest::default()
+            .with_to(parse_address(to)?)
+            .with_value(U256::from(value));
+        self.send(request, None).await
+    }
+
+    /// Transfer `amount` of ERC-20 `token`, in its smallest unit, to `to`
+    ///
+    /// With an allow-list on the adapter, the token is
+    /// [verified](EvmAdapter::verify_token) first.
+    pub async fn transfer_token(
+        &self,
+        token: &Address,
+        to: &Address,
+        amount: u128,
+    ) -> Result<TxHash> {
+        self.adapter.verify_token(token).await?;
+        let request = TransactionRequest::default()
+            .with_to(parse_address(token)?)
+            .with_input(Bytes::from(transfer_data(parse_address(to)?, amount)));
+        self.send(request, None).await
+    }
+
+    /// Call contract `to` with `data`, attaching `value` wei
+    ///
+    /// The gas limit is estimated by the node unless `gas_limit` is given.
+    pub async fn call(
+        &self,
+        to: &Address,
+        data: Vec<u8>,
+        value: u128,
+        gas_limit: Option<u64>,
+    ) -> Result<TxHash> {
+        let request = TransactionRequest::default()
+            .with_to(parse_address(to)?)
+            .with_input(Bytes::from(data))
+            .with_value(U256::from(value));
+        self.send(request, gas_limit).await
+    }
+
+    /// Deploy a contract from `code` (creation bytecode followed by encoded
+    /// constructor arguments)
+    pub async fn deploy(
+        &self,
+        code: Vec<u8>,
+        value: u128,
+        gas_limit: Option<u64>,
+    ) -> Result<TxHash> {
+        let request = TransactionRequest::default()
+            .with_deploy_code(Bytes::from(code))
+            .with_value(U256::from(value));
+        self.send(request, gas_limit).await
+    }
+
+    async fn send(&self, request: TransactionRequest, gas_limit: Option<u64>) -> Result<TxHash> {
+        let from = self.signer.address();
+        let provider = self.adapter.provider();
+        let request = request
+            .with_from(from)
+            .with_chain_id(self.adapter.chain_id());
+
+        let fees = self.adapter.estimate_fees().await?;
+        let gas_limit = match gas_limit {
+            Some(gas_limit) => gas_limit,
+            None => provider
+                .estimate_gas(request.clone())
+                .await
+                .map_err(|e| Error::Transaction(format!("Gas estimation failed: {}", e)))?,
+        };
+
+        let nonces = self.adapter.nonce_manager();
+        let nonce = nonces.reserve(provider, from).await?;
+        debug!(
+            from = %from,
+            nonce,
+            gas_limit,
+            max_fee_per_gas = fees.max_fee_per_gas,
+            "Signing transaction"
+        );
+
+        let result = async {
+            let envelope = request
+                .with_nonce(nonce)
+                .with_gas_limit(gas_limit)
+                .with_max_fee_per_gas(fees.max_fee_per_gas)
+                .with_max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+                .build(&EthereumWallet::from(self.signer.clone()))
+                .await
+                .map_err(|e| Error::Signer(e.to_string()))?;
+            if let Some(hook) = &self.broadcast_hook {
+                hook(&envelope.tx_hash().to_string())
+                    .map_err(|e| Error::Transaction(format!("Transaction not broadcast: {}", e)))?;
+            }
+            // The node may have received a transaction whose broadcast
+            // failed, so the failure is not a retryable connection error
+            self.adapter
+                .send_raw(&envelope.encoded_2718())
+                .await
+                .map_err(|e| match e {
+                    Error::Connection(msg) => {
+                        Error::Transaction(format!("Broadcast failed: {}", msg))
+                    }
+                    e => e,
+                })
+        }
+        .await;
+
+        match result {
+            Ok(tx_hash) => {
+                info!(from = %from, nonce, tx_hash = %tx_hash, "Transaction submitted");
+                Ok(tx_hash)
+            }
+            Err(e) => {
+                // Let the next transaction reuse the nonce this one reserved
+                nonces.reset(from).await;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{B256, U64};
+    use alloy::providers::ProviderBuilder;
+    use alloy::rpc::types::FeeHistory;
+    use alloy::transports::mock::Asserter;
+
+    const KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    #[tokio::test]
+    async fn test_transfer_signs_and_broadcasts() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(1));
+        let adapter = EvmAdapter::from_provider(provider)
+            .await
+            .unwrap()
+            .with_signer(KEY.parse().unwrap());
+
+        let tx_hash = B256::repeat_byte(0x42);
+        asserter.push_success(&FeeHistory {
+            base_fee_per_gas: vec![10, 20],
+            reward: Some(vec![vec![3]]),
+            ..Default::default()
+        });
+        asserter.push_success(&U64::from(21_000));
+        asserter.push_success(&U64::from(4));
+        asserter.push_success(&tx_hash);
+
+        let to = Address::evm("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let executor = adapter.transaction_executor().unwrap();
+        let hash = executor.transfer(&to, 1_000).await.unwrap();
+        assert_eq!(hash.to_string(), format!("{:#x}", tx_hash));
+
+        // The reserved nonce is not handed out again
+        asserter.push_success(&U64::from(4));
+        let from = adapter.signer_address().unwrap();
+        let next = apex_sdk_core::NonceManager::get_next_nonce(&adapter, &from)
+            .await
+            .unwrap();
+        assert_eq!(next, 5);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_hook_sees_hash_first() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(1));
+        let adapter = EvmAdapter::from_provider(provider)
+            .await
+            .unwrap()
+            .with_signer(KEY.parse().unwrap());
+        let to = Address::evm("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let fees = FeeHistory {
+            base_fee_per_gas: vec![10, 20],
+            reward: Some(vec![vec![3]]),
+            ..Default::default()
+        };
+
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        let executor =
+            adapter
+                .transaction_executor()
+                .unwrap()
+                .with_broadcast_hook(std::sync::Arc::new(move |hash: &str| {
+                    recorded.lock().unwrap().push(hash.to_string());
+                    Ok(())
+                }));
+        asserter.push_success(&fees);
+        asserter.push_success(&U64::from(21_000));
+        asserter.push_success(&U64::from(4));
+        asserter.push_success(&B256::repeat_byte(0x42));
+        executor.transfer(&to, 1_000).await.unwrap();
+
+        let seen = seen.lock().unwrap().clone();
+        assert_eq!(seen.len(), 1);
+        assert!(seen[0].starts_with("0x") && seen[0].len() == 66);
+
+        // A failing hook stops the broadcast
+        let executor =
+            adapter
+                .transaction_executor()
+                .unwrap()
+                .with_broadcast_hook(std::sync::Arc::new(|_: &str| {
+                    Err(apex_sdk_core::SdkError::TransactionError(
+                        "journal unavailable".to_string(),
+                    ))
+                }));
+        asserter.push_success(&fees);
+        asserter.push_success(&U64::from(21_000));
+        asserter.push_success(&U64::from(5));
+        let err = executor.transfer(&to, 1_000).await.unwrap_err();
+        assert!(err.to_string().contains("not broadcast"));
+    }
+
+    #[tokio::test]
+    async fn test_executor_requires_signer() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(1));
+        let adapter = EvmAdapter::from_provider(provider).await.unwrap();
+
+        assert!(matches!(
+            adapter.transaction_executor(),
+            Err(Error::Signer(_))
+        ));
+    }
+}
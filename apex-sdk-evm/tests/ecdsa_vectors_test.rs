@@ -0,0 +1,120 @@
+//! Golden-vector tests for ECDSA (secp256k1) key derivation and signatures
+//!
+//! The vectors in `fixtures/ecdsa_vectors.json` are the well-known Hardhat and
+//! Anvil development accounts, which MetaMask, ethers and every other
+//! BIP-44 wallet derive from the same phrase. Derived keys go through
+//! `EvmAdapter::with_signer`, so the addresses checked are the ones the
+//! adapter signs with. Signatures are RFC 6979
+//! deterministic, so they are compared byte for byte. If these fail, fix the
+//! code rather than the fixture.
+
+use alloy::primitives::{Address, Signature, U64};
+use alloy::providers::ProviderBuilder;
+use alloy::signers::SignerSync;
+use alloy::transports::mock::Asserter;
+use alloy_signer_local::{coins_bip39::English, MnemonicBuilder};
+use apex_sdk_evm::{EvmAdapter, PrivateKeySigner};
+use serde::Deserialize;
+
+const VECTORS: &str = include_str!("fixtures/ecdsa_vectors.json");
+
+#[derive(Deserialize)]
+struct Vectors {
+    derivation: Vec<DerivationVector>,
+    signatures: Vec<SignatureVector>,
+}
+
+#[derive(Deserialize)]
+struct DerivationVector {
+    phrase: String,
+    path: String,
+    private_key: String,
+    address: String,
+}
+
+#[derive(Deserialize)]
+struct SignatureVector {
+    comment: String,
+    private_key: String,
+    address: String,
+    message: String,
+    signature: String,
+}
+
+fn load() -> Vectors {
+    serde_json::from_str(VECTORS).expect("ecdsa_vectors.json is malformed")
+}
+
+fn bytes(hex_str: &str) -> Vec<u8> {
+    alloy::hex::decode(hex_str).unwrap()
+}
+
+async fn adapter() -> EvmAdapter {
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new()
+        .disable_recommended_fillers()
+        .connect_mocked_client(asserter.clone());
+    asserter.push_success(&U64::from(1));
+    EvmAdapter::from_provider(provider).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_derivation_vectors() {
+    let vectors = load();
+    assert!(!vectors.derivation.is_empty());
+
+    for vector in &vectors.derivation {
+        let signer = MnemonicBuilder::<English>::default()
+            .phrase(vector.phrase.as_str())
+            .derivation_path(vector.path.as_str())
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            bytes(&vector.private_key),
+            signer.credential().to_bytes().to_vec(),
+            "{}: private key",
+            vector.path
+        );
+
+        // The adapter reports the EIP-55 checksummed form
+        let adapter = adapter().await.with_signer(signer);
+        assert_eq!(
+            adapter.signer_address().unwrap().as_str(),
+            vector.address,
+            "{}: address",
+            vector.path
+        );
+    }
+}
+
+#[test]
+fn test_signature_vectors() {
+    for vector in load().signatures {
+        let signer: PrivateKeySigner = vector.private_key.parse().unwrap();
+        let expected_address: Address = vector.address.parse().unwrap();
+        assert_eq!(signer.address(), expected_address, "{}", vector.comment);
+
+        let message = bytes(&vector.message);
+        let signature = Signature::try_from(bytes(&vector.signature).as_slice()).unwrap();
+
+        assert_eq!(
+            signature.recover_address_from_msg(&message).unwrap(),
+            expected_address,
+            "{}: recovered address",
+            vector.comment
+        );
+
+        let mut tampered = message.clone();
+        tampered.push(0);
+        assert_ne!(
+            signature.recover_address_from_msg(&tampered).unwrap(),
+            expected_address,
+            "{}: signature recovers for a different message",
+            vector.comment
+        );
+
+        let fresh = signer.sign_message_sync(&message).unwrap();
+        assert_eq!(fresh, signature, "{}", vector.comment);
+    }
+}
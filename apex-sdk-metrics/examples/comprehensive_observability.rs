@@ -17,6 +17,7 @@ use apex_sdk_metrics::{
     HealthStatus, MetricsAggregator, MetricsServer, ObservabilityConfig, OperationType,
     PerformanceProfiler, TimeWindow,
 };
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -37,7 +38,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("📊 Step 2: Create Observability Components");
     let collector = MetricsCollector::new();
     let profiler = PerformanceProfiler::new();
-    let health_checker = HealthChecker::new();
+    let health_checker = Arc::new(HealthChecker::new());
     let aggregator = MetricsAggregator::with_time_window(TimeWindow::FiveMinutes);
     println!("✓ Components created\n");
 
@@ -51,7 +52,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("📊 Step 5: Monitor Component Health");
     monitor_health(&health_checker);
-    let health_summary = health_checker.health_summary();
+    let health_summary = health_checker.health_summary_async().await;
     println!("✓ Overall Status: {:?}", health_summary.status);
     println!(
         "✓ CPU Usage: {:.1}%",
@@ -70,7 +71,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     display_performance_stats(&profiler);
 
     println!("📊 Step 8: Starting Prometheus Metrics Server");
-    let server = MetricsServer::new(config.prometheus_port, collector.clone()).await?;
+    let server = MetricsServer::from_config(&config, collector.clone())
+        .await?
+        .with_profiler(profiler.clone())
+        .with_health_checker(health_checker.clone());
     println!(
         "✓ Metrics server listening on http://localhost:{}",
         config.prometheus_port
@@ -232,5 +236,6 @@ fn format_time_window(window: TimeWindow) -> String {
         TimeWindow::OneHour => "last 1 hour".to_string(),
         TimeWindow::OneDay => "last 24 hours".to_string(),
         TimeWindow::OneWeek => "last 7 days".to_string(),
+        TimeWindow::Custom(_) => format!("last {}", window),
     }
 }
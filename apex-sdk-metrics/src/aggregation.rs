@@ -8,8 +8,11 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+/// Most snapshots returned by [`MetricsAggregator::sliding`]
+pub const MAX_SLIDING_WINDOWS: usize = 1024;
+
 /// Time window for aggregation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TimeWindow {
     /// Last minute
     OneMinute,
@@ -23,6 +26,8 @@ pub enum TimeWindow {
     OneDay,
     /// Last 7 days
     OneWeek,
+    /// Any other length; metric timestamps have one-second resolution
+    Custom(Duration),
 }
 
 impl TimeWindow {
@@ -35,6 +40,7 @@ impl TimeWindow {
             TimeWindow::OneHour => Duration::from_secs(3600),
             TimeWindow::OneDay => Duration::from_secs(86400),
             TimeWindow::OneWeek => Duration::from_secs(604800),
+            TimeWindow::Custom(duration) => *duration,
         }
     }
 
@@ -42,6 +48,35 @@ impl TimeWindow {
     pub fn seconds(&self) -> u64 {
         self.duration().as_secs()
     }
+
+    /// Window of any length, using the named variant when one matches
+    pub fn custom(duration: Duration) -> Self {
+        [
+            TimeWindow::OneMinute,
+            TimeWindow::FiveMinutes,
+            TimeWindow::FifteenMinutes,
+            TimeWindow::OneHour,
+            TimeWindow::OneDay,
+            TimeWindow::OneWeek,
+        ]
+        .into_iter()
+        .find(|window| window.duration() == duration)
+        .unwrap_or(TimeWindow::Custom(duration))
+    }
+}
+
+impl std::fmt::Display for TimeWindow {
+    /// Short form such as `5m`, `1h` or `90s`, as used in dashboard legends
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seconds = self.seconds();
+        match seconds {
+            0 => write!(f, "{}ms", self.duration().as_millis()),
+            s if s % 86_400 == 0 => write!(f, "{}d", s / 86_400),
+            s if s % 3_600 == 0 => write!(f, "{}h", s / 3_600),
+            s if s % 60 == 0 => write!(f, "{}m", s / 60),
+            s => write!(f, "{}s", s),
+        }
+    }
 }
 
 /// Statistical snapshot of metrics
@@ -79,6 +114,9 @@ pub struct StatisticalSnapshot {
 
 impl StatisticalSnapshot {
     /// Calculate statistical snapshot from metrics
+    ///
+    /// Covers metrics with `now - window < timestamp`, the same window as
+    /// [`from_metrics_at`](Self::from_metrics_at) ending now.
     pub fn from_metrics(
         metrics: &[Metric],
         metric_name: &str,
@@ -91,12 +129,48 @@ impl StatisticalSnapshot {
 
         let cutoff_time = now.saturating_sub(time_window.seconds());
 
-        let mut values: Vec<f64> = metrics
-            .iter()
-            .filter(|m| m.name == metric_name && m.timestamp >= cutoff_time)
-            .map(|m| m.value)
-            .collect();
+        Self::from_values(
+            metrics
+                .iter()
+                .filter(|m| m.name == metric_name && m.timestamp > cutoff_time)
+                .map(|m| m.value)
+                .collect(),
+            metric_name,
+            time_window,
+            now,
+        )
+    }
 
+    /// Calculate a snapshot of the window ending at `end`, a Unix timestamp
+    /// in seconds
+    ///
+    /// Covers metrics with `end - window < timestamp <= end`, so
+    /// consecutive windows do not share samples.
+    pub fn from_metrics_at(
+        metrics: &[Metric],
+        metric_name: &str,
+        time_window: TimeWindow,
+        end: u64,
+    ) -> Option<Self> {
+        let start = end.saturating_sub(time_window.seconds());
+        Self::from_values(
+            metrics
+                .iter()
+                .filter(|m| m.name == metric_name && m.timestamp > start && m.timestamp <= end)
+                .map(|m| m.value)
+                .collect(),
+            metric_name,
+            time_window,
+            end,
+        )
+    }
+
+    pub(crate) fn from_values(
+        mut values: Vec<f64>,
+        metric_name: &str,
+        time_window: TimeWindow,
+        timestamp: u64,
+    ) -> Option<Self> {
         if values.is_empty() {
             return None;
         }
@@ -124,13 +198,13 @@ impl StatisticalSnapshot {
             p90: percentile(&values, 90.0),
             p95: percentile(&values, 95.0),
             p99: percentile(&values, 99.0),
-            timestamp: now,
+            timestamp,
         })
     }
 
     /// Calculate rate per second
     pub fn rate_per_second(&self) -> f64 {
-        let window_seconds = self.time_window.seconds() as f64;
+        let window_seconds = self.time_window.duration().as_secs_f64();
         if window_seconds > 0.0 {
             self.count as f64 / window_seconds
         } else {
@@ -267,6 +341,100 @@ impl MetricsAggregator {
         result
     }
 
+    /// Statistics of one metric over several windows at once, such as 1m,
+    /// 5m and 1h, in the order given
+    ///
+    /// Windows without samples are left out.
+    pub fn snapshot_windows(
+        &self,
+        metrics: &[Metric],
+        metric_name: &str,
+        windows: &[TimeWindow],
+    ) -> Vec<StatisticalSnapshot> {
+        let named: Vec<Metric> = metrics
+            .iter()
+            .filter(|m| m.name == metric_name)
+            .cloned()
+            .collect();
+        windows
+            .iter()
+            .filter_map(|window| StatisticalSnapshot::from_metrics(&named, metric_name, *window))
+            .collect()
+    }
+
+    /// [`aggregate`](Self::aggregate) over each of several windows, in the
+    /// order given
+    pub fn aggregate_windows(
+        &self,
+        metrics: &[Metric],
+        windows: &[TimeWindow],
+    ) -> Vec<AggregatedMetrics> {
+        windows
+            .iter()
+            .map(|window| Self::with_time_window(*window).aggregate(metrics))
+            .collect()
+    }
+
+    /// Statistics of one metric over the aggregator's window, slid back from
+    /// now by `step` for as long as older samples remain
+    ///
+    /// Returns one snapshot per step that has samples, oldest first, each
+    /// stamped with the end of its window. A step shorter than the window
+    /// gives overlapping windows, e.g. a 5-minute p95 every minute. Windows
+    /// cover the same samples as
+    /// [`StatisticalSnapshot::from_metrics_at`], and at most
+    /// [`MAX_SLIDING_WINDOWS`] steps are taken.
+    pub fn sliding(
+        &self,
+        metrics: &[Metric],
+        metric_name: &str,
+        step: Duration,
+    ) -> Vec<StatisticalSnapshot> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.sliding_until(metrics, metric_name, step, now)
+    }
+
+    fn sliding_until(
+        &self,
+        metrics: &[Metric],
+        metric_name: &str,
+        step: Duration,
+        now: u64,
+    ) -> Vec<StatisticalSnapshot> {
+        let mut named: Vec<&Metric> = metrics.iter().filter(|m| m.name == metric_name).collect();
+        named.sort_by_key(|m| m.timestamp);
+        let Some(oldest) = named.first().map(|m| m.timestamp) else {
+            return Vec::new();
+        };
+
+        let step = step.as_secs().max(1);
+        let window = self.time_window.seconds();
+        let mut snapshots = Vec::new();
+        let mut end = now;
+        for _ in 0..MAX_SLIDING_WINDOWS {
+            let start = end.saturating_sub(window);
+            let from = named.partition_point(|m| m.timestamp <= start);
+            let to = named.partition_point(|m| m.timestamp <= end);
+            if let Some(snapshot) = StatisticalSnapshot::from_values(
+                named[from..to].iter().map(|m| m.value).collect(),
+                metric_name,
+                self.time_window,
+                end,
+            ) {
+                snapshots.push(snapshot);
+            }
+            if start < oldest || end < step {
+                break;
+            }
+            end -= step;
+        }
+        snapshots.reverse();
+        snapshots
+    }
+
     /// Extract label combinations from metrics
     fn extract_label_combinations(metrics: &[Metric]) -> HashMap<String, Vec<String>> {
         let mut combinations: HashMap<String, std::collections::HashSet<String>> = HashMap::new();
@@ -326,7 +494,7 @@ impl Default for MetricsAggregator {
     }
 }
 
-fn percentile(sorted_data: &[f64], p: f64) -> f64 {
+pub(crate) fn percentile(sorted_data: &[f64], p: f64) -> f64 {
     if sorted_data.is_empty() {
         return 0.0;
     }
@@ -439,5 +607,84 @@ mod tests {
         assert_eq!(TimeWindow::OneMinute.seconds(), 60);
         assert_eq!(TimeWindow::FiveMinutes.seconds(), 300);
         assert_eq!(TimeWindow::OneHour.seconds(), 3600);
+
+        assert_eq!(
+            TimeWindow::custom(Duration::from_secs(3600)),
+            TimeWindow::OneHour
+        );
+        let custom = TimeWindow::custom(Duration::from_secs(90));
+        assert_eq!(custom, TimeWindow::Custom(Duration::from_secs(90)));
+        assert_eq!(custom.to_string(), "90s");
+        assert_eq!(TimeWindow::FifteenMinutes.to_string(), "15m");
+        assert_eq!(TimeWindow::OneWeek.to_string(), "7d");
+    }
+
+    #[test]
+    fn test_snapshot_windows() {
+        // One sample every 2 seconds over the last 200 seconds
+        let metrics = create_test_metrics();
+        let aggregator = MetricsAggregator::new();
+        let snapshots = aggregator.snapshot_windows(
+            &metrics,
+            "tx_duration",
+            &[
+                TimeWindow::custom(Duration::from_secs(19)),
+                TimeWindow::OneMinute,
+                TimeWindow::OneHour,
+            ],
+        );
+
+        let counts: Vec<usize> = snapshots.iter().map(|s| s.count).collect();
+        assert_eq!(counts, vec![10, 30, 100]);
+        assert_eq!(snapshots[1].time_window, TimeWindow::OneMinute);
+
+        let aggregated =
+            aggregator.aggregate_windows(&metrics, &[TimeWindow::OneMinute, TimeWindow::OneHour]);
+        assert_eq!(aggregated[0].overall["tx_duration"].count, 30);
+        assert_eq!(aggregated[1].time_window, TimeWindow::OneHour);
+    }
+
+    #[test]
+    fn test_sliding_windows() {
+        let now = 1_000_000;
+        let metrics: Vec<Metric> = (0..30)
+            .map(|i| {
+                let mut metric = Metric::new(MetricType::TransactionLatency, "latency", i as f64);
+                metric.timestamp = now - 29 + i;
+                metric
+            })
+            .collect();
+
+        let aggregator =
+            MetricsAggregator::with_time_window(TimeWindow::custom(Duration::from_secs(10)));
+        let snapshots = aggregator.sliding_until(&metrics, "latency", Duration::from_secs(5), now);
+
+        // Windows end every 5 seconds until one reaches past the oldest sample
+        let ends: Vec<u64> = snapshots.iter().map(|s| now - s.timestamp).collect();
+        assert_eq!(ends, vec![20, 15, 10, 5, 0]);
+        assert!(snapshots.iter().all(|s| s.count == 10));
+        assert_eq!(snapshots[0].max, 9.0);
+        assert_eq!(snapshots.last().unwrap().max, 29.0);
+        assert_eq!(snapshots.last().unwrap().min, 20.0);
+
+        // The newest window covers the same samples as a snapshot ending now
+        let at = StatisticalSnapshot::from_metrics_at(
+            &metrics,
+            "latency",
+            TimeWindow::custom(Duration::from_secs(10)),
+            now,
+        )
+        .unwrap();
+        assert_eq!(snapshots.last().unwrap().count, at.count);
+
+        // A single very old sample does not make the walk unbounded
+        let mut ancient = Metric::new(MetricType::TransactionLatency, "latency", 1.0);
+        ancient.timestamp = 1;
+        let mut with_ancient = metrics.clone();
+        with_ancient.push(ancient);
+        let snapshots =
+            aggregator.sliding_until(&with_ancient, "latency", Duration::from_secs(1), now);
+        assert!(snapshots.len() <= MAX_SLIDING_WINDOWS);
+        assert_eq!(snapshots.last().unwrap().timestamp, now);
     }
 }
@@ -0,0 +1,850 @@
+//! Threshold alert rules
+//!
+//! [`AlertEngine`] evaluates [`AlertRule`]s over the metrics recorded in a
+//! [`MetricsCollector`] and the running [`ErrorStatistics`], and notifies
+//! callbacks, channel subscribers and webhooks when a rule starts or stops
+//! breaching its threshold. Events are emitted on transitions only, so a rule
+//! that keeps breaching fires once and resolves once. Events detected outside
+//! the rules, such as unexpected wallet activity, can be sent through the same
+//! channels with [`AlertEngine::raise`], and anomalies found by an
+//! [`AnomalyDetector`](crate::AnomalyDetector) with
+//! [`AlertEngine::raise_anomaly`].
+//!
+//! ```rust,no_run
+//! use apex_sdk_core::metrics::MetricsCollector;
+//! use apex_sdk_metrics::alerting::{AlertEngine, AlertMetric, AlertRule, Statistic, WebhookFormat};
+//! use apex_sdk_metrics::{ErrorSeverity, ErrorStatistics, TimeWindow};
+//! use std::sync::{Arc, Mutex};
+//! use std::time::Duration;
+//!
+//! # async fn example() {
+//! let engine = AlertEngine::new()
+//!     .with_rule(
+//!         AlertRule::new("high_error_rate", AlertMetric::transaction_error_rate())
+//!             .above(5.0)
+//!             .over(TimeWindow::FiveMinutes)
+//!             .with_severity(ErrorSeverity::Critical),
+//!     )
+//!     .with_rule(
+//!         AlertRule::new(
+//!             "slow_rpc",
+//!             AlertMetric::statistic("provider_response_time_seconds", Statistic::P99),
+//!         )
+//!         .above(2.0),
+//!     )
+//!     .with_webhook("https://hooks.slack.com/services/T000/B000/XXX", WebhookFormat::Slack);
+//!
+//! let mut alerts = engine.subscribe();
+//! let collector = MetricsCollector::new();
+//! let errors = Arc::new(Mutex::new(ErrorStatistics::new()));
+//! Arc::new(engine).spawn(collector, errors, Duration::from_secs(30));
+//!
+//! while let Some(event) = alerts.recv().await {
+//!     println!("{}", event.summary());
+//! }
+//! # }
+//! ```
+
+use crate::aggregation::{StatisticalSnapshot, TimeWindow};
+use crate::anomaly::{Anomaly, AnomalyDirection};
+use crate::error_categorization::{ErrorCategory, ErrorSeverity, ErrorStatistics};
+use crate::unix_now;
+use apex_sdk_core::codec::Codec;
+use apex_sdk_core::metrics::{Metric, MetricType, MetricsCollector};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Statistic of a metric's samples within a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Statistic {
+    Count,
+    Sum,
+    Mean,
+    Min,
+    Max,
+    P50,
+    P90,
+    P95,
+    P99,
+    /// Samples per second
+    RatePerSecond,
+}
+
+impl Statistic {
+    pub(crate) fn of(self, snapshot: &StatisticalSnapshot) -> f64 {
+        match self {
+            Statistic::Count => snapshot.count as f64,
+            Statistic::Sum => snapshot.sum,
+            Statistic::Mean => snapshot.mean,
+            Statistic::Min => snapshot.min,
+            Statistic::Max => snapshot.max,
+            Statistic::P50 => snapshot.p50,
+            Statistic::P90 => snapshot.p90,
+            Statistic::P95 => snapshot.p95,
+            Statistic::P99 => snapshot.p99,
+            Statistic::RatePerSecond => snapshot.rate_per_second(),
+        }
+    }
+}
+
+/// Value an [`AlertRule`] compares against its threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AlertMetric {
+    /// Statistic of a named metric within the rule's window, restricted to
+    /// samples carrying all of `labels`
+    Statistic {
+        metric: String,
+        statistic: Statistic,
+        labels: HashMap<String, String>,
+    },
+    /// Percentage of failed transactions within the rule's window
+    TransactionErrorRate { chain: Option<String> },
+    /// Percentage of recorded errors in a category (all time)
+    ErrorCategoryRate(ErrorCategory),
+    /// Percentage of recorded errors that are critical (all time)
+    CriticalErrorRate,
+    /// Number of recorded errors (all time)
+    ErrorCount,
+}
+
+impl AlertMetric {
+    /// Statistic of a named metric
+    pub fn statistic(metric: impl Into<String>, statistic: Statistic) -> Self {
+        Self::Statistic {
+            metric: metric.into(),
+            statistic,
+            labels: HashMap::new(),
+        }
+    }
+
+    /// Percentage of failed transactions across chains
+    pub fn transaction_error_rate() -> Self {
+        Self::TransactionErrorRate { chain: None }
+    }
+
+    /// Restrict a [`AlertMetric::Statistic`] to samples with a label value,
+    /// or a [`AlertMetric::TransactionErrorRate`] to a chain (`chain` label)
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        match &mut self {
+            Self::Statistic { labels, .. } => {
+                labels.insert(key.into(), value.into());
+            }
+            Self::TransactionErrorRate { chain } if key.into() == "chain" => {
+                *chain = Some(value.into());
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Current value, or `None` without samples in the window
+    fn value(
+        &self,
+        window: TimeWindow,
+        metrics: &[Metric],
+        errors: &ErrorStatistics,
+    ) -> Option<f64> {
+        match self {
+            Self::Statistic {
+                metric,
+                statistic,
+                labels,
+            } => {
+                let samples: Vec<Metric> = metrics
+                    .iter()
+                    .filter(|m| {
+                        &m.name == metric && labels.iter().all(|(k, v)| m.labels.get(k) == Some(v))
+                    })
+                    .cloned()
+                    .collect();
+                StatisticalSnapshot::from_metrics(&samples, metric, window)
+                    .map(|snapshot| statistic.of(&snapshot))
+            }
+            Self::TransactionErrorRate { chain } => {
+                let cutoff = unix_now().saturating_sub(window.seconds());
+                let (failed, total) = metrics
+                    .iter()
+                    .filter(|m| {
+                        m.metric_type == MetricType::TransactionSuccessRate
+                            && m.timestamp >= cutoff
+                            && chain
+                                .as_ref()
+                                .is_none_or(|chain| m.labels.get("chain") == Some(chain))
+                    })
+                    .fold((0.0, 0.0), |(failed, total), m| {
+                        match m.labels.get("status").map(String::as_str) {
+                            Some("success") => (failed, total + m.value),
+                            Some("failure") => (failed + m.value, total + m.value),
+                            _ => (failed, total),
+                        }
+                    });
+                (total > 0.0).then(|| failed / total * 100.0)
+            }
+            Self::ErrorCategoryRate(category) => {
+                (errors.total_errors > 0).then(|| errors.category_rate(*category))
+            }
+            Self::CriticalErrorRate => {
+                (errors.total_errors > 0).then(|| errors.critical_error_rate())
+            }
+            Self::ErrorCount => Some(errors.total_errors as f64),
+        }
+    }
+}
+
+/// Direction of a threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparison {
+    /// Breached when the value is greater than the threshold
+    Above,
+    /// Breached when the value is less than the threshold
+    Below,
+}
+
+/// Threshold rule over a metric
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Rule name, unique within an engine
+    pub name: String,
+    /// Evaluated value
+    pub metric: AlertMetric,
+    /// Threshold direction
+    pub comparison: Comparison,
+    /// Threshold
+    pub threshold: f64,
+    /// Window of samples the value is computed over
+    pub window: TimeWindow,
+    /// Severity reported with events
+    pub severity: ErrorSeverity,
+    /// Human-readable explanation included in notifications
+    pub description: Option<String>,
+}
+
+impl AlertRule {
+    /// Create a rule breached when the value is above zero over five minutes
+    pub fn new(name: impl Into<String>, metric: AlertMetric) -> Self {
+        Self {
+            name: name.into(),
+            metric,
+            comparison: Comparison::Above,
+            threshold: 0.0,
+            window: TimeWindow::FiveMinutes,
+            severity: ErrorSeverity::High,
+            description: None,
+        }
+    }
+
+    /// Breach when the value exceeds `threshold`
+    pub fn above(mut self, threshold: f64) -> Self {
+        self.comparison = Comparison::Above;
+        self.threshold = threshold;
+        self
+    }
+
+    /// Breach when the value drops below `threshold`
+    pub fn below(mut self, threshold: f64) -> Self {
+        self.comparison = Comparison::Below;
+        self.threshold = threshold;
+        self
+    }
+
+    /// Compute the value over `window`
+    pub fn over(mut self, window: TimeWindow) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Set the severity reported with events
+    pub fn with_severity(mut self, severity: ErrorSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Set the explanation included in notifications
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    fn breached(&self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Above => value > self.threshold,
+            Comparison::Below => value < self.threshold,
+        }
+    }
+}
+
+/// Whether an alert started or stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+/// Notification of a rule state change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertEvent {
+    /// Rule name
+    pub rule: String,
+    /// New state
+    pub state: AlertState,
+    /// Value at evaluation; `None` if the rule resolved for lack of samples
+    /// or the event was [raised](AlertEngine::raise) directly
+    pub value: Option<f64>,
+    /// Rule threshold
+    pub threshold: f64,
+    /// Rule threshold direction
+    pub comparison: Comparison,
+    /// Rule severity
+    pub severity: ErrorSeverity,
+    /// Rule description
+    pub description: Option<String>,
+    /// Unix timestamp of the evaluation
+    pub timestamp: u64,
+}
+
+impl AlertEvent {
+    /// One-line description, e.g. for chat notifications
+    pub fn summary(&self) -> String {
+        let state = match self.state {
+            AlertState::Firing => "FIRING",
+            AlertState::Resolved => "RESOLVED",
+        };
+        let comparison = match self.comparison {
+            Comparison::Above => ">",
+            Comparison::Below => "<",
+        };
+        let mut summary = match (self.state, self.value) {
+            (_, Some(value)) => format!(
+                "[{}] {} ({:?}): {:.4} (threshold {} {})",
+                state, self.rule, self.severity, value, comparison, self.threshold
+            ),
+            (AlertState::Resolved, None) => format!(
+                "[{}] {} ({:?}): no data (threshold {} {})",
+                state, self.rule, self.severity, comparison, self.threshold
+            ),
+            // Raised directly, without a threshold
+            (AlertState::Firing, None) => {
+                format!("[{}] {} ({:?})", state, self.rule, self.severity)
+            }
+        };
+        if let Some(description) = &self.description {
+            summary.push_str(" - ");
+            summary.push_str(description);
+        }
+        summary
+    }
+}
+
+impl From<&Anomaly> for AlertEvent {
+    /// Firing event named `anomaly:<metric>`, with the observed value and
+    /// the expected value as threshold
+    fn from(anomaly: &Anomaly) -> Self {
+        Self {
+            rule: format!("anomaly:{}", anomaly.metric),
+            state: AlertState::Firing,
+            value: Some(anomaly.observed),
+            threshold: anomaly.expected,
+            comparison: match anomaly.direction {
+                AnomalyDirection::Spike => Comparison::Above,
+                AnomalyDirection::Drop => Comparison::Below,
+            },
+            severity: anomaly.severity,
+            description: Some(anomaly.summary()),
+            timestamp: anomaly.timestamp,
+        }
+    }
+}
+
+/// Body of webhook notifications
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookFormat {
+    /// The [`AlertEvent`] as JSON
+    Json,
+    /// Slack incoming-webhook message: `{"text": "<summary>"}`
+    Slack,
+    /// The [`AlertEvent`] in the given codec, sent with its content type
+    Encoded(Codec),
+}
+
+type AlertCallback = Arc<dyn Fn(&AlertEvent) + Send + Sync>;
+
+/// Evaluates alert rules and dispatches state changes
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    firing: Mutex<HashSet<String>>,
+    callbacks: Vec<AlertCallback>,
+    subscribers: Mutex<Vec<mpsc::UnboundedSender<AlertEvent>>>,
+    webhooks: Vec<(String, WebhookFormat)>,
+    client: reqwest::Client,
+}
+
+impl AlertEngine {
+    /// Create an engine without rules
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            firing: Mutex::new(HashSet::new()),
+            callbacks: Vec::new(),
+            subscribers: Mutex::new(Vec::new()),
+            webhooks: Vec::new(),
+            client: reqwest::Client::builder()
+                .timeout(WEBHOOK_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Add a rule
+    pub fn with_rule(mut self, rule: AlertRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Call `callback` for every event
+    pub fn on_alert(mut self, callback: impl Fn(&AlertEvent) + Send + Sync + 'static) -> Self {
+        self.callbacks.push(Arc::new(callback));
+        self
+    }
+
+    /// POST every event to `url`
+    pub fn with_webhook(mut self, url: impl Into<String>, format: WebhookFormat) -> Self {
+        self.webhooks.push((url.into(), format));
+        self
+    }
+
+    /// Receive every event emitted from now on
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<AlertEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.lock_subscribers().push(tx);
+        rx
+    }
+
+    /// Registered rules
+    pub fn rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
+    /// Names of the rules currently firing
+    pub fn firing(&self) -> Vec<String> {
+        let mut firing: Vec<String> = self.lock_firing().iter().cloned().collect();
+        firing.sort();
+        firing
+    }
+
+    /// Dispatch a firing event that no rule produced
+    ///
+    /// For detections made elsewhere, such as a wallet signing something the
+    /// SDK did not. The event has no value or threshold, and is not tracked
+    /// by [`firing`](Self::firing) since nothing resolves it.
+    pub async fn raise(
+        &self,
+        rule: impl Into<String>,
+        severity: ErrorSeverity,
+        description: impl Into<String>,
+    ) -> AlertEvent {
+        let event = AlertEvent {
+            rule: rule.into(),
+            state: AlertState::Firing,
+            value: None,
+            threshold: 0.0,
+            comparison: Comparison::Above,
+            severity,
+            description: Some(description.into()),
+            timestamp: unix_now(),
+        };
+        self.dispatch(&event).await;
+        event
+    }
+
+    /// Dispatch a firing event for an anomaly
+    ///
+    /// Like [`raise`](Self::raise), the event is not tracked by
+    /// [`firing`](Self::firing).
+    pub async fn raise_anomaly(&self, anomaly: &Anomaly) -> AlertEvent {
+        let event = AlertEvent::from(anomaly);
+        self.dispatch(&event).await;
+        event
+    }
+
+    /// Evaluate every rule and dispatch state changes
+    ///
+    /// A rule without samples in its window counts as not breached. Returns
+    /// the emitted events.
+    pub async fn evaluate(&self, metrics: &[Metric], errors: &ErrorStatistics) -> Vec<AlertEvent> {
+        let timestamp = unix_now();
+        let mut events = Vec::new();
+
+        {
+            let mut firing = self.lock_firing();
+            for rule in &self.rules {
+                let value = rule.metric.value(rule.window, metrics, errors);
+                let breached = value.is_some_and(|value| rule.breached(value));
+
+                let state = match (breached, firing.contains(&rule.name)) {
+                    (true, false) => {
+                        firing.insert(rule.name.clone());
+                        AlertState::Firing
+                    }
+                    (false, true) => {
+                        firing.remove(&rule.name);
+                        AlertState::Resolved
+                    }
+                    _ => continue,
+                };
+
+                events.push(AlertEvent {
+                    rule: rule.name.clone(),
+                    state,
+                    value,
+                    threshold: rule.threshold,
+                    comparison: rule.comparison,
+                    severity: rule.severity,
+                    description: rule.description.clone(),
+                    timestamp,
+                });
+            }
+        }
+
+        for event in &events {
+            self.dispatch(event).await;
+        }
+        events
+    }
+
+    /// Evaluate periodically in the background
+    ///
+    /// `errors` is read at each evaluation, so keep recording into it.
+    pub fn spawn(
+        self: Arc<Self>,
+        collector: MetricsCollector,
+        errors: Arc<Mutex<ErrorStatistics>>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let errors = errors
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .clone();
+                self.evaluate(&collector.get_metrics(), &errors).await;
+            }
+        })
+    }
+
+    async fn dispatch(&self, event: &AlertEvent) {
+        for callback in &self.callbacks {
+            callback(event);
+        }
+        self.lock_subscribers()
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+
+        for (url, format) in &self.webhooks {
+            let request = match format {
+                WebhookFormat::Json => self.client.post(url).json(event),
+                WebhookFormat::Slack => self
+                    .client
+                    .post(url)
+                    .json(&serde_json::json!({ "text": event.summary() })),
+                WebhookFormat::Encoded(codec) => match codec.encode(event) {
+                    Ok(body) => self
+                        .client
+                        .post(url)
+                        .header(reqwest::header::CONTENT_TYPE, codec.content_type())
+                        .body(body),
+                    Err(e) => {
+                        warn!(
+                            operation = "alert_webhook",
+                            endpoint = %url,
+                            error = %e,
+                            "Failed to encode alert webhook"
+                        );
+                        continue;
+                    }
+                },
+            };
+            match request.send().await {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => warn!(
+                    operation = "alert_webhook",
+                    endpoint = %url,
+                    status = %response.status(),
+                    "Alert webhook rejected notification"
+                ),
+                Err(e) => warn!(
+                    operation = "alert_webhook",
+                    endpoint = %url,
+                    error = %e,
+                    "Failed to deliver alert webhook"
+                ),
+            }
+        }
+    }
+
+    fn lock_firing(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.firing
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn lock_subscribers(
+        &self,
+    ) -> std::sync::MutexGuard<'_, Vec<mpsc::UnboundedSender<AlertEvent>>> {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for AlertEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_categorization::categorize_error;
+    use axum::{body::Bytes, extract::State, routing::post, Router};
+    use tokio::net::TcpListener;
+
+    fn transactions(success: f64, failure: f64) -> Vec<Metric> {
+        [("success", success), ("failure", failure)]
+            .into_iter()
+            .map(|(status, count)| {
+                Metric::new(MetricType::TransactionSuccessRate, "transactions", count)
+                    .with_label("chain", "westend")
+                    .with_label("status", status)
+            })
+            .collect()
+    }
+
+    fn latencies(values: &[f64]) -> Vec<Metric> {
+        values
+            .iter()
+            .map(|&v| {
+                Metric::new(
+                    MetricType::ProviderResponseTime,
+                    "provider_response_time_seconds",
+                    v,
+                )
+                .with_label("chain", "westend")
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_error_rate_fires_and_resolves_once() {
+        let engine = AlertEngine::new().with_rule(
+            AlertRule::new("error_rate", AlertMetric::transaction_error_rate())
+                .above(5.0)
+                .with_severity(ErrorSeverity::Critical),
+        );
+        let errors = ErrorStatistics::new();
+
+        let events = engine.evaluate(&transactions(90.0, 10.0), &errors).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].state, AlertState::Firing);
+        assert_eq!(events[0].value, Some(10.0));
+        assert_eq!(engine.firing(), vec!["error_rate".to_string()]);
+
+        assert!(engine
+            .evaluate(&transactions(80.0, 20.0), &errors)
+            .await
+            .is_empty());
+
+        let events = engine.evaluate(&transactions(99.0, 1.0), &errors).await;
+        assert_eq!(events[0].state, AlertState::Resolved);
+        assert!(engine.firing().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_no_samples_do_not_fire() {
+        let engine = AlertEngine::new()
+            .with_rule(
+                AlertRule::new("error_rate", AlertMetric::transaction_error_rate()).above(5.0),
+            )
+            .with_rule(
+                AlertRule::new(
+                    "throughput",
+                    AlertMetric::statistic("transactions", Statistic::Count),
+                )
+                .below(1.0),
+            );
+
+        let events = engine.evaluate(&[], &ErrorStatistics::new()).await;
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_latency_statistic_with_labels() {
+        let rule = AlertRule::new(
+            "slow_rpc",
+            AlertMetric::statistic("provider_response_time_seconds", Statistic::P99)
+                .with_label("chain", "westend"),
+        )
+        .above(2.0);
+        let engine = AlertEngine::new().with_rule(rule);
+        let errors = ErrorStatistics::new();
+
+        let mut metrics = latencies(&[0.1; 99]);
+        metrics.push(
+            Metric::new(
+                MetricType::ProviderResponseTime,
+                "provider_response_time_seconds",
+                9.0,
+            )
+            .with_label("chain", "polkadot"),
+        );
+        assert!(engine.evaluate(&metrics, &errors).await.is_empty());
+
+        metrics.extend(latencies(&[3.0, 3.5]));
+        let events = engine.evaluate(&metrics, &errors).await;
+        assert_eq!(events.len(), 1);
+        assert!(events[0].value.unwrap() > 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_error_statistics_rules() {
+        let engine = AlertEngine::new().with_rule(
+            AlertRule::new(
+                "auth_errors",
+                AlertMetric::ErrorCategoryRate(ErrorCategory::Authentication),
+            )
+            .above(50.0),
+        );
+
+        let mut errors = ErrorStatistics::new();
+        errors.record(&categorize_error("connection timeout", None));
+        assert!(engine.evaluate(&[], &errors).await.is_empty());
+
+        errors.record(&categorize_error("unauthorized access", None));
+        errors.record(&categorize_error("unauthorized access", None));
+        let events = engine.evaluate(&[], &errors).await;
+        assert_eq!(events[0].rule, "auth_errors");
+    }
+
+    #[tokio::test]
+    async fn test_callbacks_and_subscribers() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let engine = AlertEngine::new()
+            .with_rule(AlertRule::new("errors", AlertMetric::ErrorCount).above(0.0))
+            .on_alert({
+                let received = received.clone();
+                move |event| received.lock().unwrap().push(event.rule.clone())
+            });
+        let mut subscriber = engine.subscribe();
+
+        let mut errors = ErrorStatistics::new();
+        errors.record(&categorize_error("connection timeout", None));
+        engine.evaluate(&[], &errors).await;
+
+        assert_eq!(*received.lock().unwrap(), vec!["errors".to_string()]);
+        assert_eq!(subscriber.recv().await.unwrap().state, AlertState::Firing);
+
+        let raised = engine
+            .raise("intrusion", ErrorSeverity::Critical, "Unexpected transfer")
+            .await;
+        assert_eq!(subscriber.recv().await.unwrap(), raised);
+        assert_eq!(
+            raised.summary(),
+            "[FIRING] intrusion (Critical) - Unexpected transfer"
+        );
+
+        let anomaly = crate::anomaly::Anomaly {
+            metric: "tx_latency_ms".to_string(),
+            statistic: Statistic::P95,
+            time_window: TimeWindow::FiveMinutes,
+            timestamp: 1_700_000_000,
+            expected: 200.0,
+            observed: 50.0,
+            std_dev: 20.0,
+            z_score: -7.5,
+            direction: AnomalyDirection::Drop,
+            severity: ErrorSeverity::High,
+        };
+        let raised = engine.raise_anomaly(&anomaly).await;
+        assert_eq!(subscriber.recv().await.unwrap(), raised);
+        assert_eq!(raised.rule, "anomaly:tx_latency_ms");
+        assert_eq!(raised.comparison, Comparison::Below);
+        assert_eq!(raised.value, Some(50.0));
+        assert_eq!(engine.firing(), vec!["errors".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_webhooks() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<(String, Bytes)>();
+        let app = Router::new()
+            .route(
+                "/json",
+                post(
+                    |State(tx): State<mpsc::UnboundedSender<_>>, body: Bytes| async move {
+                        let _ = tx.send(("json".to_string(), body));
+                    },
+                ),
+            )
+            .route(
+                "/cbor",
+                post(
+                    |State(tx): State<mpsc::UnboundedSender<_>>, body: Bytes| async move {
+                        let _ = tx.send(("cbor".to_string(), body));
+                    },
+                ),
+            )
+            .route(
+                "/slack",
+                post(
+                    |State(tx): State<mpsc::UnboundedSender<_>>, body: Bytes| async move {
+                        let _ = tx.send(("slack".to_string(), body));
+                    },
+                ),
+            )
+            .with_state(tx);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let engine = AlertEngine::new()
+            .with_rule(
+                AlertRule::new("error_rate", AlertMetric::transaction_error_rate())
+                    .above(5.0)
+                    .with_description("Transactions are failing"),
+            )
+            .with_webhook(format!("http://{}/json", addr), WebhookFormat::Json)
+            .with_webhook(format!("http://{}/slack", addr), WebhookFormat::Slack)
+            .with_webhook(
+                format!("http://{}/cbor", addr),
+                WebhookFormat::Encoded(Codec::Cbor),
+            );
+        engine
+            .evaluate(&transactions(50.0, 50.0), &ErrorStatistics::new())
+            .await;
+
+        let (route, body) = rx.recv().await.unwrap();
+        assert_eq!(route, "json");
+        let event: AlertEvent = serde_json::from_slice(&body).unwrap();
+        assert_eq!(event.value, Some(50.0));
+
+        let (route, body) = rx.recv().await.unwrap();
+        assert_eq!(route, "slack");
+        let message: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let text = message["text"].as_str().unwrap();
+        assert!(text.starts_with("[FIRING] error_rate"));
+        assert!(text.ends_with("Transactions are failing"));
+
+        let (route, body) = rx.recv().await.unwrap();
+        assert_eq!(route, "cbor");
+        let decoded: AlertEvent = Codec::Cbor.decode(&body).unwrap();
+        assert_eq!(decoded, event);
+    }
+}
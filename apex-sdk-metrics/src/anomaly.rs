@@ -0,0 +1,447 @@
+//! Anomaly detection over aggregated metrics
+//!
+//! [`StatisticalSnapshot::is_trending_up`] only compares a snapshot's p95
+//! with its own mean. [`AnomalyDetector`] instead compares a statistic of
+//! each snapshot in a series, such as one from [`MetricsAggregator::sliding`]
+//! or successive [`StreamingAggregator`] snapshots, with what earlier
+//! snapshots predict.
+//!
+//! The prediction is an exponentially weighted moving average (EWMA) with an
+//! exponentially weighted variance. A value whose z-score against them
+//! reaches the threshold (3 by default) is an [`Anomaly`]. With
+//! [`Baseline::Seasonal`] each position in a cycle, such as each hour of the
+//! day, keeps its own average, so a daily peak is compared with earlier
+//! peaks rather than with the quiet hours before it. Every value, anomalous
+//! or not, is then folded into the average, so a lasting change of level
+//! stops being reported once the baseline has adapted to it.
+//!
+//! Anomalies carry the metric, window, expected and observed values, and can
+//! be sent to alert subscribers and webhooks with
+//! [`AlertEngine::raise_anomaly`](crate::AlertEngine::raise_anomaly).
+//!
+//! ```rust
+//! use apex_sdk_metrics::alerting::Statistic;
+//! use apex_sdk_metrics::{AnomalyDetector, StreamingAggregator};
+//! use apex_sdk_core::metrics::{Metric, MetricType};
+//!
+//! let aggregator = StreamingAggregator::new();
+//! let detector = AnomalyDetector::new(Statistic::P95).with_threshold(4.0);
+//!
+//! aggregator.record(&Metric::new(MetricType::TransactionLatency, "tx_latency_ms", 120.0));
+//! // Called periodically
+//! if let Some(snapshot) = aggregator.snapshot("tx_latency_ms") {
+//!     if let Some(anomaly) = detector.observe(&snapshot) {
+//!         println!("{}", anomaly.summary());
+//!     }
+//! }
+//! ```
+//!
+//! [`MetricsAggregator::sliding`]: crate::MetricsAggregator::sliding
+//! [`StreamingAggregator`]: crate::StreamingAggregator
+
+use crate::aggregation::{StatisticalSnapshot, TimeWindow};
+use crate::alerting::Statistic;
+use crate::error_categorization::ErrorSeverity;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Default weight of the newest value in the moving average
+pub const DEFAULT_SMOOTHING: f64 = 0.3;
+
+/// Default z-score at which a value is anomalous
+pub const DEFAULT_Z_THRESHOLD: f64 = 3.0;
+
+/// Default number of values a baseline needs before it can flag anomalies
+pub const DEFAULT_WARMUP: usize = 8;
+
+/// Default smallest standard deviation assumed, relative to the expected
+/// value; the accuracy of sketched percentiles
+pub const DEFAULT_MIN_RELATIVE_STD_DEV: f64 = crate::streaming::DEFAULT_RELATIVE_ACCURACY;
+
+/// How the expected value of the next snapshot is predicted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Baseline {
+    /// One moving average over every snapshot
+    Ewma,
+    /// One moving average per position in a cycle of `period` snapshots, e.g.
+    /// 24 for hourly snapshots of a metric with a daily pattern
+    Seasonal { period: usize },
+}
+
+/// Whether an anomalous value is above or below its expected value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnomalyDirection {
+    Spike,
+    Drop,
+}
+
+/// A snapshot statistic that deviates from its baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Anomaly {
+    /// Metric name
+    pub metric: String,
+    /// Statistic that deviated
+    pub statistic: Statistic,
+    /// Window of the snapshot
+    pub time_window: TimeWindow,
+    /// Timestamp of the snapshot
+    pub timestamp: u64,
+    /// Value predicted by the baseline
+    pub expected: f64,
+    /// Value of the snapshot
+    pub observed: f64,
+    /// Standard deviation of the baseline
+    pub std_dev: f64,
+    /// Deviation in standard deviations, negative for drops
+    pub z_score: f64,
+    /// Direction of the deviation
+    pub direction: AnomalyDirection,
+    /// `High` from twice the detector's threshold, `Medium` below
+    pub severity: ErrorSeverity,
+}
+
+impl Anomaly {
+    /// One-line description, e.g. for chat notifications
+    pub fn summary(&self) -> String {
+        let direction = match self.direction {
+            AnomalyDirection::Spike => "spike",
+            AnomalyDirection::Drop => "drop",
+        };
+        format!(
+            "{} {:?} over {}: observed {:.4}, expected {:.4} ± {:.4} (z = {:.1}, {})",
+            self.metric,
+            self.statistic,
+            self.time_window,
+            self.observed,
+            self.expected,
+            self.std_dev,
+            self.z_score,
+            direction
+        )
+    }
+}
+
+/// Exponentially weighted mean and variance
+#[derive(Debug, Clone, Copy, Default)]
+struct Ewma {
+    mean: f64,
+    variance: f64,
+    count: usize,
+}
+
+impl Ewma {
+    fn update(&mut self, value: f64, smoothing: f64) {
+        if self.count == 0 {
+            self.mean = value;
+        } else {
+            let diff = value - self.mean;
+            let increment = smoothing * diff;
+            self.mean += increment;
+            self.variance = (1.0 - smoothing) * (self.variance + diff * increment);
+        }
+        self.count += 1;
+    }
+}
+
+/// Baselines of one metric
+#[derive(Debug, Clone, Default)]
+struct Series {
+    phases: Vec<Ewma>,
+    seen: usize,
+}
+
+/// Flags snapshots whose statistic deviates from a moving baseline
+///
+/// [`detect`](Self::detect) scans a whole series at once.
+/// [`observe`](Self::observe) takes one snapshot at a time and keeps a
+/// baseline per metric name between calls.
+#[derive(Debug)]
+pub struct AnomalyDetector {
+    statistic: Statistic,
+    baseline: Baseline,
+    smoothing: f64,
+    threshold: f64,
+    warmup: usize,
+    min_relative_std_dev: f64,
+    series: Mutex<HashMap<String, Series>>,
+}
+
+impl AnomalyDetector {
+    /// Create a detector for one statistic of each snapshot, with an EWMA
+    /// baseline and the default smoothing, threshold and warmup
+    pub fn new(statistic: Statistic) -> Self {
+        Self {
+            statistic,
+            baseline: Baseline::Ewma,
+            smoothing: DEFAULT_SMOOTHING,
+            threshold: DEFAULT_Z_THRESHOLD,
+            warmup: DEFAULT_WARMUP,
+            min_relative_std_dev: DEFAULT_MIN_RELATIVE_STD_DEV,
+            series: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set how expected values are predicted
+    pub fn with_baseline(mut self, baseline: Baseline) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// Set the weight of the newest value in the moving average, between 0
+    /// and 1; higher values adapt faster
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        self.smoothing = smoothing.clamp(0.01, 1.0);
+        self
+    }
+
+    /// Set the z-score at which a value is anomalous
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Set how many values a baseline needs before it can flag anomalies
+    ///
+    /// With a seasonal baseline this counts values at the same position in
+    /// the cycle.
+    pub fn with_warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup.max(2);
+        self
+    }
+
+    /// Set the smallest standard deviation assumed, as a fraction of the
+    /// expected value
+    ///
+    /// A baseline that has barely moved would otherwise turn any change,
+    /// such as percentile rounding, into a huge z-score.
+    pub fn with_min_relative_std_dev(mut self, min_relative_std_dev: f64) -> Self {
+        self.min_relative_std_dev = min_relative_std_dev.max(0.0);
+        self
+    }
+
+    /// Anomalies in a series of snapshots, oldest first
+    ///
+    /// Baselines start empty and are kept per metric name, so a series may
+    /// mix metrics. State kept by [`observe`](Self::observe) is not used.
+    pub fn detect(&self, snapshots: &[StatisticalSnapshot]) -> Vec<Anomaly> {
+        let mut series = HashMap::new();
+        snapshots
+            .iter()
+            .filter_map(|snapshot| {
+                self.check(
+                    series.entry(snapshot.metric_name.clone()).or_default(),
+                    snapshot,
+                )
+            })
+            .collect()
+    }
+
+    /// Compare the next snapshot of a metric with its baseline, then add it
+    /// to the baseline
+    pub fn observe(&self, snapshot: &StatisticalSnapshot) -> Option<Anomaly> {
+        let mut series = self
+            .series
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.check(
+            series.entry(snapshot.metric_name.clone()).or_default(),
+            snapshot,
+        )
+    }
+
+    /// Forget the baselines built by [`observe`](Self::observe)
+    pub fn reset(&self) {
+        self.series
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+    }
+
+    fn check(&self, series: &mut Series, snapshot: &StatisticalSnapshot) -> Option<Anomaly> {
+        let observed = self.statistic.of(snapshot);
+        if !observed.is_finite() {
+            return None;
+        }
+
+        let period = match self.baseline {
+            Baseline::Ewma => 1,
+            Baseline::Seasonal { period } => period.max(1),
+        };
+        if series.phases.len() != period {
+            series.phases = vec![Ewma::default(); period];
+        }
+        let phase = &mut series.phases[series.seen % period];
+        series.seen += 1;
+
+        let expected = phase.mean;
+        let std_dev = phase.variance.sqrt();
+        let ready = phase.count >= self.warmup;
+        phase.update(observed, self.smoothing);
+        if !ready {
+            return None;
+        }
+
+        // A flat baseline has no spread of its own; a change is measured
+        // against the floor instead
+        let floor = self.min_relative_std_dev * expected.abs().max(1.0);
+        let scale = std_dev.max(floor).max(f64::EPSILON);
+        let z_score = (observed - expected) / scale;
+        if z_score.abs() < self.threshold {
+            return None;
+        }
+
+        Some(Anomaly {
+            metric: snapshot.metric_name.clone(),
+            statistic: self.statistic,
+            time_window: snapshot.time_window,
+            timestamp: snapshot.timestamp,
+            expected,
+            observed,
+            std_dev,
+            z_score,
+            direction: if z_score > 0.0 {
+                AnomalyDirection::Spike
+            } else {
+                AnomalyDirection::Drop
+            },
+            severity: if z_score.abs() >= 2.0 * self.threshold {
+                ErrorSeverity::High
+            } else {
+                ErrorSeverity::Medium
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: &[f64]) -> Vec<StatisticalSnapshot> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                StatisticalSnapshot::from_values(
+                    vec![value],
+                    "latency",
+                    TimeWindow::FiveMinutes,
+                    i as u64 * 60,
+                )
+                .unwrap()
+            })
+            .collect()
+    }
+
+    /// Small deterministic noise around `level`
+    fn noisy(level: f64, count: usize) -> Vec<f64> {
+        (0..count)
+            .map(|i| level + [-2.0, 1.0, 3.0, -1.0, 0.0, 2.0, -3.0][i % 7])
+            .collect()
+    }
+
+    #[test]
+    fn test_ewma_flags_spikes_and_drops() {
+        let detector = AnomalyDetector::new(Statistic::Mean);
+
+        let steady = noisy(100.0, 40);
+        assert!(detector.detect(&series(&steady)).is_empty());
+
+        let mut values = steady.clone();
+        values[30] = 160.0;
+        values[35] = 40.0;
+        let anomalies = detector.detect(&series(&values));
+        assert_eq!(anomalies.len(), 2);
+
+        let spike = &anomalies[0];
+        assert_eq!(spike.timestamp, 30 * 60);
+        assert_eq!(spike.direction, AnomalyDirection::Spike);
+        assert_eq!(spike.observed, 160.0);
+        assert!((spike.expected - 100.0).abs() < 5.0);
+        assert_eq!(spike.severity, ErrorSeverity::High);
+        assert!(spike
+            .summary()
+            .starts_with("latency Mean over 5m: observed 160"));
+        assert_eq!(anomalies[1].direction, AnomalyDirection::Drop);
+        assert!(anomalies[1].z_score < 0.0);
+    }
+
+    #[test]
+    fn test_warmup_and_level_shift() {
+        // Nothing is flagged before the warmup, however odd
+        let detector = AnomalyDetector::new(Statistic::Mean).with_warmup(5);
+        assert!(detector
+            .detect(&series(&[1.0, 500.0, 3.0, 900.0, 2.0]))
+            .is_empty());
+
+        // A lasting change is reported at first, then becomes the baseline
+        let mut values = noisy(100.0, 20);
+        values.extend(noisy(300.0, 20));
+        let anomalies = detector.detect(&series(&values));
+        assert!(!anomalies.is_empty());
+        assert!(anomalies.iter().all(|a| a.timestamp < 30 * 60));
+    }
+
+    #[test]
+    fn test_seasonal_baseline() {
+        // Every fourth snapshot is a regular peak
+        let values: Vec<f64> = noisy(100.0, 48)
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| if i % 4 == 3 { v + 200.0 } else { v })
+            .collect();
+
+        let ewma = AnomalyDetector::new(Statistic::Mean).with_warmup(4);
+        let seasonal = AnomalyDetector::new(Statistic::Mean)
+            .with_warmup(4)
+            .with_baseline(Baseline::Seasonal { period: 4 });
+        assert!(seasonal.detect(&series(&values)).is_empty());
+
+        // A peak at the wrong time is only anomalous against its season;
+        // a single average has learnt that peaks happen
+        let mut shifted = values.clone();
+        shifted[41] += 200.0;
+        let anomalies = seasonal.detect(&series(&shifted));
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].timestamp, 41 * 60);
+        assert!(ewma
+            .detect(&series(&shifted))
+            .iter()
+            .all(|anomaly| anomaly.timestamp != 41 * 60));
+    }
+
+    #[test]
+    fn test_flat_baseline_tolerates_small_changes() {
+        let detector = AnomalyDetector::new(Statistic::Mean);
+        let mut values = vec![21_000.0; 20];
+        values.push(21_001.0);
+        // Percentile rounding of a sketch
+        values.push(21_000.0 * 1.01);
+        assert!(detector.detect(&series(&values)).is_empty());
+
+        values.push(30_000.0);
+        let anomalies = detector.detect(&series(&values));
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].severity, ErrorSeverity::High);
+    }
+
+    #[test]
+    fn test_observe_keeps_baselines_per_metric() {
+        let detector = AnomalyDetector::new(Statistic::Mean);
+        for snapshot in series(&noisy(100.0, 20)) {
+            assert!(detector.observe(&snapshot).is_none());
+        }
+
+        // Another metric starts its own warmup
+        let mut other = series(&[1000.0]).remove(0);
+        other.metric_name = "fees".to_string();
+        assert!(detector.observe(&other).is_none());
+
+        let spike = series(&[200.0]).remove(0);
+        assert!(detector.observe(&spike).is_some());
+        detector.reset();
+        assert!(detector.observe(&spike).is_none());
+    }
+}
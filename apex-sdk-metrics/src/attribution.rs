@@ -0,0 +1,505 @@
+//! Per-operation cost attribution
+//!
+//! [`CostAttributor`] combines profiler spans, recorded fees and RPC-call
+//! counts into periodic [`AttributionReport`]s showing which operation types
+//! and which callers consumed what share of RPC quota, fees and time. Reports
+//! serialize to JSON and are exported as Prometheus gauges by
+//! [`PrometheusRegistry::update_from_attribution`].
+//!
+//! Callers are identified by the `caller` span attribute. Work run inside
+//! [`with_caller`] is attributed to that caller: adapters instrumented with
+//! [`ChainInstrumentation`] tag their spans with it, and fees recorded within
+//! it are booked against it.
+//!
+//! ```rust,no_run
+//! use apex_sdk_metrics::attribution::{with_caller, CostAttributor};
+//! use apex_sdk_metrics::{ObservabilityFacade, OperationType, PerformanceProfiler};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # async fn example() {
+//! let facade = ObservabilityFacade::new();
+//! let attributor = Arc::new(
+//!     CostAttributor::new(PerformanceProfiler::clone(&facade.profiler()))
+//!         .with_json_output("cost-attribution.json"),
+//! );
+//! attributor.clone().spawn(Duration::from_secs(300));
+//!
+//! with_caller("indexer", async {
+//!     // Adapter calls made here are attributed to "indexer"
+//!     attributor.record_fee(OperationType::TransactionSubmit, 0.0021);
+//! })
+//! .await;
+//! # }
+//! ```
+//!
+//! [`PrometheusRegistry::update_from_attribution`]: crate::PrometheusRegistry::update_from_attribution
+//! [`ChainInstrumentation`]: crate::ChainInstrumentation
+
+use crate::profiling::{OperationType, PerformanceProfiler, SpanRecord};
+use crate::{unix_now, MetricsError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Span attribute naming the caller an operation is attributed to
+pub const CALLER_ATTRIBUTE: &str = "caller";
+
+/// Span attribute overriding the number of RPC requests a span made
+pub const RPC_CALLS_ATTRIBUTE: &str = "rpc_calls";
+
+/// Caller of spans and fees recorded outside [`with_caller`]
+pub const UNATTRIBUTED: &str = "unattributed";
+
+tokio::task_local! {
+    static CALLER: String;
+}
+
+/// Attribute the operations run by `future` to `caller`
+pub async fn with_caller<F: Future>(caller: impl Into<String>, future: F) -> F::Output {
+    CALLER.scope(caller.into(), future).await
+}
+
+/// Caller set by the enclosing [`with_caller`], if any
+pub fn current_caller() -> Option<String> {
+    CALLER.try_with(Clone::clone).ok()
+}
+
+/// Resources consumed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostUsage {
+    /// Number of RPC requests
+    pub rpc_calls: u64,
+    /// Fees paid, in the unit they were recorded in
+    pub fees: f64,
+    /// Time spent in seconds
+    pub time_secs: f64,
+}
+
+impl CostUsage {
+    fn add(&mut self, other: &CostUsage) {
+        self.rpc_calls += other.rpc_calls;
+        self.fees += other.fees;
+        self.time_secs += other.time_secs;
+    }
+}
+
+/// Resources consumed by an operation type, a caller, or both, and their share
+/// of the report totals
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostShare {
+    /// Operation type, unset in per-caller rows
+    pub operation: Option<OperationType>,
+    /// Caller, unset in per-operation rows
+    pub caller: Option<String>,
+    /// Resources consumed
+    pub usage: CostUsage,
+    /// Share of all RPC requests (0-1)
+    pub rpc_share: f64,
+    /// Share of all fees (0-1)
+    pub fee_share: f64,
+    /// Share of all time spent (0-1)
+    pub time_share: f64,
+}
+
+/// Cost attribution over a reporting window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributionReport {
+    /// Unix timestamp of the window start, inclusive
+    pub window_start: u64,
+    /// Unix timestamp of the window end, exclusive
+    pub window_end: u64,
+    /// Resources consumed in the window
+    pub totals: CostUsage,
+    /// Consumption per operation type, largest RPC share first
+    pub by_operation: Vec<CostShare>,
+    /// Consumption per caller, largest RPC share first
+    pub by_caller: Vec<CostShare>,
+    /// Consumption per operation type and caller, largest RPC share first
+    pub entries: Vec<CostShare>,
+}
+
+impl AttributionReport {
+    /// Build a report from the spans and fees of a window
+    ///
+    /// Spans and fees outside `[window_start, window_end)` are ignored.
+    pub fn from_records<'a>(
+        window_start: u64,
+        window_end: u64,
+        spans: impl IntoIterator<Item = &'a SpanRecord>,
+        fees: impl IntoIterator<Item = &'a FeeRecord>,
+    ) -> Self {
+        let in_window = |timestamp: u64| timestamp >= window_start && timestamp < window_end;
+        let mut entries: HashMap<(OperationType, String), CostUsage> = HashMap::new();
+
+        for span in spans.into_iter().filter(|s| in_window(s.start_timestamp)) {
+            let usage = entries
+                .entry((span.operation_type, span_caller(span).to_string()))
+                .or_default();
+            usage.rpc_calls += span_rpc_calls(span);
+            usage.time_secs += span.duration.as_secs_f64();
+        }
+        for fee in fees.into_iter().filter(|f| in_window(f.timestamp)) {
+            entries
+                .entry((fee.operation, fee.caller.clone()))
+                .or_default()
+                .fees += fee.amount;
+        }
+
+        let mut totals = CostUsage::default();
+        let mut by_operation: HashMap<OperationType, CostUsage> = HashMap::new();
+        let mut by_caller: HashMap<String, CostUsage> = HashMap::new();
+        for ((operation, caller), usage) in &entries {
+            totals.add(usage);
+            by_operation.entry(*operation).or_default().add(usage);
+            by_caller.entry(caller.clone()).or_default().add(usage);
+        }
+
+        let share = |operation: Option<OperationType>, caller: Option<String>, usage: CostUsage| {
+            CostShare {
+                operation,
+                caller,
+                rpc_share: ratio(usage.rpc_calls as f64, totals.rpc_calls as f64),
+                fee_share: ratio(usage.fees, totals.fees),
+                time_share: ratio(usage.time_secs, totals.time_secs),
+                usage,
+            }
+        };
+
+        Self {
+            window_start,
+            window_end,
+            totals,
+            by_operation: sorted(
+                by_operation
+                    .into_iter()
+                    .map(|(operation, usage)| share(Some(operation), None, usage))
+                    .collect(),
+            ),
+            by_caller: sorted(
+                by_caller
+                    .into_iter()
+                    .map(|(caller, usage)| share(None, Some(caller), usage))
+                    .collect(),
+            ),
+            entries: sorted(
+                entries
+                    .into_iter()
+                    .map(|((operation, caller), usage)| share(Some(operation), Some(caller), usage))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Serialize the report as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| MetricsError::ExportFailed(e.to_string()))
+    }
+
+    /// Length of the reporting window
+    pub fn window(&self) -> Duration {
+        Duration::from_secs(self.window_end.saturating_sub(self.window_start))
+    }
+}
+
+/// A fee paid by an operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeRecord {
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    /// Operation that paid the fee
+    pub operation: OperationType,
+    /// Caller the fee is attributed to
+    pub caller: String,
+    /// Fee amount
+    pub amount: f64,
+}
+
+/// Produces cost attribution reports from a profiler and recorded fees
+///
+/// Each report covers the window since the previous one. Spans are attributed
+/// to the window they started in, and the current second is left to the next
+/// window so late spans are not split across reports.
+pub struct CostAttributor {
+    profiler: PerformanceProfiler,
+    fees: Mutex<Vec<FeeRecord>>,
+    window_start: Mutex<u64>,
+    latest: Mutex<Option<AttributionReport>>,
+    json_output: Option<PathBuf>,
+}
+
+impl CostAttributor {
+    /// Attribute the spans of `profiler`, starting the first window now
+    pub fn new(profiler: PerformanceProfiler) -> Self {
+        Self {
+            profiler,
+            fees: Mutex::new(Vec::new()),
+            window_start: Mutex::new(unix_now()),
+            latest: Mutex::new(None),
+            json_output: None,
+        }
+    }
+
+    /// Write each report generated by [`CostAttributor::spawn`] to a JSON file
+    pub fn with_json_output(mut self, path: impl Into<PathBuf>) -> Self {
+        self.json_output = Some(path.into());
+        self
+    }
+
+    /// Record a fee paid by `operation`
+    ///
+    /// The fee is attributed to the caller set by [`with_caller`]. Fees are
+    /// summed as given, so record them in one unit (e.g. USD) when several
+    /// chains are attributed together.
+    pub fn record_fee(&self, operation: OperationType, amount: f64) {
+        let caller = current_caller().unwrap_or_else(|| UNATTRIBUTED.to_string());
+        self.lock_fees().push(FeeRecord {
+            timestamp: unix_now(),
+            operation,
+            caller,
+            amount,
+        });
+    }
+
+    /// Close the current window and report on it
+    pub fn report(&self) -> AttributionReport {
+        let window_end = unix_now();
+        let window_start = {
+            let mut start = self
+                .window_start
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let previous = *start;
+            *start = window_end.max(previous);
+            previous
+        };
+
+        let fees = {
+            let mut fees = self.lock_fees();
+            let (closed, open): (Vec<_>, Vec<_>) =
+                fees.drain(..).partition(|fee| fee.timestamp < window_end);
+            *fees = open;
+            closed
+        };
+
+        let report = AttributionReport::from_records(
+            window_start,
+            window_end,
+            &self.profiler.get_spans(),
+            &fees,
+        );
+        *self
+            .latest
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(report.clone());
+        report
+    }
+
+    /// Most recent report
+    pub fn latest(&self) -> Option<AttributionReport> {
+        self.latest
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Report periodically in the background
+    ///
+    /// Each report is logged and, if configured, written as JSON.
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+
+                let report = self.report();
+                if let Some(top) = report.by_caller.first() {
+                    info!(
+                        operation = "cost_attribution",
+                        rpc_calls = report.totals.rpc_calls,
+                        fees = report.totals.fees,
+                        time_secs = report.totals.time_secs,
+                        top_caller = top.caller.as_deref().unwrap_or(UNATTRIBUTED),
+                        top_caller_rpc_share = top.rpc_share,
+                        "Cost attribution report"
+                    );
+                }
+
+                if let Some(path) = &self.json_output {
+                    let written = report.to_json().and_then(|json| {
+                        std::fs::write(path, json)
+                            .map_err(|e| MetricsError::ExportFailed(e.to_string()))
+                    });
+                    if let Err(e) = written {
+                        warn!(
+                            operation = "cost_attribution",
+                            error = %e,
+                            "Failed to write cost attribution report"
+                        );
+                    }
+                }
+            }
+        })
+    }
+
+    fn lock_fees(&self) -> std::sync::MutexGuard<'_, Vec<FeeRecord>> {
+        self.fees
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn span_caller(span: &SpanRecord) -> &str {
+    span.attributes
+        .get(CALLER_ATTRIBUTE)
+        .or_else(|| span.context.attributes.get(CALLER_ATTRIBUTE))
+        .map(String::as_str)
+        .unwrap_or(UNATTRIBUTED)
+}
+
+/// RPC requests made by a span: its `rpc_calls` attribute, otherwise one for
+/// every operation except local signing
+fn span_rpc_calls(span: &SpanRecord) -> u64 {
+    match span.attributes.get(RPC_CALLS_ATTRIBUTE) {
+        Some(calls) => calls.parse().unwrap_or(1),
+        None if span.operation_type == OperationType::Signing => 0,
+        None => 1,
+    }
+}
+
+fn ratio(part: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        part / total
+    } else {
+        0.0
+    }
+}
+
+fn sorted(mut shares: Vec<CostShare>) -> Vec<CostShare> {
+    shares.sort_by(|a, b| {
+        b.usage
+            .rpc_calls
+            .cmp(&a.usage.rpc_calls)
+            .then(b.usage.time_secs.total_cmp(&a.usage.time_secs))
+            .then_with(|| a.caller.cmp(&b.caller))
+            .then_with(|| {
+                let name = |s: &CostShare| s.operation.map(|o| o.to_string());
+                name(a).cmp(&name(b))
+            })
+    });
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiling::SpanContext;
+
+    fn span(operation: OperationType, caller: Option<&str>, millis: u64) -> SpanRecord {
+        let mut attributes = HashMap::new();
+        if let Some(caller) = caller {
+            attributes.insert(CALLER_ATTRIBUTE.to_string(), caller.to_string());
+        }
+        SpanRecord {
+            context: SpanContext::new(operation),
+            operation_type: operation,
+            duration: Duration::from_millis(millis),
+            start_timestamp: 100,
+            attributes,
+        }
+    }
+
+    fn fee(operation: OperationType, caller: &str, amount: f64) -> FeeRecord {
+        FeeRecord {
+            timestamp: 100,
+            operation,
+            caller: caller.to_string(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn test_report_shares() {
+        let mut batched = span(OperationType::BlockQuery, Some("indexer"), 300);
+        batched
+            .attributes
+            .insert(RPC_CALLS_ATTRIBUTE.to_string(), "5".to_string());
+        let spans = vec![
+            batched,
+            span(OperationType::BalanceQuery, Some("wallet"), 100),
+            span(OperationType::TransactionSubmit, Some("wallet"), 500),
+            span(OperationType::Signing, Some("wallet"), 100),
+            span(OperationType::BalanceQuery, None, 0),
+        ];
+        let fees = vec![
+            fee(OperationType::TransactionSubmit, "wallet", 3.0),
+            fee(OperationType::TransactionSubmit, "bot", 1.0),
+            // Outside the window
+            FeeRecord {
+                timestamp: 200,
+                ..fee(OperationType::TransactionSubmit, "bot", 10.0)
+            },
+        ];
+
+        let report = AttributionReport::from_records(100, 101, &spans, &fees);
+
+        assert_eq!(report.totals.rpc_calls, 8);
+        assert_eq!(report.totals.fees, 4.0);
+        assert!((report.totals.time_secs - 1.0).abs() < 1e-9);
+
+        let indexer = &report.by_caller[0];
+        assert_eq!(indexer.caller.as_deref(), Some("indexer"));
+        assert_eq!(indexer.rpc_share, 5.0 / 8.0);
+        assert_eq!(indexer.fee_share, 0.0);
+
+        let wallet = &report.by_caller[1];
+        assert_eq!(wallet.caller.as_deref(), Some("wallet"));
+        assert_eq!(wallet.usage.rpc_calls, 2);
+        assert_eq!(wallet.fee_share, 0.75);
+        assert!((wallet.time_share - 0.7).abs() < 1e-9);
+
+        assert!(report
+            .by_caller
+            .iter()
+            .any(|s| s.caller.as_deref() == Some(UNATTRIBUTED)));
+
+        let submit = report
+            .by_operation
+            .iter()
+            .find(|s| s.operation == Some(OperationType::TransactionSubmit))
+            .unwrap();
+        assert_eq!(submit.usage.fees, 4.0);
+        assert_eq!(report.entries.len(), 6);
+
+        let json = report.to_json().unwrap();
+        let parsed: AttributionReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.totals.rpc_calls, 8);
+        assert_eq!(parsed.by_caller[0].caller.as_deref(), Some("indexer"));
+    }
+
+    #[tokio::test]
+    async fn test_fees_attributed_to_current_caller() {
+        let attributor = CostAttributor::new(PerformanceProfiler::new());
+
+        assert_eq!(current_caller(), None);
+        with_caller("indexer", async {
+            assert_eq!(current_caller().as_deref(), Some("indexer"));
+            attributor.record_fee(OperationType::TransactionSubmit, 2.0);
+        })
+        .await;
+        attributor.record_fee(OperationType::TransactionSubmit, 1.0);
+
+        let fees = attributor.lock_fees().clone();
+        assert_eq!(fees[0].caller, "indexer");
+        assert_eq!(fees[1].caller, UNATTRIBUTED);
+
+        let report = attributor.report();
+        assert_eq!(attributor.latest(), Some(report));
+    }
+}
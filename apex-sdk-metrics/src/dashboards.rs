@@ -0,0 +1,774 @@
+//! Grafana dashboards and Prometheus rules generated from the registry
+//!
+//! [`PrometheusRegistry::families`] describes the metric families a registry
+//! exports: name, help, type, label names and histogram buckets. A
+//! [`DashboardGenerator`] turns them into a Grafana dashboard with a panel
+//! per family and a Prometheus rule file with recording rules for counter
+//! rates and histogram quantiles, plus alerts derived from the families'
+//! labels and buckets. Because both are generated from what is registered,
+//! they stay in step with the SDK's metrics as families are added.
+//!
+//! ```rust
+//! use apex_sdk_metrics::dashboards::DashboardGenerator;
+//! use apex_sdk_metrics::PrometheusRegistry;
+//!
+//! let registry = PrometheusRegistry::new().unwrap();
+//! let generator = DashboardGenerator::new(&registry).with_title("Indexer");
+//!
+//! let dashboard = generator.dashboard_json().unwrap();
+//! let rules = generator.rules_yaml();
+//! assert!(rules.contains("record: chain_status:apex_sdk_transactions:rate5m"));
+//! # let _ = dashboard;
+//! ```
+
+use crate::prometheus_exporter::PrometheusRegistry;
+use crate::{MetricsError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// Prometheus metric type of a family
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricKind {
+    /// Monotonic counter
+    Counter,
+    /// Value that goes up and down
+    Gauge,
+    /// Bucketed observations
+    Histogram,
+}
+
+/// A metric family registered in a [`PrometheusRegistry`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricFamilyInfo {
+    /// Exported name, e.g. `apex_sdk_transactions_total`
+    pub name: String,
+    /// Help text
+    pub help: String,
+    /// Metric type
+    pub kind: MetricKind,
+    /// Variable label names, without const labels
+    pub labels: Vec<String>,
+    /// Upper bounds of the finite histogram buckets; empty for other types
+    pub buckets: Vec<f64>,
+}
+
+impl MetricFamilyInfo {
+    /// Name without the `_total` suffix of counters
+    fn base_name(&self) -> &str {
+        self.name.strip_suffix("_total").unwrap_or(&self.name)
+    }
+
+    /// Recording rule level: the label names the rule aggregates by
+    fn level(&self) -> String {
+        if self.labels.is_empty() {
+            "job".to_string()
+        } else {
+            self.labels.join("_")
+        }
+    }
+
+    fn by_clause(&self) -> String {
+        if self.labels.is_empty() {
+            "job".to_string()
+        } else {
+            self.labels.join(", ")
+        }
+    }
+
+    fn has_label(&self, label: &str) -> bool {
+        self.labels.iter().any(|l| l == label)
+    }
+
+    fn rate_record(&self) -> String {
+        format!("{}:{}:rate5m", self.level(), self.base_name())
+    }
+
+    fn quantile_record(&self, quantile: f64) -> String {
+        format!(
+            "{}:{}:p{}_5m",
+            self.level(),
+            self.name,
+            (quantile * 100.0).round()
+        )
+    }
+}
+
+/// Quantiles recorded and charted for histograms
+const QUANTILES: [f64; 3] = [0.5, 0.95, 0.99];
+
+/// A rule in a Prometheus rule group
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrometheusRule {
+    /// Recording rule
+    Record {
+        /// Name of the recorded series
+        record: String,
+        /// PromQL expression
+        expr: String,
+    },
+    /// Alerting rule
+    Alert {
+        /// Alert name
+        alert: String,
+        /// PromQL expression; the alert fires for each series it returns
+        expr: String,
+        /// How long the expression must hold before firing, e.g. `10m`
+        #[serde(rename = "for")]
+        for_duration: String,
+        /// Labels attached to the alert, including `severity`
+        labels: BTreeMap<String, String>,
+        /// `summary` and `description` annotations
+        annotations: BTreeMap<String, String>,
+    },
+}
+
+/// A named group of Prometheus rules
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleGroup {
+    /// Group name
+    pub name: String,
+    /// Evaluation interval, e.g. `30s`
+    pub interval: String,
+    /// Rules evaluated in order
+    pub rules: Vec<PrometheusRule>,
+}
+
+/// Generates a Grafana dashboard and Prometheus rules for metric families
+///
+/// Alerts cover:
+/// - failure ratio of counters with a `status` label
+/// - rate of counters with a `severity` label at `critical`
+/// - p95 of histograms above a latency threshold, by default the
+///   second-highest bucket bound
+/// - gauges with a `status` label reporting `unhealthy`
+/// - `_ratio` gauges with an `endpoint` label below the availability target
+#[derive(Debug, Clone)]
+pub struct DashboardGenerator {
+    families: Vec<MetricFamilyInfo>,
+    title: String,
+    uid: String,
+    rule_interval: String,
+    failure_ratio: f64,
+    availability_target: f64,
+    latency_thresholds: HashMap<String, f64>,
+}
+
+impl DashboardGenerator {
+    /// Generate from the families registered in `registry`
+    pub fn new(registry: &PrometheusRegistry) -> Self {
+        Self::from_families(registry.families())
+    }
+
+    /// Generate from explicit family descriptions
+    pub fn from_families(families: Vec<MetricFamilyInfo>) -> Self {
+        Self {
+            families,
+            title: "Apex SDK".to_string(),
+            uid: "apex-sdk-generated".to_string(),
+            rule_interval: "30s".to_string(),
+            failure_ratio: 0.05,
+            availability_target: 0.99,
+            latency_thresholds: HashMap::new(),
+        }
+    }
+
+    /// Set the dashboard title
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set the dashboard UID, which Grafana uses to update it in place
+    pub fn with_uid(mut self, uid: impl Into<String>) -> Self {
+        self.uid = uid.into();
+        self
+    }
+
+    /// Set the rule group evaluation interval (default `30s`)
+    pub fn with_rule_interval(mut self, interval: impl Into<String>) -> Self {
+        self.rule_interval = interval.into();
+        self
+    }
+
+    /// Failure ratio above which status-labelled counters alert (default 0.05)
+    pub fn with_failure_ratio(mut self, ratio: f64) -> Self {
+        self.failure_ratio = ratio;
+        self
+    }
+
+    /// Availability below which endpoint ratio gauges alert (default 0.99)
+    pub fn with_availability_target(mut self, target: f64) -> Self {
+        self.availability_target = target;
+        self
+    }
+
+    /// p95 latency in seconds above which a histogram alerts
+    ///
+    /// Rounded up to the next bucket bound, where the quantile estimate is
+    /// exact.
+    pub fn with_latency_threshold(mut self, family: impl Into<String>, seconds: f64) -> Self {
+        self.latency_thresholds.insert(family.into(), seconds);
+        self
+    }
+
+    /// The families the dashboard and rules are generated from
+    pub fn families(&self) -> &[MetricFamilyInfo] {
+        &self.families
+    }
+
+    /// Grafana dashboard model
+    ///
+    /// Wrapped as `{"dashboard": ...}` like the dashboards shipped in
+    /// `dashboards/`, ready for the import API. Panels query the data source
+    /// chosen in the `datasource` variable, filtered by the `job` variable.
+    pub fn dashboard(&self) -> Value {
+        let mut panels = Vec::new();
+        for family in &self.families {
+            panels.push(self.panel(family));
+            if family.kind == MetricKind::Histogram {
+                panels.push(heatmap_panel(family));
+            }
+        }
+        for (index, panel) in panels.iter_mut().enumerate() {
+            panel["id"] = json!(index + 1);
+            panel["gridPos"] = json!({
+                "x": (index % 2) * 12,
+                "y": (index / 2) * 8,
+                "w": 12,
+                "h": 8,
+            });
+        }
+
+        let job_query = self
+            .families
+            .first()
+            .map(|family| format!("label_values({}, job)", series_name(family)))
+            .unwrap_or_else(|| "label_values(job)".to_string());
+
+        json!({
+            "dashboard": {
+                "uid": self.uid,
+                "title": self.title,
+                "tags": ["apex-sdk", "generated"],
+                "timezone": "browser",
+                "schemaVersion": 38,
+                "version": 1,
+                "refresh": "30s",
+                "time": { "from": "now-1h", "to": "now" },
+                "templating": {
+                    "list": [
+                        {
+                            "name": "datasource",
+                            "label": "Data source",
+                            "type": "datasource",
+                            "query": "prometheus",
+                        },
+                        {
+                            "name": "job",
+                            "label": "Job",
+                            "type": "query",
+                            "datasource": datasource(),
+                            "query": job_query,
+                            "refresh": 2,
+                            "multi": true,
+                            "includeAll": true,
+                            "allValue": ".*",
+                            "current": { "text": "All", "value": "$__all" },
+                        },
+                    ]
+                },
+                "panels": panels,
+            }
+        })
+    }
+
+    /// Grafana dashboard as pretty-printed JSON
+    pub fn dashboard_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self.dashboard())
+            .map_err(|e| MetricsError::ExportFailed(e.to_string()))
+    }
+
+    fn panel(&self, family: &MetricFamilyInfo) -> Value {
+        let selector = "{job=~\"$job\"}";
+        let legend = legend(family);
+        let (targets, unit) = match family.kind {
+            MetricKind::Counter => (
+                vec![json!({
+                    "expr": format!(
+                        "sum by ({}) (rate({}{}[$__rate_interval]))",
+                        family.by_clause(),
+                        family.name,
+                        selector
+                    ),
+                    "legendFormat": legend,
+                    "refId": "A",
+                })],
+                "ops",
+            ),
+            MetricKind::Gauge => (
+                vec![json!({
+                    "expr": format!("{}{}", family.name, selector),
+                    "legendFormat": legend,
+                    "refId": "A",
+                })],
+                unit(&family.name),
+            ),
+            MetricKind::Histogram => (
+                QUANTILES
+                    .iter()
+                    .enumerate()
+                    .map(|(i, quantile)| {
+                        json!({
+                            "expr": format!(
+                                "histogram_quantile({}, sum by (le, {}) (rate({}_bucket{}[$__rate_interval])))",
+                                quantile,
+                                family.by_clause(),
+                                family.name,
+                                selector
+                            ),
+                            "legendFormat": format!("p{} {}", (quantile * 100.0).round(), legend),
+                            "refId": ((b'A' + i as u8) as char).to_string(),
+                        })
+                    })
+                    .collect(),
+                unit(&family.name),
+            ),
+        };
+
+        json!({
+            "title": title(family),
+            "description": family.help,
+            "type": "timeseries",
+            "datasource": datasource(),
+            "targets": targets,
+            "fieldConfig": { "defaults": { "unit": unit }, "overrides": [] },
+            "options": {
+                "legend": { "showLegend": true, "displayMode": "list", "placement": "bottom" },
+                "tooltip": { "mode": "multi" },
+            },
+        })
+    }
+
+    /// Recording and alerting rule groups
+    pub fn rule_groups(&self) -> Vec<RuleGroup> {
+        let mut records = Vec::new();
+        let mut alerts = Vec::new();
+
+        for family in &self.families {
+            match family.kind {
+                MetricKind::Counter => {
+                    records.push(PrometheusRule::Record {
+                        record: family.rate_record(),
+                        expr: format!(
+                            "sum by ({}) (rate({}[5m]))",
+                            family.by_clause(),
+                            family.name
+                        ),
+                    });
+                    alerts.extend(self.counter_alerts(family));
+                }
+                MetricKind::Histogram => {
+                    for quantile in QUANTILES {
+                        records.push(PrometheusRule::Record {
+                            record: family.quantile_record(quantile),
+                            expr: format!(
+                                "histogram_quantile({}, sum by (le, {}) (rate({}_bucket[5m])))",
+                                quantile,
+                                family.by_clause(),
+                                family.name
+                            ),
+                        });
+                    }
+                    alerts.extend(self.latency_alert(family));
+                }
+                MetricKind::Gauge => alerts.extend(self.gauge_alerts(family)),
+            }
+        }
+
+        vec![
+            RuleGroup {
+                name: "apex_sdk_recording".to_string(),
+                interval: self.rule_interval.clone(),
+                rules: records,
+            },
+            RuleGroup {
+                name: "apex_sdk_alerts".to_string(),
+                interval: self.rule_interval.clone(),
+                rules: alerts,
+            },
+        ]
+    }
+
+    /// Prometheus rule file holding [`rule_groups`](Self::rule_groups)
+    ///
+    /// Alerts on counters and histograms read the recorded series, so load
+    /// both groups together.
+    pub fn rules_yaml(&self) -> String {
+        let mut out = String::from("groups:\n");
+        for group in self.rule_groups() {
+            let _ = writeln!(out, "  - name: {}", quote(&group.name));
+            let _ = writeln!(out, "    interval: {}", group.interval);
+            if group.rules.is_empty() {
+                out.push_str("    rules: []\n");
+                continue;
+            }
+            out.push_str("    rules:\n");
+            for rule in &group.rules {
+                match rule {
+                    PrometheusRule::Record { record, expr } => {
+                        let _ = writeln!(out, "      - record: {}", record);
+                        let _ = writeln!(out, "        expr: {}", quote(expr));
+                    }
+                    PrometheusRule::Alert {
+                        alert,
+                        expr,
+                        for_duration,
+                        labels,
+                        annotations,
+                    } => {
+                        let _ = writeln!(out, "      - alert: {}", alert);
+                        let _ = writeln!(out, "        expr: {}", quote(expr));
+                        let _ = writeln!(out, "        for: {}", for_duration);
+                        for (key, pairs) in [("labels", labels), ("annotations", annotations)] {
+                            let _ = writeln!(out, "        {}:", key);
+                            for (name, value) in pairs {
+                                let _ = writeln!(out, "          {}: {}", name, quote(value));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    fn counter_alerts(&self, family: &MetricFamilyInfo) -> Vec<PrometheusRule> {
+        let mut alerts = Vec::new();
+        let others: Vec<&str> = family
+            .labels
+            .iter()
+            .map(String::as_str)
+            .filter(|l| *l != "status" && *l != "severity")
+            .collect();
+        let by = if others.is_empty() {
+            "job".to_string()
+        } else {
+            others.join(", ")
+        };
+
+        if family.has_label("status") {
+            alerts.push(alert(
+                format!("{}HighFailureRatio", alert_name(family)),
+                format!(
+                    "sum by ({by}) ({rate}{{status!=\"success\"}}) / sum by ({by}) ({rate}) > {ratio}",
+                    by = by,
+                    rate = family.rate_record(),
+                    ratio = self.failure_ratio
+                ),
+                "10m",
+                "warning",
+                format!(
+                    "More than {}% of {} are failing",
+                    self.failure_ratio * 100.0,
+                    family.base_name()
+                ),
+                &family.help,
+            ));
+        }
+        if family.has_label("severity") {
+            alerts.push(alert(
+                format!("{}Critical", alert_name(family)),
+                format!(
+                    "sum by ({}) ({}{{severity=\"critical\"}}) > 0",
+                    by,
+                    family.rate_record()
+                ),
+                "1m",
+                "critical",
+                format!("Critical {} are being recorded", family.base_name()),
+                &family.help,
+            ));
+        }
+        alerts
+    }
+
+    fn latency_alert(&self, family: &MetricFamilyInfo) -> Option<PrometheusRule> {
+        let threshold = match self.latency_thresholds.get(&family.name) {
+            Some(&seconds) => family
+                .buckets
+                .iter()
+                .copied()
+                .find(|&bound| bound >= seconds)
+                .unwrap_or(seconds),
+            // The top bucket bound is usually a catch-all; the one below it
+            // marks the slow end of the expected range
+            None => match family.buckets.len() {
+                0 => return None,
+                1 => family.buckets[0],
+                n => family.buckets[n - 2],
+            },
+        };
+
+        Some(alert(
+            format!("{}SlowP95", alert_name(family)),
+            format!("{} > {}", family.quantile_record(0.95), threshold),
+            "10m",
+            "warning",
+            format!("p95 of {} is above {}s", family.name, threshold),
+            &family.help,
+        ))
+    }
+
+    fn gauge_alerts(&self, family: &MetricFamilyInfo) -> Vec<PrometheusRule> {
+        let mut alerts = Vec::new();
+        if family.has_label("status") {
+            alerts.push(alert(
+                format!("{}Unhealthy", alert_name(family)),
+                format!("{}{{status=\"unhealthy\"}} == 1", family.name),
+                "5m",
+                "critical",
+                format!("{} reports unhealthy", family.name),
+                &family.help,
+            ));
+        }
+        if family.has_label("endpoint") && family.name.ends_with("_ratio") {
+            alerts.push(alert(
+                format!("{}BelowTarget", alert_name(family)),
+                format!("{} < {}", family.name, self.availability_target),
+                "15m",
+                "warning",
+                format!("{} is below {}", family.name, self.availability_target),
+                &family.help,
+            ));
+        }
+        alerts
+    }
+}
+
+fn alert(
+    name: String,
+    expr: String,
+    for_duration: &str,
+    severity: &str,
+    summary: String,
+    description: &str,
+) -> PrometheusRule {
+    PrometheusRule::Alert {
+        alert: name,
+        expr,
+        for_duration: for_duration.to_string(),
+        labels: BTreeMap::from([("severity".to_string(), severity.to_string())]),
+        annotations: BTreeMap::from([
+            ("summary".to_string(), summary),
+            ("description".to_string(), description.to_string()),
+        ]),
+    }
+}
+
+fn heatmap_panel(family: &MetricFamilyInfo) -> Value {
+    json!({
+        "title": format!("{} distribution", title(family)),
+        "description": family.help,
+        "type": "heatmap",
+        "datasource": datasource(),
+        "targets": [{
+            "expr": format!(
+                "sum by (le) (increase({}_bucket{{job=~\"$job\"}}[$__rate_interval]))",
+                family.name
+            ),
+            "format": "heatmap",
+            "legendFormat": "{{le}}",
+            "refId": "A",
+        }],
+        "options": {
+            "calculate": false,
+            "yAxis": { "unit": unit(&family.name) },
+        },
+    })
+}
+
+fn datasource() -> Value {
+    json!({ "type": "prometheus", "uid": "${datasource}" })
+}
+
+/// A series name that exists for the family, for `label_values`
+fn series_name(family: &MetricFamilyInfo) -> String {
+    match family.kind {
+        MetricKind::Histogram => format!("{}_count", family.name),
+        _ => family.name.clone(),
+    }
+}
+
+/// Panel title: the name without the `apex_sdk_` prefix, in words
+fn title(family: &MetricFamilyInfo) -> String {
+    let words = family
+        .base_name()
+        .strip_prefix("apex_sdk_")
+        .unwrap_or(family.base_name())
+        .replace('_', " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => words,
+    }
+}
+
+/// Alert name: the name without the `apex_sdk_` prefix, in CamelCase
+fn alert_name(family: &MetricFamilyInfo) -> String {
+    let name = family.base_name();
+    let name = name.strip_prefix("apex_sdk_").unwrap_or(name);
+    let mut out = String::from("ApexSdk");
+    for word in name.split('_') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.push_str(chars.as_str());
+        }
+    }
+    out
+}
+
+fn legend(family: &MetricFamilyInfo) -> String {
+    family
+        .labels
+        .iter()
+        .map(|label| format!("{{{{{}}}}}", label))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Grafana unit from the metric name suffix
+fn unit(name: &str) -> &'static str {
+    let name = name.strip_suffix("_total").unwrap_or(name);
+    if name.ends_with("_seconds") {
+        "s"
+    } else if name.ends_with("_ms") {
+        "ms"
+    } else if name.ends_with("_bytes") {
+        "bytes"
+    } else if name.ends_with("_percent") {
+        "percent"
+    } else if name.ends_with("_ratio") || name.ends_with("_share") {
+        "percentunit"
+    } else {
+        "short"
+    }
+}
+
+/// YAML double-quoted scalar; JSON string escapes are valid YAML
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generator() -> DashboardGenerator {
+        DashboardGenerator::new(&PrometheusRegistry::new().unwrap())
+    }
+
+    #[test]
+    fn test_families_describe_registered_metrics() {
+        let families = PrometheusRegistry::new().unwrap().families();
+        let transactions = families
+            .iter()
+            .find(|f| f.name == "apex_sdk_transactions_total")
+            .unwrap();
+        assert_eq!(transactions.kind, MetricKind::Counter);
+        assert_eq!(transactions.labels, ["chain", "status"]);
+
+        let rpc = families
+            .iter()
+            .find(|f| f.name == "apex_sdk_rpc_duration_seconds")
+            .unwrap();
+        assert_eq!(rpc.kind, MetricKind::Histogram);
+        assert_eq!(rpc.buckets.last(), Some(&5.0));
+    }
+
+    #[test]
+    fn test_dashboard_has_panel_per_family() {
+        let generator = generator();
+        let dashboard = generator.dashboard();
+        let panels = dashboard["dashboard"]["panels"].as_array().unwrap();
+
+        let histograms = generator
+            .families()
+            .iter()
+            .filter(|f| f.kind == MetricKind::Histogram)
+            .count();
+        assert_eq!(panels.len(), generator.families().len() + histograms);
+
+        let latency = panels
+            .iter()
+            .find(|p| p["title"] == "Rpc duration seconds")
+            .unwrap();
+        assert_eq!(latency["fieldConfig"]["defaults"]["unit"], "s");
+        assert_eq!(latency["targets"].as_array().unwrap().len(), 3);
+        assert!(latency["targets"][1]["expr"]
+            .as_str()
+            .unwrap()
+            .starts_with("histogram_quantile(0.95, sum by (le, chain, operation)"));
+
+        // Round-trips through JSON
+        let json = generator.dashboard_json().unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&json).unwrap(), dashboard);
+    }
+
+    #[test]
+    fn test_rules_from_labels_and_buckets() {
+        let generator = generator()
+            .with_failure_ratio(0.1)
+            .with_latency_threshold("apex_sdk_transaction_duration_seconds", 7.0);
+        let groups = generator.rule_groups();
+        let find = |name: &str| {
+            groups
+                .iter()
+                .flat_map(|g| &g.rules)
+                .find_map(|rule| match rule {
+                    PrometheusRule::Alert { alert, expr, .. } if alert == name => {
+                        Some(expr.clone())
+                    }
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        assert!(groups[0].rules.contains(&PrometheusRule::Record {
+            record: "chain_status:apex_sdk_transactions:rate5m".to_string(),
+            expr: "sum by (chain, status) (rate(apex_sdk_transactions_total[5m]))".to_string(),
+        }));
+        assert!(find("ApexSdkTransactionsHighFailureRatio").ends_with("> 0.1"));
+        assert!(find("ApexSdkErrorsCritical").contains("severity=\"critical\""));
+        // Default threshold: the second-highest RPC bucket
+        assert_eq!(
+            find("ApexSdkRpcDurationSecondsSlowP95"),
+            "chain_operation:apex_sdk_rpc_duration_seconds:p95_5m > 1"
+        );
+        // Explicit thresholds round up to a bucket bound
+        assert!(find("ApexSdkTransactionDurationSecondsSlowP95").ends_with("> 10"));
+        assert_eq!(
+            find("ApexSdkHealthStatusUnhealthy"),
+            "apex_sdk_health_status{status=\"unhealthy\"} == 1"
+        );
+        assert!(find("ApexSdkEndpointUptimeRatioBelowTarget").ends_with("< 0.99"));
+    }
+
+    #[test]
+    fn test_rules_yaml() {
+        let yaml = generator().rules_yaml();
+        assert!(yaml.starts_with("groups:\n  - name: \"apex_sdk_recording\"\n    interval: 30s\n"));
+        assert!(yaml.contains(
+            "      - record: chain_status:apex_sdk_transactions:rate5m\n        \
+             expr: \"sum by (chain, status) (rate(apex_sdk_transactions_total[5m]))\"\n"
+        ));
+        assert!(yaml.contains(
+            "        expr: \"apex_sdk_health_status{status=\\\"unhealthy\\\"} == 1\"\n        \
+             for: 5m\n        labels:\n          severity: \"critical\"\n"
+        ));
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides comprehensive error taxonomy and automatic categorization
 //! for improved debugging, monitoring, and alerting.
 
+use apex_sdk_core::{privacy, ErrorCode};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -50,6 +51,31 @@ pub enum ErrorCategory {
     ChainSpecific,
 }
 
+impl ErrorCategory {
+    /// Every category
+    pub const ALL: [ErrorCategory; 12] = [
+        ErrorCategory::Network,
+        ErrorCategory::Transaction,
+        ErrorCategory::Authentication,
+        ErrorCategory::ResourceExhaustion,
+        ErrorCategory::Configuration,
+        ErrorCategory::Validation,
+        ErrorCategory::ExternalService,
+        ErrorCategory::Internal,
+        ErrorCategory::Cryptography,
+        ErrorCategory::Timeout,
+        ErrorCategory::RateLimit,
+        ErrorCategory::ChainSpecific,
+    ];
+
+    /// Category with the given name, ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| format!("{:?}", category).eq_ignore_ascii_case(name))
+    }
+}
+
 /// Error impact assessment
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ErrorImpact {
@@ -80,6 +106,10 @@ pub struct ErrorClassification {
     pub error_codes: Vec<String>,
     /// Metrics labels for this error
     pub labels: HashMap<String, String>,
+    /// User-registered category refining [`category`](Self::category), see
+    /// [`ErrorClassifier`](crate::ErrorClassifier)
+    #[serde(default)]
+    pub custom_category: Option<String>,
 }
 
 impl ErrorClassification {
@@ -98,6 +128,7 @@ impl ErrorClassification {
             remediation: None,
             error_codes: Vec::new(),
             labels: HashMap::new(),
+            custom_category: None,
         }
     }
 
@@ -113,9 +144,33 @@ impl ErrorClassification {
         self
     }
 
+    /// Record the stable code of the error being classified
+    ///
+    /// The code is appended to [`error_codes`](Self::error_codes) and set as
+    /// the `error_code` label.
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        let code = code.to_string();
+        self.labels.insert("error_code".to_string(), code.clone());
+        self.error_codes.push(code);
+        self
+    }
+
+    /// Refine the category with a user-registered one
+    pub fn with_custom_category(mut self, name: impl Into<String>) -> Self {
+        self.custom_category = Some(name.into());
+        self
+    }
+
     /// Add a metric label
+    ///
+    /// Address and amount labels are redacted when privacy mode is enabled.
     pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.labels.insert(key.into(), value.into());
+        let key = key.into();
+        let value = match privacy::redactor() {
+            Some(redactor) => redactor.redact(&key, &value.into()).into_owned(),
+            None => value.into(),
+        };
+        self.labels.insert(key, value);
         self
     }
 
@@ -135,11 +190,18 @@ impl ErrorClassification {
         labels.insert("category".to_string(), format!("{:?}", self.category));
         labels.insert("severity".to_string(), format!("{:?}", self.severity));
         labels.insert("impact".to_string(), format!("{:?}", self.impact));
+        if let Some(custom) = &self.custom_category {
+            labels.insert("custom_category".to_string(), custom.clone());
+        }
         labels
     }
 }
 
 /// Categorize an error based on its message and type
+///
+/// These are the built-in heuristics over message keywords. An
+/// [`ErrorClassifier`](crate::ErrorClassifier) tries its rules first and
+/// falls back to this function.
 pub fn categorize_error(error_message: &str, _error_type: Option<&str>) -> ErrorClassification {
     let lower_msg = error_message.to_lowercase();
 
@@ -314,6 +376,9 @@ pub struct ErrorStatistics {
     pub by_category: HashMap<ErrorCategory, u64>,
     /// Errors by severity
     pub by_severity: HashMap<ErrorSeverity, u64>,
+    /// Errors by user-registered category
+    #[serde(default)]
+    pub by_custom_category: HashMap<String, u64>,
     /// Retryable error count
     pub retryable_errors: u64,
     /// Critical error count
@@ -331,6 +396,9 @@ impl ErrorStatistics {
         self.total_errors += 1;
         *self.by_category.entry(classification.category).or_insert(0) += 1;
         *self.by_severity.entry(classification.severity).or_insert(0) += 1;
+        if let Some(custom) = &classification.custom_category {
+            *self.by_custom_category.entry(custom.clone()).or_insert(0) += 1;
+        }
 
         if classification.is_retryable() {
             self.retryable_errors += 1;
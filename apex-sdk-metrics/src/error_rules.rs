@@ -0,0 +1,648 @@
+//! Rule-based error classification
+//!
+//! [`ErrorClassifier`] classifies an [`ErrorSource`] — the error message
+//! along with its type, [`ErrorCode`], chain and Substrate dispatch error —
+//! with an ordered list of [`ClassificationRule`]s, and falls back to the
+//! built-in [`categorize_error`] heuristics when no rule matches. Rules match
+//! on regular expressions over the message, the error type or code, the
+//! chain, and dispatch errors such as `Balances::InsufficientBalance`. Rules
+//! can file errors under [`CustomCategory`]s registered by the application,
+//! each counted under one of the built-in [`ErrorCategory`]s.
+//!
+//! Rules are built in code or loaded from a TOML or JSON [`RuleSet`]:
+//!
+//! ```toml
+//! [[categories]]
+//! name = "insufficient_funds"
+//! parent = "Transaction"
+//! severity = "Medium"
+//! impact = "Isolated"
+//! description = "Account cannot pay for the transaction"
+//! remediation = "Top up the account"
+//!
+//! [[rules]]
+//! name = "balances-insufficient"
+//! dispatch_error = "Balances::InsufficientBalance"
+//! category = "insufficient_funds"
+//!
+//! [[rules]]
+//! name = "moonbeam-gas-price"
+//! chain = "moonbeam"
+//! pattern = "(?i)gas price .* too low"
+//! category = "Transaction"
+//! impact = "Retryable"
+//! ```
+
+use crate::error_categorization::{
+    categorize_error, ErrorCategory, ErrorClassification, ErrorImpact, ErrorSeverity,
+};
+use crate::{MetricsError, Result};
+use apex_sdk_core::ErrorCode;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+/// Error to classify, with what is known about where it came from
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorSource {
+    /// Error message
+    pub message: String,
+    /// Type or variant of the error, e.g. `SubstrateError::Transaction`
+    #[serde(default)]
+    pub error_type: Option<String>,
+    /// Stable code of the error
+    #[serde(default)]
+    pub code: Option<ErrorCode>,
+    /// Chain the error happened on
+    #[serde(default)]
+    pub chain: Option<String>,
+    /// Dispatch error as `Pallet::Error`
+    #[serde(default)]
+    pub dispatch_error: Option<String>,
+}
+
+impl ErrorSource {
+    /// Create a source from an error message
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the type or variant of the error
+    pub fn with_error_type(mut self, error_type: impl Into<String>) -> Self {
+        self.error_type = Some(error_type.into());
+        self
+    }
+
+    /// Set the stable code of the error
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Set the chain the error happened on
+    pub fn with_chain(mut self, chain: impl Into<String>) -> Self {
+        self.chain = Some(chain.into());
+        self
+    }
+
+    /// Set the dispatch error, as `Pallet::Error`
+    pub fn with_dispatch_error(mut self, dispatch_error: impl Into<String>) -> Self {
+        self.dispatch_error = Some(dispatch_error.into());
+        self
+    }
+
+    /// Dispatch errors of the source: the one set, or else every
+    /// `Pallet::Error` mentioned in the message
+    pub fn dispatch_errors(&self) -> Vec<&str> {
+        if let Some(dispatch_error) = &self.dispatch_error {
+            return vec![dispatch_error.as_str()];
+        }
+        static PATH: OnceLock<Regex> = OnceLock::new();
+        PATH.get_or_init(|| Regex::new(r"\b[A-Z][A-Za-z0-9]*::[A-Z][A-Za-z0-9]*\b").unwrap())
+            .find_iter(&self.message)
+            .map(|m| m.as_str())
+            .collect()
+    }
+}
+
+/// Category registered by the application
+///
+/// Errors filed under it are counted under [`parent`](Self::parent) and
+/// carry the custom name in
+/// [`ErrorClassification::custom_category`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomCategory {
+    /// Name rules refer to the category by
+    pub name: String,
+    /// Built-in category the errors are counted under
+    pub parent: ErrorCategory,
+    /// Severity of errors in the category
+    pub severity: ErrorSeverity,
+    /// Impact of errors in the category
+    pub impact: ErrorImpact,
+    /// Description of errors in the category
+    pub description: String,
+    /// Suggested remediation
+    #[serde(default)]
+    pub remediation: Option<String>,
+}
+
+impl CustomCategory {
+    /// Create a category counted under `parent`
+    pub fn new(
+        name: impl Into<String>,
+        parent: ErrorCategory,
+        severity: ErrorSeverity,
+        impact: ErrorImpact,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            parent,
+            severity,
+            impact,
+            description: description.into(),
+            remediation: None,
+        }
+    }
+
+    /// Add remediation suggestion
+    pub fn with_remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+}
+
+/// Rule filing matching errors under a category
+///
+/// Every condition that is set must match. Severity, impact, description and
+/// remediation override those of the category; built-in categories default
+/// to medium severity and isolated impact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassificationRule {
+    /// Name of the rule, recorded as the `rule` label
+    pub name: String,
+    /// Category: a built-in [`ErrorCategory`] name or a registered
+    /// [`CustomCategory`]
+    pub category: String,
+    /// Regular expression the message must match; prefix with `(?i)` to
+    /// ignore case
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Error type the source must have
+    #[serde(default)]
+    pub error_type: Option<String>,
+    /// Error code the source must have
+    #[serde(default)]
+    pub code: Option<ErrorCode>,
+    /// Chain the error must come from, ignoring case
+    #[serde(default)]
+    pub chain: Option<String>,
+    /// Dispatch error as `Pallet::Error`, or `Pallet::*` for any error of a
+    /// pallet
+    #[serde(default)]
+    pub dispatch_error: Option<String>,
+    /// Severity of matching errors
+    #[serde(default)]
+    pub severity: Option<ErrorSeverity>,
+    /// Impact of matching errors
+    #[serde(default)]
+    pub impact: Option<ErrorImpact>,
+    /// Description of matching errors
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Suggested remediation
+    #[serde(default)]
+    pub remediation: Option<String>,
+    /// Extra labels of matching errors
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+impl ClassificationRule {
+    /// Create a rule filing errors under `category`; add conditions with the
+    /// `matching_*` methods
+    pub fn new(name: impl Into<String>, category: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            category: category.into(),
+            pattern: None,
+            error_type: None,
+            code: None,
+            chain: None,
+            dispatch_error: None,
+            severity: None,
+            impact: None,
+            description: None,
+            remediation: None,
+            labels: BTreeMap::new(),
+        }
+    }
+
+    /// Match messages against a regular expression
+    pub fn matching_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    /// Match errors of a type
+    pub fn matching_error_type(mut self, error_type: impl Into<String>) -> Self {
+        self.error_type = Some(error_type.into());
+        self
+    }
+
+    /// Match errors with a code
+    pub fn matching_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Match errors of one chain only
+    pub fn matching_chain(mut self, chain: impl Into<String>) -> Self {
+        self.chain = Some(chain.into());
+        self
+    }
+
+    /// Match a dispatch error, `Pallet::Error` or `Pallet::*`
+    pub fn matching_dispatch_error(mut self, dispatch_error: impl Into<String>) -> Self {
+        self.dispatch_error = Some(dispatch_error.into());
+        self
+    }
+
+    /// Set the severity of matching errors
+    pub fn with_severity(mut self, severity: ErrorSeverity) -> Self {
+        self.severity = Some(severity);
+        self
+    }
+
+    /// Set the impact of matching errors
+    pub fn with_impact(mut self, impact: ErrorImpact) -> Self {
+        self.impact = Some(impact);
+        self
+    }
+
+    /// Set the description of matching errors
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add remediation suggestion
+    pub fn with_remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+
+    /// Add a label to matching errors
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    fn has_condition(&self) -> bool {
+        self.pattern.is_some()
+            || self.error_type.is_some()
+            || self.code.is_some()
+            || self.chain.is_some()
+            || self.dispatch_error.is_some()
+    }
+}
+
+/// Categories and rules loaded together, e.g. from a file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleSet {
+    /// Custom categories, registered before the rules are added
+    #[serde(default)]
+    pub categories: Vec<CustomCategory>,
+    /// Rules, tried in order
+    #[serde(default)]
+    pub rules: Vec<ClassificationRule>,
+}
+
+impl RuleSet {
+    /// Parse a TOML rule set
+    pub fn from_toml(source: &str) -> Result<Self> {
+        toml::from_str(source).map_err(|e| MetricsError::InvalidRule(e.to_string()))
+    }
+
+    /// Parse a JSON rule set
+    pub fn from_json(source: &str) -> Result<Self> {
+        serde_json::from_str(source).map_err(|e| MetricsError::InvalidRule(e.to_string()))
+    }
+}
+
+/// Rule with its pattern compiled
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    rule: ClassificationRule,
+    pattern: Option<Regex>,
+}
+
+impl CompiledRule {
+    fn matches(&self, source: &ErrorSource) -> bool {
+        let rule = &self.rule;
+        self.pattern
+            .as_ref()
+            .is_none_or(|pattern| pattern.is_match(&source.message))
+            && rule
+                .error_type
+                .as_ref()
+                .is_none_or(|error_type| source.error_type.as_ref() == Some(error_type))
+            && rule.code.is_none_or(|code| source.code == Some(code))
+            && rule.chain.as_ref().is_none_or(|chain| {
+                source
+                    .chain
+                    .as_ref()
+                    .is_some_and(|c| c.eq_ignore_ascii_case(chain))
+            })
+            && rule.dispatch_error.as_ref().is_none_or(|expected| {
+                source
+                    .dispatch_errors()
+                    .iter()
+                    .any(|actual| dispatch_error_matches(expected, actual))
+            })
+    }
+}
+
+/// Whether `actual` is the dispatch error `expected`, where `Pallet::*`
+/// matches any error of the pallet
+fn dispatch_error_matches(expected: &str, actual: &str) -> bool {
+    match expected.strip_suffix("::*") {
+        Some(pallet) => actual
+            .split_once("::")
+            .is_some_and(|(actual_pallet, _)| actual_pallet == pallet),
+        None => expected == actual,
+    }
+}
+
+/// Classifies errors with ordered rules, falling back to
+/// [`categorize_error`]
+#[derive(Debug, Clone, Default)]
+pub struct ErrorClassifier {
+    categories: HashMap<String, CustomCategory>,
+    rules: Vec<CompiledRule>,
+}
+
+impl ErrorClassifier {
+    /// Create a classifier without rules, which uses the built-in heuristics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a classifier from a rule set
+    pub fn from_rule_set(rule_set: RuleSet) -> Result<Self> {
+        let mut classifier = Self::new();
+        for category in rule_set.categories {
+            classifier.register_category(category);
+        }
+        for rule in rule_set.rules {
+            classifier.add_rule(rule)?;
+        }
+        Ok(classifier)
+    }
+
+    /// Create a classifier from a TOML rule set
+    pub fn from_toml(source: &str) -> Result<Self> {
+        Self::from_rule_set(RuleSet::from_toml(source)?)
+    }
+
+    /// Create a classifier from a JSON rule set
+    pub fn from_json(source: &str) -> Result<Self> {
+        Self::from_rule_set(RuleSet::from_json(source)?)
+    }
+
+    /// Register a custom category, replacing one with the same name
+    pub fn register_category(&mut self, category: CustomCategory) {
+        self.categories.insert(category.name.clone(), category);
+    }
+
+    /// Add a custom category
+    pub fn with_category(mut self, category: CustomCategory) -> Self {
+        self.register_category(category);
+        self
+    }
+
+    /// Append a rule, tried after the existing ones
+    ///
+    /// Fails if the rule has no condition, its pattern is not a valid
+    /// regular expression, or its category is neither built-in nor
+    /// registered.
+    pub fn add_rule(&mut self, rule: ClassificationRule) -> Result<()> {
+        if !rule.has_condition() {
+            return Err(MetricsError::InvalidRule(format!(
+                "Rule {} has no condition",
+                rule.name
+            )));
+        }
+        if ErrorCategory::from_name(&rule.category).is_none()
+            && !self.categories.contains_key(&rule.category)
+        {
+            return Err(MetricsError::InvalidRule(format!(
+                "Rule {} uses unknown category {}",
+                rule.name, rule.category
+            )));
+        }
+        let pattern = rule
+            .pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| MetricsError::InvalidRule(format!("Rule {}: {}", rule.name, e)))?;
+        self.rules.push(CompiledRule { rule, pattern });
+        Ok(())
+    }
+
+    /// Append a rule
+    pub fn with_rule(mut self, rule: ClassificationRule) -> Result<Self> {
+        self.add_rule(rule)?;
+        Ok(self)
+    }
+
+    /// Rules in the order they are tried
+    pub fn rules(&self) -> impl Iterator<Item = &ClassificationRule> {
+        self.rules.iter().map(|compiled| &compiled.rule)
+    }
+
+    /// Registered custom categories
+    pub fn categories(&self) -> impl Iterator<Item = &CustomCategory> {
+        self.categories.values()
+    }
+
+    /// Classify an error with the first matching rule, or the built-in
+    /// heuristics if none matches
+    pub fn classify(&self, source: &ErrorSource) -> ErrorClassification {
+        self.apply_rules(source)
+            .unwrap_or_else(|| categorize_error(&source.message, source.error_type.as_deref()))
+    }
+
+    /// Classification of the first matching rule, if any
+    ///
+    /// Lets callers with a typed classification of their own, such as
+    /// `categorize_dispatch_error` in `apex-sdk-substrate`, use it in place
+    /// of the heuristics while still honouring the rules.
+    pub fn apply_rules(&self, source: &ErrorSource) -> Option<ErrorClassification> {
+        self.rules
+            .iter()
+            .find(|compiled| compiled.matches(source))
+            .map(|compiled| self.apply(&compiled.rule))
+    }
+
+    fn apply(&self, rule: &ClassificationRule) -> ErrorClassification {
+        let mut classification = match self.categories.get(&rule.category) {
+            Some(custom) => {
+                let classification = ErrorClassification::new(
+                    custom.parent,
+                    custom.severity,
+                    custom.impact,
+                    custom.description.clone(),
+                )
+                .with_custom_category(custom.name.clone());
+                match &custom.remediation {
+                    Some(remediation) => classification.with_remediation(remediation.clone()),
+                    None => classification,
+                }
+            }
+            None => ErrorClassification::new(
+                ErrorCategory::from_name(&rule.category).unwrap_or(ErrorCategory::Internal),
+                ErrorSeverity::Medium,
+                ErrorImpact::Isolated,
+                format!("Matched rule {}", rule.name),
+            ),
+        };
+
+        if let Some(severity) = rule.severity {
+            classification.severity = severity;
+        }
+        if let Some(impact) = rule.impact {
+            classification.impact = impact;
+        }
+        if let Some(description) = &rule.description {
+            classification.description = description.clone();
+        }
+        if let Some(remediation) = &rule.remediation {
+            classification.remediation = Some(remediation.clone());
+        }
+        classification = classification.with_label("rule", rule.name.clone());
+        for (key, value) in &rule.labels {
+            classification = classification.with_label(key.clone(), value.clone());
+        }
+        if classification.is_retryable() {
+            classification = classification.with_label("retryable", "true");
+        }
+        classification
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = r#"
+[[categories]]
+name = "insufficient_funds"
+parent = "Transaction"
+severity = "Medium"
+impact = "Isolated"
+description = "Account cannot pay for the transaction"
+remediation = "Top up the account"
+
+[[rules]]
+name = "balances-insufficient"
+dispatch_error = "Balances::InsufficientBalance"
+category = "insufficient_funds"
+
+[[rules]]
+name = "assets"
+dispatch_error = "Assets::*"
+category = "ChainSpecific"
+labels = { pallet = "assets" }
+
+[[rules]]
+name = "moonbeam-gas-price"
+chain = "moonbeam"
+pattern = "(?i)gas price .* too low"
+category = "Transaction"
+impact = "Retryable"
+"#;
+
+    #[test]
+    fn test_toml_rules() {
+        let classifier = ErrorClassifier::from_toml(RULES).unwrap();
+        assert_eq!(classifier.rules().count(), 3);
+
+        // Dispatch errors are found in the message when not set
+        let funds = classifier.classify(&ErrorSource::new(
+            "Transaction failed: Balances::InsufficientBalance",
+        ));
+        assert_eq!(funds.category, ErrorCategory::Transaction);
+        assert_eq!(funds.custom_category.as_deref(), Some("insufficient_funds"));
+        assert_eq!(funds.remediation.as_deref(), Some("Top up the account"));
+        assert_eq!(
+            funds.to_prometheus_labels().get("rule").unwrap(),
+            "balances-insufficient"
+        );
+
+        let assets = classifier.classify(
+            &ErrorSource::new("dispatch failed").with_dispatch_error("Assets::NoAccount"),
+        );
+        assert_eq!(assets.category, ErrorCategory::ChainSpecific);
+        assert_eq!(assets.labels.get("pallet").unwrap(), "assets");
+
+        let gas = ErrorSource::new("Gas price 1 gwei too low");
+        assert!(classifier
+            .classify(&gas.clone().with_chain("Moonbeam"))
+            .is_retryable());
+        // Other chains fall back to the heuristics
+        let fallback = classifier.classify(&gas.with_chain("ethereum"));
+        assert_eq!(fallback.category, ErrorCategory::Transaction);
+        assert!(!fallback.is_retryable());
+        assert!(!fallback.labels.contains_key("rule"));
+    }
+
+    #[test]
+    fn test_code_rules_and_json() {
+        let classifier = ErrorClassifier::new()
+            .with_category(CustomCategory::new(
+                "fee_cap",
+                ErrorCategory::Configuration,
+                ErrorSeverity::Low,
+                ErrorImpact::Isolated,
+                "Fee exceeds the configured cap",
+            ))
+            .with_rule(
+                ClassificationRule::new("fee-cap", "fee_cap")
+                    .matching_code(ErrorCode::new(1210))
+                    .matching_error_type("SubstrateError::FeeCap"),
+            )
+            .unwrap();
+
+        let source = ErrorSource::new("fee too high")
+            .with_code(ErrorCode::new(1210))
+            .with_error_type("SubstrateError::FeeCap");
+        assert_eq!(
+            classifier.classify(&source).custom_category.as_deref(),
+            Some("fee_cap")
+        );
+        let other_code = source.clone().with_code(ErrorCode::new(1211));
+        assert_eq!(classifier.classify(&other_code).custom_category, None);
+
+        let json = r#"{"rules": [{"name": "timeouts", "pattern": "deadline", "category": "timeout", "impact": "Retryable"}]}"#;
+        let classifier = ErrorClassifier::from_json(json).unwrap();
+        let classification = classifier.classify(&ErrorSource::new("deadline exceeded"));
+        assert_eq!(classification.category, ErrorCategory::Timeout);
+        assert!(classification.is_retryable());
+
+        let mut stats = crate::ErrorStatistics::new();
+        stats.record(&classifier.classify(&ErrorSource::new("deadline exceeded")));
+        stats.record(
+            &ErrorClassifier::from_toml(RULES).unwrap().classify(
+                &ErrorSource::new("x").with_dispatch_error("Balances::InsufficientBalance"),
+            ),
+        );
+        assert_eq!(stats.by_custom_category.get("insufficient_funds"), Some(&1));
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let mut classifier = ErrorClassifier::new();
+        assert!(classifier
+            .add_rule(ClassificationRule::new("empty", "Network"))
+            .unwrap_err()
+            .to_string()
+            .contains("no condition"));
+        assert!(classifier
+            .add_rule(ClassificationRule::new("unknown", "billing").matching_pattern("x"))
+            .unwrap_err()
+            .to_string()
+            .contains("unknown category billing"));
+        assert!(classifier
+            .add_rule(ClassificationRule::new("regex", "Network").matching_pattern("("))
+            .is_err());
+        assert!(ErrorClassifier::from_toml("[[rules]]\nname = 1").is_err());
+        assert_eq!(classifier.rules().count(), 0);
+    }
+}
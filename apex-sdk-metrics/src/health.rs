@@ -3,11 +3,27 @@
 //! This module provides comprehensive health checking for SDK components,
 //! including RPC providers, metrics collection, and system resources.
 
+use apex_sdk_core::blocking::BlockingPool;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use sysinfo::System;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::{Disks, Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// Default maximum age of a cached system resource sample
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Component reported as degraded when a resource threshold is exceeded
+pub const RESOURCES_COMPONENT: &str = "system_resources";
+
+/// Status transitions kept by [`HealthChecker::events`]
+pub const MAX_HEALTH_EVENTS: usize = 256;
+
+/// Shortest interval and timeout of a scheduled probe
+pub const MIN_PROBE_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Health status for a component
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -116,7 +132,7 @@ impl HealthSummary {
 }
 
 /// System resource information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SystemResources {
     /// CPU usage percentage
     pub cpu_usage_percent: f32,
@@ -126,47 +142,494 @@ pub struct SystemResources {
     pub memory_total_bytes: u64,
     /// Memory usage percentage
     pub memory_usage_percent: f32,
+    /// CPU usage of the SDK process, as a percentage of one core
+    #[serde(default)]
+    pub process_cpu_usage_percent: f32,
+    /// Resident set size of the SDK process in bytes
+    #[serde(default)]
+    pub process_memory_bytes: u64,
+    /// File descriptors open in the SDK process (Linux only)
+    #[serde(default)]
+    pub open_file_descriptors: Option<u64>,
+    /// Soft limit on open file descriptors (Linux only)
+    #[serde(default)]
+    pub file_descriptor_limit: Option<u64>,
+    /// Network sockets open in the SDK process (Linux only)
+    #[serde(default)]
+    pub open_sockets: Option<u64>,
+    /// Usage of the filesystems holding the configured data directories
+    #[serde(default)]
+    pub data_dirs: Vec<DiskUsage>,
+}
+
+/// Usage of the filesystem holding a data directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskUsage {
+    /// Data directory name, e.g. `keystore`
+    pub name: String,
+    /// Data directory path
+    pub path: PathBuf,
+    /// Mount point of the filesystem holding it
+    pub mount_point: PathBuf,
+    /// Filesystem size in bytes
+    pub total_bytes: u64,
+    /// Bytes available to the SDK process
+    pub available_bytes: u64,
+    /// Used space percentage
+    pub usage_percent: f32,
+}
+
+/// Resource levels above which [`RESOURCES_COMPONENT`] is reported degraded
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResourceThresholds {
+    /// Maximum used space percentage of a data directory's filesystem
+    pub disk_usage_percent: f32,
+    /// Maximum open file descriptors as a percentage of the process limit
+    pub file_descriptor_percent: f32,
+}
+
+impl Default for ResourceThresholds {
+    fn default() -> Self {
+        Self {
+            disk_usage_percent: 90.0,
+            file_descriptor_percent: 80.0,
+        }
+    }
+}
+
+impl ResourceThresholds {
+    /// Describe every threshold `resources` exceeds
+    pub fn violations(&self, resources: &SystemResources) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        for disk in &resources.data_dirs {
+            if disk.usage_percent > self.disk_usage_percent {
+                violations.push(format!(
+                    "data directory '{}' is {:.1}% full ({} bytes free on {})",
+                    disk.name,
+                    disk.usage_percent,
+                    disk.available_bytes,
+                    disk.mount_point.display()
+                ));
+            }
+        }
+
+        if let (Some(open), Some(limit)) = (
+            resources.open_file_descriptors,
+            resources.file_descriptor_limit,
+        ) {
+            if limit > 0 && (open as f32 / limit as f32) * 100.0 > self.file_descriptor_percent {
+                violations.push(format!(
+                    "{} of {} file descriptors in use ({} sockets)",
+                    open,
+                    limit,
+                    resources.open_sockets.unwrap_or_default()
+                ));
+            }
+        }
+
+        violations
+    }
+}
+
+/// Future returned by a scheduled health probe
+pub type ProbeFuture = Pin<Box<dyn Future<Output = ComponentHealth> + Send>>;
+
+/// Schedule and failure handling of a registered probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeConfig {
+    /// Time between probe runs
+    pub interval: Duration,
+    /// Time after which a run counts as failed
+    pub timeout: Duration,
+    /// Consecutive failed runs before the component is reported unhealthy
+    ///
+    /// Earlier failures are reported as degraded, so a single slow or
+    /// dropped request does not flip the component to unhealthy.
+    pub failure_threshold: u32,
+}
+
+impl ProbeConfig {
+    /// Probe every `interval`, timing out after the interval or 10 seconds,
+    /// whichever is shorter, and failing after 3 consecutive failed runs
+    ///
+    /// Intervals shorter than [`MIN_PROBE_INTERVAL`], including zero, are
+    /// raised to it.
+    pub fn new(interval: Duration) -> Self {
+        let interval = interval.max(MIN_PROBE_INTERVAL);
+        Self {
+            interval,
+            timeout: interval.min(Duration::from_secs(10)),
+            failure_threshold: 3,
+        }
+    }
+
+    /// Set the time after which a run counts as failed, at least
+    /// [`MIN_PROBE_INTERVAL`]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout.max(MIN_PROBE_INTERVAL);
+        self
+    }
+
+    /// Set the consecutive failed runs before the component is unhealthy
+    pub fn with_failure_threshold(mut self, threshold: u32) -> Self {
+        self.failure_threshold = threshold.max(1);
+        self
+    }
+}
+
+/// A component's health status changing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthEvent {
+    /// Component name
+    pub component: String,
+    /// Previous status, [`HealthStatus::Unknown`] for a new component
+    pub from: HealthStatus,
+    /// New status
+    pub to: HealthStatus,
+    /// Status message of the new status
+    pub message: Option<String>,
+    /// Unix timestamp of the change
+    pub timestamp: u64,
+}
+
+/// Component statuses and their transitions, shared with probe tasks
+#[derive(Default)]
+struct Registry {
+    components: Mutex<HashMap<String, ComponentHealth>>,
+    events: Mutex<VecDeque<HealthEvent>>,
+}
+
+impl Registry {
+    /// Store a component's health, recording an event if its status changed
+    fn update(&self, health: ComponentHealth) {
+        let from = self
+            .components()
+            .insert(health.name.clone(), health.clone())
+            .map_or(HealthStatus::Unknown, |previous| previous.status);
+        if from == health.status {
+            return;
+        }
+
+        tracing::info!(
+            component = %health.name,
+            from = ?from,
+            to = ?health.status,
+            message = health.message.as_deref().unwrap_or_default(),
+            "Component health changed"
+        );
+
+        let mut events = self
+            .events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if events.len() == MAX_HEALTH_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(HealthEvent {
+            component: health.name,
+            from,
+            to: health.status,
+            message: health.message,
+            timestamp: health.last_check,
+        });
+    }
+
+    fn components(&self) -> std::sync::MutexGuard<'_, HashMap<String, ComponentHealth>> {
+        self.components
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Run a probe forever, storing each result under `name`
+async fn run_probe(
+    registry: Arc<Registry>,
+    name: String,
+    config: ProbeConfig,
+    probe: Arc<dyn Fn() -> ProbeFuture + Send + Sync>,
+) {
+    // The fields are public, so a config may bypass the clamping of its
+    // constructor; a zero period would panic the interval
+    let timeout = config.timeout.max(MIN_PROBE_INTERVAL);
+    let mut ticker = tokio::time::interval(config.interval.max(MIN_PROBE_INTERVAL));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut failures = 0u32;
+
+    loop {
+        ticker.tick().await;
+        let started = Instant::now();
+        let mut health = match tokio::time::timeout(timeout, probe()).await {
+            Ok(health) => health,
+            Err(_) => ComponentHealth::new(&name, HealthStatus::Unhealthy)
+                .with_message(format!("Probe timed out after {:?}", timeout)),
+        };
+        health.name = name.clone();
+        if health.response_time_ms.is_none() {
+            health = health.with_response_time(started.elapsed());
+        }
+
+        if health.status == HealthStatus::Unhealthy {
+            failures += 1;
+            health = health.with_metadata("consecutive_failures", failures.to_string());
+            if failures < config.failure_threshold {
+                // Not yet unhealthy: a single failed run only degrades it
+                health.status = HealthStatus::Degraded;
+            }
+        } else {
+            failures = 0;
+        }
+
+        registry.update(health);
+    }
 }
 
 /// Health checker for monitoring component health
 pub struct HealthChecker {
-    components: Arc<Mutex<HashMap<String, ComponentHealth>>>,
+    registry: Arc<Registry>,
+    probes: Mutex<HashMap<String, tokio::task::JoinHandle<()>>>,
     start_time: SystemTime,
+    probe: Arc<Mutex<ResourceProbe>>,
+    latest: Arc<Mutex<Option<(Instant, SystemResources)>>>,
+    sample_interval: Duration,
+    thresholds: ResourceThresholds,
+    blocking: Arc<BlockingPool>,
 }
 
 impl HealthChecker {
     /// Create a new health checker
     pub fn new() -> Self {
         Self {
-            components: Arc::new(Mutex::new(HashMap::new())),
+            registry: Arc::new(Registry::default()),
+            probes: Mutex::new(HashMap::new()),
             start_time: SystemTime::now(),
+            probe: Arc::new(Mutex::new(ResourceProbe::new())),
+            latest: Arc::new(Mutex::new(None)),
+            sample_interval: DEFAULT_SAMPLE_INTERVAL,
+            thresholds: ResourceThresholds::default(),
+            blocking: BlockingPool::global(),
         }
     }
 
+    /// Report disk usage of the filesystem holding a data directory
+    ///
+    /// Use for directories whose filesystem filling up breaks the SDK, such as
+    /// the keystore or an indexer store.
+    pub fn with_data_dir(self, name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        self.probe
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .data_dirs
+            .push((name.into(), path.into()));
+        self
+    }
+
+    /// Set the resource levels that degrade [`RESOURCES_COMPONENT`]
+    pub fn with_resource_thresholds(mut self, thresholds: ResourceThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Reuse system resource samples younger than `interval`
+    ///
+    /// Also the period of [`HealthChecker::spawn_sampler`]. Defaults to
+    /// [`DEFAULT_SAMPLE_INTERVAL`].
+    pub fn with_sample_interval(mut self, interval: Duration) -> Self {
+        self.sample_interval = interval;
+        self
+    }
+
+    /// Probe system resources on a specific blocking pool
+    ///
+    /// Defaults to [`BlockingPool::global`].
+    pub fn with_blocking_pool(mut self, pool: Arc<BlockingPool>) -> Self {
+        self.blocking = pool;
+        self
+    }
+
     /// Register or update a component health status
+    ///
+    /// A change of status is recorded as a [`HealthEvent`].
     pub fn update_component(&self, health: ComponentHealth) {
-        if let Ok(mut components) = self.components.lock() {
-            components.insert(health.name.clone(), health);
-        }
+        self.registry.update(health);
     }
 
     /// Get health status for a specific component
     pub fn get_component(&self, name: &str) -> Option<ComponentHealth> {
-        self.components
+        self.registry.components().get(name).cloned()
+    }
+
+    /// Probe a component every `interval` in the background
+    ///
+    /// Uses [`ProbeConfig::new`] for the timeout and failure threshold; see
+    /// [`register_probe_with`](Self::register_probe_with).
+    pub fn register_probe<F, Fut>(&self, name: impl Into<String>, interval: Duration, probe: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ComponentHealth> + Send + 'static,
+    {
+        self.register_probe_with(name, ProbeConfig::new(interval), probe);
+    }
+
+    /// Probe a component on the schedule in `config` in the background
+    ///
+    /// Each result is stored under `name`, whatever name the probe gives it.
+    /// A run that exceeds the timeout counts as an unhealthy result. An
+    /// unhealthy result is reported as degraded until `failure_threshold`
+    /// runs in a row have failed, and any other result resets the count.
+    /// Registering a probe under a name already in use replaces it. Probes
+    /// stop when unregistered or when the checker is dropped.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn register_probe_with<F, Fut>(
+        &self,
+        name: impl Into<String>,
+        config: ProbeConfig,
+        probe: F,
+    ) where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ComponentHealth> + Send + 'static,
+    {
+        let name = name.into();
+        let probe: Arc<dyn Fn() -> ProbeFuture + Send + Sync> =
+            Arc::new(move || Box::pin(probe()) as ProbeFuture);
+        let task = tokio::spawn(run_probe(
+            self.registry.clone(),
+            name.clone(),
+            config,
+            probe,
+        ));
+        if let Some(previous) = self.lock_probes().insert(name, task) {
+            previous.abort();
+        }
+    }
+
+    /// Stop probing a component and remove its status
+    pub fn unregister_probe(&self, name: &str) {
+        if let Some(task) = self.lock_probes().remove(name) {
+            task.abort();
+        }
+        self.remove_component(name);
+    }
+
+    /// Names of the components probed in the background
+    pub fn probe_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.lock_probes().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Recent component status changes, oldest first
+    ///
+    /// At most [`MAX_HEALTH_EVENTS`] are kept.
+    pub fn events(&self) -> Vec<HealthEvent> {
+        self.registry
+            .events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    fn lock_probes(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<String, tokio::task::JoinHandle<()>>> {
+        self.probes
             .lock()
-            .ok()
-            .and_then(|components| components.get(name).cloned())
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
     }
 
     /// Get health summary for all components
+    ///
+    /// Samples system resources on the calling thread if the cached sample is
+    /// stale; from async code use [`HealthChecker::health_summary_async`] instead.
     pub fn health_summary(&self) -> HealthSummary {
-        let components: Vec<ComponentHealth> = self
-            .components
+        let resources = match self.fresh_sample() {
+            Some(resources) => resources,
+            None => Self::sample(&self.probe, &self.latest),
+        };
+        self.summarize(resources)
+    }
+
+    /// Get health summary, sampling system resources on the blocking pool
+    pub async fn health_summary_async(&self) -> HealthSummary {
+        let resources = self.system_resources().await;
+        self.summarize(resources)
+    }
+
+    /// Current system resources
+    ///
+    /// Returns the cached sample if it is younger than the sample interval,
+    /// otherwise samples on the blocking pool.
+    pub async fn system_resources(&self) -> SystemResources {
+        if let Some(resources) = self.fresh_sample() {
+            return resources;
+        }
+        self.refresh_resources().await
+    }
+
+    /// Most recent system resource sample, however old
+    pub fn latest_resources(&self) -> Option<SystemResources> {
+        self.latest
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner())
-            .values()
-            .cloned()
-            .collect();
+            .as_ref()
+            .map(|(_, resources)| resources.clone())
+    }
+
+    /// Sample system resources every sample interval in the background
+    ///
+    /// Keeps the cache warm so health summaries and metric scrapes never wait
+    /// on `sysinfo`. CPU figures are deltas between consecutive samples, so
+    /// they are only meaningful from the second sample on.
+    pub fn spawn_sampler(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.sample_interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                self.refresh_resources().await;
+            }
+        })
+    }
+
+    async fn refresh_resources(&self) -> SystemResources {
+        let probe = self.probe.clone();
+        let latest = self.latest.clone();
+        match self
+            .blocking
+            .run(move || Self::sample(&probe, &latest))
+            .await
+        {
+            Ok(resources) => resources,
+            Err(e) => {
+                tracing::warn!(error = %e, "System resource probe failed");
+                self.latest_resources().unwrap_or_default()
+            }
+        }
+    }
+
+    fn fresh_sample(&self) -> Option<SystemResources> {
+        self.latest
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .as_ref()
+            .filter(|(sampled_at, _)| sampled_at.elapsed() < self.sample_interval)
+            .map(|(_, resources)| resources.clone())
+    }
+
+    fn summarize(&self, resources: SystemResources) -> HealthSummary {
+        let mut components: Vec<ComponentHealth> =
+            self.registry.components().values().cloned().collect();
+
+        let violations = self.thresholds.violations(&resources);
+        if !violations.is_empty() {
+            components.push(
+                ComponentHealth::new(RESOURCES_COMPONENT, HealthStatus::Degraded)
+                    .with_message(violations.join("; ")),
+            );
+        }
 
         let overall_status = if components.is_empty() {
             HealthStatus::Unknown
@@ -191,14 +654,80 @@ impl HealthChecker {
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs(),
-            resources: Self::get_system_resources(),
+            resources,
+        }
+    }
+
+    /// Sample resources and cache the result; blocks while `sysinfo` reads /proc
+    fn sample(
+        probe: &Mutex<ResourceProbe>,
+        latest: &Mutex<Option<(Instant, SystemResources)>>,
+    ) -> SystemResources {
+        let resources = probe
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .probe();
+        *latest
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+            Some((Instant::now(), resources.clone()));
+        resources
+    }
+
+    /// Remove a component from monitoring
+    pub fn remove_component(&self, name: &str) {
+        self.registry.components().remove(name);
+    }
+
+    /// Clear all component statuses
+    pub fn clear(&self) {
+        self.registry.components().clear();
+    }
+
+    /// Get component count
+    pub fn component_count(&self) -> usize {
+        self.registry.components().len()
+    }
+}
+
+impl Drop for HealthChecker {
+    fn drop(&mut self) {
+        for (_, task) in self.lock_probes().drain() {
+            task.abort();
+        }
+    }
+}
+
+impl Default for HealthChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `sysinfo` state reused between samples, so each sample refreshes incrementally
+struct ResourceProbe {
+    system: System,
+    /// Listed on first use, only when data directories are configured
+    disks: Option<Disks>,
+    pid: Option<Pid>,
+    data_dirs: Vec<(String, PathBuf)>,
+}
+
+impl ResourceProbe {
+    fn new() -> Self {
+        Self {
+            system: System::new(),
+            disks: None,
+            pid: sysinfo::get_current_pid().ok(),
+            data_dirs: Vec::new(),
         }
     }
 
-    /// Get system resource information
-    fn get_system_resources() -> SystemResources {
-        let mut sys = System::new_all();
-        sys.refresh_all();
+    /// Refresh CPU, memory, own-process and data directory figures
+    fn probe(&mut self) -> SystemResources {
+        let sys = &mut self.system;
+        sys.refresh_memory();
+        sys.refresh_cpu_usage();
 
         let cpu_usage = sys.global_cpu_usage();
         let memory_used = sys.used_memory();
@@ -209,41 +738,115 @@ impl HealthChecker {
             0.0
         };
 
+        let process = self.pid.and_then(|pid| {
+            sys.refresh_processes_specifics(
+                ProcessesToUpdate::Some(&[pid]),
+                true,
+                ProcessRefreshKind::nothing().with_cpu().with_memory(),
+            );
+            sys.process(pid)
+        });
+
+        let data_dirs = if self.data_dirs.is_empty() {
+            Vec::new()
+        } else {
+            let disks = match &mut self.disks {
+                Some(disks) => {
+                    disks.refresh(false);
+                    disks
+                }
+                None => self.disks.insert(Disks::new_with_refreshed_list()),
+            };
+            self.data_dirs
+                .iter()
+                .filter_map(|(name, path)| disk_usage(disks, name, path))
+                .collect()
+        };
+
+        let (open_file_descriptors, open_sockets) = match file_descriptors() {
+            Some((fds, sockets)) => (Some(fds), Some(sockets)),
+            None => (None, None),
+        };
+
         SystemResources {
             cpu_usage_percent: cpu_usage,
             memory_used_bytes: memory_used,
             memory_total_bytes: memory_total,
             memory_usage_percent: memory_percent,
+            process_cpu_usage_percent: process.map(|p| p.cpu_usage()).unwrap_or_default(),
+            process_memory_bytes: process.map(|p| p.memory()).unwrap_or_default(),
+            open_file_descriptors,
+            file_descriptor_limit: file_descriptor_limit(),
+            open_sockets,
+            data_dirs,
         }
     }
+}
 
-    /// Remove a component from monitoring
-    pub fn remove_component(&self, name: &str) {
-        if let Ok(mut components) = self.components.lock() {
-            components.remove(name);
-        }
-    }
+/// Usage of the filesystem with the longest mount point containing `path`
+fn disk_usage(disks: &Disks, name: &str, path: &Path) -> Option<DiskUsage> {
+    let canonical = path.canonicalize().ok()?;
+    let disk = disks
+        .list()
+        .iter()
+        .filter(|disk| canonical.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())?;
 
-    /// Clear all component statuses
-    pub fn clear(&self) {
-        if let Ok(mut components) = self.components.lock() {
-            components.clear();
+    let total_bytes = disk.total_space();
+    let available_bytes = disk.available_space();
+    let usage_percent = if total_bytes > 0 {
+        (total_bytes.saturating_sub(available_bytes) as f32 / total_bytes as f32) * 100.0
+    } else {
+        0.0
+    };
+
+    Some(DiskUsage {
+        name: name.to_string(),
+        path: path.to_path_buf(),
+        mount_point: disk.mount_point().to_path_buf(),
+        total_bytes,
+        available_bytes,
+        usage_percent,
+    })
+}
+
+/// Open file descriptors and sockets of this process
+#[cfg(target_os = "linux")]
+fn file_descriptors() -> Option<(u64, u64)> {
+    let mut fds = 0;
+    let mut sockets = 0;
+    for entry in std::fs::read_dir("/proc/self/fd").ok()?.flatten() {
+        fds += 1;
+        if std::fs::read_link(entry.path())
+            .is_ok_and(|target| target.to_string_lossy().starts_with("socket:"))
+        {
+            sockets += 1;
         }
     }
+    Some((fds, sockets))
+}
 
-    /// Get component count
-    pub fn component_count(&self) -> usize {
-        self.components
-            .lock()
-            .unwrap_or_else(|poisoned| poisoned.into_inner())
-            .len()
-    }
+#[cfg(not(target_os = "linux"))]
+fn file_descriptors() -> Option<(u64, u64)> {
+    None
 }
 
-impl Default for HealthChecker {
-    fn default() -> Self {
-        Self::new()
-    }
+/// Soft `RLIMIT_NOFILE` of this process
+#[cfg(target_os = "linux")]
+fn file_descriptor_limit() -> Option<u64> {
+    let limits = std::fs::read_to_string("/proc/self/limits").ok()?;
+    limits
+        .lines()
+        .find_map(|line| line.strip_prefix("Max open files"))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn file_descriptor_limit() -> Option<u64> {
+    None
 }
 
 #[cfg(test)]
@@ -296,9 +899,237 @@ mod tests {
 
     #[test]
     fn test_system_resources() {
-        let resources = HealthChecker::get_system_resources();
+        let resources = ResourceProbe::new().probe();
         assert!(resources.memory_total_bytes > 0);
         assert!(resources.memory_usage_percent >= 0.0);
         assert!(resources.cpu_usage_percent >= 0.0);
+        assert!(resources.process_memory_bytes > 0);
+        assert!(resources.process_memory_bytes <= resources.memory_total_bytes);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_file_descriptor_counts() {
+        let _socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let resources = ResourceProbe::new().probe();
+
+        assert!(resources.open_file_descriptors.unwrap() >= 3);
+        assert!(resources.open_sockets.unwrap() >= 1);
+        assert!(resources.file_descriptor_limit.unwrap() > 0);
+    }
+
+    #[test]
+    fn test_data_dir_usage() {
+        let dir = std::env::temp_dir();
+        let mut probe = ResourceProbe::new();
+        probe.data_dirs.push(("tmp".to_string(), dir.clone()));
+        probe
+            .data_dirs
+            .push(("missing".to_string(), dir.join("apex-sdk-missing-dir")));
+
+        let resources = probe.probe();
+        assert!(resources.data_dirs.len() <= 1);
+        if let Some(usage) = resources.data_dirs.first() {
+            assert_eq!(usage.name, "tmp");
+            assert!(usage.available_bytes <= usage.total_bytes);
+            assert!((0.0..=100.0).contains(&usage.usage_percent));
+        }
+    }
+
+    #[test]
+    fn test_thresholds_degrade_health() {
+        let resources = SystemResources {
+            open_file_descriptors: Some(900),
+            file_descriptor_limit: Some(1024),
+            open_sockets: Some(850),
+            data_dirs: vec![DiskUsage {
+                name: "keystore".to_string(),
+                path: PathBuf::from("/var/lib/apex/keystore"),
+                mount_point: PathBuf::from("/var"),
+                total_bytes: 1000,
+                available_bytes: 20,
+                usage_percent: 98.0,
+            }],
+            ..Default::default()
+        };
+
+        let violations = ResourceThresholds::default().violations(&resources);
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].contains("keystore"));
+        assert!(violations[1].contains("900 of 1024"));
+
+        let relaxed = ResourceThresholds {
+            disk_usage_percent: 99.0,
+            file_descriptor_percent: 95.0,
+        };
+        assert!(relaxed.violations(&resources).is_empty());
+
+        let checker = HealthChecker::new();
+        checker.update_component(ComponentHealth::new("rpc", HealthStatus::Healthy));
+        let summary = checker.summarize(resources);
+        assert_eq!(summary.status, HealthStatus::Degraded);
+        assert!(summary
+            .components
+            .iter()
+            .any(|c| c.name == RESOURCES_COMPONENT && c.status == HealthStatus::Degraded));
+    }
+
+    #[tokio::test]
+    async fn test_system_resources_are_cached() {
+        let pool = Arc::new(BlockingPool::new("health", 1));
+        let checker = HealthChecker::new()
+            .with_blocking_pool(pool.clone())
+            .with_sample_interval(Duration::from_secs(60));
+        assert!(checker.latest_resources().is_none());
+
+        checker.system_resources().await;
+        checker.system_resources().await;
+        checker.health_summary();
+        assert_eq!(pool.stats().completed, 1);
+        assert!(checker.latest_resources().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_sampler_refreshes_cache() {
+        let pool = Arc::new(BlockingPool::new("sampler", 1));
+        let checker = Arc::new(
+            HealthChecker::new()
+                .with_blocking_pool(pool.clone())
+                .with_sample_interval(Duration::from_millis(10)),
+        );
+
+        let sampler = checker.clone().spawn_sampler();
+        while pool.stats().completed < 3 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        sampler.abort();
+
+        let resources = checker.latest_resources().unwrap();
+        assert!(resources.process_memory_bytes > 0);
+    }
+
+    #[tokio::test]
+    async fn test_health_summary_async_uses_blocking_pool() {
+        let pool = Arc::new(BlockingPool::new("health", 1));
+        let checker = HealthChecker::new().with_blocking_pool(pool.clone());
+        checker.update_component(ComponentHealth::new("rpc", HealthStatus::Healthy));
+
+        let summary = checker.health_summary_async().await;
+        assert_eq!(summary.status, HealthStatus::Healthy);
+        assert!(summary.resources.memory_total_bytes > 0);
+        assert_eq!(pool.stats().completed, 1);
+    }
+
+    #[test]
+    fn test_status_transitions_are_recorded() {
+        let checker = HealthChecker::new();
+        checker.update_component(ComponentHealth::new("rpc", HealthStatus::Healthy));
+        checker.update_component(ComponentHealth::new("rpc", HealthStatus::Healthy));
+        checker.update_component(
+            ComponentHealth::new("rpc", HealthStatus::Unhealthy).with_message("refused"),
+        );
+
+        let events = checker.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].from, HealthStatus::Unknown);
+        assert_eq!(events[0].to, HealthStatus::Healthy);
+        assert_eq!(events[1].from, HealthStatus::Healthy);
+        assert_eq!(events[1].to, HealthStatus::Unhealthy);
+        assert_eq!(events[1].message.as_deref(), Some("refused"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_probe_failure_threshold_and_timeout() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let checker = HealthChecker::new();
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = runs.clone();
+        checker.register_probe_with(
+            "node",
+            ProbeConfig::new(Duration::from_secs(10))
+                .with_timeout(Duration::from_secs(1))
+                .with_failure_threshold(2),
+            move || {
+                let run = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if run == 0 {
+                        ComponentHealth::new("ignored", HealthStatus::Healthy)
+                    } else {
+                        // Hangs past the timeout
+                        tokio::time::sleep(Duration::from_secs(60)).await;
+                        ComponentHealth::new("ignored", HealthStatus::Healthy)
+                    }
+                }
+            },
+        );
+        assert_eq!(checker.probe_names(), vec!["node".to_string()]);
+
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(
+            checker.get_component("node").unwrap().status,
+            HealthStatus::Healthy
+        );
+
+        tokio::time::sleep(Duration::from_secs(11)).await;
+        let node = checker.get_component("node").unwrap();
+        assert_eq!(node.status, HealthStatus::Degraded);
+        assert!(node.message.unwrap().contains("timed out"));
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert_eq!(
+            checker.get_component("node").unwrap().status,
+            HealthStatus::Unhealthy
+        );
+
+        let transitions: Vec<_> = checker.events().iter().map(|e| e.to).collect();
+        assert_eq!(
+            transitions,
+            vec![
+                HealthStatus::Healthy,
+                HealthStatus::Degraded,
+                HealthStatus::Unhealthy
+            ]
+        );
+
+        checker.unregister_probe("node");
+        assert!(checker.probe_names().is_empty());
+        assert!(checker.get_component("node").is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_zero_probe_interval_is_clamped() {
+        let config = ProbeConfig::new(Duration::ZERO).with_timeout(Duration::ZERO);
+        assert_eq!(config.interval, MIN_PROBE_INTERVAL);
+        assert_eq!(config.timeout, MIN_PROBE_INTERVAL);
+
+        let checker = HealthChecker::new();
+        let healthy = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let status = healthy.clone();
+        checker.register_probe("node", Duration::ZERO, move || {
+            let healthy = status.load(std::sync::atomic::Ordering::SeqCst);
+            async move {
+                let status = if healthy {
+                    HealthStatus::Healthy
+                } else {
+                    HealthStatus::Unhealthy
+                };
+                ComponentHealth::new("ignored", status)
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(
+            checker.get_component("node").unwrap().status,
+            HealthStatus::Healthy
+        );
+
+        // The probe keeps running on the clamped interval
+        healthy.store(false, std::sync::atomic::Ordering::SeqCst);
+        tokio::time::sleep(MIN_PROBE_INTERVAL * 3).await;
+        assert_eq!(
+            checker.get_component("node").unwrap().status,
+            HealthStatus::Unhealthy
+        );
     }
 }
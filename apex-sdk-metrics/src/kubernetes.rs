@@ -0,0 +1,225 @@
+//! Kubernetes pod metadata
+//!
+//! Replicas of the same deployment report identical service names, so their
+//! traces and metrics cannot be told apart without knowing which pod produced
+//! them. [`KubernetesMetadata::detect`] reads the pod name, namespace, node
+//! and container from environment variables (typically populated through the
+//! downward API) and the service account mount, for use as OpenTelemetry
+//! resource attributes and Prometheus const labels.
+//!
+//! Expose the fields with the downward API in the pod spec:
+//!
+//! ```yaml
+//! env:
+//!   - name: POD_NAME
+//!     valueFrom: { fieldRef: { fieldPath: metadata.name } }
+//!   - name: POD_NAMESPACE
+//!     valueFrom: { fieldRef: { fieldPath: metadata.namespace } }
+//!   - name: NODE_NAME
+//!     valueFrom: { fieldRef: { fieldPath: spec.nodeName } }
+//!   - name: CONTAINER_NAME
+//!     value: apex-indexer
+//! ```
+//!
+//! Without them, the pod name falls back to `HOSTNAME` and the namespace to
+//! the service account mount, both of which Kubernetes sets by default.
+
+use opentelemetry::KeyValue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Environment variables holding the pod name, in order of precedence
+pub const POD_NAME_ENV: &[&str] = &["POD_NAME", "K8S_POD_NAME"];
+
+/// Environment variables holding the pod namespace, in order of precedence
+pub const POD_NAMESPACE_ENV: &[&str] = &["POD_NAMESPACE", "K8S_NAMESPACE"];
+
+/// Environment variables holding the node name, in order of precedence
+pub const NODE_NAME_ENV: &[&str] = &["NODE_NAME", "K8S_NODE_NAME"];
+
+/// Environment variables holding the container name, in order of precedence
+pub const CONTAINER_NAME_ENV: &[&str] = &["CONTAINER_NAME", "K8S_CONTAINER_NAME"];
+
+/// Set by Kubernetes in every container
+const SERVICE_HOST_ENV: &str = "KUBERNETES_SERVICE_HOST";
+
+const SERVICE_ACCOUNT_NAMESPACE: &str = "/var/run/secrets/kubernetes.io/serviceaccount/namespace";
+
+/// Identity of the pod the SDK runs in
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KubernetesMetadata {
+    /// Pod name
+    pub pod_name: Option<String>,
+    /// Pod namespace
+    pub namespace: Option<String>,
+    /// Node the pod is scheduled on
+    pub node_name: Option<String>,
+    /// Container name within the pod
+    pub container_name: Option<String>,
+}
+
+impl KubernetesMetadata {
+    /// Detect pod metadata from the environment
+    ///
+    /// Returns `None` outside Kubernetes, i.e. when neither
+    /// `KUBERNETES_SERVICE_HOST` nor any of the metadata variables is set.
+    pub fn detect() -> Option<Self> {
+        Self::detect_with(
+            |name| std::env::var(name).ok(),
+            || std::fs::read_to_string(SERVICE_ACCOUNT_NAMESPACE).ok(),
+        )
+    }
+
+    fn detect_with(
+        env: impl Fn(&str) -> Option<String>,
+        service_account_namespace: impl FnOnce() -> Option<String>,
+    ) -> Option<Self> {
+        let first = |names: &[&str]| {
+            names
+                .iter()
+                .filter_map(|name| env(name))
+                .map(|value| value.trim().to_string())
+                .find(|value| !value.is_empty())
+        };
+
+        let mut metadata = Self {
+            pod_name: first(POD_NAME_ENV),
+            namespace: first(POD_NAMESPACE_ENV),
+            node_name: first(NODE_NAME_ENV),
+            container_name: first(CONTAINER_NAME_ENV),
+        };
+
+        let in_cluster = env(SERVICE_HOST_ENV).is_some();
+        if !in_cluster && metadata.is_empty() {
+            return None;
+        }
+
+        if in_cluster {
+            if metadata.pod_name.is_none() {
+                metadata.pod_name = first(&["HOSTNAME"]);
+            }
+            if metadata.namespace.is_none() {
+                metadata.namespace = service_account_namespace()
+                    .map(|namespace| namespace.trim().to_string())
+                    .filter(|namespace| !namespace.is_empty());
+            }
+        }
+        Some(metadata)
+    }
+
+    /// Whether no field is known
+    pub fn is_empty(&self) -> bool {
+        self.pod_name.is_none()
+            && self.namespace.is_none()
+            && self.node_name.is_none()
+            && self.container_name.is_none()
+    }
+
+    /// OpenTelemetry resource attributes, using the semantic convention keys
+    pub fn resource_attributes(&self) -> Vec<KeyValue> {
+        self.fields([
+            "k8s.pod.name",
+            "k8s.namespace.name",
+            "k8s.node.name",
+            "k8s.container.name",
+        ])
+        .map(|(key, value)| KeyValue::new(key, value.to_string()))
+        .collect()
+    }
+
+    /// Prometheus const labels: `pod`, `namespace`, `node` and `container`
+    pub fn const_labels(&self) -> HashMap<String, String> {
+        self.fields(["pod", "namespace", "node", "container"])
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn fields(&self, keys: [&'static str; 4]) -> impl Iterator<Item = (&'static str, &str)> {
+        keys.into_iter()
+            .zip([
+                &self.pod_name,
+                &self.namespace,
+                &self.node_name,
+                &self.container_name,
+            ])
+            .filter_map(|(key, value)| value.as_deref().map(|value| (key, value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_not_detected_outside_kubernetes() {
+        let metadata = KubernetesMetadata::detect_with(env(&[("HOSTNAME", "laptop")]), || {
+            panic!("service account must not be read outside Kubernetes")
+        });
+        assert_eq!(metadata, None);
+    }
+
+    #[test]
+    fn test_detects_downward_api_env() {
+        let metadata = KubernetesMetadata::detect_with(
+            env(&[
+                ("KUBERNETES_SERVICE_HOST", "10.0.0.1"),
+                ("POD_NAME", "indexer-7d9f-abcde"),
+                ("K8S_NAMESPACE", "chains"),
+                ("NODE_NAME", "node-3"),
+                ("CONTAINER_NAME", "apex"),
+                ("HOSTNAME", "ignored"),
+            ]),
+            || Some("ignored".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(metadata.pod_name.as_deref(), Some("indexer-7d9f-abcde"));
+        assert_eq!(metadata.namespace.as_deref(), Some("chains"));
+        assert_eq!(metadata.node_name.as_deref(), Some("node-3"));
+        assert_eq!(metadata.container_name.as_deref(), Some("apex"));
+    }
+
+    #[test]
+    fn test_falls_back_to_hostname_and_service_account() {
+        let metadata = KubernetesMetadata::detect_with(
+            env(&[
+                ("KUBERNETES_SERVICE_HOST", "10.0.0.1"),
+                ("HOSTNAME", "indexer-0"),
+            ]),
+            || Some("chains\n".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(metadata.pod_name.as_deref(), Some("indexer-0"));
+        assert_eq!(metadata.namespace.as_deref(), Some("chains"));
+        assert_eq!(metadata.node_name, None);
+    }
+
+    #[test]
+    fn test_attributes_and_labels() {
+        let metadata = KubernetesMetadata {
+            pod_name: Some("indexer-0".to_string()),
+            namespace: Some("chains".to_string()),
+            node_name: None,
+            container_name: Some("apex".to_string()),
+        };
+
+        let attributes = metadata.resource_attributes();
+        assert_eq!(attributes.len(), 3);
+        assert!(attributes.contains(&KeyValue::new("k8s.pod.name", "indexer-0")));
+        assert!(attributes.contains(&KeyValue::new("k8s.container.name", "apex")));
+
+        let labels = metadata.const_labels();
+        assert_eq!(labels.len(), 3);
+        assert_eq!(labels["namespace"], "chains");
+        assert!(!labels.contains_key("node"));
+    }
+}
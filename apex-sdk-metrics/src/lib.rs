@@ -6,10 +6,25 @@
 //!
 //! - **Operation-specific metrics**: Track detailed metrics for every operation type
 //! - **Error categorization**: Advanced error taxonomy with automatic categorization
+//! - **Classification rules**: Regex, error type, code, chain and dispatch error rules from code, TOML or JSON
 //! - **Performance profiling**: OpenTelemetry-based distributed tracing and span tracking
 //! - **Prometheus integration**: HTTP server with Prometheus-compatible metrics endpoint
+//! - **Push export**: Pushgateway and remote-write pushes for jobs that cannot be scraped
+//! - **File snapshots**: Prometheus text and JSON dumps for air-gapped environments
 //! - **Health checks**: Comprehensive health status monitoring
+//! - **Endpoint probes**: Health checks for Substrate and EVM RPC endpoints
+//! - **Uptime SLA reports**: Rolling availability and latency percentiles per endpoint
+//! - **Soak testing**: Leak detection over memory, file descriptor, span buffer and task samples
 //! - **Metrics aggregation**: Statistical analysis and trend detection
+//! - **Streaming aggregation**: Rolling percentiles updated as metrics are recorded
+//! - **Alerting**: Threshold rules with callback, channel and webhook notifications
+//! - **Anomaly detection**: EWMA and seasonal baselines with z-score anomaly events
+//! - **Cost attribution**: RPC, fee and time shares per operation type and caller
+//! - **Structured logging**: Standard field set rendered as top-level JSON keys
+//! - **Privacy mode**: Redaction of addresses and amounts in logs and metric labels
+//! - **Relabeling**: Label allow-lists and rewrite rules applied before export
+//! - **Adapter instrumentation**: Spans and errors recorded per chain and endpoint
+//! - **Kubernetes metadata**: Pod, namespace, node and container on traces and metrics
 //!
 //! ## Example Usage
 //!
@@ -35,23 +50,66 @@
 //! ```
 
 pub mod aggregation;
+pub mod alerting;
+pub mod anomaly;
+pub mod attribution;
+pub mod dashboards;
 pub mod error_categorization;
+pub mod error_rules;
 pub mod health;
+pub mod instrumentation;
+pub mod kubernetes;
+pub mod logging;
+pub mod otel;
+pub mod probe;
 pub mod profiling;
 pub mod prometheus_exporter;
+pub mod push;
+pub mod relabel;
+pub mod snapshot;
+pub mod soak;
+pub mod streaming;
 pub mod telemetry;
+pub mod uptime;
 
-use std::sync::Arc;
+use apex_sdk_core::metrics::MetricsCollector;
+use apex_sdk_core::{ErrorCode, HasErrorCode};
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
 pub use aggregation::{AggregatedMetrics, MetricsAggregator, StatisticalSnapshot, TimeWindow};
+pub use alerting::{AlertEngine, AlertEvent, AlertMetric, AlertRule};
+pub use anomaly::{Anomaly, AnomalyDetector, AnomalyDirection, Baseline};
+pub use attribution::{AttributionReport, CostAttributor, CostShare};
+pub use dashboards::{DashboardGenerator, MetricFamilyInfo, MetricKind};
 pub use error_categorization::{
     categorize_error, ErrorCategory, ErrorClassification, ErrorImpact, ErrorSeverity,
+    ErrorStatistics,
 };
-pub use health::{ComponentHealth, HealthChecker, HealthStatus};
-pub use profiling::{OperationSpan, OperationType, PerformanceProfiler, SpanContext};
-pub use prometheus_exporter::{MetricsServer, PrometheusRegistry};
-pub use telemetry::{init_telemetry, ObservabilityConfig, TelemetryLayer};
+pub use error_rules::{ClassificationRule, CustomCategory, ErrorClassifier, ErrorSource, RuleSet};
+pub use health::{
+    ComponentHealth, DiskUsage, HealthChecker, HealthEvent, HealthStatus, ProbeConfig, ProbeFuture,
+    ResourceThresholds, SystemResources,
+};
+pub use instrumentation::ChainInstrumentation;
+pub use kubernetes::KubernetesMetadata;
+pub use logging::{RedactingFields, StandardFieldsLayer, StandardJsonFormat};
+pub use otel::OtelMetricsBridge;
+pub use probe::{EndpointProbe, ProbeKind};
+pub use profiling::{
+    OperationSpan, OperationStats, OperationType, PerformanceProfiler, SpanContext,
+};
+pub use prometheus_exporter::{MetricsServer, MetricsServerHandle, PrometheusRegistry};
+pub use push::{MetricsPusher, PushTarget};
+pub use relabel::{RelabelConfig, RelabelRule};
+pub use snapshot::{MetricsDump, SnapshotExporter};
+pub use soak::{
+    LeakDetector, LeakThresholds, LeakTolerance, ResourceTrend, SoakMonitor, SoakReport,
+    SoakResource, SoakSample,
+};
+pub use streaming::{QuantileSketch, StreamingAggregator};
+pub use telemetry::{init_telemetry, ObservabilityConfig, TelemetryLayer, PRIVACY_MODE_ENV};
+pub use uptime::{AvailabilitySample, UptimeReport, UptimeTracker};
 
 /// Errors that can occur in the metrics system
 #[derive(Error, Debug)]
@@ -73,6 +131,31 @@ pub enum MetricsError {
 
     #[error("Metrics aggregation error: {0}")]
     Aggregation(String),
+
+    #[error("Failed to persist metrics state: {0}")]
+    Persistence(String),
+
+    #[error("Failed to push metrics: {0}")]
+    PushFailed(String),
+
+    #[error("Invalid error classification rule: {0}")]
+    InvalidRule(String),
+}
+
+impl HasErrorCode for MetricsError {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::new(match self {
+            MetricsError::PrometheusInit(_) => 1500,
+            MetricsError::ServerStart(_) => 1501,
+            MetricsError::ExportFailed(_) => 1502,
+            MetricsError::TelemetryConfig(_) => 1503,
+            MetricsError::HealthCheck(_) => 1504,
+            MetricsError::Aggregation(_) => 1505,
+            MetricsError::Persistence(_) => 1506,
+            MetricsError::PushFailed(_) => 1507,
+            MetricsError::InvalidRule(_) => 1508,
+        })
+    }
 }
 
 /// Result type for metrics operations
@@ -84,6 +167,9 @@ pub struct ObservabilityFacade {
     profiler: Arc<PerformanceProfiler>,
     health_checker: Arc<HealthChecker>,
     aggregator: Arc<MetricsAggregator>,
+    errors: Arc<Mutex<ErrorStatistics>>,
+    error_classifier: Arc<ErrorClassifier>,
+    sdk_metrics: Option<MetricsCollector>,
 }
 
 impl ObservabilityFacade {
@@ -93,9 +179,35 @@ impl ObservabilityFacade {
             profiler: Arc::new(PerformanceProfiler::new()),
             health_checker: Arc::new(HealthChecker::new()),
             aggregator: Arc::new(MetricsAggregator::new()),
+            errors: Arc::new(Mutex::new(ErrorStatistics::new())),
+            error_classifier: Arc::new(ErrorClassifier::new()),
+            sdk_metrics: None,
         }
     }
 
+    /// Classify instrumented errors with custom rules before the built-in
+    /// heuristics
+    pub fn with_error_classifier(mut self, classifier: ErrorClassifier) -> Self {
+        self.error_classifier = Arc::new(classifier);
+        self
+    }
+
+    /// Get the classifier of instrumented errors
+    pub fn error_classifier(&self) -> Arc<ErrorClassifier> {
+        Arc::clone(&self.error_classifier)
+    }
+
+    /// Classify an error with the facade's classifier
+    pub fn classify_error(&self, source: &ErrorSource) -> ErrorClassification {
+        self.error_classifier.classify(source)
+    }
+
+    /// Also record instrumented adapter calls in SDK metrics
+    pub fn with_sdk_metrics(mut self, sdk_metrics: MetricsCollector) -> Self {
+        self.sdk_metrics = Some(sdk_metrics);
+        self
+    }
+
     /// Get the performance profiler
     pub fn profiler(&self) -> Arc<PerformanceProfiler> {
         Arc::clone(&self.profiler)
@@ -110,6 +222,37 @@ impl ObservabilityFacade {
     pub fn aggregator(&self) -> Arc<MetricsAggregator> {
         Arc::clone(&self.aggregator)
     }
+
+    /// Get the error statistics of instrumented calls
+    pub fn error_statistics(&self) -> Arc<Mutex<ErrorStatistics>> {
+        Arc::clone(&self.errors)
+    }
+
+    /// Get the SDK metrics collector, if attached
+    pub fn sdk_metrics(&self) -> Option<MetricsCollector> {
+        self.sdk_metrics.clone()
+    }
+
+    /// Record a categorized error in the error statistics
+    pub fn record_error(&self, classification: &ErrorClassification) {
+        self.errors
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .record(classification);
+    }
+
+    /// Create a metrics server exporting the facade's profiler and health
+    /// checker alongside SDK metrics
+    pub async fn metrics_server(
+        &self,
+        port: u16,
+        sdk_metrics: MetricsCollector,
+    ) -> Result<MetricsServer> {
+        Ok(MetricsServer::new(port, sdk_metrics)
+            .await?
+            .with_profiler(PerformanceProfiler::clone(&self.profiler))
+            .with_health_checker(self.health_checker()))
+    }
 }
 
 impl Default for ObservabilityFacade {
@@ -117,3 +260,11 @@ impl Default for ObservabilityFacade {
         Self::new()
     }
 }
+
+/// Current Unix time in seconds
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
@@ -0,0 +1,439 @@
+//! Structured log output with the standard SDK field set
+//!
+//! SDK crates emit the fields defined in [`apex_sdk_core::log_fields`]
+//! (`chain`, `endpoint`, `tx_hash`, `wallet`, `operation`, `span_id`) on events
+//! and spans. This module renders them as flat, top-level JSON keys so that log
+//! pipelines can query them without knowing which span set which field:
+//!
+//! ```json
+//! {"timestamp":"…","level":"INFO","target":"apex_sdk_substrate::transaction",
+//!  "message":"Transaction finalized","chain":"westend","operation":"transfer",
+//!  "tx_hash":"0x…","span_id":"0000000000000001","fields":{"attempt":1}}
+//! ```
+//!
+//! Standard fields recorded on enclosing spans are inherited by events; the
+//! innermost value wins. Non-standard fields are nested under `fields`.
+//!
+//! Both [`StandardJsonFormat`] and [`RedactingFields`] (for plain-text output)
+//! redact address and amount fields when privacy mode is enabled, see
+//! [`apex_sdk_core::privacy`].
+
+use apex_sdk_core::log_fields;
+use apex_sdk_core::privacy::{self, Redactor};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Standard fields recorded on a span
+#[derive(Debug, Clone, Default)]
+struct SpanFields(Map<String, Value>);
+
+/// Collects standard fields from spans so events can inherit them
+///
+/// Install alongside [`StandardJsonFormat`]; without it only fields on the
+/// event itself are emitted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StandardFieldsLayer;
+
+impl<S> Layer<S> for StandardFieldsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanFields(visitor.standard));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            match extensions.get_mut::<SpanFields>() {
+                Some(fields) => fields.0.extend(visitor.standard),
+                None => extensions.insert(SpanFields(visitor.standard)),
+            }
+        }
+    }
+}
+
+/// JSON event formatter emitting the standard fields at the top level
+#[derive(Debug, Clone, Default)]
+pub struct StandardJsonFormat {
+    redactor: Option<Arc<Redactor>>,
+}
+
+impl StandardJsonFormat {
+    /// Redact fields with the given redactor instead of the process-wide one
+    pub fn with_redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = Some(Arc::new(redactor));
+        self
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for StandardJsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut standard = Map::new();
+
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                if let Some(fields) = span.extensions().get::<SpanFields>() {
+                    standard.extend(fields.0.clone());
+                }
+            }
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        standard.extend(visitor.standard);
+
+        if !standard.contains_key(log_fields::SPAN_ID) {
+            if let Some(span) = ctx.lookup_current() {
+                standard.insert(
+                    log_fields::SPAN_ID.to_string(),
+                    Value::String(format!("{:016x}", span.id().into_u64())),
+                );
+            }
+        }
+
+        let metadata = event.metadata();
+        let mut record = Map::new();
+        record.insert(
+            "timestamp".to_string(),
+            Value::String(chrono::Utc::now().to_rfc3339()),
+        );
+        record.insert(
+            "level".to_string(),
+            Value::String(metadata.level().to_string()),
+        );
+        record.insert(
+            "target".to_string(),
+            Value::String(metadata.target().to_string()),
+        );
+        record.insert(
+            "message".to_string(),
+            visitor.message.map(Value::String).unwrap_or(Value::Null),
+        );
+
+        for name in log_fields::ALL {
+            if let Some(value) = standard.remove(name) {
+                record.insert(name.to_string(), value);
+            }
+        }
+
+        let mut other = visitor.other;
+        if let Some(redactor) = self.redactor.clone().or_else(privacy::redactor) {
+            redact_map(&redactor, &mut record);
+            redact_map(&redactor, &mut other);
+        }
+
+        if !other.is_empty() {
+            record.insert("fields".to_string(), Value::Object(other));
+        }
+
+        let line = serde_json::to_string(&record).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{}", line)
+    }
+}
+
+/// Plain-text field formatter that redacts sensitive fields
+///
+/// Writes fields as `name=value` pairs like the default `tracing-subscriber`
+/// formatter. Use with `fmt::layer().fmt_fields(RedactingFields::default())`.
+#[derive(Debug, Clone, Default)]
+pub struct RedactingFields {
+    redactor: Option<Arc<Redactor>>,
+}
+
+impl RedactingFields {
+    /// Redact fields with the given redactor instead of the process-wide one
+    pub fn with_redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = Some(Arc::new(redactor));
+        self
+    }
+}
+
+impl<'writer> FormatFields<'writer> for RedactingFields {
+    fn format_fields<R: RecordFields>(&self, writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut visitor = RedactingVisitor {
+            writer,
+            redactor: self.redactor.clone().or_else(privacy::redactor),
+            first: true,
+            result: Ok(()),
+        };
+        fields.record(&mut visitor);
+        visitor.result
+    }
+}
+
+struct RedactingVisitor<'writer> {
+    writer: Writer<'writer>,
+    redactor: Option<Arc<Redactor>>,
+    first: bool,
+    result: fmt::Result,
+}
+
+impl RedactingVisitor<'_> {
+    fn write(&mut self, field: &Field, value: &str, quote: bool) {
+        if self.result.is_err() {
+            return;
+        }
+
+        let separator = if self.first { "" } else { " " };
+        self.first = false;
+
+        let value = match &self.redactor {
+            Some(redactor) => redactor.redact(field.name(), value),
+            None => value.into(),
+        };
+
+        self.result = match (field.name(), quote) {
+            ("message", _) => write!(self.writer, "{}{}", separator, value),
+            (name, true) => write!(self.writer, "{}{}={:?}", separator, name, value),
+            (name, false) => write!(self.writer, "{}{}={}", separator, name, value),
+        };
+    }
+}
+
+impl Visit for RedactingVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.write(field, value, true);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.write(field, &format!("{:?}", value), false);
+    }
+}
+
+/// Redact sensitive values in a JSON object, in place
+fn redact_map(redactor: &Redactor, map: &mut Map<String, Value>) {
+    for (name, value) in map.iter_mut() {
+        if !redactor.is_sensitive(name) {
+            continue;
+        }
+        let raw = match &*value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        *value = Value::String(redactor.redact(name, &raw).into_owned());
+    }
+}
+
+/// Splits recorded fields into message, standard and other fields
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    standard: Map<String, Value>,
+    other: Map<String, Value>,
+}
+
+impl FieldVisitor {
+    fn insert(&mut self, field: &Field, value: Value) {
+        let name = field.name();
+        if name == "message" {
+            self.message = Some(match value {
+                Value::String(s) => s,
+                other => other.to_string(),
+            });
+        } else if log_fields::is_standard(name) {
+            self.standard.insert(name.to_string(), value);
+        } else {
+            self.other.insert(name.to_string(), value);
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, Value::String(value.to_string()));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, Value::Bool(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_u128(&mut self, field: &Field, value: u128) {
+        // JSON numbers lose precision above 2^53, keep large values exact
+        self.insert(field, Value::String(value.to_string()));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, Value::String(format!("{:?}", value)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apex_sdk_core::privacy::PrivacyMode;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    fn capture(f: impl FnOnce()) -> Vec<Value> {
+        capture_with(StandardJsonFormat::default(), f)
+    }
+
+    fn capture_with(format: StandardJsonFormat, f: impl FnOnce()) -> Vec<Value> {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry()
+            .with(StandardFieldsLayer)
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .event_format(format)
+                    .with_writer(move || writer.clone()),
+            );
+
+        tracing::subscriber::with_default(subscriber, f);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_event_fields_are_flattened() {
+        let logs = capture(|| {
+            tracing::info!(
+                chain = "westend",
+                tx_hash = "0xabc",
+                attempt = 2u64,
+                "Transaction finalized"
+            );
+        });
+
+        assert_eq!(logs.len(), 1);
+        let log = &logs[0];
+        assert_eq!(log["message"], "Transaction finalized");
+        assert_eq!(log["chain"], "westend");
+        assert_eq!(log["tx_hash"], "0xabc");
+        assert_eq!(log["fields"]["attempt"], 2);
+        assert_eq!(log["level"], "INFO");
+    }
+
+    #[test]
+    fn test_span_fields_are_inherited() {
+        let logs = capture(|| {
+            let span = tracing::info_span!("submit", chain = "polkadot", operation = "transfer");
+            let _guard = span.enter();
+            tracing::info!(operation = "retry", "Retrying");
+        });
+
+        let log = &logs[0];
+        assert_eq!(log["chain"], "polkadot");
+        // The event's own value takes precedence
+        assert_eq!(log["operation"], "retry");
+        assert!(log["span_id"].is_string());
+    }
+
+    #[test]
+    fn test_recorded_span_fields() {
+        let logs = capture(|| {
+            let span = tracing::info_span!("submit", tx_hash = tracing::field::Empty);
+            let _guard = span.enter();
+            span.record("tx_hash", "0xdef");
+            tracing::info!("Submitted");
+        });
+
+        assert_eq!(logs[0]["tx_hash"], "0xdef");
+        assert!(logs[0].get("fields").is_none());
+    }
+
+    #[test]
+    fn test_json_redaction() {
+        let format =
+            StandardJsonFormat::default().with_redactor(Redactor::new(PrivacyMode::Truncate));
+        let logs = capture_with(format, || {
+            let span = tracing::info_span!("submit", wallet = ALICE);
+            let _guard = span.enter();
+            tracing::info!(
+                to = ALICE,
+                amount = 1_500_000u64,
+                chain = "westend",
+                "Transfer"
+            );
+        });
+
+        let log = &logs[0];
+        assert_eq!(log["wallet"], "5Grwva…utQY");
+        assert_eq!(log["fields"]["to"], "5Grwva…utQY");
+        assert_eq!(log["fields"]["amount"], "~10^6");
+        assert_eq!(log["chain"], "westend");
+    }
+
+    #[test]
+    fn test_text_redaction() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .fmt_fields(RedactingFields::default().with_redactor(Redactor::new(PrivacyMode::Hash)))
+            .without_time()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(wallet = %ALICE, attempt = 1u64, "Signing");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("Signing"));
+        assert!(output.contains("wallet=h:"));
+        assert!(output.contains("attempt=1"));
+        assert!(!output.contains(ALICE));
+    }
+}
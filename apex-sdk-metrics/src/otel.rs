@@ -0,0 +1,167 @@
+//! OpenTelemetry metrics bridge
+//!
+//! Records SDK metrics into OpenTelemetry instruments so they can be shipped
+//! by whichever exporter the global `MeterProvider` is configured with (OTLP,
+//! stdout, ...). Labels become attributes after [`RelabelConfig`] has been
+//! applied, so the same allow-list protects both the Prometheus and the OTLP
+//! export paths.
+
+use crate::relabel::RelabelConfig;
+use apex_sdk_core::metrics::{Metric, MetricType};
+use opentelemetry::metrics::{Counter, Gauge, Histogram, Meter};
+use opentelemetry::KeyValue;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Instrumentation scope name used for the default meter
+const METER_NAME: &str = "apex-sdk";
+
+/// Instrument backing a metric name
+#[derive(Clone)]
+enum Instrument {
+    Counter(Counter<f64>),
+    Histogram(Histogram<f64>),
+    Gauge(Gauge<f64>),
+}
+
+/// Forwards SDK metrics to OpenTelemetry instruments
+pub struct OtelMetricsBridge {
+    meter: Meter,
+    relabel: RelabelConfig,
+    instruments: Mutex<HashMap<String, Instrument>>,
+}
+
+impl OtelMetricsBridge {
+    /// Create a bridge using the global meter provider
+    pub fn new() -> Self {
+        Self::with_meter(opentelemetry::global::meter(METER_NAME))
+    }
+
+    /// Create a bridge using a specific meter
+    pub fn with_meter(meter: Meter) -> Self {
+        Self {
+            meter,
+            relabel: RelabelConfig::default(),
+            instruments: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Relabel SDK metrics before they are recorded
+    pub fn with_relabel_config(mut self, relabel: RelabelConfig) -> Self {
+        self.relabel = relabel;
+        self
+    }
+
+    /// Record metrics into their instruments
+    ///
+    /// Counters are incremented by each metric's value, so pass newly
+    /// collected metrics only.
+    pub fn record(&self, metrics: &[Metric]) {
+        for metric in metrics {
+            let attributes = self.attributes(metric);
+
+            match self.instrument(metric) {
+                Instrument::Counter(counter) => counter.add(metric.value, &attributes),
+                Instrument::Histogram(histogram) => histogram.record(metric.value, &attributes),
+                Instrument::Gauge(gauge) => gauge.record(metric.value, &attributes),
+            }
+        }
+    }
+
+    /// Relabeled attributes for a metric, sorted by key
+    fn attributes(&self, metric: &Metric) -> Vec<KeyValue> {
+        let mut attributes: Vec<KeyValue> = self
+            .relabel
+            .apply(&metric.labels)
+            .into_iter()
+            .map(|(key, value)| KeyValue::new(key, value))
+            .collect();
+        attributes.sort_by(|a, b| a.key.as_str().cmp(b.key.as_str()));
+        attributes
+    }
+
+    fn instrument(&self, metric: &Metric) -> Instrument {
+        let mut instruments = self
+            .instruments
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        instruments
+            .entry(metric.name.clone())
+            .or_insert_with(|| {
+                let name = metric.name.clone();
+                let description = metric.help.clone().unwrap_or_default();
+
+                match metric.metric_type {
+                    MetricType::TransactionCount
+                    | MetricType::TransactionSuccessRate
+                    | MetricType::ErrorRate => Instrument::Counter(
+                        self.meter
+                            .f64_counter(name)
+                            .with_description(description)
+                            .build(),
+                    ),
+                    MetricType::TransactionLatency | MetricType::ProviderResponseTime => {
+                        Instrument::Histogram(
+                            self.meter
+                                .f64_histogram(name)
+                                .with_description(description)
+                                .with_unit("s")
+                                .build(),
+                        )
+                    }
+                    _ => Instrument::Gauge(
+                        self.meter
+                            .f64_gauge(name)
+                            .with_description(description)
+                            .build(),
+                    ),
+                }
+            })
+            .clone()
+    }
+}
+
+impl Default for OtelMetricsBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apex_sdk_core::metrics::MetricsCollector;
+
+    #[test]
+    fn test_attributes_are_relabeled() {
+        let bridge = OtelMetricsBridge::new()
+            .with_relabel_config(RelabelConfig::new().drop_label("tx_hash"));
+
+        let metric = Metric::new(MetricType::TransactionCount, "transactions", 1.0)
+            .with_label("status", "success")
+            .with_label("tx_hash", "0xabc")
+            .with_label("chain", "polkadot");
+
+        let keys: Vec<_> = bridge
+            .attributes(&metric)
+            .iter()
+            .map(|kv| kv.key.as_str().to_string())
+            .collect();
+        assert_eq!(keys, vec!["chain", "status"]);
+    }
+
+    #[test]
+    fn test_record_reuses_instruments() {
+        let bridge = OtelMetricsBridge::new();
+        let collector = MetricsCollector::new();
+        collector.record_transaction_success("polkadot", "0x1");
+        collector.record_transaction_success("polkadot", "0x2");
+        collector.record_gas_usage("polkadot", 21000, 21000);
+
+        bridge.record(&collector.get_metrics());
+
+        let instruments = bridge.instruments.lock().unwrap();
+        assert!(instruments.len() < collector.get_metrics().len());
+    }
+}
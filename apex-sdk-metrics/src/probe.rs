@@ -0,0 +1,404 @@
+//! Endpoint health probes
+//!
+//! [`EndpointProbe`] checks a Substrate or EVM JSON-RPC endpoint over HTTP and
+//! reports the result as a [`ComponentHealth`], so endpoints can be vetted
+//! before they are wired into the SDK. WebSocket URLs are probed through the
+//! HTTP interface served on the same host and port.
+//!
+//! The probe records the chain name, best and finalized block heights and sync
+//! state as component metadata under the `META_*` keys. An endpoint is:
+//!
+//! - **unhealthy** if it cannot be reached or a required call fails
+//! - **degraded** if it is syncing, has no peers or responds slower than the
+//!   degraded latency threshold
+//! - **healthy** otherwise
+//!
+//! ```rust,no_run
+//! use apex_sdk_metrics::EndpointProbe;
+//!
+//! # async fn example() {
+//! let health = EndpointProbe::substrate("wss://westend-rpc.polkadot.io")
+//!     .check()
+//!     .await;
+//!
+//! println!("{:?}: {:?}", health.status, health.metadata.get("best_block"));
+//! # }
+//! ```
+
+use crate::health::{ComponentHealth, HealthStatus};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+/// Metadata key for the chain name (Substrate) or chain ID (EVM)
+pub const META_CHAIN: &str = "chain";
+
+/// Metadata key for the best block height
+pub const META_BEST_BLOCK: &str = "best_block";
+
+/// Metadata key for the finalized block height
+pub const META_FINALIZED_BLOCK: &str = "finalized_block";
+
+/// Metadata key for the sync status (`true` while syncing)
+pub const META_SYNCING: &str = "syncing";
+
+/// Metadata key for the peer count (Substrate only)
+pub const META_PEERS: &str = "peers";
+
+/// Default request timeout
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default latency above which an endpoint is reported as degraded
+const DEFAULT_DEGRADED_LATENCY: Duration = Duration::from_secs(2);
+
+/// Chain family of a probed endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeKind {
+    /// Substrate node (`system_*` / `chain_*` RPCs)
+    Substrate,
+    /// EVM node (`eth_*` RPCs)
+    Evm,
+}
+
+/// Chain state reported by an endpoint
+#[derive(Debug, Default)]
+struct ProbeReport {
+    chain: String,
+    best_block: u64,
+    finalized_block: Option<u64>,
+    syncing: bool,
+    peers: Option<u64>,
+}
+
+/// Health probe for a single JSON-RPC endpoint
+#[derive(Debug, Clone)]
+pub struct EndpointProbe {
+    url: String,
+    kind: ProbeKind,
+    name: Option<String>,
+    timeout: Duration,
+    degraded_latency: Duration,
+}
+
+impl EndpointProbe {
+    /// Probe a Substrate endpoint
+    pub fn substrate(url: impl Into<String>) -> Self {
+        Self::new(url, ProbeKind::Substrate)
+    }
+
+    /// Probe an EVM endpoint
+    pub fn evm(url: impl Into<String>) -> Self {
+        Self::new(url, ProbeKind::Evm)
+    }
+
+    fn new(url: impl Into<String>, kind: ProbeKind) -> Self {
+        Self {
+            url: url.into(),
+            kind,
+            name: None,
+            timeout: DEFAULT_TIMEOUT,
+            degraded_latency: DEFAULT_DEGRADED_LATENCY,
+        }
+    }
+
+    /// Set the component name (defaults to the URL)
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the per-request timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the latency above which the endpoint is reported as degraded
+    pub fn with_degraded_latency(mut self, latency: Duration) -> Self {
+        self.degraded_latency = latency;
+        self
+    }
+
+    /// Get the probed URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get the chain family
+    pub fn kind(&self) -> ProbeKind {
+        self.kind
+    }
+
+    /// Run the probe
+    ///
+    /// Never fails: unreachable endpoints are reported as
+    /// [`HealthStatus::Unhealthy`] with the error as message. The response
+    /// time is the round trip of the first request.
+    pub async fn check(&self) -> ComponentHealth {
+        let name = self.name.clone().unwrap_or_else(|| self.url.clone());
+
+        let client = match reqwest::Client::builder().timeout(self.timeout).build() {
+            Ok(client) => client,
+            Err(e) => {
+                return ComponentHealth::new(name, HealthStatus::Unhealthy)
+                    .with_message(format!("Failed to create HTTP client: {}", e))
+            }
+        };
+        let rpc = RpcClient {
+            client,
+            url: http_url(&self.url),
+        };
+
+        let start = Instant::now();
+        let report = match self.kind {
+            ProbeKind::Substrate => probe_substrate(&rpc, start).await,
+            ProbeKind::Evm => probe_evm(&rpc, start).await,
+        };
+
+        match report {
+            Ok((report, latency)) => self.health(name, report, latency),
+            Err(e) => ComponentHealth::new(name, HealthStatus::Unhealthy)
+                .with_message(e)
+                .with_response_time(start.elapsed()),
+        }
+    }
+
+    fn health(&self, name: String, report: ProbeReport, latency: Duration) -> ComponentHealth {
+        let mut problems = Vec::new();
+        if report.syncing {
+            problems.push("node is syncing".to_string());
+        }
+        if report.peers == Some(0) {
+            problems.push("node has no peers".to_string());
+        }
+        if latency > self.degraded_latency {
+            problems.push(format!("slow response ({}ms)", latency.as_millis()));
+        }
+
+        let status = if problems.is_empty() {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Degraded
+        };
+
+        let mut health = ComponentHealth::new(name, status)
+            .with_response_time(latency)
+            .with_metadata(META_CHAIN, report.chain)
+            .with_metadata(META_BEST_BLOCK, report.best_block.to_string())
+            .with_metadata(META_SYNCING, report.syncing.to_string());
+        if let Some(finalized) = report.finalized_block {
+            health = health.with_metadata(META_FINALIZED_BLOCK, finalized.to_string());
+        }
+        if let Some(peers) = report.peers {
+            health = health.with_metadata(META_PEERS, peers.to_string());
+        }
+        if !problems.is_empty() {
+            health = health.with_message(problems.join(", "));
+        }
+        health
+    }
+}
+
+struct RpcClient {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl RpcClient {
+    async fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let response: Value = self
+            .client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| format!("{} request failed: {}", method, e))?
+            .error_for_status()
+            .map_err(|e| format!("{} request failed: {}", method, e))?
+            .json()
+            .await
+            .map_err(|e| format!("{} returned invalid JSON: {}", method, e))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(format!("{} returned error: {}", method, error));
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| format!("{} returned no result", method))
+    }
+}
+
+async fn probe_substrate(
+    rpc: &RpcClient,
+    start: Instant,
+) -> Result<(ProbeReport, Duration), String> {
+    let chain = rpc.call("system_chain", json!([])).await?;
+    let latency = start.elapsed();
+
+    let health = rpc.call("system_health", json!([])).await?;
+    let best = rpc.call("chain_getHeader", json!([])).await?;
+    let finalized_hash = rpc.call("chain_getFinalizedHead", json!([])).await?;
+    let finalized = rpc.call("chain_getHeader", json!([finalized_hash])).await?;
+
+    let should_have_peers = health["shouldHavePeers"].as_bool().unwrap_or(true);
+    let report = ProbeReport {
+        chain: chain.as_str().unwrap_or_default().to_string(),
+        best_block: header_number(&best).ok_or("chain_getHeader returned no block number")?,
+        finalized_block: header_number(&finalized),
+        syncing: health["isSyncing"].as_bool().unwrap_or(false),
+        peers: health["peers"].as_u64().filter(|_| should_have_peers),
+    };
+    Ok((report, latency))
+}
+
+async fn probe_evm(rpc: &RpcClient, start: Instant) -> Result<(ProbeReport, Duration), String> {
+    let chain_id = rpc.call("eth_chainId", json!([])).await?;
+    let latency = start.elapsed();
+
+    let block_number = rpc.call("eth_blockNumber", json!([])).await?;
+    let syncing = rpc.call("eth_syncing", json!([])).await?;
+    // Nodes predating the merge do not know the `finalized` tag
+    let finalized = rpc
+        .call("eth_getBlockByNumber", json!(["finalized", false]))
+        .await
+        .ok();
+
+    let report = ProbeReport {
+        chain: parse_quantity(&chain_id)
+            .ok_or("eth_chainId returned an invalid chain ID")?
+            .to_string(),
+        best_block: parse_quantity(&block_number)
+            .ok_or("eth_blockNumber returned an invalid block number")?,
+        finalized_block: finalized.as_ref().and_then(header_number),
+        syncing: syncing != Value::Bool(false),
+        peers: None,
+    };
+    Ok((report, latency))
+}
+
+/// Map WebSocket URLs to the HTTP endpoint on the same host
+fn http_url(url: &str) -> String {
+    if let Some(rest) = url.strip_prefix("wss://") {
+        format!("https://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("ws://") {
+        format!("http://{}", rest)
+    } else {
+        url.to_string()
+    }
+}
+
+/// Block number of a Substrate header or EVM block object
+fn header_number(header: &Value) -> Option<u64> {
+    parse_quantity(&header["number"])
+}
+
+/// Parse a hex-encoded JSON-RPC quantity
+fn parse_quantity(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(s) => u64::from_str_radix(s.strip_prefix("0x")?, 16).ok(),
+        Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Json, Router};
+    use tokio::net::TcpListener;
+
+    async fn serve(handler: fn(&str) -> Value) -> String {
+        let app = Router::new().route(
+            "/",
+            post(move |Json(request): Json<Value>| async move {
+                let method = request["method"].as_str().unwrap_or_default().to_string();
+                Json(json!({"jsonrpc": "2.0", "id": 1, "result": handler(&method)}))
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{}", addr)
+    }
+
+    fn substrate_node(method: &str) -> Value {
+        match method {
+            "system_chain" => json!("Westend"),
+            "system_health" => json!({"peers": 12, "isSyncing": false, "shouldHavePeers": true}),
+            "chain_getHeader" => json!({"number": "0x64"}),
+            "chain_getFinalizedHead" => json!("0x01"),
+            _ => Value::Null,
+        }
+    }
+
+    fn syncing_evm_node(method: &str) -> Value {
+        match method {
+            "eth_chainId" => json!("0x190f1b46"),
+            "eth_blockNumber" => json!("0x10"),
+            "eth_syncing" => json!({"currentBlock": "0x10", "highestBlock": "0x20"}),
+            "eth_getBlockByNumber" => json!({"number": "0xe"}),
+            _ => Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_http_url() {
+        assert_eq!(http_url("wss://rpc.example.com"), "https://rpc.example.com");
+        assert_eq!(http_url("ws://127.0.0.1:9944"), "http://127.0.0.1:9944");
+        assert_eq!(http_url("http://localhost:8545"), "http://localhost:8545");
+    }
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity(&json!("0x1f")), Some(31));
+        assert_eq!(parse_quantity(&json!(7)), Some(7));
+        assert_eq!(parse_quantity(&json!("31")), None);
+    }
+
+    #[tokio::test]
+    async fn test_substrate_probe() {
+        let url = serve(substrate_node).await;
+        let health = EndpointProbe::substrate(&url)
+            .with_name("westend")
+            .check()
+            .await;
+
+        assert_eq!(health.name, "westend");
+        assert_eq!(health.status, HealthStatus::Healthy);
+        assert_eq!(health.metadata[META_CHAIN], "Westend");
+        assert_eq!(health.metadata[META_BEST_BLOCK], "100");
+        assert_eq!(health.metadata[META_FINALIZED_BLOCK], "100");
+        assert_eq!(health.metadata[META_PEERS], "12");
+        assert!(health.response_time_ms.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_evm_probe_syncing_is_degraded() {
+        let url = serve(syncing_evm_node).await;
+        let health = EndpointProbe::evm(&url).check().await;
+
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert_eq!(health.metadata[META_CHAIN], "420420422");
+        assert_eq!(health.metadata[META_BEST_BLOCK], "16");
+        assert_eq!(health.metadata[META_FINALIZED_BLOCK], "14");
+        assert_eq!(health.metadata[META_SYNCING], "true");
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_is_unhealthy() {
+        let health = EndpointProbe::evm("http://127.0.0.1:1")
+            .with_timeout(Duration::from_secs(1))
+            .check()
+            .await;
+
+        assert_eq!(health.status, HealthStatus::Unhealthy);
+        assert!(health.message.unwrap().contains("eth_chainId"));
+    }
+}
@@ -309,37 +309,30 @@ impl PerformanceProfiler {
     /// Get performance statistics for an operation type
     pub fn operation_stats(&self, operation_type: OperationType) -> OperationStats {
         let spans = self.get_spans_by_operation(operation_type);
-
-        if spans.is_empty() {
-            return OperationStats::default();
+        OperationStats::from_spans(operation_type, spans.iter())
+    }
+
+    /// Get performance statistics for every operation type with recorded spans
+    ///
+    /// Ordered by operation name.
+    pub fn all_operation_stats(&self) -> Vec<OperationStats> {
+        let spans = self.get_spans();
+        let mut by_operation: HashMap<OperationType, Vec<&SpanRecord>> = HashMap::new();
+        for span in &spans {
+            by_operation
+                .entry(span.operation_type)
+                .or_default()
+                .push(span);
         }
 
-        let total_count = spans.len();
-        let success_count = spans.iter().filter(|s| s.is_success()).count();
-        let error_count = spans.iter().filter(|s| s.is_error()).count();
-
-        let mut durations: Vec<f64> = spans.iter().map(|s| s.duration.as_secs_f64()).collect();
-        durations.sort_by(|a, b| a.partial_cmp(b).unwrap());
-
-        let total_duration: f64 = durations.iter().sum();
-        let mean_duration = total_duration / total_count as f64;
-
-        let p50 = percentile(&durations, 50.0);
-        let p95 = percentile(&durations, 95.0);
-        let p99 = percentile(&durations, 99.0);
-
-        OperationStats {
-            operation_type,
-            total_count,
-            success_count,
-            error_count,
-            mean_duration_secs: mean_duration,
-            p50_duration_secs: p50,
-            p95_duration_secs: p95,
-            p99_duration_secs: p99,
-            min_duration_secs: durations.first().copied().unwrap_or(0.0),
-            max_duration_secs: durations.last().copied().unwrap_or(0.0),
-        }
+        let mut stats: Vec<OperationStats> = by_operation
+            .into_iter()
+            .map(|(operation_type, spans)| {
+                OperationStats::from_spans(operation_type, spans.into_iter())
+            })
+            .collect();
+        stats.sort_by_key(|s| s.operation_type.to_string());
+        stats
     }
 
     /// Clear all recorded spans
@@ -397,6 +390,46 @@ pub struct OperationStats {
     pub max_duration_secs: f64,
 }
 
+impl OperationStats {
+    fn from_spans<'a>(
+        operation_type: OperationType,
+        spans: impl Iterator<Item = &'a SpanRecord>,
+    ) -> Self {
+        let mut durations = Vec::new();
+        let mut success_count = 0;
+        let mut error_count = 0;
+        for span in spans {
+            durations.push(span.duration.as_secs_f64());
+            success_count += span.is_success() as usize;
+            error_count += span.is_error() as usize;
+        }
+
+        if durations.is_empty() {
+            return Self {
+                operation_type,
+                ..Self::default()
+            };
+        }
+        durations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let total_count = durations.len();
+        let total_duration: f64 = durations.iter().sum();
+
+        Self {
+            operation_type,
+            total_count,
+            success_count,
+            error_count,
+            mean_duration_secs: total_duration / total_count as f64,
+            p50_duration_secs: percentile(&durations, 50.0),
+            p95_duration_secs: percentile(&durations, 95.0),
+            p99_duration_secs: percentile(&durations, 99.0),
+            min_duration_secs: durations.first().copied().unwrap_or(0.0),
+            max_duration_secs: durations.last().copied().unwrap_or(0.0),
+        }
+    }
+}
+
 impl Default for OperationStats {
     fn default() -> Self {
         Self {
@@ -498,6 +531,24 @@ mod tests {
         assert_eq!(stats.success_rate(), 90.0);
     }
 
+    #[test]
+    fn test_all_operation_stats() {
+        let profiler = PerformanceProfiler::new();
+        profiler.start_span(OperationType::Signing).success();
+        profiler.start_span(OperationType::BlockQuery).success();
+        profiler
+            .start_span(OperationType::BlockQuery)
+            .error("timeout");
+
+        let stats = profiler.all_operation_stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].operation_type, OperationType::BlockQuery);
+        assert_eq!(stats[0].total_count, 2);
+        assert_eq!(stats[0].error_count, 1);
+        assert_eq!(stats[1].operation_type, OperationType::Signing);
+        assert_eq!(stats[1].success_count, 1);
+    }
+
     #[test]
     fn test_span_attributes() {
         let profiler = PerformanceProfiler::new();
@@ -3,25 +3,61 @@
 //! This module provides a production-ready Prometheus metrics server with
 //! automatic metric registration, scraping endpoint, and integration with
 //! the Apex SDK core metrics system.
+//!
+//! The server binds `0.0.0.0` by default and can be restricted to another
+//! address, served over TLS, and have `/metrics` require a bearer token or
+//! basic auth. [`MetricsServer::spawn`] returns a [`MetricsServerHandle`]
+//! that stops it gracefully.
 
+use crate::attribution::{AttributionReport, CostAttributor};
+use crate::dashboards::{MetricFamilyInfo, MetricKind};
+use crate::health::{HealthChecker, HealthStatus, HealthSummary, SystemResources};
+use crate::profiling::{OperationStats, PerformanceProfiler};
+use crate::relabel::RelabelConfig;
+use crate::telemetry::ObservabilityConfig;
+use crate::uptime::{UptimeReport, UptimeTracker};
 use crate::{MetricsError, Result};
 use apex_sdk_core::metrics::{Metric, MetricType, MetricsCollector};
+use apex_sdk_core::readiness::{Readiness, ReadinessCondition};
 use axum::{
-    extract::State,
-    http::StatusCode,
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::{self, Next},
     response::{IntoResponse, Response},
     routing::get,
     Router,
 };
+use base64::Engine;
+use prometheus::core::Collector;
+use prometheus::proto::MetricType as PromMetricType;
 use prometheus::{
     register_counter_vec_with_registry, register_gauge_vec_with_registry,
-    register_histogram_vec_with_registry, CounterVec, Encoder, GaugeVec, HistogramVec, Registry,
-    TextEncoder,
+    register_gauge_with_registry, register_histogram_vec_with_registry, CounterVec, Encoder, Gauge,
+    GaugeVec, HistogramVec, Registry, TextEncoder,
 };
-use std::net::SocketAddr;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::Path;
 use std::sync::Arc;
-use tokio::net::TcpListener;
-use tracing::{error, info};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+use tokio::task::{JoinHandle, JoinSet};
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::{crypto, ServerConfig};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tracing::{debug, error, info};
+
+/// Buckets of `apex_sdk_transaction_duration_seconds`
+const TRANSACTION_DURATION_BUCKETS: [f64; 10] =
+    [0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0];
+
+/// Buckets of `apex_sdk_rpc_duration_seconds`
+const RPC_DURATION_BUCKETS: [f64; 8] = [0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0];
 
 /// Prometheus metrics registry wrapper
 pub struct PrometheusRegistry {
@@ -31,13 +67,45 @@ pub struct PrometheusRegistry {
     gas_usage: GaugeVec,
     error_counter: CounterVec,
     rpc_duration: HistogramVec,
+    endpoint_uptime: GaugeVec,
+    endpoint_latency: GaugeVec,
+    blocking_tasks: GaugeVec,
+    retention_purged: CounterVec,
+    retention_store_bytes: GaugeVec,
+    system_cpu: Gauge,
+    system_memory: Gauge,
+    process_cpu: Gauge,
+    process_memory: Gauge,
+    open_fds: Gauge,
+    open_sockets: Gauge,
+    data_dir_usage: GaugeVec,
+    data_dir_available: GaugeVec,
+    operation_duration: GaugeVec,
+    operation_count: GaugeVec,
+    cost_usage: GaugeVec,
+    cost_share: GaugeVec,
+    health_status: GaugeVec,
+    component_status: GaugeVec,
+    relabel: RelabelConfig,
 }
 
 impl PrometheusRegistry {
     /// Create a new Prometheus registry with standard metrics
     pub fn new() -> Result<Self> {
-        let registry = Registry::new();
+        Self::from_registry(Registry::new())
+    }
+
+    /// Create a registry whose metrics all carry `labels`
+    ///
+    /// Used to tell replicas apart, e.g. with
+    /// [`KubernetesMetadata::const_labels`](crate::KubernetesMetadata::const_labels).
+    pub fn with_const_labels(labels: HashMap<String, String>) -> Result<Self> {
+        let registry = Registry::new_custom(None, Some(labels))
+            .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+        Self::from_registry(registry)
+    }
 
+    fn from_registry(registry: Registry) -> Result<Self> {
         let transaction_counter = register_counter_vec_with_registry!(
             "apex_sdk_transactions_total",
             "Total number of transactions by chain and status",
@@ -50,7 +118,7 @@ impl PrometheusRegistry {
             "apex_sdk_transaction_duration_seconds",
             "Transaction execution duration in seconds",
             &["chain", "operation"],
-            vec![0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0],
+            TRANSACTION_DURATION_BUCKETS.to_vec(),
             registry
         )
         .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
@@ -75,7 +143,153 @@ impl PrometheusRegistry {
             "apex_sdk_rpc_duration_seconds",
             "RPC request duration in seconds",
             &["chain", "operation"],
-            vec![0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0],
+            RPC_DURATION_BUCKETS.to_vec(),
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let endpoint_uptime = register_gauge_vec_with_registry!(
+            "apex_sdk_endpoint_uptime_ratio",
+            "Endpoint availability over the SLA window (0-1)",
+            &["endpoint"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let endpoint_latency = register_gauge_vec_with_registry!(
+            "apex_sdk_endpoint_latency_ms",
+            "Endpoint probe latency percentiles over the SLA window in milliseconds",
+            &["endpoint", "quantile"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let blocking_tasks = register_gauge_vec_with_registry!(
+            "apex_sdk_blocking_tasks",
+            "Tasks in blocking pools by state (queued or active)",
+            &["pool", "state"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let retention_purged = register_counter_vec_with_registry!(
+            "apex_sdk_retention_purged_records_total",
+            "Records purged from persistent stores by retention",
+            &["store"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let retention_store_bytes = register_gauge_vec_with_registry!(
+            "apex_sdk_retention_store_bytes",
+            "Size of persistent stores after the latest retention run",
+            &["store"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let system_cpu = register_gauge_with_registry!(
+            "apex_sdk_system_cpu_usage_percent",
+            "Host CPU usage percentage",
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let system_memory = register_gauge_with_registry!(
+            "apex_sdk_system_memory_used_bytes",
+            "Host memory in use in bytes",
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let process_cpu = register_gauge_with_registry!(
+            "apex_sdk_process_cpu_usage_percent",
+            "CPU usage of the SDK process as a percentage of one core",
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let process_memory = register_gauge_with_registry!(
+            "apex_sdk_process_resident_memory_bytes",
+            "Resident set size of the SDK process in bytes",
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let open_fds = register_gauge_with_registry!(
+            "apex_sdk_process_open_fds",
+            "File descriptors open in the SDK process",
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let open_sockets = register_gauge_with_registry!(
+            "apex_sdk_process_open_sockets",
+            "Network sockets open in the SDK process",
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let data_dir_usage = register_gauge_vec_with_registry!(
+            "apex_sdk_data_dir_usage_percent",
+            "Used space percentage of the filesystem holding a data directory",
+            &["dir"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let data_dir_available = register_gauge_vec_with_registry!(
+            "apex_sdk_data_dir_available_bytes",
+            "Free space on the filesystem holding a data directory",
+            &["dir"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let operation_duration = register_gauge_vec_with_registry!(
+            "apex_sdk_operation_duration_seconds",
+            "Profiled operation duration quantiles",
+            &["operation", "quantile"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let operation_count = register_gauge_vec_with_registry!(
+            "apex_sdk_profiled_operations",
+            "Operations recorded by the profiler by outcome (success or error)",
+            &["operation", "outcome"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let cost_usage = register_gauge_vec_with_registry!(
+            "apex_sdk_cost_attribution_usage",
+            "RPC requests, fees and seconds consumed in the last attribution window",
+            &["dimension", "key", "resource"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let cost_share = register_gauge_vec_with_registry!(
+            "apex_sdk_cost_attribution_share",
+            "Share of RPC requests, fees and time in the last attribution window (0-1)",
+            &["dimension", "key", "resource"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let health_status = register_gauge_vec_with_registry!(
+            "apex_sdk_health_status",
+            "Overall SDK health; 1 for the current status, 0 otherwise",
+            &["status"],
+            registry
+        )
+        .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
+
+        let component_status = register_gauge_vec_with_registry!(
+            "apex_sdk_component_status",
+            "Component health; 1 for the current status, 0 otherwise",
+            &["component", "status"],
             registry
         )
         .map_err(|e| MetricsError::PrometheusInit(e.to_string()))?;
@@ -87,16 +301,46 @@ impl PrometheusRegistry {
             gas_usage,
             error_counter,
             rpc_duration,
+            endpoint_uptime,
+            endpoint_latency,
+            blocking_tasks,
+            retention_purged,
+            retention_store_bytes,
+            system_cpu,
+            system_memory,
+            process_cpu,
+            process_memory,
+            open_fds,
+            open_sockets,
+            data_dir_usage,
+            data_dir_available,
+            operation_duration,
+            operation_count,
+            cost_usage,
+            cost_share,
+            health_status,
+            component_status,
+            relabel: RelabelConfig::default(),
         })
     }
 
+    /// Relabel SDK metrics before they are exported
+    ///
+    /// Standard metric families have fixed label names; a family whose
+    /// required label (e.g. `chain`) is dropped is not updated.
+    pub fn with_relabel_config(mut self, relabel: RelabelConfig) -> Self {
+        self.relabel = relabel;
+        self
+    }
+
     /// Update Prometheus metrics from SDK metrics
     pub fn update_from_sdk_metrics(&self, metrics: &[Metric]) {
         for metric in metrics {
+            let labels = self.relabel.apply(&metric.labels);
+
             match metric.metric_type {
                 MetricType::TransactionCount | MetricType::TransactionSuccessRate => {
-                    if let (Some(chain), Some(status)) =
-                        (metric.labels.get("chain"), metric.labels.get("status"))
+                    if let (Some(chain), Some(status)) = (labels.get("chain"), labels.get("status"))
                     {
                         self.transaction_counter
                             .with_label_values(&[chain, status])
@@ -105,10 +349,9 @@ impl PrometheusRegistry {
                 }
 
                 MetricType::TransactionLatency => {
-                    if let Some(chain) = metric.labels.get("chain") {
+                    if let Some(chain) = labels.get("chain") {
                         let default_operation = String::from("unknown");
-                        let operation =
-                            metric.labels.get("operation").unwrap_or(&default_operation);
+                        let operation = labels.get("operation").unwrap_or(&default_operation);
                         self.transaction_duration
                             .with_label_values(&[chain, operation])
                             .observe(metric.value);
@@ -116,17 +359,17 @@ impl PrometheusRegistry {
                 }
 
                 MetricType::GasUsage => {
-                    if let Some(chain) = metric.labels.get("chain") {
+                    if let Some(chain) = labels.get("chain") {
                         self.gas_usage.with_label_values(&[chain]).set(metric.value);
                     }
                 }
 
                 MetricType::ErrorRate => {
                     let default_unknown = String::from("unknown");
-                    let error_type = metric.labels.get("error_type").unwrap_or(&default_unknown);
-                    let operation = metric.labels.get("operation").unwrap_or(&default_unknown);
-                    let category = metric.labels.get("category").unwrap_or(&default_unknown);
-                    let severity = metric.labels.get("severity").unwrap_or(&default_unknown);
+                    let error_type = labels.get("error_type").unwrap_or(&default_unknown);
+                    let operation = labels.get("operation").unwrap_or(&default_unknown);
+                    let category = labels.get("category").unwrap_or(&default_unknown);
+                    let severity = labels.get("severity").unwrap_or(&default_unknown);
 
                     self.error_counter
                         .with_label_values(&[error_type, operation, category, severity])
@@ -134,21 +377,192 @@ impl PrometheusRegistry {
                 }
 
                 MetricType::ProviderResponseTime => {
-                    if let Some(chain) = metric.labels.get("chain") {
+                    if let Some(chain) = labels.get("chain") {
                         let default_operation = String::from("unknown");
-                        let operation =
-                            metric.labels.get("operation").unwrap_or(&default_operation);
+                        let operation = labels.get("operation").unwrap_or(&default_operation);
                         self.rpc_duration
                             .with_label_values(&[chain, operation])
                             .observe(metric.value);
                     }
                 }
 
+                MetricType::QueueDepth => {
+                    let state = match metric.name.as_str() {
+                        "blocking_queue_depth" => "queued",
+                        "blocking_active_tasks" => "active",
+                        _ => continue,
+                    };
+                    if let Some(pool) = labels.get("pool") {
+                        self.blocking_tasks
+                            .with_label_values(&[pool.as_str(), state])
+                            .set(metric.value);
+                    }
+                }
+
+                MetricType::Retention => {
+                    let Some(store) = labels.get("store") else {
+                        continue;
+                    };
+                    match metric.name.as_str() {
+                        // Records carry the running total; only add what is new
+                        "retention_purged_records" => {
+                            let counter = self.retention_purged.with_label_values(&[store]);
+                            let delta = metric.value - counter.get();
+                            if delta > 0.0 {
+                                counter.inc_by(delta);
+                            }
+                        }
+                        "retention_store_bytes" => {
+                            self.retention_store_bytes
+                                .with_label_values(&[store])
+                                .set(metric.value);
+                        }
+                        _ => {}
+                    }
+                }
+
                 _ => {}
             }
         }
     }
 
+    /// Update endpoint uptime gauges from SLA reports
+    ///
+    /// The `endpoint` label goes through the same relabeling as SDK metrics;
+    /// reports whose `endpoint` label is dropped are not exported.
+    pub fn update_from_uptime(&self, reports: &[UptimeReport]) {
+        for report in reports {
+            let labels = self.relabel.apply(&HashMap::from([(
+                "endpoint".to_string(),
+                report.endpoint.clone(),
+            )]));
+            let Some(endpoint) = labels.get("endpoint") else {
+                continue;
+            };
+
+            self.endpoint_uptime
+                .with_label_values(&[endpoint])
+                .set(report.uptime_percent / 100.0);
+
+            for (quantile, latency) in [
+                ("0.5", report.latency_p50_ms),
+                ("0.95", report.latency_p95_ms),
+                ("0.99", report.latency_p99_ms),
+            ] {
+                if let Some(latency) = latency {
+                    self.endpoint_latency
+                        .with_label_values(&[endpoint.as_str(), quantile])
+                        .set(latency);
+                }
+            }
+        }
+    }
+
+    /// Update host and process resource gauges
+    pub fn update_from_resources(&self, resources: &SystemResources) {
+        self.system_cpu.set(resources.cpu_usage_percent as f64);
+        self.system_memory.set(resources.memory_used_bytes as f64);
+        self.process_cpu
+            .set(resources.process_cpu_usage_percent as f64);
+        self.process_memory
+            .set(resources.process_memory_bytes as f64);
+        if let Some(open) = resources.open_file_descriptors {
+            self.open_fds.set(open as f64);
+        }
+        if let Some(sockets) = resources.open_sockets {
+            self.open_sockets.set(sockets as f64);
+        }
+        for disk in &resources.data_dirs {
+            self.data_dir_usage
+                .with_label_values(&[disk.name.as_str()])
+                .set(disk.usage_percent as f64);
+            self.data_dir_available
+                .with_label_values(&[disk.name.as_str()])
+                .set(disk.available_bytes as f64);
+        }
+    }
+
+    /// Update operation gauges from profiler statistics
+    ///
+    /// Replaces previous values, so operations the profiler no longer holds
+    /// spans for disappear.
+    pub fn update_from_profiler(&self, stats: &[OperationStats]) {
+        self.operation_duration.reset();
+        self.operation_count.reset();
+
+        for stats in stats {
+            let operation = stats.operation_type.to_string();
+            for (quantile, duration) in [
+                ("0.5", stats.p50_duration_secs),
+                ("0.95", stats.p95_duration_secs),
+                ("0.99", stats.p99_duration_secs),
+            ] {
+                self.operation_duration
+                    .with_label_values(&[operation.as_str(), quantile])
+                    .set(duration);
+            }
+            self.operation_count
+                .with_label_values(&[operation.as_str(), "success"])
+                .set(stats.success_count as f64);
+            self.operation_count
+                .with_label_values(&[operation.as_str(), "error"])
+                .set(stats.error_count as f64);
+        }
+    }
+
+    /// Update cost attribution gauges from a report
+    ///
+    /// Gauges are labelled with `dimension` (`operation` or `caller`), its
+    /// `key` and the `resource` (`rpc_calls`, `fees` or `time`). Replaces
+    /// previous values, so callers absent from the report disappear.
+    pub fn update_from_attribution(&self, report: &AttributionReport) {
+        self.cost_usage.reset();
+        self.cost_share.reset();
+
+        let operations = report.by_operation.iter().map(|share| {
+            let key = share.operation.map(|o| o.to_string()).unwrap_or_default();
+            ("operation", key, share)
+        });
+        let callers = report
+            .by_caller
+            .iter()
+            .map(|share| ("caller", share.caller.clone().unwrap_or_default(), share));
+
+        for (dimension, key, share) in operations.chain(callers) {
+            for (resource, usage, ratio) in [
+                ("rpc_calls", share.usage.rpc_calls as f64, share.rpc_share),
+                ("fees", share.usage.fees, share.fee_share),
+                ("time", share.usage.time_secs, share.time_share),
+            ] {
+                let labels = [dimension, key.as_str(), resource];
+                self.cost_usage.with_label_values(&labels).set(usage);
+                self.cost_share.with_label_values(&labels).set(ratio);
+            }
+        }
+    }
+
+    /// Update health status and resource gauges from a health summary
+    ///
+    /// Replaces previous component values, so removed components disappear.
+    pub fn update_from_health(&self, summary: &HealthSummary) {
+        for status in HEALTH_STATUSES {
+            self.health_status
+                .with_label_values(&[status_label(status)])
+                .set((summary.status == status) as u8 as f64);
+        }
+
+        self.component_status.reset();
+        for component in &summary.components {
+            for status in HEALTH_STATUSES {
+                self.component_status
+                    .with_label_values(&[component.name.as_str(), status_label(status)])
+                    .set((component.status == status) as u8 as f64);
+            }
+        }
+
+        self.update_from_resources(&summary.resources);
+    }
+
     /// Export all metrics in Prometheus text format
     pub fn export(&self) -> Result<String> {
         let encoder = TextEncoder::new();
@@ -166,6 +580,109 @@ impl PrometheusRegistry {
     pub fn registry(&self) -> &Registry {
         &self.registry
     }
+
+    /// Describe the metric families in the registry
+    ///
+    /// Lists the SDK's standard families, whether or not they have samples
+    /// yet, followed by any other family registered on
+    /// [`registry`](Self::registry) that has samples. Used by
+    /// [`DashboardGenerator`](crate::dashboards::DashboardGenerator).
+    pub fn families(&self) -> Vec<MetricFamilyInfo> {
+        use MetricKind::{Counter, Gauge, Histogram};
+
+        let standard: [(&dyn Collector, MetricKind, &[f64]); 24] = [
+            (&self.transaction_counter, Counter, &[]),
+            (
+                &self.transaction_duration,
+                Histogram,
+                &TRANSACTION_DURATION_BUCKETS,
+            ),
+            (&self.gas_usage, Gauge, &[]),
+            (&self.error_counter, Counter, &[]),
+            (&self.rpc_duration, Histogram, &RPC_DURATION_BUCKETS),
+            (&self.endpoint_uptime, Gauge, &[]),
+            (&self.endpoint_latency, Gauge, &[]),
+            (&self.blocking_tasks, Gauge, &[]),
+            (&self.retention_purged, Counter, &[]),
+            (&self.retention_store_bytes, Gauge, &[]),
+            (&self.system_cpu, Gauge, &[]),
+            (&self.system_memory, Gauge, &[]),
+            (&self.process_cpu, Gauge, &[]),
+            (&self.process_memory, Gauge, &[]),
+            (&self.open_fds, Gauge, &[]),
+            (&self.open_sockets, Gauge, &[]),
+            (&self.data_dir_usage, Gauge, &[]),
+            (&self.data_dir_available, Gauge, &[]),
+            (&self.operation_duration, Gauge, &[]),
+            (&self.operation_count, Gauge, &[]),
+            (&self.cost_usage, Gauge, &[]),
+            (&self.cost_share, Gauge, &[]),
+            (&self.health_status, Gauge, &[]),
+            (&self.component_status, Gauge, &[]),
+        ];
+
+        let mut families: Vec<MetricFamilyInfo> = Vec::new();
+        let mut const_labels = Vec::new();
+        for (collector, kind, buckets) in standard {
+            for desc in collector.desc() {
+                if families.iter().any(|f| f.name == desc.fq_name) {
+                    continue;
+                }
+                const_labels = desc
+                    .const_label_pairs
+                    .iter()
+                    .map(|pair| pair.name().to_string())
+                    .collect();
+                families.push(MetricFamilyInfo {
+                    name: desc.fq_name.clone(),
+                    help: desc.help.clone(),
+                    kind,
+                    labels: desc.variable_labels.clone(),
+                    buckets: buckets.to_vec(),
+                });
+            }
+        }
+
+        for family in self.registry.gather() {
+            if families.iter().any(|f| f.name == family.name()) {
+                continue;
+            }
+            let kind = match family.get_field_type() {
+                PromMetricType::COUNTER => Counter,
+                PromMetricType::GAUGE => Gauge,
+                PromMetricType::HISTOGRAM => Histogram,
+                _ => continue,
+            };
+            let Some(sample) = family.get_metric().first() else {
+                continue;
+            };
+            let buckets = if kind == Histogram {
+                sample
+                    .get_histogram()
+                    .get_bucket()
+                    .iter()
+                    .map(|bucket| bucket.upper_bound())
+                    .filter(|bound| bound.is_finite())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            families.push(MetricFamilyInfo {
+                name: family.name().to_string(),
+                help: family.help().to_string(),
+                kind,
+                labels: sample
+                    .get_label()
+                    .iter()
+                    .map(|pair| pair.name().to_string())
+                    .filter(|name| !const_labels.contains(name))
+                    .collect(),
+                buckets,
+            });
+        }
+
+        families
+    }
 }
 
 impl Default for PrometheusRegistry {
@@ -174,74 +691,508 @@ impl Default for PrometheusRegistry {
     }
 }
 
-/// Metrics server state
+/// Registry and the sources refreshed into it before each export
+///
+/// Shared by the scrape endpoint and [`MetricsPusher`](crate::MetricsPusher).
 #[derive(Clone)]
-struct ServerState {
-    prometheus_registry: Arc<PrometheusRegistry>,
-    sdk_metrics: Arc<MetricsCollector>,
+pub(crate) struct ExportState {
+    pub(crate) prometheus_registry: Arc<PrometheusRegistry>,
+    pub(crate) sdk_metrics: Arc<MetricsCollector>,
+    pub(crate) uptime: Option<Arc<UptimeTracker>>,
+    pub(crate) health: Option<Arc<HealthChecker>>,
+    pub(crate) profiler: Option<PerformanceProfiler>,
+    pub(crate) attribution: Option<Arc<CostAttributor>>,
+}
+
+impl ExportState {
+    pub(crate) fn new(
+        prometheus_registry: PrometheusRegistry,
+        sdk_metrics: MetricsCollector,
+    ) -> Self {
+        Self {
+            prometheus_registry: Arc::new(prometheus_registry),
+            sdk_metrics: Arc::new(sdk_metrics),
+            uptime: None,
+            health: None,
+            profiler: None,
+            attribution: None,
+        }
+    }
+
+    /// Update the registry from every attached source
+    pub(crate) async fn refresh(&self) {
+        let sdk_metrics = self.sdk_metrics.get_metrics();
+
+        self.prometheus_registry
+            .update_from_sdk_metrics(&sdk_metrics);
+        if let Some(uptime) = &self.uptime {
+            self.prometheus_registry
+                .update_from_uptime(&uptime.reports());
+        }
+        if let Some(health) = &self.health {
+            self.prometheus_registry
+                .update_from_health(&health.health_summary_async().await);
+        }
+        if let Some(profiler) = &self.profiler {
+            self.prometheus_registry
+                .update_from_profiler(&profiler.all_operation_stats());
+        }
+        if let Some(report) = self.attribution.as_ref().and_then(|a| a.latest()) {
+            self.prometheus_registry.update_from_attribution(&report);
+        }
+    }
+}
+
+const HEALTH_STATUSES: [HealthStatus; 4] = [
+    HealthStatus::Healthy,
+    HealthStatus::Degraded,
+    HealthStatus::Unhealthy,
+    HealthStatus::Unknown,
+];
+
+fn status_label(status: HealthStatus) -> &'static str {
+    match status {
+        HealthStatus::Healthy => "healthy",
+        HealthStatus::Degraded => "degraded",
+        HealthStatus::Unhealthy => "unhealthy",
+        HealthStatus::Unknown => "unknown",
+    }
 }
 
 /// Prometheus metrics HTTP server
 pub struct MetricsServer {
+    bind_address: IpAddr,
     port: u16,
-    state: ServerState,
+    state: ExportState,
+    readiness: Option<Readiness>,
+    auth: Option<ScrapeAuth>,
+    tls: Option<Arc<ServerConfig>>,
 }
 
 impl MetricsServer {
     /// Create a new metrics server
     pub async fn new(port: u16, sdk_metrics: MetricsCollector) -> Result<Self> {
-        let prometheus_registry = Arc::new(PrometheusRegistry::new()?);
+        Self::with_relabel_config(port, sdk_metrics, RelabelConfig::default()).await
+    }
 
+    /// Create a new metrics server that relabels metrics before export
+    pub async fn with_relabel_config(
+        port: u16,
+        sdk_metrics: MetricsCollector,
+        relabel: RelabelConfig,
+    ) -> Result<Self> {
+        let prometheus_registry = PrometheusRegistry::new()?.with_relabel_config(relabel);
+        Self::with_registry(port, sdk_metrics, prometheus_registry).await
+    }
+
+    /// Create a metrics server exporting a preconfigured registry
+    pub async fn with_registry(
+        port: u16,
+        sdk_metrics: MetricsCollector,
+        prometheus_registry: PrometheusRegistry,
+    ) -> Result<Self> {
         Ok(Self {
+            bind_address: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
             port,
-            state: ServerState {
-                prometheus_registry,
-                sdk_metrics: Arc::new(sdk_metrics),
-            },
+            state: ExportState::new(prometheus_registry, sdk_metrics),
+            readiness: None,
+            auth: None,
+            tls: None,
         })
     }
 
+    /// Create a metrics server on the configured address and port
+    ///
+    /// Applies the configured relabeling and, when running in Kubernetes, the
+    /// pod metadata const labels (see [`ObservabilityConfig::prometheus_registry`]).
+    pub async fn from_config(
+        config: &ObservabilityConfig,
+        sdk_metrics: MetricsCollector,
+    ) -> Result<Self> {
+        let server = Self::with_registry(
+            config.prometheus_port,
+            sdk_metrics,
+            config.prometheus_registry()?,
+        )
+        .await?;
+        Ok(server.with_bind_address(config.prometheus_bind_address))
+    }
+
+    /// Listen on `address` instead of all interfaces
+    pub fn with_bind_address(mut self, address: IpAddr) -> Self {
+        self.bind_address = address;
+        self
+    }
+
+    /// Require HTTP basic auth on `/metrics`
+    ///
+    /// `/health` and `/ready` stay open for orchestrator probes.
+    pub fn with_basic_auth(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.auth = Some(ScrapeAuth::basic(&username.into(), &password.into()));
+        self
+    }
+
+    /// Require a bearer token on `/metrics`
+    ///
+    /// `/health` and `/ready` stay open for orchestrator probes.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.auth = Some(ScrapeAuth::bearer(token.into()));
+        self
+    }
+
+    /// Serve HTTPS with a PEM certificate chain and private key read from files
+    pub fn with_tls(self, cert_path: impl AsRef<Path>, key_path: impl AsRef<Path>) -> Result<Self> {
+        let read = |path: &Path| {
+            std::fs::read(path).map_err(|e| {
+                MetricsError::ServerStart(format!("Failed to read {}: {}", path.display(), e))
+            })
+        };
+        let cert_pem = read(cert_path.as_ref())?;
+        let key_pem = read(key_path.as_ref())?;
+        self.with_tls_pem(&cert_pem, &key_pem)
+    }
+
+    /// Serve HTTPS with a PEM certificate chain and private key
+    ///
+    /// The chain starts with the server's certificate. Keys may be PKCS#8,
+    /// PKCS#1 (RSA) or SEC1 (EC).
+    pub fn with_tls_pem(mut self, cert_pem: &[u8], key_pem: &[u8]) -> Result<Self> {
+        let tls_error = |e: &dyn std::fmt::Display| {
+            MetricsError::ServerStart(format!("Invalid TLS configuration: {}", e))
+        };
+        let certs = CertificateDer::pem_slice_iter(cert_pem)
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| tls_error(&e))?;
+        if certs.is_empty() {
+            return Err(tls_error(&"no certificate in PEM"));
+        }
+        let key = PrivateKeyDer::from_pem_slice(key_pem).map_err(|e| tls_error(&e))?;
+
+        // Both rustls providers are linked in, so name one explicitly
+        let mut config =
+            ServerConfig::builder_with_provider(Arc::new(crypto::aws_lc_rs::default_provider()))
+                .with_safe_default_protocol_versions()
+                .map_err(|e| tls_error(&e))?
+                .with_no_client_auth()
+                .with_single_cert(certs, key)
+                .map_err(|e| tls_error(&e))?;
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+        self.tls = Some(Arc::new(config));
+        Ok(self)
+    }
+
+    /// Export endpoint uptime reports alongside SDK metrics
+    pub fn with_uptime_tracker(mut self, tracker: Arc<UptimeTracker>) -> Self {
+        self.state.uptime = Some(tracker);
+        self
+    }
+
+    /// Export component health and the resource gauges of a health checker
+    ///
+    /// Scrapes reuse the checker's cached resource sample; run
+    /// [`HealthChecker::spawn_sampler`] to keep it fresh. `/health` then
+    /// returns the checker's [`HealthSummary`] as JSON, and `/health` and
+    /// `/ready` answer `503 Service Unavailable` while it is unhealthy.
+    pub fn with_health_checker(mut self, health: Arc<HealthChecker>) -> Self {
+        self.state.health = Some(health);
+        self
+    }
+
+    /// Export per-operation duration quantiles and counts of a profiler
+    pub fn with_profiler(mut self, profiler: PerformanceProfiler) -> Self {
+        self.state.profiler = Some(profiler);
+        self
+    }
+
+    /// Export the latest report of a cost attributor
+    ///
+    /// Scrapes do not close attribution windows; run
+    /// [`CostAttributor::spawn`] to produce reports.
+    pub fn with_cost_attributor(mut self, attributor: Arc<CostAttributor>) -> Self {
+        self.state.attribution = Some(attributor);
+        self
+    }
+
+    /// Gate `/ready` on readiness conditions
+    ///
+    /// `/ready` responds `503 Service Unavailable` until every required
+    /// condition holds, with the condition report as JSON. Without readiness
+    /// conditions or a health checker it always responds ready.
+    pub fn with_readiness(mut self, readiness: Readiness) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
     /// Start the metrics server
+    ///
+    /// Runs until the task is aborted; use [`spawn`](Self::spawn) or
+    /// [`start_with_shutdown`](Self::start_with_shutdown) to stop it cleanly.
     pub async fn start(self) -> Result<()> {
-        let app = Router::new()
-            .route("/metrics", get(metrics_handler))
-            .route("/health", get(health_handler))
-            .route("/ready", get(ready_handler))
-            .with_state(self.state);
+        self.start_with_shutdown(std::future::pending()).await
+    }
 
-        let addr = SocketAddr::from(([0, 0, 0, 0], self.port));
-        let listener = TcpListener::bind(addr)
-            .await
+    /// Start the metrics server and stop it once `signal` completes
+    ///
+    /// Stops accepting connections when `signal` completes and returns after
+    /// in-flight scrapes have been answered.
+    pub async fn start_with_shutdown(
+        self,
+        signal: impl Future<Output = ()> + Send + 'static,
+    ) -> Result<()> {
+        let listener = self.bind().await?;
+        self.serve(listener, signal).await
+    }
+
+    /// Start the metrics server in the background
+    pub fn start_background(self) -> JoinHandle<Result<()>> {
+        tokio::spawn(async move { self.start().await })
+    }
+
+    /// Bind and start the metrics server in the background
+    ///
+    /// Binding errors are returned here rather than from the task. Dropping
+    /// the returned handle also shuts the server down.
+    pub async fn spawn(self) -> Result<MetricsServerHandle> {
+        let listener = self.bind().await?;
+        let local_addr = listener
+            .local_addr()
             .map_err(|e| MetricsError::ServerStart(e.to_string()))?;
+        let (shutdown, signal) = oneshot::channel();
+        let task = tokio::spawn(self.serve(listener, async {
+            let _ = signal.await;
+        }));
 
-        info!("Metrics server listening on http://{}", addr);
-        info!("Prometheus metrics available at http://{}/metrics", addr);
-        info!("Health check available at http://{}/health", addr);
+        Ok(MetricsServerHandle {
+            local_addr,
+            shutdown: Some(shutdown),
+            task,
+        })
+    }
 
-        axum::serve(listener, app)
+    async fn bind(&self) -> Result<TcpListener> {
+        let addr = SocketAddr::new(self.bind_address, self.port);
+        TcpListener::bind(addr)
             .await
+            .map_err(|e| MetricsError::ServerStart(format!("Failed to bind {}: {}", addr, e)))
+    }
+
+    async fn serve(
+        self,
+        listener: TcpListener,
+        signal: impl Future<Output = ()> + Send + 'static,
+    ) -> Result<()> {
+        let readiness = self.readiness;
+        let health = self.state.health.clone();
+
+        let mut app = Router::new().route("/metrics", get(metrics_handler));
+        // Route layers only wrap the routes added before them
+        if let Some(auth) = self.auth {
+            app = app.route_layer(middleware::from_fn_with_state(Arc::new(auth), require_auth));
+        }
+        let app = app
+            .route("/health", get(health_handler))
+            .route(
+                "/ready",
+                get(move || ready_handler(readiness.clone(), health.clone())),
+            )
+            .with_state(self.state);
+
+        let addr = listener
+            .local_addr()
             .map_err(|e| MetricsError::ServerStart(e.to_string()))?;
+        let scheme = if self.tls.is_some() { "https" } else { "http" };
+        info!(
+            endpoint = %format!("{}://{}", scheme, addr),
+            operation = "metrics_server",
+            "Metrics server listening (/metrics, /health, /ready)"
+        );
+
+        let served = match self.tls {
+            Some(config) => {
+                let listener = TlsListener::new(listener, config);
+                axum::serve(listener, app)
+                    .with_graceful_shutdown(signal)
+                    .await
+            }
+            None => {
+                axum::serve(listener, app)
+                    .with_graceful_shutdown(signal)
+                    .await
+            }
+        };
+        served.map_err(|e| MetricsError::ServerStart(e.to_string()))?;
 
+        info!(operation = "metrics_server", "Metrics server stopped");
         Ok(())
     }
+}
 
-    /// Start the metrics server in the background
-    pub fn start_background(self) -> tokio::task::JoinHandle<Result<()>> {
-        tokio::spawn(async move { self.start().await })
+/// Handle to a metrics server started with [`MetricsServer::spawn`]
+pub struct MetricsServerHandle {
+    local_addr: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+    task: JoinHandle<Result<()>>,
+}
+
+impl MetricsServerHandle {
+    /// Address the server is listening on
+    ///
+    /// Holds the assigned port when the server was created with port `0`.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stop accepting connections and wait for in-flight scrapes to finish
+    pub async fn shutdown(mut self) -> Result<()> {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        (&mut self.task)
+            .await
+            .map_err(|e| MetricsError::ServerStart(format!("Metrics server task failed: {}", e)))?
     }
 }
 
-async fn metrics_handler(State(state): State<ServerState>) -> Response {
-    let sdk_metrics = state.sdk_metrics.get_metrics();
+/// Credentials `/metrics` requires, as sent in the `Authorization` header
+struct ScrapeAuth {
+    scheme: &'static str,
+    credentials: String,
+}
+
+impl ScrapeAuth {
+    fn basic(username: &str, password: &str) -> Self {
+        Self {
+            scheme: "Basic",
+            credentials: base64::engine::general_purpose::STANDARD
+                .encode(format!("{}:{}", username, password)),
+        }
+    }
+
+    fn bearer(token: String) -> Self {
+        Self {
+            scheme: "Bearer",
+            credentials: token,
+        }
+    }
 
-    state
-        .prometheus_registry
-        .update_from_sdk_metrics(&sdk_metrics);
+    fn authorizes(&self, headers: &HeaderMap) -> bool {
+        let Some(value) = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+        else {
+            return false;
+        };
+        let Some((scheme, credentials)) = value.split_once(' ') else {
+            return false;
+        };
+        scheme.eq_ignore_ascii_case(self.scheme)
+            && constant_time_eq(credentials.trim().as_bytes(), self.credentials.as_bytes())
+    }
+}
+
+/// Compare secrets without returning early on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+async fn require_auth(
+    State(auth): State<Arc<ScrapeAuth>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if auth.authorizes(request.headers()) {
+        return next.run(request).await;
+    }
+    (
+        StatusCode::UNAUTHORIZED,
+        [(
+            header::WWW_AUTHENTICATE,
+            format!("{} realm=\"metrics\"", auth.scheme),
+        )],
+        "unauthorized",
+    )
+        .into_response()
+}
+
+/// Time a client has to complete the TLS handshake
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// TCP listener that hands out connections once their TLS handshake is done
+///
+/// Handshakes run concurrently, so a slow or stalled client does not hold up
+/// other connections.
+struct TlsListener {
+    tcp: TcpListener,
+    acceptor: TlsAcceptor,
+    handshakes: JoinSet<Option<(TlsStream<TcpStream>, SocketAddr)>>,
+}
+
+impl TlsListener {
+    fn new(tcp: TcpListener, config: Arc<ServerConfig>) -> Self {
+        Self {
+            tcp,
+            acceptor: TlsAcceptor::from(config),
+            handshakes: JoinSet::new(),
+        }
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        loop {
+            tokio::select! {
+                accepted = self.tcp.accept() => match accepted {
+                    Ok((stream, peer)) => {
+                        let acceptor = self.acceptor.clone();
+                        self.handshakes.spawn(async move {
+                            match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                                Ok(Ok(stream)) => Some((stream, peer)),
+                                Ok(Err(e)) => {
+                                    debug!(peer = %peer, error = %e, "TLS handshake failed");
+                                    None
+                                }
+                                Err(_) => {
+                                    debug!(peer = %peer, "TLS handshake timed out");
+                                    None
+                                }
+                            }
+                        });
+                    }
+                    Err(e) => {
+                        // Mostly running out of file descriptors; back off
+                        // like axum's own TCP listener
+                        error!(operation = "metrics_server", error = %e, "Failed to accept connection");
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                    }
+                },
+                Some(handshake) = self.handshakes.join_next() => {
+                    if let Ok(Some(connection)) = handshake {
+                        return connection;
+                    }
+                }
+            }
+        }
+    }
+
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        self.tcp.local_addr()
+    }
+}
+
+async fn metrics_handler(State(state): State<ExportState>) -> Response {
+    state.refresh().await;
 
     match state.prometheus_registry.export() {
         Ok(metrics) => (StatusCode::OK, metrics).into_response(),
         Err(e) => {
-            error!("Failed to export metrics: {}", e);
+            error!(operation = "metrics_export", error = %e, "Failed to export metrics");
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Failed to export metrics: {}", e),
@@ -251,17 +1202,80 @@ async fn metrics_handler(State(state): State<ServerState>) -> Response {
     }
 }
 
-async fn health_handler() -> impl IntoResponse {
-    (StatusCode::OK, "healthy")
+/// Liveness: the health summary as JSON, `503` while the overall status is
+/// unhealthy
+///
+/// Without a health checker it always answers `200 OK`.
+async fn health_handler(State(state): State<ExportState>) -> Response {
+    let Some(health) = &state.health else {
+        return (StatusCode::OK, "healthy").into_response();
+    };
+
+    let summary = health.health_summary_async().await;
+    let status = if summary.status == HealthStatus::Unhealthy {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (status, axum::Json(summary)).into_response()
 }
 
-async fn ready_handler() -> impl IntoResponse {
-    (StatusCode::OK, "ready")
+/// Body of a `/ready` response
+#[derive(Debug, Serialize)]
+struct ReadyResponse {
+    /// Every required condition holds and no component is unhealthy
+    ready: bool,
+    conditions: Vec<ReadinessCondition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    health: Option<HealthSummary>,
+}
+
+/// Readiness: `503` until every required condition holds, or while the
+/// health summary is unhealthy
+///
+/// Without readiness conditions or a health checker it always answers
+/// `200 OK`.
+async fn ready_handler(
+    readiness: Option<Readiness>,
+    health: Option<Arc<HealthChecker>>,
+) -> Response {
+    if readiness.is_none() && health.is_none() {
+        return (StatusCode::OK, "ready").into_response();
+    }
+
+    let (mut ready, conditions) = match readiness {
+        Some(readiness) => {
+            let report = readiness.report();
+            (report.ready, report.conditions)
+        }
+        None => (true, Vec::new()),
+    };
+    let health = match health {
+        Some(health) => Some(health.health_summary_async().await),
+        None => None,
+    };
+    if let Some(summary) = &health {
+        ready &= summary.status != HealthStatus::Unhealthy;
+    }
+
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let body = ReadyResponse {
+        ready,
+        conditions,
+        health,
+    };
+    (status, axum::Json(body)).into_response()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::health::{ComponentHealth, DiskUsage};
+    use crate::profiling::OperationType;
 
     #[tokio::test]
     async fn test_prometheus_registry_creation() {
@@ -285,10 +1299,414 @@ mod tests {
         assert!(exported.contains("apex_sdk_gas_used"));
     }
 
+    #[tokio::test]
+    async fn test_relabeling_before_export() {
+        let registry = PrometheusRegistry::new().unwrap().with_relabel_config(
+            RelabelConfig::new()
+                .rename_label("network", "chain")
+                .map_value("chain", "eth", "ethereum"),
+        );
+
+        let metric = apex_sdk_core::metrics::Metric::new(MetricType::GasUsage, "gas_used", 21000.0)
+            .with_label("network", "eth");
+        registry.update_from_sdk_metrics(&[metric]);
+
+        let exported = registry.export().unwrap();
+        assert!(exported.contains("apex_sdk_gas_used{chain=\"ethereum\"} 21000"));
+    }
+
+    #[tokio::test]
+    async fn test_uptime_gauges() {
+        let registry = PrometheusRegistry::new().unwrap();
+        let tracker = UptimeTracker::new();
+        tracker.record("rpc-a", crate::AvailabilitySample::now(true, Some(120)));
+        tracker.record("rpc-a", crate::AvailabilitySample::now(false, None));
+
+        registry.update_from_uptime(&tracker.reports());
+
+        let exported = registry.export().unwrap();
+        assert!(exported.contains("apex_sdk_endpoint_uptime_ratio{endpoint=\"rpc-a\"} 0.5"));
+        assert!(exported
+            .contains("apex_sdk_endpoint_latency_ms{endpoint=\"rpc-a\",quantile=\"0.99\"} 120"));
+    }
+
+    #[tokio::test]
+    async fn test_uptime_gauges_are_relabeled() {
+        let registry =
+            PrometheusRegistry::new()
+                .unwrap()
+                .with_relabel_config(RelabelConfig::new().map_value(
+                    "endpoint",
+                    "wss://rpc.example.com",
+                    "primary",
+                ));
+        let tracker = UptimeTracker::new();
+        tracker.record(
+            "wss://rpc.example.com",
+            crate::AvailabilitySample::now(true, Some(80)),
+        );
+
+        registry.update_from_uptime(&tracker.reports());
+
+        let exported = registry.export().unwrap();
+        assert!(exported.contains("apex_sdk_endpoint_uptime_ratio{endpoint=\"primary\"} 1"));
+        assert!(exported
+            .contains("apex_sdk_endpoint_latency_ms{endpoint=\"primary\",quantile=\"0.5\"} 80"));
+        assert!(!exported.contains("rpc.example.com"));
+    }
+
+    #[test]
+    fn test_resource_gauges() {
+        let registry = PrometheusRegistry::new().unwrap();
+        registry.update_from_resources(&SystemResources {
+            cpu_usage_percent: 12.5,
+            memory_used_bytes: 4096,
+            memory_total_bytes: 8192,
+            memory_usage_percent: 50.0,
+            process_cpu_usage_percent: 150.0,
+            process_memory_bytes: 1024,
+            open_file_descriptors: Some(42),
+            file_descriptor_limit: Some(1024),
+            open_sockets: Some(7),
+            data_dirs: vec![DiskUsage {
+                name: "keystore".to_string(),
+                path: "/var/lib/apex/keystore".into(),
+                mount_point: "/var".into(),
+                total_bytes: 1000,
+                available_bytes: 250,
+                usage_percent: 75.0,
+            }],
+        });
+
+        let exported = registry.export().unwrap();
+        assert!(exported.contains("apex_sdk_system_cpu_usage_percent 12.5"));
+        assert!(exported.contains("apex_sdk_system_memory_used_bytes 4096"));
+        assert!(exported.contains("apex_sdk_process_cpu_usage_percent 150"));
+        assert!(exported.contains("apex_sdk_process_resident_memory_bytes 1024"));
+        assert!(exported.contains("apex_sdk_process_open_fds 42"));
+        assert!(exported.contains("apex_sdk_process_open_sockets 7"));
+        assert!(exported.contains("apex_sdk_data_dir_usage_percent{dir=\"keystore\"} 75"));
+        assert!(exported.contains("apex_sdk_data_dir_available_bytes{dir=\"keystore\"} 250"));
+    }
+
+    #[test]
+    fn test_profiler_gauges() {
+        let registry = PrometheusRegistry::new().unwrap();
+        let profiler = PerformanceProfiler::new();
+        profiler.start_span(OperationType::BlockQuery).success();
+        profiler
+            .start_span(OperationType::BlockQuery)
+            .error("timeout");
+
+        registry.update_from_profiler(&profiler.all_operation_stats());
+        let exported = registry.export().unwrap();
+        assert!(exported.contains(
+            "apex_sdk_operation_duration_seconds{operation=\"block_query\",quantile=\"0.99\"}"
+        ));
+        assert!(exported.contains(
+            "apex_sdk_profiled_operations{operation=\"block_query\",outcome=\"error\"} 1"
+        ));
+
+        profiler.clear();
+        registry.update_from_profiler(&profiler.all_operation_stats());
+        assert!(!registry.export().unwrap().contains("block_query"));
+    }
+
+    #[test]
+    fn test_attribution_gauges() {
+        use crate::attribution::{CostShare, CostUsage};
+
+        let registry = PrometheusRegistry::new().unwrap();
+        let usage = CostUsage {
+            rpc_calls: 4,
+            fees: 0.5,
+            time_secs: 2.0,
+        };
+        let report = AttributionReport {
+            window_start: 0,
+            window_end: 60,
+            totals: usage,
+            by_operation: vec![CostShare {
+                operation: Some(OperationType::BlockQuery),
+                caller: None,
+                usage,
+                rpc_share: 1.0,
+                fee_share: 1.0,
+                time_share: 1.0,
+            }],
+            by_caller: vec![CostShare {
+                operation: None,
+                caller: Some("indexer".to_string()),
+                usage,
+                rpc_share: 1.0,
+                fee_share: 1.0,
+                time_share: 1.0,
+            }],
+            entries: vec![],
+        };
+
+        registry.update_from_attribution(&report);
+        let exported = registry.export().unwrap();
+        assert!(exported.contains(
+            "apex_sdk_cost_attribution_usage{dimension=\"caller\",key=\"indexer\",resource=\"rpc_calls\"} 4"
+        ));
+        assert!(exported.contains(
+            "apex_sdk_cost_attribution_share{dimension=\"operation\",key=\"block_query\",resource=\"fees\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_health_gauges() {
+        let registry = PrometheusRegistry::new().unwrap();
+        let checker = HealthChecker::new();
+        checker.update_component(ComponentHealth::new("rpc", HealthStatus::Degraded));
+        checker.update_component(ComponentHealth::new("metrics", HealthStatus::Healthy));
+
+        registry.update_from_health(&checker.health_summary());
+        let exported = registry.export().unwrap();
+        assert!(exported.contains("apex_sdk_health_status{status=\"degraded\"} 1"));
+        assert!(exported.contains("apex_sdk_health_status{status=\"healthy\"} 0"));
+        assert!(
+            exported.contains("apex_sdk_component_status{component=\"rpc\",status=\"degraded\"} 1")
+        );
+        assert!(exported
+            .contains("apex_sdk_component_status{component=\"metrics\",status=\"healthy\"} 1"));
+        assert!(exported.contains("apex_sdk_system_memory_used_bytes"));
+
+        checker.remove_component("rpc");
+        registry.update_from_health(&checker.health_summary());
+        assert!(!registry.export().unwrap().contains("component=\"rpc\""));
+    }
+
+    #[tokio::test]
+    async fn test_ready_handler_gates_on_conditions() {
+        assert_eq!(ready_handler(None, None).await.status(), StatusCode::OK);
+
+        let readiness = Readiness::new().with_requirement("adapter_connected");
+        let response = ready_handler(Some(readiness.clone()), None).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        readiness.mark_ready("adapter_connected");
+        let response = ready_handler(Some(readiness), None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoints_report_summary() {
+        let checker = Arc::new(HealthChecker::new());
+        checker.update_component(ComponentHealth::new("rpc", HealthStatus::Degraded));
+        let mut state =
+            ExportState::new(PrometheusRegistry::new().unwrap(), MetricsCollector::new());
+        state.health = Some(checker.clone());
+
+        let response = health_handler(State(state.clone())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let summary = body_json(response).await;
+        assert_eq!(summary["status"], "degraded");
+        assert_eq!(summary["components"][0]["name"], "rpc");
+
+        let response = ready_handler(None, Some(checker.clone())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["health"]["status"], "degraded");
+
+        checker.update_component(ComponentHealth::new("rpc", HealthStatus::Unhealthy));
+        let response = health_handler(State(state)).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body_json(response).await["status"], "unhealthy");
+
+        let readiness = Readiness::new().with_requirement("adapter_connected");
+        readiness.mark_ready("adapter_connected");
+        let response = ready_handler(Some(readiness), Some(checker)).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = body_json(response).await;
+        assert_eq!(body["ready"], false);
+        assert_eq!(body["conditions"][0]["ready"], true);
+    }
+
+    #[test]
+    fn test_const_labels() {
+        let labels = HashMap::from([("pod".to_string(), "indexer-0".to_string())]);
+        let registry = PrometheusRegistry::with_const_labels(labels).unwrap();
+        registry.update_from_resources(&SystemResources {
+            process_memory_bytes: 1024,
+            ..Default::default()
+        });
+
+        let exported = registry.export().unwrap();
+        assert!(exported.contains("apex_sdk_process_resident_memory_bytes{pod=\"indexer-0\"} 1024"));
+    }
+
+    #[test]
+    fn test_retention_metrics_are_not_double_counted() {
+        let registry = PrometheusRegistry::new().unwrap();
+        let purged = |total: f64| {
+            Metric::new(MetricType::Retention, "retention_purged_records", total)
+                .with_label("store", "indexer")
+        };
+        let records = vec![
+            purged(5.0),
+            Metric::new(MetricType::Retention, "retention_store_bytes", 4096.0)
+                .with_label("store", "indexer"),
+            purged(8.0),
+        ];
+
+        // The collector hands out all records on every refresh
+        registry.update_from_sdk_metrics(&records);
+        registry.update_from_sdk_metrics(&records);
+
+        let exported = registry.export().unwrap();
+        assert!(exported.contains("apex_sdk_retention_purged_records_total{store=\"indexer\"} 8"));
+        assert!(exported.contains("apex_sdk_retention_store_bytes{store=\"indexer\"} 4096"));
+    }
+
+    #[tokio::test]
+    async fn test_blocking_pool_gauges() {
+        let registry = PrometheusRegistry::new().unwrap();
+        let collector = MetricsCollector::new();
+        let pool =
+            apex_sdk_core::blocking::BlockingPool::new("crypto", 2).with_metrics(collector.clone());
+        pool.run(|| ()).await.unwrap();
+
+        registry.update_from_sdk_metrics(&collector.get_metrics());
+
+        let exported = registry.export().unwrap();
+        assert!(exported.contains("apex_sdk_blocking_tasks{pool=\"crypto\",state=\"queued\"} 0"));
+        assert!(exported.contains("apex_sdk_blocking_tasks{pool=\"crypto\",state=\"active\"} 0"));
+    }
+
     #[tokio::test]
     async fn test_metrics_server_creation() {
         let collector = MetricsCollector::new();
         let server = MetricsServer::new(0, collector).await;
         assert!(server.is_ok());
     }
+
+    async fn local_server() -> MetricsServer {
+        MetricsServer::new(0, MetricsCollector::new())
+            .await
+            .unwrap()
+            .with_bind_address(IpAddr::V4(Ipv4Addr::LOCALHOST))
+    }
+
+    #[tokio::test]
+    async fn test_spawned_server_shuts_down() {
+        let handle = local_server().await.spawn().await.unwrap();
+        let addr = handle.local_addr();
+        assert!(addr.ip().is_loopback());
+        assert_ne!(addr.port(), 0);
+
+        let response = reqwest::get(format!("http://{}/health", addr))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        handle.shutdown().await.unwrap();
+        assert!(TcpStream::connect(addr).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_require_auth() {
+        let handle = local_server()
+            .await
+            .with_bearer_token("s3cret")
+            .spawn()
+            .await
+            .unwrap();
+        let url = |path: &str| format!("http://{}{}", handle.local_addr(), path);
+        let client = reqwest::Client::new();
+
+        let response = client.get(url("/metrics")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            response.headers()[header::WWW_AUTHENTICATE],
+            "Bearer realm=\"metrics\""
+        );
+
+        let response = client
+            .get(url("/metrics"))
+            .bearer_auth("wrong")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = client
+            .get(url("/metrics"))
+            .bearer_auth("s3cret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.text().await.unwrap().contains("apex_sdk_"));
+
+        // Probes stay open
+        let response = client.get(url("/ready")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        handle.shutdown().await.unwrap();
+    }
+
+    #[test]
+    fn test_basic_auth_header() {
+        let auth = ScrapeAuth::basic("prometheus", "hunter2");
+        let headers =
+            |value: &str| HeaderMap::from_iter([(header::AUTHORIZATION, value.parse().unwrap())]);
+
+        // base64("prometheus:hunter2")
+        assert!(auth.authorizes(&headers("Basic cHJvbWV0aGV1czpodW50ZXIy")));
+        assert!(auth.authorizes(&headers("basic cHJvbWV0aGV1czpodW50ZXIy")));
+        assert!(!auth.authorizes(&headers("Bearer cHJvbWV0aGV1czpodW50ZXIy")));
+        assert!(!auth.authorizes(&headers("Basic cHJvbWV0aGV1czpodW50ZXIz")));
+        assert!(!auth.authorizes(&HeaderMap::new()));
+    }
+
+    #[tokio::test]
+    async fn test_tls_server() {
+        let handle = local_server()
+            .await
+            .with_tls_pem(
+                include_bytes!("../tests/fixtures/tls-cert.pem"),
+                include_bytes!("../tests/fixtures/tls-key.pem"),
+            )
+            .unwrap()
+            .spawn()
+            .await
+            .unwrap();
+
+        // The fixture certificate is self-signed
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .unwrap();
+        let response = client
+            .get(format!("https://{}/health", handle.local_addr()))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Plain HTTP is not answered
+        assert!(
+            reqwest::get(format!("http://{}/health", handle.local_addr()))
+                .await
+                .is_err()
+        );
+
+        handle.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_tls_pem() {
+        let result = local_server()
+            .await
+            .with_tls_pem(b"not a certificate", b"not a key");
+        assert!(matches!(result, Err(MetricsError::ServerStart(_))));
+    }
 }
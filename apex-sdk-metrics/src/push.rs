@@ -0,0 +1,666 @@
+//! Push-based metrics export
+//!
+//! Short-lived CLI invocations and batch jobs exit before Prometheus scrapes
+//! them. [`MetricsPusher`] sends the same metrics [`MetricsServer`] would serve
+//! to a [Pushgateway] or a Prometheus [remote-write] endpoint instead, either
+//! periodically in the background or once before the process exits.
+//!
+//! ```rust,no_run
+//! use apex_sdk_core::metrics::MetricsCollector;
+//! use apex_sdk_metrics::{MetricsPusher, PushTarget};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> apex_sdk_metrics::Result<()> {
+//! let collector = MetricsCollector::new();
+//! let pusher = MetricsPusher::new(
+//!     PushTarget::pushgateway("http://pushgateway:9091"),
+//!     collector.clone(),
+//! )?
+//! .with_job("apex-batch-transfer")
+//! .with_instance("worker-1")
+//! .with_interval(Duration::from_secs(10));
+//!
+//! // ... run the job, recording into `collector` ...
+//!
+//! // Push the final values before exiting
+//! pusher.push().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`MetricsServer`]: crate::MetricsServer
+//! [Pushgateway]: https://github.com/prometheus/pushgateway
+//! [remote-write]: https://prometheus.io/docs/specs/prw/remote_write_spec/
+
+use crate::attribution::CostAttributor;
+use crate::health::HealthChecker;
+use crate::profiling::PerformanceProfiler;
+use crate::prometheus_exporter::{ExportState, PrometheusRegistry};
+use crate::relabel::RelabelConfig;
+use crate::uptime::UptimeTracker;
+use crate::{MetricsError, Result};
+use apex_sdk_core::metrics::MetricsCollector;
+use base64::Engine;
+use prometheus::proto::{MetricFamily, MetricType};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_ENCODING, CONTENT_TYPE};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+/// Default interval between background pushes
+pub const DEFAULT_PUSH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Default `job` label of pushed metrics
+pub const DEFAULT_JOB: &str = "apex_sdk";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where a [`MetricsPusher`] sends metrics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PushTarget {
+    /// Prometheus Pushgateway base URL, e.g. `http://pushgateway:9091`
+    ///
+    /// Each push replaces the metrics of the `job`/`instance` group.
+    Pushgateway(String),
+    /// Remote-write receiver URL, e.g. `http://prometheus:9090/api/v1/write`
+    RemoteWrite(String),
+}
+
+impl PushTarget {
+    /// Push to a Pushgateway
+    pub fn pushgateway(url: impl Into<String>) -> Self {
+        Self::Pushgateway(url.into())
+    }
+
+    /// Push to a remote-write endpoint
+    pub fn remote_write(url: impl Into<String>) -> Self {
+        Self::RemoteWrite(url.into())
+    }
+}
+
+#[derive(Clone)]
+enum Auth {
+    Basic { username: String, password: String },
+    Bearer(String),
+}
+
+/// Pushes SDK metrics to a Pushgateway or remote-write endpoint
+pub struct MetricsPusher {
+    target: PushTarget,
+    state: ExportState,
+    client: reqwest::Client,
+    job: String,
+    instance: Option<String>,
+    interval: Duration,
+    headers: HeaderMap,
+    auth: Option<Auth>,
+}
+
+impl MetricsPusher {
+    /// Create a pusher for SDK metrics
+    pub fn new(target: PushTarget, sdk_metrics: MetricsCollector) -> Result<Self> {
+        Self::with_relabel_config(target, sdk_metrics, RelabelConfig::default())
+    }
+
+    /// Create a pusher that relabels metrics before export
+    pub fn with_relabel_config(
+        target: PushTarget,
+        sdk_metrics: MetricsCollector,
+        relabel: RelabelConfig,
+    ) -> Result<Self> {
+        let prometheus_registry = PrometheusRegistry::new()?.with_relabel_config(relabel);
+        Self::with_registry(target, sdk_metrics, prometheus_registry)
+    }
+
+    /// Create a pusher exporting a preconfigured registry, e.g.
+    /// [`ObservabilityConfig::prometheus_registry`](crate::ObservabilityConfig::prometheus_registry)
+    pub fn with_registry(
+        target: PushTarget,
+        sdk_metrics: MetricsCollector,
+        prometheus_registry: PrometheusRegistry,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| MetricsError::PushFailed(e.to_string()))?;
+
+        Ok(Self {
+            target,
+            state: ExportState::new(prometheus_registry, sdk_metrics),
+            client,
+            job: DEFAULT_JOB.to_string(),
+            instance: None,
+            interval: DEFAULT_PUSH_INTERVAL,
+            headers: HeaderMap::new(),
+            auth: None,
+        })
+    }
+
+    /// Set the `job` label
+    pub fn with_job(mut self, job: impl Into<String>) -> Self {
+        self.job = job.into();
+        self
+    }
+
+    /// Set the `instance` label
+    pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Set the interval between background pushes
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Add an HTTP header to every push, e.g. a tenant ID
+    pub fn with_header(mut self, name: &str, value: &str) -> Result<Self> {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| MetricsError::PushFailed(format!("Invalid header name: {}", e)))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|e| MetricsError::PushFailed(format!("Invalid header value: {}", e)))?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Authenticate with HTTP basic auth
+    pub fn with_basic_auth(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.auth = Some(Auth::Basic {
+            username: username.into(),
+            password: password.into(),
+        });
+        self
+    }
+
+    /// Authenticate with a bearer token
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.auth = Some(Auth::Bearer(token.into()));
+        self
+    }
+
+    /// Push endpoint uptime reports alongside SDK metrics
+    pub fn with_uptime_tracker(mut self, tracker: Arc<UptimeTracker>) -> Self {
+        self.state.uptime = Some(tracker);
+        self
+    }
+
+    /// Push component health and the resource gauges of a health checker
+    pub fn with_health_checker(mut self, health: Arc<HealthChecker>) -> Self {
+        self.state.health = Some(health);
+        self
+    }
+
+    /// Push per-operation duration quantiles and counts of a profiler
+    pub fn with_profiler(mut self, profiler: PerformanceProfiler) -> Self {
+        self.state.profiler = Some(profiler);
+        self
+    }
+
+    /// Push the latest report of a cost attributor
+    pub fn with_cost_attributor(mut self, attributor: Arc<CostAttributor>) -> Self {
+        self.state.attribution = Some(attributor);
+        self
+    }
+
+    /// Refresh the registry from its sources and push it once
+    pub async fn push(&self) -> Result<()> {
+        self.state.refresh().await;
+
+        let request = match &self.target {
+            PushTarget::Pushgateway(url) => {
+                let body = self.state.prometheus_registry.export()?;
+                self.client
+                    .put(self.grouping_url(url))
+                    .header(CONTENT_TYPE, prometheus::TEXT_FORMAT)
+                    .body(body)
+            }
+            PushTarget::RemoteWrite(url) => {
+                let families = self.state.prometheus_registry.registry().gather();
+                let series = self.time_series(&families, unix_millis());
+                let body = snap::raw::Encoder::new()
+                    .compress_vec(&encode_write_request(&series))
+                    .map_err(|e| MetricsError::PushFailed(e.to_string()))?;
+                self.client
+                    .post(url)
+                    .header(CONTENT_TYPE, "application/x-protobuf")
+                    .header(CONTENT_ENCODING, "snappy")
+                    .header("X-Prometheus-Remote-Write-Version", "0.1.0")
+                    .body(body)
+            }
+        };
+
+        self.send(request).await?;
+        debug!(operation = "metrics_push", target = ?self.target, "Pushed metrics");
+        Ok(())
+    }
+
+    /// Delete this job's group from the Pushgateway
+    ///
+    /// Call when a job finishes so its last values do not linger. Remote-write
+    /// series simply go stale, so this is a no-op for remote-write targets.
+    pub async fn delete(&self) -> Result<()> {
+        match &self.target {
+            PushTarget::Pushgateway(url) => {
+                self.send(self.client.delete(self.grouping_url(url))).await
+            }
+            PushTarget::RemoteWrite(_) => Ok(()),
+        }
+    }
+
+    /// Push periodically in the background
+    ///
+    /// Failed pushes are logged and retried on the next tick.
+    pub fn start_background(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.push().await {
+                    warn!(operation = "metrics_push", error = %e, "Failed to push metrics");
+                }
+            }
+        })
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<()> {
+        let request = match &self.auth {
+            Some(Auth::Basic { username, password }) => {
+                request.basic_auth(username, Some(password))
+            }
+            Some(Auth::Bearer(token)) => request.bearer_auth(token),
+            None => request,
+        };
+
+        let response = request
+            .headers(self.headers.clone())
+            .send()
+            .await
+            .map_err(|e| MetricsError::PushFailed(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(MetricsError::PushFailed(format!(
+                "{} responded {}: {}",
+                self.target_url(),
+                status,
+                body.trim()
+            )));
+        }
+        Ok(())
+    }
+
+    fn target_url(&self) -> &str {
+        match &self.target {
+            PushTarget::Pushgateway(url) | PushTarget::RemoteWrite(url) => url,
+        }
+    }
+
+    /// `<base>/metrics/job/<job>[/instance/<instance>]`
+    fn grouping_url(&self, base: &str) -> String {
+        let mut url = format!(
+            "{}/metrics/{}",
+            base.trim_end_matches('/'),
+            grouping_segment("job", &self.job)
+        );
+        if let Some(instance) = &self.instance {
+            url.push('/');
+            url.push_str(&grouping_segment("instance", instance));
+        }
+        url
+    }
+
+    /// Flatten gathered families into remote-write series labeled with job and instance
+    fn time_series(&self, families: &[MetricFamily], timestamp_ms: i64) -> Vec<TimeSeries> {
+        let mut target_labels = vec![("job".to_string(), self.job.clone())];
+        if let Some(instance) = &self.instance {
+            target_labels.push(("instance".to_string(), instance.clone()));
+        }
+
+        let mut series = Vec::new();
+        for family in families {
+            let name = family.name();
+            for metric in family.get_metric() {
+                let labels: Vec<(String, String)> = metric
+                    .get_label()
+                    .iter()
+                    .map(|label| (label.name().to_string(), label.value().to_string()))
+                    .chain(target_labels.iter().cloned())
+                    .collect();
+                let mut push = |suffix: &str, extra: Option<(&str, String)>, value: f64| {
+                    let mut labels = labels.clone();
+                    labels.extend(extra.map(|(name, value)| (name.to_string(), value)));
+                    labels.push(("__name__".to_string(), format!("{}{}", name, suffix)));
+                    labels.sort();
+                    series.push(TimeSeries {
+                        labels,
+                        value,
+                        timestamp_ms,
+                    });
+                };
+
+                match family.get_field_type() {
+                    MetricType::COUNTER => push("", None, metric.get_counter().value()),
+                    MetricType::GAUGE => push("", None, metric.get_gauge().value()),
+                    MetricType::UNTYPED => push("", None, metric.untyped.value()),
+                    MetricType::HISTOGRAM => {
+                        let histogram = metric.get_histogram();
+                        for bucket in histogram.get_bucket() {
+                            push(
+                                "_bucket",
+                                Some(("le", bucket.upper_bound().to_string())),
+                                bucket.cumulative_count() as f64,
+                            );
+                        }
+                        push(
+                            "_bucket",
+                            Some(("le", "+Inf".to_string())),
+                            histogram.sample_count() as f64,
+                        );
+                        push("_sum", None, histogram.sample_sum());
+                        push("_count", None, histogram.sample_count() as f64);
+                    }
+                    MetricType::SUMMARY => {
+                        let summary = metric.get_summary();
+                        for quantile in summary.get_quantile() {
+                            push(
+                                "",
+                                Some(("quantile", quantile.quantile().to_string())),
+                                quantile.value(),
+                            );
+                        }
+                        push("_sum", None, summary.sample_sum());
+                        push("_count", None, summary.sample_count() as f64);
+                    }
+                }
+            }
+        }
+        series
+    }
+}
+
+/// Pushgateway path segment, base64-encoding values it cannot carry verbatim
+fn grouping_segment(label: &str, value: &str) -> String {
+    if value.is_empty() || value.contains('/') || value.contains('%') {
+        let encoded = base64::engine::general_purpose::URL_SAFE.encode(value);
+        format!(
+            "{}@base64/{}",
+            label,
+            if encoded.is_empty() { "=" } else { &encoded }
+        )
+    } else {
+        format!("{}/{}", label, value)
+    }
+}
+
+fn unix_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// Single-sample remote-write series; labels are sorted by name
+#[derive(Debug, Clone, PartialEq)]
+struct TimeSeries {
+    labels: Vec<(String, String)>,
+    value: f64,
+    timestamp_ms: i64,
+}
+
+/// Encode a `prometheus.WriteRequest` protobuf message
+///
+/// ```text
+/// message WriteRequest { repeated TimeSeries timeseries = 1; }
+/// message TimeSeries   { repeated Label labels = 1; repeated Sample samples = 2; }
+/// message Label        { string name = 1; string value = 2; }
+/// message Sample       { double value = 1; int64 timestamp = 2; }
+/// ```
+fn encode_write_request(series: &[TimeSeries]) -> Vec<u8> {
+    let mut request = Vec::new();
+    let mut message = Vec::new();
+    let mut field = Vec::new();
+
+    for series in series {
+        message.clear();
+        for (name, value) in &series.labels {
+            field.clear();
+            put_bytes(&mut field, 1, name.as_bytes());
+            put_bytes(&mut field, 2, value.as_bytes());
+            put_bytes(&mut message, 1, &field);
+        }
+
+        field.clear();
+        put_key(&mut field, 1, 1);
+        field.extend_from_slice(&series.value.to_le_bytes());
+        put_key(&mut field, 2, 0);
+        put_varint(&mut field, series.timestamp_ms as u64);
+        put_bytes(&mut message, 2, &field);
+
+        put_bytes(&mut request, 1, &message);
+    }
+    request
+}
+
+fn put_key(buf: &mut Vec<u8>, field: u32, wire_type: u8) {
+    put_varint(buf, u64::from(field << 3 | u32::from(wire_type)));
+}
+
+fn put_bytes(buf: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    put_key(buf, field, 2);
+    put_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn put_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apex_sdk_core::metrics::{Metric, MetricType as SdkMetricType};
+    use axum::{
+        body::Bytes,
+        extract::State,
+        http::{HeaderMap as Headers, Method, Uri},
+        routing::any,
+        Router,
+    };
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    struct Captured {
+        method: Method,
+        uri: Uri,
+        headers: Headers,
+        body: Bytes,
+    }
+
+    /// Start a server that records every request it receives
+    async fn capture_server() -> (String, mpsc::UnboundedReceiver<Captured>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let app = Router::new()
+            .fallback(any(
+                |State(tx): State<mpsc::UnboundedSender<Captured>>,
+                 method: Method,
+                 uri: Uri,
+                 headers: Headers,
+                 body: Bytes| async move {
+                    let _ = tx.send(Captured {
+                        method,
+                        uri,
+                        headers,
+                        body,
+                    });
+                },
+            ))
+            .with_state(tx);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (format!("http://{}", addr), rx)
+    }
+
+    fn collector() -> MetricsCollector {
+        let collector = MetricsCollector::new();
+        collector.record(
+            Metric::new(SdkMetricType::TransactionCount, "transactions", 3.0)
+                .with_label("chain", "westend")
+                .with_label("status", "success"),
+        );
+        collector
+    }
+
+    #[test]
+    fn test_grouping_url() {
+        let pusher = MetricsPusher::new(PushTarget::pushgateway("http://gw:9091/"), collector())
+            .unwrap()
+            .with_job("batch")
+            .with_instance("host/1");
+
+        assert_eq!(
+            pusher.grouping_url("http://gw:9091/"),
+            "http://gw:9091/metrics/job/batch/instance@base64/aG9zdC8x"
+        );
+        assert_eq!(grouping_segment("instance", ""), "instance@base64/=");
+    }
+
+    #[test]
+    fn test_write_request_encoding() {
+        let series = [TimeSeries {
+            labels: vec![("__name__".to_string(), "up".to_string())],
+            value: 1.0,
+            timestamp_ms: 1,
+        }];
+        let encoded = encode_write_request(&series);
+
+        let mut expected = vec![0x0a, 0x1d, 0x0a, 0x0e, 0x0a, 0x08];
+        expected.extend_from_slice(b"__name__");
+        expected.extend_from_slice(&[0x12, 0x02]);
+        expected.extend_from_slice(b"up");
+        expected.extend_from_slice(&[0x12, 0x0b, 0x09]);
+        expected.extend_from_slice(&1.0f64.to_le_bytes());
+        expected.extend_from_slice(&[0x10, 0x01]);
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    fn test_time_series_flattens_histograms() {
+        let pusher = MetricsPusher::new(PushTarget::remote_write("http://rw"), collector())
+            .unwrap()
+            .with_instance("worker-1");
+        let registry = PrometheusRegistry::new().unwrap();
+        registry.update_from_sdk_metrics(&[Metric::new(
+            SdkMetricType::ProviderResponseTime,
+            "rpc",
+            0.02,
+        )
+        .with_label("chain", "westend")
+        .with_label("operation", "chain_getBlock")]);
+
+        let series = pusher.time_series(&registry.registry().gather(), 42);
+        let names: Vec<&str> = series
+            .iter()
+            .filter_map(|s| s.labels.iter().find(|(n, _)| n == "__name__"))
+            .map(|(_, v)| v.as_str())
+            .collect();
+        assert!(names.contains(&"apex_sdk_rpc_duration_seconds_bucket"));
+        assert!(names.contains(&"apex_sdk_rpc_duration_seconds_count"));
+
+        let inf = series
+            .iter()
+            .find(|s| s.labels.contains(&("le".to_string(), "+Inf".to_string())))
+            .unwrap();
+        assert_eq!(inf.value, 1.0);
+        assert_eq!(inf.timestamp_ms, 42);
+        assert!(inf
+            .labels
+            .contains(&("instance".to_string(), "worker-1".to_string())));
+        assert!(inf.labels.windows(2).all(|w| w[0].0 <= w[1].0));
+    }
+
+    #[tokio::test]
+    async fn test_push_to_pushgateway() {
+        let (url, mut requests) = capture_server().await;
+        let pusher = MetricsPusher::new(PushTarget::pushgateway(&url), collector())
+            .unwrap()
+            .with_job("cli")
+            .with_bearer_token("secret")
+            .with_header("X-Scope-OrgID", "team-a")
+            .unwrap();
+
+        pusher.push().await.unwrap();
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, Method::PUT);
+        assert_eq!(request.uri.path(), "/metrics/job/cli");
+        assert_eq!(request.headers["authorization"], "Bearer secret");
+        assert_eq!(request.headers["x-scope-orgid"], "team-a");
+        let body = String::from_utf8(request.body.to_vec()).unwrap();
+        assert!(body.contains("apex_sdk_transactions_total"));
+
+        pusher.delete().await.unwrap();
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, Method::DELETE);
+        assert_eq!(request.uri.path(), "/metrics/job/cli");
+    }
+
+    #[tokio::test]
+    async fn test_push_to_remote_write() {
+        let (url, mut requests) = capture_server().await;
+        let pusher = MetricsPusher::new(
+            PushTarget::remote_write(format!("{}/api/v1/write", url)),
+            collector(),
+        )
+        .unwrap()
+        .with_basic_auth("apex", "hunter2");
+
+        pusher.push().await.unwrap();
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.uri.path(), "/api/v1/write");
+        assert_eq!(request.headers["content-encoding"], "snappy");
+        assert!(request.headers["authorization"]
+            .to_str()
+            .unwrap()
+            .starts_with("Basic "));
+
+        let payload = snap::raw::Decoder::new()
+            .decompress_vec(&request.body)
+            .unwrap();
+        let payload = String::from_utf8_lossy(&payload);
+        assert!(payload.contains("apex_sdk_transactions_total"));
+        assert!(payload.contains("westend"));
+        assert!(payload.contains(DEFAULT_JOB));
+    }
+
+    #[tokio::test]
+    async fn test_push_reports_http_errors() {
+        let app = Router::new().fallback(any(|| async {
+            (axum::http::StatusCode::BAD_REQUEST, "out of order sample")
+        }));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let pusher = MetricsPusher::new(
+            PushTarget::remote_write(format!("http://{}/api/v1/write", addr)),
+            collector(),
+        )
+        .unwrap();
+        let err = pusher.push().await.unwrap_err();
+        assert!(err.to_string().contains("400"));
+        assert!(err.to_string().contains("out of order sample"));
+    }
+}
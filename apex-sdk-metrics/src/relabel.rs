@@ -0,0 +1,225 @@
+//! Label relabeling before export
+//!
+//! SDK and user code can attach arbitrary labels to metrics, including
+//! high-cardinality values such as transaction hashes. A [`RelabelConfig`]
+//! rewrites label sets before they reach an exporter, similar to Prometheus
+//! `relabel_configs`:
+//!
+//! 1. rules are applied in order (drop, rename, value mapping)
+//! 2. if an allow-list is set, every label not on it is dropped
+//!
+//! ```rust
+//! use apex_sdk_metrics::RelabelConfig;
+//! use std::collections::HashMap;
+//!
+//! let config = RelabelConfig::new()
+//!     .drop_label("tx_hash")
+//!     .map_value("chain", "westend-asset-hub", "asset-hub")
+//!     .with_allowed_labels(["chain", "status", "operation"]);
+//!
+//! let labels = HashMap::from([
+//!     ("chain".to_string(), "westend-asset-hub".to_string()),
+//!     ("tx_hash".to_string(), "0xabc".to_string()),
+//!     ("user_id".to_string(), "42".to_string()),
+//! ]);
+//!
+//! let relabeled = config.apply(&labels);
+//! assert_eq!(relabeled.len(), 1);
+//! assert_eq!(relabeled["chain"], "asset-hub");
+//! ```
+
+use apex_sdk_core::metrics::Metric;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// A single relabeling step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RelabelRule {
+    /// Remove a label
+    Drop {
+        /// Label name
+        label: String,
+    },
+    /// Rename a label, keeping its value
+    Rename {
+        /// Current label name
+        from: String,
+        /// New label name
+        to: String,
+    },
+    /// Replace one value of a label
+    MapValue {
+        /// Label name
+        label: String,
+        /// Value to replace
+        from: String,
+        /// Replacement value
+        to: String,
+    },
+}
+
+/// Relabeling applied to metric labels before export
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelabelConfig {
+    /// Rules applied in order
+    #[serde(default)]
+    pub rules: Vec<RelabelRule>,
+    /// Labels kept after the rules ran; all labels are kept when unset
+    #[serde(default)]
+    pub allowed_labels: Option<BTreeSet<String>>,
+}
+
+impl RelabelConfig {
+    /// Create an empty configuration that keeps labels unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule
+    pub fn with_rule(mut self, rule: RelabelRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Drop a label
+    pub fn drop_label(self, label: impl Into<String>) -> Self {
+        self.with_rule(RelabelRule::Drop {
+            label: label.into(),
+        })
+    }
+
+    /// Rename a label
+    pub fn rename_label(self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.with_rule(RelabelRule::Rename {
+            from: from.into(),
+            to: to.into(),
+        })
+    }
+
+    /// Replace a label value
+    pub fn map_value(
+        self,
+        label: impl Into<String>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        self.with_rule(RelabelRule::MapValue {
+            label: label.into(),
+            from: from.into(),
+            to: to.into(),
+        })
+    }
+
+    /// Only export the given labels
+    pub fn with_allowed_labels<I, S>(mut self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_labels = Some(labels.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Check whether this configuration leaves labels unchanged
+    pub fn is_noop(&self) -> bool {
+        self.rules.is_empty() && self.allowed_labels.is_none()
+    }
+
+    /// Apply the configuration to a label set
+    pub fn apply(&self, labels: &HashMap<String, String>) -> HashMap<String, String> {
+        let mut labels = labels.clone();
+
+        for rule in &self.rules {
+            match rule {
+                RelabelRule::Drop { label } => {
+                    labels.remove(label);
+                }
+                RelabelRule::Rename { from, to } => {
+                    if let Some(value) = labels.remove(from) {
+                        labels.insert(to.clone(), value);
+                    }
+                }
+                RelabelRule::MapValue { label, from, to } => {
+                    if let Some(value) = labels.get_mut(label) {
+                        if value == from {
+                            *value = to.clone();
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(allowed) = &self.allowed_labels {
+            labels.retain(|name, _| allowed.contains(name));
+        }
+
+        labels
+    }
+
+    /// Apply the configuration to a metric's labels
+    pub fn apply_metric(&self, metric: &Metric) -> Metric {
+        let mut metric = metric.clone();
+        metric.labels = self.apply(&metric.labels);
+        metric
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_noop_keeps_labels() {
+        let input = labels(&[("chain", "polkadot"), ("tx_hash", "0x1")]);
+        let config = RelabelConfig::new();
+        assert!(config.is_noop());
+        assert_eq!(config.apply(&input), input);
+    }
+
+    #[test]
+    fn test_rules_apply_in_order() {
+        let config = RelabelConfig::new()
+            .rename_label("network", "chain")
+            .map_value("chain", "dot", "polkadot")
+            .drop_label("tx_hash");
+
+        let output = config.apply(&labels(&[("network", "dot"), ("tx_hash", "0x1")]));
+        assert_eq!(output, labels(&[("chain", "polkadot")]));
+    }
+
+    #[test]
+    fn test_allow_list_runs_after_rules() {
+        let config = RelabelConfig::new()
+            .rename_label("net", "chain")
+            .with_allowed_labels(["chain"]);
+
+        let output = config.apply(&labels(&[("net", "kusama"), ("user", "bob")]));
+        assert_eq!(output, labels(&[("chain", "kusama")]));
+    }
+
+    #[test]
+    fn test_deserialize_config() {
+        let config: RelabelConfig = serde_json::from_value(serde_json::json!({
+            "rules": [
+                {"action": "drop", "label": "tx_hash"},
+                {"action": "map_value", "label": "chain", "from": "wnd", "to": "westend"}
+            ],
+            "allowed_labels": ["chain", "status"]
+        }))
+        .unwrap();
+
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(
+            config.apply(&labels(&[("chain", "wnd"), ("tx_hash", "0x1")])),
+            labels(&[("chain", "westend")])
+        );
+    }
+}
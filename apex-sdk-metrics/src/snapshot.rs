@@ -0,0 +1,210 @@
+//! File snapshots of exported metrics
+//!
+//! Air-gapped deployments can neither be scraped nor push to a gateway.
+//! [`SnapshotExporter`] captures the same metrics [`MetricsServer`] would
+//! serve, in the Prometheus text exposition format, so the file can be
+//! shipped by hand and read by a node_exporter textfile collector on the
+//! other side. Profiler spans and SDK metric aggregates can be written
+//! alongside as JSON.
+//!
+//! ```rust,no_run
+//! use apex_sdk_core::metrics::MetricsCollector;
+//! use apex_sdk_metrics::{PerformanceProfiler, SnapshotExporter};
+//!
+//! # async fn example() -> apex_sdk_metrics::Result<()> {
+//! let collector = MetricsCollector::new();
+//! let profiler = PerformanceProfiler::new();
+//! let exporter = SnapshotExporter::new(collector)?.with_profiler(profiler);
+//!
+//! let dump = exporter.capture().await?;
+//! dump.write_prometheus("metrics.prom")?;
+//! dump.write_json("metrics.json")?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`MetricsServer`]: crate::MetricsServer
+
+use crate::aggregation::{AggregatedMetrics, MetricsAggregator};
+use crate::attribution::CostAttributor;
+use crate::health::HealthChecker;
+use crate::profiling::{OperationStats, PerformanceProfiler, SpanRecord};
+use crate::prometheus_exporter::{ExportState, PrometheusRegistry};
+use crate::uptime::{UptimeReport, UptimeTracker};
+use crate::{MetricsError, Result};
+use apex_sdk_core::metrics::MetricsCollector;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Captures exported metrics for writing to files
+pub struct SnapshotExporter {
+    state: ExportState,
+    aggregator: MetricsAggregator,
+}
+
+impl SnapshotExporter {
+    /// Create an exporter for SDK metrics
+    pub fn new(sdk_metrics: MetricsCollector) -> Result<Self> {
+        Ok(Self::with_registry(sdk_metrics, PrometheusRegistry::new()?))
+    }
+
+    /// Create an exporter for a preconfigured registry, e.g.
+    /// [`ObservabilityConfig::prometheus_registry`](crate::ObservabilityConfig::prometheus_registry)
+    pub fn with_registry(
+        sdk_metrics: MetricsCollector,
+        prometheus_registry: PrometheusRegistry,
+    ) -> Self {
+        Self {
+            state: ExportState::new(prometheus_registry, sdk_metrics),
+            aggregator: MetricsAggregator::new(),
+        }
+    }
+
+    /// Export endpoint uptime reports alongside SDK metrics
+    pub fn with_uptime_tracker(mut self, tracker: Arc<UptimeTracker>) -> Self {
+        self.state.uptime = Some(tracker);
+        self
+    }
+
+    /// Export component health and the resource gauges of a health checker
+    pub fn with_health_checker(mut self, health: Arc<HealthChecker>) -> Self {
+        self.state.health = Some(health);
+        self
+    }
+
+    /// Export per-operation duration quantiles and counts of a profiler, and
+    /// include its spans in the JSON dump
+    pub fn with_profiler(mut self, profiler: PerformanceProfiler) -> Self {
+        self.state.profiler = Some(profiler);
+        self
+    }
+
+    /// Export the latest report of a cost attributor
+    pub fn with_cost_attributor(mut self, attributor: Arc<CostAttributor>) -> Self {
+        self.state.attribution = Some(attributor);
+        self
+    }
+
+    /// Aggregate SDK metrics over `aggregator`'s time window in the JSON dump
+    pub fn with_aggregator(mut self, aggregator: MetricsAggregator) -> Self {
+        self.aggregator = aggregator;
+        self
+    }
+
+    /// Refresh the registry from every source and capture its current state
+    pub async fn capture(&self) -> Result<MetricsDump> {
+        self.state.refresh().await;
+        let prometheus = self.state.prometheus_registry.export()?;
+
+        let (spans, operations) = match &self.state.profiler {
+            Some(profiler) => (profiler.get_spans(), profiler.all_operation_stats()),
+            None => (Vec::new(), Vec::new()),
+        };
+        let uptime = self
+            .state
+            .uptime
+            .as_ref()
+            .map(|tracker| tracker.reports())
+            .unwrap_or_default();
+
+        Ok(MetricsDump {
+            captured_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            prometheus,
+            aggregates: self
+                .aggregator
+                .aggregate(&self.state.sdk_metrics.get_metrics()),
+            operations,
+            spans,
+            uptime,
+        })
+    }
+}
+
+/// Metrics captured by a [`SnapshotExporter`]
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsDump {
+    /// Unix timestamp of the capture, in seconds
+    pub captured_at: u64,
+    /// Prometheus text exposition; not part of the JSON dump
+    #[serde(skip)]
+    pub prometheus: String,
+    /// SDK metrics aggregated over the aggregator's time window
+    pub aggregates: AggregatedMetrics,
+    /// Per-operation statistics of the profiler
+    pub operations: Vec<OperationStats>,
+    /// Spans held by the profiler
+    pub spans: Vec<SpanRecord>,
+    /// Uptime reports per endpoint
+    pub uptime: Vec<UptimeReport>,
+}
+
+impl MetricsDump {
+    /// Write the Prometheus text exposition to `path`
+    pub fn write_prometheus(&self, path: impl AsRef<Path>) -> Result<()> {
+        write_atomic(path.as_ref(), self.prometheus.as_bytes())
+    }
+
+    /// Write spans, operation statistics, aggregates and uptime reports to
+    /// `path` as JSON
+    pub fn write_json(&self, path: impl AsRef<Path>) -> Result<()> {
+        let json = serde_json::to_vec_pretty(self)
+            .map_err(|e| MetricsError::ExportFailed(e.to_string()))?;
+        write_atomic(path.as_ref(), &json)
+    }
+}
+
+/// Write through a sibling file so a reader never sees a partial snapshot
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    std::fs::write(&tmp, contents)
+        .and_then(|_| std::fs::rename(&tmp, path))
+        .map_err(|e| {
+            MetricsError::ExportFailed(format!("Failed to write {}: {}", path.display(), e))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiling::OperationType;
+    use crate::uptime::AvailabilitySample;
+
+    #[tokio::test]
+    async fn test_capture_and_write() {
+        let profiler = PerformanceProfiler::new();
+        profiler.start_span(OperationType::BalanceQuery).success();
+        let uptime = Arc::new(UptimeTracker::new());
+        uptime.record("ws://node", AvailabilitySample::now(true, Some(12)));
+
+        let exporter = SnapshotExporter::new(MetricsCollector::new())
+            .unwrap()
+            .with_profiler(profiler)
+            .with_uptime_tracker(uptime);
+        let dump = exporter.capture().await.unwrap();
+
+        assert!(dump.prometheus.contains("apex_sdk_operation"));
+        assert!(dump.prometheus.contains("ws://node"));
+        assert_eq!(dump.spans.len(), 1);
+        assert_eq!(dump.operations.len(), 1);
+        assert_eq!(dump.uptime.len(), 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let prom = dir.path().join("metrics.prom");
+        let json = dir.path().join("metrics.json");
+        dump.write_prometheus(&prom).unwrap();
+        dump.write_json(&json).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&prom).unwrap(), dump.prometheus);
+        let value: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&json).unwrap()).unwrap();
+        assert_eq!(value["spans"].as_array().unwrap().len(), 1);
+        assert!(value.get("prometheus").is_none());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+}
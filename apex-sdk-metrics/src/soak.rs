@@ -0,0 +1,475 @@
+//! Resource leak detection for long-running soak tests
+//!
+//! [`SoakMonitor`] samples the process's resident memory, open file
+//! descriptors, profiler span buffer and live Tokio tasks while a workload
+//! runs. [`LeakDetector`] then checks each series for growth that continues
+//! after the warm-up period instead of levelling off.
+//!
+//! A series is reported as leaking when its post-warm-up samples, split into
+//! four equal quarters, have strictly increasing quarter means and the last
+//! quarter exceeds the first by more than the resource's [`LeakTolerance`].
+//! Caches and pools that fill up and then plateau are not flagged.
+
+use crate::health::HealthChecker;
+use crate::profiling::PerformanceProfiler;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Resource tracked during a soak run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoakResource {
+    /// Resident set size of the process, in bytes
+    Rss,
+    /// Open file descriptors (Linux only)
+    FileDescriptors,
+    /// Records held in the profiler's span buffer
+    SpanBuffer,
+    /// Live Tokio tasks
+    Tasks,
+}
+
+impl SoakResource {
+    /// Every tracked resource
+    pub const ALL: [SoakResource; 4] = [
+        SoakResource::Rss,
+        SoakResource::FileDescriptors,
+        SoakResource::SpanBuffer,
+        SoakResource::Tasks,
+    ];
+}
+
+impl std::fmt::Display for SoakResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SoakResource::Rss => write!(f, "rss_bytes"),
+            SoakResource::FileDescriptors => write!(f, "open_fds"),
+            SoakResource::SpanBuffer => write!(f, "span_buffer"),
+            SoakResource::Tasks => write!(f, "tasks"),
+        }
+    }
+}
+
+/// One sample of the tracked resources
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SoakSample {
+    /// Seconds since the run started
+    pub elapsed_secs: f64,
+    /// Resident set size in bytes
+    pub rss_bytes: u64,
+    /// Open file descriptors, where the platform reports them
+    pub open_fds: Option<u64>,
+    /// Span buffer length, if a profiler is attached
+    pub span_buffer: Option<usize>,
+    /// Live Tokio tasks, if sampled inside a runtime
+    pub tasks: Option<usize>,
+}
+
+impl SoakSample {
+    /// Value of `resource` in this sample, if it was measured
+    pub fn value(&self, resource: SoakResource) -> Option<f64> {
+        match resource {
+            SoakResource::Rss => Some(self.rss_bytes as f64),
+            SoakResource::FileDescriptors => self.open_fds.map(|v| v as f64),
+            SoakResource::SpanBuffer => self.span_buffer.map(|v| v as f64),
+            SoakResource::Tasks => self.tasks.map(|v| v as f64),
+        }
+    }
+}
+
+/// Growth a resource may show before it counts as a leak
+///
+/// Growth must exceed both the relative and the absolute bound.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LeakTolerance {
+    /// Allowed growth as a fraction of the first quarter's mean
+    pub ratio: f64,
+    /// Allowed growth in the resource's own units
+    pub absolute: f64,
+}
+
+impl LeakTolerance {
+    /// Create a tolerance
+    pub fn new(ratio: f64, absolute: f64) -> Self {
+        Self { ratio, absolute }
+    }
+
+    fn allows(&self, baseline: f64, growth: f64) -> bool {
+        growth <= self.absolute || growth <= self.ratio * baseline
+    }
+}
+
+/// When a soak run's resource series count as leaking
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeakThresholds {
+    /// Samples taken before this much time has passed are ignored
+    pub warmup: Duration,
+    /// Fewest post-warm-up samples needed to judge a series
+    pub min_samples: usize,
+    /// Tolerance for resident memory
+    pub rss: LeakTolerance,
+    /// Tolerance for open file descriptors
+    pub file_descriptors: LeakTolerance,
+    /// Tolerance for the span buffer
+    pub span_buffer: LeakTolerance,
+    /// Tolerance for live tasks
+    pub tasks: LeakTolerance,
+}
+
+impl Default for LeakThresholds {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_secs(300),
+            min_samples: 8,
+            rss: LeakTolerance::new(0.25, 16.0 * 1024.0 * 1024.0),
+            file_descriptors: LeakTolerance::new(0.25, 8.0),
+            span_buffer: LeakTolerance::new(0.25, 100.0),
+            tasks: LeakTolerance::new(0.25, 16.0),
+        }
+    }
+}
+
+impl LeakThresholds {
+    /// Set the warm-up period
+    pub fn with_warmup(mut self, warmup: Duration) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    /// Set the fewest post-warm-up samples needed to judge a series
+    pub fn with_min_samples(mut self, min_samples: usize) -> Self {
+        self.min_samples = min_samples.max(4);
+        self
+    }
+
+    /// Set the tolerance for one resource
+    pub fn with_tolerance(mut self, resource: SoakResource, tolerance: LeakTolerance) -> Self {
+        match resource {
+            SoakResource::Rss => self.rss = tolerance,
+            SoakResource::FileDescriptors => self.file_descriptors = tolerance,
+            SoakResource::SpanBuffer => self.span_buffer = tolerance,
+            SoakResource::Tasks => self.tasks = tolerance,
+        }
+        self
+    }
+
+    /// Tolerance for `resource`
+    pub fn tolerance(&self, resource: SoakResource) -> LeakTolerance {
+        match resource {
+            SoakResource::Rss => self.rss,
+            SoakResource::FileDescriptors => self.file_descriptors,
+            SoakResource::SpanBuffer => self.span_buffer,
+            SoakResource::Tasks => self.tasks,
+        }
+    }
+}
+
+/// How one resource changed over the post-warm-up samples
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceTrend {
+    /// Resource
+    pub resource: SoakResource,
+    /// Post-warm-up samples of this resource
+    pub samples: usize,
+    /// Mean of the first quarter of samples
+    pub start: f64,
+    /// Mean of the last quarter of samples
+    pub end: f64,
+    /// Largest value seen
+    pub peak: f64,
+    /// Whether there were enough samples to judge the series
+    pub evaluated: bool,
+    /// Whether the series grew steadily past its tolerance
+    pub leaking: bool,
+}
+
+impl ResourceTrend {
+    /// Growth from the first to the last quarter
+    pub fn growth(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Result of a soak run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoakReport {
+    /// Seconds covered by the samples
+    pub duration_secs: f64,
+    /// Samples taken, including warm-up
+    pub samples: usize,
+    /// Trend of each measured resource
+    pub trends: Vec<ResourceTrend>,
+}
+
+impl SoakReport {
+    /// Trends flagged as leaking
+    pub fn leaks(&self) -> Vec<&ResourceTrend> {
+        self.trends.iter().filter(|trend| trend.leaking).collect()
+    }
+
+    /// Whether no resource was flagged as leaking
+    pub fn is_leak_free(&self) -> bool {
+        self.trends.iter().all(|trend| !trend.leaking)
+    }
+}
+
+/// Judges resource samples for unbounded growth
+#[derive(Debug, Clone, Default)]
+pub struct LeakDetector {
+    thresholds: LeakThresholds,
+    samples: Vec<SoakSample>,
+}
+
+impl LeakDetector {
+    /// Create a detector with the given thresholds
+    pub fn new(thresholds: LeakThresholds) -> Self {
+        Self {
+            thresholds,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Add a sample
+    pub fn record(&mut self, sample: SoakSample) {
+        self.samples.push(sample);
+    }
+
+    /// Samples recorded so far
+    pub fn samples(&self) -> &[SoakSample] {
+        &self.samples
+    }
+
+    /// Trend of `resource`, or `None` if it was never measured
+    pub fn trend(&self, resource: SoakResource) -> Option<ResourceTrend> {
+        let warmup = self.thresholds.warmup.as_secs_f64();
+        let values: Vec<f64> = self
+            .samples
+            .iter()
+            .filter(|sample| sample.elapsed_secs >= warmup)
+            .filter_map(|sample| sample.value(resource))
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+
+        let quarter = values.len() / 4;
+        let evaluated = values.len() >= self.thresholds.min_samples.max(4);
+        let means: Vec<f64> = if quarter == 0 {
+            vec![values[0], values[values.len() - 1]]
+        } else {
+            (0..4)
+                .map(|i| {
+                    let start = i * values.len() / 4;
+                    let end = (i + 1) * values.len() / 4;
+                    values[start..end].iter().sum::<f64>() / (end - start) as f64
+                })
+                .collect()
+        };
+        let start = means[0];
+        let end = means[means.len() - 1];
+
+        let rising = means.windows(2).all(|pair| pair[1] > pair[0]);
+        let leaking = evaluated
+            && rising
+            && !self
+                .thresholds
+                .tolerance(resource)
+                .allows(start, end - start);
+
+        Some(ResourceTrend {
+            resource,
+            samples: values.len(),
+            start,
+            end,
+            peak: values.iter().cloned().fold(f64::MIN, f64::max),
+            evaluated,
+            leaking,
+        })
+    }
+
+    /// Trends of every measured resource
+    pub fn report(&self) -> SoakReport {
+        SoakReport {
+            duration_secs: self
+                .samples
+                .last()
+                .map(|sample| sample.elapsed_secs)
+                .unwrap_or_default(),
+            samples: self.samples.len(),
+            trends: SoakResource::ALL
+                .into_iter()
+                .filter_map(|resource| self.trend(resource))
+                .collect(),
+        }
+    }
+}
+
+/// Samples process resources for a [`LeakDetector`] during a soak run
+pub struct SoakMonitor {
+    checker: HealthChecker,
+    profiler: Option<PerformanceProfiler>,
+    detector: LeakDetector,
+    started: Instant,
+}
+
+impl SoakMonitor {
+    /// Start a run now
+    pub fn new(thresholds: LeakThresholds) -> Self {
+        Self {
+            checker: HealthChecker::new().with_sample_interval(Duration::ZERO),
+            profiler: None,
+            detector: LeakDetector::new(thresholds),
+            started: Instant::now(),
+        }
+    }
+
+    /// Track the span buffer of `profiler`
+    pub fn with_profiler(mut self, profiler: PerformanceProfiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    /// Take a sample and record it
+    ///
+    /// Live tasks are counted on the current Tokio runtime.
+    pub async fn sample(&mut self) -> SoakSample {
+        let resources = self.checker.system_resources().await;
+        let sample = SoakSample {
+            elapsed_secs: self.started.elapsed().as_secs_f64(),
+            rss_bytes: resources.process_memory_bytes,
+            open_fds: resources.open_file_descriptors,
+            span_buffer: self.profiler.as_ref().map(|p| p.span_count()),
+            tasks: tokio::runtime::Handle::try_current()
+                .ok()
+                .map(|handle| handle.metrics().num_alive_tasks()),
+        };
+        self.detector.record(sample.clone());
+        sample
+    }
+
+    /// Detector holding the samples taken so far
+    pub fn detector(&self) -> &LeakDetector {
+        &self.detector
+    }
+
+    /// Trends of the samples taken so far
+    pub fn report(&self) -> SoakReport {
+        self.detector.report()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(elapsed_secs: f64, rss_bytes: u64, open_fds: u64, tasks: usize) -> SoakSample {
+        SoakSample {
+            elapsed_secs,
+            rss_bytes,
+            open_fds: Some(open_fds),
+            span_buffer: None,
+            tasks: Some(tasks),
+        }
+    }
+
+    fn detector() -> LeakDetector {
+        LeakDetector::new(LeakThresholds::default().with_warmup(Duration::from_secs(60)))
+    }
+
+    #[test]
+    fn test_steady_resources_are_leak_free() {
+        let mut detector = detector();
+        for i in 0..40u64 {
+            // Warm-up growth, then noise around a plateau
+            let rss = if i < 2 {
+                50 << 20
+            } else {
+                (100 << 20) + (i % 3) * 4096
+            };
+            detector.record(sample(i as f64 * 30.0, rss, 20 + i % 2, 12));
+        }
+
+        let report = detector.report();
+        assert!(report.is_leak_free());
+        assert_eq!(report.samples, 40);
+        assert!(report.trends.iter().all(|t| t.evaluated));
+        // No profiler attached, so the span buffer is not reported
+        assert_eq!(report.trends.len(), 3);
+    }
+
+    #[test]
+    fn test_unbounded_growth_is_a_leak() {
+        let mut detector = detector();
+        for i in 0..40u64 {
+            detector.record(sample(
+                i as f64 * 30.0,
+                (100 << 20) + i * (2 << 20),
+                20 + i,
+                12,
+            ));
+        }
+
+        let report = detector.report();
+        let leaks: Vec<_> = report.leaks().iter().map(|t| t.resource).collect();
+        assert_eq!(
+            leaks,
+            vec![SoakResource::Rss, SoakResource::FileDescriptors]
+        );
+        let fds = detector.trend(SoakResource::FileDescriptors).unwrap();
+        assert!(fds.growth() > 8.0);
+        assert_eq!(fds.peak, 59.0);
+    }
+
+    #[test]
+    fn test_growth_within_tolerance_or_too_few_samples() {
+        // Steady but small growth stays under the absolute tolerance
+        let mut detector = detector();
+        for i in 0..40u64 {
+            detector.record(sample(
+                i as f64 * 30.0,
+                100 << 20,
+                20,
+                12 + (i / 8) as usize,
+            ));
+        }
+        assert!(detector.report().is_leak_free());
+
+        // A short run is reported but not judged
+        let mut detector = detector_with_short_run();
+        for i in 0..5u64 {
+            detector.record(sample(
+                60.0 + i as f64,
+                (100 << 20) + i * (64 << 20),
+                20,
+                12,
+            ));
+        }
+        let rss = detector.trend(SoakResource::Rss).unwrap();
+        assert!(!rss.evaluated);
+        assert!(!rss.leaking);
+    }
+
+    fn detector_with_short_run() -> LeakDetector {
+        LeakDetector::new(
+            LeakThresholds::default()
+                .with_warmup(Duration::from_secs(60))
+                .with_min_samples(8),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_monitor_samples_process() {
+        let profiler = PerformanceProfiler::new();
+        profiler
+            .start_span(crate::profiling::OperationType::BalanceQuery)
+            .success();
+
+        let mut monitor = SoakMonitor::new(LeakThresholds::default()).with_profiler(profiler);
+        let sample = monitor.sample().await;
+
+        assert!(sample.rss_bytes > 0);
+        assert_eq!(sample.span_buffer, Some(1));
+        assert!(sample.tasks.is_some());
+        assert_eq!(monitor.detector().samples().len(), 1);
+    }
+}
@@ -0,0 +1,707 @@
+//! Incremental metrics aggregation
+//!
+//! [`MetricsAggregator`] rescans and sorts every metric for each name and
+//! label on every call. [`StreamingAggregator`] instead updates its
+//! statistics as metrics are recorded and answers queries without looking at
+//! individual samples again, producing the same [`StatisticalSnapshot`]s.
+//!
+//! Each series, one per metric name and one per metric name and label value,
+//! keeps a ring of up to [`SLOTS`] sub-windows covering the time window. Every
+//! slot holds a [`QuantileSketch`]: a log-bucketed histogram whose
+//! percentiles are within a relative error of the true value (1% by
+//! default). A query merges the live slots, so its cost depends on the
+//! number of slots and buckets but not on the number of samples. Samples
+//! leave the window one slot at a time, so the window is exact to within
+//! one slot: 1/[`SLOTS`] of its length, or a second for windows shorter
+//! than [`SLOTS`] seconds.
+//!
+//! ```rust
+//! use apex_sdk_core::metrics::{Metric, MetricType};
+//! use apex_sdk_metrics::{StreamingAggregator, TimeWindow};
+//!
+//! let aggregator = StreamingAggregator::with_time_window(TimeWindow::FiveMinutes);
+//! for ms in [120.0, 80.0, 95.0, 410.0] {
+//!     aggregator.record(
+//!         &Metric::new(MetricType::TransactionLatency, "tx_latency_ms", ms)
+//!             .with_label("chain", "polkadot"),
+//!     );
+//! }
+//!
+//! let snapshot = aggregator.snapshot("tx_latency_ms").unwrap();
+//! assert_eq!(snapshot.count, 4);
+//! assert_eq!(snapshot.max, 410.0);
+//! assert!(aggregator.label_snapshot("chain", "polkadot", "tx_latency_ms").is_some());
+//! ```
+//!
+//! [`MetricsAggregator`]: crate::MetricsAggregator
+
+use crate::aggregation::{AggregatedMetrics, StatisticalSnapshot, TimeWindow};
+use crate::unix_now;
+use apex_sdk_core::metrics::{Metric, MetricType};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+/// Most sub-windows per time window
+pub const SLOTS: u64 = 60;
+
+/// Default relative error of sketch percentiles
+pub const DEFAULT_RELATIVE_ACCURACY: f64 = 0.01;
+
+/// Mergeable summary of a stream of values with approximate percentiles
+///
+/// Values fall into logarithmically sized buckets, so any percentile is
+/// within `relative_accuracy` of the value it estimates. Count, sum, mean,
+/// minimum, maximum and standard deviation are exact.
+#[derive(Debug, Clone)]
+pub struct QuantileSketch {
+    relative_accuracy: f64,
+    ln_gamma: f64,
+    positive: BTreeMap<i32, u64>,
+    negative: BTreeMap<i32, u64>,
+    zero: u64,
+    count: u64,
+    sum: f64,
+    sum_of_squares: f64,
+    min: f64,
+    max: f64,
+}
+
+impl QuantileSketch {
+    /// Create an empty sketch whose percentiles are within
+    /// `relative_accuracy` (for example `0.01` for 1%) of the true value
+    pub fn new(relative_accuracy: f64) -> Self {
+        let relative_accuracy = relative_accuracy.clamp(1e-6, 0.5);
+        let gamma = (1.0 + relative_accuracy) / (1.0 - relative_accuracy);
+        Self {
+            relative_accuracy,
+            ln_gamma: gamma.ln(),
+            positive: BTreeMap::new(),
+            negative: BTreeMap::new(),
+            zero: 0,
+            count: 0,
+            sum: 0.0,
+            sum_of_squares: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Relative error of percentiles
+    pub fn relative_accuracy(&self) -> f64 {
+        self.relative_accuracy
+    }
+
+    /// Add a value; NaN is ignored
+    pub fn insert(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        if value > 0.0 {
+            *self.positive.entry(self.bucket(value)).or_default() += 1;
+        } else if value < 0.0 {
+            *self.negative.entry(self.bucket(-value)).or_default() += 1;
+        } else {
+            self.zero += 1;
+        }
+        self.count += 1;
+        self.sum += value;
+        self.sum_of_squares += value * value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Add every value of another sketch
+    ///
+    /// Both sketches should have the same relative accuracy; buckets of the
+    /// other sketch are taken as they are.
+    pub fn merge(&mut self, other: &QuantileSketch) {
+        for (bucket, count) in &other.positive {
+            *self.positive.entry(*bucket).or_default() += count;
+        }
+        for (bucket, count) in &other.negative {
+            *self.negative.entry(*bucket).or_default() += count;
+        }
+        self.zero += other.zero;
+        self.count += other.count;
+        self.sum += other.sum;
+        self.sum_of_squares += other.sum_of_squares;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Number of values
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Whether no value was added
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Sum of the values
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Mean, `None` when empty
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+
+    /// Smallest value, `None` when empty
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// Largest value, `None` when empty
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    /// Population standard deviation, `None` when empty
+    pub fn std_dev(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let variance = self.sum_of_squares / self.count as f64 - mean * mean;
+        Some(variance.max(0.0).sqrt())
+    }
+
+    /// Estimate of the `p`th percentile (0 to 100), `None` when empty
+    ///
+    /// Ranks match [`MetricsAggregator`](crate::MetricsAggregator): the
+    /// value at index `round(p / 100 * (count - 1))` of the sorted samples.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * (self.count - 1) as f64).round() as u64;
+        if rank == 0 {
+            return Some(self.min);
+        }
+        if rank == self.count - 1 {
+            return Some(self.max);
+        }
+
+        let mut seen = 0;
+        // Most negative first: the largest magnitude comes first
+        for (bucket, count) in self.negative.iter().rev() {
+            seen += count;
+            if seen > rank {
+                return Some(self.clamp(-self.value(*bucket)));
+            }
+        }
+        seen += self.zero;
+        if seen > rank {
+            return Some(self.clamp(0.0));
+        }
+        for (bucket, count) in &self.positive {
+            seen += count;
+            if seen > rank {
+                return Some(self.clamp(self.value(*bucket)));
+            }
+        }
+        Some(self.max)
+    }
+
+    fn bucket(&self, magnitude: f64) -> i32 {
+        (magnitude.ln() / self.ln_gamma).ceil() as i32
+    }
+
+    /// Value a bucket stands for, within the relative accuracy of all its
+    /// values
+    fn value(&self, bucket: i32) -> f64 {
+        let gamma = self.ln_gamma.exp();
+        2.0 * (bucket as f64 * self.ln_gamma).exp() / (gamma + 1.0)
+    }
+
+    fn clamp(&self, value: f64) -> f64 {
+        value.clamp(self.min, self.max)
+    }
+}
+
+impl Default for QuantileSketch {
+    fn default() -> Self {
+        Self::new(DEFAULT_RELATIVE_ACCURACY)
+    }
+}
+
+impl StatisticalSnapshot {
+    /// Snapshot of the values summarized by a sketch, `None` when empty
+    pub fn from_sketch(
+        sketch: &QuantileSketch,
+        metric_name: &str,
+        time_window: TimeWindow,
+    ) -> Option<Self> {
+        Self::sketched(sketch, metric_name, time_window, unix_now())
+    }
+
+    fn sketched(
+        sketch: &QuantileSketch,
+        metric_name: &str,
+        time_window: TimeWindow,
+        timestamp: u64,
+    ) -> Option<Self> {
+        let median = sketch.percentile(50.0)?;
+        Some(Self {
+            metric_name: metric_name.to_string(),
+            time_window,
+            count: sketch.count() as usize,
+            sum: sketch.sum(),
+            mean: sketch.mean()?,
+            median,
+            min: sketch.min()?,
+            max: sketch.max()?,
+            std_dev: sketch.std_dev()?,
+            p50: median,
+            p90: sketch.percentile(90.0)?,
+            p95: sketch.percentile(95.0)?,
+            p99: sketch.percentile(99.0)?,
+            timestamp,
+        })
+    }
+}
+
+/// One sub-window of a series
+#[derive(Debug)]
+struct Slot {
+    /// Timestamp divided by the slot length
+    epoch: u64,
+    sketch: QuantileSketch,
+}
+
+/// Ring of slots covering the time window
+#[derive(Debug)]
+struct Series {
+    metric_type: MetricType,
+    slots: Vec<Option<Slot>>,
+}
+
+impl Series {
+    fn new(metric_type: MetricType, slots: u64) -> Self {
+        Self {
+            metric_type,
+            slots: (0..slots).map(|_| None).collect(),
+        }
+    }
+
+    fn record(&mut self, epoch: u64, value: f64, relative_accuracy: f64) {
+        let len = self.slots.len() as u64;
+        let slot = &mut self.slots[(epoch % len) as usize];
+        match slot {
+            Some(current) if current.epoch == epoch => current.sketch.insert(value),
+            // A late sample whose slot was already reused
+            Some(current) if current.epoch > epoch => {}
+            _ => {
+                let mut sketch = QuantileSketch::new(relative_accuracy);
+                sketch.insert(value);
+                *slot = Some(Slot { epoch, sketch });
+            }
+        }
+    }
+
+    /// Merge the slots still inside the window ending at `now_epoch`
+    fn merged(&self, now_epoch: u64, relative_accuracy: f64) -> QuantileSketch {
+        let mut merged = QuantileSketch::new(relative_accuracy);
+        for slot in self.slots.iter().flatten() {
+            if self.is_live_epoch(slot.epoch, now_epoch) {
+                merged.merge(&slot.sketch);
+            }
+        }
+        merged
+    }
+
+    fn is_live(&self, now_epoch: u64) -> bool {
+        self.slots
+            .iter()
+            .flatten()
+            .any(|slot| self.is_live_epoch(slot.epoch, now_epoch))
+    }
+
+    fn is_live_epoch(&self, epoch: u64, now_epoch: u64) -> bool {
+        is_live(epoch, now_epoch, self.slots.len() as u64)
+    }
+}
+
+/// Whether a slot is among the `slots` newest at `now_epoch`
+fn is_live(epoch: u64, now_epoch: u64, slots: u64) -> bool {
+    epoch + slots > now_epoch
+}
+
+/// Series key of a metric name restricted to one label value
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct LabelSeries {
+    label_key: String,
+    label_value: String,
+    name: String,
+}
+
+#[derive(Debug, Default)]
+struct StreamingState {
+    overall: HashMap<String, Series>,
+    by_label: HashMap<LabelSeries, Series>,
+}
+
+/// Aggregator that updates its statistics as metrics are recorded
+///
+/// Produces the same [`AggregatedMetrics`] as
+/// [`MetricsAggregator::aggregate`](crate::MetricsAggregator::aggregate),
+/// with percentiles estimated by [`QuantileSketch`]es.
+#[derive(Debug)]
+pub struct StreamingAggregator {
+    time_window: TimeWindow,
+    /// Sub-windows per series
+    slots: u64,
+    /// Seconds covered by one sub-window
+    slot_seconds: u64,
+    relative_accuracy: f64,
+    state: Mutex<StreamingState>,
+}
+
+impl StreamingAggregator {
+    /// Create an aggregator over the last five minutes
+    pub fn new() -> Self {
+        Self::with_time_window(TimeWindow::FiveMinutes)
+    }
+
+    /// Create an aggregator with a specific time window
+    ///
+    /// The window is split into at most [`SLOTS`] slots of whole seconds,
+    /// as few as cover it: a 90 second window has 45 slots of 2 seconds, a
+    /// 10 second window 10 slots of 1 second.
+    pub fn with_time_window(time_window: TimeWindow) -> Self {
+        let seconds = time_window.seconds().max(1);
+        let slot_seconds = seconds.div_ceil(SLOTS);
+        Self {
+            time_window,
+            slots: seconds.div_ceil(slot_seconds),
+            slot_seconds,
+            relative_accuracy: DEFAULT_RELATIVE_ACCURACY,
+            state: Mutex::new(StreamingState::default()),
+        }
+    }
+
+    /// Set the relative error of percentiles (1% by default)
+    ///
+    /// Finer accuracy keeps more buckets per slot.
+    pub fn with_relative_accuracy(mut self, relative_accuracy: f64) -> Self {
+        self.relative_accuracy = relative_accuracy;
+        self
+    }
+
+    /// Time window the statistics cover
+    pub fn time_window(&self) -> TimeWindow {
+        self.time_window
+    }
+
+    /// Seconds covered by one slot
+    pub fn slot_seconds(&self) -> u64 {
+        self.slot_seconds
+    }
+
+    /// Slots the window is split into
+    pub fn slots(&self) -> u64 {
+        self.slots
+    }
+
+    /// Add a metric to its name's series and to one series per label
+    ///
+    /// Metrics already outside the window are ignored.
+    pub fn record(&self, metric: &Metric) {
+        self.record_at(metric, unix_now());
+    }
+
+    /// Add several metrics, such as those of
+    /// [`MetricsCollector::get_metrics`](apex_sdk_core::metrics::MetricsCollector::get_metrics)
+    pub fn record_all<'a>(&self, metrics: impl IntoIterator<Item = &'a Metric>) {
+        let now = unix_now();
+        for metric in metrics {
+            self.record_at(metric, now);
+        }
+    }
+
+    fn record_at(&self, metric: &Metric, now: u64) {
+        let epoch = metric.timestamp / self.slot_seconds();
+        if !is_live(epoch, now / self.slot_seconds(), self.slots) {
+            return;
+        }
+
+        let mut state = self.lock();
+        state
+            .overall
+            .entry(metric.name.clone())
+            .or_insert_with(|| Series::new(metric.metric_type.clone(), self.slots))
+            .record(epoch, metric.value, self.relative_accuracy);
+        for (label_key, label_value) in &metric.labels {
+            let key = LabelSeries {
+                label_key: label_key.clone(),
+                label_value: label_value.clone(),
+                name: metric.name.clone(),
+            };
+            state
+                .by_label
+                .entry(key)
+                .or_insert_with(|| Series::new(metric.metric_type.clone(), self.slots))
+                .record(epoch, metric.value, self.relative_accuracy);
+        }
+    }
+
+    /// Statistics of a metric over the window
+    pub fn snapshot(&self, metric_name: &str) -> Option<StatisticalSnapshot> {
+        let now = unix_now();
+        let state = self.lock();
+        self.series_snapshot(state.overall.get(metric_name)?, metric_name, now)
+    }
+
+    /// Statistics of a metric's samples with one label value over the
+    /// window
+    pub fn label_snapshot(
+        &self,
+        label_key: &str,
+        label_value: &str,
+        metric_name: &str,
+    ) -> Option<StatisticalSnapshot> {
+        let now = unix_now();
+        let key = LabelSeries {
+            label_key: label_key.to_string(),
+            label_value: label_value.to_string(),
+            name: metric_name.to_string(),
+        };
+        let state = self.lock();
+        self.series_snapshot(state.by_label.get(&key)?, metric_name, now)
+    }
+
+    /// Statistics of every metric and label value, keyed like
+    /// [`MetricsAggregator::aggregate`](crate::MetricsAggregator::aggregate)
+    ///
+    /// Series without samples in the window are dropped.
+    pub fn aggregate(&self) -> AggregatedMetrics {
+        self.aggregate_at(unix_now())
+    }
+
+    fn aggregate_at(&self, now: u64) -> AggregatedMetrics {
+        let now_epoch = now / self.slot_seconds();
+        let mut state = self.lock();
+        state.overall.retain(|_, series| series.is_live(now_epoch));
+        state.by_label.retain(|_, series| series.is_live(now_epoch));
+
+        let overall = state
+            .overall
+            .iter()
+            .filter_map(|(name, series)| {
+                self.series_snapshot(series, name, now)
+                    .map(|snapshot| (name.clone(), snapshot))
+            })
+            .collect();
+
+        let mut by_label: HashMap<String, HashMap<String, StatisticalSnapshot>> = HashMap::new();
+        for (key, series) in &state.by_label {
+            if let Some(snapshot) = self.series_snapshot(series, &key.name, now) {
+                by_label
+                    .entry(key.label_key.clone())
+                    .or_default()
+                    .insert(format!("{}:{}", key.label_value, key.name), snapshot);
+            }
+        }
+
+        AggregatedMetrics {
+            by_label,
+            overall,
+            time_window: self.time_window,
+        }
+    }
+
+    /// Statistics of every metric of one type over the window
+    pub fn aggregate_by_type(
+        &self,
+        metric_type: MetricType,
+    ) -> HashMap<String, StatisticalSnapshot> {
+        let now = unix_now();
+        let state = self.lock();
+        state
+            .overall
+            .iter()
+            .filter(|(_, series)| series.metric_type == metric_type)
+            .filter_map(|(name, series)| {
+                self.series_snapshot(series, name, now)
+                    .map(|snapshot| (name.clone(), snapshot))
+            })
+            .collect()
+    }
+
+    /// Drop every series
+    pub fn clear(&self) {
+        *self.lock() = StreamingState::default();
+    }
+
+    fn series_snapshot(
+        &self,
+        series: &Series,
+        metric_name: &str,
+        now: u64,
+    ) -> Option<StatisticalSnapshot> {
+        let merged = series.merged(now / self.slot_seconds(), self.relative_accuracy);
+        StatisticalSnapshot::sketched(&merged, metric_name, self.time_window, now)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StreamingState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for StreamingAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregation::percentile;
+
+    fn metric(name: &str, value: f64, timestamp: u64) -> Metric {
+        let mut metric = Metric::new(MetricType::TransactionLatency, name, value);
+        metric.timestamp = timestamp;
+        metric
+    }
+
+    #[test]
+    fn test_sketch_percentiles_within_accuracy() {
+        let mut sketch = QuantileSketch::default();
+        let mut values: Vec<f64> = (1..=10_000).map(|i| (i as f64).powf(1.5) / 7.0).collect();
+        for value in &values {
+            sketch.insert(*value);
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        for p in [0.0, 25.0, 50.0, 90.0, 95.0, 99.0, 100.0] {
+            let exact = percentile(&values, p);
+            let estimate = sketch.percentile(p).unwrap();
+            assert!(
+                (estimate - exact).abs() <= exact * DEFAULT_RELATIVE_ACCURACY,
+                "p{}: {} vs {}",
+                p,
+                estimate,
+                exact
+            );
+        }
+
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        assert!((sketch.mean().unwrap() - mean).abs() < 1e-6 * mean);
+        assert_eq!(sketch.min(), Some(values[0]));
+        assert_eq!(sketch.max(), values.last().copied());
+    }
+
+    #[test]
+    fn test_sketch_merge_and_signed_values() {
+        let mut low = QuantileSketch::default();
+        let mut high = QuantileSketch::default();
+        for value in [-5.0, -1.0, 0.0] {
+            low.insert(value);
+        }
+        for value in [2.0, 8.0, f64::NAN] {
+            high.insert(value);
+        }
+        low.merge(&high);
+
+        assert_eq!(low.count(), 5);
+        assert_eq!(low.percentile(0.0), Some(-5.0));
+        assert_eq!(low.percentile(50.0), Some(0.0));
+        assert_eq!(low.percentile(100.0), Some(8.0));
+        assert!((low.percentile(25.0).unwrap() + 1.0).abs() <= 0.01);
+    }
+
+    #[test]
+    fn test_samples_leave_the_window() {
+        let aggregator = StreamingAggregator::with_time_window(TimeWindow::OneMinute);
+        let now = 1_000_000;
+        aggregator.record_at(&metric("latency", 10.0, now - 59), now - 59);
+        aggregator.record_at(&metric("latency", 30.0, now), now);
+        // Already outside the window
+        aggregator.record_at(&metric("latency", 99.0, now - 600), now);
+
+        let aggregated = aggregator.aggregate_at(now);
+        let snapshot = &aggregated.overall["latency"];
+        assert_eq!(snapshot.count, 2);
+        assert_eq!(snapshot.max, 30.0);
+
+        // The first sample's slot expires a minute later
+        let aggregated = aggregator.aggregate_at(now + 1);
+        assert_eq!(aggregated.overall["latency"].count, 1);
+        assert!(aggregator.aggregate_at(now + 60).overall.is_empty());
+        assert!(aggregator.lock().overall.is_empty());
+    }
+
+    #[test]
+    fn test_custom_window_span() {
+        let now = 1_000_000;
+        for (seconds, slots, slot_seconds) in [(10, 10, 1), (90, 45, 2), (61, 31, 2)] {
+            let aggregator = StreamingAggregator::with_time_window(TimeWindow::Custom(
+                std::time::Duration::from_secs(seconds),
+            ));
+            assert_eq!(aggregator.slots(), slots);
+            assert_eq!(aggregator.slot_seconds(), slot_seconds);
+
+            // The window is exact to within one slot
+            let oldest = now - seconds + slot_seconds;
+            aggregator.record_at(&metric("latency", 10.0, oldest), now);
+            aggregator.record_at(&metric("latency", 20.0, now), now);
+            // Outside the window, though inside the 60 seconds of fixed slots
+            aggregator.record_at(&metric("latency", 99.0, now - seconds - 2), now);
+            assert_eq!(
+                aggregator.aggregate_at(now).overall["latency"].count,
+                2,
+                "{}s window",
+                seconds
+            );
+
+            let later = aggregator.aggregate_at(now + seconds + 1);
+            assert!(later.overall.is_empty(), "{}s window", seconds);
+        }
+    }
+
+    #[test]
+    fn test_matches_metrics_aggregator_layout() {
+        let aggregator = StreamingAggregator::new();
+        let metrics: Vec<Metric> = (0..100)
+            .map(|i| {
+                Metric::new(
+                    MetricType::TransactionLatency,
+                    "tx_duration",
+                    i as f64 * 0.1,
+                )
+                .with_label("chain", if i % 2 == 0 { "ethereum" } else { "polkadot" })
+            })
+            .chain([Metric::new(MetricType::GasUsage, "gas_used", 21_000.0)])
+            .collect();
+        aggregator.record_all(&metrics);
+
+        let streamed = aggregator.aggregate();
+        let rescanned = crate::MetricsAggregator::new().aggregate(&metrics);
+        assert_eq!(
+            streamed
+                .overall
+                .keys()
+                .collect::<std::collections::BTreeSet<_>>(),
+            rescanned.overall.keys().collect()
+        );
+        assert_eq!(
+            streamed.by_label["chain"]
+                .keys()
+                .collect::<std::collections::BTreeSet<_>>(),
+            rescanned.by_label["chain"].keys().collect()
+        );
+
+        let streamed = &streamed.by_label["chain"]["polkadot:tx_duration"];
+        let rescanned = &rescanned.by_label["chain"]["polkadot:tx_duration"];
+        assert_eq!(streamed.count, rescanned.count);
+        assert!((streamed.mean - rescanned.mean).abs() < 1e-9);
+        assert!((streamed.p95 - rescanned.p95).abs() <= rescanned.p95 * 0.01);
+
+        let gas = aggregator.aggregate_by_type(MetricType::GasUsage);
+        assert_eq!(gas.len(), 1);
+        assert_eq!(gas["gas_used"].p99, 21_000.0);
+    }
+}
@@ -3,9 +3,15 @@
 //! This module provides comprehensive telemetry initialization with support for
 //! OpenTelemetry, distributed tracing, and structured logging.
 
+use crate::kubernetes::KubernetesMetadata;
+use crate::logging::{RedactingFields, StandardFieldsLayer, StandardJsonFormat};
+use crate::prometheus_exporter::PrometheusRegistry;
+use crate::relabel::RelabelConfig;
 use crate::{MetricsError, Result};
+use apex_sdk_core::privacy::{self, PrivacyMode, Redactor};
 use opentelemetry_sdk::trace::SdkTracerProvider;
 use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr};
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 /// Observability configuration
@@ -19,6 +25,9 @@ pub struct ObservabilityConfig {
     pub environment: String,
     /// Prometheus metrics port
     pub prometheus_port: u16,
+    /// Address the metrics server listens on
+    #[serde(default = "default_prometheus_bind_address")]
+    pub prometheus_bind_address: IpAddr,
     /// Enable OpenTelemetry tracing
     pub enable_tracing: bool,
     /// OpenTelemetry collector endpoint (optional)
@@ -27,8 +36,41 @@ pub struct ObservabilityConfig {
     pub log_level: String,
     /// Enable JSON logging
     pub json_logs: bool,
+    /// Emit the standard SDK fields as top-level JSON keys (JSON logs only)
+    #[serde(default = "default_standard_fields")]
+    pub standard_fields: bool,
     /// Enable console output
     pub console_output: bool,
+    /// Redaction of addresses and amounts in logs and metric labels
+    #[serde(default)]
+    pub privacy_mode: PrivacyMode,
+    /// Salt mixed into hashed addresses in [`PrivacyMode::Hash`]
+    ///
+    /// Never serialized, so a saved or logged configuration does not
+    /// reveal it.
+    #[serde(default, skip_serializing)]
+    pub privacy_salt: Option<String>,
+    /// Label rewriting applied before metrics are exported
+    #[serde(default)]
+    pub relabel: RelabelConfig,
+    /// Attach detected Kubernetes pod metadata to traces and metrics
+    #[serde(default = "default_kubernetes_metadata")]
+    pub kubernetes_metadata: bool,
+}
+
+/// Environment variable overriding [`ObservabilityConfig::privacy_mode`]
+pub const PRIVACY_MODE_ENV: &str = "APEX_SDK_PRIVACY_MODE";
+
+fn default_prometheus_bind_address() -> IpAddr {
+    IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+}
+
+fn default_standard_fields() -> bool {
+    true
+}
+
+fn default_kubernetes_metadata() -> bool {
+    true
 }
 
 impl ObservabilityConfig {
@@ -39,11 +81,17 @@ impl ObservabilityConfig {
             service_version: env!("CARGO_PKG_VERSION").to_string(),
             environment: "development".to_string(),
             prometheus_port: 9090,
+            prometheus_bind_address: default_prometheus_bind_address(),
             enable_tracing: true,
             otlp_endpoint: None,
             log_level: "info".to_string(),
             json_logs: false,
+            standard_fields: true,
             console_output: true,
+            privacy_mode: PrivacyMode::Off,
+            privacy_salt: None,
+            relabel: RelabelConfig::default(),
+            kubernetes_metadata: true,
         }
     }
 
@@ -65,6 +113,14 @@ impl ObservabilityConfig {
         self
     }
 
+    /// Set the address the metrics server listens on
+    ///
+    /// Defaults to `0.0.0.0`; use `127.0.0.1` to keep metrics off the network.
+    pub fn with_prometheus_bind_address(mut self, address: IpAddr) -> Self {
+        self.prometheus_bind_address = address;
+        self
+    }
+
     /// Enable OpenTelemetry tracing
     pub fn with_tracing(mut self, enabled: bool) -> Self {
         self.enable_tracing = enabled;
@@ -89,11 +145,87 @@ impl ObservabilityConfig {
         self
     }
 
+    /// Emit the standard SDK fields as top-level JSON keys
+    ///
+    /// Only applies when JSON logging is enabled. Disable to fall back to the
+    /// default `tracing-subscriber` JSON layout.
+    pub fn with_standard_fields(mut self, enabled: bool) -> Self {
+        self.standard_fields = enabled;
+        self
+    }
+
     /// Enable console output
     pub fn with_console_output(mut self, enabled: bool) -> Self {
         self.console_output = enabled;
         self
     }
+
+    /// Set the privacy mode for logs and metric labels
+    pub fn with_privacy_mode(mut self, mode: PrivacyMode) -> Self {
+        self.privacy_mode = mode;
+        self
+    }
+
+    /// Set the salt used when hashing addresses
+    pub fn with_privacy_salt(mut self, salt: impl Into<String>) -> Self {
+        self.privacy_salt = Some(salt.into());
+        self
+    }
+
+    /// Set the label relabeling applied before export
+    pub fn with_relabel_config(mut self, relabel: RelabelConfig) -> Self {
+        self.relabel = relabel;
+        self
+    }
+
+    /// Attach detected Kubernetes pod metadata to traces and metrics
+    pub fn with_kubernetes_metadata(mut self, enabled: bool) -> Self {
+        self.kubernetes_metadata = enabled;
+        self
+    }
+
+    /// Kubernetes pod metadata, if enabled and running in Kubernetes
+    pub fn kubernetes(&self) -> Option<KubernetesMetadata> {
+        if self.kubernetes_metadata {
+            KubernetesMetadata::detect()
+        } else {
+            None
+        }
+    }
+
+    /// Build the Prometheus registry for this configuration
+    ///
+    /// Applies the relabel configuration and labels every metric with the
+    /// pod metadata from [`ObservabilityConfig::kubernetes`].
+    pub fn prometheus_registry(&self) -> Result<PrometheusRegistry> {
+        let registry = match self.kubernetes() {
+            Some(metadata) => PrometheusRegistry::with_const_labels(metadata.const_labels())?,
+            None => PrometheusRegistry::new()?,
+        };
+        Ok(registry.with_relabel_config(self.relabel.clone()))
+    }
+
+    /// Resolve the privacy mode for this environment
+    ///
+    /// [`PRIVACY_MODE_ENV`] takes precedence over the configured mode so that
+    /// redaction can be toggled per deployment without code changes.
+    pub fn effective_privacy_mode(&self) -> Result<PrivacyMode> {
+        match std::env::var(PRIVACY_MODE_ENV) {
+            Ok(value) => value
+                .parse()
+                .map_err(|e: String| MetricsError::TelemetryConfig(e)),
+            Err(_) => Ok(self.privacy_mode),
+        }
+    }
+
+    /// Build the redactor for this configuration
+    pub fn redactor(&self) -> Result<Redactor> {
+        let redactor = Redactor::new(self.effective_privacy_mode()?);
+        Ok(match &self.privacy_salt {
+            Some(salt) => redactor.with_salt(salt.clone()),
+            None => redactor,
+        })
+    }
 }
 
 impl Default for ObservabilityConfig {
@@ -124,12 +256,17 @@ impl TelemetryLayer {
         use opentelemetry::KeyValue;
         use opentelemetry_sdk::Resource;
 
+        let mut attributes = vec![
+            KeyValue::new("service.version", config.service_version.clone()),
+            KeyValue::new("deployment.environment", config.environment.clone()),
+        ];
+        if let Some(metadata) = config.kubernetes() {
+            attributes.extend(metadata.resource_attributes());
+        }
+
         let resource = Resource::builder_empty()
             .with_service_name(config.service_name.clone())
-            .with_attributes(vec![
-                KeyValue::new("service.version", config.service_version.clone()),
-                KeyValue::new("deployment.environment", config.environment.clone()),
-            ])
+            .with_attributes(attributes)
             .build();
 
         let provider = SdkTracerProvider::builder().with_resource(resource).build();
@@ -161,8 +298,22 @@ pub fn init_telemetry(config: ObservabilityConfig) -> Result<TelemetryLayer> {
 
     let telemetry = TelemetryLayer::new(&config)?;
 
+    let redactor = config.redactor()?;
+    let privacy_mode = redactor.mode();
+    privacy::set_redactor(redactor);
+
     if config.console_output {
-        if config.json_logs {
+        // Redaction needs the SDK formatters, the stock layouts bypass them
+        if config.json_logs && (config.standard_fields || privacy_mode.is_enabled()) {
+            let subscriber = tracing_subscriber::registry()
+                .with(env_filter)
+                .with(StandardFieldsLayer)
+                .with(fmt::layer().event_format(StandardJsonFormat::default()));
+
+            subscriber.try_init().map_err(|e| {
+                MetricsError::TelemetryConfig(format!("Failed to initialize tracing: {}", e))
+            })?;
+        } else if config.json_logs {
             let subscriber = tracing_subscriber::registry().with(env_filter).with(
                 fmt::layer()
                     .json()
@@ -171,6 +322,19 @@ pub fn init_telemetry(config: ObservabilityConfig) -> Result<TelemetryLayer> {
                     .with_target(true),
             );
 
+            subscriber.try_init().map_err(|e| {
+                MetricsError::TelemetryConfig(format!("Failed to initialize tracing: {}", e))
+            })?;
+        } else if privacy_mode.is_enabled() {
+            let subscriber = tracing_subscriber::registry().with(env_filter).with(
+                fmt::layer()
+                    .fmt_fields(RedactingFields::default())
+                    .with_target(true)
+                    .with_thread_ids(true)
+                    .with_file(true)
+                    .with_line_number(true),
+            );
+
             subscriber.try_init().map_err(|e| {
                 MetricsError::TelemetryConfig(format!("Failed to initialize tracing: {}", e))
             })?;
@@ -199,6 +363,7 @@ pub fn init_telemetry(config: ObservabilityConfig) -> Result<TelemetryLayer> {
         service = %config.service_name,
         version = %config.service_version,
         environment = %config.environment,
+        privacy_mode = %privacy_mode,
         "Telemetry initialized"
     );
 
@@ -233,6 +398,72 @@ mod tests {
         assert_eq!(config.environment, "development");
         assert_eq!(config.prometheus_port, 9090);
         assert!(config.enable_tracing);
+        assert!(config.standard_fields);
+    }
+
+    #[test]
+    fn test_standard_fields_default_when_deserializing() {
+        let mut value = serde_json::to_value(ObservabilityConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("standard_fields");
+
+        let config: ObservabilityConfig = serde_json::from_value(value).unwrap();
+        assert!(config.standard_fields);
+    }
+
+    #[test]
+    fn test_privacy_config() {
+        let config = ObservabilityConfig::new("test-service")
+            .with_environment("production")
+            .with_privacy_mode(PrivacyMode::Hash)
+            .with_privacy_salt("secret");
+
+        assert_eq!(config.privacy_mode, PrivacyMode::Hash);
+        assert_eq!(config.privacy_salt.as_deref(), Some("secret"));
+        let serialized = serde_json::to_value(&config).unwrap();
+        assert!(serialized.get("privacy_salt").is_none());
+        assert_eq!(
+            ObservabilityConfig::default().privacy_mode,
+            PrivacyMode::Off
+        );
+    }
+
+    #[test]
+    fn test_privacy_mode_deserialization() {
+        let mut value = serde_json::to_value(ObservabilityConfig::default()).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("privacy_mode");
+        object.remove("privacy_salt");
+
+        let config: ObservabilityConfig = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(config.privacy_mode, PrivacyMode::Off);
+
+        value["privacy_mode"] = serde_json::json!("truncate");
+        let config: ObservabilityConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.privacy_mode, PrivacyMode::Truncate);
+    }
+
+    #[test]
+    fn test_relabel_config() {
+        let config = ObservabilityConfig::default()
+            .with_relabel_config(RelabelConfig::new().drop_label("tx_hash"));
+        assert_eq!(config.relabel.rules.len(), 1);
+
+        let mut value = serde_json::to_value(ObservabilityConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("relabel");
+        let config: ObservabilityConfig = serde_json::from_value(value).unwrap();
+        assert!(config.relabel.is_noop());
+    }
+
+    #[test]
+    fn test_kubernetes_metadata_toggle() {
+        let config = ObservabilityConfig::default().with_kubernetes_metadata(false);
+        assert_eq!(config.kubernetes(), None);
+        assert!(config.prometheus_registry().is_ok());
+
+        let mut value = serde_json::to_value(ObservabilityConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("kubernetes_metadata");
+        let config: ObservabilityConfig = serde_json::from_value(value).unwrap();
+        assert!(config.kubernetes_metadata);
     }
 
     #[test]
@@ -0,0 +1,390 @@
+//! Uptime SLA reporting per endpoint
+//!
+//! [`UptimeTracker`] records availability samples, usually produced by an
+//! [`EndpointProbe`], and computes rolling uptime and latency percentiles per
+//! endpoint over the SLA window (30 days by default). Samples can be persisted
+//! to a JSON file so reports survive restarts, and reports are exported as
+//! Prometheus gauges by [`PrometheusRegistry::update_from_uptime`].
+//!
+//! ```rust,no_run
+//! use apex_sdk_metrics::{EndpointProbe, UptimeTracker};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> apex_sdk_metrics::Result<()> {
+//! let tracker = Arc::new(UptimeTracker::with_persistence("uptime.json")?);
+//!
+//! tracker.clone().spawn_probes(
+//!     vec![
+//!         EndpointProbe::substrate("wss://rpc.polkadot.io").with_name("parity"),
+//!         EndpointProbe::substrate("wss://polkadot.api.onfinality.io/public-ws")
+//!             .with_name("onfinality"),
+//!     ],
+//!     Duration::from_secs(60),
+//! );
+//!
+//! for report in tracker.reports() {
+//!     println!("{}: {:.3}% up", report.endpoint, report.uptime_percent);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`EndpointProbe`]: crate::probe::EndpointProbe
+//! [`PrometheusRegistry::update_from_uptime`]: crate::PrometheusRegistry::update_from_uptime
+
+use crate::aggregation::percentile;
+use crate::health::ComponentHealth;
+use crate::probe::EndpointProbe;
+use crate::{unix_now, MetricsError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::warn;
+
+/// Default SLA window (30 days)
+pub const DEFAULT_SLA_WINDOW: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// A single availability observation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AvailabilitySample {
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    /// Whether the endpoint was available
+    pub up: bool,
+    /// Response time in milliseconds, if the endpoint answered
+    pub latency_ms: Option<u64>,
+}
+
+impl AvailabilitySample {
+    /// Create a sample taken now
+    pub fn now(up: bool, latency_ms: Option<u64>) -> Self {
+        Self {
+            timestamp: unix_now(),
+            up,
+            latency_ms,
+        }
+    }
+
+    /// Create a sample from a health check result
+    ///
+    /// Degraded endpoints count as available.
+    pub fn from_health(health: &ComponentHealth) -> Self {
+        let up = health.is_operational();
+        Self {
+            timestamp: health.last_check,
+            up,
+            latency_ms: health.response_time_ms.filter(|_| up),
+        }
+    }
+}
+
+/// Uptime and latency of an endpoint over the SLA window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UptimeReport {
+    /// Endpoint name
+    pub endpoint: String,
+    /// SLA window in seconds
+    pub window_secs: u64,
+    /// Number of samples in the window
+    pub samples: usize,
+    /// Number of samples where the endpoint was available
+    pub up_samples: usize,
+    /// Availability in percent
+    pub uptime_percent: f64,
+    /// Median latency in milliseconds
+    pub latency_p50_ms: Option<f64>,
+    /// 95th percentile latency in milliseconds
+    pub latency_p95_ms: Option<f64>,
+    /// 99th percentile latency in milliseconds
+    pub latency_p99_ms: Option<f64>,
+    /// Timestamp of the oldest sample in the window
+    pub first_sample: u64,
+    /// Timestamp of the newest sample in the window
+    pub last_sample: u64,
+}
+
+impl UptimeReport {
+    /// Check whether uptime meets a target, e.g. `99.9`
+    pub fn meets_sla(&self, target_percent: f64) -> bool {
+        self.uptime_percent >= target_percent
+    }
+
+    /// Period covered by the samples
+    pub fn coverage(&self) -> Duration {
+        Duration::from_secs(self.last_sample.saturating_sub(self.first_sample))
+    }
+}
+
+/// On-disk format of the tracker state
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UptimeState {
+    endpoints: BTreeMap<String, VecDeque<AvailabilitySample>>,
+}
+
+/// Records availability samples and computes rolling uptime per endpoint
+pub struct UptimeTracker {
+    window: Duration,
+    path: Option<PathBuf>,
+    state: Mutex<UptimeState>,
+}
+
+impl UptimeTracker {
+    /// Create an in-memory tracker with the default 30-day window
+    pub fn new() -> Self {
+        Self {
+            window: DEFAULT_SLA_WINDOW,
+            path: None,
+            state: Mutex::new(UptimeState::default()),
+        }
+    }
+
+    /// Create a tracker persisted to a JSON file
+    ///
+    /// Samples already stored in the file are loaded. The file is written by
+    /// [`save`](Self::save).
+    pub fn with_persistence(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let state = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                MetricsError::Persistence(format!("Invalid uptime file {}: {}", path.display(), e))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => UptimeState::default(),
+            Err(e) => {
+                return Err(MetricsError::Persistence(format!(
+                    "Failed to read {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+
+        Ok(Self {
+            window: DEFAULT_SLA_WINDOW,
+            path: Some(path),
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Set the SLA window
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Get the SLA window
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Record a sample for an endpoint
+    pub fn record(&self, endpoint: impl Into<String>, sample: AvailabilitySample) {
+        let cutoff = self.cutoff();
+        let mut state = self.lock();
+        let samples = state.endpoints.entry(endpoint.into()).or_default();
+
+        samples.push_back(sample);
+        if samples.len() > 1 && samples[samples.len() - 2].timestamp > sample.timestamp {
+            samples.make_contiguous().sort_by_key(|s| s.timestamp);
+        }
+        while samples.front().is_some_and(|s| s.timestamp < cutoff) {
+            samples.pop_front();
+        }
+    }
+
+    /// Record a health check result under the component name
+    pub fn record_health(&self, health: &ComponentHealth) {
+        self.record(health.name.clone(), AvailabilitySample::from_health(health));
+    }
+
+    /// Names of tracked endpoints
+    pub fn endpoints(&self) -> Vec<String> {
+        self.lock().endpoints.keys().cloned().collect()
+    }
+
+    /// Uptime report for an endpoint
+    pub fn report(&self, endpoint: &str) -> Option<UptimeReport> {
+        let cutoff = self.cutoff();
+        let state = self.lock();
+        let samples: Vec<&AvailabilitySample> = state
+            .endpoints
+            .get(endpoint)?
+            .iter()
+            .filter(|s| s.timestamp >= cutoff)
+            .collect();
+
+        let first = samples.first()?;
+        let last = samples.last()?;
+        let up_samples = samples.iter().filter(|s| s.up).count();
+
+        let mut latencies: Vec<f64> = samples
+            .iter()
+            .filter_map(|s| s.latency_ms)
+            .map(|ms| ms as f64)
+            .collect();
+        latencies.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let latency = |p: f64| (!latencies.is_empty()).then(|| percentile(&latencies, p));
+
+        Some(UptimeReport {
+            endpoint: endpoint.to_string(),
+            window_secs: self.window.as_secs(),
+            samples: samples.len(),
+            up_samples,
+            uptime_percent: up_samples as f64 / samples.len() as f64 * 100.0,
+            latency_p50_ms: latency(50.0),
+            latency_p95_ms: latency(95.0),
+            latency_p99_ms: latency(99.0),
+            first_sample: first.timestamp,
+            last_sample: last.timestamp,
+        })
+    }
+
+    /// Uptime reports for all endpoints, sorted by name
+    pub fn reports(&self) -> Vec<UptimeReport> {
+        self.endpoints()
+            .iter()
+            .filter_map(|endpoint| self.report(endpoint))
+            .collect()
+    }
+
+    /// Write samples to the persistence file
+    ///
+    /// Does nothing for in-memory trackers.
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let json = serde_json::to_vec(&*self.lock())
+            .map_err(|e| MetricsError::Persistence(e.to_string()))?;
+
+        // Write to a sibling file first so a crash cannot truncate the history
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| {
+                MetricsError::Persistence(format!("Failed to write {}: {}", path.display(), e))
+            })
+    }
+
+    /// Probe endpoints periodically in the background
+    ///
+    /// Each round records one sample per probe and saves the tracker.
+    pub fn spawn_probes(
+        self: Arc<Self>,
+        probes: Vec<EndpointProbe>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+
+                let mut checks = tokio::task::JoinSet::new();
+                for probe in &probes {
+                    let probe = probe.clone();
+                    checks.spawn(async move { probe.check().await });
+                }
+                while let Some(result) = checks.join_next().await {
+                    if let Ok(health) = result {
+                        self.record_health(&health);
+                    }
+                }
+
+                if let Err(e) = self.save() {
+                    warn!(operation = "uptime_probe", error = %e, "Failed to save uptime samples");
+                }
+            }
+        })
+    }
+
+    fn cutoff(&self) -> u64 {
+        unix_now().saturating_sub(self.window.as_secs())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, UptimeState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for UptimeTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::HealthStatus;
+
+    fn sample(age_secs: u64, up: bool, latency_ms: u64) -> AvailabilitySample {
+        AvailabilitySample {
+            timestamp: unix_now() - age_secs,
+            up,
+            latency_ms: up.then_some(latency_ms),
+        }
+    }
+
+    #[test]
+    fn test_uptime_and_percentiles() {
+        let tracker = UptimeTracker::new();
+        for i in 0..99 {
+            tracker.record("rpc-a", sample(1000 - i, true, 10 + i));
+        }
+        tracker.record("rpc-a", sample(1, false, 0));
+
+        let report = tracker.report("rpc-a").unwrap();
+        assert_eq!(report.samples, 100);
+        assert_eq!(report.up_samples, 99);
+        assert!((report.uptime_percent - 99.0).abs() < f64::EPSILON);
+        assert!(report.meets_sla(99.0));
+        assert!(!report.meets_sla(99.9));
+        assert_eq!(report.latency_p50_ms, Some(59.0));
+        assert_eq!(report.latency_p99_ms, Some(107.0));
+        assert!(tracker.report("rpc-b").is_none());
+    }
+
+    #[test]
+    fn test_samples_outside_window_are_dropped() {
+        let tracker = UptimeTracker::new().with_window(Duration::from_secs(3600));
+        tracker.record("rpc", sample(7200, false, 0));
+        tracker.record("rpc", sample(60, true, 20));
+
+        let report = tracker.report("rpc").unwrap();
+        assert_eq!(report.samples, 1);
+        assert_eq!(report.uptime_percent, 100.0);
+    }
+
+    #[test]
+    fn test_record_health() {
+        let tracker = UptimeTracker::new();
+        tracker.record_health(
+            &ComponentHealth::new("rpc", HealthStatus::Degraded)
+                .with_response_time(Duration::from_millis(250)),
+        );
+        tracker.record_health(&ComponentHealth::new("rpc", HealthStatus::Unhealthy));
+
+        let report = tracker.report("rpc").unwrap();
+        assert_eq!(report.up_samples, 1);
+        assert_eq!(report.latency_p50_ms, Some(250.0));
+    }
+
+    #[test]
+    fn test_persistence_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("uptime.json");
+
+        let tracker = UptimeTracker::with_persistence(&path).unwrap();
+        tracker.record("rpc", sample(10, true, 42));
+        tracker.save().unwrap();
+
+        let restored = UptimeTracker::with_persistence(&path).unwrap();
+        assert_eq!(restored.endpoints(), vec!["rpc"]);
+        assert_eq!(restored.report("rpc").unwrap().latency_p50_ms, Some(42.0));
+    }
+}
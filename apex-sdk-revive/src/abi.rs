@@ -0,0 +1,999 @@
+//! Solidity ABI encoding and decoding
+//!
+//! Parses a Solidity ABI JSON (as emitted by `solc` or `resolc`) and encodes
+//! calldata / decodes return data and event logs for [`ContractManager`], so
+//! callers do not have to hand-roll bytes.
+//!
+//! ```rust
+//! use apex_sdk_revive::abi::{Abi, AbiValue};
+//!
+//! let abi = Abi::from_json(
+//!     r#"[{
+//!         "type": "function",
+//!         "name": "transfer",
+//!         "inputs": [
+//!             {"name": "to", "type": "address"},
+//!             {"name": "amount", "type": "uint256"}
+//!         ],
+//!         "outputs": [{"name": "", "type": "bool"}],
+//!         "stateMutability": "nonpayable"
+//!     }]"#,
+//! )
+//! .unwrap();
+//!
+//! let transfer = abi.function("transfer").unwrap();
+//! assert_eq!(transfer.signature(), "transfer(address,uint256)");
+//! assert_eq!(transfer.selector(), [0xa9, 0x05, 0x9c, 0xbb]);
+//!
+//! let calldata = transfer
+//!     .encode_input(&[AbiValue::Address([0x11; 20]), AbiValue::uint(1_000)])
+//!     .unwrap();
+//! assert_eq!(calldata.len(), 4 + 2 * 32);
+//!
+//! let mut output = [0u8; 32];
+//! output[31] = 1;
+//! assert_eq!(transfer.decode_output(&output).unwrap(), vec![AbiValue::Bool(true)]);
+//! ```
+//!
+//! [`ContractManager`]: crate::ContractManager
+
+use crate::{Error, Result};
+use serde::Deserialize;
+use sha3::{Digest, Keccak256};
+
+/// Size of an ABI word in bytes
+const WORD: usize = 32;
+
+/// A Solidity ABI type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiType {
+    /// `uintN`
+    Uint(usize),
+    /// `intN`
+    Int(usize),
+    /// `address`
+    Address,
+    /// `bool`
+    Bool,
+    /// `bytesN`
+    FixedBytes(usize),
+    /// `bytes`
+    Bytes,
+    /// `string`
+    String,
+    /// `T[]`
+    Array(Box<AbiType>),
+    /// `T[N]`
+    FixedArray(Box<AbiType>, usize),
+    /// `(T1,T2,...)`
+    Tuple(Vec<AbiType>),
+}
+
+impl AbiType {
+    /// Whether the type is encoded out of place
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            AbiType::Bytes | AbiType::String | AbiType::Array(_) => true,
+            AbiType::FixedArray(inner, _) => inner.is_dynamic(),
+            AbiType::Tuple(types) => types.iter().any(AbiType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Canonical type name used in signatures
+    pub fn canonical(&self) -> String {
+        match self {
+            AbiType::Uint(bits) => format!("uint{}", bits),
+            AbiType::Int(bits) => format!("int{}", bits),
+            AbiType::Address => "address".to_string(),
+            AbiType::Bool => "bool".to_string(),
+            AbiType::FixedBytes(len) => format!("bytes{}", len),
+            AbiType::Bytes => "bytes".to_string(),
+            AbiType::String => "string".to_string(),
+            AbiType::Array(inner) => format!("{}[]", inner.canonical()),
+            AbiType::FixedArray(inner, len) => format!("{}[{}]", inner.canonical(), len),
+            AbiType::Tuple(types) => format!(
+                "({})",
+                types
+                    .iter()
+                    .map(AbiType::canonical)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+
+    /// Size of the type in the head of an enclosing tuple
+    fn head_size(&self) -> usize {
+        match self {
+            AbiType::FixedArray(inner, len) if !self.is_dynamic() => inner.head_size() * len,
+            AbiType::Tuple(types) if !self.is_dynamic() => types.iter().map(Self::head_size).sum(),
+            _ => WORD,
+        }
+    }
+
+    /// Parse a type string; `components` describe `tuple` members
+    fn parse(kind: &str, components: &[AbiParam]) -> Result<Self> {
+        if let Some(stripped) = kind.strip_suffix(']') {
+            let open = stripped
+                .rfind('[')
+                .ok_or_else(|| Error::Abi(format!("Invalid type '{}'", kind)))?;
+            let inner = Box::new(Self::parse(&stripped[..open], components)?);
+            let len = &stripped[open + 1..];
+            return if len.is_empty() {
+                Ok(AbiType::Array(inner))
+            } else {
+                let len = len
+                    .parse()
+                    .map_err(|_| Error::Abi(format!("Invalid array length in '{}'", kind)))?;
+                Ok(AbiType::FixedArray(inner, len))
+            };
+        }
+
+        let parsed = match kind {
+            "address" => AbiType::Address,
+            "bool" => AbiType::Bool,
+            "bytes" => AbiType::Bytes,
+            "string" => AbiType::String,
+            "uint" => AbiType::Uint(256),
+            "int" => AbiType::Int(256),
+            "tuple" => AbiType::Tuple(
+                components
+                    .iter()
+                    .map(AbiParam::abi_type)
+                    .collect::<Result<_>>()?,
+            ),
+            _ => {
+                let sized = |prefix: &str| kind.strip_prefix(prefix)?.parse::<usize>().ok();
+                if let Some(bits) = sized("uint").filter(|b| valid_int_bits(*b)) {
+                    AbiType::Uint(bits)
+                } else if let Some(bits) = sized("int").filter(|b| valid_int_bits(*b)) {
+                    AbiType::Int(bits)
+                } else if let Some(len) = sized("bytes").filter(|l| (1..=32).contains(l)) {
+                    AbiType::FixedBytes(len)
+                } else {
+                    return Err(Error::Abi(format!("Unsupported type '{}'", kind)));
+                }
+            }
+        };
+        Ok(parsed)
+    }
+}
+
+fn valid_int_bits(bits: usize) -> bool {
+    bits > 0 && bits <= 256 && bits.is_multiple_of(8)
+}
+
+/// A Solidity ABI value
+///
+/// Integers are held as 32-byte big-endian words (two's complement for
+/// `int`), which covers the full `uint256`/`int256` range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiValue {
+    /// `uintN`, as a big-endian word
+    Uint([u8; 32]),
+    /// `intN`, as a sign-extended big-endian word
+    Int([u8; 32]),
+    /// `address`
+    Address([u8; 20]),
+    /// `bool`
+    Bool(bool),
+    /// `bytesN`; the length must match `N`
+    FixedBytes(Vec<u8>),
+    /// `bytes`
+    Bytes(Vec<u8>),
+    /// `string`
+    String(String),
+    /// Values of `T[]` or `T[N]`
+    Array(Vec<AbiValue>),
+    /// Members of `(T1,T2,...)`, in order
+    Tuple(Vec<AbiValue>),
+}
+
+impl AbiValue {
+    /// Unsigned integer value
+    pub fn uint(value: u128) -> Self {
+        let mut word = [0u8; 32];
+        word[16..].copy_from_slice(&value.to_be_bytes());
+        AbiValue::Uint(word)
+    }
+
+    /// Signed integer value
+    pub fn int(value: i128) -> Self {
+        let mut word = if value < 0 { [0xff; 32] } else { [0u8; 32] };
+        word[16..].copy_from_slice(&value.to_be_bytes());
+        AbiValue::Int(word)
+    }
+
+    /// Unsigned integer as `u128`, if it fits
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            AbiValue::Uint(word) if word[..16].iter().all(|b| *b == 0) => {
+                Some(u128::from_be_bytes(word[16..].try_into().ok()?))
+            }
+            _ => None,
+        }
+    }
+
+    /// Signed integer as `i128`, if it fits
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            AbiValue::Int(word) => {
+                let value = i128::from_be_bytes(word[16..].try_into().ok()?);
+                let extension = if value < 0 { 0xff } else { 0 };
+                word[..16].iter().all(|b| *b == extension).then_some(value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Address bytes
+    pub fn as_address(&self) -> Option<&[u8; 20]> {
+        match self {
+            AbiValue::Address(address) => Some(address),
+            _ => None,
+        }
+    }
+
+    /// Boolean value
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AbiValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Contents of `bytes` or `bytesN`
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            AbiValue::Bytes(bytes) | AbiValue::FixedBytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Contents of `string`
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AbiValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Elements of an array or tuple
+    pub fn as_slice(&self) -> Option<&[AbiValue]> {
+        match self {
+            AbiValue::Array(values) | AbiValue::Tuple(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// A function or constructor parameter in ABI JSON
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbiParam {
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub components: Vec<AbiParam>,
+    /// Whether an event parameter is stored in a topic
+    #[serde(default)]
+    pub indexed: bool,
+}
+
+impl AbiParam {
+    /// Parsed type of the parameter
+    pub fn abi_type(&self) -> Result<AbiType> {
+        AbiType::parse(&self.kind, &self.components)
+    }
+}
+
+/// A contract function
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub name: String,
+    pub inputs: Vec<AbiParam>,
+    pub outputs: Vec<AbiParam>,
+    pub state_mutability: String,
+    input_types: Vec<AbiType>,
+    output_types: Vec<AbiType>,
+}
+
+impl Function {
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    pub fn signature(&self) -> String {
+        format!(
+            "{}{}",
+            self.name,
+            AbiType::Tuple(self.input_types.clone()).canonical()
+        )
+    }
+
+    /// First four bytes of the Keccak-256 hash of the signature
+    pub fn selector(&self) -> [u8; 4] {
+        let hash = Keccak256::digest(self.signature().as_bytes());
+        [hash[0], hash[1], hash[2], hash[3]]
+    }
+
+    /// Whether the function does not modify state
+    pub fn is_read_only(&self) -> bool {
+        matches!(self.state_mutability.as_str(), "view" | "pure")
+    }
+
+    /// Encode calldata: selector followed by the encoded arguments
+    pub fn encode_input(&self, args: &[AbiValue]) -> Result<Vec<u8>> {
+        let mut data = self.selector().to_vec();
+        data.extend(encode(&self.input_types, args)?);
+        Ok(data)
+    }
+
+    /// Decode return data into one value per output
+    pub fn decode_output(&self, data: &[u8]) -> Result<Vec<AbiValue>> {
+        decode(&self.output_types, data)
+    }
+}
+
+/// A contract constructor
+#[derive(Debug, Clone)]
+pub struct Constructor {
+    pub inputs: Vec<AbiParam>,
+    input_types: Vec<AbiType>,
+}
+
+impl Constructor {
+    /// Encode constructor arguments, appended to the code on deployment
+    pub fn encode_input(&self, args: &[AbiValue]) -> Result<Vec<u8>> {
+        encode(&self.input_types, args)
+    }
+}
+
+/// A contract event
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub name: String,
+    pub inputs: Vec<AbiParam>,
+    pub anonymous: bool,
+    input_types: Vec<AbiType>,
+}
+
+impl Event {
+    /// Canonical signature, e.g. `Transfer(address,address,uint256)`
+    pub fn signature(&self) -> String {
+        format!(
+            "{}{}",
+            self.name,
+            AbiType::Tuple(self.input_types.clone()).canonical()
+        )
+    }
+
+    /// Keccak-256 hash of the signature, emitted as the first topic
+    pub fn topic(&self) -> [u8; 32] {
+        Keccak256::digest(self.signature().as_bytes()).into()
+    }
+
+    /// Decode a log into one value per input, in declaration order
+    ///
+    /// Indexed parameters are read from `topics` (after the signature topic
+    /// unless the event is anonymous) and the rest from `data`. Indexed
+    /// parameters of dynamic type only carry their hash, which is returned
+    /// as a `bytes32` value.
+    pub fn decode_log(&self, topics: &[[u8; 32]], data: &[u8]) -> Result<Vec<AbiValue>> {
+        let mut topics = topics.iter().skip(usize::from(!self.anonymous));
+
+        let data_types: Vec<AbiType> = self
+            .inputs
+            .iter()
+            .zip(&self.input_types)
+            .filter(|(param, _)| !param.indexed)
+            .map(|(_, kind)| kind.clone())
+            .collect();
+        let mut data_values = decode(&data_types, data)?.into_iter();
+
+        self.inputs
+            .iter()
+            .zip(&self.input_types)
+            .map(|(param, kind)| {
+                if !param.indexed {
+                    return data_values.next().ok_or_else(out_of_bounds);
+                }
+                let topic = topics.next().ok_or_else(|| {
+                    Error::Abi(format!("Missing topic for indexed '{}'", param.name))
+                })?;
+                if kind.is_dynamic() || matches!(kind, AbiType::FixedArray(..) | AbiType::Tuple(_))
+                {
+                    Ok(AbiValue::FixedBytes(topic.to_vec()))
+                } else {
+                    decode_value(kind, topic)
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AbiEntry {
+    #[serde(rename = "type", default = "default_entry_type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    inputs: Vec<AbiParam>,
+    #[serde(default)]
+    outputs: Vec<AbiParam>,
+    #[serde(default)]
+    state_mutability: String,
+    #[serde(default)]
+    anonymous: bool,
+}
+
+fn default_entry_type() -> String {
+    "function".to_string()
+}
+
+/// A parsed contract ABI
+///
+/// Functions, events and the constructor are kept; errors, `receive` and
+/// `fallback` entries are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct Abi {
+    functions: Vec<Function>,
+    events: Vec<Event>,
+    constructor: Option<Constructor>,
+}
+
+impl Abi {
+    /// Parse an ABI JSON array
+    pub fn from_json(json: &str) -> Result<Self> {
+        let entries: Vec<AbiEntry> = serde_json::from_str(json)
+            .map_err(|e| Error::Abi(format!("Invalid ABI JSON: {}", e)))?;
+
+        let types = |params: &[AbiParam]| -> Result<Vec<AbiType>> {
+            params.iter().map(AbiParam::abi_type).collect()
+        };
+
+        let mut abi = Abi::default();
+        for entry in entries {
+            match entry.kind.as_str() {
+                "function" => abi.functions.push(Function {
+                    input_types: types(&entry.inputs)?,
+                    output_types: types(&entry.outputs)?,
+                    name: entry.name,
+                    inputs: entry.inputs,
+                    outputs: entry.outputs,
+                    state_mutability: entry.state_mutability,
+                }),
+                "event" => abi.events.push(Event {
+                    input_types: types(&entry.inputs)?,
+                    name: entry.name,
+                    inputs: entry.inputs,
+                    anonymous: entry.anonymous,
+                }),
+                "constructor" => {
+                    abi.constructor = Some(Constructor {
+                        input_types: types(&entry.inputs)?,
+                        inputs: entry.inputs,
+                    })
+                }
+                _ => {}
+            }
+        }
+        Ok(abi)
+    }
+
+    /// First function with the given name
+    ///
+    /// Use [`Abi::function_by_signature`] to pick an overload.
+    pub fn function(&self, name: &str) -> Option<&Function> {
+        self.functions.iter().find(|f| f.name == name)
+    }
+
+    /// Function with the given canonical signature
+    pub fn function_by_signature(&self, signature: &str) -> Option<&Function> {
+        self.functions.iter().find(|f| f.signature() == signature)
+    }
+
+    /// Function with the given selector
+    pub fn function_by_selector(&self, selector: [u8; 4]) -> Option<&Function> {
+        self.functions.iter().find(|f| f.selector() == selector)
+    }
+
+    pub fn functions(&self) -> &[Function] {
+        &self.functions
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Non-anonymous event whose signature hashes to `topic`
+    pub fn event_by_topic(&self, topic: &[u8; 32]) -> Option<&Event> {
+        self.events
+            .iter()
+            .find(|e| !e.anonymous && &e.topic() == topic)
+    }
+
+    pub fn constructor(&self) -> Option<&Constructor> {
+        self.constructor.as_ref()
+    }
+
+    /// Encode constructor arguments; empty if the ABI has no constructor
+    pub fn encode_constructor(&self, args: &[AbiValue]) -> Result<Vec<u8>> {
+        match &self.constructor {
+            Some(constructor) => constructor.encode_input(args),
+            None if args.is_empty() => Ok(Vec::new()),
+            None => Err(Error::Abi("ABI has no constructor".into())),
+        }
+    }
+}
+
+/// Encode values as an ABI tuple of `types`
+pub fn encode(types: &[AbiType], values: &[AbiValue]) -> Result<Vec<u8>> {
+    if types.len() != values.len() {
+        return Err(Error::Abi(format!(
+            "Expected {} values, got {}",
+            types.len(),
+            values.len()
+        )));
+    }
+
+    let head_len: usize = types.iter().map(AbiType::head_size).sum();
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+
+    for (kind, value) in types.iter().zip(values) {
+        let encoded = encode_value(kind, value)?;
+        if kind.is_dynamic() {
+            head.extend(usize_word(head_len + tail.len()));
+            tail.extend(encoded);
+        } else {
+            head.extend(encoded);
+        }
+    }
+
+    head.extend(tail);
+    Ok(head)
+}
+
+/// Decode ABI tuple data of `types`
+pub fn decode(types: &[AbiType], data: &[u8]) -> Result<Vec<AbiValue>> {
+    let mut offset = 0;
+    types
+        .iter()
+        .map(|kind| {
+            let value = if kind.is_dynamic() {
+                let start = read_usize(data, offset)?;
+                decode_value(kind, data.get(start..).ok_or_else(out_of_bounds)?)
+            } else {
+                decode_value(kind, data.get(offset..).ok_or_else(out_of_bounds)?)
+            };
+            offset += kind.head_size();
+            value
+        })
+        .collect()
+}
+
+fn encode_value(kind: &AbiType, value: &AbiValue) -> Result<Vec<u8>> {
+    let mismatch = || {
+        Error::Abi(format!(
+            "Value {:?} does not match type {}",
+            value,
+            kind.canonical()
+        ))
+    };
+
+    match (kind, value) {
+        (AbiType::Uint(bits), AbiValue::Uint(word)) => {
+            let unused = (256 - bits) / 8;
+            if word[..unused].iter().any(|b| *b != 0) {
+                return Err(mismatch());
+            }
+            Ok(word.to_vec())
+        }
+        (AbiType::Int(bits), AbiValue::Int(word)) => {
+            let unused = (256 - bits) / 8;
+            let extension = if word[unused] & 0x80 != 0 { 0xff } else { 0 };
+            if word[..unused].iter().any(|b| *b != extension) {
+                return Err(mismatch());
+            }
+            Ok(word.to_vec())
+        }
+        (AbiType::Address, AbiValue::Address(address)) => {
+            let mut word = vec![0u8; 12];
+            word.extend_from_slice(address);
+            Ok(word)
+        }
+        (AbiType::Bool, AbiValue::Bool(value)) => Ok(usize_word(*value as usize).to_vec()),
+        (AbiType::FixedBytes(len), AbiValue::FixedBytes(bytes)) if bytes.len() == *len => {
+            Ok(pad_right(bytes))
+        }
+        (AbiType::Bytes, AbiValue::Bytes(bytes)) => {
+            let mut encoded = usize_word(bytes.len()).to_vec();
+            encoded.extend(pad_right(bytes));
+            Ok(encoded)
+        }
+        (AbiType::String, AbiValue::String(value)) => {
+            let mut encoded = usize_word(value.len()).to_vec();
+            encoded.extend(pad_right(value.as_bytes()));
+            Ok(encoded)
+        }
+        (AbiType::Array(inner), AbiValue::Array(values)) => {
+            let mut encoded = usize_word(values.len()).to_vec();
+            encoded.extend(encode(&vec![(**inner).clone(); values.len()], values)?);
+            Ok(encoded)
+        }
+        (AbiType::FixedArray(inner, len), AbiValue::Array(values)) if values.len() == *len => {
+            encode(&vec![(**inner).clone(); *len], values)
+        }
+        (AbiType::Tuple(types), AbiValue::Tuple(values)) => encode(types, values),
+        _ => Err(mismatch()),
+    }
+}
+
+fn decode_value(kind: &AbiType, data: &[u8]) -> Result<AbiValue> {
+    match kind {
+        AbiType::Uint(_) => Ok(AbiValue::Uint(read_word(data, 0)?)),
+        AbiType::Int(_) => Ok(AbiValue::Int(read_word(data, 0)?)),
+        AbiType::Address => {
+            let word = read_word(data, 0)?;
+            let mut address = [0u8; 20];
+            address.copy_from_slice(&word[12..]);
+            Ok(AbiValue::Address(address))
+        }
+        AbiType::Bool => match read_usize(data, 0)? {
+            0 => Ok(AbiValue::Bool(false)),
+            1 => Ok(AbiValue::Bool(true)),
+            other => Err(Error::Abi(format!("Invalid bool value {}", other))),
+        },
+        AbiType::FixedBytes(len) => Ok(AbiValue::FixedBytes(read_word(data, 0)?[..*len].to_vec())),
+        AbiType::Bytes => Ok(AbiValue::Bytes(read_bytes(data)?.to_vec())),
+        AbiType::String => String::from_utf8(read_bytes(data)?.to_vec())
+            .map(AbiValue::String)
+            .map_err(|e| Error::Abi(format!("Invalid UTF-8 string: {}", e))),
+        AbiType::Array(inner) => {
+            let len = read_usize(data, 0)?;
+            // Every element takes at least one word; reject lengths the data cannot hold
+            if len > data.len() / WORD {
+                return Err(out_of_bounds());
+            }
+            decode(&vec![(**inner).clone(); len], &data[WORD..]).map(AbiValue::Array)
+        }
+        AbiType::FixedArray(inner, len) => {
+            decode(&vec![(**inner).clone(); *len], data).map(AbiValue::Array)
+        }
+        AbiType::Tuple(types) => decode(types, data).map(AbiValue::Tuple),
+    }
+}
+
+fn out_of_bounds() -> Error {
+    Error::Abi("Data too short".into())
+}
+
+fn read_word(data: &[u8], offset: usize) -> Result<[u8; 32]> {
+    data.get(offset..offset + WORD)
+        .and_then(|word| word.try_into().ok())
+        .ok_or_else(out_of_bounds)
+}
+
+fn read_usize(data: &[u8], offset: usize) -> Result<usize> {
+    let word = read_word(data, offset)?;
+    if word[..24].iter().any(|b| *b != 0) {
+        return Err(Error::Abi("Offset or length out of range".into()));
+    }
+    Ok(u64::from_be_bytes(word[24..].try_into().expect("8 bytes")) as usize)
+}
+
+/// Length-prefixed `bytes`/`string` contents
+fn read_bytes(data: &[u8]) -> Result<&[u8]> {
+    let len = read_usize(data, 0)?;
+    data.get(WORD..)
+        .and_then(|rest| rest.get(..len))
+        .ok_or_else(out_of_bounds)
+}
+
+fn usize_word(value: usize) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+fn pad_right(bytes: &[u8]) -> Vec<u8> {
+    let mut padded = bytes.to_vec();
+    padded.resize(bytes.len().div_ceil(WORD) * WORD, 0);
+    padded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ERC20: &str = r#"[
+        {
+            "type": "function",
+            "name": "transfer",
+            "inputs": [
+                {"name": "to", "type": "address"},
+                {"name": "amount", "type": "uint256"}
+            ],
+            "outputs": [{"name": "", "type": "bool"}],
+            "stateMutability": "nonpayable"
+        },
+        {
+            "type": "event",
+            "name": "Transfer",
+            "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ],
+            "anonymous": false
+        }
+    ]"#;
+
+    fn function(name: &str, inputs: &[&str]) -> Function {
+        let inputs: Vec<_> = inputs
+            .iter()
+            .map(|kind| format!(r#"{{"name": "", "type": "{}"}}"#, kind))
+            .collect();
+        let json = format!(
+            r#"[{{"type": "function", "name": "{}", "inputs": [{}], "outputs": []}}]"#,
+            name,
+            inputs.join(",")
+        );
+        Abi::from_json(&json).unwrap().functions()[0].clone()
+    }
+
+    /// Concatenated hex words, whitespace ignored
+    fn words(hex_words: &str) -> Vec<u8> {
+        let joined: String = hex_words.split_whitespace().collect();
+        hex::decode(joined).unwrap()
+    }
+
+    fn round_trip(function: &Function, args: &[AbiValue], expected: &[u8]) {
+        let calldata = function.encode_input(args).unwrap();
+        assert_eq!(hex::encode(&calldata), hex::encode(expected));
+        assert_eq!(decode(&function.input_types, &calldata[4..]).unwrap(), args);
+    }
+
+    #[test]
+    fn test_transfer_calldata() {
+        let abi = Abi::from_json(ERC20).unwrap();
+        let transfer = abi.function("transfer").unwrap();
+        assert_eq!(transfer.selector(), [0xa9, 0x05, 0x9c, 0xbb]);
+
+        round_trip(
+            transfer,
+            &[AbiValue::Address([0x11; 20]), AbiValue::uint(1_000)],
+            &words(
+                "a9059cbb
+                 0000000000000000000000001111111111111111111111111111111111111111
+                 00000000000000000000000000000000000000000000000000000000000003e8",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_spec_static_arguments() {
+        // baz(uint32,bool) with 69, true from the Solidity ABI specification
+        round_trip(
+            &function("baz", &["uint32", "bool"]),
+            &[AbiValue::uint(69), AbiValue::Bool(true)],
+            &words(
+                "cdcd77c0
+                 0000000000000000000000000000000000000000000000000000000000000045
+                 0000000000000000000000000000000000000000000000000000000000000001",
+            ),
+        );
+
+        // bar(bytes3[2]) with ["abc", "def"]: a fixed array is encoded in place
+        round_trip(
+            &function("bar", &["bytes3[2]"]),
+            &[AbiValue::Array(vec![
+                AbiValue::FixedBytes(b"abc".to_vec()),
+                AbiValue::FixedBytes(b"def".to_vec()),
+            ])],
+            &words(
+                "fce353f6
+                 6162630000000000000000000000000000000000000000000000000000000000
+                 6465660000000000000000000000000000000000000000000000000000000000",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_spec_dynamic_arguments() {
+        // f(uint256,uint32[],bytes10,bytes) from the Solidity ABI specification
+        let f = function("f", &["uint256", "uint32[]", "bytes10", "bytes"]);
+        assert_eq!(f.signature(), "f(uint256,uint32[],bytes10,bytes)");
+
+        round_trip(
+            &f,
+            &[
+                AbiValue::uint(0x123),
+                AbiValue::Array(vec![AbiValue::uint(0x456), AbiValue::uint(0x789)]),
+                AbiValue::FixedBytes(b"1234567890".to_vec()),
+                AbiValue::Bytes(b"Hello, world!".to_vec()),
+            ],
+            &words(
+                "8be65246
+                 0000000000000000000000000000000000000000000000000000000000000123
+                 0000000000000000000000000000000000000000000000000000000000000080
+                 3132333435363738393000000000000000000000000000000000000000000000
+                 00000000000000000000000000000000000000000000000000000000000000e0
+                 0000000000000000000000000000000000000000000000000000000000000002
+                 0000000000000000000000000000000000000000000000000000000000000456
+                 0000000000000000000000000000000000000000000000000000000000000789
+                 000000000000000000000000000000000000000000000000000000000000000d
+                 48656c6c6f2c20776f726c642100000000000000000000000000000000000000",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_spec_nested_dynamic_arguments() {
+        // g(uint256[][],string[]) with ([[1, 2], [3]], ["one", "two", "three"])
+        let uints =
+            |values: &[u128]| AbiValue::Array(values.iter().map(|v| AbiValue::uint(*v)).collect());
+        let strings = ["one", "two", "three"]
+            .iter()
+            .map(|s| AbiValue::String(s.to_string()))
+            .collect();
+
+        round_trip(
+            &function("g", &["uint256[][]", "string[]"]),
+            &[
+                AbiValue::Array(vec![uints(&[1, 2]), uints(&[3])]),
+                AbiValue::Array(strings),
+            ],
+            &words(
+                "2289b18c
+                 0000000000000000000000000000000000000000000000000000000000000040
+                 0000000000000000000000000000000000000000000000000000000000000140
+                 0000000000000000000000000000000000000000000000000000000000000002
+                 0000000000000000000000000000000000000000000000000000000000000040
+                 00000000000000000000000000000000000000000000000000000000000000a0
+                 0000000000000000000000000000000000000000000000000000000000000002
+                 0000000000000000000000000000000000000000000000000000000000000001
+                 0000000000000000000000000000000000000000000000000000000000000002
+                 0000000000000000000000000000000000000000000000000000000000000001
+                 0000000000000000000000000000000000000000000000000000000000000003
+                 0000000000000000000000000000000000000000000000000000000000000003
+                 0000000000000000000000000000000000000000000000000000000000000060
+                 00000000000000000000000000000000000000000000000000000000000000a0
+                 00000000000000000000000000000000000000000000000000000000000000e0
+                 0000000000000000000000000000000000000000000000000000000000000003
+                 6f6e650000000000000000000000000000000000000000000000000000000000
+                 0000000000000000000000000000000000000000000000000000000000000003
+                 74776f0000000000000000000000000000000000000000000000000000000000
+                 0000000000000000000000000000000000000000000000000000000000000005
+                 7468726565000000000000000000000000000000000000000000000000000000",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_negative_int_is_sign_extended() {
+        let f = function("neg", &["int8", "int256"]);
+        round_trip(
+            &f,
+            &[AbiValue::int(-1), AbiValue::int(-2)],
+            &[
+                f.selector().to_vec(),
+                vec![0xff; 32],
+                words("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe"),
+            ]
+            .concat(),
+        );
+
+        let calldata = f
+            .encode_input(&[AbiValue::int(-128), AbiValue::int(i128::MIN)])
+            .unwrap();
+        let decoded = decode(&f.input_types, &calldata[4..]).unwrap();
+        assert_eq!(decoded[0].as_i128(), Some(-128));
+        assert_eq!(decoded[1].as_i128(), Some(i128::MIN));
+
+        // -129 does not fit in int8, 256 does not fit in uint8
+        assert!(f
+            .encode_input(&[AbiValue::int(-129), AbiValue::int(0)])
+            .is_err());
+        assert!(function("u", &["uint8"])
+            .encode_input(&[AbiValue::uint(256)])
+            .is_err());
+    }
+
+    #[test]
+    fn test_fixed_bytes_length_must_match() {
+        let f = function("b", &["bytes4"]);
+        assert!(f
+            .encode_input(&[AbiValue::FixedBytes(vec![1, 2, 3])])
+            .is_err());
+        assert!(f
+            .encode_input(&[AbiValue::FixedBytes(vec![1, 2, 3, 4, 5])])
+            .is_err());
+    }
+
+    #[test]
+    fn test_decode_output_rejects_short_data() {
+        let abi = Abi::from_json(ERC20).unwrap();
+        let transfer = abi.function("transfer").unwrap();
+        assert!(transfer.decode_output(&[0u8; 31]).is_err());
+
+        let mut invalid = [0u8; 32];
+        invalid[31] = 2;
+        assert!(transfer.decode_output(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_erc20_transfer_log() {
+        let abi = Abi::from_json(ERC20).unwrap();
+        let topic: [u8; 32] =
+            words("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")
+                .try_into()
+                .unwrap();
+
+        let event = abi.event_by_topic(&topic).unwrap();
+        assert_eq!(event.signature(), "Transfer(address,address,uint256)");
+
+        let pad = |address: [u8; 20]| {
+            let mut word = [0u8; 32];
+            word[12..].copy_from_slice(&address);
+            word
+        };
+        let topics = [topic, pad([0xaa; 20]), pad([0xbb; 20])];
+        let data = words("0000000000000000000000000000000000000000000000000de0b6b3a7640000");
+
+        assert_eq!(
+            event.decode_log(&topics, &data).unwrap(),
+            vec![
+                AbiValue::Address([0xaa; 20]),
+                AbiValue::Address([0xbb; 20]),
+                AbiValue::uint(1_000_000_000_000_000_000),
+            ]
+        );
+
+        // The `to` topic is missing
+        assert!(event.decode_log(&topics[..2], &data).is_err());
+    }
+
+    #[test]
+    fn test_indexed_dynamic_parameter_is_its_hash() {
+        let abi = Abi::from_json(
+            r#"[{
+                "type": "event",
+                "name": "Named",
+                "inputs": [
+                    {"name": "name", "type": "string", "indexed": true},
+                    {"name": "id", "type": "uint256", "indexed": false}
+                ],
+                "anonymous": false
+            }]"#,
+        )
+        .unwrap();
+        let event = &abi.events()[0];
+
+        let hash: [u8; 32] = Keccak256::digest(b"alice").into();
+        let data = encode(&[AbiType::Uint(256)], &[AbiValue::uint(7)]).unwrap();
+        let values = event.decode_log(&[event.topic(), hash], &data).unwrap();
+        assert_eq!(
+            values,
+            vec![AbiValue::FixedBytes(hash.to_vec()), AbiValue::uint(7)]
+        );
+    }
+
+    #[test]
+    fn test_accessors() {
+        assert_eq!(AbiValue::uint(5).as_u128(), Some(5));
+        assert_eq!(AbiValue::Uint([0xff; 32]).as_u128(), None);
+        assert_eq!(AbiValue::int(-5).as_i128(), Some(-5));
+        assert_eq!(AbiValue::int(-5).as_u128(), None);
+        assert_eq!(AbiValue::Bool(true).as_bool(), Some(true));
+        assert_eq!(AbiValue::Address([1; 20]).as_address(), Some(&[1; 20]));
+        assert_eq!(AbiValue::FixedBytes(vec![1]).as_bytes(), Some(&[1u8][..]));
+        assert_eq!(AbiValue::String("a".into()).as_str(), Some("a"));
+        assert_eq!(
+            AbiValue::Tuple(vec![AbiValue::Bool(false)]).as_slice(),
+            Some(&[AbiValue::Bool(false)][..])
+        );
+    }
+}
@@ -1,27 +1,72 @@
 use crate::{Error, Result};
-use apex_sdk_core::{BlockInfo, ChainAdapter, Provider, SdkError};
-use apex_sdk_types::{Address, TransactionStatus, TxStatus};
+use apex_sdk_core::{BlockInfo, ChainAdapter, Provider, SdkError, TxInclusion, TxLookup, TxSearch};
+use apex_sdk_types::{Address, ChainProperties, TransactionStatus, TxHash};
 use async_trait::async_trait;
+use subxt::backend::{legacy::LegacyRpcMethods, rpc::RpcClient};
 use subxt::dynamic::{At, Value};
 use subxt::{OnlineClient, PolkadotConfig};
+use tokio::sync::OnceCell;
+
+/// Number of recent blocks searched by
+/// [`ReviveAdapter::get_transaction_status_async`]
+pub const DEFAULT_SEARCH_DEPTH: u32 = 100;
 
 /// Adapter for interacting with pallet-revive on System Chains
 pub struct ReviveAdapter {
     client: OnlineClient<PolkadotConfig>,
+    endpoint: Option<String>,
+    /// Direct RPC access, if connected through [`ReviveAdapter::connect`]
+    rpc: Option<LegacyRpcMethods<PolkadotConfig>>,
+    /// Token and address format reported by the node (lazy-initialized)
+    properties: OnceCell<ChainProperties>,
+    /// Span and error recording for adapter calls
+    #[cfg(feature = "observability")]
+    pub(crate) instrumentation: Option<apex_sdk_metrics::ChainInstrumentation>,
 }
 
 impl ReviveAdapter {
     /// Create a new adapter from a subxt client (internal use)
     pub fn new(client: OnlineClient<PolkadotConfig>) -> Self {
-        Self { client }
+        Self {
+            client,
+            endpoint: None,
+            rpc: None,
+            properties: OnceCell::new(),
+            #[cfg(feature = "observability")]
+            instrumentation: None,
+        }
     }
 
     /// Connect to a node with pallet-revive
     pub async fn connect(url: &str) -> Result<Self> {
-        let client = OnlineClient::from_url(url)
+        let rpc = RpcClient::from_url(url)
+            .await
+            .map_err(|e| Error::Connection(e.to_string()))?;
+        let client = OnlineClient::from_rpc_client(rpc.clone())
             .await
             .map_err(|e| Error::Connection(e.to_string()))?;
-        Ok(Self { client })
+        Ok(Self {
+            endpoint: Some(url.to_string()),
+            rpc: Some(LegacyRpcMethods::new(rpc)),
+            ..Self::new(client)
+        })
+    }
+
+    /// Record balance queries, contract transactions and RPC requests as
+    /// spans and errors in `facade`, labelled with this chain and endpoint
+    #[cfg(feature = "observability")]
+    pub fn with_observability(mut self, facade: apex_sdk_metrics::ObservabilityFacade) -> Self {
+        self.instrumentation = Some(apex_sdk_metrics::ChainInstrumentation::new(
+            facade,
+            "Revive",
+            self.endpoint.as_deref(),
+        ));
+        self
+    }
+
+    /// Get the endpoint URL, if connected through [`ReviveAdapter::connect`]
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoint.as_deref()
     }
 
     /// Get the underlying subxt client
@@ -29,6 +74,29 @@ impl ReviveAdapter {
         &self.client
     }
 
+    /// Native token and address format of the connected chain
+    ///
+    /// Fetched with the `system_properties` RPC on first use and cached.
+    /// Balances from [`get_revive_balance`](Self::get_revive_balance) are in
+    /// these units. Needs an adapter created with [`ReviveAdapter::connect`].
+    pub async fn chain_properties(&self) -> Result<ChainProperties> {
+        self.properties
+            .get_or_try_init(|| async {
+                let rpc = self.rpc.as_ref().ok_or_else(|| {
+                    Error::Connection(
+                        "system_properties needs an adapter created with ReviveAdapter::connect"
+                            .to_string(),
+                    )
+                })?;
+                let reported = rpc.system_properties().await.map_err(|e| {
+                    Error::Connection(format!("Failed to fetch system properties: {}", e))
+                })?;
+                Ok(ChainProperties::from_system_properties(&reported))
+            })
+            .await
+            .cloned()
+    }
+
     /// Check if connected to the node
     pub async fn is_connected(&self) -> bool {
         self.client.blocks().at_latest().await.is_ok()
@@ -38,13 +106,15 @@ impl ReviveAdapter {
 #[async_trait]
 impl Provider for ReviveAdapter {
     async fn get_block_number(&self) -> std::result::Result<u64, SdkError> {
-        let block = self
-            .client
-            .blocks()
-            .at_latest()
-            .await
-            .map_err(|e| SdkError::NetworkError(e.to_string()))?;
-        Ok(block.number() as u64)
+        observed!(self, RpcRequest, "get_block_number", async {
+            let block = self
+                .client
+                .blocks()
+                .at_latest()
+                .await
+                .map_err(|e| SdkError::NetworkError(e.to_string()))?;
+            Ok(block.number() as u64)
+        })
     }
 
     async fn get_balance(&self, address: &Address) -> std::result::Result<u128, SdkError> {
@@ -151,6 +221,8 @@ impl Provider for ReviveAdapter {
             extrinsic_count: 0,
             event_count: None,
             is_finalized: false,
+            extrinsics: Vec::new(),
+            author: None,
         })
     }
 
@@ -163,7 +235,7 @@ impl Provider for ReviveAdapter {
 impl ChainAdapter for ReviveAdapter {
     async fn get_transaction_status(
         &self,
-        tx_hash: &str,
+        tx_hash: &TxHash,
     ) -> std::result::Result<TransactionStatus, String> {
         self.get_transaction_status_async(tx_hash)
             .await
@@ -184,61 +256,160 @@ impl ChainAdapter for ReviveAdapter {
 
 impl ReviveAdapter {
     /// Get transaction status by hash
-    pub async fn get_transaction_status_async(&self, tx_hash: &str) -> Result<TransactionStatus> {
-        let hash_vec = if let Some(stripped) = tx_hash.strip_prefix("0x") {
-            hex::decode(stripped).map_err(|e| Error::Other(format!("Invalid hash: {}", e)))?
-        } else {
-            hex::decode(tx_hash).map_err(|e| Error::Other(format!("Invalid hash: {}", e)))?
-        };
-
-        if hash_vec.len() != 32 {
-            return Err(Error::Other("Transaction hash must be 32 bytes".into()));
-        }
+    ///
+    /// Searches the [`DEFAULT_SEARCH_DEPTH`] most recent blocks; use
+    /// [`lookup_transaction`](Self::lookup_transaction) to search elsewhere.
+    pub async fn get_transaction_status_async(
+        &self,
+        tx_hash: &TxHash,
+    ) -> Result<TransactionStatus> {
+        let lookup = self
+            .lookup_transaction(tx_hash, TxSearch::Depth(DEFAULT_SEARCH_DEPTH))
+            .await?;
+        Ok(lookup.to_status(tx_hash.to_string()))
+    }
 
-        let mut hash = [0u8; 32];
-        hash.copy_from_slice(&hash_vec);
+    /// Look up a transaction by extrinsic hash in the blocks of `search`
+    ///
+    /// With an adapter created by [`ReviveAdapter::connect`], blocks are
+    /// fetched by number with `chain_getBlockHash`, so a search can start at
+    /// any height the node still has; otherwise the adapter walks parent
+    /// hashes back from the finalized head.
+    pub async fn lookup_transaction(&self, tx_hash: &TxHash, search: TxSearch) -> Result<TxLookup> {
+        observed!(
+            self,
+            RpcRequest,
+            "get_transaction_status",
+            self.query_transaction_status(tx_hash, search)
+        )
+    }
 
-        // Try to find the transaction in the last few blocks
-        let best_block = self
+    async fn query_transaction_status(
+        &self,
+        tx_hash: &TxHash,
+        search: TxSearch,
+    ) -> Result<TxLookup> {
+        let finalized = self
             .client
             .blocks()
             .at_latest()
             .await
             .map_err(|e| Error::Connection(e.to_string()))?;
+        let finalized_number = finalized.number() as u64;
+        let best = match &self.rpc {
+            Some(rpc) => rpc
+                .chain_get_header(None)
+                .await
+                .map_err(|e| Error::Connection(e.to_string()))?
+                .map_or(finalized_number, |header| header.number as u64),
+            None => finalized_number,
+        };
 
-        let mut current_block_hash = best_block.hash();
-
-        // Check last 10 blocks by following parent hashes
-        for _ in 0..10 {
-            let block = self.client.blocks().at(current_block_hash).await;
-            if let Ok(block) = block {
-                let block_number = block.number() as u64;
-                let extrinsics = block.extrinsics().await;
-                if let Ok(extrinsics) = extrinsics {
-                    for ext_details in extrinsics.iter() {
-                        if ext_details.hash().0 == hash {
-                            // Found it!
-                            return Ok(TransactionStatus {
-                                hash: tx_hash.to_string(),
-                                status: TxStatus::Confirmed,
-                                block_number: Some(block_number),
-                                block_hash: Some(format!("0x{:x}", block.hash())),
-                                ..Default::default()
-                            });
-                        }
+        let Some((from, to)) = search.range(best) else {
+            return Ok(TxLookup::Pending);
+        };
+
+        let numbers: Box<dyn Iterator<Item = u64> + Send> = match search {
+            TxSearch::Depth(_) => Box::new((from..=to).rev()),
+            TxSearch::Since { .. } => Box::new(from..=to),
+        };
+        for number in numbers {
+            let Some(hash) = self.block_hash(number, &finalized).await? else {
+                continue;
+            };
+            let block = self
+                .client
+                .blocks()
+                .at(hash)
+                .await
+                .map_err(|e| Error::Connection(e.to_string()))?;
+            let extrinsics = block
+                .extrinsics()
+                .await
+                .map_err(|e| Error::Connection(e.to_string()))?;
+
+            let Some(extrinsic) = extrinsics
+                .iter()
+                .find(|extrinsic| extrinsic.hash().0 == *tx_hash.as_bytes())
+            else {
+                continue;
+            };
+
+            let success = extrinsic.events().await.ok().and_then(|events| {
+                events.iter().flatten().find_map(|event| {
+                    match (event.pallet_name(), event.variant_name()) {
+                        ("System", "ExtrinsicSuccess") => Some(true),
+                        ("System", "ExtrinsicFailed") => Some(false),
+                        _ => None,
                     }
-                }
-                current_block_hash = block.header().parent_hash;
+                })
+            });
+            let inclusion = TxInclusion {
+                block_number: number,
+                block_hash: format!("0x{:x}", hash),
+                extrinsic_index: extrinsic.index(),
+                confirmations: best.saturating_sub(number) as u32,
+                success,
+                error: None,
+            };
+            return Ok(if number <= finalized_number {
+                TxLookup::Finalized(inclusion)
             } else {
-                break;
-            }
+                TxLookup::InBlock(inclusion)
+            });
         }
 
-        Ok(TransactionStatus::pending(tx_hash.to_string()))
+        Ok(if search.is_complete(best) {
+            TxLookup::Dropped { from, to }
+        } else {
+            TxLookup::Pending
+        })
+    }
+
+    /// Hash of the canonical block at `number`
+    ///
+    /// Without direct RPC access, only finalized blocks can be reached, by
+    /// walking parent hashes back from `finalized`.
+    async fn block_hash(
+        &self,
+        number: u64,
+        finalized: &subxt::blocks::Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    ) -> Result<Option<subxt::utils::H256>> {
+        if let Some(rpc) = &self.rpc {
+            return rpc
+                .chain_get_block_hash(Some(number.into()))
+                .await
+                .map_err(|e| Error::Connection(e.to_string()));
+        }
+
+        let latest = finalized.number() as u64;
+        if number > latest {
+            return Ok(None);
+        }
+        let mut hash = finalized.hash();
+        for _ in number..latest {
+            let block = self
+                .client
+                .blocks()
+                .at(hash)
+                .await
+                .map_err(|e| Error::Connection(e.to_string()))?;
+            hash = block.header().parent_hash;
+        }
+        Ok(Some(hash))
     }
 
     /// Get the balance of an address (native currency)
     pub async fn get_revive_balance(&self, address: &Address) -> Result<u128> {
+        observed!(
+            self,
+            BalanceQuery,
+            "get_balance",
+            self.query_balance(address)
+        )
+    }
+
+    async fn query_balance(&self, address: &Address) -> Result<u128> {
         let address_bytes = match address {
             Address::Substrate(_s) => address.as_str().as_bytes().to_vec(),
             Address::Evm(e) => hex::decode(e.trim_start_matches("0x"))
@@ -1,8 +1,168 @@
+use crate::abi::{AbiValue, Function};
 use crate::{Error, Result, ReviveAdapter};
 use apex_sdk_types::Address;
+use subxt::blocks::ExtrinsicEvents;
 use subxt::dynamic::{At, Value};
+use subxt::ext::scale_value::{Composite, ValueDef};
 use subxt::tx::Signer;
-use tracing::info;
+use tracing::{debug, info};
+
+/// Safety margin added to dry-run results, in percent
+///
+/// Covers state changes between the dry run and block inclusion.
+const ESTIMATE_MARGIN_PERCENT: u64 = 10;
+
+/// Storage deposit reported by a dry run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageDeposit {
+    /// Deposit charged to the caller
+    Charge(u128),
+    /// Deposit refunded to the caller
+    Refund(u128),
+}
+
+impl Default for StorageDeposit {
+    fn default() -> Self {
+        Self::Charge(0)
+    }
+}
+
+/// Resources a contract deployment or call needs, from a `ReviveApi` dry run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GasEstimate {
+    /// Computation weight required
+    pub ref_time: u64,
+    /// Proof size required
+    pub proof_size: u64,
+    /// Storage deposit charged or refunded
+    pub storage_deposit: StorageDeposit,
+}
+
+impl GasEstimate {
+    /// Parse the `ContractResult` returned by `ReviveApi_call` or
+    /// `ReviveApi_instantiate`
+    ///
+    /// Fails if the dry run reverted or returned a dispatch error.
+    pub fn from_dry_run<T>(result: &Value<T>) -> Result<Self> {
+        match result.at("result").map(|r| &r.value) {
+            Some(ValueDef::Variant(variant)) if variant.name == "Err" => {
+                return Err(Error::Contract(format!(
+                    "Dry run failed: {}",
+                    variant
+                        .values
+                        .values()
+                        .next()
+                        .map_or_else(String::new, |e| e.to_string())
+                )))
+            }
+            Some(ValueDef::Variant(variant)) => {
+                let flags = variant
+                    .values
+                    .values()
+                    .next()
+                    .and_then(|ok| {
+                        ok.at("result")
+                            .and_then(|r| r.at("flags"))
+                            .or(ok.at("flags"))
+                    })
+                    .and_then(|f| {
+                        // `ReturnFlags` is a bitflags newtype around `bits: u32`
+                        f.as_u128()
+                            .or_else(|| f.at("bits").and_then(|b| b.as_u128()))
+                            .or_else(|| f.at(0).and_then(|b| b.as_u128()))
+                    })
+                    .unwrap_or(0);
+                // Bit 0 is `REVERT`
+                if flags & 1 == 1 {
+                    return Err(Error::Contract("Dry run reverted".into()));
+                }
+            }
+            _ => {}
+        }
+
+        let weight = result
+            .at("gas_required")
+            .ok_or_else(|| Error::Contract("Dry run result has no gas_required".into()))?;
+        let field = |name: &str| -> Result<u64> {
+            let value = weight
+                .at(name)
+                .and_then(|v| v.as_u128())
+                .ok_or_else(|| Error::Contract(format!("Dry run result has no {}", name)))?;
+            u64::try_from(value).map_err(|_| {
+                Error::Contract(format!("Dry run {} {} does not fit in u64", name, value))
+            })
+        };
+
+        let storage_deposit = match result.at("storage_deposit").map(|d| &d.value) {
+            Some(ValueDef::Variant(variant)) => {
+                let amount = variant
+                    .values
+                    .values()
+                    .next()
+                    .and_then(|v| v.as_u128())
+                    .unwrap_or(0);
+                if variant.name == "Refund" {
+                    StorageDeposit::Refund(amount)
+                } else {
+                    StorageDeposit::Charge(amount)
+                }
+            }
+            _ => StorageDeposit::default(),
+        };
+
+        Ok(Self {
+            ref_time: field("ref_time")?,
+            proof_size: field("proof_size")?,
+            storage_deposit,
+        })
+    }
+
+    /// Add a safety margin in percent to weight and storage deposit
+    pub fn with_margin(self, percent: u64) -> Self {
+        let grow = |v: u64| v.saturating_add(v.saturating_mul(percent) / 100);
+        let storage_deposit = match self.storage_deposit {
+            StorageDeposit::Charge(amount) => StorageDeposit::Charge(
+                amount.saturating_add(amount.saturating_mul(percent as u128) / 100),
+            ),
+            refund => refund,
+        };
+
+        Self {
+            ref_time: grow(self.ref_time),
+            proof_size: grow(self.proof_size),
+            storage_deposit,
+        }
+    }
+
+    /// Storage deposit limit to submit with the transaction
+    pub fn storage_deposit_limit(&self) -> u128 {
+        match self.storage_deposit {
+            StorageDeposit::Charge(amount) => amount,
+            StorageDeposit::Refund(_) => 0,
+        }
+    }
+
+    /// Weight limit value for `gas_limit` extrinsic arguments
+    fn weight_value(&self) -> Value {
+        Value::named_composite([
+            ("ref_time", Value::u128(self.ref_time as u128)),
+            ("proof_size", Value::u128(self.proof_size as u128)),
+        ])
+    }
+}
+
+/// A log emitted by a contract, from a `Revive::ContractEmitted` event
+///
+/// Decode it with [`Event::decode_log`](crate::abi::Event::decode_log).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractEvent {
+    /// Contract that emitted the log
+    pub contract: Address,
+    /// Indexed topics; the first is the event signature hash unless anonymous
+    pub topics: Vec<[u8; 32]>,
+    /// ABI-encoded non-indexed parameters
+    pub data: Vec<u8>,
+}
 
 /// High-level API for Solidity contract lifecycle on pallet-revive
 pub struct ContractManager<'a, S: Signer<subxt::PolkadotConfig>> {
@@ -24,72 +184,65 @@ impl<'a, S: Signer<subxt::PolkadotConfig>> ContractManager<'a, S> {
         value: u128,
         gas_limit: Option<u64>,
     ) -> Result<Address> {
-        info!("Deploying contract to pallet-revive...");
+        self.deploy_with_events(code, constructor_data, salt, value, gas_limit)
+            .await
+            .map(|(address, _)| address)
+    }
 
-        let gas_limit_val = if let Some(g) = gas_limit {
-            Value::from(g)
-        } else {
-            Value::unnamed_variant("ReadOnly", vec![])
-        };
+    /// Deploy a Solidity contract and return the logs its constructor emitted
+    pub async fn deploy_with_events(
+        &self,
+        code: Vec<u8>,
+        constructor_data: Vec<u8>,
+        salt: [u8; 32],
+        value: u128,
+        gas_limit: Option<u64>,
+    ) -> Result<(Address, Vec<ContractEvent>)> {
+        info!(
+            operation = "contract_deploy",
+            wallet = %self.signer.account_id(),
+            code_len = code.len(),
+            "Deploying contract to pallet-revive"
+        );
+
+        let mut estimate = self
+            .estimate_deploy_gas(code.clone(), constructor_data.clone(), value)
+            .await?;
+        if let Some(ref_time) = gas_limit {
+            estimate.ref_time = ref_time;
+        }
 
         // Prepare extrinsic call
         let client = self.adapter.client();
         let tx = subxt::dynamic::tx(
             "Revive",
-            "instantiate",
+            "instantiate_with_code",
             vec![
                 Value::from(value),
-                gas_limit_val,
+                estimate.weight_value(),
+                Value::from(estimate.storage_deposit_limit()),
                 Value::from(code),
                 Value::from(constructor_data),
-                Value::from(salt.to_vec()),
+                Value::unnamed_variant("Some", vec![Value::from(salt.to_vec())]),
             ],
         );
 
-        let finalized = client
-            .tx()
-            .sign_and_submit_then_watch_default(&tx, &self.signer)
-            .await?
-            .wait_for_finalized_success()
-            .await?;
+        let finalized = observed!(self.adapter, TransactionSubmit, "contract_deploy", async {
+            client
+                .tx()
+                .sign_and_submit_then_watch_default(&tx, &self.signer)
+                .await?
+                .wait_for_finalized_success()
+                .await
+                .map_err(Error::from)
+        })?;
 
-        // Extract contract address from events
-        let address = finalized
-            .iter()
-            .filter_map(|ev| {
-                let ev = ev.ok()?;
-                if ev.pallet_name() == "Revive" && ev.variant_name() == "Instantiated" {
-                    let fields = ev.field_values().ok()?;
-                    if let Some(contract_field) = fields.at("contract") {
-                        let s = format!("{:?}", contract_field);
-                        if s.contains("0x") {
-                            let parts: Vec<&str> = s.split('\"').collect();
-                            for p in parts {
-                                if p.starts_with("0x") && p.len() == 42 {
-                                    return Some(Address::evm(p));
-                                }
-                            }
-                        }
-                        if s.contains('[') && s.contains(']') {
-                            let content = s.trim_matches(|c| c == '[' || c == ']' || c == ' ');
-                            let bytes: Vec<u8> = content
-                                .split(',')
-                                .filter_map(|b| b.trim().parse::<u8>().ok())
-                                .collect();
-                            if bytes.len() == 20 {
-                                return Some(Address::evm(format!("0x{}", hex::encode(bytes))));
-                            }
-                        }
-                    }
-                }
-                None
-            })
-            .next()
-            .ok_or_else(|| {
+        let address =
+            find_event(&finalized, "Instantiated", instantiated_address).ok_or_else(|| {
                 Error::Contract("Failed to extract contract address from events".into())
             })?;
 
-        Ok(address)
+        Ok((address, contract_events(&finalized)))
     }
 
     /// Call a method on a deployed contract (Transaction)
@@ -100,7 +253,25 @@ impl<'a, S: Signer<subxt::PolkadotConfig>> ContractManager<'a, S> {
         value: u128,
         gas_limit: Option<u64>,
     ) -> Result<Vec<u8>> {
-        info!("Calling contract at {}...", address);
+        self.call_with_events(address, data, value, gas_limit)
+            .await
+            .map(|(return_data, _)| return_data)
+    }
+
+    /// Call a method on a deployed contract and return the logs it emitted
+    pub async fn call_with_events(
+        &self,
+        address: &Address,
+        data: Vec<u8>,
+        value: u128,
+        gas_limit: Option<u64>,
+    ) -> Result<(Vec<u8>, Vec<ContractEvent>)> {
+        info!(
+            operation = "contract_call",
+            wallet = %self.signer.account_id(),
+            contract = %address,
+            "Calling contract"
+        );
 
         let dest_bytes = match address {
             Address::Evm(e) => hex::decode(e.trim_start_matches("0x"))
@@ -112,11 +283,10 @@ impl<'a, S: Signer<subxt::PolkadotConfig>> ContractManager<'a, S> {
             }
         };
 
-        let gas_limit_val = if let Some(g) = gas_limit {
-            Value::from(g)
-        } else {
-            Value::unnamed_variant("ReadOnly", vec![])
-        };
+        let mut estimate = self.estimate_call_gas(address, data.clone(), value).await?;
+        if let Some(ref_time) = gas_limit {
+            estimate.ref_time = ref_time;
+        }
 
         let client = self.adapter.client();
         let tx = subxt::dynamic::tx(
@@ -125,48 +295,35 @@ impl<'a, S: Signer<subxt::PolkadotConfig>> ContractManager<'a, S> {
             vec![
                 Value::from(dest_bytes),
                 Value::from(value),
-                gas_limit_val,
+                estimate.weight_value(),
+                Value::from(estimate.storage_deposit_limit()),
                 Value::from(data),
             ],
         );
 
-        let finalized = client
-            .tx()
-            .sign_and_submit_then_watch_default(&tx, &self.signer)
-            .await?
-            .wait_for_finalized_success()
-            .await?;
+        let finalized = observed!(self.adapter, TransactionSubmit, "contract_call", async {
+            client
+                .tx()
+                .sign_and_submit_then_watch_default(&tx, &self.signer)
+                .await?
+                .wait_for_finalized_success()
+                .await
+                .map_err(Error::from)
+        })?;
 
         // Extract return data from events if present
-        let return_data = finalized
-            .iter()
-            .filter_map(|ev| {
-                let ev = ev.ok()?;
-                if ev.pallet_name() == "Revive" && ev.variant_name() == "Called" {
-                    let fields = ev.field_values().ok()?;
-                    if let Some(data_field) = fields.at("return_data") {
-                        let s = format!("{:?}", data_field);
-                        if s.contains('[') && s.contains(']') {
-                            let content = s.trim_matches(|c| c == '[' || c == ']' || c == ' ');
-                            let bytes: Vec<u8> = content
-                                .split(',')
-                                .filter_map(|b| b.trim().parse::<u8>().ok())
-                                .collect();
-                            return Some(bytes);
-                        }
-                    }
-                }
-                None
-            })
-            .next()
-            .unwrap_or_default();
+        let return_data = find_event(&finalized, "Called", event_return_data).unwrap_or_default();
 
-        Ok(return_data)
+        Ok((return_data, contract_events(&finalized)))
     }
 
     /// Query contract state (Dry-run/Static call)
     pub async fn read(&self, address: &Address, data: Vec<u8>, value: u128) -> Result<Vec<u8>> {
-        info!("Reading contract state at {}...", address);
+        info!(
+            operation = "contract_read",
+            contract = %address,
+            "Reading contract state"
+        );
 
         let dest_bytes = match address {
             Address::Evm(e) => hex::decode(e.trim_start_matches("0x"))
@@ -190,60 +347,240 @@ impl<'a, S: Signer<subxt::PolkadotConfig>> ContractManager<'a, S> {
             ],
         );
 
-        let finalized = client
-            .tx()
-            .sign_and_submit_then_watch_default(&tx, &self.signer)
-            .await?
-            .wait_for_finalized_success()
-            .await?;
+        let finalized = observed!(self.adapter, ContractCall, "contract_read", async {
+            client
+                .tx()
+                .sign_and_submit_then_watch_default(&tx, &self.signer)
+                .await?
+                .wait_for_finalized_success()
+                .await
+                .map_err(Error::from)
+        })?;
 
-        let return_data = finalized
-            .iter()
-            .filter_map(|ev| {
-                let ev = ev.ok()?;
-                if ev.pallet_name() == "Revive" && ev.variant_name() == "Read" {
-                    let fields = ev.field_values().ok()?;
-                    if let Some(data_field) = fields.at("return_data") {
-                        let s = format!("{:?}", data_field);
-                        if s.contains('[') && s.contains(']') {
-                            let content = s.trim_matches(|c| c == '[' || c == ']' || c == ' ');
-                            let bytes: Vec<u8> = content
-                                .split(',')
-                                .filter_map(|b| b.trim().parse::<u8>().ok())
-                                .collect();
-                            return Some(bytes);
-                        }
-                    }
-                }
-                None
-            })
-            .next()
-            .unwrap_or_default();
+        let return_data = find_event(&finalized, "Read", event_return_data).unwrap_or_default();
 
         Ok(return_data)
     }
 
-    /// Estimate gas for a deployment
+    /// Call an ABI function on a deployed contract and decode its return data
+    ///
+    /// Returns no values if the call produced no return data.
+    pub async fn call_function(
+        &self,
+        address: &Address,
+        function: &Function,
+        args: &[AbiValue],
+        value: u128,
+        gas_limit: Option<u64>,
+    ) -> Result<Vec<AbiValue>> {
+        let data = function.encode_input(args)?;
+        let return_data = self.call(address, data, value, gas_limit).await?;
+        if return_data.is_empty() {
+            return Ok(Vec::new());
+        }
+        function.decode_output(&return_data)
+    }
+
+    /// Read contract state through an ABI function and decode the result
+    pub async fn read_function(
+        &self,
+        address: &Address,
+        function: &Function,
+        args: &[AbiValue],
+    ) -> Result<Vec<AbiValue>> {
+        let data = function.encode_input(args)?;
+        let return_data = self.read(address, data, 0).await?;
+        function.decode_output(&return_data)
+    }
+
+    /// Estimate gas and storage deposit for a deployment
+    ///
+    /// Dry-runs `ReviveApi_instantiate` with the signer as origin and adds a
+    /// safety margin to the result.
     pub async fn estimate_deploy_gas(
         &self,
-        _code: Vec<u8>,
-        _constructor_data: Vec<u8>,
-        _value: u128,
-    ) -> Result<u64> {
-        Ok(500_000)
+        code: Vec<u8>,
+        constructor_data: Vec<u8>,
+        value: u128,
+    ) -> Result<GasEstimate> {
+        let payload = subxt::dynamic::runtime_api_call(
+            "ReviveApi",
+            "instantiate",
+            vec![
+                self.origin(),
+                Value::from(value),
+                Value::unnamed_variant("None", vec![]),
+                Value::unnamed_variant("None", vec![]),
+                Value::unnamed_variant("Upload", vec![Value::from(code)]),
+                Value::from(constructor_data),
+                Value::unnamed_variant("None", vec![]),
+            ],
+        );
+
+        self.dry_run(payload, "contract_deploy").await
     }
 
-    /// Estimate gas for a call
+    /// Estimate gas and storage deposit for a call
+    ///
+    /// Dry-runs `ReviveApi_call` with the signer as origin and adds a safety
+    /// margin to the result.
     pub async fn estimate_call_gas(
         &self,
-        _address: &Address,
-        _data: Vec<u8>,
-        _value: u128,
-    ) -> Result<u64> {
-        Ok(200_000)
+        address: &Address,
+        data: Vec<u8>,
+        value: u128,
+    ) -> Result<GasEstimate> {
+        let dest_bytes = match address {
+            Address::Evm(e) => hex::decode(e.trim_start_matches("0x"))
+                .map_err(|_| Error::Contract("Invalid EVM address".into()))?,
+            Address::Substrate(_) => {
+                return Err(Error::Contract(
+                    "Revive calls require EVM-style addresses".into(),
+                ))
+            }
+        };
+
+        let payload = subxt::dynamic::runtime_api_call(
+            "ReviveApi",
+            "call",
+            vec![
+                self.origin(),
+                Value::from(dest_bytes),
+                Value::from(value),
+                Value::unnamed_variant("None", vec![]),
+                Value::unnamed_variant("None", vec![]),
+                Value::from(data),
+            ],
+        );
+
+        self.dry_run(payload, "contract_call").await
+    }
+
+    fn origin(&self) -> Value {
+        Value::from_bytes(self.signer.account_id().0)
+    }
+
+    async fn dry_run(
+        &self,
+        payload: subxt::runtime_api::DynamicPayload,
+        operation: &str,
+    ) -> Result<GasEstimate> {
+        let result = self
+            .adapter
+            .client()
+            .runtime_api()
+            .at_latest()
+            .await?
+            .call(payload)
+            .await?
+            .to_value()
+            .map_err(|e| Error::Contract(format!("Failed to decode dry run result: {}", e)))?;
+
+        let estimate = GasEstimate::from_dry_run(&result)?.with_margin(ESTIMATE_MARGIN_PERCENT);
+        debug!(
+            operation = operation,
+            ref_time = estimate.ref_time,
+            proof_size = estimate.proof_size,
+            storage_deposit = estimate.storage_deposit_limit(),
+            "Estimated contract gas"
+        );
+        Ok(estimate)
     }
 }
 
+/// Decode the first `Revive` event named `variant` with `extract`
+fn find_event<R>(
+    events: &ExtrinsicEvents<subxt::PolkadotConfig>,
+    variant: &str,
+    extract: impl Fn(&Composite<u32>) -> Option<R>,
+) -> Option<R> {
+    events.iter().flatten().find_map(|ev| {
+        if ev.pallet_name() != "Revive" || ev.variant_name() != variant {
+            return None;
+        }
+        extract(&ev.field_values().ok()?)
+    })
+}
+
+/// All `Revive::ContractEmitted` logs of an extrinsic, in emission order
+fn contract_events(events: &ExtrinsicEvents<subxt::PolkadotConfig>) -> Vec<ContractEvent> {
+    events
+        .iter()
+        .flatten()
+        .filter(|ev| ev.pallet_name() == "Revive" && ev.variant_name() == "ContractEmitted")
+        .filter_map(|ev| emitted_event(&ev.field_values().ok()?))
+        .collect()
+}
+
+/// Log carried by a `ContractEmitted { contract, data, topics }` event
+fn emitted_event<T>(fields: &Composite<T>) -> Option<ContractEvent> {
+    let Composite::Named(named) = fields else {
+        return None;
+    };
+    let field = |name: &str| named.iter().find(|(n, _)| n == name).map(|(_, v)| v);
+
+    let contract: [u8; 20] = value_bytes(field("contract")?)?.try_into().ok()?;
+    let data = value_bytes(field("data")?)?;
+    let ValueDef::Composite(topics) = &field("topics")?.value else {
+        return None;
+    };
+    let topics = topics
+        .values()
+        .map(|topic| value_bytes(topic)?.try_into().ok())
+        .collect::<Option<Vec<[u8; 32]>>>()?;
+
+    Some(ContractEvent {
+        contract: Address::evm(format!("0x{}", hex::encode(contract))),
+        topics,
+        data,
+    })
+}
+
+/// Contract address of an `Instantiated { deployer, contract }` event
+fn instantiated_address<T>(fields: &Composite<T>) -> Option<Address> {
+    let contract = match fields {
+        Composite::Named(named) => named
+            .iter()
+            .find(|(name, _)| name == "contract")
+            .map(|(_, v)| v),
+        // Positional fields in older metadata: `(deployer, contract)`
+        Composite::Unnamed(values) => values.get(1),
+    }?;
+
+    let bytes: [u8; 20] = value_bytes(contract)?.try_into().ok()?;
+    Some(Address::evm(format!("0x{}", hex::encode(bytes))))
+}
+
+/// Return bytes carried by a `Called`/`Read` event
+fn event_return_data<T>(fields: &Composite<T>) -> Option<Vec<u8>> {
+    let Composite::Named(named) = fields else {
+        return None;
+    };
+    named
+        .iter()
+        .find(|(name, _)| name == "return_data" || name == "data")
+        .and_then(|(_, v)| value_bytes(v))
+}
+
+/// Flatten a byte array value, unwrapping newtypes such as `H160`
+fn value_bytes<T>(value: &Value<T>) -> Option<Vec<u8>> {
+    let ValueDef::Composite(composite) = &value.value else {
+        return None;
+    };
+
+    let values: Vec<&Value<T>> = composite.values().collect();
+    if let [inner] = values.as_slice() {
+        if matches!(inner.value, ValueDef::Composite(_)) {
+            return value_bytes(inner);
+        }
+    }
+
+    values
+        .iter()
+        .map(|v| v.as_u128().and_then(|b| u8::try_from(b).ok()))
+        .collect()
+}
+
 /// Represents a deployed contract on pallet-revive
 pub struct Contract<T> {
     address: Address,
@@ -262,3 +599,293 @@ impl<T> Contract<T> {
         &self.address
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: [u8; 20] = [
+        0x5f, 0xbd, 0xb2, 0x31, 0x56, 0x78, 0xaf, 0xec, 0xb3, 0x67, 0xf0, 0x32, 0xd9, 0x3f, 0x64,
+        0x2f, 0x64, 0x18, 0x0a, 0xa3,
+    ];
+
+    fn bytes(data: &[u8]) -> Value {
+        Value::unnamed_composite(data.iter().map(|b| Value::u128(*b as u128)))
+    }
+
+    /// `H160` as decoded from current metadata: a newtype around `[u8; 20]`
+    fn h160(data: &[u8]) -> Value {
+        Value::unnamed_composite([bytes(data)])
+    }
+
+    fn named(fields: Vec<(&str, Value)>) -> Composite<()> {
+        Composite::Named(
+            fields
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_instantiated_address_from_named_h160() {
+        // Revive::Instantiated { deployer: H160, contract: H160 }
+        let fields = named(vec![
+            ("deployer", h160(&[0xaa; 20])),
+            ("contract", h160(&CONTRACT)),
+        ]);
+
+        assert_eq!(
+            instantiated_address(&fields),
+            Some(Address::evm("0x5fbdb2315678afecb367f032d93f642f64180aa3"))
+        );
+    }
+
+    #[test]
+    fn test_instantiated_address_from_raw_array() {
+        let fields = named(vec![
+            ("deployer", bytes(&[0xaa; 20])),
+            ("contract", bytes(&CONTRACT)),
+        ]);
+
+        assert_eq!(
+            instantiated_address(&fields),
+            Some(Address::evm("0x5fbdb2315678afecb367f032d93f642f64180aa3"))
+        );
+    }
+
+    #[test]
+    fn test_instantiated_address_from_positional_fields() {
+        let fields = Composite::Unnamed(vec![h160(&[0xaa; 20]), h160(&CONTRACT)]);
+
+        assert_eq!(
+            instantiated_address(&fields),
+            Some(Address::evm("0x5fbdb2315678afecb367f032d93f642f64180aa3"))
+        );
+    }
+
+    #[test]
+    fn test_instantiated_address_rejects_wrong_length() {
+        // A 32-byte account id is not a contract address
+        let fields = named(vec![("contract", h160(&[1; 32]))]);
+        assert_eq!(instantiated_address(&fields), None);
+
+        let fields = named(vec![("deployer", h160(&CONTRACT))]);
+        assert_eq!(instantiated_address(&fields), None);
+    }
+
+    #[test]
+    fn test_event_return_data() {
+        let fields = named(vec![
+            ("contract", h160(&CONTRACT)),
+            ("return_data", bytes(&[0, 0, 0, 42])),
+        ]);
+        assert_eq!(event_return_data(&fields), Some(vec![0, 0, 0, 42]));
+
+        let fields = named(vec![("data", bytes(&[]))]);
+        assert_eq!(event_return_data(&fields), Some(vec![]));
+
+        let fields = named(vec![("contract", h160(&CONTRACT))]);
+        assert_eq!(event_return_data(&fields), None);
+    }
+
+    #[test]
+    fn test_emitted_event() {
+        // Revive::ContractEmitted { contract: H160, data: Vec<u8>, topics: Vec<H256> }
+        let fields = named(vec![
+            ("contract", h160(&CONTRACT)),
+            ("data", bytes(&[0, 1, 2])),
+            (
+                "topics",
+                Value::unnamed_composite([h160(&[0x11; 32]), h160(&[0x22; 32])]),
+            ),
+        ]);
+
+        assert_eq!(
+            emitted_event(&fields),
+            Some(ContractEvent {
+                contract: Address::evm("0x5fbdb2315678afecb367f032d93f642f64180aa3"),
+                topics: vec![[0x11; 32], [0x22; 32]],
+                data: vec![0, 1, 2],
+            })
+        );
+
+        let fields = named(vec![
+            ("contract", h160(&CONTRACT)),
+            ("data", bytes(&[])),
+            ("topics", Value::unnamed_composite([h160(&[0x11; 20])])),
+        ]);
+        assert_eq!(emitted_event(&fields), None);
+    }
+
+    fn weight(ref_time: u128, proof_size: u128) -> Value {
+        Value::named_composite([
+            ("ref_time", Value::u128(ref_time)),
+            ("proof_size", Value::u128(proof_size)),
+        ])
+    }
+
+    /// `ContractResult` with the given `result` and a charged deposit
+    fn dry_run(result: Value) -> Value {
+        Value::named_composite([
+            ("gas_consumed", weight(900, 90)),
+            ("gas_required", weight(1_000, 100)),
+            (
+                "storage_deposit",
+                Value::unnamed_variant("Charge", [Value::u128(5_000)]),
+            ),
+            ("result", result),
+        ])
+    }
+
+    /// `Ok(ExecReturnValue { flags, data })`
+    fn returned(flags: Value) -> Value {
+        Value::unnamed_variant(
+            "Ok",
+            [Value::named_composite([
+                ("flags", flags),
+                ("data", bytes(&[])),
+            ])],
+        )
+    }
+
+    #[test]
+    fn test_gas_estimate_from_ok_dry_run() {
+        let estimate = GasEstimate::from_dry_run(&dry_run(returned(Value::u128(0)))).unwrap();
+        assert_eq!(
+            estimate,
+            GasEstimate {
+                ref_time: 1_000,
+                proof_size: 100,
+                storage_deposit: StorageDeposit::Charge(5_000),
+            }
+        );
+        assert_eq!(estimate.storage_deposit_limit(), 5_000);
+    }
+
+    #[test]
+    fn test_gas_estimate_from_instantiate_dry_run() {
+        // `Ok(InstantiateReturnValue { result: ExecReturnValue, addr })`
+        let result = Value::unnamed_variant(
+            "Ok",
+            [Value::named_composite([
+                (
+                    "result",
+                    Value::named_composite([("flags", Value::u128(0)), ("data", bytes(&[]))]),
+                ),
+                ("addr", h160(&CONTRACT)),
+            ])],
+        );
+        assert!(GasEstimate::from_dry_run(&dry_run(result)).is_ok());
+    }
+
+    #[test]
+    fn test_gas_estimate_rejects_dispatch_error() {
+        let result = Value::unnamed_variant(
+            "Err",
+            [Value::unnamed_variant(
+                "Module",
+                [Value::named_composite([
+                    ("index", Value::u128(60)),
+                    ("error", bytes(&[1, 0, 0, 0])),
+                ])],
+            )],
+        );
+
+        let err = GasEstimate::from_dry_run(&dry_run(result)).unwrap_err();
+        assert!(err.to_string().contains("Dry run failed"));
+        assert!(err.to_string().contains("Module"));
+    }
+
+    #[test]
+    fn test_gas_estimate_rejects_revert_in_every_flags_shape() {
+        let shapes = [
+            Value::u128(1),
+            Value::named_composite([("bits", Value::u128(1))]),
+            Value::unnamed_composite([Value::u128(1)]),
+        ];
+
+        for flags in shapes {
+            let err = GasEstimate::from_dry_run(&dry_run(returned(flags.clone()))).unwrap_err();
+            assert!(err.to_string().contains("reverted"), "{:?}", flags);
+        }
+    }
+
+    #[test]
+    fn test_gas_estimate_refund() {
+        let result = Value::named_composite([
+            ("gas_required", weight(1_000, 100)),
+            (
+                "storage_deposit",
+                Value::unnamed_variant("Refund", [Value::u128(700)]),
+            ),
+            ("result", returned(Value::u128(0))),
+        ]);
+
+        let estimate = GasEstimate::from_dry_run(&result).unwrap();
+        assert_eq!(estimate.storage_deposit, StorageDeposit::Refund(700));
+        assert_eq!(estimate.storage_deposit_limit(), 0);
+        // Refunds are not grown by the margin
+        assert_eq!(
+            estimate.with_margin(50).storage_deposit,
+            StorageDeposit::Refund(700)
+        );
+    }
+
+    #[test]
+    fn test_gas_estimate_requires_gas_required() {
+        let result = Value::named_composite([("result", returned(Value::u128(0)))]);
+        let err = GasEstimate::from_dry_run(&result).unwrap_err();
+        assert!(err.to_string().contains("gas_required"));
+
+        let result = Value::named_composite([
+            (
+                "gas_required",
+                Value::named_composite([("ref_time", Value::u128(1))]),
+            ),
+            ("result", returned(Value::u128(0))),
+        ]);
+        let err = GasEstimate::from_dry_run(&result).unwrap_err();
+        assert!(err.to_string().contains("proof_size"));
+    }
+
+    #[test]
+    fn test_gas_estimate_rejects_oversized_weight() {
+        let result = Value::named_composite([
+            ("gas_required", weight(u64::MAX as u128 + 1, 100)),
+            ("result", returned(Value::u128(0))),
+        ]);
+        let err = GasEstimate::from_dry_run(&result).unwrap_err();
+        assert!(err.to_string().contains("ref_time"));
+    }
+
+    #[test]
+    fn test_gas_estimate_with_margin() {
+        let estimate = GasEstimate {
+            ref_time: 1_000,
+            proof_size: 200,
+            storage_deposit: StorageDeposit::Charge(5_000),
+        };
+
+        let grown = estimate.with_margin(ESTIMATE_MARGIN_PERCENT);
+        assert_eq!(grown.ref_time, 1_100);
+        assert_eq!(grown.proof_size, 220);
+        assert_eq!(grown.storage_deposit_limit(), 5_500);
+
+        let saturated = GasEstimate {
+            ref_time: u64::MAX,
+            ..estimate
+        }
+        .with_margin(10);
+        assert_eq!(saturated.ref_time, u64::MAX);
+    }
+
+    #[test]
+    fn test_value_bytes_rejects_non_bytes() {
+        assert_eq!(value_bytes(&Value::u128(7)), None);
+        assert_eq!(
+            value_bytes(&Value::unnamed_composite([Value::u128(300)])),
+            None
+        );
+    }
+}
@@ -2,14 +2,38 @@
 //!
 //! Native Rust adapter for `pallet-revive` (PolkaVM/Solidity) on Polkadot System Chains.
 
-use apex_sdk_core::SdkError;
+use apex_sdk_core::{ErrorCode, HasErrorCode, SdkError};
 use thiserror::Error;
 
+/// Run an adapter call through the attached observability instrumentation, if any
+macro_rules! observed {
+    ($adapter:expr, $operation:ident, $name:literal, $call:expr) => {{
+        #[cfg(feature = "observability")]
+        let result = match &$adapter.instrumentation {
+            Some(instrumentation) => {
+                instrumentation
+                    .observe(
+                        apex_sdk_metrics::profiling::OperationType::$operation,
+                        $name,
+                        $call,
+                    )
+                    .await
+            }
+            None => $call.await,
+        };
+        #[cfg(not(feature = "observability"))]
+        let result = $call.await;
+        result
+    }};
+}
+
+pub mod abi;
 pub mod adapter;
 pub mod contract;
 
-pub use adapter::ReviveAdapter;
-pub use contract::{Contract, ContractManager};
+pub use abi::{Abi, AbiType, AbiValue};
+pub use adapter::{ReviveAdapter, DEFAULT_SEARCH_DEPTH};
+pub use contract::{Contract, ContractEvent, ContractManager, GasEstimate, StorageDeposit};
 
 /// Revive adapter error
 #[derive(Error, Debug)]
@@ -26,11 +50,34 @@ pub enum Error {
     #[error("Storage error: {0}")]
     Storage(String),
 
+    #[error("ABI error: {0}")]
+    Abi(String),
+
     #[error("Other error: {0}")]
     Other(String),
 
     #[error("Subxt error: {0}")]
-    Subxt(#[from] subxt::Error),
+    Subxt(Box<subxt::Error>),
+}
+
+impl From<subxt::Error> for Error {
+    fn from(err: subxt::Error) -> Self {
+        Error::Subxt(Box::new(err))
+    }
+}
+
+impl HasErrorCode for Error {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::new(match self {
+            Error::Connection(_) => 1300,
+            Error::Transaction(_) => 1301,
+            Error::Contract(_) => 1302,
+            Error::Storage(_) => 1303,
+            Error::Abi(_) => 1304,
+            Error::Subxt(_) => 1305,
+            Error::Other(_) => 1399,
+        })
+    }
 }
 
 impl From<Error> for SdkError {
@@ -40,6 +87,7 @@ impl From<Error> for SdkError {
             Error::Transaction(msg) => SdkError::TransactionError(msg),
             Error::Contract(msg) => SdkError::TransactionError(msg),
             Error::Storage(msg) => SdkError::ProviderError(msg),
+            Error::Abi(msg) => SdkError::TransactionError(msg),
             Error::Other(msg) => SdkError::ProviderError(msg),
             Error::Subxt(e) => SdkError::ProviderError(e.to_string()),
         }
@@ -33,6 +33,8 @@ fn benchmark_blockinfo_creation(c: &mut Criterion) {
                 extrinsic_count: 5,
                 event_count: Some(15),
                 is_finalized: true,
+                extrinsics: Vec::new(),
+                author: None,
             })
         })
     });
@@ -61,6 +63,8 @@ fn benchmark_blockinfo_creation(c: &mut Criterion) {
                 extrinsic_count: 10,
                 event_count: Some(30),
                 is_finalized: true,
+                extrinsics: Vec::new(),
+                author: None,
             })
         })
     });
@@ -83,6 +87,8 @@ fn benchmark_blockinfo_creation(c: &mut Criterion) {
             extrinsic_count: 5,
             event_count: Some(15),
             is_finalized: true,
+            extrinsics: Vec::new(),
+            author: None,
         };
 
         b.iter(|| {
@@ -113,6 +119,8 @@ fn benchmark_block_caching(c: &mut Criterion) {
         extrinsic_count: 5,
         event_count: None,
         is_finalized: true,
+        extrinsics: Vec::new(),
+        author: None,
     };
 
     let block_recent = BlockInfo {
@@ -127,6 +135,8 @@ fn benchmark_block_caching(c: &mut Criterion) {
         extrinsic_count: 3,
         event_count: None,
         is_finalized: false,
+        extrinsics: Vec::new(),
+        author: None,
     };
 
     // Benchmark cache insertion for finalized blocks
@@ -194,11 +204,12 @@ fn benchmark_block_caching(c: &mut Criterion) {
         );
 
         cache.put_block(block_finalized.clone());
+        let block_hash = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"
+            .parse()
+            .unwrap();
 
         b.iter(|| {
-            black_box(cache.get_block_by_hash(
-                "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef",
-            ));
+            black_box(cache.get_block_by_hash(&block_hash));
         })
     });
 
@@ -235,6 +246,8 @@ fn benchmark_block_cache_scale(c: &mut Criterion) {
                     extrinsic_count: 0,
                     event_count: None,
                     is_finalized: i < size / 2,
+                    extrinsics: Vec::new(),
+                    author: None,
                 };
                 cache.put_block(block);
             }
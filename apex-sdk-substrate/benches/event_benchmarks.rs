@@ -0,0 +1,263 @@
+use apex_sdk_substrate::{EventFilter, EventScanner};
+use criterion::{criterion_group, criterion_main, Bencher, BenchmarkId, Criterion, Throughput};
+use parity_scale_codec::{Compact, Encode};
+use scale_info::{meta_type, TypeInfo};
+use std::hint::black_box;
+use subxt::events::{Events, Phase};
+use subxt::ext::frame_metadata::{
+    v15::{
+        CustomMetadata, ExtrinsicMetadata, OuterEnums, PalletEventMetadata, PalletMetadata,
+        RuntimeMetadataV15,
+    },
+    RuntimeMetadataPrefixed,
+};
+use subxt::{Metadata, PolkadotConfig};
+
+// ============================================================================
+// Synthetic Runtime
+// ============================================================================
+
+#[allow(unused)]
+#[derive(Encode, TypeInfo)]
+enum SystemEvent {
+    ExtrinsicSuccess {
+        ref_time: u64,
+        proof_size: u64,
+    },
+    ExtrinsicFailed {
+        error: u8,
+        ref_time: u64,
+        proof_size: u64,
+    },
+}
+
+#[allow(unused)]
+#[derive(Encode, TypeInfo)]
+enum BalancesEvent {
+    Withdraw {
+        who: [u8; 32],
+        amount: u128,
+    },
+    Deposit {
+        who: [u8; 32],
+        amount: u128,
+    },
+    Transfer {
+        from: [u8; 32],
+        to: [u8; 32],
+        amount: u128,
+    },
+}
+
+#[allow(unused)]
+#[derive(Encode, TypeInfo)]
+enum RuntimeEvent {
+    #[codec(index = 0)]
+    System(SystemEvent),
+    #[codec(index = 10)]
+    Balances(BalancesEvent),
+}
+
+#[derive(Encode)]
+struct EventRecord {
+    phase: Phase,
+    event: RuntimeEvent,
+    topics: Vec<[u8; 32]>,
+}
+
+fn metadata() -> Metadata {
+    #[allow(unused)]
+    #[derive(TypeInfo)]
+    enum RuntimeCall {
+        System(Call),
+    }
+    #[allow(unused)]
+    #[derive(TypeInfo)]
+    enum Call {
+        Remark,
+    }
+
+    let pallet = |name, index, ty| PalletMetadata {
+        name,
+        storage: None,
+        calls: None,
+        event: Some(PalletEventMetadata { ty }),
+        constants: vec![],
+        error: None,
+        index,
+        docs: vec![],
+    };
+    let metadata = RuntimeMetadataV15::new(
+        vec![
+            pallet("System", 0, meta_type::<SystemEvent>()),
+            pallet("Balances", 10, meta_type::<BalancesEvent>()),
+        ],
+        ExtrinsicMetadata {
+            version: 4,
+            signed_extensions: vec![],
+            address_ty: meta_type::<()>(),
+            call_ty: meta_type::<RuntimeCall>(),
+            signature_ty: meta_type::<()>(),
+            extra_ty: meta_type::<()>(),
+        },
+        meta_type::<()>(),
+        vec![],
+        OuterEnums {
+            call_enum_ty: meta_type::<RuntimeCall>(),
+            event_enum_ty: meta_type::<RuntimeEvent>(),
+            error_enum_ty: meta_type::<()>(),
+        },
+        CustomMetadata {
+            map: Default::default(),
+        },
+    );
+    Metadata::try_from(RuntimeMetadataPrefixed::from(metadata)).unwrap()
+}
+
+/// A block of balance transfers: withdraw, transfer, deposit and success per extrinsic
+fn block_events(metadata: &Metadata, extrinsics: u32) -> (Events<PolkadotConfig>, u64) {
+    let mut records = Vec::new();
+    for i in 0..extrinsics {
+        let phase = Phase::ApplyExtrinsic(i);
+        let account = [i as u8; 32];
+        records.extend([
+            (
+                phase,
+                RuntimeEvent::Balances(BalancesEvent::Withdraw {
+                    who: account,
+                    amount: 1_000,
+                }),
+            ),
+            (
+                phase,
+                RuntimeEvent::Balances(BalancesEvent::Transfer {
+                    from: account,
+                    to: [0xff; 32],
+                    amount: 10u128.pow(12),
+                }),
+            ),
+            (
+                phase,
+                RuntimeEvent::Balances(BalancesEvent::Deposit {
+                    who: [0xee; 32],
+                    amount: 800,
+                }),
+            ),
+            (
+                phase,
+                RuntimeEvent::System(SystemEvent::ExtrinsicSuccess {
+                    ref_time: 150_000_000,
+                    proof_size: 3_500,
+                }),
+            ),
+        ]);
+    }
+
+    let count = records.len() as u64;
+    let mut bytes = Compact(count as u32).encode();
+    for (phase, event) in records {
+        EventRecord {
+            phase,
+            event,
+            topics: vec![],
+        }
+        .encode_to(&mut bytes);
+    }
+    (Events::decode_from(bytes, metadata.clone()), count)
+}
+
+// ============================================================================
+// Event Processing Benchmarks
+// ============================================================================
+
+fn benchmark_event_processing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("event_processing");
+    let metadata = metadata();
+
+    for extrinsics in [50u32, 500] {
+        let (events, count) = block_events(&metadata, extrinsics);
+        group.throughput(Throughput::Elements(count));
+
+        // Baseline: decode every event, then match by name
+        group.bench_with_input(
+            BenchmarkId::new("full_decode", count),
+            &events,
+            |b: &mut Bencher, events| {
+                b.iter(|| {
+                    let mut outcomes = 0;
+                    for event in events.iter().flatten() {
+                        let fields = event.field_values().unwrap();
+                        if event.pallet_name() == "System"
+                            && event.variant_name() == "ExtrinsicSuccess"
+                        {
+                            outcomes += 1;
+                        }
+                        black_box(fields);
+                    }
+                    black_box(outcomes)
+                })
+            },
+        );
+
+        // Fast path: match on indices, decode only the matches
+        group.bench_with_input(
+            BenchmarkId::new("filtered", count),
+            &events,
+            |b: &mut Bencher, events| {
+                let mut scanner = EventScanner::new(EventFilter::new(
+                    &metadata,
+                    &[
+                        ("System", "ExtrinsicSuccess"),
+                        ("System", "ExtrinsicFailed"),
+                    ],
+                ));
+                b.iter(|| {
+                    let matched = scanner.scan(events).unwrap();
+                    for event in matched {
+                        black_box(event.field_values().unwrap());
+                    }
+                    black_box(matched.len())
+                })
+            },
+        );
+
+        // Fast path without decoding matches, as used for outcome lookups
+        group.bench_with_input(
+            BenchmarkId::new("filtered_no_decode", count),
+            &events,
+            |b: &mut Bencher, events| {
+                let mut scanner = EventScanner::new(EventFilter::new(
+                    &metadata,
+                    &[
+                        ("System", "ExtrinsicSuccess"),
+                        ("System", "ExtrinsicFailed"),
+                    ],
+                ));
+                b.iter(|| black_box(scanner.scan(events).unwrap().len()))
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn benchmark_filter_lookup(c: &mut Criterion) {
+    let metadata = metadata();
+    let filter = EventFilter::new(&metadata, &[("Balances", "Transfer")]);
+
+    c.bench_function("event_filter_matches", |b: &mut Bencher| {
+        b.iter(|| {
+            let mut hits = 0;
+            for pallet in 0..=255u8 {
+                if filter.matches(black_box(pallet), black_box(2)) {
+                    hits += 1;
+                }
+            }
+            black_box(hits)
+        })
+    });
+}
+
+criterion_group!(benches, benchmark_event_processing, benchmark_filter_lookup);
+
+criterion_main!(benches);
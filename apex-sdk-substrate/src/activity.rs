@@ -0,0 +1,472 @@
+//! Detection of extrinsics a wallet signed outside this SDK
+//!
+//! A [`WalletActivityWatcher`] follows finalized blocks and checks every
+//! extrinsic signed by a watched address against a [`SigningLog`]. An
+//! extrinsic without a matching receipt was signed with the wallet's key
+//! somewhere else, possibly after the key leaked. The watcher logs it,
+//! notifies subscribers, raises a critical alert on an attached
+//! `AlertEngine` (with the `observability` feature) and can freeze the
+//! wallet's [`ScopedSigner`]s through their [`FreezeSwitch`].
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::{SigningLog, SigningScope, Wallet, WalletActivityWatcher};
+//! use std::sync::Arc;
+//!
+//! # async fn example(adapter: &apex_sdk_substrate::SubstrateAdapter) -> apex_sdk_substrate::Result<()> {
+//! let log = Arc::new(SigningLog::open("signatures.jsonl")?.with_metadata(adapter.client().metadata()));
+//! let wallet = Wallet::new_random().with_signing_log(log.clone());
+//! let signer = adapter.scoped_signer(wallet.clone(), SigningScope::balances_only());
+//!
+//! let watcher = Arc::new(WalletActivityWatcher::new(log));
+//! watcher.watch_with_freeze(&wallet.address(), signer.freeze_switch())?;
+//! let mut unexpected = watcher.subscribe();
+//! watcher.clone().spawn(adapter.event_bus());
+//!
+//! while let Some(activity) = unexpected.recv().await {
+//!     eprintln!("{}", activity.description());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A receipt matches an extrinsic from its signer whose call has the same
+//! summary, and each receipt matches one extrinsic. Receipts without a call
+//! summary, written by a log without metadata or for payloads over 256 bytes,
+//! match any extrinsic from their signer, so give the log metadata. Receipts
+//! older than the [receipt window](WalletActivityWatcher::with_receipt_window)
+//! are not considered.
+//!
+//! [`ScopedSigner`]: crate::ScopedSigner
+
+use crate::event_bus::{DeliveryPolicy, EventBus, FinalizedBlock};
+use crate::signer::FreezeSwitch;
+use crate::signing_log::{summarize_call, SigningLog, SigningReceipt};
+use crate::{unix_now, Error, Result};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use subxt::Metadata;
+use tokio::sync::mpsc;
+use tracing::{error, warn};
+
+/// Default age after which a receipt no longer matches extrinsics (24 hours)
+pub const DEFAULT_RECEIPT_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Rule name of the alerts raised for unexpected activity
+pub const UNEXPECTED_ACTIVITY_ALERT: &str = "unexpected_wallet_activity";
+
+/// Extrinsic signed by a watched wallet without a matching signing receipt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnexpectedActivity {
+    /// Wallet address, as it was passed to the watcher
+    pub address: String,
+    /// Number of the finalized block holding the extrinsic
+    pub block_number: u64,
+    /// Hash of that block
+    pub block_hash: String,
+    /// Index of the extrinsic in the block
+    pub extrinsic_index: u32,
+    /// BLAKE2-256 hash of the extrinsic
+    pub extrinsic_hash: String,
+    /// Decoded call, in the format of [`SigningReceipt::call`]
+    ///
+    /// [`SigningReceipt::call`]: crate::SigningReceipt::call
+    pub call: Option<String>,
+    /// Whether the wallet's freeze switch was thrown
+    pub frozen: bool,
+}
+
+impl UnexpectedActivity {
+    /// One-line description, for logs and alerts
+    pub fn description(&self) -> String {
+        let mut description = format!(
+            "{} signed {} (extrinsic {}-{}, {}) without a signing receipt",
+            self.address,
+            self.call.as_deref().unwrap_or("an extrinsic"),
+            self.block_number,
+            self.extrinsic_index,
+            self.extrinsic_hash
+        );
+        if self.frozen {
+            description.push_str("; signing frozen");
+        }
+        description
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WatchedWallet {
+    address: String,
+    freeze: Option<FreezeSwitch>,
+}
+
+/// Watches wallets for extrinsics not signed through this SDK
+///
+/// See the [module documentation](self).
+pub struct WalletActivityWatcher {
+    log: Arc<SigningLog>,
+    wallets: RwLock<HashMap<[u8; 32], WatchedWallet>>,
+    /// Sequence numbers of the receipts already matched to an extrinsic
+    matched: Mutex<BTreeSet<u64>>,
+    receipt_window: Duration,
+    subscribers: Mutex<Vec<mpsc::UnboundedSender<UnexpectedActivity>>>,
+    #[cfg(feature = "observability")]
+    alerts: Option<Arc<apex_sdk_metrics::AlertEngine>>,
+}
+
+impl WalletActivityWatcher {
+    /// Create a watcher matching extrinsics against the receipts in `log`
+    ///
+    /// `log` must be the log the watched wallets sign through.
+    pub fn new(log: Arc<SigningLog>) -> Self {
+        Self {
+            log,
+            wallets: RwLock::new(HashMap::new()),
+            matched: Mutex::new(BTreeSet::new()),
+            receipt_window: DEFAULT_RECEIPT_WINDOW,
+            subscribers: Mutex::new(Vec::new()),
+            #[cfg(feature = "observability")]
+            alerts: None,
+        }
+    }
+
+    /// Only match extrinsics against receipts younger than `window`
+    /// (default [`DEFAULT_RECEIPT_WINDOW`])
+    ///
+    /// Keep it longer than the mortality of the extrinsics the SDK signs.
+    pub fn with_receipt_window(mut self, window: Duration) -> Self {
+        self.receipt_window = window;
+        self
+    }
+
+    /// Raise a [`Critical`] alert named [`UNEXPECTED_ACTIVITY_ALERT`] on
+    /// `engine` for every unexpected extrinsic
+    ///
+    /// [`Critical`]: apex_sdk_metrics::ErrorSeverity::Critical
+    #[cfg(feature = "observability")]
+    pub fn with_alert_engine(mut self, engine: Arc<apex_sdk_metrics::AlertEngine>) -> Self {
+        self.alerts = Some(engine);
+        self
+    }
+
+    /// Watch an SS58 address
+    pub fn watch(&self, address: &str) -> Result<()> {
+        self.insert(address, None)
+    }
+
+    /// Watch an SS58 address and freeze `freeze` when it signs something
+    /// unexpected
+    pub fn watch_with_freeze(&self, address: &str, freeze: FreezeSwitch) -> Result<()> {
+        self.insert(address, Some(freeze))
+    }
+
+    /// Stop watching an address; returns whether it was watched
+    pub fn unwatch(&self, address: &str) -> Result<bool> {
+        Ok(self
+            .wallets
+            .write()
+            .remove(&account_bytes(address)?)
+            .is_some())
+    }
+
+    /// Watched addresses
+    pub fn watched(&self) -> Vec<String> {
+        let mut addresses: Vec<String> = self
+            .wallets
+            .read()
+            .values()
+            .map(|wallet| wallet.address.clone())
+            .collect();
+        addresses.sort();
+        addresses
+    }
+
+    /// Receive every unexpected extrinsic found from now on
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<UnexpectedActivity> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.subscribers.lock().push(tx);
+        rx
+    }
+
+    /// Check every finalized block from `bus` in the background
+    ///
+    /// No block is skipped: the watcher subscribes with
+    /// [`DeliveryPolicy::Replay`].
+    pub fn spawn(self: Arc<Self>, bus: Arc<EventBus>) -> tokio::task::JoinHandle<()> {
+        let mut blocks = bus.subscribe_with_policy("wallet-activity", DeliveryPolicy::Replay);
+        tokio::spawn(async move {
+            while let Some(block) = blocks.recv().await {
+                match self.process_block(&block, &bus.metadata()).await {
+                    Ok(found) => {
+                        for activity in found {
+                            self.report(activity).await;
+                        }
+                    }
+                    Err(e) => warn!(
+                        block = block.number,
+                        error = %e,
+                        "Failed to check block for wallet activity"
+                    ),
+                }
+            }
+        })
+    }
+
+    fn insert(&self, address: &str, freeze: Option<FreezeSwitch>) -> Result<()> {
+        let account = account_bytes(address)?;
+        self.wallets.write().insert(
+            account,
+            WatchedWallet {
+                address: address.to_string(),
+                freeze,
+            },
+        );
+        Ok(())
+    }
+
+    /// Find the extrinsics in a block signed by watched wallets without a
+    /// receipt, freezing their wallets
+    async fn process_block(
+        &self,
+        block: &FinalizedBlock,
+        metadata: &Metadata,
+    ) -> Result<Vec<UnexpectedActivity>> {
+        if self.wallets.read().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let extrinsics = block
+            .block
+            .extrinsics()
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to get extrinsics: {}", e)))?;
+
+        let mut found = Vec::new();
+        for ext in extrinsics.iter() {
+            let Some(signer) = ext.address_bytes().and_then(signer_account) else {
+                continue;
+            };
+            let call = summarize_call(metadata, ext.call_bytes());
+            let Some(wallet) = self.inspect(&signer, call.as_deref()) else {
+                continue;
+            };
+
+            let mut activity = UnexpectedActivity {
+                address: wallet.address,
+                block_number: block.number,
+                block_hash: format!("0x{}", hex::encode(block.hash)),
+                extrinsic_index: ext.index(),
+                extrinsic_hash: format!("0x{}", hex::encode(sp_core::blake2_256(ext.bytes()))),
+                call,
+                frozen: false,
+            };
+            if let Some(freeze) = wallet.freeze {
+                freeze.freeze(activity.description());
+                activity.frozen = true;
+            }
+            found.push(activity);
+        }
+        Ok(found)
+    }
+
+    /// The watched wallet `signer` belongs to, if it signed `call` without
+    /// a receipt
+    fn inspect(&self, signer: &[u8; 32], call: Option<&str>) -> Option<WatchedWallet> {
+        let wallet = self.wallets.read().get(signer).cloned()?;
+        if self.match_receipt(signer, call) {
+            None
+        } else {
+            Some(wallet)
+        }
+    }
+
+    /// Consume a receipt of `signer` for `call`; returns whether there was one
+    fn match_receipt(&self, signer: &[u8; 32], call: Option<&str>) -> bool {
+        let oldest = unix_now().saturating_sub(self.receipt_window.as_secs());
+        let receipts = self.log.receipts_since(oldest);
+
+        let mut matched = self.matched.lock();
+        // Receipts that left the window can no longer match
+        if let Some(first) = receipts.first() {
+            *matched = matched.split_off(&first.sequence);
+        }
+
+        match find_receipt(&receipts, &matched, signer, call) {
+            Some(sequence) => {
+                matched.insert(sequence);
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn report(&self, activity: UnexpectedActivity) {
+        let description = activity.description();
+        error!(
+            address = %activity.address,
+            block = activity.block_number,
+            extrinsic = %activity.extrinsic_hash,
+            frozen = activity.frozen,
+            "Unexpected wallet activity: {}",
+            description
+        );
+
+        #[cfg(feature = "observability")]
+        if let Some(engine) = &self.alerts {
+            engine
+                .raise(
+                    UNEXPECTED_ACTIVITY_ALERT,
+                    apex_sdk_metrics::ErrorSeverity::Critical,
+                    description,
+                )
+                .await;
+        }
+
+        self.subscribers
+            .lock()
+            .retain(|subscriber| subscriber.send(activity.clone()).is_ok());
+    }
+}
+
+impl std::fmt::Debug for WalletActivityWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalletActivityWatcher")
+            .field("log", &self.log)
+            .field("watched", &self.watched())
+            .field("receipt_window", &self.receipt_window)
+            .finish()
+    }
+}
+
+/// Sequence number of the oldest unmatched receipt of `signer` for `call`,
+/// preferring one with the same call summary over one without a summary
+fn find_receipt(
+    receipts: &[SigningReceipt],
+    matched: &BTreeSet<u64>,
+    signer: &[u8; 32],
+    call: Option<&str>,
+) -> Option<u64> {
+    let candidates = || {
+        receipts.iter().filter(|receipt| {
+            !matched.contains(&receipt.sequence)
+                && account_bytes(&receipt.signer).ok().as_ref() == Some(signer)
+        })
+    };
+    candidates()
+        .find(|receipt| call.is_some() && receipt.call.as_deref() == call)
+        .or_else(|| candidates().find(|receipt| receipt.call.is_none()))
+        .map(|receipt| receipt.sequence)
+}
+
+fn account_bytes(address: &str) -> Result<[u8; 32]> {
+    AccountId32::from_ss58check(address)
+        .map(Into::into)
+        .map_err(|e| Error::Wallet(format!("Invalid address {}: {}", address, e)))
+}
+
+/// Account of a SCALE-encoded `MultiAddress::Id`, the only address form
+/// the SDK signs with
+fn signer_account(address: &[u8]) -> Option<[u8; 32]> {
+    match address {
+        [0, account @ ..] => account.try_into().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Wallet;
+
+    fn account(wallet: &Wallet) -> [u8; 32] {
+        account_bytes(&wallet.address()).unwrap()
+    }
+
+    #[test]
+    fn test_extrinsics_without_receipts_are_flagged() {
+        let log = Arc::new(SigningLog::new());
+        let watcher = WalletActivityWatcher::new(log.clone());
+        let wallet = Wallet::new_random();
+        watcher.watch(&wallet.address()).unwrap();
+        let signer = account(&wallet);
+
+        // Unwatched signers are ignored
+        assert!(watcher
+            .inspect(&account(&Wallet::new_random()), None)
+            .is_none());
+
+        // A receipt without a summary covers one extrinsic of any call
+        log.record(AccountId32::from(signer).to_string(), b"payload")
+            .unwrap();
+        assert!(watcher
+            .inspect(&signer, Some("Balances::transfer_all"))
+            .is_none());
+        let flagged = watcher.inspect(&signer, Some("Balances::transfer_all"));
+        assert_eq!(flagged.unwrap().address, wallet.address());
+
+        assert!(watcher.unwatch(&wallet.address()).unwrap());
+        assert!(watcher.inspect(&signer, None).is_none());
+        assert_eq!(watcher.watched(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_receipts_match_call_summaries() {
+        let alice = Wallet::new_random();
+        let bob = Wallet::new_random();
+        let log = SigningLog::new();
+        for (wallet, payload) in [(&alice, b"one"), (&alice, b"two"), (&bob, b"six")] {
+            log.record(AccountId32::from(account(wallet)).to_string(), payload)
+                .unwrap();
+        }
+        let mut receipts = log.receipts();
+        receipts[0].call = Some("System::remark".to_string());
+        receipts[1].call = Some("Balances::transfer_all".to_string());
+        let mut matched = BTreeSet::new();
+
+        let found = find_receipt(
+            &receipts,
+            &matched,
+            &account(&alice),
+            Some("Balances::transfer_all"),
+        );
+        assert_eq!(found, Some(1));
+        matched.insert(1);
+
+        // Summarized receipts only match their own call
+        assert_eq!(
+            find_receipt(
+                &receipts,
+                &matched,
+                &account(&alice),
+                Some("Balances::transfer_all")
+            ),
+            None
+        );
+        assert_eq!(
+            find_receipt(
+                &receipts,
+                &matched,
+                &account(&alice),
+                Some("System::remark")
+            ),
+            Some(0)
+        );
+        // and receipts only match their signer
+        assert_eq!(
+            find_receipt(&receipts, &matched, &account(&bob), None),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_signer_account_decodes_multiaddress_id() {
+        let mut encoded = vec![0u8];
+        encoded.extend_from_slice(&[7u8; 32]);
+        assert_eq!(signer_account(&encoded), Some([7u8; 32]));
+        // MultiAddress::Index
+        assert_eq!(signer_account(&[1, 4]), None);
+    }
+}
@@ -1,17 +1,309 @@
-use crate::{Result, SubstrateAdapter};
-use apex_sdk_types::Address;
-use subxt::dynamic::Value;
-use subxt::ext::scale_value::Primitive;
-use tracing::info;
+use crate::chain_client::StorageKey;
+use crate::storage::{extract_u128, StorageQuery};
+use crate::xcm::{encode_multilocation, MultiLocation, XcmVersion};
+use crate::{Error, Result, SubstrateAdapter};
+use apex_sdk_core::AssetAllowList;
+use apex_sdk_types::{Address, IntoAddress};
+use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+use subxt::dynamic::{At as _, Value};
+use subxt::ext::scale_value::{scale::decode_as_type, Primitive, ValueDef};
+use subxt::Metadata;
+use tracing::{debug, info};
+
+/// Pallet holding assets created on Asset Hub
+const ASSETS_PALLET: &str = "Assets";
+
+/// Pallet holding assets from other consensus systems, keyed by location
+const FOREIGN_ASSETS_PALLET: &str = "ForeignAssets";
+
+/// Identifier of an asset on Asset Hub
+#[derive(Debug, Clone, PartialEq)]
+pub enum HubAssetId {
+    /// Asset in the `Assets` pallet
+    Local(u32),
+    /// Asset in the `ForeignAssets` pallet, identified by its XCM location
+    Foreign(Value),
+}
+
+impl HubAssetId {
+    /// Foreign asset at `location`, e.g. a parachain's native token
+    pub fn foreign(location: &MultiLocation) -> Result<Self> {
+        // Foreign assets are keyed by a v4 (or later, identically shaped) location
+        Ok(HubAssetId::Foreign(encode_multilocation(
+            location,
+            XcmVersion::V4,
+        )?))
+    }
+
+    /// Pallet the asset lives in
+    pub fn pallet(&self) -> &'static str {
+        match self {
+            HubAssetId::Local(_) => ASSETS_PALLET,
+            HubAssetId::Foreign(_) => FOREIGN_ASSETS_PALLET,
+        }
+    }
+
+    fn key(&self) -> Value {
+        match self {
+            HubAssetId::Local(id) => Value::u128(*id as u128),
+            HubAssetId::Foreign(location) => location.clone(),
+        }
+    }
+}
+
+impl From<u32> for HubAssetId {
+    fn from(id: u32) -> Self {
+        HubAssetId::Local(id)
+    }
+}
+
+/// Configuration and supply of an asset, from `Asset` storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetDetails {
+    /// Account that owns the asset class
+    pub owner: String,
+    /// Account allowed to mint
+    pub issuer: String,
+    /// Account allowed to burn and force transfers
+    pub admin: String,
+    /// Account allowed to freeze accounts
+    pub freezer: String,
+    /// Total issuance
+    pub supply: u128,
+    /// Deposit reserved from the owner
+    pub deposit: u128,
+    /// Smallest balance an account may hold
+    pub min_balance: u128,
+    /// Whether holding the asset keeps an account alive without native tokens
+    pub is_sufficient: bool,
+    /// Number of accounts holding the asset
+    pub accounts: u32,
+    /// Number of accounts the asset keeps alive
+    pub sufficients: u32,
+    /// Number of open approvals
+    pub approvals: u32,
+    /// `Live`, `Frozen` or `Destroying`
+    pub status: String,
+}
+
+impl AssetDetails {
+    /// Extract asset details from a decoded `Asset` value, encoding accounts
+    /// with `ss58_prefix`
+    pub(crate) fn from_value<T>(value: &Value<T>, ss58_prefix: u16) -> Result<Self> {
+        let account = |name: &str| {
+            value
+                .at(name)
+                .and_then(value_bytes)
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                .map(|bytes| {
+                    AccountId32::from(bytes)
+                        .to_ss58check_with_version(Ss58AddressFormat::custom(ss58_prefix))
+                })
+                .ok_or_else(|| missing_field(name))
+        };
+        let number = |name: &str| extract_u128(value, &[name]).ok_or_else(|| missing_field(name));
+        let count = |name: &str| {
+            number(name).and_then(|n| u32::try_from(n).map_err(|_| missing_field(name)))
+        };
+
+        Ok(Self {
+            owner: account("owner")?,
+            issuer: account("issuer")?,
+            admin: account("admin")?,
+            freezer: account("freezer")?,
+            supply: number("supply")?,
+            deposit: number("deposit")?,
+            min_balance: number("min_balance")?,
+            is_sufficient: value
+                .at("is_sufficient")
+                .and_then(|v| v.as_bool())
+                .ok_or_else(|| missing_field("is_sufficient"))?,
+            accounts: count("accounts")?,
+            sufficients: count("sufficients")?,
+            approvals: count("approvals")?,
+            status: match value.at("status").map(|v| &v.value) {
+                Some(ValueDef::Variant(variant)) => variant.name.clone(),
+                _ => return Err(missing_field("status")),
+            },
+        })
+    }
+}
+
+/// Name, symbol and decimals of an asset, from `Metadata` storage
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetMetadata {
+    /// Deposit reserved for the metadata
+    pub deposit: u128,
+    /// Asset name
+    pub name: String,
+    /// Ticker symbol
+    pub symbol: String,
+    /// Number of decimals in a display amount
+    pub decimals: u8,
+    /// Whether the metadata can no longer be changed
+    pub is_frozen: bool,
+}
+
+impl AssetMetadata {
+    /// Extract asset metadata from a decoded `Metadata` value
+    pub(crate) fn from_value<T>(value: &Value<T>) -> Result<Self> {
+        let text = |name: &str| {
+            value
+                .at(name)
+                .and_then(value_bytes)
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .ok_or_else(|| missing_field(name))
+        };
+
+        Ok(Self {
+            deposit: extract_u128(value, &["deposit"]).ok_or_else(|| missing_field("deposit"))?,
+            name: text("name")?,
+            symbol: text("symbol")?,
+            decimals: extract_u128(value, &["decimals"])
+                .and_then(|d| u8::try_from(d).ok())
+                .ok_or_else(|| missing_field("decimals"))?,
+            is_frozen: value
+                .at("is_frozen")
+                .and_then(|v| v.as_bool())
+                .ok_or_else(|| missing_field("is_frozen"))?,
+        })
+    }
+}
 
 /// High-level API for interacting with pallet-assets on Asset Hub
+///
+/// Calls are built for the `Assets` pallet; queries also cover the
+/// `ForeignAssets` pallet through [`HubAssetId::Foreign`].
 pub struct AssetManager<'a> {
-    _adapter: &'a SubstrateAdapter,
+    adapter: &'a SubstrateAdapter,
+    allow_list: Option<AssetAllowList>,
 }
 
 impl<'a> AssetManager<'a> {
     pub fn new(adapter: &'a SubstrateAdapter) -> Self {
-        Self { _adapter: adapter }
+        Self {
+            adapter,
+            allow_list: None,
+        }
+    }
+
+    /// Only transfer assets on `allow_list` whose on-chain symbol and
+    /// decimals match their entry
+    ///
+    /// Entries are keyed by asset ID, e.g. `"1984"`.
+    pub fn with_allow_list(mut self, allow_list: AssetAllowList) -> Self {
+        self.allow_list = Some(allow_list);
+        self
+    }
+
+    /// Check an asset against the allow-list, if one is set
+    ///
+    /// Fails with [`Error::AssetPolicy`] if the asset is not on the list, or
+    /// if its `Metadata` storage has another symbol or number of decimals
+    /// than its entry.
+    pub async fn verify_asset(&self, id: u32) -> Result<()> {
+        let Some(allow_list) = &self.allow_list else {
+            return Ok(());
+        };
+        let asset = id.to_string();
+        allow_list.check_allowed(&asset)?;
+
+        let metadata = self.asset_metadata(id).await?;
+        allow_list.verify(&asset, &metadata.symbol, metadata.decimals)?;
+        Ok(())
+    }
+
+    /// Balance of `account` in an asset, 0 if the account holds none
+    pub async fn asset_balance(
+        &self,
+        id: impl Into<HubAssetId>,
+        account: impl IntoAddress,
+    ) -> Result<u128> {
+        let id = id.into();
+        let key = StorageKey::new(
+            id.pallet(),
+            "Account",
+            vec![id.key(), StorageQuery::parse_address(account)?],
+        );
+        debug!(asset = ?id, "Querying asset balance");
+
+        match self.adapter.chain_client().storage(&key, None).await? {
+            Some(account) => {
+                extract_u128(&account.value, &["balance"]).ok_or_else(|| missing_field("balance"))
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Name, symbol and decimals of an asset
+    ///
+    /// Assets without metadata return the default (empty name, 0 decimals),
+    /// as the chain does.
+    pub async fn asset_metadata(&self, id: impl Into<HubAssetId>) -> Result<AssetMetadata> {
+        let id = id.into();
+        let key = StorageKey::new(id.pallet(), "Metadata", vec![id.key()]);
+        debug!(asset = ?id, "Querying asset metadata");
+
+        match self.adapter.chain_client().storage(&key, None).await? {
+            Some(metadata) => AssetMetadata::from_value(&metadata.value),
+            None => Ok(AssetMetadata::default()),
+        }
+    }
+
+    /// Details of an asset, or `None` if it does not exist
+    pub async fn asset_details(&self, id: impl Into<HubAssetId>) -> Result<Option<AssetDetails>> {
+        let id = id.into();
+        let key = StorageKey::new(id.pallet(), "Asset", vec![id.key()]);
+        debug!(asset = ?id, "Querying asset details");
+
+        self.adapter
+            .chain_client()
+            .storage(&key, None)
+            .await?
+            .map(|details| AssetDetails::from_value(&details.value, self.ss58_prefix()))
+            .transpose()
+    }
+
+    /// Every asset in the `Assets` and `ForeignAssets` pallets, with its details
+    ///
+    /// Reads the whole `Asset` map of each pallet the runtime has.
+    pub async fn list_assets(&self) -> Result<Vec<(HubAssetId, AssetDetails)>> {
+        let chain = self.adapter.chain_client();
+        let metadata = chain.metadata();
+
+        let mut assets = Vec::new();
+        for pallet in [ASSETS_PALLET, FOREIGN_ASSETS_PALLET] {
+            if metadata
+                .pallet_by_name(pallet)
+                .and_then(|p| p.storage())
+                .and_then(|s| s.entry_by_name("Asset"))
+                .is_none()
+            {
+                debug!("{} not in runtime, skipping", pallet);
+                continue;
+            }
+
+            for (key, value) in chain.storage_entries(pallet, "Asset").await? {
+                let (key, value) = decode_map_entry(&metadata, pallet, "Asset", &key, &value)?;
+                let id = if pallet == ASSETS_PALLET {
+                    let id = key
+                        .as_u128()
+                        .and_then(|id| u32::try_from(id).ok())
+                        .ok_or_else(|| Error::Storage("Asset ID is not a u32".to_string()))?;
+                    HubAssetId::Local(id)
+                } else {
+                    HubAssetId::Foreign(key)
+                };
+                assets.push((id, AssetDetails::from_value(&value, self.ss58_prefix())?));
+            }
+        }
+
+        debug!("Found {} assets", assets.len());
+        Ok(assets)
+    }
+
+    fn ss58_prefix(&self) -> u16 {
+        self.adapter.config().ss58_prefix
     }
 
     /// Create a new asset
@@ -21,7 +313,7 @@ impl<'a> AssetManager<'a> {
         admin: &Address,
         min_balance: u128,
     ) -> Result<subxt::tx::DynamicPayload> {
-        info!("Preparing to create asset {} with admin {}", id, admin);
+        info!(asset_id = id, admin = %admin, "Preparing to create asset");
 
         // Convert Address to subxt Value for dynamic call
         let admin_val = Value::primitive(Primitive::String(admin.to_string()));
@@ -47,10 +339,7 @@ impl<'a> AssetManager<'a> {
         symbol: String,
         decimals: u8,
     ) -> Result<subxt::tx::DynamicPayload> {
-        info!(
-            "Preparing to set metadata for asset {}: {} ({})",
-            id, name, symbol
-        );
+        info!(asset_id = id, name = %name, symbol = %symbol, "Preparing to set asset metadata");
 
         let payload = subxt::dynamic::tx(
             "Assets",
@@ -74,8 +363,10 @@ impl<'a> AssetManager<'a> {
         amount: u128,
     ) -> Result<subxt::tx::DynamicPayload> {
         info!(
-            "Preparing to mint {} of asset {} to {}",
-            amount, id, beneficiary
+            asset_id = id,
+            beneficiary = %beneficiary,
+            amount,
+            "Preparing to mint asset"
         );
 
         let beneficiary_val = Value::primitive(Primitive::String(beneficiary.to_string()));
@@ -94,15 +385,20 @@ impl<'a> AssetManager<'a> {
     }
 
     /// Transfer assets to a target
+    ///
+    /// With an allow-list, the asset is [verified](Self::verify_asset) first.
     pub async fn transfer(
         &self,
         id: u32,
         target: &Address,
         amount: u128,
     ) -> Result<subxt::tx::DynamicPayload> {
+        self.verify_asset(id).await?;
         info!(
-            "Preparing to transfer {} of asset {} to {}",
-            amount, id, target
+            asset_id = id,
+            to = %target,
+            amount,
+            "Preparing to transfer asset"
         );
 
         let target_val = Value::primitive(Primitive::String(target.to_string()));
@@ -120,3 +416,170 @@ impl<'a> AssetManager<'a> {
         Ok(payload)
     }
 }
+
+pub(crate) fn missing_field(name: &str) -> Error {
+    Error::Storage(format!("Failed to extract '{}' field", name))
+}
+
+/// A number, unwrapping newtypes such as `ParaId`
+pub(crate) fn value_number<T>(value: &Value<T>) -> Option<u128> {
+    match &value.value {
+        ValueDef::Composite(composite) if composite.len() == 1 => {
+            composite.values().next().and_then(value_number)
+        }
+        _ => value.as_u128(),
+    }
+}
+
+/// Flatten a byte array value, unwrapping newtypes such as `AccountId32`
+/// and `BoundedVec`
+pub(crate) fn value_bytes<T>(value: &Value<T>) -> Option<Vec<u8>> {
+    let ValueDef::Composite(composite) = &value.value else {
+        return None;
+    };
+
+    let values: Vec<&Value<T>> = composite.values().collect();
+    if let [inner] = values.as_slice() {
+        if matches!(inner.value, ValueDef::Composite(_)) {
+            return value_bytes(inner);
+        }
+    }
+
+    values
+        .iter()
+        .map(|v| v.as_u128().and_then(|b| u8::try_from(b).ok()))
+        .collect()
+}
+
+/// Decode the key and value of a single-key storage map entry read by
+/// [`ChainClient::storage_entries`](crate::ChainClient::storage_entries)
+pub(crate) fn decode_map_entry(
+    metadata: &Metadata,
+    pallet: &str,
+    item: &str,
+    key: &[u8],
+    value: &[u8],
+) -> Result<(Value, Value)> {
+    let entry = metadata
+        .pallet_by_name(pallet)
+        .and_then(|p| p.storage())
+        .and_then(|s| s.entry_by_name(item))
+        .ok_or_else(|| Error::Metadata(format!("Storage entry {}::{} not found", pallet, item)))?;
+
+    let subxt::metadata::types::StorageEntryType::Map {
+        hashers,
+        key_ty,
+        value_ty,
+    } = entry.entry_type()
+    else {
+        return Err(Error::Metadata(format!(
+            "{}::{} is not a map",
+            pallet, item
+        )));
+    };
+    let [hasher] = hashers.as_slice() else {
+        return Err(Error::Metadata(format!(
+            "{}::{} is not a single-key map",
+            pallet, item
+        )));
+    };
+    if !hasher.ends_with_key() {
+        return Err(Error::Storage(format!(
+            "Keys of {}::{} are hashed and cannot be decoded",
+            pallet, item
+        )));
+    }
+
+    // Skip the pallet and item prefixes and the hash preceding the key
+    let offset = 32 + hasher.len_excluding_key();
+    let mut key_bytes = key.get(offset..).ok_or_else(|| {
+        Error::Storage(format!("Storage key of {}::{} is too short", pallet, item))
+    })?;
+    let decode_error = |e| {
+        Error::Storage(format!(
+            "Failed to decode {}::{} entry: {}",
+            pallet, item, e
+        ))
+    };
+    let key = decode_as_type(&mut key_bytes, *key_ty, metadata.types()).map_err(decode_error)?;
+    let value =
+        decode_as_type(&mut &value[..], *value_ty, metadata.types()).map_err(decode_error)?;
+
+    Ok((key.remove_context(), value.remove_context()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(data: &[u8]) -> Value {
+        Value::unnamed_composite(data.iter().map(|b| Value::u128(*b as u128)))
+    }
+
+    fn account(byte: u8) -> Value {
+        // AccountId32 is a newtype around [u8; 32]
+        Value::unnamed_composite([bytes(&[byte; 32])])
+    }
+
+    #[test]
+    fn test_asset_details_from_value() {
+        let value = Value::named_composite([
+            ("owner", account(1)),
+            ("issuer", account(1)),
+            ("admin", account(2)),
+            ("freezer", account(2)),
+            ("supply", Value::u128(1_000_000)),
+            ("deposit", Value::u128(10)),
+            ("min_balance", Value::u128(70_000)),
+            ("is_sufficient", Value::bool(true)),
+            ("accounts", Value::u128(42)),
+            ("sufficients", Value::u128(40)),
+            ("approvals", Value::u128(0)),
+            ("status", Value::unnamed_variant("Live", [])),
+        ]);
+
+        let details = AssetDetails::from_value(&value, 0).unwrap();
+        assert_eq!(
+            details.owner,
+            AccountId32::from([1u8; 32]).to_ss58check_with_version(Ss58AddressFormat::custom(0))
+        );
+        assert_eq!(details.supply, 1_000_000);
+        assert_eq!(details.min_balance, 70_000);
+        assert!(details.is_sufficient);
+        assert_eq!(details.accounts, 42);
+        assert_eq!(details.status, "Live");
+    }
+
+    #[test]
+    fn test_asset_metadata_from_value() {
+        // BoundedVec<u8> decodes as a newtype around the bytes
+        let value = Value::named_composite([
+            ("deposit", Value::u128(2_008_200_000)),
+            ("name", Value::unnamed_composite([bytes(b"Tether USD")])),
+            ("symbol", Value::unnamed_composite([bytes(b"USDt")])),
+            ("decimals", Value::u128(6)),
+            ("is_frozen", Value::bool(false)),
+        ]);
+
+        let metadata = AssetMetadata::from_value(&value).unwrap();
+        assert_eq!(metadata.name, "Tether USD");
+        assert_eq!(metadata.symbol, "USDt");
+        assert_eq!(metadata.decimals, 6);
+        assert!(!metadata.is_frozen);
+
+        let missing = Value::named_composite([("deposit", Value::u128(0))]);
+        assert!(AssetMetadata::from_value(&missing).is_err());
+    }
+
+    #[test]
+    fn test_hub_asset_id() {
+        assert_eq!(HubAssetId::from(1984).pallet(), "Assets");
+
+        let foreign = HubAssetId::foreign(&MultiLocation::parachain(2004)).unwrap();
+        assert_eq!(foreign.pallet(), "ForeignAssets");
+        assert_eq!(
+            foreign.key().at("parents").and_then(|p| p.as_u128()),
+            Some(1)
+        );
+    }
+}
@@ -0,0 +1,480 @@
+//! Offline signing of payload batches
+//!
+//! For air-gapped payouts, an online machine builds the signer payloads of
+//! many transactions (for example with `PartialTransaction::signer_payload`),
+//! collects them in a [`SigningBatch`] and saves it to a file. The offline
+//! machine loads the batch, signs every payload with
+//! [`Wallet::sign_batch`] and saves the resulting [`SignatureBundle`]. Back
+//! online, [`SigningBatch::import`] pairs each signature with its payload.
+//!
+//! Every request carries its index and the BLAKE2-256 hash of its payload,
+//! and the batch ID commits to the signer and to all payload hashes in
+//! order. Signing refuses a batch whose indices, hashes or ID do not match
+//! its payloads, and importing refuses a bundle from another batch or
+//! signer, with missing, duplicate or out-of-range indices, with a payload
+//! hash that differs from the request at that index, or with a signature
+//! that does not verify against that payload. A signature therefore cannot
+//! end up attached to the wrong payload.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::{SigningBatch, SignatureBundle, Wallet};
+//!
+//! # fn example(payloads: Vec<Vec<u8>>, wallet: &Wallet) -> apex_sdk_substrate::Result<()> {
+//! // Online
+//! SigningBatch::new(wallet.address(), payloads).save("payouts.batch.json")?;
+//!
+//! // Offline
+//! let batch = SigningBatch::load("payouts.batch.json")?;
+//! wallet.sign_batch(&batch)?.save("payouts.signatures.json")?;
+//!
+//! // Online again
+//! let batch = SigningBatch::load("payouts.batch.json")?;
+//! let bundle = SignatureBundle::load("payouts.signatures.json")?;
+//! for signed in batch.import(&bundle)? {
+//!     println!("#{}: {} byte signature", signed.index, signed.signature.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::wallet::{KeyPairType, VerifyingKey, Wallet};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tracing::info;
+
+/// Version of the batch and bundle file format
+pub const BATCH_FORMAT_VERSION: u32 = 1;
+
+/// A payload to sign, at its position in the batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningRequest {
+    /// Position in the batch, starting at 0
+    pub index: u32,
+    /// BLAKE2-256 hash of the payload
+    pub payload_hash: String,
+    /// Bytes to sign
+    #[serde(with = "hex_bytes")]
+    pub payload: Vec<u8>,
+}
+
+/// Payloads to be signed offline by one signer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningBatch {
+    /// File format version
+    pub version: u32,
+    /// Hash of the signer and every payload hash, in order
+    pub batch_id: String,
+    /// SS58 address expected to sign
+    pub signer: String,
+    /// Payloads, in index order
+    pub requests: Vec<SigningRequest>,
+}
+
+/// A signature over the payload at `index`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchSignature {
+    /// Index of the signed request
+    pub index: u32,
+    /// Hash of the payload that was signed
+    pub payload_hash: String,
+    /// 64-byte signature
+    #[serde(with = "hex_bytes")]
+    pub signature: Vec<u8>,
+}
+
+/// Signatures returned for a [`SigningBatch`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureBundle {
+    /// File format version
+    pub version: u32,
+    /// ID of the signed batch
+    pub batch_id: String,
+    /// SS58 address of the signer
+    pub signer: String,
+    /// Key type of the signer
+    pub key_type: KeyPairType,
+    /// One signature per request, in index order
+    pub signatures: Vec<BatchSignature>,
+}
+
+/// A payload and its verified signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedPayload {
+    /// Index of the request
+    pub index: u32,
+    /// Signed bytes
+    pub payload: Vec<u8>,
+    /// 64-byte signature
+    pub signature: Vec<u8>,
+}
+
+impl SigningBatch {
+    /// Create a batch of payloads for `signer` to sign, indexed in order
+    pub fn new(signer: impl Into<String>, payloads: Vec<Vec<u8>>) -> Self {
+        let signer = signer.into();
+        let requests: Vec<SigningRequest> = payloads
+            .into_iter()
+            .enumerate()
+            .map(|(index, payload)| SigningRequest {
+                index: index as u32,
+                payload_hash: payload_hash(&payload),
+                payload,
+            })
+            .collect();
+        Self {
+            version: BATCH_FORMAT_VERSION,
+            batch_id: batch_id(&signer, &requests),
+            signer,
+            requests,
+        }
+    }
+
+    /// Number of payloads
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Whether the batch has no payloads
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Check that indices run from 0 in order, that every payload matches
+    /// its hash and that the batch ID matches the signer and hashes
+    pub fn verify(&self) -> Result<()> {
+        check_version(self.version)?;
+        for (position, request) in self.requests.iter().enumerate() {
+            if request.index as usize != position {
+                return Err(Error::Signature(format!(
+                    "Request at position {} has index {}",
+                    position, request.index
+                )));
+            }
+            if payload_hash(&request.payload) != request.payload_hash {
+                return Err(Error::Signature(format!(
+                    "Payload of request {} does not match its hash",
+                    request.index
+                )));
+            }
+        }
+        if batch_id(&self.signer, &self.requests) != self.batch_id {
+            return Err(Error::Signature(format!(
+                "Batch ID {} does not match its signer and payloads",
+                self.batch_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Pair every payload with its signature from `bundle`
+    ///
+    /// Fails unless the bundle was produced for this batch by its signer and
+    /// holds exactly one valid signature per request.
+    pub fn import(&self, bundle: &SignatureBundle) -> Result<Vec<SignedPayload>> {
+        self.verify()?;
+        check_version(bundle.version)?;
+        if bundle.batch_id != self.batch_id {
+            return Err(Error::Signature(format!(
+                "Bundle is for batch {}, expected {}",
+                bundle.batch_id, self.batch_id
+            )));
+        }
+        let public = account_id(&self.signer)?;
+        if account_id(&bundle.signer)? != public {
+            return Err(Error::Signature(format!(
+                "Bundle was signed by {}, expected {}",
+                bundle.signer, self.signer
+            )));
+        }
+        let key = VerifyingKey::from_public(bundle.key_type, public);
+
+        let mut signatures = BTreeMap::new();
+        for signature in &bundle.signatures {
+            let Some(request) = self.requests.get(signature.index as usize) else {
+                return Err(Error::Signature(format!(
+                    "Signature index {} is outside the batch of {}",
+                    signature.index,
+                    self.len()
+                )));
+            };
+            if signature.payload_hash != request.payload_hash {
+                return Err(Error::Signature(format!(
+                    "Signature {} is for payload {}, expected {}",
+                    signature.index, signature.payload_hash, request.payload_hash
+                )));
+            }
+            if !key.verify(&request.payload, &signature.signature) {
+                return Err(Error::Signature(format!(
+                    "Signature {} does not verify against its payload",
+                    signature.index
+                )));
+            }
+            if signatures.insert(signature.index, signature).is_some() {
+                return Err(Error::Signature(format!(
+                    "Duplicate signature for index {}",
+                    signature.index
+                )));
+            }
+        }
+        if signatures.len() != self.len() {
+            let missing: Vec<u32> = self
+                .requests
+                .iter()
+                .map(|request| request.index)
+                .filter(|index| !signatures.contains_key(index))
+                .collect();
+            return Err(Error::Signature(format!(
+                "Missing signatures for indices {:?}",
+                missing
+            )));
+        }
+
+        Ok(self
+            .requests
+            .iter()
+            .zip(signatures.into_values())
+            .map(|(request, signature)| SignedPayload {
+                index: request.index,
+                payload: request.payload.clone(),
+                signature: signature.signature.clone(),
+            })
+            .collect())
+    }
+
+    /// Serialize the batch as JSON
+    pub fn to_json(&self) -> Result<String> {
+        to_json(self)
+    }
+
+    /// Parse a batch from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        from_json(json)
+    }
+
+    /// Write the batch to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        write(path.as_ref(), &self.to_json()?)
+    }
+
+    /// Read a batch from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&read(path.as_ref())?)
+    }
+}
+
+impl SignatureBundle {
+    /// Serialize the bundle as JSON
+    pub fn to_json(&self) -> Result<String> {
+        to_json(self)
+    }
+
+    /// Parse a bundle from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        from_json(json)
+    }
+
+    /// Write the bundle to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        write(path.as_ref(), &self.to_json()?)
+    }
+
+    /// Read a bundle from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&read(path.as_ref())?)
+    }
+}
+
+impl Wallet {
+    /// Sign every payload of a batch
+    ///
+    /// The batch is checked with [`SigningBatch::verify`] first and must be
+    /// addressed to this wallet's account. Each signature is recorded in the
+    /// wallet's signing log, if it has one.
+    pub fn sign_batch(&self, batch: &SigningBatch) -> Result<SignatureBundle> {
+        batch.verify()?;
+        if account_id(&batch.signer)?.as_slice() != self.public_key().as_slice() {
+            return Err(Error::Signature(format!(
+                "Batch is addressed to {}, not {}",
+                batch.signer,
+                self.address()
+            )));
+        }
+
+        let signatures = batch
+            .requests
+            .iter()
+            .map(|request| BatchSignature {
+                index: request.index,
+                payload_hash: request.payload_hash.clone(),
+                signature: self.sign(&request.payload),
+            })
+            .collect();
+        info!(
+            "Signed batch {} of {} payloads",
+            batch.batch_id,
+            batch.len()
+        );
+
+        Ok(SignatureBundle {
+            version: BATCH_FORMAT_VERSION,
+            batch_id: batch.batch_id.clone(),
+            signer: self.address(),
+            key_type: self.key_type(),
+            signatures,
+        })
+    }
+}
+
+fn payload_hash(payload: &[u8]) -> String {
+    format!("0x{}", hex::encode(sp_core::blake2_256(payload)))
+}
+
+fn batch_id(signer: &str, requests: &[SigningRequest]) -> String {
+    let mut preimage = signer.as_bytes().to_vec();
+    for request in requests {
+        preimage.extend_from_slice(&request.index.to_le_bytes());
+        preimage.extend_from_slice(request.payload_hash.as_bytes());
+    }
+    payload_hash(&preimage)
+}
+
+fn account_id(address: &str) -> Result<[u8; 32]> {
+    AccountId32::from_ss58check_with_version(address)
+        .map(|(account, _)| account.into())
+        .map_err(|e| Error::Signature(format!("Invalid signer address {}: {:?}", address, e)))
+}
+
+fn check_version(version: u32) -> Result<()> {
+    if version != BATCH_FORMAT_VERSION {
+        return Err(Error::Signature(format!(
+            "Unsupported batch format version {}, expected {}",
+            version, BATCH_FORMAT_VERSION
+        )));
+    }
+    Ok(())
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string_pretty(value).map_err(|e| Error::Encoding(e.to_string()))
+}
+
+fn from_json<T: for<'de> Deserialize<'de>>(json: &str) -> Result<T> {
+    serde_json::from_str(json).map_err(|e| Error::Encoding(e.to_string()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    std::fs::write(path, contents)
+        .map_err(|e| Error::Other(format!("Failed to write {}: {}", path.display(), e)))
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| Error::Other(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hex_str = String::deserialize(deserializer)?;
+        hex::decode(hex_str.trim_start_matches("0x")).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payloads(count: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|i| format!("payout #{}", i).into_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip_through_files() {
+        let wallet = Wallet::new_random();
+        let dir = tempfile::tempdir().unwrap();
+        let batch_path = dir.path().join("batch.json");
+        let bundle_path = dir.path().join("signatures.json");
+
+        SigningBatch::new(wallet.address(), payloads(300))
+            .save(&batch_path)
+            .unwrap();
+        wallet
+            .sign_batch(&SigningBatch::load(&batch_path).unwrap())
+            .unwrap()
+            .save(&bundle_path)
+            .unwrap();
+
+        let batch = SigningBatch::load(&batch_path).unwrap();
+        let signed = batch
+            .import(&SignatureBundle::load(&bundle_path).unwrap())
+            .unwrap();
+        assert_eq!(signed.len(), 300);
+        for (i, signed) in signed.iter().enumerate() {
+            assert_eq!(signed.index as usize, i);
+            assert!(wallet.verify(&signed.payload, &signed.signature));
+        }
+    }
+
+    #[test]
+    fn test_sign_batch_refuses_tampered_batches() {
+        let wallet = Wallet::new_random_with_type(KeyPairType::Ed25519);
+
+        let mut batch = SigningBatch::new(wallet.address(), payloads(3));
+        batch.requests[1].payload = b"payout to someone else".to_vec();
+        assert!(wallet.sign_batch(&batch).is_err());
+
+        // Swapping requests keeps every hash but breaks the indices
+        let mut batch = SigningBatch::new(wallet.address(), payloads(3));
+        batch.requests.swap(0, 2);
+        assert!(wallet.sign_batch(&batch).is_err());
+
+        let batch = SigningBatch::new(Wallet::new_random().address(), payloads(3));
+        assert!(wallet.sign_batch(&batch).is_err());
+    }
+
+    #[test]
+    fn test_import_refuses_mismatched_signatures() {
+        let wallet = Wallet::new_random();
+        let batch = SigningBatch::new(wallet.address(), payloads(4));
+        let bundle = wallet.sign_batch(&batch).unwrap();
+        assert!(batch.import(&bundle).is_ok());
+
+        // Signatures moved to other indices
+        let mut swapped = bundle.clone();
+        swapped.signatures[0].index = 1;
+        swapped.signatures[1].index = 0;
+        assert!(batch.import(&swapped).is_err());
+
+        // Another payload's signature under the right index and hash
+        let mut moved = bundle.clone();
+        moved.signatures[0].signature = bundle.signatures[1].signature.clone();
+        assert!(batch.import(&moved).is_err());
+
+        let mut missing = bundle.clone();
+        missing.signatures.pop();
+        assert!(batch.import(&missing).is_err());
+
+        let mut duplicate = bundle.clone();
+        duplicate.signatures[3] = duplicate.signatures[2].clone();
+        assert!(batch.import(&duplicate).is_err());
+
+        let other = SigningBatch::new(wallet.address(), payloads(5));
+        assert!(other.import(&bundle).is_err());
+    }
+}
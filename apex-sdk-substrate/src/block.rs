@@ -6,21 +6,115 @@
 //! - Extract block metadata (timestamp, extrinsics, events)
 //! - Detect block finality
 //! - Parse extrinsics and compute hashes
+//! - Resolve the block author from the consensus digest
+//!
+//! [`BlockOptions`] skips the parts of a block a caller does not need.
 
 use crate::Error;
-use apex_sdk_core::{BlockEvent, BlockInfo, DetailedBlockInfo, ExtrinsicInfo};
+use apex_sdk_core::{BlockEvent, BlockExtrinsic, BlockInfo, DetailedBlockInfo, ExtrinsicInfo};
+use apex_sdk_types::BlockHash;
+use parity_scale_codec::Decode;
+use std::collections::HashMap;
+use subxt::config::substrate::{Digest, DigestItem};
+use subxt::events::Phase;
 use subxt::{OnlineClient, PolkadotConfig};
 use tracing::debug;
 
+type Block = subxt::blocks::Block<PolkadotConfig, OnlineClient<PolkadotConfig>>;
+
+/// BABE consensus engine ID
+const BABE_ENGINE_ID: [u8; 4] = *b"BABE";
+
+/// Aura consensus engine ID
+const AURA_ENGINE_ID: [u8; 4] = *b"aura";
+
+/// Parts of a block to fetch beyond its header
+///
+/// Everything is fetched by default. Each part costs at least one more
+/// request per block, so views of many blocks can skip what they do not show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockOptions {
+    /// Fetch the block body: extrinsic hashes, calls and counts
+    pub extrinsics: bool,
+    /// Fetch the block's events: per-extrinsic success flags and event counts
+    pub events: bool,
+    /// Resolve the block author from the consensus digest
+    pub author: bool,
+    /// Compare the block with the finalized head
+    pub finality: bool,
+}
+
+impl Default for BlockOptions {
+    fn default() -> Self {
+        Self::full()
+    }
+}
+
+impl BlockOptions {
+    /// Fetch every part of the block
+    pub fn full() -> Self {
+        Self {
+            extrinsics: true,
+            events: true,
+            author: true,
+            finality: true,
+        }
+    }
+
+    /// Fetch only the header and timestamp
+    pub fn header_only() -> Self {
+        Self {
+            extrinsics: false,
+            events: false,
+            author: false,
+            finality: false,
+        }
+    }
+
+    /// Whether to fetch the block body
+    pub fn with_extrinsics(mut self, extrinsics: bool) -> Self {
+        self.extrinsics = extrinsics;
+        self
+    }
+
+    /// Whether to fetch the block's events
+    pub fn with_events(mut self, events: bool) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Whether to resolve the block author
+    pub fn with_author(mut self, author: bool) -> Self {
+        self.author = author;
+        self
+    }
+
+    /// Whether to check finality
+    pub fn with_finality(mut self, finality: bool) -> Self {
+        self.finality = finality;
+        self
+    }
+}
+
 /// Block query client for retrieving and parsing block information
 pub struct BlockQuery {
     client: OnlineClient<PolkadotConfig>,
+    options: BlockOptions,
 }
 
 impl BlockQuery {
     /// Create a new BlockQuery instance
     pub fn new(client: OnlineClient<PolkadotConfig>) -> Self {
-        Self { client }
+        Self {
+            client,
+            options: BlockOptions::default(),
+        }
+    }
+
+    /// Fetch only the parts of each block in `options`
+    pub fn with_options(mut self, options: BlockOptions) -> Self {
+        self.options = options;
+        self
     }
 
     /// Get block information by block number
@@ -93,23 +187,10 @@ impl BlockQuery {
     /// Get block information by block hash
     ///
     /// This is the most efficient way to query a specific block if you have its hash.
-    pub async fn get_block_by_hash(&self, hash_hex: &str) -> Result<BlockInfo, Error> {
-        debug!("Fetching block by hash: {}", hash_hex);
-
-        // Parse the hex string to H256
-        let hash_hex = hash_hex.trim_start_matches("0x");
-        let hash_bytes = hex::decode(hash_hex)
-            .map_err(|e| Error::Transaction(format!("Invalid block hash: {}", e)))?;
-
-        if hash_bytes.len() != 32 {
-            return Err(Error::Transaction(
-                "Block hash must be 32 bytes".to_string(),
-            ));
-        }
+    pub async fn get_block_by_hash(&self, block_hash: &BlockHash) -> Result<BlockInfo, Error> {
+        debug!("Fetching block by hash: {}", block_hash);
 
-        let mut hash_array = [0u8; 32];
-        hash_array.copy_from_slice(&hash_bytes);
-        let block_hash: subxt::utils::H256 = hash_array.into();
+        let block_hash = subxt::utils::H256(block_hash.into_bytes());
 
         // Query the block
         let block = self
@@ -190,133 +271,145 @@ impl BlockQuery {
     }
 
     /// Parse block information from a subxt Block
-    async fn parse_block_info(
-        &self,
-        block: subxt::blocks::Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
-    ) -> Result<BlockInfo, Error> {
+    async fn parse_block_info(&self, block: Block) -> Result<BlockInfo, Error> {
+        let header = block.header();
         let number = block.number() as u64;
-        let hash = format!("0x{}", hex::encode(block.hash()));
-        let parent_hash = format!("0x{}", hex::encode(block.header().parent_hash));
 
-        // Extract timestamp
-        let timestamp = self.extract_timestamp(&block).await?;
+        let mut info = BlockInfo {
+            number,
+            hash: format!("0x{}", hex::encode(block.hash())),
+            parent_hash: format!("0x{}", hex::encode(header.parent_hash)),
+            timestamp: self.extract_timestamp(&block).await,
+            transactions: Vec::new(),
+            state_root: Some(format!("0x{}", hex::encode(header.state_root))),
+            extrinsics_root: Some(format!("0x{}", hex::encode(header.extrinsics_root))),
+            extrinsic_count: 0,
+            event_count: None,
+            is_finalized: false,
+            extrinsics: Vec::new(),
+            author: None,
+        };
+
+        if self.options.extrinsics {
+            let extrinsics = block
+                .extrinsics()
+                .await
+                .map_err(|e| Error::Transaction(format!("Failed to get extrinsics: {}", e)))?;
 
-        // Get extrinsics and compute hashes
-        let extrinsics = block
-            .extrinsics()
-            .await
-            .map_err(|e| Error::Transaction(format!("Failed to get extrinsics: {}", e)))?;
+            for ext in extrinsics.iter() {
+                let hash = format!("0x{}", hex::encode(sp_core::blake2_256(ext.bytes())));
+                info.transactions.push(hash.clone());
+                info.extrinsics.push(BlockExtrinsic {
+                    index: ext.index(),
+                    hash,
+                    pallet: ext.pallet_name().unwrap_or("Unknown").to_string(),
+                    call: ext.variant_name().unwrap_or("Unknown").to_string(),
+                    signed: ext.is_signed(),
+                    success: None,
+                    event_count: None,
+                });
+            }
+            info.extrinsic_count = info.extrinsics.len() as u32;
+        }
 
-        let mut transactions = Vec::new();
-        let extrinsic_count = extrinsics.len() as u32;
+        if self.options.events {
+            // Events of pruned blocks cannot be read; leave the counts unknown
+            if let Ok(events) = block.events().await {
+                let outcomes = extrinsic_outcomes(events.iter().flatten().map(|event| {
+                    (
+                        event.phase(),
+                        event.pallet_name() == "System",
+                        event.variant_name().to_string(),
+                    )
+                }));
+                info.event_count = Some(events.len());
+                for extrinsic in &mut info.extrinsics {
+                    let outcome = outcomes.get(&extrinsic.index);
+                    extrinsic.event_count = Some(outcome.map_or(0, |o| o.event_count));
+                    extrinsic.success = outcome.and_then(|o| o.success);
+                }
+            }
+        }
 
-        for ext_details in extrinsics.iter() {
-            let ext_bytes = ext_details.bytes();
-            let hash = sp_core::blake2_256(ext_bytes);
-            transactions.push(format!("0x{}", hex::encode(hash)));
+        if self.options.author {
+            info.author = self
+                .extract_author(&block)
+                .await
+                .map(|account| format!("0x{}", hex::encode(account)));
         }
 
-        // Check finality
-        let is_finalized = self.check_finality(block.hash()).await?;
+        if self.options.finality {
+            info.is_finalized = self.check_finality(number).await?;
+        }
 
-        // Get state root and extrinsics root from header
-        let state_root = Some(format!("0x{}", hex::encode(block.header().state_root)));
-        let extrinsics_root = Some(format!("0x{}", hex::encode(block.header().extrinsics_root)));
+        Ok(info)
+    }
 
-        // Count events (we'll do a quick count without full parsing for basic info)
-        let event_count = self.count_block_events(&block).await.ok();
+    /// Extract timestamp from block, in seconds
+    ///
+    /// Reads `Timestamp::Now` at the block. Blocks whose state has been
+    /// pruned fall back to the current time.
+    async fn extract_timestamp(&self, block: &Block) -> u64 {
+        let address = subxt::dynamic::storage("Timestamp", "Now", ());
+        let millis = match block.storage().fetch(&address).await {
+            Ok(Some(value)) => u64::decode(&mut value.encoded()).ok(),
+            _ => None,
+        };
 
-        Ok(BlockInfo {
-            number,
-            hash,
-            parent_hash,
-            timestamp,
-            transactions,
-            state_root,
-            extrinsics_root,
-            extrinsic_count,
-            event_count,
-            is_finalized,
+        millis.map(|ms| ms / 1000).unwrap_or_else(|| {
+            debug!(
+                "Timestamp::Now unavailable for block {}, using current time",
+                block.number()
+            );
+            chrono::Utc::now().timestamp() as u64
         })
     }
 
-    /// Extract timestamp from block
+    /// Account of the block author
     ///
-    /// Uses multiple fallback methods:
-    /// 1. Query Timestamp pallet storage at block hash
-    /// 2. Scan for Timestamp::set extrinsic
-    /// 3. Use current time as last resort (with warning)
-    async fn extract_timestamp(
-        &self,
-        block: &subxt::blocks::Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
-    ) -> Result<u64, Error> {
-        // For now, extract timestamp from block header's inherent data
-        // Most Substrate chains include timestamp as an inherent extrinsic
-        // We'll scan for the Timestamp::set call
-        if let Ok(extrinsics) = block.extrinsics().await {
-            for ext in extrinsics.iter() {
-                if let Ok(pallet) = ext.pallet_name() {
-                    if pallet == "Timestamp" {
-                        if let Ok(call) = ext.variant_name() {
-                            if call == "set" {
-                                // Timestamp extrinsic found
-                                // For now, use a heuristic based on block time
-                                // In production, this would decode the extrinsic parameters
-                                debug!(
-                                    "Found Timestamp::set extrinsic in block {}",
-                                    block.number()
-                                );
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    /// BABE pre-digests name the authority index directly; Aura ones give
+    /// the slot, whose author is `slot % authorities`. The index is looked up
+    /// in `Session::Validators`, or `Aura::Authorities` on chains without
+    /// sessions.
+    async fn extract_author(&self, block: &Block) -> Option<[u8; 32]> {
+        let author = block_author(&block.header().digest)?;
+        let validators = match self.account_list(block, "Session", "Validators").await {
+            Some(validators) if !validators.is_empty() => validators,
+            _ => self.account_list(block, "Aura", "Authorities").await?,
+        };
+        let index = match author {
+            BlockAuthor::Authority(index) => index as usize,
+            BlockAuthor::Slot(slot) => (slot % validators.len() as u64) as usize,
+        };
+        validators.get(index).copied()
+    }
 
-        // Use current time as approximation
-        // Note: This is a limitation of the dynamic API approach
-        // For accurate timestamps, use typed metadata
-        debug!(
-            "Using current time as timestamp for block {} (dynamic API limitation)",
-            block.number()
-        );
-        Ok(chrono::Utc::now().timestamp() as u64)
+    /// A storage value holding a list of 32-byte accounts or keys, at `block`
+    async fn account_list(
+        &self,
+        block: &Block,
+        pallet: &str,
+        entry: &str,
+    ) -> Option<Vec<[u8; 32]>> {
+        let address = subxt::dynamic::storage(pallet, entry, ());
+        let value = block.storage().fetch(&address).await.ok()??;
+        Vec::<[u8; 32]>::decode(&mut value.encoded()).ok()
     }
 
     /// Check if a block is finalized
     ///
-    /// This is a best-effort check. If the block is older than 100 blocks from
-    /// the current head, we assume it's finalized. For recent blocks, we check
-    /// if they're older than the typical finalization depth.
-    async fn check_finality(&self, block_hash: subxt::utils::H256) -> Result<bool, Error> {
-        // Get latest block to determine how far back this block is
-        let latest_block = self
+    /// A block is finalized once the finalized head has reached its number.
+    /// Blocks are fetched by hash from the canonical chain or by number below
+    /// the finalized head, so a number is enough.
+    async fn check_finality(&self, block_number: u64) -> Result<bool, Error> {
+        let finalized = self
             .client
             .blocks()
             .at_latest()
             .await
-            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?;
-
-        let latest_number = latest_block.number();
-
-        // Get the block we're checking
-        let check_block = self
-            .client
-            .blocks()
-            .at(block_hash)
-            .await
-            .map_err(|e| Error::Connection(format!("Failed to get block: {}", e)))?;
+            .map_err(|e| Error::Connection(format!("Failed to get finalized block: {}", e)))?;
 
-        let block_number = check_block.number();
-
-        // If block is more than 100 blocks old, it's almost certainly finalized
-        // (typical finalization is 2-3 blocks for most Substrate chains)
-        if latest_number.saturating_sub(block_number) > 100 {
-            return Ok(true);
-        }
-
-        // For recent blocks, be conservative and mark as not finalized
-        Ok(false)
+        Ok(block_number <= finalized.number() as u64)
     }
 
     /// Extract extrinsic information from a block
@@ -407,39 +500,143 @@ impl BlockQuery {
 
         Ok(all_events)
     }
+}
 
-    /// Count events in a block (lightweight, no full parsing)
-    async fn count_block_events(
-        &self,
-        block: &subxt::blocks::Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
-    ) -> Result<u32, Error> {
-        let extrinsics = block
-            .extrinsics()
-            .await
-            .map_err(|e| Error::Transaction(format!("Failed to get extrinsics: {}", e)))?;
+/// Who authored a block, as named by its consensus pre-digest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockAuthor {
+    /// Index into the authority set (BABE)
+    Authority(u32),
+    /// Slot the block was authored in (Aura)
+    Slot(u64),
+}
 
-        let mut count = 0u32;
-        for ext_details in extrinsics.iter() {
-            if let Ok(events) = ext_details.events().await {
-                count += events.iter().count() as u32;
-            }
+/// Author named by a block's BABE or Aura pre-runtime digest
+fn block_author(digest: &Digest) -> Option<BlockAuthor> {
+    digest.logs.iter().find_map(|item| match item {
+        // Every BABE pre-digest variant starts with the authority index
+        DigestItem::PreRuntime(BABE_ENGINE_ID, data) => data
+            .get(1..5)
+            .and_then(|bytes| <[u8; 4]>::try_from(bytes).ok())
+            .map(|bytes| BlockAuthor::Authority(u32::from_le_bytes(bytes))),
+        DigestItem::PreRuntime(AURA_ENGINE_ID, data) => {
+            u64::decode(&mut &data[..]).ok().map(BlockAuthor::Slot)
         }
+        _ => None,
+    })
+}
+
+/// Events and dispatch outcome of one extrinsic
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct ExtrinsicOutcome {
+    event_count: u32,
+    success: Option<bool>,
+}
 
-        Ok(count)
+/// Outcome per extrinsic index from `(phase, is_system_event, variant)`
+/// for each event of a block
+fn extrinsic_outcomes(
+    events: impl IntoIterator<Item = (Phase, bool, String)>,
+) -> HashMap<u32, ExtrinsicOutcome> {
+    let mut outcomes: HashMap<u32, ExtrinsicOutcome> = HashMap::new();
+    for (phase, system, variant) in events {
+        let Phase::ApplyExtrinsic(index) = phase else {
+            continue;
+        };
+        let outcome = outcomes.entry(index).or_default();
+        outcome.event_count += 1;
+        if system {
+            match variant.as_str() {
+                "ExtrinsicSuccess" => outcome.success = Some(true),
+                "ExtrinsicFailed" => outcome.success = Some(false),
+                _ => {}
+            }
+        }
     }
+    outcomes
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_block_hash_parsing() {
-        // Test with 0x prefix
-        let hash = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
-        let stripped = hash.trim_start_matches("0x");
-        assert_eq!(stripped.len(), 64);
-
-        // Test without prefix
-        let hash2 = "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
-        assert_eq!(hash2.len(), 64);
+        let hex = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+        let hash: BlockHash = hex.parse().unwrap();
+        assert_eq!(hash.as_bytes()[..2], [0x12, 0x34]);
+        assert_eq!(hash.to_string(), hex);
+
+        // The prefix and all 32 bytes are required
+        assert!(hex.trim_start_matches("0x").parse::<BlockHash>().is_err());
+        assert!(hex[..64].parse::<BlockHash>().is_err());
+    }
+
+    #[test]
+    fn test_block_author_from_digest() {
+        // BABE SecondaryPlain { authority_index: 7, slot }
+        let mut babe = vec![2u8];
+        babe.extend(7u32.to_le_bytes());
+        babe.extend(300_000_000u64.to_le_bytes());
+        let digest = Digest {
+            logs: vec![
+                DigestItem::Other(vec![1, 2, 3]),
+                DigestItem::PreRuntime(BABE_ENGINE_ID, babe),
+            ],
+        };
+        assert_eq!(block_author(&digest), Some(BlockAuthor::Authority(7)));
+
+        let digest = Digest {
+            logs: vec![DigestItem::PreRuntime(
+                AURA_ENGINE_ID,
+                290_000_123u64.to_le_bytes().to_vec(),
+            )],
+        };
+        assert_eq!(block_author(&digest), Some(BlockAuthor::Slot(290_000_123)));
+
+        let digest = Digest {
+            logs: vec![DigestItem::Seal(BABE_ENGINE_ID, vec![0; 64])],
+        };
+        assert_eq!(block_author(&digest), None);
+    }
+
+    #[test]
+    fn test_extrinsic_outcomes() {
+        let event = |phase, system, variant: &str| (phase, system, variant.to_string());
+        let outcomes = extrinsic_outcomes([
+            event(Phase::Initialization, false, "NewSession"),
+            event(Phase::ApplyExtrinsic(0), true, "ExtrinsicSuccess"),
+            event(Phase::ApplyExtrinsic(1), false, "Withdraw"),
+            event(Phase::ApplyExtrinsic(1), false, "TransactionFeePaid"),
+            event(Phase::ApplyExtrinsic(1), true, "ExtrinsicFailed"),
+            // Another pallet's event named like a System one
+            event(Phase::ApplyExtrinsic(2), false, "ExtrinsicSuccess"),
+            event(Phase::Finalization, false, "Rewarded"),
+        ]);
+
+        assert_eq!(
+            outcomes[&0],
+            ExtrinsicOutcome {
+                event_count: 1,
+                success: Some(true)
+            }
+        );
+        assert_eq!(
+            outcomes[&1],
+            ExtrinsicOutcome {
+                event_count: 3,
+                success: Some(false)
+            }
+        );
+        assert_eq!(outcomes[&2].success, None);
+        assert_eq!(outcomes.len(), 3);
+    }
+
+    #[test]
+    fn test_block_options() {
+        assert_eq!(BlockOptions::default(), BlockOptions::full());
+        let options = BlockOptions::header_only().with_extrinsics(true);
+        assert!(options.extrinsics);
+        assert!(!options.events && !options.author && !options.finality);
     }
 }
@@ -262,7 +262,10 @@ impl Cache {
     }
 
     /// Get block by hash from cache
-    pub fn get_block_by_hash(&self, block_hash: &str) -> Option<apex_sdk_core::BlockInfo> {
+    pub fn get_block_by_hash(
+        &self,
+        block_hash: &apex_sdk_types::BlockHash,
+    ) -> Option<apex_sdk_core::BlockInfo> {
         let key = format!("block:hash:{}", block_hash);
         let mut cache = self.block_cache.write();
         if let Some(entry) = cache.get(&key) {
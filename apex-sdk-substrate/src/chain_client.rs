@@ -0,0 +1,1215 @@
+//! Chain client abstraction
+//!
+//! subxt major releases regularly change the dynamic value, storage and
+//! event APIs. [`ChainClient`] wraps the node interactions the adapter relies
+//! on — storage reads, constants, runtime API calls, extrinsic submission,
+//! block hashes and finalized block subscriptions — behind SCALE bytes,
+//! 32-byte hashes and dynamic values, so a subxt upgrade is contained in
+//! [`SubxtClient`].
+//!
+//! Implement the trait to run storage-backed components such as
+//! [`StorageClient`](crate::StorageClient) against an in-memory chain in
+//! tests.
+
+use crate::{Error, Result};
+use async_trait::async_trait;
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::backend::rpc::RpcClient;
+use subxt::ext::futures::stream::{BoxStream, StreamExt};
+use subxt::ext::scale_value::scale::decode_as_type;
+use subxt::ext::subxt_rpcs::rpc_params;
+use subxt::utils::H256;
+use subxt::{Metadata, OnlineClient, PolkadotConfig};
+use tracing::debug;
+
+/// Dynamic value used for storage keys and decoded storage values
+pub use subxt::dynamic::Value;
+
+/// A storage entry addressed by pallet, item and map keys
+#[derive(Debug, Clone)]
+pub struct StorageKey {
+    /// Pallet name
+    pub pallet: String,
+    /// Storage item name
+    pub item: String,
+    /// Map keys; empty for plain storage values
+    pub keys: Vec<Value>,
+}
+
+impl StorageKey {
+    /// Address a storage entry
+    pub fn new(pallet: impl Into<String>, item: impl Into<String>, keys: Vec<Value>) -> Self {
+        Self {
+            pallet: pallet.into(),
+            item: item.into(),
+            keys,
+        }
+    }
+}
+
+/// A storage value, as stored and decoded
+#[derive(Debug, Clone)]
+pub struct StorageValue {
+    /// SCALE-encoded value
+    pub bytes: Vec<u8>,
+    /// Value decoded with the runtime metadata
+    pub value: Value,
+}
+
+/// A finalized block announced by a subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FinalizedHead {
+    /// Block number
+    pub number: u64,
+    /// Block hash
+    pub hash: [u8; 32],
+}
+
+/// Node interactions used by the Substrate adapter
+#[async_trait]
+pub trait ChainClient: Send + Sync {
+    /// Runtime metadata of the connected chain
+    fn metadata(&self) -> Metadata;
+
+    /// Hash of the latest finalized block
+    async fn finalized_block_hash(&self) -> Result<[u8; 32]>;
+
+    /// Read a storage entry at `at`, or at the latest block
+    async fn storage(&self, key: &StorageKey, at: Option<[u8; 32]>)
+        -> Result<Option<StorageValue>>;
+
+    /// Read several storage entries at the same block
+    ///
+    /// Returns one value per key, in key order.
+    async fn storage_batch(
+        &self,
+        keys: &[StorageKey],
+        at: [u8; 32],
+    ) -> Result<Vec<Option<StorageValue>>>;
+
+    /// Read every entry of a storage map at the latest block, as raw keys and
+    /// values
+    async fn storage_entries(&self, pallet: &str, item: &str) -> Result<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// Read up to `limit` entries of a storage map at the latest block, in
+    /// raw key order, starting after the raw key `start_after`
+    ///
+    /// The default implementation reads the whole map with
+    /// [`storage_entries`](Self::storage_entries).
+    async fn storage_entries_paged(
+        &self,
+        pallet: &str,
+        item: &str,
+        start_after: Option<&[u8]>,
+        limit: u32,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let entries = self.storage_entries(pallet, item).await?;
+        Ok(page_entries(entries, start_after, limit))
+    }
+
+    /// Read up to `limit` entries under a partial storage key at `at`, or at
+    /// the latest finalized block, in raw key order, starting after the raw
+    /// key `start_after`
+    ///
+    /// `prefix.keys` holds the leading map keys, such as the first key of a
+    /// double map; with none, the whole map is read. The default
+    /// implementation only reads whole maps at the latest block, with
+    /// [`storage_entries_paged`](Self::storage_entries_paged).
+    async fn storage_prefix_paged(
+        &self,
+        prefix: &StorageKey,
+        start_after: Option<&[u8]>,
+        limit: u32,
+        at: Option<[u8; 32]>,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        if !prefix.keys.is_empty() || at.is_some() {
+            return Err(Error::Storage(format!(
+                "Iterating {}::{} by partial key or at a block is not supported by this client",
+                prefix.pallet, prefix.item
+            )));
+        }
+        self.storage_entries_paged(&prefix.pallet, &prefix.item, start_after, limit)
+            .await
+    }
+
+    /// Read up to `limit` entries of a default child trie at the latest
+    /// finalized block, in raw key order, starting after the raw key
+    /// `start_after`
+    ///
+    /// `child_key` is the trie's unprefixed key, as the owning pallet
+    /// derives it. The default implementation does not support child tries.
+    async fn child_storage_entries_paged(
+        &self,
+        child_key: &[u8],
+        start_after: Option<&[u8]>,
+        limit: u32,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let _ = (child_key, start_after, limit);
+        Err(Error::Storage(
+            "Child storage is not supported by this client".to_string(),
+        ))
+    }
+
+    /// Read an entry of a default child trie at the latest finalized block
+    ///
+    /// The default implementation does not support child tries.
+    async fn child_storage(&self, child_key: &[u8], key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let _ = (child_key, key);
+        Err(Error::Storage(
+            "Child storage is not supported by this client".to_string(),
+        ))
+    }
+
+    /// SCALE-encoded runtime constant
+    fn constant(&self, pallet: &str, name: &str) -> Result<Vec<u8>>;
+
+    /// Call a runtime API method with SCALE-encoded parameters at the latest
+    /// block
+    async fn runtime_call(&self, method: &str, params: &[u8]) -> Result<Vec<u8>>;
+
+    /// The node's `system_properties`: token symbol, decimals and SS58 format
+    async fn system_properties(&self) -> Result<serde_json::Map<String, serde_json::Value>>;
+
+    /// Submit a signed extrinsic, returning its hash
+    async fn submit_extrinsic(&self, extrinsic: &[u8]) -> Result<[u8; 32]>;
+
+    /// Subscribe to finalized blocks
+    async fn subscribe_finalized_heads(&self) -> Result<BoxStream<'static, Result<FinalizedHead>>>;
+
+    /// Hash of the canonical block at `number`, or `None` if the chain has
+    /// not reached it yet
+    async fn block_hash(&self, number: u64) -> Result<Option<[u8; 32]>>;
+
+    /// Number of the best block, which may not be finalized yet
+    async fn best_block_number(&self) -> Result<u64>;
+}
+
+/// Storage key of a default child trie as RPC methods take it
+fn prefixed_child_key(child_key: &[u8]) -> String {
+    let mut key = b":child_storage:default:".to_vec();
+    key.extend_from_slice(child_key);
+    to_hex(&key)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn from_hex(value: &str) -> Result<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| Error::Storage(format!("Node returned invalid hex {}: {}", value, e)))
+}
+
+/// Up to `limit` entries after `start_after`, in raw key order
+fn page_entries(
+    mut entries: Vec<(Vec<u8>, Vec<u8>)>,
+    start_after: Option<&[u8]>,
+    limit: u32,
+) -> Vec<(Vec<u8>, Vec<u8>)> {
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+        .into_iter()
+        .filter(|(key, _)| start_after.is_none_or(|start| key.as_slice() > start))
+        .take(limit as usize)
+        .collect()
+}
+
+/// [`ChainClient`] backed by subxt
+#[derive(Clone)]
+pub struct SubxtClient {
+    client: OnlineClient<PolkadotConfig>,
+    rpc: Option<LegacyRpcMethods<PolkadotConfig>>,
+    /// For RPC methods `LegacyRpcMethods` does not cover
+    raw_rpc: Option<RpcClient>,
+}
+
+impl SubxtClient {
+    /// Wrap a subxt client
+    ///
+    /// Without direct RPC access, batched storage reads fall back to one
+    /// request per key; prefer [`from_rpc_client`](Self::from_rpc_client).
+    pub fn new(client: OnlineClient<PolkadotConfig>) -> Self {
+        Self {
+            client,
+            rpc: None,
+            raw_rpc: None,
+        }
+    }
+
+    /// Connect a subxt client over `rpc`, keeping direct access to the
+    /// node's RPC methods
+    pub async fn from_rpc_client(rpc: RpcClient) -> Result<Self> {
+        let client = OnlineClient::<PolkadotConfig>::from_rpc_client(rpc.clone())
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to connect: {}", e)))?;
+        Ok(Self {
+            client,
+            rpc: Some(LegacyRpcMethods::new(rpc.clone())),
+            raw_rpc: Some(rpc),
+        })
+    }
+
+    /// Underlying subxt client
+    pub fn online_client(&self) -> &OnlineClient<PolkadotConfig> {
+        &self.client
+    }
+
+    fn address_bytes(&self, key: &StorageKey) -> Result<Vec<u8>> {
+        self.client
+            .storage()
+            .address_bytes(&subxt::dynamic::storage(
+                key.pallet.as_str(),
+                key.item.as_str(),
+                key.keys.clone(),
+            ))
+            .map_err(|e| {
+                Error::Storage(format!(
+                    "Failed to encode storage key {}::{}: {}",
+                    key.pallet, key.item, e
+                ))
+            })
+    }
+
+    fn decode(&self, key: &StorageKey, bytes: Vec<u8>) -> Result<StorageValue> {
+        let metadata = self.client.metadata();
+        let value_ty = storage_value_type(&metadata, &key.pallet, &key.item)?;
+
+        let value = decode_as_type(&mut &bytes[..], value_ty, metadata.types())
+            .map_err(|e| {
+                Error::Storage(format!(
+                    "Failed to decode {}::{}: {}",
+                    key.pallet, key.item, e
+                ))
+            })?
+            .remove_context();
+        Ok(StorageValue { bytes, value })
+    }
+
+    async fn storage_at(
+        &self,
+        at: Option<[u8; 32]>,
+    ) -> Result<subxt::storage::Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>> {
+        match at {
+            Some(hash) => Ok(self.client.storage().at(H256(hash))),
+            None => self
+                .client
+                .storage()
+                .at_latest()
+                .await
+                .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e))),
+        }
+    }
+
+    fn raw_rpc(&self, method: &str) -> Result<&RpcClient> {
+        self.raw_rpc.as_ref().ok_or_else(|| {
+            Error::Connection(format!(
+                "{} needs direct RPC access; use SubxtClient::from_rpc_client",
+                method
+            ))
+        })
+    }
+
+    /// Every entry under `prefix`, read with subxt's key iteration
+    async fn iter_entries(
+        &self,
+        prefix: &StorageKey,
+        at: Option<[u8; 32]>,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let storage_query = subxt::dynamic::storage(
+            prefix.pallet.as_str(),
+            prefix.item.as_str(),
+            prefix.keys.clone(),
+        );
+        let mut iter = self
+            .storage_at(at)
+            .await?
+            .iter(storage_query)
+            .await
+            .map_err(|e| {
+                Error::Storage(format!(
+                    "Failed to iterate storage {}::{}: {}",
+                    prefix.pallet, prefix.item, e
+                ))
+            })?;
+
+        let mut results = Vec::new();
+        while let Some(result) = iter.next().await {
+            let kv_pair = result
+                .map_err(|e| Error::Storage(format!("Failed to fetch storage entry: {}", e)))?;
+            results.push((kv_pair.key_bytes, kv_pair.value.encoded().to_vec()));
+        }
+        Ok(results)
+    }
+}
+
+/// Type id of a storage entry's value in `metadata`
+pub(crate) fn storage_value_type(metadata: &Metadata, pallet: &str, item: &str) -> Result<u32> {
+    metadata
+        .pallet_by_name(pallet)
+        .and_then(|pallet| pallet.storage())
+        .and_then(|storage| storage.entry_by_name(item))
+        .map(|entry| entry.entry_type().value_ty())
+        .ok_or_else(|| Error::Metadata(format!("Storage entry {}::{} not found", pallet, item)))
+}
+
+#[async_trait]
+impl ChainClient for SubxtClient {
+    fn metadata(&self) -> Metadata {
+        self.client.metadata()
+    }
+
+    async fn finalized_block_hash(&self) -> Result<[u8; 32]> {
+        let block = self
+            .client
+            .backend()
+            .latest_finalized_block_ref()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch finalized block: {}", e)))?;
+        Ok(block.hash().0)
+    }
+
+    async fn storage(
+        &self,
+        key: &StorageKey,
+        at: Option<[u8; 32]>,
+    ) -> Result<Option<StorageValue>> {
+        let bytes = self
+            .storage_at(at)
+            .await?
+            .fetch_raw(self.address_bytes(key)?)
+            .await
+            .map_err(|e| {
+                Error::Storage(format!(
+                    "Failed to query storage {}::{}: {}",
+                    key.pallet, key.item, e
+                ))
+            })?;
+
+        bytes.map(|bytes| self.decode(key, bytes)).transpose()
+    }
+
+    async fn storage_batch(
+        &self,
+        keys: &[StorageKey],
+        at: [u8; 32],
+    ) -> Result<Vec<Option<StorageValue>>> {
+        let raw_keys = keys
+            .iter()
+            .map(|key| self.address_bytes(key))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut values = std::collections::HashMap::new();
+        match &self.rpc {
+            Some(rpc) => {
+                debug!(keys = raw_keys.len(), "Querying storage batch");
+                let change_sets = rpc
+                    .state_query_storage_at(raw_keys.iter().map(Vec::as_slice), Some(H256(at)))
+                    .await
+                    .map_err(|e| Error::Storage(format!("Batched storage query failed: {}", e)))?;
+                for change_set in change_sets {
+                    for (key, value) in change_set.changes {
+                        if let Some(value) = value {
+                            values.insert(key.0, value.0);
+                        }
+                    }
+                }
+            }
+            None => {
+                let mut responses = self
+                    .client
+                    .backend()
+                    .storage_fetch_values(raw_keys.clone(), H256(at))
+                    .await
+                    .map_err(|e| Error::Storage(format!("Batched storage query failed: {}", e)))?;
+                while let Some(response) = responses.next().await {
+                    let response = response.map_err(|e| {
+                        Error::Storage(format!("Batched storage query failed: {}", e))
+                    })?;
+                    values.insert(response.key, response.value);
+                }
+            }
+        }
+
+        keys.iter()
+            .zip(&raw_keys)
+            .map(|(key, raw_key)| {
+                // A key may appear more than once in a batch
+                values
+                    .get(raw_key)
+                    .map(|bytes| self.decode(key, bytes.clone()))
+                    .transpose()
+            })
+            .collect()
+    }
+
+    async fn storage_entries(&self, pallet: &str, item: &str) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.iter_entries(&StorageKey::new(pallet, item, Vec::new()), None)
+            .await
+    }
+
+    async fn storage_entries_paged(
+        &self,
+        pallet: &str,
+        item: &str,
+        start_after: Option<&[u8]>,
+        limit: u32,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.storage_prefix_paged(
+            &StorageKey::new(pallet, item, Vec::new()),
+            start_after,
+            limit,
+            None,
+        )
+        .await
+    }
+
+    async fn storage_prefix_paged(
+        &self,
+        prefix: &StorageKey,
+        start_after: Option<&[u8]>,
+        limit: u32,
+        at: Option<[u8; 32]>,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let Some(rpc) = &self.rpc else {
+            let entries = self.iter_entries(prefix, at).await?;
+            return Ok(page_entries(entries, start_after, limit));
+        };
+
+        // Keys and values are read at the same block so a page is consistent
+        let at = H256(match at {
+            Some(hash) => hash,
+            None => self.finalized_block_hash().await?,
+        });
+        let prefix_bytes = self.address_bytes(prefix)?;
+        let keys = rpc
+            .state_get_keys_paged(&prefix_bytes, limit, start_after, Some(at))
+            .await
+            .map_err(|e| {
+                Error::Storage(format!(
+                    "Failed to page storage keys {}::{}: {}",
+                    prefix.pallet, prefix.item, e
+                ))
+            })?;
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut values = std::collections::HashMap::new();
+        for change_set in rpc
+            .state_query_storage_at(keys.iter().map(Vec::as_slice), Some(at))
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to fetch storage page: {}", e)))?
+        {
+            for (key, value) in change_set.changes {
+                if let Some(value) = value {
+                    values.insert(key.0, value.0);
+                }
+            }
+        }
+
+        Ok(keys
+            .into_iter()
+            .filter_map(|key| values.remove(&key).map(|value| (key, value)))
+            .collect())
+    }
+
+    async fn child_storage_entries_paged(
+        &self,
+        child_key: &[u8],
+        start_after: Option<&[u8]>,
+        limit: u32,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let rpc = self.raw_rpc("childstate_getKeysPaged")?;
+        let child_key = prefixed_child_key(child_key);
+        let at = to_hex(&self.finalized_block_hash().await?);
+
+        let keys: Vec<String> = rpc
+            .request(
+                "childstate_getKeysPaged",
+                rpc_params![&child_key, "0x", limit, start_after.map(to_hex), &at],
+            )
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to page child storage keys: {}", e)))?;
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Keys and values are read at the same block so a page is consistent
+        let values: Vec<Option<String>> = rpc
+            .request(
+                "childstate_getStorageEntries",
+                rpc_params![&child_key, &keys, &at],
+            )
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to fetch child storage page: {}", e)))?;
+
+        keys.iter()
+            .zip(values)
+            .filter_map(|(key, value)| value.map(|value| Ok((from_hex(key)?, from_hex(&value)?))))
+            .collect()
+    }
+
+    async fn child_storage(&self, child_key: &[u8], key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let rpc = self.raw_rpc("childstate_getStorage")?;
+        let at = to_hex(&self.finalized_block_hash().await?);
+        let value: Option<String> = rpc
+            .request(
+                "childstate_getStorage",
+                rpc_params![prefixed_child_key(child_key), to_hex(key), at],
+            )
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query child storage: {}", e)))?;
+        value.map(|value| from_hex(&value)).transpose()
+    }
+
+    fn constant(&self, pallet: &str, name: &str) -> Result<Vec<u8>> {
+        let value = self
+            .client
+            .constants()
+            .at(&subxt::dynamic::constant(pallet, name))
+            .map_err(|e| Error::Storage(format!("Failed to get constant: {}", e)))?;
+        Ok(value.encoded().to_vec())
+    }
+
+    async fn runtime_call(&self, method: &str, params: &[u8]) -> Result<Vec<u8>> {
+        self.client
+            .runtime_api()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?
+            .call_raw(method, Some(params))
+            .await
+            .map_err(|e| Error::Transaction(format!("Runtime API call {} failed: {}", method, e)))
+    }
+
+    async fn system_properties(&self) -> Result<serde_json::Map<String, serde_json::Value>> {
+        let rpc = self.rpc.as_ref().ok_or_else(|| {
+            Error::Connection(
+                "system_properties needs direct RPC access; use SubxtClient::from_rpc_client"
+                    .to_string(),
+            )
+        })?;
+        rpc.system_properties()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch system properties: {}", e)))
+    }
+
+    async fn submit_extrinsic(&self, extrinsic: &[u8]) -> Result<[u8; 32]> {
+        let hash = match &self.rpc {
+            Some(rpc) => rpc
+                .author_submit_extrinsic(extrinsic)
+                .await
+                .map_err(|e| Error::Transaction(format!("Failed to submit extrinsic: {}", e)))?,
+            None => subxt::tx::SubmittableTransaction::from_bytes(
+                self.client.clone(),
+                extrinsic.to_vec(),
+            )
+            .submit()
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to submit extrinsic: {}", e)))?,
+        };
+        Ok(hash.0)
+    }
+
+    async fn subscribe_finalized_heads(&self) -> Result<BoxStream<'static, Result<FinalizedHead>>> {
+        let subscription = self
+            .client
+            .blocks()
+            .subscribe_finalized()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to subscribe to blocks: {}", e)))?;
+
+        Ok(subscription
+            .map(|block| {
+                block
+                    .map(|block| FinalizedHead {
+                        number: block.number() as u64,
+                        hash: block.hash().0,
+                    })
+                    .map_err(|e| Error::Connection(format!("Block subscription failed: {}", e)))
+            })
+            .boxed())
+    }
+
+    async fn block_hash(&self, number: u64) -> Result<Option<[u8; 32]>> {
+        if let Some(rpc) = &self.rpc {
+            let hash = rpc
+                .chain_get_block_hash(Some(number.into()))
+                .await
+                .map_err(|e| Error::Connection(format!("Failed to get block hash: {}", e)))?;
+            return Ok(hash.map(|hash| hash.0));
+        }
+
+        // Without direct RPC access, walk parent hashes back from the head
+        let mut block = self
+            .client
+            .blocks()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?;
+        let latest = block.number() as u64;
+        if number > latest {
+            return Ok(None);
+        }
+        for _ in number..latest {
+            block = self
+                .client
+                .blocks()
+                .at(block.header().parent_hash)
+                .await
+                .map_err(|e| Error::Connection(format!("Failed to navigate to block: {}", e)))?;
+        }
+        Ok(Some(block.hash().0))
+    }
+
+    async fn best_block_number(&self) -> Result<u64> {
+        if let Some(rpc) = &self.rpc {
+            let header = rpc
+                .chain_get_header(None)
+                .await
+                .map_err(|e| Error::Connection(format!("Failed to get best header: {}", e)))?
+                .ok_or_else(|| Error::Connection("Node returned no best header".to_string()))?;
+            return Ok(header.number as u64);
+        }
+
+        // Without direct RPC access, the latest block subxt knows is finalized
+        let block = self
+            .client
+            .blocks()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?;
+        Ok(block.number() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parity_scale_codec::Encode;
+    use scale_info::{meta_type, TypeInfo};
+    use serde_json::json;
+    use sp_core::hashing::{blake2_128, twox_128};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use subxt::backend::rpc::{RawRpcFuture, RawRpcSubscription, RawValue, RpcClientT};
+    use subxt::client::RuntimeVersion;
+    use subxt::ext::frame_metadata::{
+        v14::{
+            PalletStorageMetadata, StorageEntryMetadata, StorageEntryModifier, StorageEntryType,
+            StorageHasher,
+        },
+        v15::{CustomMetadata, ExtrinsicMetadata, OuterEnums, PalletMetadata, RuntimeMetadataV15},
+        RuntimeMetadataPrefixed,
+    };
+    use subxt::ext::subxt_rpcs;
+
+    /// Answers each RPC method with a scripted response and records the
+    /// calls it receives
+    #[derive(Default)]
+    struct ScriptedRpc {
+        responses: HashMap<&'static str, std::result::Result<serde_json::Value, &'static str>>,
+        calls: Mutex<Vec<(String, serde_json::Value)>>,
+    }
+
+    impl ScriptedRpc {
+        fn ok(mut self, method: &'static str, response: serde_json::Value) -> Self {
+            self.responses.insert(method, Ok(response));
+            self
+        }
+
+        fn err(mut self, method: &'static str, message: &'static str) -> Self {
+            self.responses.insert(method, Err(message));
+            self
+        }
+
+        /// Parameters of every call to `method`, in call order
+        fn params(&self, method: &str) -> Vec<serde_json::Value> {
+            self.calls
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(called, _)| called == method)
+                .map(|(_, params)| params.clone())
+                .collect()
+        }
+    }
+
+    impl RpcClientT for ScriptedRpc {
+        fn request_raw<'a>(
+            &'a self,
+            method: &'a str,
+            params: Option<Box<RawValue>>,
+        ) -> RawRpcFuture<'a, Box<RawValue>> {
+            let params = params
+                .map(|params| serde_json::from_str(params.get()).unwrap())
+                .unwrap_or(serde_json::Value::Null);
+            self.calls
+                .lock()
+                .unwrap()
+                .push((method.to_string(), params));
+            let response = match self.responses.get(method) {
+                Some(Ok(value)) => Ok(RawValue::from_string(value.to_string()).unwrap()),
+                Some(Err(message)) => Err(subxt_rpcs::Error::Client((*message).into())),
+                None => Err(subxt_rpcs::Error::Client(
+                    format!("unscripted method {}", method).into(),
+                )),
+            };
+            Box::pin(async move { response })
+        }
+
+        fn subscribe_raw<'a>(
+            &'a self,
+            _sub: &'a str,
+            _params: Option<Box<RawValue>>,
+            _unsub: &'a str,
+        ) -> RawRpcFuture<'a, RawRpcSubscription> {
+            Box::pin(async { Err(subxt_rpcs::Error::Client("unsupported".into())) })
+        }
+    }
+
+    /// Metadata with a plain `System::Number` value and a `System::Account`
+    /// map from 32-byte account ids to balances
+    fn metadata() -> Metadata {
+        #[allow(unused)]
+        #[derive(TypeInfo)]
+        enum RuntimeCall {
+            System(Call),
+        }
+        #[allow(unused)]
+        #[derive(TypeInfo)]
+        enum Call {
+            Remark,
+        }
+
+        let entry = |name, ty| StorageEntryMetadata {
+            name,
+            modifier: StorageEntryModifier::Optional,
+            ty,
+            default: vec![],
+            docs: vec![],
+        };
+        let system = PalletMetadata {
+            name: "System",
+            storage: Some(PalletStorageMetadata {
+                prefix: "System",
+                entries: vec![
+                    entry("Number", StorageEntryType::Plain(meta_type::<u32>())),
+                    entry(
+                        "Account",
+                        StorageEntryType::Map {
+                            hashers: vec![StorageHasher::Blake2_128Concat],
+                            key: meta_type::<[u8; 32]>(),
+                            value: meta_type::<u128>(),
+                        },
+                    ),
+                ],
+            }),
+            calls: None,
+            event: None,
+            constants: vec![],
+            error: None,
+            index: 0,
+            docs: vec![],
+        };
+        let metadata = RuntimeMetadataV15::new(
+            vec![system],
+            ExtrinsicMetadata {
+                version: 4,
+                signed_extensions: vec![],
+                address_ty: meta_type::<()>(),
+                call_ty: meta_type::<RuntimeCall>(),
+                signature_ty: meta_type::<()>(),
+                extra_ty: meta_type::<()>(),
+            },
+            meta_type::<()>(),
+            vec![],
+            OuterEnums {
+                call_enum_ty: meta_type::<RuntimeCall>(),
+                event_enum_ty: meta_type::<()>(),
+                error_enum_ty: meta_type::<()>(),
+            },
+            CustomMetadata {
+                map: Default::default(),
+            },
+        );
+        Metadata::try_from(RuntimeMetadataPrefixed::from(metadata)).expect("valid metadata")
+    }
+
+    /// A client over `rpc`, with direct RPC access if `direct`
+    fn scripted_client(rpc: ScriptedRpc, direct: bool) -> (SubxtClient, Arc<ScriptedRpc>) {
+        let rpc = Arc::new(rpc);
+        let rpc_client = RpcClient::new(rpc.clone());
+        let online = OnlineClient::<PolkadotConfig>::from_rpc_client_with(
+            H256::zero(),
+            RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 1,
+            },
+            metadata(),
+            rpc_client.clone(),
+        )
+        .unwrap();
+        let client = if direct {
+            SubxtClient {
+                client: online,
+                rpc: Some(LegacyRpcMethods::new(rpc_client.clone())),
+                raw_rpc: Some(rpc_client),
+            }
+        } else {
+            SubxtClient::new(online)
+        };
+        (client, rpc)
+    }
+
+    fn number_key() -> Vec<u8> {
+        [twox_128(b"System"), twox_128(b"Number")].concat()
+    }
+
+    fn account_key(account: [u8; 32]) -> Vec<u8> {
+        let mut key = [twox_128(b"System"), twox_128(b"Account")].concat();
+        key.extend_from_slice(&blake2_128(&account));
+        key.extend_from_slice(&account);
+        key
+    }
+
+    fn header(number: u64) -> serde_json::Value {
+        json!({
+            "parentHash": to_hex(&[1u8; 32]),
+            "number": format!("{:#x}", number),
+            "stateRoot": to_hex(&[2u8; 32]),
+            "extrinsicsRoot": to_hex(&[3u8; 32]),
+            "digest": { "logs": [] },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_finalized_block_hash() {
+        let (client, _) = scripted_client(
+            ScriptedRpc::default().ok("chain_getFinalizedHead", json!(to_hex(&[7u8; 32]))),
+            true,
+        );
+        assert_eq!(client.finalized_block_hash().await.unwrap(), [7u8; 32]);
+
+        let (client, _) = scripted_client(
+            ScriptedRpc::default().err("chain_getFinalizedHead", "node unavailable"),
+            true,
+        );
+        let err = client.finalized_block_hash().await.unwrap_err();
+        assert!(matches!(err, Error::Connection(_)));
+        assert!(err.to_string().contains("Failed to fetch finalized block"));
+    }
+
+    #[tokio::test]
+    async fn test_block_hash_lookup() {
+        let (found, rpc) = scripted_client(
+            ScriptedRpc::default().ok("chain_getBlockHash", json!(to_hex(&[9u8; 32]))),
+            true,
+        );
+        assert_eq!(found.block_hash(42).await.unwrap(), Some([9u8; 32]));
+        assert_eq!(rpc.params("chain_getBlockHash").len(), 1);
+
+        // The chain has not reached the block yet
+        let (missing, _) = scripted_client(
+            ScriptedRpc::default().ok("chain_getBlockHash", serde_json::Value::Null),
+            true,
+        );
+        assert_eq!(missing.block_hash(1_000_000).await.unwrap(), None);
+
+        let (failing, _) = scripted_client(
+            ScriptedRpc::default().err("chain_getBlockHash", "node unavailable"),
+            true,
+        );
+        let err = failing.block_hash(42).await.unwrap_err();
+        assert!(matches!(err, Error::Connection(_)));
+        assert!(err.to_string().contains("Failed to get block hash"));
+    }
+
+    #[tokio::test]
+    async fn test_best_block_number() {
+        let (client, _) = scripted_client(
+            ScriptedRpc::default().ok("chain_getHeader", header(42)),
+            true,
+        );
+        assert_eq!(client.best_block_number().await.unwrap(), 42);
+
+        let (client, _) = scripted_client(
+            ScriptedRpc::default().ok("chain_getHeader", serde_json::Value::Null),
+            true,
+        );
+        let err = client.best_block_number().await.unwrap_err();
+        assert!(matches!(err, Error::Connection(_)));
+        assert!(err.to_string().contains("no best header"));
+    }
+
+    #[tokio::test]
+    async fn test_storage_reads_and_decodes_value() {
+        let at = [4u8; 32];
+        let (client, rpc) = scripted_client(
+            ScriptedRpc::default().ok("state_getStorage", json!(to_hex(&7u32.encode()))),
+            true,
+        );
+
+        let value = client
+            .storage(&StorageKey::new("System", "Number", vec![]), Some(at))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(value.bytes, 7u32.encode());
+        assert_eq!(value.value, Value::u128(7));
+
+        let params = rpc.params("state_getStorage");
+        assert_eq!(params, vec![json!([to_hex(&number_key()), to_hex(&at)])]);
+    }
+
+    #[tokio::test]
+    async fn test_storage_missing_entry_is_none() {
+        let (client, _) = scripted_client(
+            ScriptedRpc::default().ok("state_getStorage", serde_json::Value::Null),
+            true,
+        );
+        let value = client
+            .storage(
+                &StorageKey::new("System", "Account", vec![Value::from_bytes([1u8; 32])]),
+                Some([0u8; 32]),
+            )
+            .await
+            .unwrap();
+        assert!(value.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_storage_error_mapping() {
+        let (client, _) = scripted_client(
+            ScriptedRpc::default().err("state_getStorage", "node unavailable"),
+            true,
+        );
+
+        let err = client
+            .storage(
+                &StorageKey::new("System", "Number", vec![]),
+                Some([0u8; 32]),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Storage(_)));
+        assert!(err.to_string().contains("System::Number"));
+
+        // Unknown entries fail before anything is sent to the node
+        let err = client
+            .storage(
+                &StorageKey::new("System", "Missing", vec![]),
+                Some([0u8; 32]),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Storage(_)));
+        assert!(err.to_string().contains("Failed to encode storage key"));
+
+        // A stored value that does not match the metadata type
+        let (client, _) = scripted_client(
+            ScriptedRpc::default().ok("state_getStorage", json!("0x01")),
+            true,
+        );
+        let err = client
+            .storage(
+                &StorageKey::new("System", "Number", vec![]),
+                Some([0u8; 32]),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Failed to decode System::Number"));
+    }
+
+    #[tokio::test]
+    async fn test_storage_batch_returns_values_in_key_order() {
+        let at = [5u8; 32];
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        let (client, rpc) = scripted_client(
+            ScriptedRpc::default().ok(
+                "state_queryStorageAt",
+                json!([{
+                    "block": to_hex(&at),
+                    "changes": [
+                        [to_hex(&account_key(bob)), to_hex(&500u128.encode())],
+                        [to_hex(&account_key(alice)), serde_json::Value::Null],
+                        [to_hex(&number_key()), to_hex(&9u32.encode())],
+                    ],
+                }]),
+            ),
+            true,
+        );
+
+        let keys = [
+            StorageKey::new("System", "Number", vec![]),
+            StorageKey::new("System", "Account", vec![Value::from_bytes(alice)]),
+            StorageKey::new("System", "Account", vec![Value::from_bytes(bob)]),
+        ];
+        let values = client.storage_batch(&keys, at).await.unwrap();
+
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].as_ref().unwrap().value, Value::u128(9));
+        assert!(values[1].is_none());
+        assert_eq!(values[2].as_ref().unwrap().value, Value::u128(500));
+        assert_eq!(rpc.params("state_queryStorageAt").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_storage_batch_without_direct_rpc_reads_each_key() {
+        // Repeated keys each get the value
+        let (client, rpc) = scripted_client(
+            ScriptedRpc::default().ok("state_getStorage", json!(to_hex(&3u32.encode()))),
+            false,
+        );
+        let keys = [
+            StorageKey::new("System", "Number", vec![]),
+            StorageKey::new("System", "Number", vec![]),
+        ];
+        let values = client.storage_batch(&keys, [0u8; 32]).await.unwrap();
+
+        assert!(values.iter().all(|value| value.is_some()));
+        assert_eq!(rpc.params("state_getStorage").len(), 2);
+        assert!(rpc.params("state_queryStorageAt").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_storage_prefix_paged_reads_keys_and_values_at_one_block() {
+        let at = [6u8; 32];
+        let alice = account_key([1u8; 32]);
+        let bob = account_key([2u8; 32]);
+        let (client, rpc) = scripted_client(
+            ScriptedRpc::default()
+                .ok("state_getKeysPaged", json!([to_hex(&alice), to_hex(&bob)]))
+                .ok(
+                    "state_queryStorageAt",
+                    json!([{
+                        "block": to_hex(&at),
+                        "changes": [
+                            [to_hex(&bob), to_hex(&2u128.encode())],
+                            [to_hex(&alice), to_hex(&1u128.encode())],
+                        ],
+                    }]),
+                ),
+            true,
+        );
+
+        let page = client
+            .storage_prefix_paged(
+                &StorageKey::new("System", "Account", vec![]),
+                Some(&[0xaa]),
+                2,
+                Some(at),
+            )
+            .await
+            .unwrap();
+        assert_eq!(page, vec![(alice, 1u128.encode()), (bob, 2u128.encode())]);
+
+        let prefix = [twox_128(b"System"), twox_128(b"Account")].concat();
+        assert_eq!(
+            rpc.params("state_getKeysPaged"),
+            vec![json!([to_hex(&prefix), 2, "0xaa", to_hex(&at)])]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_extrinsic() {
+        let (client, rpc) = scripted_client(
+            ScriptedRpc::default().ok("author_submitExtrinsic", json!(to_hex(&[8u8; 32]))),
+            true,
+        );
+        assert_eq!(
+            client.submit_extrinsic(&[1, 2, 3]).await.unwrap(),
+            [8u8; 32]
+        );
+        assert_eq!(
+            rpc.params("author_submitExtrinsic"),
+            vec![json!(["0x010203"])]
+        );
+
+        let (client, _) = scripted_client(
+            ScriptedRpc::default().err("author_submitExtrinsic", "pool full"),
+            true,
+        );
+        let err = client.submit_extrinsic(&[1, 2, 3]).await.unwrap_err();
+        assert!(matches!(err, Error::Transaction(_)));
+        assert!(err.to_string().contains("Failed to submit extrinsic"));
+    }
+
+    #[tokio::test]
+    async fn test_child_storage() {
+        let (client, rpc) = scripted_client(
+            ScriptedRpc::default()
+                .ok("chain_getFinalizedHead", json!(to_hex(&[3u8; 32])))
+                .ok("childstate_getStorage", json!("0x2a")),
+            true,
+        );
+        assert_eq!(
+            client.child_storage(b"trie", &[1]).await.unwrap(),
+            Some(vec![0x2a])
+        );
+        assert_eq!(
+            rpc.params("childstate_getStorage"),
+            vec![json!([
+                prefixed_child_key(b"trie"),
+                "0x01",
+                to_hex(&[3u8; 32])
+            ])]
+        );
+
+        let (client, _) = scripted_client(
+            ScriptedRpc::default()
+                .ok("chain_getFinalizedHead", json!(to_hex(&[3u8; 32])))
+                .ok("childstate_getStorage", json!("0xzz")),
+            true,
+        );
+        let err = client.child_storage(b"trie", &[1]).await.unwrap_err();
+        assert!(err.to_string().contains("invalid hex"));
+    }
+
+    #[tokio::test]
+    async fn test_direct_rpc_methods_need_rpc_access() {
+        let (client, rpc) = scripted_client(ScriptedRpc::default(), false);
+
+        let err = client.system_properties().await.unwrap_err();
+        assert!(matches!(err, Error::Connection(_)));
+        assert!(err.to_string().contains("from_rpc_client"));
+
+        let err = client.child_storage(b"trie", &[1]).await.unwrap_err();
+        assert!(err.to_string().contains("childstate_getStorage"));
+        assert!(rpc.calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_system_properties() {
+        let (client, _) = scripted_client(
+            ScriptedRpc::default().ok(
+                "system_properties",
+                json!({ "tokenSymbol": "DOT", "tokenDecimals": 10, "ss58Format": 0 }),
+            ),
+            true,
+        );
+        let properties = client.system_properties().await.unwrap();
+        assert_eq!(properties["tokenSymbol"], "DOT");
+        assert_eq!(properties["tokenDecimals"], 10);
+    }
+
+    #[test]
+    fn test_page_entries() {
+        let entries = vec![
+            (vec![3], vec![30]),
+            (vec![1], vec![10]),
+            (vec![2], vec![20]),
+        ];
+
+        assert_eq!(
+            page_entries(entries.clone(), None, 2),
+            vec![(vec![1], vec![10]), (vec![2], vec![20])]
+        );
+        assert_eq!(
+            page_entries(entries.clone(), Some(&[2]), 10),
+            vec![(vec![3], vec![30])]
+        );
+        assert!(page_entries(entries, Some(&[3]), 10).is_empty());
+    }
+}
@@ -0,0 +1,423 @@
+//! Runtime constants
+//!
+//! [`ConstantsManager`] reads the constants every runtime publishes in its
+//! metadata: a generic [`constant`](ConstantsManager::constant) accessor that
+//! decodes into any [`DecodeAsType`] type, typed accessors for the values
+//! most applications need (existential deposit, block weights, block time,
+//! maximum nominations, and the deposits of assets, multisigs and proxies),
+//! and a [`list`](ConstantsManager::list) of every constant for browsing.
+//! Values come from the connected runtime, so they are right for every chain
+//! and stay right across runtime upgrades.
+//!
+//! ```rust,no_run
+//! # fn example(adapter: &apex_sdk_substrate::SubstrateAdapter) -> apex_sdk_substrate::Result<()> {
+//! let constants = adapter.constants();
+//! let ed = constants.existential_deposit()?;
+//! let max_signatories: u32 = constants.constant("Multisig", "MaxSignatories")?;
+//! for info in constants.pallet("Staking") {
+//!     println!("{} = {}", info.name, info.value);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::fee_estimator::BlockLimits;
+use crate::storage::DecodeAsType;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use subxt::dynamic::Value;
+use subxt::ext::scale_value::scale::decode_as_type;
+use subxt::metadata::types::ConstantMetadata;
+use subxt::Metadata;
+
+/// A runtime constant, decoded for display
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstantInfo {
+    /// Pallet name
+    pub pallet: String,
+    /// Constant name
+    pub name: String,
+    /// Decoded value, or the hex encoding if it cannot be decoded
+    pub value: String,
+    /// Documentation from the runtime
+    pub docs: String,
+}
+
+/// Deposits of pallet-assets, in raw units of the native token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetDeposits {
+    /// Reserved when creating an asset class
+    pub asset: u128,
+    /// Reserved per account holding a non-sufficient asset
+    pub account: u128,
+    /// Base reserved when setting asset metadata
+    pub metadata_base: u128,
+    /// Reserved per byte of asset metadata
+    pub metadata_per_byte: u128,
+    /// Reserved when creating an approval
+    pub approval: u128,
+}
+
+/// Deposits of pallet-multisig, in raw units of the native token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigDeposits {
+    /// Base reserved when opening a multisig operation
+    pub base: u128,
+    /// Reserved per signatory
+    pub factor: u128,
+    /// Maximum number of signatories
+    pub max_signatories: u32,
+}
+
+impl MultisigDeposits {
+    /// Deposit for an operation of a multisig with `signatories` members
+    pub fn for_signatories(&self, signatories: u32) -> u128 {
+        self.base
+            .saturating_add(self.factor.saturating_mul(u128::from(signatories)))
+    }
+}
+
+/// Deposits of pallet-proxy, in raw units of the native token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxyDeposits {
+    /// Base reserved for an account's proxies
+    pub base: u128,
+    /// Reserved per proxy
+    pub factor: u128,
+    /// Maximum proxies per account
+    pub max_proxies: u32,
+    /// Base reserved for an account's announcements
+    pub announcement_base: u128,
+    /// Reserved per announcement
+    pub announcement_factor: u128,
+}
+
+impl ProxyDeposits {
+    /// Deposit held for `proxies` proxies
+    pub fn for_proxies(&self, proxies: u32) -> u128 {
+        self.base
+            .saturating_add(self.factor.saturating_mul(u128::from(proxies)))
+    }
+}
+
+/// Commonly needed constants of a runtime; `None` where it lacks the pallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeConstants {
+    /// Minimum balance that keeps an account alive
+    pub existential_deposit: Option<u128>,
+    /// Maximum weight of a block
+    pub max_block_weight: Option<crate::Weight>,
+    /// Target time between blocks
+    pub block_time: Option<Duration>,
+    /// Maximum validators a nominator may nominate
+    pub max_nominations: Option<u32>,
+    /// Deposits of pallet-assets
+    pub assets: Option<AssetDeposits>,
+    /// Deposits of pallet-multisig
+    pub multisig: Option<MultisigDeposits>,
+    /// Deposits of pallet-proxy
+    pub proxy: Option<ProxyDeposits>,
+}
+
+/// Typed access to the constants of the connected runtime
+#[derive(Debug, Clone)]
+pub struct ConstantsManager {
+    metadata: Metadata,
+}
+
+impl ConstantsManager {
+    /// Read constants from runtime metadata
+    pub fn new(metadata: Metadata) -> Self {
+        Self { metadata }
+    }
+
+    /// A constant decoded into `T` using its type in the metadata
+    ///
+    /// Numbers decode into any integer type wide enough for the value.
+    pub fn constant<T: DecodeAsType>(&self, pallet: &str, name: &str) -> Result<T> {
+        let constant = self.lookup(pallet, name)?;
+        T::decode_as_type(&mut constant.value(), constant.ty(), self.metadata.types())
+            .map_err(|e| Error::Metadata(format!("Failed to decode {}::{}: {}", pallet, name, e)))
+    }
+
+    /// Like [`constant`](Self::constant), but `None` if the runtime lacks
+    /// the pallet or constant
+    pub fn try_constant<T: DecodeAsType>(&self, pallet: &str, name: &str) -> Result<Option<T>> {
+        if self.has(pallet, name) {
+            self.constant(pallet, name).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Whether the runtime has a constant
+    pub fn has(&self, pallet: &str, name: &str) -> bool {
+        self.lookup(pallet, name).is_ok()
+    }
+
+    /// A constant as a dynamic value, with type IDs as context
+    pub fn value(&self, pallet: &str, name: &str) -> Result<Value<u32>> {
+        let constant = self.lookup(pallet, name)?;
+        decode_as_type(&mut constant.value(), constant.ty(), self.metadata.types())
+            .map_err(|e| Error::Metadata(format!("Failed to decode {}::{}: {}", pallet, name, e)))
+    }
+
+    fn lookup(&self, pallet: &str, name: &str) -> Result<&ConstantMetadata> {
+        self.metadata
+            .pallet_by_name(pallet)
+            .and_then(|p| p.constant_by_name(name))
+            .ok_or_else(|| Error::Metadata(format!("Constant {}::{} not found", pallet, name)))
+    }
+
+    /// Every constant of every pallet
+    pub fn list(&self) -> Vec<ConstantInfo> {
+        self.metadata
+            .pallets()
+            .flat_map(|pallet| self.pallet(pallet.name()))
+            .collect()
+    }
+
+    /// Every constant of a pallet, empty if the runtime lacks it
+    pub fn pallet(&self, pallet: &str) -> Vec<ConstantInfo> {
+        let Some(metadata) = self.metadata.pallet_by_name(pallet) else {
+            return Vec::new();
+        };
+        metadata
+            .constants()
+            .map(|constant| ConstantInfo {
+                pallet: pallet.to_string(),
+                name: constant.name().to_string(),
+                value: decode_as_type(&mut constant.value(), constant.ty(), self.metadata.types())
+                    .map(|value| value.to_string())
+                    .unwrap_or_else(|_| format!("0x{}", hex::encode(constant.value()))),
+                docs: constant.docs().join("\n").trim().to_string(),
+            })
+            .collect()
+    }
+
+    /// `Balances::ExistentialDeposit`
+    pub fn existential_deposit(&self) -> Result<u128> {
+        self.constant("Balances", "ExistentialDeposit")
+    }
+
+    /// Limits of `System::BlockWeights`
+    pub fn block_limits(&self) -> Result<BlockLimits> {
+        BlockLimits::from_metadata(&self.metadata)
+    }
+
+    /// Target block time
+    ///
+    /// `Babe::ExpectedBlockTime` on relay chains, `Aura::SlotDuration` on
+    /// parachains, otherwise twice `Timestamp::MinimumPeriod`.
+    pub fn block_time(&self) -> Result<Duration> {
+        if let Some(ms) = self.try_constant::<u64>("Babe", "ExpectedBlockTime")? {
+            return Ok(Duration::from_millis(ms));
+        }
+        if let Some(ms) = self.try_constant::<u64>("Aura", "SlotDuration")? {
+            return Ok(Duration::from_millis(ms));
+        }
+        let period: u64 = self.constant("Timestamp", "MinimumPeriod")?;
+        Ok(Duration::from_millis(period.saturating_mul(2)))
+    }
+
+    /// `Staking::MaxNominations`
+    pub fn max_nominations(&self) -> Result<u32> {
+        self.constant("Staking", "MaxNominations")
+    }
+
+    /// Deposits of pallet-assets
+    pub fn asset_deposits(&self) -> Result<AssetDeposits> {
+        Ok(AssetDeposits {
+            asset: self.constant("Assets", "AssetDeposit")?,
+            account: self.constant("Assets", "AssetAccountDeposit")?,
+            metadata_base: self.constant("Assets", "MetadataDepositBase")?,
+            metadata_per_byte: self.constant("Assets", "MetadataDepositPerByte")?,
+            approval: self.constant("Assets", "ApprovalDeposit")?,
+        })
+    }
+
+    /// Deposits of pallet-multisig
+    pub fn multisig_deposits(&self) -> Result<MultisigDeposits> {
+        Ok(MultisigDeposits {
+            base: self.constant("Multisig", "DepositBase")?,
+            factor: self.constant("Multisig", "DepositFactor")?,
+            max_signatories: self.constant("Multisig", "MaxSignatories")?,
+        })
+    }
+
+    /// Deposits of pallet-proxy
+    pub fn proxy_deposits(&self) -> Result<ProxyDeposits> {
+        Ok(ProxyDeposits {
+            base: self.constant("Proxy", "ProxyDepositBase")?,
+            factor: self.constant("Proxy", "ProxyDepositFactor")?,
+            max_proxies: self.constant("Proxy", "MaxProxies")?,
+            announcement_base: self.constant("Proxy", "AnnouncementDepositBase")?,
+            announcement_factor: self.constant("Proxy", "AnnouncementDepositFactor")?,
+        })
+    }
+
+    /// Every typed constant the runtime has
+    pub fn all(&self) -> RuntimeConstants {
+        RuntimeConstants {
+            existential_deposit: self.existential_deposit().ok(),
+            max_block_weight: self.block_limits().ok().map(|limits| limits.max_block),
+            block_time: self.block_time().ok(),
+            max_nominations: self.max_nominations().ok(),
+            assets: self.asset_deposits().ok(),
+            multisig: self.multisig_deposits().ok(),
+            proxy: self.proxy_deposits().ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parity_scale_codec::Encode;
+    use scale_info::{meta_type, TypeInfo};
+    use subxt::ext::frame_metadata::{
+        v15::{
+            CustomMetadata, ExtrinsicMetadata, OuterEnums, PalletConstantMetadata, PalletMetadata,
+            RuntimeMetadataV15,
+        },
+        RuntimeMetadataPrefixed,
+    };
+
+    fn constant<T: Encode + TypeInfo + 'static>(
+        name: &'static str,
+        value: T,
+    ) -> PalletConstantMetadata {
+        PalletConstantMetadata {
+            name,
+            ty: meta_type::<T>(),
+            value: value.encode(),
+            docs: vec![" Docs of", " the constant."],
+        }
+    }
+
+    fn metadata() -> Metadata {
+        #[allow(unused)]
+        #[derive(TypeInfo)]
+        enum RuntimeCall {
+            System,
+        }
+
+        let pallet = |name, index, constants| PalletMetadata {
+            name,
+            storage: None,
+            calls: None,
+            event: None,
+            constants,
+            error: None,
+            index,
+            docs: vec![],
+        };
+        let metadata = RuntimeMetadataV15::new(
+            vec![
+                pallet(
+                    "Balances",
+                    10,
+                    vec![constant("ExistentialDeposit", 10_000_000_000u128)],
+                ),
+                pallet("Timestamp", 3, vec![constant("MinimumPeriod", 3_000u64)]),
+                pallet(
+                    "Multisig",
+                    30,
+                    vec![
+                        constant("DepositBase", 200_880_000_000u128),
+                        constant("DepositFactor", 320_000_000u128),
+                        constant("MaxSignatories", 100u32),
+                    ],
+                ),
+            ],
+            ExtrinsicMetadata {
+                version: 4,
+                signed_extensions: vec![],
+                address_ty: meta_type::<()>(),
+                call_ty: meta_type::<RuntimeCall>(),
+                signature_ty: meta_type::<()>(),
+                extra_ty: meta_type::<()>(),
+            },
+            meta_type::<()>(),
+            vec![],
+            OuterEnums {
+                call_enum_ty: meta_type::<RuntimeCall>(),
+                event_enum_ty: meta_type::<()>(),
+                error_enum_ty: meta_type::<()>(),
+            },
+            CustomMetadata {
+                map: Default::default(),
+            },
+        );
+        Metadata::try_from(RuntimeMetadataPrefixed::from(metadata)).expect("valid metadata")
+    }
+
+    #[test]
+    fn test_generic_constant_access() {
+        let constants = ConstantsManager::new(metadata());
+
+        let period: u64 = constants.constant("Timestamp", "MinimumPeriod").unwrap();
+        assert_eq!(period, 3_000);
+        // Integers decode into any type the value fits
+        let period: u128 = constants.constant("Timestamp", "MinimumPeriod").unwrap();
+        assert_eq!(period, 3_000);
+        assert!(constants
+            .constant::<u8>("Timestamp", "MinimumPeriod")
+            .is_err());
+
+        assert!(constants
+            .constant::<u32>("Staking", "MaxNominations")
+            .unwrap_err()
+            .to_string()
+            .contains("Staking::MaxNominations not found"));
+        assert_eq!(
+            constants
+                .try_constant::<u32>("Staking", "MaxNominations")
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            constants
+                .value("Multisig", "MaxSignatories")
+                .unwrap()
+                .as_u128(),
+            Some(100)
+        );
+    }
+
+    #[test]
+    fn test_typed_constants() {
+        let constants = ConstantsManager::new(metadata());
+        assert_eq!(constants.existential_deposit().unwrap(), 10_000_000_000);
+        assert_eq!(constants.block_time().unwrap(), Duration::from_secs(6));
+
+        let multisig = constants.multisig_deposits().unwrap();
+        assert_eq!(multisig.max_signatories, 100);
+        assert_eq!(
+            multisig.for_signatories(3),
+            200_880_000_000 + 3 * 320_000_000
+        );
+
+        let all = constants.all();
+        assert_eq!(all.existential_deposit, Some(10_000_000_000));
+        assert_eq!(all.multisig, Some(multisig));
+        assert_eq!(all.assets, None);
+        assert_eq!(all.proxy, None);
+        assert_eq!(all.max_nominations, None);
+        assert_eq!(all.max_block_weight, None);
+    }
+
+    #[test]
+    fn test_list_constants() {
+        let constants = ConstantsManager::new(metadata());
+        let all = constants.list();
+        assert_eq!(all.len(), 5);
+
+        let multisig = constants.pallet("Multisig");
+        assert_eq!(multisig[2].name, "MaxSignatories");
+        assert_eq!(multisig[2].value, "100");
+        assert_eq!(multisig[2].docs, "Docs of\n the constant.");
+        assert!(constants.pallet("Proxy").is_empty());
+    }
+}
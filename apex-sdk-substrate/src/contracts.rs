@@ -32,7 +32,7 @@
 //!     .await?;
 //! ```
 
-use crate::{Error, Result, Sr25519Signer, Wallet};
+use crate::{Error, Result, Wallet};
 use serde::{Deserialize, Serialize};
 use subxt::{OnlineClient, PolkadotConfig};
 use tracing::{debug, info};
@@ -359,7 +359,12 @@ impl ContractClient {
         wallet: &Wallet,
         salt: Option<Vec<u8>>,
     ) -> Result<Self> {
-        info!("Deploying contract with constructor: {}", constructor_name);
+        info!(
+            operation = "contract_deploy",
+            wallet = %wallet.address(),
+            constructor = constructor_name,
+            "Deploying contract"
+        );
 
         // Find the constructor
         let constructor = metadata
@@ -397,15 +402,18 @@ impl ContractClient {
         );
 
         // Submit the transaction
-        let pair = wallet
-            .sr25519_pair()
-            .ok_or_else(|| Error::Transaction("Wallet does not have SR25519 key".to_string()))?;
+        if wallet.sr25519_pair().is_none() {
+            return Err(Error::Transaction(
+                "Wallet does not have SR25519 key".to_string(),
+            ));
+        }
 
-        let signer = Sr25519Signer::new(pair.clone());
+        let signer = wallet.to_subxt_signer();
 
-        let mut progress = client
-            .tx()
-            .sign_and_submit_then_watch_default(&instantiate_call, &signer)
+        let mut progress = signer
+            .create_signed(&client, &instantiate_call, Default::default())
+            .await?
+            .submit_and_watch()
             .await
             .map_err(|e| {
                 Error::Transaction(format!("Failed to submit deploy transaction: {}", e))
@@ -488,7 +496,12 @@ impl ContractClient {
     ///
     /// Transaction hash of the call
     pub async fn call(&self, method_name: &str, args: &[u8], wallet: &Wallet) -> Result<String> {
-        info!("Calling contract method: {}", method_name);
+        info!(
+            operation = "contract_call",
+            wallet = %wallet.address(),
+            method = method_name,
+            "Calling contract method"
+        );
 
         // Find the message in metadata
         let message = if let Some(ref metadata) = self.metadata {
@@ -526,16 +539,18 @@ impl ContractClient {
         );
 
         // Submit the transaction
-        let pair = wallet
-            .sr25519_pair()
-            .ok_or_else(|| Error::Transaction("Wallet does not have SR25519 key".to_string()))?;
+        if wallet.sr25519_pair().is_none() {
+            return Err(Error::Transaction(
+                "Wallet does not have SR25519 key".to_string(),
+            ));
+        }
 
-        let signer = Sr25519Signer::new(pair.clone());
+        let signer = wallet.to_subxt_signer();
 
-        let mut progress = self
-            .client
-            .tx()
-            .sign_and_submit_then_watch_default(&call_tx, &signer)
+        let mut progress = signer
+            .create_signed(&self.client, &call_tx, Default::default())
+            .await?
+            .submit_and_watch()
             .await
             .map_err(|e| Error::Transaction(format!("Failed to submit call transaction: {}", e)))?;
 
@@ -546,7 +561,7 @@ impl ContractClient {
 
             if let Some(finalized) = event.as_finalized() {
                 let tx_hash = format!("0x{}", hex::encode(finalized.extrinsic_hash()));
-                info!("Contract call finalized: {}", tx_hash);
+                info!(operation = "contract_call", tx_hash = %tx_hash, "Contract call finalized");
 
                 finalized
                     .wait_for_success()
@@ -0,0 +1,788 @@
+//! Parachain crowdloans and slot auctions
+//!
+//! [`CrowdloanManager`] reads crowdloan funds from pallet-crowdloan,
+//! including individual contributions kept in each fund's child trie, the
+//! lease periods and leases of pallet-slots, and the current auction and its
+//! winning bids from pallet-auctions. It also builds the `contribute` and
+//! `withdraw` calls, so a treasury can track and manage its locked
+//! contributions through the SDK.
+//!
+//! ```rust,no_run
+//! # async fn example(adapter: &apex_sdk_substrate::SubstrateAdapter) -> apex_sdk_substrate::Result<()> {
+//! let crowdloans = adapter.crowdloan();
+//! let treasury = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+//! for (para_id, contribution) in crowdloans.account_contributions(treasury).await? {
+//!     println!("{} locked in the crowdloan of {}", contribution.amount, para_id);
+//! }
+//!
+//! let contribute = crowdloans.contribute(2_000, 5_000_000_000_000);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::assets::{decode_map_entry, missing_field, value_bytes, value_number as number};
+use crate::chain_client::StorageKey;
+use crate::{Error, Result, SubstrateAdapter};
+use apex_sdk_types::IntoAddress;
+use parity_scale_codec::Decode;
+use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+use subxt::dynamic::{At as _, Value};
+use subxt::ext::scale_value::ValueDef;
+use tracing::{debug, info};
+
+const CROWDLOAN_PALLET: &str = "Crowdloan";
+const SLOTS_PALLET: &str = "Slots";
+const AUCTIONS_PALLET: &str = "Auctions";
+
+/// Lease periods a single auction offers
+pub const LEASE_PERIODS_PER_SLOT: u32 = 8;
+
+/// Contributions read per child storage request
+const CONTRIBUTIONS_PAGE: u32 = 1_000;
+
+/// Whether a crowdloan still accepts contributions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FundStatus {
+    /// Accepting contributions
+    Open,
+    /// Raised its cap
+    Capped,
+    /// Past its end block; contributions can be withdrawn once the
+    /// parachain's lease ends or the fund did not win
+    Ended,
+}
+
+/// Last contribution to a fund, used to decide whether it bids again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LastContribution {
+    /// No contribution yet
+    Never,
+    /// Before the ending period of the auction with this index
+    PreEnding(u32),
+    /// During an ending period, at this block
+    Ending(u32),
+}
+
+/// A crowdloan fund, from `Crowdloan::Funds`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crowdloan {
+    /// Parachain the fund bids for
+    pub para_id: u32,
+    /// Account that created the fund and placed its deposit
+    pub depositor: String,
+    /// Deposit reserved from the depositor
+    pub deposit: u128,
+    /// Total contributed so far
+    pub raised: u128,
+    /// Most the fund may raise
+    pub cap: u128,
+    /// Block after which contributions are refused
+    pub end: u32,
+    /// First lease period the fund bids for
+    pub first_period: u32,
+    /// Last lease period the fund bids for
+    pub last_period: u32,
+    /// Index of the child trie holding the contributions
+    pub fund_index: u32,
+    /// Last contribution
+    pub last_contribution: LastContribution,
+}
+
+impl Crowdloan {
+    pub(crate) fn from_value<T>(para_id: u32, value: &Value<T>, ss58_prefix: u16) -> Result<Self> {
+        let field = |name: &str| {
+            value
+                .at(name)
+                .and_then(number)
+                .ok_or_else(|| missing_field(name))
+        };
+        let block = |name: &str| {
+            field(name).and_then(|n| u32::try_from(n).map_err(|_| missing_field(name)))
+        };
+
+        let last_contribution = match value.at("last_contribution").map(|v| &v.value) {
+            Some(ValueDef::Variant(variant)) => {
+                let inner = variant.values.values().next().and_then(number);
+                match (variant.name.as_str(), inner) {
+                    ("Never", _) => LastContribution::Never,
+                    ("PreEnding", Some(index)) => LastContribution::PreEnding(index as u32),
+                    ("Ending", Some(at)) => LastContribution::Ending(at as u32),
+                    _ => return Err(missing_field("last_contribution")),
+                }
+            }
+            _ => return Err(missing_field("last_contribution")),
+        };
+
+        Ok(Self {
+            para_id,
+            depositor: value
+                .at("depositor")
+                .and_then(account_id)
+                .map(|id| ss58(id, ss58_prefix))
+                .ok_or_else(|| missing_field("depositor"))?,
+            deposit: field("deposit")?,
+            raised: field("raised")?,
+            cap: field("cap")?,
+            end: block("end")?,
+            first_period: block("first_period")?,
+            last_period: block("last_period")?,
+            // Named `trie_index` before the fund index was introduced
+            fund_index: block("fund_index").or_else(|_| block("trie_index"))?,
+            last_contribution,
+        })
+    }
+
+    /// Status of the fund at block `now`
+    pub fn status(&self, now: u32) -> FundStatus {
+        if now > self.end {
+            FundStatus::Ended
+        } else if self.raised >= self.cap {
+            FundStatus::Capped
+        } else {
+            FundStatus::Open
+        }
+    }
+
+    /// Amount the fund can still raise
+    pub fn remaining(&self) -> u128 {
+        self.cap.saturating_sub(self.raised)
+    }
+
+    /// Unprefixed key of the child trie holding the fund's contributions
+    pub fn child_trie_key(&self) -> [u8; 32] {
+        contributions_trie_key(self.fund_index)
+    }
+}
+
+/// `blake2_256(b"crowdloan" ++ fund_index)`, as pallet-crowdloan derives it
+fn contributions_trie_key(fund_index: u32) -> [u8; 32] {
+    let mut buf = b"crowdloan".to_vec();
+    buf.extend_from_slice(&fund_index.to_le_bytes());
+    sp_core::blake2_256(&buf)
+}
+
+/// A contribution to a crowdloan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contribution {
+    /// Contributing account
+    pub contributor: String,
+    /// Amount locked in the fund
+    pub amount: u128,
+    /// Memo attached with `add_memo`, often a referral code
+    pub memo: Vec<u8>,
+}
+
+impl Contribution {
+    /// Decode a child trie entry: the contributor's account ID as key and
+    /// `(amount, memo)` as value
+    pub(crate) fn from_entry(key: &[u8], value: &[u8], ss58_prefix: u16) -> Result<Self> {
+        let contributor = <[u8; 32]>::try_from(key).map_err(|_| {
+            Error::Storage(format!(
+                "Crowdloan contribution key is {} bytes, expected 32",
+                key.len()
+            ))
+        })?;
+        let (amount, memo) = <(u128, Vec<u8>)>::decode(&mut &value[..])
+            .map_err(|e| Error::Storage(format!("Failed to decode contribution: {}", e)))?;
+        Ok(Self {
+            contributor: ss58(contributor, ss58_prefix),
+            amount,
+            memo,
+        })
+    }
+}
+
+/// Length and offset of lease periods, from the `Slots` constants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeasePeriods {
+    /// Blocks per lease period
+    pub length: u32,
+    /// Block the first lease period starts at
+    pub offset: u32,
+}
+
+impl LeasePeriods {
+    /// Lease period containing block `at`, `None` before the first one
+    pub fn period_at(&self, at: u32) -> Option<u32> {
+        if self.length == 0 {
+            return None;
+        }
+        at.checked_sub(self.offset)
+            .map(|blocks| blocks / self.length)
+    }
+
+    /// First block of a lease period
+    pub fn start_of(&self, period: u32) -> u32 {
+        period
+            .saturating_mul(self.length)
+            .saturating_add(self.offset)
+    }
+
+    /// Last block of a lease period
+    pub fn end_of(&self, period: u32) -> u32 {
+        self.start_of(period.saturating_add(1)).saturating_sub(1)
+    }
+}
+
+/// A lease period held by a parachain, from `Slots::Leases`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lease {
+    /// Lease period
+    pub period: u32,
+    /// Account whose deposit secures the lease, the fund account for
+    /// crowdloans
+    pub account: String,
+    /// Deposit reserved for the lease
+    pub deposit: u128,
+}
+
+/// The current auction, from `Auctions::AuctionInfo`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuctionInfo {
+    /// Auction index, from `Auctions::AuctionCounter`
+    pub index: u32,
+    /// First of the lease periods on auction
+    pub first_lease_period: u32,
+    /// First block of the ending period
+    pub ending_period_start: u32,
+}
+
+impl AuctionInfo {
+    /// Phase of the auction at block `now`, given the `EndingPeriod` and
+    /// `SampleLength` constants
+    pub fn phase(&self, now: u32, ending_period: u32, sample_length: u32) -> AuctionPhase {
+        let Some(offset) = now.checked_sub(self.ending_period_start) else {
+            return AuctionPhase::Opening;
+        };
+        if offset < ending_period {
+            AuctionPhase::Ending {
+                sample: offset / sample_length.max(1),
+            }
+        } else {
+            AuctionPhase::VrfDelay
+        }
+    }
+
+    /// Lease periods on auction
+    pub fn lease_periods(&self) -> std::ops::RangeInclusive<u32> {
+        self.first_lease_period..=self.first_lease_period + LEASE_PERIODS_PER_SLOT - 1
+    }
+}
+
+/// Phase of an auction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionPhase {
+    /// Before the ending period; bids are final
+    Opening,
+    /// In the ending period; the winner is drawn from the leading bids of
+    /// one of its samples
+    Ending {
+        /// Current sample
+        sample: u32,
+    },
+    /// Ending period over, waiting for randomness to pick the winning sample
+    VrfDelay,
+}
+
+/// A leading bid for a range of lease periods, from `Auctions::Winning`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bid {
+    /// First lease period of the range
+    pub first_period: u32,
+    /// Last lease period of the range
+    pub last_period: u32,
+    /// Bidding account, the fund account for crowdloans
+    pub bidder: String,
+    /// Parachain bid for
+    pub para_id: u32,
+    /// Amount bid
+    pub amount: u128,
+}
+
+/// Lease period offsets `(first, last)` of each slot range, in the order of
+/// the runtime's `SlotRange` enum: `ZeroZero`, `ZeroOne`, ... `SevenSeven`
+fn slot_ranges() -> impl Iterator<Item = (u32, u32)> {
+    (0..LEASE_PERIODS_PER_SLOT)
+        .flat_map(|first| (first..LEASE_PERIODS_PER_SLOT).map(move |last| (first, last)))
+}
+
+/// Decode `WinningData`: one optional `(bidder, para_id, amount)` per slot
+/// range
+pub(crate) fn winning_bids<T>(
+    winning: &Value<T>,
+    first_lease_period: u32,
+    ss58_prefix: u16,
+) -> Result<Vec<Bid>> {
+    let ValueDef::Composite(ranges) = &winning.value else {
+        return Err(Error::Storage(
+            "Auctions::Winning is not an array".to_string(),
+        ));
+    };
+
+    let mut bids = Vec::new();
+    for ((first, last), entry) in slot_ranges().zip(ranges.values()) {
+        let Some(bid) = some(entry) else {
+            continue;
+        };
+        bids.push(Bid {
+            first_period: first_lease_period + first,
+            last_period: first_lease_period + last,
+            bidder: bid
+                .at(0)
+                .and_then(account_id)
+                .map(|id| ss58(id, ss58_prefix))
+                .ok_or_else(|| missing_field("bidder"))?,
+            para_id: bid
+                .at(1)
+                .and_then(number)
+                .and_then(|id| u32::try_from(id).ok())
+                .ok_or_else(|| missing_field("para_id"))?,
+            amount: bid
+                .at(2)
+                .and_then(number)
+                .ok_or_else(|| missing_field("amount"))?,
+        });
+    }
+    Ok(bids)
+}
+
+/// High-level API for pallet-crowdloan, pallet-slots and pallet-auctions on
+/// a relay chain
+pub struct CrowdloanManager<'a> {
+    adapter: &'a SubstrateAdapter,
+}
+
+impl<'a> CrowdloanManager<'a> {
+    pub fn new(adapter: &'a SubstrateAdapter) -> Self {
+        Self { adapter }
+    }
+
+    /// Crowdloan of a parachain, `None` if it has none
+    pub async fn fund(&self, para_id: u32) -> Result<Option<Crowdloan>> {
+        let key = StorageKey::new(
+            CROWDLOAN_PALLET,
+            "Funds",
+            vec![Value::u128(para_id as u128)],
+        );
+        self.adapter
+            .chain_client()
+            .storage(&key, None)
+            .await?
+            .map(|fund| Crowdloan::from_value(para_id, &fund.value, self.ss58_prefix()))
+            .transpose()
+    }
+
+    /// Every crowdloan, by parachain ID
+    pub async fn funds(&self) -> Result<Vec<Crowdloan>> {
+        let chain = self.adapter.chain_client();
+        let metadata = chain.metadata();
+
+        let mut funds = Vec::new();
+        for (key, value) in chain.storage_entries(CROWDLOAN_PALLET, "Funds").await? {
+            let (key, value) =
+                decode_map_entry(&metadata, CROWDLOAN_PALLET, "Funds", &key, &value)?;
+            let para_id = number(&key)
+                .and_then(|id| u32::try_from(id).ok())
+                .ok_or_else(|| Error::Storage("Parachain ID is not a u32".to_string()))?;
+            funds.push(Crowdloan::from_value(para_id, &value, self.ss58_prefix())?);
+        }
+
+        funds.sort_by_key(|fund| fund.para_id);
+        debug!("Found {} crowdloans", funds.len());
+        Ok(funds)
+    }
+
+    /// Status of a parachain's crowdloan at the best block, `None` if it
+    /// has none
+    pub async fn fund_status(&self, para_id: u32) -> Result<Option<FundStatus>> {
+        let Some(fund) = self.fund(para_id).await? else {
+            return Ok(None);
+        };
+        Ok(Some(fund.status(self.best_block().await?)))
+    }
+
+    /// An account's contribution to a parachain's crowdloan, `None` if it
+    /// has not contributed or the parachain has no crowdloan
+    ///
+    /// Needs a client with child storage access, such as
+    /// [`SubxtClient::from_rpc_client`](crate::SubxtClient::from_rpc_client).
+    pub async fn contribution(
+        &self,
+        para_id: u32,
+        account: impl IntoAddress,
+    ) -> Result<Option<Contribution>> {
+        let Some(fund) = self.fund(para_id).await? else {
+            return Ok(None);
+        };
+        self.fund_contribution(&fund, &account.into_address()?.account_id()?)
+            .await
+    }
+
+    /// Every contribution to a parachain's crowdloan, largest first
+    ///
+    /// Needs a client with child storage access.
+    pub async fn contributions(&self, para_id: u32) -> Result<Vec<Contribution>> {
+        let Some(fund) = self.fund(para_id).await? else {
+            return Ok(Vec::new());
+        };
+        let chain = self.adapter.chain_client();
+        let trie = fund.child_trie_key();
+
+        let mut contributions = Vec::new();
+        let mut start_after: Option<Vec<u8>> = None;
+        loop {
+            let page = chain
+                .child_storage_entries_paged(&trie, start_after.as_deref(), CONTRIBUTIONS_PAGE)
+                .await?;
+            let full = page.len() == CONTRIBUTIONS_PAGE as usize;
+            start_after = page.last().map(|(key, _)| key.clone());
+            for (key, value) in &page {
+                contributions.push(Contribution::from_entry(key, value, self.ss58_prefix())?);
+            }
+            if !full {
+                break;
+            }
+        }
+
+        contributions.sort_by_key(|contribution| std::cmp::Reverse(contribution.amount));
+        debug!(
+            "Found {} contributions to the crowdloan of {}",
+            contributions.len(),
+            para_id
+        );
+        Ok(contributions)
+    }
+
+    /// An account's contributions to every crowdloan, by parachain ID
+    ///
+    /// Reads one child storage entry per fund. Needs a client with child
+    /// storage access.
+    pub async fn account_contributions(
+        &self,
+        account: impl IntoAddress,
+    ) -> Result<Vec<(u32, Contribution)>> {
+        let account = account.into_address()?.account_id()?;
+        let mut contributions = Vec::new();
+        for fund in self.funds().await? {
+            if let Some(contribution) = self.fund_contribution(&fund, &account).await? {
+                contributions.push((fund.para_id, contribution));
+            }
+        }
+        Ok(contributions)
+    }
+
+    async fn fund_contribution(
+        &self,
+        fund: &Crowdloan,
+        account: &[u8; 32],
+    ) -> Result<Option<Contribution>> {
+        self.adapter
+            .chain_client()
+            .child_storage(&fund.child_trie_key(), account)
+            .await?
+            .map(|value| Contribution::from_entry(account, &value, self.ss58_prefix()))
+            .transpose()
+    }
+
+    /// Lease period length and offset of the runtime
+    pub fn lease_periods(&self) -> Result<LeasePeriods> {
+        Ok(LeasePeriods {
+            length: self.constant_u32(SLOTS_PALLET, "LeasePeriod")?,
+            // Runtimes without an offset start lease periods at genesis
+            offset: self.constant_u32(SLOTS_PALLET, "LeaseOffset").unwrap_or(0),
+        })
+    }
+
+    /// Lease periods held by a parachain, from the current one on
+    ///
+    /// Periods the parachain holds no lease for in between are skipped.
+    pub async fn leases(&self, para_id: u32) -> Result<Vec<Lease>> {
+        let key = StorageKey::new(SLOTS_PALLET, "Leases", vec![Value::u128(para_id as u128)]);
+        let Some(leases) = self.adapter.chain_client().storage(&key, None).await? else {
+            return Ok(Vec::new());
+        };
+        let ValueDef::Composite(entries) = &leases.value.value else {
+            return Err(Error::Storage("Slots::Leases is not a list".to_string()));
+        };
+
+        let current = self
+            .lease_periods()?
+            .period_at(self.best_block().await?)
+            .unwrap_or(0);
+        let ss58_prefix = self.ss58_prefix();
+        entries
+            .values()
+            .enumerate()
+            .filter_map(|(offset, entry)| some(entry).map(|lease| (offset, lease)))
+            .map(|(offset, lease)| {
+                Ok(Lease {
+                    period: current + offset as u32,
+                    account: lease
+                        .at(0)
+                        .and_then(account_id)
+                        .map(|id| ss58(id, ss58_prefix))
+                        .ok_or_else(|| missing_field("account"))?,
+                    deposit: lease
+                        .at(1)
+                        .and_then(number)
+                        .ok_or_else(|| missing_field("deposit"))?,
+                })
+            })
+            .collect()
+    }
+
+    /// The current auction, `None` between auctions
+    pub async fn auction(&self) -> Result<Option<AuctionInfo>> {
+        let chain = self.adapter.chain_client();
+        let Some(info) = chain
+            .storage(
+                &StorageKey::new(AUCTIONS_PALLET, "AuctionInfo", Vec::new()),
+                None,
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+        let index = chain
+            .storage(
+                &StorageKey::new(AUCTIONS_PALLET, "AuctionCounter", Vec::new()),
+                None,
+            )
+            .await?
+            .and_then(|counter| counter.value.as_u128())
+            .unwrap_or(0);
+
+        let block = |position: usize, name: &str| {
+            info.value
+                .at(position)
+                .and_then(number)
+                .and_then(|n| u32::try_from(n).ok())
+                .ok_or_else(|| missing_field(name))
+        };
+        Ok(Some(AuctionInfo {
+            index: index as u32,
+            first_lease_period: block(0, "lease_period")?,
+            ending_period_start: block(1, "ending_period_start")?,
+        }))
+    }
+
+    /// The current auction and its phase at the best block
+    pub async fn auction_status(&self) -> Result<Option<(AuctionInfo, AuctionPhase)>> {
+        let Some(auction) = self.auction().await? else {
+            return Ok(None);
+        };
+        let phase = auction.phase(
+            self.best_block().await?,
+            self.constant_u32(AUCTIONS_PALLET, "EndingPeriod")?,
+            self.constant_u32(AUCTIONS_PALLET, "SampleLength")?,
+        );
+        Ok(Some((auction, phase)))
+    }
+
+    /// Leading bids of the current auction, from its latest sample
+    pub async fn winning_bids(&self) -> Result<Vec<Bid>> {
+        let Some(auction) = self.auction().await? else {
+            return Ok(Vec::new());
+        };
+        let chain = self.adapter.chain_client();
+        let metadata = chain.metadata();
+
+        let mut latest: Option<(u128, Value)> = None;
+        for (key, value) in chain.storage_entries(AUCTIONS_PALLET, "Winning").await? {
+            let (sample, value) =
+                decode_map_entry(&metadata, AUCTIONS_PALLET, "Winning", &key, &value)?;
+            let sample = number(&sample).unwrap_or(0);
+            if latest.as_ref().is_none_or(|(best, _)| sample >= *best) {
+                latest = Some((sample, value));
+            }
+        }
+
+        match latest {
+            Some((_, winning)) => {
+                winning_bids(&winning, auction.first_lease_period, self.ss58_prefix())
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Contribute `value` to a parachain's crowdloan
+    ///
+    /// Crowdloans with a verifier also need a signature over the
+    /// contribution, which this call does not carry.
+    pub fn contribute(&self, para_id: u32, value: u128) -> subxt::tx::DynamicPayload {
+        info!(para_id, value, "Preparing crowdloan contribution");
+        subxt::dynamic::tx(
+            CROWDLOAN_PALLET,
+            "contribute",
+            vec![
+                Value::u128(para_id as u128),
+                Value::u128(value),
+                Value::unnamed_variant("None", []),
+            ],
+        )
+    }
+
+    /// Return a contributor's funds from an ended crowdloan that did not
+    /// win, or whose lease is over
+    ///
+    /// Anyone may submit it on behalf of the contributor.
+    pub fn withdraw(
+        &self,
+        contributor: impl IntoAddress,
+        para_id: u32,
+    ) -> Result<subxt::tx::DynamicPayload> {
+        let contributor = contributor.into_address()?.account_id()?;
+        Ok(subxt::dynamic::tx(
+            CROWDLOAN_PALLET,
+            "withdraw",
+            vec![Value::from_bytes(contributor), Value::u128(para_id as u128)],
+        ))
+    }
+
+    async fn best_block(&self) -> Result<u32> {
+        let best = self.adapter.chain_client().best_block_number().await?;
+        u32::try_from(best).map_err(|_| Error::Storage(format!("Block {} exceeds u32", best)))
+    }
+
+    fn constant_u32(&self, pallet: &str, name: &str) -> Result<u32> {
+        self.adapter.constants().constant(pallet, name)
+    }
+
+    fn ss58_prefix(&self) -> u16 {
+        self.adapter.config().ss58_prefix
+    }
+}
+
+/// Inner value of `Some`
+fn some<T>(value: &Value<T>) -> Option<&Value<T>> {
+    match &value.value {
+        ValueDef::Variant(variant) if variant.name == "Some" => variant.values.values().next(),
+        _ => None,
+    }
+}
+
+fn account_id<T>(value: &Value<T>) -> Option<[u8; 32]> {
+    value_bytes(value).and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+}
+
+fn ss58(id: [u8; 32], ss58_prefix: u16) -> String {
+    AccountId32::from(id).to_ss58check_with_version(Ss58AddressFormat::custom(ss58_prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parity_scale_codec::Encode;
+
+    fn account(byte: u8) -> Value {
+        Value::unnamed_composite([Value::from_bytes([byte; 32])])
+    }
+
+    fn fund(raised: u128) -> Value {
+        Value::named_composite([
+            ("depositor", account(1)),
+            ("verifier", Value::unnamed_variant("None", [])),
+            ("deposit", Value::u128(500)),
+            ("raised", Value::u128(raised)),
+            ("end", Value::u128(10_000)),
+            ("cap", Value::u128(1_000_000)),
+            (
+                "last_contribution",
+                Value::unnamed_variant("Ending", [Value::u128(9_000)]),
+            ),
+            ("first_period", Value::u128(13)),
+            ("last_period", Value::u128(20)),
+            ("fund_index", Value::u128(42)),
+        ])
+    }
+
+    #[test]
+    fn test_fund_from_value() {
+        let crowdloan = Crowdloan::from_value(2_000, &fund(250_000), 0).unwrap();
+        assert_eq!(
+            crowdloan.depositor,
+            AccountId32::from([1u8; 32]).to_ss58check_with_version(Ss58AddressFormat::custom(0))
+        );
+        assert_eq!(crowdloan.last_contribution, LastContribution::Ending(9_000));
+        assert_eq!(crowdloan.fund_index, 42);
+        assert_eq!(crowdloan.remaining(), 750_000);
+        assert_eq!(crowdloan.status(9_999), FundStatus::Open);
+        assert_eq!(crowdloan.status(10_001), FundStatus::Ended);
+
+        let capped = Crowdloan::from_value(2_000, &fund(1_000_000), 0).unwrap();
+        assert_eq!(capped.status(9_999), FundStatus::Capped);
+
+        let mut expected = b"crowdloan".to_vec();
+        expected.extend(42u32.encode());
+        assert_eq!(crowdloan.child_trie_key(), sp_core::blake2_256(&expected));
+    }
+
+    #[test]
+    fn test_contribution_from_entry() {
+        let value = (7_500u128, b"ref-1".to_vec()).encode();
+        let contribution = Contribution::from_entry(&[2u8; 32], &value, 2).unwrap();
+        assert_eq!(contribution.amount, 7_500);
+        assert_eq!(contribution.memo, b"ref-1");
+        assert_eq!(
+            contribution.contributor,
+            AccountId32::from([2u8; 32]).to_ss58check_with_version(Ss58AddressFormat::custom(2))
+        );
+
+        assert!(Contribution::from_entry(&[2u8; 16], &value, 2).is_err());
+    }
+
+    #[test]
+    fn test_lease_periods_and_auction_phase() {
+        let periods = LeasePeriods {
+            length: 100,
+            offset: 50,
+        };
+        assert_eq!(periods.period_at(49), None);
+        assert_eq!(periods.period_at(50), Some(0));
+        assert_eq!(periods.period_at(349), Some(2));
+        assert_eq!(periods.start_of(3), 350);
+        assert_eq!(periods.end_of(3), 449);
+
+        let auction = AuctionInfo {
+            index: 5,
+            first_lease_period: 13,
+            ending_period_start: 1_000,
+        };
+        assert_eq!(auction.lease_periods(), 13..=20);
+        assert_eq!(auction.phase(999, 200, 10), AuctionPhase::Opening);
+        assert_eq!(
+            auction.phase(1_025, 200, 10),
+            AuctionPhase::Ending { sample: 2 }
+        );
+        assert_eq!(auction.phase(1_200, 200, 10), AuctionPhase::VrfDelay);
+    }
+
+    #[test]
+    fn test_winning_bids() {
+        let count = slot_ranges().count();
+        assert_eq!(count, 36);
+
+        let mut ranges = vec![Value::unnamed_variant("None", []); count];
+        let bid = |para_id: u128, amount: u128| {
+            Value::unnamed_variant(
+                "Some",
+                [Value::unnamed_composite([
+                    account(3),
+                    Value::unnamed_composite([Value::u128(para_id)]),
+                    Value::u128(amount),
+                ])],
+            )
+        };
+        // ZeroSeven: all eight periods
+        ranges[7] = bid(2_000, 900);
+        // OneTwo
+        ranges[9] = bid(2_001, 300);
+
+        let bids = winning_bids(&Value::unnamed_composite(ranges), 13, 0).unwrap();
+        assert_eq!(bids.len(), 2);
+        assert_eq!((bids[0].first_period, bids[0].last_period), (13, 20));
+        assert_eq!(bids[0].para_id, 2_000);
+        assert_eq!((bids[1].first_period, bids[1].last_period), (14, 15));
+        assert_eq!(bids[1].amount, 300);
+    }
+}
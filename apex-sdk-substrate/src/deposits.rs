@@ -0,0 +1,501 @@
+//! Deposits of storage-creating operations
+//!
+//! Creating assets, proxies, multisig operations, identities and NFTs
+//! reserves part of the caller's balance until the storage is removed.
+//! [`DepositCalculator`] works out how much from the runtime's constants and
+//! the byte size of the data being stored, either for an intended
+//! [`DepositOperation`] or for encoded call data, so the amount can be shown
+//! before signing. [`TransactionExecutor::simulate`] includes the deposits of
+//! the simulated call in its report.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::DepositOperation;
+//!
+//! # fn example(adapter: &apex_sdk_substrate::SubstrateAdapter) -> apex_sdk_substrate::Result<()> {
+//! let deposits = adapter.deposits();
+//! let deposit = deposits.deposit(&DepositOperation::SetAssetMetadata { bytes: 12 })?;
+//! println!("{} reserved", deposit.total());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`TransactionExecutor::simulate`]: crate::TransactionExecutor::simulate
+
+use crate::assets::{value_bytes, value_number};
+use crate::constants::ConstantsManager;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use subxt::dynamic::{At as _, Value};
+use subxt::ext::scale_encode::EncodeAsType;
+use subxt::ext::scale_value::{scale::decode_as_type, Composite, ValueDef};
+use subxt::Metadata;
+
+/// Operation that reserves a deposit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DepositOperation {
+    /// Create an asset class with `Assets::create`
+    CreateAsset,
+    /// Set asset metadata whose name and symbol take `bytes` bytes
+    SetAssetMetadata { bytes: u32 },
+    /// Create an account for a non-sufficient asset with `Assets::touch`
+    TouchAssetAccount,
+    /// Approve a delegated asset transfer
+    ApproveAsset,
+    /// Add a proxy, including the base held for an account's first proxy
+    AddProxy,
+    /// Announce a proxy call, including the base held for an account's
+    /// first announcement
+    Announce,
+    /// Open a multisig operation, which the first approval pays for
+    OpenMultisig { threshold: u16 },
+    /// Set an identity whose encoded information takes `bytes` bytes
+    SetIdentity { bytes: u32 },
+    /// Register `count` sub-accounts of an identity
+    SetSubs { count: u32 },
+    /// Create an NFT collection
+    CreateNftCollection,
+    /// Mint an NFT
+    MintNft,
+    /// Set `bytes` bytes of NFT or collection metadata
+    SetNftMetadata { bytes: u32 },
+    /// Set an NFT attribute whose key and value take `bytes` bytes
+    SetNftAttribute { bytes: u32 },
+}
+
+/// One runtime constant's share of a deposit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepositComponent {
+    /// Constant the amount comes from, e.g. `Assets::MetadataDepositPerByte`
+    pub constant: String,
+    /// How many times the constant is charged, e.g. the number of bytes
+    pub units: u128,
+    /// Value of the constant in raw units of the native token
+    pub per_unit: u128,
+}
+
+impl DepositComponent {
+    /// Amount this component reserves
+    pub fn amount(&self) -> u128 {
+        self.per_unit.saturating_mul(self.units)
+    }
+}
+
+/// Deposit reserved by an operation, with its breakdown
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deposit {
+    /// Operation the deposit is for
+    pub operation: DepositOperation,
+    /// Constants the deposit is made of
+    pub components: Vec<DepositComponent>,
+}
+
+impl Deposit {
+    /// Total amount reserved, in raw units of the native token
+    pub fn total(&self) -> u128 {
+        self.components.iter().fold(0u128, |total, component| {
+            total.saturating_add(component.amount())
+        })
+    }
+}
+
+/// Computes deposits from the constants of the connected runtime
+#[derive(Debug, Clone)]
+pub struct DepositCalculator {
+    metadata: Metadata,
+    constants: ConstantsManager,
+}
+
+impl DepositCalculator {
+    /// Create a calculator for the runtime described by `metadata`
+    pub fn new(metadata: Metadata) -> Self {
+        Self {
+            constants: ConstantsManager::new(metadata.clone()),
+            metadata,
+        }
+    }
+
+    /// Deposit `operation` would reserve
+    ///
+    /// Fails if the runtime lacks the pallet or one of its deposit constants.
+    pub fn deposit(&self, operation: &DepositOperation) -> Result<Deposit> {
+        let components = match *operation {
+            DepositOperation::CreateAsset => vec![self.component("Assets", "AssetDeposit", 1)?],
+            DepositOperation::SetAssetMetadata { bytes } => vec![
+                self.component("Assets", "MetadataDepositBase", 1)?,
+                self.component("Assets", "MetadataDepositPerByte", bytes.into())?,
+            ],
+            DepositOperation::TouchAssetAccount => {
+                vec![self.component("Assets", "AssetAccountDeposit", 1)?]
+            }
+            DepositOperation::ApproveAsset => {
+                vec![self.component("Assets", "ApprovalDeposit", 1)?]
+            }
+            DepositOperation::AddProxy => vec![
+                self.component("Proxy", "ProxyDepositBase", 1)?,
+                self.component("Proxy", "ProxyDepositFactor", 1)?,
+            ],
+            DepositOperation::Announce => vec![
+                self.component("Proxy", "AnnouncementDepositBase", 1)?,
+                self.component("Proxy", "AnnouncementDepositFactor", 1)?,
+            ],
+            DepositOperation::OpenMultisig { threshold } => vec![
+                self.component("Multisig", "DepositBase", 1)?,
+                self.component("Multisig", "DepositFactor", threshold.into())?,
+            ],
+            DepositOperation::SetIdentity { bytes } => vec![
+                self.component("Identity", "BasicDeposit", 1)?,
+                self.component("Identity", "ByteDeposit", bytes.into())?,
+            ],
+            DepositOperation::SetSubs { count } => {
+                vec![self.component("Identity", "SubAccountDeposit", count.into())?]
+            }
+            DepositOperation::CreateNftCollection => {
+                vec![self.component("Nfts", "CollectionDeposit", 1)?]
+            }
+            DepositOperation::MintNft => vec![self.component("Nfts", "ItemDeposit", 1)?],
+            DepositOperation::SetNftMetadata { bytes } => vec![
+                self.component("Nfts", "MetadataDepositBase", 1)?,
+                self.component("Nfts", "DepositPerByte", bytes.into())?,
+            ],
+            DepositOperation::SetNftAttribute { bytes } => vec![
+                self.component("Nfts", "AttributeDepositBase", 1)?,
+                self.component("Nfts", "DepositPerByte", bytes.into())?,
+            ],
+        };
+        Ok(Deposit {
+            operation: *operation,
+            components,
+        })
+    }
+
+    /// Deposits SCALE-encoded call data would reserve
+    ///
+    /// Calls inside batches and `Proxy::proxy` are included; calls that
+    /// reserve nothing give an empty list. Trailing bytes after the call are
+    /// ignored.
+    pub fn for_call(&self, call_bytes: &[u8]) -> Result<Vec<Deposit>> {
+        self.operations(call_bytes)?
+            .iter()
+            .map(|operation| self.deposit(operation))
+            .collect()
+    }
+
+    /// Storage-creating operations in SCALE-encoded call data
+    pub fn operations(&self, call_bytes: &[u8]) -> Result<Vec<DepositOperation>> {
+        let call_ty = self.metadata.outer_enums().call_enum_ty();
+        let call = decode_as_type(&mut &call_bytes[..], call_ty, self.metadata.types())
+            .map_err(|e| Error::Encoding(format!("Cannot decode call for deposits: {}", e)))?;
+        let mut operations = Vec::new();
+        self.collect(&call, &mut operations);
+        Ok(operations)
+    }
+
+    fn component(&self, pallet: &str, name: &str, units: u128) -> Result<DepositComponent> {
+        Ok(DepositComponent {
+            constant: format!("{}::{}", pallet, name),
+            units,
+            per_unit: self.constants.constant(pallet, name)?,
+        })
+    }
+
+    /// Append the operations of a decoded runtime call, recursing into
+    /// batches and proxied calls
+    fn collect(&self, call: &Value<u32>, operations: &mut Vec<DepositOperation>) {
+        let ValueDef::Variant(pallet) = &call.value else {
+            return;
+        };
+        let Some(ValueDef::Variant(inner)) = pallet.values.values().next().map(|v| &v.value) else {
+            return;
+        };
+        let args = &inner.values;
+        let arg = |name: &str, position: usize| match args {
+            Composite::Named(_) => args.at(name),
+            Composite::Unnamed(values) => values.get(position),
+        };
+        let byte_len = |name: &str, position: usize| {
+            arg(name, position)
+                .and_then(value_bytes)
+                .map_or(0, |bytes| bytes.len() as u32)
+        };
+
+        let operation = match (pallet.name.as_str(), inner.name.as_str()) {
+            ("Assets", "create") => Some(DepositOperation::CreateAsset),
+            ("Assets", "set_metadata") => Some(DepositOperation::SetAssetMetadata {
+                bytes: byte_len("name", 1) + byte_len("symbol", 2),
+            }),
+            ("Assets", "touch" | "touch_other") => Some(DepositOperation::TouchAssetAccount),
+            ("Assets", "approve_transfer") => Some(DepositOperation::ApproveAsset),
+            ("Proxy", "add_proxy" | "create_pure") => Some(DepositOperation::AddProxy),
+            ("Proxy", "announce") => Some(DepositOperation::Announce),
+            ("Multisig", "as_multi" | "approve_as_multi") => arg("threshold", 0)
+                .and_then(value_number)
+                .and_then(|threshold| u16::try_from(threshold).ok())
+                .map(|threshold| DepositOperation::OpenMultisig { threshold }),
+            ("Identity", "set_identity") => arg("info", 0)
+                .and_then(|info| {
+                    info.encode_as_type(info.context, self.metadata.types())
+                        .ok()
+                })
+                .map(|encoded| DepositOperation::SetIdentity {
+                    bytes: encoded.len() as u32,
+                }),
+            ("Identity", "set_subs") => arg("subs", 0)
+                .and_then(sequence_len)
+                .map(|count| DepositOperation::SetSubs { count }),
+            ("Identity", "add_sub") => Some(DepositOperation::SetSubs { count: 1 }),
+            ("Nfts", "create") => Some(DepositOperation::CreateNftCollection),
+            ("Nfts", "mint") => Some(DepositOperation::MintNft),
+            ("Nfts", "set_metadata") => Some(DepositOperation::SetNftMetadata {
+                bytes: byte_len("data", 2),
+            }),
+            ("Nfts", "set_collection_metadata") => Some(DepositOperation::SetNftMetadata {
+                bytes: byte_len("data", 1),
+            }),
+            ("Nfts", "set_attribute") => Some(DepositOperation::SetNftAttribute {
+                bytes: byte_len("key", 3) + byte_len("value", 4),
+            }),
+            ("Utility", "batch" | "batch_all" | "force_batch") => {
+                if let Some(Value {
+                    value: ValueDef::Composite(calls),
+                    ..
+                }) = arg("calls", 0)
+                {
+                    for call in calls.values() {
+                        self.collect(call, operations);
+                    }
+                }
+                None
+            }
+            ("Proxy", "proxy") => {
+                if let Some(call) = arg("call", 2) {
+                    self.collect(call, operations);
+                }
+                None
+            }
+            _ => None,
+        };
+        operations.extend(operation);
+    }
+}
+
+/// Number of items in a sequence value
+fn sequence_len<T>(value: &Value<T>) -> Option<u32> {
+    match &value.value {
+        ValueDef::Composite(composite) => Some(composite.len() as u32),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parity_scale_codec::Encode;
+    use scale_info::{meta_type, TypeInfo};
+    use subxt::ext::frame_metadata::{
+        v15::{
+            CustomMetadata, ExtrinsicMetadata, OuterEnums, PalletConstantMetadata, PalletMetadata,
+            RuntimeMetadataV15,
+        },
+        RuntimeMetadataPrefixed,
+    };
+
+    #[allow(unused, non_camel_case_types)]
+    #[derive(Encode, TypeInfo)]
+    enum AssetsCall {
+        create {
+            id: u32,
+            admin: [u8; 32],
+            min_balance: u128,
+        },
+        set_metadata {
+            id: u32,
+            name: Vec<u8>,
+            symbol: Vec<u8>,
+            decimals: u8,
+        },
+        transfer {
+            id: u32,
+            target: [u8; 32],
+            amount: u128,
+        },
+    }
+
+    #[allow(unused)]
+    #[derive(Encode, TypeInfo)]
+    struct IdentityInfo {
+        display: Vec<u8>,
+        twitter: Vec<u8>,
+    }
+
+    #[allow(unused, non_camel_case_types)]
+    #[derive(Encode, TypeInfo)]
+    enum IdentityCall {
+        set_identity { info: IdentityInfo },
+    }
+
+    #[allow(unused, non_camel_case_types)]
+    #[derive(Encode, TypeInfo)]
+    enum UtilityCall {
+        batch_all { calls: Vec<RuntimeCall> },
+    }
+
+    #[allow(unused)]
+    #[derive(Encode, TypeInfo)]
+    enum RuntimeCall {
+        Assets(AssetsCall),
+        Identity(IdentityCall),
+        Utility(UtilityCall),
+    }
+
+    fn constant(name: &'static str, value: u128) -> PalletConstantMetadata {
+        PalletConstantMetadata {
+            name,
+            ty: meta_type::<u128>(),
+            value: value.encode(),
+            docs: vec![],
+        }
+    }
+
+    fn metadata() -> Metadata {
+        let pallet = |name, index, constants| PalletMetadata {
+            name,
+            storage: None,
+            calls: None,
+            event: None,
+            constants,
+            error: None,
+            index,
+            docs: vec![],
+        };
+        let metadata = RuntimeMetadataV15::new(
+            vec![
+                pallet(
+                    "Assets",
+                    50,
+                    vec![
+                        constant("AssetDeposit", 100_000_000_000),
+                        constant("AssetAccountDeposit", 1_000_000_000),
+                        constant("MetadataDepositBase", 2_000_000_000),
+                        constant("MetadataDepositPerByte", 10_000_000),
+                        constant("ApprovalDeposit", 100_000_000),
+                    ],
+                ),
+                pallet(
+                    "Identity",
+                    28,
+                    vec![
+                        constant("BasicDeposit", 200_000_000_000),
+                        constant("ByteDeposit", 100_000_000),
+                        constant("SubAccountDeposit", 20_000_000_000),
+                    ],
+                ),
+            ],
+            ExtrinsicMetadata {
+                version: 4,
+                signed_extensions: vec![],
+                address_ty: meta_type::<()>(),
+                call_ty: meta_type::<RuntimeCall>(),
+                signature_ty: meta_type::<()>(),
+                extra_ty: meta_type::<()>(),
+            },
+            meta_type::<()>(),
+            vec![],
+            OuterEnums {
+                call_enum_ty: meta_type::<RuntimeCall>(),
+                event_enum_ty: meta_type::<()>(),
+                error_enum_ty: meta_type::<()>(),
+            },
+            CustomMetadata {
+                map: Default::default(),
+            },
+        );
+        Metadata::try_from(RuntimeMetadataPrefixed::from(metadata)).expect("valid metadata")
+    }
+
+    #[test]
+    fn test_operation_deposits() {
+        let deposits = DepositCalculator::new(metadata());
+
+        let metadata = deposits
+            .deposit(&DepositOperation::SetAssetMetadata { bytes: 12 })
+            .unwrap();
+        assert_eq!(metadata.components.len(), 2);
+        assert_eq!(
+            metadata.components[1].constant,
+            "Assets::MetadataDepositPerByte"
+        );
+        assert_eq!(metadata.components[1].units, 12);
+        assert_eq!(metadata.total(), 2_000_000_000 + 12 * 10_000_000);
+
+        let subs = deposits
+            .deposit(&DepositOperation::SetSubs { count: 3 })
+            .unwrap();
+        assert_eq!(subs.total(), 60_000_000_000);
+
+        assert!(deposits
+            .deposit(&DepositOperation::AddProxy)
+            .unwrap_err()
+            .to_string()
+            .contains("Proxy::ProxyDepositBase not found"));
+    }
+
+    #[test]
+    fn test_call_deposits() {
+        let deposits = DepositCalculator::new(metadata());
+
+        let call = RuntimeCall::Utility(UtilityCall::batch_all {
+            calls: vec![
+                RuntimeCall::Assets(AssetsCall::create {
+                    id: 7,
+                    admin: [1u8; 32],
+                    min_balance: 1,
+                }),
+                RuntimeCall::Assets(AssetsCall::set_metadata {
+                    id: 7,
+                    name: b"Token".to_vec(),
+                    symbol: b"TKN".to_vec(),
+                    decimals: 10,
+                }),
+                RuntimeCall::Assets(AssetsCall::transfer {
+                    id: 7,
+                    target: [2u8; 32],
+                    amount: 5,
+                }),
+            ],
+        });
+        assert_eq!(
+            deposits.operations(&call.encode()).unwrap(),
+            vec![
+                DepositOperation::CreateAsset,
+                DepositOperation::SetAssetMetadata { bytes: 8 },
+            ]
+        );
+        let total: u128 = deposits
+            .for_call(&call.encode())
+            .unwrap()
+            .iter()
+            .map(Deposit::total)
+            .sum();
+        assert_eq!(total, 100_000_000_000 + 2_000_000_000 + 8 * 10_000_000);
+
+        // Identity deposits are charged on the encoded size of the information
+        let info = IdentityInfo {
+            display: b"Alice".to_vec(),
+            twitter: b"@alice".to_vec(),
+        };
+        let bytes = info.encoded_size() as u32;
+        let call = RuntimeCall::Identity(IdentityCall::set_identity { info });
+        assert_eq!(
+            deposits.operations(&call.encode()).unwrap(),
+            vec![DepositOperation::SetIdentity { bytes }]
+        );
+
+        let transfer = RuntimeCall::Assets(AssetsCall::transfer {
+            id: 7,
+            target: [2u8; 32],
+            amount: 5,
+        });
+        assert!(deposits.for_call(&transfer.encode()).unwrap().is_empty());
+        assert!(deposits.for_call(&[0xff]).is_err());
+    }
+}
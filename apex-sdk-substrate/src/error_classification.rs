@@ -0,0 +1,547 @@
+//! Typed classification of runtime and subxt errors
+//!
+//! [`categorize_dispatch_error`] maps a decoded `DispatchError` to an
+//! [`ErrorClassification`] from its variant rather than its message. Module
+//! errors are resolved against the metadata they were decoded with, so
+//! `Balances::InsufficientBalance` is known by pallet and error name whatever
+//! the pallet's index on the chain. [`categorize_subxt_error`] does the same
+//! for `subxt::Error`, covering RPC, metadata, codec and transaction pool
+//! failures. Adapters built `with_observability` classify their errors this
+//! way; rules of the facade's [`ErrorClassifier`] still take precedence.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::error_classification::categorize_subxt_error;
+//!
+//! # fn example(error: subxt::Error) {
+//! let classification = categorize_subxt_error(&error);
+//! if classification.is_retryable() {
+//!     // back off and retry
+//! }
+//! # }
+//! ```
+//!
+//! [`ErrorClassifier`]: apex_sdk_metrics::ErrorClassifier
+
+use crate::Error;
+use apex_sdk_metrics::{
+    categorize_error, ErrorCategory, ErrorClassification, ErrorImpact, ErrorSeverity,
+};
+use subxt::error::{
+    ArithmeticError, DispatchError, RpcError, TokenError, TransactionError, TransactionalError,
+};
+use subxt::ext::subxt_rpcs;
+
+/// Classification of a pallet error, by pallet and error name
+///
+/// Errors of Balances, Assets, Staking, Proxy, Sudo and the XCM pallet get a
+/// specific classification; errors of other pallets are classified by
+/// conventional names such as `NoPermission` or `TooMany*`, and as
+/// chain-specific otherwise. The `dispatch_error` and `pallet` labels are
+/// set.
+pub fn categorize_module_error(pallet: &str, error: &str) -> ErrorClassification {
+    use ErrorCategory::*;
+    use ErrorImpact::*;
+    use ErrorSeverity::*;
+
+    let (category, severity, impact, remediation) = match (pallet, error) {
+        ("Balances", "InsufficientBalance") | ("Assets", "BalanceLow") => (
+            ResourceExhaustion,
+            Medium,
+            Isolated,
+            "Top up the account or lower the amount",
+        ),
+        ("Balances", "ExistentialDeposit") | ("Assets", "BelowMinimum") => (
+            Validation,
+            Low,
+            Isolated,
+            "Send at least the existential deposit or minimum balance",
+        ),
+        ("Balances", "Expendability") => (
+            Validation,
+            Medium,
+            Isolated,
+            "Keep the existential deposit in the sender, or use a call that may reap it",
+        ),
+        ("Balances", "LiquidityRestrictions" | "VestingBalance") | ("Assets", "Frozen") => (
+            Transaction,
+            Medium,
+            Isolated,
+            "Part of the balance is locked, frozen or vesting; wait or lower the amount",
+        ),
+        ("Balances", "TooManyReserves" | "TooManyHolds" | "TooManyFreezes") => (
+            ResourceExhaustion,
+            Medium,
+            Isolated,
+            "Release existing reserves, holds or freezes first",
+        ),
+        ("Staking", "InsufficientBond") => (
+            ResourceExhaustion,
+            Medium,
+            Isolated,
+            "Bond at least the minimum bond",
+        ),
+        ("Staking", "NotController" | "NotStash") | ("Proxy", "NotProxy" | "Unproxyable") => (
+            Authentication,
+            High,
+            Isolated,
+            "Sign with the account that controls the operation",
+        ),
+        ("Sudo", "RequireSudo") => (
+            Authentication,
+            High,
+            Blocking,
+            "Only the sudo key can dispatch this call",
+        ),
+        ("XcmPallet" | "PolkadotXcm", "Unreachable" | "SendFailure") => (
+            ExternalService,
+            High,
+            Retryable,
+            "Check that the destination chain has an open channel and retry",
+        ),
+        (_, error) if error.starts_with("Insufficient") => (
+            ResourceExhaustion,
+            Medium,
+            Isolated,
+            "Top up the account or lower the amount",
+        ),
+        (_, "NoPermission" | "NotOwner" | "NotAuthorized" | "Unauthorized") => (
+            Authentication,
+            High,
+            Isolated,
+            "Sign with an account that has permission",
+        ),
+        (_, error) if error.starts_with("TooMany") || error.starts_with("TooBig") => (
+            ResourceExhaustion,
+            Medium,
+            Isolated,
+            "Reduce the number or size of items",
+        ),
+        (_, "Unknown" | "NotFound" | "NoAccount" | "AlreadyExists" | "InUse") => (
+            Validation,
+            Medium,
+            Isolated,
+            "Check the identifiers the call refers to",
+        ),
+        _ => (
+            ChainSpecific,
+            Medium,
+            Isolated,
+            "See the pallet documentation for this error",
+        ),
+    };
+
+    let dispatch_error = format!("{}::{}", pallet, error);
+    ErrorClassification::new(
+        category,
+        severity,
+        impact,
+        format!("Dispatch failed with {}", dispatch_error),
+    )
+    .with_remediation(remediation)
+    .with_label("dispatch_error", dispatch_error)
+    .with_label("pallet", pallet)
+}
+
+/// Classification of a decoded `DispatchError`
+///
+/// Module errors whose pallet or error index is not in the metadata are
+/// classified as chain-specific.
+pub fn categorize_dispatch_error(error: &DispatchError) -> ErrorClassification {
+    use ErrorCategory::*;
+    use ErrorImpact::*;
+    use ErrorSeverity::*;
+
+    let (category, severity, impact, description, remediation) = match error {
+        DispatchError::Module(module) => {
+            return match module.details() {
+                Ok(details) => {
+                    categorize_module_error(details.pallet.name(), &details.variant.name)
+                }
+                Err(_) => ErrorClassification::new(
+                    ChainSpecific,
+                    Medium,
+                    Isolated,
+                    module.details_string(),
+                )
+                .with_remediation("Refresh the runtime metadata to resolve the error")
+                .with_label("pallet_index", module.pallet_index().to_string()),
+            };
+        }
+        DispatchError::BadOrigin | DispatchError::RootNotAllowed => (
+            Authentication,
+            High,
+            Isolated,
+            "Call dispatched from the wrong origin",
+            "Sign with an account allowed to dispatch the call",
+        ),
+        DispatchError::CannotLookup => (
+            Validation,
+            Medium,
+            Isolated,
+            "Account lookup failed",
+            "Check the addresses the call refers to",
+        ),
+        DispatchError::ConsumerRemaining
+        | DispatchError::NoProviders
+        | DispatchError::TooManyConsumers => (
+            Validation,
+            Medium,
+            Isolated,
+            "Account reference counts do not allow the operation",
+            "Remove dependent state, or keep the account alive with the existential deposit",
+        ),
+        DispatchError::Token(token) => match token {
+            TokenError::FundsUnavailable | TokenError::NotExpendable | TokenError::OnlyProvider => {
+                (
+                    ResourceExhaustion,
+                    Medium,
+                    Isolated,
+                    "Funds are unavailable",
+                    "Top up the account or lower the amount",
+                )
+            }
+            TokenError::BelowMinimum => (
+                Validation,
+                Low,
+                Isolated,
+                "Amount is below the minimum balance",
+                "Send at least the existential deposit or minimum balance",
+            ),
+            TokenError::Frozen | TokenError::Blocked => (
+                Transaction,
+                Medium,
+                Isolated,
+                "Funds are frozen or the account is blocked",
+                "Wait for the freeze to be lifted",
+            ),
+            _ => (
+                Validation,
+                Medium,
+                Isolated,
+                "Token operation not possible",
+                "Check the asset and accounts involved",
+            ),
+        },
+        DispatchError::Arithmetic(arithmetic) => (
+            Validation,
+            Medium,
+            Isolated,
+            match arithmetic {
+                ArithmeticError::Underflow => "Arithmetic underflow",
+                ArithmeticError::Overflow => "Arithmetic overflow",
+                _ => "Arithmetic error",
+            },
+            "Check the amounts in the call",
+        ),
+        DispatchError::Transactional(transactional) => (
+            Internal,
+            High,
+            Isolated,
+            match transactional {
+                TransactionalError::LimitReached => "Transactional layer limit reached",
+                _ => "Transactional layer error",
+            },
+            "Reduce the nesting of the call",
+        ),
+        DispatchError::Exhausted => (
+            ResourceExhaustion,
+            Medium,
+            Isolated,
+            "Runtime resources exhausted",
+            "Reduce the size of the data the call reads or writes",
+        ),
+        DispatchError::Corruption => (
+            ChainSpecific,
+            Critical,
+            Blocking,
+            "Chain state is corrupt",
+            "Report the issue to the chain's maintainers",
+        ),
+        DispatchError::Unavailable => (
+            ChainSpecific,
+            Low,
+            Retryable,
+            "A resource is temporarily unavailable",
+            "Retry later",
+        ),
+        _ => (
+            ChainSpecific,
+            Medium,
+            Isolated,
+            "Dispatch failed",
+            "See the runtime documentation for this error",
+        ),
+    };
+
+    ErrorClassification::new(category, severity, impact, description)
+        .with_remediation(remediation)
+        .with_label("dispatch_error", format!("{:?}", error))
+}
+
+/// Classification of a `subxt::Error`
+///
+/// Runtime errors go through [`categorize_dispatch_error`]; JSON-RPC errors
+/// from the transaction pool are classified by their code. Errors carrying
+/// only a message fall back to the message heuristics.
+pub fn categorize_subxt_error(error: &subxt::Error) -> ErrorClassification {
+    use ErrorCategory::*;
+    use ErrorImpact::*;
+    use ErrorSeverity::*;
+
+    let classification = |category, severity, impact, description: &str, remediation: &str| {
+        ErrorClassification::new(category, severity, impact, description)
+            .with_remediation(remediation)
+    };
+
+    match error {
+        subxt::Error::Runtime(dispatch) => categorize_dispatch_error(dispatch),
+        subxt::Error::Rpc(RpcError::ClientError(rpc)) => categorize_rpc_error(rpc),
+        subxt::Error::Rpc(RpcError::SubscriptionDropped) | subxt::Error::Io(_) => classification(
+            Network,
+            High,
+            Retryable,
+            "Connection to the node lost",
+            "Reconnect and retry",
+        )
+        .with_label("retryable", "true"),
+        subxt::Error::Rpc(_) => classification(
+            ExternalService,
+            Medium,
+            Retryable,
+            "Node refused the request",
+            "Retry later or use another endpoint",
+        )
+        .with_label("retryable", "true"),
+        subxt::Error::Metadata(_) | subxt::Error::MetadataDecoding(_) => classification(
+            Configuration,
+            High,
+            Blocking,
+            "Runtime metadata does not match the request",
+            "Refresh the metadata after a runtime upgrade and check pallet and call names",
+        ),
+        subxt::Error::Decode(_)
+        | subxt::Error::Encode(_)
+        | subxt::Error::Extrinsic(_)
+        | subxt::Error::StorageAddress(_) => classification(
+            Validation,
+            Medium,
+            Isolated,
+            "Values do not match the runtime's types",
+            "Check the call arguments or storage keys against the metadata",
+        ),
+        subxt::Error::Transaction(TransactionError::Invalid(_)) => classification(
+            Transaction,
+            Medium,
+            Isolated,
+            "Transaction pool rejected the transaction",
+            "Check the nonce, balance and mortality of the transaction",
+        ),
+        subxt::Error::Transaction(TransactionError::Dropped(_)) => classification(
+            Transaction,
+            Medium,
+            Retryable,
+            "Transaction dropped from the pool",
+            "Resubmit the transaction",
+        )
+        .with_label("retryable", "true"),
+        subxt::Error::Transaction(_) | subxt::Error::Block(_) => classification(
+            Transaction,
+            Medium,
+            Isolated,
+            "Transaction could not be tracked",
+            "Check the transaction status on chain",
+        ),
+        subxt::Error::Codec(_) | subxt::Error::Serialization(_) | subxt::Error::Unknown(_) => {
+            classification(
+                Internal,
+                Medium,
+                Isolated,
+                "Response could not be decoded",
+                "Review error details and SDK logs",
+            )
+        }
+        other => categorize_error(&other.to_string(), None),
+    }
+}
+
+/// Classification of a JSON-RPC client error
+fn categorize_rpc_error(error: &subxt_rpcs::Error) -> ErrorClassification {
+    use ErrorCategory::*;
+    use ErrorImpact::*;
+    use ErrorSeverity::*;
+
+    let (category, severity, impact, description, remediation) = match error {
+        // Author RPC codes of the transaction pool
+        subxt_rpcs::Error::User(user) => match user.code {
+            1010 => (
+                Transaction,
+                Medium,
+                Isolated,
+                "Invalid transaction",
+                "Check the nonce, balance and mortality of the transaction",
+            ),
+            1012 | 1014 => (
+                Transaction,
+                Medium,
+                Retryable,
+                "Transaction temporarily refused by the pool",
+                "Resubmit later, with a higher tip if the priority is too low",
+            ),
+            1013 => (
+                Transaction,
+                Low,
+                Isolated,
+                "Transaction already in the pool",
+                "Wait for the pending transaction",
+            ),
+            _ => (
+                ExternalService,
+                Medium,
+                Isolated,
+                "Node returned an error",
+                "Review the node's error message",
+            ),
+        },
+        subxt_rpcs::Error::Client(_) | subxt_rpcs::Error::DisconnectedWillReconnect(_) => (
+            Network,
+            High,
+            Retryable,
+            "Connection to the node failed",
+            "Check network connection and RPC endpoint availability",
+        ),
+        subxt_rpcs::Error::InsecureUrl(_) => (
+            Configuration,
+            High,
+            Blocking,
+            "Endpoint URL is insecure",
+            "Use a wss:// or https:// endpoint",
+        ),
+        _ => (
+            ExternalService,
+            High,
+            Degraded,
+            "Node response could not be decoded",
+            "Check RPC provider status and consider fallback providers",
+        ),
+    };
+
+    let classification = ErrorClassification::new(category, severity, impact, description)
+        .with_remediation(remediation);
+    let classification = match error {
+        subxt_rpcs::Error::User(user) => {
+            classification.with_label("rpc_code", user.code.to_string())
+        }
+        _ => classification,
+    };
+    if classification.is_retryable() {
+        classification.with_label("retryable", "true")
+    } else {
+        classification
+    }
+}
+
+/// Errors returned by adapter calls, classified by type where possible
+pub(crate) trait AdapterError {
+    /// Typed classification, or `None` to use the message heuristics
+    fn classification(&self) -> Option<ErrorClassification>;
+}
+
+impl AdapterError for Error {
+    fn classification(&self) -> Option<ErrorClassification> {
+        match self {
+            Error::Subxt(error) => Some(categorize_subxt_error(error)),
+            _ => None,
+        }
+    }
+}
+
+impl AdapterError for apex_sdk_core::SdkError {
+    fn classification(&self) -> Option<ErrorClassification> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_errors() {
+        let balance = categorize_module_error("Balances", "InsufficientBalance");
+        assert_eq!(balance.category, ErrorCategory::ResourceExhaustion);
+        assert_eq!(
+            balance.labels["dispatch_error"],
+            "Balances::InsufficientBalance"
+        );
+        assert_eq!(balance.labels["pallet"], "Balances");
+
+        let sudo = categorize_module_error("Sudo", "RequireSudo");
+        assert_eq!(sudo.category, ErrorCategory::Authentication);
+        assert_eq!(sudo.impact, ErrorImpact::Blocking);
+
+        // Conventional names of other pallets
+        assert_eq!(
+            categorize_module_error("Nfts", "NoPermission").category,
+            ErrorCategory::Authentication
+        );
+        assert_eq!(
+            categorize_module_error("Multisig", "TooManySignatories").category,
+            ErrorCategory::ResourceExhaustion
+        );
+        assert_eq!(
+            categorize_module_error("Referenda", "NotOngoing").category,
+            ErrorCategory::ChainSpecific
+        );
+    }
+
+    #[test]
+    fn test_dispatch_errors() {
+        let origin = categorize_dispatch_error(&DispatchError::BadOrigin);
+        assert_eq!(origin.category, ErrorCategory::Authentication);
+        assert_eq!(origin.labels["dispatch_error"], "BadOrigin");
+
+        let funds = categorize_dispatch_error(&DispatchError::Token(TokenError::FundsUnavailable));
+        assert_eq!(funds.category, ErrorCategory::ResourceExhaustion);
+        assert!(!funds.is_retryable());
+
+        assert!(categorize_dispatch_error(&DispatchError::Unavailable).is_retryable());
+        assert_eq!(
+            categorize_dispatch_error(&DispatchError::Corruption).severity,
+            ErrorSeverity::Critical
+        );
+    }
+
+    #[test]
+    fn test_subxt_errors() {
+        // The message mentions neither gas nor nonce, yet the variant is known
+        let runtime = subxt::Error::Runtime(DispatchError::Arithmetic(ArithmeticError::Overflow));
+        assert_eq!(
+            categorize_subxt_error(&runtime).category,
+            ErrorCategory::Validation
+        );
+
+        let dropped = subxt::Error::Transaction(TransactionError::Dropped("full".to_string()));
+        assert!(categorize_subxt_error(&dropped).is_retryable());
+
+        let priority = subxt::Error::Rpc(RpcError::ClientError(subxt_rpcs::Error::User(
+            subxt_rpcs::UserError {
+                code: 1014,
+                message: "Priority is too low".to_string(),
+                data: None,
+            },
+        )));
+        let classification = categorize_subxt_error(&priority);
+        assert_eq!(classification.category, ErrorCategory::Transaction);
+        assert!(classification.is_retryable());
+        assert_eq!(classification.labels["rpc_code"], "1014");
+
+        let adapter = Error::from(subxt::Error::Other("rate limit exceeded".to_string()));
+        assert_eq!(
+            adapter.classification().unwrap().category,
+            ErrorCategory::RateLimit
+        );
+        assert!(Error::Connection("down".to_string())
+            .classification()
+            .is_none());
+    }
+}
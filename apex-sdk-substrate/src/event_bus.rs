@@ -0,0 +1,1262 @@
+//! Shared finalized-block subscription with per-consumer queues
+//!
+//! Subsystems that follow the chain (transaction monitor, XCM delivery
+//! tracking, indexers, balance watchers) each used to open their own
+//! finalized-block subscription and fetch the same events. The [`EventBus`]
+//! subscribes once per adapter, fetches each block's events once and fans the
+//! result out to every consumer.
+//!
+//! Each consumer has its own queue with a [`DeliveryPolicy`]. A slow consumer
+//! never stalls the subscription or other consumers. By default the queue is
+//! bounded and new blocks are dropped for that consumer only while it is
+//! full. [`DeliveryPolicy::Replay`] keeps every block instead, and
+//! [`DeliveryPolicy::Latest`] keeps only the newest.
+//! [`BusReceiver::recv_delivery`] reports where blocks were skipped, and
+//! [`EventBus::consumer_stats`] reports queue depth, drops and how many blocks
+//! each consumer is behind.
+//!
+//! Consumers that need blocks before finality, such as the transaction
+//! monitor reporting inclusion early, use [`EventBus::subscribe_best`]. Best
+//! blocks are published on a second subscription, opened only once a
+//! consumer asks for them.
+//!
+//! A failed or closed subscription is reopened with exponential backoff and
+//! jitter. Finalized blocks missed while it was down are fetched and
+//! published in order, so consumers keep their state across reconnects;
+//! [`EventBus::health`] reports the subscription's state and last block.
+//!
+//! A consumer that must not miss blocks across its own restarts saves
+//! [`FinalizedBlock::resume_token`] after processing each block and
+//! subscribes with [`EventBus::subscribe_from`], which delivers the blocks
+//! finalized since then before the live stream.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::SubstrateAdapter;
+//!
+//! # async fn example() -> apex_sdk_substrate::Result<()> {
+//! let adapter = SubstrateAdapter::connect("wss://westend-rpc.polkadot.io").await?;
+//! let mut blocks = adapter.event_bus().subscribe("balance-watcher");
+//!
+//! while let Some(block) = blocks.recv().await {
+//!     for event in block.events.iter().flatten() {
+//!         if event.pallet_name() == "Balances" && event.variant_name() == "Transfer" {
+//!             println!("transfer in block #{}", block.number);
+//!         }
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::PolkadotConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use subxt::blocks::Block;
+use subxt::events::Events;
+use subxt::utils::H256;
+use subxt::OnlineClient;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+/// Default queue capacity per consumer
+pub const DEFAULT_CONSUMER_CAPACITY: usize = 64;
+
+/// Delay ceiling for the first resubscription attempt
+const RESUBSCRIBE_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Longest delay between resubscription attempts
+const RESUBSCRIBE_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Most finalized blocks fetched after a resubscription to fill the gap
+pub const MAX_BACKFILL_BLOCKS: u64 = 256;
+
+/// Most finalized blocks fetched when resuming from a [`ResumeToken`]
+/// (a day of 6 second blocks)
+pub const MAX_RESUME_BLOCKS: u64 = 14_400;
+
+/// Which chain head a bus subscription follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Head {
+    Finalized,
+    Best,
+}
+
+/// A finalized block and its events
+pub struct FinalizedBlock {
+    /// Block number
+    pub number: u64,
+    /// Block hash
+    pub hash: subxt::utils::H256,
+    /// The block, for extrinsic access
+    pub block: Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    /// Events emitted in the block
+    pub events: Events<PolkadotConfig>,
+}
+
+impl FinalizedBlock {
+    /// Token to resume a subscription after this block
+    pub fn resume_token(&self) -> ResumeToken {
+        ResumeToken {
+            number: self.number,
+            hash: self.hash,
+        }
+    }
+}
+
+/// Last finalized block a consumer processed, to resume its subscription
+/// after a restart or reconnect
+///
+/// Persist it after processing each block and pass it to
+/// [`EventBus::subscribe_from`]. The token is stable across processes and
+/// serializes as `{"number": .., "hash": "0x.."}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResumeToken {
+    /// Block number
+    pub number: u64,
+    /// Block hash
+    pub hash: H256,
+}
+
+/// A block on the best chain and its events
+///
+/// Unlike a [`FinalizedBlock`] it can still be orphaned by a reorg: a later
+/// best block at the same or a lower height replaces it.
+pub struct BestBlock {
+    /// Block number
+    pub number: u64,
+    /// Block hash
+    pub hash: subxt::utils::H256,
+    /// Hash of the parent block
+    pub parent_hash: subxt::utils::H256,
+    /// The block, for extrinsic access
+    pub block: Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    /// Events emitted in the block
+    pub events: Events<PolkadotConfig>,
+}
+
+/// State of a bus subscription
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubscriptionHealth {
+    /// The subscription is open
+    pub subscribed: bool,
+    /// Number of the last block published
+    pub last_block: Option<u64>,
+    /// Hash of the last block published
+    pub last_hash: Option<H256>,
+    /// When the last block was published
+    pub last_block_at: Option<Instant>,
+    /// Times the subscription was reopened after failing or ending
+    pub resubscriptions: u64,
+    /// Failed attempts since the last published block
+    pub consecutive_failures: u32,
+    /// Finalized blocks fetched to fill gaps after resubscribing
+    pub backfilled: u64,
+}
+
+impl SubscriptionHealth {
+    /// Time since the last block was published
+    pub fn since_last_block(&self) -> Option<Duration> {
+        self.last_block_at.map(|at| at.elapsed())
+    }
+}
+
+/// Shared, updatable [`SubscriptionHealth`]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HealthHandle(Arc<Mutex<SubscriptionHealth>>);
+
+impl HealthHandle {
+    pub(crate) fn get(&self) -> SubscriptionHealth {
+        self.lock().clone()
+    }
+
+    fn update(&self, f: impl FnOnce(&mut SubscriptionHealth)) {
+        f(&mut self.lock());
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SubscriptionHealth> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Exponential resubscription delays with jitter
+#[derive(Debug, Default)]
+struct Backoff {
+    attempt: u32,
+}
+
+impl Backoff {
+    /// Delay before the next attempt
+    fn next_delay(&mut self) -> Duration {
+        let delay = Self::delay(self.attempt, rand::random::<f64>());
+        self.attempt = self.attempt.saturating_add(1);
+        delay
+    }
+
+    /// Start again from the base delay
+    fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    /// Delay for `attempt`: a ceiling doubling from the base delay up to the
+    /// maximum, half of it fixed and half scaled by `jitter` in `0.0..1.0`
+    ///
+    /// The jitter keeps clients that lost the same node from reconnecting
+    /// in lockstep.
+    fn delay(attempt: u32, jitter: f64) -> Duration {
+        let ceiling = RESUBSCRIBE_BASE_DELAY
+            .saturating_mul(1 << attempt.min(16))
+            .min(RESUBSCRIBE_MAX_DELAY);
+        let half = ceiling / 2;
+        half + half.mul_f64(jitter.clamp(0.0, 1.0))
+    }
+}
+
+/// How a consumer's queue treats items it has no room for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryPolicy {
+    /// Queue up to `capacity` items and drop new ones while full
+    ///
+    /// At-most-once: a consumer that falls behind loses items but never
+    /// holds more than `capacity` in memory.
+    Bounded {
+        /// Items queued at most
+        capacity: usize,
+    },
+    /// Queue every item until it is received
+    ///
+    /// At-least-once for alerting and accounting consumers: nothing is
+    /// dropped, so the queue grows for as long as the consumer lags.
+    Replay,
+    /// Keep only the newest item, replacing one not yet received
+    ///
+    /// For UIs and dashboards that only show the current state.
+    Latest,
+}
+
+impl Default for DeliveryPolicy {
+    fn default() -> Self {
+        Self::Bounded {
+            capacity: DEFAULT_CONSUMER_CAPACITY,
+        }
+    }
+}
+
+/// What a consumer receives from [`BusReceiver::recv_delivery`]
+#[derive(Debug)]
+pub enum Delivery<T> {
+    /// The next item
+    Item(Arc<T>),
+    /// This many items were dropped or replaced here by the delivery policy
+    Skipped(u64),
+}
+
+/// Queue statistics for a consumer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerStats {
+    /// Consumer name
+    pub name: String,
+    /// Delivery policy of the consumer's queue
+    pub policy: DeliveryPolicy,
+    /// Items waiting in the queue
+    pub queued: usize,
+    /// Most items ever waiting in the queue
+    pub max_queued: usize,
+    /// Items delivered to the queue
+    pub delivered: u64,
+    /// Items dropped because the queue was full, or replaced by newer ones
+    pub dropped: u64,
+    /// Items published since the consumer subscribed but not yet received,
+    /// including dropped ones
+    pub lag: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    delivered: AtomicU64,
+    dropped: AtomicU64,
+    received: AtomicU64,
+}
+
+enum Entry<T> {
+    Item(Arc<T>),
+    Gap(u64),
+}
+
+struct QueueState<T> {
+    entries: VecDeque<Entry<T>>,
+    /// Items in `entries`, gaps excluded
+    items: usize,
+    max_items: usize,
+    /// The bus side is gone; the receiver drains what is left
+    closed: bool,
+    receiver_gone: bool,
+}
+
+/// Outcome of queueing an item
+enum Queued {
+    Delivered,
+    /// The queue was full and the item was dropped
+    Dropped,
+    /// The item replaced an older one
+    Replaced,
+    /// The receiver is gone
+    Gone,
+}
+
+/// Single-receiver queue applying a [`DeliveryPolicy`]
+struct Queue<T> {
+    policy: DeliveryPolicy,
+    state: Mutex<QueueState<T>>,
+    notify: Notify,
+}
+
+impl<T> Queue<T> {
+    fn new(policy: DeliveryPolicy) -> Self {
+        Self {
+            policy,
+            state: Mutex::new(QueueState {
+                entries: VecDeque::new(),
+                items: 0,
+                max_items: 0,
+                closed: false,
+                receiver_gone: false,
+            }),
+            notify: Notify::new(),
+        }
+    }
+
+    fn push(&self, item: Arc<T>) -> Queued {
+        let mut state = self.lock();
+        if state.receiver_gone {
+            return Queued::Gone;
+        }
+
+        let queued = match self.policy {
+            DeliveryPolicy::Bounded { capacity } if state.items >= capacity.max(1) => {
+                Self::add_gap(&mut state, 1);
+                return Queued::Dropped;
+            }
+            DeliveryPolicy::Latest if state.items > 0 => {
+                state.entries.retain(|entry| matches!(entry, Entry::Gap(_)));
+                state.items = 0;
+                Self::add_gap(&mut state, 1);
+                Queued::Replaced
+            }
+            _ => Queued::Delivered,
+        };
+
+        state.entries.push_back(Entry::Item(item));
+        state.items += 1;
+        state.max_items = state.max_items.max(state.items);
+        drop(state);
+        self.notify.notify_one();
+        queued
+    }
+
+    /// Record `count` skipped items after those queued so far
+    fn push_gap(&self, count: u64) {
+        Self::add_gap(&mut self.lock(), count);
+        self.notify.notify_one();
+    }
+
+    fn add_gap(state: &mut QueueState<T>, count: u64) {
+        match state.entries.back_mut() {
+            Some(Entry::Gap(skipped)) => *skipped += count,
+            _ => state.entries.push_back(Entry::Gap(count)),
+        }
+    }
+
+    /// Next entry, or `None` once the queue is closed and drained
+    async fn next(&self) -> Option<Entry<T>> {
+        loop {
+            {
+                let mut state = self.lock();
+                if let Some(entry) = state.entries.pop_front() {
+                    if matches!(entry, Entry::Item(_)) {
+                        state.items -= 1;
+                    }
+                    return Some(entry);
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.notify.notify_one();
+    }
+
+    fn receiver_gone(&self) -> bool {
+        self.lock().receiver_gone
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueState<T>> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Bus side of a consumer's queue; closes the queue when dropped
+struct Consumer<T> {
+    name: String,
+    queue: Arc<Queue<T>>,
+    counters: Arc<Counters>,
+    /// Items the bus had published when the consumer subscribed
+    subscribed_at: u64,
+}
+
+impl<T> Consumer<T> {
+    fn new(name: String, policy: DeliveryPolicy) -> (Self, BusReceiver<T>) {
+        let queue = Arc::new(Queue::new(policy));
+        let counters = Arc::new(Counters::default());
+        let receiver = BusReceiver {
+            name: name.clone(),
+            queue: queue.clone(),
+            counters: counters.clone(),
+        };
+        let consumer = Self {
+            name,
+            queue,
+            counters,
+            subscribed_at: 0,
+        };
+        (consumer, receiver)
+    }
+
+    /// Queue an item; returns `false` once the receiver is gone
+    fn deliver(&self, item: Arc<T>) -> bool {
+        match self.queue.push(item) {
+            Queued::Delivered => {
+                self.counters.delivered.fetch_add(1, Ordering::Relaxed);
+            }
+            Queued::Replaced => {
+                self.counters.delivered.fetch_add(1, Ordering::Relaxed);
+                self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            Queued::Dropped => {
+                let dropped = self.counters.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                warn!(
+                    consumer = %self.name,
+                    dropped,
+                    "Event bus consumer is lagging, dropping block"
+                );
+            }
+            Queued::Gone => {
+                debug!(consumer = %self.name, "Event bus consumer closed");
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl<T> Drop for Consumer<T> {
+    fn drop(&mut self) {
+        self.queue.close();
+    }
+}
+
+/// Receiving end of a bus subscription
+pub struct BusReceiver<T = FinalizedBlock> {
+    name: String,
+    queue: Arc<Queue<T>>,
+    counters: Arc<Counters>,
+}
+
+impl<T> BusReceiver<T> {
+    /// Receive the next item, or `None` once the bus is gone
+    ///
+    /// Items skipped by the delivery policy are passed over silently; use
+    /// [`recv_delivery`](Self::recv_delivery) to be told about them.
+    pub async fn recv(&mut self) -> Option<Arc<T>> {
+        loop {
+            match self.recv_delivery().await? {
+                Delivery::Item(item) => return Some(item),
+                Delivery::Skipped(_) => {}
+            }
+        }
+    }
+
+    /// Receive the next item, or a notice of items skipped before it
+    pub async fn recv_delivery(&mut self) -> Option<Delivery<T>> {
+        match self.queue.next().await? {
+            Entry::Item(item) => {
+                self.counters.received.fetch_add(1, Ordering::Relaxed);
+                Some(Delivery::Item(item))
+            }
+            Entry::Gap(skipped) => Some(Delivery::Skipped(skipped)),
+        }
+    }
+
+    /// Consumer name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Delivery policy of this consumer's queue
+    pub fn policy(&self) -> DeliveryPolicy {
+        self.queue.policy
+    }
+
+    /// Number of items dropped for this consumer so far
+    pub fn dropped(&self) -> u64 {
+        self.counters.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Drop for BusReceiver<T> {
+    fn drop(&mut self) {
+        self.queue.lock().receiver_gone = true;
+    }
+}
+
+/// Fans items out to per-consumer queues without blocking
+struct Fanout<T> {
+    consumers: Mutex<Vec<Consumer<T>>>,
+    published: AtomicU64,
+}
+
+impl<T> Fanout<T> {
+    fn new() -> Self {
+        Self {
+            consumers: Mutex::new(Vec::new()),
+            published: AtomicU64::new(0),
+        }
+    }
+
+    fn subscribe(&self, name: String, policy: DeliveryPolicy) -> BusReceiver<T> {
+        let (mut consumer, receiver) = Consumer::new(name, policy);
+        let mut consumers = self.lock();
+        consumer.subscribed_at = self.published.load(Ordering::Relaxed);
+        consumers.push(consumer);
+        receiver
+    }
+
+    /// Deliver an item to every consumer according to its policy
+    fn publish(&self, item: Arc<T>) {
+        // Counted under the lock so subscribers see a consistent baseline
+        let mut consumers = self.lock();
+        self.published.fetch_add(1, Ordering::Relaxed);
+        consumers.retain(|consumer| consumer.deliver(item.clone()));
+    }
+
+    fn stats(&self) -> Vec<ConsumerStats> {
+        let published = self.published.load(Ordering::Relaxed);
+        self.lock()
+            .iter()
+            .filter_map(|consumer| {
+                let (queued, max_queued) = {
+                    let state = consumer.queue.lock();
+                    if state.receiver_gone {
+                        return None;
+                    }
+                    (state.items, state.max_items)
+                };
+                let received = consumer.counters.received.load(Ordering::Relaxed);
+                Some(ConsumerStats {
+                    name: consumer.name.clone(),
+                    policy: consumer.queue.policy,
+                    queued,
+                    max_queued,
+                    delivered: consumer.counters.delivered.load(Ordering::Relaxed),
+                    dropped: consumer.counters.dropped.load(Ordering::Relaxed),
+                    lag: published
+                        .saturating_sub(consumer.subscribed_at)
+                        .saturating_sub(received),
+                })
+            })
+            .collect()
+    }
+
+    fn has_consumers(&self) -> bool {
+        self.lock()
+            .iter()
+            .any(|consumer| !consumer.queue.receiver_gone())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Consumer<T>>> {
+        self.consumers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Single finalized-block subscription shared by all consumers of a chain
+pub struct EventBus {
+    client: OnlineClient<PolkadotConfig>,
+    fanout: Arc<Fanout<FinalizedBlock>>,
+    task: Mutex<Option<JoinHandle<()>>>,
+    health: HealthHandle,
+    best_fanout: Arc<Fanout<BestBlock>>,
+    best_task: Mutex<Option<JoinHandle<()>>>,
+    best_health: HealthHandle,
+}
+
+impl EventBus {
+    /// Create a bus for a client
+    ///
+    /// The subscription is opened when the first consumer subscribes.
+    pub fn new(client: OnlineClient<PolkadotConfig>) -> Self {
+        Self {
+            client,
+            fanout: Arc::new(Fanout::new()),
+            task: Mutex::new(None),
+            health: HealthHandle::default(),
+            best_fanout: Arc::new(Fanout::new()),
+            best_task: Mutex::new(None),
+            best_health: HealthHandle::default(),
+        }
+    }
+
+    /// Subscribe with the default queue capacity
+    pub fn subscribe(&self, name: impl Into<String>) -> BusReceiver {
+        self.subscribe_with_capacity(name, DEFAULT_CONSUMER_CAPACITY)
+    }
+
+    /// Subscribe with a specific queue capacity
+    pub fn subscribe_with_capacity(&self, name: impl Into<String>, capacity: usize) -> BusReceiver {
+        self.subscribe_with_policy(name, DeliveryPolicy::Bounded { capacity })
+    }
+
+    /// Subscribe with a specific delivery policy
+    pub fn subscribe_with_policy(
+        &self,
+        name: impl Into<String>,
+        policy: DeliveryPolicy,
+    ) -> BusReceiver {
+        let receiver = self.fanout.subscribe(name.into(), policy);
+        ensure_running(&self.task, || {
+            run(
+                self.client.clone(),
+                self.fanout.clone(),
+                self.health.clone(),
+                Head::Finalized,
+                finalized_block,
+            )
+        });
+        receiver
+    }
+
+    /// Subscribe to the finalized blocks after `token`
+    ///
+    /// Blocks finalized since the token's block, at most
+    /// [`MAX_RESUME_BLOCKS`], are fetched and delivered in order before the
+    /// live stream, so a consumer that persists [`FinalizedBlock::resume_token`]
+    /// after each block sees every later block at least once. A block may be
+    /// delivered again if the consumer stopped before saving its token.
+    ///
+    /// The subscription uses [`DeliveryPolicy::Replay`].
+    pub fn subscribe_from(&self, name: impl Into<String>, token: ResumeToken) -> BusReceiver {
+        let live = self.subscribe_with_policy(name, DeliveryPolicy::Replay);
+        let (consumer, mut resumed) = Consumer::new(live.name.clone(), DeliveryPolicy::Replay);
+        // Received counts stay with the consumer registered on the bus
+        resumed.counters = live.counters.clone();
+        tokio::spawn(resume(self.client.clone(), token, live, consumer));
+        resumed
+    }
+
+    /// Subscribe to best blocks with the default queue capacity
+    ///
+    /// Best blocks arrive before they are finalized, and may be orphaned by
+    /// a reorg; compare [`BestBlock::parent_hash`] with the previous block.
+    pub fn subscribe_best(&self, name: impl Into<String>) -> BusReceiver<BestBlock> {
+        self.subscribe_best_with_policy(name, DeliveryPolicy::default())
+    }
+
+    /// Subscribe to best blocks with a specific delivery policy
+    pub fn subscribe_best_with_policy(
+        &self,
+        name: impl Into<String>,
+        policy: DeliveryPolicy,
+    ) -> BusReceiver<BestBlock> {
+        let receiver = self.best_fanout.subscribe(name.into(), policy);
+        ensure_running(&self.best_task, || {
+            run(
+                self.client.clone(),
+                self.best_fanout.clone(),
+                self.best_health.clone(),
+                Head::Best,
+                |block, events| BestBlock {
+                    number: block.number().into(),
+                    hash: block.hash(),
+                    parent_hash: block.header().parent_hash,
+                    block,
+                    events,
+                },
+            )
+        });
+        receiver
+    }
+
+    /// Metadata of the chain the bus follows
+    pub fn metadata(&self) -> subxt::Metadata {
+        self.client.metadata()
+    }
+
+    /// Queue statistics for all live consumers
+    pub fn consumer_stats(&self) -> Vec<ConsumerStats> {
+        self.fanout.stats()
+    }
+
+    /// Queue statistics for all live best-block consumers
+    pub fn best_consumer_stats(&self) -> Vec<ConsumerStats> {
+        self.best_fanout.stats()
+    }
+
+    /// Number of finalized blocks published so far
+    pub fn published(&self) -> u64 {
+        self.fanout.published.load(Ordering::Relaxed)
+    }
+
+    /// State of the finalized-block subscription
+    pub fn health(&self) -> SubscriptionHealth {
+        self.health.get()
+    }
+
+    /// Token for the last finalized block published, if any
+    pub fn resume_token(&self) -> Option<ResumeToken> {
+        let health = self.health.get();
+        Some(ResumeToken {
+            number: health.last_block?,
+            hash: health.last_hash?,
+        })
+    }
+
+    /// State of the best-block subscription
+    pub fn best_health(&self) -> SubscriptionHealth {
+        self.best_health.get()
+    }
+
+    pub(crate) fn health_handles(&self) -> (HealthHandle, HealthHandle) {
+        (self.health.clone(), self.best_health.clone())
+    }
+}
+
+/// Start a subscription task unless one is already running
+fn ensure_running<F>(task: &Mutex<Option<JoinHandle<()>>>, start: impl FnOnce() -> F)
+where
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
+    let mut task = task.lock().unwrap_or_else(|p| p.into_inner());
+    if task.as_ref().is_some_and(|t| !t.is_finished()) {
+        return;
+    }
+    *task = Some(tokio::spawn(start()));
+}
+
+impl Drop for EventBus {
+    fn drop(&mut self) {
+        for task in [&self.task, &self.best_task] {
+            if let Some(task) = task.lock().unwrap_or_else(|p| p.into_inner()).take() {
+                task.abort();
+            }
+        }
+    }
+}
+
+type SubxtBlock = Block<PolkadotConfig, OnlineClient<PolkadotConfig>>;
+
+type BlockItem<T> = fn(SubxtBlock, Events<PolkadotConfig>) -> T;
+
+fn finalized_block(block: SubxtBlock, events: Events<PolkadotConfig>) -> FinalizedBlock {
+    FinalizedBlock {
+        number: block.number().into(),
+        hash: block.hash(),
+        block,
+        events,
+    }
+}
+
+/// Subscription loop; exits once every consumer is gone
+///
+/// Failed subscriptions are retried with exponential backoff. After a
+/// resubscription the finalized head skips the blocks already published and
+/// fetches the ones finalized while it was down before resuming the live
+/// stream. A finalized block that cannot be fetched is retried on the next
+/// subscription rather than skipped, so each is published at least once.
+async fn run<T>(
+    client: OnlineClient<PolkadotConfig>,
+    fanout: Arc<Fanout<T>>,
+    health: HealthHandle,
+    head: Head,
+    item: BlockItem<T>,
+) {
+    let mut backoff = Backoff::default();
+    let mut last_number: Option<u64> = None;
+
+    while fanout.has_consumers() {
+        let subscription = match head {
+            Head::Finalized => client.blocks().subscribe_finalized().await,
+            Head::Best => client.blocks().subscribe_best().await,
+        };
+        let mut subscription = match subscription {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                let delay = backoff.next_delay();
+                health.update(|h| h.consecutive_failures += 1);
+                error!(
+                    error = %e,
+                    head = ?head,
+                    retry_in = ?delay,
+                    "Event bus failed to subscribe to blocks"
+                );
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+        };
+        info!(head = ?head, "Event bus subscribed to blocks");
+        health.update(|h| h.subscribed = true);
+
+        while let Some(block) = subscription.next().await {
+            let block = match block {
+                Ok(block) => block,
+                Err(e) => {
+                    error!(error = %e, head = ?head, "Event bus subscription error, resubscribing");
+                    break;
+                }
+            };
+            let number: u64 = block.number().into();
+
+            if head == Head::Finalized {
+                if let Some(last) = last_number.as_mut() {
+                    if number <= *last {
+                        // Already published before resubscribing
+                        continue;
+                    }
+                    if number > *last + 1 {
+                        let result = backfill(&client, &fanout, &block, last, item).await;
+                        health.update(|h| {
+                            h.backfilled += result.as_ref().map_or(0, |published| *published)
+                        });
+                        if let Err(e) = result {
+                            error!(
+                                error = %e,
+                                "Event bus failed to fetch missed finalized blocks, resubscribing"
+                            );
+                            break;
+                        }
+                    }
+                }
+            }
+
+            let events = match block.events().await {
+                Ok(events) => events,
+                Err(e) => {
+                    error!(
+                        block_number = number,
+                        error = %e,
+                        "Event bus failed to fetch block events"
+                    );
+                    if head == Head::Finalized {
+                        // Fetched again as part of the gap after resubscribing
+                        break;
+                    }
+                    continue;
+                }
+            };
+
+            let block_hash = block.hash();
+            fanout.publish(Arc::new(item(block, events)));
+            last_number = Some(number);
+            backoff.reset();
+            health.update(|h| {
+                h.last_block = Some(number);
+                h.last_block_at = Some(Instant::now());
+                h.last_hash = Some(block_hash);
+                h.consecutive_failures = 0;
+            });
+
+            if !fanout.has_consumers() {
+                break;
+            }
+        }
+
+        health.update(|h| h.subscribed = false);
+        if fanout.has_consumers() {
+            let delay = backoff.next_delay();
+            health.update(|h| {
+                h.resubscriptions += 1;
+                h.consecutive_failures += 1;
+            });
+            warn!(head = ?head, retry_in = ?delay, "Event bus subscription ended, resubscribing");
+            tokio::time::sleep(delay).await;
+        }
+    }
+    debug!("Event bus stopped, no consumers left");
+}
+
+/// Publish the finalized blocks between `last` and `head` in order,
+/// advancing `last`; returns how many were published
+///
+/// At most [`MAX_BACKFILL_BLOCKS`] blocks are fetched; older ones are
+/// skipped. On error `last` is the last block published, so the rest of the
+/// gap can be fetched later.
+async fn backfill<T>(
+    client: &OnlineClient<PolkadotConfig>,
+    fanout: &Fanout<T>,
+    head: &SubxtBlock,
+    last: &mut u64,
+    item: BlockItem<T>,
+) -> std::result::Result<u64, subxt::Error> {
+    let gap = missed_blocks(client, *last, head, MAX_BACKFILL_BLOCKS).await?;
+    if gap.skipped > 0 {
+        warn!(
+            skipped = gap.skipped,
+            "Event bus missed too many finalized blocks, skipping the oldest"
+        );
+    }
+
+    let mut published = 0;
+    for hash in gap.hashes {
+        let block = client.blocks().at(hash).await?;
+        let events = block.events().await?;
+        let number = block.number().into();
+        fanout.publish(Arc::new(item(block, events)));
+        published += 1;
+        *last = number;
+    }
+    if published > 0 {
+        info!(
+            blocks = published,
+            "Event bus published missed finalized blocks"
+        );
+    }
+    Ok(published)
+}
+
+/// Finalized blocks between a known block and a later one
+struct Gap {
+    /// Hashes of the missed blocks, oldest first
+    hashes: Vec<H256>,
+    /// Parent of the oldest missed block
+    parent: H256,
+    /// Blocks beyond the limit, not fetched
+    skipped: u64,
+}
+
+/// Find the blocks after `last` and before `head` by walking parent hashes
+/// back from `head`, at most `limit` of them
+async fn missed_blocks(
+    client: &OnlineClient<PolkadotConfig>,
+    last: u64,
+    head: &SubxtBlock,
+    limit: u64,
+) -> std::result::Result<Gap, subxt::Error> {
+    let number: u64 = head.number().into();
+    let from = (last + 1).max(number.saturating_sub(limit));
+
+    let mut hashes = Vec::new();
+    let mut parent = head.header().parent_hash;
+    for _ in from..number {
+        let header = client
+            .backend()
+            .block_header(parent)
+            .await?
+            .ok_or_else(|| subxt::Error::Other(format!("Block {parent:?} not found")))?;
+        hashes.push(parent);
+        parent = header.parent_hash;
+    }
+    hashes.reverse();
+
+    Ok(Gap {
+        hashes,
+        parent,
+        skipped: from - (last + 1),
+    })
+}
+
+/// Deliver finalized blocks after `token` to one consumer, then forward its
+/// live blocks
+///
+/// Each fetch is retried with backoff until it succeeds or the consumer is
+/// gone, so no block after the token is skipped.
+async fn resume(
+    client: OnlineClient<PolkadotConfig>,
+    token: ResumeToken,
+    live: BusReceiver,
+    consumer: Consumer<FinalizedBlock>,
+) {
+    // The first live block marks the end of the gap. The live queue is read
+    // directly, leaving received counts to the consumer's receiver
+    let first = loop {
+        match live.queue.next().await {
+            Some(Entry::Item(block)) => break block,
+            Some(Entry::Gap(skipped)) => consumer.queue.push_gap(skipped),
+            None => return,
+        }
+    };
+
+    if first.number > token.number + 1 {
+        let mut backoff = Backoff::default();
+        let gap = loop {
+            match missed_blocks(&client, token.number, &first.block, MAX_RESUME_BLOCKS).await {
+                Ok(gap) => break gap,
+                Err(e) => {
+                    let delay = backoff.next_delay();
+                    error!(error = %e, retry_in = ?delay, "Failed to find blocks to resume from");
+                    if consumer.queue.receiver_gone() {
+                        return;
+                    }
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        };
+        if gap.skipped > 0 {
+            warn!(
+                skipped = gap.skipped,
+                "Resume token too old, skipping the oldest missed blocks"
+            );
+            consumer.queue.push_gap(gap.skipped);
+        } else if gap.parent != token.hash {
+            warn!(
+                block_number = token.number,
+                "Resume token block is not on the finalized chain"
+            );
+        }
+        info!(
+            from = token.number + 1,
+            blocks = gap.hashes.len(),
+            "Resuming subscription from token"
+        );
+
+        for hash in gap.hashes {
+            let block = loop {
+                let fetched = match client.blocks().at(hash).await {
+                    Ok(block) => block
+                        .events()
+                        .await
+                        .map(|events| finalized_block(block, events)),
+                    Err(e) => Err(e),
+                };
+                match fetched {
+                    Ok(block) => break block,
+                    Err(e) => {
+                        let delay = backoff.next_delay();
+                        error!(error = %e, retry_in = ?delay, "Failed to fetch block to resume from");
+                        if consumer.queue.receiver_gone() {
+                            return;
+                        }
+                        tokio::time::sleep(delay).await;
+                    }
+                }
+            };
+            backoff.reset();
+            if !consumer.deliver(Arc::new(block)) {
+                return;
+            }
+        }
+    }
+
+    let mut next = Some(Entry::Item(first));
+    while let Some(entry) = next {
+        match entry {
+            Entry::Item(block) if block.number > token.number => {
+                if !consumer.deliver(block) {
+                    return;
+                }
+            }
+            Entry::Item(_) => {}
+            Entry::Gap(skipped) => consumer.queue.push_gap(skipped),
+        }
+        next = live.queue.next().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounded(capacity: usize) -> DeliveryPolicy {
+        DeliveryPolicy::Bounded { capacity }
+    }
+
+    #[tokio::test]
+    async fn test_fanout_delivers_to_all_consumers() {
+        let fanout = Fanout::new();
+        let mut a = fanout.subscribe("a".into(), bounded(4));
+        let mut b = fanout.subscribe("b".into(), bounded(4));
+
+        fanout.publish(Arc::new(1u64));
+        fanout.publish(Arc::new(2u64));
+
+        assert_eq!(*a.recv().await.unwrap(), 1);
+        assert_eq!(*a.recv().await.unwrap(), 2);
+        assert_eq!(*b.recv().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_slow_consumer_drops_without_blocking_others() {
+        let fanout = Fanout::new();
+        let mut fast = fanout.subscribe("fast".into(), bounded(8));
+        let slow = fanout.subscribe("slow".into(), bounded(2));
+
+        for i in 0..5u64 {
+            fanout.publish(Arc::new(i));
+            assert_eq!(*fast.recv().await.unwrap(), i);
+        }
+
+        assert_eq!(slow.dropped(), 3);
+        let stats = fanout.stats();
+        let slow_stats = stats.iter().find(|s| s.name == "slow").unwrap();
+        assert_eq!(slow_stats.queued, 2);
+        assert_eq!(slow_stats.delivered, 2);
+        assert_eq!(slow_stats.lag, 5);
+
+        let fast_stats = stats.iter().find(|s| s.name == "fast").unwrap();
+        assert_eq!(fast_stats.lag, 0);
+        assert_eq!(fast_stats.dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn test_closed_consumers_are_removed() {
+        let fanout = Fanout::new();
+        let receiver = fanout.subscribe("gone".into(), bounded(1));
+        assert!(fanout.has_consumers());
+
+        drop(receiver);
+        assert!(!fanout.has_consumers());
+
+        fanout.publish(Arc::new(0u8));
+        assert!(fanout.stats().is_empty());
+        assert!(fanout.lock().is_empty());
+    }
+
+    #[test]
+    fn test_backoff_delays_grow_with_jitter() {
+        assert_eq!(Backoff::delay(0, 0.0), Duration::from_millis(250));
+        assert_eq!(Backoff::delay(0, 1.0), Duration::from_millis(500));
+        assert_eq!(Backoff::delay(3, 0.0), Duration::from_secs(2));
+        assert_eq!(Backoff::delay(3, 1.0), Duration::from_secs(4));
+        assert_eq!(Backoff::delay(30, 1.0), RESUBSCRIBE_MAX_DELAY);
+        assert_eq!(Backoff::delay(30, 0.0), RESUBSCRIBE_MAX_DELAY / 2);
+
+        let mut backoff = Backoff::default();
+        let first = backoff.next_delay();
+        let second = backoff.next_delay();
+        assert!(first <= RESUBSCRIBE_BASE_DELAY);
+        assert!(second >= RESUBSCRIBE_BASE_DELAY);
+        backoff.reset();
+        assert!(backoff.next_delay() <= RESUBSCRIBE_BASE_DELAY);
+    }
+
+    #[test]
+    fn test_resume_token_serialization() {
+        let token = ResumeToken {
+            number: 42,
+            hash: H256([0xab; 32]),
+        };
+        let json = serde_json::to_value(token).unwrap();
+        assert_eq!(json["number"], 42);
+        assert_eq!(json["hash"], format!("0x{}", "ab".repeat(32)));
+        assert_eq!(serde_json::from_value::<ResumeToken>(json).unwrap(), token);
+    }
+
+    #[tokio::test]
+    async fn test_bounded_consumer_is_notified_of_drops() {
+        let fanout = Fanout::new();
+        let mut receiver = fanout.subscribe("alerts".into(), bounded(2));
+        for i in 0..5u64 {
+            fanout.publish(Arc::new(i));
+        }
+        fanout.publish(Arc::new(5u64));
+
+        assert!(matches!(receiver.recv_delivery().await, Some(Delivery::Item(i)) if *i == 0));
+        assert!(matches!(receiver.recv_delivery().await, Some(Delivery::Item(i)) if *i == 1));
+        assert!(matches!(
+            receiver.recv_delivery().await,
+            Some(Delivery::Skipped(4))
+        ));
+
+        fanout.publish(Arc::new(6u64));
+        assert_eq!(*receiver.recv().await.unwrap(), 6);
+        assert_eq!(receiver.dropped(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_replay_consumer_never_drops() {
+        let fanout = Fanout::new();
+        let mut receiver = fanout.subscribe("ledger".into(), DeliveryPolicy::Replay);
+        for i in 0..200u64 {
+            fanout.publish(Arc::new(i));
+        }
+
+        let stats = fanout.stats();
+        assert_eq!(stats[0].queued, 200);
+        assert_eq!(stats[0].max_queued, 200);
+        assert_eq!(stats[0].lag, 200);
+        for i in 0..200u64 {
+            assert_eq!(*receiver.recv().await.unwrap(), i);
+        }
+        assert_eq!(receiver.dropped(), 0);
+        assert_eq!(fanout.stats()[0].lag, 0);
+    }
+
+    #[tokio::test]
+    async fn test_lag_counts_from_subscription() {
+        let fanout = Fanout::new();
+        let _early = fanout.subscribe("early".into(), DeliveryPolicy::Replay);
+        for i in 0..10u64 {
+            fanout.publish(Arc::new(i));
+        }
+
+        let mut late = fanout.subscribe("late".into(), DeliveryPolicy::Replay);
+        let lag = |name: &str| {
+            fanout
+                .stats()
+                .into_iter()
+                .find(|s| s.name == name)
+                .unwrap()
+                .lag
+        };
+        assert_eq!(lag("late"), 0);
+        assert_eq!(lag("early"), 10);
+
+        fanout.publish(Arc::new(10));
+        fanout.publish(Arc::new(11));
+        assert_eq!(lag("late"), 2);
+        assert_eq!(*late.recv().await.unwrap(), 10);
+        assert_eq!(lag("late"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_latest_consumer_conflates() {
+        let fanout = Fanout::new();
+        let mut receiver = fanout.subscribe("ui".into(), DeliveryPolicy::Latest);
+        for i in 0..3u64 {
+            fanout.publish(Arc::new(i));
+        }
+
+        assert!(matches!(
+            receiver.recv_delivery().await,
+            Some(Delivery::Skipped(2))
+        ));
+        assert_eq!(*receiver.recv().await.unwrap(), 2);
+        let stats = fanout.stats();
+        assert_eq!(stats[0].queued, 0);
+        assert_eq!(stats[0].max_queued, 1);
+        assert_eq!(stats[0].delivered, 3);
+        assert_eq!(stats[0].dropped, 2);
+    }
+
+    #[tokio::test]
+    async fn test_receiver_drains_after_bus_is_gone() {
+        let fanout = Fanout::new();
+        let mut receiver = fanout.subscribe("late".into(), bounded(4));
+        fanout.publish(Arc::new(1u8));
+        drop(fanout);
+
+        assert_eq!(*receiver.recv().await.unwrap(), 1);
+        assert!(receiver.recv().await.is_none());
+    }
+}
@@ -0,0 +1,383 @@
+//! Index-based event filtering for block processing
+//!
+//! Decoding every event of a block into [`scale_value`] composites (via
+//! `field_values()`) dominates block processing cost, even though most
+//! consumers only care about a handful of event kinds. [`EventFilter`]
+//! resolves `(pallet, event)` names to their metadata indices once, so each
+//! event is matched by comparing the two index bytes that follow the event
+//! phase, before any field is decoded or any name is compared.
+//!
+//! [`EventScanner`] applies a filter to whole blocks and keeps its result
+//! buffer between blocks, so steady-state scanning does not allocate.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::{EventFilter, EventScanner, SubstrateAdapter};
+//!
+//! # async fn example() -> apex_sdk_substrate::Result<()> {
+//! let adapter = SubstrateAdapter::connect("wss://westend-rpc.polkadot.io").await?;
+//! let filter = EventFilter::new(&adapter.client().metadata(), &[("Balances", "Transfer")]);
+//! let mut scanner = EventScanner::new(filter);
+//!
+//! let mut blocks = adapter.event_bus().subscribe("transfers");
+//! while let Some(block) = blocks.recv().await {
+//!     for event in scanner.scan(&block.events)? {
+//!         // Only matching events pay for a full decode
+//!         let fields = event.field_values()?;
+//!         println!("#{}: {}", block.number, fields);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`scale_value`]: subxt::ext::scale_value
+
+use crate::{PolkadotConfig, Result};
+use subxt::events::{EventDetails, Events};
+use subxt::Metadata;
+
+/// Set of events selected by pallet and variant index
+#[derive(Clone)]
+pub struct EventFilter {
+    /// One bit per `(pallet_index, variant_index)` pair
+    keys: Box<[u64; 1024]>,
+    len: usize,
+}
+
+impl EventFilter {
+    /// Resolve `(pallet, event)` names against metadata
+    ///
+    /// Events the runtime does not have are ignored, so one filter can serve
+    /// chains with different pallet sets.
+    pub fn new(metadata: &Metadata, events: &[(&str, &str)]) -> Self {
+        let mut filter = Self {
+            keys: Box::new([0; 1024]),
+            len: 0,
+        };
+
+        for (pallet_name, event_name) in events {
+            let Some(pallet) = metadata.pallet_by_name(pallet_name) else {
+                continue;
+            };
+            let Some(variant) = pallet
+                .event_variants()
+                .and_then(|variants| variants.iter().find(|v| v.name == *event_name))
+            else {
+                continue;
+            };
+            filter.insert(pallet.index(), variant.index);
+        }
+        filter
+    }
+
+    fn insert(&mut self, pallet_index: u8, variant_index: u8) {
+        let (word, bit) = Self::position(pallet_index, variant_index);
+        if self.keys[word] & bit == 0 {
+            self.keys[word] |= bit;
+            self.len += 1;
+        }
+    }
+
+    /// Whether the filter selects the event with these indices
+    #[inline]
+    pub fn matches(&self, pallet_index: u8, variant_index: u8) -> bool {
+        let (word, bit) = Self::position(pallet_index, variant_index);
+        self.keys[word] & bit != 0
+    }
+
+    /// Number of events selected
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether none of the requested events exist in the metadata
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    fn position(pallet_index: u8, variant_index: u8) -> (usize, u64) {
+        let key = (pallet_index as usize) << 8 | variant_index as usize;
+        (key >> 6, 1 << (key & 63))
+    }
+}
+
+impl std::fmt::Debug for EventFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventFilter")
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// Applies an [`EventFilter`] to blocks, reusing its buffer across blocks
+pub struct EventScanner {
+    filter: EventFilter,
+    matched: Vec<EventDetails<PolkadotConfig>>,
+}
+
+impl EventScanner {
+    /// Create a scanner for the events selected by `filter`
+    pub fn new(filter: EventFilter) -> Self {
+        Self {
+            filter,
+            matched: Vec::new(),
+        }
+    }
+
+    /// The filter applied to each scanned block
+    pub fn filter(&self) -> &EventFilter {
+        &self.filter
+    }
+
+    /// Collect the events of a block that match the filter
+    ///
+    /// The returned slice is valid until the next scan. Event fields are not
+    /// decoded; call `field_values()` or `as_event()` on the matches.
+    pub fn scan(
+        &mut self,
+        events: &Events<PolkadotConfig>,
+    ) -> Result<&[EventDetails<PolkadotConfig>]> {
+        self.matched.clear();
+        if self.filter.is_empty() {
+            return Ok(&self.matched);
+        }
+
+        for event in events.iter() {
+            let event = event?;
+            if self
+                .filter
+                .matches(event.pallet_index(), event.variant_index())
+            {
+                self.matched.push(event);
+            }
+        }
+        Ok(&self.matched)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    //! Synthetic runtime metadata and event records for tests
+
+    use parity_scale_codec::{Compact, Encode};
+    use scale_info::{meta_type, TypeInfo};
+    use subxt::events::{Events, Phase};
+    use subxt::ext::frame_metadata::{
+        v15::{
+            CustomMetadata, ExtrinsicMetadata, OuterEnums, PalletEventMetadata, PalletMetadata,
+            RuntimeMetadataV15,
+        },
+        RuntimeMetadataPrefixed,
+    };
+    use subxt::{Metadata, PolkadotConfig};
+
+    #[derive(Encode, TypeInfo)]
+    pub enum SystemEvent {
+        ExtrinsicSuccess { weight: u64 },
+        ExtrinsicFailed { error: u8, weight: u64 },
+    }
+
+    #[derive(Encode, TypeInfo)]
+    pub enum BalancesEvent {
+        Deposit {
+            who: [u8; 32],
+            amount: u128,
+        },
+        Transfer {
+            from: [u8; 32],
+            to: [u8; 32],
+            amount: u128,
+        },
+    }
+
+    #[derive(Encode, TypeInfo)]
+    pub enum RuntimeEvent {
+        #[codec(index = 0)]
+        System(SystemEvent),
+        #[codec(index = 10)]
+        Balances(BalancesEvent),
+    }
+
+    #[derive(Encode)]
+    struct EventRecord {
+        phase: Phase,
+        event: RuntimeEvent,
+        topics: Vec<[u8; 32]>,
+    }
+
+    pub fn metadata() -> Metadata {
+        #[allow(unused)]
+        #[derive(TypeInfo)]
+        struct Extrinsic<Call> {
+            call: Call,
+        }
+        #[allow(unused)]
+        #[derive(TypeInfo)]
+        enum RuntimeCall {
+            System(Call),
+        }
+        #[allow(unused)]
+        #[derive(TypeInfo)]
+        enum Call {
+            Remark,
+        }
+
+        let pallet = |name, index, ty| PalletMetadata {
+            name,
+            storage: None,
+            calls: None,
+            event: Some(PalletEventMetadata { ty }),
+            constants: vec![],
+            error: None,
+            index,
+            docs: vec![],
+        };
+        let metadata = RuntimeMetadataV15::new(
+            vec![
+                pallet("System", 0, meta_type::<SystemEvent>()),
+                pallet("Balances", 10, meta_type::<BalancesEvent>()),
+            ],
+            ExtrinsicMetadata {
+                version: 4,
+                signed_extensions: vec![],
+                address_ty: meta_type::<()>(),
+                call_ty: meta_type::<RuntimeCall>(),
+                signature_ty: meta_type::<()>(),
+                extra_ty: meta_type::<()>(),
+            },
+            meta_type::<()>(),
+            vec![],
+            OuterEnums {
+                call_enum_ty: meta_type::<RuntimeCall>(),
+                event_enum_ty: meta_type::<RuntimeEvent>(),
+                error_enum_ty: meta_type::<()>(),
+            },
+            CustomMetadata {
+                map: Default::default(),
+            },
+        );
+        Metadata::try_from(RuntimeMetadataPrefixed::from(metadata)).expect("valid metadata")
+    }
+
+    /// Encode records the way `System.Events` storage holds them
+    pub fn events(
+        metadata: &Metadata,
+        records: Vec<(Phase, RuntimeEvent)>,
+    ) -> Events<PolkadotConfig> {
+        let mut bytes = Compact(records.len() as u32).encode();
+        for (phase, event) in records {
+            EventRecord {
+                phase,
+                event,
+                topics: vec![],
+            }
+            .encode_to(&mut bytes);
+        }
+        Events::decode_from(bytes, metadata.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_utils::*;
+    use super::*;
+    use subxt::events::Phase;
+
+    fn transfer(amount: u128) -> RuntimeEvent {
+        RuntimeEvent::Balances(BalancesEvent::Transfer {
+            from: [1; 32],
+            to: [2; 32],
+            amount,
+        })
+    }
+
+    #[test]
+    fn test_filter_resolves_indices() {
+        let metadata = metadata();
+        let filter = EventFilter::new(
+            &metadata,
+            &[("Balances", "Transfer"), ("System", "ExtrinsicFailed")],
+        );
+
+        assert_eq!(filter.len(), 2);
+        assert!(filter.matches(10, 1));
+        assert!(filter.matches(0, 1));
+        assert!(!filter.matches(10, 0));
+        assert!(!filter.matches(0, 0));
+    }
+
+    #[test]
+    fn test_filter_ignores_unknown_events() {
+        let metadata = metadata();
+        let filter = EventFilter::new(
+            &metadata,
+            &[("XcmpQueue", "Success"), ("Balances", "Burned")],
+        );
+        assert!(filter.is_empty());
+    }
+
+    #[test]
+    fn test_scanner_returns_matching_events_in_order() {
+        let metadata = metadata();
+        let events = events(
+            &metadata,
+            vec![
+                (
+                    Phase::ApplyExtrinsic(0),
+                    RuntimeEvent::Balances(BalancesEvent::Deposit {
+                        who: [3; 32],
+                        amount: 5,
+                    }),
+                ),
+                (Phase::ApplyExtrinsic(0), transfer(100)),
+                (
+                    Phase::ApplyExtrinsic(0),
+                    RuntimeEvent::System(SystemEvent::ExtrinsicSuccess { weight: 1 }),
+                ),
+                (Phase::ApplyExtrinsic(1), transfer(200)),
+                (
+                    Phase::ApplyExtrinsic(1),
+                    RuntimeEvent::System(SystemEvent::ExtrinsicFailed {
+                        error: 3,
+                        weight: 1,
+                    }),
+                ),
+                (Phase::Finalization, transfer(300)),
+            ],
+        );
+
+        let mut scanner =
+            EventScanner::new(EventFilter::new(&metadata, &[("Balances", "Transfer")]));
+        let matched = scanner.scan(&events).unwrap();
+
+        assert_eq!(matched.len(), 3);
+        assert_eq!(matched[1].phase(), Phase::ApplyExtrinsic(1));
+        assert_eq!(matched[2].phase(), Phase::Finalization);
+        let amount = matched[1].field_values().unwrap();
+        assert_eq!(
+            subxt::dynamic::At::at(&amount, "amount").and_then(|v| v.as_u128()),
+            Some(200)
+        );
+    }
+
+    #[test]
+    fn test_scanner_reuses_buffer() {
+        let metadata = metadata();
+        let mut scanner =
+            EventScanner::new(EventFilter::new(&metadata, &[("Balances", "Transfer")]));
+
+        let block = events(
+            &metadata,
+            (0..32)
+                .map(|i| (Phase::ApplyExtrinsic(i), transfer(i as u128)))
+                .collect(),
+        );
+        assert_eq!(scanner.scan(&block).unwrap().len(), 32);
+        let capacity = scanner.matched.capacity();
+
+        let block = events(&metadata, vec![(Phase::Initialization, transfer(1))]);
+        assert_eq!(scanner.scan(&block).unwrap().len(), 1);
+        assert_eq!(scanner.matched.capacity(), capacity);
+    }
+}
@@ -0,0 +1,164 @@
+//! Fault-injecting RPC client
+//!
+//! Wraps a subxt RPC client so that calls are delayed, failed or dropped
+//! according to a [`FaultInjector`]. Use
+//! [`SubstrateAdapter::connect_with_fault_injector`] to get an adapter whose
+//! every RPC call goes through the injector.
+//!
+//! Subscriptions are only faulted when they are opened; items of an open
+//! subscription are passed through unchanged.
+//!
+//! [`SubstrateAdapter::connect_with_fault_injector`]: crate::SubstrateAdapter::connect_with_fault_injector
+
+use apex_sdk_core::fault::{Fault, FaultInjector, FaultKind};
+use std::sync::Arc;
+use subxt::backend::rpc::{RawRpcFuture, RawRpcSubscription, RawValue, RpcClient, RpcClientT};
+use subxt::ext::subxt_rpcs;
+
+/// RPC client that injects faults into calls to one endpoint
+#[derive(Clone)]
+pub struct FaultInjectingRpcClient {
+    inner: RpcClient,
+    endpoint: String,
+    injector: Arc<FaultInjector>,
+}
+
+impl FaultInjectingRpcClient {
+    /// Wrap an RPC client connected to `endpoint`
+    pub fn new(
+        inner: RpcClient,
+        endpoint: impl Into<String>,
+        injector: Arc<FaultInjector>,
+    ) -> Self {
+        Self {
+            inner,
+            endpoint: endpoint.into(),
+            injector,
+        }
+    }
+
+    fn injected(&self, method: &str, kind: FaultKind) -> subxt_rpcs::Error {
+        subxt_rpcs::Error::Client(Box::new(self.injector.error(&self.endpoint, method, kind)))
+    }
+}
+
+impl RpcClientT for FaultInjectingRpcClient {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RawRpcFuture<'a, Box<RawValue>> {
+        Box::pin(async move {
+            let fault = self.injector.decide(&self.endpoint, method);
+            match fault {
+                Some(Fault::Error) => return Err(self.injected(method, FaultKind::Error)),
+                Some(Fault::Delay(delay)) => tokio::time::sleep(delay).await,
+                _ => {}
+            }
+
+            let response = self.inner.request_raw(method, params).await?;
+
+            if fault == Some(Fault::Drop) {
+                return Err(self.injected(method, FaultKind::Drop));
+            }
+            Ok(response)
+        })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        params: Option<Box<RawValue>>,
+        unsub: &'a str,
+    ) -> RawRpcFuture<'a, RawRpcSubscription> {
+        Box::pin(async move {
+            match self.injector.decide(&self.endpoint, sub) {
+                Some(Fault::Error) => Err(self.injected(sub, FaultKind::Error)),
+                Some(Fault::Drop) => Err(self.injected(sub, FaultKind::Drop)),
+                Some(Fault::Delay(delay)) => {
+                    tokio::time::sleep(delay).await;
+                    self.inner.subscribe_raw(sub, params, unsub).await
+                }
+                None => self.inner.subscribe_raw(sub, params, unsub).await,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apex_sdk_core::fault::{FaultConfig, FaultRule};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const ENDPOINT: &str = "ws://127.0.0.1:9944";
+
+    /// Answers every request with `null` and counts them
+    #[derive(Default)]
+    struct CountingRpc {
+        calls: AtomicUsize,
+    }
+
+    impl RpcClientT for CountingRpc {
+        fn request_raw<'a>(
+            &'a self,
+            _method: &'a str,
+            _params: Option<Box<RawValue>>,
+        ) -> RawRpcFuture<'a, Box<RawValue>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Ok(RawValue::from_string("null".to_string()).unwrap()) })
+        }
+
+        fn subscribe_raw<'a>(
+            &'a self,
+            _sub: &'a str,
+            _params: Option<Box<RawValue>>,
+            _unsub: &'a str,
+        ) -> RawRpcFuture<'a, RawRpcSubscription> {
+            Box::pin(async { Err(subxt_rpcs::Error::Client("unsupported".into())) })
+        }
+    }
+
+    fn client(rule: FaultRule) -> (FaultInjectingRpcClient, Arc<CountingRpc>) {
+        let backend = Arc::new(CountingRpc::default());
+        let injector =
+            FaultInjector::new(FaultConfig::new().with_endpoint(ENDPOINT, rule)).unwrap();
+        let client = FaultInjectingRpcClient::new(
+            RpcClient::new(backend.clone()),
+            ENDPOINT,
+            Arc::new(injector),
+        );
+        (client, backend)
+    }
+
+    #[tokio::test]
+    async fn test_error_is_not_forwarded() {
+        let (client, backend) = client(FaultRule::new().with_error_rate(1.0));
+        let result = client.request_raw("chain_getHeader", None).await;
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Injected error fault"));
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drop_is_forwarded_then_fails() {
+        let (client, backend) = client(FaultRule::new().with_drop_rate(1.0));
+        let result = client.request_raw("author_submitExtrinsic", None).await;
+
+        assert!(result.is_err());
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(client.injector.injected_count(ENDPOINT, FaultKind::Drop), 1);
+    }
+
+    #[tokio::test]
+    async fn test_untouched_calls_pass_through() {
+        let (client, backend) = client(FaultRule::new());
+        let result = client.request_raw("system_chain", None).await.unwrap();
+
+        assert_eq!(result.get(), "null");
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -2,15 +2,21 @@
 //!
 //! This module provides comprehensive fee estimation capabilities including:
 //! - Weight-based dynamic fee calculation using runtime metadata
-//! - Network congestion monitoring and analysis
-//! - Configurable fee strategies (Fast, Normal, Slow)
+//! - Network congestion monitoring and analysis, on demand or from a block
+//!   subscription
+//! - Configurable fee strategies (Fast, Normal, Slow, or a target confirmation time)
+//! - Inclusion latency per tip bucket, learned from monitored transactions
 //! - Fee estimation accuracy metrics and tracking
-//! - Integration with TransactionPayment runtime API
+//! - Integration with TransactionPayment runtime API: inclusion fee
+//!   components from `query_fee_details` and the `NextFeeMultiplier`
 
+use crate::transaction::{FeeCapExceeded, FeeConfig};
 use crate::{Error, Result};
 use parity_scale_codec::{Decode, Encode};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use subxt::{OnlineClient, PolkadotConfig};
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
@@ -25,24 +31,38 @@ pub enum FeeStrategy {
     Normal,
     /// Slow confirmation with lower fees (1.0x multiplier)
     Slow,
+    /// Lowest tip observed to confirm within the given time
+    ///
+    /// The tip comes from [`InclusionLatencies`] recorded by the transaction
+    /// monitor. Until enough confirmations have been observed, the strategy
+    /// behaves like the fixed tier from [`fixed_tier`](Self::fixed_tier).
+    TargetTime(Duration),
 }
 
 impl FeeStrategy {
-    /// Get the fee multiplier for this strategy
+    /// Default fee multiplier for this strategy
+    ///
+    /// [`DynamicFeeEstimator`] uses the multiplier from its
+    /// [`FeeStrategyConfig`], which starts from these values.
     pub fn multiplier(&self) -> f64 {
         match self {
             FeeStrategy::Fast => 1.5,
             FeeStrategy::Normal => 1.2,
             FeeStrategy::Slow => 1.0,
+            FeeStrategy::TargetTime(_) => self.fixed_tier().multiplier(),
         }
     }
 
-    /// Get the tip amount for this strategy (in Planck)
+    /// Default tip amount for this strategy (in Planck)
+    ///
+    /// For [`TargetTime`](Self::TargetTime) this is the fixed tier's tip; the
+    /// estimator replaces it with a learned one when it can.
     pub fn tip(&self) -> u128 {
         match self {
             FeeStrategy::Fast => 1_000_000, // 0.001 DOT tip
             FeeStrategy::Normal => 100_000, // 0.0001 DOT tip
             FeeStrategy::Slow => 0,         // No tip
+            FeeStrategy::TargetTime(_) => self.fixed_tier().tip(),
         }
     }
 
@@ -52,14 +72,344 @@ impl FeeStrategy {
             FeeStrategy::Fast => "Fast: Higher fees for quicker confirmation",
             FeeStrategy::Normal => "Normal: Standard fees with typical confirmation time",
             FeeStrategy::Slow => "Slow: Lower fees with longer confirmation time",
+            FeeStrategy::TargetTime(_) => "Target time: Lowest tip observed to confirm in time",
+        }
+    }
+
+    /// Fast, Normal or Slow tier closest to this strategy
+    ///
+    /// A target of up to 2 finalized blocks' time (24s) maps to Fast, up to
+    /// 1 minute to Normal, anything longer to Slow.
+    pub fn fixed_tier(&self) -> FeeStrategy {
+        match self {
+            FeeStrategy::TargetTime(target) if *target <= Duration::from_secs(24) => {
+                FeeStrategy::Fast
+            }
+            FeeStrategy::TargetTime(target) if *target <= Duration::from_secs(60) => {
+                FeeStrategy::Normal
+            }
+            FeeStrategy::TargetTime(_) => FeeStrategy::Slow,
+            fixed => *fixed,
+        }
+    }
+}
+
+/// Multiplier, tip and fee cap of each fee strategy
+///
+/// The defaults are the constants of [`FeeStrategy::multiplier`] and
+/// [`FeeStrategy::tip`], which are sized for DOT. Chains whose token is worth
+/// much more or less per Planck should override the tiers, and can add named
+/// strategies of their own (see [`DynamicFeeEstimator::estimate_fee_named`]).
+#[derive(Debug, Clone)]
+pub struct FeeStrategyConfig {
+    fast: FeeConfig,
+    normal: FeeConfig,
+    slow: FeeConfig,
+    named: BTreeMap<String, FeeConfig>,
+}
+
+impl Default for FeeStrategyConfig {
+    fn default() -> Self {
+        let tier = |strategy: FeeStrategy| {
+            FeeConfig::new()
+                .with_multiplier(strategy.multiplier())
+                .with_tip(strategy.tip())
+        };
+        Self {
+            fast: tier(FeeStrategy::Fast),
+            normal: tier(FeeStrategy::Normal),
+            slow: tier(FeeStrategy::Slow),
+            named: BTreeMap::new(),
+        }
+    }
+}
+
+impl FeeStrategyConfig {
+    /// Create a configuration with the default tiers and no named strategies
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the profile of a Fast, Normal or Slow tier
+    ///
+    /// [`FeeStrategy::TargetTime`] sets the tier it falls back to.
+    pub fn with_strategy(mut self, strategy: FeeStrategy, config: FeeConfig) -> Self {
+        *self.tier_mut(strategy) = config;
+        self
+    }
+
+    /// Add a named strategy
+    pub fn with_named(mut self, name: impl Into<String>, config: FeeConfig) -> Self {
+        self.register(name, config);
+        self
+    }
+
+    /// Add or replace a named strategy, returning the profile it replaced
+    pub fn register(&mut self, name: impl Into<String>, config: FeeConfig) -> Option<FeeConfig> {
+        self.named.insert(name.into(), config)
+    }
+
+    /// Profile of a strategy; [`FeeStrategy::TargetTime`] uses its fixed tier's
+    pub fn get(&self, strategy: FeeStrategy) -> &FeeConfig {
+        match strategy.fixed_tier() {
+            FeeStrategy::Fast => &self.fast,
+            FeeStrategy::Slow => &self.slow,
+            _ => &self.normal,
+        }
+    }
+
+    /// Profile of a named strategy
+    pub fn named(&self, name: &str) -> Option<&FeeConfig> {
+        self.named.get(name)
+    }
+
+    /// Names of the registered strategies, in sorted order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.named.keys().map(String::as_str)
+    }
+
+    fn tier_mut(&mut self, strategy: FeeStrategy) -> &mut FeeConfig {
+        match strategy.fixed_tier() {
+            FeeStrategy::Fast => &mut self.fast,
+            FeeStrategy::Slow => &mut self.slow,
+            _ => &mut self.normal,
         }
     }
 }
 
+/// Most recent latencies kept per tip bucket
+const MAX_LATENCY_SAMPLES: usize = 100;
+
+/// Samples a bucket needs before it is used to pick a tip
+const MIN_LATENCY_SAMPLES: usize = 5;
+
+/// Upper bounds of the inclusion latency histogram, in seconds
+const LATENCY_HISTOGRAM_BOUNDS_SECS: [u64; 8] = [6, 12, 18, 24, 36, 60, 120, 300];
+
+/// Observed latencies for tips of the same order of magnitude
+#[derive(Debug, Default)]
+struct LatencyBucket {
+    samples: VecDeque<Duration>,
+    max_tip: u128,
+    /// Observations per histogram bound, not cumulative; the last slot
+    /// counts latencies above every bound
+    histogram: [u64; LATENCY_HISTOGRAM_BOUNDS_SECS.len() + 1],
+    count: u64,
+    sum: Duration,
+}
+
+impl LatencyBucket {
+    fn record(&mut self, tip: u128, latency: Duration) {
+        self.samples.push_back(latency);
+        if self.samples.len() > MAX_LATENCY_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.max_tip = self.max_tip.max(tip);
+
+        let slot = LATENCY_HISTOGRAM_BOUNDS_SECS
+            .iter()
+            .position(|bound| latency <= Duration::from_secs(*bound))
+            .unwrap_or(LATENCY_HISTOGRAM_BOUNDS_SECS.len());
+        self.histogram[slot] += 1;
+        self.count += 1;
+        self.sum += latency;
+    }
+
+    /// Latency at quantile `q` (0.0 to 1.0) of the recent samples
+    fn quantile(&self, q: f64) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort();
+        let rank = ((sorted.len() as f64 * q).ceil() as usize).clamp(1, sorted.len());
+        Some(sorted[rank - 1])
+    }
+
+    /// 90th percentile latency, once the bucket has enough samples to pick
+    /// a tip from
+    fn p90(&self) -> Option<Duration> {
+        if self.samples.len() < MIN_LATENCY_SAMPLES {
+            return None;
+        }
+        self.quantile(0.9)
+    }
+
+    /// Cumulative counts at each histogram bound
+    fn cumulative_histogram(&self) -> Vec<(Duration, u64)> {
+        LATENCY_HISTOGRAM_BOUNDS_SECS
+            .iter()
+            .zip(self.histogram.iter())
+            .scan(0, |total, (bound, count)| {
+                *total += count;
+                Some((Duration::from_secs(*bound), *total))
+            })
+            .collect()
+    }
+}
+
+/// Inclusion latency statistics of a tip bucket at a congestion level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InclusionStats {
+    /// Congestion level when the transactions were submitted
+    pub congestion: CongestionLevel,
+    /// Smallest tip in the bucket (0, or a power of ten)
+    pub min_tip: u128,
+    /// Largest tip observed in the bucket
+    pub max_tip: u128,
+    /// Transactions observed since the estimator was created
+    pub count: u64,
+    /// Total latency of those transactions
+    pub sum: Duration,
+    /// Median latency of the recent transactions
+    pub p50: Option<Duration>,
+    /// 90th percentile latency of the recent transactions
+    pub p90: Option<Duration>,
+    /// Transactions included within each bound (6s, 12s, 18s, 24s, 36s,
+    /// 1m, 2m, 5m), cumulative like Prometheus histogram buckets
+    pub histogram: Vec<(Duration, u64)>,
+}
+
+#[derive(Debug, Default)]
+struct LatencyState {
+    buckets: BTreeMap<(CongestionLevel, u32), LatencyBucket>,
+    congestion: CongestionLevel,
+}
+
+/// Time from broadcast to inclusion in a finalized block, per tip bucket and
+/// congestion level
+///
+/// Tips are bucketed by order of magnitude (0, 1–9, 10–99, ...). Clones share
+/// the same samples, so the [`TransactionMonitor`] can record latencies that
+/// a [`DynamicFeeEstimator`] uses for [`FeeStrategy::TargetTime`].
+///
+/// [`TransactionMonitor`]: crate::monitor::TransactionMonitor
+#[derive(Debug, Clone, Default)]
+pub struct InclusionLatencies {
+    state: Arc<parking_lot::Mutex<LatencyState>>,
+}
+
+impl InclusionLatencies {
+    /// Create an empty set of latencies
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record how long a transaction with `tip`, submitted at `congestion`,
+    /// took to be included
+    pub fn record(&self, tip: u128, congestion: CongestionLevel, latency: Duration) {
+        self.state
+            .lock()
+            .buckets
+            .entry((congestion, tip_bucket(tip)))
+            .or_default()
+            .record(tip, latency);
+    }
+
+    /// Current congestion level, which new observations are filed under
+    pub fn congestion(&self) -> CongestionLevel {
+        self.state.lock().congestion
+    }
+
+    /// Set the current congestion level
+    pub fn set_congestion(&self, congestion: CongestionLevel) {
+        self.state.lock().congestion = congestion;
+    }
+
+    /// 90th percentile latency of tips in the same bucket as `tip`
+    pub fn latency(&self, tip: u128, congestion: CongestionLevel) -> Option<Duration> {
+        self.state
+            .lock()
+            .buckets
+            .get(&(congestion, tip_bucket(tip)))?
+            .p90()
+    }
+
+    /// Lowest observed tip whose bucket confirms within `target` 90% of
+    /// the time at `congestion`
+    ///
+    /// Returns the largest tip seen in the cheapest such bucket, or `None`
+    /// if no bucket has enough samples and is fast enough.
+    pub fn tip_for(&self, target: Duration, congestion: CongestionLevel) -> Option<u128> {
+        self.state
+            .lock()
+            .buckets
+            .range((congestion, 0)..=(congestion, u32::MAX))
+            .map(|(_, bucket)| bucket)
+            .find(|bucket| bucket.p90().is_some_and(|p90| p90 <= target))
+            .map(|bucket| bucket.max_tip)
+    }
+
+    /// Statistics of every bucket, by congestion level then tip
+    pub fn stats(&self) -> Vec<InclusionStats> {
+        self.state
+            .lock()
+            .buckets
+            .iter()
+            .map(|((congestion, index), bucket)| InclusionStats {
+                congestion: *congestion,
+                min_tip: match index {
+                    0 => 0,
+                    n => 10u128.pow(n - 1),
+                },
+                max_tip: bucket.max_tip,
+                count: bucket.count,
+                sum: bucket.sum,
+                p50: bucket.quantile(0.5),
+                p90: bucket.quantile(0.9),
+                histogram: bucket.cumulative_histogram(),
+            })
+            .collect()
+    }
+
+    /// Render the latencies as an `apex_sdk_inclusion_latency_seconds`
+    /// histogram in Prometheus text format, labelled with `chain`
+    pub fn export_prometheus(&self, chain: &str) -> String {
+        const NAME: &str = "apex_sdk_inclusion_latency_seconds";
+
+        let mut output = format!(
+            "# HELP {NAME} Time from broadcast to inclusion in a finalized block by tip bucket and congestion\n# TYPE {NAME} histogram\n"
+        );
+        for stats in self.stats() {
+            let labels = format!(
+                "chain=\"{}\",tip_bucket=\"{}\",congestion=\"{}\"",
+                chain,
+                stats.min_tip,
+                stats.congestion.as_str()
+            );
+            for (bound, count) in &stats.histogram {
+                output.push_str(&format!(
+                    "{NAME}_bucket{{{labels},le=\"{}\"}} {count}\n",
+                    bound.as_secs()
+                ));
+            }
+            output.push_str(&format!(
+                "{NAME}_bucket{{{labels},le=\"+Inf\"}} {}\n",
+                stats.count
+            ));
+            output.push_str(&format!(
+                "{NAME}_sum{{{labels}}} {}\n",
+                stats.sum.as_secs_f64()
+            ));
+            output.push_str(&format!("{NAME}_count{{{labels}}} {}\n", stats.count));
+        }
+        output
+    }
+}
+
+/// Bucket index of a tip: 0 for no tip, otherwise its number of digits
+fn tip_bucket(tip: u128) -> u32 {
+    match tip {
+        0 => 0,
+        tip => tip.ilog10() + 1,
+    }
+}
+
 /// Network congestion level
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum CongestionLevel {
     /// Low congestion - blocks are not full
+    #[default]
     Low,
     /// Medium congestion - blocks are moderately full
     Medium,
@@ -67,6 +417,17 @@ pub enum CongestionLevel {
     High,
 }
 
+impl CongestionLevel {
+    /// Lowercase name, used as a metric label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CongestionLevel::Low => "low",
+            CongestionLevel::Medium => "medium",
+            CongestionLevel::High => "high",
+        }
+    }
+}
+
 /// Network congestion information
 #[derive(Debug, Clone)]
 pub struct NetworkCongestion {
@@ -78,6 +439,8 @@ pub struct NetworkCongestion {
     pub avg_fee: u128,
     /// Number of blocks analyzed
     pub blocks_analyzed: u32,
+    /// Average utilization of each dispatch class's share of the block
+    pub utilization: ClassUtilization,
     /// Timestamp of last analysis
     pub last_updated: std::time::SystemTime,
 }
@@ -98,10 +461,17 @@ impl NetworkCongestion {
             avg_block_fullness,
             avg_fee,
             blocks_analyzed,
+            utilization: ClassUtilization::default(),
             last_updated: std::time::SystemTime::now(),
         }
     }
 
+    /// Set the per-class utilization
+    pub fn with_utilization(mut self, utilization: ClassUtilization) -> Self {
+        self.utilization = utilization;
+        self
+    }
+
     /// Get the congestion multiplier to apply to fees
     pub fn multiplier(&self) -> f64 {
         match self.level {
@@ -119,12 +489,18 @@ impl Default for NetworkCongestion {
             avg_block_fullness: 0.0,
             avg_fee: 0,
             blocks_analyzed: 0,
+            utilization: ClassUtilization::default(),
             last_updated: std::time::SystemTime::now(),
         }
     }
 }
 
 /// Fee estimation result with detailed breakdown
+///
+/// `base_fee`, `length_fee` and `weight_fee` are the inclusion fee
+/// components the runtime reports. `margin` is what the fee strategy and
+/// congestion add on top of them, so
+/// `total_fee = base_fee + length_fee + weight_fee + margin + tip`.
 #[derive(Debug, Clone)]
 pub struct FeeEstimate {
     /// Total estimated fee (in Planck)
@@ -133,8 +509,10 @@ pub struct FeeEstimate {
     pub base_fee: u128,
     /// Length fee component
     pub length_fee: u128,
-    /// Weight fee component
+    /// Weight fee component, adjusted by the fee multiplier
     pub weight_fee: u128,
+    /// Safety margin from the strategy and congestion multipliers
+    pub margin: u128,
     /// Tip amount
     pub tip: u128,
     /// Fee strategy used
@@ -143,10 +521,13 @@ pub struct FeeEstimate {
     pub congestion: NetworkCongestion,
     /// Estimated transaction weight
     pub weight: Option<Weight>,
+    /// `TransactionPayment::NextFeeMultiplier` at estimation time, if it
+    /// could be read
+    pub fee_multiplier: Option<f64>,
 }
 
 impl FeeEstimate {
-    /// Create a new fee estimate
+    /// Create a new fee estimate without margin or fee multiplier
     pub fn new(
         base_fee: u128,
         length_fee: u128,
@@ -162,16 +543,36 @@ impl FeeEstimate {
             base_fee,
             length_fee,
             weight_fee,
+            margin: 0,
             tip,
             strategy,
             congestion,
             weight,
+            fee_multiplier: None,
         }
     }
+
+    /// Add a safety margin to the total
+    pub fn with_margin(mut self, margin: u128) -> Self {
+        self.total_fee = self.total_fee - self.margin + margin;
+        self.margin = margin;
+        self
+    }
+
+    /// Record the fee multiplier the runtime applied to the weight fee
+    pub fn with_fee_multiplier(mut self, multiplier: f64) -> Self {
+        self.fee_multiplier = Some(multiplier);
+        self
+    }
+
+    /// Inclusion fee without margin or tip, as the runtime would charge it now
+    pub fn inclusion_fee(&self) -> u128 {
+        self.base_fee + self.length_fee + self.weight_fee
+    }
 }
 
 /// Transaction weight information
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct Weight {
     /// Reference time (computational weight)
     pub ref_time: u64,
@@ -192,6 +593,14 @@ impl Weight {
     pub fn from_parts(ref_time: u64, proof_size: u64) -> Self {
         Self::new(ref_time, proof_size)
     }
+
+    /// Add component-wise, saturating at `u64::MAX`
+    pub fn saturating_add(self, other: Weight) -> Self {
+        Self::new(
+            self.ref_time.saturating_add(other.ref_time),
+            self.proof_size.saturating_add(other.proof_size),
+        )
+    }
 }
 
 /// Runtime dispatch info from TransactionPaymentApi
@@ -209,13 +618,15 @@ pub struct RuntimeDispatchInfo {
 #[derive(Debug, Clone, Decode, Encode)]
 pub struct WeightV2 {
     /// Reference time component
+    #[codec(compact)]
     pub ref_time: u64,
     /// Proof size component
+    #[codec(compact)]
     pub proof_size: u64,
 }
 
 /// Dispatch class
-#[derive(Debug, Clone, Decode, Encode)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode)]
 pub enum DispatchClass {
     /// Normal dispatch
     Normal,
@@ -225,6 +636,346 @@ pub enum DispatchClass {
     Mandatory,
 }
 
+/// Fee breakdown from `TransactionPaymentApi_query_fee_details`
+/// (matches pallet_transaction_payment::FeeDetails)
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode)]
+pub struct FeeDetails {
+    /// Fee for including the extrinsic in a block; `None` for unsigned
+    /// extrinsics, which pay nothing
+    pub inclusion_fee: Option<InclusionFee>,
+    /// Tip the extrinsic carries
+    pub tip: u128,
+}
+
+impl FeeDetails {
+    /// Inclusion fee plus tip
+    pub fn final_fee(&self) -> u128 {
+        self.inclusion_fee
+            .as_ref()
+            .map_or(0, InclusionFee::total)
+            .saturating_add(self.tip)
+    }
+}
+
+/// Components of the inclusion fee
+/// (matches pallet_transaction_payment::InclusionFee)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Decode, Encode)]
+pub struct InclusionFee {
+    /// Fixed fee of every extrinsic, from `ExtrinsicBaseWeight`
+    pub base_fee: u128,
+    /// Fee for the encoded length, from `LengthToFee`
+    pub len_fee: u128,
+    /// Fee for the dispatch weight, from `WeightToFee`, scaled by the
+    /// `NextFeeMultiplier`
+    pub adjusted_weight_fee: u128,
+}
+
+impl InclusionFee {
+    /// Sum of the three components
+    pub fn total(&self) -> u128 {
+        self.base_fee
+            .saturating_add(self.len_fee)
+            .saturating_add(self.adjusted_weight_fee)
+    }
+}
+
+/// Fixed point scale of `sp_arithmetic::FixedU128`
+const FIXED_U128_DIV: f64 = 1_000_000_000_000_000_000.0;
+
+impl DispatchClass {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Normal" => Some(DispatchClass::Normal),
+            "Operational" => Some(DispatchClass::Operational),
+            "Mandatory" => Some(DispatchClass::Mandatory),
+            _ => None,
+        }
+    }
+}
+
+/// Block weight limits from the `System::BlockWeights` constant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockLimits {
+    /// Maximum weight of a block
+    pub max_block: Weight,
+    /// Maximum total weight of normal dispatches
+    pub max_normal: Weight,
+    /// Maximum total weight of operational dispatches
+    pub max_operational: Weight,
+}
+
+impl BlockLimits {
+    /// Read the limits from runtime metadata
+    pub fn from_metadata(metadata: &subxt::Metadata) -> Result<Self> {
+        let constant = metadata
+            .pallet_by_name("System")
+            .and_then(|pallet| pallet.constant_by_name("BlockWeights"))
+            .ok_or_else(|| Error::Metadata("System::BlockWeights not found".to_string()))?;
+        Self::decode(constant.value())
+    }
+
+    /// Decode a SCALE-encoded `frame_system::limits::BlockWeights`
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        let weights = BlockWeights::decode(&mut &bytes[..])
+            .map_err(|e| Error::Metadata(format!("Failed to decode BlockWeights: {}", e)))?;
+        let max_block = Weight::from(&weights.max_block);
+        // An unset class limit means the class may fill the whole block
+        let class_max = |class: &WeightsPerClass| {
+            class
+                .max_total
+                .as_ref()
+                .map(Weight::from)
+                .unwrap_or(max_block)
+        };
+
+        Ok(Self {
+            max_block,
+            max_normal: class_max(&weights.per_class.normal),
+            max_operational: class_max(&weights.per_class.operational),
+        })
+    }
+}
+
+impl Default for BlockLimits {
+    /// Polkadot relay chain limits, for runtimes without the constant
+    fn default() -> Self {
+        let max_block = Weight::new(2_000_000_000_000, u64::MAX);
+        Self {
+            max_block,
+            max_normal: Weight::new(max_block.ref_time / 4 * 3, max_block.proof_size / 4 * 3),
+            max_operational: max_block,
+        }
+    }
+}
+
+/// `frame_system::limits::BlockWeights`
+#[derive(Decode, Encode)]
+struct BlockWeights {
+    base_block: WeightV2,
+    max_block: WeightV2,
+    per_class: PerDispatchClass,
+}
+
+#[derive(Decode, Encode)]
+struct PerDispatchClass {
+    normal: WeightsPerClass,
+    operational: WeightsPerClass,
+    mandatory: WeightsPerClass,
+}
+
+#[derive(Decode, Encode)]
+struct WeightsPerClass {
+    base_extrinsic: WeightV2,
+    max_extrinsic: Option<WeightV2>,
+    max_total: Option<WeightV2>,
+    reserved: Option<WeightV2>,
+}
+
+impl From<&WeightV2> for Weight {
+    fn from(weight: &WeightV2) -> Self {
+        Weight::new(weight.ref_time, weight.proof_size)
+    }
+}
+
+/// Share of a limit used, between 0.0 and 1.0
+///
+/// Weight has two dimensions and a block is full once either runs out, so
+/// this is the larger of the two ratios.
+fn weight_ratio(used: Weight, limit: Weight) -> f64 {
+    let ratio = |used: u64, limit: u64| {
+        if limit == 0 {
+            0.0
+        } else {
+            used as f64 / limit as f64
+        }
+    };
+    ratio(used.ref_time, limit.ref_time)
+        .max(ratio(used.proof_size, limit.proof_size))
+        .min(1.0)
+}
+
+/// Weight consumed by the extrinsics of a block, per dispatch class
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockUsage {
+    /// Weight of normal extrinsics
+    pub normal: Weight,
+    /// Weight of operational extrinsics
+    pub operational: Weight,
+    /// Weight of mandatory extrinsics (inherents)
+    pub mandatory: Weight,
+}
+
+impl BlockUsage {
+    /// Add the weight of one extrinsic
+    pub fn add(&mut self, class: DispatchClass, weight: Weight) {
+        let total = match class {
+            DispatchClass::Normal => &mut self.normal,
+            DispatchClass::Operational => &mut self.operational,
+            DispatchClass::Mandatory => &mut self.mandatory,
+        };
+        *total = total.saturating_add(weight);
+    }
+
+    /// Add the weight reported by a `System::ExtrinsicSuccess` or
+    /// `System::ExtrinsicFailed` event
+    ///
+    /// Returns `false` if the fields carry no dispatch info. Events without a
+    /// class are counted as normal.
+    pub fn add_event_fields<T>(&mut self, fields: &subxt::ext::scale_value::Composite<T>) -> bool {
+        let Some(weight) = dispatch_weight(fields) else {
+            return false;
+        };
+        self.add(
+            dispatch_class(fields).unwrap_or(DispatchClass::Normal),
+            weight,
+        );
+        true
+    }
+
+    /// Weight of all classes together
+    pub fn total(&self) -> Weight {
+        self.normal
+            .saturating_add(self.operational)
+            .saturating_add(self.mandatory)
+    }
+
+    /// Share of the block's capacity used, between 0.0 and 1.0
+    pub fn fullness(&self, limits: &BlockLimits) -> f64 {
+        weight_ratio(self.total(), limits.max_block)
+    }
+
+    /// Share of each class's limit used
+    pub fn utilization(&self, limits: &BlockLimits) -> ClassUtilization {
+        ClassUtilization {
+            normal: weight_ratio(self.normal, limits.max_normal),
+            operational: weight_ratio(self.operational, limits.max_operational),
+        }
+    }
+}
+
+/// Share of each dispatch class's weight limit in use, between 0.0 and 1.0
+///
+/// Regular transactions are normal dispatches, which may only use part of a
+/// block (75% on most chains), so normal utilization can reach 1.0 while the
+/// block still has room for operational dispatches.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ClassUtilization {
+    /// Normal dispatches against their `max_total`
+    pub normal: f64,
+    /// Operational dispatches against their `max_total`
+    pub operational: f64,
+}
+
+/// Blocks the congestion averages cover
+const CONGESTION_WINDOW: usize = 10;
+
+/// Delay before the congestion tracker subscribes again after losing its
+/// subscription
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// Usage and average fee of the most recent blocks, oldest first
+#[derive(Debug, Default)]
+struct CongestionWindow {
+    blocks: VecDeque<(u64, BlockUsage, u128)>,
+}
+
+impl CongestionWindow {
+    /// Add a block, dropping blocks at or above its number that a reorg
+    /// replaced and blocks that fell out of the window
+    fn push(&mut self, number: u64, usage: BlockUsage, avg_fee: u128) {
+        while self.blocks.back().is_some_and(|(n, ..)| *n >= number) {
+            self.blocks.pop_back();
+        }
+        self.blocks.push_back((number, usage, avg_fee));
+        while self.blocks.len() > CONGESTION_WINDOW {
+            self.blocks.pop_front();
+        }
+    }
+
+    /// Congestion averaged over the window, if it holds any blocks
+    fn congestion(&self, limits: &BlockLimits) -> Option<NetworkCongestion> {
+        if self.blocks.is_empty() {
+            return None;
+        }
+
+        let count = self.blocks.len() as f64;
+        let mut fullness = 0.0;
+        let mut normal = 0.0;
+        let mut operational = 0.0;
+        let mut fees = 0u128;
+        for (_, usage, avg_fee) in &self.blocks {
+            let utilization = usage.utilization(limits);
+            fullness += usage.fullness(limits);
+            normal += utilization.normal;
+            operational += utilization.operational;
+            fees += avg_fee;
+        }
+
+        Some(
+            NetworkCongestion::new(
+                fullness / count,
+                fees / self.blocks.len() as u128,
+                self.blocks.len() as u32,
+            )
+            .with_utilization(ClassUtilization {
+                normal: normal / count,
+                operational: operational / count,
+            }),
+        )
+    }
+}
+
+/// Marks congestion as tracked until dropped, including when the tracking
+/// task is aborted
+struct TrackingGuard(Arc<AtomicBool>);
+
+impl TrackingGuard {
+    fn new(tracking: &Arc<AtomicBool>) -> Self {
+        tracking.store(true, Ordering::Release);
+        Self(tracking.clone())
+    }
+}
+
+impl Drop for TrackingGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// Arguments of the TransactionPaymentApi fee queries: the encoded
+/// extrinsic as is, followed by its length as a `u32`
+fn transaction_payment_params(extrinsic_bytes: &[u8]) -> Vec<u8> {
+    let mut params = extrinsic_bytes.to_vec();
+    (extrinsic_bytes.len() as u32).encode_to(&mut params);
+    params
+}
+
+/// Extract `dispatch_info.weight` from `System::ExtrinsicSuccess` or
+/// `System::ExtrinsicFailed` event fields
+pub(crate) fn dispatch_weight<T>(fields: &subxt::ext::scale_value::Composite<T>) -> Option<Weight> {
+    use subxt::dynamic::At as _;
+
+    let weight = fields.at("dispatch_info")?.at("weight")?;
+    let ref_time = weight.at("ref_time")?.as_u128()?;
+    let proof_size = weight.at("proof_size")?.as_u128()?;
+
+    Some(Weight::new(
+        u64::try_from(ref_time).ok()?,
+        u64::try_from(proof_size).ok()?,
+    ))
+}
+
+/// Extract `dispatch_info.class` from the same event fields
+fn dispatch_class<T>(fields: &subxt::ext::scale_value::Composite<T>) -> Option<DispatchClass> {
+    use subxt::dynamic::At as _;
+    use subxt::ext::scale_value::ValueDef;
+
+    match &fields.at("dispatch_info")?.at("class")?.value {
+        ValueDef::Variant(variant) => DispatchClass::from_name(&variant.name),
+        _ => None,
+    }
+}
+
 /// Fee estimation accuracy metric
 #[derive(Debug, Clone)]
 pub struct FeeAccuracyMetric {
@@ -276,12 +1027,19 @@ pub struct FeeAccuracyStats {
 }
 
 /// Dynamic fee estimator with dynamic calculation
+///
+/// Clones share congestion state and accuracy history.
+#[derive(Clone)]
 pub struct DynamicFeeEstimator {
     client: OnlineClient<PolkadotConfig>,
     congestion: Arc<RwLock<NetworkCongestion>>,
     accuracy_metrics: Arc<RwLock<VecDeque<FeeAccuracyMetric>>>,
     max_metrics: usize,
     congestion_update_interval: std::time::Duration,
+    latencies: InclusionLatencies,
+    window: Arc<parking_lot::Mutex<CongestionWindow>>,
+    tracking: Arc<AtomicBool>,
+    strategies: Arc<parking_lot::RwLock<FeeStrategyConfig>>,
 }
 
 impl DynamicFeeEstimator {
@@ -293,6 +1051,10 @@ impl DynamicFeeEstimator {
             accuracy_metrics: Arc::new(RwLock::new(VecDeque::new())),
             max_metrics: 1000,
             congestion_update_interval: std::time::Duration::from_secs(30),
+            latencies: InclusionLatencies::new(),
+            window: Arc::default(),
+            tracking: Arc::default(),
+            strategies: Arc::default(),
         }
     }
 
@@ -308,14 +1070,70 @@ impl DynamicFeeEstimator {
             accuracy_metrics: Arc::new(RwLock::new(VecDeque::new())),
             max_metrics,
             congestion_update_interval,
+            latencies: InclusionLatencies::new(),
+            window: Arc::default(),
+            tracking: Arc::default(),
+            strategies: Arc::default(),
         }
     }
 
+    /// Use `config` for strategy multipliers, tips and fee caps
+    pub fn with_strategies(self, config: FeeStrategyConfig) -> Self {
+        *self.strategies.write() = config;
+        self
+    }
+
+    /// Current strategy profiles
+    pub fn strategy_config(&self) -> FeeStrategyConfig {
+        self.strategies.read().clone()
+    }
+
+    /// Add or replace a named strategy, for this estimator and its clones
+    pub fn register_strategy(&self, name: impl Into<String>, config: FeeConfig) {
+        let name = name.into();
+        debug!("Registering fee strategy {}", name);
+        self.strategies.write().register(name, config);
+    }
+
     /// Estimate fee for a transaction with detailed breakdown
+    ///
+    /// Fails with [`Error::FeeCapExceeded`] when the estimate is above the
+    /// strategy's [`max_fee`](FeeConfig::max_fee).
     pub async fn estimate_fee(
         &self,
         extrinsic_bytes: &[u8],
         strategy: FeeStrategy,
+    ) -> Result<FeeEstimate> {
+        let profile = self.strategies.read().get(strategy).clone();
+        let tip = self.tip_for(strategy);
+        self.estimate_with_profile(extrinsic_bytes, strategy, &profile, tip)
+            .await
+    }
+
+    /// Estimate fee using a strategy registered by name
+    ///
+    /// The estimate reports [`FeeStrategy::Normal`] as its strategy.
+    pub async fn estimate_fee_named(
+        &self,
+        extrinsic_bytes: &[u8],
+        name: &str,
+    ) -> Result<FeeEstimate> {
+        let profile = self
+            .strategies
+            .read()
+            .named(name)
+            .cloned()
+            .ok_or_else(|| Error::Other(format!("Unknown fee strategy: {}", name)))?;
+        self.estimate_with_profile(extrinsic_bytes, FeeStrategy::Normal, &profile, profile.tip)
+            .await
+    }
+
+    async fn estimate_with_profile(
+        &self,
+        extrinsic_bytes: &[u8],
+        strategy: FeeStrategy,
+        profile: &FeeConfig,
+        tip: u128,
     ) -> Result<FeeEstimate> {
         debug!(
             "Estimating fee for {} byte extrinsic with {:?} strategy",
@@ -327,76 +1145,194 @@ impl DynamicFeeEstimator {
 
         let congestion = self.congestion.read().await.clone();
 
-        let dispatch_info = match self.query_fee_details(extrinsic_bytes).await {
+        let dispatch_info = match self.query_info(extrinsic_bytes).await {
             Ok(info) => {
                 debug!(
-                    "Got fee details from runtime: partial_fee={}, weight=({}, {})",
+                    "Got dispatch info from runtime: partial_fee={}, weight=({}, {})",
                     info.partial_fee, info.weight.ref_time, info.weight.proof_size
                 );
                 Some(info)
             }
             Err(e) => {
-                warn!("Failed to query runtime fee details: {}, using fallback", e);
+                warn!(
+                    "Failed to query runtime dispatch info: {}, using fallback",
+                    e
+                );
                 None
             }
         };
+        let weight = dispatch_info
+            .as_ref()
+            .map(|info| Weight::from_parts(info.weight.ref_time, info.weight.proof_size));
 
-        let (base_fee, weight_opt) = if let Some(info) = dispatch_info {
-            let weight = Weight::from_parts(info.weight.ref_time, info.weight.proof_size);
-            (info.partial_fee, Some(weight))
-        } else {
-            (self.calculate_fallback_fee(extrinsic_bytes), None)
+        let inclusion_fee = match self.query_fee_details(extrinsic_bytes).await {
+            Ok(details) => details.inclusion_fee.unwrap_or_default(),
+            Err(e) => {
+                warn!("Failed to query runtime fee details: {}", e);
+                // Without a breakdown, report the whole fee as base fee
+                InclusionFee {
+                    base_fee: dispatch_info.as_ref().map_or_else(
+                        || self.calculate_fallback_fee(extrinsic_bytes),
+                        |info| info.partial_fee,
+                    ),
+                    ..Default::default()
+                }
+            }
         };
 
-        let length_fee = (extrinsic_bytes.len() as u128) * 1_000;
-        let weight_fee = if let Some(weight) = weight_opt {
-            (weight.ref_time as u128) / 1_000_000
-        } else {
-            0
+        let fee_multiplier = match self.next_fee_multiplier().await {
+            Ok(multiplier) => Some(multiplier),
+            Err(e) => {
+                debug!("Failed to read NextFeeMultiplier: {}", e);
+                None
+            }
         };
 
-        let strategy_multiplier = strategy.multiplier();
+        let strategy_multiplier = profile.multiplier;
         let congestion_multiplier = congestion.multiplier();
         let combined_multiplier = strategy_multiplier * congestion_multiplier;
 
-        let adjusted_base = (base_fee as f64 * combined_multiplier) as u128;
-        let tip = strategy.tip();
+        let inclusion = inclusion_fee.total();
+        let margin = (inclusion as f64 * (combined_multiplier - 1.0)).max(0.0) as u128;
 
-        let estimate = FeeEstimate::new(
-            adjusted_base,
-            length_fee,
-            weight_fee,
+        let mut estimate = FeeEstimate::new(
+            inclusion_fee.base_fee,
+            inclusion_fee.len_fee,
+            inclusion_fee.adjusted_weight_fee,
             tip,
             strategy,
             congestion,
-            weight_opt,
-        );
+            weight,
+        )
+        .with_margin(margin);
+        if let Some(multiplier) = fee_multiplier {
+            estimate = estimate.with_fee_multiplier(multiplier);
+        }
 
         debug!(
-            "Fee estimate: total={}, base={}, strategy_mult={}, congestion_mult={}",
-            estimate.total_fee, adjusted_base, strategy_multiplier, congestion_multiplier
+            "Fee estimate: total={}, inclusion={}, strategy_mult={}, congestion_mult={}",
+            estimate.total_fee, inclusion, strategy_multiplier, congestion_multiplier
         );
 
-        Ok(estimate)
+        match profile.max_fee {
+            Some(max_fee) if estimate.total_fee > max_fee => {
+                Err(Error::FeeCapExceeded(Box::new(FeeCapExceeded {
+                    partial_fee: inclusion,
+                    multiplier: strategy_multiplier,
+                    congestion_multiplier,
+                    tip,
+                    total_fee: estimate.total_fee,
+                    max_fee,
+                })))
+            }
+            _ => Ok(estimate),
+        }
     }
 
-    /// Query fee details from runtime
-    async fn query_fee_details(&self, extrinsic_bytes: &[u8]) -> Result<RuntimeDispatchInfo> {
-        let length = extrinsic_bytes.len() as u32;
-        let call_data = (extrinsic_bytes, length).encode();
+    /// Tip for a strategy, learned from inclusion latencies for
+    /// [`FeeStrategy::TargetTime`]
+    pub fn tip_for(&self, strategy: FeeStrategy) -> u128 {
+        let configured = self.strategies.read().get(strategy).tip;
+        let FeeStrategy::TargetTime(target) = strategy else {
+            return configured;
+        };
 
+        match self.latencies.tip_for(target, self.latencies.congestion()) {
+            Some(tip) => {
+                debug!("Observed tip {} confirms within {:?}", tip, target);
+                tip
+            }
+            None => {
+                let tier = strategy.fixed_tier();
+                debug!(
+                    "No observed tip confirms within {:?}, using {:?} tier",
+                    target, tier
+                );
+                configured
+            }
+        }
+    }
+
+    /// Inclusion latency statistics per tip bucket and congestion level,
+    /// showing what each tip size has bought
+    pub fn inclusion_stats(&self) -> Vec<InclusionStats> {
+        self.latencies.stats()
+    }
+
+    /// Inclusion latencies [`FeeStrategy::TargetTime`] picks tips from
+    ///
+    /// The returned handle shares samples with this estimator; hand it to a
+    /// [`TransactionMonitor`](crate::monitor::TransactionMonitor) to record
+    /// confirmations.
+    pub fn inclusion_latencies(&self) -> InclusionLatencies {
+        self.latencies.clone()
+    }
+
+    /// Weight, dispatch class and partial fee of an extrinsic, from
+    /// `TransactionPaymentApi_query_info`
+    pub async fn query_info(&self, extrinsic_bytes: &[u8]) -> Result<RuntimeDispatchInfo> {
         let result = self
+            .call_transaction_payment_api("TransactionPaymentApi_query_info", extrinsic_bytes)
+            .await?;
+
+        RuntimeDispatchInfo::decode(&mut &result[..])
+            .map_err(|e| Error::Transaction(format!("Failed to decode dispatch info: {}", e)))
+    }
+
+    /// Inclusion fee components of an extrinsic, from
+    /// `TransactionPaymentApi_query_fee_details`
+    ///
+    /// `extrinsic_bytes` is a complete encoded extrinsic, signed or unsigned.
+    /// Unsigned extrinsics have no inclusion fee.
+    pub async fn query_fee_details(&self, extrinsic_bytes: &[u8]) -> Result<FeeDetails> {
+        let result = self
+            .call_transaction_payment_api(
+                "TransactionPaymentApi_query_fee_details",
+                extrinsic_bytes,
+            )
+            .await?;
+
+        FeeDetails::decode(&mut &result[..])
+            .map_err(|e| Error::Transaction(format!("Failed to decode fee details: {}", e)))
+    }
+
+    /// Current `TransactionPayment::NextFeeMultiplier`
+    ///
+    /// The runtime scales the weight fee of every extrinsic by this factor,
+    /// which rises while blocks are fuller than the target and falls while
+    /// they are emptier.
+    pub async fn next_fee_multiplier(&self) -> Result<f64> {
+        let address = subxt::dynamic::storage("TransactionPayment", "NextFeeMultiplier", ());
+        let value = self
             .client
-            .runtime_api()
+            .storage()
             .at_latest()
             .await
             .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?
-            .call_raw("TransactionPaymentApi_query_info", Some(&call_data))
+            .fetch(&address)
             .await
-            .map_err(|e| Error::Transaction(format!("Failed to query fee info: {}", e)))?;
+            .map_err(|e| Error::Storage(format!("Failed to read NextFeeMultiplier: {}", e)))?
+            .ok_or_else(|| Error::Storage("NextFeeMultiplier is not set".to_string()))?;
 
-        RuntimeDispatchInfo::decode(&mut &result[..])
-            .map_err(|e| Error::Transaction(format!("Failed to decode dispatch info: {}", e)))
+        let inner = u128::decode(&mut value.encoded())
+            .map_err(|e| Error::Storage(format!("Failed to decode NextFeeMultiplier: {}", e)))?;
+        Ok(inner as f64 / FIXED_U128_DIV)
+    }
+
+    /// Call a TransactionPaymentApi method taking `(extrinsic, length)`
+    async fn call_transaction_payment_api(
+        &self,
+        method: &str,
+        extrinsic_bytes: &[u8],
+    ) -> Result<Vec<u8>> {
+        self.client
+            .runtime_api()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?
+            .call_raw(method, Some(&transaction_payment_params(extrinsic_bytes)))
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to call {}: {}", method, e)))
     }
 
     /// Calculate fallback fee when runtime query fails
@@ -417,128 +1353,188 @@ impl DynamicFeeEstimator {
     }
 
     /// Update network congestion information
+    ///
+    /// Walks the last 10 blocks, measuring each against the runtime's
+    /// `System::BlockWeights` limits with the weight its extrinsics report in
+    /// `System::ExtrinsicSuccess`/`ExtrinsicFailed` events.
     pub async fn update_congestion(&self) -> Result<()> {
         debug!("Updating network congestion information");
 
-        let blocks_to_analyze = 10u32;
-        let latest_block = self
+        let mut block = self
             .client
             .blocks()
             .at_latest()
             .await
             .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?;
 
-        let latest_number = latest_block.number();
-        let mut total_fullness = 0.0f64;
-        let mut total_fees = 0u128;
-        let mut blocks_analyzed = 0u32;
-
-        for offset in 0..blocks_to_analyze {
-            let block_number = latest_number.saturating_sub(offset);
-            match self.analyze_block_congestion(block_number).await {
-                Ok((fullness, avg_fee)) => {
-                    total_fullness += fullness;
-                    total_fees += avg_fee;
-                    blocks_analyzed += 1;
-                }
+        let mut analyzed = Vec::with_capacity(CONGESTION_WINDOW);
+        for offset in 0..CONGESTION_WINDOW {
+            match self.analyze_block_congestion(&block).await {
+                Ok((usage, avg_fee)) => analyzed.push((block.number().into(), usage, avg_fee)),
                 Err(e) => {
-                    warn!("Failed to analyze block {}: {}", block_number, e);
+                    warn!("Failed to analyze block {}: {}", block.number(), e);
                 }
             }
-        }
 
-        if blocks_analyzed > 0 {
-            let avg_fullness = total_fullness / blocks_analyzed as f64;
-            let avg_fee = total_fees / blocks_analyzed as u128;
+            if offset + 1 == CONGESTION_WINDOW || block.number() == 0 {
+                break;
+            }
+            block = self
+                .client
+                .blocks()
+                .at(block.header().parent_hash)
+                .await
+                .map_err(|e| Error::Connection(format!("Failed to navigate to block: {}", e)))?;
+        }
 
-            let congestion = NetworkCongestion::new(avg_fullness, avg_fee, blocks_analyzed);
+        let congestion = {
+            let mut window = self.window.lock();
+            *window = CongestionWindow::default();
+            for (number, usage, avg_fee) in analyzed.into_iter().rev() {
+                window.push(number, usage, avg_fee);
+            }
+            window.congestion(&self.block_limits())
+        };
+        if let Some(congestion) = congestion {
             info!(
-                "Network congestion updated: level={:?}, fullness={:.2}%, avg_fee={}",
+                "Network congestion updated: level={:?}, fullness={:.2}%, normal={:.2}%, avg_fee={}",
                 congestion.level,
-                avg_fullness * 100.0,
-                avg_fee
+                congestion.avg_block_fullness * 100.0,
+                congestion.utilization.normal * 100.0,
+                congestion.avg_fee
             );
-
-            *self.congestion.write().await = congestion;
+            self.set_congestion(congestion).await;
         }
 
         Ok(())
     }
 
-    /// Analyze a single block for congestion metrics
-    async fn analyze_block_congestion(&self, block_number: u32) -> Result<(f64, u128)> {
-        let latest = self
-            .client
-            .blocks()
-            .at_latest()
-            .await
-            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?;
+    /// Keep congestion current from a best block subscription
+    ///
+    /// The returned task analyzes each new best block as it arrives and
+    /// updates the 10-block averages incrementally, so
+    /// [`estimate_fee`](Self::estimate_fee) reads them without walking blocks
+    /// itself. While the subscription is down, estimation falls back to
+    /// refreshing on demand. Abort the handle to stop tracking.
+    pub fn start_congestion_tracking(&self) -> tokio::task::JoinHandle<()> {
+        let estimator = self.clone();
+        tokio::spawn(async move { estimator.track_congestion().await })
+    }
 
-        let target_number = block_number;
-        let latest_number = latest.number();
+    /// Whether a tracking task is currently subscribed to new blocks
+    pub fn is_tracking_congestion(&self) -> bool {
+        self.tracking.load(Ordering::Acquire)
+    }
 
-        if target_number > latest_number {
-            return Err(Error::Connection(format!(
-                "Block {} is in the future (latest: {})",
-                target_number, latest_number
-            )));
-        }
+    async fn track_congestion(&self) {
+        loop {
+            let mut subscription = match self.client.blocks().subscribe_best().await {
+                Ok(subscription) => subscription,
+                Err(e) => {
+                    warn!("Congestion tracker failed to subscribe to blocks: {}", e);
+                    tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+                    continue;
+                }
+            };
 
-        let offset = latest_number.saturating_sub(target_number);
-        let mut current_block = latest;
+            // Fill the window with the blocks before the subscription started
+            if let Err(e) = self.update_congestion().await {
+                warn!("Congestion tracker failed to load recent blocks: {}", e);
+            }
+            let tracking = TrackingGuard::new(&self.tracking);
+            debug!("Congestion tracker subscribed to best blocks");
+
+            while let Some(block) = subscription.next().await {
+                let block = match block {
+                    Ok(block) => block,
+                    Err(e) => {
+                        warn!(
+                            "Congestion tracker subscription error, resubscribing: {}",
+                            e
+                        );
+                        break;
+                    }
+                };
+
+                match self.analyze_block_congestion(&block).await {
+                    Ok((usage, avg_fee)) => {
+                        let congestion = {
+                            let mut window = self.window.lock();
+                            window.push(block.number().into(), usage, avg_fee);
+                            window.congestion(&self.block_limits())
+                        };
+                        if let Some(congestion) = congestion {
+                            debug!(
+                                "Congestion at block {}: level={:?}, fullness={:.2}%",
+                                block.number(),
+                                congestion.level,
+                                congestion.avg_block_fullness * 100.0
+                            );
+                            self.set_congestion(congestion).await;
+                        }
+                    }
+                    Err(e) => warn!("Failed to analyze block {}: {}", block.number(), e),
+                }
+            }
 
-        for _ in 0..offset {
-            let parent_hash = current_block.header().parent_hash;
-            current_block =
-                self.client.blocks().at(parent_hash).await.map_err(|e| {
-                    Error::Connection(format!("Failed to navigate to block: {}", e))
-                })?;
+            drop(tracking);
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
         }
+    }
+
+    async fn set_congestion(&self, congestion: NetworkCongestion) {
+        self.latencies.set_congestion(congestion.level);
+        *self.congestion.write().await = congestion;
+    }
+
+    /// Block weight limits of the connected runtime
+    ///
+    /// Falls back to [`BlockLimits::default`] if the metadata lacks or has
+    /// an unexpected `System::BlockWeights` constant.
+    pub fn block_limits(&self) -> BlockLimits {
+        BlockLimits::from_metadata(&self.client.metadata()).unwrap_or_else(|e| {
+            warn!("{}, using default block limits", e);
+            BlockLimits::default()
+        })
+    }
 
-        let extrinsics = current_block
-            .extrinsics()
+    /// Weight used by a block's extrinsics and their average fee
+    async fn analyze_block_congestion(
+        &self,
+        block: &subxt::blocks::Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    ) -> Result<(BlockUsage, u128)> {
+        use subxt::dynamic::At as _;
+
+        let events = block
+            .events()
             .await
-            .map_err(|e| Error::Transaction(format!("Failed to get extrinsics: {}", e)))?;
+            .map_err(|e| Error::Transaction(format!("Failed to get events: {}", e)))?;
 
-        let extrinsic_count = extrinsics.len();
-        let max_block_weight = 2_000_000_000_000u64;
-        let mut total_weight = 0u64;
+        let mut usage = BlockUsage::default();
         let mut total_fees = 0u128;
         let mut fee_count = 0u32;
 
-        for ext in extrinsics.iter() {
-            match ext.events().await {
-                Ok(events) => {
-                    for event_result in events.iter() {
-                        match event_result {
-                            Ok(event) => {
-                                if event.pallet_name() == "TransactionPayment"
-                                    && event.variant_name() == "TransactionFeePaid"
-                                {
-                                    let fee_event = event.field_bytes();
-                                    if fee_event.len() >= 16 {
-                                        let fee_bytes = &fee_event[fee_event.len() - 16..];
-                                        if fee_bytes.len() == 16 {
-                                            let mut fee_array = [0u8; 16];
-                                            fee_array.copy_from_slice(fee_bytes);
-                                            let fee = u128::from_le_bytes(fee_array);
-                                            total_fees += fee;
-                                            fee_count += 1;
-                                        }
-                                    }
-                                }
-                            }
-                            Err(_) => continue,
-                        }
+        for event in events.iter().flatten() {
+            match (event.pallet_name(), event.variant_name()) {
+                ("System", "ExtrinsicSuccess" | "ExtrinsicFailed") => {
+                    if let Ok(fields) = event.field_values() {
+                        usage.add_event_fields(&fields);
+                    }
+                }
+                ("TransactionPayment", "TransactionFeePaid") => {
+                    let fee = event
+                        .field_values()
+                        .ok()
+                        .and_then(|fields| fields.at("actual_fee").and_then(|fee| fee.as_u128()));
+                    if let Some(fee) = fee {
+                        total_fees += fee;
+                        fee_count += 1;
                     }
                 }
-                Err(_) => continue,
+                _ => {}
             }
-
-            total_weight += 200_000_000;
         }
 
-        let block_fullness = (total_weight as f64) / (max_block_weight as f64);
         let avg_fee = if fee_count > 0 {
             total_fees / fee_count as u128
         } else {
@@ -546,18 +1542,24 @@ impl DynamicFeeEstimator {
         };
 
         debug!(
-            "Block {} analysis: {} extrinsics, fullness={:.2}%, avg_fee={}",
-            block_number,
-            extrinsic_count,
-            block_fullness * 100.0,
+            "Block {} analysis: weight=({}, {}), normal={:?}, avg_fee={}",
+            block.number(),
+            usage.total().ref_time,
+            usage.total().proof_size,
+            usage.normal,
             avg_fee
         );
 
-        Ok((block_fullness.min(1.0), avg_fee))
+        Ok((usage, avg_fee))
     }
 
-    /// Update congestion if enough time has passed
+    /// Update congestion if enough time has passed and no tracking task
+    /// keeps it current
     async fn update_congestion_if_needed(&self) -> Result<()> {
+        if self.is_tracking_congestion() {
+            return Ok(());
+        }
+
         let should_update = {
             let congestion = self.congestion.read().await;
             match congestion.last_updated.elapsed() {
@@ -644,6 +1646,113 @@ mod tests {
         assert_eq!(FeeStrategy::Slow.tip(), 0);
     }
 
+    #[test]
+    fn test_target_time_falls_back_to_fixed_tiers() {
+        let quick = FeeStrategy::TargetTime(Duration::from_secs(12));
+        assert_eq!(quick.fixed_tier(), FeeStrategy::Fast);
+        assert_eq!(quick.tip(), FeeStrategy::Fast.tip());
+        assert_eq!(
+            FeeStrategy::TargetTime(Duration::from_secs(60)).fixed_tier(),
+            FeeStrategy::Normal
+        );
+        assert_eq!(
+            FeeStrategy::TargetTime(Duration::from_secs(600)).multiplier(),
+            FeeStrategy::Slow.multiplier()
+        );
+        assert_eq!(FeeStrategy::Fast.fixed_tier(), FeeStrategy::Fast);
+    }
+
+    #[test]
+    fn test_fee_strategy_config() {
+        let defaults = FeeStrategyConfig::default();
+        assert_eq!(defaults.get(FeeStrategy::Fast).multiplier, 1.5);
+        assert_eq!(defaults.get(FeeStrategy::Normal).tip, 100_000);
+        assert_eq!(defaults.get(FeeStrategy::Slow).max_fee, None);
+
+        let mut config = FeeStrategyConfig::new()
+            .with_strategy(
+                FeeStrategy::Fast,
+                FeeConfig::new()
+                    .with_multiplier(2.0)
+                    .with_tip(10)
+                    .with_max_fee(1_000),
+            )
+            .with_named("cheap", FeeConfig::new().with_multiplier(1.0));
+        let target = FeeStrategy::TargetTime(Duration::from_secs(12));
+        assert_eq!(config.get(target).multiplier, 2.0);
+        assert_eq!(config.get(FeeStrategy::Fast).max_fee, Some(1_000));
+        assert_eq!(config.get(FeeStrategy::Normal).multiplier, 1.2);
+
+        assert!(config
+            .register("priority", FeeConfig::new().with_tip(5))
+            .is_none());
+        assert!(config.register("cheap", FeeConfig::new()).is_some());
+        assert_eq!(config.named("priority").map(|p| p.tip), Some(5));
+        assert!(config.named("missing").is_none());
+        assert_eq!(config.names().collect::<Vec<_>>(), ["cheap", "priority"]);
+    }
+
+    #[test]
+    fn test_inclusion_latencies_pick_cheapest_fast_enough_tip() {
+        let latencies = InclusionLatencies::new();
+        let secs = Duration::from_secs;
+        let low = CongestionLevel::Low;
+
+        // Untipped transactions take about a minute, tips around 0.0001 DOT
+        // about 20 seconds, larger tips about 12 seconds
+        for _ in 0..10 {
+            latencies.record(0, low, secs(60));
+            latencies.record(100_000, low, secs(18));
+            latencies.record(250_000, low, secs(20));
+            latencies.record(1_000_000, low, secs(12));
+            latencies.record(1_000_000, CongestionLevel::High, secs(40));
+        }
+        // Too few samples to count
+        latencies.record(5, low, secs(6));
+
+        assert_eq!(latencies.tip_for(secs(90), low), Some(0));
+        assert_eq!(latencies.tip_for(secs(30), low), Some(250_000));
+        assert_eq!(latencies.tip_for(secs(12), low), Some(1_000_000));
+        assert_eq!(latencies.tip_for(secs(6), low), None);
+        assert_eq!(latencies.tip_for(secs(30), CongestionLevel::High), None);
+        assert_eq!(latencies.latency(300_000, low), Some(secs(20)));
+    }
+
+    #[test]
+    fn test_inclusion_stats_histogram() {
+        let latencies = InclusionLatencies::new();
+        let secs = Duration::from_secs;
+        latencies.set_congestion(CongestionLevel::Medium);
+        let level = latencies.congestion();
+
+        for latency in [5, 6, 7, 13, 400] {
+            latencies.record(2_000, level, secs(latency));
+        }
+
+        let stats = latencies.stats();
+        assert_eq!(stats.len(), 1);
+        let stats = &stats[0];
+        assert_eq!(stats.congestion, CongestionLevel::Medium);
+        assert_eq!((stats.min_tip, stats.max_tip), (1_000, 2_000));
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.sum, secs(431));
+        assert_eq!(stats.p50, Some(secs(7)));
+        assert_eq!(
+            &stats.histogram[..3],
+            &[(secs(6), 2), (secs(12), 3), (secs(18), 4)]
+        );
+        assert_eq!(stats.histogram.last(), Some(&(secs(300), 4)));
+
+        let exported = latencies.export_prometheus("polkadot");
+        assert!(exported.contains("# TYPE apex_sdk_inclusion_latency_seconds histogram"));
+        assert!(exported.contains(
+            "apex_sdk_inclusion_latency_seconds_bucket{chain=\"polkadot\",tip_bucket=\"1000\",congestion=\"medium\",le=\"12\"} 3"
+        ));
+        assert!(exported.contains(
+            "apex_sdk_inclusion_latency_seconds_count{chain=\"polkadot\",tip_bucket=\"1000\",congestion=\"medium\"} 5"
+        ));
+    }
+
     #[test]
     fn test_fee_strategy_default() {
         assert_eq!(FeeStrategy::default(), FeeStrategy::Normal);
@@ -661,6 +1770,123 @@ mod tests {
         assert_eq!(high.level, CongestionLevel::High);
     }
 
+    fn block_weights(max_normal: Option<WeightV2>) -> Vec<u8> {
+        let weight = |ref_time, proof_size| WeightV2 {
+            ref_time,
+            proof_size,
+        };
+        let class = |max_total| WeightsPerClass {
+            base_extrinsic: weight(100, 0),
+            max_extrinsic: None,
+            max_total,
+            reserved: None,
+        };
+        BlockWeights {
+            base_block: weight(5_000, 0),
+            max_block: weight(1_000_000, 10_000),
+            per_class: PerDispatchClass {
+                normal: class(max_normal),
+                operational: class(None),
+                mandatory: class(None),
+            },
+        }
+        .encode()
+    }
+
+    #[test]
+    fn test_block_limits_decode() {
+        let limits = BlockLimits::decode(&block_weights(Some(WeightV2 {
+            ref_time: 750_000,
+            proof_size: 7_500,
+        })))
+        .unwrap();
+        assert_eq!(limits.max_block, Weight::new(1_000_000, 10_000));
+        assert_eq!(limits.max_normal, Weight::new(750_000, 7_500));
+        // No class limit: the class may use the whole block
+        assert_eq!(limits.max_operational, limits.max_block);
+
+        assert!(BlockLimits::decode(&[0u8; 3]).is_err());
+    }
+
+    #[test]
+    fn test_block_usage_from_dispatch_events() {
+        use subxt::dynamic::Value;
+
+        let event = |class: Option<&str>, ref_time: u128, proof_size: u128| {
+            let mut info = vec![(
+                "weight",
+                Value::named_composite([
+                    ("ref_time", Value::u128(ref_time)),
+                    ("proof_size", Value::u128(proof_size)),
+                ]),
+            )];
+            if let Some(class) = class {
+                info.push(("class", Value::unnamed_variant(class, [])));
+            }
+            subxt::ext::scale_value::Composite::named([(
+                "dispatch_info".to_string(),
+                Value::named_composite(info),
+            )])
+        };
+
+        let mut usage = BlockUsage::default();
+        assert!(usage.add_event_fields(&event(Some("Mandatory"), 100_000, 0)));
+        assert!(usage.add_event_fields(&event(Some("Normal"), 300_000, 1_000)));
+        assert!(usage.add_event_fields(&event(None, 75_000, 6_500)));
+        assert!(usage.add_event_fields(&event(Some("Operational"), 25_000, 0)));
+        assert!(!usage.add_event_fields(&subxt::ext::scale_value::Composite::<()>::Named(vec![])));
+
+        assert_eq!(usage.normal, Weight::new(375_000, 7_500));
+        assert_eq!(usage.total(), Weight::new(500_000, 7_500));
+
+        let limits = BlockLimits {
+            max_block: Weight::new(1_000_000, 10_000),
+            max_normal: Weight::new(750_000, 7_500),
+            max_operational: Weight::new(1_000_000, 10_000),
+        };
+        // Proof size runs out before ref time
+        assert_eq!(usage.fullness(&limits), 0.75);
+        let utilization = usage.utilization(&limits);
+        assert_eq!(utilization.normal, 1.0);
+        assert_eq!(utilization.operational, 0.025);
+    }
+
+    #[test]
+    fn test_congestion_window_updates_incrementally() {
+        let limits = BlockLimits {
+            max_block: Weight::new(1_000, u64::MAX),
+            max_normal: Weight::new(750, u64::MAX),
+            max_operational: Weight::new(1_000, u64::MAX),
+        };
+        let usage = |ref_time| BlockUsage {
+            normal: Weight::new(ref_time, 0),
+            ..Default::default()
+        };
+
+        let mut window = CongestionWindow::default();
+        assert!(window.congestion(&limits).is_none());
+        for number in 1..=CONGESTION_WINDOW as u64 {
+            window.push(number, usage(900), 1_000);
+        }
+        let congestion = window.congestion(&limits).unwrap();
+        assert_eq!(congestion.level, CongestionLevel::High);
+        assert_eq!(congestion.blocks_analyzed, CONGESTION_WINDOW as u32);
+
+        // New empty blocks push the full ones out one at a time
+        for number in 11..=15 {
+            window.push(number, usage(0), 3_000);
+        }
+        let congestion = window.congestion(&limits).unwrap();
+        assert_eq!(congestion.blocks_analyzed, CONGESTION_WINDOW as u32);
+        assert!((congestion.avg_block_fullness - 0.45).abs() < 1e-9);
+        assert_eq!(congestion.avg_fee, 2_000);
+
+        // A reorg to a different block 14 replaces blocks 14 and 15
+        window.push(14, usage(900), 1_000);
+        assert_eq!(window.blocks.len(), 9);
+        assert_eq!(window.blocks.back().unwrap().0, 14);
+    }
+
     #[test]
     fn test_congestion_multipliers() {
         let low = NetworkCongestion::new(0.3, 100_000, 10);
@@ -726,5 +1952,43 @@ mod tests {
         assert_eq!(estimate.length_fee, 50_000);
         assert_eq!(estimate.weight_fee, 200_000);
         assert_eq!(estimate.tip, 10_000);
+        assert_eq!(estimate.margin, 0);
+        assert_eq!(estimate.fee_multiplier, None);
+
+        let estimate = estimate
+            .with_margin(70_000)
+            .with_margin(35_000)
+            .with_fee_multiplier(1.25);
+        assert_eq!(estimate.total_fee, 395_000);
+        assert_eq!(estimate.inclusion_fee(), 350_000);
+        assert_eq!(estimate.fee_multiplier, Some(1.25));
+    }
+
+    #[test]
+    fn test_fee_details_decode() {
+        let details = FeeDetails {
+            inclusion_fee: Some(InclusionFee {
+                base_fee: 1_000,
+                len_fee: 200,
+                adjusted_weight_fee: 30,
+            }),
+            tip: 4,
+        };
+        let decoded = FeeDetails::decode(&mut &details.encode()[..]).unwrap();
+        assert_eq!(decoded, details);
+        assert_eq!(decoded.inclusion_fee.unwrap().total(), 1_230);
+        assert_eq!(decoded.final_fee(), 1_234);
+
+        // Unsigned extrinsics pay no inclusion fee
+        let unsigned = FeeDetails::decode(&mut &[0u8; 17][..]).unwrap();
+        assert_eq!(unsigned.inclusion_fee, None);
+        assert_eq!(unsigned.final_fee(), 0);
+    }
+
+    #[test]
+    fn test_transaction_payment_params() {
+        // The extrinsic is passed as is, not re-encoded with a length prefix
+        let params = transaction_payment_params(&[0x10, 0xaa, 0xbb, 0xcc, 0xdd]);
+        assert_eq!(params, [0x10, 0xaa, 0xbb, 0xcc, 0xdd, 5, 0, 0, 0]);
     }
 }
@@ -0,0 +1,669 @@
+//! OpenGov referenda and conviction voting
+//!
+//! [`GovernanceManager`] reads referenda from pallet-referenda, builds
+//! pallet-conviction-voting calls to vote and delegate, and decodes
+//! referendum outcomes from the events of a block or receipt.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::governance::{AccountVote, Conviction};
+//!
+//! # async fn example(adapter: &apex_sdk_substrate::SubstrateAdapter) -> apex_sdk_substrate::Result<()> {
+//! let governance = adapter.governance();
+//! for referendum in governance.active_referenda().await? {
+//!     println!("#{} on track {}: {:?}", referendum.index, referendum.track, referendum.phase);
+//! }
+//!
+//! let vote = governance.vote(
+//!     1_234,
+//!     AccountVote::Standard {
+//!         aye: true,
+//!         conviction: Conviction::Locked1x,
+//!         balance: 10_000_000_000,
+//!     },
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::assets::{decode_map_entry, missing_field, value_bytes};
+use crate::chain_client::StorageKey;
+use crate::storage::{extract_u128, StorageQuery};
+use crate::transaction::ReceiptEvent;
+use crate::{Error, Result, SubstrateAdapter};
+use apex_sdk_types::IntoAddress;
+use subxt::dynamic::{At as _, Value};
+use subxt::events::Events;
+use subxt::ext::scale_value::{Composite, ValueDef};
+use subxt::PolkadotConfig;
+use tracing::{debug, info};
+
+const REFERENDA_PALLET: &str = "Referenda";
+const CONVICTION_VOTING_PALLET: &str = "ConvictionVoting";
+
+/// Votes for and against a referendum, and its turnout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    /// Conviction-weighted aye votes
+    pub ayes: u128,
+    /// Conviction-weighted nay votes
+    pub nays: u128,
+    /// Aye and abstain votes without conviction, measured against the
+    /// track's support curve
+    pub support: u128,
+}
+
+impl Tally {
+    pub(crate) fn from_value<T>(value: &Value<T>) -> Result<Self> {
+        let number = |name: &str| extract_u128(value, &[name]).ok_or_else(|| missing_field(name));
+        Ok(Self {
+            ayes: number("ayes")?,
+            nays: number("nays")?,
+            support: number("support")?,
+        })
+    }
+
+    /// Share of conviction-weighted votes in favour, in parts per billion
+    pub fn approval(&self) -> u32 {
+        let total = self.ayes.saturating_add(self.nays);
+        if total == 0 {
+            return 0;
+        }
+        (self.ayes.saturating_mul(1_000_000_000) / total) as u32
+    }
+}
+
+/// Where an ongoing referendum is in its decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecisionPhase {
+    /// Waiting for the decision deposit, the prepare period or a free
+    /// decision slot on its track
+    Preparing,
+    /// Being decided since the given block
+    Deciding { since: u32 },
+    /// Passing, and approved at block `until` if it keeps passing
+    Confirming { since: u32, until: u32 },
+}
+
+/// An ongoing referendum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Referendum {
+    /// Referendum index
+    pub index: u32,
+    /// Track the referendum is decided on
+    pub track: u16,
+    /// Block the referendum was submitted in
+    pub submitted: u32,
+    /// Whether the decision deposit was placed
+    pub decision_deposit_placed: bool,
+    /// Decision phase
+    pub phase: DecisionPhase,
+    /// Current votes
+    pub tally: Tally,
+}
+
+impl Referendum {
+    pub(crate) fn from_value<T>(index: u32, status: &Value<T>) -> Result<Self> {
+        let block = |value: &Value<T>, name: &str| {
+            extract_u128(value, &[name])
+                .and_then(|n| u32::try_from(n).ok())
+                .ok_or_else(|| missing_field(name))
+        };
+
+        let phase = match status.at("deciding").and_then(some) {
+            None => DecisionPhase::Preparing,
+            Some(deciding) => {
+                let since = block(deciding, "since")?;
+                match deciding
+                    .at("confirming")
+                    .and_then(some)
+                    .and_then(|c| c.as_u128())
+                {
+                    Some(until) => DecisionPhase::Confirming {
+                        since,
+                        until: until as u32,
+                    },
+                    None => DecisionPhase::Deciding { since },
+                }
+            }
+        };
+
+        Ok(Self {
+            index,
+            track: extract_u128(status, &["track"])
+                .and_then(|t| u16::try_from(t).ok())
+                .ok_or_else(|| missing_field("track"))?,
+            submitted: block(status, "submitted")?,
+            decision_deposit_placed: status.at("decision_deposit").and_then(some).is_some(),
+            phase,
+            tally: Tally::from_value(status.at("tally").ok_or_else(|| missing_field("tally"))?)?,
+        })
+    }
+}
+
+/// How a referendum ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Verdict {
+    /// Passed and scheduled for enactment
+    Approved,
+    /// Failed to pass before its decision period ended
+    Rejected,
+    /// Cancelled by the cancel origin; deposits are refunded
+    Cancelled,
+    /// Never started deciding before the undeciding timeout
+    TimedOut,
+    /// Killed by the kill origin; deposits are slashed
+    Killed,
+}
+
+impl Verdict {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "Approved" => Verdict::Approved,
+            "Rejected" => Verdict::Rejected,
+            "Cancelled" => Verdict::Cancelled,
+            "TimedOut" => Verdict::TimedOut,
+            "Killed" => Verdict::Killed,
+            _ => return None,
+        })
+    }
+}
+
+/// A referendum, from `ReferendumInfoFor` storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReferendumInfo {
+    /// Still open
+    Ongoing(Referendum),
+    /// Ended at block `at`
+    Concluded { verdict: Verdict, at: u32 },
+}
+
+impl ReferendumInfo {
+    pub(crate) fn from_value<T>(index: u32, value: &Value<T>) -> Result<Self> {
+        let ValueDef::Variant(variant) = &value.value else {
+            return Err(Error::Storage(format!(
+                "Referendum {} is not a ReferendumInfo",
+                index
+            )));
+        };
+        let first = variant.values.values().next();
+
+        if variant.name == "Ongoing" {
+            let status = first.ok_or_else(|| missing_field("Ongoing"))?;
+            return Ok(ReferendumInfo::Ongoing(Referendum::from_value(
+                index, status,
+            )?));
+        }
+
+        let verdict = Verdict::from_name(&variant.name).ok_or_else(|| {
+            Error::Storage(format!(
+                "Unknown state {} of referendum {}",
+                variant.name, index
+            ))
+        })?;
+        Ok(ReferendumInfo::Concluded {
+            verdict,
+            at: first
+                .and_then(|at| at.as_u128())
+                .and_then(|at| u32::try_from(at).ok())
+                .ok_or_else(|| missing_field(&variant.name))?,
+        })
+    }
+}
+
+/// End of a referendum, from a `Referenda` event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferendumOutcome {
+    /// Referendum index
+    pub index: u32,
+    /// How it ended
+    pub verdict: Verdict,
+    /// Final votes; `Approved` events carry none, the `Confirmed` event
+    /// before them does
+    pub tally: Option<Tally>,
+}
+
+impl ReferendumOutcome {
+    /// Decode a `Referenda` event, `None` for events that do not end a
+    /// referendum
+    pub fn from_event<T>(pallet: &str, variant: &str, fields: &Composite<T>) -> Option<Self> {
+        if pallet != REFERENDA_PALLET {
+            return None;
+        }
+        let verdict = Verdict::from_name(variant)?;
+        Some(Self {
+            index: fields
+                .at("index")
+                .and_then(|i| i.as_u128())
+                .and_then(|i| u32::try_from(i).ok())?,
+            verdict,
+            tally: fields
+                .at("tally")
+                .and_then(|tally| Tally::from_value(tally).ok()),
+        })
+    }
+
+    /// Decode an event from a transaction receipt
+    pub fn from_receipt_event(event: &ReceiptEvent) -> Option<Self> {
+        Self::from_event(&event.pallet, &event.variant, &event.fields)
+    }
+
+    /// Every referendum ended in a block's events
+    pub fn from_events(events: &Events<PolkadotConfig>) -> Vec<Self> {
+        events
+            .iter()
+            .flatten()
+            .filter_map(|event| {
+                let fields = event.field_values().ok()?;
+                Self::from_event(event.pallet_name(), event.variant_name(), &fields)
+            })
+            .collect()
+    }
+}
+
+/// A referendum track, from the `Referenda::Tracks` constant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackInfo {
+    /// Track ID
+    pub id: u16,
+    /// Track name, e.g. `root` or `small_spender`
+    pub name: String,
+    /// Referenda that can be decided at once
+    pub max_deciding: u32,
+    /// Deposit required to start deciding
+    pub decision_deposit: u128,
+    /// Blocks before a referendum can start deciding
+    pub prepare_period: u32,
+    /// Blocks a referendum has to pass
+    pub decision_period: u32,
+    /// Blocks a referendum has to keep passing before approval
+    pub confirm_period: u32,
+}
+
+impl TrackInfo {
+    pub(crate) fn from_value<T>(id: u16, info: &Value<T>) -> Result<Self> {
+        let number = |name: &str| extract_u128(info, &[name]).ok_or_else(|| missing_field(name));
+        let block = |name: &str| number(name).map(|n| n as u32);
+        let name = info.at("name").ok_or_else(|| missing_field("name"))?;
+
+        Ok(Self {
+            id,
+            // Older runtimes use a string, newer ones a zero-padded byte array
+            name: match name.as_str() {
+                Some(name) => name.to_string(),
+                None => value_bytes(name)
+                    .map(|bytes| {
+                        String::from_utf8_lossy(&bytes)
+                            .trim_end_matches('\0')
+                            .to_string()
+                    })
+                    .ok_or_else(|| missing_field("name"))?,
+            },
+            max_deciding: block("max_deciding")?,
+            decision_deposit: number("decision_deposit")?,
+            prepare_period: block("prepare_period")?,
+            decision_period: block("decision_period")?,
+            confirm_period: block("confirm_period")?,
+        })
+    }
+}
+
+/// Conviction of a vote: how long the voted balance stays locked after the
+/// referendum, and how much the vote is multiplied by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Conviction {
+    /// 0.1x votes, not locked
+    #[default]
+    None,
+    /// 1x votes, locked for one enactment period
+    Locked1x,
+    /// 2x votes, locked for 2 periods
+    Locked2x,
+    /// 3x votes, locked for 4 periods
+    Locked3x,
+    /// 4x votes, locked for 8 periods
+    Locked4x,
+    /// 5x votes, locked for 16 periods
+    Locked5x,
+    /// 6x votes, locked for 32 periods
+    Locked6x,
+}
+
+impl Conviction {
+    fn name(self) -> &'static str {
+        match self {
+            Conviction::None => "None",
+            Conviction::Locked1x => "Locked1x",
+            Conviction::Locked2x => "Locked2x",
+            Conviction::Locked3x => "Locked3x",
+            Conviction::Locked4x => "Locked4x",
+            Conviction::Locked5x => "Locked5x",
+            Conviction::Locked6x => "Locked6x",
+        }
+    }
+}
+
+/// A vote on a referendum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountVote {
+    /// Aye or nay with the whole balance, at a conviction
+    Standard {
+        aye: bool,
+        conviction: Conviction,
+        balance: u128,
+    },
+    /// Balance split between aye and nay, without conviction
+    Split { aye: u128, nay: u128 },
+    /// Balance split between aye, nay and abstain, without conviction
+    SplitAbstain { aye: u128, nay: u128, abstain: u128 },
+}
+
+impl AccountVote {
+    fn to_value(self) -> Value {
+        match self {
+            AccountVote::Standard {
+                aye,
+                conviction,
+                balance,
+            } => {
+                // `Vote` packs the direction in the top bit and the conviction below it
+                let vote = (if aye { 0x80 } else { 0 }) | conviction as u8;
+                Value::named_variant(
+                    "Standard",
+                    [
+                        (
+                            "vote",
+                            Value::unnamed_composite([Value::u128(vote as u128)]),
+                        ),
+                        ("balance", Value::u128(balance)),
+                    ],
+                )
+            }
+            AccountVote::Split { aye, nay } => Value::named_variant(
+                "Split",
+                [("aye", Value::u128(aye)), ("nay", Value::u128(nay))],
+            ),
+            AccountVote::SplitAbstain { aye, nay, abstain } => Value::named_variant(
+                "SplitAbstain",
+                [
+                    ("aye", Value::u128(aye)),
+                    ("nay", Value::u128(nay)),
+                    ("abstain", Value::u128(abstain)),
+                ],
+            ),
+        }
+    }
+}
+
+/// High-level API for OpenGov: pallet-referenda queries and
+/// pallet-conviction-voting calls
+pub struct GovernanceManager<'a> {
+    adapter: &'a SubstrateAdapter,
+}
+
+impl<'a> GovernanceManager<'a> {
+    pub fn new(adapter: &'a SubstrateAdapter) -> Self {
+        Self { adapter }
+    }
+
+    /// A referendum by index, `None` if it does not exist
+    pub async fn referendum(&self, index: u32) -> Result<Option<ReferendumInfo>> {
+        let key = StorageKey::new(
+            REFERENDA_PALLET,
+            "ReferendumInfoFor",
+            vec![Value::u128(index as u128)],
+        );
+        self.adapter
+            .chain_client()
+            .storage(&key, None)
+            .await?
+            .map(|info| ReferendumInfo::from_value(index, &info.value))
+            .transpose()
+    }
+
+    /// Ongoing referenda, by index
+    pub async fn active_referenda(&self) -> Result<Vec<Referendum>> {
+        let chain = self.adapter.chain_client();
+        let metadata = chain.metadata();
+
+        let mut referenda = Vec::new();
+        for (key, value) in chain
+            .storage_entries(REFERENDA_PALLET, "ReferendumInfoFor")
+            .await?
+        {
+            let (key, value) = decode_map_entry(
+                &metadata,
+                REFERENDA_PALLET,
+                "ReferendumInfoFor",
+                &key,
+                &value,
+            )?;
+            let index = key
+                .as_u128()
+                .and_then(|i| u32::try_from(i).ok())
+                .ok_or_else(|| Error::Storage("Referendum index is not a u32".to_string()))?;
+            if let ReferendumInfo::Ongoing(referendum) = ReferendumInfo::from_value(index, &value)?
+            {
+                referenda.push(referendum);
+            }
+        }
+
+        referenda.sort_by_key(|r| r.index);
+        debug!("Found {} active referenda", referenda.len());
+        Ok(referenda)
+    }
+
+    /// Referendum tracks of the runtime
+    pub fn tracks(&self) -> Result<Vec<TrackInfo>> {
+        let tracks = self.adapter.constants().value(REFERENDA_PALLET, "Tracks")?;
+        let ValueDef::Composite(tracks) = &tracks.value else {
+            return Err(Error::Metadata(
+                "Referenda::Tracks is not a list".to_string(),
+            ));
+        };
+
+        tracks
+            .values()
+            .map(|track| {
+                // Either `(id, info)` tuples or `Track { id, info }`
+                let (id, info) = match (track.at("id"), track.at("info")) {
+                    (Some(id), Some(info)) => (id, info),
+                    _ => (
+                        track.at(0).ok_or_else(|| missing_field("id"))?,
+                        track.at(1).ok_or_else(|| missing_field("info"))?,
+                    ),
+                };
+                let id = id
+                    .as_u128()
+                    .and_then(|id| u16::try_from(id).ok())
+                    .ok_or_else(|| missing_field("id"))?;
+                TrackInfo::from_value(id, info)
+            })
+            .collect()
+    }
+
+    /// Vote on a referendum, replacing an earlier vote on it
+    pub fn vote(&self, index: u32, vote: AccountVote) -> subxt::tx::DynamicPayload {
+        info!("Preparing vote on referendum {}", index);
+        subxt::dynamic::tx(
+            CONVICTION_VOTING_PALLET,
+            "vote",
+            vec![Value::u128(index as u128), vote.to_value()],
+        )
+    }
+
+    /// Remove a vote, unlocking its balance once the conviction period has
+    /// passed
+    ///
+    /// `track` is only required when the referendum has ended.
+    pub fn remove_vote(&self, track: Option<u16>, index: u32) -> subxt::tx::DynamicPayload {
+        let track = match track {
+            Some(track) => Value::unnamed_variant("Some", [Value::u128(track as u128)]),
+            None => Value::unnamed_variant("None", []),
+        };
+        subxt::dynamic::tx(
+            CONVICTION_VOTING_PALLET,
+            "remove_vote",
+            vec![track, Value::u128(index as u128)],
+        )
+    }
+
+    /// Delegate voting power on a track to another account
+    pub fn delegate(
+        &self,
+        track: u16,
+        to: impl IntoAddress,
+        conviction: Conviction,
+        balance: u128,
+    ) -> Result<subxt::tx::DynamicPayload> {
+        info!("Preparing delegation on track {}", track);
+        Ok(subxt::dynamic::tx(
+            CONVICTION_VOTING_PALLET,
+            "delegate",
+            vec![
+                Value::u128(track as u128),
+                Value::unnamed_variant("Id", [StorageQuery::parse_address(to)?]),
+                Value::unnamed_variant(conviction.name(), []),
+                Value::u128(balance),
+            ],
+        ))
+    }
+
+    /// Stop delegating on a track
+    pub fn undelegate(&self, track: u16) -> subxt::tx::DynamicPayload {
+        subxt::dynamic::tx(
+            CONVICTION_VOTING_PALLET,
+            "undelegate",
+            vec![Value::u128(track as u128)],
+        )
+    }
+}
+
+/// Inner value of `Some`
+fn some<T>(value: &Value<T>) -> Option<&Value<T>> {
+    match &value.value {
+        ValueDef::Variant(variant) if variant.name == "Some" => variant.values.values().next(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(ayes: u128, nays: u128) -> Value {
+        Value::named_composite([
+            ("ayes", Value::u128(ayes)),
+            ("nays", Value::u128(nays)),
+            ("support", Value::u128(ayes)),
+        ])
+    }
+
+    fn ongoing(deciding: Value) -> Value {
+        Value::unnamed_variant(
+            "Ongoing",
+            [Value::named_composite([
+                ("track", Value::u128(33)),
+                ("submitted", Value::u128(1_000)),
+                (
+                    "decision_deposit",
+                    Value::unnamed_variant("Some", [Value::u128(1)]),
+                ),
+                ("deciding", deciding),
+                ("tally", tally(300, 100)),
+            ])],
+        )
+    }
+
+    #[test]
+    fn test_referendum_info_from_value() {
+        let preparing =
+            ReferendumInfo::from_value(7, &ongoing(Value::unnamed_variant("None", []))).unwrap();
+        let ReferendumInfo::Ongoing(referendum) = preparing else {
+            panic!("expected an ongoing referendum");
+        };
+        assert_eq!(referendum.track, 33);
+        assert!(referendum.decision_deposit_placed);
+        assert_eq!(referendum.phase, DecisionPhase::Preparing);
+        assert_eq!(referendum.tally.approval(), 750_000_000);
+
+        let confirming = ongoing(Value::unnamed_variant(
+            "Some",
+            [Value::named_composite([
+                ("since", Value::u128(1_100)),
+                (
+                    "confirming",
+                    Value::unnamed_variant("Some", [Value::u128(1_500)]),
+                ),
+            ])],
+        ));
+        match ReferendumInfo::from_value(7, &confirming).unwrap() {
+            ReferendumInfo::Ongoing(r) => assert_eq!(
+                r.phase,
+                DecisionPhase::Confirming {
+                    since: 1_100,
+                    until: 1_500
+                }
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let approved = Value::unnamed_variant(
+            "Approved",
+            [Value::u128(2_000), Value::unnamed_variant("None", [])],
+        );
+        assert_eq!(
+            ReferendumInfo::from_value(7, &approved).unwrap(),
+            ReferendumInfo::Concluded {
+                verdict: Verdict::Approved,
+                at: 2_000
+            }
+        );
+    }
+
+    #[test]
+    fn test_outcome_from_event() {
+        let fields = Composite::named([("index", Value::u128(12)), ("tally", tally(10, 90))]);
+        let outcome = ReferendumOutcome::from_event("Referenda", "Rejected", &fields).unwrap();
+        assert_eq!(outcome.index, 12);
+        assert_eq!(outcome.verdict, Verdict::Rejected);
+        assert_eq!(outcome.tally.unwrap().nays, 90);
+
+        let approved = Composite::named([("index", Value::u128(13))]);
+        let outcome = ReferendumOutcome::from_event("Referenda", "Approved", &approved).unwrap();
+        assert_eq!(outcome.tally, None);
+
+        assert!(ReferendumOutcome::from_event("Referenda", "Submitted", &approved).is_none());
+        assert!(
+            ReferendumOutcome::from_event("FellowshipReferenda", "Approved", &approved).is_none()
+        );
+    }
+
+    #[test]
+    fn test_vote_encoding() {
+        let vote = AccountVote::Standard {
+            aye: true,
+            conviction: Conviction::Locked3x,
+            balance: 5,
+        }
+        .to_value();
+        assert_eq!(
+            vote.at("vote")
+                .and_then(|v| v.at(0))
+                .and_then(|v| v.as_u128()),
+            Some(0x83)
+        );
+
+        let nay = AccountVote::Standard {
+            aye: false,
+            conviction: Conviction::None,
+            balance: 5,
+        }
+        .to_value();
+        assert_eq!(
+            nay.at("vote")
+                .and_then(|v| v.at(0))
+                .and_then(|v| v.as_u128()),
+            Some(0)
+        );
+    }
+}
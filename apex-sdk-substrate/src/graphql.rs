@@ -0,0 +1,1381 @@
+//! GraphQL queries over indexed chain data (`graphql` feature)
+//!
+//! [`IndexStore`] is an [`IndexSink`] that folds the blocks of an
+//! [`Indexer`](crate::Indexer) into accounts, transfers, fee payments and
+//! per-asset balance flows. [`GraphqlServer`] serves them over HTTP, so
+//! front ends can query SDK-collected data without running an indexer stack
+//! of their own.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::graphql::{GraphqlServer, IndexStore};
+//! use apex_sdk_substrate::SubstrateAdapter;
+//!
+//! # async fn example() -> apex_sdk_substrate::Result<()> {
+//! let adapter = SubstrateAdapter::connect("wss://westend-rpc.polkadot.io").await?;
+//! let store = IndexStore::new();
+//! GraphqlServer::new(store.clone(), 8080).start_background();
+//!
+//! let indexer = adapter.indexer();
+//! indexer.index_range(20_000_000..=20_000_100, &mut store.clone()).await?;
+//! indexer.follow_finalized(Some(20_000_101), &mut store.clone()).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Then `POST /graphql` with a query such as:
+//!
+//! ```graphql
+//! {
+//!   transfers(filter: { account: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY" }, first: 20) {
+//!     items { blockNumber from to asset amount }
+//!     nextCursor
+//!   }
+//! }
+//! ```
+//!
+//! Accounts are reported as `0x`-prefixed hex account ids; filters also
+//! accept SS58 addresses. Amounts are decimal strings in the asset's
+//! smallest unit. Balances are the flows seen in indexed transfers, not
+//! on-chain balances: the store only knows the blocks it was given.
+//!
+//! The store keeps every transfer and fee payment it is given; register it
+//! with a [`RetentionGc`](apex_sdk_core::retention::RetentionGc) to bound it.
+
+use crate::indexer::{IndexSink, IndexedBlock, IndexedEvent};
+use crate::{Error, Result};
+use apex_sdk_core::retention::{PurgeReport, RetentionError, RetentionPolicy, RetentionStore};
+use apex_sdk_types::{Cursor, Page, PageRequest, ValidationError};
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, InputObject, InputValueError, InputValueResult,
+    Object, OutputType, Scalar, ScalarType, Schema, SimpleObject,
+};
+use async_trait::async_trait;
+use axum::{extract::State, routing::get, Json, Router};
+use parking_lot::RwLock;
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use std::collections::{BTreeMap, VecDeque};
+use std::io::BufRead;
+use std::mem::size_of;
+use std::net::SocketAddr;
+use std::ops::Bound;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::net::TcpListener;
+use tracing::info;
+
+/// Asset name of the chain's native token in transfers and balances
+pub const NATIVE_ASSET: &str = "native";
+
+/// Deepest query nesting the schema accepts
+const MAX_QUERY_DEPTH: usize = 8;
+
+/// Balance in an asset's smallest unit
+///
+/// A GraphQL scalar serialized as a decimal string, since `Int` holds only
+/// 32 bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Amount(pub u128);
+
+#[Scalar]
+impl ScalarType for Amount {
+    fn parse(value: async_graphql::Value) -> InputValueResult<Self> {
+        match &value {
+            async_graphql::Value::String(s) => s
+                .parse()
+                .map(Amount)
+                .map_err(|_| InputValueError::expected_type(value)),
+            async_graphql::Value::Number(n) => n
+                .as_u64()
+                .map(|n| Amount(n as u128))
+                .ok_or_else(|| InputValueError::expected_type(value)),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> async_graphql::Value {
+        async_graphql::Value::String(self.0.to_string())
+    }
+}
+
+/// A transfer of the native token or of an asset
+#[derive(Debug, Clone, PartialEq, Eq, SimpleObject)]
+pub struct Transfer {
+    /// Block the transfer happened in
+    pub block_number: u64,
+    /// Block timestamp in milliseconds, if indexed
+    pub timestamp: Option<u64>,
+    /// Index of the transfer event in the block
+    pub event_index: u32,
+    /// Extrinsic that made the transfer, if any
+    pub extrinsic_index: Option<u32>,
+    /// [`NATIVE_ASSET`] or the asset id
+    pub asset: String,
+    /// Sending account
+    pub from: String,
+    /// Receiving account
+    pub to: String,
+    /// Amount transferred
+    pub amount: Amount,
+}
+
+/// A transaction fee charged to an account
+#[derive(Debug, Clone, PartialEq, Eq, SimpleObject)]
+pub struct FeePayment {
+    /// Block the fee was charged in
+    pub block_number: u64,
+    /// Block timestamp in milliseconds, if indexed
+    pub timestamp: Option<u64>,
+    /// Extrinsic the fee was paid for
+    pub extrinsic_index: Option<u32>,
+    /// Paying account
+    pub account: String,
+    /// Fee charged, including the tip
+    pub fee: Amount,
+    /// Tip included in the fee
+    pub tip: Amount,
+}
+
+/// Activity of an account in the indexed blocks
+#[derive(Debug, Clone, PartialEq, Eq, SimpleObject)]
+pub struct Account {
+    /// Account id
+    pub address: String,
+    /// Extrinsics signed by the account
+    pub extrinsics: u64,
+    /// Signed extrinsics that failed
+    pub failed_extrinsics: u64,
+    /// Transfers sent, of any asset
+    pub transfers_sent: u64,
+    /// Transfers received, of any asset
+    pub transfers_received: u64,
+    /// Total fees charged, including tips
+    pub fees_paid: Amount,
+    /// Total tips paid
+    pub tips_paid: Amount,
+    /// First block the account appears in
+    pub first_block: u64,
+    /// Last block the account appears in
+    pub last_block: u64,
+}
+
+/// Flow of one asset in and out of an account
+#[derive(Debug, Clone, PartialEq, Eq, SimpleObject)]
+pub struct AssetBalance {
+    /// Account id
+    pub account: String,
+    /// [`NATIVE_ASSET`] or the asset id
+    pub asset: String,
+    /// Total received in transfers
+    pub received: Amount,
+    /// Total sent in transfers
+    pub sent: Amount,
+    /// Received minus sent, as a signed decimal string
+    pub net: String,
+}
+
+/// One page of a list, with the cursor of the next page
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(concrete(name = "TransferPage", params(Transfer)))]
+#[graphql(concrete(name = "FeePage", params(FeePayment)))]
+#[graphql(concrete(name = "AccountPage", params(Account)))]
+#[graphql(concrete(name = "BalancePage", params(AssetBalance)))]
+pub struct GraphPage<T: OutputType> {
+    /// Items of this page
+    pub items: Vec<T>,
+    /// Pass as `after` for the next page; `null` on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T: OutputType> From<Page<T>> for GraphPage<T> {
+    fn from(page: Page<T>) -> Self {
+        Self {
+            items: page.items,
+            next_cursor: page.next_cursor.map(|cursor| cursor.to_string()),
+        }
+    }
+}
+
+/// Transfers to return; unset fields match everything
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct TransferFilter {
+    /// Sender or receiver
+    pub account: Option<String>,
+    /// Sender
+    pub from: Option<String>,
+    /// Receiver
+    pub to: Option<String>,
+    /// [`NATIVE_ASSET`] or an asset id
+    pub asset: Option<String>,
+    /// First block, inclusive
+    pub from_block: Option<u64>,
+    /// Last block, inclusive
+    pub to_block: Option<u64>,
+}
+
+/// Fee payments to return; unset fields match everything
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct FeeFilter {
+    /// Paying account
+    pub account: Option<String>,
+    /// First block, inclusive
+    pub from_block: Option<u64>,
+    /// Last block, inclusive
+    pub to_block: Option<u64>,
+}
+
+/// Balances to return; unset fields match everything
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct BalanceFilter {
+    /// Account
+    pub account: Option<String>,
+    /// [`NATIVE_ASSET`] or an asset id
+    pub asset: Option<String>,
+}
+
+/// Accounts, transfers, fees and balance flows of indexed blocks
+///
+/// Clones share their data, so one clone can be written by the indexer
+/// while another is queried. Blocks must arrive in ascending order, as the
+/// indexer writes them; a block at or below the last one stored is
+/// ignored, so resuming an indexer does not count a block twice.
+///
+/// As a [`RetentionStore`], the store drops its oldest transfers and fee
+/// payments. Account activity and balance flows are running totals and keep
+/// counting purged records.
+#[derive(Clone, Default)]
+pub struct IndexStore {
+    state: Arc<RwLock<StoreState>>,
+}
+
+#[derive(Default)]
+struct StoreState {
+    transfers: Records<Transfer>,
+    fees: Records<FeePayment>,
+    accounts: BTreeMap<String, Account>,
+    balances: BTreeMap<(String, String), (u128, u128)>,
+    last_block: Option<u64>,
+}
+
+/// Transfers or fee payments, oldest first
+///
+/// Every record keeps the sequence number it was stored with, which page
+/// cursors hold, so cursors stay valid while old records are purged.
+struct Records<T> {
+    items: VecDeque<T>,
+    /// Records purged so far, and so the sequence number of the oldest one
+    purged: u64,
+}
+
+impl<T> Default for Records<T> {
+    fn default() -> Self {
+        Self {
+            items: VecDeque::new(),
+            purged: 0,
+        }
+    }
+}
+
+/// A record kept in [`Records`]
+trait Record: Clone {
+    fn block_number(&self) -> u64;
+
+    fn timestamp(&self) -> Option<u64>;
+
+    /// Approximate memory held by the record, in bytes
+    fn size(&self) -> u64;
+}
+
+impl Record for Transfer {
+    fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+
+    fn size(&self) -> u64 {
+        (size_of::<Self>() + self.asset.len() + self.from.len() + self.to.len()) as u64
+    }
+}
+
+impl Record for FeePayment {
+    fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+
+    fn size(&self) -> u64 {
+        (size_of::<Self>() + self.account.len()) as u64
+    }
+}
+
+impl<T: Record> Records<T> {
+    fn push(&mut self, record: T) {
+        self.items.push_back(record);
+    }
+
+    /// Records matching `matches` within blocks `from..=to`, continuing at
+    /// the request's cursor
+    ///
+    /// Records are in block order, so the block range is found by binary
+    /// search; only the returned records are cloned.
+    fn page(
+        &self,
+        request: &PageRequest,
+        from: Option<u64>,
+        to: Option<u64>,
+        matches: impl Fn(&T) -> bool,
+    ) -> std::result::Result<Page<T>, ValidationError> {
+        let end = self.purged + self.items.len() as u64;
+        let mut start = match &request.cursor {
+            Some(cursor) => {
+                let sequence = cursor.offset()?;
+                if sequence > end {
+                    return Err(ValidationError::InvalidCursor(cursor.to_string()));
+                }
+                // Records purged since the cursor was issued are skipped
+                sequence.saturating_sub(self.purged) as usize
+            }
+            None => 0,
+        };
+        if let Some(from) = from {
+            start = start.max(self.items.partition_point(|r| r.block_number() < from));
+        }
+
+        let sequence = self.purged + start as u64;
+        let records = self
+            .items
+            .range(start..)
+            .zip(sequence..)
+            .take_while(|(record, _)| to.is_none_or(|to| record.block_number() <= to))
+            .filter(|(record, _)| matches(record))
+            .map(|(record, sequence)| (sequence, record.clone()));
+        Ok(take_page(records, request, |sequence| {
+            Cursor::from_offset(sequence + 1)
+        }))
+    }
+
+    /// Remove the oldest record, returning its size
+    fn pop_oldest(&mut self) -> Option<u64> {
+        let record = self.items.pop_front()?;
+        self.purged += 1;
+        Some(record.size())
+    }
+
+    /// Remove records with a timestamp before `cutoff_ms`, returning how
+    /// many and their size
+    fn purge_before(&mut self, cutoff_ms: u64) -> (u64, u64) {
+        let (mut count, mut size) = (0, 0);
+        while self
+            .items
+            .front()
+            .and_then(Record::timestamp)
+            .is_some_and(|timestamp| timestamp < cutoff_ms)
+        {
+            size += self.pop_oldest().unwrap_or(0);
+            count += 1;
+        }
+        (count, size)
+    }
+
+    fn size(&self) -> u64 {
+        self.items.iter().map(Record::size).sum()
+    }
+}
+
+impl IndexStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild a store from a file written by
+    /// [`JsonLinesSink`](crate::indexer::JsonLinesSink)
+    pub fn load_json_lines(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .map_err(|e| Error::Storage(format!("Failed to open {}: {}", path.display(), e)))?;
+
+        let store = Self::new();
+        for (number, line) in std::io::BufReader::new(file).lines().enumerate() {
+            let line = line
+                .map_err(|e| Error::Storage(format!("Failed to read {}: {}", path.display(), e)))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let block: IndexedBlock = serde_json::from_str(&line).map_err(|e| {
+                Error::Storage(format!(
+                    "Invalid block on line {} of {}: {}",
+                    number + 1,
+                    path.display(),
+                    e
+                ))
+            })?;
+            store.insert(&block);
+        }
+        Ok(store)
+    }
+
+    /// Add a block's transfers, fees and account activity
+    pub fn insert(&self, block: &IndexedBlock) {
+        let mut state = self.state.write();
+        if state.last_block.is_some_and(|last| block.number <= last) {
+            return;
+        }
+        state.last_block = Some(block.number);
+
+        for extrinsic in &block.extrinsics {
+            let Some(signer) = extrinsic.signer.as_deref().map(normalize_account) else {
+                continue;
+            };
+            let account = state.touch(&signer, block.number);
+            account.extrinsics += 1;
+            if !extrinsic.success {
+                account.failed_extrinsics += 1;
+            }
+        }
+
+        for event in &block.events {
+            if let Some(transfer) = transfer(block, event) {
+                state.record_transfer(transfer);
+            } else if let Some(fee) = fee_payment(block, event) {
+                let account = state.touch(&fee.account, block.number);
+                account.fees_paid.0 = account.fees_paid.0.saturating_add(fee.fee.0);
+                account.tips_paid.0 = account.tips_paid.0.saturating_add(fee.tip.0);
+                state.fees.push(fee);
+            }
+        }
+    }
+
+    /// Number of the last block stored
+    pub fn last_block(&self) -> Option<u64> {
+        self.state.read().last_block
+    }
+
+    /// Activity of one account, SS58 or hex, if it appears in the store
+    pub fn account(&self, address: &str) -> std::result::Result<Option<Account>, ValidationError> {
+        let address = parse_account(address)?;
+        Ok(self.state.read().accounts.get(&address).cloned())
+    }
+
+    /// Accounts in account id order
+    pub fn accounts(
+        &self,
+        request: &PageRequest,
+    ) -> std::result::Result<Page<Account>, ValidationError> {
+        let after = match &request.cursor {
+            Some(cursor) => Bound::Excluded(
+                String::from_utf8(cursor.as_bytes().to_vec())
+                    .map_err(|_| ValidationError::InvalidCursor(cursor.to_string()))?,
+            ),
+            None => Bound::Unbounded,
+        };
+
+        let state = self.state.read();
+        let accounts = state
+            .accounts
+            .range((after, Bound::Unbounded))
+            .map(|(address, account)| (address, account.clone()));
+        Ok(take_page(accounts, request, |address| {
+            Cursor::from_bytes(address.as_bytes())
+        }))
+    }
+
+    /// Transfers matching `filter`, oldest first
+    pub fn transfers(
+        &self,
+        filter: &TransferFilter,
+        request: &PageRequest,
+    ) -> std::result::Result<Page<Transfer>, ValidationError> {
+        let account = filter.account.as_deref().map(parse_account).transpose()?;
+        let from = filter.from.as_deref().map(parse_account).transpose()?;
+        let to = filter.to.as_deref().map(parse_account).transpose()?;
+
+        self.state
+            .read()
+            .transfers
+            .page(request, filter.from_block, filter.to_block, |t| {
+                account.as_ref().is_none_or(|a| *a == t.from || *a == t.to)
+                    && from.as_ref().is_none_or(|a| *a == t.from)
+                    && to.as_ref().is_none_or(|a| *a == t.to)
+                    && filter.asset.as_ref().is_none_or(|asset| *asset == t.asset)
+            })
+    }
+
+    /// Fee payments matching `filter`, oldest first
+    pub fn fees(
+        &self,
+        filter: &FeeFilter,
+        request: &PageRequest,
+    ) -> std::result::Result<Page<FeePayment>, ValidationError> {
+        let account = filter.account.as_deref().map(parse_account).transpose()?;
+
+        self.state
+            .read()
+            .fees
+            .page(request, filter.from_block, filter.to_block, |fee| {
+                account.as_ref().is_none_or(|a| *a == fee.account)
+            })
+    }
+
+    /// Balance flows matching `filter`, by account then asset
+    pub fn balances(
+        &self,
+        filter: &BalanceFilter,
+        request: &PageRequest,
+    ) -> std::result::Result<Page<AssetBalance>, ValidationError> {
+        let account = filter.account.as_deref().map(parse_account).transpose()?;
+        let after = match &request.cursor {
+            Some(cursor) => Bound::Excluded(
+                serde_json::from_slice::<(String, String)>(cursor.as_bytes())
+                    .map_err(|_| ValidationError::InvalidCursor(cursor.to_string()))?,
+            ),
+            None => Bound::Unbounded,
+        };
+
+        let state = self.state.read();
+        let balances = state
+            .balances
+            .range((after, Bound::Unbounded))
+            .filter(|((holder, asset), _)| {
+                account.as_ref().is_none_or(|a| a == holder)
+                    && filter.asset.as_ref().is_none_or(|a| a == asset)
+            })
+            .map(|(key, (received, sent))| {
+                let balance = AssetBalance {
+                    account: key.0.clone(),
+                    asset: key.1.clone(),
+                    received: Amount(*received),
+                    sent: Amount(*sent),
+                    net: if received >= sent {
+                        (received - sent).to_string()
+                    } else {
+                        format!("-{}", sent - received)
+                    },
+                };
+                (key, balance)
+            });
+        Ok(take_page(balances, request, |key| {
+            Cursor::from_bytes(serde_json::to_vec(key).unwrap_or_default())
+        }))
+    }
+
+    /// GraphQL schema querying this store
+    pub fn schema(&self) -> IndexSchema {
+        Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .data(self.clone())
+            .limit_depth(MAX_QUERY_DEPTH)
+            .finish()
+    }
+}
+
+impl StoreState {
+    fn touch(&mut self, address: &str, block: u64) -> &mut Account {
+        let account = self
+            .accounts
+            .entry(address.to_string())
+            .or_insert_with(|| Account {
+                address: address.to_string(),
+                extrinsics: 0,
+                failed_extrinsics: 0,
+                transfers_sent: 0,
+                transfers_received: 0,
+                fees_paid: Amount::default(),
+                tips_paid: Amount::default(),
+                first_block: block,
+                last_block: block,
+            });
+        account.last_block = block;
+        account
+    }
+
+    fn record_transfer(&mut self, transfer: Transfer) {
+        let amount = transfer.amount.0;
+        self.touch(&transfer.from, transfer.block_number)
+            .transfers_sent += 1;
+        self.touch(&transfer.to, transfer.block_number)
+            .transfers_received += 1;
+
+        let sent = self
+            .balances
+            .entry((transfer.from.clone(), transfer.asset.clone()))
+            .or_default();
+        sent.1 = sent.1.saturating_add(amount);
+        let received = self
+            .balances
+            .entry((transfer.to.clone(), transfer.asset.clone()))
+            .or_default();
+        received.0 = received.0.saturating_add(amount);
+
+        self.transfers.push(transfer);
+    }
+
+    /// Approximate memory held by the store, in bytes
+    fn size(&self) -> u64 {
+        let accounts: usize = self
+            .accounts
+            .keys()
+            .map(|address| size_of::<Account>() + 2 * address.len())
+            .sum();
+        let balances: usize = self
+            .balances
+            .keys()
+            .map(|(account, asset)| {
+                size_of::<((String, String), (u128, u128))>() + account.len() + asset.len()
+            })
+            .sum();
+        self.transfers.size() + self.fees.size() + (accounts + balances) as u64
+    }
+}
+
+/// Purged transfers and fee payments are no longer listed. Records are
+/// purged oldest first, so the max age stops at the first record whose block
+/// has no timestamp; the size limit, which applies to the approximate memory
+/// held by the store, still purges it.
+#[async_trait]
+impl RetentionStore for IndexStore {
+    fn name(&self) -> &str {
+        "graphql_index"
+    }
+
+    async fn purge(
+        &self,
+        policy: &RetentionPolicy,
+    ) -> std::result::Result<PurgeReport, RetentionError> {
+        let mut state = self.state.write();
+        let mut size = state.size();
+        let mut purged = 0;
+
+        if let Some(cutoff) = policy.cutoff(SystemTime::now()) {
+            let cutoff_ms = cutoff.saturating_mul(1000);
+            for (count, bytes) in [
+                state.transfers.purge_before(cutoff_ms),
+                state.fees.purge_before(cutoff_ms),
+            ] {
+                purged += count;
+                size = size.saturating_sub(bytes);
+            }
+        }
+        while policy.exceeds_size(size) {
+            // Oldest first, across transfers and fee payments
+            let transfer = state.transfers.items.front().map(Record::block_number);
+            let fee = state.fees.items.front().map(Record::block_number);
+            let popped = match (transfer, fee) {
+                (Some(transfer), Some(fee)) if fee < transfer => state.fees.pop_oldest(),
+                (Some(_), _) => state.transfers.pop_oldest(),
+                (None, Some(_)) => state.fees.pop_oldest(),
+                (None, None) => break,
+            };
+            purged += 1;
+            size = size.saturating_sub(popped.unwrap_or(0));
+        }
+
+        Ok(PurgeReport {
+            purged,
+            size_bytes: size,
+        })
+    }
+}
+
+#[async_trait]
+impl IndexSink for IndexStore {
+    async fn write(&mut self, block: &IndexedBlock) -> Result<()> {
+        self.insert(block);
+        Ok(())
+    }
+}
+
+/// `Balances::Transfer` or `Assets::Transferred` event as a transfer
+fn transfer(block: &IndexedBlock, event: &IndexedEvent) -> Option<Transfer> {
+    let asset = if event.is("Balances", "Transfer") {
+        NATIVE_ASSET.to_string()
+    } else if event.is("Assets", "Transferred") {
+        match event.fields.get("asset_id")? {
+            serde_json::Value::String(id) => id.clone(),
+            id => id.to_string(),
+        }
+    } else {
+        return None;
+    };
+
+    Some(Transfer {
+        block_number: block.number,
+        timestamp: block.timestamp,
+        event_index: event.index,
+        extrinsic_index: event.extrinsic_index,
+        asset,
+        from: json_account(event.fields.get("from")?)?,
+        to: json_account(event.fields.get("to")?)?,
+        amount: Amount(json_amount(event.fields.get("amount")?)?),
+    })
+}
+
+/// `TransactionPayment::TransactionFeePaid` event as a fee payment
+fn fee_payment(block: &IndexedBlock, event: &IndexedEvent) -> Option<FeePayment> {
+    if !event.is("TransactionPayment", "TransactionFeePaid") {
+        return None;
+    }
+    Some(FeePayment {
+        block_number: block.number,
+        timestamp: block.timestamp,
+        extrinsic_index: event.extrinsic_index,
+        account: json_account(event.fields.get("who")?)?,
+        fee: Amount(json_amount(event.fields.get("actual_fee")?)?),
+        tip: Amount(
+            event
+                .fields
+                .get("tip")
+                .and_then(json_amount)
+                .unwrap_or_default(),
+        ),
+    })
+}
+
+fn json_account(value: &serde_json::Value) -> Option<String> {
+    value.as_str().map(normalize_account)
+}
+
+/// Amount from an indexed number, or a decimal string beyond 64 bits
+fn json_amount(value: &serde_json::Value) -> Option<u128> {
+    match value {
+        serde_json::Value::Number(n) => n.as_u64().map(u128::from),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Lowercase hex account id; an indexed signer's `MultiAddress::Id`
+/// prefix byte is dropped so it matches account ids in events
+fn normalize_account(account: &str) -> String {
+    let account = account.to_ascii_lowercase();
+    match account.strip_prefix("0x00") {
+        Some(id) if id.len() == 64 => format!("0x{}", id),
+        _ => account,
+    }
+}
+
+/// Account id of an SS58 address or a hex account id
+fn parse_account(address: &str) -> std::result::Result<String, ValidationError> {
+    if let Some(digits) = address.strip_prefix("0x") {
+        let bytes = hex::decode(digits)
+            .ok()
+            .filter(|bytes| bytes.len() == 32 || (bytes.len() == 33 && bytes[0] == 0))
+            .ok_or_else(|| ValidationError::UnrecognizedAddress(address.to_string()))?;
+        return Ok(normalize_account(&format!("0x{}", hex::encode(bytes))));
+    }
+    AccountId32::from_ss58check(address)
+        .map(|id| format!("0x{}", hex::encode(<[u8; 32]>::from(id))))
+        .map_err(|_| ValidationError::InvalidSubstrateAddress(address.to_string()))
+}
+
+/// Page of the first `limit` items, with a cursor made from the key of the
+/// last one if more follow
+fn take_page<K, T>(
+    items: impl Iterator<Item = (K, T)>,
+    request: &PageRequest,
+    cursor: impl FnOnce(K) -> Cursor,
+) -> Page<T> {
+    let limit = request.effective_limit() as usize;
+    let mut items = items.peekable();
+    let mut page = Vec::with_capacity(limit.min(PageRequest::DEFAULT_LIMIT as usize));
+    while let Some((key, item)) = items.next() {
+        page.push(item);
+        if page.len() == limit {
+            let more = items.peek().is_some();
+            return Page::new(page, more.then(|| cursor(key)));
+        }
+    }
+    Page::last(page)
+}
+
+/// Page request from GraphQL `first` and `after` arguments
+fn page_request(first: Option<u32>, after: Option<String>) -> async_graphql::Result<PageRequest> {
+    let request = PageRequest::new(first.unwrap_or(PageRequest::DEFAULT_LIMIT));
+    Ok(match after {
+        Some(cursor) => request.with_cursor(cursor.parse()?),
+        None => request,
+    })
+}
+
+/// Schema served by [`GraphqlServer`]
+pub type IndexSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Root of GraphQL queries over an [`IndexStore`]
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Number of the last indexed block
+    async fn last_block(&self, ctx: &Context<'_>) -> Option<u64> {
+        ctx.data_unchecked::<IndexStore>().last_block()
+    }
+
+    /// Activity of one account, by SS58 address or hex account id
+    async fn account(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+    ) -> async_graphql::Result<Option<Account>> {
+        Ok(ctx.data_unchecked::<IndexStore>().account(&address)?)
+    }
+
+    /// Accounts seen in indexed blocks, in account id order
+    async fn accounts(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<GraphPage<Account>> {
+        let request = page_request(first, after)?;
+        Ok(ctx
+            .data_unchecked::<IndexStore>()
+            .accounts(&request)?
+            .into())
+    }
+
+    /// Native token and asset transfers, oldest first
+    async fn transfers(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TransferFilter>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<GraphPage<Transfer>> {
+        let request = page_request(first, after)?;
+        let filter = filter.unwrap_or_default();
+        Ok(ctx
+            .data_unchecked::<IndexStore>()
+            .transfers(&filter, &request)?
+            .into())
+    }
+
+    /// Transaction fees charged, oldest first
+    async fn fees(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<FeeFilter>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<GraphPage<FeePayment>> {
+        let request = page_request(first, after)?;
+        let filter = filter.unwrap_or_default();
+        Ok(ctx
+            .data_unchecked::<IndexStore>()
+            .fees(&filter, &request)?
+            .into())
+    }
+
+    /// Per-asset transfer flows of accounts
+    async fn balances(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<BalanceFilter>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<GraphPage<AssetBalance>> {
+        let request = page_request(first, after)?;
+        let filter = filter.unwrap_or_default();
+        Ok(ctx
+            .data_unchecked::<IndexStore>()
+            .balances(&filter, &request)?
+            .into())
+    }
+}
+
+/// HTTP server for GraphQL queries over an [`IndexStore`]
+///
+/// Serves `POST /graphql` with a JSON `{"query", "variables",
+/// "operationName"}` body, and the schema in SDL at `GET /graphql`.
+pub struct GraphqlServer {
+    schema: IndexSchema,
+    port: u16,
+}
+
+impl GraphqlServer {
+    /// Create a server for `store` on `port`
+    pub fn new(store: IndexStore, port: u16) -> Self {
+        Self {
+            schema: store.schema(),
+            port,
+        }
+    }
+
+    /// Router with the GraphQL routes, to mount in an existing server
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/graphql", get(sdl_handler).post(graphql_handler))
+            .with_state(self.schema.clone())
+    }
+
+    /// Start the server
+    pub async fn start(self) -> Result<()> {
+        let addr = SocketAddr::from(([0, 0, 0, 0], self.port));
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to bind {}: {}", addr, e)))?;
+
+        info!("GraphQL server listening on http://{}/graphql", addr);
+        axum::serve(listener, self.router())
+            .await
+            .map_err(|e| Error::Connection(format!("GraphQL server failed: {}", e)))
+    }
+
+    /// Start the server in the background
+    pub fn start_background(self) -> tokio::task::JoinHandle<Result<()>> {
+        tokio::spawn(async move { self.start().await })
+    }
+}
+
+async fn graphql_handler(
+    State(schema): State<IndexSchema>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request).await)
+}
+
+async fn sdl_handler(State(schema): State<IndexSchema>) -> String {
+    schema.sdl()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::IndexedExtrinsic;
+    use serde_json::json;
+
+    const ALICE: &str = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+    const BOB: &str = "0x8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48";
+
+    fn event(index: u32, pallet: &str, name: &str, fields: serde_json::Value) -> IndexedEvent {
+        IndexedEvent {
+            index,
+            extrinsic_index: Some(1),
+            pallet: pallet.to_string(),
+            event: name.to_string(),
+            fields,
+        }
+    }
+
+    fn block(number: u64, events: Vec<IndexedEvent>) -> IndexedBlock {
+        IndexedBlock {
+            number,
+            hash: format!("0x{:064x}", number),
+            parent_hash: format!("0x{:064x}", number - 1),
+            timestamp: Some(1_700_000_000_000 + number * 6_000),
+            extrinsics: vec![IndexedExtrinsic {
+                index: 1,
+                hash: format!("0x{:064x}", number + 1_000),
+                signer: Some(format!("0x00{}", &ALICE[2..])),
+                pallet: "Balances".to_string(),
+                call: "transfer_keep_alive".to_string(),
+                args: json!({}),
+                success: true,
+            }],
+            events,
+        }
+    }
+
+    fn store() -> IndexStore {
+        let store = IndexStore::new();
+        store.insert(&block(
+            10,
+            vec![
+                event(
+                    0,
+                    "Balances",
+                    "Transfer",
+                    json!({ "from": ALICE, "to": BOB, "amount": 1_000 }),
+                ),
+                event(
+                    1,
+                    "TransactionPayment",
+                    "TransactionFeePaid",
+                    json!({ "who": ALICE, "actual_fee": 150, "tip": 50 }),
+                ),
+            ],
+        ));
+        store.insert(&block(
+            11,
+            vec![event(
+                0,
+                "Assets",
+                "Transferred",
+                json!({
+                    "asset_id": 1984,
+                    "from": BOB,
+                    "to": ALICE,
+                    "amount": "340282366920938463463374607431768211455",
+                }),
+            )],
+        ));
+        store
+    }
+
+    #[test]
+    fn test_store_folds_indexed_blocks() {
+        let store = store();
+        assert_eq!(store.last_block(), Some(11));
+
+        // Re-indexed blocks are not counted twice
+        store.insert(&block(10, vec![]));
+        let alice = store.account(ALICE).unwrap().unwrap();
+        assert_eq!(alice.extrinsics, 2);
+        assert_eq!(alice.transfers_sent, 1);
+        assert_eq!(alice.transfers_received, 1);
+        assert_eq!(alice.fees_paid, Amount(150));
+        assert_eq!(alice.tips_paid, Amount(50));
+        assert_eq!((alice.first_block, alice.last_block), (10, 11));
+
+        // SS58 and hex addresses name the same account
+        let by_ss58 = store
+            .account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .unwrap();
+        assert_eq!(by_ss58, Some(alice));
+        assert!(store.account("not an address").is_err());
+
+        let filter = TransferFilter {
+            asset: Some("1984".to_string()),
+            ..Default::default()
+        };
+        let transfers = store.transfers(&filter, &PageRequest::default()).unwrap();
+        assert_eq!(transfers.items.len(), 1);
+        assert_eq!(transfers.items[0].amount, Amount(u128::MAX));
+
+        let filter = BalanceFilter {
+            account: Some(BOB.to_string()),
+            asset: Some(NATIVE_ASSET.to_string()),
+        };
+        let balances = store.balances(&filter, &PageRequest::default()).unwrap();
+        assert_eq!(balances.items[0].received, Amount(1_000));
+        assert_eq!(balances.items[0].net, "1000");
+
+        let first = store
+            .transfers(&TransferFilter::default(), &PageRequest::new(1))
+            .unwrap();
+        assert_eq!(first.items[0].block_number, 10);
+        let second = store
+            .transfers(&TransferFilter::default(), &first.next_request(1).unwrap())
+            .unwrap();
+        assert_eq!(second.items[0].block_number, 11);
+        assert!(!second.has_more());
+    }
+
+    /// Blocks 20..30, each with a native transfer from Alice to Bob of the
+    /// block number, and an asset transfer back in even blocks
+    fn transfers_store() -> IndexStore {
+        let store = IndexStore::new();
+        for number in 20..30u64 {
+            let mut events = vec![event(
+                0,
+                "Balances",
+                "Transfer",
+                json!({ "from": ALICE, "to": BOB, "amount": number }),
+            )];
+            if number % 2 == 0 {
+                events.push(event(
+                    1,
+                    "Assets",
+                    "Transferred",
+                    json!({ "asset_id": "1984", "from": BOB, "to": ALICE, "amount": 1 }),
+                ));
+            }
+            store.insert(&block(number, events));
+        }
+        store
+    }
+
+    fn blocks(page: &Page<Transfer>) -> Vec<u64> {
+        page.items.iter().map(|t| t.block_number).collect()
+    }
+
+    #[test]
+    fn test_transfer_filters() {
+        let store = transfers_store();
+        let all = PageRequest::default();
+        let query = |filter: TransferFilter| store.transfers(&filter, &all).unwrap();
+
+        assert_eq!(query(TransferFilter::default()).len(), 15);
+
+        let from_bob = query(TransferFilter {
+            from: Some(BOB.to_string()),
+            ..Default::default()
+        });
+        assert_eq!(blocks(&from_bob), vec![20, 22, 24, 26, 28]);
+        assert!(from_bob.items.iter().all(|t| t.asset == "1984"));
+
+        let to_bob = query(TransferFilter {
+            to: Some(BOB.to_string()),
+            ..Default::default()
+        });
+        assert_eq!(to_bob.len(), 10);
+        assert!(to_bob.items.iter().all(|t| t.asset == NATIVE_ASSET));
+
+        // Either side, by SS58 address
+        let alice = query(TransferFilter {
+            account: Some("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(alice.len(), 15);
+
+        let native = query(TransferFilter {
+            asset: Some(NATIVE_ASSET.to_string()),
+            ..Default::default()
+        });
+        assert_eq!(native.len(), 10);
+        let unknown = query(TransferFilter {
+            asset: Some("42".to_string()),
+            ..Default::default()
+        });
+        assert!(unknown.is_empty());
+
+        // Block bounds are inclusive
+        let range = query(TransferFilter {
+            asset: Some(NATIVE_ASSET.to_string()),
+            from_block: Some(23),
+            to_block: Some(25),
+            ..Default::default()
+        });
+        assert_eq!(blocks(&range), vec![23, 24, 25]);
+        let after_last = query(TransferFilter {
+            from_block: Some(30),
+            ..Default::default()
+        });
+        assert!(after_last.is_empty() && !after_last.has_more());
+        let empty_range = query(TransferFilter {
+            from_block: Some(25),
+            to_block: Some(24),
+            ..Default::default()
+        });
+        assert!(empty_range.is_empty());
+
+        let combined = query(TransferFilter {
+            from: Some(BOB.to_string()),
+            to: Some(ALICE.to_string()),
+            from_block: Some(25),
+            ..Default::default()
+        });
+        assert_eq!(blocks(&combined), vec![26, 28]);
+    }
+
+    #[test]
+    fn test_transfer_cursor_edges() {
+        let store = transfers_store();
+        let filter = TransferFilter {
+            from: Some(BOB.to_string()),
+            ..Default::default()
+        };
+
+        // Pages of a filtered list continue after the last match
+        let mut pages = Vec::new();
+        let mut request = PageRequest::new(2);
+        loop {
+            let page = store.transfers(&filter, &request).unwrap();
+            pages.push(blocks(&page));
+            match page.next_request(2) {
+                Some(next) => request = next,
+                None => break,
+            }
+        }
+        assert_eq!(pages, vec![vec![20, 22], vec![24, 26], vec![28]]);
+
+        // A page that ends with the last match has no next cursor
+        let exact = store.transfers(&filter, &PageRequest::new(5)).unwrap();
+        assert_eq!(exact.len(), 5);
+        assert!(!exact.has_more());
+
+        // Cursors past the end or from another list are rejected
+        let past_end = PageRequest::new(2).with_cursor(Cursor::from_offset(16));
+        assert!(matches!(
+            store.transfers(&filter, &past_end),
+            Err(ValidationError::InvalidCursor(_))
+        ));
+        let at_end = PageRequest::new(2).with_cursor(Cursor::from_offset(15));
+        assert!(store.transfers(&filter, &at_end).unwrap().is_empty());
+        let accounts = store.accounts(&PageRequest::new(1)).unwrap();
+        let foreign = PageRequest::new(2).with_cursor(accounts.next_cursor.unwrap());
+        assert!(matches!(
+            store.transfers(&filter, &foreign),
+            Err(ValidationError::InvalidCursor(_))
+        ));
+        let foreign = PageRequest::new(2).with_cursor(Cursor::from_bytes(vec![0xff]));
+        assert!(matches!(
+            store.accounts(&foreign),
+            Err(ValidationError::InvalidCursor(_))
+        ));
+        assert!(matches!(
+            store.balances(&BalanceFilter::default(), &foreign),
+            Err(ValidationError::InvalidCursor(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_filter_addresses() {
+        let store = transfers_store();
+        let all = PageRequest::default();
+
+        for address in ["0x1234", "0xzz", &format!("0x01{}", &ALICE[2..])] {
+            let filter = TransferFilter {
+                from: Some(address.to_string()),
+                ..Default::default()
+            };
+            assert!(matches!(
+                store.transfers(&filter, &all),
+                Err(ValidationError::UnrecognizedAddress(_))
+            ));
+        }
+
+        let filter = TransferFilter {
+            to: Some("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQX".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            store.transfers(&filter, &all),
+            Err(ValidationError::InvalidSubstrateAddress(_))
+        ));
+        let filter = FeeFilter {
+            account: Some("bob".to_string()),
+            ..Default::default()
+        };
+        assert!(store.fees(&filter, &all).is_err());
+        let filter = BalanceFilter {
+            account: Some(String::new()),
+            ..Default::default()
+        };
+        assert!(store.balances(&filter, &all).is_err());
+
+        // A signer's `MultiAddress::Id` prefix names the same account
+        let filter = TransferFilter {
+            to: Some(format!("0x00{}", &BOB[2..])),
+            ..Default::default()
+        };
+        assert_eq!(store.transfers(&filter, &all).unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_account_and_balance_pages() {
+        let store = transfers_store();
+
+        let first = store.accounts(&PageRequest::new(1)).unwrap();
+        assert_eq!(first.items[0].address, BOB);
+        let second = store.accounts(&first.next_request(1).unwrap()).unwrap();
+        assert_eq!(second.items[0].address, ALICE);
+        assert!(!second.has_more());
+
+        let first = store
+            .balances(&BalanceFilter::default(), &PageRequest::new(3))
+            .unwrap();
+        let keys: Vec<_> = first
+            .items
+            .iter()
+            .map(|b| (b.account.as_str(), b.asset.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![(BOB, "1984"), (BOB, NATIVE_ASSET), (ALICE, "1984")]
+        );
+        let rest = store
+            .balances(&BalanceFilter::default(), &first.next_request(3).unwrap())
+            .unwrap();
+        assert_eq!(rest.items[0].account, ALICE);
+        assert_eq!(rest.items[0].asset, NATIVE_ASSET);
+        assert!(!rest.has_more());
+    }
+
+    #[tokio::test]
+    async fn test_retention_purges_oldest_records() {
+        let store = transfers_store();
+        assert_eq!(store.name(), "graphql_index");
+
+        let unbounded = store.purge(&RetentionPolicy::new()).await.unwrap();
+        assert_eq!(unbounded.purged, 0);
+
+        // A cursor issued before the purge continues where it left off
+        let page = store
+            .transfers(&TransferFilter::default(), &PageRequest::new(4))
+            .unwrap();
+        let request = page.next_request(4).unwrap();
+
+        // Just too large with the five oldest transfers gone
+        let oldest = store
+            .transfers(&TransferFilter::default(), &PageRequest::new(5))
+            .unwrap();
+        let five: u64 = oldest.items.iter().map(Record::size).sum();
+        let policy = RetentionPolicy::new().with_max_size(unbounded.size_bytes - five - 1);
+        let report = store.purge(&policy).await.unwrap();
+        assert_eq!(report.purged, 6);
+        assert!(report.size_bytes <= policy.max_size.unwrap());
+
+        let all = store
+            .transfers(&TransferFilter::default(), &PageRequest::default())
+            .unwrap();
+        assert_eq!(all.len(), 9);
+        assert_eq!(all.items[0].block_number, 24);
+        let resumed = store
+            .transfers(&TransferFilter::default(), &request)
+            .unwrap();
+        assert_eq!(resumed.items[0].block_number, 24);
+        assert_eq!(resumed.items[0].asset, NATIVE_ASSET);
+
+        // Totals still count purged transfers
+        let alice = store.account(ALICE).unwrap().unwrap();
+        assert_eq!(alice.transfers_sent, 10);
+
+        // Every indexed block is older than a day
+        let report = store
+            .purge(&RetentionPolicy::new().with_max_age(std::time::Duration::from_secs(86_400)))
+            .await
+            .unwrap();
+        assert_eq!(report.purged, 9);
+        assert!(store
+            .transfers(&TransferFilter::default(), &request)
+            .unwrap()
+            .is_empty());
+        assert_eq!(store.accounts(&PageRequest::default()).unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_graphql_queries() {
+        let schema = store().schema();
+        let query = format!(
+            r#"{{
+                lastBlock
+                transfers(filter: {{ to: "{BOB}" }}) {{ items {{ blockNumber asset amount }} nextCursor }}
+                fees(filter: {{ account: "{ALICE}" }}) {{ items {{ fee tip }} }}
+                balances(filter: {{ account: "{ALICE}", asset: "native" }}) {{ items {{ sent net }} }}
+                accounts(first: 1) {{ items {{ address }} nextCursor }}
+            }}"#
+        );
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["lastBlock"], 11);
+        assert_eq!(
+            data["transfers"],
+            json!({
+                "items": [{ "blockNumber": 10, "asset": "native", "amount": "1000" }],
+                "nextCursor": null,
+            })
+        );
+        assert_eq!(
+            data["fees"]["items"],
+            json!([{ "fee": "150", "tip": "50" }])
+        );
+        assert_eq!(
+            data["balances"]["items"],
+            json!([{ "sent": "1000", "net": "-1000" }])
+        );
+        assert_eq!(data["accounts"]["items"], json!([{ "address": BOB }]));
+        assert!(data["accounts"]["nextCursor"].is_string());
+
+        let invalid = schema
+            .execute(r#"{ transfers(after: "bogus") { nextCursor } }"#)
+            .await;
+        assert_eq!(invalid.errors.len(), 1);
+    }
+}
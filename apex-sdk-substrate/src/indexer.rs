@@ -0,0 +1,954 @@
+//! Historical block and event indexer
+//!
+//! [`Indexer`] walks a range of blocks, or follows the finalized head,
+//! decodes each block's extrinsics and events into [`IndexedBlock`]s and
+//! hands them to an [`IndexSink`] in block order. Sinks are provided for a
+//! callback, a channel and a JSON Lines file, plus SQLite with the `sqlite`
+//! feature; implement the trait to write anywhere else.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::indexer::JsonLinesSink;
+//! use apex_sdk_substrate::SubstrateAdapter;
+//!
+//! # async fn example() -> apex_sdk_substrate::Result<()> {
+//! let adapter = SubstrateAdapter::connect("wss://westend-rpc.polkadot.io").await?;
+//! let indexer = adapter.indexer();
+//! let mut sink = JsonLinesSink::append("blocks.jsonl")?;
+//!
+//! // Backfill, then keep up with finality
+//! let stats = indexer.index_range(20_000_000..=20_000_100, &mut sink).await?;
+//! indexer.follow_finalized(stats.last_block.map(|n| n + 1), &mut sink).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Blocks are decoded with the metadata of the runtime the client connected
+//! to. Calls and events of older runtimes that changed shape fail to decode;
+//! their names are still recorded, with `null` arguments or fields.
+
+use crate::{ChainClient, Error, PolkadotConfig, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::sync::Arc;
+use subxt::events::{Events, Phase};
+use subxt::ext::futures::StreamExt;
+use subxt::ext::scale_value::{Composite, Primitive, Value, ValueDef};
+use subxt::utils::H256;
+use subxt::OnlineClient;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+type SubxtBlock = subxt::blocks::Block<PolkadotConfig, OnlineClient<PolkadotConfig>>;
+
+/// A block with its decoded extrinsics and events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedBlock {
+    /// Block number
+    pub number: u64,
+    /// Block hash (0x-prefixed)
+    pub hash: String,
+    /// Parent block hash (0x-prefixed)
+    pub parent_hash: String,
+    /// Block time in milliseconds, from the `Timestamp::set` inherent
+    pub timestamp: Option<u64>,
+    /// Extrinsics in block order
+    pub extrinsics: Vec<IndexedExtrinsic>,
+    /// Events in emission order
+    pub events: Vec<IndexedEvent>,
+}
+
+/// A decoded extrinsic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedExtrinsic {
+    /// Index of the extrinsic in the block
+    pub index: u32,
+    /// Extrinsic hash (0x-prefixed)
+    pub hash: String,
+    /// Signer address bytes (0x-prefixed), for signed extrinsics
+    pub signer: Option<String>,
+    /// Pallet name
+    pub pallet: String,
+    /// Call name
+    pub call: String,
+    /// Call arguments, see [`value_to_json`]
+    pub args: serde_json::Value,
+    /// Whether `System::ExtrinsicSuccess` was emitted for the extrinsic
+    pub success: bool,
+}
+
+/// A decoded event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedEvent {
+    /// Index of the event in the block
+    pub index: u32,
+    /// Index of the extrinsic that emitted the event, if any
+    pub extrinsic_index: Option<u32>,
+    /// Pallet name
+    pub pallet: String,
+    /// Event name
+    pub event: String,
+    /// Event fields, see [`value_to_json`]
+    pub fields: serde_json::Value,
+}
+
+impl IndexedEvent {
+    /// Check if this is `pallet::event`
+    pub fn is(&self, pallet: &str, event: &str) -> bool {
+        self.pallet == pallet && self.event == event
+    }
+}
+
+/// Totals of an indexing run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexStats {
+    /// Blocks written to the sink
+    pub blocks: u64,
+    /// Extrinsics in those blocks
+    pub extrinsics: u64,
+    /// Events in those blocks
+    pub events: u64,
+    /// Number of the last block written
+    pub last_block: Option<u64>,
+}
+
+impl IndexStats {
+    fn record(&mut self, block: &IndexedBlock) {
+        self.blocks += 1;
+        self.extrinsics += block.extrinsics.len() as u64;
+        self.events += block.events.len() as u64;
+        self.last_block = Some(block.number);
+    }
+}
+
+/// Destination of indexed blocks
+///
+/// Blocks arrive in ascending order. An error from the sink stops the
+/// indexer and is returned to its caller.
+#[async_trait]
+pub trait IndexSink: Send {
+    /// Store one block
+    async fn write(&mut self, block: &IndexedBlock) -> Result<()>;
+
+    /// Persist buffered blocks; called after each range and each finalized head
+    async fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Sink that calls a closure for every block
+pub struct CallbackSink<F> {
+    callback: F,
+}
+
+impl<F> CallbackSink<F>
+where
+    F: FnMut(&IndexedBlock) -> Result<()> + Send,
+{
+    /// Call `callback` for every block
+    pub fn new(callback: F) -> Self {
+        Self { callback }
+    }
+}
+
+#[async_trait]
+impl<F> IndexSink for CallbackSink<F>
+where
+    F: FnMut(&IndexedBlock) -> Result<()> + Send,
+{
+    async fn write(&mut self, block: &IndexedBlock) -> Result<()> {
+        (self.callback)(block)
+    }
+}
+
+/// Sink that sends blocks over a bounded channel
+///
+/// A full channel pauses the indexer, so a slow consumer applies
+/// backpressure instead of losing blocks. Indexing stops once the receiver
+/// is dropped.
+pub struct ChannelSink {
+    sender: mpsc::Sender<Arc<IndexedBlock>>,
+}
+
+impl ChannelSink {
+    /// Create a sink and the receiver for its blocks
+    pub fn new(capacity: usize) -> (Self, mpsc::Receiver<Arc<IndexedBlock>>) {
+        let (sender, receiver) = mpsc::channel(capacity);
+        (Self { sender }, receiver)
+    }
+}
+
+#[async_trait]
+impl IndexSink for ChannelSink {
+    async fn write(&mut self, block: &IndexedBlock) -> Result<()> {
+        self.sender
+            .send(Arc::new(block.clone()))
+            .await
+            .map_err(|_| Error::Other("Indexer channel closed".to_string()))
+    }
+}
+
+/// Sink that appends one JSON object per block to a file
+pub struct JsonLinesSink {
+    writer: BufWriter<File>,
+}
+
+impl JsonLinesSink {
+    /// Open `path` for appending, creating it if needed
+    pub fn append(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| Error::Storage(format!("Failed to open {}: {}", path.display(), e)))?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+#[async_trait]
+impl IndexSink for JsonLinesSink {
+    async fn write(&mut self, block: &IndexedBlock) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, block).map_err(|e| {
+            Error::Storage(format!("Failed to write block {}: {}", block.number, e))
+        })?;
+        self.writer
+            .write_all(b"\n")
+            .map_err(|e| Error::Storage(format!("Failed to write block {}: {}", block.number, e)))
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.writer
+            .flush()
+            .map_err(|e| Error::Storage(format!("Failed to flush index: {}", e)))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::{SqliteRetention, SqliteSink};
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::{IndexSink, IndexedBlock};
+    use crate::{Error, Result};
+    use apex_sdk_core::retention::{PurgeReport, RetentionError, RetentionPolicy, RetentionStore};
+    use async_trait::async_trait;
+    use rusqlite::{params, Connection};
+    use std::path::Path;
+    use std::sync::Mutex;
+    use std::time::{Duration, SystemTime};
+
+    const SCHEMA: &str = "
+        CREATE TABLE IF NOT EXISTS blocks (
+            number INTEGER PRIMARY KEY,
+            hash TEXT NOT NULL,
+            parent_hash TEXT NOT NULL,
+            timestamp INTEGER
+        );
+        CREATE TABLE IF NOT EXISTS extrinsics (
+            block_number INTEGER NOT NULL,
+            idx INTEGER NOT NULL,
+            hash TEXT NOT NULL,
+            signer TEXT,
+            pallet TEXT NOT NULL,
+            call TEXT NOT NULL,
+            args TEXT NOT NULL,
+            success INTEGER NOT NULL,
+            PRIMARY KEY (block_number, idx)
+        );
+        CREATE TABLE IF NOT EXISTS events (
+            block_number INTEGER NOT NULL,
+            idx INTEGER NOT NULL,
+            extrinsic_idx INTEGER,
+            pallet TEXT NOT NULL,
+            event TEXT NOT NULL,
+            fields TEXT NOT NULL,
+            PRIMARY KEY (block_number, idx)
+        );
+        CREATE INDEX IF NOT EXISTS events_by_name ON events (pallet, event);
+        CREATE INDEX IF NOT EXISTS extrinsics_by_signer ON extrinsics (signer);
+    ";
+
+    fn sql_error(e: rusqlite::Error) -> Error {
+        Error::Storage(format!("SQLite error: {}", e))
+    }
+
+    /// Sink that stores blocks, extrinsics and events in SQLite tables
+    ///
+    /// Arguments and event fields are stored as JSON text, queryable with
+    /// SQLite's JSON functions. Each block is written in one transaction and
+    /// re-indexing a block replaces its rows.
+    pub struct SqliteSink {
+        connection: Connection,
+    }
+
+    impl SqliteSink {
+        /// Open or create the database at `path`
+        pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+            Self::with_connection(Connection::open(path).map_err(sql_error)?)
+        }
+
+        /// Open a database that lives in memory
+        pub fn open_in_memory() -> Result<Self> {
+            Self::with_connection(Connection::open_in_memory().map_err(sql_error)?)
+        }
+
+        fn with_connection(connection: Connection) -> Result<Self> {
+            connection.execute_batch(SCHEMA).map_err(sql_error)?;
+            Ok(Self { connection })
+        }
+
+        /// Highest block number stored, to resume indexing after
+        pub fn last_indexed(&self) -> Result<Option<u64>> {
+            self.connection
+                .query_row("SELECT MAX(number) FROM blocks", [], |row| row.get(0))
+                .map_err(sql_error)
+        }
+
+        /// The underlying connection, for queries
+        pub fn connection(&self) -> &Connection {
+            &self.connection
+        }
+    }
+
+    #[async_trait]
+    impl IndexSink for SqliteSink {
+        async fn write(&mut self, block: &IndexedBlock) -> Result<()> {
+            let tx = self.connection.transaction().map_err(sql_error)?;
+            for table in ["extrinsics", "events"] {
+                tx.execute(
+                    &format!("DELETE FROM {} WHERE block_number = ?1", table),
+                    params![block.number],
+                )
+                .map_err(sql_error)?;
+            }
+            tx.execute(
+                "INSERT OR REPLACE INTO blocks (number, hash, parent_hash, timestamp)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![block.number, block.hash, block.parent_hash, block.timestamp],
+            )
+            .map_err(sql_error)?;
+            for ext in &block.extrinsics {
+                tx.execute(
+                    "INSERT INTO extrinsics
+                     (block_number, idx, hash, signer, pallet, call, args, success)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        block.number,
+                        ext.index,
+                        ext.hash,
+                        ext.signer,
+                        ext.pallet,
+                        ext.call,
+                        ext.args.to_string(),
+                        ext.success
+                    ],
+                )
+                .map_err(sql_error)?;
+            }
+            for event in &block.events {
+                tx.execute(
+                    "INSERT INTO events
+                     (block_number, idx, extrinsic_idx, pallet, event, fields)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        block.number,
+                        event.index,
+                        event.extrinsic_index,
+                        event.pallet,
+                        event.event,
+                        event.fields.to_string()
+                    ],
+                )
+                .map_err(sql_error)?;
+            }
+            tx.commit().map_err(sql_error)
+        }
+    }
+
+    /// Retention for a database written by [`SqliteSink`]
+    ///
+    /// Opens its own connection so the GC can run while the sink keeps
+    /// writing. Blocks are purged oldest first together with their
+    /// extrinsics and events; blocks without a timestamp only expire by size.
+    /// The size limit applies to pages in use, and purges are compacted with
+    /// `VACUUM` so the file shrinks too.
+    pub struct SqliteRetention {
+        connection: Mutex<Connection>,
+    }
+
+    impl SqliteRetention {
+        /// Open the database at `path`, creating the schema if needed
+        pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+            let connection = Connection::open(path).map_err(sql_error)?;
+            connection
+                .busy_timeout(Duration::from_secs(5))
+                .map_err(sql_error)?;
+            connection.execute_batch(SCHEMA).map_err(sql_error)?;
+            Ok(Self {
+                connection: Mutex::new(connection),
+            })
+        }
+
+        fn purge_blocks(
+            &self,
+            policy: &RetentionPolicy,
+        ) -> std::result::Result<PurgeReport, rusqlite::Error> {
+            let mut connection = self.connection.lock().unwrap_or_else(|e| e.into_inner());
+            let tx = connection.transaction()?;
+            let mut purged = 0;
+
+            if let Some(cutoff) = policy.cutoff(SystemTime::now()) {
+                let last_expired: Option<u64> = tx.query_row(
+                    "SELECT MAX(number) FROM blocks WHERE timestamp < ?1",
+                    params![cutoff.saturating_mul(1000)],
+                    |row| row.get(0),
+                )?;
+                if let Some(number) = last_expired {
+                    purged += delete_through(&tx, number)?;
+                }
+            }
+
+            let mut size = used_bytes(&tx)?;
+            while policy.exceeds_size(size) {
+                let (blocks, first): (u64, Option<u64>) =
+                    tx.query_row("SELECT COUNT(*), MIN(number) FROM blocks", [], |row| {
+                        Ok((row.get(0)?, row.get(1)?))
+                    })?;
+                let Some(first) = first else { break };
+
+                // Drop the share of blocks the excess amounts to, at least one
+                let excess = size - policy.max_size.unwrap_or(size);
+                let count = (blocks * excess).div_ceil(size).max(1);
+                purged += delete_through(&tx, first + count - 1)?;
+                size = used_bytes(&tx)?;
+            }
+
+            tx.commit()?;
+            Ok(PurgeReport {
+                purged,
+                size_bytes: size,
+            })
+        }
+    }
+
+    /// Delete blocks up to and including `number`, returning how many
+    fn delete_through(tx: &rusqlite::Transaction<'_>, number: u64) -> rusqlite::Result<u64> {
+        for table in ["extrinsics", "events"] {
+            tx.execute(
+                &format!("DELETE FROM {} WHERE block_number <= ?1", table),
+                params![number],
+            )?;
+        }
+        let deleted = tx.execute("DELETE FROM blocks WHERE number <= ?1", params![number])?;
+        Ok(deleted as u64)
+    }
+
+    /// Bytes in pages holding data, excluding free pages left by deletes
+    fn used_bytes(connection: &Connection) -> rusqlite::Result<u64> {
+        connection.query_row(
+            "SELECT (page_count - freelist_count) * page_size
+             FROM pragma_page_count(), pragma_freelist_count(), pragma_page_size()",
+            [],
+            |row| row.get(0),
+        )
+    }
+
+    #[async_trait]
+    impl RetentionStore for SqliteRetention {
+        fn name(&self) -> &str {
+            "indexer"
+        }
+
+        async fn purge(
+            &self,
+            policy: &RetentionPolicy,
+        ) -> std::result::Result<PurgeReport, RetentionError> {
+            self.purge_blocks(policy)
+                .map_err(|e| RetentionError::new(self.name(), e))
+        }
+
+        async fn compact(&self) -> std::result::Result<(), RetentionError> {
+            self.connection
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .execute_batch("VACUUM")
+                .map_err(|e| RetentionError::new(self.name(), e))
+        }
+    }
+}
+
+/// Walks blocks and streams them, decoded, to an [`IndexSink`]
+#[derive(Clone)]
+pub struct Indexer {
+    client: OnlineClient<PolkadotConfig>,
+    chain: Arc<dyn ChainClient>,
+}
+
+impl Indexer {
+    /// Create an indexer reading blocks through `client`
+    ///
+    /// Block numbers are resolved to hashes through `chain`; see
+    /// [`SubstrateAdapter::indexer`](crate::SubstrateAdapter::indexer).
+    pub fn new(client: OnlineClient<PolkadotConfig>, chain: Arc<dyn ChainClient>) -> Self {
+        Self { client, chain }
+    }
+
+    /// Index blocks `range` in order
+    ///
+    /// Fails if the chain has not reached the end of the range yet.
+    pub async fn index_range(
+        &self,
+        range: RangeInclusive<u64>,
+        sink: &mut dyn IndexSink,
+    ) -> Result<IndexStats> {
+        let mut stats = IndexStats::default();
+        for number in range {
+            let block = self.index_block(number).await?;
+            sink.write(&block).await?;
+            stats.record(&block);
+        }
+        sink.flush().await?;
+        Ok(stats)
+    }
+
+    /// Index finalized blocks as they arrive, until the subscription ends
+    ///
+    /// Starts at `from`, backfilling up to the finalized head first, or at
+    /// the next finalized block. Finalized heads that skip numbers are filled
+    /// in, so no block is missed.
+    pub async fn follow_finalized(
+        &self,
+        from: Option<u64>,
+        sink: &mut dyn IndexSink,
+    ) -> Result<IndexStats> {
+        let mut heads = self.chain.subscribe_finalized_heads().await?;
+        let mut stats = IndexStats::default();
+        let mut next = from;
+
+        while let Some(head) = heads.next().await {
+            let head = head?;
+            let start = *next.get_or_insert(head.number);
+            if head.number < start {
+                continue;
+            }
+            debug!("Indexing finalized blocks {}..={}", start, head.number);
+
+            let batch = self.index_range(start..=head.number, sink).await?;
+            stats.blocks += batch.blocks;
+            stats.extrinsics += batch.extrinsics;
+            stats.events += batch.events;
+            stats.last_block = batch.last_block;
+            next = Some(head.number + 1);
+        }
+        Ok(stats)
+    }
+
+    /// Fetch and decode one block
+    pub async fn index_block(&self, number: u64) -> Result<IndexedBlock> {
+        let hash = self
+            .chain
+            .block_hash(number)
+            .await?
+            .ok_or_else(|| Error::Connection(format!("Block {} not found", number)))?;
+        let block = self
+            .client
+            .blocks()
+            .at(H256(hash))
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to get block {}: {}", number, e)))?;
+        self.decode_block(block).await
+    }
+
+    async fn decode_block(&self, block: SubxtBlock) -> Result<IndexedBlock> {
+        let number = block.number() as u64;
+        let events = block
+            .events()
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to get events: {}", e)))?;
+        let events = decode_events(&events, &self.client.metadata())?;
+
+        let extrinsics = block
+            .extrinsics()
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to get extrinsics: {}", e)))?;
+        let types = self.client.metadata();
+        let types = types.types();
+
+        let mut timestamp = None;
+        let mut indexed = Vec::with_capacity(extrinsics.len());
+        for ext in extrinsics.iter() {
+            let index = ext.index();
+            let pallet = ext.pallet_name().unwrap_or("Unknown").to_string();
+            let call = ext.variant_name().unwrap_or("Unknown").to_string();
+            let args = match ext.field_values() {
+                Ok(fields) => composite_to_json(&fields, types),
+                Err(e) => {
+                    warn!(
+                        "Failed to decode {}::{} in block {}: {}",
+                        pallet, call, number, e
+                    );
+                    serde_json::Value::Null
+                }
+            };
+            if pallet == "Timestamp" && call == "set" {
+                timestamp = args.get("now").and_then(serde_json::Value::as_u64);
+            }
+
+            indexed.push(IndexedExtrinsic {
+                index,
+                hash: format!("0x{}", hex::encode(ext.hash())),
+                signer: ext
+                    .address_bytes()
+                    .map(|bytes| format!("0x{}", hex::encode(bytes))),
+                success: events.iter().any(|event| {
+                    event.extrinsic_index == Some(index) && event.is("System", "ExtrinsicSuccess")
+                }),
+                pallet,
+                call,
+                args,
+            });
+        }
+
+        Ok(IndexedBlock {
+            number,
+            hash: format!("0x{}", hex::encode(block.hash())),
+            parent_hash: format!("0x{}", hex::encode(block.header().parent_hash)),
+            timestamp,
+            extrinsics: indexed,
+            events,
+        })
+    }
+}
+
+/// Decode every event of a block
+fn decode_events(
+    events: &Events<PolkadotConfig>,
+    metadata: &subxt::Metadata,
+) -> Result<Vec<IndexedEvent>> {
+    events
+        .iter()
+        .map(|event| {
+            let event = event?;
+            let fields = match event.field_values() {
+                Ok(fields) => composite_to_json(&fields, metadata.types()),
+                Err(e) => {
+                    warn!(
+                        "Failed to decode {}::{}: {}",
+                        event.pallet_name(),
+                        event.variant_name(),
+                        e
+                    );
+                    serde_json::Value::Null
+                }
+            };
+            Ok(IndexedEvent {
+                index: event.index(),
+                extrinsic_index: match event.phase() {
+                    Phase::ApplyExtrinsic(index) => Some(index),
+                    Phase::Initialization | Phase::Finalization => None,
+                },
+                pallet: event.pallet_name().to_string(),
+                event: event.variant_name().to_string(),
+                fields,
+            })
+        })
+        .collect()
+}
+
+/// JSON form of a decoded value
+///
+/// Byte arrays and vectors become `0x` hex strings, single-field tuple
+/// structs such as `AccountId32` are unwrapped, unit variants become their
+/// name, and integers beyond 64 bits become decimal strings so JSON parsers
+/// do not round them.
+pub fn value_to_json(
+    value: &Value<u32>,
+    types: &scale_info::PortableRegistry,
+) -> serde_json::Value {
+    use serde_json::Value as Json;
+
+    match &value.value {
+        ValueDef::Composite(Composite::Unnamed(values)) => {
+            let def = types.resolve(value.context).map(|ty| &ty.type_def);
+            if is_byte_collection(def, types) {
+                let bytes: Option<Vec<u8>> = values
+                    .iter()
+                    .map(|v| v.as_u128().and_then(|n| u8::try_from(n).ok()))
+                    .collect();
+                if let Some(bytes) = bytes {
+                    return Json::String(format!("0x{}", hex::encode(bytes)));
+                }
+            }
+            match (def, values.as_slice()) {
+                (Some(scale_info::TypeDef::Composite(_)), [inner]) => value_to_json(inner, types),
+                _ => Json::Array(values.iter().map(|v| value_to_json(v, types)).collect()),
+            }
+        }
+        ValueDef::Composite(composite) => composite_to_json(composite, types),
+        ValueDef::Variant(variant) if variant.values.is_empty() => {
+            Json::String(variant.name.clone())
+        }
+        ValueDef::Variant(variant) => {
+            let mut object = serde_json::Map::new();
+            object.insert(
+                variant.name.clone(),
+                composite_to_json(&variant.values, types),
+            );
+            Json::Object(object)
+        }
+        ValueDef::BitSequence(bits) => {
+            Json::String(bits.iter().map(|bit| if bit { '1' } else { '0' }).collect())
+        }
+        ValueDef::Primitive(primitive) => match primitive {
+            Primitive::Bool(b) => Json::Bool(*b),
+            Primitive::Char(c) => Json::String(c.to_string()),
+            Primitive::String(s) => Json::String(s.clone()),
+            Primitive::U128(n) => match u64::try_from(*n) {
+                Ok(n) => Json::from(n),
+                Err(_) => Json::String(n.to_string()),
+            },
+            Primitive::I128(n) => match i64::try_from(*n) {
+                Ok(n) => Json::from(n),
+                Err(_) => Json::String(n.to_string()),
+            },
+            Primitive::U256(bytes) | Primitive::I256(bytes) => {
+                Json::String(format!("0x{}", hex::encode(bytes)))
+            }
+        },
+    }
+}
+
+fn composite_to_json(
+    composite: &Composite<u32>,
+    types: &scale_info::PortableRegistry,
+) -> serde_json::Value {
+    match composite {
+        Composite::Named(fields) => serde_json::Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), value_to_json(value, types)))
+                .collect(),
+        ),
+        Composite::Unnamed(values) => {
+            serde_json::Value::Array(values.iter().map(|v| value_to_json(v, types)).collect())
+        }
+    }
+}
+
+/// Whether `def` is a `[u8; N]` or `Vec<u8>`
+fn is_byte_collection(
+    def: Option<&scale_info::TypeDef<scale_info::form::PortableForm>>,
+    types: &scale_info::PortableRegistry,
+) -> bool {
+    let element = match def {
+        Some(scale_info::TypeDef::Array(array)) => array.type_param.id,
+        Some(scale_info::TypeDef::Sequence(sequence)) => sequence.type_param.id,
+        _ => return false,
+    };
+    matches!(
+        types.resolve(element).map(|ty| &ty.type_def),
+        Some(scale_info::TypeDef::Primitive(
+            scale_info::TypeDefPrimitive::U8
+        ))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_filter::test_utils::*;
+
+    fn block(number: u64) -> IndexedBlock {
+        IndexedBlock {
+            number,
+            hash: format!("0x{:064x}", number),
+            parent_hash: format!("0x{:064x}", number.saturating_sub(1)),
+            timestamp: Some(1_700_000_000_000 + number * 6_000),
+            extrinsics: vec![IndexedExtrinsic {
+                index: 0,
+                hash: "0x01".to_string(),
+                signer: None,
+                pallet: "Timestamp".to_string(),
+                call: "set".to_string(),
+                args: serde_json::json!({ "now": 1_700_000_000_000u64 }),
+                success: true,
+            }],
+            events: vec![IndexedEvent {
+                index: 0,
+                extrinsic_index: Some(0),
+                pallet: "System".to_string(),
+                event: "ExtrinsicSuccess".to_string(),
+                fields: serde_json::json!({ "weight": 10 }),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_decode_events() {
+        let metadata = metadata();
+        let events = events(
+            &metadata,
+            vec![
+                (
+                    Phase::ApplyExtrinsic(1),
+                    RuntimeEvent::Balances(BalancesEvent::Transfer {
+                        from: [1; 32],
+                        to: [2; 32],
+                        amount: u128::MAX,
+                    }),
+                ),
+                (
+                    Phase::Finalization,
+                    RuntimeEvent::System(SystemEvent::ExtrinsicSuccess { weight: 5 }),
+                ),
+            ],
+        );
+
+        let decoded = decode_events(&events, &metadata).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert!(decoded[0].is("Balances", "Transfer"));
+        assert_eq!(decoded[0].extrinsic_index, Some(1));
+        assert_eq!(
+            decoded[0].fields,
+            serde_json::json!({
+                "from": format!("0x{}", "01".repeat(32)),
+                "to": format!("0x{}", "02".repeat(32)),
+                "amount": u128::MAX.to_string(),
+            })
+        );
+        assert_eq!(decoded[1].index, 1);
+        assert_eq!(decoded[1].extrinsic_index, None);
+        assert_eq!(decoded[1].fields, serde_json::json!({ "weight": 5 }));
+    }
+
+    #[tokio::test]
+    async fn test_callback_and_channel_sinks() {
+        let mut seen = Vec::new();
+        let mut sink = CallbackSink::new(|block: &IndexedBlock| {
+            seen.push(block.number);
+            Ok(())
+        });
+        sink.write(&block(1)).await.unwrap();
+        sink.write(&block(2)).await.unwrap();
+        assert_eq!(seen, vec![1, 2]);
+
+        let (mut sink, mut receiver) = ChannelSink::new(1);
+        sink.write(&block(3)).await.unwrap();
+        assert_eq!(receiver.recv().await.unwrap().number, 3);
+        drop(receiver);
+        assert!(sink.write(&block(4)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_json_lines_sink_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blocks.jsonl");
+
+        let mut sink = JsonLinesSink::append(&path).unwrap();
+        sink.write(&block(1)).await.unwrap();
+        sink.flush().await.unwrap();
+        drop(sink);
+        let mut sink = JsonLinesSink::append(&path).unwrap();
+        sink.write(&block(2)).await.unwrap();
+        sink.flush().await.unwrap();
+
+        let blocks: Vec<IndexedBlock> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(blocks, vec![block(1), block(2)]);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_sqlite_sink() {
+        let mut sink = SqliteSink::open_in_memory().unwrap();
+        assert_eq!(sink.last_indexed().unwrap(), None);
+
+        sink.write(&block(7)).await.unwrap();
+        sink.write(&block(8)).await.unwrap();
+        // Re-indexing replaces the block's rows
+        sink.write(&block(8)).await.unwrap();
+        assert_eq!(sink.last_indexed().unwrap(), Some(8));
+
+        let events: u32 = sink
+            .connection()
+            .query_row(
+                "SELECT COUNT(*) FROM events WHERE pallet = 'System'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(events, 2);
+        let now: i64 = sink
+            .connection()
+            .query_row(
+                "SELECT json_extract(args, '$.now') FROM extrinsics WHERE block_number = 7",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(now, 1_700_000_000_000);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_sqlite_retention() {
+        use apex_sdk_core::retention::{RetentionPolicy, RetentionStore};
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.db");
+        let mut sink = SqliteSink::open(&path).unwrap();
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        for number in 1..=40 {
+            let mut block = block(number);
+            // Blocks 1 to 10 are two days old
+            let age = if number <= 10 { 48 * 3600 * 1000 } else { 0 };
+            block.timestamp = Some(now_ms - age);
+            block.extrinsics[0].args = serde_json::json!({ "pad": "x".repeat(2048) });
+            sink.write(&block).await.unwrap();
+        }
+
+        let retention = SqliteRetention::open(&path).unwrap();
+        let report = retention
+            .purge(&RetentionPolicy::new().with_max_age(Duration::from_secs(24 * 3600)))
+            .await
+            .unwrap();
+        assert_eq!(report.purged, 10);
+        let first: u64 = sink
+            .connection()
+            .query_row("SELECT MIN(number) FROM blocks", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(first, 11);
+
+        let limit = report.size_bytes / 2;
+        let report = retention
+            .purge(&RetentionPolicy::new().with_max_size(limit))
+            .await
+            .unwrap();
+        assert!(report.purged > 0);
+        assert!(report.size_bytes <= limit);
+        retention.compact().await.unwrap();
+        assert_eq!(sink.last_indexed().unwrap(), Some(40));
+        let orphans: u32 = sink
+            .connection()
+            .query_row(
+                "SELECT COUNT(*) FROM events WHERE block_number NOT IN (SELECT number FROM blocks)",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(orphans, 0);
+    }
+}
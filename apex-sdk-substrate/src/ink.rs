@@ -0,0 +1,1025 @@
+//! ink! contracts on pallet-contracts
+//!
+//! [`InkContractManager`] deploys, calls and dry-runs Wasm contracts on chains
+//! running pallet-contracts, with the same surface as the Revive
+//! `ContractManager`. [`InkBundle`] loads the `.contract` bundle emitted by
+//! `cargo contract build` and uses its metadata to encode constructor and
+//! message arguments, decode return values and decode contract events. ink! 4
+//! and ink! 5 metadata are supported.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use apex_sdk_substrate::{InkBundle, InkContractManager};
+//! use subxt::dynamic::Value;
+//!
+//! let bundle = InkBundle::from_file("flipper.contract")?;
+//! let contracts = InkContractManager::new(&adapter, signer);
+//! let address = contracts
+//!     .deploy_bundle(&bundle, "new", &[Value::bool(false)], [0u8; 32], 0, None)
+//!     .await?;
+//!
+//! let receipt = contracts.call_message(&address, &bundle, "flip", &[], 0, None).await?;
+//! for event in receipt.decode_events(&bundle)? {
+//!     println!("{}: {:?}", event.label, event.fields);
+//! }
+//! let value = contracts.read_message(&address, &bundle, "get", &[]).await?;
+//! ```
+
+use crate::contracts::GasLimit;
+use crate::{Error, Result, SubstrateAdapter};
+use apex_sdk_types::Address;
+use scale_info::PortableRegistry;
+use serde::{Deserialize, Deserializer};
+use std::path::Path;
+use subxt::dynamic::{At, Value};
+use subxt::ext::scale_value::scale::{decode_as_type, encode_as_type};
+use subxt::ext::scale_value::{Composite, ValueDef};
+use subxt::tx::Signer;
+use subxt::PolkadotConfig;
+use tracing::{debug, info};
+
+/// Safety margin added to dry-run gas and storage deposit estimates
+const ESTIMATE_MARGIN_PERCENT: u64 = 10;
+
+/// A constructor or message of an ink! contract
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InkMessage {
+    /// Name in the contract source
+    pub label: String,
+    /// Selector prepended to the SCALE-encoded arguments
+    #[serde(deserialize_with = "hex_array")]
+    pub selector: [u8; 4],
+    /// Arguments, in call order
+    #[serde(default)]
+    pub args: Vec<InkArg>,
+    /// Return type; messages of ink! 4 and later return `Result<T, LangError>`
+    #[serde(default)]
+    pub return_type: Option<InkTypeSpec>,
+    /// Whether the message may change contract state
+    #[serde(default)]
+    pub mutates: bool,
+    /// Whether value may be transferred with the call
+    #[serde(default)]
+    pub payable: bool,
+}
+
+/// A constructor or message argument
+#[derive(Debug, Clone, Deserialize)]
+pub struct InkArg {
+    /// Argument name
+    pub label: String,
+    /// Argument type
+    #[serde(rename = "type")]
+    pub ty: InkTypeSpec,
+}
+
+/// Reference into the bundle's type registry
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InkTypeSpec {
+    /// Type ID in the bundle's type registry
+    #[serde(rename = "type")]
+    pub id: u32,
+    /// Type name as written in the contract source
+    #[serde(default)]
+    pub display_name: Vec<String>,
+}
+
+/// An event an ink! contract can emit
+#[derive(Debug, Clone, Deserialize)]
+pub struct InkEvent {
+    /// Event name
+    pub label: String,
+    /// Event fields, in encoding order
+    #[serde(default)]
+    pub args: Vec<InkEventArg>,
+    /// First topic of the event (ink! 5); `None` for ink! 4 and anonymous
+    /// events
+    #[serde(default, deserialize_with = "optional_hex_array")]
+    pub signature_topic: Option<[u8; 32]>,
+}
+
+/// An event field
+#[derive(Debug, Clone, Deserialize)]
+pub struct InkEventArg {
+    /// Field name
+    pub label: String,
+    /// Whether the field is also published as a topic
+    #[serde(default)]
+    pub indexed: bool,
+    /// Field type
+    #[serde(rename = "type")]
+    pub ty: InkTypeSpec,
+}
+
+#[derive(Deserialize)]
+struct RawBundle {
+    #[serde(default)]
+    source: RawSource,
+    #[serde(default)]
+    contract: RawContract,
+    spec: RawSpec,
+    #[serde(flatten)]
+    registry: PortableRegistry,
+}
+
+#[derive(Default, Deserialize)]
+struct RawSource {
+    #[serde(default)]
+    hash: Option<String>,
+    #[serde(default)]
+    wasm: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct RawContract {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct RawSpec {
+    #[serde(default)]
+    constructors: Vec<InkMessage>,
+    #[serde(default)]
+    messages: Vec<InkMessage>,
+    #[serde(default)]
+    events: Vec<InkEvent>,
+}
+
+/// A compiled ink! contract and its metadata
+///
+/// Loaded from a `.contract` bundle, which carries the Wasm code, or from the
+/// metadata-only `.json` file, in which case deployments must supply the code.
+#[derive(Debug, Clone)]
+pub struct InkBundle {
+    name: String,
+    version: String,
+    code: Option<Vec<u8>>,
+    code_hash: Option<[u8; 32]>,
+    constructors: Vec<InkMessage>,
+    messages: Vec<InkMessage>,
+    events: Vec<InkEvent>,
+    registry: PortableRegistry,
+}
+
+impl InkBundle {
+    /// Parse a `.contract` bundle or ink! metadata JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        let raw: RawBundle = serde_json::from_str(json)
+            .map_err(|e| Error::Metadata(format!("Invalid ink! contract metadata: {}", e)))?;
+
+        let code = raw
+            .source
+            .wasm
+            .map(|wasm| decode_hex(&wasm, "source.wasm"))
+            .transpose()?;
+        let code_hash = raw
+            .source
+            .hash
+            .map(|hash| {
+                decode_hex(&hash, "source.hash")?
+                    .try_into()
+                    .map_err(|_| Error::Metadata("source.hash is not 32 bytes".to_string()))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            name: raw.contract.name,
+            version: raw.contract.version,
+            code,
+            code_hash,
+            constructors: raw.spec.constructors,
+            messages: raw.spec.messages,
+            events: raw.spec.events,
+            registry: raw.registry,
+        })
+    }
+
+    /// Read a `.contract` bundle or ink! metadata file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| Error::Metadata(format!("Failed to read {}: {}", path.display(), e)))?;
+        Self::from_json(&json)
+    }
+
+    /// Contract name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Contract version
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Wasm code, if the bundle carries it
+    pub fn code(&self) -> Option<&[u8]> {
+        self.code.as_deref()
+    }
+
+    /// Hash of the Wasm code, as recorded by `cargo contract`
+    pub fn code_hash(&self) -> Option<[u8; 32]> {
+        self.code_hash
+    }
+
+    /// Contract constructors
+    pub fn constructors(&self) -> &[InkMessage] {
+        &self.constructors
+    }
+
+    /// Contract messages
+    pub fn messages(&self) -> &[InkMessage] {
+        &self.messages
+    }
+
+    /// Contract events
+    pub fn events(&self) -> &[InkEvent] {
+        &self.events
+    }
+
+    /// Look up a constructor by name
+    pub fn constructor(&self, label: &str) -> Result<&InkMessage> {
+        self.constructors
+            .iter()
+            .find(|c| c.label == label)
+            .ok_or_else(|| Error::Metadata(format!("Constructor '{}' not found", label)))
+    }
+
+    /// Look up a message by name
+    pub fn message(&self, label: &str) -> Result<&InkMessage> {
+        self.messages
+            .iter()
+            .find(|m| m.label == label)
+            .ok_or_else(|| Error::Metadata(format!("Message '{}' not found", label)))
+    }
+
+    /// Encode the input data of a constructor call
+    pub fn encode_constructor(&self, label: &str, args: &[Value]) -> Result<Vec<u8>> {
+        self.encode_input(self.constructor(label)?, args)
+    }
+
+    /// Encode the input data of a message call
+    pub fn encode_message(&self, label: &str, args: &[Value]) -> Result<Vec<u8>> {
+        self.encode_input(self.message(label)?, args)
+    }
+
+    /// Decode the data returned by a message
+    pub fn decode_return(&self, label: &str, data: &[u8]) -> Result<Value> {
+        let message = self.message(label)?;
+        let ty = message
+            .return_type
+            .as_ref()
+            .ok_or_else(|| Error::Metadata(format!("Message '{}' has no return type", label)))?;
+        self.decode_type(ty.id, &mut &data[..], &message.label)
+    }
+
+    /// Decode an event emitted by this contract
+    ///
+    /// ink! 5 events are identified by their signature topic, ink! 4 events
+    /// by the leading variant index in the event data.
+    pub fn decode_event(&self, record: &InkEventRecord) -> Result<DecodedInkEvent> {
+        let mut data = &record.data[..];
+        let event = if self.events.iter().any(|e| e.signature_topic.is_some()) {
+            let topic = record.topics.first();
+            self.events
+                .iter()
+                .find(|e| e.signature_topic.is_some() && e.signature_topic.as_ref() == topic)
+                .ok_or_else(|| {
+                    Error::Metadata(format!(
+                        "No event of {} has signature topic {}",
+                        self.name,
+                        topic.map(hex::encode).unwrap_or_default()
+                    ))
+                })?
+        } else {
+            let (&index, rest) = data
+                .split_first()
+                .ok_or_else(|| Error::Encoding("Empty contract event".to_string()))?;
+            data = rest;
+            self.events.get(index as usize).ok_or_else(|| {
+                Error::Metadata(format!("No event of {} has index {}", self.name, index))
+            })?
+        };
+
+        let fields = event
+            .args
+            .iter()
+            .map(|arg| {
+                let value = self.decode_type(arg.ty.id, &mut data, &event.label)?;
+                Ok((arg.label.clone(), value))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(DecodedInkEvent {
+            label: event.label.clone(),
+            fields,
+        })
+    }
+
+    fn encode_input(&self, entry: &InkMessage, args: &[Value]) -> Result<Vec<u8>> {
+        if args.len() != entry.args.len() {
+            return Err(Error::Encoding(format!(
+                "'{}' takes {} arguments, got {}",
+                entry.label,
+                entry.args.len(),
+                args.len()
+            )));
+        }
+
+        let mut input = entry.selector.to_vec();
+        for (arg, value) in entry.args.iter().zip(args) {
+            encode_as_type(value, arg.ty.id, &self.registry, &mut input).map_err(|e| {
+                Error::Encoding(format!(
+                    "Failed to encode argument '{}' of '{}': {}",
+                    arg.label, entry.label, e
+                ))
+            })?;
+        }
+        Ok(input)
+    }
+
+    fn decode_type(&self, ty: u32, data: &mut &[u8], context: &str) -> Result<Value> {
+        decode_as_type(data, ty, &self.registry)
+            .map(Value::remove_context)
+            .map_err(|e| Error::Encoding(format!("Failed to decode '{}': {}", context, e)))
+    }
+}
+
+/// A `Contracts::ContractEmitted` event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InkEventRecord {
+    /// Emitting contract
+    pub contract: [u8; 32],
+    /// SCALE-encoded event data
+    pub data: Vec<u8>,
+    /// Event topics
+    pub topics: Vec<[u8; 32]>,
+}
+
+/// A contract event decoded with the contract's metadata
+#[derive(Debug, Clone)]
+pub struct DecodedInkEvent {
+    /// Event name
+    pub label: String,
+    /// Event fields, in declaration order
+    pub fields: Vec<(String, Value)>,
+}
+
+impl DecodedInkEvent {
+    /// Look up a field by name
+    pub fn field(&self, label: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == label)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Outcome of a finalized contract call
+#[derive(Debug, Clone)]
+pub struct InkCallReceipt {
+    /// Extrinsic hash
+    pub tx_hash: String,
+    /// Called contract
+    pub contract: [u8; 32],
+    /// Events emitted by any contract during the call
+    pub events: Vec<InkEventRecord>,
+}
+
+impl InkCallReceipt {
+    /// Decode the events emitted by the called contract
+    pub fn decode_events(&self, bundle: &InkBundle) -> Result<Vec<DecodedInkEvent>> {
+        self.events
+            .iter()
+            .filter(|record| record.contract == self.contract)
+            .map(|record| bundle.decode_event(record))
+            .collect()
+    }
+}
+
+/// Gas and storage deposit needed by a contract call, from a dry run
+#[derive(Debug, Clone, Copy)]
+pub struct InkGasEstimate {
+    /// Weight to use as the call's gas limit
+    pub gas_limit: GasLimit,
+    /// Storage deposit charged to the caller
+    pub storage_deposit: u128,
+}
+
+impl InkGasEstimate {
+    /// Increase both weight components and the storage deposit by `percent`
+    pub fn with_margin(self, percent: u64) -> Self {
+        let scale = |v: u64| v.saturating_add(v.saturating_mul(percent) / 100);
+        Self {
+            gas_limit: GasLimit::new(
+                scale(self.gas_limit.ref_time),
+                scale(self.gas_limit.proof_size),
+            ),
+            storage_deposit: self
+                .storage_deposit
+                .saturating_add(self.storage_deposit.saturating_mul(percent as u128) / 100),
+        }
+    }
+
+    fn weight_value(&self) -> Value {
+        Value::named_composite([
+            ("ref_time", Value::u128(self.gas_limit.ref_time as u128)),
+            ("proof_size", Value::u128(self.gas_limit.proof_size as u128)),
+        ])
+    }
+}
+
+/// Result of a `ContractsApi` dry run
+struct DryRun {
+    estimate: InkGasEstimate,
+    data: Vec<u8>,
+    reverted: bool,
+}
+
+/// High-level API for ink! contract lifecycle on pallet-contracts
+pub struct InkContractManager<'a, S: Signer<PolkadotConfig>> {
+    adapter: &'a SubstrateAdapter,
+    signer: S,
+}
+
+impl<'a, S: Signer<PolkadotConfig>> InkContractManager<'a, S> {
+    pub fn new(adapter: &'a SubstrateAdapter, signer: S) -> Self {
+        Self { adapter, signer }
+    }
+
+    /// Upload Wasm code and instantiate a contract
+    ///
+    /// Gas and storage deposit are dry-run estimated; `gas_limit` overrides
+    /// `ref_time`.
+    pub async fn deploy(
+        &self,
+        code: Vec<u8>,
+        constructor_data: Vec<u8>,
+        salt: [u8; 32],
+        value: u128,
+        gas_limit: Option<u64>,
+    ) -> Result<Address> {
+        info!(
+            operation = "contract_deploy",
+            wallet = %self.signer.account_id(),
+            code_len = code.len(),
+            "Deploying contract to pallet-contracts"
+        );
+
+        let mut estimate = self
+            .estimate_deploy_gas(code.clone(), constructor_data.clone(), salt, value)
+            .await?;
+        if let Some(ref_time) = gas_limit {
+            estimate.gas_limit.ref_time = ref_time;
+        }
+
+        let tx = subxt::dynamic::tx(
+            "Contracts",
+            "instantiate_with_code",
+            vec![
+                Value::u128(value),
+                estimate.weight_value(),
+                deposit_limit(estimate.storage_deposit),
+                Value::from_bytes(code),
+                Value::from_bytes(constructor_data),
+                Value::from_bytes(salt),
+            ],
+        );
+
+        let events = self
+            .adapter
+            .client()
+            .tx()
+            .sign_and_submit_then_watch_default(&tx, &self.signer)
+            .await?
+            .wait_for_finalized_success()
+            .await?;
+
+        let contract = events
+            .iter()
+            .flatten()
+            .filter(|ev| ev.pallet_name() == "Contracts" && ev.variant_name() == "Instantiated")
+            .find_map(|ev| field_bytes(&ev.field_values().ok()?, "contract", 1))
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or_else(|| {
+                Error::Transaction("Failed to extract contract address from events".to_string())
+            })?;
+
+        Ok(Address::from_account_id(
+            &contract,
+            self.adapter.config().ss58_prefix,
+        )?)
+    }
+
+    /// Deploy the code in `bundle` through one of its constructors
+    pub async fn deploy_bundle(
+        &self,
+        bundle: &InkBundle,
+        constructor: &str,
+        args: &[Value],
+        salt: [u8; 32],
+        value: u128,
+        gas_limit: Option<u64>,
+    ) -> Result<Address> {
+        let code = bundle.code().ok_or_else(|| {
+            Error::Metadata(format!("Bundle for {} carries no Wasm code", bundle.name()))
+        })?;
+        let data = bundle.encode_constructor(constructor, args)?;
+        self.deploy(code.to_vec(), data, salt, value, gas_limit)
+            .await
+    }
+
+    /// Call a deployed contract (Transaction)
+    pub async fn call(
+        &self,
+        address: &Address,
+        data: Vec<u8>,
+        value: u128,
+        gas_limit: Option<u64>,
+    ) -> Result<InkCallReceipt> {
+        info!(
+            operation = "contract_call",
+            wallet = %self.signer.account_id(),
+            contract = %address,
+            "Calling contract"
+        );
+
+        let contract = address.account_id()?;
+        let mut estimate = self.estimate_call_gas(address, data.clone(), value).await?;
+        if let Some(ref_time) = gas_limit {
+            estimate.gas_limit.ref_time = ref_time;
+        }
+
+        let tx = subxt::dynamic::tx(
+            "Contracts",
+            "call",
+            vec![
+                Value::unnamed_variant("Id", [Value::from_bytes(contract)]),
+                Value::u128(value),
+                estimate.weight_value(),
+                deposit_limit(estimate.storage_deposit),
+                Value::from_bytes(data),
+            ],
+        );
+
+        let events = self
+            .adapter
+            .client()
+            .tx()
+            .sign_and_submit_then_watch_default(&tx, &self.signer)
+            .await?
+            .wait_for_finalized_success()
+            .await?;
+
+        let records = events
+            .iter()
+            .flatten()
+            .filter(|ev| ev.pallet_name() == "Contracts" && ev.variant_name() == "ContractEmitted")
+            .filter_map(|ev| {
+                let fields = ev.field_values().ok()?;
+                Some(InkEventRecord {
+                    contract: field_bytes(&fields, "contract", 0)?.try_into().ok()?,
+                    data: field_bytes(&fields, "data", 1)?,
+                    topics: ev.topics().iter().map(|topic| topic.0).collect(),
+                })
+            })
+            .collect();
+
+        Ok(InkCallReceipt {
+            tx_hash: format!("0x{}", hex::encode(events.extrinsic_hash())),
+            contract,
+            events: records,
+        })
+    }
+
+    /// Call a message of a deployed contract, encoding arguments with `bundle`
+    pub async fn call_message(
+        &self,
+        address: &Address,
+        bundle: &InkBundle,
+        message: &str,
+        args: &[Value],
+        value: u128,
+        gas_limit: Option<u64>,
+    ) -> Result<InkCallReceipt> {
+        let data = bundle.encode_message(message, args)?;
+        self.call(address, data, value, gas_limit).await
+    }
+
+    /// Query contract state (Dry-run)
+    ///
+    /// Returns the raw return data, including when the contract reverts.
+    pub async fn read(&self, address: &Address, data: Vec<u8>, value: u128) -> Result<Vec<u8>> {
+        info!(
+            operation = "contract_read",
+            contract = %address,
+            "Reading contract state"
+        );
+
+        let dry_run = self.dry_run_call(address, data, value).await?;
+        if dry_run.reverted {
+            debug!(contract = %address, "Contract read reverted");
+        }
+        Ok(dry_run.data)
+    }
+
+    /// Dry-run a message of a deployed contract and decode its return value
+    pub async fn read_message(
+        &self,
+        address: &Address,
+        bundle: &InkBundle,
+        message: &str,
+        args: &[Value],
+    ) -> Result<Value> {
+        let data = bundle.encode_message(message, args)?;
+        let return_data = self.read(address, data, 0).await?;
+        bundle.decode_return(message, &return_data)
+    }
+
+    /// Estimate gas and storage deposit for a deployment
+    ///
+    /// Dry-runs `ContractsApi_instantiate` with the signer as origin and adds
+    /// a safety margin to the result.
+    pub async fn estimate_deploy_gas(
+        &self,
+        code: Vec<u8>,
+        constructor_data: Vec<u8>,
+        salt: [u8; 32],
+        value: u128,
+    ) -> Result<InkGasEstimate> {
+        let payload = subxt::dynamic::runtime_api_call(
+            "ContractsApi",
+            "instantiate",
+            vec![
+                self.origin(),
+                Value::u128(value),
+                Value::unnamed_variant("None", []),
+                Value::unnamed_variant("None", []),
+                Value::unnamed_variant("Upload", [Value::from_bytes(code)]),
+                Value::from_bytes(constructor_data),
+                Value::from_bytes(salt),
+            ],
+        );
+
+        let dry_run = self.dry_run(payload, "contract_deploy").await?;
+        if dry_run.reverted {
+            return Err(Error::Transaction(format!(
+                "Constructor reverted: 0x{}",
+                hex::encode(&dry_run.data)
+            )));
+        }
+        Ok(dry_run.estimate)
+    }
+
+    /// Estimate gas and storage deposit for a call
+    ///
+    /// Dry-runs `ContractsApi_call` with the signer as origin and adds a
+    /// safety margin to the result.
+    pub async fn estimate_call_gas(
+        &self,
+        address: &Address,
+        data: Vec<u8>,
+        value: u128,
+    ) -> Result<InkGasEstimate> {
+        let dry_run = self.dry_run_call(address, data, value).await?;
+        if dry_run.reverted {
+            return Err(Error::Transaction(format!(
+                "Contract call reverted: 0x{}",
+                hex::encode(&dry_run.data)
+            )));
+        }
+        Ok(dry_run.estimate)
+    }
+
+    fn origin(&self) -> Value {
+        Value::from_bytes(self.signer.account_id().0)
+    }
+
+    async fn dry_run_call(&self, address: &Address, data: Vec<u8>, value: u128) -> Result<DryRun> {
+        let payload = subxt::dynamic::runtime_api_call(
+            "ContractsApi",
+            "call",
+            vec![
+                self.origin(),
+                Value::from_bytes(address.account_id()?),
+                Value::u128(value),
+                Value::unnamed_variant("None", []),
+                Value::unnamed_variant("None", []),
+                Value::from_bytes(data),
+            ],
+        );
+
+        self.dry_run(payload, "contract_call").await
+    }
+
+    async fn dry_run(
+        &self,
+        payload: subxt::runtime_api::DynamicPayload,
+        operation: &str,
+    ) -> Result<DryRun> {
+        let result = self
+            .adapter
+            .client()
+            .runtime_api()
+            .at_latest()
+            .await?
+            .call(payload)
+            .await?
+            .to_value()
+            .map_err(|e| Error::Transaction(format!("Failed to decode dry run result: {}", e)))?;
+
+        let dry_run = parse_dry_run(&result)?;
+        debug!(
+            operation = operation,
+            ref_time = dry_run.estimate.gas_limit.ref_time,
+            proof_size = dry_run.estimate.gas_limit.proof_size,
+            storage_deposit = dry_run.estimate.storage_deposit,
+            reverted = dry_run.reverted,
+            "Estimated contract gas"
+        );
+        Ok(dry_run)
+    }
+}
+
+/// `storage_deposit_limit` argument of contract extrinsics
+fn deposit_limit(amount: u128) -> Value {
+    Value::unnamed_variant("Some", [Value::u128(amount)])
+}
+
+/// Parse a `ContractResult`, as returned by `ContractsApi_call` and
+/// `ContractsApi_instantiate`
+fn parse_dry_run<T>(result: &Value<T>) -> Result<DryRun> {
+    let weight = |field: &str| {
+        result
+            .at("gas_required")
+            .at(field)
+            .and_then(|v| v.as_u128())
+    };
+    let gas_limit = GasLimit::new(
+        weight("ref_time").unwrap_or_default() as u64,
+        weight("proof_size").unwrap_or_default() as u64,
+    );
+
+    // StorageDeposit::Charge(amount) | StorageDeposit::Refund(amount)
+    let storage_deposit = match result.at("storage_deposit").map(|v| &v.value) {
+        Some(ValueDef::Variant(variant)) if variant.name == "Charge" => variant
+            .values
+            .values()
+            .next()
+            .and_then(|v| v.as_u128())
+            .unwrap_or_default(),
+        _ => 0,
+    };
+
+    let outcome = match result.at("result").map(|v| &v.value) {
+        Some(ValueDef::Variant(variant)) => variant,
+        _ => {
+            return Err(Error::Transaction(
+                "Dry run result has no outcome".to_string(),
+            ))
+        }
+    };
+    let payload = outcome.values.values().next();
+    if outcome.name != "Ok" {
+        let debug_message = result
+            .at("debug_message")
+            .and_then(value_bytes)
+            .map(|msg| String::from_utf8_lossy(&msg).into_owned())
+            .unwrap_or_default();
+        return Err(Error::Transaction(format!(
+            "Contract dry run failed: {} {}",
+            payload.map(|v| v.to_string()).unwrap_or_default(),
+            debug_message
+        )));
+    }
+
+    // ExecReturnValue, wrapped in InstantiateReturnValue for instantiations
+    let exec = payload
+        .map(|v| v.at("result").unwrap_or(v))
+        .ok_or_else(|| Error::Transaction("Dry run result has no return value".to_string()))?;
+    let flags = exec.at("flags").and_then(|f| f.at("bits").or(Some(f)));
+    let reverted = flags.and_then(|f| f.as_u128()).unwrap_or_default() & 1 == 1;
+    let data = exec.at("data").and_then(value_bytes).unwrap_or_default();
+
+    Ok(DryRun {
+        estimate: InkGasEstimate {
+            gas_limit,
+            storage_deposit,
+        }
+        .with_margin(ESTIMATE_MARGIN_PERCENT),
+        data,
+        reverted,
+    })
+}
+
+/// Bytes of the event field `name`, or of the field at `index` in older
+/// metadata with positional fields
+fn field_bytes<T>(fields: &Composite<T>, name: &str, index: usize) -> Option<Vec<u8>> {
+    let value = match fields {
+        Composite::Named(named) => named.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+        Composite::Unnamed(values) => values.get(index),
+    }?;
+    value_bytes(value)
+}
+
+/// Flatten a byte array value, unwrapping newtypes such as `AccountId32`
+fn value_bytes<T>(value: &Value<T>) -> Option<Vec<u8>> {
+    let ValueDef::Composite(composite) = &value.value else {
+        return None;
+    };
+
+    let values: Vec<&Value<T>> = composite.values().collect();
+    if let [inner] = values.as_slice() {
+        if matches!(inner.value, ValueDef::Composite(_)) {
+            return value_bytes(inner);
+        }
+    }
+
+    values
+        .iter()
+        .map(|v| v.as_u128().and_then(|b| u8::try_from(b).ok()))
+        .collect()
+}
+
+fn decode_hex(value: &str, field: &str) -> Result<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| Error::Metadata(format!("Invalid hex in {}: {}", field, e)))
+}
+
+fn hex_array<'de, D, const N: usize>(deserializer: D) -> std::result::Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    let bytes = hex::decode(value.trim_start_matches("0x")).map_err(serde::de::Error::custom)?;
+    bytes
+        .try_into()
+        .map_err(|_| serde::de::Error::custom(format!("expected {} bytes in {}", N, value)))
+}
+
+fn optional_hex_array<'de, D, const N: usize>(
+    deserializer: D,
+) -> std::result::Result<Option<[u8; N]>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| {
+            let bytes =
+                hex::decode(value.trim_start_matches("0x")).map_err(serde::de::Error::custom)?;
+            bytes
+                .try_into()
+                .map_err(|_| serde::de::Error::custom(format!("expected {} bytes in {}", N, value)))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Trimmed-down flipper metadata, as emitted by cargo-contract for ink! 4
+    const FLIPPER_V4: &str = r#"{
+        "source": { "hash": "0x0101010101010101010101010101010101010101010101010101010101010101", "wasm": "0x0061736d01000000" },
+        "contract": { "name": "flipper", "version": "4.0.0" },
+        "spec": {
+            "constructors": [
+                { "label": "new", "selector": "0x9bae9d5e", "payable": false,
+                  "args": [{ "label": "init_value", "type": { "type": 0, "displayName": ["bool"] } }] }
+            ],
+            "messages": [
+                { "label": "flip", "selector": "0x633aa551", "mutates": true, "payable": false, "args": [],
+                  "returnType": { "type": 2, "displayName": ["ink", "MessageResult"] } },
+                { "label": "get", "selector": "0x2f865bd9", "mutates": false, "payable": false, "args": [],
+                  "returnType": { "type": 4, "displayName": ["ink", "MessageResult"] } },
+                { "label": "set", "selector": "0xe8c45eb6", "mutates": true, "payable": false,
+                  "args": [{ "label": "count", "type": { "type": 1, "displayName": ["u32"] } }],
+                  "returnType": { "type": 2, "displayName": ["ink", "MessageResult"] } }
+            ],
+            "events": [
+                { "label": "Flipped", "args": [
+                    { "label": "value", "indexed": true, "type": { "type": 0, "displayName": ["bool"] } },
+                    { "label": "count", "indexed": false, "type": { "type": 1, "displayName": ["u32"] } }
+                ] }
+            ]
+        },
+        "types": [
+            { "id": 0, "type": { "def": { "primitive": "bool" } } },
+            { "id": 1, "type": { "def": { "primitive": "u32" } } },
+            { "id": 2, "type": { "path": ["Result"], "def": { "variant": { "variants": [
+                { "index": 0, "name": "Ok", "fields": [{ "type": 5 }] },
+                { "index": 1, "name": "Err", "fields": [{ "type": 3 }] } ] } } } },
+            { "id": 3, "type": { "path": ["ink_primitives", "LangError"], "def": { "variant": { "variants": [
+                { "index": 1, "name": "CouldNotReadInput" } ] } } } },
+            { "id": 4, "type": { "path": ["Result"], "def": { "variant": { "variants": [
+                { "index": 0, "name": "Ok", "fields": [{ "type": 0 }] },
+                { "index": 1, "name": "Err", "fields": [{ "type": 3 }] } ] } } } },
+            { "id": 5, "type": { "def": { "tuple": [] } } }
+        ],
+        "version": "4"
+    }"#;
+
+    #[test]
+    fn test_parse_bundle() {
+        let bundle = InkBundle::from_json(FLIPPER_V4).unwrap();
+        assert_eq!(bundle.name(), "flipper");
+        assert_eq!(
+            bundle.code(),
+            Some(&[0x00, 0x61, 0x73, 0x6d, 1, 0, 0, 0][..])
+        );
+        assert_eq!(bundle.code_hash(), Some([1u8; 32]));
+        assert_eq!(
+            bundle.constructor("new").unwrap().selector,
+            [0x9b, 0xae, 0x9d, 0x5e]
+        );
+        assert!(bundle.message("flip").unwrap().mutates);
+        assert!(bundle.message("transfer").is_err());
+    }
+
+    #[test]
+    fn test_encode_and_decode_messages() {
+        let bundle = InkBundle::from_json(FLIPPER_V4).unwrap();
+
+        let input = bundle.encode_message("set", &[Value::u128(5)]).unwrap();
+        assert_eq!(input, vec![0xe8, 0xc4, 0x5e, 0xb6, 5, 0, 0, 0]);
+        assert!(bundle.encode_message("set", &[]).is_err());
+        assert_eq!(
+            bundle
+                .encode_constructor("new", &[Value::bool(true)])
+                .unwrap(),
+            vec![0x9b, 0xae, 0x9d, 0x5e, 1]
+        );
+
+        // Ok(true)
+        let value = bundle.decode_return("get", &[0, 1]).unwrap();
+        let ValueDef::Variant(variant) = &value.value else {
+            panic!("expected a Result variant, got {}", value);
+        };
+        assert_eq!(variant.name, "Ok");
+        assert_eq!(
+            variant.values.values().next().and_then(|v| v.as_bool()),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_decode_events() {
+        let bundle = InkBundle::from_json(FLIPPER_V4).unwrap();
+        let record = InkEventRecord {
+            contract: [7u8; 32],
+            data: vec![0, 1, 3, 0, 0, 0],
+            topics: vec![],
+        };
+
+        let event = bundle.decode_event(&record).unwrap();
+        assert_eq!(event.label, "Flipped");
+        assert_eq!(event.field("value").and_then(|v| v.as_bool()), Some(true));
+        assert_eq!(event.field("count").and_then(|v| v.as_u128()), Some(3));
+
+        let receipt = InkCallReceipt {
+            tx_hash: "0x00".to_string(),
+            contract: [8u8; 32],
+            events: vec![record],
+        };
+        assert!(receipt.decode_events(&bundle).unwrap().is_empty());
+
+        // ink! 5 identifies events by their signature topic instead
+        let v5 = FLIPPER_V4.replace(
+            r#"{ "label": "Flipped", "args""#,
+            r#"{ "label": "Flipped", "signature_topic": "0x2222222222222222222222222222222222222222222222222222222222222222", "args""#,
+        );
+        let bundle = InkBundle::from_json(&v5).unwrap();
+        let record = InkEventRecord {
+            contract: [7u8; 32],
+            data: vec![0, 9, 0, 0, 0],
+            topics: vec![[0x22; 32]],
+        };
+        let event = bundle.decode_event(&record).unwrap();
+        assert_eq!(event.field("value").and_then(|v| v.as_bool()), Some(false));
+        assert_eq!(event.field("count").and_then(|v| v.as_u128()), Some(9));
+        assert!(bundle
+            .decode_event(&InkEventRecord {
+                topics: vec![[0x33; 32]],
+                ..record
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_gas_estimate_margin() {
+        let estimate = InkGasEstimate {
+            gas_limit: GasLimit::new(1_000, 200),
+            storage_deposit: 50,
+        }
+        .with_margin(10);
+        assert_eq!(estimate.gas_limit.ref_time, 1_100);
+        assert_eq!(estimate.gas_limit.proof_size, 220);
+        assert_eq!(estimate.storage_deposit, 55);
+    }
+}
@@ -0,0 +1,1121 @@
+//! Encrypted on-disk wallet storage
+//!
+//! This module persists wallets managed by [`WalletManager`] to disk so keys
+//! survive process restarts. It provides:
+//! - Password-based encryption (scrypt key derivation + AES-256-GCM)
+//! - One JSON file per wallet inside a keystore directory
+//! - Load, save, list and delete operations
+//! - Import and export of Polkadot-JS JSON keystore files (version 3)
+//! - Whole-keystore backups in SCALE, JSON or CBOR (see [`Codec`])
+//!
+//! # Security
+//!
+//! Secret key material is only held in memory while encrypting or decrypting and
+//! is zeroed afterwards. On Unix systems the keystore directory is created with
+//! `0o700` and wallet files with `0o600` permissions.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::{Keystore, Wallet};
+//!
+//! # fn example() -> apex_sdk_substrate::Result<()> {
+//! let keystore = Keystore::open("/var/lib/my-app/keystore")?;
+//!
+//! let wallet = Wallet::new_random();
+//! keystore.save("treasury", &wallet, "correct horse battery staple")?;
+//!
+//! let restored = keystore.load("treasury", "correct horse battery staple")?;
+//! assert_eq!(restored.address(), wallet.address());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::wallet::{KeyPairType, Wallet, WalletManager};
+use crate::{Error, Result};
+use aes_gcm::{aead::Aead, Aes256Gcm, KeyInit, Nonce};
+use apex_sdk_core::codec::Codec;
+use base64::Engine as _;
+use crypto_secretbox::XSalsa20Poly1305;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+use zeroize::Zeroizing;
+
+/// Current version of the native keystore file format
+const KEYSTORE_FILE_VERSION: u32 = 1;
+
+/// Current version of the keystore backup format
+const BACKUP_VERSION: u32 = 1;
+
+/// File extension used for wallet files
+const KEYSTORE_FILE_EXTENSION: &str = "json";
+
+/// AES-GCM nonce length in bytes
+const AES_NONCE_LENGTH: usize = 12;
+
+/// Salt length in bytes (matches Polkadot-JS)
+const SALT_LENGTH: usize = 32;
+
+/// Polkadot-JS PKCS8 header preceding the secret key
+const PKCS8_HEADER: [u8; 16] = [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+
+/// Polkadot-JS PKCS8 divider between secret and public key
+const PKCS8_DIVIDER: [u8; 5] = [161, 35, 3, 33, 0];
+
+/// XSalsa20-Poly1305 nonce length in bytes
+const SECRETBOX_NONCE_LENGTH: usize = 24;
+
+/// Length of the scrypt parameter prefix in Polkadot-JS encoded payloads
+/// (salt + N + p + r, little-endian u32 each)
+const SCRYPT_PARAMS_LENGTH: usize = SALT_LENGTH + 12;
+
+/// Largest accepted scrypt `log_n` (N = 2^20)
+pub const MAX_SCRYPT_LOG_N: u8 = 20;
+
+/// Largest accepted scrypt block size `r`
+pub const MAX_SCRYPT_R: u32 = 32;
+
+/// Largest accepted scrypt parallelization `p`
+pub const MAX_SCRYPT_P: u32 = 16;
+
+/// Most memory scrypt may use to derive a key (128 * r * N bytes)
+pub const MAX_SCRYPT_MEMORY: u64 = 1 << 30;
+
+/// Scrypt key derivation parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Base-2 logarithm of the CPU/memory cost parameter N
+    pub log_n: u8,
+    /// Block size parameter
+    pub r: u32,
+    /// Parallelization parameter
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    /// Defaults match Polkadot-JS (N = 32768, r = 8, p = 1)
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl KdfParams {
+    /// Check the parameters against the accepted maxima
+    ///
+    /// Keystore files carry their own parameters, so a crafted file could
+    /// otherwise make loading it take unbounded memory and time.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |reason: String| {
+            Err(Error::Keystore(format!(
+                "Invalid scrypt parameters: {}",
+                reason
+            )))
+        };
+
+        if self.log_n == 0 || self.log_n > MAX_SCRYPT_LOG_N {
+            return invalid(format!(
+                "log_n {} outside 1..={}",
+                self.log_n, MAX_SCRYPT_LOG_N
+            ));
+        }
+        if self.r == 0 || self.r > MAX_SCRYPT_R {
+            return invalid(format!("r {} outside 1..={}", self.r, MAX_SCRYPT_R));
+        }
+        if self.p == 0 || self.p > MAX_SCRYPT_P {
+            return invalid(format!("p {} outside 1..={}", self.p, MAX_SCRYPT_P));
+        }
+        let memory = (128 * u64::from(self.r)) << self.log_n;
+        if memory > MAX_SCRYPT_MEMORY {
+            return invalid(format!(
+                "{} bytes of memory exceeds the {} byte limit",
+                memory, MAX_SCRYPT_MEMORY
+            ));
+        }
+        Ok(())
+    }
+
+    /// Derive a 32-byte encryption key from a password
+    fn derive_key(&self, password: &str, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
+        self.validate()?;
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, 32)
+            .map_err(|e| Error::Keystore(format!("Invalid scrypt parameters: {}", e)))?;
+
+        let mut key = Zeroizing::new([0u8; 32]);
+        scrypt::scrypt(password.as_bytes(), salt, &params, key.as_mut())
+            .map_err(|e| Error::Keystore(format!("Key derivation failed: {}", e)))?;
+
+        Ok(key)
+    }
+}
+
+/// On-disk representation of an encrypted wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncryptedWalletFile {
+    version: u32,
+    name: String,
+    address: String,
+    key_type: String,
+    ss58_format: u16,
+    kdf: KdfParams,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+    created_at: u64,
+}
+
+/// Backup of a whole keystore; wallets stay encrypted with their own passwords
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeystoreBackup {
+    version: u32,
+    created_at: u64,
+    wallets: Vec<EncryptedWalletFile>,
+}
+
+/// Public metadata of a stored wallet, available without the password
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeystoreEntry {
+    /// Wallet name (file stem)
+    pub name: String,
+    /// SS58-encoded address
+    pub address: String,
+    /// Key pair type
+    pub key_type: KeyPairType,
+    /// SS58 address format the wallet was saved with
+    pub ss58_format: u16,
+    /// Unix timestamp (seconds) when the wallet was saved
+    pub created_at: u64,
+}
+
+/// Polkadot-JS JSON keystore encoding descriptor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PolkadotJsEncoding {
+    content: Vec<String>,
+    #[serde(rename = "type")]
+    kind: Vec<String>,
+    version: String,
+}
+
+/// Polkadot-JS JSON keystore file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PolkadotJsKeystore {
+    encoded: String,
+    encoding: PolkadotJsEncoding,
+    address: String,
+    #[serde(default)]
+    meta: serde_json::Value,
+}
+
+/// Encrypted wallet storage backed by a directory on disk
+#[derive(Debug, Clone)]
+pub struct Keystore {
+    dir: PathBuf,
+    kdf: KdfParams,
+}
+
+impl Keystore {
+    /// Open a keystore directory, creating it if it does not exist
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| Error::Keystore(format!("Failed to create keystore directory: {}", e)))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700)).map_err(
+                |e| Error::Keystore(format!("Failed to set keystore permissions: {}", e)),
+            )?;
+        }
+
+        debug!("Opened keystore at {}", dir.display());
+
+        Ok(Self {
+            dir,
+            kdf: KdfParams::default(),
+        })
+    }
+
+    /// Set the key derivation parameters used for newly saved wallets
+    ///
+    /// Existing files keep the parameters they were written with.
+    pub fn with_kdf_params(mut self, kdf: KdfParams) -> Self {
+        self.kdf = kdf;
+        self
+    }
+
+    /// Get the keystore directory
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Check if a wallet with the given name is stored
+    pub fn contains(&self, name: &str) -> bool {
+        validate_name(name).is_ok() && self.wallet_path(name).exists()
+    }
+
+    /// Encrypt and save a wallet under the given name
+    ///
+    /// Fails if a wallet with the same name already exists.
+    pub fn save(&self, name: &str, wallet: &Wallet, password: &str) -> Result<KeystoreEntry> {
+        validate_name(name)?;
+
+        let path = self.wallet_path(name);
+        if path.exists() {
+            return Err(Error::Keystore(format!(
+                "Wallet '{}' already exists in keystore",
+                name
+            )));
+        }
+
+        let mut salt = [0u8; SALT_LENGTH];
+        rand::rng().fill_bytes(&mut salt);
+        let mut nonce = [0u8; AES_NONCE_LENGTH];
+        rand::rng().fill_bytes(&mut nonce);
+
+        let key = self.kdf.derive_key(password, &salt)?;
+        let cipher = Aes256Gcm::new_from_slice(key.as_ref())
+            .map_err(|e| Error::Keystore(format!("Failed to create cipher: {}", e)))?;
+
+        let secret = wallet.secret_bytes();
+        let ciphertext = cipher
+            .encrypt(&Nonce::from(nonce), secret.as_slice())
+            .map_err(|e| Error::Keystore(format!("Encryption failed: {}", e)))?;
+
+        let file = EncryptedWalletFile {
+            version: KEYSTORE_FILE_VERSION,
+            name: name.to_string(),
+            address: wallet.address(),
+            key_type: key_type_name(wallet.key_type()).to_string(),
+            ss58_format: wallet.ss58_prefix(),
+            kdf: self.kdf,
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+            created_at: chrono::Utc::now().timestamp().max(0) as u64,
+        };
+
+        // The check above only saves the key derivation; two concurrent saves
+        // are told apart when the file is created
+        if !self.write_file(&file)? {
+            return Err(Error::Keystore(format!(
+                "Wallet '{}' already exists in keystore",
+                name
+            )));
+        }
+
+        info!("Saved wallet '{}' to keystore", name);
+
+        entry_from_file(&file)
+    }
+
+    /// Decrypt and load a wallet by name
+    pub fn load(&self, name: &str, password: &str) -> Result<Wallet> {
+        let file = self.read_file(name)?;
+
+        if file.version != KEYSTORE_FILE_VERSION {
+            return Err(Error::Keystore(format!(
+                "Unsupported keystore file version: {}",
+                file.version
+            )));
+        }
+
+        let salt = decode_hex_field(&file.salt, "salt")?;
+        let nonce: [u8; AES_NONCE_LENGTH] = decode_hex_field(&file.nonce, "nonce")?
+            .try_into()
+            .map_err(|_| Error::Keystore("Invalid nonce length".to_string()))?;
+        let ciphertext = decode_hex_field(&file.ciphertext, "ciphertext")?;
+
+        let key = file.kdf.derive_key(password, &salt)?;
+        let cipher = Aes256Gcm::new_from_slice(key.as_ref())
+            .map_err(|e| Error::Keystore(format!("Failed to create cipher: {}", e)))?;
+
+        let secret = Zeroizing::new(
+            cipher
+                .decrypt(&Nonce::from(nonce), ciphertext.as_slice())
+                .map_err(|_| {
+                    Error::Keystore(
+                        "Decryption failed - incorrect password or corrupted data".to_string(),
+                    )
+                })?,
+        );
+
+        let wallet = Wallet::from_secret_bytes(&secret, parse_key_type(&file.key_type)?)?
+            .with_ss58_format(file.ss58_format);
+
+        if wallet.address() != file.address {
+            return Err(Error::Keystore(format!(
+                "Decrypted key does not match stored address for wallet '{}'",
+                name
+            )));
+        }
+
+        debug!("Loaded wallet '{}' from keystore", name);
+        Ok(wallet)
+    }
+
+    /// List all stored wallets
+    pub fn list(&self) -> Result<Vec<KeystoreEntry>> {
+        let entries = std::fs::read_dir(&self.dir)
+            .map_err(|e| Error::Keystore(format!("Failed to read keystore directory: {}", e)))?;
+
+        let mut wallets = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| Error::Keystore(format!("Failed to read keystore entry: {}", e)))?
+                .path();
+
+            if path.extension().and_then(|ext| ext.to_str()) != Some(KEYSTORE_FILE_EXTENSION) {
+                continue;
+            }
+
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+
+            match self.read_file(name).and_then(|file| entry_from_file(&file)) {
+                Ok(entry) => wallets.push(entry),
+                Err(e) => debug!(
+                    "Skipping unreadable keystore file {}: {}",
+                    path.display(),
+                    e
+                ),
+            }
+        }
+
+        wallets.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(wallets)
+    }
+
+    /// Delete a stored wallet
+    pub fn delete(&self, name: &str) -> Result<()> {
+        validate_name(name)?;
+
+        let path = self.wallet_path(name);
+        if !path.exists() {
+            return Err(Error::Keystore(format!(
+                "Wallet '{}' not found in keystore",
+                name
+            )));
+        }
+
+        std::fs::remove_file(&path)
+            .map_err(|e| Error::Keystore(format!("Failed to delete wallet '{}': {}", name, e)))?;
+
+        info!("Deleted wallet '{}' from keystore", name);
+        Ok(())
+    }
+
+    /// Save every wallet of a [`WalletManager`] that is not yet stored
+    ///
+    /// Returns the number of wallets written.
+    pub fn save_all(&self, manager: &WalletManager, password: &str) -> Result<usize> {
+        let mut saved = 0;
+        for name in manager.list_wallets() {
+            if self.contains(&name) {
+                continue;
+            }
+            if let Some(wallet) = manager.get_wallet(&name) {
+                self.save(&name, &wallet, password)?;
+                saved += 1;
+            }
+        }
+        Ok(saved)
+    }
+
+    /// Load every stored wallet into a [`WalletManager`]
+    ///
+    /// All wallets must be encrypted with the same password. Returns the number of
+    /// wallets loaded.
+    pub fn load_all(&self, manager: &WalletManager, password: &str) -> Result<usize> {
+        let entries = self.list()?;
+        for entry in &entries {
+            let wallet = self.load(&entry.name, password)?;
+            manager.add_wallet(entry.name.clone(), wallet);
+        }
+        Ok(entries.len())
+    }
+
+    /// Import a Polkadot-JS JSON keystore file and store it under the given name
+    ///
+    /// The same password is used to decrypt the Polkadot-JS file and to encrypt
+    /// the stored wallet.
+    pub fn import_polkadot_js(&self, name: &str, json: &str, password: &str) -> Result<Wallet> {
+        let wallet = decode_polkadot_js(json, password)?;
+        self.save(name, &wallet, password)?;
+        Ok(wallet)
+    }
+
+    /// Export a stored wallet as a Polkadot-JS JSON keystore file
+    pub fn export_polkadot_js(&self, name: &str, password: &str) -> Result<String> {
+        let wallet = self.load(name, password)?;
+        encode_polkadot_js_with(&wallet, name, password, self.kdf)
+    }
+
+    /// Encode every stored wallet as a single backup
+    ///
+    /// Wallets are copied in their encrypted form, so no password is needed and
+    /// each wallet keeps its own password and KDF parameters.
+    pub fn export_backup(&self, codec: Codec) -> Result<Vec<u8>> {
+        let wallets = self
+            .list()?
+            .iter()
+            .map(|entry| self.read_file(&entry.name))
+            .collect::<Result<Vec<_>>>()?;
+
+        let backup = KeystoreBackup {
+            version: BACKUP_VERSION,
+            created_at: chrono::Utc::now().timestamp().max(0) as u64,
+            wallets,
+        };
+
+        codec
+            .encode(&backup)
+            .map_err(|e| Error::Keystore(format!("Failed to encode backup: {}", e)))
+    }
+
+    /// Restore wallets from a backup written by [`export_backup`](Self::export_backup)
+    ///
+    /// Wallets whose name is already stored are skipped. Returns the entries
+    /// that were written.
+    pub fn import_backup(&self, data: &[u8], codec: Codec) -> Result<Vec<KeystoreEntry>> {
+        let backup: KeystoreBackup = codec
+            .decode(data)
+            .map_err(|e| Error::Keystore(format!("Failed to decode backup: {}", e)))?;
+
+        if backup.version != BACKUP_VERSION {
+            return Err(Error::Keystore(format!(
+                "Unsupported keystore backup version: {}",
+                backup.version
+            )));
+        }
+
+        let mut imported = Vec::new();
+        for file in &backup.wallets {
+            validate_name(&file.name)?;
+            if file.version != KEYSTORE_FILE_VERSION {
+                return Err(Error::Keystore(format!(
+                    "Unsupported keystore file version for wallet '{}': {}",
+                    file.name, file.version
+                )));
+            }
+            let entry = entry_from_file(file)?;
+
+            if !self.write_file(file)? {
+                debug!("Skipping wallet '{}' already in keystore", file.name);
+                continue;
+            }
+            imported.push(entry);
+        }
+
+        info!("Restored {} wallets from backup", imported.len());
+        Ok(imported)
+    }
+
+    fn wallet_path(&self, name: &str) -> PathBuf {
+        self.dir
+            .join(format!("{}.{}", name, KEYSTORE_FILE_EXTENSION))
+    }
+
+    /// Write a wallet file; `false` if the wallet already exists
+    fn write_file(&self, file: &EncryptedWalletFile) -> Result<bool> {
+        let data = serde_json::to_string_pretty(file)
+            .map_err(|e| Error::Keystore(format!("Failed to serialize wallet: {}", e)))?;
+        let path = self.wallet_path(&file.name);
+        match create_private_file(&path, data.as_bytes()) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(Error::Keystore(format!(
+                "Failed to write {}: {}",
+                path.display(),
+                e
+            ))),
+        }
+    }
+
+    fn read_file(&self, name: &str) -> Result<EncryptedWalletFile> {
+        validate_name(name)?;
+
+        let path = self.wallet_path(name);
+        let data = std::fs::read_to_string(&path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                Error::Keystore(format!("Wallet '{}' not found in keystore", name))
+            } else {
+                Error::Keystore(format!("Failed to read wallet '{}': {}", name, e))
+            }
+        })?;
+
+        serde_json::from_str(&data)
+            .map_err(|e| Error::Keystore(format!("Failed to parse wallet '{}': {}", name, e)))
+    }
+}
+
+/// Encode a wallet as a Polkadot-JS JSON keystore file (version 3)
+///
+/// Uses scrypt with Polkadot-JS default parameters and XSalsa20-Poly1305.
+pub fn encode_polkadot_js(wallet: &Wallet, name: &str, password: &str) -> Result<String> {
+    encode_polkadot_js_with(wallet, name, password, KdfParams::default())
+}
+
+fn encode_polkadot_js_with(
+    wallet: &Wallet,
+    name: &str,
+    password: &str,
+    kdf: KdfParams,
+) -> Result<String> {
+    let secret = wallet.secret_bytes();
+    let public = wallet.public_key();
+
+    // Polkadot-JS stores 64-byte secret keys: the ed25519-compatible form for
+    // sr25519 and seed ++ public key for ed25519
+    let mut secret_key = Zeroizing::new([0u8; 64]);
+    match wallet.key_type() {
+        KeyPairType::Sr25519 => {
+            secret_key.copy_from_slice(&secret[..64]);
+            multiply_scalar_by_cofactor(&mut secret_key[..32]);
+        }
+        KeyPairType::Ed25519 => {
+            secret_key[..32].copy_from_slice(&secret[..32]);
+            secret_key[32..].copy_from_slice(&public);
+        }
+    }
+
+    let mut pkcs8 = Zeroizing::new(Vec::with_capacity(
+        PKCS8_HEADER.len() + 64 + PKCS8_DIVIDER.len() + public.len(),
+    ));
+    pkcs8.extend_from_slice(&PKCS8_HEADER);
+    pkcs8.extend_from_slice(secret_key.as_ref());
+    pkcs8.extend_from_slice(&PKCS8_DIVIDER);
+    pkcs8.extend_from_slice(&public);
+
+    // Bounds log_n, so N fits in the u32 written below
+    kdf.validate()?;
+
+    let mut salt = [0u8; SALT_LENGTH];
+    rand::rng().fill_bytes(&mut salt);
+    let mut nonce = [0u8; SECRETBOX_NONCE_LENGTH];
+    rand::rng().fill_bytes(&mut nonce);
+
+    let key = kdf.derive_key(password, &salt)?;
+    let cipher = XSalsa20Poly1305::new_from_slice(key.as_ref())
+        .map_err(|e| Error::Keystore(format!("Failed to create cipher: {}", e)))?;
+    let sealed = cipher
+        .encrypt(&nonce.into(), pkcs8.as_slice())
+        .map_err(|e| Error::Keystore(format!("Encryption failed: {}", e)))?;
+
+    let mut encoded = Vec::with_capacity(SCRYPT_PARAMS_LENGTH + nonce.len() + sealed.len());
+    encoded.extend_from_slice(&salt);
+    encoded.extend_from_slice(&(1u32 << kdf.log_n).to_le_bytes());
+    encoded.extend_from_slice(&kdf.p.to_le_bytes());
+    encoded.extend_from_slice(&kdf.r.to_le_bytes());
+    encoded.extend_from_slice(&nonce);
+    encoded.extend_from_slice(&sealed);
+
+    let keystore = PolkadotJsKeystore {
+        encoded: base64::engine::general_purpose::STANDARD.encode(encoded),
+        encoding: PolkadotJsEncoding {
+            content: vec![
+                "pkcs8".to_string(),
+                key_type_name(wallet.key_type()).to_string(),
+            ],
+            kind: vec!["scrypt".to_string(), "xsalsa20-poly1305".to_string()],
+            version: "3".to_string(),
+        },
+        address: wallet.address(),
+        meta: serde_json::json!({
+            "name": name,
+            "whenCreated": chrono::Utc::now().timestamp_millis(),
+        }),
+    };
+
+    serde_json::to_string_pretty(&keystore)
+        .map_err(|e| Error::Keystore(format!("Failed to serialize keystore: {}", e)))
+}
+
+/// Decode a Polkadot-JS JSON keystore file (version 3) into a wallet
+pub fn decode_polkadot_js(json: &str, password: &str) -> Result<Wallet> {
+    let keystore: PolkadotJsKeystore = serde_json::from_str(json)
+        .map_err(|e| Error::Keystore(format!("Invalid Polkadot-JS keystore: {}", e)))?;
+
+    if keystore.encoding.version != "3" {
+        return Err(Error::Keystore(format!(
+            "Unsupported Polkadot-JS keystore version: {}",
+            keystore.encoding.version
+        )));
+    }
+
+    if keystore.encoding.kind != ["scrypt", "xsalsa20-poly1305"] {
+        return Err(Error::Keystore(format!(
+            "Unsupported Polkadot-JS encryption: {:?}",
+            keystore.encoding.kind
+        )));
+    }
+
+    let key_type = match keystore.encoding.content.get(1).map(String::as_str) {
+        Some(name) => parse_key_type(name)?,
+        None => return Err(Error::Keystore("Missing key type in keystore".to_string())),
+    };
+
+    let encoded = base64::engine::general_purpose::STANDARD
+        .decode(keystore.encoded.as_bytes())
+        .map_err(|e| Error::Keystore(format!("Invalid keystore encoding: {}", e)))?;
+
+    if encoded.len() < SCRYPT_PARAMS_LENGTH + SECRETBOX_NONCE_LENGTH {
+        return Err(Error::Keystore("Encoded keystore is too short".to_string()));
+    }
+
+    let read_u32 = |offset: usize| {
+        u32::from_le_bytes(
+            encoded[offset..offset + 4]
+                .try_into()
+                .expect("slice length checked above"),
+        )
+    };
+    let salt = &encoded[..SALT_LENGTH];
+    let n = read_u32(SALT_LENGTH);
+    let p = read_u32(SALT_LENGTH + 4);
+    let r = read_u32(SALT_LENGTH + 8);
+
+    if !n.is_power_of_two() {
+        return Err(Error::Keystore(format!(
+            "Invalid scrypt N parameter: {}",
+            n
+        )));
+    }
+
+    let kdf = KdfParams {
+        log_n: n.trailing_zeros() as u8,
+        r,
+        p,
+    };
+    kdf.validate()?;
+
+    let nonce: [u8; SECRETBOX_NONCE_LENGTH] = encoded
+        [SCRYPT_PARAMS_LENGTH..SCRYPT_PARAMS_LENGTH + SECRETBOX_NONCE_LENGTH]
+        .try_into()
+        .expect("slice length checked above");
+    let sealed = &encoded[SCRYPT_PARAMS_LENGTH + SECRETBOX_NONCE_LENGTH..];
+
+    let key = kdf.derive_key(password, salt)?;
+    let cipher = XSalsa20Poly1305::new_from_slice(key.as_ref())
+        .map_err(|e| Error::Keystore(format!("Failed to create cipher: {}", e)))?;
+    let pkcs8 = Zeroizing::new(cipher.decrypt(&nonce.into(), sealed).map_err(|_| {
+        Error::Keystore("Decryption failed - incorrect password or corrupted data".to_string())
+    })?);
+
+    let secret_end = PKCS8_HEADER.len() + 64;
+    if pkcs8.len() < secret_end + PKCS8_DIVIDER.len() || pkcs8[..PKCS8_HEADER.len()] != PKCS8_HEADER
+    {
+        return Err(Error::Keystore("Invalid PKCS8 key encoding".to_string()));
+    }
+
+    let mut secret_key = Zeroizing::new([0u8; 64]);
+    secret_key.copy_from_slice(&pkcs8[PKCS8_HEADER.len()..secret_end]);
+
+    let wallet = match key_type {
+        KeyPairType::Sr25519 => {
+            divide_scalar_by_cofactor(&mut secret_key[..32]);
+            Wallet::from_secret_bytes(secret_key.as_ref(), key_type)?
+        }
+        KeyPairType::Ed25519 => Wallet::from_secret_bytes(&secret_key[..32], key_type)?,
+    };
+
+    // Re-encode with the network prefix of the stored address when possible
+    let wallet = match ss58_prefix_of(&keystore.address) {
+        Some(prefix) => wallet.with_ss58_format(prefix),
+        None => wallet,
+    };
+
+    if wallet.address() != keystore.address && !keystore.address.is_empty() {
+        return Err(Error::Keystore(
+            "Decrypted key does not match keystore address".to_string(),
+        ));
+    }
+
+    Ok(wallet)
+}
+
+/// Validate a wallet name so it maps to a single file inside the keystore
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 128
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !name.starts_with('.');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::Keystore(format!(
+            "Invalid wallet name '{}': use letters, digits, '-', '_' or '.'",
+            name
+        )))
+    }
+}
+
+fn key_type_name(key_type: KeyPairType) -> &'static str {
+    match key_type {
+        KeyPairType::Sr25519 => "sr25519",
+        KeyPairType::Ed25519 => "ed25519",
+    }
+}
+
+fn parse_key_type(name: &str) -> Result<KeyPairType> {
+    match name {
+        "sr25519" => Ok(KeyPairType::Sr25519),
+        "ed25519" => Ok(KeyPairType::Ed25519),
+        other => Err(Error::Keystore(format!("Unsupported key type: {}", other))),
+    }
+}
+
+fn entry_from_file(file: &EncryptedWalletFile) -> Result<KeystoreEntry> {
+    Ok(KeystoreEntry {
+        name: file.name.clone(),
+        address: file.address.clone(),
+        key_type: parse_key_type(&file.key_type)?,
+        ss58_format: file.ss58_format,
+        created_at: file.created_at,
+    })
+}
+
+fn decode_hex_field(value: &str, field: &str) -> Result<Vec<u8>> {
+    hex::decode(value).map_err(|e| Error::Keystore(format!("Invalid {} encoding: {}", field, e)))
+}
+
+fn ss58_prefix_of(address: &str) -> Option<u16> {
+    use sp_core::crypto::{AccountId32, Ss58Codec};
+    AccountId32::from_ss58check_with_version(address)
+        .ok()
+        .map(|(_, format)| format.prefix())
+}
+
+/// Create a file readable only by the current user
+///
+/// The file is created with its final permissions, and creation fails with
+/// [`std::io::ErrorKind::AlreadyExists`] rather than replacing an existing
+/// file. A partly written file is removed.
+fn create_private_file(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    file.write_all(data)
+        .and_then(|()| file.sync_all())
+        .inspect_err(|_| {
+            let _ = std::fs::remove_file(path);
+        })
+}
+
+/// Multiply a little-endian scalar by the ristretto cofactor (8)
+///
+/// Converts a schnorrkel secret scalar into the ed25519-compatible form used by
+/// Polkadot-JS.
+fn multiply_scalar_by_cofactor(scalar: &mut [u8]) {
+    let mut high = 0u8;
+    for byte in scalar.iter_mut() {
+        let r = *byte & 0b1110_0000;
+        *byte = (*byte << 3) + high;
+        high = r >> 5;
+    }
+}
+
+/// Divide a little-endian scalar by the ristretto cofactor (8)
+fn divide_scalar_by_cofactor(scalar: &mut [u8]) {
+    let mut low = 0u8;
+    for byte in scalar.iter_mut().rev() {
+        let r = *byte & 0b0000_0111;
+        *byte = (*byte >> 3) + low;
+        low = r << 5;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap parameters so tests do not spend seconds in scrypt
+    const TEST_KDF: KdfParams = KdfParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    fn test_keystore() -> (tempfile::TempDir, Keystore) {
+        let dir = tempfile::tempdir().unwrap();
+        let keystore = Keystore::open(dir.path().join("keystore"))
+            .unwrap()
+            .with_kdf_params(TEST_KDF);
+        (dir, keystore)
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let (_dir, keystore) = test_keystore();
+
+        for key_type in [KeyPairType::Sr25519, KeyPairType::Ed25519] {
+            let wallet = Wallet::new_random_with_type(key_type).with_ss58_format(0);
+            let name = format!("wallet-{:?}", key_type);
+
+            let entry = keystore.save(&name, &wallet, "password").unwrap();
+            assert_eq!(entry.address, wallet.address());
+            assert_eq!(entry.key_type, key_type);
+
+            let loaded = keystore.load(&name, "password").unwrap();
+            assert_eq!(loaded.address(), wallet.address());
+            assert_eq!(loaded.ss58_prefix(), 0);
+
+            let signature = loaded.sign(b"message");
+            assert!(wallet.verify(b"message", &signature));
+        }
+    }
+
+    #[test]
+    fn test_load_wrong_password() {
+        let (_dir, keystore) = test_keystore();
+        keystore
+            .save("alice", &Wallet::new_random(), "password")
+            .unwrap();
+
+        let result = keystore.load("alice", "wrong");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("incorrect password"));
+    }
+
+    #[test]
+    fn test_list_and_delete() {
+        let (_dir, keystore) = test_keystore();
+        keystore.save("bob", &Wallet::new_random(), "pw").unwrap();
+        keystore.save("alice", &Wallet::new_random(), "pw").unwrap();
+
+        let names: Vec<_> = keystore
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(names, vec!["alice", "bob"]);
+
+        keystore.delete("alice").unwrap();
+        assert!(!keystore.contains("alice"));
+        assert!(keystore.contains("bob"));
+        assert!(keystore.delete("alice").is_err());
+    }
+
+    #[test]
+    fn test_save_duplicate_name() {
+        let (_dir, keystore) = test_keystore();
+        keystore.save("dup", &Wallet::new_random(), "pw").unwrap();
+        assert!(keystore.save("dup", &Wallet::new_random(), "pw").is_err());
+    }
+
+    #[test]
+    fn test_concurrent_saves_keep_one_wallet() {
+        let (_dir, keystore) = test_keystore();
+        let keystore = std::sync::Arc::new(keystore);
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let keystore = keystore.clone();
+                std::thread::spawn(move || {
+                    let wallet = Wallet::new_random();
+                    keystore
+                        .save("race", &wallet, "pw")
+                        .ok()
+                        .map(|_| wallet.address())
+                })
+            })
+            .collect();
+        let saved: Vec<String> = handles
+            .into_iter()
+            .filter_map(|handle| handle.join().unwrap())
+            .collect();
+
+        assert_eq!(saved.len(), 1);
+        assert_eq!(keystore.load("race", "pw").unwrap().address(), saved[0]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wallet_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_dir, keystore) = test_keystore();
+        keystore
+            .save("private", &Wallet::new_random(), "pw")
+            .unwrap();
+
+        let mode = std::fs::metadata(keystore.wallet_path("private"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_invalid_names_rejected() {
+        let (_dir, keystore) = test_keystore();
+        let wallet = Wallet::new_random();
+
+        for name in ["", "../escape", "a/b", ".hidden"] {
+            assert!(keystore.save(name, &wallet, "pw").is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_wallet_manager_persistence() {
+        let (_dir, keystore) = test_keystore();
+
+        let manager = WalletManager::new();
+        let w1 = manager.create_wallet("one");
+        let w2 = manager.create_wallet("two");
+        assert_eq!(keystore.save_all(&manager, "pw").unwrap(), 2);
+        assert_eq!(keystore.save_all(&manager, "pw").unwrap(), 0);
+
+        let restored = WalletManager::new();
+        assert_eq!(keystore.load_all(&restored, "pw").unwrap(), 2);
+        assert_eq!(restored.get_wallet("one").unwrap().address(), w1.address());
+        assert_eq!(restored.get_wallet("two").unwrap().address(), w2.address());
+    }
+
+    #[test]
+    fn test_backup_roundtrip() {
+        let (_dir, keystore) = test_keystore();
+        let alice = Wallet::new_random();
+        let bob = Wallet::new_random_with_type(KeyPairType::Ed25519).with_ss58_format(0);
+        keystore.save("alice", &alice, "pw-a").unwrap();
+        keystore.save("bob", &bob, "pw-b").unwrap();
+
+        for codec in Codec::ALL {
+            let backup = keystore.export_backup(codec).unwrap();
+
+            let (_restore_dir, restored) = test_keystore();
+            restored
+                .save("alice", &Wallet::new_random(), "other")
+                .unwrap();
+            let imported = restored.import_backup(&backup, codec).unwrap();
+            assert_eq!(imported.len(), 1, "{}", codec);
+            assert_eq!(imported[0].name, "bob");
+
+            let loaded = restored.load("bob", "pw-b").unwrap();
+            assert_eq!(loaded.address(), bob.address());
+            assert_ne!(
+                restored.load("alice", "other").unwrap().address(),
+                alice.address()
+            );
+        }
+
+        let backup = keystore.export_backup(Codec::Json).unwrap();
+        assert!(keystore.import_backup(&backup, Codec::Cbor).is_err());
+    }
+
+    #[test]
+    fn test_polkadot_js_roundtrip() {
+        for key_type in [KeyPairType::Sr25519, KeyPairType::Ed25519] {
+            let wallet = Wallet::new_random_with_type(key_type).with_ss58_format(2);
+
+            let json = encode_polkadot_js_with(&wallet, "test", "password", TEST_KDF).unwrap();
+            let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(value["encoding"]["version"], "3");
+            assert_eq!(value["encoding"]["content"][1], key_type_name(key_type));
+            assert_eq!(value["address"], wallet.address());
+
+            let decoded = decode_polkadot_js(&json, "password").unwrap();
+            assert_eq!(decoded.address(), wallet.address());
+            assert_eq!(decoded.ss58_prefix(), 2);
+
+            assert!(decode_polkadot_js(&json, "wrong").is_err());
+        }
+    }
+
+    #[test]
+    fn test_polkadot_js_rejects_oversized_scrypt_params() {
+        let wallet = Wallet::new_random();
+        let json = encode_polkadot_js_with(&wallet, "test", "password", TEST_KDF).unwrap();
+
+        // Rewrite one little-endian u32 of the scrypt parameter prefix
+        let with_param = |offset: usize, value: u32| {
+            let mut keystore: serde_json::Value = serde_json::from_str(&json).unwrap();
+            let engine = base64::engine::general_purpose::STANDARD;
+            let mut encoded = engine
+                .decode(keystore["encoded"].as_str().unwrap())
+                .unwrap();
+            encoded[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+            keystore["encoded"] = engine.encode(encoded).into();
+            keystore.to_string()
+        };
+
+        for (offset, value) in [
+            (SALT_LENGTH, 1 << 31),
+            (SALT_LENGTH, 1 << 21),
+            (SALT_LENGTH + 4, u32::MAX),
+            (SALT_LENGTH + 4, 0),
+            (SALT_LENGTH + 8, u32::MAX),
+        ] {
+            match decode_polkadot_js(&with_param(offset, value), "password") {
+                Err(Error::Keystore(msg)) => {
+                    assert!(msg.contains("Invalid scrypt parameters"), "{}", msg)
+                }
+                other => panic!("expected a keystore error, got {:?}", other.map(|_| ())),
+            }
+        }
+
+        // N = 2^20 with r = 32 needs 4 GiB
+        let memory_hungry = KdfParams {
+            log_n: MAX_SCRYPT_LOG_N,
+            r: MAX_SCRYPT_R,
+            p: 1,
+        };
+        assert!(memory_hungry.validate().is_err());
+        assert!(KdfParams::default().validate().is_ok());
+
+        for log_n in [0, 32, u8::MAX] {
+            let kdf = KdfParams { log_n, ..TEST_KDF };
+            assert!(encode_polkadot_js_with(&wallet, "test", "password", kdf).is_err());
+        }
+    }
+
+    #[test]
+    fn test_polkadot_js_export() {
+        // Alice's development key as exported by Polkadot-JS, password "whoisalice"
+        let json = include_str!("../tests/fixtures/polkadot_js_alice.json");
+
+        let wallet = decode_polkadot_js(json, "whoisalice").unwrap();
+        assert_eq!(wallet.key_type(), KeyPairType::Sr25519);
+        assert_eq!(
+            wallet.address(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        assert!(decode_polkadot_js(json, "whoisbob").is_err());
+    }
+
+    #[test]
+    fn test_cofactor_conversion_roundtrip() {
+        let wallet = Wallet::new_random();
+        let secret = wallet.secret_bytes();
+
+        let mut scalar = [0u8; 32];
+        scalar.copy_from_slice(&secret[..32]);
+        let original = scalar;
+
+        multiply_scalar_by_cofactor(&mut scalar);
+        divide_scalar_by_cofactor(&mut scalar);
+        assert_eq!(scalar, original);
+    }
+}
@@ -0,0 +1,944 @@
+//! Ledger hardware wallet signer
+//!
+//! [`LedgerSigner`] signs Substrate extrinsics (and therefore Revive contract
+//! calls, which are submitted as extrinsics) with a key that never leaves a
+//! Ledger device running the Polkadot generic app.
+//!
+//! Device I/O goes through the [`LedgerTransport`] trait, which exchanges raw
+//! APDUs. The `ledger` feature provides [`HidTransport`] for USB devices; other
+//! transports (Speculos, Bluetooth bridges, test doubles) only need to
+//! implement `exchange`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use apex_sdk_substrate::{DerivationPath, KeyPairType, LedgerSigner};
+//!
+//! let signer = Arc::new(LedgerSigner::connect_hid(
+//!     DerivationPath::new(0, 0, 0),
+//!     KeyPairType::Ed25519,
+//!     0,
+//! )?);
+//! signer.confirm_address()?; // shown on the device screen
+//!
+//! signer.set_metadata_proof(proof);
+//! let tx_hash = signer.sign_and_submit(&client, &call, Default::default()).await?;
+//! ```
+//!
+//! # Signing
+//!
+//! A device error or a rejection by the user must not turn into a submitted
+//! extrinsic, so [`LedgerSigner`] is not a `subxt` `Signer`, whose signing
+//! cannot fail. Extrinsics are signed through
+//! [`create_signed`](LedgerSigner::create_signed) and
+//! [`sign_and_submit`](LedgerSigner::sign_and_submit), which wait for the
+//! device on a blocking thread and return its errors.
+//!
+//! The generic app verifies transactions against a metadata proof
+//! (`CheckMetadataHash`), which the SDK does not compute. Set one with
+//! [`LedgerSigner::set_metadata_proof`] before each signature; it is used for
+//! a single signature, and payloads without one are refused before anything
+//! is sent to the device.
+
+use crate::wallet::KeyPairType;
+use crate::{Error, Result};
+use apex_sdk_core::{SdkError, Signer as CoreSigner};
+use apex_sdk_types::Address;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use sp_core::crypto::{AccountId32 as SpAccountId32, Ss58AddressFormat, Ss58Codec};
+use std::sync::Arc;
+use subxt::config::polkadot::PolkadotExtrinsicParamsBuilder;
+use subxt::tx::{Payload, SubmittableTransaction};
+use subxt::utils::{AccountId32, MultiSignature};
+use subxt::{OnlineClient, PolkadotConfig};
+use tracing::{debug, info};
+
+#[cfg(feature = "ledger")]
+pub use hid::HidTransport;
+
+/// APDU class of the Polkadot generic app
+const CLA: u8 = 0xf9;
+/// Get app version
+const INS_GET_VERSION: u8 = 0x00;
+/// Get address / public key
+const INS_GET_ADDRESS: u8 = 0x01;
+/// Sign a transaction
+const INS_SIGN: u8 = 0x02;
+
+const P1_NO_CONFIRM: u8 = 0x00;
+const P1_CONFIRM: u8 = 0x01;
+
+const P1_SIGN_INIT: u8 = 0x00;
+const P1_SIGN_ADD: u8 = 0x01;
+const P1_SIGN_LAST: u8 = 0x02;
+
+/// Maximum APDU payload per chunk
+const CHUNK_SIZE: usize = 250;
+
+/// Status word for success
+const SW_OK: u16 = 0x9000;
+
+/// BIP44 coin type registered for Polkadot
+const POLKADOT_COIN_TYPE: u32 = 354;
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// A raw APDU channel to a Ledger device
+pub trait LedgerTransport: Send + Sync {
+    /// Send an APDU and return the response, including the trailing status word
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// BIP44 derivation path `m/44'/354'/account'/change'/address_index'`
+///
+/// All levels are hardened, as required by the Ledger Polkadot app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DerivationPath {
+    /// Account index
+    pub account: u32,
+    /// Change index
+    pub change: u32,
+    /// Address index
+    pub address_index: u32,
+}
+
+impl DerivationPath {
+    /// Create a derivation path
+    pub fn new(account: u32, change: u32, address_index: u32) -> Self {
+        Self {
+            account,
+            change,
+            address_index,
+        }
+    }
+
+    /// Serialize the path as five little-endian hardened `u32`s
+    fn to_bytes(self) -> [u8; 20] {
+        let levels = [
+            44,
+            POLKADOT_COIN_TYPE,
+            self.account,
+            self.change,
+            self.address_index,
+        ];
+
+        let mut out = [0u8; 20];
+        for (chunk, level) in out.chunks_exact_mut(4).zip(levels) {
+            chunk.copy_from_slice(&(level | HARDENED).to_le_bytes());
+        }
+        out
+    }
+}
+
+impl std::fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "m/44'/{}'/{}'/{}'/{}'",
+            POLKADOT_COIN_TYPE, self.account, self.change, self.address_index
+        )
+    }
+}
+
+/// Version of the app running on the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LedgerAppVersion {
+    /// Major version
+    pub major: u16,
+    /// Minor version
+    pub minor: u16,
+    /// Patch version
+    pub patch: u16,
+    /// Whether the device is locked
+    pub locked: bool,
+}
+
+/// Signer backed by a Ledger device
+pub struct LedgerSigner {
+    transport: Arc<dyn LedgerTransport>,
+    path: DerivationPath,
+    scheme: KeyPairType,
+    ss58_format: Ss58AddressFormat,
+    public_key: [u8; 32],
+    metadata_proof: Mutex<Vec<u8>>,
+}
+
+impl LedgerSigner {
+    /// Connect to a device and read the public key for `path`
+    ///
+    /// The address is not shown on the device; call
+    /// [`confirm_address`](Self::confirm_address) to have the user verify it.
+    pub fn connect(
+        transport: Arc<dyn LedgerTransport>,
+        path: DerivationPath,
+        scheme: KeyPairType,
+        ss58_prefix: u16,
+    ) -> Result<Self> {
+        let public_key =
+            get_public_key(transport.as_ref(), path, scheme, ss58_prefix, P1_NO_CONFIRM)?;
+
+        let signer = Self {
+            transport,
+            path,
+            scheme,
+            ss58_format: Ss58AddressFormat::custom(ss58_prefix),
+            public_key,
+            metadata_proof: Mutex::new(Vec::new()),
+        };
+
+        info!(
+            address = %signer.address(),
+            path = %path,
+            "Connected to Ledger account"
+        );
+
+        Ok(signer)
+    }
+
+    /// Connect to the first Ledger device found over USB HID
+    #[cfg(feature = "ledger")]
+    pub fn connect_hid(
+        path: DerivationPath,
+        scheme: KeyPairType,
+        ss58_prefix: u16,
+    ) -> Result<Self> {
+        Self::connect(Arc::new(HidTransport::open()?), path, scheme, ss58_prefix)
+    }
+
+    /// Get the version of the app running on the device
+    pub fn app_version(&self) -> Result<LedgerAppVersion> {
+        let response = exchange(self.transport.as_ref(), &apdu(INS_GET_VERSION, 0, 0, &[]))?;
+
+        if response.len() < 7 {
+            return Err(Error::Signature(format!(
+                "Unexpected version response length {}",
+                response.len()
+            )));
+        }
+
+        let read_u16 = |i: usize| u16::from_be_bytes([response[i], response[i + 1]]);
+
+        Ok(LedgerAppVersion {
+            major: read_u16(1),
+            minor: read_u16(3),
+            patch: read_u16(5),
+            locked: response[0] != 0,
+        })
+    }
+
+    /// Show the address on the device and wait for the user to approve it
+    pub fn confirm_address(&self) -> Result<String> {
+        let public_key = get_public_key(
+            self.transport.as_ref(),
+            self.path,
+            self.scheme,
+            self.ss58_format.prefix(),
+            P1_CONFIRM,
+        )?;
+
+        if public_key != self.public_key {
+            return Err(Error::Signature(
+                "Device returned a different public key than at connect time".to_string(),
+            ));
+        }
+
+        Ok(self.address())
+    }
+
+    /// Get the derivation path
+    pub fn path(&self) -> DerivationPath {
+        self.path
+    }
+
+    /// Get the signature scheme
+    pub fn scheme(&self) -> KeyPairType {
+        self.scheme
+    }
+
+    /// Get the raw public key
+    pub fn public_key(&self) -> [u8; 32] {
+        self.public_key
+    }
+
+    /// Get the SS58 address
+    pub fn address(&self) -> String {
+        SpAccountId32::from(self.public_key).to_ss58check_with_version(self.ss58_format)
+    }
+
+    /// Get the account ID
+    pub fn account_id(&self) -> AccountId32 {
+        AccountId32::from(self.public_key)
+    }
+
+    /// Set the metadata proof sent along with the next payload
+    ///
+    /// The proof is used for a single signature.
+    pub fn set_metadata_proof(&self, proof: Vec<u8>) {
+        *self.metadata_proof.lock() = proof;
+    }
+
+    /// Take the proof set for the next payload, refusing to sign without one
+    fn take_metadata_proof(&self) -> Result<Vec<u8>> {
+        let proof = std::mem::take(&mut *self.metadata_proof.lock());
+        if proof.is_empty() {
+            return Err(Error::Signature(
+                "No metadata proof set for this payload; call set_metadata_proof before signing"
+                    .to_string(),
+            ));
+        }
+        Ok(proof)
+    }
+
+    /// Sign a signer payload on the device
+    ///
+    /// Blocks until the user confirms or rejects the transaction on the
+    /// device; from async code use [`create_signed`](Self::create_signed)
+    /// instead.
+    pub fn sign_payload(&self, payload: &[u8]) -> Result<MultiSignature> {
+        let proof = self.take_metadata_proof()?;
+        sign_on_device(
+            self.transport.as_ref(),
+            self.path,
+            self.scheme,
+            payload,
+            &proof,
+        )
+    }
+
+    /// Sign a payload on a blocking thread, since the device holds the call
+    /// until the user confirms
+    async fn sign_off_runtime(&self, payload: Vec<u8>) -> Result<MultiSignature> {
+        let proof = self.take_metadata_proof()?;
+        let transport = Arc::clone(&self.transport);
+        let (path, scheme) = (self.path, self.scheme);
+
+        tokio::task::spawn_blocking(move || {
+            sign_on_device(transport.as_ref(), path, scheme, &payload, &proof)
+        })
+        .await
+        .map_err(|e| Error::Signature(format!("Ledger signing task failed: {}", e)))?
+    }
+
+    /// Create an extrinsic for `call` signed on the device
+    ///
+    /// A device error or a rejection by the user fails here, before anything
+    /// is submitted.
+    pub async fn create_signed<Call: Payload>(
+        &self,
+        client: &OnlineClient<PolkadotConfig>,
+        call: &Call,
+        params: PolkadotExtrinsicParamsBuilder<PolkadotConfig>,
+    ) -> Result<SubmittableTransaction<PolkadotConfig, OnlineClient<PolkadotConfig>>> {
+        let account_id = self.account_id();
+        let mut partial = client
+            .tx()
+            .create_partial(call, &account_id, params.build())
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to create transaction: {}", e)))?;
+        let signature = self.sign_off_runtime(partial.signer_payload()).await?;
+        Ok(partial.sign_with_account_and_signature(&account_id, &signature))
+    }
+
+    /// Sign `call` on the device and submit it, returning the transaction hash
+    pub async fn sign_and_submit<Call: Payload>(
+        &self,
+        client: &OnlineClient<PolkadotConfig>,
+        call: &Call,
+        params: PolkadotExtrinsicParamsBuilder<PolkadotConfig>,
+    ) -> Result<String> {
+        let hash = self
+            .create_signed(client, call, params)
+            .await?
+            .submit()
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to submit transaction: {}", e)))?;
+        Ok(format!("0x{}", hex::encode(hash)))
+    }
+}
+
+impl std::fmt::Debug for LedgerSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LedgerSigner")
+            .field("path", &self.path)
+            .field("scheme", &self.scheme)
+            .field("address", &self.address())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl CoreSigner for LedgerSigner {
+    /// Signs on a blocking thread, since the device holds the call until the
+    /// user confirms.
+    async fn sign_transaction(&self, tx: &[u8]) -> std::result::Result<Vec<u8>, SdkError> {
+        let signature = self.sign_off_runtime(tx.to_vec()).await?;
+
+        match signature {
+            MultiSignature::Sr25519(sig) | MultiSignature::Ed25519(sig) => Ok(sig.to_vec()),
+            _ => Err(SdkError::SignerError(
+                "Unexpected signature type from Ledger".to_string(),
+            )),
+        }
+    }
+
+    fn address(&self) -> Address {
+        Address::Substrate(LedgerSigner::address(self))
+    }
+}
+
+/// Sign a payload on the device, sending it in chunks
+fn sign_on_device(
+    transport: &dyn LedgerTransport,
+    path: DerivationPath,
+    scheme: KeyPairType,
+    payload: &[u8],
+    proof: &[u8],
+) -> Result<MultiSignature> {
+    let chunks = sign_chunks(path, payload, proof)?;
+    let p2 = scheme_byte(scheme);
+
+    debug!(
+        "Signing {} byte payload on Ledger in {} chunks",
+        payload.len(),
+        chunks.len()
+    );
+
+    let last = chunks.len() - 1;
+    let mut response = Vec::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let p1 = match i {
+            0 => P1_SIGN_INIT,
+            i if i == last => P1_SIGN_LAST,
+            _ => P1_SIGN_ADD,
+        };
+        response = exchange(transport, &apdu(INS_SIGN, p1, p2, chunk))?;
+    }
+
+    let signature = parse_signature(&response)?;
+
+    Ok(match scheme {
+        KeyPairType::Sr25519 => MultiSignature::Sr25519(signature),
+        KeyPairType::Ed25519 => MultiSignature::Ed25519(signature),
+    })
+}
+
+fn scheme_byte(scheme: KeyPairType) -> u8 {
+    match scheme {
+        KeyPairType::Ed25519 => 0x00,
+        KeyPairType::Sr25519 => 0x01,
+    }
+}
+
+fn apdu(ins: u8, p1: u8, p2: u8, data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(5 + data.len());
+    out.extend_from_slice(&[CLA, ins, p1, p2, data.len() as u8]);
+    out.extend_from_slice(data);
+    out
+}
+
+/// Exchange an APDU and strip the status word, mapping failures to errors
+fn exchange(transport: &dyn LedgerTransport, apdu: &[u8]) -> Result<Vec<u8>> {
+    let mut response = transport.exchange(apdu)?;
+
+    if response.len() < 2 {
+        return Err(Error::Signature(
+            "Truncated response from Ledger".to_string(),
+        ));
+    }
+
+    let sw_offset = response.len() - 2;
+    let status = u16::from_be_bytes([response[sw_offset], response[sw_offset + 1]]);
+    response.truncate(sw_offset);
+
+    if status == SW_OK {
+        Ok(response)
+    } else {
+        Err(Error::Signature(format!(
+            "Ledger returned 0x{:04x}: {}",
+            status,
+            status_message(status)
+        )))
+    }
+}
+
+fn status_message(status: u16) -> &'static str {
+    match status {
+        0x6986 => "transaction rejected on device",
+        0x6985 => "conditions not satisfied",
+        0x6a80 => "invalid data",
+        0x6984 => "data could not be parsed by the app",
+        0x6b00 => "wrong parameters",
+        0x6d00 | 0x6e00 | 0x6e01 => "Polkadot app is not open",
+        0x5515 => "device is locked",
+        _ => "unknown error",
+    }
+}
+
+fn get_public_key(
+    transport: &dyn LedgerTransport,
+    path: DerivationPath,
+    scheme: KeyPairType,
+    ss58_prefix: u16,
+    p1: u8,
+) -> Result<[u8; 32]> {
+    let mut data = path.to_bytes().to_vec();
+    data.extend_from_slice(&ss58_prefix.to_le_bytes());
+
+    let response = exchange(
+        transport,
+        &apdu(INS_GET_ADDRESS, p1, scheme_byte(scheme), &data),
+    )?;
+
+    response
+        .get(..32)
+        .and_then(|key| key.try_into().ok())
+        .ok_or_else(|| Error::Signature("Public key missing from Ledger response".to_string()))
+}
+
+/// Split a signing request into APDU chunks
+///
+/// The first chunk carries the derivation path; the rest carry
+/// `payload_len (u16 LE) ++ payload ++ metadata_proof`.
+fn sign_chunks(path: DerivationPath, payload: &[u8], proof: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let payload_len = u16::try_from(payload.len())
+        .map_err(|_| Error::Signature("Payload too large for Ledger".to_string()))?;
+
+    let mut message = Vec::with_capacity(2 + payload.len() + proof.len());
+    message.extend_from_slice(&payload_len.to_le_bytes());
+    message.extend_from_slice(payload);
+    message.extend_from_slice(proof);
+
+    let mut chunks = vec![path.to_bytes().to_vec()];
+    chunks.extend(message.chunks(CHUNK_SIZE).map(<[u8]>::to_vec));
+    Ok(chunks)
+}
+
+/// Extract a 64-byte signature, dropping the scheme prefix some app versions add
+fn parse_signature(response: &[u8]) -> Result<[u8; 64]> {
+    let raw = match response.len() {
+        64 => response,
+        65 => &response[1..],
+        n => {
+            return Err(Error::Signature(format!(
+                "Unexpected signature length {} from Ledger",
+                n
+            )))
+        }
+    };
+
+    let mut signature = [0u8; 64];
+    signature.copy_from_slice(raw);
+    Ok(signature)
+}
+
+#[cfg(feature = "ledger")]
+mod hid {
+    use super::LedgerTransport;
+    use crate::{Error, Result};
+    use hidapi::{HidApi, HidDevice};
+    use parking_lot::Mutex;
+
+    const LEDGER_VENDOR_ID: u16 = 0x2c97;
+    const LEDGER_USAGE_PAGE: u16 = 0xffa0;
+    const PACKET_SIZE: usize = 64;
+    const CHANNEL: u16 = 0x0101;
+    const TAG_APDU: u8 = 0x05;
+    const READ_TIMEOUT_MS: i32 = 60_000;
+
+    /// USB HID transport to a Ledger device
+    pub struct HidTransport {
+        device: Mutex<HidDevice>,
+    }
+
+    impl HidTransport {
+        /// Open the first connected Ledger device
+        pub fn open() -> Result<Self> {
+            let api = HidApi::new()
+                .map_err(|e| Error::Connection(format!("Failed to initialise HID: {}", e)))?;
+
+            let info = api
+                .device_list()
+                .find(|d| {
+                    d.vendor_id() == LEDGER_VENDOR_ID
+                        && (d.usage_page() == LEDGER_USAGE_PAGE || d.interface_number() == 0)
+                })
+                .ok_or_else(|| Error::Connection("No Ledger device found".to_string()))?;
+
+            let device = info
+                .open_device(&api)
+                .map_err(|e| Error::Connection(format!("Failed to open Ledger device: {}", e)))?;
+
+            Ok(Self {
+                device: Mutex::new(device),
+            })
+        }
+    }
+
+    impl LedgerTransport for HidTransport {
+        fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>> {
+            let device = self.device.lock();
+
+            for packet in wrap(apdu) {
+                // Leading zero is the HID report id
+                let mut report = Vec::with_capacity(PACKET_SIZE + 1);
+                report.push(0);
+                report.extend_from_slice(&packet);
+                device
+                    .write(&report)
+                    .map_err(|e| Error::Connection(format!("Ledger write failed: {}", e)))?;
+            }
+
+            let mut unwrapper = Unwrapper::default();
+            loop {
+                let mut packet = [0u8; PACKET_SIZE];
+                let read = device
+                    .read_timeout(&mut packet, READ_TIMEOUT_MS)
+                    .map_err(|e| Error::Connection(format!("Ledger read failed: {}", e)))?;
+                if read == 0 {
+                    return Err(Error::Connection(
+                        "Timed out waiting for Ledger".to_string(),
+                    ));
+                }
+                if let Some(response) = unwrapper.push(&packet[..read])? {
+                    return Ok(response);
+                }
+            }
+        }
+    }
+
+    /// Frame an APDU into HID packets
+    fn wrap(apdu: &[u8]) -> Vec<[u8; PACKET_SIZE]> {
+        let mut packets = Vec::new();
+        let mut remaining = apdu;
+        let mut sequence: u16 = 0;
+
+        loop {
+            let mut packet = [0u8; PACKET_SIZE];
+            packet[..2].copy_from_slice(&CHANNEL.to_be_bytes());
+            packet[2] = TAG_APDU;
+            packet[3..5].copy_from_slice(&sequence.to_be_bytes());
+
+            let mut offset = 5;
+            if sequence == 0 {
+                packet[5..7].copy_from_slice(&(apdu.len() as u16).to_be_bytes());
+                offset = 7;
+            }
+
+            let take = remaining.len().min(PACKET_SIZE - offset);
+            packet[offset..offset + take].copy_from_slice(&remaining[..take]);
+            remaining = &remaining[take..];
+            packets.push(packet);
+
+            if remaining.is_empty() {
+                return packets;
+            }
+            sequence += 1;
+        }
+    }
+
+    /// Reassembles HID packets into an APDU response
+    #[derive(Default)]
+    struct Unwrapper {
+        expected: Option<usize>,
+        sequence: u16,
+        data: Vec<u8>,
+    }
+
+    impl Unwrapper {
+        fn push(&mut self, packet: &[u8]) -> Result<Option<Vec<u8>>> {
+            if packet.len() < 5
+                || u16::from_be_bytes([packet[0], packet[1]]) != CHANNEL
+                || packet[2] != TAG_APDU
+            {
+                return Err(Error::Connection("Malformed Ledger HID packet".to_string()));
+            }
+
+            let sequence = u16::from_be_bytes([packet[3], packet[4]]);
+            if sequence != self.sequence {
+                return Err(Error::Connection(format!(
+                    "Out of order Ledger HID packet: expected {}, got {}",
+                    self.sequence, sequence
+                )));
+            }
+
+            let mut body = &packet[5..];
+            if sequence == 0 {
+                if body.len() < 2 {
+                    return Err(Error::Connection("Malformed Ledger HID packet".to_string()));
+                }
+                self.expected = Some(u16::from_be_bytes([body[0], body[1]]) as usize);
+                body = &body[2..];
+            }
+            self.sequence += 1;
+
+            let expected = self.expected.unwrap_or_default();
+            let take = body.len().min(expected - self.data.len());
+            self.data.extend_from_slice(&body[..take]);
+
+            Ok((self.data.len() == expected).then(|| std::mem::take(&mut self.data)))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_wrap_unwrap_roundtrip() {
+            let apdu: Vec<u8> = (0..200).map(|i| i as u8).collect();
+            let packets = wrap(&apdu);
+            assert_eq!(packets.len(), 4);
+
+            let mut unwrapper = Unwrapper::default();
+            let mut result = None;
+            for packet in &packets {
+                result = unwrapper.push(packet).unwrap();
+            }
+            assert_eq!(result.unwrap(), apdu);
+        }
+
+        #[test]
+        fn test_unwrap_rejects_out_of_order() {
+            let packets = wrap(&[0u8; 100]);
+            let mut unwrapper = Unwrapper::default();
+            assert!(unwrapper.push(&packets[1]).is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Replays canned responses and records the APDUs it was sent
+    struct MockTransport {
+        responses: Mutex<VecDeque<Vec<u8>>>,
+        sent: Mutex<Vec<Vec<u8>>>,
+    }
+
+    impl MockTransport {
+        fn new(responses: Vec<Vec<u8>>) -> Self {
+            Self {
+                responses: Mutex::new(responses.into()),
+                sent: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl LedgerTransport for MockTransport {
+        fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>> {
+            self.sent.lock().push(apdu.to_vec());
+            self.responses
+                .lock()
+                .pop_front()
+                .ok_or_else(|| Error::Connection("no response".to_string()))
+        }
+    }
+
+    fn ok(mut data: Vec<u8>) -> Vec<u8> {
+        data.extend_from_slice(&SW_OK.to_be_bytes());
+        data
+    }
+
+    fn address_response(public_key: [u8; 32]) -> Vec<u8> {
+        let mut data = public_key.to_vec();
+        data.extend_from_slice(b"5Address");
+        ok(data)
+    }
+
+    #[test]
+    fn test_derivation_path_encoding() {
+        let path = DerivationPath::new(1, 0, 2);
+        let bytes = path.to_bytes();
+
+        assert_eq!(&bytes[..4], &(44 | HARDENED).to_le_bytes());
+        assert_eq!(&bytes[4..8], &(354 | HARDENED).to_le_bytes());
+        assert_eq!(&bytes[8..12], &(1 | HARDENED).to_le_bytes());
+        assert_eq!(&bytes[16..20], &(2 | HARDENED).to_le_bytes());
+        assert_eq!(path.to_string(), "m/44'/354'/1'/0'/2'");
+    }
+
+    #[test]
+    fn test_connect_reads_public_key() {
+        let transport = Arc::new(MockTransport::new(vec![address_response([7u8; 32])]));
+        let signer = LedgerSigner::connect(
+            transport.clone(),
+            DerivationPath::default(),
+            KeyPairType::Ed25519,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(signer.public_key(), [7u8; 32]);
+        assert!(signer.address().starts_with('1'));
+
+        let sent = transport.sent.lock();
+        assert_eq!(
+            &sent[0][..5],
+            &[CLA, INS_GET_ADDRESS, P1_NO_CONFIRM, 0x00, 22]
+        );
+    }
+
+    #[test]
+    fn test_confirm_address_detects_key_mismatch() {
+        let transport = Arc::new(MockTransport::new(vec![
+            address_response([7u8; 32]),
+            address_response([8u8; 32]),
+        ]));
+        let signer = LedgerSigner::connect(
+            transport,
+            DerivationPath::default(),
+            KeyPairType::Sr25519,
+            42,
+        )
+        .unwrap();
+
+        assert!(signer.confirm_address().is_err());
+    }
+
+    #[test]
+    fn test_sign_payload_chunks() {
+        let mut signature = vec![0x01];
+        signature.extend_from_slice(&[9u8; 64]);
+
+        let transport = Arc::new(MockTransport::new(vec![
+            address_response([7u8; 32]),
+            ok(vec![]),
+            ok(vec![]),
+            ok(signature),
+        ]));
+        let signer = LedgerSigner::connect(
+            transport.clone(),
+            DerivationPath::default(),
+            KeyPairType::Sr25519,
+            42,
+        )
+        .unwrap();
+
+        signer.set_metadata_proof(vec![0xcd; 10]);
+        let sig = signer.sign_payload(&[0xab; 300]).unwrap();
+        assert!(matches!(sig, MultiSignature::Sr25519(s) if s == [9u8; 64]));
+
+        let sent = transport.sent.lock();
+        let p1s: Vec<u8> = sent[1..].iter().map(|apdu| apdu[2]).collect();
+        assert_eq!(p1s, vec![P1_SIGN_INIT, P1_SIGN_ADD, P1_SIGN_LAST]);
+    }
+
+    /// Holds the last signing chunk until the test releases it, like a
+    /// device waiting for the user
+    struct ConfirmingTransport {
+        inner: MockTransport,
+        confirm: Mutex<std::sync::mpsc::Receiver<()>>,
+    }
+
+    impl LedgerTransport for ConfirmingTransport {
+        fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>> {
+            if apdu[1] == INS_SIGN && apdu[2] == P1_SIGN_LAST {
+                self.confirm
+                    .lock()
+                    .recv_timeout(std::time::Duration::from_secs(5))
+                    .map_err(|_| Error::Connection("not confirmed".to_string()))?;
+            }
+            self.inner.exchange(apdu)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_does_not_block_runtime() {
+        let mut signature = vec![0x00];
+        signature.extend_from_slice(&[9u8; 64]);
+
+        let (confirm, confirmed) = std::sync::mpsc::channel();
+        let transport = Arc::new(ConfirmingTransport {
+            inner: MockTransport::new(vec![address_response([7u8; 32]), ok(vec![]), ok(signature)]),
+            confirm: Mutex::new(confirmed),
+        });
+        let signer = LedgerSigner::connect(
+            transport,
+            DerivationPath::default(),
+            KeyPairType::Ed25519,
+            42,
+        )
+        .unwrap();
+
+        // On this single-threaded runtime the confirmation only runs if
+        // signing leaves the worker free
+        let user = async {
+            tokio::task::yield_now().await;
+            confirm.send(()).unwrap();
+        };
+        signer.set_metadata_proof(vec![0xcd]);
+        let (signed, ()) = tokio::join!(signer.sign_transaction(&[1, 2, 3]), user);
+        assert_eq!(signed.unwrap(), vec![9u8; 64]);
+    }
+
+    #[test]
+    fn test_device_rejection() {
+        let transport = Arc::new(MockTransport::new(vec![
+            address_response([7u8; 32]),
+            ok(vec![]),
+            0x6986u16.to_be_bytes().to_vec(),
+        ]));
+        let signer = LedgerSigner::connect(
+            transport,
+            DerivationPath::default(),
+            KeyPairType::Ed25519,
+            42,
+        )
+        .unwrap();
+
+        signer.set_metadata_proof(vec![0xcd]);
+        let err = signer.sign_payload(&[1, 2, 3]).unwrap_err();
+        assert!(err.to_string().contains("rejected"));
+    }
+
+    #[test]
+    fn test_sign_requires_metadata_proof() {
+        let mut signature = vec![0x00];
+        signature.extend_from_slice(&[9u8; 64]);
+
+        let transport = Arc::new(MockTransport::new(vec![
+            address_response([7u8; 32]),
+            ok(vec![]),
+            ok(signature),
+        ]));
+        let signer = LedgerSigner::connect(
+            transport.clone(),
+            DerivationPath::default(),
+            KeyPairType::Ed25519,
+            42,
+        )
+        .unwrap();
+
+        // Refused before anything is sent to the device
+        let err = signer.sign_payload(&[1, 2, 3]).unwrap_err();
+        assert!(err.to_string().contains("metadata proof"));
+        assert_eq!(transport.sent.lock().len(), 1);
+
+        // A proof is used for a single signature
+        signer.set_metadata_proof(vec![0xcd]);
+        signer.sign_payload(&[1, 2, 3]).unwrap();
+        assert!(signer.sign_payload(&[1, 2, 3]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_returns_device_rejection() {
+        let transport = Arc::new(MockTransport::new(vec![
+            address_response([7u8; 32]),
+            ok(vec![]),
+            0x6986u16.to_be_bytes().to_vec(),
+        ]));
+        let signer = LedgerSigner::connect(
+            transport,
+            DerivationPath::default(),
+            KeyPairType::Sr25519,
+            42,
+        )
+        .unwrap();
+
+        signer.set_metadata_proof(vec![0xcd]);
+        assert!(signer.sign_transaction(&[1, 2, 3]).await.is_err());
+    }
+}
@@ -4,17 +4,29 @@
 //! It includes support for:
 //! - Connection management via WebSocket
 //! - Account and wallet management (SR25519, ED25519)
+//! - Encrypted on-disk keystore
+//! - Ledger hardware wallet signing
+//! - Multisig accounts and approvals
+//! - Cross-chain (XCM) transfers with delivery tracking
 //! - Transaction execution (extrinsics)
 //! - Storage queries
 //! - Connection pooling
 //! - Caching
+//! - Shared finalized-block event bus
+//! - GraphQL queries over indexed data (`graphql` feature)
 //! - Metrics collection
+//! - Optional span and error recording through an `ObservabilityFacade`
+//!   (`observability` feature)
 
 use apex_sdk_core::{
-    BlockInfo, Broadcaster, ConfirmationStrategy, NonceManager, Provider as CoreProvider,
-    ReceiptWatcher, SdkError,
+    AssetPolicyError, BlockInfo, Broadcaster, ConfirmationStrategy, ErrorCode, HasErrorCode,
+    NonceManager, Provider as CoreProvider, ReceiptWatcher, SdkError, TxInclusion, TxLookup,
+    TxSearch,
+};
+use apex_sdk_types::{
+    Address, BlockHash, ChainInfo, ChainProperties, ChainRegistry, IntoAddress, TransactionStatus,
+    TxHash, TxStatus, ValidationError, GENERIC_SS58_PREFIX,
 };
-use apex_sdk_types::{Address, TransactionStatus, TxStatus};
 use async_trait::async_trait;
 use std::sync::Arc;
 use subxt::{OnlineClient, PolkadotConfig};
@@ -22,48 +34,155 @@ use thiserror::Error;
 use tokio::sync::OnceCell;
 use tracing::{debug, info};
 
+pub mod activity;
 pub mod assets;
+pub mod batch_signing;
 pub mod block;
 pub mod cache;
+pub mod chain_client;
+pub mod constants;
 pub mod contracts;
+pub mod crowdloan;
+pub mod deposits;
+#[cfg(feature = "observability")]
+pub mod error_classification;
+pub mod event_bus;
+pub mod event_filter;
+#[cfg(feature = "fault-injection")]
+pub mod fault;
 pub mod fee_estimator;
+pub mod governance;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod indexer;
+pub mod ink;
+pub mod keystore;
+pub mod ledger;
 pub mod metrics;
 pub mod monitor;
+pub mod multisig;
 pub mod nft;
 pub mod nonce_manager;
 pub mod pool;
+pub mod query_planner;
 pub mod signer;
+pub mod signing_log;
+pub mod staking;
 pub mod storage;
+pub mod summary;
 pub mod transaction;
 pub mod wallet;
 pub mod xcm;
+pub mod xcm_manager;
 
 #[cfg(feature = "typed")]
 pub mod metadata;
 
-pub use assets::AssetManager;
-pub use block::BlockQuery;
+/// Run an adapter call through the attached observability instrumentation, if any
+macro_rules! observed {
+    ($adapter:expr, $operation:ident, $name:literal, $call:expr) => {{
+        #[cfg(feature = "observability")]
+        let result = match &$adapter.instrumentation {
+            Some(instrumentation) => {
+                instrumentation
+                    .observe_classified(
+                        apex_sdk_metrics::profiling::OperationType::$operation,
+                        $name,
+                        $call,
+                        error_classification::AdapterError::classification,
+                    )
+                    .await
+            }
+            None => $call.await,
+        };
+        #[cfg(not(feature = "observability"))]
+        let result = $call.await;
+        result
+    }};
+}
+
+pub use activity::{UnexpectedActivity, WalletActivityWatcher};
+pub use assets::{AssetDetails, AssetManager, AssetMetadata, HubAssetId};
+pub use batch_signing::{
+    BatchSignature, SignatureBundle, SignedPayload, SigningBatch, SigningRequest,
+};
+pub use block::{BlockOptions, BlockQuery};
 pub use cache::{Cache, CacheConfig};
+pub use chain_client::{ChainClient, FinalizedHead, StorageKey, StorageValue, SubxtClient};
+pub use constants::{
+    AssetDeposits, ConstantInfo, ConstantsManager, MultisigDeposits, ProxyDeposits,
+    RuntimeConstants,
+};
 pub use contracts::{
     parse_metadata, ContractCallBuilder, ContractClient, ContractMetadata, GasLimit,
     StorageDepositLimit,
 };
+pub use crowdloan::{
+    AuctionInfo, AuctionPhase, Bid, Contribution, Crowdloan, CrowdloanManager, FundStatus,
+    LastContribution, Lease, LeasePeriods,
+};
+pub use deposits::{Deposit, DepositCalculator, DepositComponent, DepositOperation};
+#[cfg(feature = "observability")]
+pub use error_classification::{
+    categorize_dispatch_error, categorize_module_error, categorize_subxt_error,
+};
+pub use event_bus::{
+    BestBlock, BusReceiver, ConsumerStats, Delivery, DeliveryPolicy, EventBus, FinalizedBlock,
+    ResumeToken, SubscriptionHealth,
+};
+pub use event_filter::{EventFilter, EventScanner};
 pub use fee_estimator::{
-    CongestionLevel, DynamicFeeEstimator, FeeAccuracyMetric, FeeAccuracyStats, FeeEstimate,
-    FeeStrategy, NetworkCongestion, Weight,
+    BlockLimits, BlockUsage, ClassUtilization, CongestionLevel, DispatchClass, DynamicFeeEstimator,
+    FeeAccuracyMetric, FeeAccuracyStats, FeeEstimate, FeeStrategy, FeeStrategyConfig,
+    InclusionLatencies, InclusionStats, NetworkCongestion, Weight,
+};
+pub use governance::{
+    AccountVote, Conviction, DecisionPhase, GovernanceManager, Referendum, ReferendumInfo,
+    ReferendumOutcome, Tally, TrackInfo, Verdict,
+};
+pub use indexer::{
+    CallbackSink, ChannelSink, IndexSink, IndexStats, IndexedBlock, IndexedEvent, IndexedExtrinsic,
+    Indexer, JsonLinesSink,
 };
+#[cfg(feature = "sqlite")]
+pub use indexer::{SqliteRetention, SqliteSink};
+pub use ink::{
+    DecodedInkEvent, InkBundle, InkCallReceipt, InkContractManager, InkEventRecord, InkGasEstimate,
+};
+pub use keystore::{KdfParams, Keystore, KeystoreEntry};
+#[cfg(feature = "ledger")]
+pub use ledger::HidTransport;
+pub use ledger::{DerivationPath, LedgerAppVersion, LedgerSigner, LedgerTransport};
 pub use metrics::{Metrics, MetricsSnapshot};
+pub use multisig::{
+    MultisigAccount, MultisigManager, MultisigProposal, PendingMultisig, Timepoint,
+};
 pub use nft::NftManager;
 pub use nonce_manager::SubstrateNonceManager;
-pub use pool::{ConnectionPool, PoolConfig};
-pub use signer::{ApexSigner, Ed25519Signer, Sr25519Signer};
-pub use storage::{AccountInfo, StorageClient, StorageQuery};
-pub use transaction::{BatchCall, BatchMode, FeeConfig, RetryConfig, TransactionExecutor};
+pub use pool::{ConnectionPool, EndpointRole, OperationClass, PoolConfig, PoolEndpoint};
+pub use query_planner::{QueryPlan, QueryPlanner, ReadRequest, ReadValue};
+pub use signer::{
+    ApexSigner, Ed25519Signer, FreezeSwitch, ScopedSigner, SigningScope, Sr25519Signer,
+};
+pub use signing_log::{SigningLog, SigningReceipt};
+pub use staking::{
+    ActiveEra, Nominations, NominatorExposure, PendingReward, RewardDestination, StakingLedger,
+    StakingManager, UnlockChunk,
+};
+pub use storage::{AccountInfo, DecodeAsType, StorageClient, StorageQuery};
+pub use summary::{summarize, SummaryArg, SummaryPart, TransactionSummary};
+pub use transaction::{
+    BatchBuilder, BatchCall, BatchItemResult, BatchItemStatus, BatchMode, BatchResult,
+    EscalationReceipt, EscalationSchedule, FeeCapExceeded, FeeConfig, FeeReservation, MortalEra,
+    Mortality, RawCall, ReceiptEvent, RetryConfig, SimulationResult, SpendingLimitExceeded,
+    SpendingLimiter, StuckTransactionHandler, TransactionExecutor, TransactionReceipt,
+};
 pub use wallet::{KeyPairType, Wallet, WalletManager};
 pub use xcm::{
-    AssetId, Fungibility, Junction, MultiLocation, NetworkId, WeightLimit, XcmAsset, XcmConfig,
-    XcmExecutor, XcmTransferType, XcmVersion,
+    AssetId, Fungibility, Junction, MultiLocation, MultiLocationBuilder, NetworkId, WeightLimit,
+    XcmAsset, XcmConfig, XcmExecutor, XcmTransferType, XcmVersion,
 };
+pub use xcm_manager::{XcmDelivery, XcmManager, XcmTransfer, XcmTransferReceipt};
 
 /// Maximum number of blocks to search when looking up transaction history
 const MAX_BLOCK_SEARCH_DEPTH: u32 = 100;
@@ -92,9 +211,27 @@ pub enum Error {
     #[error("Encoding error: {0}")]
     Encoding(String),
 
+    #[error("Keystore error: {0}")]
+    Keystore(String),
+
+    #[error("Invalid address: {0}")]
+    Address(#[from] ValidationError),
+
     #[error("Subxt error: {0}")]
     Subxt(Box<subxt::Error>),
 
+    #[error("Fee cap exceeded: {0}")]
+    FeeCapExceeded(Box<FeeCapExceeded>),
+
+    #[error("Spending limit exceeded: {0}")]
+    SpendingLimitExceeded(Box<SpendingLimitExceeded>),
+
+    #[error("No endpoint in the pool can serve {0} operations")]
+    NoEndpointForOperation(OperationClass),
+
+    #[error("Asset policy violation: {0}")]
+    AssetPolicy(#[from] AssetPolicyError),
+
     #[error("Other error: {0}")]
     Other(String),
 }
@@ -105,6 +242,28 @@ impl From<subxt::Error> for Error {
     }
 }
 
+impl HasErrorCode for Error {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::new(match self {
+            Error::Connection(_) => 1200,
+            Error::Transaction(_) => 1201,
+            Error::Metadata(_) => 1202,
+            Error::Storage(_) => 1203,
+            Error::Wallet(_) => 1204,
+            Error::Signature(_) => 1205,
+            Error::Encoding(_) => 1206,
+            Error::Keystore(_) => 1207,
+            Error::Address(_) => 1208,
+            Error::Subxt(_) => 1209,
+            Error::FeeCapExceeded(_) => 1210,
+            Error::NoEndpointForOperation(_) => 1211,
+            Error::SpendingLimitExceeded(_) => 1212,
+            Error::AssetPolicy(_) => 1213,
+            Error::Other(_) => 1299,
+        })
+    }
+}
+
 impl From<Error> for SdkError {
     fn from(err: Error) -> Self {
         match err {
@@ -115,7 +274,20 @@ impl From<Error> for SdkError {
             Error::Wallet(msg) => SdkError::SignerError(msg),
             Error::Signature(msg) => SdkError::SignerError(msg),
             Error::Encoding(msg) => SdkError::TransactionError(msg),
+            Error::Keystore(msg) => SdkError::SignerError(msg),
+            Error::Address(err) => SdkError::ConfigError(err.to_string()),
             Error::Subxt(err) => SdkError::ProviderError(err.to_string()),
+            Error::FeeCapExceeded(breakdown) => {
+                SdkError::TransactionError(format!("Fee cap exceeded: {}", breakdown))
+            }
+            Error::SpendingLimitExceeded(refusal) => {
+                SdkError::TransactionError(format!("Spending limit exceeded: {}", refusal))
+            }
+            Error::NoEndpointForOperation(class) => SdkError::ConfigError(format!(
+                "No endpoint in the pool can serve {} operations",
+                class
+            )),
+            Error::AssetPolicy(err) => err.into(),
             Error::Other(msg) => SdkError::ProviderError(msg),
         }
     }
@@ -137,51 +309,57 @@ pub struct ChainConfig {
     pub token_symbol: String,
     /// Token decimals
     pub token_decimals: u8,
+    /// Fee strategy profiles for the adapter's fee estimator
+    pub fee_strategies: FeeStrategyConfig,
+    /// How long to wait for a transaction whose confirmation strategy sets
+    /// no timeout
+    pub watch_timeout: std::time::Duration,
 }
 
 impl ChainConfig {
+    /// Create configuration for a chain in the default [`ChainRegistry`]
+    ///
+    /// Accepts registry ids, display names and [`Chain`](apex_sdk_types::Chain)
+    /// aliases, e.g. `polkadot` or `asset-hub-westend`.
+    pub fn from_registry(name: &str) -> Option<Self> {
+        ChainRegistry::new().get(name).map(Self::from)
+    }
+
+    fn known(name: &str) -> Self {
+        Self::from_registry(name).expect("chain is in the default registry")
+    }
+
     /// Create configuration for Polkadot
     pub fn polkadot() -> Self {
-        Self {
-            name: "Polkadot".to_string(),
-            endpoint: "wss://rpc.polkadot.io".to_string(),
-            ss58_prefix: 0,
-            token_symbol: "DOT".to_string(),
-            token_decimals: 10,
-        }
+        Self::known("polkadot")
     }
 
     /// Create configuration for Kusama
     pub fn kusama() -> Self {
-        Self {
-            name: "Kusama".to_string(),
-            endpoint: "wss://kusama-rpc.polkadot.io".to_string(),
-            ss58_prefix: 2,
-            token_symbol: "KSM".to_string(),
-            token_decimals: 12,
-        }
+        Self::known("kusama")
     }
 
     /// Create configuration for Westend (testnet)
     pub fn westend() -> Self {
-        Self {
-            name: "Westend".to_string(),
-            endpoint: "wss://westend-rpc.polkadot.io".to_string(),
-            ss58_prefix: 42,
-            token_symbol: "WND".to_string(),
-            token_decimals: 12,
-        }
+        Self::known("westend")
     }
 
     /// Create configuration for Paseo (testnet)
     pub fn paseo() -> Self {
-        Self {
-            name: "Paseo".to_string(),
-            endpoint: "wss://paseo.rpc.amforc.com".to_string(),
-            ss58_prefix: 42,
-            token_symbol: "PAS".to_string(),
-            token_decimals: 10,
-        }
+        Self::known("paseo")
+    }
+
+    /// Use `fee_strategies` for fee estimates on this chain
+    pub fn with_fee_strategies(mut self, fee_strategies: FeeStrategyConfig) -> Self {
+        self.fee_strategies = fee_strategies;
+        self
+    }
+
+    /// Wait `timeout` for transactions whose confirmation strategy sets no
+    /// timeout of its own
+    pub fn with_watch_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.watch_timeout = timeout;
+        self
     }
 
     /// Create custom configuration
@@ -192,6 +370,22 @@ impl ChainConfig {
             ss58_prefix,
             token_symbol: "UNIT".to_string(),
             token_decimals: 12,
+            fee_strategies: FeeStrategyConfig::default(),
+            watch_timeout: monitor::DEFAULT_WATCH_TIMEOUT,
+        }
+    }
+}
+
+impl From<&ChainInfo> for ChainConfig {
+    fn from(info: &ChainInfo) -> Self {
+        Self {
+            name: info.name.clone(),
+            endpoint: info.default_endpoint().unwrap_or_default().to_string(),
+            ss58_prefix: info.ss58_prefix.unwrap_or(GENERIC_SS58_PREFIX),
+            token_symbol: info.token_symbol.clone(),
+            token_decimals: info.token_decimals,
+            fee_strategies: FeeStrategyConfig::default(),
+            watch_timeout: monitor::DEFAULT_WATCH_TIMEOUT,
         }
     }
 }
@@ -202,14 +396,25 @@ pub struct SubstrateAdapter {
     endpoint: String,
     /// Subxt client
     client: OnlineClient<PolkadotConfig>,
+    /// Node interactions migrated off direct subxt calls
+    chain_client: Arc<dyn ChainClient>,
     /// Chain configuration
     config: ChainConfig,
     /// Connection status
     connected: bool,
     /// Metrics collector
     metrics: Metrics,
+    /// Shared finalized-block subscription
+    event_bus: Arc<EventBus>,
+    /// Fee estimator shared with transaction executors, so actual fees feed its accuracy stats
+    fee_estimator: DynamicFeeEstimator,
     /// Transaction monitor for subscription-based monitoring (lazy-initialized)
     monitor: Arc<OnceCell<Arc<monitor::TransactionMonitor>>>,
+    /// Token and address format reported by the node (lazy-initialized)
+    properties: Arc<OnceCell<ChainProperties>>,
+    /// Span and error recording for adapter calls
+    #[cfg(feature = "observability")]
+    instrumentation: Option<apex_sdk_metrics::ChainInstrumentation>,
 }
 
 impl SubstrateAdapter {
@@ -220,12 +425,19 @@ impl SubstrateAdapter {
 
     /// Connect to a Substrate node with specific chain configuration
     pub async fn connect_with_config(config: ChainConfig) -> Result<Self> {
-        info!("Connecting to {} at {}", config.name, config.endpoint);
+        info!(
+            chain = %config.name,
+            endpoint = %config.endpoint,
+            operation = "connect",
+            "Connecting to Substrate node"
+        );
 
         // Create subxt client
-        let client = OnlineClient::<PolkadotConfig>::from_url(&config.endpoint)
+        let rpc = subxt::backend::rpc::RpcClient::from_url(&config.endpoint)
             .await
             .map_err(|e| Error::Connection(format!("Failed to connect: {}", e)))?;
+        let chain_client = SubxtClient::from_rpc_client(rpc).await?;
+        let client = chain_client.online_client().clone();
 
         // Verify connection by fetching metadata
         let _metadata = client.metadata();
@@ -233,19 +445,109 @@ impl SubstrateAdapter {
 
         Ok(Self {
             endpoint: config.endpoint.clone(),
+            event_bus: Arc::new(EventBus::new(client.clone())),
+            fee_estimator: DynamicFeeEstimator::new(client.clone())
+                .with_strategies(config.fee_strategies.clone()),
+            client,
+            chain_client: Arc::new(chain_client),
+            config,
+            connected: true,
+            metrics: Metrics::new(),
+            monitor: Arc::new(OnceCell::new()),
+            properties: Arc::new(OnceCell::new()),
+            #[cfg(feature = "observability")]
+            instrumentation: None,
+        })
+    }
+
+    /// Connect through a fault-injecting RPC client
+    ///
+    /// Every RPC call made by the adapter, including the initial metadata
+    /// fetch, goes through `injector` (see [`fault`]).
+    #[cfg(feature = "fault-injection")]
+    pub async fn connect_with_fault_injector(
+        config: ChainConfig,
+        injector: Arc<apex_sdk_core::fault::FaultInjector>,
+    ) -> Result<Self> {
+        use subxt::backend::rpc::RpcClient;
+
+        info!(
+            chain = %config.name,
+            endpoint = %config.endpoint,
+            operation = "connect",
+            "Connecting to Substrate node with fault injection"
+        );
+
+        let rpc = RpcClient::from_url(&config.endpoint)
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to connect: {}", e)))?;
+        let rpc = RpcClient::new(fault::FaultInjectingRpcClient::new(
+            rpc,
+            config.endpoint.clone(),
+            injector,
+        ));
+
+        let chain_client = SubxtClient::from_rpc_client(rpc).await?;
+        let client = chain_client.online_client().clone();
+
+        Ok(Self {
+            endpoint: config.endpoint.clone(),
+            event_bus: Arc::new(EventBus::new(client.clone())),
+            fee_estimator: DynamicFeeEstimator::new(client.clone())
+                .with_strategies(config.fee_strategies.clone()),
             client,
+            chain_client: Arc::new(chain_client),
             config,
             connected: true,
             metrics: Metrics::new(),
             monitor: Arc::new(OnceCell::new()),
+            properties: Arc::new(OnceCell::new()),
+            #[cfg(feature = "observability")]
+            instrumentation: None,
         })
     }
 
+    /// Record balance queries, transaction submissions and RPC requests as
+    /// spans and errors in `facade`, labelled with this chain and endpoint
+    #[cfg(feature = "observability")]
+    pub fn with_observability(mut self, facade: apex_sdk_metrics::ObservabilityFacade) -> Self {
+        self.instrumentation = Some(apex_sdk_metrics::ChainInstrumentation::new(
+            facade,
+            self.config.name.clone(),
+            Some(&self.endpoint),
+        ));
+        self
+    }
+
     /// Get reference to the subxt client
     pub fn client(&self) -> &OnlineClient<PolkadotConfig> {
         &self.client
     }
 
+    /// Get the chain client used for storage reads and extrinsic submission
+    pub fn chain_client(&self) -> Arc<dyn ChainClient> {
+        self.chain_client.clone()
+    }
+
+    /// Restrict a signer to the calls allowed by `scope`, decoded with this
+    /// chain's metadata
+    ///
+    /// Summaries in the signer's log use the cached
+    /// [`chain_properties`](Self::chain_properties), or the chain
+    /// configuration before they are fetched.
+    pub fn scoped_signer<S>(&self, signer: S, scope: SigningScope) -> ScopedSigner<S> {
+        let properties = self
+            .properties
+            .get()
+            .cloned()
+            .unwrap_or_else(|| ChainProperties {
+                token_symbol: self.config.token_symbol.clone(),
+                token_decimals: self.config.token_decimals,
+                ss58_format: Some(self.config.ss58_prefix),
+            });
+        ScopedSigner::new(signer, scope, self.client.metadata()).with_chain_properties(properties)
+    }
+
     /// Get the endpoint URL
     pub fn endpoint(&self) -> &str {
         &self.endpoint
@@ -266,29 +568,89 @@ impl SubstrateAdapter {
         self.metrics.snapshot()
     }
 
+    /// Get the shared finalized-block event bus
+    ///
+    /// Subsystems that follow finalized blocks should subscribe here instead
+    /// of opening their own subscription (see [`event_bus`]).
+    pub fn event_bus(&self) -> Arc<EventBus> {
+        self.event_bus.clone()
+    }
+
+    /// Create a block and event indexer for this chain (see [`indexer`])
+    pub fn indexer(&self) -> Indexer {
+        Indexer::new(self.client.clone(), self.chain_client.clone())
+    }
+
     /// Get or initialize the transaction monitor
+    ///
+    /// The monitor records inclusion latencies into the shared fee estimator,
+    /// and reports its health to the observability facade's health checker
+    /// when one is attached.
     async fn get_monitor(&self) -> Result<Arc<monitor::TransactionMonitor>> {
         self.monitor
             .get_or_try_init(|| async {
-                monitor::TransactionMonitor::new(
-                    self.client.clone(),
+                let monitor = monitor::TransactionMonitor::with_inclusion_latencies(
+                    &self.event_bus,
                     Arc::new(self.metrics.clone()),
+                    self.fee_estimator.inclusion_latencies(),
                 )
-                .await
-                .map(Arc::new)
+                .await?
+                .with_watch_timeout(self.config.watch_timeout);
+
+                #[cfg(feature = "observability")]
+                let monitor = match &self.instrumentation {
+                    Some(instrumentation) => {
+                        monitor.with_health_checker(instrumentation.facade().health_checker())
+                    }
+                    None => monitor,
+                };
+
+                Ok(Arc::new(monitor))
             })
             .await
             .cloned()
     }
 
+    /// Health of the transaction monitor: its block subscriptions, the last
+    /// finalized block seen and the finality lag
+    pub async fn monitor_health(&self) -> Result<monitor::MonitorHealth> {
+        Ok(self.get_monitor().await?.health().await)
+    }
+
+    /// Follow a transaction through to finality, receiving every status change
+    ///
+    /// See [`TransactionMonitor::watch_progress`](monitor::TransactionMonitor::watch_progress).
+    pub async fn watch_transaction_progress(
+        &self,
+        tx_hash: &TxHash,
+        strategy: &ConfirmationStrategy,
+    ) -> Result<tokio::sync::mpsc::UnboundedReceiver<TransactionStatus>> {
+        let monitor = self.get_monitor().await?;
+        Ok(monitor.watch_progress(*tx_hash, strategy.clone()).await)
+    }
+
     /// Get block by hash
     ///
     /// This is more efficient than get_block if you have the block hash.
-    pub async fn get_block_by_hash(&self, block_hash: &str) -> Result<BlockInfo> {
+    pub async fn get_block_by_hash(&self, block_hash: &BlockHash) -> Result<BlockInfo> {
         let block_query = crate::block::BlockQuery::new(self.client.clone());
         block_query.get_block_by_hash(block_hash).await
     }
 
+    /// Get block by number, fetching only the parts in `options`
+    ///
+    /// [`get_block`](CoreProvider::get_block) fetches every part; use
+    /// [`BlockOptions::header_only`] and its `with_*` methods to skip the
+    /// body, events, author or finality check when rendering many blocks.
+    pub async fn get_block_with_options(
+        &self,
+        block_number: u64,
+        options: BlockOptions,
+    ) -> Result<BlockInfo> {
+        let block_query = crate::block::BlockQuery::new(self.client.clone()).with_options(options);
+        block_query.get_block_by_number(block_number).await
+    }
+
     /// Get detailed block information including extrinsics and events
     ///
     /// This provides comprehensive block data for advanced analysis.
@@ -312,156 +674,144 @@ impl SubstrateAdapter {
     }
 
     /// Get transaction status by extrinsic hash
-    pub async fn get_transaction_status(&self, tx_hash: &str) -> Result<TransactionStatus> {
+    ///
+    /// Searches the 100 most recent blocks; use
+    /// [`lookup_transaction`](Self::lookup_transaction) to search elsewhere.
+    pub async fn get_transaction_status(&self, tx_hash: &TxHash) -> Result<TransactionStatus> {
+        let lookup = self
+            .lookup_transaction(tx_hash, TxSearch::Depth(MAX_BLOCK_SEARCH_DEPTH))
+            .await?;
+        Ok(lookup.to_status(tx_hash.to_string()))
+    }
+
+    /// Look up a transaction by extrinsic hash in the blocks of `search`
+    ///
+    /// Blocks are fetched by number with `chain_getBlockHash`, so a search
+    /// can start at any height the node still has; point the adapter at an
+    /// archive node for blocks whose state a full node has pruned. Returns
+    /// where the transaction was included and whether that block is
+    /// finalized, or whether it was dropped or may still be included.
+    pub async fn lookup_transaction(&self, tx_hash: &TxHash, search: TxSearch) -> Result<TxLookup> {
+        observed!(
+            self,
+            RpcRequest,
+            "get_transaction_status",
+            self.query_transaction_status(tx_hash, search)
+        )
+    }
+
+    async fn query_transaction_status(
+        &self,
+        tx_hash: &TxHash,
+        search: TxSearch,
+    ) -> Result<TxLookup> {
         if !self.connected {
             return Err(Error::Connection("Not connected".to_string()));
         }
 
-        debug!("Getting transaction status for: {}", tx_hash);
+        debug!("Looking up transaction {} in {:?}", tx_hash, search);
         self.metrics.record_rpc_call("get_transaction_status");
 
-        // Parse the transaction hash
-        let hash_bytes = hex::decode(tx_hash.trim_start_matches("0x"))
-            .map_err(|e| Error::Transaction(format!("Invalid transaction hash: {}", e)))?;
-
-        if hash_bytes.len() != 32 {
-            return Err(Error::Transaction(
-                "Transaction hash must be 32 bytes".to_string(),
-            ));
-        }
-
-        let mut hash_array = [0u8; 32];
-        hash_array.copy_from_slice(&hash_bytes);
-
-        // Try to subscribe to finalized blocks and check recent history
-        // Note: This is a simplified implementation that checks recent finalized blocks
-        // For production, consider maintaining a transaction pool and using event subscriptions
-
-        // Get the latest finalized block
-        let latest_block = self
-            .client
-            .blocks()
-            .at_latest()
-            .await
-            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?;
-
-        let latest_number = latest_block.number();
-
-        // Search backwards through recent blocks
-        let mut blocks_to_check = vec![];
-        let start_num = latest_number.saturating_sub(MAX_BLOCK_SEARCH_DEPTH);
-
-        // Subscribe to finalized blocks and iterate backwards
-        let mut current_block = latest_block;
-        for _ in 0..MAX_BLOCK_SEARCH_DEPTH {
-            blocks_to_check.push((current_block.number(), current_block.hash()));
-
-            // Try to get parent block
-            match current_block.header().parent_hash {
-                parent_hash if current_block.number() > start_num => {
-                    match self.client.blocks().at(parent_hash).await {
-                        Ok(parent) => current_block = parent,
-                        Err(_) => break, // Can't go further back
-                    }
-                }
-                _ => break,
-            }
-        }
+        let best = self.chain_client.best_block_number().await?;
+        let Some((from, to)) = search.range(best) else {
+            return Ok(TxLookup::Pending);
+        };
 
-        // Now check all collected blocks for the transaction
-        for (block_num, block_hash) in blocks_to_check {
+        // Recent blocks first when counting back, oldest first after a hint
+        let numbers: Box<dyn Iterator<Item = u64> + Send> = match search {
+            TxSearch::Depth(_) => Box::new((from..=to).rev()),
+            TxSearch::Since { .. } => Box::new(from..=to),
+        };
+        for number in numbers {
+            let Some(hash) = self.chain_client.block_hash(number).await? else {
+                continue;
+            };
             let block = self
                 .client
                 .blocks()
-                .at(block_hash)
+                .at(subxt::utils::H256(hash))
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get block: {}", e)))?;
-
-            // Get extrinsics from the block
+                .map_err(|e| Error::Connection(format!("Failed to get block {}: {}", number, e)))?;
             let extrinsics = block
                 .extrinsics()
                 .await
                 .map_err(|e| Error::Transaction(format!("Failed to get extrinsics: {}", e)))?;
 
-            // Compute hash for each extrinsic and compare
-            for ext_details in extrinsics.iter() {
-                // ext_details is already an ExtrinsicDetails, no need for map_err
-                // Compute the hash from the extrinsic bytes
-                let ext_bytes = ext_details.bytes();
-                let computed_hash = sp_core::blake2_256(ext_bytes);
-
-                if computed_hash == hash_array {
-                    // Found the transaction! Get the extrinsic index
-                    let ext_index = ext_details.index();
-
-                    // Check events for this extrinsic
-                    let events = ext_details
-                        .events()
-                        .await
-                        .map_err(|e| Error::Transaction(format!("Failed to get events: {}", e)))?;
-
-                    let mut success = false;
-                    let mut error_msg = None;
-
-                    for event in events.iter() {
-                        let event = event.map_err(|e| {
-                            Error::Transaction(format!("Failed to decode event: {}", e))
-                        })?;
-
-                        // Check for ExtrinsicSuccess or ExtrinsicFailed
-                        if event.pallet_name() == "System" {
-                            if event.variant_name() == "ExtrinsicSuccess" {
-                                success = true;
-                            } else if event.variant_name() == "ExtrinsicFailed" {
-                                // Try to extract error details from event
-                                error_msg = Some(format!("Extrinsic {} failed", ext_index));
-                            }
-                        }
-                    }
+            let Some(extrinsic) = extrinsics
+                .iter()
+                .find(|extrinsic| extrinsic.hash().0 == *tx_hash.as_bytes())
+            else {
+                continue;
+            };
 
-                    let confirmations = latest_number - block_num;
-
-                    return if success {
-                        // For substrate, we consider a transaction confirmed once it's included in a block
-                        // The confirmation threshold is mainly for documentation purposes
-                        Ok(TransactionStatus::confirmed(
-                            tx_hash.to_string(),
-                            block_num as u64,
-                            block_hash.to_string(),
-                            None,
-                            None,
-                            Some(confirmations),
-                        ))
-                    } else if let Some(error) = error_msg {
-                        Ok(TransactionStatus::failed(tx_hash.to_string(), error))
-                    } else {
-                        // Transaction found but status unclear
-                        Ok(TransactionStatus::unknown(tx_hash.to_string()))
-                    };
+            // Events live in state, which full nodes prune for old blocks
+            let (success, error) = match extrinsic.events().await {
+                Ok(events) => self.extrinsic_outcome(&events),
+                Err(e) => {
+                    debug!("Events of block {} unavailable: {}", number, e);
+                    (None, None)
                 }
-            }
+            };
+            let inclusion = TxInclusion {
+                block_number: number,
+                block_hash: BlockHash::new(hash).to_string(),
+                extrinsic_index: extrinsic.index(),
+                confirmations: best.saturating_sub(number) as u32,
+                success,
+                error,
+            };
+
+            let finalized = self
+                .client
+                .blocks()
+                .at_latest()
+                .await
+                .map_err(|e| Error::Connection(format!("Failed to get finalized block: {}", e)))?
+                .number() as u64;
+            return Ok(if number <= finalized {
+                TxLookup::Finalized(inclusion)
+            } else {
+                TxLookup::InBlock(inclusion)
+            });
         }
 
-        // Transaction not found in recent blocks
-        Ok(TransactionStatus::unknown(tx_hash.to_string()))
+        Ok(if search.is_complete(best) {
+            TxLookup::Dropped { from, to }
+        } else {
+            TxLookup::Pending
+        })
+    }
+
+    /// Success and dispatch error from an extrinsic's `System` events
+    fn extrinsic_outcome(
+        &self,
+        events: &subxt::blocks::ExtrinsicEvents<PolkadotConfig>,
+    ) -> (Option<bool>, Option<String>) {
+        let metadata = self.client.metadata();
+        for event in events.iter().flatten() {
+            match (event.pallet_name(), event.variant_name()) {
+                ("System", "ExtrinsicSuccess") => return (Some(true), None),
+                ("System", "ExtrinsicFailed") => {
+                    let error = transaction::decode_dispatch_error(event.field_bytes(), &metadata);
+                    return (Some(false), Some(error));
+                }
+                _ => {}
+            }
+        }
+        (None, None)
     }
 
     /// Fallback polling implementation with exponential backoff
+    ///
+    /// Returns a [`TxStatus::TimedOut`] status once the strategy's timeout
+    /// passes.
     async fn wait_for_receipt_polling(
         &self,
-        tx_hash: &str,
+        tx_hash: &TxHash,
         strategy: &ConfirmationStrategy,
     ) -> std::result::Result<TransactionStatus, SdkError> {
         let start = std::time::Instant::now();
-        let timeout = match strategy {
-            ConfirmationStrategy::BlockConfirmations { timeout_secs, .. } => {
-                std::time::Duration::from_secs(*timeout_secs)
-            }
-            ConfirmationStrategy::Finalized { timeout_secs } => {
-                std::time::Duration::from_secs(*timeout_secs)
-            }
-            ConfirmationStrategy::Immediate => std::time::Duration::from_secs(30),
-        };
+        let timeout = strategy.timeout().unwrap_or(self.config.watch_timeout);
 
         let mut poll_interval = std::time::Duration::from_millis(500); // Start at 500ms
         let max_poll_interval = std::time::Duration::from_secs(5); // Max 5 seconds
@@ -509,10 +859,10 @@ impl SubstrateAdapter {
             poll_interval = std::cmp::min(poll_interval * 2, max_poll_interval);
         }
 
-        Err(SdkError::NetworkError(format!(
-            "Timeout waiting for transaction {} after {:?}",
-            tx_hash, timeout
-        )))
+        Ok(TransactionStatus::timed_out(
+            tx_hash.to_string(),
+            format!("Timed out after {:?} waiting for confirmation", timeout),
+        ))
     }
 
     /// Validate a Substrate address (SS58 format)
@@ -529,66 +879,77 @@ impl SubstrateAdapter {
     }
 
     /// Get account balance using dynamic storage queries
-    pub async fn get_balance(&self, address: &str) -> Result<u128> {
+    pub async fn get_balance(&self, address: impl IntoAddress) -> Result<u128> {
+        let address = address.into_address()?;
+        observed!(
+            self,
+            BalanceQuery,
+            "get_balance",
+            self.query_balance(&address)
+        )
+    }
+
+    async fn query_balance(&self, address: &Address) -> Result<u128> {
         if !self.connected {
             return Err(Error::Connection("Not connected".to_string()));
         }
 
-        debug!("Getting balance for address: {}", address);
+        debug!(address = %address, "Getting balance");
         self.metrics.record_rpc_call("get_balance");
 
-        // Parse SS58 address to get AccountId32
-        use sp_core::crypto::{AccountId32, Ss58Codec};
-        let account_id = AccountId32::from_ss58check(address)
-            .map_err(|e| Error::Storage(format!("Invalid SS58 address: {}", e)))?;
-
-        // Query account info from System pallet using dynamic API
-        let account_bytes: &[u8] = account_id.as_ref();
-        let storage_query = subxt::dynamic::storage(
-            "System",
-            "Account",
-            vec![subxt::dynamic::Value::from_bytes(account_bytes)],
-        );
+        let account = StorageQuery::new("System", "Account")
+            .account_id(address.account_id()?)
+            .fetch_value(&self.storage())
+            .await?;
 
-        let result = self
-            .client
-            .storage()
-            .at_latest()
-            .await
-            .map_err(|e| Error::Storage(format!("Failed to get latest block: {}", e)))?
-            .fetch(&storage_query)
-            .await
-            .map_err(|e| Error::Storage(format!("Failed to query storage: {}", e)))?;
-
-        if let Some(account_data) = result {
-            // Decode the storage value
-            let decoded = account_data
-                .to_value()
-                .map_err(|e| Error::Storage(format!("Failed to decode account data: {}", e)))?;
-
-            // Extract the free balance from the account data
+        if let Some(account) = account {
             // Account structure: { nonce, consumers, providers, sufficients, data: { free, reserved, ... } }
-            use subxt::dynamic::At as _;
+            let free_balance = storage::extract_u128(&account, &["data", "free"]).unwrap_or(0);
 
-            let free_balance = decoded
-                .at("data")
-                .and_then(|data| data.at("free"))
-                .and_then(|free| free.as_u128())
-                .unwrap_or(0);
-
-            debug!("Balance for {}: {}", address, free_balance);
+            debug!(address = %address, balance = free_balance, "Fetched balance");
             Ok(free_balance)
         } else {
             // Account doesn't exist, return 0
-            debug!("Account {} not found, returning 0 balance", address);
+            debug!(address = %address, "Account not found, returning 0 balance");
             Ok(0)
         }
     }
 
+    /// Native token and address format of the connected chain
+    ///
+    /// Fetched with the `system_properties` RPC on first use and cached.
+    /// Fields the node does not report fall back to the chain configuration.
+    pub async fn chain_properties(&self) -> Result<ChainProperties> {
+        self.properties
+            .get_or_try_init(|| async {
+                let reported = self.chain_client.system_properties().await?;
+                let configured = ChainProperties {
+                    token_symbol: self.config.token_symbol.clone(),
+                    token_decimals: self.config.token_decimals,
+                    ss58_format: Some(self.config.ss58_prefix),
+                };
+                Ok(configured.with_system_properties(&reported))
+            })
+            .await
+            .cloned()
+    }
+
+    /// Describe SCALE-encoded call data for confirmation before signing
+    ///
+    /// Amounts and accounts are shown with the connected chain's
+    /// [`chain_properties`](Self::chain_properties).
+    pub async fn summarize(&self, call_bytes: &[u8]) -> Result<TransactionSummary> {
+        let summary = summary::summarize(call_bytes, &self.client.metadata())?;
+        Ok(summary.with_properties(self.chain_properties().await?))
+    }
+
     /// Get formatted balance (with decimals)
-    pub async fn get_balance_formatted(&self, address: &str) -> Result<String> {
+    ///
+    /// Uses the token symbol and decimals from [`chain_properties`](Self::chain_properties).
+    pub async fn get_balance_formatted(&self, address: impl IntoAddress) -> Result<String> {
         let balance = self.get_balance(address).await?;
-        let decimals = self.config.token_decimals as u32;
+        let properties = self.chain_properties().await?;
+        let decimals = properties.token_decimals as u32;
         // Prevent overflow: 10u128.pow(decimals) will panic if decimals > 38
         let divisor = if decimals <= 38 {
             10u128.pow(decimals)
@@ -605,19 +966,32 @@ impl SubstrateAdapter {
             "{}.{:0width$} {}",
             whole,
             fraction,
-            self.config.token_symbol,
+            properties.token_symbol,
             width = decimals as usize
         ))
     }
 
     /// Create a storage client for querying chain storage
     pub fn storage(&self) -> StorageClient {
-        StorageClient::new(self.client.clone(), self.metrics.clone())
+        StorageClient::with_chain_client(self.chain_client.clone(), self.metrics.clone())
     }
 
     /// Create a transaction executor
     pub fn transaction_executor(&self) -> TransactionExecutor {
         TransactionExecutor::new(self.client.clone(), self.metrics.clone())
+            .with_fee_estimator(self.fee_estimator.clone())
+    }
+
+    /// Get a handler that resubmits stuck transactions with escalating tips
+    pub async fn stuck_transaction_handler(
+        &self,
+        schedule: EscalationSchedule,
+    ) -> Result<StuckTransactionHandler> {
+        Ok(StuckTransactionHandler::new(
+            self.transaction_executor(),
+            self.get_monitor().await?,
+            schedule,
+        ))
     }
 
     /// Get an asset manager for interacting with pallet-assets
@@ -625,13 +999,51 @@ impl SubstrateAdapter {
         AssetManager::new(self)
     }
 
+    /// Get typed access to the runtime constants of the connected chain
+    pub fn constants(&self) -> ConstantsManager {
+        ConstantsManager::new(self.chain_client.metadata())
+    }
+
+    /// Get a calculator for the deposits storage-creating calls reserve
+    pub fn deposits(&self) -> DepositCalculator {
+        DepositCalculator::new(self.chain_client.metadata())
+    }
+
+    /// Get a crowdloan manager for parachain crowdloans, leases and auctions
+    pub fn crowdloan(&self) -> CrowdloanManager<'_> {
+        CrowdloanManager::new(self)
+    }
+
+    /// Get a governance manager for OpenGov referenda and conviction voting
+    pub fn governance(&self) -> GovernanceManager<'_> {
+        GovernanceManager::new(self)
+    }
+
+    /// Get a multisig manager for interacting with pallet-multisig
+    pub fn multisig(&self) -> MultisigManager<'_> {
+        MultisigManager::new(self)
+    }
+
+    /// Get a staking manager for nominating through pallet-staking
+    pub fn staking(&self) -> StakingManager<'_> {
+        StakingManager::new(self)
+    }
+
+    /// Get an XCM manager for cross-chain transfers from this chain
+    pub fn xcm(&self) -> XcmManager<'_> {
+        XcmManager::new(self)
+    }
+
     /// This provides advanced fee estimation capabilities including:
     /// - Weight-based dynamic calculations
     /// - Network congestion monitoring
     /// - Multiple fee strategies (Fast, Normal, Slow)
     /// - Fee estimation accuracy metrics
+    ///
+    /// The estimator is shared with [`SubstrateAdapter::transaction_executor`],
+    /// which records the actual fee of every transfer it submits.
     pub fn fee_estimator(&self) -> DynamicFeeEstimator {
-        DynamicFeeEstimator::new(self.client.clone())
+        self.fee_estimator.clone()
     }
 
     /// Get runtime version
@@ -649,7 +1061,7 @@ impl SubstrateAdapter {
 impl apex_sdk_core::ChainAdapter for SubstrateAdapter {
     async fn get_transaction_status(
         &self,
-        tx_hash: &str,
+        tx_hash: &TxHash,
     ) -> std::result::Result<TransactionStatus, String> {
         self.get_transaction_status(tx_hash)
             .await
@@ -668,13 +1080,15 @@ impl apex_sdk_core::ChainAdapter for SubstrateAdapter {
 #[async_trait]
 impl CoreProvider for SubstrateAdapter {
     async fn get_block_number(&self) -> std::result::Result<u64, SdkError> {
-        let block = self
-            .client
-            .blocks()
-            .at_latest()
-            .await
-            .map_err(Error::from)?;
-        Ok(block.number() as u64)
+        observed!(self, RpcRequest, "get_block_number", async {
+            let block = self
+                .client
+                .blocks()
+                .at_latest()
+                .await
+                .map_err(Error::from)?;
+            Ok(block.number() as u64)
+        })
     }
 
     async fn get_balance(&self, address: &Address) -> std::result::Result<u128, SdkError> {
@@ -690,7 +1104,10 @@ impl CoreProvider for SubstrateAdapter {
         match address {
             Address::Substrate(addr) => {
                 // Use StorageClient to properly query the nonce
-                let storage_client = StorageClient::new(self.client.clone(), self.metrics.clone());
+                let storage_client = StorageClient::with_chain_client(
+                    self.chain_client.clone(),
+                    self.metrics.clone(),
+                );
 
                 storage_client.get_nonce(addr).await.map_err(SdkError::from)
             }
@@ -705,7 +1122,12 @@ impl CoreProvider for SubstrateAdapter {
         match self.transaction_executor().estimate_fee_for_bytes(tx).await {
             Ok(fee) => Ok(fee),
             Err(e) => {
-                tracing::warn!("Substrate fee estimation failed: {}", e);
+                tracing::warn!(
+                    chain = %self.config.name,
+                    operation = "estimate_fee",
+                    error = %e,
+                    "Substrate fee estimation failed, using fallback"
+                );
                 // Fallback to a reasonable default (1 million Planck)
                 Ok(1_000_000u128)
             }
@@ -716,10 +1138,13 @@ impl CoreProvider for SubstrateAdapter {
         // Use BlockQuery to fetch real blockchain data
         let block_query = crate::block::BlockQuery::new(self.client.clone());
 
-        block_query
-            .get_block_by_number(block_number)
-            .await
-            .map_err(|e| SdkError::ProviderError(format!("Failed to get block: {}", e)))
+        observed!(
+            self,
+            BlockQuery,
+            "get_block",
+            block_query.get_block_by_number(block_number)
+        )
+        .map_err(|e| SdkError::ProviderError(format!("Failed to get block: {}", e)))
     }
 
     async fn health_check(&self) -> std::result::Result<(), SdkError> {
@@ -742,7 +1167,7 @@ impl NonceManager for SubstrateAdapter {
 
 #[async_trait]
 impl Broadcaster for SubstrateAdapter {
-    async fn broadcast(&self, signed_tx: &[u8]) -> std::result::Result<String, SdkError> {
+    async fn broadcast(&self, signed_tx: &[u8]) -> std::result::Result<TxHash, SdkError> {
         if !self.connected {
             return Err(SdkError::NetworkError("Not connected to chain".to_string()));
         }
@@ -764,16 +1189,24 @@ impl Broadcaster for SubstrateAdapter {
         self.metrics.record_transaction_attempt();
         debug!("Broadcasting extrinsic ({} bytes)", signed_tx.len());
 
-        let tx_hash = self
-            .submit_and_watch_extrinsic(signed_tx)
-            .await
-            .map_err(|e| {
-                self.metrics.record_transaction_failure();
-                SdkError::TransactionError(format!("Broadcast failed: {}", e))
-            })?;
+        let tx_hash = observed!(
+            self,
+            TransactionSubmit,
+            "broadcast",
+            self.submit_and_watch_extrinsic(signed_tx)
+        )
+        .map_err(|e| {
+            self.metrics.record_transaction_failure();
+            SdkError::TransactionError(format!("Broadcast failed: {}", e))
+        })?;
 
         self.metrics.record_transaction_success();
-        info!("Extrinsic broadcast successful: {}", tx_hash);
+        info!(
+            chain = %self.config.name,
+            operation = "broadcast",
+            tx_hash = %tx_hash,
+            "Extrinsic broadcast successful"
+        );
 
         Ok(tx_hash)
     }
@@ -813,25 +1246,12 @@ impl SubstrateAdapter {
         Ok(())
     }
 
-    async fn submit_and_watch_extrinsic(&self, extrinsic_bytes: &[u8]) -> Result<String> {
-        use subxt::backend::{legacy::LegacyRpcMethods, rpc::RpcClient};
+    async fn submit_and_watch_extrinsic(&self, extrinsic_bytes: &[u8]) -> Result<TxHash> {
+        let tx_hash = TxHash::new(self.chain_client.submit_extrinsic(extrinsic_bytes).await?);
 
-        let rpc_client = RpcClient::from_url(&self.endpoint)
-            .await
-            .map_err(|e| Error::Connection(format!("Failed to create RPC client: {}", e)))?;
+        debug!("Extrinsic submitted with hash: {}", tx_hash);
 
-        let legacy_rpc = LegacyRpcMethods::<PolkadotConfig>::new(rpc_client);
-
-        let tx_hash = legacy_rpc
-            .author_submit_extrinsic(extrinsic_bytes)
-            .await
-            .map_err(|e| Error::Transaction(format!("Failed to submit extrinsic: {}", e)))?;
-
-        let hash_string = format!("0x{}", hex::encode(tx_hash.0));
-
-        debug!("Extrinsic submitted with hash: {}", hash_string);
-
-        Ok(hash_string)
+        Ok(tx_hash)
     }
 }
 
@@ -839,7 +1259,7 @@ impl SubstrateAdapter {
 impl ReceiptWatcher for SubstrateAdapter {
     async fn wait_for_receipt(
         &self,
-        tx_hash: &str,
+        tx_hash: &TxHash,
     ) -> std::result::Result<TransactionStatus, SdkError> {
         // Use default finalized strategy
         let strategy = ConfirmationStrategy::Finalized { timeout_secs: 60 };
@@ -849,7 +1269,7 @@ impl ReceiptWatcher for SubstrateAdapter {
 
     async fn wait_for_receipt_with_strategy(
         &self,
-        tx_hash: &str,
+        tx_hash: &TxHash,
         strategy: &ConfirmationStrategy,
     ) -> std::result::Result<TransactionStatus, SdkError> {
         debug!("Waiting for receipt with strategy: {:?}", strategy);
@@ -859,34 +1279,16 @@ impl ReceiptWatcher for SubstrateAdapter {
             Ok(monitor) => {
                 debug!("Using subscription-based monitoring for {}", tx_hash);
 
-                let rx = monitor
-                    .watch_transaction(tx_hash.to_string(), strategy.clone())
-                    .await;
+                let rx = monitor.watch_transaction(*tx_hash, strategy.clone()).await;
 
-                // Wait for the result with timeout
-                let timeout = match strategy {
-                    ConfirmationStrategy::BlockConfirmations { timeout_secs, .. } => {
-                        std::time::Duration::from_secs(*timeout_secs)
-                    }
-                    ConfirmationStrategy::Finalized { timeout_secs } => {
-                        std::time::Duration::from_secs(*timeout_secs)
-                    }
-                    ConfirmationStrategy::Immediate => {
-                        // For immediate, still wait a bit for the transaction to be included
-                        std::time::Duration::from_secs(30)
-                    }
-                };
-
-                match tokio::time::timeout(timeout, rx).await {
-                    Ok(Ok(status)) => {
+                // The monitor ends the watch with `TimedOut` at the strategy's timeout
+                match rx.await {
+                    Ok(status) => {
                         debug!("Subscription monitoring completed for {}", tx_hash);
                         return Ok(status);
                     }
-                    Ok(Err(_)) => {
-                        debug!("Subscription channel closed, falling back to polling");
-                    }
                     Err(_) => {
-                        debug!("Subscription monitoring timed out, falling back to polling");
+                        debug!("Subscription channel closed, falling back to polling");
                     }
                 }
             }
@@ -899,13 +1301,17 @@ impl ReceiptWatcher for SubstrateAdapter {
         }
 
         // Fallback to polling with exponential backoff
-        info!("Using polling fallback for {}", tx_hash);
+        info!(
+            chain = %self.config.name,
+            tx_hash = %tx_hash,
+            "Using polling fallback for receipt"
+        );
         self.wait_for_receipt_polling(tx_hash, strategy).await
     }
 
     async fn get_receipt_status(
         &self,
-        tx_hash: &str,
+        tx_hash: &TxHash,
     ) -> std::result::Result<Option<TransactionStatus>, SdkError> {
         match self.get_transaction_status(tx_hash).await {
             Ok(status) => Ok(Some(status)),
@@ -914,6 +1320,14 @@ impl ReceiptWatcher for SubstrateAdapter {
     }
 }
 
+/// Current Unix time in seconds
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -929,6 +1343,26 @@ mod tests {
         assert!(polkadot.endpoint.starts_with("wss://"));
     }
 
+    #[test]
+    fn test_watch_timeouts() {
+        let config = ChainConfig::polkadot();
+        assert_eq!(config.watch_timeout, monitor::DEFAULT_WATCH_TIMEOUT);
+        let config = config.with_watch_timeout(std::time::Duration::from_secs(60));
+        assert_eq!(config.watch_timeout, std::time::Duration::from_secs(60));
+
+        let finalized = ConfirmationStrategy::Finalized { timeout_secs: 90 };
+        assert_eq!(
+            finalized.timeout(),
+            Some(std::time::Duration::from_secs(90))
+        );
+        // No limit of its own, so the monitor's default applies
+        assert_eq!(
+            ConfirmationStrategy::Finalized { timeout_secs: 0 }.timeout(),
+            None
+        );
+        assert_eq!(ConfirmationStrategy::Immediate.timeout(), None);
+    }
+
     #[test]
     fn test_chain_config_kusama() {
         let kusama = ChainConfig::kusama();
@@ -969,6 +1403,21 @@ mod tests {
         assert_eq!(custom.token_decimals, 12);
     }
 
+    #[test]
+    fn test_chain_config_from_registry() {
+        let hub = ChainConfig::from_registry("asset-hub-westend").unwrap();
+        assert_eq!(hub.name, "Asset Hub Westend");
+        assert_eq!(hub.endpoint, "wss://westend-asset-hub-rpc.polkadot.io");
+        assert_eq!(hub.ss58_prefix, 42);
+        assert_eq!(hub.token_symbol, "WND");
+
+        let moonbeam = ChainConfig::from_registry("Moonbeam").unwrap();
+        assert_eq!(moonbeam.ss58_prefix, 1284);
+        assert_eq!(moonbeam.token_decimals, 18);
+
+        assert!(ChainConfig::from_registry("unknown").is_none());
+    }
+
     #[test]
     fn test_address_validation_valid_substrate() {
         let polkadot_addr = Address::substrate("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5");
@@ -1055,6 +1504,64 @@ mod tests {
         assert_eq!(MAX_BLOCK_SEARCH_DEPTH, 100);
     }
 
+    #[test]
+    fn test_transaction_search_ranges() {
+        assert_eq!(TxSearch::Depth(100).range(1_000), Some((901, 1_000)));
+        assert_eq!(TxSearch::Depth(100).range(40), Some((0, 40)));
+        assert_eq!(TxSearch::Depth(0).range(40), None);
+
+        // A hint searches forward, up to the best block
+        let since = TxSearch::since(500);
+        assert_eq!(since.range(520), Some((500, 520)));
+        assert_eq!(since.range(1_000), Some((500, 563)));
+        assert_eq!(since.range(499), None);
+        assert!(!since.is_complete(520));
+        assert!(since.is_complete(563));
+
+        let inclusion = TxInclusion {
+            block_number: 510,
+            block_hash: format!("0x{}", "ab".repeat(32)),
+            extrinsic_index: 2,
+            confirmations: 10,
+            success: Some(true),
+            error: None,
+        };
+        let status = TxLookup::Finalized(inclusion.clone()).to_status("0x01".to_string());
+        assert_eq!(status.status, TxStatus::Finalized);
+        assert_eq!(status.block_number, Some(510));
+        assert_eq!(status.confirmations, Some(10));
+        assert_eq!(
+            TxLookup::InBlock(inclusion.clone())
+                .to_status("0x01".to_string())
+                .status,
+            TxStatus::Confirmed
+        );
+
+        let failed = TxLookup::InBlock(TxInclusion {
+            success: Some(false),
+            error: Some("Balances::InsufficientBalance".to_string()),
+            ..inclusion
+        })
+        .to_status("0x01".to_string());
+        assert_eq!(failed.status, TxStatus::Failed);
+        assert_eq!(failed.block_number, Some(510));
+        assert_eq!(
+            failed.error.as_deref(),
+            Some("Balances::InsufficientBalance")
+        );
+
+        let dropped = TxLookup::Dropped { from: 500, to: 563 };
+        assert!(dropped.inclusion().is_none());
+        assert_eq!(
+            dropped.to_status("0x01".to_string()).status,
+            TxStatus::Unknown
+        );
+        assert_eq!(
+            TxLookup::Pending.to_status("0x01".to_string()).status,
+            TxStatus::Pending
+        );
+    }
+
     #[test]
     fn test_error_types() {
         let connection_err = Error::Connection("Test connection error".to_string());
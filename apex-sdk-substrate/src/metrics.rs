@@ -4,7 +4,9 @@
 //! - Metrics tracking for RPC calls, extrinsic submissions, and errors
 //! - A snapshot mechanism for retrieving current metrics
 //! - Atomic counters for thread-safe metric updates
+//! - Fee totals, split into charged, refunded and net amounts
 
+use parking_lot::Mutex;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
@@ -23,6 +25,32 @@ pub struct MetricsSnapshot {
     pub connection_errors: u64,
     /// Average response time for RPC calls in milliseconds
     pub avg_rpc_response_time_ms: u64,
+    /// Number of finalized extrinsics with a recorded fee
+    pub fee_payments: u64,
+    /// Total fees withdrawn before dispatch, including tips
+    pub fees_charged: u128,
+    /// Total fees refunded after dispatch for unused weight
+    pub fees_refunded: u128,
+    /// Total fees paid after refunds, including tips
+    pub fees_net: u128,
+}
+
+impl MetricsSnapshot {
+    /// Average net fee per recorded payment
+    pub fn avg_net_fee(&self) -> u128 {
+        self.fees_net
+            .checked_div(self.fee_payments as u128)
+            .unwrap_or(0)
+    }
+}
+
+/// Running fee totals, kept together so snapshots are consistent
+#[derive(Debug, Default)]
+struct FeeTotals {
+    payments: u64,
+    charged: u128,
+    refunded: u128,
+    net: u128,
 }
 
 /// Metrics collector for the Substrate adapter
@@ -34,6 +62,7 @@ pub struct Metrics {
     extrinsics_failed: Arc<AtomicU64>,
     connection_errors: Arc<AtomicU64>,
     total_rpc_response_time_ms: Arc<AtomicU64>,
+    fees: Arc<Mutex<FeeTotals>>,
 }
 
 impl Metrics {
@@ -75,16 +104,25 @@ impl Metrics {
         self.connection_errors.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Record the fee of a finalized extrinsic
+    ///
+    /// `charged` is the amount withdrawn before dispatch and `net` the
+    /// amount kept after the weight refund; both include the tip.
+    pub fn record_fee(&self, charged: u128, net: u128) {
+        let mut fees = self.fees.lock();
+        fees.payments += 1;
+        fees.charged = fees.charged.saturating_add(charged.max(net));
+        fees.refunded = fees.refunded.saturating_add(charged.saturating_sub(net));
+        fees.net = fees.net.saturating_add(net);
+    }
+
     /// Get a snapshot of the current metrics
     pub fn snapshot(&self) -> MetricsSnapshot {
         let rpc_calls = self.rpc_calls.load(Ordering::Relaxed);
         let total_response_time = self.total_rpc_response_time_ms.load(Ordering::Relaxed);
 
-        let avg_rpc_response_time_ms = if rpc_calls > 0 {
-            total_response_time / rpc_calls
-        } else {
-            0
-        };
+        let avg_rpc_response_time_ms = total_response_time.checked_div(rpc_calls).unwrap_or(0);
+        let fees = self.fees.lock();
 
         MetricsSnapshot {
             rpc_calls,
@@ -93,6 +131,10 @@ impl Metrics {
             extrinsics_failed: self.extrinsics_failed.load(Ordering::Relaxed),
             connection_errors: self.connection_errors.load(Ordering::Relaxed),
             avg_rpc_response_time_ms,
+            fee_payments: fees.payments,
+            fees_charged: fees.charged,
+            fees_refunded: fees.refunded,
+            fees_net: fees.net,
         }
     }
 }
@@ -1,294 +1,900 @@
-use crate::{Error, Metrics, PolkadotConfig, Result};
+use crate::event_bus::{
+    BestBlock, BusReceiver, DeliveryPolicy, EventBus, FinalizedBlock, HealthHandle,
+    SubscriptionHealth,
+};
+use crate::event_filter::{EventFilter, EventScanner};
+use crate::fee_estimator::{CongestionLevel, InclusionLatencies};
+use crate::{Error, Metrics, Result};
 use apex_sdk_core::ConfirmationStrategy;
-use apex_sdk_types::TransactionStatus;
-use std::collections::HashMap;
+use apex_sdk_types::{BlockHash, TransactionStatus, TxHash};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use subxt::OnlineClient;
+use subxt::blocks::Block;
+use subxt::events::{Events, Phase};
+use subxt::utils::H256;
+use subxt::{OnlineClient, PolkadotConfig};
 use tokio::sync::{mpsc, oneshot, RwLock};
 use tracing::{debug, error, info, warn};
 
-/// Maximum time to keep a transaction in the watch list (5 minutes)
-const MAX_WATCH_DURATION: Duration = Duration::from_secs(300);
+/// Default time to keep a transaction in the watch list when its
+/// confirmation strategy sets no timeout (5 minutes)
+pub const DEFAULT_WATCH_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How often watches are checked against their deadlines
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Component name the monitor reports its health under
+pub const MONITOR_COMPONENT: &str = "transaction_monitor";
+
+/// Time without a finalized block after which the monitor is degraded
+pub const STALLED_AFTER: Duration = Duration::from_secs(60);
+
+/// Best blocks ahead of the last finalized one after which the monitor is degraded
+pub const MAX_FINALITY_LAG: u64 = 20;
+
+/// How often an attached health checker probes the monitor
+#[cfg(feature = "observability")]
+const HEALTH_REPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Health of a transaction monitor and the block subscriptions feeding it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorHealth {
+    /// Finalized-block subscription
+    pub finalized: SubscriptionHealth,
+    /// Best-block subscription
+    pub best: SubscriptionHealth,
+    /// Transactions being watched
+    pub watched: usize,
+}
+
+impl MonitorHealth {
+    async fn collect(
+        pending_txs: &PendingTxs,
+        finalized: &HealthHandle,
+        best: &HealthHandle,
+    ) -> Self {
+        Self {
+            finalized: finalized.get(),
+            best: best.get(),
+            watched: pending_txs.read().await.len(),
+        }
+    }
+
+    /// Number of the last finalized block seen
+    pub fn last_block(&self) -> Option<u64> {
+        self.finalized.last_block
+    }
+
+    /// Time since the last finalized block was seen
+    pub fn since_last_block(&self) -> Option<Duration> {
+        self.finalized.since_last_block()
+    }
+
+    /// Best blocks seen beyond the last finalized block
+    pub fn finality_lag(&self) -> Option<u64> {
+        Some(
+            self.best
+                .last_block?
+                .saturating_sub(self.finalized.last_block?),
+        )
+    }
+
+    /// Why the monitor may miss or delay statuses, if it may
+    ///
+    /// The finalized subscription being down, no finalized block within
+    /// [`STALLED_AFTER`], or finality lagging more than [`MAX_FINALITY_LAG`]
+    /// blocks behind the best chain.
+    pub fn problem(&self) -> Option<String> {
+        if !self.finalized.subscribed {
+            return Some(format!(
+                "Finalized-block subscription down after {} failed attempts",
+                self.finalized.consecutive_failures
+            ));
+        }
+        if let Some(age) = self.since_last_block().filter(|age| *age >= STALLED_AFTER) {
+            return Some(format!("No finalized block for {}s", age.as_secs()));
+        }
+        match self.finality_lag() {
+            Some(lag) if lag > MAX_FINALITY_LAG => {
+                Some(format!("Finality {} blocks behind the best chain", lag))
+            }
+            _ => None,
+        }
+    }
+
+    /// Health check entry for a [`HealthChecker`](apex_sdk_metrics::HealthChecker)
+    ///
+    /// Healthy while the monitor keeps up with the chain; degraded on a
+    /// [`problem`](Self::problem) once blocks have been seen, unhealthy if
+    /// none ever arrived, and unknown while the first block is awaited.
+    #[cfg(feature = "observability")]
+    pub fn component_health(&self) -> apex_sdk_metrics::ComponentHealth {
+        use apex_sdk_metrics::{ComponentHealth, HealthStatus};
+
+        let problem = self.problem();
+        let status = match (&problem, self.last_block()) {
+            (None, Some(_)) => HealthStatus::Healthy,
+            (None, None) => HealthStatus::Unknown,
+            (Some(_), Some(_)) => HealthStatus::Degraded,
+            (Some(_), None) => HealthStatus::Unhealthy,
+        };
+
+        let mut health = ComponentHealth::new(MONITOR_COMPONENT, status)
+            .with_metadata("watched", self.watched.to_string())
+            .with_metadata(
+                "resubscriptions",
+                self.finalized.resubscriptions.to_string(),
+            )
+            .with_metadata("backfilled_blocks", self.finalized.backfilled.to_string());
+        if let Some(problem) = problem {
+            health = health.with_message(problem);
+        }
+        if let Some(block) = self.last_block() {
+            health = health.with_metadata("last_block", block.to_string());
+        }
+        if let Some(age) = self.since_last_block() {
+            health = health.with_metadata("last_block_age_secs", age.as_secs().to_string());
+        }
+        if let Some(lag) = self.finality_lag() {
+            health = health.with_metadata("finality_lag", lag.to_string());
+        }
+        health
+    }
+}
+
+/// Block a watched transaction was found in
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Inclusion {
+    number: u64,
+    hash: H256,
+    success: bool,
+    error: Option<String>,
+    /// Found in a finalized block rather than a best block
+    finalized: bool,
+}
+
+impl Inclusion {
+    /// Status of a transaction included here, `confirmations` blocks deep
+    fn status(&self, tx_hash: &TxHash, confirmations: u64) -> TransactionStatus {
+        let block_hash = BlockHash::new(self.hash.0).to_string();
+        if !self.success {
+            return TransactionStatus {
+                block_number: Some(self.number),
+                block_hash: Some(block_hash),
+                ..TransactionStatus::failed(
+                    tx_hash.to_string(),
+                    self.error
+                        .clone()
+                        .unwrap_or_else(|| "Unknown error".to_string()),
+                )
+            };
+        }
+
+        let status = if self.finalized {
+            TransactionStatus::finalized
+        } else {
+            TransactionStatus::confirmed
+        };
+        status(
+            tx_hash.to_string(),
+            self.number,
+            block_hash,
+            None,
+            None,
+            Some(confirmations as u32),
+        )
+    }
+}
+
+/// Where a watch delivers its statuses
+enum StatusSender {
+    /// Only the status that ends the watch
+    Final(oneshot::Sender<TransactionStatus>),
+    /// Every status change; closed when the watch ends
+    Progress(mpsc::UnboundedSender<TransactionStatus>),
+}
+
+impl StatusSender {
+    /// Report an intermediate status to progress watches
+    fn update(&self, status: TransactionStatus) {
+        if let Self::Progress(sender) = self {
+            let _ = sender.send(status);
+        }
+    }
+
+    /// Report the status that ends the watch
+    fn finish(self, status: TransactionStatus) {
+        match self {
+            Self::Final(sender) => {
+                let _ = sender.send(status);
+            }
+            Self::Progress(sender) => {
+                let _ = sender.send(status);
+            }
+        }
+    }
+}
 
 /// Handle for a transaction being watched
 struct TxWatchHandle {
     submitted_at: Instant,
+    /// Time after which the watch ends with [`TxStatus::TimedOut`]
+    ///
+    /// [`TxStatus::TimedOut`]: apex_sdk_types::TxStatus::TimedOut
+    timeout: Duration,
     strategy: ConfirmationStrategy,
-    sender: oneshot::Sender<TransactionStatus>,
+    sender: StatusSender,
+    /// Latest block the transaction was found in, best or finalized
+    inclusion: Option<Inclusion>,
     first_seen_block: Option<u64>,
+    /// First finalized block processed after the watch started
+    watched_from_block: Option<u64>,
+    /// Blocks after which a transaction not yet included is reported as pending
+    stuck_after: Option<u64>,
+    /// Tip the transaction was signed with, for inclusion latency tracking
+    tip: Option<u128>,
+    /// Congestion level when the watch started
+    congestion: CongestionLevel,
 }
 
+impl TxWatchHandle {
+    /// Best-chain confirmations that end the watch, or `None` if it waits
+    /// for finality
+    ///
+    /// Progress watches always run until finality.
+    fn best_confirmations(&self) -> Option<u64> {
+        if matches!(self.sender, StatusSender::Progress(_)) {
+            return None;
+        }
+        match &self.strategy {
+            ConfirmationStrategy::Immediate => Some(0),
+            ConfirmationStrategy::BlockConfirmations { confirmations, .. } => {
+                Some(*confirmations as u64)
+            }
+            ConfirmationStrategy::Finalized { .. } => None,
+        }
+    }
+}
+
+/// Request sent to the monitor loop
+enum WatchCommand {
+    Watch {
+        tx_hash: TxHash,
+        strategy: ConfirmationStrategy,
+        timeout: Duration,
+        sender: StatusSender,
+        stuck_after: Option<u64>,
+        tip: Option<u128>,
+    },
+    Cancel(TxHash),
+}
+
+/// Recent best-chain block hashes by number, to spot orphaned inclusions
+#[derive(Debug, Default)]
+struct BestChain {
+    blocks: BTreeMap<u64, H256>,
+    best: u64,
+}
+
+impl BestChain {
+    /// Record a new best block; returns whether it replaced blocks seen
+    /// before, i.e. the chain reorganized
+    fn advance(&mut self, number: u64, hash: H256, parent_hash: H256) -> bool {
+        // Blocks at or above the new best belong to the fork it replaces
+        let replaced = self.blocks.split_off(&number);
+        let mut reorg = replaced.iter().any(|(n, h)| *n != number || *h != hash);
+
+        if let Some(parent_number) = number.checked_sub(1) {
+            if let Some(previous) = self.blocks.insert(parent_number, parent_hash) {
+                reorg |= previous != parent_hash;
+            }
+        }
+        self.blocks.insert(number, hash);
+        self.best = number;
+        reorg
+    }
+
+    /// Whether a block is known to be off the best chain
+    ///
+    /// Heights the chain skipped over are assumed to still hold the block.
+    fn is_orphaned(&self, number: u64, hash: H256) -> bool {
+        match self.blocks.get(&number) {
+            Some(canonical) => *canonical != hash,
+            None => number > self.best,
+        }
+    }
+
+    /// Forget blocks below the finalized height, which cannot change
+    fn prune(&mut self, finalized: u64) {
+        self.blocks = self.blocks.split_off(&finalized);
+    }
+}
+
+type PendingTxs = Arc<RwLock<HashMap<TxHash, TxWatchHandle>>>;
+
 /// Manages subscription-based transaction monitoring
+///
+/// The monitor follows both best and finalized blocks from the event bus.
+/// Watches that only need inclusion or a number of confirmations end on the
+/// best chain; a transaction whose block is orphaned by a reorg is pending
+/// again until it is re-included.
 pub struct TransactionMonitor {
-    watch_tx: mpsc::UnboundedSender<(
-        String,
-        ConfirmationStrategy,
-        oneshot::Sender<TransactionStatus>,
-    )>,
+    watch_tx: mpsc::UnboundedSender<WatchCommand>,
+    /// Watch timeout for strategies that set none
+    default_timeout: Duration,
+    pending_txs: PendingTxs,
+    finalized_health: HealthHandle,
+    best_health: HealthHandle,
 }
 
 impl TransactionMonitor {
-    /// Create a new transaction monitor and start the subscription loop
-    pub async fn new(client: OnlineClient<PolkadotConfig>, metrics: Arc<Metrics>) -> Result<Self> {
-        let pending_txs = Arc::new(RwLock::new(HashMap::new()));
-        let (watch_tx, watch_rx) = mpsc::unbounded_channel();
+    /// Create a new transaction monitor fed by the adapter's event bus
+    pub async fn new(bus: &EventBus, metrics: Arc<Metrics>) -> Result<Self> {
+        Self::with_inclusion_latencies(bus, metrics, InclusionLatencies::new()).await
+    }
 
+    /// Create a transaction monitor recording inclusion latencies
+    ///
+    /// Transactions watched with a tip add the time from the start of their
+    /// watch to their first finalized block to `latencies`.
+    pub async fn with_inclusion_latencies(
+        bus: &EventBus,
+        metrics: Arc<Metrics>,
+        latencies: InclusionLatencies,
+    ) -> Result<Self> {
+        let pending_txs: PendingTxs = Arc::new(RwLock::new(HashMap::new()));
+        let (watch_tx, watch_rx) = mpsc::unbounded_channel();
+        let (finalized_health, best_health) = bus.health_handles();
+        // Every finalized block is needed to confirm transactions
+        let blocks = bus.subscribe_with_policy("transaction-monitor", DeliveryPolicy::Replay);
+        let best_blocks = bus.subscribe_best("transaction-monitor");
+        let scanner = EventScanner::new(EventFilter::new(
+            &bus.metadata(),
+            &[
+                ("System", "ExtrinsicSuccess"),
+                ("System", "ExtrinsicFailed"),
+            ],
+        ));
+
+        let monitored = pending_txs.clone();
         tokio::spawn(async move {
-            if let Err(e) = Self::run_monitor(client, pending_txs, metrics, watch_rx).await {
-                error!("Transaction monitor error: {}", e);
+            Self::run_monitor(
+                blocks,
+                best_blocks,
+                scanner,
+                monitored,
+                metrics,
+                latencies,
+                watch_rx,
+            )
+            .await;
+        });
+
+        Ok(Self {
+            watch_tx,
+            default_timeout: DEFAULT_WATCH_TIMEOUT,
+            pending_txs,
+            finalized_health,
+            best_health,
+        })
+    }
+
+    /// Probe the monitor's [`health`](Self::health) from `checker` every few
+    /// seconds, as the [`MONITOR_COMPONENT`] component
+    ///
+    /// Once the monitor is dropped the component is reported unhealthy.
+    #[cfg(feature = "observability")]
+    pub fn with_health_checker(self, checker: Arc<apex_sdk_metrics::HealthChecker>) -> Self {
+        let pending_txs = Arc::downgrade(&self.pending_txs);
+        let finalized = self.finalized_health.clone();
+        let best = self.best_health.clone();
+
+        checker.register_probe(MONITOR_COMPONENT, HEALTH_REPORT_INTERVAL, move || {
+            let pending_txs = pending_txs.upgrade();
+            let finalized = finalized.clone();
+            let best = best.clone();
+            async move {
+                match pending_txs {
+                    Some(pending_txs) => MonitorHealth::collect(&pending_txs, &finalized, &best)
+                        .await
+                        .component_health(),
+                    None => apex_sdk_metrics::ComponentHealth::new(
+                        MONITOR_COMPONENT,
+                        apex_sdk_metrics::HealthStatus::Unhealthy,
+                    )
+                    .with_message("Transaction monitor stopped"),
+                }
             }
         });
+        self
+    }
+
+    /// Current health: the block subscriptions' state, the last finalized
+    /// block seen and how far finality lags the best chain
+    pub async fn health(&self) -> MonitorHealth {
+        MonitorHealth::collect(&self.pending_txs, &self.finalized_health, &self.best_health).await
+    }
+
+    /// Use `timeout` for watches whose confirmation strategy sets no timeout
+    /// of its own (default [`DEFAULT_WATCH_TIMEOUT`])
+    pub fn with_watch_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = timeout;
+        self
+    }
 
-        Ok(Self { watch_tx })
+    /// Watch timeout for strategies that set none
+    pub fn watch_timeout(&self) -> Duration {
+        self.default_timeout
     }
 
     /// Watch a transaction with the given confirmation strategy
     /// Returns a receiver that will be notified when the transaction reaches the required confirmations
+    ///
+    /// `Immediate` ends with a [`TxStatus::Confirmed`] status as soon as the
+    /// transaction is in a best block, and `BlockConfirmations` once that
+    /// block is the required number of blocks deep in the best chain.
+    /// `Finalized` waits for a finalized block.
+    ///
+    /// If it does not within the strategy's timeout, or the monitor's
+    /// [`watch_timeout`](Self::watch_timeout) when the strategy sets none,
+    /// the receiver gets a [`TxStatus::TimedOut`] status.
+    ///
+    /// [`TxStatus::Confirmed`]: apex_sdk_types::TxStatus::Confirmed
+    /// [`TxStatus::TimedOut`]: apex_sdk_types::TxStatus::TimedOut
     pub async fn watch_transaction(
         &self,
-        tx_hash: String,
+        tx_hash: TxHash,
         strategy: ConfirmationStrategy,
     ) -> oneshot::Receiver<TransactionStatus> {
-        let (tx, rx) = oneshot::channel();
+        self.watch(tx_hash, strategy, None, None)
+    }
 
-        if let Err(e) = self.watch_tx.send((tx_hash.clone(), strategy, tx)) {
-            error!("Failed to add transaction to watch list: {}", e);
-        } else {
-            debug!("Added transaction to watch list: {}", tx_hash);
+    /// Watch a transaction signed with `tip`, recording its inclusion latency
+    pub async fn watch_transaction_with_tip(
+        &self,
+        tx_hash: TxHash,
+        strategy: ConfirmationStrategy,
+        tip: u128,
+    ) -> oneshot::Receiver<TransactionStatus> {
+        self.watch(tx_hash, strategy, None, Some(tip))
+    }
+
+    /// Watch a transaction that should be included within `blocks` finalized blocks
+    ///
+    /// If it is not in any of the first `blocks` finalized blocks after the
+    /// watch starts, the receiver gets a [`TxStatus::Pending`] status and the
+    /// watch ends. Its inclusion latency is recorded against `tip`.
+    ///
+    /// [`TxStatus::Pending`]: apex_sdk_types::TxStatus::Pending
+    pub async fn watch_inclusion(
+        &self,
+        tx_hash: TxHash,
+        strategy: ConfirmationStrategy,
+        blocks: u64,
+        tip: u128,
+    ) -> oneshot::Receiver<TransactionStatus> {
+        self.watch(tx_hash, strategy, Some(blocks), Some(tip))
+    }
+
+    /// Watch a transaction through to finality, receiving every status change
+    ///
+    /// The receiver gets a [`TxStatus::Confirmed`] status when the
+    /// transaction is in a best block, [`TxStatus::Pending`] if that block is
+    /// orphaned by a reorg, and finally a `Finalized`, `Failed` or `TimedOut`
+    /// status, after which it closes. The transaction is never reported as
+    /// stuck; like other watches, it times out after the strategy's timeout,
+    /// or the [`watch_timeout`](Self::watch_timeout) for a strategy without
+    /// one.
+    ///
+    /// [`TxStatus::Confirmed`]: apex_sdk_types::TxStatus::Confirmed
+    /// [`TxStatus::Pending`]: apex_sdk_types::TxStatus::Pending
+    pub async fn watch_progress(
+        &self,
+        tx_hash: TxHash,
+        strategy: ConfirmationStrategy,
+    ) -> mpsc::UnboundedReceiver<TransactionStatus> {
+        let (sender, rx) = mpsc::unbounded_channel();
+        self.send_watch(
+            tx_hash,
+            strategy,
+            StatusSender::Progress(sender),
+            None,
+            None,
+        );
+        rx
+    }
+
+    /// Stop watching a transaction
+    ///
+    /// Its receiver is closed without a status.
+    pub fn cancel_watch(&self, tx_hash: &TxHash) {
+        if self.watch_tx.send(WatchCommand::Cancel(*tx_hash)).is_err() {
+            error!(tx_hash = %tx_hash, "Failed to cancel transaction watch");
         }
+    }
 
+    fn watch(
+        &self,
+        tx_hash: TxHash,
+        strategy: ConfirmationStrategy,
+        stuck_after: Option<u64>,
+        tip: Option<u128>,
+    ) -> oneshot::Receiver<TransactionStatus> {
+        let (sender, rx) = oneshot::channel();
+        self.send_watch(
+            tx_hash,
+            strategy,
+            StatusSender::Final(sender),
+            stuck_after,
+            tip,
+        );
         rx
     }
 
-    /// Main monitoring loop that subscribes to finalized blocks
+    fn send_watch(
+        &self,
+        tx_hash: TxHash,
+        strategy: ConfirmationStrategy,
+        sender: StatusSender,
+        stuck_after: Option<u64>,
+        tip: Option<u128>,
+    ) {
+        let timeout = strategy.timeout().unwrap_or(self.default_timeout);
+        let command = WatchCommand::Watch {
+            tx_hash,
+            strategy,
+            timeout,
+            sender,
+            stuck_after,
+            tip,
+        };
+
+        if let Err(e) = self.watch_tx.send(command) {
+            error!(tx_hash = %tx_hash, error = %e, "Failed to add transaction to watch list");
+        } else {
+            debug!(tx_hash = %tx_hash, "Added transaction to watch list");
+        }
+    }
+
+    /// Main monitoring loop fed by best and finalized blocks from the event bus
     async fn run_monitor(
-        client: OnlineClient<PolkadotConfig>,
-        pending_txs: Arc<RwLock<HashMap<String, TxWatchHandle>>>,
+        mut blocks: BusReceiver,
+        mut best_blocks: BusReceiver<BestBlock>,
+        mut scanner: EventScanner,
+        pending_txs: PendingTxs,
         metrics: Arc<Metrics>,
-        mut watch_rx: mpsc::UnboundedReceiver<(
-            String,
-            ConfirmationStrategy,
-            oneshot::Sender<TransactionStatus>,
-        )>,
-    ) -> Result<()> {
-        info!("Starting transaction monitor subscription loop");
+        latencies: InclusionLatencies,
+        mut watch_rx: mpsc::UnboundedReceiver<WatchCommand>,
+    ) {
+        info!("Starting transaction monitor loop");
 
-        loop {
-            // Subscribe to finalized blocks
-            match client.blocks().subscribe_finalized().await {
-                Ok(mut subscription) => {
-                    info!("Successfully subscribed to finalized blocks");
-
-                    loop {
-                        tokio::select! {
-                            // Handle new transactions to watch
-                            Some((tx_hash, strategy, sender)) = watch_rx.recv() => {
-                                let handle = TxWatchHandle {
-                                    submitted_at: Instant::now(),
-                                    strategy,
-                                    sender,
-                                    first_seen_block: None,
-                                };
-                                pending_txs.write().await.insert(tx_hash, handle);
-                                debug!("Now watching {} transactions", pending_txs.read().await.len());
-                            }
+        let mut best_chain = BestChain::default();
 
-                            // Handle finalized blocks
-                            block_result = subscription.next() => {
-                                match block_result {
-                                    Some(Ok(block)) => {
-                                        if let Err(e) = Self::process_finalized_block(
-                                            &pending_txs,
-                                            &metrics,
-                                            block
-                                        ).await {
-                                            error!("Error processing finalized block: {}", e);
-                                        }
-                                    }
-                                    Some(Err(e)) => {
-                                        error!("Error receiving finalized block: {}", e);
-                                        break;
-                                    }
-                                    None => {
-                                        warn!("Finalized blocks subscription ended, reconnecting...");
-                                        break;
-                                    }
-                                }
-                            }
+        // An interval keeps its schedule while blocks arrive, unlike a sleep
+        // restarted on every loop iteration
+        let mut expiry_check = tokio::time::interval(EXPIRY_CHECK_INTERVAL);
+        expiry_check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
-                            // Periodic cleanup of expired transactions
-                            _ = tokio::time::sleep(Duration::from_secs(30)) => {
-                                Self::cleanup_expired_transactions(&pending_txs).await;
+        loop {
+            tokio::select! {
+                // Handle new transactions to watch
+                Some(command) = watch_rx.recv() => match command {
+                    WatchCommand::Watch { tx_hash, strategy, timeout, sender, stuck_after, tip } => {
+                        let handle = TxWatchHandle {
+                            submitted_at: Instant::now(),
+                            timeout,
+                            strategy,
+                            sender,
+                            inclusion: None,
+                            first_seen_block: None,
+                            watched_from_block: None,
+                            stuck_after,
+                            tip,
+                            congestion: latencies.congestion(),
+                        };
+                        pending_txs.write().await.insert(tx_hash, handle);
+                        debug!("Now watching {} transactions", pending_txs.read().await.len());
+                    }
+                    WatchCommand::Cancel(tx_hash) => {
+                        if pending_txs.write().await.remove(&tx_hash).is_some() {
+                            debug!(tx_hash = %tx_hash, "Cancelled transaction watch");
+                        }
+                    }
+                },
+
+                // Handle best blocks; a closed best-block stream only disables this branch
+                Some(block) = best_blocks.recv() => {
+                    if let Err(e) = Self::process_best_block(
+                        &pending_txs,
+                        &mut scanner,
+                        &metrics,
+                        &mut best_chain,
+                        &block,
+                    ).await {
+                        error!("Error processing best block: {}", e);
+                    }
+                }
+
+                // Handle finalized blocks
+                block = blocks.recv() => {
+                    match block {
+                        Some(block) => {
+                            best_chain.prune(block.number);
+                            if let Err(e) = Self::process_finalized_block(
+                                &pending_txs,
+                                &mut scanner,
+                                &metrics,
+                                &latencies,
+                                &block
+                            ).await {
+                                error!("Error processing finalized block: {}", e);
                             }
                         }
+                        None => {
+                            warn!("Event bus closed, stopping transaction monitor");
+                            return;
+                        }
                     }
                 }
-                Err(e) => {
-                    error!("Failed to subscribe to finalized blocks: {}", e);
-                    tokio::time::sleep(Duration::from_secs(5)).await;
+
+                // Periodic cleanup of expired transactions
+                _ = expiry_check.tick() => {
+                    Self::cleanup_expired_transactions(&pending_txs).await;
                 }
             }
         }
     }
 
-    /// Process a finalized block and check for watched transactions
-    async fn process_finalized_block(
-        pending_txs: &Arc<RwLock<HashMap<String, TxWatchHandle>>>,
-        metrics: &Arc<Metrics>,
-        block: subxt::blocks::Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
-    ) -> Result<()> {
-        let block_number = block.number() as u64;
-        let block_hash = block.hash();
-
-        debug!("Processing finalized block #{}", block_number);
-
+    /// Dispatch outcome of every extrinsic in a block, by hash
+    async fn block_transactions(
+        scanner: &mut EventScanner,
+        block: &Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+        events: &Events<PolkadotConfig>,
+        block_number: u64,
+    ) -> Result<HashMap<TxHash, (bool, Option<String>)>> {
         // Get all extrinsics in this block
         let extrinsics = block
             .extrinsics()
             .await
             .map_err(|e| Error::Transaction(format!("Failed to get extrinsics: {}", e)))?;
 
-        let pending = pending_txs.read().await;
-        if pending.is_empty() {
-            return Ok(());
+        // Dispatch outcome per extrinsic index, from a single pass over the events
+        let mut outcomes = HashMap::new();
+        for event in scanner.scan(events)? {
+            if let Phase::ApplyExtrinsic(index) = event.phase() {
+                outcomes.insert(index, event.variant_name() == "ExtrinsicSuccess");
+            }
         }
 
         let mut block_tx_hashes = HashMap::new();
         for ext_details in extrinsics.iter() {
             let ext_bytes = ext_details.bytes();
             let computed_hash = sp_core::blake2_256(ext_bytes);
-            let tx_hash = format!("0x{}", hex::encode(computed_hash));
-            let (success, error_msg) = match ext_details.events().await {
-                Ok(events) => {
-                    let mut success = false;
-                    let mut error_msg = None;
-
-                    for event in events.iter().flatten() {
-                        if event.pallet_name() == "System" {
-                            if event.variant_name() == "ExtrinsicSuccess" {
-                                success = true;
-                            } else if event.variant_name() == "ExtrinsicFailed" {
-                                error_msg =
-                                    Some(format!("Extrinsic failed at block {}", block_number));
-                            }
-                        }
-                    }
-                    (success, error_msg)
-                }
-                Err(_) => (false, Some("Failed to fetch events".to_string())),
+            let tx_hash = TxHash::new(computed_hash);
+
+            let (success, error_msg) = match outcomes.get(&ext_details.index()) {
+                Some(true) => (true, None),
+                Some(false) => (
+                    false,
+                    Some(format!("Extrinsic failed at block {}", block_number)),
+                ),
+                None => (false, None),
             };
 
             block_tx_hashes.insert(tx_hash, (success, error_msg));
         }
 
-        drop(pending);
+        Ok(block_tx_hashes)
+    }
+
+    /// Process a best block: report inclusions early, end watches that
+    /// only need best-chain confirmations, and re-pend transactions whose
+    /// block was orphaned
+    async fn process_best_block(
+        pending_txs: &PendingTxs,
+        scanner: &mut EventScanner,
+        metrics: &Arc<Metrics>,
+        best_chain: &mut BestChain,
+        block: &BestBlock,
+    ) -> Result<()> {
+        let block_number = block.number;
+        if best_chain.advance(block_number, block.hash, block.parent_hash) {
+            warn!(block_number, "Best chain reorganized");
+        }
+
+        if pending_txs.read().await.is_empty() {
+            return Ok(());
+        }
+        debug!("Processing best block #{}", block_number);
+
+        let block_tx_hashes =
+            Self::block_transactions(scanner, &block.block, &block.events, block_number).await?;
 
+        let mut pending = pending_txs.write().await;
         let mut to_complete = Vec::new();
-        {
-            let mut pending = pending_txs.write().await;
-
-            for (tx_hash, handle) in pending.iter_mut() {
-                if let Some((_success, _error_msg)) = block_tx_hashes.get(tx_hash) {
-                    if handle.first_seen_block.is_none() {
-                        handle.first_seen_block = Some(block_number);
-                        info!(
-                            "Transaction {} found in finalized block #{}",
-                            tx_hash, block_number
-                        );
-                    }
-                }
 
-                if let Some(first_seen) = handle.first_seen_block {
-                    let confirmations = block_number.saturating_sub(first_seen);
+        for (tx_hash, handle) in pending.iter_mut() {
+            let orphaned = handle.inclusion.as_ref().is_some_and(|inclusion| {
+                !inclusion.finalized && best_chain.is_orphaned(inclusion.number, inclusion.hash)
+            });
+            if orphaned {
+                warn!(
+                    tx_hash = %tx_hash,
+                    "Block containing transaction was orphaned, waiting for re-inclusion"
+                );
+                handle.inclusion = None;
+                handle
+                    .sender
+                    .update(TransactionStatus::pending(tx_hash.to_string()));
+            }
 
-                    let is_complete = match &handle.strategy {
-                        ConfirmationStrategy::Immediate => true,
-                        ConfirmationStrategy::Finalized { .. } => true,
-                        ConfirmationStrategy::BlockConfirmations {
-                            confirmations: required,
-                            ..
-                        } => confirmations >= (*required as u64),
+            if let Some((success, error_msg)) = block_tx_hashes.get(tx_hash) {
+                if handle.inclusion.is_none() {
+                    let inclusion = Inclusion {
+                        number: block_number,
+                        hash: block.hash,
+                        success: *success,
+                        error: error_msg.clone(),
+                        finalized: false,
                     };
+                    info!(
+                        tx_hash = %tx_hash,
+                        block_number,
+                        "Transaction found in best block"
+                    );
+                    handle.sender.update(inclusion.status(tx_hash, 0));
+                    handle.inclusion = Some(inclusion);
+                }
+            }
+
+            if let (Some(inclusion), Some(required)) =
+                (&handle.inclusion, handle.best_confirmations())
+            {
+                let confirmations = block_number.saturating_sub(inclusion.number);
+                if confirmations >= required {
+                    to_complete.push((*tx_hash, inclusion.status(tx_hash, confirmations)));
+                }
+            }
+        }
+
+        Self::complete(&mut pending, metrics, to_complete);
+        Ok(())
+    }
+
+    /// Process a finalized block and check for watched transactions
+    async fn process_finalized_block(
+        pending_txs: &PendingTxs,
+        scanner: &mut EventScanner,
+        metrics: &Arc<Metrics>,
+        latencies: &InclusionLatencies,
+        block: &FinalizedBlock,
+    ) -> Result<()> {
+        let block_number = block.number;
+        let block_hash = block.hash;
+
+        debug!("Processing finalized block #{}", block_number);
+
+        if pending_txs.read().await.is_empty() {
+            return Ok(());
+        }
+
+        let block_tx_hashes =
+            Self::block_transactions(scanner, &block.block, &block.events, block_number).await?;
 
-                    if is_complete {
-                        let status =
-                            if let Some((success, error_msg)) = block_tx_hashes.get(tx_hash) {
-                                if *success {
-                                    TransactionStatus::finalized(
-                                        tx_hash.clone(),
-                                        first_seen,
-                                        format!("0x{}", hex::encode(block_hash.0)),
-                                        None,
-                                        None,
-                                        Some(confirmations as u32),
-                                    )
-                                } else {
-                                    TransactionStatus::failed(
-                                        tx_hash.clone(),
-                                        error_msg
-                                            .clone()
-                                            .unwrap_or_else(|| "Unknown error".to_string()),
-                                    )
-                                }
-                            } else {
-                                TransactionStatus::finalized(
-                                    tx_hash.clone(),
-                                    first_seen,
-                                    format!("0x{}", hex::encode(block_hash.0)),
-                                    None,
-                                    None,
-                                    Some(confirmations as u32),
-                                )
-                            };
-
-                        to_complete.push((tx_hash.clone(), status));
-                        metrics.record_transaction_success();
+        let mut pending = pending_txs.write().await;
+        let mut to_complete = Vec::new();
+
+        for (tx_hash, handle) in pending.iter_mut() {
+            let watched_from = *handle.watched_from_block.get_or_insert(block_number);
+
+            if let Some((success, error_msg)) = block_tx_hashes.get(tx_hash) {
+                if handle.first_seen_block.is_none() {
+                    handle.first_seen_block = Some(block_number);
+                    if let Some(tip) = handle.tip {
+                        latencies.record(tip, handle.congestion, handle.submitted_at.elapsed());
                     }
+                    info!(
+                        tx_hash = %tx_hash,
+                        block_number,
+                        "Transaction found in finalized block"
+                    );
                 }
+                handle.inclusion = Some(Inclusion {
+                    number: block_number,
+                    hash: block_hash,
+                    success: *success,
+                    error: error_msg.clone(),
+                    finalized: true,
+                });
+            } else if handle
+                .inclusion
+                .as_ref()
+                .is_some_and(|inclusion| !inclusion.finalized && inclusion.number == block_number)
+            {
+                // Another block was finalized at the height it was included at
+                warn!(
+                    tx_hash = %tx_hash,
+                    block_number,
+                    "Block containing transaction was not finalized, waiting for re-inclusion"
+                );
+                handle.inclusion = None;
+                handle
+                    .sender
+                    .update(TransactionStatus::pending(tx_hash.to_string()));
             }
 
-            for (tx_hash, status) in to_complete {
-                if let Some(handle) = pending.remove(&tx_hash) {
-                    let _ = handle.sender.send(status);
-                    debug!("Completed watching transaction: {}", tx_hash);
+            if let Some(first_seen) = handle.first_seen_block {
+                let confirmations = block_number.saturating_sub(first_seen);
+
+                let is_complete = match &handle.strategy {
+                    _ if matches!(handle.sender, StatusSender::Progress(_)) => true,
+                    ConfirmationStrategy::Immediate => true,
+                    ConfirmationStrategy::Finalized { .. } => true,
+                    ConfirmationStrategy::BlockConfirmations {
+                        confirmations: required,
+                        ..
+                    } => confirmations >= (*required as u64),
+                };
+
+                if let (true, Some(inclusion)) = (is_complete, &handle.inclusion) {
+                    to_complete.push((*tx_hash, inclusion.status(tx_hash, confirmations)));
+                }
+            } else if let Some(stuck_after) = handle.stuck_after {
+                // Blocks processed since the watch started, this one included
+                if handle.inclusion.is_none() && block_number - watched_from + 1 >= stuck_after {
+                    warn!(
+                        tx_hash = %tx_hash,
+                        blocks = stuck_after,
+                        "Transaction not included"
+                    );
+                    to_complete.push((*tx_hash, TransactionStatus::pending(tx_hash.to_string())));
                 }
             }
         }
 
+        Self::complete(&mut pending, metrics, to_complete);
         Ok(())
     }
 
-    /// Remove transactions that have exceeded the maximum watch duration
-    async fn cleanup_expired_transactions(
-        pending_txs: &Arc<RwLock<HashMap<String, TxWatchHandle>>>,
+    /// End watches with their final status
+    fn complete(
+        pending: &mut HashMap<TxHash, TxWatchHandle>,
+        metrics: &Arc<Metrics>,
+        statuses: Vec<(TxHash, TransactionStatus)>,
     ) {
+        for (tx_hash, status) in statuses {
+            if let Some(handle) = pending.remove(&tx_hash) {
+                if handle.inclusion.is_some() {
+                    metrics.record_transaction_success();
+                }
+                handle.sender.finish(status);
+                debug!("Completed watching transaction: {}", tx_hash);
+            }
+        }
+    }
+
+    /// End watches that have passed their timeout with a
+    /// [`TxStatus::TimedOut`](apex_sdk_types::TxStatus::TimedOut) status
+    async fn cleanup_expired_transactions(pending_txs: &PendingTxs) {
         let now = Instant::now();
         let mut pending = pending_txs.write().await;
         let mut expired = Vec::new();
 
         for (tx_hash, handle) in pending.iter() {
-            if now.duration_since(handle.submitted_at) > MAX_WATCH_DURATION {
-                expired.push(tx_hash.clone());
+            if now.duration_since(handle.submitted_at) >= handle.timeout {
+                expired.push(*tx_hash);
             }
         }
 
         for tx_hash in expired {
             if let Some(handle) = pending.remove(&tx_hash) {
-                let timeout_secs = match &handle.strategy {
-                    ConfirmationStrategy::BlockConfirmations { timeout_secs, .. } => *timeout_secs,
-                    ConfirmationStrategy::Finalized { timeout_secs } => *timeout_secs,
-                    ConfirmationStrategy::Immediate => 0,
-                };
-
-                let status = TransactionStatus::failed(
-                    tx_hash.clone(),
-                    format!("Timeout after {} seconds", timeout_secs),
-                );
-                let _ = handle.sender.send(status);
+                let status = timed_out(&tx_hash, &handle);
+                handle.sender.finish(status);
                 warn!(
-                    "Transaction {} expired after {:?}",
-                    tx_hash, MAX_WATCH_DURATION
+                    tx_hash = %tx_hash,
+                    timeout = ?handle.timeout,
+                    "Transaction watch timed out"
                 );
             }
         }
@@ -298,3 +904,110 @@ impl TransactionMonitor {
         }
     }
 }
+
+/// Status for a watch that reached its timeout, noting whether the
+/// transaction had been included
+fn timed_out(tx_hash: &TxHash, handle: &TxWatchHandle) -> TransactionStatus {
+    let block = handle.inclusion.as_ref().map(|inclusion| inclusion.number);
+    let error = match block {
+        Some(block) => format!(
+            "Timed out after {:?} waiting for confirmations of block {}",
+            handle.timeout, block
+        ),
+        None => format!("Timed out after {:?} waiting for inclusion", handle.timeout),
+    };
+    let mut status = TransactionStatus::timed_out(tx_hash.to_string(), error);
+    status.block_number = block;
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(byte: u8) -> H256 {
+        H256([byte; 32])
+    }
+
+    #[test]
+    fn test_best_chain_detects_orphaned_blocks() {
+        let mut chain = BestChain::default();
+        assert!(!chain.advance(10, hash(10), hash(9)));
+        assert!(!chain.advance(11, hash(11), hash(10)));
+        assert!(!chain.is_orphaned(10, hash(10)));
+        assert!(!chain.is_orphaned(11, hash(11)));
+
+        // A sibling of #11 becomes best
+        assert!(chain.advance(11, hash(0xb1), hash(10)));
+        assert!(chain.is_orphaned(11, hash(11)));
+        assert!(!chain.is_orphaned(11, hash(0xb1)));
+
+        // A fork from below #10 replaces it through the parent hash
+        assert!(chain.advance(11, hash(0xc1), hash(0xc0)));
+        assert!(chain.is_orphaned(10, hash(10)));
+
+        // A shorter best chain orphans the blocks above it
+        assert!(!chain.advance(12, hash(0xc2), hash(0xc1)));
+        assert!(chain.advance(12, hash(0xd2), hash(0xc1)));
+        assert!(chain.advance(11, hash(0xc1), hash(0xc0)));
+        assert!(chain.is_orphaned(12, hash(0xd2)));
+
+        // Heights never seen are not assumed orphaned
+        assert!(!chain.is_orphaned(5, hash(5)));
+        chain.prune(11);
+        assert!(!chain.is_orphaned(10, hash(10)));
+    }
+
+    #[test]
+    fn test_inclusion_status() {
+        let tx_hash = TxHash::new([7u8; 32]);
+        let mut inclusion = Inclusion {
+            number: 42,
+            hash: hash(42),
+            success: true,
+            error: None,
+            finalized: false,
+        };
+
+        let status = inclusion.status(&tx_hash, 2);
+        assert_eq!(status.status, apex_sdk_types::TxStatus::Confirmed);
+        assert_eq!(status.block_number, Some(42));
+        assert_eq!(status.confirmations, Some(2));
+
+        inclusion.finalized = true;
+        assert!(inclusion.status(&tx_hash, 0).is_finalized());
+
+        inclusion.success = false;
+        let status = inclusion.status(&tx_hash, 0);
+        assert_eq!(status.status, apex_sdk_types::TxStatus::Failed);
+        assert_eq!(status.block_number, Some(42));
+    }
+
+    #[test]
+    fn test_monitor_health_problems() {
+        let subscription = |last_block: u64| SubscriptionHealth {
+            subscribed: true,
+            last_block: Some(last_block),
+            last_block_at: Some(Instant::now()),
+            ..Default::default()
+        };
+        let mut health = MonitorHealth {
+            finalized: subscription(100),
+            best: subscription(102),
+            watched: 3,
+        };
+        assert_eq!(health.last_block(), Some(100));
+        assert_eq!(health.finality_lag(), Some(2));
+        assert_eq!(health.problem(), None);
+
+        health.best.last_block = Some(100 + MAX_FINALITY_LAG + 1);
+        assert!(health.problem().unwrap().contains("behind the best chain"));
+
+        health.finalized.subscribed = false;
+        health.finalized.consecutive_failures = 4;
+        assert!(health.problem().unwrap().contains("4 failed attempts"));
+
+        health.best = SubscriptionHealth::default();
+        assert_eq!(health.finality_lag(), None);
+    }
+}
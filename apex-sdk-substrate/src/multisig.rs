@@ -0,0 +1,655 @@
+//! Multisig pallet support
+//!
+//! Builds `Multisig::as_multi`, `approve_as_multi` and `cancel_as_multi` calls,
+//! derives multisig account addresses and reads pending operations from
+//! `Multisig::Multisigs` storage.
+//!
+//! A multisig operation goes through three steps:
+//!
+//! 1. **propose** - the first signatory submits `as_multi` without a timepoint,
+//!    which opens the operation and reserves a deposit
+//! 2. **approve** - further signatories submit `approve_as_multi` with the
+//!    call hash and the operation's timepoint
+//! 3. **execute** - the final signatory submits `as_multi` with the full call,
+//!    which dispatches it from the multisig account
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::{MultisigAccount, SubstrateAdapter, ChainConfig, Wallet};
+//!
+//! # async fn example(alice: Wallet, bob: Wallet, charlie: Wallet) -> apex_sdk_substrate::Result<()> {
+//! let adapter = SubstrateAdapter::connect_with_config(ChainConfig::westend()).await?;
+//! let multisig = MultisigAccount::new(
+//!     [alice.address(), bob.address(), charlie.address()],
+//!     2,
+//! )?;
+//!
+//! let call = subxt::dynamic::tx("System", "remark", vec![subxt::dynamic::Value::from_bytes(b"hi")]);
+//! let manager = adapter.multisig();
+//! let call_data = manager.encode_call(&call)?;
+//!
+//! let proposal = manager.propose(&multisig, &alice, &call_data).await?;
+//! println!("Opened at block {}", proposal.timepoint.height);
+//!
+//! // Second of two approvals: dispatches the call
+//! manager.execute(&multisig, &bob, &call_data).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::fee_estimator::{Weight, WeightV2};
+use crate::storage::StorageQuery;
+use crate::transaction::RawCall;
+use crate::wallet::Wallet;
+use crate::{Error, Result, SubstrateAdapter};
+use apex_sdk_types::{Address, IntoAddress, PageRequest};
+use parity_scale_codec::{Decode, Encode};
+use tracing::{debug, info};
+
+/// Entropy prefix used by pallet-multisig to derive multisig accounts
+const MULTISIG_PREFIX: &[u8; 16] = b"modlpy/utilisuba";
+
+/// Maximum number of signatories accepted by default runtime configurations
+pub const MAX_SIGNATORIES: usize = 100;
+
+/// Block height and extrinsic index of the first approval of an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct Timepoint {
+    /// Block number
+    pub height: u32,
+    /// Extrinsic index within the block
+    pub index: u32,
+}
+
+/// A multisig account defined by its signatories and threshold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultisigAccount {
+    signatories: Vec<[u8; 32]>,
+    threshold: u16,
+}
+
+impl MultisigAccount {
+    /// Create a multisig account from its signatories and approval threshold
+    ///
+    /// Signatories are deduplicated and sorted, so their order does not affect
+    /// the derived account.
+    pub fn new<I, A>(signatories: I, threshold: u16) -> Result<Self>
+    where
+        I: IntoIterator<Item = A>,
+        A: IntoAddress,
+    {
+        let mut accounts = signatories
+            .into_iter()
+            .map(|s| s.into_address()?.account_id())
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        accounts.sort();
+        accounts.dedup();
+
+        if accounts.len() < 2 {
+            return Err(Error::Transaction(
+                "A multisig account requires at least two distinct signatories".to_string(),
+            ));
+        }
+        if accounts.len() > MAX_SIGNATORIES {
+            return Err(Error::Transaction(format!(
+                "Too many signatories: {} (max {})",
+                accounts.len(),
+                MAX_SIGNATORIES
+            )));
+        }
+        if threshold < 2 || threshold as usize > accounts.len() {
+            return Err(Error::Transaction(format!(
+                "Threshold must be between 2 and {}, got {}",
+                accounts.len(),
+                threshold
+            )));
+        }
+
+        Ok(Self {
+            signatories: accounts,
+            threshold,
+        })
+    }
+
+    /// Sorted signatory account IDs
+    pub fn signatories(&self) -> &[[u8; 32]] {
+        &self.signatories
+    }
+
+    /// Number of approvals required to execute a call
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Derive the multisig account ID
+    pub fn account_id(&self) -> [u8; 32] {
+        multi_account_id(&self.signatories, self.threshold)
+    }
+
+    /// SS58 address of the multisig account
+    pub fn address(&self, ss58_prefix: u16) -> Result<Address> {
+        Ok(Address::from_account_id(&self.account_id(), ss58_prefix)?)
+    }
+
+    /// Check whether an account is one of the signatories
+    pub fn is_signatory(&self, account: &[u8; 32]) -> bool {
+        self.signatories.binary_search(account).is_ok()
+    }
+
+    /// Signatories other than `signer`, sorted as the pallet expects
+    pub fn other_signatories(&self, signer: &[u8; 32]) -> Result<Vec<[u8; 32]>> {
+        if !self.is_signatory(signer) {
+            return Err(Error::Transaction(format!(
+                "0x{} is not a signatory of this multisig",
+                hex::encode(signer)
+            )));
+        }
+
+        Ok(self
+            .signatories
+            .iter()
+            .filter(|s| *s != signer)
+            .copied()
+            .collect())
+    }
+}
+
+/// Derive a multisig account ID the same way pallet-multisig does
+///
+/// `blake2_256(("modlpy/utilisuba", sorted_signatories, threshold).encode())`
+pub fn multi_account_id(signatories: &[[u8; 32]], threshold: u16) -> [u8; 32] {
+    let mut sorted = signatories.to_vec();
+    sorted.sort();
+    sp_core::blake2_256(&(MULTISIG_PREFIX, sorted, threshold).encode())
+}
+
+/// Hash of a SCALE-encoded call, as used to identify multisig operations
+pub fn call_hash(call_data: &[u8]) -> [u8; 32] {
+    sp_core::blake2_256(call_data)
+}
+
+/// Pending multisig operation, from `Multisig::Multisigs` storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingMultisig {
+    /// Hash of the call being approved
+    pub call_hash: [u8; 32],
+    /// Timepoint of the first approval
+    pub when: Timepoint,
+    /// Deposit reserved from the depositor
+    pub deposit: u128,
+    /// Account that opened the operation
+    pub depositor: [u8; 32],
+    /// Accounts that have approved so far, sorted
+    pub approvals: Vec<[u8; 32]>,
+}
+
+impl PendingMultisig {
+    /// Check whether an account has already approved
+    pub fn is_approved_by(&self, account: &[u8; 32]) -> bool {
+        self.approvals.contains(account)
+    }
+
+    /// Number of approvals still required for the given threshold
+    pub fn approvals_needed(&self, threshold: u16) -> usize {
+        (threshold as usize).saturating_sub(self.approvals.len())
+    }
+}
+
+/// On-chain layout of `pallet_multisig::Multisig`
+#[derive(Decode)]
+struct MultisigInfo {
+    when: Timepoint,
+    deposit: u128,
+    depositor: [u8; 32],
+    approvals: Vec<[u8; 32]>,
+}
+
+impl MultisigInfo {
+    fn into_pending(self, call_hash: [u8; 32]) -> PendingMultisig {
+        PendingMultisig {
+            call_hash,
+            when: self.when,
+            deposit: self.deposit,
+            depositor: self.depositor,
+            approvals: self.approvals,
+        }
+    }
+}
+
+/// Result of opening a multisig operation
+#[derive(Debug, Clone)]
+pub struct MultisigProposal {
+    /// Hash of the proposal extrinsic
+    pub tx_hash: String,
+    /// Hash of the proposed call
+    pub call_hash: [u8; 32],
+    /// Timepoint other signatories must reference
+    pub timepoint: Timepoint,
+}
+
+/// High-level API for interacting with pallet-multisig
+pub struct MultisigManager<'a> {
+    adapter: &'a SubstrateAdapter,
+}
+
+impl<'a> MultisigManager<'a> {
+    pub fn new(adapter: &'a SubstrateAdapter) -> Self {
+        Self { adapter }
+    }
+
+    /// SCALE-encode a call so it can be wrapped in a multisig operation
+    pub fn encode_call<Call: subxt::tx::Payload>(&self, call: &Call) -> Result<Vec<u8>> {
+        Ok(self.adapter.client().tx().call_data(call)?)
+    }
+
+    /// Build `Multisig::as_multi`
+    ///
+    /// Pass `None` as `timepoint` to open a new operation; the final approval
+    /// must reference the timepoint of the first one.
+    pub fn as_multi(
+        &self,
+        multisig: &MultisigAccount,
+        signer: &[u8; 32],
+        timepoint: Option<Timepoint>,
+        call_data: &[u8],
+        max_weight: Weight,
+    ) -> Result<RawCall> {
+        let mut args = (
+            multisig.threshold,
+            multisig.other_signatories(signer)?,
+            timepoint,
+        )
+            .encode();
+        // `Box<RuntimeCall>` encodes as the call itself
+        args.extend_from_slice(call_data);
+        max_weight_v2(max_weight).encode_to(&mut args);
+
+        self.build("as_multi", args)
+    }
+
+    /// Build `Multisig::approve_as_multi`
+    pub fn approve_as_multi(
+        &self,
+        multisig: &MultisigAccount,
+        signer: &[u8; 32],
+        timepoint: Option<Timepoint>,
+        call_hash: [u8; 32],
+        max_weight: Weight,
+    ) -> Result<RawCall> {
+        let args = (
+            multisig.threshold,
+            multisig.other_signatories(signer)?,
+            timepoint,
+            call_hash,
+            max_weight_v2(max_weight),
+        )
+            .encode();
+
+        self.build("approve_as_multi", args)
+    }
+
+    /// Build `Multisig::cancel_as_multi`
+    ///
+    /// Only the depositor of the operation can cancel it.
+    pub fn cancel_as_multi(
+        &self,
+        multisig: &MultisigAccount,
+        signer: &[u8; 32],
+        timepoint: Timepoint,
+        call_hash: [u8; 32],
+    ) -> Result<RawCall> {
+        let args = (
+            multisig.threshold,
+            multisig.other_signatories(signer)?,
+            timepoint,
+            call_hash,
+        )
+            .encode();
+
+        self.build("cancel_as_multi", args)
+    }
+
+    fn build(&self, call: &str, args: Vec<u8>) -> Result<RawCall> {
+        let metadata = self.adapter.client().metadata();
+        let pallet = metadata
+            .pallet_by_name("Multisig")
+            .ok_or_else(|| Error::Metadata("Multisig pallet not found in runtime".to_string()))?;
+        let variant = pallet.call_variant_by_name(call).ok_or_else(|| {
+            Error::Metadata(format!("Call Multisig::{} not found in runtime", call))
+        })?;
+
+        let mut call_data = vec![pallet.index(), variant.index];
+        call_data.extend(args);
+        RawCall::new(call_data)
+    }
+
+    /// Fetch a pending operation by call hash
+    pub async fn pending(
+        &self,
+        multisig: &MultisigAccount,
+        call_hash: [u8; 32],
+    ) -> Result<Option<PendingMultisig>> {
+        let value = StorageQuery::new("Multisig", "Multisigs")
+            .account_id(multisig.account_id())
+            .h256(call_hash)
+            .execute(&self.adapter.storage())
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query multisig: {}", e)))?;
+
+        value
+            .map(|bytes| decode_multisig(&bytes, call_hash))
+            .transpose()
+    }
+
+    /// List all pending operations of a multisig account
+    pub async fn pending_operations(
+        &self,
+        multisig: &MultisigAccount,
+    ) -> Result<Vec<PendingMultisig>> {
+        let query = StorageQuery::new("Multisig", "Multisigs").account_id(multisig.account_id());
+        let storage = self.adapter.storage();
+
+        let mut pending = Vec::new();
+        let mut request = PageRequest::new(PageRequest::MAX_LIMIT);
+        loop {
+            let page = query
+                .page_raw(&storage, &request)
+                .await
+                .map_err(|e| Error::Storage(format!("Failed to iterate multisigs: {}", e)))?;
+            for (key, value) in &page.items {
+                // Blake2_128Concat keeps the call hash as the last 32 key bytes
+                let call_hash = key
+                    .len()
+                    .checked_sub(32)
+                    .and_then(|start| <[u8; 32]>::try_from(&key[start..]).ok())
+                    .ok_or_else(|| Error::Storage("Malformed multisig storage key".to_string()))?;
+                pending.push(decode_multisig(value, call_hash)?);
+            }
+            match page.next_request(PageRequest::MAX_LIMIT) {
+                Some(next) => request = next,
+                None => break,
+            }
+        }
+
+        debug!(
+            address = %hex::encode(multisig.account_id()),
+            count = pending.len(),
+            "Fetched pending multisig operations"
+        );
+        Ok(pending)
+    }
+
+    /// Estimate the weight of a call via `TransactionPaymentCallApi`
+    pub async fn estimate_call_weight(&self, call_data: &[u8]) -> Result<Weight> {
+        let mut params = call_data.to_vec();
+        (call_data.len() as u32).encode_to(&mut params);
+
+        let result = self
+            .adapter
+            .client()
+            .runtime_api()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?
+            .call_raw("TransactionPaymentCallApi_query_call_info", Some(&params))
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to query call info: {}", e)))?;
+
+        let info = crate::fee_estimator::RuntimeDispatchInfo::decode(&mut &result[..])
+            .map_err(|e| Error::Transaction(format!("Failed to decode dispatch info: {}", e)))?;
+        Ok(Weight::new(info.weight.ref_time, info.weight.proof_size))
+    }
+
+    /// Open a multisig operation for a call
+    ///
+    /// Submits `as_multi` without a timepoint and reads the timepoint back
+    /// from storage once the extrinsic is finalized.
+    pub async fn propose(
+        &self,
+        multisig: &MultisigAccount,
+        signer: &Wallet,
+        call_data: &[u8],
+    ) -> Result<MultisigProposal> {
+        let signer_id = wallet_account_id(signer)?;
+        let hash = call_hash(call_data);
+
+        if self.pending(multisig, hash).await?.is_some() {
+            return Err(Error::Transaction(format!(
+                "Multisig operation 0x{} is already pending",
+                hex::encode(hash)
+            )));
+        }
+
+        info!(
+            operation = "multisig_propose",
+            wallet = %signer.address(),
+            call_hash = %hex::encode(hash),
+            "Proposing multisig call"
+        );
+
+        let call = self.as_multi(multisig, &signer_id, None, call_data, Weight::default())?;
+        let tx_hash = self.submit(signer, call).await?;
+
+        let pending = self.pending(multisig, hash).await?.ok_or_else(|| {
+            Error::Transaction("Multisig operation not found after proposal".to_string())
+        })?;
+
+        Ok(MultisigProposal {
+            tx_hash,
+            call_hash: hash,
+            timepoint: pending.when,
+        })
+    }
+
+    /// Approve a pending operation without executing it
+    pub async fn approve(
+        &self,
+        multisig: &MultisigAccount,
+        signer: &Wallet,
+        call_hash: [u8; 32],
+    ) -> Result<String> {
+        let signer_id = wallet_account_id(signer)?;
+        let pending = self.require_pending(multisig, call_hash).await?;
+
+        if pending.is_approved_by(&signer_id) {
+            return Err(Error::Transaction(format!(
+                "{} has already approved this operation",
+                signer.address()
+            )));
+        }
+
+        info!(
+            operation = "multisig_approve",
+            wallet = %signer.address(),
+            call_hash = %hex::encode(call_hash),
+            remaining = pending.approvals_needed(multisig.threshold),
+            "Approving multisig call"
+        );
+
+        let call = self.approve_as_multi(
+            multisig,
+            &signer_id,
+            Some(pending.when),
+            call_hash,
+            Weight::default(),
+        )?;
+        self.submit(signer, call).await
+    }
+
+    /// Give the final approval and dispatch the call
+    ///
+    /// The call weight is estimated from the runtime and used as `max_weight`.
+    pub async fn execute(
+        &self,
+        multisig: &MultisigAccount,
+        signer: &Wallet,
+        call_data: &[u8],
+    ) -> Result<String> {
+        let signer_id = wallet_account_id(signer)?;
+        let hash = call_hash(call_data);
+        let pending = self.require_pending(multisig, hash).await?;
+
+        let approvals = pending.approvals.len() + usize::from(!pending.is_approved_by(&signer_id));
+        if approvals < multisig.threshold as usize {
+            return Err(Error::Transaction(format!(
+                "Not enough approvals to execute: {} of {}",
+                approvals, multisig.threshold
+            )));
+        }
+
+        let max_weight = self.estimate_call_weight(call_data).await?;
+
+        info!(
+            operation = "multisig_execute",
+            wallet = %signer.address(),
+            call_hash = %hex::encode(hash),
+            ref_time = max_weight.ref_time,
+            "Executing multisig call"
+        );
+
+        let call = self.as_multi(
+            multisig,
+            &signer_id,
+            Some(pending.when),
+            call_data,
+            max_weight,
+        )?;
+        self.submit(signer, call).await
+    }
+
+    /// Cancel a pending operation and release the deposit
+    pub async fn cancel(
+        &self,
+        multisig: &MultisigAccount,
+        signer: &Wallet,
+        call_hash: [u8; 32],
+    ) -> Result<String> {
+        let signer_id = wallet_account_id(signer)?;
+        let pending = self.require_pending(multisig, call_hash).await?;
+
+        if pending.depositor != signer_id {
+            return Err(Error::Transaction(
+                "Only the depositor can cancel a multisig operation".to_string(),
+            ));
+        }
+
+        info!(
+            operation = "multisig_cancel",
+            wallet = %signer.address(),
+            call_hash = %hex::encode(call_hash),
+            "Cancelling multisig call"
+        );
+
+        let call = self.cancel_as_multi(multisig, &signer_id, pending.when, call_hash)?;
+        self.submit(signer, call).await
+    }
+
+    async fn require_pending(
+        &self,
+        multisig: &MultisigAccount,
+        call_hash: [u8; 32],
+    ) -> Result<PendingMultisig> {
+        self.pending(multisig, call_hash).await?.ok_or_else(|| {
+            Error::Transaction(format!(
+                "No pending multisig operation for call 0x{}",
+                hex::encode(call_hash)
+            ))
+        })
+    }
+
+    async fn submit(&self, signer: &Wallet, call: RawCall) -> Result<String> {
+        self.adapter
+            .transaction_executor()
+            .execute_call(signer, call.call_data().to_vec())
+            .await
+    }
+}
+
+fn max_weight_v2(weight: Weight) -> WeightV2 {
+    WeightV2 {
+        ref_time: weight.ref_time,
+        proof_size: weight.proof_size,
+    }
+}
+
+fn decode_multisig(bytes: &[u8], call_hash: [u8; 32]) -> Result<PendingMultisig> {
+    MultisigInfo::decode(&mut &bytes[..])
+        .map(|info| info.into_pending(call_hash))
+        .map_err(|e| Error::Encoding(format!("Failed to decode multisig: {}", e)))
+}
+
+fn wallet_account_id(wallet: &Wallet) -> Result<[u8; 32]> {
+    wallet
+        .public_key()
+        .try_into()
+        .map_err(|_| Error::Wallet("Public key must be 32 bytes".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+    const CHARLIE: &str = "5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y";
+
+    #[test]
+    fn test_multisig_account_is_order_independent() {
+        let a = MultisigAccount::new([ALICE, BOB, CHARLIE], 2).unwrap();
+        let b = MultisigAccount::new([CHARLIE, ALICE, BOB], 2).unwrap();
+        assert_eq!(a.account_id(), b.account_id());
+
+        let c = MultisigAccount::new([ALICE, BOB, CHARLIE], 3).unwrap();
+        assert_ne!(a.account_id(), c.account_id());
+    }
+
+    #[test]
+    fn test_multisig_account_id() {
+        let multisig = MultisigAccount::new([ALICE, BOB, CHARLIE], 2).unwrap();
+        assert_eq!(
+            multisig.address(42).unwrap().to_string(),
+            "5DjYJStmdZ2rcqXbXGX7TW85JsrW6uG4y9MUcLq2BoPMpRA7"
+        );
+    }
+
+    #[test]
+    fn test_multisig_account_validation() {
+        assert!(MultisigAccount::new([ALICE, ALICE], 2).is_err());
+        assert!(MultisigAccount::new([ALICE, BOB], 1).is_err());
+        assert!(MultisigAccount::new([ALICE, BOB], 3).is_err());
+        assert!(MultisigAccount::new(["not-an-address", BOB], 2).is_err());
+    }
+
+    #[test]
+    fn test_other_signatories() {
+        let multisig = MultisigAccount::new([ALICE, BOB, CHARLIE], 2).unwrap();
+        let alice = Address::substrate(ALICE).account_id().unwrap();
+
+        let others = multisig.other_signatories(&alice).unwrap();
+        assert_eq!(others.len(), 2);
+        assert!(!others.contains(&alice));
+        assert!(others.windows(2).all(|w| w[0] < w[1]));
+
+        assert!(multisig.other_signatories(&[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_decode_pending_multisig() {
+        let approver = [7u8; 32];
+        let encoded = (
+            Timepoint {
+                height: 100,
+                index: 2,
+            },
+            500u128,
+            approver,
+            vec![approver],
+        )
+            .encode();
+
+        let pending = decode_multisig(&encoded, [1u8; 32]).unwrap();
+        assert_eq!(pending.when.height, 100);
+        assert_eq!(pending.deposit, 500);
+        assert!(pending.is_approved_by(&approver));
+        assert_eq!(pending.approvals_needed(3), 2);
+    }
+}
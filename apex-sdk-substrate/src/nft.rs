@@ -21,8 +21,9 @@ impl<'a> NftManager<'a> {
         metadata: String,
     ) -> Result<subxt::tx::DynamicPayload> {
         info!(
-            "Preparing to create NFT collection {} for {}",
-            collection_id, owner
+            collection_id,
+            owner = %owner,
+            "Preparing to create NFT collection"
         );
         let owner_val = Value::string(owner.to_string());
         let payload = subxt::dynamic::tx(
@@ -46,8 +47,10 @@ impl<'a> NftManager<'a> {
         metadata: String,
     ) -> Result<subxt::tx::DynamicPayload> {
         info!(
-            "Preparing to mint NFT item {} in collection {} for {}",
-            item_id, collection_id, owner
+            collection_id,
+            item_id,
+            owner = %owner,
+            "Preparing to mint NFT item"
         );
         let owner_val = Value::string(owner.to_string());
         let payload = subxt::dynamic::tx(
@@ -71,8 +74,10 @@ impl<'a> NftManager<'a> {
         to: &Address,
     ) -> Result<subxt::tx::DynamicPayload> {
         info!(
-            "Preparing to transfer NFT item {} in collection {} to {}",
-            item_id, collection_id, to
+            collection_id,
+            item_id,
+            to = %to,
+            "Preparing to transfer NFT item"
         );
         let to_val = Value::string(to.to_string());
         let payload = subxt::dynamic::tx(
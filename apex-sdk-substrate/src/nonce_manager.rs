@@ -33,10 +33,10 @@ impl SubstrateNonceManager {
 
     /// Get nonce from network (via System::Account storage query)
     async fn get_network_nonce(&self, address: &str) -> Result<u64> {
-        debug!("Fetching network nonce for address: {}", address);
+        debug!(wallet = address, "Fetching network nonce");
 
         self.storage_client.get_nonce(address).await.map_err(|e| {
-            warn!("Failed to fetch network nonce for {}: {}", address, e);
+            warn!(wallet = address, error = %e, "Failed to fetch network nonce");
             e
         })
     }
@@ -45,7 +45,7 @@ impl SubstrateNonceManager {
     fn update_local_nonce(&self, address: &str, nonce: u64) {
         let mut local_nonces = self.local_nonces.lock().unwrap();
         local_nonces.insert(address.to_string(), nonce);
-        debug!("Updated local nonce cache for {} to {}", address, nonce);
+        debug!(wallet = address, nonce, "Updated local nonce cache");
     }
 
     /// Get local nonce from cache
@@ -71,13 +71,17 @@ impl SubstrateNonceManager {
 
                 if local > network_nonce {
                     debug!(
-                        "Local nonce {} ahead of network nonce {} for address {} (pending transactions)",
-                        local, network_nonce, address
+                        wallet = address,
+                        local,
+                        network_nonce,
+                        "Local nonce ahead of network nonce (pending transactions)"
                     );
                 } else if local < network_nonce {
                     debug!(
-                        "Network nonce {} ahead of local nonce {} for address {} (transactions confirmed)",
-                        network_nonce, local, address
+                        wallet = address,
+                        local,
+                        network_nonce,
+                        "Network nonce ahead of local nonce (transactions confirmed)"
                     );
                 }
 
@@ -85,8 +89,8 @@ impl SubstrateNonceManager {
             }
             None => {
                 debug!(
-                    "First nonce request for {}, using network nonce: {}",
-                    address, network_nonce
+                    wallet = address,
+                    network_nonce, "First nonce request, using network nonce"
                 );
                 network_nonce
             }
@@ -95,8 +99,11 @@ impl SubstrateNonceManager {
         self.update_local_nonce(address, next_nonce + 1);
 
         debug!(
-            "Reconciled nonce for {}: network={}, local={:?}, returning={}",
-            address, network_nonce, local_nonce, next_nonce
+            wallet = address,
+            network_nonce,
+            local_nonce = ?local_nonce,
+            next_nonce,
+            "Reconciled nonce"
         );
 
         Ok(next_nonce)
@@ -107,14 +114,14 @@ impl SubstrateNonceManager {
     /// This is useful after transaction failures or when recovering from errors.
     /// Call this method to resync with the chain's actual nonce.
     pub async fn reset_nonce(&self, address: &str) -> Result<()> {
-        debug!("Resetting nonce for address: {}", address);
+        debug!(wallet = address, "Resetting nonce");
 
         let network_nonce = self.get_network_nonce(address).await?;
         self.update_local_nonce(address, network_nonce);
 
         debug!(
-            "Reset local nonce for {} to network value: {}",
-            address, network_nonce
+            wallet = address,
+            network_nonce, "Reset local nonce to network value"
         );
 
         Ok(())
@@ -5,6 +5,7 @@
 //! - Health checks for WebSocket endpoints
 //! - Automatic failover to backup endpoints
 //! - Connection reuse
+//! - Routing of submissions, reads and historical queries to endpoints by role
 
 use crate::{Error, SubstrateAdapter};
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -12,6 +13,117 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+/// What an endpoint in the pool is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndpointRole {
+    /// Low-latency full node that transactions are submitted to
+    Submit,
+    /// Full node for queries and dry-runs against recent state
+    Read,
+    /// Archive node that keeps the state of every block
+    Archive,
+}
+
+/// Kind of operation, routed to an endpoint by [`EndpointRole`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperationClass {
+    /// Transaction submission
+    Submit,
+    /// Query against recent state
+    Read,
+    /// Dry-run of a call or fee estimate, which never broadcasts
+    DryRun,
+    /// Query against the state of an old block, which full nodes prune
+    Historical,
+}
+
+impl OperationClass {
+    /// Roles that can serve this class, most preferred first
+    ///
+    /// Submissions prefer submit endpoints, and reads and dry-runs prefer
+    /// read endpoints, so neither slows down the other; both fall back to any
+    /// other role. Historical queries need an archive endpoint.
+    pub fn roles(&self) -> &'static [EndpointRole] {
+        match self {
+            OperationClass::Submit => &[
+                EndpointRole::Submit,
+                EndpointRole::Read,
+                EndpointRole::Archive,
+            ],
+            OperationClass::Read | OperationClass::DryRun => &[
+                EndpointRole::Read,
+                EndpointRole::Archive,
+                EndpointRole::Submit,
+            ],
+            OperationClass::Historical => &[EndpointRole::Archive],
+        }
+    }
+}
+
+impl std::fmt::Display for OperationClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            OperationClass::Submit => "submit",
+            OperationClass::Read => "read",
+            OperationClass::DryRun => "dry-run",
+            OperationClass::Historical => "historical",
+        })
+    }
+}
+
+/// Endpoint URL and the roles it serves in a [`ConnectionPool`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolEndpoint {
+    /// WebSocket URL
+    pub url: String,
+    /// Roles the endpoint serves
+    pub roles: Vec<EndpointRole>,
+}
+
+impl PoolEndpoint {
+    /// Full node serving submissions and reads
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_roles(url, [EndpointRole::Submit, EndpointRole::Read])
+    }
+
+    /// Endpoint serving the given roles
+    pub fn with_roles(
+        url: impl Into<String>,
+        roles: impl IntoIterator<Item = EndpointRole>,
+    ) -> Self {
+        Self {
+            url: url.into(),
+            roles: roles.into_iter().collect(),
+        }
+    }
+
+    /// Endpoint used only for submissions
+    pub fn submit(url: impl Into<String>) -> Self {
+        Self::with_roles(url, [EndpointRole::Submit])
+    }
+
+    /// Endpoint used only for reads and dry-runs
+    pub fn read(url: impl Into<String>) -> Self {
+        Self::with_roles(url, [EndpointRole::Read])
+    }
+
+    /// Archive endpoint, which also serves reads and dry-runs
+    pub fn archive(url: impl Into<String>) -> Self {
+        Self::with_roles(url, [EndpointRole::Archive, EndpointRole::Read])
+    }
+
+    /// Whether the endpoint serves `role`
+    pub fn has_role(&self, role: EndpointRole) -> bool {
+        self.roles.contains(&role)
+    }
+}
+
+impl From<String> for PoolEndpoint {
+    fn from(url: String) -> Self {
+        Self::new(url)
+    }
+}
+
 /// Health status enumeration
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HealthStatus {
@@ -75,6 +187,7 @@ impl Default for EndpointHealth {
 pub struct PooledConnection {
     adapter: Arc<SubstrateAdapter>,
     endpoint: String,
+    roles: Vec<EndpointRole>,
     health: Arc<RwLock<EndpointHealth>>,
 }
 
@@ -89,6 +202,20 @@ impl PooledConnection {
         &self.endpoint
     }
 
+    /// Roles the endpoint serves
+    pub fn roles(&self) -> &[EndpointRole] {
+        &self.roles
+    }
+
+    fn share(&self) -> Arc<PooledConnection> {
+        Arc::new(PooledConnection {
+            adapter: self.adapter.clone(),
+            endpoint: self.endpoint.clone(),
+            roles: self.roles.clone(),
+            health: self.health.clone(),
+        })
+    }
+
     /// Get current health status
     pub async fn health(&self) -> EndpointHealth {
         self.health.read().await.clone()
@@ -118,7 +245,7 @@ impl PooledConnection {
         // Mark as unhealthy after 3 consecutive failures
         if health.failure_count >= 3 {
             health.is_healthy = false;
-            tracing::warn!("Endpoint {} marked as unhealthy", self.endpoint);
+            tracing::warn!(endpoint = %self.endpoint, "Endpoint marked as unhealthy");
         }
     }
 }
@@ -197,32 +324,50 @@ impl ConnectionPool {
     }
 
     /// Create a new connection pool with custom configuration
+    ///
+    /// Every endpoint serves submissions and reads; use
+    /// [`with_endpoints`](Self::with_endpoints) to assign roles.
     pub async fn with_config(endpoints: Vec<String>, config: PoolConfig) -> Result<Self, Error> {
+        let endpoints = endpoints.into_iter().map(PoolEndpoint::from).collect();
+        Self::with_endpoints(endpoints, config).await
+    }
+
+    /// Create a connection pool of endpoints with roles
+    pub async fn with_endpoints(
+        endpoints: Vec<PoolEndpoint>,
+        config: PoolConfig,
+    ) -> Result<Self, Error> {
         if endpoints.is_empty() {
             return Err(Error::Connection("No endpoints provided".to_string()));
         }
 
         tracing::info!(
-            "Creating connection pool with {} endpoints",
-            endpoints.len()
+            operation = "pool_connect",
+            endpoints = endpoints.len(),
+            "Creating connection pool"
         );
 
         let mut connections = Vec::new();
 
         // Create initial connections
-        for endpoint in &endpoints {
+        for PoolEndpoint {
+            url: endpoint,
+            roles,
+        } in &endpoints
+        {
             match SubstrateAdapter::connect(endpoint).await {
                 Ok(adapter) => {
                     let conn = PooledConnection {
                         adapter: Arc::new(adapter),
                         endpoint: endpoint.clone(),
+                        roles: roles.clone(),
                         health: Arc::new(RwLock::new(EndpointHealth::default())),
                     };
                     connections.push(conn);
-                    tracing::info!("Successfully connected to endpoint: {}", endpoint);
+                    tracing::info!(endpoint = %endpoint, "Connected to endpoint");
                 }
                 Err(e) => {
-                    tracing::warn!("Failed to connect to endpoint {}: {}", endpoint, e);
+                    tracing::warn!(endpoint = %endpoint, error = %e, "Failed to connect to endpoint");
                     // Create unhealthy connection
                     let adapter = SubstrateAdapter::connect(endpoint).await?;
                     let health = EndpointHealth {
@@ -234,6 +379,7 @@ impl ConnectionPool {
                     let conn = PooledConnection {
                         adapter: Arc::new(adapter),
                         endpoint: endpoint.clone(),
+                        roles: roles.clone(),
                         health: Arc::new(RwLock::new(health)),
                     };
                     connections.push(conn);
@@ -242,7 +388,7 @@ impl ConnectionPool {
         }
 
         Ok(Self {
-            endpoints,
+            endpoints: endpoints.into_iter().map(|endpoint| endpoint.url).collect(),
             connections: Arc::new(RwLock::new(connections)),
             next_index: AtomicUsize::new(0),
             config,
@@ -259,34 +405,60 @@ impl ConnectionPool {
             return Err(Error::Connection("No connections available".to_string()));
         }
 
-        let total = connections.len();
+        let candidates: Vec<usize> = (0..connections.len()).collect();
+        Ok(self.pick(&connections, &candidates).await)
+    }
+
+    /// Get a connection for an operation class, by endpoint role
+    ///
+    /// Picks round-robin among the healthy endpoints with the most preferred
+    /// role in [`OperationClass::roles`] that any endpoint has. Fails with
+    /// [`Error::NoEndpointForOperation`] when no endpoint has any of them,
+    /// e.g. a historical query to a pool without an archive endpoint.
+    pub async fn get_connection_for(
+        &self,
+        class: OperationClass,
+    ) -> Result<Arc<PooledConnection>, Error> {
+        let connections = self.connections.read().await;
+        let roles: Vec<&[EndpointRole]> = connections.iter().map(|conn| conn.roles()).collect();
+        let (role, candidates) =
+            route(&roles, class).ok_or(Error::NoEndpointForOperation(class))?;
+
+        if role != class.roles()[0] {
+            tracing::debug!(%class, ?role, "No preferred endpoint, falling back");
+        }
+        Ok(self.pick(&connections, &candidates).await)
+    }
+
+    /// Round-robin over `candidates`, skipping unhealthy connections
+    async fn pick(
+        &self,
+        connections: &[PooledConnection],
+        candidates: &[usize],
+    ) -> Arc<PooledConnection> {
+        let total = candidates.len();
         let mut attempts = 0;
 
         // Try to find a healthy connection
         while attempts < total {
-            let index = self.next_index.fetch_add(1, Ordering::Relaxed) % total;
+            let index = candidates[self.next_index.fetch_add(1, Ordering::Relaxed) % total];
             let conn = &connections[index];
 
             let health = conn.health.read().await;
             if health.is_healthy {
                 drop(health);
-                return Ok(Arc::new(PooledConnection {
-                    adapter: conn.adapter.clone(),
-                    endpoint: conn.endpoint.clone(),
-                    health: conn.health.clone(),
-                }));
+                return conn.share();
             }
 
             // Check if enough time has passed to retry unhealthy endpoint
             if let Some(last_failure) = health.last_failure {
                 if last_failure.elapsed().as_secs() > self.config.unhealthy_retry_delay_secs {
                     drop(health);
-                    tracing::info!("Retrying previously unhealthy endpoint: {}", conn.endpoint);
-                    return Ok(Arc::new(PooledConnection {
-                        adapter: conn.adapter.clone(),
-                        endpoint: conn.endpoint.clone(),
-                        health: conn.health.clone(),
-                    }));
+                    tracing::info!(
+                        endpoint = %conn.endpoint,
+                        "Retrying previously unhealthy endpoint"
+                    );
+                    return conn.share();
                 }
             }
 
@@ -294,13 +466,8 @@ impl ConnectionPool {
         }
 
         // All endpoints unhealthy, return the first one and let caller handle retry
-        let conn = &connections[0];
         tracing::warn!("All endpoints unhealthy, returning first endpoint");
-        Ok(Arc::new(PooledConnection {
-            adapter: conn.adapter.clone(),
-            endpoint: conn.endpoint.clone(),
-            health: conn.health.clone(),
-        }))
+        connections[candidates[0]].share()
     }
 
     /// Get health status of all endpoints
@@ -330,11 +497,15 @@ impl ConnectionPool {
                 Ok(_) => {
                     let elapsed = start.elapsed().as_millis() as u64;
                     conn.mark_healthy(elapsed).await;
-                    tracing::debug!("Health check passed for {}: {}ms", conn.endpoint, elapsed);
+                    tracing::debug!(
+                        endpoint = %conn.endpoint,
+                        elapsed_ms = elapsed,
+                        "Health check passed"
+                    );
                 }
                 Err(e) => {
                     conn.mark_unhealthy().await;
-                    tracing::warn!("Health check failed for {}: {}", conn.endpoint, e);
+                    tracing::warn!(endpoint = %conn.endpoint, error = %e, "Health check failed");
                 }
             }
         }
@@ -353,12 +524,16 @@ impl ConnectionPool {
                 tokio::time::sleep(interval).await;
 
                 if let Err(e) = pool.run_health_checks().await {
-                    tracing::error!("Health check error: {}", e);
+                    tracing::error!(operation = "health_check", error = %e, "Health check error");
                 }
             }
         });
 
-        tracing::info!("Started health checker with interval: {}s", interval_secs);
+        tracing::info!(
+            operation = "health_check",
+            interval_secs,
+            "Started health checker"
+        );
     }
 
     /// Get the number of endpoints
@@ -372,6 +547,23 @@ impl ConnectionPool {
     }
 }
 
+/// Most preferred role for `class` that any endpoint has, and the indices
+/// of the endpoints with it
+fn route(
+    endpoint_roles: &[&[EndpointRole]],
+    class: OperationClass,
+) -> Option<(EndpointRole, Vec<usize>)> {
+    class.roles().iter().find_map(|role| {
+        let candidates: Vec<usize> = endpoint_roles
+            .iter()
+            .enumerate()
+            .filter(|(_, roles)| roles.contains(role))
+            .map(|(index, _)| index)
+            .collect();
+        (!candidates.is_empty()).then_some((*role, candidates))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,6 +586,45 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_route_by_role() {
+        use EndpointRole::*;
+        let submit = PoolEndpoint::submit("wss://a");
+        let full = PoolEndpoint::new("wss://b");
+        let archive = PoolEndpoint::archive("wss://c");
+        let roles = |endpoints: &[&PoolEndpoint]| -> Vec<Vec<EndpointRole>> {
+            endpoints.iter().map(|e| e.roles.clone()).collect()
+        };
+
+        let all = roles(&[&submit, &full, &archive]);
+        let all: Vec<&[EndpointRole]> = all.iter().map(Vec::as_slice).collect();
+        assert_eq!(
+            route(&all, OperationClass::Submit),
+            Some((Submit, vec![0, 1]))
+        );
+        assert_eq!(
+            route(&all, OperationClass::DryRun),
+            Some((Read, vec![1, 2]))
+        );
+        assert_eq!(
+            route(&all, OperationClass::Historical),
+            Some((Archive, vec![2]))
+        );
+
+        // Reads fall back to the submit endpoint; historical queries cannot
+        let only_submit = [submit.roles.as_slice()];
+        assert_eq!(
+            route(&only_submit, OperationClass::Read),
+            Some((Submit, vec![0]))
+        );
+        assert_eq!(route(&only_submit, OperationClass::Historical), None);
+        assert!(full.has_role(Submit) && !full.has_role(Archive));
+        assert_eq!(
+            Error::NoEndpointForOperation(OperationClass::Historical).to_string(),
+            "No endpoint in the pool can serve historical operations"
+        );
+    }
+
     #[tokio::test]
     #[ignore] // Requires network
     async fn test_connection_pool_integration() {
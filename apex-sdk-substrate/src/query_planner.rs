@@ -0,0 +1,390 @@
+//! Read query planning
+//!
+//! Dashboards read balances, nonces and asset balances for many addresses at
+//! once. Issuing one storage query per read wastes round trips: balance and
+//! nonce share the `System::Account` entry, and any number of storage entries
+//! can be read at the same block with a single `state_queryStorageAt` call.
+//!
+//! [`QueryPlanner`] coalesces a batch of reads into the minimal set of
+//! storage entries, fetched in chunks of multi-queries, and
+//! [`QueryPlan::explain`] shows how the reads map onto RPC calls.
+
+use crate::chain_client::{StorageKey, Value};
+use crate::storage::{extract_u128, AccountInfo};
+use crate::{Error, Result, SubstrateAdapter};
+use apex_sdk_types::Address;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Default number of storage keys fetched per batched storage call
+pub const DEFAULT_BATCH_SIZE: usize = 256;
+
+/// A read requested from the planner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadRequest {
+    /// Free balance of an account
+    Balance(Address),
+    /// Nonce of an account
+    Nonce(Address),
+    /// Full `System::Account` information
+    AccountInfo(Address),
+    /// Balance of an account in a pallet-assets asset
+    AssetBalance { asset_id: u32, address: Address },
+}
+
+impl ReadRequest {
+    /// Storage entry that answers this read
+    fn entry(&self) -> Result<StorageEntry> {
+        Ok(match self {
+            ReadRequest::Balance(address)
+            | ReadRequest::Nonce(address)
+            | ReadRequest::AccountInfo(address) => StorageEntry::Account(address.account_id()?),
+            ReadRequest::AssetBalance { asset_id, address } => StorageEntry::AssetAccount {
+                asset_id: *asset_id,
+                account: address.account_id()?,
+            },
+        })
+    }
+}
+
+impl fmt::Display for ReadRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadRequest::Balance(address) => write!(f, "balance({})", address),
+            ReadRequest::Nonce(address) => write!(f, "nonce({})", address),
+            ReadRequest::AccountInfo(address) => write!(f, "account_info({})", address),
+            ReadRequest::AssetBalance { asset_id, address } => {
+                write!(f, "asset_balance({}, {})", asset_id, address)
+            }
+        }
+    }
+}
+
+/// Result of a [`ReadRequest`]
+#[derive(Debug, Clone)]
+pub enum ReadValue {
+    /// Free balance
+    Balance(u128),
+    /// Account nonce
+    Nonce(u64),
+    /// Account information
+    AccountInfo(AccountInfo),
+    /// Asset balance
+    AssetBalance(u128),
+}
+
+/// Storage entry fetched by a plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageEntry {
+    /// `System::Account` of an account ID
+    Account([u8; 32]),
+    /// `Assets::Account` of an asset and account ID
+    AssetAccount { asset_id: u32, account: [u8; 32] },
+}
+
+impl StorageEntry {
+    /// Pallet and storage item names
+    pub fn location(&self) -> (&'static str, &'static str) {
+        match self {
+            StorageEntry::Account(_) => ("System", "Account"),
+            StorageEntry::AssetAccount { .. } => ("Assets", "Account"),
+        }
+    }
+
+    fn keys(&self) -> Vec<Value> {
+        match self {
+            StorageEntry::Account(account) => vec![Value::from_bytes(account)],
+            StorageEntry::AssetAccount { asset_id, account } => {
+                vec![Value::u128(*asset_id as u128), Value::from_bytes(account)]
+            }
+        }
+    }
+}
+
+impl fmt::Display for StorageEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (pallet, item) = self.location();
+        match self {
+            StorageEntry::Account(account) => {
+                write!(f, "{}::{}(0x{})", pallet, item, hex::encode(account))
+            }
+            StorageEntry::AssetAccount { asset_id, account } => write!(
+                f,
+                "{}::{}({}, 0x{})",
+                pallet,
+                item,
+                asset_id,
+                hex::encode(account)
+            ),
+        }
+    }
+}
+
+/// A storage entry in a plan and the reads it answers
+#[derive(Debug, Clone)]
+pub struct PlannedEntry {
+    /// Entry to fetch
+    pub entry: StorageEntry,
+    /// Indices of the reads answered by the entry
+    pub reads: Vec<usize>,
+}
+
+/// Batch of reads coalesced into storage multi-queries
+#[derive(Debug, Clone)]
+pub struct QueryPlan {
+    reads: Vec<ReadRequest>,
+    entries: Vec<PlannedEntry>,
+    batch_size: usize,
+}
+
+impl QueryPlan {
+    /// Requested reads, in request order
+    pub fn reads(&self) -> &[ReadRequest] {
+        &self.reads
+    }
+
+    /// Distinct storage entries to fetch
+    pub fn entries(&self) -> &[PlannedEntry] {
+        &self.entries
+    }
+
+    /// Number of RPC calls executing the plan takes
+    ///
+    /// One call resolves the finalized block every batch is read at; each
+    /// batch of up to `batch_size` keys is one more.
+    pub fn rpc_calls(&self) -> usize {
+        if self.entries.is_empty() {
+            0
+        } else {
+            1 + self.entries.len().div_ceil(self.batch_size)
+        }
+    }
+
+    /// Describe the plan: the RPC calls it makes and the reads each storage
+    /// entry answers
+    pub fn explain(&self) -> String {
+        let mut out = format!(
+            "{} reads -> {} storage entries -> {} RPC calls (batch size {})\n",
+            self.reads.len(),
+            self.entries.len(),
+            self.rpc_calls(),
+            self.batch_size
+        );
+        if self.entries.is_empty() {
+            return out;
+        }
+
+        out.push_str("  1. resolve finalized block\n");
+        for (batch, entries) in self.entries.chunks(self.batch_size).enumerate() {
+            out.push_str(&format!(
+                "  {}. state_queryStorageAt ({} keys)\n",
+                batch + 2,
+                entries.len()
+            ));
+            for planned in entries {
+                let reads: Vec<String> = planned
+                    .reads
+                    .iter()
+                    .map(|&i| self.reads[i].to_string())
+                    .collect();
+                out.push_str(&format!(
+                    "       {} <- {}\n",
+                    planned.entry,
+                    reads.join(", ")
+                ));
+            }
+        }
+        out
+    }
+
+    /// Fetch every entry at the latest finalized block
+    ///
+    /// Returns one value per read, in request order. Reads of accounts
+    /// without a storage entry return zero balances and nonces.
+    pub async fn execute(&self, adapter: &SubstrateAdapter) -> Result<Vec<ReadValue>> {
+        if self.entries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let chain = adapter.chain_client();
+        let at = chain.finalized_block_hash().await?;
+        let keys: Vec<StorageKey> = self
+            .entries
+            .iter()
+            .map(|planned| {
+                let (pallet, item) = planned.entry.location();
+                StorageKey::new(pallet, item, planned.entry.keys())
+            })
+            .collect();
+
+        let mut values = Vec::with_capacity(keys.len());
+        for batch in keys.chunks(self.batch_size) {
+            values.extend(chain.storage_batch(batch, at).await?);
+        }
+
+        let mut results = vec![None; self.reads.len()];
+        for (planned, value) in self.entries.iter().zip(&values) {
+            let decoded = value.as_ref().map(|value| &value.value);
+            for &index in &planned.reads {
+                results[index] = Some(read_value(&self.reads[index], decoded)?);
+            }
+        }
+
+        // Every read is answered by exactly one entry
+        Ok(results.into_iter().flatten().collect())
+    }
+}
+
+/// Builds [`QueryPlan`]s from a batch of reads
+#[derive(Debug, Clone)]
+pub struct QueryPlanner {
+    reads: Vec<ReadRequest>,
+    batch_size: usize,
+}
+
+impl Default for QueryPlanner {
+    fn default() -> Self {
+        Self {
+            reads: Vec::new(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+}
+
+impl QueryPlanner {
+    /// Create a planner without reads
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of keys fetched per RPC call
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Add a read
+    pub fn read(mut self, request: ReadRequest) -> Self {
+        self.reads.push(request);
+        self
+    }
+
+    /// Add a free balance read
+    pub fn balance(self, address: Address) -> Self {
+        self.read(ReadRequest::Balance(address))
+    }
+
+    /// Add a nonce read
+    pub fn nonce(self, address: Address) -> Self {
+        self.read(ReadRequest::Nonce(address))
+    }
+
+    /// Add an account information read
+    pub fn account_info(self, address: Address) -> Self {
+        self.read(ReadRequest::AccountInfo(address))
+    }
+
+    /// Add an asset balance read
+    pub fn asset_balance(self, asset_id: u32, address: Address) -> Self {
+        self.read(ReadRequest::AssetBalance { asset_id, address })
+    }
+
+    /// Coalesce the reads into distinct storage entries
+    ///
+    /// Reads of the same account share an entry, whatever network the
+    /// address is encoded for.
+    pub fn plan(self) -> Result<QueryPlan> {
+        let mut entries: Vec<PlannedEntry> = Vec::new();
+        let mut index: HashMap<StorageEntry, usize> = HashMap::new();
+
+        for (read, request) in self.reads.iter().enumerate() {
+            let entry = request.entry()?;
+            let position = *index.entry(entry).or_insert_with(|| {
+                entries.push(PlannedEntry {
+                    entry,
+                    reads: Vec::new(),
+                });
+                entries.len() - 1
+            });
+            entries[position].reads.push(read);
+        }
+
+        Ok(QueryPlan {
+            reads: self.reads,
+            entries,
+            batch_size: self.batch_size,
+        })
+    }
+}
+
+fn read_value(request: &ReadRequest, value: Option<&Value>) -> Result<ReadValue> {
+    if let ReadRequest::AssetBalance { .. } = request {
+        let balance = match value {
+            Some(value) => extract_u128(value, &["balance"])
+                .ok_or_else(|| Error::Storage("Failed to extract 'balance' field".to_string()))?,
+            None => 0,
+        };
+        return Ok(ReadValue::AssetBalance(balance));
+    }
+
+    let info = value
+        .map(AccountInfo::from_value)
+        .transpose()?
+        .unwrap_or_default();
+    Ok(match request {
+        ReadRequest::Balance(_) => ReadValue::Balance(info.free),
+        ReadRequest::Nonce(_) => ReadValue::Nonce(info.nonce),
+        _ => ReadValue::AccountInfo(info),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const ALICE_POLKADOT: &str = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+    #[test]
+    fn test_plan_coalesces_reads() {
+        let plan = QueryPlanner::new()
+            .with_batch_size(2)
+            .balance(Address::substrate(ALICE))
+            .nonce(Address::substrate(ALICE_POLKADOT))
+            .balance(Address::substrate(BOB))
+            .asset_balance(1984, Address::substrate(ALICE))
+            .asset_balance(1984, Address::substrate(ALICE))
+            .plan()
+            .unwrap();
+
+        assert_eq!(plan.reads().len(), 5);
+        assert_eq!(plan.entries().len(), 3);
+        assert_eq!(plan.entries()[0].reads, vec![0, 1]);
+        assert_eq!(plan.entries()[2].reads, vec![3, 4]);
+        // Finalized block, then batches of two and one keys
+        assert_eq!(plan.rpc_calls(), 3);
+
+        let explain = plan.explain();
+        assert!(explain.starts_with("5 reads -> 3 storage entries -> 3 RPC calls"));
+        assert!(explain.contains("state_queryStorageAt (2 keys)"));
+        assert!(explain.contains(&format!(
+            "asset_balance(1984, {}), asset_balance(1984, {})",
+            ALICE, ALICE
+        )));
+    }
+
+    #[test]
+    fn test_empty_plan() {
+        let plan = QueryPlanner::new().plan().unwrap();
+        assert_eq!(plan.rpc_calls(), 0);
+    }
+
+    #[test]
+    fn test_plan_rejects_evm_address() {
+        let result = QueryPlanner::new()
+            .balance(Address::evm("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"))
+            .plan();
+        assert!(result.is_err());
+    }
+}
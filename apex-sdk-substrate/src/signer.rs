@@ -1,11 +1,24 @@
 //! Signer implementations for Substrate extrinsics
 //!
 //! This module provides concrete implementations of the `subxt::tx::Signer`
-//! trait for SR25519 and ED25519 key pairs, enabling transaction signing.
+//! trait for SR25519 and ED25519 key pairs, enabling transaction signing,
+//! and [`ScopedSigner`], which restricts what another signer may sign and
+//! can be frozen through a [`FreezeSwitch`].
 
+use crate::signing_log::SigningLog;
+use crate::summary::{summarize, TransactionSummary};
+use crate::{Error, Result};
+use apex_sdk_types::ChainProperties;
+use parking_lot::RwLock;
 use sp_core::{ed25519, sr25519, Pair};
-use subxt::tx::Signer;
-use subxt::utils::AccountId32;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use subxt::config::polkadot::PolkadotExtrinsicParamsBuilder;
+use subxt::ext::scale_value::{scale::decode_as_type, Composite, Value, ValueDef};
+use subxt::tx::{Payload, Signer, SubmittableTransaction};
+use subxt::utils::{AccountId32, MultiSignature};
+use subxt::{Metadata, OnlineClient, PolkadotConfig};
+use tracing::{debug, error};
 
 /// A signer for SR25519 key pairs
 #[derive(Clone)]
@@ -59,11 +72,82 @@ impl Signer<subxt::PolkadotConfig> for Ed25519Signer {
     }
 }
 
-/// A generic signer that can hold either an SR25519 or ED25519 signer
+/// A generic signer that can hold an SR25519 or ED25519 signer
+///
+/// Hardware signers are not `subxt` signers, since device errors must not
+/// become signatures; see [`LedgerSigner`](crate::LedgerSigner).
+///
+/// A logged signer only signs payloads whose receipt was recorded. Sign
+/// through [`try_sign`](Self::try_sign), [`create_signed`](Self::create_signed)
+/// or [`sign_and_submit`](Self::sign_and_submit) to get a failed recording as
+/// an error; the `subxt::tx::Signer` implementation has no way to report it
+/// and panics instead.
 #[derive(Clone)]
 pub enum ApexSigner {
     Sr25519(Box<Sr25519Signer>),
     Ed25519(Box<Ed25519Signer>),
+    /// Another signer whose signatures are recorded in a [`SigningLog`]
+    Logged(Box<ApexSigner>, Arc<SigningLog>),
+}
+
+impl ApexSigner {
+    /// Record a receipt in `log` for every payload this signer signs
+    pub fn with_signing_log(self, log: Arc<SigningLog>) -> Self {
+        ApexSigner::Logged(Box::new(self), log)
+    }
+
+    /// Sign a signer payload, failing without a signature if its receipt
+    /// cannot be recorded
+    pub fn try_sign(&self, signer_payload: &[u8]) -> Result<MultiSignature> {
+        match self {
+            ApexSigner::Sr25519(signer) => Ok(signer.sign(signer_payload)),
+            ApexSigner::Ed25519(signer) => Ok(signer.sign(signer_payload)),
+            ApexSigner::Logged(signer, log) => {
+                log.record(signer.account_id().to_string(), signer_payload)
+                    .map_err(|e| {
+                        error!("Failed to record signing receipt: {}", e);
+                        Error::Signature(format!("Signing receipt not recorded: {}", e))
+                    })?;
+                signer.try_sign(signer_payload)
+            }
+        }
+    }
+
+    /// Create a signed extrinsic for `call`
+    ///
+    /// Prefer this to passing the signer to `create_signed`: a receipt that
+    /// cannot be recorded fails here, before anything is signed.
+    pub async fn create_signed<Call: Payload>(
+        &self,
+        client: &OnlineClient<PolkadotConfig>,
+        call: &Call,
+        params: PolkadotExtrinsicParamsBuilder<PolkadotConfig>,
+    ) -> Result<SubmittableTransaction<PolkadotConfig, OnlineClient<PolkadotConfig>>> {
+        let account_id = self.account_id();
+        let mut partial = client
+            .tx()
+            .create_partial(call, &account_id, params.build())
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to create transaction: {}", e)))?;
+        let signature = self.try_sign(&partial.signer_payload())?;
+        Ok(partial.sign_with_account_and_signature(&account_id, &signature))
+    }
+
+    /// Sign `call` and submit it, returning the transaction hash
+    pub async fn sign_and_submit<Call: Payload>(
+        &self,
+        client: &OnlineClient<PolkadotConfig>,
+        call: &Call,
+        params: PolkadotExtrinsicParamsBuilder<PolkadotConfig>,
+    ) -> Result<String> {
+        let hash = self
+            .create_signed(client, call, params)
+            .await?
+            .submit()
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to submit transaction: {}", e)))?;
+        Ok(format!("0x{}", hex::encode(hash)))
+    }
 }
 
 impl From<Sr25519Signer> for ApexSigner {
@@ -83,13 +167,623 @@ impl Signer<subxt::PolkadotConfig> for ApexSigner {
         match self {
             ApexSigner::Sr25519(signer) => signer.account_id(),
             ApexSigner::Ed25519(signer) => signer.account_id(),
+            ApexSigner::Logged(signer, _) => signer.account_id(),
         }
     }
 
+    /// # Panics
+    ///
+    /// A logged signer refuses to sign a payload whose receipt cannot be
+    /// recorded, and this method cannot return an error; use
+    /// [`ApexSigner::try_sign`] to handle the failure.
     fn sign(&self, signer_payload: &[u8]) -> <subxt::PolkadotConfig as subxt::Config>::Signature {
-        match self {
-            ApexSigner::Sr25519(signer) => signer.sign(signer_payload),
-            ApexSigner::Ed25519(signer) => signer.sign(signer_payload),
+        match self.try_sign(signer_payload) {
+            Ok(signature) => signature,
+            Err(e) => panic!("Refusing to sign: {}", e),
+        }
+    }
+}
+
+/// Call arguments counted against [`SigningScope::with_max_amount`]
+const AMOUNT_FIELDS: &[&str] = &["value", "amount"];
+
+/// Variant holding the amount of a fungible XCM asset, as in
+/// `Asset { id, fun: Fungible(amount) }`
+const FUNGIBLE_VARIANT: &str = "Fungible";
+
+/// Calls that move an amount not stated in their arguments, refused when a
+/// maximum amount is set
+const UNBOUNDED_TRANSFERS: &[(&str, &str)] = &[("Balances", "transfer_all")];
+
+/// Calls a [`ScopedSigner`] may sign
+///
+/// A new scope allows nothing. Calls nested in other calls, such as those in
+/// `Utility::batch`, must be allowed as well as the call wrapping them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SigningScope {
+    pallets: BTreeSet<String>,
+    calls: BTreeSet<(String, String)>,
+    max_amount: Option<u128>,
+}
+
+impl SigningScope {
+    /// Create a scope that allows nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a scope that allows only `Balances` calls
+    pub fn balances_only() -> Self {
+        Self::new().allow_pallet("Balances")
+    }
+
+    /// Allow every call of a pallet
+    pub fn allow_pallet(mut self, pallet: impl Into<String>) -> Self {
+        self.pallets.insert(pallet.into());
+        self
+    }
+
+    /// Allow a single call
+    pub fn allow_call(mut self, pallet: impl Into<String>, call: impl Into<String>) -> Self {
+        self.calls.insert((pallet.into(), call.into()));
+        self
+    }
+
+    /// Limit the total of the `value` and `amount` arguments of an extrinsic,
+    /// including those of nested calls and the fungible asset amounts of XCM
+    /// transfers
+    pub fn with_max_amount(mut self, max_amount: u128) -> Self {
+        self.max_amount = Some(max_amount);
+        self
+    }
+
+    /// Check whether the scope allows a call by name
+    pub fn permits(&self, pallet: &str, call: &str) -> bool {
+        self.pallets.contains(pallet)
+            || self.calls.contains(&(pallet.to_string(), call.to_string()))
+    }
+
+    /// Check SCALE-encoded call data against the scope
+    ///
+    /// Trailing bytes after the call, such as the signed extensions of a
+    /// signer payload, are ignored.
+    pub fn check(&self, metadata: &Metadata, call_data: &[u8]) -> Result<()> {
+        let call_ty = metadata.outer_enums().call_enum_ty();
+        let call = decode_as_type(&mut &call_data[..], call_ty, metadata.types())
+            .map_err(|e| Error::Signature(format!("Cannot decode call to check scope: {}", e)))?;
+        self.check_decoded(&call, call_ty)
+    }
+
+    /// Check a call decoded as the runtime call type `call_ty`, with type IDs
+    /// as value context
+    fn check_decoded(&self, call: &Value<u32>, call_ty: u32) -> Result<()> {
+        let mut total = 0u128;
+        self.check_call(call, call_ty, &mut total)?;
+
+        match self.max_amount {
+            Some(max) if total > max => Err(Error::Signature(format!(
+                "Signing scope allows amounts up to {}, extrinsic moves {}",
+                max, total
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_call(&self, call: &Value<u32>, call_ty: u32, total: &mut u128) -> Result<()> {
+        let (pallet, call_name, args) = match &call.value {
+            ValueDef::Variant(pallet) => match pallet.values.values().next().map(|v| &v.value) {
+                Some(ValueDef::Variant(inner)) => (&pallet.name, &inner.name, &inner.values),
+                _ => {
+                    return Err(Error::Signature(format!(
+                        "Unexpected encoding of a {} call",
+                        pallet.name
+                    )))
+                }
+            },
+            _ => return Err(Error::Signature("Call is not a runtime call".to_string())),
+        };
+
+        if !self.permits(pallet, call_name) {
+            return Err(Error::Signature(format!(
+                "Signing scope does not allow {}::{}",
+                pallet, call_name
+            )));
+        }
+        if self.max_amount.is_some()
+            && UNBOUNDED_TRANSFERS.contains(&(pallet.as_str(), call_name.as_str()))
+        {
+            return Err(Error::Signature(format!(
+                "Signing scope limits amounts, {}::{} transfers the whole balance",
+                pallet, call_name
+            )));
+        }
+
+        add_amounts(args, total);
+        for value in args.values() {
+            self.check_nested(value, call_ty, total)?;
+        }
+        Ok(())
+    }
+
+    /// Check the runtime calls found anywhere inside a call argument, and
+    /// count the amounts nested in it, such as those of XCM assets
+    fn check_nested(&self, value: &Value<u32>, call_ty: u32, total: &mut u128) -> Result<()> {
+        if value.context == call_ty {
+            return self.check_call(value, call_ty, total);
+        }
+        match &value.value {
+            ValueDef::Composite(composite) => {
+                add_amounts(composite, total);
+                composite
+                    .values()
+                    .try_for_each(|v| self.check_nested(v, call_ty, total))
+            }
+            ValueDef::Variant(variant) => {
+                if variant.name == FUNGIBLE_VARIANT {
+                    let amount = variant.values.values().next().and_then(Value::as_u128);
+                    *total = total.saturating_add(amount.unwrap_or(0));
+                }
+                add_amounts(&variant.values, total);
+                variant
+                    .values
+                    .values()
+                    .try_for_each(|v| self.check_nested(v, call_ty, total))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Add the named amount fields of a call argument or nested value to `total`
+fn add_amounts(fields: &Composite<u32>, total: &mut u128) {
+    if let Composite::Named(fields) = fields {
+        for (name, value) in fields {
+            if AMOUNT_FIELDS.contains(&name.as_str()) {
+                *total = total.saturating_add(value.as_u128().unwrap_or(0));
+            }
+        }
+    }
+}
+
+/// Shared switch that stops [`ScopedSigner`]s from signing anything
+///
+/// Clones control the same switch, so one can be handed to a
+/// [`WalletActivityWatcher`] that freezes the signer when it sees the wallet
+/// sign something the SDK did not.
+///
+/// [`WalletActivityWatcher`]: crate::WalletActivityWatcher
+#[derive(Debug, Clone, Default)]
+pub struct FreezeSwitch {
+    reason: Arc<RwLock<Option<String>>>,
+}
+
+impl FreezeSwitch {
+    /// Create an unfrozen switch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Refuse every payload until [`unfreeze`](Self::unfreeze) is called
+    pub fn freeze(&self, reason: impl Into<String>) {
+        *self.reason.write() = Some(reason.into());
+    }
+
+    /// Allow signing again
+    pub fn unfreeze(&self) {
+        *self.reason.write() = None;
+    }
+
+    /// Whether signing is frozen
+    pub fn is_frozen(&self) -> bool {
+        self.reason.read().is_some()
+    }
+
+    /// Why signing was frozen, if it is
+    pub fn reason(&self) -> Option<String> {
+        self.reason.read().clone()
+    }
+}
+
+/// A signer that only signs calls allowed by a [`SigningScope`]
+///
+/// The call is decoded from the signer payload before it is passed to the
+/// wrapped signer, which makes it safe to hand to plugins or automation that
+/// should only, say, send small transfers. Since `subxt` signing cannot
+/// fail, a scoped signer is not a `subxt` [`Signer`]: it signs through
+/// [`try_sign`](Self::try_sign), [`create_signed`](Self::create_signed) and
+/// [`sign_and_submit`](Self::sign_and_submit), which return a refusal as an
+/// error before anything is signed.
+///
+/// Each payload is logged with the pallet, call, recipient and amount of its
+/// [`summary`](Self::summarize) as fields, which privacy mode redacts:
+/// signed payloads at debug level, refusals at error level with the reason.
+#[derive(Clone)]
+pub struct ScopedSigner<S> {
+    inner: S,
+    scope: SigningScope,
+    metadata: Metadata,
+    freeze: FreezeSwitch,
+    properties: ChainProperties,
+}
+
+impl<S> ScopedSigner<S> {
+    /// Wrap a signer, decoding calls with the given runtime metadata
+    pub fn new(inner: S, scope: SigningScope, metadata: Metadata) -> Self {
+        Self {
+            inner,
+            scope,
+            metadata,
+            freeze: FreezeSwitch::new(),
+            properties: ChainProperties::default(),
+        }
+    }
+
+    /// Show amounts and accounts in summaries with the token and SS58
+    /// format of a chain
+    pub fn with_chain_properties(mut self, properties: ChainProperties) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    /// Share `freeze` with other signers or a watcher instead of the
+    /// signer's own switch
+    pub fn with_freeze_switch(mut self, freeze: FreezeSwitch) -> Self {
+        self.freeze = freeze;
+        self
+    }
+
+    /// Get the scope
+    pub fn scope(&self) -> &SigningScope {
+        &self.scope
+    }
+
+    /// Switch that freezes this signer
+    pub fn freeze_switch(&self) -> FreezeSwitch {
+        self.freeze.clone()
+    }
+
+    /// Check a signer payload against the scope
+    ///
+    /// Every payload is refused while the signer is frozen.
+    pub fn check(&self, signer_payload: &[u8]) -> Result<()> {
+        if let Some(reason) = self.freeze.reason() {
+            return Err(Error::Signature(format!("Signer is frozen: {}", reason)));
+        }
+        // Payloads longer than 256 bytes are signed as their 32-byte hash,
+        // which hides the call
+        if signer_payload.len() == 32 {
+            return Err(Error::Signature(
+                "Signer payload is hashed, its call cannot be checked against the scope"
+                    .to_string(),
+            ));
         }
+        self.scope.check(&self.metadata, signer_payload)
+    }
+
+    /// Describe the call of a signer payload
+    ///
+    /// Hashed payloads cannot be described.
+    pub fn summarize(&self, signer_payload: &[u8]) -> Result<TransactionSummary> {
+        if signer_payload.len() == 32 {
+            return Err(Error::Signature(
+                "Signer payload is hashed, its call cannot be summarized".to_string(),
+            ));
+        }
+        Ok(summarize(signer_payload, &self.metadata)?.with_properties(self.properties.clone()))
+    }
+
+    /// Log a signing decision
+    ///
+    /// The recipient and amount are fields rather than message text, so
+    /// privacy mode redacts them.
+    fn log_decision(&self, signer_payload: &[u8], refusal: Option<&Error>) {
+        let summary = self.summarize(signer_payload).ok();
+        let pallet = summary.as_ref().map(|s| s.pallet.as_str());
+        let call = summary.as_ref().map(|s| s.call.as_str());
+        let to = summary.as_ref().and_then(TransactionSummary::recipient);
+        let amount = summary.as_ref().and_then(TransactionSummary::amount);
+        let payload_len = signer_payload.len();
+
+        match refusal {
+            None => debug!(
+                pallet,
+                call,
+                to = to.as_deref(),
+                amount,
+                payload_len,
+                "Signing"
+            ),
+            Some(e) => error!(
+                pallet,
+                call,
+                to = to.as_deref(),
+                amount,
+                payload_len,
+                error = %e,
+                "Refusing to sign"
+            ),
+        }
+    }
+}
+
+impl<S: Signer<subxt::PolkadotConfig>> ScopedSigner<S> {
+    /// Sign a signer payload, or refuse it with the reason from
+    /// [`check`](Self::check)
+    ///
+    /// Nothing is signed when the payload is refused.
+    pub fn try_sign(&self, signer_payload: &[u8]) -> Result<MultiSignature> {
+        if let Err(e) = self.check(signer_payload) {
+            self.log_decision(signer_payload, Some(&e));
+            return Err(e);
+        }
+        self.log_decision(signer_payload, None);
+        Ok(self.inner.sign(signer_payload))
+    }
+
+    /// Create a signed extrinsic for `call`, refusing calls outside the scope
+    ///
+    /// Prefer this to passing the signer to `create_signed`: a refused call
+    /// fails here with the scope violation, before anything is signed or
+    /// submitted.
+    pub async fn create_signed<Call: Payload>(
+        &self,
+        client: &OnlineClient<PolkadotConfig>,
+        call: &Call,
+        params: PolkadotExtrinsicParamsBuilder<PolkadotConfig>,
+    ) -> Result<SubmittableTransaction<PolkadotConfig, OnlineClient<PolkadotConfig>>> {
+        let account_id = self.inner.account_id();
+        let mut partial = client
+            .tx()
+            .create_partial(call, &account_id, params.build())
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to create transaction: {}", e)))?;
+        let signature = self.try_sign(&partial.signer_payload())?;
+        Ok(partial.sign_with_account_and_signature(&account_id, &signature))
+    }
+
+    /// Sign `call` and submit it, returning the transaction hash
+    ///
+    /// A call outside the scope fails with the scope violation and is
+    /// neither signed nor submitted.
+    pub async fn sign_and_submit<Call: Payload>(
+        &self,
+        client: &OnlineClient<PolkadotConfig>,
+        call: &Call,
+        params: PolkadotExtrinsicParamsBuilder<PolkadotConfig>,
+    ) -> Result<String> {
+        let hash = self
+            .create_signed(client, call, params)
+            .await?
+            .submit()
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to submit transaction: {}", e)))?;
+        Ok(format!("0x{}", hex::encode(hash)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use subxt::ext::scale_value::Variant;
+
+    const CALL_TY: u32 = 100;
+
+    fn variant(name: &str, values: Composite<u32>, context: u32) -> Value<u32> {
+        Value {
+            value: ValueDef::Variant(Variant {
+                name: name.to_string(),
+                values,
+            }),
+            context,
+        }
+    }
+
+    /// A runtime call value, with the runtime call type as context
+    fn call(pallet: &str, name: &str, args: Vec<(&str, Value<u32>)>) -> Value<u32> {
+        let args = args.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        let inner = variant(name, Composite::Named(args), 0);
+        variant(pallet, Composite::Unnamed(vec![inner]), CALL_TY)
+    }
+
+    fn transfer(value: u128) -> Value<u32> {
+        call(
+            "Balances",
+            "transfer_keep_alive",
+            vec![
+                ("dest", Value::from_bytes([1u8; 32]).map_context(|_| 0)),
+                ("value", Value::u128(value).map_context(|_| 0)),
+            ],
+        )
+    }
+
+    fn batch(calls: Vec<Value<u32>>) -> Value<u32> {
+        let calls = Value {
+            value: ValueDef::Composite(Composite::Unnamed(calls)),
+            context: 0,
+        };
+        call("Utility", "batch", vec![("calls", calls)])
+    }
+
+    #[test]
+    fn test_scope_allows_pallets_and_calls() {
+        let scope = SigningScope::balances_only();
+        assert!(scope.check_decoded(&transfer(10), CALL_TY).is_ok());
+
+        let remark = call("System", "remark", vec![]);
+        assert!(scope.check_decoded(&remark, CALL_TY).is_err());
+        let scope = scope.allow_call("System", "remark");
+        assert!(scope.check_decoded(&remark, CALL_TY).is_ok());
+        assert!(!scope.permits("System", "set_code"));
+
+        assert!(SigningScope::new()
+            .check_decoded(&transfer(10), CALL_TY)
+            .is_err());
+    }
+
+    #[test]
+    fn test_freeze_switch_is_shared() {
+        let switch = FreezeSwitch::new();
+        let clone = switch.clone();
+        assert!(!clone.is_frozen());
+
+        switch.freeze("unexpected transfer");
+        assert_eq!(clone.reason().as_deref(), Some("unexpected transfer"));
+        clone.unfreeze();
+        assert!(!switch.is_frozen());
+    }
+
+    #[test]
+    fn test_try_sign_refuses_before_signing() {
+        use crate::event_filter::test_utils::metadata;
+
+        // System::remark in the test metadata
+        let payload = [0u8, 0];
+        let pair = sr25519::Pair::from_seed(&[1; 32]);
+
+        let scoped = ScopedSigner::new(
+            Sr25519Signer::new(pair.clone()),
+            SigningScope::balances_only(),
+            metadata(),
+        );
+        assert!(matches!(
+            scoped.try_sign(&payload),
+            Err(Error::Signature(reason)) if reason.contains("System")
+        ));
+
+        let scoped = ScopedSigner::new(
+            Sr25519Signer::new(pair),
+            SigningScope::new().allow_pallet("System"),
+            metadata(),
+        );
+        assert!(matches!(
+            scoped.try_sign(&payload),
+            Ok(MultiSignature::Sr25519(signature)) if signature != [0u8; 64]
+        ));
+
+        scoped.freeze_switch().freeze("unexpected transfer");
+        assert!(matches!(
+            scoped.try_sign(&payload),
+            Err(Error::Signature(reason)) if reason.contains("frozen")
+        ));
+    }
+
+    #[test]
+    fn test_scope_limits_total_amount() {
+        let scope = SigningScope::balances_only().with_max_amount(100);
+        assert!(scope.check_decoded(&transfer(100), CALL_TY).is_ok());
+        assert!(scope.check_decoded(&transfer(101), CALL_TY).is_err());
+
+        let transfer_all = call("Balances", "transfer_all", vec![]);
+        assert!(scope.check_decoded(&transfer_all, CALL_TY).is_err());
+        assert!(SigningScope::balances_only()
+            .check_decoded(&transfer_all, CALL_TY)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_scope_checks_nested_calls() {
+        let scope = SigningScope::balances_only().allow_pallet("Utility");
+        assert!(scope
+            .check_decoded(&batch(vec![transfer(1), transfer(2)]), CALL_TY)
+            .is_ok());
+
+        // Nested calls must be allowed too
+        let smuggled = batch(vec![transfer(1), call("System", "set_code", vec![])]);
+        let err = scope.check_decoded(&smuggled, CALL_TY).unwrap_err();
+        assert!(err.to_string().contains("System::set_code"));
+
+        // and count towards the limit
+        let scope = scope.with_max_amount(50);
+        assert!(scope
+            .check_decoded(&batch(vec![transfer(30), transfer(30)]), CALL_TY)
+            .is_err());
+
+        // The wrapper itself must be allowed
+        assert!(SigningScope::balances_only()
+            .check_decoded(&batch(vec![transfer(1)]), CALL_TY)
+            .is_err());
+    }
+
+    #[test]
+    fn test_scope_limits_xcm_asset_amounts() {
+        let value = |v: Value<()>| v.map_context(|_| 0);
+        let xcm_transfer = |amount: u128| {
+            let asset = Value::named_composite([
+                ("id", Value::unnamed_composite([])),
+                (
+                    "fun",
+                    Value::unnamed_variant("Fungible", [Value::u128(amount)]),
+                ),
+            ]);
+            call(
+                "PolkadotXcm",
+                "limited_reserve_transfer_assets",
+                vec![
+                    ("dest", value(Value::unnamed_variant("V4", []))),
+                    ("beneficiary", value(Value::unnamed_variant("V4", []))),
+                    (
+                        "assets",
+                        value(Value::unnamed_variant(
+                            "V4",
+                            [Value::unnamed_composite([asset])],
+                        )),
+                    ),
+                    ("fee_asset_item", value(Value::u128(0))),
+                    (
+                        "weight_limit",
+                        value(Value::unnamed_variant("Unlimited", [])),
+                    ),
+                ],
+            )
+        };
+
+        let scope = SigningScope::new()
+            .allow_pallet("PolkadotXcm")
+            .with_max_amount(100);
+        assert!(scope.check_decoded(&xcm_transfer(100), CALL_TY).is_ok());
+        let err = scope
+            .check_decoded(&xcm_transfer(101), CALL_TY)
+            .unwrap_err();
+        assert!(err.to_string().contains("moves 101"));
+    }
+
+    /// Logged signer whose log file can no longer be written
+    fn unwritable_logged_signer() -> (ApexSigner, Arc<SigningLog>) {
+        let dir = tempfile::tempdir().unwrap();
+        let log = Arc::new(SigningLog::open(dir.path().join("signatures.jsonl")).unwrap());
+        drop(dir);
+        let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+        let signer = ApexSigner::from(Sr25519Signer::new(pair)).with_signing_log(log.clone());
+        (signer, log)
+    }
+
+    #[test]
+    fn test_logged_signer_records_before_signing() {
+        let log = Arc::new(SigningLog::new());
+        let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+        let signer = ApexSigner::from(Sr25519Signer::new(pair)).with_signing_log(log.clone());
+
+        signer.try_sign(b"payload").unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log.receipts()[0].signer, signer.account_id().to_string());
+    }
+
+    #[test]
+    fn test_logged_signer_fails_closed() {
+        let (signer, log) = unwritable_logged_signer();
+
+        match signer.try_sign(b"payload") {
+            Err(Error::Signature(msg)) => assert!(msg.contains("receipt not recorded"), "{}", msg),
+            other => panic!("expected a signature error, got {:?}", other.map(|_| ())),
+        }
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Refusing to sign")]
+    fn test_logged_subxt_signer_refuses_without_receipt() {
+        let (signer, _log) = unwritable_logged_signer();
+        Signer::<PolkadotConfig>::sign(&signer, b"payload");
     }
 }
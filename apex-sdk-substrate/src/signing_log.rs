@@ -0,0 +1,400 @@
+//! Tamper-evident log of signatures
+//!
+//! A [`SigningLog`] attached to a [`Wallet`] or an [`ApexSigner`] receives a
+//! [`SigningReceipt`] for every signature it produces: the hash of the signed
+//! payload, a summary of the call it carries, the time and the signer's
+//! address. Each receipt includes the hash of the previous one, so editing,
+//! removing or reordering entries in the log file breaks the chain, which
+//! [`SigningLog::verify`] detects. A payload whose receipt cannot be
+//! recorded, e.g. because the log file is not writable, is not signed.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::{SigningLog, Wallet};
+//! use std::sync::Arc;
+//!
+//! # fn example(adapter: &apex_sdk_substrate::SubstrateAdapter) -> apex_sdk_substrate::Result<()> {
+//! let log = Arc::new(SigningLog::open("signatures.jsonl")?.with_metadata(adapter.client().metadata()));
+//! let wallet = Wallet::new_random().with_signing_log(log.clone());
+//! // ... sign transactions with `wallet` ...
+//! log.verify()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Wallet`]: crate::Wallet
+//! [`ApexSigner`]: crate::ApexSigner
+
+use crate::{unix_now, Error, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use subxt::ext::scale_value::{scale::decode_as_type, ValueDef};
+use subxt::Metadata;
+
+/// Longest call summary kept in a receipt
+const MAX_SUMMARY_LEN: usize = 512;
+
+/// Hash the chain starts from
+const GENESIS_HASH: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Record of a single signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningReceipt {
+    /// Position in the log, starting at 0
+    pub sequence: u64,
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    /// SS58 address of the signer
+    pub signer: String,
+    /// BLAKE2-256 hash of the signed payload
+    pub payload_hash: String,
+    /// Decoded call, e.g. `Balances::transfer_keep_alive { .. }`, when the
+    /// payload is a signer payload and the log has runtime metadata
+    pub call: Option<String>,
+    /// Hash of the previous receipt
+    pub prev_hash: String,
+    /// Hash of this receipt, covering all fields above
+    pub hash: String,
+}
+
+impl SigningReceipt {
+    fn compute_hash(&self) -> String {
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(self.prev_hash.as_bytes());
+        preimage.extend_from_slice(&self.sequence.to_le_bytes());
+        preimage.extend_from_slice(&self.timestamp.to_le_bytes());
+        for field in [&self.signer, &self.payload_hash] {
+            preimage.extend_from_slice(&(field.len() as u64).to_le_bytes());
+            preimage.extend_from_slice(field.as_bytes());
+        }
+        if let Some(call) = &self.call {
+            preimage.extend_from_slice(&(call.len() as u64).to_le_bytes());
+            preimage.extend_from_slice(call.as_bytes());
+        }
+        format!("0x{}", hex::encode(sp_core::blake2_256(&preimage)))
+    }
+}
+
+/// Append-only, hash-chained log of signing receipts
+///
+/// Persisted logs are JSON Lines files, one receipt per line, appended and
+/// flushed before the signature is returned.
+pub struct SigningLog {
+    path: Option<PathBuf>,
+    metadata: Option<Metadata>,
+    receipts: Mutex<Vec<SigningReceipt>>,
+}
+
+impl SigningLog {
+    /// Create an in-memory log
+    pub fn new() -> Self {
+        Self {
+            path: None,
+            metadata: None,
+            receipts: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Open a log file, creating it if needed
+    ///
+    /// Existing receipts are loaded and new ones continue their chain. The
+    /// file is not verified; call [`verify`](Self::verify) for that.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let receipts = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| {
+                    serde_json::from_str(line).map_err(|e| {
+                        Error::Wallet(format!("Invalid signing log {}: {}", path.display(), e))
+                    })
+                })
+                .collect::<Result<Vec<SigningReceipt>>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                return Err(Error::Wallet(format!(
+                    "Failed to read {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+
+        Ok(Self {
+            path: Some(path),
+            metadata: None,
+            receipts: Mutex::new(receipts),
+        })
+    }
+
+    /// Decode calls in signer payloads with the given runtime metadata
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Append a receipt for a payload about to be signed
+    pub fn record(&self, signer: impl Into<String>, payload: &[u8]) -> Result<SigningReceipt> {
+        let mut receipts = self.receipts.lock();
+        let (sequence, prev_hash) = match receipts.last() {
+            Some(last) => (last.sequence + 1, last.hash.clone()),
+            None => (0, GENESIS_HASH.to_string()),
+        };
+
+        let mut receipt = SigningReceipt {
+            sequence,
+            timestamp: unix_now(),
+            signer: signer.into(),
+            payload_hash: format!("0x{}", hex::encode(sp_core::blake2_256(payload))),
+            call: self.summarize(payload),
+            prev_hash,
+            hash: String::new(),
+        };
+        receipt.hash = receipt.compute_hash();
+
+        if let Some(path) = &self.path {
+            let mut line = serde_json::to_string(&receipt)
+                .map_err(|e| Error::Wallet(format!("Failed to encode signing receipt: {}", e)))?;
+            line.push('\n');
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| {
+                    file.write_all(line.as_bytes())?;
+                    file.sync_data()
+                })
+                .map_err(|e| Error::Wallet(format!("Failed to write {}: {}", path.display(), e)))?;
+        }
+
+        receipts.push(receipt.clone());
+        Ok(receipt)
+    }
+
+    /// All receipts, oldest first
+    pub fn receipts(&self) -> Vec<SigningReceipt> {
+        self.receipts.lock().clone()
+    }
+
+    /// Receipts signed at or after `timestamp` (Unix seconds), oldest first
+    pub fn receipts_since(&self, timestamp: u64) -> Vec<SigningReceipt> {
+        self.receipts
+            .lock()
+            .iter()
+            .filter(|receipt| receipt.timestamp >= timestamp)
+            .cloned()
+            .collect()
+    }
+
+    /// Number of receipts
+    pub fn len(&self) -> usize {
+        self.receipts.lock().len()
+    }
+
+    /// Whether the log has no receipts
+    pub fn is_empty(&self) -> bool {
+        self.receipts.lock().is_empty()
+    }
+
+    /// Check the hash chain
+    ///
+    /// Fails at the first receipt whose hash, sequence number or link to the
+    /// previous receipt does not match.
+    pub fn verify(&self) -> Result<()> {
+        verify_chain(&self.receipts.lock())
+    }
+
+    /// Decode the call at the start of a signer payload
+    ///
+    /// Payloads over 256 bytes are signed as their hash and have no summary.
+    fn summarize(&self, payload: &[u8]) -> Option<String> {
+        let metadata = self.metadata.as_ref()?;
+        if payload.len() == 32 {
+            return None;
+        }
+        summarize_call(metadata, payload)
+    }
+}
+
+/// Summarize the runtime call at the start of `data`, e.g.
+/// `Balances::transfer_keep_alive { .. }`
+///
+/// Trailing bytes, such as the extensions of a signer payload, are ignored.
+pub(crate) fn summarize_call(metadata: &Metadata, data: &[u8]) -> Option<String> {
+    let call_ty = metadata.outer_enums().call_enum_ty();
+    let call = decode_as_type(&mut &data[..], call_ty, metadata.types()).ok()?;
+    let ValueDef::Variant(pallet) = &call.value else {
+        return None;
+    };
+    let inner = pallet.values.values().next()?;
+
+    let mut summary = format!("{}::{}", pallet.name, inner);
+    if summary.len() > MAX_SUMMARY_LEN {
+        let mut end = MAX_SUMMARY_LEN;
+        while !summary.is_char_boundary(end) {
+            end -= 1;
+        }
+        summary.truncate(end);
+        summary.push('…');
+    }
+    Some(summary)
+}
+
+impl Default for SigningLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for SigningLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningLog")
+            .field("path", &self.path)
+            .field("receipts", &self.len())
+            .finish()
+    }
+}
+
+fn verify_chain(receipts: &[SigningReceipt]) -> Result<()> {
+    let mut prev_hash = GENESIS_HASH;
+    for (index, receipt) in receipts.iter().enumerate() {
+        if receipt.sequence != index as u64
+            || receipt.prev_hash != prev_hash
+            || receipt.hash != receipt.compute_hash()
+        {
+            return Err(Error::Wallet(format!(
+                "Signing log broken at entry {} (sequence {})",
+                index, receipt.sequence
+            )));
+        }
+        prev_hash = &receipt.hash;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_chains_and_detects_tampering() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signatures.jsonl");
+
+        let log = SigningLog::open(&path).unwrap();
+        let first = log.record("alice", b"payload one").unwrap();
+        let second = log.record("alice", b"payload two").unwrap();
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.prev_hash, first.hash);
+        assert_eq!(second.sequence, 1);
+        assert!(first.call.is_none());
+        log.verify().unwrap();
+
+        // Reopening continues the chain
+        let reopened = SigningLog::open(&path).unwrap();
+        let third = reopened.record("bob", b"payload three").unwrap();
+        assert_eq!(third.prev_hash, second.hash);
+        reopened.verify().unwrap();
+
+        // Rewriting an entry breaks it
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, contents.replacen("alice", "mallory", 1)).unwrap();
+        let err = SigningLog::open(&path).unwrap().verify().unwrap_err();
+        assert!(err.to_string().contains("entry 0"));
+
+        // So does dropping one
+        let mut receipts = reopened.receipts();
+        receipts.remove(1);
+        assert!(verify_chain(&receipts).is_err());
+    }
+
+    #[test]
+    fn test_verify_detects_edited_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signatures.jsonl");
+        let log = SigningLog::open(&path).unwrap();
+        for payload in [b"one", b"two", b"six"] {
+            log.record("alice", payload).unwrap();
+        }
+        let lines: Vec<String> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let second: SigningReceipt = serde_json::from_str(&lines[1]).unwrap();
+
+        let broken_at = |lines: &[String]| {
+            std::fs::write(&path, lines.join("\n")).unwrap();
+            SigningLog::open(&path)
+                .unwrap()
+                .verify()
+                .unwrap_err()
+                .to_string()
+        };
+
+        // Another payload hash
+        let mut edited = lines.clone();
+        edited[1] = edited[1].replace(&second.payload_hash, &format!("0x{}", "00".repeat(32)));
+        assert!(broken_at(&edited).contains("entry 1"));
+
+        // Entries swapped
+        let mut swapped = lines.clone();
+        swapped.swap(1, 2);
+        assert!(broken_at(&swapped).contains("entry 1"));
+
+        // Hash recomputed for the edit, which breaks the next link
+        let mut rehashed = second.clone();
+        rehashed.signer = "mallory".to_string();
+        rehashed.hash = rehashed.compute_hash();
+        let mut relinked = lines.clone();
+        relinked[1] = serde_json::to_string(&rehashed).unwrap();
+        assert!(broken_at(&relinked).contains("entry 2"));
+
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        SigningLog::open(&path).unwrap().verify().unwrap();
+    }
+
+    #[test]
+    fn test_open_rejects_truncated_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signatures.jsonl");
+        let log = SigningLog::open(&path).unwrap();
+        log.record("alice", b"one").unwrap();
+        log.record("alice", b"two").unwrap();
+
+        // A write cut short leaves half a receipt on the last line
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, &contents[..contents.len() - 20]).unwrap();
+
+        let err = SigningLog::open(&path).unwrap_err();
+        assert!(err.to_string().contains("Invalid signing log"), "{}", err);
+    }
+
+    #[test]
+    fn test_reopen_resumes_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signatures.jsonl");
+
+        let mut last = None;
+        for round in 0..3u64 {
+            let log = SigningLog::open(&path).unwrap();
+            assert_eq!(log.len() as u64, round * 2);
+
+            for payload in [b"first".as_slice(), b"second"] {
+                let receipt = log.record("alice", payload).unwrap();
+                let prev_hash = last.map_or(GENESIS_HASH.to_string(), |r: SigningReceipt| r.hash);
+                assert_eq!(receipt.prev_hash, prev_hash);
+                last = Some(receipt);
+            }
+        }
+
+        let log = SigningLog::open(&path).unwrap();
+        log.verify().unwrap();
+        let sequences: Vec<u64> = log.receipts().iter().map(|r| r.sequence).collect();
+        assert_eq!(sequences, (0..6).collect::<Vec<_>>());
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 6);
+    }
+}
@@ -0,0 +1,728 @@
+//! Nominated proof-of-stake through pallet-staking
+//!
+//! [`StakingManager`] builds the calls a nominator needs (bonding, unbonding,
+//! withdrawing and choosing validators) and reads the staking state that
+//! goes with them: the active era, the bonded ledger and its unbonding
+//! schedule, where a nominator's stake is exposed, and rewards that have not
+//! been paid out yet.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::staking::RewardDestination;
+//!
+//! # async fn example(adapter: &apex_sdk_substrate::SubstrateAdapter) -> apex_sdk_substrate::Result<()> {
+//! let staking = adapter.staking();
+//! let bond = staking.bond(10_000_000_000_000, RewardDestination::Staked)?;
+//!
+//! let era = staking.active_era().await?.index;
+//! let rewards = staking
+//!     .pending_rewards("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", era.saturating_sub(7)..era)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::assets::{missing_field, value_bytes};
+use crate::chain_client::StorageKey;
+use crate::storage::{extract_u128, StorageQuery};
+use crate::{Result, SubstrateAdapter};
+use apex_sdk_types::{Address, IntoAddress};
+use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+use std::ops::Range;
+use subxt::dynamic::{At as _, Value};
+use subxt::ext::scale_value::ValueDef;
+use tracing::{debug, info};
+
+const STAKING_PALLET: &str = "Staking";
+
+/// Parts per billion in a `Perbill`
+const PERBILL: u128 = 1_000_000_000;
+
+/// Where staking rewards are paid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RewardDestination {
+    /// Paid to the stash and added to the bond
+    Staked,
+    /// Paid to the stash, not bonded
+    Stash,
+    /// Paid to another account
+    Account(Address),
+    /// Not paid out
+    None,
+}
+
+impl RewardDestination {
+    fn to_value(&self) -> Result<Value> {
+        Ok(match self {
+            RewardDestination::Staked => Value::unnamed_variant("Staked", []),
+            RewardDestination::Stash => Value::unnamed_variant("Stash", []),
+            RewardDestination::Account(account) => {
+                Value::unnamed_variant("Account", [StorageQuery::parse_address(account.clone())?])
+            }
+            RewardDestination::None => Value::unnamed_variant("None", []),
+        })
+    }
+}
+
+/// The era rewards are currently paid for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveEra {
+    /// Era index
+    pub index: u32,
+    /// Start of the era in milliseconds since the Unix epoch, once its first
+    /// block was produced
+    pub start: Option<u64>,
+}
+
+impl ActiveEra {
+    pub(crate) fn from_value<T>(value: &Value<T>) -> Result<Self> {
+        Ok(Self {
+            index: extract_u128(value, &["index"])
+                .and_then(|i| u32::try_from(i).ok())
+                .ok_or_else(|| missing_field("index"))?,
+            start: value
+                .at("start")
+                .and_then(some)
+                .and_then(number)
+                .and_then(|s| u64::try_from(s).ok()),
+        })
+    }
+}
+
+/// Stake being unbonded, withdrawable from `era` on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnlockChunk {
+    /// Amount unbonding
+    pub value: u128,
+    /// First era the amount can be withdrawn in
+    pub era: u32,
+}
+
+/// Bonded funds of a stash, from `Ledger` storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StakingLedger {
+    /// SS58 address of the stash
+    pub stash: String,
+    /// Bonded amount, including what is unbonding
+    pub total: u128,
+    /// Amount at stake in upcoming eras
+    pub active: u128,
+    /// Amounts unbonding, oldest first
+    pub unlocking: Vec<UnlockChunk>,
+}
+
+impl StakingLedger {
+    pub(crate) fn from_value<T>(value: &Value<T>, ss58_prefix: u16) -> Result<Self> {
+        let mut unlocking = Vec::new();
+        if let Some(chunks) = value.at("unlocking") {
+            collect_unlock_chunks(chunks, &mut unlocking);
+        }
+
+        Ok(Self {
+            stash: value
+                .at("stash")
+                .and_then(account_id)
+                .map(|id| ss58(id, ss58_prefix))
+                .ok_or_else(|| missing_field("stash"))?,
+            total: extract_u128(value, &["total"]).ok_or_else(|| missing_field("total"))?,
+            active: extract_u128(value, &["active"]).ok_or_else(|| missing_field("active"))?,
+            unlocking,
+        })
+    }
+
+    /// Amount `withdraw_unbonded` releases in `current_era`
+    pub fn withdrawable(&self, current_era: u32) -> u128 {
+        self.unlocking
+            .iter()
+            .filter(|chunk| chunk.era <= current_era)
+            .map(|chunk| chunk.value)
+            .sum()
+    }
+}
+
+/// Validators a stash nominates, from `Nominators` storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nominations {
+    /// SS58 addresses of the nominated validators
+    pub targets: Vec<String>,
+    /// Era the nominations were submitted in
+    pub submitted_in: u32,
+}
+
+/// A nominator's stake behind one validator in an era
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NominatorExposure {
+    /// Era of the exposure
+    pub era: u32,
+    /// SS58 address of the validator
+    pub validator: String,
+    /// Exposure page the nominator is paid out in
+    pub page: u32,
+    /// Nominator's stake behind the validator
+    pub value: u128,
+    /// Total stake behind the validator, its own included
+    pub total: u128,
+}
+
+/// Reward earned by a nominator and not paid out yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingReward {
+    /// Era the reward was earned in
+    pub era: u32,
+    /// SS58 address of the validator to call `payout_stakers_by_page` for
+    pub validator: String,
+    /// Exposure page to pay out
+    pub page: u32,
+    /// Nominator's share of the reward
+    pub amount: u128,
+}
+
+/// High-level API for interacting with pallet-staking as a nominator
+///
+/// Queries read the paged exposures (`ErasStakersOverview` and
+/// `ErasStakersPaged`) introduced with paged rewards; runtimes that only
+/// have the legacy `ErasStakers` report no exposures.
+pub struct StakingManager<'a> {
+    adapter: &'a SubstrateAdapter,
+}
+
+impl<'a> StakingManager<'a> {
+    pub fn new(adapter: &'a SubstrateAdapter) -> Self {
+        Self { adapter }
+    }
+
+    /// Bond `value` from the signing stash
+    pub fn bond(&self, value: u128, payee: RewardDestination) -> Result<subxt::tx::DynamicPayload> {
+        info!(operation = "bond", amount = %value, "Preparing to bond");
+        Ok(subxt::dynamic::tx(
+            STAKING_PALLET,
+            "bond",
+            vec![Value::u128(value), payee.to_value()?],
+        ))
+    }
+
+    /// Add `max_additional` of the stash's free balance to its bond
+    pub fn bond_extra(&self, max_additional: u128) -> subxt::tx::DynamicPayload {
+        info!(
+            operation = "bond_extra",
+            amount = %max_additional,
+            "Preparing to bond more"
+        );
+        subxt::dynamic::tx(
+            STAKING_PALLET,
+            "bond_extra",
+            vec![Value::u128(max_additional)],
+        )
+    }
+
+    /// Start unbonding `value`; it becomes withdrawable after the bonding
+    /// duration
+    pub fn unbond(&self, value: u128) -> subxt::tx::DynamicPayload {
+        info!(operation = "unbond", amount = %value, "Preparing to unbond");
+        subxt::dynamic::tx(STAKING_PALLET, "unbond", vec![Value::u128(value)])
+    }
+
+    /// Withdraw unbonded chunks that reached their era
+    ///
+    /// `num_slashing_spans` is the stash's number of slashing spans, 0 for a
+    /// stash that was never slashed; it only bounds the call's weight.
+    pub fn withdraw_unbonded(&self, num_slashing_spans: u32) -> subxt::tx::DynamicPayload {
+        info!("Preparing to withdraw unbonded funds");
+        subxt::dynamic::tx(
+            STAKING_PALLET,
+            "withdraw_unbonded",
+            vec![Value::u128(num_slashing_spans as u128)],
+        )
+    }
+
+    /// Nominate validators from the next era on
+    pub fn nominate<A: IntoAddress>(
+        &self,
+        targets: impl IntoIterator<Item = A>,
+    ) -> Result<subxt::tx::DynamicPayload> {
+        let targets = targets
+            .into_iter()
+            .map(|target| {
+                Ok(Value::unnamed_variant(
+                    "Id",
+                    [StorageQuery::parse_address(target)?],
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        info!("Preparing to nominate {} validators", targets.len());
+
+        Ok(subxt::dynamic::tx(
+            STAKING_PALLET,
+            "nominate",
+            vec![Value::unnamed_composite(targets)],
+        ))
+    }
+
+    /// Stop nominating (or validating) from the next era on
+    pub fn chill(&self) -> subxt::tx::DynamicPayload {
+        info!("Preparing to chill");
+        subxt::dynamic::tx(STAKING_PALLET, "chill", Vec::<Value>::new())
+    }
+
+    /// The era rewards are currently paid for
+    pub async fn active_era(&self) -> Result<ActiveEra> {
+        let value = self
+            .read("ActiveEra", vec![])
+            .await?
+            .ok_or_else(|| missing_field("ActiveEra"))?;
+        ActiveEra::from_value(&value)
+    }
+
+    /// The latest planned era, which unbonding eras count against
+    pub async fn current_era(&self) -> Result<u32> {
+        self.read("CurrentEra", vec![])
+            .await?
+            .and_then(|value| number(&value))
+            .and_then(|era| u32::try_from(era).ok())
+            .ok_or_else(|| missing_field("CurrentEra"))
+    }
+
+    /// Bonded funds of a stash, `None` if it is not bonded
+    pub async fn ledger(&self, stash: impl IntoAddress) -> Result<Option<StakingLedger>> {
+        let stash = StorageQuery::parse_address(stash)?;
+        let Some(controller) = self.read("Bonded", vec![stash]).await? else {
+            return Ok(None);
+        };
+        let controller = account_id(&controller).ok_or_else(|| missing_field("Bonded"))?;
+
+        self.read("Ledger", vec![Value::from_bytes(controller)])
+            .await?
+            .map(|ledger| StakingLedger::from_value(&ledger, self.adapter.config().ss58_prefix))
+            .transpose()
+    }
+
+    /// Chunks of a stash's bond that are unbonding, oldest first
+    pub async fn unbonding_schedule(&self, stash: impl IntoAddress) -> Result<Vec<UnlockChunk>> {
+        Ok(self
+            .ledger(stash)
+            .await?
+            .map(|ledger| ledger.unlocking)
+            .unwrap_or_default())
+    }
+
+    /// Validators a stash nominates, `None` if it is not nominating
+    pub async fn nominations(&self, stash: impl IntoAddress) -> Result<Option<Nominations>> {
+        let stash = stash.into_address()?.account_id()?;
+        let ss58_prefix = self.adapter.config().ss58_prefix;
+
+        Ok(self
+            .targets_of(stash)
+            .await?
+            .map(|(targets, submitted_in)| Nominations {
+                targets: targets
+                    .into_iter()
+                    .map(|id| ss58(id, ss58_prefix))
+                    .collect(),
+                submitted_in,
+            }))
+    }
+
+    /// Where a nominator's stake was exposed in `era`
+    ///
+    /// Only the validators the nominator currently nominates are checked, so
+    /// validators it dropped since `era` are missed.
+    pub async fn nominator_exposure(
+        &self,
+        era: u32,
+        nominator: impl IntoAddress,
+    ) -> Result<Vec<NominatorExposure>> {
+        let nominator = nominator.into_address()?.account_id()?;
+        self.exposures_of(era, nominator).await
+    }
+
+    /// Rewards a nominator earned in `eras` that were not paid out yet
+    ///
+    /// Eras older than the chain's history depth can no longer be paid out
+    /// and report nothing, as does the active era, whose reward is not known
+    /// yet. Amounts are computed the way the runtime splits a payout and may
+    /// differ from it by rounding.
+    pub async fn pending_rewards(
+        &self,
+        nominator: impl IntoAddress,
+        eras: Range<u32>,
+    ) -> Result<Vec<PendingReward>> {
+        let nominator = nominator.into_address()?.account_id()?;
+        let mut rewards = Vec::new();
+
+        for era in eras {
+            let era_key = Value::u128(era as u128);
+            let Some(era_payout) = self
+                .read("ErasValidatorReward", vec![era_key.clone()])
+                .await?
+                .and_then(|value| number(&value))
+            else {
+                continue;
+            };
+            let Some(points) = self.read("ErasRewardPoints", vec![era_key.clone()]).await? else {
+                continue;
+            };
+            let total_points = extract_u128(&points, &["total"]).unwrap_or(0);
+
+            for exposure in self.exposures_of(era, nominator).await? {
+                let validator_id = validator_account(&exposure.validator)?;
+                let validator = Value::from_bytes(validator_id);
+
+                let claimed = self
+                    .read("ClaimedRewards", vec![era_key.clone(), validator.clone()])
+                    .await?
+                    .is_some_and(|pages| contains_page(&pages, exposure.page));
+                if claimed {
+                    continue;
+                }
+
+                let validator_points = points_of(&points, &validator_id).unwrap_or(0);
+                let commission = self
+                    .read("ErasValidatorPrefs", vec![era_key.clone(), validator])
+                    .await?
+                    .and_then(|prefs| prefs.at("commission").and_then(number))
+                    .unwrap_or(0);
+
+                let amount = nominator_reward(
+                    era_payout,
+                    total_points,
+                    validator_points,
+                    commission,
+                    exposure.total,
+                    exposure.value,
+                );
+                if amount > 0 {
+                    rewards.push(PendingReward {
+                        era,
+                        validator: exposure.validator,
+                        page: exposure.page,
+                        amount,
+                    });
+                }
+            }
+        }
+
+        Ok(rewards)
+    }
+
+    /// Validators `stash` nominates and the era it nominated them in
+    async fn targets_of(&self, stash: [u8; 32]) -> Result<Option<(Vec<[u8; 32]>, u32)>> {
+        self.read("Nominators", vec![Value::from_bytes(stash)])
+            .await?
+            .map(|value| {
+                let mut targets = Vec::new();
+                if let Some(list) = value.at("targets") {
+                    collect_accounts(list, &mut targets);
+                }
+                let submitted_in = extract_u128(&value, &["submitted_in"])
+                    .and_then(|era| u32::try_from(era).ok())
+                    .ok_or_else(|| missing_field("submitted_in"))?;
+                Ok((targets, submitted_in))
+            })
+            .transpose()
+    }
+
+    async fn exposures_of(&self, era: u32, nominator: [u8; 32]) -> Result<Vec<NominatorExposure>> {
+        let Some((targets, _)) = self.targets_of(nominator).await? else {
+            return Ok(Vec::new());
+        };
+        debug!(
+            era,
+            validators = targets.len(),
+            "Querying nominator exposure"
+        );
+
+        let ss58_prefix = self.adapter.config().ss58_prefix;
+        let mut exposures = Vec::new();
+        for validator_id in targets {
+            let validator = ss58(validator_id, ss58_prefix);
+            let validator_key = Value::from_bytes(validator_id);
+            let Some(overview) = self
+                .read(
+                    "ErasStakersOverview",
+                    vec![Value::u128(era as u128), validator_key.clone()],
+                )
+                .await?
+            else {
+                continue;
+            };
+            let total =
+                extract_u128(&overview, &["total"]).ok_or_else(|| missing_field("total"))?;
+            let page_count = extract_u128(&overview, &["page_count"]).unwrap_or(0) as u32;
+
+            for page in 0..page_count {
+                let Some(exposure_page) = self
+                    .read(
+                        "ErasStakersPaged",
+                        vec![
+                            Value::u128(era as u128),
+                            validator_key.clone(),
+                            Value::u128(page as u128),
+                        ],
+                    )
+                    .await?
+                else {
+                    continue;
+                };
+                if let Some(value) = stake_of(&exposure_page, &nominator) {
+                    exposures.push(NominatorExposure {
+                        era,
+                        validator: validator.clone(),
+                        page,
+                        value,
+                        total,
+                    });
+                    break;
+                }
+            }
+        }
+
+        Ok(exposures)
+    }
+
+    async fn read(&self, item: &str, keys: Vec<Value>) -> Result<Option<Value>> {
+        Ok(self
+            .adapter
+            .chain_client()
+            .storage(&StorageKey::new(STAKING_PALLET, item, keys), None)
+            .await?
+            .map(|entry| entry.value))
+    }
+}
+
+/// A nominator's share of a validator's era payout
+///
+/// The validator earns `era_payout` in proportion to its reward points, keeps
+/// its commission (in parts per billion) and shares the rest among everyone
+/// exposed behind it in proportion to their stake.
+pub(crate) fn nominator_reward(
+    era_payout: u128,
+    total_points: u128,
+    validator_points: u128,
+    commission: u128,
+    exposure_total: u128,
+    stake: u128,
+) -> u128 {
+    let validator_payout = mul_div(era_payout, validator_points, total_points);
+    let commission_payout = mul_div(validator_payout, commission.min(PERBILL), PERBILL);
+    mul_div(validator_payout - commission_payout, stake, exposure_total)
+}
+
+/// `a * b / c` without overflowing when the result fits
+fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    if c == 0 {
+        return 0;
+    }
+    match a.checked_mul(b) {
+        Some(product) => product / c,
+        None => (a / c)
+            .saturating_mul(b)
+            .saturating_add((a % c).saturating_mul(b) / c),
+    }
+}
+
+fn ss58(id: [u8; 32], ss58_prefix: u16) -> String {
+    AccountId32::from(id).to_ss58check_with_version(Ss58AddressFormat::custom(ss58_prefix))
+}
+
+fn validator_account(address: &str) -> Result<[u8; 32]> {
+    Ok(address.into_address()?.account_id()?)
+}
+
+fn account_id<T>(value: &Value<T>) -> Option<[u8; 32]> {
+    value_bytes(value).and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+}
+
+/// Inner value of `Some`
+fn some<T>(value: &Value<T>) -> Option<&Value<T>> {
+    match &value.value {
+        ValueDef::Variant(variant) if variant.name == "Some" => variant.values.values().next(),
+        _ => None,
+    }
+}
+
+/// A number, unwrapping newtypes such as `Perbill`
+fn number<T>(value: &Value<T>) -> Option<u128> {
+    value.as_u128().or_else(|| match &value.value {
+        ValueDef::Composite(composite) if composite.len() == 1 => {
+            composite.values().next().and_then(number)
+        }
+        _ => None,
+    })
+}
+
+/// Every account ID in a (possibly bounded) list
+fn collect_accounts<T>(value: &Value<T>, accounts: &mut Vec<[u8; 32]>) {
+    if let Some(id) = account_id(value) {
+        accounts.push(id);
+    } else if let ValueDef::Composite(composite) = &value.value {
+        for inner in composite.values() {
+            collect_accounts(inner, accounts);
+        }
+    }
+}
+
+fn collect_unlock_chunks<T>(value: &Value<T>, chunks: &mut Vec<UnlockChunk>) {
+    if let (Some(amount), Some(era)) = (
+        value.at("value").and_then(number),
+        value.at("era").and_then(number),
+    ) {
+        chunks.push(UnlockChunk {
+            value: amount,
+            era: era as u32,
+        });
+    } else if let ValueDef::Composite(composite) = &value.value {
+        for inner in composite.values() {
+            collect_unlock_chunks(inner, chunks);
+        }
+    }
+}
+
+/// Stake of `account` in an exposure page
+fn stake_of<T>(value: &Value<T>, account: &[u8; 32]) -> Option<u128> {
+    if let Some(who) = value.at("who") {
+        return (account_id(who).as_ref() == Some(account))
+            .then(|| value.at("value").and_then(number))
+            .flatten();
+    }
+    match &value.value {
+        ValueDef::Composite(composite) => composite.values().find_map(|v| stake_of(v, account)),
+        _ => None,
+    }
+}
+
+/// Reward points of `account` in an era's `(account, points)` map
+fn points_of<T>(value: &Value<T>, account: &[u8; 32]) -> Option<u128> {
+    let ValueDef::Composite(composite) = &value.value else {
+        return None;
+    };
+    let values: Vec<&Value<T>> = composite.values().collect();
+    if let [key, points] = values.as_slice() {
+        if account_id(key).as_ref() == Some(account) {
+            return number(points);
+        }
+    }
+    values.iter().find_map(|v| points_of(v, account))
+}
+
+fn contains_page<T>(pages: &Value<T>, page: u32) -> bool {
+    match &pages.value {
+        ValueDef::Composite(composite) => composite.values().any(|v| contains_page(v, page)),
+        _ => pages.as_u128() == Some(page as u128),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(byte: u8) -> Value {
+        // AccountId32 is a newtype around [u8; 32]
+        Value::unnamed_composite([Value::unnamed_composite(
+            [byte; 32].iter().map(|b| Value::u128(*b as u128)),
+        )])
+    }
+
+    fn bounded(items: impl IntoIterator<Item = Value>) -> Value {
+        Value::unnamed_composite([Value::unnamed_composite(items)])
+    }
+
+    #[test]
+    fn test_ledger_from_value() {
+        let chunk = |value: u128, era: u128| {
+            Value::named_composite([("value", Value::u128(value)), ("era", Value::u128(era))])
+        };
+        let value = Value::named_composite([
+            ("stash", account(1)),
+            ("total", Value::u128(1_500)),
+            ("active", Value::u128(1_000)),
+            ("unlocking", bounded([chunk(300, 10), chunk(200, 12)])),
+            ("legacy_claimed_rewards", bounded([])),
+        ]);
+
+        let ledger = StakingLedger::from_value(&value, 0).unwrap();
+        assert_eq!(ledger.stash, ss58([1; 32], 0));
+        assert_eq!(ledger.active, 1_000);
+        assert_eq!(
+            ledger.unlocking,
+            vec![
+                UnlockChunk {
+                    value: 300,
+                    era: 10
+                },
+                UnlockChunk {
+                    value: 200,
+                    era: 12
+                }
+            ]
+        );
+        assert_eq!(ledger.withdrawable(9), 0);
+        assert_eq!(ledger.withdrawable(11), 300);
+        assert_eq!(ledger.withdrawable(12), 500);
+
+        let era = Value::named_composite([
+            ("index", Value::u128(1_700)),
+            (
+                "start",
+                Value::unnamed_variant("Some", [Value::u128(1_700_000)]),
+            ),
+        ]);
+        assert_eq!(
+            ActiveEra::from_value(&era).unwrap(),
+            ActiveEra {
+                index: 1_700,
+                start: Some(1_700_000)
+            }
+        );
+    }
+
+    #[test]
+    fn test_exposure_and_points_lookup() {
+        let individual = |who: u8, value: u128| {
+            Value::named_composite([("who", account(who)), ("value", Value::u128(value))])
+        };
+        let page = Value::named_composite([
+            ("page_total", Value::u128(300)),
+            (
+                "others",
+                Value::unnamed_composite([individual(2, 100), individual(3, 200)]),
+            ),
+        ]);
+        assert_eq!(stake_of(&page, &[3; 32]), Some(200));
+        assert_eq!(stake_of(&page, &[4; 32]), None);
+
+        let points = Value::named_composite([
+            ("total", Value::u128(100)),
+            (
+                "individual",
+                Value::unnamed_composite([
+                    Value::unnamed_composite([account(5), Value::u128(60)]),
+                    Value::unnamed_composite([account(6), Value::u128(40)]),
+                ]),
+            ),
+        ]);
+        assert_eq!(points_of(&points, &[6; 32]), Some(40));
+
+        let mut targets = Vec::new();
+        collect_accounts(&bounded([account(5)]), &mut targets);
+        collect_accounts(&bounded([account(6), account(7)]), &mut targets);
+        assert_eq!(targets, vec![[5; 32], [6; 32], [7; 32]]);
+
+        assert!(contains_page(
+            &Value::unnamed_composite([Value::u128(0), Value::u128(2)]),
+            2
+        ));
+    }
+
+    #[test]
+    fn test_nominator_reward() {
+        // 40% of the points, 10% commission, a quarter of the stake
+        let reward = nominator_reward(1_000_000, 100, 40, 100_000_000, 400, 100);
+        assert_eq!(reward, 90_000);
+
+        // No points or no stake earns nothing
+        assert_eq!(nominator_reward(1_000_000, 0, 0, 0, 400, 100), 0);
+        assert_eq!(nominator_reward(1_000_000, 100, 40, 0, 0, 0), 0);
+
+        // Large payouts don't overflow
+        assert_eq!(mul_div(u128::MAX / 2, 4, 8), u128::MAX / 4);
+    }
+}
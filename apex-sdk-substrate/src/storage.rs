@@ -6,107 +6,82 @@
 //! - Runtime constants
 //! - Metadata inspection
 
+use crate::chain_client::{storage_value_type, ChainClient, StorageKey, SubxtClient};
 use crate::{Error, Metrics, Result};
+use apex_sdk_types::{Cursor, IntoAddress, Page, PageRequest};
+use std::sync::Arc;
 use subxt::dynamic::At as _;
 use subxt::{OnlineClient, PolkadotConfig};
 use tracing::debug;
 
+pub use subxt::ext::scale_decode::{self, DecodeAsType};
+
 /// Storage query client for accessing chain storage
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct StorageClient {
-    client: OnlineClient<PolkadotConfig>,
+    chain: Arc<dyn ChainClient>,
     metrics: Metrics,
 }
 
+impl std::fmt::Debug for StorageClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StorageClient")
+            .field("metrics", &self.metrics)
+            .finish_non_exhaustive()
+    }
+}
+
 impl StorageClient {
     /// Create a new storage client
     pub fn new(client: OnlineClient<PolkadotConfig>, metrics: Metrics) -> Self {
-        Self { client, metrics }
+        Self::with_chain_client(Arc::new(SubxtClient::new(client)), metrics)
+    }
+
+    /// Create a storage client reading through any [`ChainClient`]
+    pub fn with_chain_client(chain: Arc<dyn ChainClient>, metrics: Metrics) -> Self {
+        Self { chain, metrics }
     }
 
     /// Query account information including balance and nonce
-    pub async fn get_account_info(&self, address: &str) -> Result<AccountInfo> {
-        debug!("Querying account info for: {}", address);
-        self.metrics.record_storage_query();
+    pub async fn get_account_info(&self, address: impl IntoAddress) -> Result<AccountInfo> {
+        let address = address.into_address()?;
+        let account_id = address.account_id()?;
 
-        // Parse SS58 address to get AccountId32
-        use sp_core::crypto::{AccountId32, Ss58Codec};
-        let account_id = AccountId32::from_ss58check(address)
-            .map_err(|e| Error::Storage(format!("Invalid SS58 address: {}", e)))?;
+        debug!(address = %address, "Querying account info");
+        self.metrics.record_storage_query();
 
         // Query System::Account storage using dynamic API
-        let account_bytes: &[u8] = account_id.as_ref();
-        let storage_query = subxt::dynamic::storage(
+        let key = StorageKey::new(
             "System",
             "Account",
-            vec![subxt::dynamic::Value::from_bytes(account_bytes)],
+            vec![subxt::dynamic::Value::from_bytes(account_id)],
         );
 
-        let storage = self
-            .client
-            .storage()
-            .at_latest()
-            .await
-            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?;
-
-        let result = storage
-            .fetch(&storage_query)
+        let result = self
+            .chain
+            .storage(&key, None)
             .await
             .map_err(|e| Error::Storage(format!("Failed to query account info: {}", e)))?;
 
         // Decode the result
-        if let Some(value) = result {
+        if let Some(account_data) = result {
             // The System::Account storage returns AccountInfo structure
-            // We need to decode it from the dynamic value
-            let account_data = value
-                .to_value()
-                .map_err(|e| Error::Storage(format!("Failed to decode account value: {}", e)))?;
-
-            // Extract fields from the composite value
-            let nonce = extract_u64(&account_data, &["nonce"])
-                .ok_or_else(|| Error::Storage("Failed to extract 'nonce' field".to_string()))?;
-            let consumers = extract_u32(&account_data, &["consumers"])
-                .ok_or_else(|| Error::Storage("Failed to extract 'consumers' field".to_string()))?;
-            let providers = extract_u32(&account_data, &["providers"])
-                .ok_or_else(|| Error::Storage("Failed to extract 'providers' field".to_string()))?;
-            let sufficients = extract_u32(&account_data, &["sufficients"]).ok_or_else(|| {
-                Error::Storage("Failed to extract 'sufficients' field".to_string())
-            })?;
-
-            // Extract balance data (nested in "data" field)
-            let free = extract_u128(&account_data, &["data", "free"])
-                .ok_or_else(|| Error::Storage("Failed to extract 'data.free' field".to_string()))?;
-            let reserved = extract_u128(&account_data, &["data", "reserved"]).ok_or_else(|| {
-                Error::Storage("Failed to extract 'data.reserved' field".to_string())
-            })?;
-            let frozen = extract_u128(&account_data, &["data", "frozen"]).ok_or_else(|| {
-                Error::Storage("Failed to extract 'data.frozen' field".to_string())
-            })?;
-
-            Ok(AccountInfo {
-                nonce,
-                consumers,
-                providers,
-                sufficients,
-                free,
-                reserved,
-                frozen,
-            })
+            AccountInfo::from_value(&account_data.value)
         } else {
             // Account doesn't exist, return default
-            debug!("Account {} not found, returning default", address);
+            debug!(address = %address, "Account not found, returning default");
             Ok(AccountInfo::default())
         }
     }
 
     /// Query account balance (free balance only)
-    pub async fn get_balance(&self, address: &str) -> Result<u128> {
+    pub async fn get_balance(&self, address: impl IntoAddress) -> Result<u128> {
         let account_info = self.get_account_info(address).await?;
         Ok(account_info.free)
     }
 
     /// Query account nonce
-    pub async fn get_nonce(&self, address: &str) -> Result<u64> {
+    pub async fn get_nonce(&self, address: impl IntoAddress) -> Result<u64> {
         let account_info = self.get_account_info(address).await?;
         Ok(account_info.nonce)
     }
@@ -121,23 +96,12 @@ impl StorageClient {
         debug!("Querying storage: {}::{}", pallet, item);
         self.metrics.record_storage_query();
 
-        let storage_query = subxt::dynamic::storage(pallet, item, keys);
-
-        let storage = self
-            .client
-            .storage()
-            .at_latest()
-            .await
-            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?;
-
-        let result = storage.fetch(&storage_query).await.map_err(|e| {
-            Error::Storage(format!(
-                "Failed to query storage {}::{}: {}",
-                pallet, item, e
-            ))
-        })?;
+        let result = self
+            .chain
+            .storage(&StorageKey::new(pallet, item, keys), None)
+            .await?;
 
-        Ok(result.map(|v| v.encoded().to_vec()))
+        Ok(result.map(|v| v.bytes))
     }
 
     /// Get a runtime constant (returns raw bytes)
@@ -145,15 +109,7 @@ impl StorageClient {
         debug!("Getting constant: {}::{}", pallet, constant);
         self.metrics.record_storage_query();
 
-        let constant_address = subxt::dynamic::constant(pallet, constant);
-
-        let value = self
-            .client
-            .constants()
-            .at(&constant_address)
-            .map_err(|e| Error::Storage(format!("Failed to get constant: {}", e)))?;
-
-        Ok(value.encoded().to_vec())
+        self.chain.constant(pallet, constant)
     }
 
     /// Get the existential deposit (minimum balance to keep account alive)
@@ -183,22 +139,12 @@ impl StorageClient {
         // Parse the block hash
         let block_hash = parse_block_hash(block_hash_hex)?;
 
-        let storage_query = subxt::dynamic::storage(pallet, item, keys);
-
         let result = self
-            .client
-            .storage()
-            .at(block_hash)
-            .fetch(&storage_query)
-            .await
-            .map_err(|e| {
-                Error::Storage(format!(
-                    "Failed to query storage {}::{} at block: {}",
-                    pallet, item, e
-                ))
-            })?;
+            .chain
+            .storage(&StorageKey::new(pallet, item, keys), Some(block_hash))
+            .await?;
 
-        Ok(result.map(|v| v.encoded().to_vec()))
+        Ok(result.map(|v| v.bytes))
     }
 
     /// Iterate over storage entries and return their keys and values
@@ -206,39 +152,61 @@ impl StorageClient {
         debug!("Iterating storage: {}::{}", pallet, item);
         self.metrics.record_storage_query();
 
-        let storage_query =
-            subxt::dynamic::storage(pallet, item, Vec::<subxt::dynamic::Value>::new());
-
-        let mut results = Vec::new();
-        let storage = self
-            .client
-            .storage()
-            .at_latest()
-            .await
-            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?;
-
-        let mut iter = storage.iter(storage_query).await.map_err(|e| {
-            Error::Storage(format!(
-                "Failed to iterate storage {}::{}: {}",
-                pallet, item, e
-            ))
-        })?;
-
-        while let Some(result) = iter.next().await {
-            let kv_pair = result
-                .map_err(|e| Error::Storage(format!("Failed to fetch storage entry: {}", e)))?;
-            results.push((kv_pair.key_bytes, kv_pair.value.encoded().to_vec()));
-        }
+        let results = self.chain.storage_entries(pallet, item).await?;
 
         debug!("Found {} entries in {}::{}", results.len(), pallet, item);
         Ok(results)
     }
 
+    /// One page of storage entries, in raw key order
+    ///
+    /// The cursor is the last raw key of the previous page. Each page is read
+    /// at the latest finalized block, so entries changed between calls may be
+    /// skipped or seen twice.
+    pub async fn iter_storage_page(
+        &self,
+        pallet: &str,
+        item: &str,
+        request: &PageRequest,
+    ) -> Result<Page<(Vec<u8>, Vec<u8>)>> {
+        self.page_entries(&StorageKey::new(pallet, item, Vec::new()), None, request)
+            .await
+    }
+
+    /// One page of the entries under the partial key `prefix`
+    async fn page_entries(
+        &self,
+        prefix: &StorageKey,
+        at: Option<[u8; 32]>,
+        request: &PageRequest,
+    ) -> Result<Page<(Vec<u8>, Vec<u8>)>> {
+        debug!("Iterating storage page: {}::{}", prefix.pallet, prefix.item);
+        self.metrics.record_storage_query();
+
+        let limit = request.effective_limit();
+        let start_after = request.cursor.as_ref().map(Cursor::as_bytes);
+        // One extra entry tells whether another page follows
+        let mut entries = self
+            .chain
+            .storage_prefix_paged(prefix, start_after, limit + 1, at)
+            .await?;
+
+        let next_cursor = if entries.len() > limit as usize {
+            entries.truncate(limit as usize);
+            entries
+                .last()
+                .map(|(key, _)| Cursor::from_bytes(key.clone()))
+        } else {
+            None
+        };
+        Ok(Page::new(entries, next_cursor))
+    }
+
     /// Get metadata about a pallet
     pub fn get_pallet_metadata(&self, pallet: &str) -> Result<PalletMetadata> {
         debug!("Getting pallet metadata: {}", pallet);
 
-        let metadata = self.client.metadata();
+        let metadata = self.chain.metadata();
 
         // Check if pallet exists
         let pallet_metadata = metadata
@@ -279,7 +247,7 @@ impl StorageClient {
 
     /// List all available pallets
     pub fn list_pallets(&self) -> Vec<String> {
-        let metadata = self.client.metadata();
+        let metadata = self.chain.metadata();
         metadata.pallets().map(|p| p.name().to_string()).collect()
     }
 }
@@ -304,6 +272,37 @@ pub struct AccountInfo {
 }
 
 impl AccountInfo {
+    /// Extract account information from a decoded `System::Account` value
+    pub(crate) fn from_value<T>(account_data: &subxt::dynamic::Value<T>) -> Result<Self> {
+        // Extract fields from the composite value
+        let nonce = extract_u64(account_data, &["nonce"])
+            .ok_or_else(|| Error::Storage("Failed to extract 'nonce' field".to_string()))?;
+        let consumers = extract_u32(account_data, &["consumers"])
+            .ok_or_else(|| Error::Storage("Failed to extract 'consumers' field".to_string()))?;
+        let providers = extract_u32(account_data, &["providers"])
+            .ok_or_else(|| Error::Storage("Failed to extract 'providers' field".to_string()))?;
+        let sufficients = extract_u32(account_data, &["sufficients"])
+            .ok_or_else(|| Error::Storage("Failed to extract 'sufficients' field".to_string()))?;
+
+        // Extract balance data (nested in "data" field)
+        let free = extract_u128(account_data, &["data", "free"])
+            .ok_or_else(|| Error::Storage("Failed to extract 'data.free' field".to_string()))?;
+        let reserved = extract_u128(account_data, &["data", "reserved"])
+            .ok_or_else(|| Error::Storage("Failed to extract 'data.reserved' field".to_string()))?;
+        let frozen = extract_u128(account_data, &["data", "frozen"])
+            .ok_or_else(|| Error::Storage("Failed to extract 'data.frozen' field".to_string()))?;
+
+        Ok(Self {
+            nonce,
+            consumers,
+            providers,
+            sufficients,
+            free,
+            reserved,
+            frozen,
+        })
+    }
+
     /// Get total balance (free + reserved)
     pub fn total(&self) -> u128 {
         self.free.saturating_add(self.reserved)
@@ -334,23 +333,57 @@ pub struct PalletMetadata {
     pub error_count: usize,
 }
 
-/// Storage query helper
+/// Builder for storage queries with typed decoding
+///
+/// Plain storage values take no keys, maps one and double maps two. Keys are
+/// added in order with [`key`](Self::key) or the typed helpers
+/// [`account`](Self::account), [`account_id`](Self::account_id),
+/// [`u32`](Self::u32) and [`h256`](Self::h256). Leaving out trailing keys
+/// addresses every entry under the given ones, which [`page`](Self::page)
+/// iterates.
+///
+/// Values decode into any type implementing [`DecodeAsType`], using the
+/// runtime metadata's type for the entry:
+///
+/// ```no_run
+/// use apex_sdk_substrate::{DecodeAsType, StorageQuery, SubstrateAdapter};
+///
+/// #[derive(DecodeAsType)]
+/// #[decode_as_type(crate_path = "apex_sdk_substrate::storage::scale_decode")]
+/// struct AccountData {
+///     free: u128,
+///     reserved: u128,
+/// }
+///
+/// #[derive(DecodeAsType)]
+/// #[decode_as_type(crate_path = "apex_sdk_substrate::storage::scale_decode")]
+/// struct Account {
+///     nonce: u32,
+///     data: AccountData,
+/// }
+///
+/// # async fn example() -> apex_sdk_substrate::Result<()> {
+/// let adapter = SubstrateAdapter::connect("ws://127.0.0.1:9944").await?;
+/// let account: Option<Account> = StorageQuery::new("System", "Account")
+///     .account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")?
+///     .fetch(&adapter.storage())
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
 pub struct StorageQuery {
     pallet: String,
     item: String,
     keys: Vec<subxt::dynamic::Value>,
+    at: Option<[u8; 32]>,
 }
 
 impl StorageQuery {
     /// Parse an SS58 address into a subxt Value for use in storage queries
-    pub fn parse_address(address: &str) -> Result<subxt::dynamic::Value> {
-        use sp_core::crypto::{AccountId32, Ss58Codec};
-
-        let account_id = AccountId32::from_ss58check(address)
-            .map_err(|e| Error::Storage(format!("Invalid SS58 address: {}", e)))?;
-
-        let account_bytes: &[u8] = account_id.as_ref();
-        Ok(subxt::dynamic::Value::from_bytes(account_bytes))
+    pub fn parse_address(address: impl IntoAddress) -> Result<subxt::dynamic::Value> {
+        let account_id = address.into_address()?.account_id()?;
+        Ok(subxt::dynamic::Value::from_bytes(account_id))
     }
 
     /// Create a new storage query
@@ -359,6 +392,7 @@ impl StorageQuery {
             pallet: pallet.into(),
             item: item.into(),
             keys: Vec::new(),
+            at: None,
         }
     }
 
@@ -374,18 +408,135 @@ impl StorageQuery {
         self
     }
 
+    /// Add an `AccountId` key from an SS58 address
+    pub fn account(self, address: impl IntoAddress) -> Result<Self> {
+        Ok(self.key(Self::parse_address(address)?))
+    }
+
+    /// Add an `AccountId` key from raw public key bytes
+    pub fn account_id(self, account_id: [u8; 32]) -> Self {
+        self.key(subxt::dynamic::Value::from_bytes(account_id))
+    }
+
+    /// Add a `u32` key, such as an asset id or block number
+    pub fn u32(self, value: u32) -> Self {
+        self.key(subxt::dynamic::Value::u128(value as u128))
+    }
+
+    /// Add an `H256` key, such as a call or block hash
+    pub fn h256(self, hash: [u8; 32]) -> Self {
+        self.key(subxt::dynamic::Value::from_bytes(hash))
+    }
+
+    /// Read at `block_hash` instead of the latest block
+    pub fn at(mut self, block_hash: [u8; 32]) -> Self {
+        self.at = Some(block_hash);
+        self
+    }
+
+    /// Read at a hex block hash, with or without `0x`
+    pub fn at_hex(self, block_hash: &str) -> Result<Self> {
+        Ok(self.at(parse_block_hash(block_hash)?))
+    }
+
+    fn storage_key(&self) -> StorageKey {
+        StorageKey::new(self.pallet.as_str(), self.item.as_str(), self.keys.clone())
+    }
+
     /// Execute the query (returns raw bytes)
     pub async fn execute(&self, client: &StorageClient) -> Result<Option<Vec<u8>>> {
+        Ok(self.fetch_raw(client).await?.map(|v| v.bytes))
+    }
+
+    /// Fetch the value decoded as a dynamic [`Value`](subxt::dynamic::Value)
+    pub async fn fetch_value(
+        &self,
+        client: &StorageClient,
+    ) -> Result<Option<subxt::dynamic::Value>> {
+        Ok(self.fetch_raw(client).await?.map(|v| v.value))
+    }
+
+    /// Fetch the value decoded into `T`
+    pub async fn fetch<T: DecodeAsType>(&self, client: &StorageClient) -> Result<Option<T>> {
+        let Some(value) = self.fetch_raw(client).await? else {
+            return Ok(None);
+        };
+        let metadata = client.chain.metadata();
+        let value_ty = storage_value_type(&metadata, &self.pallet, &self.item)?;
+        decode_as(
+            &value.bytes,
+            value_ty,
+            metadata.types(),
+            &self.pallet,
+            &self.item,
+        )
+        .map(Some)
+    }
+
+    async fn fetch_raw(&self, client: &StorageClient) -> Result<Option<crate::StorageValue>> {
+        debug!("Querying storage: {}::{}", self.pallet, self.item);
+        client.metrics.record_storage_query();
+        client.chain.storage(&self.storage_key(), self.at).await
+    }
+
+    /// One page of the entries under this query's keys, as raw keys and
+    /// values decoded into `T`
+    ///
+    /// Entries come in raw key order and the cursor is the last raw key of
+    /// the previous page. For `Blake2_128Concat` and `Twox64Concat` maps, the
+    /// last key is readable at the end of the raw key. Without [`at`](Self::at),
+    /// each page is read at the latest finalized block, so entries changed
+    /// between calls may be skipped or seen twice.
+    pub async fn page<T: DecodeAsType>(
+        &self,
+        client: &StorageClient,
+        request: &PageRequest,
+    ) -> Result<Page<(Vec<u8>, T)>> {
+        let page = client
+            .page_entries(&self.storage_key(), self.at, request)
+            .await?;
+        let metadata = client.chain.metadata();
+        let value_ty = storage_value_type(&metadata, &self.pallet, &self.item)?;
+
+        let next_cursor = page.next_cursor;
+        let items = page
+            .items
+            .into_iter()
+            .map(|(key, bytes)| {
+                decode_as(&bytes, value_ty, metadata.types(), &self.pallet, &self.item)
+                    .map(|value| (key, value))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Page::new(items, next_cursor))
+    }
+
+    /// One page of the entries under this query's keys, as raw keys and
+    /// values
+    pub async fn page_raw(
+        &self,
+        client: &StorageClient,
+        request: &PageRequest,
+    ) -> Result<Page<(Vec<u8>, Vec<u8>)>> {
         client
-            .query_storage(&self.pallet, &self.item, self.keys.clone())
+            .page_entries(&self.storage_key(), self.at, request)
             .await
     }
 }
 
-// Helper function for parsing block hash from hex string
-fn parse_block_hash(hash_hex: &str) -> Result<subxt::config::substrate::H256> {
-    use subxt::config::substrate::H256;
+/// Decode a storage value of type `type_id` into `T`
+fn decode_as<T: DecodeAsType>(
+    bytes: &[u8],
+    type_id: u32,
+    types: &scale_info::PortableRegistry,
+    pallet: &str,
+    item: &str,
+) -> Result<T> {
+    T::decode_as_type(&mut &bytes[..], type_id, types)
+        .map_err(|e| Error::Storage(format!("Failed to decode {}::{}: {}", pallet, item, e)))
+}
 
+// Helper function for parsing block hash from hex string
+fn parse_block_hash(hash_hex: &str) -> Result<[u8; 32]> {
     // Remove 0x prefix if present
     let hash_hex = hash_hex.strip_prefix("0x").unwrap_or(hash_hex);
 
@@ -394,7 +545,7 @@ fn parse_block_hash(hash_hex: &str) -> Result<subxt::config::substrate::H256> {
     hex::decode_to_slice(hash_hex, &mut bytes)
         .map_err(|e| Error::Storage(format!("Invalid block hash hex: {}", e)))?;
 
-    Ok(H256::from(bytes))
+    Ok(bytes)
 }
 
 // Helper functions for extracting values from subxt::dynamic::Value types
@@ -416,7 +567,7 @@ fn extract_u32<T>(value: &subxt::dynamic::Value<T>, path: &[&str]) -> Option<u32
     current.as_u128().and_then(|v| u32::try_from(v).ok())
 }
 
-fn extract_u128<T>(value: &subxt::dynamic::Value<T>, path: &[&str]) -> Option<u128> {
+pub(crate) fn extract_u128<T>(value: &subxt::dynamic::Value<T>, path: &[&str]) -> Option<u128> {
     let mut current = value;
     for &key in path {
         current = current.at(key)?;
@@ -521,4 +672,256 @@ mod tests {
         assert_eq!(query.item, "Account");
         assert_eq!(query.keys.len(), 1);
     }
+
+    #[test]
+    fn test_storage_query_typed_keys() {
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+        let plain = StorageQuery::new("Timestamp", "Now");
+        assert!(plain.keys.is_empty());
+        assert!(plain.at.is_none());
+
+        let map = StorageQuery::new("System", "Account")
+            .account(alice)
+            .unwrap();
+        assert_eq!(
+            map.keys,
+            vec![subxt::dynamic::Value::from_bytes(
+                alice.into_address().unwrap().account_id().unwrap()
+            )]
+        );
+        assert!(StorageQuery::new("System", "Account")
+            .account("not an address")
+            .is_err());
+
+        let double_map = StorageQuery::new("Assets", "Account")
+            .u32(1984)
+            .account_id([7u8; 32])
+            .at_hex(&format!("0x{}", "ab".repeat(32)))
+            .unwrap();
+        assert_eq!(
+            double_map.storage_key().keys,
+            vec![
+                subxt::dynamic::Value::u128(1984),
+                subxt::dynamic::Value::from_bytes([7u8; 32])
+            ]
+        );
+        assert_eq!(double_map.at, Some([0xab; 32]));
+
+        let by_hash = StorageQuery::new("Multisig", "Multisigs")
+            .account_id([1u8; 32])
+            .h256([2u8; 32])
+            .at([3u8; 32]);
+        assert_eq!(by_hash.keys.len(), 2);
+        assert_eq!(by_hash.at, Some([3u8; 32]));
+    }
+
+    /// In its own module so the derive does not pick up the crate's `Result`
+    mod user_types {
+        // Field order and integer widths need not match the runtime's
+        #[derive(Debug, PartialEq, super::DecodeAsType)]
+        #[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+        pub struct Decoded {
+            pub free: u128,
+            pub nonce: u64,
+        }
+    }
+
+    #[test]
+    fn test_decode_as_user_type() {
+        use parity_scale_codec::Encode;
+        use scale_info::{meta_type, PortableRegistry, Registry, TypeInfo};
+        use user_types::Decoded;
+
+        #[derive(Encode, TypeInfo)]
+        struct OnChain {
+            nonce: u32,
+            free: u128,
+            flags: Vec<u8>,
+        }
+
+        let mut registry = Registry::new();
+        let type_id = registry.register_type(&meta_type::<OnChain>()).id;
+        let types = PortableRegistry::from(registry);
+        let bytes = OnChain {
+            nonce: 7,
+            free: 1_000,
+            flags: vec![1],
+        }
+        .encode();
+
+        let decoded: Decoded = decode_as(&bytes, type_id, &types, "Test", "Value").unwrap();
+        assert_eq!(
+            decoded,
+            Decoded {
+                free: 1_000,
+                nonce: 7
+            }
+        );
+
+        let err = decode_as::<u8>(&bytes, type_id, &types, "Test", "Value").unwrap_err();
+        assert!(err.to_string().contains("Failed to decode Test::Value"));
+    }
+
+    struct MockChain {
+        account: Option<subxt::dynamic::Value>,
+        entries: Vec<(Vec<u8>, Vec<u8>)>,
+    }
+
+    #[async_trait::async_trait]
+    impl ChainClient for MockChain {
+        fn metadata(&self) -> subxt::Metadata {
+            unimplemented!("not used by account queries")
+        }
+
+        async fn finalized_block_hash(&self) -> Result<[u8; 32]> {
+            Ok([0u8; 32])
+        }
+
+        async fn storage(
+            &self,
+            key: &StorageKey,
+            _at: Option<[u8; 32]>,
+        ) -> Result<Option<crate::StorageValue>> {
+            assert_eq!(
+                (key.pallet.as_str(), key.item.as_str()),
+                ("System", "Account")
+            );
+            Ok(self.account.clone().map(|value| crate::StorageValue {
+                bytes: Vec::new(),
+                value,
+            }))
+        }
+
+        async fn storage_batch(
+            &self,
+            keys: &[StorageKey],
+            at: [u8; 32],
+        ) -> Result<Vec<Option<crate::StorageValue>>> {
+            let mut values = Vec::new();
+            for key in keys {
+                values.push(self.storage(key, Some(at)).await?);
+            }
+            Ok(values)
+        }
+
+        async fn storage_entries(
+            &self,
+            _pallet: &str,
+            _item: &str,
+        ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+            Ok(self.entries.clone())
+        }
+
+        fn constant(&self, pallet: &str, name: &str) -> Result<Vec<u8>> {
+            Err(Error::Storage(format!("No constant {}::{}", pallet, name)))
+        }
+
+        async fn runtime_call(&self, method: &str, _params: &[u8]) -> Result<Vec<u8>> {
+            Err(Error::Transaction(format!("No runtime API {}", method)))
+        }
+
+        async fn system_properties(&self) -> Result<serde_json::Map<String, serde_json::Value>> {
+            Ok(serde_json::Map::new())
+        }
+
+        async fn submit_extrinsic(&self, _extrinsic: &[u8]) -> Result<[u8; 32]> {
+            Ok([1u8; 32])
+        }
+
+        async fn subscribe_finalized_heads(
+            &self,
+        ) -> Result<subxt::ext::futures::stream::BoxStream<'static, Result<crate::FinalizedHead>>>
+        {
+            Ok(Box::pin(subxt::ext::futures::stream::empty()))
+        }
+
+        async fn block_hash(&self, _number: u64) -> Result<Option<[u8; 32]>> {
+            Ok(None)
+        }
+
+        async fn best_block_number(&self) -> Result<u64> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_account_info_from_chain_client() {
+        use subxt::dynamic::Value;
+
+        let account = Value::named_composite([
+            ("nonce", Value::u128(7)),
+            ("consumers", Value::u128(0)),
+            ("providers", Value::u128(1)),
+            ("sufficients", Value::u128(0)),
+            (
+                "data",
+                Value::named_composite([
+                    ("free", Value::u128(1_000)),
+                    ("reserved", Value::u128(200)),
+                    ("frozen", Value::u128(100)),
+                ]),
+            ),
+        ]);
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+        let client = StorageClient::with_chain_client(
+            Arc::new(MockChain {
+                account: Some(account),
+                entries: Vec::new(),
+            }),
+            Metrics::new(),
+        );
+        let info = client.get_account_info(alice).await.unwrap();
+        assert_eq!(info.nonce, 7);
+        assert_eq!(info.free, 1_000);
+        assert_eq!(info.transferable(), 900);
+
+        let missing = StorageClient::with_chain_client(
+            Arc::new(MockChain {
+                account: None,
+                entries: Vec::new(),
+            }),
+            Metrics::new(),
+        );
+        assert_eq!(missing.get_balance(alice).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_iter_storage_page() {
+        // Unordered, as a node may return them
+        let entries: Vec<(Vec<u8>, Vec<u8>)> =
+            (0u8..7).rev().map(|i| (vec![0xaa, i], vec![i])).collect();
+        let client = StorageClient::with_chain_client(
+            Arc::new(MockChain {
+                account: None,
+                entries,
+            }),
+            Metrics::new(),
+        );
+
+        let mut request = PageRequest::new(3);
+        let mut values = Vec::new();
+        loop {
+            let page = client
+                .iter_storage_page("Assets", "Asset", &request)
+                .await
+                .unwrap();
+            assert!(page.len() <= 3);
+            values.extend(page.items.iter().map(|(_, value)| value[0]));
+            match page.next_request(3) {
+                Some(next) => request = next,
+                None => break,
+            }
+        }
+        assert_eq!(values, (0..7).collect::<Vec<_>>());
+
+        // A full last page has no cursor
+        let page = client
+            .iter_storage_page("Assets", "Asset", &PageRequest::new(7))
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 7);
+        assert!(!page.has_more());
+    }
 }
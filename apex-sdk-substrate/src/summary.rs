@@ -0,0 +1,535 @@
+//! Human-readable transaction summaries
+//!
+//! [`summarize`] decodes a runtime call and describes it in one line, such as
+//! `Transfer 1.5 DOT to 15oF…Sp5; fee ≈ 0.0021 DOT; mortal 64 blocks`, for
+//! the user to read before signing. Transfers, staking, remarks and batches
+//! get a dedicated description; any other call is shown as `Pallet::call`.
+//!
+//! A [`TransactionSummary`] keeps the decoded parts and renders them from
+//! message templates keyed like `summary.transfer`. [`MESSAGES`] holds the
+//! English templates; [`TransactionSummary::render_with`] takes translations
+//! from another catalog, such as the CLI's.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::SubstrateAdapter;
+//!
+//! # async fn example(adapter: &SubstrateAdapter, call_data: &[u8]) -> apex_sdk_substrate::Result<()> {
+//! let summary = adapter.summarize(call_data).await?.with_fee(2_100_000);
+//! println!("{}", summary);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::assets::{value_bytes, value_number};
+use crate::deposits::{Deposit, DepositCalculator};
+use crate::transaction::{MortalEra, Mortality};
+use crate::{Error, Result};
+use apex_sdk_types::ChainProperties;
+use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+use subxt::dynamic::{At as _, Value};
+use subxt::ext::scale_value::{scale::decode_as_type, Composite, ValueDef};
+use subxt::Metadata;
+
+/// English templates for every summary key
+pub const MESSAGES: &[(&str, &str)] = &[
+    ("summary.transfer", "Transfer {amount} to {to}"),
+    ("summary.transfer_all", "Transfer the whole balance to {to}"),
+    (
+        "summary.asset_transfer",
+        "Transfer {amount} units of asset {asset} to {to}",
+    ),
+    ("summary.bond", "Bond {amount}"),
+    ("summary.bond_extra", "Bond {amount} more"),
+    ("summary.unbond", "Unbond {amount}"),
+    ("summary.nominate", "Nominate {count} validators"),
+    ("summary.remark", "Remark of {bytes} bytes"),
+    ("summary.batch", "Batch of {count} calls: {calls}"),
+    ("summary.call", "{pallet}::{call}"),
+    ("summary.deposit", "deposit {deposit} reserved"),
+    ("summary.fee", "fee ≈ {fee}"),
+    ("summary.mortal", "mortal {blocks} blocks"),
+    ("summary.immortal", "immortal"),
+];
+
+/// SS58 format used when the chain properties report none
+const DEFAULT_SS58_FORMAT: u16 = 42;
+
+/// Value filled into a summary template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SummaryArg {
+    /// Raw amount of the native token
+    Amount(u128),
+    /// Account ID, shown as a shortened SS58 address
+    Account([u8; 32]),
+    /// Plain number
+    Number(u128),
+    /// Text shown as it is
+    Text(String),
+    /// Descriptions of nested calls
+    Calls(Vec<SummaryPart>),
+}
+
+/// One clause of a summary: a template key and its arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummaryPart {
+    /// Template key, one of [`MESSAGES`]
+    pub key: &'static str,
+    /// Values for the template's placeholders
+    pub args: Vec<(&'static str, SummaryArg)>,
+}
+
+impl SummaryPart {
+    fn new(key: &'static str, args: Vec<(&'static str, SummaryArg)>) -> Self {
+        Self { key, args }
+    }
+
+    fn render(
+        &self,
+        properties: &ChainProperties,
+        lookup: &dyn Fn(&str) -> Option<String>,
+    ) -> String {
+        let template = lookup(self.key).unwrap_or_else(|| english(self.key).to_string());
+        self.args.iter().fold(template, |message, (name, arg)| {
+            let value = match arg {
+                SummaryArg::Amount(raw) => properties.format_amount(*raw),
+                SummaryArg::Account(account) => short_address(account, properties),
+                SummaryArg::Number(n) => n.to_string(),
+                SummaryArg::Text(text) => text.clone(),
+                SummaryArg::Calls(calls) => calls
+                    .iter()
+                    .map(|call| call.render(properties, lookup))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            message.replace(&format!("{{{}}}", name), &value)
+        })
+    }
+}
+
+/// Short structured description of a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionSummary {
+    /// Pallet of the call
+    pub pallet: String,
+    /// Name of the call
+    pub call: String,
+    /// What the call does
+    pub action: SummaryPart,
+    /// Deposit the call reserves in raw units, if any
+    pub deposit: Option<u128>,
+    /// Expected fee in raw units, if known
+    pub fee: Option<u128>,
+    /// Lifetime of the extrinsic, if known
+    pub mortality: Option<Mortality>,
+    /// Token and address format amounts and accounts are shown in
+    pub properties: ChainProperties,
+}
+
+impl TransactionSummary {
+    /// Show amounts and accounts with the token and SS58 format of a chain
+    pub fn with_properties(mut self, properties: ChainProperties) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    /// Mention the deposit the call reserves
+    ///
+    /// See [`DepositCalculator`](crate::DepositCalculator).
+    pub fn with_deposit(mut self, deposit: u128) -> Self {
+        self.deposit = Some(deposit);
+        self
+    }
+
+    /// Mention the expected fee
+    pub fn with_fee(mut self, fee: u128) -> Self {
+        self.fee = Some(fee);
+        self
+    }
+
+    /// Mention the lifetime of the extrinsic
+    ///
+    /// Mortal periods are shown rounded as they will be signed.
+    pub fn with_mortality(mut self, mortality: Mortality) -> Self {
+        self.mortality = Some(match mortality {
+            Mortality::Mortal { blocks } => Mortality::Mortal {
+                blocks: MortalEra::new(blocks, 0, [0u8; 32]).period,
+            },
+            Mortality::Immortal => Mortality::Immortal,
+        });
+        self
+    }
+
+    /// Clauses of the summary: the action, then the deposit, fee and mortality
+    pub fn parts(&self) -> Vec<SummaryPart> {
+        let mut parts = vec![self.action.clone()];
+        if let Some(deposit) = self.deposit {
+            parts.push(SummaryPart::new(
+                "summary.deposit",
+                vec![("deposit", SummaryArg::Amount(deposit))],
+            ));
+        }
+        if let Some(fee) = self.fee {
+            parts.push(SummaryPart::new(
+                "summary.fee",
+                vec![("fee", SummaryArg::Amount(fee))],
+            ));
+        }
+        match self.mortality {
+            Some(Mortality::Mortal { blocks }) => parts.push(SummaryPart::new(
+                "summary.mortal",
+                vec![("blocks", SummaryArg::Number(u128::from(blocks)))],
+            )),
+            Some(Mortality::Immortal) => parts.push(SummaryPart::new("summary.immortal", vec![])),
+            None => {}
+        }
+        parts
+    }
+
+    /// Recipient of the action as a full SS58 address, if it has one
+    pub fn recipient(&self) -> Option<String> {
+        self.action.args.iter().find_map(|(name, arg)| match arg {
+            SummaryArg::Account(account) if *name == "to" => {
+                Some(ss58_address(account, &self.properties))
+            }
+            _ => None,
+        })
+    }
+
+    /// Amount of the native token the action moves or bonds, in raw units
+    pub fn amount(&self) -> Option<u128> {
+        self.action.args.iter().find_map(|(name, arg)| match arg {
+            SummaryArg::Amount(amount) if *name == "amount" => Some(*amount),
+            _ => None,
+        })
+    }
+
+    /// Render the summary with templates from `lookup`
+    ///
+    /// Keys `lookup` returns `None` for use the English template.
+    pub fn render_with(&self, lookup: impl Fn(&str) -> Option<String>) -> String {
+        self.parts()
+            .iter()
+            .map(|part| part.render(&self.properties, &lookup))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+impl std::fmt::Display for TransactionSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render_with(|_| None))
+    }
+}
+
+/// Describe SCALE-encoded call data
+///
+/// Deposits the call reserves are mentioned when the runtime has the
+/// constants to compute them. Trailing bytes after the call, such as the
+/// signed extensions of a signer payload, are ignored. Amounts and accounts use the default
+/// [`ChainProperties`] until [`TransactionSummary::with_properties`] is
+/// called.
+pub fn summarize(call_bytes: &[u8], metadata: &Metadata) -> Result<TransactionSummary> {
+    let call_ty = metadata.outer_enums().call_enum_ty();
+    let call = decode_as_type(&mut &call_bytes[..], call_ty, metadata.types())
+        .map_err(|e| Error::Encoding(format!("Cannot decode call to summarize: {}", e)))?;
+    let summary = summarize_value(&call)?;
+    let deposit = DepositCalculator::new(metadata.clone())
+        .for_call(call_bytes)
+        .map(|deposits| deposits.iter().map(Deposit::total).sum::<u128>());
+    Ok(match deposit {
+        Ok(deposit) if deposit > 0 => summary.with_deposit(deposit),
+        _ => summary,
+    })
+}
+
+/// Describe a decoded runtime call
+fn summarize_value<T>(call: &Value<T>) -> Result<TransactionSummary> {
+    let (pallet, call, action) =
+        describe(call).ok_or_else(|| Error::Encoding("Call is not a runtime call".to_string()))?;
+    Ok(TransactionSummary {
+        pallet,
+        call,
+        action,
+        deposit: None,
+        fee: None,
+        mortality: None,
+        properties: ChainProperties::default(),
+    })
+}
+
+/// Pallet, call name and description of a runtime call value
+fn describe<T>(call: &Value<T>) -> Option<(String, String, SummaryPart)> {
+    let ValueDef::Variant(pallet) = &call.value else {
+        return None;
+    };
+    let ValueDef::Variant(inner) = &pallet.values.values().next()?.value else {
+        return None;
+    };
+    let args = &inner.values;
+    let arg = |name: &str, position: usize| match args {
+        Composite::Named(_) => args.at(name),
+        Composite::Unnamed(values) => values.get(position),
+    };
+    let amount = |name: &str, position: usize| arg(name, position).and_then(value_number);
+
+    let action = match (pallet.name.as_str(), inner.name.as_str()) {
+        ("Balances", "transfer_keep_alive" | "transfer_allow_death" | "transfer") => {
+            match (arg("dest", 0).and_then(account), amount("value", 1)) {
+                (Some(to), Some(value)) => Some(SummaryPart::new(
+                    "summary.transfer",
+                    vec![
+                        ("amount", SummaryArg::Amount(value)),
+                        ("to", SummaryArg::Account(to)),
+                    ],
+                )),
+                _ => None,
+            }
+        }
+        ("Balances", "transfer_all") => arg("dest", 0).and_then(account).map(|to| {
+            SummaryPart::new(
+                "summary.transfer_all",
+                vec![("to", SummaryArg::Account(to))],
+            )
+        }),
+        ("Assets", "transfer" | "transfer_keep_alive") => match (
+            amount("id", 0),
+            arg("target", 1).and_then(account),
+            amount("amount", 2),
+        ) {
+            (Some(asset), Some(to), Some(value)) => Some(SummaryPart::new(
+                "summary.asset_transfer",
+                vec![
+                    ("amount", SummaryArg::Number(value)),
+                    ("asset", SummaryArg::Number(asset)),
+                    ("to", SummaryArg::Account(to)),
+                ],
+            )),
+            _ => None,
+        },
+        ("Staking", "bond") => amount("value", 0).map(|value| {
+            SummaryPart::new("summary.bond", vec![("amount", SummaryArg::Amount(value))])
+        }),
+        ("Staking", "bond_extra") => amount("max_additional", 0).map(|value| {
+            SummaryPart::new(
+                "summary.bond_extra",
+                vec![("amount", SummaryArg::Amount(value))],
+            )
+        }),
+        ("Staking", "unbond") => amount("value", 0).map(|value| {
+            SummaryPart::new(
+                "summary.unbond",
+                vec![("amount", SummaryArg::Amount(value))],
+            )
+        }),
+        ("Staking", "nominate") => arg("targets", 0).and_then(sequence_len).map(|count| {
+            SummaryPart::new(
+                "summary.nominate",
+                vec![("count", SummaryArg::Number(count))],
+            )
+        }),
+        ("System", "remark" | "remark_with_event") => {
+            arg("remark", 0).and_then(sequence_len).map(|bytes| {
+                SummaryPart::new("summary.remark", vec![("bytes", SummaryArg::Number(bytes))])
+            })
+        }
+        ("Utility", "batch" | "batch_all" | "force_batch") => match arg("calls", 0) {
+            Some(Value {
+                value: ValueDef::Composite(calls),
+                ..
+            }) => calls
+                .values()
+                .map(|call| describe(call).map(|(_, _, part)| part))
+                .collect::<Option<Vec<_>>>()
+                .map(|calls| {
+                    SummaryPart::new(
+                        "summary.batch",
+                        vec![
+                            ("count", SummaryArg::Number(calls.len() as u128)),
+                            ("calls", SummaryArg::Calls(calls)),
+                        ],
+                    )
+                }),
+            _ => None,
+        },
+        _ => None,
+    }
+    .unwrap_or_else(|| {
+        SummaryPart::new(
+            "summary.call",
+            vec![
+                ("pallet", SummaryArg::Text(pallet.name.clone())),
+                ("call", SummaryArg::Text(inner.name.clone())),
+            ],
+        )
+    });
+
+    Some((pallet.name.clone(), inner.name.clone(), action))
+}
+
+/// Account ID of an `AccountId32` or a `MultiAddress::Id`
+fn account<T>(value: &Value<T>) -> Option<[u8; 32]> {
+    let value = match &value.value {
+        ValueDef::Variant(variant) if variant.name == "Id" => variant.values.values().next()?,
+        _ => value,
+    };
+    value_bytes(value).and_then(|bytes| bytes.try_into().ok())
+}
+
+/// Number of items in a sequence value
+fn sequence_len<T>(value: &Value<T>) -> Option<u128> {
+    match &value.value {
+        ValueDef::Composite(composite) => Some(composite.len() as u128),
+        _ => None,
+    }
+}
+
+/// English template for `key`, or the key itself
+fn english(key: &str) -> &str {
+    MESSAGES
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map_or(key, |(_, message)| message)
+}
+
+/// SS58 address shortened to its first four and last three characters
+fn ss58_address(account: &[u8; 32], properties: &ChainProperties) -> String {
+    let format = properties.ss58_format.unwrap_or(DEFAULT_SS58_FORMAT);
+    AccountId32::from(*account).to_ss58check_with_version(Ss58AddressFormat::custom(format))
+}
+
+fn short_address(account: &[u8; 32], properties: &ChainProperties) -> String {
+    let address = ss58_address(account, properties);
+    let chars: Vec<char> = address.chars().collect();
+    if chars.len() <= 8 {
+        return address;
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 3..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use subxt::ext::scale_value::Variant;
+
+    fn variant(name: &str, values: Composite<()>) -> Value {
+        Value {
+            value: ValueDef::Variant(Variant {
+                name: name.to_string(),
+                values,
+            }),
+            context: (),
+        }
+    }
+
+    fn call(pallet: &str, name: &str, args: Vec<(&str, Value)>) -> Value {
+        let args = args.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        let inner = variant(name, Composite::Named(args));
+        variant(pallet, Composite::Unnamed(vec![inner]))
+    }
+
+    fn transfer(to: [u8; 32], value: u128) -> Value {
+        call(
+            "Balances",
+            "transfer_keep_alive",
+            vec![
+                (
+                    "dest",
+                    Value::unnamed_variant("Id", [Value::from_bytes(to)]),
+                ),
+                ("value", Value::u128(value)),
+            ],
+        )
+    }
+
+    fn polkadot() -> ChainProperties {
+        ChainProperties {
+            token_symbol: "DOT".to_string(),
+            token_decimals: 10,
+            ss58_format: Some(0),
+        }
+    }
+
+    #[test]
+    fn test_transfer_summary() {
+        let to = [7u8; 32];
+        let summary = summarize_value(&transfer(to, 15_000_000_000))
+            .unwrap()
+            .with_properties(polkadot())
+            .with_fee(21_000_000)
+            .with_mortality(Mortality::Mortal { blocks: 50 });
+
+        assert_eq!(summary.pallet, "Balances");
+        assert_eq!(summary.call, "transfer_keep_alive");
+        let address = AccountId32::from(to).to_ss58check_with_version(Ss58AddressFormat::custom(0));
+        assert_eq!(summary.recipient(), Some(address.clone()));
+        assert_eq!(summary.amount(), Some(15_000_000_000));
+        let short = format!("{}…{}", &address[..4], &address[address.len() - 3..]);
+        assert_eq!(
+            summary.to_string(),
+            format!(
+                "Transfer 1.5 DOT to {}; fee ≈ 0.0021 DOT; mortal 64 blocks",
+                short
+            )
+        );
+
+        let immortal = summary.with_mortality(Mortality::Immortal);
+        assert!(immortal.to_string().ends_with("; immortal"));
+
+        let deposit = immortal.with_deposit(2_000_000_000);
+        assert!(deposit
+            .to_string()
+            .contains("; deposit 0.2 DOT reserved; fee ≈ 0.0021 DOT;"));
+    }
+
+    #[test]
+    fn test_batch_and_unknown_calls() {
+        let batch = call(
+            "Utility",
+            "batch_all",
+            vec![(
+                "calls",
+                Value::unnamed_composite([
+                    transfer([1u8; 32], 10),
+                    call(
+                        "System",
+                        "set_code",
+                        vec![("code", Value::from_bytes([0u8; 4]))],
+                    ),
+                ]),
+            )],
+        );
+        let summary = summarize_value(&batch).unwrap();
+        let rendered = summary.to_string();
+        assert!(rendered.starts_with("Batch of 2 calls: Transfer 0.00000000001 UNIT to "));
+        assert!(rendered.ends_with(", System::set_code"));
+        // Nested calls are not lifted to the batch
+        assert_eq!(summary.recipient(), None);
+        assert_eq!(summary.amount(), None);
+
+        let remark = call(
+            "System",
+            "remark",
+            vec![("remark", Value::from_bytes([1u8; 5]))],
+        );
+        assert_eq!(
+            summarize_value(&remark).unwrap().to_string(),
+            "Remark of 5 bytes"
+        );
+        assert!(summarize_value(&Value::u128(1)).is_err());
+    }
+
+    #[test]
+    fn test_render_with_translations() {
+        let summary = summarize_value(&transfer([2u8; 32], 1_000_000_000_000))
+            .unwrap()
+            .with_fee(1);
+        let rendered = summary.render_with(|key| {
+            (key == "summary.transfer").then(|| "Enviar {amount} para {to}".to_string())
+        });
+        assert!(rendered.starts_with("Enviar 1 UNIT para 5"));
+        assert!(rendered.ends_with("; fee ≈ 0.000000000001 UNIT"));
+    }
+}
@@ -6,11 +6,24 @@
 //! - Transaction signing
 //! - Retry logic with exponential backoff
 //! - Transaction confirmation tracking
-
-use crate::{Error, Metrics, Result, Sr25519Signer, Wallet};
-use apex_sdk_core::{FeeEstimator, SdkError};
+//! - Mortal eras anchored at the latest finalized block
+
+use crate::deposits::{Deposit, DepositCalculator};
+use crate::fee_estimator::{dispatch_weight, DynamicFeeEstimator};
+use crate::summary::{summarize, TransactionSummary};
+use crate::{Error, Metrics, Result, Sr25519Signer, Wallet, Weight};
+use apex_sdk_core::{BroadcastHook, FeeEstimator, SdkError, ThrottleHint};
+use apex_sdk_types::{IntoAddress, TxHash};
 use async_trait::async_trait;
 use std::time::Duration;
+use subxt::blocks::ExtrinsicEvents;
+use subxt::config::polkadot::PolkadotExtrinsicParamsBuilder;
+use subxt::ext::scale_value::Composite;
+use subxt::tx::{
+    SubmittableTransaction, TransactionInvalid, TransactionUnknown, TxInBlock, TxProgress,
+    TxStatus, ValidationResult,
+};
+use subxt::utils::{Era, H256};
 use subxt::{OnlineClient, PolkadotConfig};
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
@@ -31,25 +44,483 @@ pub enum BatchMode {
 }
 
 /// Represents a single call in a batch transaction
+///
+/// A call is identified either by raw indices ([`BatchCall::new`]) or by
+/// pallet and call name ([`BatchCall::named`]).
 #[derive(Debug, Clone)]
 pub struct BatchCall {
-    /// Pallet index in the runtime
-    pub pallet_index: u8,
-    /// Call index within the pallet
-    pub call_index: u8,
-    /// Encoded call arguments
-    pub args_encoded: Vec<u8>,
+    id: CallId,
+    args_encoded: Vec<u8>,
+}
+
+/// How a [`BatchCall`] identifies its pallet and call
+#[derive(Debug, Clone)]
+enum CallId {
+    /// Raw indices in the runtime
+    Indices { pallet_index: u8, call_index: u8 },
+    /// Names resolved against runtime metadata at submission time
+    Names { pallet: String, call: String },
 }
 
 impl BatchCall {
-    /// Create a new batch call
+    /// Create a new batch call from raw pallet and call indices
+    ///
+    /// Raw indices are runtime-specific and may change after a runtime upgrade.
+    /// Prefer [`BatchCall::named`] unless the indices are known to be stable.
     pub fn new(pallet_index: u8, call_index: u8, args_encoded: Vec<u8>) -> Self {
         Self {
-            pallet_index,
-            call_index,
+            id: CallId::Indices {
+                pallet_index,
+                call_index,
+            },
+            args_encoded,
+        }
+    }
+
+    /// Create a new batch call identified by pallet and call name
+    ///
+    /// The indices are resolved against the current runtime metadata when the
+    /// batch is submitted, so the call keeps working across runtime upgrades that
+    /// reorder pallets or calls.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use apex_sdk_substrate::BatchCall;
+    ///
+    /// let call = BatchCall::named("Balances", "transfer_keep_alive", vec![]);
+    /// assert!(call.is_named());
+    /// assert_eq!(call.indices(), None);
+    /// ```
+    pub fn named(
+        pallet: impl Into<String>,
+        call: impl Into<String>,
+        args_encoded: Vec<u8>,
+    ) -> Self {
+        Self {
+            id: CallId::Names {
+                pallet: pallet.into(),
+                call: call.into(),
+            },
             args_encoded,
         }
     }
+
+    /// Check whether this call is resolved by name at submission time
+    pub fn is_named(&self) -> bool {
+        matches!(self.id, CallId::Names { .. })
+    }
+
+    /// Pallet and call indices of a call created with [`BatchCall::new`]
+    ///
+    /// Named calls have no indices until resolved with
+    /// [`resolve_indices`](Self::resolve_indices).
+    pub fn indices(&self) -> Option<(u8, u8)> {
+        match self.id {
+            CallId::Indices {
+                pallet_index,
+                call_index,
+            } => Some((pallet_index, call_index)),
+            CallId::Names { .. } => None,
+        }
+    }
+
+    /// Pallet and call names of a call created with [`BatchCall::named`]
+    pub fn call_name(&self) -> Option<(&str, &str)> {
+        match &self.id {
+            CallId::Names { pallet, call } => Some((pallet.as_str(), call.as_str())),
+            CallId::Indices { .. } => None,
+        }
+    }
+
+    /// Get the encoded call arguments
+    pub fn args_encoded(&self) -> &[u8] {
+        &self.args_encoded
+    }
+
+    /// Resolve the pallet and call indices against the given runtime metadata
+    ///
+    /// Calls created with [`BatchCall::new`] return their raw indices unchanged.
+    /// Named calls return an error if the pallet or call no longer exists.
+    pub fn resolve_indices(&self, metadata: &subxt::Metadata) -> Result<(u8, u8)> {
+        let (pallet_name, call_name) = match &self.id {
+            CallId::Indices {
+                pallet_index,
+                call_index,
+            } => return Ok((*pallet_index, *call_index)),
+            CallId::Names { pallet, call } => (pallet, call),
+        };
+
+        let pallet = metadata.pallet_by_name(pallet_name).ok_or_else(|| {
+            Error::Metadata(format!(
+                "Pallet {} not found in runtime metadata",
+                pallet_name
+            ))
+        })?;
+
+        let call = pallet.call_variant_by_name(call_name).ok_or_else(|| {
+            Error::Metadata(format!(
+                "Call {}::{} not found in runtime metadata",
+                pallet_name, call_name
+            ))
+        })?;
+
+        Ok((pallet.index(), call.index))
+    }
+
+    /// Encode the call as `pallet_index ++ call_index ++ args`, resolving named
+    /// calls against the given runtime metadata
+    pub fn encode_call(&self, metadata: &subxt::Metadata) -> Result<Vec<u8>> {
+        let (pallet_index, call_index) = self.resolve_indices(metadata)?;
+
+        let mut call_bytes = Vec::with_capacity(2 + self.args_encoded.len());
+        call_bytes.push(pallet_index);
+        call_bytes.push(call_index);
+        call_bytes.extend_from_slice(&self.args_encoded);
+
+        Ok(call_bytes)
+    }
+}
+
+/// A pre-encoded runtime call (`pallet_index ++ call_index ++ args`)
+///
+/// The pallet and call indices are checked against the runtime metadata when the
+/// extrinsic is built, so call data encoded for a different runtime fails before
+/// anything is signed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawCall {
+    call_data: Vec<u8>,
+}
+
+impl RawCall {
+    /// Create a raw call from SCALE-encoded call data
+    pub fn new(call_data: Vec<u8>) -> Result<Self> {
+        if call_data.len() < 2 {
+            return Err(Error::Encoding(
+                "Call data must contain at least a pallet and call index".to_string(),
+            ));
+        }
+        Ok(Self { call_data })
+    }
+
+    /// Get the encoded call data
+    pub fn call_data(&self) -> &[u8] {
+        &self.call_data
+    }
+
+    /// Get the pallet index
+    pub fn pallet_index(&self) -> u8 {
+        self.call_data[0]
+    }
+
+    /// Get the call index within the pallet
+    pub fn call_index(&self) -> u8 {
+        self.call_data[1]
+    }
+}
+
+impl subxt::tx::Payload for RawCall {
+    fn encode_call_data_to(
+        &self,
+        metadata: &subxt::Metadata,
+        out: &mut Vec<u8>,
+    ) -> std::result::Result<(), subxt::ext::subxt_core::Error> {
+        use subxt::ext::subxt_core::error::MetadataError;
+
+        let pallet = metadata
+            .pallet_by_index(self.pallet_index())
+            .ok_or(MetadataError::PalletIndexNotFound(self.pallet_index()))?;
+        pallet
+            .call_variant_by_index(self.call_index())
+            .ok_or(MetadataError::VariantIndexNotFound(self.call_index()))?;
+
+        out.extend_from_slice(&self.call_data);
+        Ok(())
+    }
+}
+
+/// Dispatch status of a single call within an executed batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchItemStatus {
+    /// The call was dispatched successfully (`Utility::ItemCompleted`)
+    Completed,
+    /// The call was dispatched and failed with the given error
+    /// (`Utility::ItemFailed` or `Utility::BatchInterrupted`)
+    Failed(String),
+    /// The call was not dispatched because the batch was interrupted earlier
+    NotExecuted,
+}
+
+/// Result of a single call within an executed batch
+#[derive(Debug, Clone)]
+pub struct BatchItemResult {
+    /// Index of the call in the submitted batch
+    pub index: u32,
+    /// Dispatch status of the call
+    pub status: BatchItemStatus,
+    /// Pre-dispatch weight of the call reported by `TransactionPaymentCallApi`
+    pub weight: Option<Weight>,
+}
+
+impl BatchItemResult {
+    /// Check if the call was dispatched successfully
+    pub fn is_success(&self) -> bool {
+        self.status == BatchItemStatus::Completed
+    }
+}
+
+/// Result of an executed batch with per-item dispatch outcomes
+#[derive(Debug, Clone)]
+pub struct BatchResult {
+    /// Hash of the batch extrinsic
+    pub tx_hash: String,
+    /// Hash of the block the batch was finalized in
+    pub block_hash: String,
+    /// Batch mode the calls were executed with
+    pub mode: BatchMode,
+    /// Per-call results, in submission order
+    pub items: Vec<BatchItemResult>,
+    /// Index of the call that interrupted an optimistic batch, if any
+    pub interrupted_at: Option<u32>,
+    /// Weight actually consumed by the batch extrinsic (`System::ExtrinsicSuccess`)
+    pub actual_weight: Option<Weight>,
+}
+
+impl BatchResult {
+    /// Check if every call in the batch was dispatched successfully
+    pub fn is_complete_success(&self) -> bool {
+        self.items.iter().all(BatchItemResult::is_success)
+    }
+
+    /// Indices of calls that were dispatched and failed
+    pub fn failed_indices(&self) -> Vec<u32> {
+        self.items
+            .iter()
+            .filter(|item| matches!(item.status, BatchItemStatus::Failed(_)))
+            .map(|item| item.index)
+            .collect()
+    }
+
+    /// Indices of calls that need to be resubmitted: failed calls plus calls that
+    /// were never dispatched because the batch was interrupted
+    pub fn retry_indices(&self) -> Vec<u32> {
+        self.items
+            .iter()
+            .filter(|item| !item.is_success())
+            .map(|item| item.index)
+            .collect()
+    }
+
+    /// Sum of the pre-dispatch weights of all calls, if every weight is known
+    pub fn estimated_weight(&self) -> Option<Weight> {
+        self.items
+            .iter()
+            .try_fold(Weight::default(), |total, item| {
+                item.weight.map(|w| {
+                    Weight::new(
+                        total.ref_time.saturating_add(w.ref_time),
+                        total.proof_size.saturating_add(w.proof_size),
+                    )
+                })
+            })
+    }
+}
+
+/// Event emitted by a submitted extrinsic, with its fields decoded
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceiptEvent {
+    /// Pallet that emitted the event
+    pub pallet: String,
+    /// Event variant name
+    pub variant: String,
+    /// Decoded event fields
+    pub fields: Composite<()>,
+}
+
+impl ReceiptEvent {
+    /// Check if this is `pallet::variant`
+    pub fn is(&self, pallet: &str, variant: &str) -> bool {
+        self.pallet == pallet && self.variant == variant
+    }
+}
+
+/// Outcome of a finalized extrinsic
+#[derive(Debug, Clone)]
+pub struct TransactionReceipt {
+    /// Hash of the extrinsic
+    pub tx_hash: String,
+    /// Hash of the block the extrinsic was finalized in
+    pub block_hash: String,
+    /// Number of that block
+    pub block_number: u64,
+    /// Index of the extrinsic within the block
+    pub extrinsic_index: u32,
+    /// Events emitted by the extrinsic, in emission order
+    pub events: Vec<ReceiptEvent>,
+    /// Fee actually charged, including tip (`TransactionPayment::TransactionFeePaid`)
+    ///
+    /// This is the net fee, after any weight refund.
+    pub actual_fee: Option<u128>,
+    /// Tip included in [`TransactionReceipt::actual_fee`]
+    pub tip: Option<u128>,
+    /// Fee estimated by `TransactionPaymentApi` before submission, excluding tip
+    pub estimated_fee: Option<u128>,
+    /// Fee withdrawn from the payer before dispatch, including tip
+    /// (`Balances::Withdraw`)
+    pub charged_fee: Option<u128>,
+    /// Part of [`charged_fee`](Self::charged_fee) returned after dispatch
+    /// for weight the call did not use
+    pub refunded_fee: Option<u128>,
+    /// Part of the fee deposited into the treasury (`Treasury::Deposit`)
+    pub treasury_deposit: Option<u128>,
+    /// Weight consumed by the extrinsic (`System::ExtrinsicSuccess`)
+    pub weight: Option<Weight>,
+}
+
+impl TransactionReceipt {
+    fn new(
+        tx_hash: String,
+        block_hash: String,
+        block_number: u64,
+        extrinsic_index: u32,
+        events: Vec<ReceiptEvent>,
+    ) -> Self {
+        use subxt::dynamic::At as _;
+
+        let fee_paid = events
+            .iter()
+            .find(|e| e.is("TransactionPayment", "TransactionFeePaid"));
+        let actual_fee = fee_paid.and_then(|e| e.fields.at("actual_fee")?.as_u128());
+        let tip = fee_paid.and_then(|e| e.fields.at("tip")?.as_u128());
+        let weight = events
+            .iter()
+            .find(|e| e.is("System", "ExtrinsicSuccess"))
+            .and_then(|e| dispatch_weight(&e.fields));
+
+        // The fee is withdrawn from the payer up front, and the unused
+        // weight's share is refunded before `TransactionFeePaid`
+        let payer = fee_paid.and_then(|e| e.fields.at("who"));
+        let charged_fee = payer
+            .and_then(|payer| {
+                events
+                    .iter()
+                    .find(|e| e.is("Balances", "Withdraw") && e.fields.at("who") == Some(payer))
+            })
+            .and_then(|e| e.fields.at("amount")?.as_u128())
+            .filter(|charged| actual_fee.is_some_and(|actual| *charged >= actual));
+        let refunded_fee = charged_fee.zip(actual_fee).map(|(c, a)| c - a);
+        let treasury_deposit = events
+            .iter()
+            .filter(|e| e.is("Treasury", "Deposit"))
+            .filter_map(|e| e.fields.at("value")?.as_u128())
+            .reduce(u128::saturating_add);
+
+        Self {
+            tx_hash,
+            block_hash,
+            block_number,
+            extrinsic_index,
+            events,
+            actual_fee,
+            tip,
+            estimated_fee: None,
+            charged_fee,
+            refunded_fee,
+            treasury_deposit,
+            weight,
+        }
+    }
+
+    /// First event matching `pallet::variant`
+    pub fn find_event(&self, pallet: &str, variant: &str) -> Option<&ReceiptEvent> {
+        self.events.iter().find(|e| e.is(pallet, variant))
+    }
+}
+
+/// Outcome of [`TransactionExecutor::simulate`]
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    /// Why the transaction pool would reject the extrinsic, if it would
+    pub invalid: Option<String>,
+    /// Dispatch error reported by the dry run, e.g. `Balances::InsufficientBalance`
+    pub dispatch_error: Option<String>,
+    /// Events the call would emit
+    pub events: Vec<ReceiptEvent>,
+    /// Fee estimated by `TransactionPaymentApi`, excluding tip
+    pub estimated_fee: Option<u128>,
+    /// Whether the call was dry-run; `false` if the runtime has no `DryRunApi`
+    pub dry_run: bool,
+    /// Deposits the call would reserve, see [`DepositCalculator`]
+    pub deposits: Vec<Deposit>,
+}
+
+impl SimulationResult {
+    /// Check if the extrinsic would be accepted and dispatched successfully
+    pub fn is_success(&self) -> bool {
+        self.invalid.is_none() && self.dispatch_error.is_none()
+    }
+
+    /// Reason the extrinsic would fail, if any
+    pub fn error(&self) -> Option<&str> {
+        self.invalid.as_deref().or(self.dispatch_error.as_deref())
+    }
+
+    /// Total of the deposits the call would reserve
+    pub fn total_deposit(&self) -> u128 {
+        self.deposits.iter().fold(0u128, |total, deposit| {
+            total.saturating_add(deposit.total())
+        })
+    }
+}
+
+/// Utility pallet events relevant to batch outcomes
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum UtilityEvent {
+    ItemCompleted,
+    ItemFailed(String),
+    BatchInterrupted { index: u32, error: String },
+}
+
+/// Map the Utility events of a batch extrinsic to per-call statuses
+///
+/// `Utility::batch` emits `ItemCompleted` for each call up to the first failure,
+/// followed by `BatchInterrupted`. `Utility::force_batch` emits `ItemCompleted` or
+/// `ItemFailed` for every call. `Utility::batch_all` only succeeds if every call
+/// completed.
+fn batch_item_statuses(
+    call_count: usize,
+    events: &[UtilityEvent],
+) -> (Vec<BatchItemStatus>, Option<u32>) {
+    let mut statuses = Vec::with_capacity(call_count);
+    let mut interrupted_at = None;
+
+    for event in events {
+        match event {
+            UtilityEvent::ItemCompleted => statuses.push(BatchItemStatus::Completed),
+            UtilityEvent::ItemFailed(error) => {
+                statuses.push(BatchItemStatus::Failed(error.clone()))
+            }
+            UtilityEvent::BatchInterrupted { index, error } => {
+                statuses.truncate(*index as usize);
+                statuses.resize(*index as usize, BatchItemStatus::Completed);
+                statuses.push(BatchItemStatus::Failed(error.clone()));
+                interrupted_at = Some(*index);
+                break;
+            }
+        }
+    }
+
+    statuses.truncate(call_count);
+    let fill = if interrupted_at.is_some() {
+        BatchItemStatus::NotExecuted
+    } else {
+        // No per-item events means the runtime did not report them (e.g. an old
+        // Utility pallet); a successful extrinsic implies every call completed
+        BatchItemStatus::Completed
+    };
+    statuses.resize(call_count, fill);
+
+    (statuses, interrupted_at)
 }
 
 /// Fee estimation configuration
@@ -96,6 +567,355 @@ impl FeeConfig {
         self.tip = tip;
         self
     }
+
+    /// Final fee of a transaction quoted `partial_fee`, checked against
+    /// [`max_fee`](Self::max_fee)
+    ///
+    /// The final fee is the partial fee scaled by the safety and congestion
+    /// multipliers, plus `tip`. Fails with [`Error::FeeCapExceeded`] when it
+    /// is above the cap.
+    pub fn check_max_fee(
+        &self,
+        partial_fee: u128,
+        congestion_multiplier: f64,
+        tip: u128,
+    ) -> Result<u128> {
+        let scaled = (partial_fee as f64 * self.multiplier * congestion_multiplier).ceil() as u128;
+        let total_fee = scaled.saturating_add(tip);
+
+        match self.max_fee {
+            Some(max_fee) if total_fee > max_fee => {
+                Err(Error::FeeCapExceeded(Box::new(FeeCapExceeded {
+                    partial_fee,
+                    multiplier: self.multiplier,
+                    congestion_multiplier,
+                    tip,
+                    total_fee,
+                    max_fee,
+                })))
+            }
+            _ => Ok(total_fee),
+        }
+    }
+}
+
+/// Fee breakdown of a transaction refused by [`FeeConfig::max_fee`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeCapExceeded {
+    /// Fee quoted by `TransactionPaymentApi`, excluding tip
+    pub partial_fee: u128,
+    /// Safety multiplier from [`FeeConfig::multiplier`]
+    pub multiplier: f64,
+    /// Multiplier for the last observed network congestion
+    pub congestion_multiplier: f64,
+    /// Tip the transaction was signed with
+    pub tip: u128,
+    /// Final estimated fee: scaled partial fee plus tip
+    pub total_fee: u128,
+    /// Configured cap
+    pub max_fee: u128,
+}
+
+impl std::fmt::Display for FeeCapExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "estimated fee {} exceeds maximum {} (partial fee {} x{} safety x{} congestion + tip {})",
+            self.total_fee,
+            self.max_fee,
+            self.partial_fee,
+            self.multiplier,
+            self.congestion_multiplier,
+            self.tip
+        )
+    }
+}
+
+/// Fees refused by a [`SpendingLimiter`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpendingLimitExceeded {
+    /// Account the fee would be charged to
+    pub account: String,
+    /// Estimated fee of the refused transaction, including tip
+    pub fee: u128,
+    /// Fees already spent or reserved in the window
+    pub spent: u128,
+    /// Budget for the window
+    pub limit: u128,
+    /// Length of the window; `None` for a session budget
+    pub window: Option<Duration>,
+}
+
+impl std::fmt::Display for SpendingLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "fee {} for {} would exceed budget {} ({} already spent",
+            self.fee, self.account, self.limit, self.spent
+        )?;
+        match self.window {
+            Some(window) => write!(f, " in the last {:?})", window),
+            None => write!(f, " this session)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Spend {
+    id: u64,
+    at: std::time::Instant,
+    fee: u128,
+}
+
+/// Spends of one account
+///
+/// A windowed limiter keeps every spend until it leaves the window. A session
+/// limiter folds spends into `settled` once their reservation is settled or
+/// dropped, keeping only those that can still be replaced or released.
+#[derive(Debug, Default)]
+struct AccountSpends {
+    settled: u128,
+    spends: std::collections::VecDeque<Spend>,
+}
+
+impl AccountSpends {
+    fn total(&self) -> u128 {
+        self.spends
+            .iter()
+            .fold(self.settled, |total, spend| total.saturating_add(spend.fee))
+    }
+
+    fn find_mut(&mut self, id: u64) -> Option<&mut Spend> {
+        self.spends.iter_mut().find(|spend| spend.id == id)
+    }
+
+    fn remove(&mut self, id: u64) -> Option<Spend> {
+        let at = self.spends.iter().position(|spend| spend.id == id)?;
+        self.spends.remove(at)
+    }
+}
+
+#[derive(Debug, Default)]
+struct SpendLedger {
+    next_id: u64,
+    accounts: std::collections::HashMap<String, AccountSpends>,
+}
+
+/// Fee budget per account, over a sliding window or a whole session
+///
+/// The executor reserves a transaction's estimated fee, including tip, before
+/// broadcasting it, and releases the reservation if the broadcast fails.
+/// Estimates include the safety margin, so the budget is enforced against an
+/// upper bound of what is actually charged. Clones share the same ledger, so
+/// one limiter can guard several executors.
+#[derive(Debug, Clone)]
+pub struct SpendingLimiter {
+    limit: u128,
+    window: Option<Duration>,
+    ledger: std::sync::Arc<parking_lot::Mutex<SpendLedger>>,
+}
+
+impl SpendingLimiter {
+    /// Allow at most `limit` in fees per account within any `window`
+    pub fn new(limit: u128, window: Duration) -> Self {
+        Self {
+            limit,
+            window: Some(window),
+            ledger: Default::default(),
+        }
+    }
+
+    /// Allow at most `limit` in fees per account for the limiter's lifetime
+    pub fn session(limit: u128) -> Self {
+        Self {
+            limit,
+            window: None,
+            ledger: Default::default(),
+        }
+    }
+
+    /// Budget per account
+    pub fn limit(&self) -> u128 {
+        self.limit
+    }
+
+    /// Fees spent or reserved by `account` in the current window
+    pub fn spent(&self, account: &str) -> u128 {
+        let mut ledger = self.ledger.lock();
+        self.expire(&mut ledger, account)
+    }
+
+    /// Fees `account` can still spend in the current window
+    pub fn remaining(&self, account: &str) -> u128 {
+        self.limit.saturating_sub(self.spent(account))
+    }
+
+    /// Reserve `fee` for `account`, failing with
+    /// [`Error::SpendingLimitExceeded`] if it does not fit in the budget
+    pub fn try_spend(&self, account: impl Into<String>, fee: u128) -> Result<FeeReservation> {
+        let account = account.into();
+        let mut ledger = self.ledger.lock();
+        let spent = self.expire(&mut ledger, &account);
+        self.check(&account, spent, fee)?;
+
+        let id = ledger.next_id;
+        ledger.next_id += 1;
+        ledger
+            .accounts
+            .entry(account.clone())
+            .or_default()
+            .spends
+            .push_back(Spend {
+                id,
+                at: std::time::Instant::now(),
+                fee,
+            });
+
+        Ok(FeeReservation {
+            limiter: self.clone(),
+            account,
+            id,
+            fee,
+        })
+    }
+
+    /// Drop spends older than the window and return the rest's total
+    fn expire(&self, ledger: &mut SpendLedger, account: &str) -> u128 {
+        let Some(account) = ledger.accounts.get_mut(account) else {
+            return 0;
+        };
+        if let Some(window) = self.window {
+            while account
+                .spends
+                .front()
+                .is_some_and(|spend| spend.at.elapsed() >= window)
+            {
+                account.spends.pop_front();
+            }
+        }
+        account.total()
+    }
+
+    /// Count a reservation's spend for good
+    ///
+    /// A session limiter folds it into the account's settled total; a
+    /// windowed one keeps it until it leaves the window.
+    fn settle(&self, account: &str, id: u64, fee: Option<u128>) {
+        let mut ledger = self.ledger.lock();
+        let Some(account) = ledger.accounts.get_mut(account) else {
+            return;
+        };
+        if self.window.is_some() {
+            if let (Some(spend), Some(fee)) = (account.find_mut(id), fee) {
+                spend.fee = fee;
+            }
+        } else if let Some(spend) = account.remove(id) {
+            account.settled = account.settled.saturating_add(fee.unwrap_or(spend.fee));
+        }
+    }
+
+    fn check(&self, account: &str, spent: u128, fee: u128) -> Result<()> {
+        if spent.saturating_add(fee) > self.limit {
+            return Err(Error::SpendingLimitExceeded(Box::new(
+                SpendingLimitExceeded {
+                    account: account.to_string(),
+                    fee,
+                    spent,
+                    limit: self.limit,
+                    window: self.window,
+                },
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Fee state of a submitted extrinsic, carried until its receipt
+#[derive(Debug, Default)]
+struct SubmittedFee {
+    /// Partial fee estimated before submission, excluding tip
+    estimated: Option<u128>,
+    /// Spending-limit reservation to settle once the fee is known
+    reservation: Option<FeeReservation>,
+}
+
+/// Fee reserved against a [`SpendingLimiter`] budget
+///
+/// Dropping the reservation keeps the fee counted; call
+/// [`settle`](Self::settle) once the fee paid is known, or
+/// [`release`](Self::release) when the transaction was never broadcast.
+#[derive(Debug)]
+pub struct FeeReservation {
+    limiter: SpendingLimiter,
+    account: String,
+    id: u64,
+    fee: u128,
+}
+
+impl FeeReservation {
+    /// Reserved fee
+    pub fn fee(&self) -> u128 {
+        self.fee
+    }
+
+    /// Change the reserved fee, e.g. for a replacement with a higher tip
+    ///
+    /// Keeps the old fee reserved if the new one does not fit in the budget.
+    pub fn replace(&mut self, fee: u128) -> Result<()> {
+        let mut ledger = self.limiter.ledger.lock();
+        let spent = self.limiter.expire(&mut ledger, &self.account);
+        let spend = ledger
+            .accounts
+            .get_mut(&self.account)
+            .and_then(|account| account.find_mut(self.id));
+
+        match spend {
+            Some(spend) => {
+                self.limiter.check(&self.account, spent - spend.fee, fee)?;
+                spend.fee = fee;
+            }
+            // Expired from the window, so only the new fee counts
+            None => {
+                self.limiter.check(&self.account, spent, fee)?;
+                ledger
+                    .accounts
+                    .entry(self.account.clone())
+                    .or_default()
+                    .spends
+                    .push_back(Spend {
+                        id: self.id,
+                        at: std::time::Instant::now(),
+                        fee,
+                    });
+            }
+        }
+        self.fee = fee;
+        Ok(())
+    }
+
+    /// Count `fee` in place of the reserved fee once the transaction is
+    /// finalized, returning any weight refund to the budget
+    ///
+    /// Unlike [`replace`](Self::replace) this never fails: the fee has
+    /// already been paid.
+    pub fn settle(self, fee: u128) {
+        self.limiter.settle(&self.account, self.id, Some(fee));
+    }
+
+    /// Return the reserved fee to the budget
+    pub fn release(self) {
+        let mut ledger = self.limiter.ledger.lock();
+        if let Some(account) = ledger.accounts.get_mut(&self.account) {
+            account.remove(self.id);
+        }
+    }
+}
+
+impl Drop for FeeReservation {
+    fn drop(&mut self) {
+        // No-op once settled or released
+        self.limiter.settle(&self.account, self.id, None);
+    }
 }
 
 /// Retry configuration for transaction submission
@@ -141,69 +961,268 @@ impl RetryConfig {
     }
 }
 
+/// Default lifetime of signed extrinsics, in blocks
+pub const DEFAULT_MORTALITY_BLOCKS: u64 = 64;
+
+/// Lifetime of signed extrinsics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mortality {
+    /// Valid until included. An immortal transaction can be replayed if its
+    /// signer's account is reaped and the nonce starts over.
+    Immortal,
+    /// Valid for `blocks` blocks from the latest finalized block at signing
+    /// time, rounded up to a power of two between 4 and 65536
+    Mortal { blocks: u64 },
+}
+
+impl Default for Mortality {
+    fn default() -> Self {
+        Mortality::Mortal {
+            blocks: DEFAULT_MORTALITY_BLOCKS,
+        }
+    }
+}
+
+/// Validity window of a mortal extrinsic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MortalEra {
+    /// Era length in blocks
+    pub period: u64,
+    /// Position of the checkpoint within the period
+    pub phase: u64,
+    /// Number of the block the era is anchored at
+    pub checkpoint_number: u64,
+    /// Hash of that block, signed as part of the extrinsic
+    pub checkpoint_hash: [u8; 32],
+}
+
+impl MortalEra {
+    /// Era lasting about `blocks` blocks from the checkpoint block
+    pub fn new(blocks: u64, checkpoint_number: u64, checkpoint_hash: [u8; 32]) -> Self {
+        let (period, phase) = match Era::mortal(blocks, checkpoint_number) {
+            Era::Mortal { period, phase } => (period, phase),
+            Era::Immortal => unreachable!("Era::mortal always returns a mortal era"),
+        };
+        Self {
+            period,
+            phase,
+            checkpoint_number,
+            checkpoint_hash,
+        }
+    }
+
+    /// First block the extrinsic can be included in
+    pub fn birth_block(&self) -> u64 {
+        (self.checkpoint_number.max(self.phase) - self.phase) / self.period * self.period
+            + self.phase
+    }
+
+    /// First block the extrinsic can no longer be included in
+    pub fn death_block(&self) -> u64 {
+        self.birth_block() + self.period
+    }
+
+    /// Whether the era has ended by `block_number`
+    pub fn is_expired_at(&self, block_number: u64) -> bool {
+        block_number >= self.death_block()
+    }
+}
+
+/// XCM version requested for messages in dry-run results
+const DRY_RUN_XCM_VERSION: u32 = 4;
+
 /// Transaction executor for building and submitting extrinsics
 pub struct TransactionExecutor {
     client: OnlineClient<PolkadotConfig>,
     fee_config: FeeConfig,
     retry_config: RetryConfig,
+    mortality: Mortality,
     metrics: Metrics,
+    fee_estimator: DynamicFeeEstimator,
+    spending_limiter: Option<SpendingLimiter>,
+    broadcast_hook: Option<BroadcastHook>,
 }
 
 impl TransactionExecutor {
     /// Create a new transaction executor
     pub fn new(client: OnlineClient<PolkadotConfig>, metrics: Metrics) -> Self {
         Self {
+            fee_estimator: DynamicFeeEstimator::new(client.clone()),
             client,
             fee_config: FeeConfig::default(),
             retry_config: RetryConfig::default(),
+            mortality: Mortality::default(),
             metrics,
+            spending_limiter: None,
+            broadcast_hook: None,
         }
     }
 
+    /// Record actual fees into a shared fee estimator
+    ///
+    /// Every transaction submitted by this executor compares the fee paid with
+    /// the pre-submission estimate via [`DynamicFeeEstimator::record_actual_fee`].
+    pub fn with_fee_estimator(mut self, fee_estimator: DynamicFeeEstimator) -> Self {
+        self.fee_estimator = fee_estimator;
+        self
+    }
+
     /// Set the fee configuration
     pub fn with_fee_config(mut self, fee_config: FeeConfig) -> Self {
         self.fee_config = fee_config;
         self
     }
 
+    /// Get the fee configuration
+    pub fn fee_config(&self) -> &FeeConfig {
+        &self.fee_config
+    }
+
+    /// Refuse transactions whose estimated fee would take the sender over
+    /// the limiter's budget
+    ///
+    /// Refused transactions fail with [`Error::SpendingLimitExceeded`]
+    /// before they are broadcast.
+    pub fn with_spending_limiter(mut self, limiter: SpendingLimiter) -> Self {
+        self.spending_limiter = Some(limiter);
+        self
+    }
+
+    /// Pass the hash of every signed extrinsic to `hook` before it is
+    /// submitted
+    ///
+    /// If the hook fails, the extrinsic is not submitted.
+    pub fn with_broadcast_hook(mut self, hook: BroadcastHook) -> Self {
+        self.broadcast_hook = Some(hook);
+        self
+    }
+
     /// Set the retry configuration
     pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
         self.retry_config = retry_config;
         self
     }
 
-    /// Submit a balance transfer transaction
-    pub async fn transfer(&self, from: &Wallet, to: &str, amount: u128) -> Result<String> {
-        info!(
-            "Submitting transfer from {} to {} of {} units",
-            from.address(),
-            to,
-            amount
-        );
-
-        use sp_core::crypto::Ss58Codec;
-        let dest = sp_core::sr25519::Public::from_ss58check(to)
-            .map_err(|e| Error::Transaction(format!("Invalid destination address: {}", e)))?;
-
-        use subxt::dynamic::Value;
+    /// Set the lifetime of signed extrinsics
+    ///
+    /// Mortal extrinsics are anchored at the latest finalized block when they
+    /// are signed, so every retry gets a fresh era. Defaults to
+    /// [`DEFAULT_MORTALITY_BLOCKS`] blocks.
+    pub fn with_mortality(mut self, mortality: Mortality) -> Self {
+        self.mortality = mortality;
+        self
+    }
 
-        let dest_value = Value::unnamed_variant("Id", vec![Value::from_bytes(dest.0)]);
+    /// Submit a balance transfer transaction and wait for finalization
+    pub async fn transfer(
+        &self,
+        from: &Wallet,
+        to: impl IntoAddress,
+        amount: u128,
+    ) -> Result<TransactionReceipt> {
+        let to = to.into_address()?;
+        let dest = to.account_id()?;
 
-        let transfer_call = subxt::dynamic::tx(
-            "Balances",
-            "transfer_keep_alive",
-            vec![dest_value, Value::u128(amount)],
+        info!(
+            operation = "transfer",
+            wallet = %from.address(),
+            to = %to,
+            amount,
+            "Submitting transfer"
         );
 
+        let transfer_call = transfer_call(dest, amount);
         self.submit_extrinsic_with_retry(&transfer_call, from).await
     }
 
-    /// Submit an extrinsic with retry logic
-    async fn submit_extrinsic_with_retry<Call>(
+    /// Sign a balance transfer with an explicit nonce without submitting it
+    ///
+    /// Returns the SCALE-encoded extrinsic, ready for
+    /// [`Broadcaster::broadcast`](apex_sdk_core::Broadcaster::broadcast).
+    /// Callers that pipeline many transfers from one account take nonces from
+    /// a [`NonceManager`](apex_sdk_core::NonceManager) rather than the node.
+    pub async fn sign_transfer(
         &self,
-        call: &Call,
-        signer: &Wallet,
-    ) -> Result<String>
+        from: &Wallet,
+        to: impl IntoAddress,
+        amount: u128,
+        nonce: u64,
+    ) -> Result<Vec<u8>> {
+        let dest = to.into_address()?.account_id()?;
+        let transfer_call = transfer_call(dest, amount);
+        let (signed, _) = self
+            .sign_with(&transfer_call, from, Some(nonce), self.fee_config.tip)
+            .await?;
+        Ok(signed.encoded().to_vec())
+    }
+
+    /// Describe a balance transfer and the executor's mortality for
+    /// confirmation before it is submitted
+    pub fn summarize_transfer(
+        &self,
+        to: impl IntoAddress,
+        amount: u128,
+    ) -> Result<TransactionSummary> {
+        use subxt::tx::Payload;
+
+        let dest = to.into_address()?.account_id()?;
+        let metadata = self.client.metadata();
+        let call_data = transfer_call(dest, amount)
+            .encode_call_data(&metadata)
+            .map_err(|e| Error::Encoding(format!("Failed to encode transfer: {}", e)))?;
+        Ok(summarize(&call_data, &metadata)?.with_mortality(self.mortality))
+    }
+
+    /// Submit a pre-encoded runtime call
+    ///
+    /// `call_data` is the SCALE encoding of a `RuntimeCall`
+    /// (`pallet_index ++ call_index ++ args`). The indices are validated against
+    /// the connected runtime before signing.
+    pub async fn execute_call(&self, from: &Wallet, call_data: Vec<u8>) -> Result<String> {
+        let call = RawCall::new(call_data)?;
+
+        info!(
+            operation = "call",
+            wallet = %from.address(),
+            pallet_index = call.pallet_index(),
+            call_index = call.call_index(),
+            len = call.call_data().len(),
+            "Submitting call"
+        );
+
+        self.submit_extrinsic_with_retry(&call, from)
+            .await
+            .map(|receipt| receipt.tx_hash)
+    }
+
+    /// Submit a dynamic call built from pallet and call names
+    pub async fn execute_dynamic(
+        &self,
+        from: &Wallet,
+        pallet: &str,
+        call: &str,
+        args: Vec<subxt::dynamic::Value>,
+    ) -> Result<String> {
+        info!(
+            operation = "dynamic_call",
+            wallet = %from.address(),
+            pallet,
+            call,
+            "Submitting dynamic call"
+        );
+
+        let tx = subxt::dynamic::tx(pallet, call, args);
+        self.submit_extrinsic_with_retry(&tx, from)
+            .await
+            .map(|receipt| receipt.tx_hash)
+    }
+
+    /// Submit an extrinsic with retry logic
+    async fn submit_extrinsic_with_retry<Call>(
+        &self,
+        call: &Call,
+        signer: &Wallet,
+    ) -> Result<TransactionReceipt>
     where
         Call: subxt::tx::Payload,
     {
@@ -215,21 +1234,43 @@ impl TransactionExecutor {
             self.metrics.record_transaction_attempt();
 
             match self.submit_extrinsic(call, signer).await {
-                Ok(hash) => {
+                Ok(receipt) => {
                     self.metrics.record_transaction_success();
-                    return Ok(hash);
+                    return Ok(receipt);
+                }
+                Err(e @ (Error::FeeCapExceeded(_) | Error::SpendingLimitExceeded(_))) => {
+                    // A retry would be refused the same way
+                    warn!(wallet = %signer.address(), error = %e, "Transaction refused");
+                    self.metrics.record_transaction_failure();
+                    return Err(e);
                 }
                 Err(e) => {
                     if attempts >= self.retry_config.max_retries {
-                        warn!("Transaction failed after {} attempts: {}", attempts, e);
+                        warn!(
+                            wallet = %signer.address(),
+                            attempts,
+                            error = %e,
+                            "Transaction failed after all retries"
+                        );
                         self.metrics.record_transaction_failure();
                         return Err(e);
                     }
 
+                    // Wait as long as a throttling provider asked, up to the
+                    // maximum delay
+                    let hinted = ThrottleHint::parse(&e.to_string())
+                        .and_then(|h| h.retry_after_capped(self.retry_config.max_delay));
                     warn!(
-                        "Transaction attempt {} failed: {}. Retrying in {:?}",
-                        attempts, e, delay
+                        wallet = %signer.address(),
+                        attempt = attempts,
+                        error = %e,
+                        retry_in = ?hinted.unwrap_or(delay),
+                        "Transaction attempt failed, retrying"
                     );
+                    if let Some(wait) = hinted {
+                        sleep(wait).await;
+                        continue;
+                    }
                     sleep(delay).await;
 
                     delay = Duration::from_secs_f64(
@@ -241,52 +1282,447 @@ impl TransactionExecutor {
         }
     }
 
-    /// Submit an extrinsic and wait for it to be included in a block
-    async fn submit_extrinsic<Call>(&self, call: &Call, signer: &Wallet) -> Result<String>
+    /// Submit an extrinsic and wait for it to be finalized
+    async fn submit_extrinsic<Call>(
+        &self,
+        call: &Call,
+        signer: &Wallet,
+    ) -> Result<TransactionReceipt>
     where
         Call: subxt::tx::Payload,
     {
         debug!("Submitting extrinsic");
 
-        let pair = signer
-            .sr25519_pair()
-            .ok_or_else(|| Error::Transaction("Wallet does not have SR25519 key".to_string()))?;
-
-        let apex_signer = Sr25519Signer::new(pair.clone());
-
-        let mut progress = self
-            .client
-            .tx()
-            .sign_and_submit_then_watch_default(call, &apex_signer)
-            .await
-            .map_err(|e| Error::Transaction(format!("Failed to submit transaction: {}", e)))?;
+        let (mut progress, fee, era) = self.sign_and_watch(call, signer).await?;
 
         while let Some(event) = progress.next().await {
             let event =
                 event.map_err(|e| Error::Transaction(format!("Transaction error: {}", e)))?;
+            if let Some(error) = self.rejection(&event, era.as_ref()).await {
+                return Err(error);
+            }
 
             if event.as_in_block().is_some() {
-                info!("Transaction included in block");
+                info!(wallet = %signer.address(), "Transaction included in block");
             }
 
             if let Some(finalized) = event.as_finalized() {
-                let tx_hash = format!("0x{}", hex::encode(finalized.extrinsic_hash()));
-                info!("Transaction finalized: {}", tx_hash);
-
-                finalized
+                let events = finalized
                     .wait_for_success()
                     .await
                     .map_err(|e| Error::Transaction(format!("Transaction failed: {}", e)))?;
+                let receipt = self.receipt(finalized, &events, fee).await?;
+                info!(
+                    wallet = %signer.address(),
+                    tx_hash = %receipt.tx_hash,
+                    block = receipt.block_number,
+                    fee = ?receipt.actual_fee,
+                    "Transaction finalized"
+                );
+                return Ok(receipt);
+            }
+        }
+
+        Err(self.expiry(era.as_ref()).await.unwrap_or_else(|| {
+            Error::Transaction("Transaction stream ended without finalization".to_string())
+        }))
+    }
 
-                return Ok(tx_hash);
+    /// Sign and submit a call, estimating its fee on the signed extrinsic first
+    ///
+    /// The estimate is best-effort; runtimes without `TransactionPaymentApi`
+    /// still get their transaction submitted.
+    async fn sign_and_watch<Call>(
+        &self,
+        call: &Call,
+        signer: &Wallet,
+    ) -> Result<(
+        TxProgress<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+        SubmittedFee,
+        Option<MortalEra>,
+    )>
+    where
+        Call: subxt::tx::Payload,
+    {
+        let (signed, era) = self.sign(call, signer).await?;
+        let mut reservation = None;
+        let estimated_fee = self
+            .enforce_fee_limits(&signed, self.fee_config.tip, signer, &mut reservation)
+            .await?;
+
+        if let Some(hook) = &self.broadcast_hook {
+            if let Err(e) = hook(&format!("0x{}", hex::encode(signed.hash()))) {
+                if let Some(reservation) = reservation {
+                    reservation.release();
+                }
+                return Err(Error::Transaction(format!(
+                    "Transaction not submitted: {}",
+                    e
+                )));
             }
         }
 
-        Err(Error::Transaction(
-            "Transaction stream ended without finalization".to_string(),
+        let progress = match signed.submit_and_watch().await {
+            Ok(progress) => progress,
+            Err(e) => {
+                if let Some(reservation) = reservation {
+                    reservation.release();
+                }
+                return Err(Error::Transaction(format!(
+                    "Failed to submit transaction: {}",
+                    e
+                )));
+            }
+        };
+        Ok((
+            progress,
+            SubmittedFee {
+                estimated: estimated_fee,
+                reservation,
+            },
+            era,
         ))
     }
 
+    /// Estimate the partial fee of a signed extrinsic and refuse it if the
+    /// final fee exceeds [`FeeConfig::max_fee`] or the spending limiter's
+    /// budget
+    ///
+    /// The final fee is reserved in `reservation`, replacing the fee already
+    /// reserved there. Without a cap or limiter the estimate is best-effort.
+    /// With either, an extrinsic whose fee cannot be estimated is refused,
+    /// since the limit could not be enforced.
+    async fn enforce_fee_limits(
+        &self,
+        signed: &SubmittableTransaction<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+        tip: u128,
+        signer: &Wallet,
+        reservation: &mut Option<FeeReservation>,
+    ) -> Result<Option<u128>> {
+        let estimated_fee = partial_fee(signed).await;
+        if self.fee_config.max_fee.is_none() && self.spending_limiter.is_none() {
+            return Ok(estimated_fee);
+        }
+
+        let partial_fee = estimated_fee.ok_or_else(|| {
+            Error::Transaction(
+                "Cannot enforce fee limits: the fee could not be estimated".to_string(),
+            )
+        })?;
+        let congestion = self.fee_estimator.get_congestion().await.multiplier();
+        let total_fee = self
+            .fee_config
+            .check_max_fee(partial_fee, congestion, tip)?;
+
+        if let Some(limiter) = &self.spending_limiter {
+            match reservation {
+                Some(reserved) => reserved.replace(total_fee)?,
+                None => *reservation = Some(limiter.try_spend(signer.address(), total_fee)?),
+            }
+        }
+        debug!(partial_fee, total_fee, "Fee within limits");
+        Ok(estimated_fee)
+    }
+
+    /// Sign a call with the wallet's SR25519 key and the configured tip
+    ///
+    /// Returns the era the extrinsic was signed with, if it is mortal.
+    async fn sign<Call>(
+        &self,
+        call: &Call,
+        signer: &Wallet,
+    ) -> Result<(
+        SubmittableTransaction<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+        Option<MortalEra>,
+    )>
+    where
+        Call: subxt::tx::Payload,
+    {
+        self.sign_with(call, signer, None, self.fee_config.tip)
+            .await
+    }
+
+    /// Sign a call with an explicit tip and, optionally, nonce
+    ///
+    /// Without a nonce, the account's next nonce is fetched from the node.
+    async fn sign_with<Call>(
+        &self,
+        call: &Call,
+        signer: &Wallet,
+        nonce: Option<u64>,
+        tip: u128,
+    ) -> Result<(
+        SubmittableTransaction<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+        Option<MortalEra>,
+    )>
+    where
+        Call: subxt::tx::Payload,
+    {
+        if signer.sr25519_pair().is_none() {
+            return Err(Error::Transaction(
+                "Wallet does not have SR25519 key".to_string(),
+            ));
+        }
+
+        let apex_signer = signer.to_subxt_signer();
+
+        let (params, era) = match self.mortality {
+            Mortality::Immortal => (PolkadotExtrinsicParamsBuilder::new().immortal(), None),
+            Mortality::Mortal { blocks } => {
+                let checkpoint = self.client.blocks().at_latest().await.map_err(|e| {
+                    Error::Connection(format!("Failed to fetch finalized block: {}", e))
+                })?;
+                let era = MortalEra::new(blocks, checkpoint.number() as u64, checkpoint.hash().0);
+                debug!(
+                    checkpoint = era.checkpoint_number,
+                    period = era.period,
+                    death_block = era.death_block(),
+                    "Signing mortal extrinsic"
+                );
+                let params = PolkadotExtrinsicParamsBuilder::new().mortal_from_unchecked(
+                    blocks,
+                    era.checkpoint_number,
+                    H256(era.checkpoint_hash),
+                );
+                (params, Some(era))
+            }
+        };
+        let params = params.tip(tip);
+        let params = match nonce {
+            Some(nonce) => params.nonce(nonce),
+            None => params,
+        };
+
+        let signed = apex_signer
+            .create_signed(&self.client, call, params)
+            .await?;
+        Ok((signed, era))
+    }
+
+    /// Error for a transaction the pool rejected or dropped, if `status` says so
+    async fn rejection(
+        &self,
+        status: &TxStatus<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+        era: Option<&MortalEra>,
+    ) -> Option<Error> {
+        let (kind, message) = match status {
+            TxStatus::Invalid { message } => ("invalid", message),
+            TxStatus::Dropped { message } => ("dropped", message),
+            TxStatus::Error { message } => ("rejected", message),
+            _ => return None,
+        };
+        Some(
+            self.expiry(era).await.unwrap_or_else(|| {
+                Error::Transaction(format!("Transaction {}: {}", kind, message))
+            }),
+        )
+    }
+
+    /// Error for a mortal transaction whose era has ended
+    async fn expiry(&self, era: Option<&MortalEra>) -> Option<Error> {
+        let era = era?;
+        let finalized = self.client.blocks().at_latest().await.ok()?.number() as u64;
+        era.is_expired_at(finalized).then(|| {
+            Error::Transaction(format!(
+                "Transaction expired: its {}-block era from block {} ended at block {} (finalized head is {}); sign it again",
+                era.period,
+                era.birth_block(),
+                era.death_block(),
+                finalized
+            ))
+        })
+    }
+
+    /// Simulate a call against current chain state without broadcasting it
+    ///
+    /// Signs the call and checks it the way the transaction pool would
+    /// (`TaggedTransactionQueue_validate_transaction`), then executes it with
+    /// `DryRunApi_dry_run_call` to find dispatch errors such as
+    /// `Balances::InsufficientBalance` or `BadOrigin` and the events it would
+    /// emit, along with the deposits it would reserve. No fees are paid.
+    /// Runtimes without `DryRunApi` only get the validity check; see
+    /// [`SimulationResult::dry_run`].
+    pub async fn simulate<Call>(&self, from: &Wallet, call: &Call) -> Result<SimulationResult>
+    where
+        Call: subxt::tx::Payload,
+    {
+        debug!(wallet = %from.address(), "Simulating extrinsic");
+
+        let (signed, _) = self.sign(call, from).await?;
+        let estimated_fee = partial_fee(&signed).await;
+
+        let invalid = match signed
+            .validate()
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to validate transaction: {}", e)))?
+        {
+            ValidationResult::Valid(_) => None,
+            ValidationResult::Invalid(reason) => Some(describe_invalid(&reason)),
+            ValidationResult::Unknown(reason) => Some(describe_unknown(&reason)),
+        };
+
+        let call_data = self
+            .client
+            .tx()
+            .call_data(call)
+            .map_err(|e| Error::Transaction(format!("Failed to encode call: {}", e)))?;
+
+        let (dry_run, dispatch_error, events) = match self.dry_run_call(from, &call_data).await? {
+            Some((dispatch_error, events)) => (true, dispatch_error, events),
+            None => (false, None, Vec::new()),
+        };
+
+        let deposits = DepositCalculator::new(self.client.metadata())
+            .for_call(&call_data)
+            .unwrap_or_else(|e| {
+                debug!("Cannot compute deposits: {}", e);
+                Vec::new()
+            });
+
+        let result = SimulationResult {
+            invalid,
+            dispatch_error,
+            events,
+            estimated_fee,
+            dry_run,
+            deposits,
+        };
+        debug!(
+            success = result.is_success(),
+            error = ?result.error(),
+            "Simulation finished"
+        );
+        Ok(result)
+    }
+
+    /// Execute a call with `DryRunApi_dry_run_call` as signed by `from`
+    ///
+    /// Returns `None` if the runtime has no `DryRunApi`.
+    async fn dry_run_call(
+        &self,
+        from: &Wallet,
+        call_data: &[u8],
+    ) -> Result<Option<(Option<String>, Vec<ReceiptEvent>)>> {
+        use parity_scale_codec::Encode;
+        use subxt::dynamic::Value;
+        use subxt::ext::scale_value::scale::{decode_as_type, encode_as_type};
+
+        let metadata = self.client.metadata();
+        let Some(method) = metadata
+            .runtime_api_trait_by_name("DryRunApi")
+            .and_then(|api| api.method_by_name("dry_run_call"))
+        else {
+            return Ok(None);
+        };
+        let inputs: Vec<_> = method.inputs().collect();
+        let Some(origin_param) = inputs.first() else {
+            return Ok(None);
+        };
+
+        // OriginCaller::system(RawOrigin::Signed(account))
+        let origin = Value::unnamed_variant(
+            "system",
+            [Value::unnamed_variant(
+                "Signed",
+                [Value::from_bytes(from.public_key())],
+            )],
+        );
+        let mut params = Vec::new();
+        encode_as_type(&origin, origin_param.ty, metadata.types(), &mut params)
+            .map_err(|e| Error::Transaction(format!("Failed to encode dry-run origin: {}", e)))?;
+        params.extend_from_slice(call_data);
+        // `result_xcms_version` was added in DryRunApi v2
+        if inputs.len() > 2 {
+            DRY_RUN_XCM_VERSION.encode_to(&mut params);
+        }
+
+        let bytes = self
+            .client
+            .runtime_api()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?
+            .call_raw("DryRunApi_dry_run_call", Some(&params))
+            .await
+            .map_err(|e| Error::Transaction(format!("Dry run failed: {}", e)))?;
+
+        let output = decode_as_type(&mut &bytes[..], method.output_ty(), metadata.types())
+            .map_err(|e| Error::Transaction(format!("Failed to decode dry-run result: {}", e)))?;
+
+        let effects = dry_run_effects(&output).map_err(Error::Transaction)?;
+        let dispatch_error = effects.dispatch_error.map(|error| {
+            let mut bytes = Vec::new();
+            match encode_as_type(error, error.context, metadata.types(), &mut bytes) {
+                Ok(()) => decode_dispatch_error(&bytes, &metadata),
+                Err(_) => error.to_string(),
+            }
+        });
+
+        Ok(Some((dispatch_error, effects.events)))
+    }
+
+    /// Build the receipt of a finalized extrinsic from its events
+    ///
+    /// Feeds the fee charged back into the fee estimator's accuracy stats
+    /// and the fee metrics, and settles the spending-limit reservation at
+    /// the fee paid after refunds.
+    async fn receipt(
+        &self,
+        finalized: &TxInBlock<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+        events: &ExtrinsicEvents<PolkadotConfig>,
+        fee: SubmittedFee,
+    ) -> Result<TransactionReceipt> {
+        let block_number = self
+            .client
+            .blocks()
+            .at(finalized.block_hash())
+            .await
+            // The transaction is already finalized, so this is not a
+            // connection failure a caller may retry
+            .map_err(|e| Error::Transaction(format!("Failed to fetch block: {}", e)))?
+            .number()
+            .into();
+
+        let decoded = events
+            .iter()
+            .map(|event| {
+                let event = event?;
+                Ok(ReceiptEvent {
+                    pallet: event.pallet_name().to_string(),
+                    variant: event.variant_name().to_string(),
+                    fields: event.field_values()?.map_context(|_| ()),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut receipt = TransactionReceipt::new(
+            format!("0x{}", hex::encode(finalized.extrinsic_hash())),
+            format!("0x{}", hex::encode(finalized.block_hash())),
+            block_number,
+            events.extrinsic_index(),
+            decoded,
+        );
+        receipt.estimated_fee = fee.estimated;
+
+        if let Some(actual) = receipt.actual_fee {
+            let charged = receipt.charged_fee.unwrap_or(actual);
+            self.metrics.record_fee(charged, actual);
+            if let Some(reservation) = fee.reservation {
+                reservation.settle(actual);
+            }
+            // The estimate is made before dispatch, so compare it with the
+            // fee charged before the weight refund
+            if let Some(estimated) = fee.estimated {
+                let charged = charged.saturating_sub(receipt.tip.unwrap_or(0));
+                self.fee_estimator
+                    .record_actual_fee(estimated, charged)
+                    .await;
+            }
+        }
+
+        Ok(receipt)
+    }
+
     /// Estimate fees for a transaction
     ///
     /// # Arguments
@@ -338,23 +1774,17 @@ impl TransactionExecutor {
             fee_array.copy_from_slice(fee_bytes);
             let base_fee = u128::from_le_bytes(fee_array);
 
-            let estimated_fee = (base_fee as f64 * self.fee_config.multiplier) as u128;
-
-            if let Some(max_fee) = self.fee_config.max_fee {
-                if estimated_fee > max_fee {
-                    return Err(Error::Transaction(format!(
-                        "Estimated fee {} exceeds maximum {}",
-                        estimated_fee, max_fee
-                    )));
-                }
-            }
+            let congestion = self.fee_estimator.get_congestion().await.multiplier();
+            let estimated_fee =
+                self.fee_config
+                    .check_max_fee(base_fee, congestion, self.fee_config.tip)?;
 
             debug!(
-                "Estimated fee: {} (base: {}, multiplier: {})",
-                estimated_fee, base_fee, self.fee_config.multiplier
+                "Estimated fee: {} (base: {}, multiplier: {}, congestion: {})",
+                estimated_fee, base_fee, self.fee_config.multiplier, congestion
             );
 
-            Ok(estimated_fee + self.fee_config.tip)
+            Ok(estimated_fee)
         } else {
             warn!("Unexpected fee query response format, using fallback");
             Ok(1_000_000u128) // 1 million Planck
@@ -439,13 +1869,48 @@ impl TransactionExecutor {
     /// * `wallet` - The wallet to sign the batch transaction
     /// * `batch_mode` - The batch execution mode (see BatchMode)
     ///
-    /// Returns the transaction hash of the batch extrinsic
+    /// Returns the transaction hash of the batch extrinsic. Use
+    /// [`TransactionExecutor::execute_batch_detailed`] to get per-call results.
     pub async fn execute_batch(
         &self,
         calls: Vec<BatchCall>,
         wallet: &Wallet,
         batch_mode: BatchMode,
     ) -> Result<String> {
+        self.submit_batch(calls, wallet, batch_mode, false)
+            .await
+            .map(|(result, _)| result.tx_hash)
+    }
+
+    /// Execute a batch of transactions and report per-call outcomes
+    ///
+    /// Decodes `Utility::ItemCompleted`, `Utility::ItemFailed` and
+    /// `Utility::BatchInterrupted` events into a [`BatchResult`], so callers can
+    /// resubmit only the calls listed by [`BatchResult::retry_indices`]. The
+    /// pre-dispatch weight of every call is queried from
+    /// `TransactionPaymentCallApi_query_call_info` and the actual weight of the
+    /// whole batch is taken from `System::ExtrinsicSuccess`.
+    ///
+    /// With [`BatchMode::AllOrNothing`] a failing call reverts the whole batch and
+    /// an error is returned instead.
+    pub async fn execute_batch_detailed(
+        &self,
+        calls: Vec<BatchCall>,
+        wallet: &Wallet,
+        batch_mode: BatchMode,
+    ) -> Result<BatchResult> {
+        self.submit_batch(calls, wallet, batch_mode, true)
+            .await
+            .map(|(result, _)| result)
+    }
+
+    async fn submit_batch(
+        &self,
+        calls: Vec<BatchCall>,
+        wallet: &Wallet,
+        batch_mode: BatchMode,
+        query_weights: bool,
+    ) -> Result<(BatchResult, TransactionReceipt)> {
         debug!(
             "Executing batch of {} calls with mode {:?}",
             calls.len(),
@@ -457,17 +1922,41 @@ impl TransactionExecutor {
             return Err(Error::Transaction("Cannot execute empty batch".to_string()));
         }
 
-        // generate typed metadata using `subxt codegen` for better type safety
-        let call_values: Vec<subxt::dynamic::Value> = calls
-            .into_iter()
-            .map(|call| {
-                let mut call_bytes = Vec::new();
-                call_bytes.push(call.pallet_index);
-                call_bytes.push(call.call_index);
-                call_bytes.extend_from_slice(&call.args_encoded);
+        // Resolve named calls against the metadata of the runtime we are submitting to
+        let metadata = self.client.metadata();
+        let encoded_calls = calls
+            .iter()
+            .map(|call| call.encode_call(&metadata))
+            .collect::<Result<Vec<_>>>()?;
 
-                subxt::dynamic::Value::from_bytes(&call_bytes)
-            })
+        self.submit_encoded_batch(encoded_calls, wallet, batch_mode, query_weights)
+            .await
+    }
+
+    /// Submit encoded runtime calls as a `Utility` batch and decode per-call
+    /// outcomes from its events
+    async fn submit_encoded_batch(
+        &self,
+        encoded_calls: Vec<Vec<u8>>,
+        wallet: &Wallet,
+        batch_mode: BatchMode,
+        query_weights: bool,
+    ) -> Result<(BatchResult, TransactionReceipt)> {
+        let metadata = self.client.metadata();
+
+        let mut item_weights = vec![None; encoded_calls.len()];
+        if query_weights {
+            for (weight, call_bytes) in item_weights.iter_mut().zip(&encoded_calls) {
+                match self.query_call_weight(call_bytes).await {
+                    Ok(w) => *weight = Some(w),
+                    Err(e) => debug!("Failed to query call weight: {}", e),
+                }
+            }
+        }
+
+        let call_values: Vec<subxt::dynamic::Value> = encoded_calls
+            .iter()
+            .map(subxt::dynamic::Value::from_bytes)
             .collect();
 
         let calls_value = subxt::dynamic::Value::unnamed_composite(call_values);
@@ -484,56 +1973,140 @@ impl TransactionExecutor {
         // Create the batch transaction
         let tx = subxt::dynamic::tx("Utility", batch_call_name, vec![calls_value]);
 
-        let pair = wallet
-            .sr25519_pair()
-            .ok_or_else(|| Error::Transaction("Wallet does not have SR25519 key".to_string()))?;
-
-        let apex_signer = Sr25519Signer::new(pair.clone());
-
-        // Sign and submit
-        let mut signed_tx = self
-            .client
-            .tx()
-            .sign_and_submit_then_watch_default(&tx, &apex_signer)
-            .await
-            .map_err(|e| Error::Transaction(format!("Failed to submit batch: {}", e)))?;
+        let (mut signed_tx, fee, era) = self.sign_and_watch(&tx, wallet).await?;
 
         while let Some(event) = signed_tx.next().await {
             let event =
                 event.map_err(|e| Error::Transaction(format!("Batch transaction error: {}", e)))?;
+            if let Some(error) = self.rejection(&event, era.as_ref()).await {
+                self.metrics.record_transaction_failure();
+                return Err(error);
+            }
 
             if event.as_in_block().is_some() {
-                info!("Batch transaction included in block");
+                info!(operation = "batch", "Batch transaction included in block");
             }
 
             if let Some(finalized) = event.as_finalized() {
-                let tx_hash = format!("0x{}", hex::encode(finalized.extrinsic_hash()));
-                info!("Batch transaction finalized: {}", tx_hash);
+                let events = finalized.wait_for_success().await.map_err(|e| {
+                    self.metrics.record_transaction_failure();
+                    Error::Transaction(format!("Batch transaction failed: {}", e))
+                })?;
+                let receipt = self.receipt(finalized, &events, fee).await?;
+                info!(
+                    operation = "batch",
+                    tx_hash = %receipt.tx_hash,
+                    fee = ?receipt.actual_fee,
+                    "Batch transaction finalized"
+                );
+
+                let mut utility_events = Vec::new();
+
+                for event in events.iter() {
+                    let event = event.map_err(|e| {
+                        Error::Transaction(format!("Failed to decode batch event: {}", e))
+                    })?;
+
+                    match (event.pallet_name(), event.variant_name()) {
+                        ("Utility", "ItemCompleted") => {
+                            utility_events.push(UtilityEvent::ItemCompleted)
+                        }
+                        ("Utility", "ItemFailed") => utility_events.push(UtilityEvent::ItemFailed(
+                            decode_dispatch_error(event.field_bytes(), &metadata),
+                        )),
+                        ("Utility", "BatchInterrupted") => {
+                            use parity_scale_codec::Decode;
+                            let mut bytes = event.field_bytes();
+                            let index = u32::decode(&mut bytes).map_err(|e| {
+                                Error::Transaction(format!(
+                                    "Failed to decode BatchInterrupted index: {}",
+                                    e
+                                ))
+                            })?;
+                            utility_events.push(UtilityEvent::BatchInterrupted {
+                                index,
+                                error: decode_dispatch_error(bytes, &metadata),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
 
-                finalized
-                    .wait_for_success()
-                    .await
-                    .map_err(|e| Error::Transaction(format!("Batch transaction failed: {}", e)))?;
+                let (statuses, interrupted_at) =
+                    batch_item_statuses(encoded_calls.len(), &utility_events);
+
+                let items = statuses
+                    .into_iter()
+                    .zip(item_weights)
+                    .enumerate()
+                    .map(|(index, (status, weight))| BatchItemResult {
+                        index: index as u32,
+                        status,
+                        weight,
+                    })
+                    .collect::<Vec<_>>();
+
+                if let Some(index) = interrupted_at {
+                    warn!(
+                        operation = "batch",
+                        tx_hash = %receipt.tx_hash,
+                        index,
+                        "Batch interrupted"
+                    );
+                }
 
                 self.metrics.record_transaction_success();
-                return Ok(tx_hash);
+                let result = BatchResult {
+                    tx_hash: receipt.tx_hash.clone(),
+                    block_hash: receipt.block_hash.clone(),
+                    mode: batch_mode,
+                    items,
+                    interrupted_at,
+                    actual_weight: receipt.weight,
+                };
+                return Ok((result, receipt));
             }
         }
 
-        Err(Error::Transaction(
-            "Batch transaction stream ended without finalization".to_string(),
-        ))
+        Err(self.expiry(era.as_ref()).await.unwrap_or_else(|| {
+            Error::Transaction("Batch transaction stream ended without finalization".to_string())
+        }))
+    }
+
+    /// Query the pre-dispatch weight of an encoded runtime call
+    async fn query_call_weight(&self, call_bytes: &[u8]) -> Result<Weight> {
+        use parity_scale_codec::{Decode, Encode};
+
+        // query_call_info(call: RuntimeCall, len: u32) -> RuntimeDispatchInfo
+        let mut params = call_bytes.to_vec();
+        params.extend((call_bytes.len() as u32).encode());
+
+        let result = self
+            .client
+            .runtime_api()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?
+            .call_raw("TransactionPaymentCallApi_query_call_info", Some(&params))
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to query call info: {}", e)))?;
+
+        let info = crate::fee_estimator::RuntimeDispatchInfo::decode(&mut &result[..])
+            .map_err(|e| Error::Transaction(format!("Failed to decode dispatch info: {}", e)))?;
+
+        Ok(Weight::new(info.weight.ref_time, info.weight.proof_size))
     }
 
     /// Execute a batch of balance transfers
     ///
-    /// Convenience method for batching multiple transfers
+    /// Convenience method for batching multiple transfers. The receipt covers the
+    /// batch extrinsic as a whole; its events include the `Utility` item events.
     pub async fn execute_batch_transfers(
         &self,
         transfers: Vec<(String, u128)>, // (recipient, amount) pairs
         wallet: &Wallet,
         batch_mode: BatchMode,
-    ) -> Result<String> {
+    ) -> Result<TransactionReceipt> {
         use sp_core::crypto::{AccountId32, Ss58Codec};
 
         // Convert transfers to BatchCalls
@@ -550,14 +2123,12 @@ impl TransactionExecutor {
             use parity_scale_codec::Encode;
             let args = (to_bytes, amount).encode();
 
-            calls.push(BatchCall {
-                pallet_index: 5, // Balances pallet (typical index, may vary by chain)
-                call_index: 3,   // transfer_keep_alive (typical index, may vary by chain)
-                args_encoded: args,
-            });
+            calls.push(BatchCall::named("Balances", "transfer_keep_alive", args));
         }
 
-        self.execute_batch(calls, wallet, batch_mode).await
+        self.submit_batch(calls, wallet, batch_mode, false)
+            .await
+            .map(|(_, receipt)| receipt)
     }
 
     /// This provides access to advanced fee estimation features including:
@@ -565,8 +2136,11 @@ impl TransactionExecutor {
     /// - Network congestion monitoring
     /// - Multiple fee strategies (Fast, Normal, Slow)
     /// - Fee estimation accuracy tracking
-    pub fn dynamic_fee_estimator(&self) -> crate::fee_estimator::DynamicFeeEstimator {
-        crate::fee_estimator::DynamicFeeEstimator::new(self.client.clone())
+    ///
+    /// The estimator is shared with this executor, so its accuracy stats include
+    /// the actual fees of submitted transactions.
+    pub fn dynamic_fee_estimator(&self) -> DynamicFeeEstimator {
+        self.fee_estimator.clone()
     }
 
     /// Estimate fee using dynamic fee estimator with strategy
@@ -637,6 +2211,548 @@ impl TransactionExecutor {
     }
 }
 
+/// Pre-submission partial fee of a signed extrinsic, excluding tip
+///
+/// Best-effort; runtimes without `TransactionPaymentApi` still get their
+/// transaction submitted.
+async fn partial_fee(
+    signed: &SubmittableTransaction<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+) -> Option<u128> {
+    match signed.partial_fee_estimate().await {
+        Ok(fee) => Some(fee),
+        Err(e) => {
+            debug!("Failed to estimate fee: {}", e);
+            None
+        }
+    }
+}
+
+/// `Balances::transfer_keep_alive` to `dest`
+fn transfer_call(dest: [u8; 32], amount: u128) -> subxt::tx::DynamicPayload {
+    use subxt::dynamic::Value;
+
+    let dest_value = Value::unnamed_variant("Id", vec![Value::from_bytes(dest)]);
+    subxt::dynamic::tx(
+        "Balances",
+        "transfer_keep_alive",
+        vec![dest_value, Value::u128(amount)],
+    )
+}
+
+/// Actionable message for a transaction the pool would reject
+fn describe_invalid(reason: &TransactionInvalid) -> String {
+    match reason {
+        TransactionInvalid::Call => "Call is not allowed in a transaction".to_string(),
+        TransactionInvalid::Payment => {
+            "Account cannot pay the transaction fee (balance too low)".to_string()
+        }
+        TransactionInvalid::Future => "Nonce is too high".to_string(),
+        TransactionInvalid::Stale => "Nonce is too low (transaction already included)".to_string(),
+        TransactionInvalid::BadProof => {
+            "Bad signature (signer or chain parameters mismatch)".to_string()
+        }
+        TransactionInvalid::AncientBirthBlock => "Transaction mortality has expired".to_string(),
+        TransactionInvalid::ExhaustsResources => {
+            "Transaction would exhaust the block's resources".to_string()
+        }
+        TransactionInvalid::BadSigner => "Signer is not allowed to submit this call".to_string(),
+        other => format!("Invalid transaction: {:?}", other),
+    }
+}
+
+/// Message for a transaction the runtime could not validate
+fn describe_unknown(reason: &TransactionUnknown) -> String {
+    match reason {
+        TransactionUnknown::CannotLookup => {
+            "Could not look up information required to validate the transaction".to_string()
+        }
+        other => format!("Unknown transaction validity: {:?}", other),
+    }
+}
+
+/// Fields of interest in a decoded `DryRunApi_dry_run_call` result
+#[derive(Debug)]
+struct DryRunEffects<'a, T> {
+    /// `DispatchError` of the call, if it failed
+    dispatch_error: Option<&'a subxt::dynamic::Value<T>>,
+    /// Events emitted by the call
+    events: Vec<ReceiptEvent>,
+}
+
+/// Extract the outcome from `Result<CallDryRunEffects<RuntimeEvent>, Error>`
+fn dry_run_effects<T: Clone>(
+    output: &subxt::dynamic::Value<T>,
+) -> std::result::Result<DryRunEffects<'_, T>, String> {
+    use subxt::dynamic::At as _;
+    use subxt::ext::scale_value::ValueDef;
+
+    let ValueDef::Variant(result) = &output.value else {
+        return Err("Unexpected dry-run result shape".to_string());
+    };
+    let Some(effects) = result.values.values().next() else {
+        return Err("Empty dry-run result".to_string());
+    };
+    if result.name == "Err" {
+        return Err(format!("Dry run rejected: {}", effects));
+    }
+
+    let execution = effects
+        .at("execution_result")
+        .ok_or_else(|| "Dry-run result has no execution_result".to_string())?;
+    let dispatch_error = match &execution.value {
+        ValueDef::Variant(outcome) if outcome.name == "Err" => outcome
+            .values
+            .values()
+            .next()
+            .and_then(|err| err.at("error")),
+        _ => None,
+    };
+
+    let events = match effects.at("emitted_events").map(|events| &events.value) {
+        Some(ValueDef::Composite(events)) => events.values().filter_map(runtime_event).collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(DryRunEffects {
+        dispatch_error,
+        events,
+    })
+}
+
+/// Convert a `RuntimeEvent` value (`Pallet(Event { .. })`) into a [`ReceiptEvent`]
+fn runtime_event<T: Clone>(event: &subxt::dynamic::Value<T>) -> Option<ReceiptEvent> {
+    use subxt::ext::scale_value::ValueDef;
+
+    let ValueDef::Variant(pallet) = &event.value else {
+        return None;
+    };
+    let ValueDef::Variant(inner) = &pallet.values.values().next()?.value else {
+        return None;
+    };
+    Some(ReceiptEvent {
+        pallet: pallet.name.clone(),
+        variant: inner.name.clone(),
+        fields: inner.values.clone().map_context(|_| ()),
+    })
+}
+
+/// Decode a SCALE-encoded `DispatchError` into a readable message
+pub(crate) fn decode_dispatch_error(bytes: &[u8], metadata: &subxt::Metadata) -> String {
+    match subxt::error::DispatchError::decode_from(bytes, metadata.clone()) {
+        Ok(error) => error.to_string(),
+        Err(_) => format!("Undecodable dispatch error: 0x{}", hex::encode(bytes)),
+    }
+}
+
+/// A call queued in a [`BatchBuilder`]
+#[derive(Debug, Clone)]
+enum BatchEntry {
+    Encoded(BatchCall),
+    Dynamic(subxt::tx::DynamicPayload),
+    /// A call whose arguments failed validation, reported on execution
+    Invalid(String),
+}
+
+/// Builder for `Utility` batches mixing calls of any pallet
+///
+/// Calls can be pre-encoded [`BatchCall`]s or dynamic payloads built from
+/// pallet and call names, like those returned by [`AssetManager`]. They are
+/// encoded against the connected runtime when the batch is executed.
+///
+/// ```rust,no_run
+/// use apex_sdk_substrate::{BatchBuilder, BatchMode};
+/// use subxt::dynamic::Value;
+///
+/// # async fn example(
+/// #     executor: &apex_sdk_substrate::TransactionExecutor,
+/// #     wallet: &apex_sdk_substrate::Wallet,
+/// # ) -> apex_sdk_substrate::Result<()> {
+/// let result = BatchBuilder::new()
+///     .with_mode(BatchMode::Force)
+///     .transfer("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", 10_000_000_000)
+///     .asset_transfer(1984, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", 1_000_000)
+///     .remark(b"payroll 2024-06".to_vec())
+///     .dynamic("Staking", "bond_extra", vec![Value::u128(5_000_000_000)])
+///     .execute(executor, wallet)
+///     .await?;
+/// for index in result.retry_indices() {
+///     println!("call {} did not go through", index);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`AssetManager`]: crate::AssetManager
+#[derive(Debug, Clone, Default)]
+pub struct BatchBuilder {
+    mode: BatchMode,
+    entries: Vec<BatchEntry>,
+}
+
+impl BatchBuilder {
+    /// Create an empty builder using [`BatchMode::Optimistic`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the batch mode
+    pub fn with_mode(mut self, mode: BatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Add a pre-encoded call
+    pub fn call(mut self, call: BatchCall) -> Self {
+        self.entries.push(BatchEntry::Encoded(call));
+        self
+    }
+
+    /// Add a dynamic payload
+    pub fn payload(mut self, payload: subxt::tx::DynamicPayload) -> Self {
+        self.entries.push(BatchEntry::Dynamic(payload));
+        self
+    }
+
+    /// Add a call built from pallet and call names and its arguments
+    pub fn dynamic(self, pallet: &str, call: &str, fields: Vec<subxt::dynamic::Value>) -> Self {
+        self.payload(subxt::dynamic::tx(pallet, call, fields))
+    }
+
+    /// Add a `Balances::transfer_keep_alive`
+    pub fn transfer(self, to: impl IntoAddress, amount: u128) -> Self {
+        use subxt::dynamic::Value;
+
+        match to.into_address().and_then(|to| to.account_id()) {
+            Ok(dest) => self.dynamic(
+                "Balances",
+                "transfer_keep_alive",
+                vec![
+                    Value::unnamed_variant("Id", [Value::from_bytes(dest)]),
+                    Value::u128(amount),
+                ],
+            ),
+            Err(e) => self.invalid(format!("Invalid transfer recipient: {}", e)),
+        }
+    }
+
+    /// Add an `Assets::transfer` of a pallet-assets asset
+    pub fn asset_transfer(self, asset_id: u32, to: impl IntoAddress, amount: u128) -> Self {
+        use subxt::dynamic::Value;
+
+        match to.into_address().and_then(|to| to.account_id()) {
+            Ok(target) => self.dynamic(
+                "Assets",
+                "transfer",
+                vec![
+                    Value::u128(asset_id as u128),
+                    Value::unnamed_variant("Id", [Value::from_bytes(target)]),
+                    Value::u128(amount),
+                ],
+            ),
+            Err(e) => self.invalid(format!("Invalid asset transfer target: {}", e)),
+        }
+    }
+
+    /// Add a `System::remark`
+    pub fn remark(self, remark: Vec<u8>) -> Self {
+        self.dynamic(
+            "System",
+            "remark",
+            vec![subxt::dynamic::Value::from_bytes(remark)],
+        )
+    }
+
+    fn invalid(mut self, reason: String) -> Self {
+        self.entries.push(BatchEntry::Invalid(reason));
+        self
+    }
+
+    /// Get the batch mode
+    pub fn mode(&self) -> BatchMode {
+        self.mode
+    }
+
+    /// Number of calls in the batch
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the batch has no calls
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Encode every call against the given runtime metadata, in order
+    pub fn encode(&self, metadata: &subxt::Metadata) -> Result<Vec<Vec<u8>>> {
+        use subxt::tx::Payload;
+
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| match entry {
+                BatchEntry::Encoded(call) => call.encode_call(metadata),
+                BatchEntry::Dynamic(payload) => payload.encode_call_data(metadata).map_err(|e| {
+                    Error::Encoding(format!("Failed to encode batch call {}: {}", index, e))
+                }),
+                BatchEntry::Invalid(reason) => Err(Error::Transaction(format!(
+                    "Batch call {}: {}",
+                    index, reason
+                ))),
+            })
+            .collect()
+    }
+
+    /// Sign and submit the batch and report per-call outcomes
+    ///
+    /// Outcomes are decoded from `Utility::ItemCompleted`, `Utility::ItemFailed`
+    /// and `Utility::BatchInterrupted` events, as in
+    /// [`TransactionExecutor::execute_batch_detailed`].
+    pub async fn execute(
+        &self,
+        executor: &TransactionExecutor,
+        wallet: &Wallet,
+    ) -> Result<BatchResult> {
+        if self.is_empty() {
+            return Err(Error::Transaction("Cannot execute empty batch".to_string()));
+        }
+        executor.metrics.record_transaction_attempt();
+
+        let encoded_calls = self.encode(&executor.client.metadata())?;
+        executor
+            .submit_encoded_batch(encoded_calls, wallet, self.mode, true)
+            .await
+            .map(|(result, _)| result)
+    }
+}
+
+/// Tips to resubmit a stuck transaction with
+///
+/// A transaction that is not in a finalized block within
+/// `stuck_after_blocks` blocks is replaced by the same call, signed with the
+/// same nonce and the next tip of the schedule. The pool only replaces a
+/// transaction with one of higher priority, so tips must increase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscalationSchedule {
+    stuck_after_blocks: u64,
+    tips: Vec<u128>,
+}
+
+impl EscalationSchedule {
+    /// Create a schedule from explicit tips, one per replacement
+    pub fn new(stuck_after_blocks: u64, tips: Vec<u128>) -> Result<Self> {
+        if stuck_after_blocks == 0 {
+            return Err(Error::Transaction(
+                "Escalation needs to wait at least one block".to_string(),
+            ));
+        }
+        if tips.windows(2).any(|pair| pair[1] <= pair[0]) {
+            return Err(Error::Transaction(
+                "Escalation tips must be strictly increasing".to_string(),
+            ));
+        }
+        Ok(Self {
+            stuck_after_blocks,
+            tips,
+        })
+    }
+
+    /// Create a schedule of `replacements` tips, starting at `initial_tip` and
+    /// growing by `bump_percent` each time (by at least 1)
+    pub fn geometric(
+        stuck_after_blocks: u64,
+        initial_tip: u128,
+        bump_percent: u32,
+        replacements: usize,
+    ) -> Result<Self> {
+        let tips = std::iter::successors(Some(initial_tip), |tip| {
+            let bumped = tip.saturating_mul(100 + bump_percent as u128) / 100;
+            Some(bumped.max(tip.saturating_add(1)))
+        })
+        .take(replacements)
+        .collect();
+        Self::new(stuck_after_blocks, tips)
+    }
+
+    /// Finalized blocks to wait for inclusion before replacing a transaction
+    pub fn stuck_after_blocks(&self) -> u64 {
+        self.stuck_after_blocks
+    }
+
+    /// Tips of the successive replacements
+    pub fn tips(&self) -> &[u128] {
+        &self.tips
+    }
+}
+
+/// Outcome of a transaction submitted through a [`StuckTransactionHandler`]
+#[derive(Debug, Clone)]
+pub struct EscalationReceipt {
+    /// Hash of the extrinsic that was finalized
+    pub tx_hash: String,
+    /// Hash of the block it was finalized in
+    pub block_hash: Option<String>,
+    /// Number of that block
+    pub block_number: Option<u64>,
+    /// Tip it was signed with
+    pub tip: u128,
+    /// Nonce shared by the original and its replacements
+    pub nonce: u64,
+    /// Hashes of the extrinsics it replaced, oldest first
+    pub replaced: Vec<String>,
+}
+
+/// Resubmits transactions that are not included with increasing tips
+///
+/// Each submission is watched by the adapter's [`TransactionMonitor`]. When it
+/// is not included in time, its watch is cancelled and the call is signed
+/// again with the same nonce and the next tip of the [`EscalationSchedule`],
+/// replacing it in the transaction pool.
+///
+/// [`TransactionMonitor`]: crate::monitor::TransactionMonitor
+pub struct StuckTransactionHandler {
+    executor: TransactionExecutor,
+    monitor: std::sync::Arc<crate::monitor::TransactionMonitor>,
+    schedule: EscalationSchedule,
+}
+
+impl StuckTransactionHandler {
+    /// Create a handler signing with `executor` and watching with `monitor`
+    pub fn new(
+        executor: TransactionExecutor,
+        monitor: std::sync::Arc<crate::monitor::TransactionMonitor>,
+        schedule: EscalationSchedule,
+    ) -> Self {
+        Self {
+            executor,
+            monitor,
+            schedule,
+        }
+    }
+
+    /// Get the escalation schedule
+    pub fn schedule(&self) -> &EscalationSchedule {
+        &self.schedule
+    }
+
+    /// Submit a call, replacing it with higher tips until it is finalized
+    ///
+    /// The first submission uses the executor's configured tip. Fails if the
+    /// call is still not included after the last tip of the schedule, or if
+    /// a replacement is refused because the nonce has been used in the
+    /// meantime; the error lists the hashes submitted so far.
+    pub async fn submit<Call>(&self, call: &Call, signer: &Wallet) -> Result<EscalationReceipt>
+    where
+        Call: subxt::tx::Payload,
+    {
+        use subxt::tx::Signer;
+
+        let pair = signer
+            .sr25519_pair()
+            .ok_or_else(|| Error::Transaction("Wallet does not have SR25519 key".to_string()))?;
+        let account = Sr25519Signer::new(pair.clone()).account_id();
+        let nonce = self
+            .executor
+            .client
+            .tx()
+            .account_nonce(&account)
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to fetch nonce: {}", e)))?;
+
+        let tips =
+            std::iter::once(self.executor.fee_config.tip).chain(self.schedule.tips.iter().copied());
+        let mut submitted: Vec<String> = Vec::new();
+        // Replacements share one reservation, since only one can be included
+        let mut reservation = None;
+
+        for tip in tips {
+            let (signed, _) = self
+                .executor
+                .sign_with(call, signer, Some(nonce), tip)
+                .await?;
+            // Stop escalating once the tip would take the fee over the cap or budget
+            self.executor
+                .enforce_fee_limits(&signed, tip, signer, &mut reservation)
+                .await?;
+            let tx_hash = TxHash::new(signed.hash().0);
+
+            if let Err(e) = signed.submit().await {
+                if submitted.is_empty() {
+                    if let Some(reservation) = reservation.take() {
+                        reservation.release();
+                    }
+                }
+                return Err(Error::Transaction(if submitted.is_empty() {
+                    format!("Failed to submit transaction: {}", e)
+                } else {
+                    format!(
+                        "Replacement with tip {} refused ({}); nonce {} may already be used by one of {}",
+                        tip,
+                        e,
+                        nonce,
+                        submitted.join(", ")
+                    )
+                }));
+            }
+            info!(
+                tx_hash = %tx_hash,
+                nonce,
+                tip,
+                replaces = submitted.last().map(String::as_str),
+                "Submitted transaction"
+            );
+
+            let watch = self
+                .monitor
+                .watch_inclusion(
+                    tx_hash,
+                    apex_sdk_core::ConfirmationStrategy::Finalized { timeout_secs: 0 },
+                    self.schedule.stuck_after_blocks,
+                    tip,
+                )
+                .await;
+            let status = watch.await.map_err(|_| {
+                Error::Transaction(format!("Watch on transaction {} ended", tx_hash))
+            })?;
+
+            match status.status {
+                // A watch that times out is treated as stuck too
+                apex_sdk_types::TxStatus::Pending | apex_sdk_types::TxStatus::TimedOut => {
+                    self.monitor.cancel_watch(&tx_hash);
+                    warn!(
+                        tx_hash = %tx_hash,
+                        blocks = self.schedule.stuck_after_blocks,
+                        "Transaction stuck, escalating tip"
+                    );
+                    submitted.push(tx_hash.to_string());
+                }
+                apex_sdk_types::TxStatus::Failed => {
+                    return Err(Error::Transaction(format!(
+                        "Transaction {} failed: {}",
+                        tx_hash,
+                        status.error.unwrap_or_default()
+                    )))
+                }
+                _ => {
+                    return Ok(EscalationReceipt {
+                        tx_hash: tx_hash.to_string(),
+                        block_hash: status.block_hash,
+                        block_number: status.block_number,
+                        tip,
+                        nonce,
+                        replaced: submitted,
+                    })
+                }
+            }
+        }
+
+        Err(Error::Transaction(format!(
+            "Transaction not included after {} tip escalations; submitted {}",
+            self.schedule.tips.len(),
+            submitted.join(", ")
+        )))
+    }
+}
+
 #[async_trait]
 impl FeeEstimator for TransactionExecutor {
     async fn estimate_fee(&self, tx: &[u8]) -> std::result::Result<u128, SdkError> {
@@ -650,6 +2766,7 @@ impl FeeEstimator for TransactionExecutor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use subxt::dynamic::Value;
 
     #[test]
     fn test_fee_config() {
@@ -663,6 +2780,496 @@ mod tests {
         assert_eq!(config.tip, 100);
     }
 
+    #[test]
+    fn test_check_max_fee() {
+        let config = FeeConfig::new().with_multiplier(1.2).with_max_fee(1_000);
+
+        // 500 x1.2 x1.3 + 100 = 880
+        assert_eq!(config.check_max_fee(500, 1.3, 100).unwrap(), 880);
+
+        match config.check_max_fee(600, 1.3, 100) {
+            Err(Error::FeeCapExceeded(breakdown)) => {
+                assert_eq!(breakdown.total_fee, 1_036);
+                assert_eq!(breakdown.partial_fee, 600);
+                assert_eq!(breakdown.tip, 100);
+                assert_eq!(breakdown.max_fee, 1_000);
+            }
+            other => panic!("expected FeeCapExceeded, got {:?}", other),
+        }
+
+        // The tip alone can push a fee over the cap
+        assert!(config.check_max_fee(500, 1.0, 500).is_err());
+        assert!(FeeConfig::new().check_max_fee(u128::MAX, 1.0, 1).is_ok());
+    }
+
+    #[test]
+    fn test_spending_limiter() {
+        let limiter = SpendingLimiter::session(1_000);
+        let first = limiter.try_spend("alice", 600).unwrap();
+        assert_eq!(limiter.remaining("alice"), 400);
+        assert_eq!(limiter.remaining("bob"), 1_000);
+
+        match limiter.try_spend("alice", 500) {
+            Err(Error::SpendingLimitExceeded(refusal)) => {
+                assert_eq!(refusal.spent, 600);
+                assert_eq!(refusal.limit, 1_000);
+                assert_eq!(refusal.window, None);
+            }
+            other => panic!("expected SpendingLimitExceeded, got {:?}", other),
+        }
+
+        // A replacement is checked without the fee it replaces
+        let mut second = limiter.try_spend("alice", 300).unwrap();
+        second.replace(400).unwrap();
+        assert!(second.replace(500).is_err());
+        assert_eq!(second.fee(), 400);
+        assert_eq!(limiter.spent("alice"), 1_000);
+
+        first.release();
+        assert_eq!(limiter.spent("alice"), 400);
+
+        // Settling returns the weight refund to the budget
+        second.settle(250);
+        assert_eq!(limiter.spent("alice"), 250);
+
+        let windowed = SpendingLimiter::new(100, Duration::from_millis(20));
+        windowed.try_spend("alice", 100).unwrap();
+        assert!(windowed.try_spend("alice", 1).is_err());
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(windowed.spent("alice"), 0);
+        assert!(windowed.try_spend("alice", 100).is_ok());
+    }
+
+    #[test]
+    fn test_session_limiter_folds_settled_spends() {
+        let limiter = SpendingLimiter::session(1_000);
+        let open_spends = || limiter.ledger.lock().accounts["alice"].spends.len();
+
+        for _ in 0..100 {
+            limiter.try_spend("alice", 1).unwrap().settle(2);
+        }
+        assert_eq!(open_spends(), 0);
+        assert_eq!(limiter.spent("alice"), 200);
+
+        // A dropped reservation stays counted at its reserved fee
+        drop(limiter.try_spend("alice", 300).unwrap());
+        assert_eq!(open_spends(), 0);
+        assert_eq!(limiter.spent("alice"), 500);
+
+        // Only open reservations are kept, so they can still be released
+        let open = limiter.try_spend("alice", 400).unwrap();
+        assert_eq!(open_spends(), 1);
+        open.release();
+        assert_eq!(open_spends(), 0);
+        assert_eq!(limiter.remaining("alice"), 500);
+    }
+
+    #[test]
+    fn test_batch_builder_defers_invalid_calls() {
+        let builder = BatchBuilder::new()
+            .with_mode(BatchMode::AllOrNothing)
+            .remark(b"hello".to_vec())
+            .transfer("not-an-address", 10)
+            .call(BatchCall::new(0, 1, vec![]));
+
+        assert_eq!(builder.len(), 3);
+        assert_eq!(builder.mode(), BatchMode::AllOrNothing);
+        assert!(
+            matches!(&builder.entries[1], BatchEntry::Invalid(reason) if reason.contains("recipient"))
+        );
+        assert!(BatchBuilder::new().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_indices() {
+        let metadata = crate::event_filter::test_utils::metadata();
+
+        let raw = BatchCall::new(5, 3, vec![]);
+        assert_eq!(raw.resolve_indices(&metadata).unwrap(), (5, 3));
+
+        let unknown_pallet = BatchCall::named("Nfts", "mint", vec![]);
+        assert!(matches!(
+            unknown_pallet.resolve_indices(&metadata),
+            Err(Error::Metadata(msg)) if msg.contains("Pallet Nfts")
+        ));
+
+        let unknown_call = BatchCall::named("Balances", "transfer_all", vec![]);
+        assert!(matches!(
+            unknown_call.resolve_indices(&metadata),
+            Err(Error::Metadata(msg)) if msg.contains("Balances::transfer_all")
+        ));
+    }
+
+    #[test]
+    fn test_escalation_schedule() {
+        let schedule = EscalationSchedule::geometric(5, 1_000, 50, 3).unwrap();
+        assert_eq!(schedule.tips(), &[1_000, 1_500, 2_250]);
+        assert_eq!(schedule.stuck_after_blocks(), 5);
+
+        // Zero tips still increase
+        let schedule = EscalationSchedule::geometric(5, 0, 10, 3).unwrap();
+        assert_eq!(schedule.tips(), &[0, 1, 2]);
+
+        assert!(EscalationSchedule::new(5, vec![10, 10]).is_err());
+        assert!(EscalationSchedule::new(0, vec![10]).is_err());
+        assert!(EscalationSchedule::new(5, vec![]).is_ok());
+    }
+
+    #[test]
+    fn test_mortal_era_window() {
+        let era = MortalEra::new(64, 1_000, [0u8; 32]);
+        assert_eq!(era.period, 64);
+        assert_eq!(era.phase, 1_000 % 64);
+        assert_eq!(era.birth_block(), 1_000);
+        assert_eq!(era.death_block(), 1_064);
+        assert!(!era.is_expired_at(1_063));
+        assert!(era.is_expired_at(1_064));
+
+        // Periods round up to a power of two, at least 4
+        assert_eq!(MortalEra::new(100, 1_000, [0u8; 32]).period, 128);
+        assert_eq!(MortalEra::new(1, 1_000, [0u8; 32]).period, 4);
+
+        // Long periods quantize the phase, so the era can start before the checkpoint
+        let era = MortalEra::new(65_536, 100_005, [0u8; 32]);
+        assert!(era.birth_block() <= 100_005);
+        assert!(era.death_block() > 100_005);
+    }
+
+    #[test]
+    fn test_raw_call_indices() {
+        let call = RawCall::new(vec![5, 3, 1, 2, 3]).unwrap();
+
+        assert_eq!(call.pallet_index(), 5);
+        assert_eq!(call.call_index(), 3);
+        assert_eq!(call.call_data(), &[5, 3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_raw_call_too_short() {
+        assert!(RawCall::new(vec![]).is_err());
+        assert!(RawCall::new(vec![5]).is_err());
+    }
+
+    #[test]
+    fn test_batch_item_statuses_force() {
+        let events = vec![
+            UtilityEvent::ItemCompleted,
+            UtilityEvent::ItemFailed("Balances::InsufficientBalance".to_string()),
+            UtilityEvent::ItemCompleted,
+        ];
+
+        let (statuses, interrupted_at) = batch_item_statuses(3, &events);
+
+        assert_eq!(interrupted_at, None);
+        assert_eq!(
+            statuses,
+            vec![
+                BatchItemStatus::Completed,
+                BatchItemStatus::Failed("Balances::InsufficientBalance".to_string()),
+                BatchItemStatus::Completed,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_batch_item_statuses_interrupted() {
+        let events = vec![
+            UtilityEvent::ItemCompleted,
+            UtilityEvent::BatchInterrupted {
+                index: 1,
+                error: "BadOrigin".to_string(),
+            },
+        ];
+
+        let (statuses, interrupted_at) = batch_item_statuses(4, &events);
+
+        assert_eq!(interrupted_at, Some(1));
+        assert_eq!(
+            statuses,
+            vec![
+                BatchItemStatus::Completed,
+                BatchItemStatus::Failed("BadOrigin".to_string()),
+                BatchItemStatus::NotExecuted,
+                BatchItemStatus::NotExecuted,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_batch_item_statuses_without_item_events() {
+        let (statuses, interrupted_at) = batch_item_statuses(2, &[]);
+
+        assert_eq!(interrupted_at, None);
+        assert_eq!(statuses, vec![BatchItemStatus::Completed; 2]);
+    }
+
+    #[test]
+    fn test_batch_result_indices_and_weights() {
+        let result = BatchResult {
+            tx_hash: "0x01".to_string(),
+            block_hash: "0x02".to_string(),
+            mode: BatchMode::Optimistic,
+            items: vec![
+                BatchItemResult {
+                    index: 0,
+                    status: BatchItemStatus::Completed,
+                    weight: Some(Weight::new(100, 10)),
+                },
+                BatchItemResult {
+                    index: 1,
+                    status: BatchItemStatus::Failed("error".to_string()),
+                    weight: Some(Weight::new(200, 20)),
+                },
+                BatchItemResult {
+                    index: 2,
+                    status: BatchItemStatus::NotExecuted,
+                    weight: Some(Weight::new(300, 30)),
+                },
+            ],
+            interrupted_at: Some(1),
+            actual_weight: None,
+        };
+
+        assert!(!result.is_complete_success());
+        assert_eq!(result.failed_indices(), vec![1]);
+        assert_eq!(result.retry_indices(), vec![1, 2]);
+        assert_eq!(result.estimated_weight(), Some(Weight::new(600, 60)));
+
+        let mut unknown = result.clone();
+        unknown.items[0].weight = None;
+        assert_eq!(unknown.estimated_weight(), None);
+    }
+
+    fn event(pallet: &str, variant: &str, fields: Vec<(&str, Value<()>)>) -> ReceiptEvent {
+        ReceiptEvent {
+            pallet: pallet.to_string(),
+            variant: variant.to_string(),
+            fields: Composite::named(fields.into_iter().map(|(k, v)| (k.to_string(), v))),
+        }
+    }
+
+    #[test]
+    fn test_receipt_extracts_fee_and_weight() {
+        let events = vec![
+            event(
+                "Balances",
+                "Withdraw",
+                vec![
+                    ("who", Value::from_bytes([1u8; 32])),
+                    ("amount", Value::u128(160)),
+                ],
+            ),
+            event(
+                "Balances",
+                "Transfer",
+                vec![
+                    ("from", Value::from_bytes([1u8; 32])),
+                    ("to", Value::from_bytes([2u8; 32])),
+                    ("amount", Value::u128(1_000)),
+                ],
+            ),
+            event(
+                "TransactionPayment",
+                "TransactionFeePaid",
+                vec![
+                    ("who", Value::from_bytes([1u8; 32])),
+                    ("actual_fee", Value::u128(160)),
+                    ("tip", Value::u128(10)),
+                ],
+            ),
+            event(
+                "System",
+                "ExtrinsicSuccess",
+                vec![(
+                    "dispatch_info",
+                    Value::named_composite([(
+                        "weight",
+                        Value::named_composite([
+                            ("ref_time", Value::u128(150_000)),
+                            ("proof_size", Value::u128(3_500)),
+                        ]),
+                    )]),
+                )],
+            ),
+        ];
+
+        let receipt =
+            TransactionReceipt::new("0x01".to_string(), "0x02".to_string(), 42, 2, events);
+
+        assert_eq!(receipt.block_number, 42);
+        assert_eq!(receipt.extrinsic_index, 2);
+        assert_eq!(receipt.actual_fee, Some(160));
+        assert_eq!(receipt.tip, Some(10));
+        assert_eq!(receipt.weight, Some(Weight::new(150_000, 3_500)));
+        assert_eq!(receipt.estimated_fee, None);
+        assert_eq!(receipt.charged_fee, Some(160));
+        assert_eq!(receipt.refunded_fee, Some(0));
+        assert_eq!(receipt.treasury_deposit, None);
+        assert!(receipt.find_event("Balances", "Transfer").is_some());
+        assert!(receipt.find_event("Balances", "Deposit").is_none());
+    }
+
+    #[test]
+    fn test_receipt_with_weight_refund() {
+        let payer = || Value::from_bytes([1u8; 32]);
+        let events = vec![
+            // A transfer withdrawal from another account must not be taken as the fee
+            event(
+                "Balances",
+                "Withdraw",
+                vec![
+                    ("who", Value::from_bytes([3u8; 32])),
+                    ("amount", Value::u128(5_000)),
+                ],
+            ),
+            event(
+                "Balances",
+                "Withdraw",
+                vec![("who", payer()), ("amount", Value::u128(250))],
+            ),
+            event(
+                "Balances",
+                "Deposit",
+                vec![("who", payer()), ("amount", Value::u128(90))],
+            ),
+            event("Treasury", "Deposit", vec![("value", Value::u128(128))]),
+            event(
+                "TransactionPayment",
+                "TransactionFeePaid",
+                vec![
+                    ("who", payer()),
+                    ("actual_fee", Value::u128(160)),
+                    ("tip", Value::u128(10)),
+                ],
+            ),
+        ];
+
+        let receipt =
+            TransactionReceipt::new("0x01".to_string(), "0x02".to_string(), 42, 2, events);
+
+        assert_eq!(receipt.actual_fee, Some(160));
+        assert_eq!(receipt.charged_fee, Some(250));
+        assert_eq!(receipt.refunded_fee, Some(90));
+        assert_eq!(receipt.treasury_deposit, Some(128));
+
+        let metrics = crate::Metrics::new();
+        metrics.record_fee(250, 160);
+        metrics.record_fee(100, 100);
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.fee_payments, 2);
+        assert_eq!(snapshot.fees_charged, 350);
+        assert_eq!(snapshot.fees_refunded, 90);
+        assert_eq!(snapshot.fees_net, 260);
+        assert_eq!(snapshot.avg_net_fee(), 130);
+    }
+
+    #[test]
+    fn test_receipt_without_fee_event() {
+        let receipt = TransactionReceipt::new(
+            "0x01".to_string(),
+            "0x02".to_string(),
+            1,
+            0,
+            vec![event("System", "ExtrinsicSuccess", vec![])],
+        );
+
+        assert_eq!(receipt.actual_fee, None);
+        assert_eq!(receipt.tip, None);
+        assert_eq!(receipt.charged_fee, None);
+        assert_eq!(receipt.refunded_fee, None);
+        assert_eq!(receipt.weight, None);
+    }
+
+    fn dry_run_output(execution_result: Value<()>) -> Value<()> {
+        Value::unnamed_variant(
+            "Ok",
+            [Value::named_composite([
+                ("execution_result", execution_result),
+                (
+                    "emitted_events",
+                    Value::unnamed_composite([Value::unnamed_variant(
+                        "Balances",
+                        [Value::named_variant(
+                            "Transfer",
+                            [
+                                ("from", Value::from_bytes([1u8; 32])),
+                                ("to", Value::from_bytes([2u8; 32])),
+                                ("amount", Value::u128(5)),
+                            ],
+                        )],
+                    )]),
+                ),
+                ("local_xcm", Value::unnamed_variant("None", [])),
+                ("forwarded_xcms", Value::unnamed_composite([])),
+            ])],
+        )
+    }
+
+    #[test]
+    fn test_dry_run_effects_success() {
+        let output = dry_run_output(Value::unnamed_variant(
+            "Ok",
+            [Value::named_composite([(
+                "pays_fee",
+                Value::unnamed_variant("Yes", []),
+            )])],
+        ));
+
+        let effects = dry_run_effects(&output).unwrap();
+        assert!(effects.dispatch_error.is_none());
+        assert_eq!(effects.events.len(), 1);
+        assert!(effects.events[0].is("Balances", "Transfer"));
+        assert_eq!(
+            subxt::dynamic::At::at(&effects.events[0].fields, "amount").and_then(|v| v.as_u128()),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn test_dry_run_effects_dispatch_error() {
+        let error = Value::unnamed_variant("BadOrigin", []);
+        let output = dry_run_output(Value::unnamed_variant(
+            "Err",
+            [Value::named_composite([
+                ("post_info", Value::unnamed_composite([])),
+                ("error", error.clone()),
+            ])],
+        ));
+
+        let effects = dry_run_effects(&output).unwrap();
+        assert_eq!(effects.dispatch_error, Some(&error));
+    }
+
+    #[test]
+    fn test_dry_run_effects_rejected() {
+        let output = Value::unnamed_variant("Err", [Value::unnamed_variant("Unimplemented", [])]);
+        assert!(dry_run_effects(&output)
+            .unwrap_err()
+            .contains("Unimplemented"));
+    }
+
+    #[test]
+    fn test_simulation_result_error() {
+        let mut result = SimulationResult {
+            invalid: None,
+            dispatch_error: None,
+            events: vec![],
+            estimated_fee: Some(100),
+            dry_run: true,
+            deposits: vec![],
+        };
+        assert!(result.is_success());
+        assert_eq!(result.error(), None);
+        assert_eq!(result.total_deposit(), 0);
+
+        result.dispatch_error = Some("Balances::InsufficientBalance".to_string());
+        assert!(!result.is_success());
+        assert_eq!(result.error(), Some("Balances::InsufficientBalance"));
+
+        result.invalid = Some(describe_invalid(&TransactionInvalid::Payment));
+        assert!(result.error().unwrap().contains("fee"));
+    }
+
     #[test]
     fn test_retry_config() {
         let config = RetryConfig::new()
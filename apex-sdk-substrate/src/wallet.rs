@@ -16,20 +16,24 @@
 //! - Use `Arc<Wallet>` for shared access
 //! - Ensure wallets are dropped when no longer needed
 
+use crate::signing_log::SigningLog;
 use crate::{Error, Result};
+use apex_sdk_core::blocking::BlockingPool;
 use apex_sdk_core::{SdkError, Signer as CoreSigner};
 use apex_sdk_types::Address;
 use async_trait::async_trait;
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
 use sp_core::{ed25519, sr25519, Pair as PairTrait};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::{debug, info};
+use tracing::{debug, error, info};
 use zeroize::Zeroize;
 
 /// Supported key pair types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum KeyPairType {
     /// SR25519 (Schnorrkel) - Default for Substrate
     #[default]
@@ -84,6 +88,8 @@ pub struct Wallet {
     ed25519_pair: Option<ed25519::Pair>,
     /// SS58 address format (network prefix)
     ss58_format: Ss58AddressFormat,
+    /// Log receiving a receipt for every signature
+    signing_log: Option<Arc<SigningLog>>,
 }
 
 impl Wallet {
@@ -104,6 +110,7 @@ impl Wallet {
                     sr25519_pair: Some(pair),
                     ed25519_pair: None,
                     ss58_format: Ss58AddressFormat::custom(42), // Default to generic
+                    signing_log: None,
                 }
             }
             KeyPairType::Ed25519 => {
@@ -113,6 +120,7 @@ impl Wallet {
                     sr25519_pair: None,
                     ed25519_pair: Some(pair),
                     ss58_format: Ss58AddressFormat::custom(42),
+                    signing_log: None,
                 }
             }
         }
@@ -123,6 +131,27 @@ impl Wallet {
         Self::from_mnemonic_with_path(mnemonic, None, key_type)
     }
 
+    /// Create wallet from mnemonic phrase without blocking the async executor
+    ///
+    /// Key derivation runs on [`BlockingPool::global`].
+    pub async fn from_mnemonic_async(mnemonic: &str, key_type: KeyPairType) -> Result<Self> {
+        Self::from_mnemonic_with_path_async(mnemonic, None, key_type).await
+    }
+
+    /// Create wallet from mnemonic phrase with derivation path on the blocking pool
+    pub async fn from_mnemonic_with_path_async(
+        mnemonic: &str,
+        path: Option<&str>,
+        key_type: KeyPairType,
+    ) -> Result<Self> {
+        let mnemonic = zeroize::Zeroizing::new(mnemonic.to_string());
+        let path = path.map(str::to_string);
+        BlockingPool::global()
+            .run(move || Self::from_mnemonic_with_path(&mnemonic, path.as_deref(), key_type))
+            .await
+            .map_err(|e| Error::Wallet(e.to_string()))?
+    }
+
     /// Create wallet from mnemonic phrase with derivation path
     pub fn from_mnemonic_with_path(
         mnemonic: &str,
@@ -152,6 +181,7 @@ impl Wallet {
                     sr25519_pair: Some(pair),
                     ed25519_pair: None,
                     ss58_format: Ss58AddressFormat::custom(42),
+                    signing_log: None,
                 })
             }
             KeyPairType::Ed25519 => {
@@ -163,6 +193,7 @@ impl Wallet {
                     sr25519_pair: None,
                     ed25519_pair: Some(pair),
                     ss58_format: Ss58AddressFormat::custom(42),
+                    signing_log: None,
                 })
             }
         }
@@ -187,6 +218,7 @@ impl Wallet {
                     sr25519_pair: Some(pair),
                     ed25519_pair: None,
                     ss58_format: Ss58AddressFormat::custom(42),
+                    signing_log: None,
                 })
             }
             KeyPairType::Ed25519 => {
@@ -196,6 +228,7 @@ impl Wallet {
                     sr25519_pair: None,
                     ed25519_pair: Some(pair),
                     ss58_format: Ss58AddressFormat::custom(42),
+                    signing_log: None,
                 })
             }
         };
@@ -226,6 +259,73 @@ impl Wallet {
         self
     }
 
+    /// Record a receipt in `log` for every signature made with this wallet,
+    /// including transactions signed through [`to_subxt_signer`](Self::to_subxt_signer)
+    pub fn with_signing_log(mut self, log: Arc<SigningLog>) -> Self {
+        self.signing_log = Some(log);
+        self
+    }
+
+    /// Get the signing log (if configured)
+    pub fn signing_log(&self) -> Option<&Arc<SigningLog>> {
+        self.signing_log.as_ref()
+    }
+
+    /// Get the SS58 address format (network prefix)
+    pub fn ss58_prefix(&self) -> u16 {
+        self.ss58_format.prefix()
+    }
+
+    /// Create wallet from raw secret key material as produced by [`Wallet::secret_bytes`]
+    ///
+    /// SR25519 expects the 64-byte expanded secret key, ED25519 the 32-byte seed.
+    pub(crate) fn from_secret_bytes(secret: &[u8], key_type: KeyPairType) -> Result<Self> {
+        match key_type {
+            KeyPairType::Sr25519 => {
+                let pair = sr25519::Pair::from_seed_slice(secret)
+                    .map_err(|e| Error::Wallet(format!("Invalid SR25519 secret: {:?}", e)))?;
+                Ok(Self {
+                    key_type,
+                    sr25519_pair: Some(pair),
+                    ed25519_pair: None,
+                    ss58_format: Ss58AddressFormat::custom(42),
+                    signing_log: None,
+                })
+            }
+            KeyPairType::Ed25519 => {
+                let pair = ed25519::Pair::from_seed_slice(secret)
+                    .map_err(|e| Error::Wallet(format!("Invalid ED25519 secret: {:?}", e)))?;
+                Ok(Self {
+                    key_type,
+                    sr25519_pair: None,
+                    ed25519_pair: Some(pair),
+                    ss58_format: Ss58AddressFormat::custom(42),
+                    signing_log: None,
+                })
+            }
+        }
+    }
+
+    /// Get the raw secret key material
+    ///
+    /// Returns the 64-byte expanded secret key for SR25519 and the 32-byte seed for
+    /// ED25519. The buffer is zeroed on drop.
+    pub(crate) fn secret_bytes(&self) -> zeroize::Zeroizing<Vec<u8>> {
+        let raw = match self.key_type {
+            KeyPairType::Sr25519 => self
+                .sr25519_pair
+                .as_ref()
+                .expect("SR25519 pair must exist for SR25519 key type")
+                .to_raw_vec(),
+            KeyPairType::Ed25519 => self
+                .ed25519_pair
+                .as_ref()
+                .expect("ED25519 pair must exist for ED25519 key type")
+                .to_raw_vec(),
+        };
+        zeroize::Zeroizing::new(raw)
+    }
+
     /// Get the public key as bytes
     pub fn public_key(&self) -> Vec<u8> {
         match self.key_type {
@@ -274,7 +374,32 @@ impl Wallet {
     }
 
     /// Sign a message
+    ///
+    /// # Panics
+    ///
+    /// With a signing log attached, if the receipt cannot be recorded; use
+    /// [`try_sign`](Self::try_sign) to handle that as an error.
     pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        match self.try_sign(message) {
+            Ok(signature) => signature,
+            Err(e) => panic!("Refusing to sign: {}", e),
+        }
+    }
+
+    /// Sign a message, failing without a signature if the signing log
+    /// cannot record its receipt
+    pub fn try_sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        if let Some(log) = &self.signing_log {
+            log.record(self.address(), message).map_err(|e| {
+                error!("Failed to record signing receipt: {}", e);
+                Error::Signature(format!("Signing receipt not recorded: {}", e))
+            })?;
+        }
+
+        Ok(self.sign_unlogged(message))
+    }
+
+    fn sign_unlogged(&self, message: &[u8]) -> Vec<u8> {
         match self.key_type {
             KeyPairType::Sr25519 => {
                 let pair = self
@@ -295,35 +420,40 @@ impl Wallet {
 
     /// Verify a signature
     pub fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        self.verifying_key().verify(message, signature)
+    }
+
+    /// Verify many `(message, signature)` pairs on the blocking pool
+    ///
+    /// Returns one result per pair, in order. Use this instead of calling
+    /// [`Wallet::verify`] in a loop from async code.
+    pub async fn verify_batch(&self, items: Vec<(Vec<u8>, Vec<u8>)>) -> Result<Vec<bool>> {
+        let key = self.verifying_key();
+        BlockingPool::global()
+            .run(move || {
+                items
+                    .iter()
+                    .map(|(message, signature)| key.verify(message, signature))
+                    .collect()
+            })
+            .await
+            .map_err(|e| Error::Wallet(e.to_string()))
+    }
+
+    fn verifying_key(&self) -> VerifyingKey {
         match self.key_type {
-            KeyPairType::Sr25519 => {
-                if signature.len() != 64 {
-                    return false;
-                }
-                let mut sig_array = [0u8; 64];
-                sig_array.copy_from_slice(signature);
-                let sig = sr25519::Signature::from_raw(sig_array);
-                let public = self
-                    .sr25519_pair
+            KeyPairType::Sr25519 => VerifyingKey::Sr25519(
+                self.sr25519_pair
                     .as_ref()
                     .expect("SR25519 pair must exist for SR25519 key type")
-                    .public();
-                sr25519::Pair::verify(&sig, message, &public)
-            }
-            KeyPairType::Ed25519 => {
-                if signature.len() != 64 {
-                    return false;
-                }
-                let mut sig_array = [0u8; 64];
-                sig_array.copy_from_slice(signature);
-                let sig = ed25519::Signature::from_raw(sig_array);
-                let public = self
-                    .ed25519_pair
+                    .public(),
+            ),
+            KeyPairType::Ed25519 => VerifyingKey::Ed25519(
+                self.ed25519_pair
                     .as_ref()
                     .expect("ED25519 pair must exist for ED25519 key type")
-                    .public();
-                ed25519::Pair::verify(&sig, message, &public)
-            }
+                    .public(),
+            ),
         }
     }
 
@@ -355,7 +485,7 @@ impl Wallet {
     /// Convert the wallet to a subxt-compatible signer
     #[allow(clippy::clone_on_copy)]
     pub fn to_subxt_signer(&self) -> crate::signer::ApexSigner {
-        match self.key_type {
+        let signer = match self.key_type {
             KeyPairType::Sr25519 => {
                 let pair = self.sr25519_pair.as_ref().expect("SR25519 pair missing");
                 crate::signer::ApexSigner::Sr25519(Box::new(crate::signer::Sr25519Signer::new(
@@ -368,6 +498,11 @@ impl Wallet {
                     pair.clone(),
                 )))
             }
+        };
+
+        match &self.signing_log {
+            Some(log) => signer.with_signing_log(log.clone()),
+            None => signer,
         }
     }
 }
@@ -387,17 +522,46 @@ impl Drop for Wallet {
         // Log wallet cleanup for security auditing
         // Note: sp_core::Pair types handle actual memory zeroing internally
         debug!(
-            "Dropping wallet of type {:?} at address {}",
-            self.key_type,
-            self.address()
+            key_type = ?self.key_type,
+            wallet = %self.address(),
+            "Dropping wallet"
         );
     }
 }
 
+/// Public half of a wallet, movable onto blocking threads
+pub(crate) enum VerifyingKey {
+    Sr25519(sr25519::Public),
+    Ed25519(ed25519::Public),
+}
+
+impl VerifyingKey {
+    pub(crate) fn from_public(key_type: KeyPairType, public: [u8; 32]) -> Self {
+        match key_type {
+            KeyPairType::Sr25519 => VerifyingKey::Sr25519(sr25519::Public::from_raw(public)),
+            KeyPairType::Ed25519 => VerifyingKey::Ed25519(ed25519::Public::from_raw(public)),
+        }
+    }
+
+    pub(crate) fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        let Ok(sig_array) = <[u8; 64]>::try_from(signature) else {
+            return false;
+        };
+        match self {
+            VerifyingKey::Sr25519(public) => {
+                sr25519::Pair::verify(&sr25519::Signature::from_raw(sig_array), message, public)
+            }
+            VerifyingKey::Ed25519(public) => {
+                ed25519::Pair::verify(&ed25519::Signature::from_raw(sig_array), message, public)
+            }
+        }
+    }
+}
+
 #[async_trait]
 impl CoreSigner for Wallet {
     async fn sign_transaction(&self, tx: &[u8]) -> std::result::Result<Vec<u8>, SdkError> {
-        Ok(self.sign(tx))
+        Ok(self.try_sign(tx)?)
     }
 
     fn address(&self) -> Address {
@@ -433,7 +597,7 @@ impl WalletManager {
         let wallet = Wallet::new_random_with_type(self.default_key_type);
         let name = name.into();
 
-        debug!("Creating wallet '{}' at address {}", name, wallet.address());
+        debug!(name, address = %wallet.address(), "Creating wallet");
 
         self.wallets.write().insert(name.clone(), wallet.clone());
         wallet
@@ -442,7 +606,7 @@ impl WalletManager {
     /// Add an existing wallet
     pub fn add_wallet(&self, name: impl Into<String>, wallet: Wallet) {
         let name = name.into();
-        debug!("Adding wallet '{}' at address {}", name, wallet.address());
+        debug!(name, address = %wallet.address(), "Adding wallet");
         self.wallets.write().insert(name, wallet);
     }
 
@@ -513,6 +677,40 @@ mod tests {
         assert!(!wallet.verify(b"Different message", &signature));
     }
 
+    #[tokio::test]
+    async fn test_verify_batch() {
+        let wallet = Wallet::new_random();
+        let signature = wallet.sign(b"first");
+
+        let results = wallet
+            .verify_batch(vec![
+                (b"first".to_vec(), signature.clone()),
+                (b"second".to_vec(), signature),
+                (b"first".to_vec(), vec![0u8; 12]),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(results, vec![true, false, false]);
+    }
+
+    #[tokio::test]
+    async fn test_from_mnemonic_async_matches_sync() {
+        let mnemonic = Wallet::generate_mnemonic().unwrap();
+        let sync =
+            Wallet::from_mnemonic_with_path(&mnemonic, Some("0"), KeyPairType::Sr25519).unwrap();
+        let derived =
+            Wallet::from_mnemonic_with_path_async(&mnemonic, Some("0"), KeyPairType::Sr25519)
+                .await
+                .unwrap();
+        assert_eq!(derived.address(), sync.address());
+
+        assert!(
+            Wallet::from_mnemonic_async("not a mnemonic", KeyPairType::Sr25519)
+                .await
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_generate_mnemonic() {
         let mnemonic = Wallet::generate_mnemonic().unwrap();
@@ -685,4 +883,37 @@ mod tests {
         assert!(ed25519_wallet.verify(message, &ed25519_sig));
         assert_eq!(ed25519_sig.len(), 64);
     }
+
+    #[test]
+    fn test_signing_log_records_wallet_signatures() {
+        use subxt::tx::Signer;
+
+        let log = Arc::new(SigningLog::new());
+        let wallet = Wallet::new_random().with_signing_log(log.clone());
+
+        wallet.sign(b"first");
+        wallet.to_subxt_signer().sign(b"second");
+
+        let receipts = log.receipts();
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].signer, wallet.address());
+        assert_eq!(receipts[1].signer, wallet.address());
+        assert_eq!(receipts[1].prev_hash, receipts[0].hash);
+        log.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wallet_refuses_to_sign_without_receipt() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = Arc::new(SigningLog::open(dir.path().join("signatures.jsonl")).unwrap());
+        drop(dir);
+        let wallet = Wallet::new_random().with_signing_log(log.clone());
+
+        assert!(matches!(
+            wallet.try_sign(b"payload"),
+            Err(Error::Signature(_))
+        ));
+        assert!(wallet.sign_transaction(b"payload").await.is_err());
+        assert!(log.is_empty());
+    }
 }
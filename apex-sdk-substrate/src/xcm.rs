@@ -30,7 +30,7 @@
 //!     .await?;
 //! ```
 
-use crate::{Error, Result, Sr25519Signer, Wallet};
+use crate::{Error, Result, Wallet};
 use subxt::{OnlineClient, PolkadotConfig};
 use tracing::{debug, info};
 
@@ -74,6 +74,29 @@ pub struct MultiLocation {
 }
 
 impl MultiLocation {
+    /// Create a MultiLocation pointing to the current chain
+    pub fn here() -> Self {
+        Self::new(0, vec![])
+    }
+
+    /// Start building a MultiLocation junction by junction
+    ///
+    /// ```rust
+    /// use apex_sdk_substrate::MultiLocation;
+    ///
+    /// let location = MultiLocation::builder()
+    ///     .parents(1)
+    ///     .parachain(1000)
+    ///     .account_id32([1u8; 32])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(location.parachain_id(), Some(1000));
+    /// ```
+    pub fn builder() -> MultiLocationBuilder {
+        MultiLocationBuilder::default()
+    }
+
     /// Create a MultiLocation pointing to the parent (relay chain)
     pub fn parent() -> Self {
         Self {
@@ -142,6 +165,72 @@ impl MultiLocation {
     }
 }
 
+/// Maximum number of interior junctions in a MultiLocation
+pub const MAX_JUNCTIONS: usize = 8;
+
+/// Typed builder for [`MultiLocation`]
+#[derive(Debug, Clone, Default)]
+pub struct MultiLocationBuilder {
+    parents: u8,
+    interior: Vec<Junction>,
+}
+
+impl MultiLocationBuilder {
+    /// Set the number of parent levels
+    pub fn parents(mut self, parents: u8) -> Self {
+        self.parents = parents;
+        self
+    }
+
+    /// Go up one level (towards the relay chain)
+    pub fn parent(mut self) -> Self {
+        self.parents = self.parents.saturating_add(1);
+        self
+    }
+
+    /// Append a junction
+    pub fn junction(mut self, junction: Junction) -> Self {
+        self.interior.push(junction);
+        self
+    }
+
+    /// Append a parachain junction
+    pub fn parachain(self, para_id: u32) -> Self {
+        self.junction(Junction::Parachain(para_id))
+    }
+
+    /// Append a 32-byte account junction
+    pub fn account_id32(self, id: [u8; 32]) -> Self {
+        self.junction(Junction::AccountId32 { network: None, id })
+    }
+
+    /// Append a 20-byte (EVM) account junction
+    pub fn account_key20(self, key: [u8; 20]) -> Self {
+        self.junction(Junction::AccountId20 { network: None, key })
+    }
+
+    /// Append a pallet instance junction
+    pub fn pallet_instance(self, instance: u8) -> Self {
+        self.junction(Junction::PalletInstance(instance))
+    }
+
+    /// Append a general index junction
+    pub fn general_index(self, index: u128) -> Self {
+        self.junction(Junction::GeneralIndex(index))
+    }
+
+    /// Build the location
+    pub fn build(self) -> Result<MultiLocation> {
+        if self.interior.len() > MAX_JUNCTIONS {
+            return Err(Error::Transaction(format!(
+                "Too many junctions (max {})",
+                MAX_JUNCTIONS
+            )));
+        }
+        Ok(MultiLocation::new(self.parents, self.interior))
+    }
+}
+
 /// Interior junction types for multi-location
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Junction {
@@ -313,11 +402,16 @@ impl XcmExecutor {
         beneficiary: [u8; 32],
         assets: Vec<XcmAsset>,
     ) -> Result<String> {
-        info!("Executing reserve transfer to {:?} for beneficiary", dest);
+        info!(
+            operation = "xcm_reserve_transfer",
+            wallet = %wallet.address(),
+            dest = ?dest,
+            "Executing reserve transfer"
+        );
 
         // Build the reserve transfer call using dynamic API
-        let dest_value = self.encode_multilocation(&dest)?;
-        let beneficiary_value = self.encode_multilocation(&MultiLocation::account(beneficiary))?;
+        let dest_value = self.encode_location(&dest)?;
+        let beneficiary_value = self.encode_location(&MultiLocation::account(beneficiary))?;
         let assets_value = self.encode_assets(&assets)?;
         let fee_index = 0u32; // Use first asset for fees
 
@@ -355,10 +449,15 @@ impl XcmExecutor {
         beneficiary: [u8; 32],
         assets: Vec<XcmAsset>,
     ) -> Result<String> {
-        info!("Executing teleport to {:?} for beneficiary", dest);
+        info!(
+            operation = "xcm_teleport",
+            wallet = %wallet.address(),
+            dest = ?dest,
+            "Executing teleport"
+        );
 
-        let dest_value = self.encode_multilocation(&dest)?;
-        let beneficiary_value = self.encode_multilocation(&MultiLocation::account(beneficiary))?;
+        let dest_value = self.encode_location(&dest)?;
+        let beneficiary_value = self.encode_location(&MultiLocation::account(beneficiary))?;
         let assets_value = self.encode_assets(&assets)?;
         let fee_index = 0u32;
 
@@ -386,7 +485,7 @@ impl XcmExecutor {
         beneficiary: [u8; 32],
         amount: u128,
     ) -> Result<String> {
-        debug!("Transferring {} to relay chain", amount);
+        debug!(amount, "Transferring to relay chain");
 
         self.reserve_transfer(
             wallet,
@@ -407,7 +506,7 @@ impl XcmExecutor {
         beneficiary: [u8; 32],
         amount: u128,
     ) -> Result<String> {
-        debug!("Transferring {} to parachain {}", amount, para_id);
+        debug!(amount, para_id, "Transferring to parachain");
 
         self.reserve_transfer(
             wallet,
@@ -420,136 +519,12 @@ impl XcmExecutor {
 
     // Helper methods for encoding XCM types
 
-    fn encode_multilocation(&self, location: &MultiLocation) -> Result<subxt::dynamic::Value> {
-        // Encode MultiLocation as composite value
-        // Structure: { parents: u8, interior: Junctions }
-
-        let interior = self.encode_junctions(&location.interior)?;
-
-        Ok(subxt::dynamic::Value::named_composite([
-            (
-                "parents",
-                subxt::dynamic::Value::u128(location.parents as u128),
-            ),
-            ("interior", interior),
-        ]))
-    }
-
-    fn encode_junctions(&self, junctions: &[Junction]) -> Result<subxt::dynamic::Value> {
-        if junctions.is_empty() {
-            // X0 (Here) variant
-            return Ok(subxt::dynamic::Value::unnamed_variant("Here", vec![]));
-        }
-
-        // Encode junctions as nested X1, X2, etc.
-        let encoded_junctions: Vec<subxt::dynamic::Value> = junctions
-            .iter()
-            .map(|j| self.encode_junction(j))
-            .collect::<Result<Vec<_>>>()?;
-
-        // Use appropriate variant based on number of junctions
-        let variant_name = match junctions.len() {
-            1 => "X1",
-            2 => "X2",
-            3 => "X3",
-            4 => "X4",
-            5 => "X5",
-            6 => "X6",
-            7 => "X7",
-            8 => "X8",
-            _ => return Err(Error::Transaction("Too many junctions (max 8)".to_string())),
-        };
-
-        Ok(subxt::dynamic::Value::unnamed_variant(
-            variant_name,
-            encoded_junctions,
-        ))
-    }
-
-    fn encode_junction(&self, junction: &Junction) -> Result<subxt::dynamic::Value> {
-        match junction {
-            Junction::Parachain(id) => Ok(subxt::dynamic::Value::unnamed_variant(
-                "Parachain",
-                vec![subxt::dynamic::Value::u128(*id as u128)],
-            )),
-            Junction::AccountId32 { network, id } => {
-                let network_value = if let Some(_net) = network {
-                    // Encode network if present
-                    subxt::dynamic::Value::unnamed_variant("Some", vec![])
-                } else {
-                    subxt::dynamic::Value::unnamed_variant("None", vec![])
-                };
-
-                Ok(subxt::dynamic::Value::unnamed_variant(
-                    "AccountId32",
-                    vec![network_value, subxt::dynamic::Value::from_bytes(id)],
-                ))
-            }
-            Junction::AccountId20 { network, key } => {
-                let network_value = if let Some(_net) = network {
-                    subxt::dynamic::Value::unnamed_variant("Some", vec![])
-                } else {
-                    subxt::dynamic::Value::unnamed_variant("None", vec![])
-                };
-
-                Ok(subxt::dynamic::Value::unnamed_variant(
-                    "AccountId20",
-                    vec![network_value, subxt::dynamic::Value::from_bytes(key)],
-                ))
-            }
-            Junction::GeneralIndex(index) => Ok(subxt::dynamic::Value::unnamed_variant(
-                "GeneralIndex",
-                vec![subxt::dynamic::Value::u128(*index)],
-            )),
-            Junction::GeneralKey { data } => Ok(subxt::dynamic::Value::unnamed_variant(
-                "GeneralKey",
-                vec![subxt::dynamic::Value::from_bytes(data)],
-            )),
-            Junction::PalletInstance(instance) => Ok(subxt::dynamic::Value::unnamed_variant(
-                "PalletInstance",
-                vec![subxt::dynamic::Value::u128(*instance as u128)],
-            )),
-        }
+    fn encode_location(&self, location: &MultiLocation) -> Result<subxt::dynamic::Value> {
+        encode_versioned_location(location, self.config.version)
     }
 
     fn encode_assets(&self, assets: &[XcmAsset]) -> Result<subxt::dynamic::Value> {
-        let encoded_assets: Vec<subxt::dynamic::Value> = assets
-            .iter()
-            .map(|asset| {
-                let id_value = match &asset.id {
-                    AssetId::Concrete(location) => {
-                        let loc = self.encode_multilocation(location)?;
-                        subxt::dynamic::Value::unnamed_variant("Concrete", vec![loc])
-                    }
-                    AssetId::Abstract(data) => subxt::dynamic::Value::unnamed_variant(
-                        "Abstract",
-                        vec![subxt::dynamic::Value::from_bytes(data)],
-                    ),
-                };
-
-                let fun_value = match asset.fun {
-                    Fungibility::Fungible(amount) => subxt::dynamic::Value::unnamed_variant(
-                        "Fungible",
-                        vec![subxt::dynamic::Value::u128(amount)],
-                    ),
-                    Fungibility::NonFungible(instance) => subxt::dynamic::Value::unnamed_variant(
-                        "NonFungible",
-                        vec![subxt::dynamic::Value::u128(instance)],
-                    ),
-                };
-
-                Ok(subxt::dynamic::Value::named_composite([
-                    ("id", id_value),
-                    ("fun", fun_value),
-                ]))
-            })
-            .collect::<Result<Vec<_>>>()?;
-
-        // Wrap in VersionedAssets::V3
-        Ok(subxt::dynamic::Value::unnamed_variant(
-            "V3",
-            vec![subxt::dynamic::Value::unnamed_composite(encoded_assets)],
-        ))
+        encode_versioned_assets(assets, self.config.version)
     }
 
     fn encode_weight_limit(&self) -> Result<subxt::dynamic::Value> {
@@ -570,16 +545,18 @@ impl XcmExecutor {
     {
         debug!("Submitting XCM extrinsic");
 
-        let pair = wallet
-            .sr25519_pair()
-            .ok_or_else(|| Error::Transaction("Wallet does not have SR25519 key".to_string()))?;
+        if wallet.sr25519_pair().is_none() {
+            return Err(Error::Transaction(
+                "Wallet does not have SR25519 key".to_string(),
+            ));
+        }
 
-        let signer = Sr25519Signer::new(pair.clone());
+        let signer = wallet.to_subxt_signer();
 
-        let mut progress = self
-            .client
-            .tx()
-            .sign_and_submit_then_watch_default(call, &signer)
+        let mut progress = signer
+            .create_signed(&self.client, call, Default::default())
+            .await?
+            .submit_and_watch()
             .await
             .map_err(|e| Error::Transaction(format!("Failed to submit XCM transaction: {}", e)))?;
 
@@ -594,7 +571,7 @@ impl XcmExecutor {
 
             if let Some(finalized) = event.as_finalized() {
                 let tx_hash = format!("0x{}", hex::encode(finalized.extrinsic_hash()));
-                info!("XCM transaction finalized: {}", tx_hash);
+                info!(tx_hash = %tx_hash, "XCM transaction finalized");
 
                 finalized
                     .wait_for_success()
@@ -611,9 +588,187 @@ impl XcmExecutor {
     }
 }
 
+/// Encode a MultiLocation wrapped in `VersionedLocation`
+///
+/// XCM v2 is encoded as v3, which every current runtime accepts.
+pub(crate) fn encode_versioned_location(
+    location: &MultiLocation,
+    version: XcmVersion,
+) -> Result<subxt::dynamic::Value> {
+    Ok(subxt::dynamic::Value::unnamed_variant(
+        version_variant(version),
+        vec![encode_multilocation(location, version)?],
+    ))
+}
+
+/// Encode assets wrapped in `VersionedAssets`
+pub(crate) fn encode_versioned_assets(
+    assets: &[XcmAsset],
+    version: XcmVersion,
+) -> Result<subxt::dynamic::Value> {
+    Ok(subxt::dynamic::Value::unnamed_variant(
+        version_variant(version),
+        vec![encode_assets(assets, version)?],
+    ))
+}
+
+/// Encode assets as an unversioned `Assets` list
+pub(crate) fn encode_assets(
+    assets: &[XcmAsset],
+    version: XcmVersion,
+) -> Result<subxt::dynamic::Value> {
+    let encoded_assets: Vec<subxt::dynamic::Value> = assets
+        .iter()
+        .map(|asset| encode_asset(asset, version))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(subxt::dynamic::Value::unnamed_composite(encoded_assets))
+}
+
+/// Encode a single asset as `{ id, fun }`
+pub(crate) fn encode_asset(asset: &XcmAsset, version: XcmVersion) -> Result<subxt::dynamic::Value> {
+    let id_value = match (&asset.id, version) {
+        // v4 replaced `Concrete(location)` with the `AssetId(location)` newtype
+        (AssetId::Concrete(location), XcmVersion::V4) => {
+            subxt::dynamic::Value::unnamed_composite([encode_multilocation(location, version)?])
+        }
+        (AssetId::Concrete(location), _) => subxt::dynamic::Value::unnamed_variant(
+            "Concrete",
+            vec![encode_multilocation(location, version)?],
+        ),
+        (AssetId::Abstract(_), XcmVersion::V4) => {
+            return Err(Error::Transaction(
+                "Abstract asset IDs are not supported in XCM v4".to_string(),
+            ))
+        }
+        (AssetId::Abstract(data), _) => subxt::dynamic::Value::unnamed_variant(
+            "Abstract",
+            vec![subxt::dynamic::Value::from_bytes(data)],
+        ),
+    };
+
+    let fun_value = match asset.fun {
+        Fungibility::Fungible(amount) => subxt::dynamic::Value::unnamed_variant(
+            "Fungible",
+            vec![subxt::dynamic::Value::u128(amount)],
+        ),
+        Fungibility::NonFungible(instance) => subxt::dynamic::Value::unnamed_variant(
+            "NonFungible",
+            vec![subxt::dynamic::Value::u128(instance)],
+        ),
+    };
+
+    Ok(subxt::dynamic::Value::named_composite([
+        ("id", id_value),
+        ("fun", fun_value),
+    ]))
+}
+
+pub(crate) fn version_variant(version: XcmVersion) -> &'static str {
+    match version {
+        XcmVersion::V2 | XcmVersion::V3 => "V3",
+        XcmVersion::V4 => "V4",
+    }
+}
+
+/// Encode a MultiLocation as `{ parents, interior }`
+///
+/// XCM v4 represents `X1`..`X8` as arrays rather than tuples.
+pub(crate) fn encode_multilocation(
+    location: &MultiLocation,
+    version: XcmVersion,
+) -> Result<subxt::dynamic::Value> {
+    let interior = encode_junctions(&location.interior, version)?;
+
+    Ok(subxt::dynamic::Value::named_composite([
+        (
+            "parents",
+            subxt::dynamic::Value::u128(location.parents as u128),
+        ),
+        ("interior", interior),
+    ]))
+}
+
+fn encode_junctions(junctions: &[Junction], version: XcmVersion) -> Result<subxt::dynamic::Value> {
+    if junctions.is_empty() {
+        // X0 (Here) variant
+        return Ok(subxt::dynamic::Value::unnamed_variant("Here", vec![]));
+    }
+
+    if junctions.len() > MAX_JUNCTIONS {
+        return Err(Error::Transaction(format!(
+            "Too many junctions (max {})",
+            MAX_JUNCTIONS
+        )));
+    }
+
+    let encoded_junctions: Vec<subxt::dynamic::Value> = junctions
+        .iter()
+        .map(encode_junction)
+        .collect::<Result<Vec<_>>>()?;
+
+    let variant_name = format!("X{}", junctions.len());
+
+    Ok(match version {
+        XcmVersion::V4 => subxt::dynamic::Value::unnamed_variant(
+            variant_name,
+            vec![subxt::dynamic::Value::unnamed_composite(encoded_junctions)],
+        ),
+        XcmVersion::V2 | XcmVersion::V3 => {
+            subxt::dynamic::Value::unnamed_variant(variant_name, encoded_junctions)
+        }
+    })
+}
+
+fn encode_junction(junction: &Junction) -> Result<subxt::dynamic::Value> {
+    match junction {
+        Junction::Parachain(id) => Ok(subxt::dynamic::Value::unnamed_variant(
+            "Parachain",
+            vec![subxt::dynamic::Value::u128(*id as u128)],
+        )),
+        Junction::AccountId32 { network, id } => {
+            let network_value = if let Some(_net) = network {
+                // Encode network if present
+                subxt::dynamic::Value::unnamed_variant("Some", vec![])
+            } else {
+                subxt::dynamic::Value::unnamed_variant("None", vec![])
+            };
+
+            Ok(subxt::dynamic::Value::unnamed_variant(
+                "AccountId32",
+                vec![network_value, subxt::dynamic::Value::from_bytes(id)],
+            ))
+        }
+        Junction::AccountId20 { network, key } => {
+            let network_value = if let Some(_net) = network {
+                subxt::dynamic::Value::unnamed_variant("Some", vec![])
+            } else {
+                subxt::dynamic::Value::unnamed_variant("None", vec![])
+            };
+
+            Ok(subxt::dynamic::Value::unnamed_variant(
+                "AccountId20",
+                vec![network_value, subxt::dynamic::Value::from_bytes(key)],
+            ))
+        }
+        Junction::GeneralIndex(index) => Ok(subxt::dynamic::Value::unnamed_variant(
+            "GeneralIndex",
+            vec![subxt::dynamic::Value::u128(*index)],
+        )),
+        Junction::GeneralKey { data } => Ok(subxt::dynamic::Value::unnamed_variant(
+            "GeneralKey",
+            vec![subxt::dynamic::Value::from_bytes(data)],
+        )),
+        Junction::PalletInstance(instance) => Ok(subxt::dynamic::Value::unnamed_variant(
+            "PalletInstance",
+            vec![subxt::dynamic::Value::u128(*instance as u128)],
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use subxt::ext::scale_value::ValueDef;
 
     #[test]
     fn test_multilocation_parent() {
@@ -623,6 +778,34 @@ mod tests {
         assert!(location.is_parent());
     }
 
+    #[test]
+    fn test_multilocation_builder() {
+        let location = MultiLocation::builder()
+            .parent()
+            .parachain(1000)
+            .pallet_instance(50)
+            .general_index(1984)
+            .build()
+            .unwrap();
+        assert_eq!(location.parents, 1);
+        assert_eq!(location.parachain_id(), Some(1000));
+        assert_eq!(location.interior.len(), 3);
+
+        let too_deep = (0..=MAX_JUNCTIONS as u128)
+            .fold(MultiLocation::builder(), |b, i| b.general_index(i))
+            .build();
+        assert!(too_deep.is_err());
+    }
+
+    #[test]
+    fn test_versioned_location_encoding() {
+        let location = MultiLocation::parachain(1000);
+        let v3 = encode_versioned_location(&location, XcmVersion::V3).unwrap();
+        let v4 = encode_versioned_location(&location, XcmVersion::V4).unwrap();
+        assert!(matches!(v3.value, ValueDef::Variant(ref v) if v.name == "V3"));
+        assert!(matches!(v4.value, ValueDef::Variant(ref v) if v.name == "V4"));
+    }
+
     #[test]
     fn test_multilocation_parachain() {
         let location = MultiLocation::parachain(2000);
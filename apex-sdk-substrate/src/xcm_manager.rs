@@ -0,0 +1,826 @@
+//! High-level XCM transfers between the relay chain and parachains
+//!
+//! [`XcmManager`] builds `limited_reserve_transfer_assets` and
+//! `limited_teleport_assets` calls for the connected chain, picks the XCM
+//! pallet the runtime exposes (`XcmPallet` on relay chains, `PolkadotXcm` on
+//! parachains), estimates the weight the message needs on the destination and
+//! tracks delivery by watching the destination's message queue events.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::{ChainConfig, SubstrateAdapter, Wallet, XcmAsset, XcmTransfer};
+//! use std::time::Duration;
+//!
+//! # async fn example(wallet: Wallet, beneficiary: [u8; 32]) -> apex_sdk_substrate::Result<()> {
+//! let relay = SubstrateAdapter::connect_with_config(ChainConfig::westend()).await?;
+//! let asset_hub =
+//!     SubstrateAdapter::connect_with_config(ChainConfig::custom("asset-hub", "wss://westend-asset-hub-rpc.polkadot.io", 42)).await?;
+//!
+//! let xcm = relay.xcm();
+//! let transfer = XcmTransfer::teleport(
+//!     xcm.parachain_destination(1000)?,
+//!     beneficiary,
+//!     vec![XcmAsset::native(1_000_000_000_000)],
+//! );
+//!
+//! let receipt = xcm.transfer(&wallet, &transfer).await?;
+//! let delivery = xcm
+//!     .track_delivery(&asset_hub, &receipt, Duration::from_secs(120))
+//!     .await?;
+//! assert!(delivery.success);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::event_filter::{EventFilter, EventScanner};
+use crate::fee_estimator::Weight;
+use crate::xcm::{
+    encode_asset, encode_assets, encode_multilocation, encode_versioned_assets,
+    encode_versioned_location, version_variant, MultiLocation, WeightLimit, XcmAsset, XcmConfig,
+    XcmTransferType, XcmVersion,
+};
+use crate::{Error, Result, SubstrateAdapter, Wallet};
+use std::time::Duration;
+use subxt::ext::scale_value::{Composite, Value, ValueDef};
+use tracing::{debug, info, warn};
+
+/// XCM pallet name on relay chains
+const RELAY_XCM_PALLET: &str = "XcmPallet";
+
+/// XCM pallet name on parachains
+const PARACHAIN_XCM_PALLET: &str = "PolkadotXcm";
+
+/// Weight charged per XCM instruction by typical runtime configurations
+pub const DEFAULT_UNIT_WEIGHT: Weight = Weight {
+    ref_time: 1_000_000_000,
+    proof_size: 64 * 1024,
+};
+
+/// Instructions executed on the destination for a reserve transfer or
+/// teleport whatever its assets: `ClearOrigin`, `BuyExecution` and
+/// `SetTopic`
+const FIXED_INSTRUCTIONS: u64 = 3;
+
+/// Instructions counted per transferred asset: its receipt
+/// (`ReserveAssetDeposited` or `ReceiveTeleportedAsset`) and its
+/// `DepositAsset`, whose benchmarked weights grow with the asset count
+const PER_ASSET_INSTRUCTIONS: u64 = 2;
+
+/// A cross-chain asset transfer
+#[derive(Debug, Clone)]
+pub struct XcmTransfer {
+    /// Reserve transfer or teleport
+    pub transfer_type: XcmTransferType,
+    /// Destination chain, relative to the sending chain
+    pub dest: MultiLocation,
+    /// Beneficiary, relative to the destination chain
+    pub beneficiary: MultiLocation,
+    /// Assets to transfer
+    pub assets: Vec<XcmAsset>,
+    /// Index of the asset used to pay for execution
+    pub fee_asset_index: u32,
+    /// Destination weight limit; estimated when unset
+    pub weight_limit: Option<WeightLimit>,
+}
+
+impl XcmTransfer {
+    /// Create a reserve transfer to an account on the destination
+    pub fn reserve(dest: MultiLocation, beneficiary: [u8; 32], assets: Vec<XcmAsset>) -> Self {
+        Self::new(
+            XcmTransferType::LimitedReserveTransfer,
+            dest,
+            beneficiary,
+            assets,
+        )
+    }
+
+    /// Create a teleport to an account on the destination
+    pub fn teleport(dest: MultiLocation, beneficiary: [u8; 32], assets: Vec<XcmAsset>) -> Self {
+        Self::new(XcmTransferType::LimitedTeleport, dest, beneficiary, assets)
+    }
+
+    fn new(
+        transfer_type: XcmTransferType,
+        dest: MultiLocation,
+        beneficiary: [u8; 32],
+        assets: Vec<XcmAsset>,
+    ) -> Self {
+        Self {
+            transfer_type,
+            dest,
+            beneficiary: MultiLocation::account(beneficiary),
+            assets,
+            fee_asset_index: 0,
+            weight_limit: None,
+        }
+    }
+
+    /// Use a custom beneficiary location
+    pub fn with_beneficiary(mut self, beneficiary: MultiLocation) -> Self {
+        self.beneficiary = beneficiary;
+        self
+    }
+
+    /// Pay execution fees with the asset at `index`
+    pub fn with_fee_asset_index(mut self, index: u32) -> Self {
+        self.fee_asset_index = index;
+        self
+    }
+
+    /// Set the destination weight limit instead of estimating it
+    pub fn with_weight_limit(mut self, limit: WeightLimit) -> Self {
+        self.weight_limit = Some(limit);
+        self
+    }
+
+    /// Extrinsic name for this transfer type
+    fn call_name(&self) -> &'static str {
+        match self.transfer_type {
+            XcmTransferType::ReserveTransfer | XcmTransferType::LimitedReserveTransfer => {
+                "limited_reserve_transfer_assets"
+            }
+            XcmTransferType::Teleport | XcmTransferType::LimitedTeleport => {
+                "limited_teleport_assets"
+            }
+        }
+    }
+}
+
+/// Result of submitting an XCM transfer
+#[derive(Debug, Clone)]
+pub struct XcmTransferReceipt {
+    /// Hash of the transfer extrinsic
+    pub tx_hash: String,
+    /// XCM message ID from the `Sent` event, used to track delivery
+    pub message_id: Option<[u8; 32]>,
+    /// Weight limit the message was sent with
+    pub dest_weight: Weight,
+}
+
+/// Outcome of an XCM message on the destination chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XcmDelivery {
+    /// Destination block that processed the message
+    pub block_number: u64,
+    /// Hash of that block
+    pub block_hash: String,
+    /// Whether the message executed successfully
+    pub success: bool,
+    /// Event reporting the outcome, as `Pallet::Event`
+    pub event: String,
+}
+
+/// High-level API for cross-chain transfers through the XCM pallet
+pub struct XcmManager<'a> {
+    adapter: &'a SubstrateAdapter,
+    config: XcmConfig,
+    unit_weight: Weight,
+}
+
+impl<'a> XcmManager<'a> {
+    pub fn new(adapter: &'a SubstrateAdapter) -> Self {
+        Self {
+            adapter,
+            config: XcmConfig::default(),
+            unit_weight: DEFAULT_UNIT_WEIGHT,
+        }
+    }
+
+    /// Set the XCM version used to encode locations and assets
+    pub fn with_version(mut self, version: XcmVersion) -> Self {
+        self.config.version = version;
+        self
+    }
+
+    /// Set the per-instruction weight used for destination weight estimation
+    pub fn with_unit_weight(mut self, unit_weight: Weight) -> Self {
+        self.unit_weight = unit_weight;
+        self
+    }
+
+    /// Name of the XCM pallet in the connected runtime
+    pub fn pallet_name(&self) -> Result<&'static str> {
+        let metadata = self.adapter.client().metadata();
+        [RELAY_XCM_PALLET, PARACHAIN_XCM_PALLET]
+            .into_iter()
+            .find(|name| metadata.pallet_by_name(name).is_some())
+            .ok_or_else(|| Error::Metadata("No XCM pallet found in runtime".to_string()))
+    }
+
+    /// Check whether the connected chain is a relay chain
+    pub fn is_relay_chain(&self) -> bool {
+        self.pallet_name().ok() == Some(RELAY_XCM_PALLET)
+    }
+
+    /// Location of the relay chain, seen from the connected parachain
+    pub fn relay_destination(&self) -> Result<MultiLocation> {
+        if self.is_relay_chain() {
+            return Err(Error::Transaction(
+                "Already connected to the relay chain".to_string(),
+            ));
+        }
+        Ok(MultiLocation::parent())
+    }
+
+    /// Location of a parachain, seen from the connected chain
+    ///
+    /// Parachains are children of the relay chain and siblings of each other.
+    pub fn parachain_destination(&self, para_id: u32) -> Result<MultiLocation> {
+        MultiLocation::builder()
+            .parents(if self.is_relay_chain() { 0 } else { 1 })
+            .parachain(para_id)
+            .build()
+    }
+
+    /// Estimate the weight the transfer message needs on the destination
+    ///
+    /// Counts the instructions the destination executes for the transfer,
+    /// two per asset, at the configured per-instruction weight. This is the
+    /// offline fallback of
+    /// [`query_destination_weight`](Self::query_destination_weight), used
+    /// when no destination connection is at hand.
+    pub fn estimate_destination_weight(&self, transfer: &XcmTransfer) -> Weight {
+        estimated_weight(self.unit_weight, transfer)
+    }
+
+    /// Ask the destination what the transfer message weighs there
+    ///
+    /// Calls `XcmPaymentApi_query_xcm_weight` on `destination` with the
+    /// message it will execute. Destinations without `XcmPaymentApi` get
+    /// the [offline estimate](Self::estimate_destination_weight). Send with
+    /// the result through [`XcmTransfer::with_weight_limit`], as
+    /// `WeightLimit::Limited(weight.ref_time)`.
+    pub async fn query_destination_weight(
+        &self,
+        destination: &SubstrateAdapter,
+        transfer: &XcmTransfer,
+    ) -> Result<Weight> {
+        use subxt::ext::scale_value::scale::{decode_as_type, encode_as_type};
+
+        let metadata = destination.client().metadata();
+        let Some(method) = metadata
+            .runtime_api_trait_by_name("XcmPaymentApi")
+            .and_then(|api| api.method_by_name("query_xcm_weight"))
+        else {
+            debug!("Destination has no XcmPaymentApi, estimating XCM weight");
+            return Ok(self.estimate_destination_weight(transfer));
+        };
+        let message_ty = method
+            .inputs()
+            .next()
+            .map(|input| input.ty)
+            .ok_or_else(|| Error::Metadata("query_xcm_weight takes no message".to_string()))?;
+
+        let message = destination_message(transfer, self.config.version)?;
+        let mut params = Vec::new();
+        encode_as_type(&message, message_ty, metadata.types(), &mut params)
+            .map_err(|e| Error::Encoding(format!("Failed to encode XCM message: {}", e)))?;
+
+        let bytes = destination
+            .client()
+            .runtime_api()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?
+            .call_raw("XcmPaymentApi_query_xcm_weight", Some(&params))
+            .await
+            .map_err(|e| Error::Transaction(format!("XCM weight query failed: {}", e)))?;
+        let output = decode_as_type(&mut &bytes[..], method.output_ty(), metadata.types())
+            .map_err(|e| Error::Encoding(format!("Failed to decode XCM weight: {}", e)))?;
+
+        let weight = queried_weight(&output)?;
+        debug!(
+            ref_time = weight.ref_time,
+            proof_size = weight.proof_size,
+            "Destination XCM weight"
+        );
+        Ok(weight)
+    }
+
+    /// Build the transfer extrinsic
+    pub fn build_transfer(&self, transfer: &XcmTransfer) -> Result<subxt::tx::DynamicPayload> {
+        if transfer.assets.is_empty() {
+            return Err(Error::Transaction("No assets to transfer".to_string()));
+        }
+        if transfer.fee_asset_index as usize >= transfer.assets.len() {
+            return Err(Error::Transaction(format!(
+                "Fee asset index {} out of range for {} assets",
+                transfer.fee_asset_index,
+                transfer.assets.len()
+            )));
+        }
+
+        let version = self.config.version;
+        let weight_limit = encode_weight_limit(self.weight_limit(transfer));
+
+        Ok(subxt::dynamic::tx(
+            self.pallet_name()?,
+            transfer.call_name(),
+            vec![
+                encode_versioned_location(&transfer.dest, version)?,
+                encode_versioned_location(&transfer.beneficiary, version)?,
+                encode_versioned_assets(&transfer.assets, version)?,
+                subxt::dynamic::Value::u128(transfer.fee_asset_index as u128),
+                weight_limit,
+            ],
+        ))
+    }
+
+    fn weight_limit(&self, transfer: &XcmTransfer) -> Option<Weight> {
+        match transfer.weight_limit {
+            None => Some(self.estimate_destination_weight(transfer)),
+            Some(WeightLimit::Unlimited) => None,
+            Some(WeightLimit::Limited(ref_time)) => Some(Weight::new(
+                ref_time,
+                self.estimate_destination_weight(transfer).proof_size,
+            )),
+        }
+    }
+
+    /// Submit a transfer and wait for finalization
+    pub async fn transfer(
+        &self,
+        wallet: &Wallet,
+        transfer: &XcmTransfer,
+    ) -> Result<XcmTransferReceipt> {
+        let pallet = self.pallet_name()?;
+        let call = self.build_transfer(transfer)?;
+        let dest_weight = self.weight_limit(transfer).unwrap_or_default();
+
+        info!(
+            operation = "xcm_transfer",
+            wallet = %wallet.address(),
+            dest = ?transfer.dest,
+            call = transfer.call_name(),
+            ref_time = dest_weight.ref_time,
+            "Submitting XCM transfer"
+        );
+
+        if wallet.sr25519_pair().is_none() {
+            return Err(Error::Transaction(
+                "Wallet does not have SR25519 key".to_string(),
+            ));
+        }
+        let signer = wallet.to_subxt_signer();
+
+        let mut progress = signer
+            .create_signed(self.adapter.client(), &call, Default::default())
+            .await?
+            .submit_and_watch()
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to submit XCM transaction: {}", e)))?;
+
+        while let Some(event) = progress.next().await {
+            let event =
+                event.map_err(|e| Error::Transaction(format!("XCM transaction error: {}", e)))?;
+
+            if let Some(finalized) = event.as_finalized() {
+                let tx_hash = format!("0x{}", hex::encode(finalized.extrinsic_hash()));
+                let events = finalized
+                    .wait_for_success()
+                    .await
+                    .map_err(|e| Error::Transaction(format!("XCM transaction failed: {}", e)))?;
+
+                let mut message_id = None;
+                for evt in events.iter() {
+                    let evt = evt.map_err(|e| {
+                        Error::Transaction(format!("Failed to decode event: {}", e))
+                    })?;
+                    if evt.pallet_name() == pallet && evt.variant_name() == "Sent" {
+                        message_id = evt
+                            .field_values()
+                            .ok()
+                            .and_then(|fields| field_bytes32(&fields, &["message_id"]));
+                    }
+                }
+
+                if message_id.is_none() {
+                    warn!(tx_hash = %tx_hash, "No XCM Sent event found, delivery cannot be tracked");
+                }
+                info!(tx_hash = %tx_hash, "XCM transfer finalized");
+
+                return Ok(XcmTransferReceipt {
+                    tx_hash,
+                    message_id,
+                    dest_weight,
+                });
+            }
+        }
+
+        Err(Error::Transaction(
+            "XCM transaction stream ended without finalization".to_string(),
+        ))
+    }
+
+    /// Wait for the destination chain to process a transfer's message
+    ///
+    /// Watches finalized blocks on `destination` for `MessageQueue`,
+    /// `XcmpQueue` or `DmpQueue` events carrying the message ID.
+    pub async fn track_delivery(
+        &self,
+        destination: &SubstrateAdapter,
+        receipt: &XcmTransferReceipt,
+        timeout: Duration,
+    ) -> Result<XcmDelivery> {
+        let message_id = receipt.message_id.ok_or_else(|| {
+            Error::Transaction("Transfer receipt has no XCM message ID".to_string())
+        })?;
+
+        tokio::time::timeout(timeout, watch_delivery(destination, message_id))
+            .await
+            .map_err(|_| {
+                Error::Transaction(format!(
+                    "XCM message 0x{} not processed within {:?}",
+                    hex::encode(message_id),
+                    timeout
+                ))
+            })?
+    }
+}
+
+async fn watch_delivery(
+    destination: &SubstrateAdapter,
+    message_id: [u8; 32],
+) -> Result<XcmDelivery> {
+    let mut blocks = destination.event_bus().subscribe("xcm-delivery");
+    let mut scanner = EventScanner::new(EventFilter::new(
+        &destination.client().metadata(),
+        DELIVERY_EVENTS,
+    ));
+
+    while let Some(block) = blocks.recv().await {
+        let Ok(events) = scanner.scan(&block.events) else {
+            continue;
+        };
+        for evt in events {
+            let Ok(fields) = evt.field_values() else {
+                continue;
+            };
+
+            if let Some(success) =
+                delivery_outcome(evt.pallet_name(), evt.variant_name(), &fields, &message_id)
+            {
+                let delivery = XcmDelivery {
+                    block_number: block.number,
+                    block_hash: format!("0x{}", hex::encode(block.hash)),
+                    success,
+                    event: format!("{}::{}", evt.pallet_name(), evt.variant_name()),
+                };
+                debug!(
+                    tx_hash = %delivery.block_hash,
+                    success,
+                    event = %delivery.event,
+                    "XCM message processed on destination"
+                );
+                return Ok(delivery);
+            }
+        }
+    }
+
+    Err(Error::Connection(
+        "Destination event bus closed".to_string(),
+    ))
+}
+
+/// Destination events that report on an incoming message
+const DELIVERY_EVENTS: &[(&str, &str)] = &[
+    ("MessageQueue", "Processed"),
+    ("MessageQueue", "ProcessingFailed"),
+    ("XcmpQueue", "Success"),
+    ("XcmpQueue", "Fail"),
+    ("DmpQueue", "ExecutedDownward"),
+];
+
+/// Match a destination event against a message ID
+///
+/// Returns the execution outcome if the event reports on the message.
+fn delivery_outcome<T>(
+    pallet: &str,
+    variant: &str,
+    fields: &Composite<T>,
+    message_id: &[u8; 32],
+) -> Option<bool> {
+    let outcome = match (pallet, variant) {
+        ("MessageQueue", "Processed") => field(fields, "success").and_then(Value::as_bool)?,
+        ("MessageQueue", "ProcessingFailed") => false,
+        ("XcmpQueue", "Success") => true,
+        ("XcmpQueue", "Fail") => false,
+        ("DmpQueue", "ExecutedDownward") => match field(fields, "outcome").map(|v| &v.value) {
+            Some(ValueDef::Variant(variant)) => variant.name == "Complete",
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let id = field_bytes32(fields, &["id", "message_id", "message_hash"])?;
+    (&id == message_id).then_some(outcome)
+}
+
+fn field<'c, T>(fields: &'c Composite<T>, name: &str) -> Option<&'c Value<T>> {
+    match fields {
+        Composite::Named(named) => named.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+        Composite::Unnamed(_) => None,
+    }
+}
+
+/// Read the first of `names` that holds 32 bytes
+fn field_bytes32<T>(fields: &Composite<T>, names: &[&str]) -> Option<[u8; 32]> {
+    names
+        .iter()
+        .filter_map(|name| field(fields, name))
+        .find_map(|value| value_bytes(value)?.try_into().ok())
+}
+
+/// Flatten a byte array value, unwrapping newtypes such as `H256`
+fn value_bytes<T>(value: &Value<T>) -> Option<Vec<u8>> {
+    let ValueDef::Composite(composite) = &value.value else {
+        return None;
+    };
+
+    let values: Vec<&Value<T>> = composite.values().collect();
+    if let [inner] = values.as_slice() {
+        if matches!(inner.value, ValueDef::Composite(_)) {
+            return value_bytes(inner);
+        }
+    }
+
+    values
+        .iter()
+        .map(|v| v.as_u128().and_then(|b| u8::try_from(b).ok()))
+        .collect()
+}
+
+/// Destination weight of a transfer's instructions at `unit_weight` each
+fn estimated_weight(unit_weight: Weight, transfer: &XcmTransfer) -> Weight {
+    let instructions = FIXED_INSTRUCTIONS
+        .saturating_add(PER_ASSET_INSTRUCTIONS.saturating_mul(transfer.assets.len() as u64));
+    Weight::new(
+        unit_weight.ref_time.saturating_mul(instructions),
+        unit_weight.proof_size.saturating_mul(instructions),
+    )
+}
+
+/// The message a transfer makes the destination execute, as `VersionedXcm`
+///
+/// Mirrors what the XCM pallet sends: the assets arrive, the origin is
+/// cleared, execution is bought with the fee asset and everything is
+/// deposited to the beneficiary.
+fn destination_message(transfer: &XcmTransfer, version: XcmVersion) -> Result<Value> {
+    let fee_asset = transfer
+        .assets
+        .get(transfer.fee_asset_index as usize)
+        .ok_or_else(|| {
+            Error::Transaction(format!(
+                "Fee asset index {} out of range for {} assets",
+                transfer.fee_asset_index,
+                transfer.assets.len()
+            ))
+        })?;
+    let receipt = match transfer.transfer_type {
+        XcmTransferType::ReserveTransfer | XcmTransferType::LimitedReserveTransfer => {
+            "ReserveAssetDeposited"
+        }
+        XcmTransferType::Teleport | XcmTransferType::LimitedTeleport => "ReceiveTeleportedAsset",
+    };
+
+    let instructions = vec![
+        Value::unnamed_variant(receipt, [encode_assets(&transfer.assets, version)?]),
+        Value::unnamed_variant("ClearOrigin", []),
+        Value::named_variant(
+            "BuyExecution",
+            [
+                ("fees", encode_asset(fee_asset, version)?),
+                ("weight_limit", Value::unnamed_variant("Unlimited", [])),
+            ],
+        ),
+        Value::named_variant(
+            "DepositAsset",
+            [
+                (
+                    "assets",
+                    Value::unnamed_variant(
+                        "Wild",
+                        [Value::unnamed_variant(
+                            "AllCounted",
+                            [Value::u128(transfer.assets.len() as u128)],
+                        )],
+                    ),
+                ),
+                (
+                    "beneficiary",
+                    encode_multilocation(&transfer.beneficiary, version)?,
+                ),
+            ],
+        ),
+        Value::unnamed_variant("SetTopic", [Value::from_bytes([0u8; 32])]),
+    ];
+    Ok(Value::unnamed_variant(
+        version_variant(version),
+        [Value::unnamed_composite(instructions)],
+    ))
+}
+
+/// Weight in the `Result<Weight, XcmPaymentApiError>` returned by
+/// `query_xcm_weight`
+fn queried_weight<T>(output: &Value<T>) -> Result<Weight> {
+    let ValueDef::Variant(result) = &output.value else {
+        return Err(Error::Encoding("Unexpected XCM weight result".to_string()));
+    };
+    let inner = result.values.values().next();
+    if result.name != "Ok" {
+        return Err(Error::Transaction(format!(
+            "Destination cannot weigh the XCM message: {}",
+            inner.map(ToString::to_string).unwrap_or_default()
+        )));
+    }
+    let Some(ValueDef::Composite(weight)) = inner.map(|value| &value.value) else {
+        return Err(Error::Encoding(
+            "Unexpected XCM weight encoding".to_string(),
+        ));
+    };
+    let part = |name: &str| {
+        field(weight, name)
+            .and_then(Value::as_u128)
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or_else(|| Error::Encoding(format!("XCM weight has no valid {}", name)))
+    };
+    Ok(Weight::new(part("ref_time")?, part("proof_size")?))
+}
+
+fn encode_weight_limit(weight: Option<Weight>) -> subxt::dynamic::Value {
+    match weight {
+        None => subxt::dynamic::Value::unnamed_variant("Unlimited", vec![]),
+        Some(weight) => subxt::dynamic::Value::unnamed_variant(
+            "Limited",
+            vec![subxt::dynamic::Value::named_composite([
+                (
+                    "ref_time",
+                    subxt::dynamic::Value::u128(weight.ref_time as u128),
+                ),
+                (
+                    "proof_size",
+                    subxt::dynamic::Value::u128(weight.proof_size as u128),
+                ),
+            ])],
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE_ID: [u8; 32] = [9u8; 32];
+
+    #[test]
+    fn test_transfer_call_names() {
+        let reserve = XcmTransfer::reserve(MultiLocation::parent(), [1u8; 32], vec![]);
+        assert_eq!(reserve.call_name(), "limited_reserve_transfer_assets");
+
+        let teleport = XcmTransfer::teleport(MultiLocation::parachain(1000), [1u8; 32], vec![]);
+        assert_eq!(teleport.call_name(), "limited_teleport_assets");
+        assert_eq!(teleport.beneficiary, MultiLocation::account([1u8; 32]));
+    }
+
+    #[test]
+    fn test_destination_weight_scales_with_assets() {
+        let one = XcmTransfer::reserve(
+            MultiLocation::parachain(2000),
+            [1u8; 32],
+            vec![XcmAsset::native(100)],
+        );
+        let two = XcmTransfer::reserve(
+            MultiLocation::parachain(2000),
+            [1u8; 32],
+            vec![XcmAsset::native(100), XcmAsset::native(200)],
+        );
+
+        let single = estimated_weight(DEFAULT_UNIT_WEIGHT, &one);
+        assert_eq!(single.ref_time, 5 * DEFAULT_UNIT_WEIGHT.ref_time);
+        let double = estimated_weight(DEFAULT_UNIT_WEIGHT, &two);
+        assert_eq!(double.ref_time, 7 * DEFAULT_UNIT_WEIGHT.ref_time);
+        assert!(double.proof_size > single.proof_size);
+    }
+
+    #[test]
+    fn test_destination_message_instructions() {
+        let transfer = XcmTransfer::teleport(
+            MultiLocation::parachain(1000),
+            [1u8; 32],
+            vec![XcmAsset::native(100), XcmAsset::native(200)],
+        )
+        .with_fee_asset_index(1);
+        let message = destination_message(&transfer, XcmVersion::V4).unwrap();
+
+        let ValueDef::Variant(versioned) = &message.value else {
+            panic!("message is not versioned");
+        };
+        assert_eq!(versioned.name, "V4");
+        let Some(ValueDef::Composite(instructions)) =
+            versioned.values.values().next().map(|v| &v.value)
+        else {
+            panic!("message has no instructions");
+        };
+        let names: Vec<_> = instructions
+            .values()
+            .map(|instruction| match &instruction.value {
+                ValueDef::Variant(variant) => variant.name.as_str(),
+                _ => "",
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "ReceiveTeleportedAsset",
+                "ClearOrigin",
+                "BuyExecution",
+                "DepositAsset",
+                "SetTopic"
+            ]
+        );
+
+        let out_of_range = transfer.with_fee_asset_index(2);
+        assert!(destination_message(&out_of_range, XcmVersion::V4).is_err());
+    }
+
+    #[test]
+    fn test_queried_weight() {
+        let weight = Value::named_composite([
+            ("ref_time", Value::u128(1_500)),
+            ("proof_size", Value::u128(64)),
+        ]);
+        let ok = Value::unnamed_variant("Ok", [weight]);
+        assert_eq!(queried_weight(&ok).unwrap(), Weight::new(1_500, 64));
+
+        let err =
+            Value::unnamed_variant("Err", [Value::unnamed_variant("UnhandledXcmVersion", [])]);
+        assert!(queried_weight(&err).is_err());
+    }
+
+    #[test]
+    fn test_message_queue_processed() {
+        let fields = Composite::named([
+            (
+                "id",
+                Value::unnamed_composite([Value::from_bytes(MESSAGE_ID)]),
+            ),
+            ("success", Value::bool(true)),
+        ]);
+
+        assert_eq!(
+            delivery_outcome("MessageQueue", "Processed", &fields, &MESSAGE_ID),
+            Some(true)
+        );
+        assert_eq!(
+            delivery_outcome("MessageQueue", "Processed", &fields, &[0u8; 32]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_xcmp_queue_fail() {
+        let fields = Composite::named([
+            ("message_hash", Value::from_bytes(MESSAGE_ID)),
+            ("error", Value::unnamed_variant("Barrier", [])),
+        ]);
+
+        assert_eq!(
+            delivery_outcome("XcmpQueue", "Fail", &fields, &MESSAGE_ID),
+            Some(false)
+        );
+        assert_eq!(
+            delivery_outcome("Balances", "Transfer", &fields, &MESSAGE_ID),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dmp_queue_outcome() {
+        let fields = Composite::named([
+            ("message_id", Value::from_bytes(MESSAGE_ID)),
+            (
+                "outcome",
+                Value::unnamed_variant("Complete", [Value::u128(1)]),
+            ),
+        ]);
+
+        assert_eq!(
+            delivery_outcome("DmpQueue", "ExecutedDownward", &fields, &MESSAGE_ID),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_weight_limit_encoding() {
+        let limited = encode_weight_limit(Some(Weight::new(5, 6)));
+        assert!(matches!(
+            limited.value,
+            ValueDef::Variant(ref v) if v.name == "Limited"
+        ));
+
+        let unlimited = encode_weight_limit(None);
+        assert!(matches!(
+            unlimited.value,
+            ValueDef::Variant(ref v) if v.name == "Unlimited"
+        ));
+    }
+}
@@ -2,6 +2,8 @@ use apex_sdk_core::BlockInfo;
 use apex_sdk_substrate::{cache::CacheConfig, Cache};
 use std::time::Duration;
 
+const BLOCK_HASH: &str = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+
 #[test]
 fn test_blockinfo_creation() {
     let block_info = BlockInfo {
@@ -20,6 +22,8 @@ fn test_blockinfo_creation() {
         extrinsic_count: 2,
         event_count: Some(6),
         is_finalized: true,
+        extrinsics: Vec::new(),
+        author: None,
     };
 
     assert_eq!(block_info.number, 12345678);
@@ -43,6 +47,8 @@ fn test_blockinfo_serialization() {
         extrinsic_count: 0,
         event_count: None,
         is_finalized: false,
+        extrinsics: Vec::new(),
+        author: None,
     };
 
     // Test JSON serialization
@@ -75,6 +81,8 @@ fn test_blockinfo_backward_compatibility() {
     assert_eq!(block_info.extrinsic_count, 0);
     assert_eq!(block_info.event_count, None);
     assert!(!block_info.is_finalized);
+    assert!(block_info.extrinsics.is_empty());
+    assert_eq!(block_info.author, None);
 }
 
 #[test]
@@ -107,6 +115,8 @@ fn test_block_cache_put_and_get() {
         extrinsic_count: 0,
         event_count: None,
         is_finalized: true,
+        extrinsics: Vec::new(),
+        author: None,
     };
 
     // Put block in cache
@@ -118,8 +128,7 @@ fn test_block_cache_put_and_get() {
     assert_eq!(retrieved.unwrap().number, 12345678);
 
     // Retrieve by hash
-    let retrieved_by_hash = cache
-        .get_block_by_hash("0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef");
+    let retrieved_by_hash = cache.get_block_by_hash(&BLOCK_HASH.parse().unwrap());
     assert!(retrieved_by_hash.is_some());
     assert_eq!(retrieved_by_hash.unwrap().number, 12345678);
 }
@@ -158,6 +167,8 @@ fn test_block_cache_finality_aware_ttl() {
         extrinsic_count: 0,
         event_count: None,
         is_finalized: true,
+        extrinsics: Vec::new(),
+        author: None,
     };
 
     // Add recent (non-finalized) block
@@ -173,6 +184,8 @@ fn test_block_cache_finality_aware_ttl() {
         extrinsic_count: 0,
         event_count: None,
         is_finalized: false,
+        extrinsics: Vec::new(),
+        author: None,
     };
 
     cache.put_block(finalized_block.clone());
@@ -206,14 +219,15 @@ fn test_block_cache_dual_key_storage() {
         extrinsic_count: 0,
         event_count: None,
         is_finalized: true,
+        extrinsics: Vec::new(),
+        author: None,
     };
 
     cache.put_block(block_info.clone());
 
     // Should be retrievable by both number and hash
     let by_number = cache.get_block_by_number(12345678);
-    let by_hash = cache
-        .get_block_by_hash("0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef");
+    let by_hash = cache.get_block_by_hash(&BLOCK_HASH.parse().unwrap());
 
     assert!(by_number.is_some());
     assert!(by_hash.is_some());
@@ -238,6 +252,8 @@ fn test_genesis_block_handling() {
         extrinsic_count: 0,
         event_count: Some(0),
         is_finalized: true,
+        extrinsics: Vec::new(),
+        author: None,
     };
 
     assert_eq!(genesis_block.number, 0);
@@ -259,6 +275,8 @@ fn test_block_without_transactions() {
         extrinsic_count: 0,
         event_count: Some(0),
         is_finalized: true,
+        extrinsics: Vec::new(),
+        author: None,
     };
 
     assert_eq!(empty_block.transactions.len(), 0);
@@ -286,6 +304,8 @@ fn test_cache_clear_removes_blocks() {
         extrinsic_count: 0,
         event_count: None,
         is_finalized: true,
+        extrinsics: Vec::new(),
+        author: None,
     };
 
     cache.put_block(block_info.clone());
@@ -0,0 +1,163 @@
+//! Golden-vector tests for wallet derivation and signatures
+//!
+//! The vectors in `fixtures/wallet_vectors.json` are outputs of `subkey` and
+//! polkadot-js for the development phrase, plus the RFC 8032 Ed25519 test
+//! vectors. Any change to key derivation, SS58 encoding or signing that makes
+//! these fail breaks compatibility with every other Substrate tool, so fix
+//! the code rather than the fixture.
+
+use apex_sdk_substrate::{KeyPairType, Wallet};
+use serde::Deserialize;
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use std::collections::BTreeMap;
+
+const VECTORS: &str = include_str!("fixtures/wallet_vectors.json");
+
+#[derive(Deserialize)]
+struct Vectors {
+    derivation: Vec<DerivationVector>,
+    signatures: Vec<SignatureVector>,
+}
+
+#[derive(Deserialize)]
+struct DerivationVector {
+    key_type: String,
+    phrase: String,
+    path: Option<String>,
+    public_key: String,
+    /// SS58 address keyed by network prefix
+    addresses: BTreeMap<u16, String>,
+}
+
+#[derive(Deserialize)]
+struct SignatureVector {
+    key_type: String,
+    comment: String,
+    #[serde(default)]
+    seed: Option<String>,
+    #[serde(default)]
+    phrase: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+    public_key: String,
+    message: String,
+    signature: String,
+}
+
+impl SignatureVector {
+    fn wallet(&self) -> Wallet {
+        let key_type = key_type(&self.key_type);
+        match (&self.seed, &self.phrase) {
+            (Some(seed), _) => Wallet::from_seed(&bytes(seed), key_type).unwrap(),
+            (None, Some(phrase)) => {
+                Wallet::from_mnemonic_with_path(phrase, self.path.as_deref(), key_type).unwrap()
+            }
+            (None, None) => panic!("{}: vector needs a seed or a phrase", self.comment),
+        }
+    }
+}
+
+fn load() -> Vectors {
+    serde_json::from_str(VECTORS).expect("wallet_vectors.json is malformed")
+}
+
+fn key_type(name: &str) -> KeyPairType {
+    match name {
+        "sr25519" => KeyPairType::Sr25519,
+        "ed25519" => KeyPairType::Ed25519,
+        other => panic!("unsupported key type in fixture: {}", other),
+    }
+}
+
+fn bytes(hex_str: &str) -> Vec<u8> {
+    hex::decode(hex_str.trim_start_matches("0x")).unwrap()
+}
+
+#[test]
+fn test_derivation_vectors() {
+    let vectors = load();
+    assert!(!vectors.derivation.is_empty());
+
+    for vector in &vectors.derivation {
+        let label = format!(
+            "{} {}",
+            vector.key_type,
+            vector.path.as_deref().unwrap_or("<root>")
+        );
+        let wallet = Wallet::from_mnemonic_with_path(
+            &vector.phrase,
+            vector.path.as_deref(),
+            key_type(&vector.key_type),
+        )
+        .unwrap();
+
+        assert_eq!(
+            wallet.public_key(),
+            bytes(&vector.public_key),
+            "{}: public key",
+            label
+        );
+        for (prefix, address) in &vector.addresses {
+            assert_eq!(
+                &wallet.clone().with_ss58_format(*prefix).address(),
+                address,
+                "{}: address at prefix {}",
+                label,
+                prefix
+            );
+        }
+    }
+}
+
+#[test]
+fn test_address_vectors_decode_to_public_key() {
+    for vector in load().derivation {
+        for (prefix, address) in &vector.addresses {
+            let (account, format) = AccountId32::from_ss58check_with_version(address).unwrap();
+            assert_eq!(u16::from(format), *prefix, "{}: prefix", address);
+            assert_eq!(
+                AsRef::<[u8]>::as_ref(&account),
+                bytes(&vector.public_key).as_slice(),
+                "{}: public key",
+                address
+            );
+        }
+    }
+}
+
+#[test]
+fn test_signature_vectors() {
+    for vector in load().signatures {
+        let wallet = vector.wallet();
+        let message = bytes(&vector.message);
+        let signature = bytes(&vector.signature);
+
+        assert_eq!(
+            wallet.public_key(),
+            bytes(&vector.public_key),
+            "{}: public key",
+            vector.comment
+        );
+        assert!(
+            wallet.verify(&message, &signature),
+            "{}: signature does not verify",
+            vector.comment
+        );
+
+        let mut tampered = message.clone();
+        tampered.push(0);
+        assert!(
+            !wallet.verify(&tampered, &signature),
+            "{}: signature verifies for a different message",
+            vector.comment
+        );
+
+        // Ed25519 is deterministic, so fresh signatures must match byte for
+        // byte; Sr25519 signing is randomized and can only be round-tripped
+        let fresh = wallet.sign(&message);
+        match wallet.key_type() {
+            KeyPairType::Ed25519 => assert_eq!(fresh, signature, "{}", vector.comment),
+            KeyPairType::Sr25519 => assert!(wallet.verify(&message, &fresh)),
+        }
+    }
+}
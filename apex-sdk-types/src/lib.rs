@@ -9,9 +9,15 @@
 //!
 //! - **Chain**: Enumeration of supported blockchain networks
 //! - **ChainType**: Classification of chains (Substrate, EVM, Hybrid)
+//! - **ChainRegistry** / **ChainInfo**: Endpoints, SS58 prefix, native token and block time of known chains
+//! - **ChainProperties**: Native token symbol and decimals reported by a node
 //! - **Address**: Generic address type supporting multiple formats
+//! - **IntoAddress**: Validated, normalized conversion into `Address`
+//! - **TxHash** / **BlockHash**: Validated 32-byte transaction and block hashes
+//! - **Page** / **PageRequest** / **Cursor**: Cursor-based pagination for list APIs
 //! - **TransactionStatus**: Unified transaction status representation
 //! - **CrossChainTransaction**: Cross-chain transaction information
+//! - **ErrorCode** / **HasErrorCode**: Stable numeric codes of SDK error variants
 //!
 //! ## Example
 //!
@@ -63,6 +69,108 @@ pub enum ValidationError {
     /// SS58 checksum validation failed
     #[error("SS58 checksum validation failed for address: {0}")]
     InvalidSs58Checksum(String),
+
+    /// SS58 network prefix outside the valid 14-bit range
+    #[error("Invalid SS58 network prefix: {0}")]
+    InvalidSs58Prefix(u16),
+
+    /// Input is neither a hex nor an SS58 address
+    #[error("Unrecognized address format: {0}")]
+    UnrecognizedAddress(String),
+
+    /// Hash is not `0x` followed by 64 hex digits
+    #[error("Invalid {kind}: {value}")]
+    InvalidHash { kind: &'static str, value: String },
+
+    /// Pagination cursor was not issued by the API it was passed to
+    #[error("Invalid cursor: {0}")]
+    InvalidCursor(String),
+
+    /// Amount is malformed, in an unknown unit or finer than the token's
+    /// smallest unit
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    /// Address is valid but of the wrong kind for the operation
+    #[error("Expected {expected} address, got {address}")]
+    UnexpectedAddressType {
+        expected: &'static str,
+        address: String,
+    },
+}
+
+impl HasErrorCode for ValidationError {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::new(match self {
+            ValidationError::InvalidEvmAddress(_) => 1600,
+            ValidationError::InvalidChecksum(_) => 1601,
+            ValidationError::InvalidChainId { .. } => 1602,
+            ValidationError::ChainIdNotFound(_) => 1603,
+            ValidationError::InvalidSubstrateAddress(_) => 1604,
+            ValidationError::InvalidSs58Checksum(_) => 1605,
+            ValidationError::InvalidSs58Prefix(_) => 1606,
+            ValidationError::UnrecognizedAddress(_) => 1607,
+            ValidationError::InvalidHash { .. } => 1608,
+            ValidationError::InvalidCursor(_) => 1609,
+            ValidationError::UnexpectedAddressType { .. } => 1610,
+            ValidationError::InvalidAmount(_) => 1611,
+        })
+    }
+}
+
+/// Stable identifier of an error variant, displayed as `APEX-1203`
+///
+/// A code keeps its meaning across releases, so logs, metrics and API
+/// clients can match on it instead of on messages. Each crate owns a block
+/// of codes:
+///
+/// | Codes | Crate |
+/// |-------|-------|
+/// | 1000-1099 | `apex-sdk` |
+/// | 1100-1199 | `apex-sdk-core` |
+/// | 1200-1299 | `apex-sdk-substrate` |
+/// | 1300-1399 | `apex-sdk-revive` |
+/// | 1400-1499 | `apex-sdk-evm` |
+/// | 1500-1599 | `apex-sdk-metrics` |
+/// | 1600-1699 | `apex-sdk-types` |
+///
+/// Serializes as the bare number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ErrorCode(u16);
+
+impl ErrorCode {
+    /// Wrap a numeric code
+    pub const fn new(code: u16) -> Self {
+        Self(code)
+    }
+
+    /// Numeric value
+    pub const fn get(self) -> u16 {
+        self.0
+    }
+
+    /// Parse `APEX-1203` or `1203`
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let number = s
+            .get(..5)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("APEX-"))
+            .map_or(s, |_| &s[5..]);
+        number.parse().ok().map(Self)
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "APEX-{}", self.0)
+    }
+}
+
+/// Errors whose variants carry a stable [`ErrorCode`]
+pub trait HasErrorCode {
+    /// Code of this error's variant
+    fn code(&self) -> ErrorCode;
 }
 
 /// Blockchain types
@@ -353,6 +461,606 @@ impl Chain {
             }
         }
     }
+
+    /// Get the SS58 address format of Substrate and hybrid chains
+    pub fn ss58_prefix(&self) -> Option<u16> {
+        match self {
+            Chain::Polkadot => Some(0),
+            Chain::Kusama => Some(2),
+            Chain::Westend | Chain::Paseo => Some(42),
+            Chain::Moonbeam => Some(1284),
+            Chain::Astar => Some(5),
+            Chain::Acala => Some(10),
+            Chain::Phala => Some(30),
+            Chain::Bifrost => Some(6),
+            _ => None,
+        }
+    }
+
+    /// Find the chain a Substrate address format belongs to
+    ///
+    /// Returns None for the generic format 42, which is shared by testnets
+    /// and chain-agnostic addresses.
+    pub fn from_ss58_prefix(prefix: u16) -> Option<Self> {
+        match prefix {
+            42 => None,
+            _ => [
+                Chain::Polkadot,
+                Chain::Kusama,
+                Chain::Moonbeam,
+                Chain::Astar,
+                Chain::Acala,
+                Chain::Phala,
+                Chain::Bifrost,
+            ]
+            .into_iter()
+            .find(|chain| chain.ss58_prefix() == Some(prefix)),
+        }
+    }
+
+    /// Get the parachain ID of chains connected to a relay chain
+    pub fn parachain_id(&self) -> Option<u32> {
+        match self {
+            Chain::Acala => Some(2000),
+            Chain::Moonbeam => Some(2004),
+            Chain::Astar => Some(2006),
+            Chain::Bifrost => Some(2030),
+            Chain::Phala => Some(2035),
+            _ => None,
+        }
+    }
+
+    /// Get the relay chain a parachain is connected to
+    pub fn relay_chain(&self) -> Option<Chain> {
+        self.parachain_id().map(|_| Chain::Polkadot)
+    }
+
+    /// Check if this is a relay chain
+    pub fn is_relay_chain(&self) -> bool {
+        matches!(
+            self,
+            Chain::Polkadot | Chain::Kusama | Chain::Westend | Chain::Paseo
+        )
+    }
+}
+
+/// Properties of a chain: endpoints, address format, native token and block time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainInfo {
+    /// Registry key, e.g. `polkadot` or `asset-hub-westend`
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Matching [`Chain`] variant, if there is one
+    pub chain: Option<Chain>,
+    /// Chain type
+    pub chain_type: ChainType,
+    /// RPC endpoints, preferred first
+    pub endpoints: Vec<String>,
+    /// SS58 address format of Substrate and hybrid chains
+    pub ss58_prefix: Option<u16>,
+    /// EIP-155 chain ID of EVM and hybrid chains
+    pub evm_chain_id: Option<u64>,
+    /// Native token symbol
+    pub token_symbol: String,
+    /// Native token decimals
+    pub token_decimals: u8,
+    /// Target block time in milliseconds
+    pub block_time_ms: u64,
+    /// Whether this is a test network
+    pub testnet: bool,
+}
+
+impl ChainInfo {
+    /// Create an entry with no endpoints and a `UNIT` token of 12 decimals
+    pub fn new(id: impl Into<String>, name: impl Into<String>, chain_type: ChainType) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            chain: None,
+            chain_type,
+            endpoints: Vec::new(),
+            ss58_prefix: None,
+            evm_chain_id: None,
+            token_symbol: "UNIT".to_string(),
+            token_decimals: 12,
+            block_time_ms: 6_000,
+            testnet: false,
+        }
+    }
+
+    /// Create an entry from a [`Chain`], taking its name, endpoints and identifiers
+    pub fn from_chain(id: impl Into<String>, chain: Chain) -> Self {
+        let mut info = Self::new(id, chain.name(), chain.chain_type());
+        info.endpoints = chain
+            .rpc_endpoints()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        info.ss58_prefix = chain.ss58_prefix();
+        info.evm_chain_id = chain.chain_id();
+        info.testnet = chain.is_testnet();
+        info.chain = Some(chain);
+        info
+    }
+
+    /// Add an RPC endpoint
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoints.push(endpoint.into());
+        self
+    }
+
+    /// Set the SS58 address format
+    pub fn with_ss58_prefix(mut self, prefix: u16) -> Self {
+        self.ss58_prefix = Some(prefix);
+        self
+    }
+
+    /// Set the EIP-155 chain ID
+    pub fn with_evm_chain_id(mut self, chain_id: u64) -> Self {
+        self.evm_chain_id = Some(chain_id);
+        self
+    }
+
+    /// Set the native token symbol and decimals
+    pub fn with_token(mut self, symbol: impl Into<String>, decimals: u8) -> Self {
+        self.token_symbol = symbol.into();
+        self.token_decimals = decimals;
+        self
+    }
+
+    /// Set the target block time
+    pub fn with_block_time(mut self, block_time: std::time::Duration) -> Self {
+        self.block_time_ms = block_time.as_millis() as u64;
+        self
+    }
+
+    /// Mark the chain as a test network
+    pub fn testnet(mut self) -> Self {
+        self.testnet = true;
+        self
+    }
+
+    /// Preferred RPC endpoint
+    pub fn default_endpoint(&self) -> Option<&str> {
+        self.endpoints.first().map(String::as_str)
+    }
+
+    /// Target block time
+    pub fn block_time(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.block_time_ms)
+    }
+
+    /// Format a raw amount of the native token, e.g. `1.5 DOT`
+    pub fn format_amount(&self, raw: u128) -> String {
+        format!(
+            "{} {}",
+            format_units(raw, self.token_decimals),
+            self.token_symbol
+        )
+    }
+
+    /// Parse a human amount of the native token into raw units
+    ///
+    /// Accepts a decimal number with an optional unit suffix, e.g. `1.5 DOT`,
+    /// `0.01WND` or `2`. A suffix other than the chain's token symbol is
+    /// rejected, as are amounts with more fractional digits than the token
+    /// has decimals.
+    pub fn parse_amount(&self, input: &str) -> Result<u128, ValidationError> {
+        let (number, unit) = split_amount(input)?;
+        if let Some(unit) = unit {
+            if !unit.eq_ignore_ascii_case(&self.token_symbol) {
+                return Err(ValidationError::InvalidAmount(format!(
+                    "{} is in {}, but the native token of {} is {}",
+                    input.trim(),
+                    unit,
+                    self.name,
+                    self.token_symbol
+                )));
+            }
+        }
+        parse_units(number, self.token_decimals)
+    }
+
+    /// Native token and address format of this chain
+    pub fn properties(&self) -> ChainProperties {
+        ChainProperties {
+            token_symbol: self.token_symbol.clone(),
+            token_decimals: self.token_decimals,
+            ss58_format: self.ss58_prefix,
+        }
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        let name = normalize_chain_name(name);
+        normalize_chain_name(&self.id) == name
+            || normalize_chain_name(&self.name) == name
+            || (self.chain.is_some() && Chain::from_str_case_insensitive(&name) == self.chain)
+    }
+}
+
+/// Lowercase a chain name and drop separators, so `Asset Hub Westend` matches
+/// `asset-hub-westend`
+fn normalize_chain_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Format a raw token amount with `decimals` decimal places, trimming trailing zeros
+pub fn format_units(raw: u128, decimals: u8) -> String {
+    let Some(divisor) = 10u128.checked_pow(u32::from(decimals)) else {
+        return raw.to_string();
+    };
+    let whole = raw / divisor;
+    let frac = raw % divisor;
+
+    if frac == 0 {
+        whole.to_string()
+    } else {
+        let frac = format!("{:0width$}", frac, width = usize::from(decimals));
+        format!("{}.{}", whole, frac.trim_end_matches('0'))
+    }
+}
+
+/// Parse a decimal token amount into raw units with `decimals` decimal places
+///
+/// The inverse of [`format_units`]. Fails if the amount has more significant
+/// fractional digits than `decimals`, since they would be lost, or if it does
+/// not fit in a `u128`.
+pub fn parse_units(amount: &str, decimals: u8) -> Result<u128, ValidationError> {
+    let invalid = |reason: &str| ValidationError::InvalidAmount(format!("{} ({})", amount, reason));
+
+    let (whole, frac) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && frac.is_empty() {
+        return Err(invalid("expected a number"));
+    }
+    if !whole
+        .chars()
+        .chain(frac.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid("expected a number"));
+    }
+
+    let frac = frac.trim_end_matches('0');
+    if frac.len() > usize::from(decimals) {
+        return Err(invalid(&format!(
+            "more than {} decimal places would lose precision",
+            decimals
+        )));
+    }
+
+    let overflow = || invalid("too large");
+    let scale = 10u128
+        .checked_pow(u32::from(decimals))
+        .ok_or_else(overflow)?;
+    let whole = if whole.is_empty() {
+        0
+    } else {
+        whole.parse::<u128>().map_err(|_| overflow())?
+    };
+    let frac = if frac.is_empty() {
+        0
+    } else {
+        // At most `decimals` digits, so padding keeps it below `scale`
+        let padded = format!("{:0<width$}", frac, width = usize::from(decimals));
+        padded.parse::<u128>().map_err(|_| overflow())?
+    };
+
+    whole
+        .checked_mul(scale)
+        .and_then(|raw| raw.checked_add(frac))
+        .ok_or_else(overflow)
+}
+
+/// Split `1.5 DOT` or `1.5DOT` into the number and the unit, if any
+fn split_amount(input: &str) -> Result<(&str, Option<&str>), ValidationError> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    let unit = unit.trim();
+
+    if number.is_empty() {
+        return Err(ValidationError::InvalidAmount(format!(
+            "{} (expected a number)",
+            input
+        )));
+    }
+    if !unit.is_empty() && !unit.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(ValidationError::InvalidAmount(format!(
+            "{} (unrecognized unit {})",
+            input, unit
+        )));
+    }
+    Ok((number, (!unit.is_empty()).then_some(unit)))
+}
+
+/// Native token and address format reported by a node's `system_properties`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainProperties {
+    /// Native token symbol
+    pub token_symbol: String,
+    /// Native token decimals
+    pub token_decimals: u8,
+    /// SS58 address format, if the chain reports one
+    pub ss58_format: Option<u16>,
+}
+
+impl Default for ChainProperties {
+    fn default() -> Self {
+        Self {
+            token_symbol: "UNIT".to_string(),
+            token_decimals: 12,
+            ss58_format: None,
+        }
+    }
+}
+
+impl ChainProperties {
+    /// Parse the JSON object returned by the `system_properties` RPC
+    ///
+    /// Fields the node does not report keep their [`Default`] values.
+    pub fn from_system_properties(properties: &serde_json::Map<String, serde_json::Value>) -> Self {
+        Self::default().with_system_properties(properties)
+    }
+
+    /// Override the fields reported in a `system_properties` response
+    ///
+    /// Chains with several native tokens report arrays; the first entry is
+    /// used. Missing or malformed fields are left unchanged.
+    pub fn with_system_properties(
+        mut self,
+        properties: &serde_json::Map<String, serde_json::Value>,
+    ) -> Self {
+        fn first(value: &serde_json::Value) -> &serde_json::Value {
+            value
+                .as_array()
+                .and_then(|values| values.first())
+                .unwrap_or(value)
+        }
+
+        if let Some(symbol) = properties
+            .get("tokenSymbol")
+            .and_then(|value| first(value).as_str())
+        {
+            self.token_symbol = symbol.to_string();
+        }
+        if let Some(decimals) = properties
+            .get("tokenDecimals")
+            .and_then(|value| first(value).as_u64())
+            .and_then(|decimals| u8::try_from(decimals).ok())
+        {
+            self.token_decimals = decimals;
+        }
+        if let Some(format) = properties
+            .get("ss58Format")
+            .and_then(|value| value.as_u64())
+            .and_then(|format| u16::try_from(format).ok())
+        {
+            self.ss58_format = Some(format);
+        }
+        self
+    }
+
+    /// Format a raw amount with the token's decimals and symbol, e.g. `1.5 DOT`
+    pub fn format_amount(&self, raw: u128) -> String {
+        format!(
+            "{} {}",
+            format_units(raw, self.token_decimals),
+            self.token_symbol
+        )
+    }
+}
+
+/// Lookup table of chain properties
+///
+/// [`ChainRegistry::new`] contains every [`Chain`] variant plus the Asset Hub
+/// system parachains and Sepolia. Entries can be added or replaced with
+/// [`with_chain`](Self::with_chain), e.g. for a local development chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainRegistry {
+    chains: Vec<ChainInfo>,
+}
+
+impl Default for ChainRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChainRegistry {
+    /// Create a registry of the well-known chains
+    pub fn new() -> Self {
+        use std::time::Duration;
+        let six = Duration::from_secs(6);
+        let twelve = Duration::from_secs(12);
+
+        let chains = vec![
+            // Relay chains
+            ChainInfo::from_chain("polkadot", Chain::Polkadot)
+                .with_token("DOT", 10)
+                .with_block_time(six),
+            ChainInfo::from_chain("kusama", Chain::Kusama)
+                .with_token("KSM", 12)
+                .with_block_time(six),
+            ChainInfo::from_chain("westend", Chain::Westend)
+                .with_token("WND", 12)
+                .with_block_time(six),
+            ChainInfo::from_chain("paseo", Chain::Paseo)
+                .with_token("PAS", 10)
+                .with_block_time(six),
+            // System parachains
+            ChainInfo::new(
+                "asset-hub-polkadot",
+                "Asset Hub Polkadot",
+                ChainType::Substrate,
+            )
+            .with_endpoint("wss://polkadot-asset-hub-rpc.polkadot.io")
+            .with_ss58_prefix(0)
+            .with_token("DOT", 10)
+            .with_block_time(six),
+            ChainInfo::new("asset-hub-kusama", "Asset Hub Kusama", ChainType::Substrate)
+                .with_endpoint("wss://kusama-asset-hub-rpc.polkadot.io")
+                .with_ss58_prefix(2)
+                .with_token("KSM", 12)
+                .with_block_time(six),
+            ChainInfo::new(
+                "asset-hub-westend",
+                "Asset Hub Westend",
+                ChainType::Substrate,
+            )
+            .with_endpoint("wss://westend-asset-hub-rpc.polkadot.io")
+            .with_ss58_prefix(GENERIC_SS58_PREFIX)
+            .with_token("WND", 12)
+            .with_block_time(six)
+            .testnet(),
+            ChainInfo::new("asset-hub-paseo", "Asset Hub Paseo", ChainType::Substrate)
+                .with_endpoint("wss://paseo-asset-hub-pub.dwellir.com")
+                .with_ss58_prefix(GENERIC_SS58_PREFIX)
+                .with_token("PAS", 10)
+                .with_block_time(six)
+                .testnet(),
+            // Parachains
+            ChainInfo::from_chain("moonbeam", Chain::Moonbeam)
+                .with_token("GLMR", 18)
+                .with_block_time(six),
+            ChainInfo::from_chain("astar", Chain::Astar)
+                .with_token("ASTR", 18)
+                .with_block_time(six),
+            ChainInfo::from_chain("acala", Chain::Acala)
+                .with_token("ACA", 12)
+                .with_block_time(twelve),
+            ChainInfo::from_chain("phala", Chain::Phala)
+                .with_token("PHA", 12)
+                .with_block_time(twelve),
+            ChainInfo::from_chain("bifrost", Chain::Bifrost)
+                .with_token("BNC", 12)
+                .with_block_time(twelve),
+            // EVM
+            ChainInfo::from_chain("ethereum", Chain::Ethereum)
+                .with_token("ETH", 18)
+                .with_block_time(twelve),
+            ChainInfo::new("sepolia", "Sepolia", ChainType::Evm)
+                .with_endpoint("https://ethereum-sepolia-rpc.publicnode.com")
+                .with_evm_chain_id(11_155_111)
+                .with_token("ETH", 18)
+                .with_block_time(twelve)
+                .testnet(),
+            ChainInfo::from_chain("bsc", Chain::BinanceSmartChain)
+                .with_token("BNB", 18)
+                .with_block_time(Duration::from_secs(3)),
+            ChainInfo::from_chain("polygon", Chain::Polygon)
+                .with_token("POL", 18)
+                .with_block_time(Duration::from_secs(2)),
+            ChainInfo::from_chain("avalanche", Chain::Avalanche)
+                .with_token("AVAX", 18)
+                .with_block_time(Duration::from_secs(2)),
+            ChainInfo::from_chain("arbitrum", Chain::Arbitrum)
+                .with_token("ETH", 18)
+                .with_block_time(Duration::from_millis(250)),
+            ChainInfo::from_chain("optimism", Chain::Optimism)
+                .with_token("ETH", 18)
+                .with_block_time(Duration::from_secs(2)),
+            ChainInfo::from_chain("zksync", Chain::ZkSync)
+                .with_token("ETH", 18)
+                .with_block_time(Duration::from_secs(1)),
+            ChainInfo::from_chain("base", Chain::Base)
+                .with_token("ETH", 18)
+                .with_block_time(Duration::from_secs(2)),
+        ];
+
+        Self { chains }
+    }
+
+    /// Create a registry without entries
+    pub fn empty() -> Self {
+        Self { chains: Vec::new() }
+    }
+
+    /// Add an entry, replacing any entry with the same id
+    pub fn with_chain(mut self, info: ChainInfo) -> Self {
+        self.register(info);
+        self
+    }
+
+    /// Add an entry, replacing any entry with the same id
+    pub fn register(&mut self, info: ChainInfo) {
+        match self.chains.iter_mut().find(|entry| entry.id == info.id) {
+            Some(entry) => *entry = info,
+            None => self.chains.push(info),
+        }
+    }
+
+    /// All entries, in registration order
+    pub fn chains(&self) -> &[ChainInfo] {
+        &self.chains
+    }
+
+    /// Find an entry by id, display name or [`Chain`] alias, ignoring case
+    /// and separators
+    pub fn get(&self, name: &str) -> Option<&ChainInfo> {
+        self.chains.iter().find(|info| info.matches(name))
+    }
+
+    /// Find the entry of a [`Chain`] variant
+    pub fn for_chain(&self, chain: &Chain) -> Option<&ChainInfo> {
+        self.chains
+            .iter()
+            .find(|info| info.chain.as_ref() == Some(chain))
+    }
+
+    /// Find the entry listing `endpoint` among its RPC endpoints
+    pub fn by_endpoint(&self, endpoint: &str) -> Option<&ChainInfo> {
+        let endpoint = endpoint.trim_end_matches('/');
+        self.chains.iter().find(|info| {
+            info.endpoints
+                .iter()
+                .any(|known| known.trim_end_matches('/').eq_ignore_ascii_case(endpoint))
+        })
+    }
+
+    /// Find an entry by EIP-155 chain ID
+    pub fn by_evm_chain_id(&self, chain_id: u64) -> Option<&ChainInfo> {
+        self.chains
+            .iter()
+            .find(|info| info.evm_chain_id == Some(chain_id))
+    }
+
+    /// Find the first entry whose native token is `symbol`, ignoring case
+    pub fn by_token_symbol(&self, symbol: &str) -> Option<&ChainInfo> {
+        self.chains
+            .iter()
+            .find(|info| info.token_symbol.eq_ignore_ascii_case(symbol))
+    }
+
+    /// Parse a human amount such as `1.5 DOT` using the decimals of the
+    /// token named by its unit suffix
+    ///
+    /// Chains sharing a symbol share its decimals, so the first match is
+    /// used. Amounts without a unit are rejected, since their decimals are
+    /// unknown; use [`ChainInfo::parse_amount`] when the chain is.
+    pub fn parse_amount(&self, input: &str) -> Result<u128, ValidationError> {
+        match split_amount(input)? {
+            (_, Some(unit)) => self
+                .by_token_symbol(unit)
+                .ok_or_else(|| {
+                    ValidationError::InvalidAmount(format!(
+                        "{} (unknown unit {})",
+                        input.trim(),
+                        unit
+                    ))
+                })?
+                .parse_amount(input),
+            (_, None) => Err(ValidationError::InvalidAmount(format!(
+                "{} (a unit such as DOT is required without a chain)",
+                input.trim()
+            ))),
+        }
+    }
 }
 
 /// Validates an EVM address format (0x followed by 40 hex characters)
@@ -533,6 +1241,76 @@ pub fn extract_ss58_prefix(address: &str) -> Option<u16> {
     Some(network_id)
 }
 
+/// Generic SS58 network prefix used when none is specified
+pub const GENERIC_SS58_PREFIX: u16 = 42;
+
+/// Highest SS58 network prefix representable in the two-byte encoding
+const MAX_SS58_PREFIX: u16 = 0x3fff;
+
+/// Decode an SS58 address into its network prefix and 32-byte account id
+fn decode_ss58(addr: &str) -> Result<(u16, [u8; 32]), ValidationError> {
+    let decoded = bs58::decode(addr)
+        .into_vec()
+        .map_err(|_| ValidationError::UnrecognizedAddress(addr.to_string()))?;
+
+    let (prefix, prefix_len) = match decoded.first() {
+        Some(&b) if b < 64 => (u16::from(b), 1),
+        Some(&b) if b < 128 && decoded.len() > 1 => {
+            let lower = (b << 2) | (decoded[1] >> 6);
+            let upper = decoded[1] & 0b0011_1111;
+            (u16::from(lower) | (u16::from(upper) << 8), 2)
+        }
+        _ => return Err(ValidationError::InvalidSubstrateAddress(addr.to_string())),
+    };
+
+    if decoded.len() != prefix_len + 32 + 2 {
+        return Err(ValidationError::InvalidSubstrateAddress(addr.to_string()));
+    }
+
+    let body_len = prefix_len + 32;
+    if ss58_checksum(&decoded[..body_len])[..2] != decoded[body_len..] {
+        return Err(ValidationError::InvalidSs58Checksum(addr.to_string()));
+    }
+
+    let mut account = [0u8; 32];
+    account.copy_from_slice(&decoded[prefix_len..body_len]);
+    Ok((prefix, account))
+}
+
+/// Encode a 32-byte account id as an SS58 address
+fn encode_ss58(account: &[u8; 32], prefix: u16) -> Result<String, ValidationError> {
+    let mut body = match prefix {
+        0..=63 => vec![prefix as u8],
+        64..=MAX_SS58_PREFIX => vec![
+            ((prefix & 0b0000_0000_1111_1100) >> 2) as u8 | 0b0100_0000,
+            (prefix >> 8) as u8 | ((prefix & 0b0000_0000_0000_0011) << 6) as u8,
+        ],
+        _ => return Err(ValidationError::InvalidSs58Prefix(prefix)),
+    };
+    body.extend_from_slice(account);
+
+    let checksum = ss58_checksum(&body);
+    body.extend_from_slice(&checksum[..2]);
+    Ok(bs58::encode(body).into_string())
+}
+
+fn ss58_checksum(body: &[u8]) -> [u8; 64] {
+    let mut hasher = Blake2b512::new();
+    hasher.update(b"SS58PRE");
+    hasher.update(body);
+    hasher.finalize().into()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
 /// Generic address type for different chains
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Address {
@@ -653,18 +1431,9 @@ impl Address {
     ) -> Result<Self, ValidationError> {
         let addr_str = addr.into();
 
-        let expected_ss58_format = match chain {
-            Chain::Polkadot => 0,
-            Chain::Kusama => 2,
-            Chain::Westend => 42,
-            Chain::Paseo => 42, // Same as Westend
-            Chain::Moonbeam => 1284,
-            Chain::Astar => 5,
-            Chain::Acala => 10,
-            Chain::Phala => 30,
-            Chain::Bifrost => 6,
-            _ => return Err(ValidationError::ChainIdNotFound(chain.name().to_string())),
-        };
+        let expected_ss58_format = chain
+            .ss58_prefix()
+            .ok_or_else(|| ValidationError::ChainIdNotFound(chain.name().to_string()))?;
 
         if !validate_ss58_for_network(&addr_str, expected_ss58_format) {
             return Err(ValidationError::InvalidSs58Checksum(format!(
@@ -695,6 +1464,140 @@ impl Address {
         }
     }
 
+    /// Parse and normalize an address from user input
+    ///
+    /// Accepts:
+    /// - `0x` + 40 hex characters: EVM address, returned in EIP-55 checksum form
+    /// - `0x` + 64 hex characters: raw account id, encoded with the generic SS58 prefix
+    /// - SS58 strings: validated and kept as given
+    ///
+    /// Surrounding whitespace is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apex_sdk_types::Address;
+    ///
+    /// let addr = Address::parse("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+    /// assert_eq!(addr.as_str(), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+    /// ```
+    pub fn parse(input: &str) -> Result<Self, ValidationError> {
+        let input = input.trim();
+
+        if let Some(hex) = input
+            .strip_prefix("0x")
+            .or_else(|| input.strip_prefix("0X"))
+        {
+            return match hex.len() {
+                40 => {
+                    let addr = format!("0x{}", hex);
+                    if !is_valid_evm_format(&addr) {
+                        return Err(ValidationError::InvalidEvmAddress(input.to_string()));
+                    }
+                    if !validate_eip55_checksum(&addr) {
+                        return Err(ValidationError::InvalidChecksum(input.to_string()));
+                    }
+                    Ok(Address::Evm(to_checksum_address(&addr)))
+                }
+                64 => {
+                    let bytes = decode_hex(hex)
+                        .ok_or_else(|| ValidationError::UnrecognizedAddress(input.to_string()))?;
+                    let mut account = [0u8; 32];
+                    account.copy_from_slice(&bytes);
+                    Address::from_account_id(&account, GENERIC_SS58_PREFIX)
+                }
+                _ => Err(ValidationError::UnrecognizedAddress(input.to_string())),
+            };
+        }
+
+        decode_ss58(input)?;
+        Ok(Address::Substrate(input.to_string()))
+    }
+
+    /// Create a Substrate address from a raw 32-byte account id
+    pub fn from_account_id(account: &[u8; 32], ss58_prefix: u16) -> Result<Self, ValidationError> {
+        encode_ss58(account, ss58_prefix).map(Address::Substrate)
+    }
+
+    /// Validate the address and return its canonical form
+    ///
+    /// EVM addresses are returned in EIP-55 checksum form; Substrate
+    /// addresses are returned unchanged once validated.
+    pub fn normalize(&self) -> Result<Self, ValidationError> {
+        self.validate()?;
+        Ok(match self {
+            Address::Evm(addr) => Address::Evm(to_checksum_address(addr)),
+            Address::Substrate(_) => self.clone(),
+        })
+    }
+
+    /// Check if this is a Substrate address
+    pub fn is_substrate(&self) -> bool {
+        matches!(self, Address::Substrate(_))
+    }
+
+    /// Check if this is an EVM address
+    pub fn is_evm(&self) -> bool {
+        matches!(self, Address::Evm(_))
+    }
+
+    /// Get the 32-byte account id of a Substrate address
+    pub fn account_id(&self) -> Result<[u8; 32], ValidationError> {
+        match self {
+            Address::Substrate(addr) => decode_ss58(addr).map(|(_, account)| account),
+            Address::Evm(addr) => Err(ValidationError::UnexpectedAddressType {
+                expected: "Substrate",
+                address: addr.clone(),
+            }),
+        }
+    }
+
+    /// Get the SS58 network prefix of a Substrate address
+    pub fn ss58_prefix(&self) -> Option<u16> {
+        match self {
+            Address::Substrate(addr) => decode_ss58(addr).ok().map(|(prefix, _)| prefix),
+            Address::Evm(_) => None,
+        }
+    }
+
+    /// Re-encode a Substrate address for another network
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apex_sdk_types::Address;
+    ///
+    /// let generic = Address::parse("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY").unwrap();
+    /// let polkadot = generic.with_ss58_prefix(0).unwrap();
+    /// assert_eq!(polkadot.ss58_prefix(), Some(0));
+    /// assert_eq!(polkadot.account_id(), generic.account_id());
+    /// ```
+    pub fn with_ss58_prefix(&self, ss58_prefix: u16) -> Result<Self, ValidationError> {
+        Address::from_account_id(&self.account_id()?, ss58_prefix)
+    }
+
+    /// Ensure this is a Substrate address and return its SS58 string
+    pub fn expect_substrate(&self) -> Result<&str, ValidationError> {
+        match self {
+            Address::Substrate(addr) => Ok(addr),
+            Address::Evm(addr) => Err(ValidationError::UnexpectedAddressType {
+                expected: "Substrate",
+                address: addr.clone(),
+            }),
+        }
+    }
+
+    /// Ensure this is an EVM address and return its hex string
+    pub fn expect_evm(&self) -> Result<&str, ValidationError> {
+        match self {
+            Address::Evm(addr) => Ok(addr),
+            Address::Substrate(addr) => Err(ValidationError::UnexpectedAddressType {
+                expected: "EVM",
+                address: addr.clone(),
+            }),
+        }
+    }
+
     /// Validate the address format and checksum
     ///
     /// For EVM addresses, validates EIP-55 checksum.
@@ -720,6 +1623,81 @@ impl Address {
     }
 }
 
+/// Conversion into a validated, normalized [`Address`]
+///
+/// Public APIs that take an address accept `impl IntoAddress`, so callers can
+/// pass an [`Address`], an SS58 or hex string, or raw key bytes. The conversion
+/// validates checksums and normalizes EVM addresses to EIP-55 form.
+///
+/// # Example
+///
+/// ```
+/// use apex_sdk_types::{Address, IntoAddress};
+///
+/// let from_str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".into_address().unwrap();
+/// let from_bytes = [0u8; 20].into_address().unwrap();
+///
+/// assert!(from_str.is_substrate());
+/// assert_eq!(from_bytes, Address::evm("0x0000000000000000000000000000000000000000"));
+/// ```
+pub trait IntoAddress {
+    /// Validate and convert into an [`Address`]
+    fn into_address(self) -> Result<Address, ValidationError>;
+}
+
+impl IntoAddress for Address {
+    fn into_address(self) -> Result<Address, ValidationError> {
+        self.normalize()
+    }
+}
+
+impl IntoAddress for &Address {
+    fn into_address(self) -> Result<Address, ValidationError> {
+        self.normalize()
+    }
+}
+
+impl IntoAddress for &str {
+    fn into_address(self) -> Result<Address, ValidationError> {
+        Address::parse(self)
+    }
+}
+
+impl IntoAddress for String {
+    fn into_address(self) -> Result<Address, ValidationError> {
+        Address::parse(&self)
+    }
+}
+
+impl IntoAddress for &String {
+    fn into_address(self) -> Result<Address, ValidationError> {
+        Address::parse(self)
+    }
+}
+
+/// Raw 20-byte EVM address
+impl IntoAddress for [u8; 20] {
+    fn into_address(self) -> Result<Address, ValidationError> {
+        let hex: String = self.iter().map(|b| format!("{:02x}", b)).collect();
+        Ok(Address::Evm(to_checksum_address(&hex)))
+    }
+}
+
+/// Raw 32-byte account id, encoded with the generic SS58 prefix
+impl IntoAddress for [u8; 32] {
+    fn into_address(self) -> Result<Address, ValidationError> {
+        Address::from_account_id(&self, GENERIC_SS58_PREFIX)
+    }
+}
+
+impl std::str::FromStr for Address {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Address::parse(s)
+    }
+}
+
 impl std::fmt::Display for Address {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -729,6 +1707,345 @@ impl std::fmt::Display for Address {
     }
 }
 
+/// Defines a 32-byte hash newtype
+///
+/// Hashes parse from and display as `0x` followed by 64 lowercase hex digits,
+/// and serialize as that string.
+macro_rules! hash_type {
+    ($(#[$meta:meta])* $name:ident, $kind:literal) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+        pub struct $name([u8; 32]);
+
+        impl $name {
+            /// Wrap raw hash bytes
+            pub const fn new(bytes: [u8; 32]) -> Self {
+                Self(bytes)
+            }
+
+            /// Parse a `0x`-prefixed hex hash
+            pub fn parse(s: &str) -> Result<Self, ValidationError> {
+                let invalid = || ValidationError::InvalidHash {
+                    kind: $kind,
+                    value: s.to_string(),
+                };
+                let digits = s.strip_prefix("0x").ok_or_else(invalid)?;
+                let mut bytes = [0u8; 32];
+                hex::decode_to_slice(digits, &mut bytes).map_err(|_| invalid())?;
+                Ok(Self(bytes))
+            }
+
+            /// Raw hash bytes
+            pub const fn as_bytes(&self) -> &[u8; 32] {
+                &self.0
+            }
+
+            /// Consume the hash, returning its bytes
+            pub const fn into_bytes(self) -> [u8; 32] {
+                self.0
+            }
+        }
+
+        impl From<[u8; 32]> for $name {
+            fn from(bytes: [u8; 32]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        impl From<$name> for [u8; 32] {
+            fn from(hash: $name) -> Self {
+                hash.0
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = ValidationError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                <[u8; 32]>::try_from(bytes)
+                    .map(Self)
+                    .map_err(|_| ValidationError::InvalidHash {
+                        kind: $kind,
+                        value: format!("{} bytes", bytes.len()),
+                    })
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = ValidationError;
+
+            fn try_from(s: &str) -> Result<Self, Self::Error> {
+                Self::parse(s)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = ValidationError;
+
+            fn try_from(s: String) -> Result<Self, Self::Error> {
+                Self::parse(&s)
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = ValidationError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::parse(s)
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "0x{}", hex::encode(self.0))
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}({})", stringify!($name), self)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                Self::parse(&s).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+hash_type!(
+    /// Hash of a transaction or extrinsic
+    ///
+    /// ```rust
+    /// use apex_sdk_types::TxHash;
+    ///
+    /// let hash: TxHash = "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(hash.as_bytes()[0], 0x5c);
+    /// assert!("0x5c50".parse::<TxHash>().is_err());
+    /// ```
+    TxHash,
+    "transaction hash"
+);
+
+hash_type!(
+    /// Hash of a block
+    BlockHash,
+    "block hash"
+);
+
+/// Opaque continuation token of a paginated list
+///
+/// Each list API decides what its cursors encode (a storage key, an offset,
+/// ...); callers only pass back the [`Page::next_cursor`] they received.
+/// Cursors serialize as `0x`-prefixed hex strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Cursor(Vec<u8>);
+
+impl Cursor {
+    /// Cursor carrying raw bytes, such as the last storage key of a page
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Self(bytes.into())
+    }
+
+    /// Cursor pointing at position `offset` of an ordered list
+    pub fn from_offset(offset: u64) -> Self {
+        Self(offset.to_be_bytes().to_vec())
+    }
+
+    /// Parse a `0x`-prefixed hex cursor
+    pub fn parse(s: &str) -> Result<Self, ValidationError> {
+        s.strip_prefix("0x")
+            .and_then(|digits| hex::decode(digits).ok())
+            .map(Self)
+            .ok_or_else(|| ValidationError::InvalidCursor(s.to_string()))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Offset of a cursor created with [`Cursor::from_offset`]
+    pub fn offset(&self) -> Result<u64, ValidationError> {
+        <[u8; 8]>::try_from(self.0.as_slice())
+            .map(u64::from_be_bytes)
+            .map_err(|_| ValidationError::InvalidCursor(self.to_string()))
+    }
+}
+
+impl std::fmt::Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode(&self.0))
+    }
+}
+
+impl std::str::FromStr for Cursor {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Cursor {
+    type Error = ValidationError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(&s)
+    }
+}
+
+impl From<Cursor> for String {
+    fn from(cursor: Cursor) -> Self {
+        cursor.to_string()
+    }
+}
+
+/// Limit and starting point of a paginated list query
+///
+/// ```rust
+/// use apex_sdk_types::{Page, PageRequest};
+///
+/// let items: Vec<u32> = (0..25).collect();
+/// let first = Page::from_slice(&items, &PageRequest::new(10)).unwrap();
+/// assert_eq!(first.items, (0..10).collect::<Vec<_>>());
+///
+/// let request = first.next_request(10).unwrap();
+/// let second = Page::from_slice(&items, &request).unwrap();
+/// assert_eq!(second.items[0], 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageRequest {
+    /// Maximum number of items to return, between 1 and [`PageRequest::MAX_LIMIT`]
+    pub limit: u32,
+    /// Continue after a previous page; `None` starts from the beginning
+    pub cursor: Option<Cursor>,
+}
+
+impl PageRequest {
+    /// Limit used by [`PageRequest::default`]
+    pub const DEFAULT_LIMIT: u32 = 100;
+    /// Largest accepted limit; larger limits are clamped
+    pub const MAX_LIMIT: u32 = 1000;
+
+    /// First page of at most `limit` items
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit: limit.clamp(1, Self::MAX_LIMIT),
+            cursor: None,
+        }
+    }
+
+    /// Continue after `cursor`
+    pub fn with_cursor(mut self, cursor: Cursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// Limit clamped to the accepted range, for requests built by hand
+    pub fn effective_limit(&self) -> u32 {
+        self.limit.clamp(1, Self::MAX_LIMIT)
+    }
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_LIMIT)
+    }
+}
+
+/// One page of a list, with the cursor of the next page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// Items of this page, in the list's order
+    pub items: Vec<T>,
+    /// Cursor of the next page; `None` on the last page
+    pub next_cursor: Option<Cursor>,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, next_cursor: Option<Cursor>) -> Self {
+        Self { items, next_cursor }
+    }
+
+    /// Final page of a list
+    pub fn last(items: Vec<T>) -> Self {
+        Self::new(items, None)
+    }
+
+    /// Whether more items follow this page
+    pub fn has_more(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+
+    /// Request for the next page of at most `limit` items, if there is one
+    pub fn next_request(&self, limit: u32) -> Option<PageRequest> {
+        self.next_cursor
+            .clone()
+            .map(|cursor| PageRequest::new(limit).with_cursor(cursor))
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Convert the items, keeping the cursor
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+impl<T: Clone> Page<T> {
+    /// Page of an in-memory list, using offset cursors
+    pub fn from_slice(items: &[T], request: &PageRequest) -> Result<Self, ValidationError> {
+        let start = match &request.cursor {
+            Some(cursor) => usize::try_from(cursor.offset()?)
+                .ok()
+                .filter(|offset| *offset <= items.len())
+                .ok_or_else(|| ValidationError::InvalidCursor(cursor.to_string()))?,
+            None => 0,
+        };
+        let end = start
+            .saturating_add(request.effective_limit() as usize)
+            .min(items.len());
+        let next_cursor = (end < items.len()).then(|| Cursor::from_offset(end as u64));
+        Ok(Self::new(items[start..end].to_vec(), next_cursor))
+    }
+}
+
+impl<T> IntoIterator for Page<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
 /// Transaction status
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TxStatus {
@@ -742,6 +2059,9 @@ pub enum TxStatus {
     Finalized,
     /// Transaction failed
     Failed,
+    /// Waiting for the transaction timed out before it reached the required
+    /// confirmations; it may still be included later
+    TimedOut,
     /// Transaction status unknown
     Unknown,
 }
@@ -763,7 +2083,7 @@ pub struct TransactionStatus {
     pub effective_gas_price: Option<u128>,
     /// Number of confirmations
     pub confirmations: Option<u32>,
-    /// Error message (if status is Failed)
+    /// Error message (if status is Failed or TimedOut)
     pub error: Option<String>,
 }
 
@@ -837,6 +2157,20 @@ impl TransactionStatus {
         }
     }
 
+    /// Create a new timed out transaction status
+    pub fn timed_out(hash: String, error: String) -> Self {
+        Self {
+            hash,
+            status: TxStatus::TimedOut,
+            block_number: None,
+            block_hash: None,
+            gas_used: None,
+            effective_gas_price: None,
+            confirmations: None,
+            error: Some(error),
+        }
+    }
+
     pub fn unknown(hash: String) -> Self {
         Self {
             hash,
@@ -1014,6 +2348,22 @@ mod tests {
         assert_eq!(Chain::Paseo.chain_id(), None);
     }
 
+    #[test]
+    fn test_ss58_prefix_and_parachains() {
+        assert_eq!(Chain::Kusama.ss58_prefix(), Some(2));
+        assert_eq!(Chain::Ethereum.ss58_prefix(), None);
+        assert_eq!(Chain::from_ss58_prefix(0), Some(Chain::Polkadot));
+        assert_eq!(Chain::from_ss58_prefix(1284), Some(Chain::Moonbeam));
+        assert_eq!(Chain::from_ss58_prefix(42), None);
+        assert_eq!(Chain::from_ss58_prefix(7), None);
+
+        assert_eq!(Chain::Acala.parachain_id(), Some(2000));
+        assert_eq!(Chain::Acala.relay_chain(), Some(Chain::Polkadot));
+        assert_eq!(Chain::Polkadot.relay_chain(), None);
+        assert!(Chain::Polkadot.is_relay_chain());
+        assert!(!Chain::Moonbeam.is_relay_chain());
+    }
+
     #[test]
     fn test_chain_id_validation() {
         // Valid chain IDs
@@ -1238,6 +2588,88 @@ mod tests {
         assert!(err.to_string().contains("checksum"));
     }
 
+    #[test]
+    fn test_parse_normalizes_evm_checksum() {
+        let addr = Address::parse("  0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed ").unwrap();
+        assert_eq!(
+            addr,
+            Address::Evm("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".to_string())
+        );
+
+        let err = Address::parse("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").unwrap_err();
+        assert!(matches!(err, ValidationError::InvalidChecksum(_)));
+    }
+
+    #[test]
+    fn test_parse_ss58_and_raw_account() {
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let addr = Address::parse(alice).unwrap();
+        assert_eq!(addr.ss58_prefix(), Some(42));
+
+        let account = addr.account_id().unwrap();
+        let hex: String = account.iter().map(|b| format!("{:02x}", b)).collect();
+        let from_hex = Address::parse(&format!("0x{}", hex)).unwrap();
+        assert_eq!(from_hex, addr);
+
+        assert!(matches!(
+            Address::parse("not-an-address"),
+            Err(ValidationError::UnrecognizedAddress(_))
+        ));
+        assert!(matches!(
+            Address::parse("0x1234"),
+            Err(ValidationError::UnrecognizedAddress(_))
+        ));
+    }
+
+    #[test]
+    fn test_ss58_prefix_reencoding() {
+        let alice = Address::parse("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY").unwrap();
+
+        let polkadot = alice.with_ss58_prefix(0).unwrap();
+        assert_eq!(
+            polkadot.as_str(),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+
+        // Two-byte prefixes roundtrip
+        let moonbeam = alice.with_ss58_prefix(1284).unwrap();
+        assert_eq!(moonbeam.ss58_prefix(), Some(1284));
+        assert_eq!(moonbeam.account_id(), alice.account_id());
+
+        assert_eq!(
+            alice.with_ss58_prefix(0x4000),
+            Err(ValidationError::InvalidSs58Prefix(0x4000))
+        );
+    }
+
+    #[test]
+    fn test_into_address_impls() {
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+        assert!(alice.into_address().is_ok());
+        assert!(alice.to_string().into_address().is_ok());
+        assert!((&alice.to_string()).into_address().is_ok());
+        assert!(Address::substrate(alice).into_address().is_ok());
+        assert!(Address::substrate("bogus").into_address().is_err());
+        assert!([1u8; 32].into_address().unwrap().is_substrate());
+        assert!([1u8; 20].into_address().unwrap().is_evm());
+    }
+
+    #[test]
+    fn test_expect_address_type() {
+        let evm = Address::evm("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+
+        assert!(evm.expect_evm().is_ok());
+        assert!(matches!(
+            evm.expect_substrate(),
+            Err(ValidationError::UnexpectedAddressType {
+                expected: "Substrate",
+                ..
+            })
+        ));
+        assert!(evm.account_id().is_err());
+    }
+
     #[test]
     fn test_overflow_protection_in_validation() {
         let long_string = "a".repeat(1000);
@@ -1247,4 +2679,261 @@ mod tests {
         let result = Address::substrate_checked(&long_string);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_hash_parsing_and_serde() {
+        let hex = format!("0x{}", "ab".repeat(32));
+        let hash: TxHash = hex.parse().unwrap();
+        assert_eq!(hash.as_bytes(), &[0xab; 32]);
+        assert_eq!(hash.to_string(), hex);
+        assert_eq!(
+            TxHash::parse(&hex.to_uppercase().replace("0X", "0x")).unwrap(),
+            hash
+        );
+
+        // Prefix and length are required
+        assert!(TxHash::parse(&"ab".repeat(32)).is_err());
+        assert!(TxHash::parse("0xabab").is_err());
+        assert!(TxHash::parse(&format!("0x{}", "zz".repeat(32))).is_err());
+        assert!(BlockHash::try_from(&[0u8; 31][..]).is_err());
+
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(json, format!("\"{}\"", hex));
+        assert_eq!(serde_json::from_str::<TxHash>(&json).unwrap(), hash);
+        assert!(serde_json::from_str::<BlockHash>("\"0x01\"").is_err());
+    }
+
+    #[test]
+    fn test_chain_registry() {
+        let registry = ChainRegistry::new();
+
+        for chain in [
+            Chain::Polkadot,
+            Chain::Kusama,
+            Chain::Westend,
+            Chain::Paseo,
+            Chain::Moonbeam,
+            Chain::Astar,
+            Chain::Acala,
+            Chain::Phala,
+            Chain::Bifrost,
+            Chain::Ethereum,
+            Chain::BinanceSmartChain,
+            Chain::Polygon,
+            Chain::Avalanche,
+            Chain::Arbitrum,
+            Chain::Optimism,
+            Chain::ZkSync,
+            Chain::Base,
+        ] {
+            let info = registry.for_chain(&chain).unwrap();
+            assert_eq!(info.default_endpoint(), Some(chain.default_endpoint()));
+            assert_eq!(info.ss58_prefix, chain.ss58_prefix());
+            assert_eq!(info.evm_chain_id, chain.chain_id());
+        }
+
+        let polkadot = registry.get("Polkadot").unwrap();
+        assert_eq!(polkadot.token_symbol, "DOT");
+        assert_eq!(polkadot.token_decimals, 10);
+        assert_eq!(polkadot.block_time(), std::time::Duration::from_secs(6));
+        assert_eq!(registry.get("bsc").unwrap().token_symbol, "BNB");
+        assert_eq!(registry.get("Binance Smart Chain").unwrap().id, "bsc");
+        assert_eq!(
+            registry.get("Asset Hub Westend").unwrap().id,
+            "asset-hub-westend"
+        );
+        assert!(registry.get("unknown").is_none());
+
+        assert_eq!(
+            registry
+                .by_endpoint("wss://westend-rpc.polkadot.io/")
+                .unwrap()
+                .id,
+            "westend"
+        );
+        assert_eq!(registry.by_evm_chain_id(11_155_111).unwrap().id, "sepolia");
+
+        let registry = registry.with_chain(
+            ChainInfo::new("local", "Local Node", ChainType::Substrate)
+                .with_endpoint("ws://127.0.0.1:9944")
+                .with_token("DEV", 18)
+                .testnet(),
+        );
+        let local = registry.by_endpoint("ws://127.0.0.1:9944").unwrap();
+        assert_eq!(local.format_amount(1_500_000_000_000_000_000), "1.5 DEV");
+
+        assert_eq!(format_units(0, 10), "0");
+        assert_eq!(format_units(1, 10), "0.0000000001");
+        assert_eq!(format_units(25_000_000_000, 10), "2.5");
+        assert_eq!(format_units(42, 0), "42");
+        assert_eq!(format_units(u128::MAX, 255), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let code = ErrorCode::new(1203);
+        assert_eq!(code.to_string(), "APEX-1203");
+        assert_eq!(ErrorCode::parse("APEX-1203"), Some(code));
+        assert_eq!(ErrorCode::parse("apex-1203"), Some(code));
+        assert_eq!(ErrorCode::parse("1203"), Some(code));
+        assert_eq!(ErrorCode::parse("APEX-"), None);
+        assert_eq!(ErrorCode::parse("E1203"), None);
+        assert_eq!(serde_json::to_string(&code).unwrap(), "1203");
+
+        let errors = [
+            ValidationError::InvalidEvmAddress(String::new()),
+            ValidationError::InvalidChecksum(String::new()),
+            ValidationError::InvalidChainId {
+                chain: String::new(),
+                expected: 1,
+                actual: 2,
+            },
+            ValidationError::ChainIdNotFound(String::new()),
+            ValidationError::InvalidSubstrateAddress(String::new()),
+            ValidationError::InvalidSs58Checksum(String::new()),
+            ValidationError::InvalidSs58Prefix(0),
+            ValidationError::UnrecognizedAddress(String::new()),
+            ValidationError::InvalidHash {
+                kind: "hash",
+                value: String::new(),
+            },
+            ValidationError::InvalidCursor(String::new()),
+            ValidationError::UnexpectedAddressType {
+                expected: "EVM",
+                address: String::new(),
+            },
+            ValidationError::InvalidAmount(String::new()),
+        ];
+        let codes: std::collections::HashSet<_> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(codes.len(), errors.len());
+        assert!(codes.iter().all(|code| (1600..1700).contains(&code.get())));
+    }
+
+    #[test]
+    fn test_parse_amounts() {
+        assert_eq!(parse_units("1.5", 10), Ok(15_000_000_000));
+        assert_eq!(parse_units("0.0000000001", 10), Ok(1));
+        assert_eq!(parse_units(".5", 1), Ok(5));
+        assert_eq!(parse_units("3.", 2), Ok(300));
+        assert_eq!(parse_units("2.50000", 1), Ok(25));
+        assert_eq!(parse_units("42", 0), Ok(42));
+        assert!(parse_units("0.00000000001", 10).is_err());
+        assert!(parse_units("1.5", 0).is_err());
+        assert!(parse_units("-1", 10).is_err());
+        assert!(parse_units("1e3", 10).is_err());
+        assert!(parse_units("", 10).is_err());
+        assert!(parse_units(".", 10).is_err());
+        assert!(parse_units("1.2.3", 10).is_err());
+        assert!(parse_units(&u128::MAX.to_string(), 1).is_err());
+        for raw in [0, 1, 25_000_000_000, u128::MAX / 10] {
+            assert_eq!(parse_units(&format_units(raw, 10), 10), Ok(raw));
+        }
+
+        let registry = ChainRegistry::new();
+        let polkadot = registry.get("polkadot").unwrap();
+        assert_eq!(polkadot.parse_amount("1.5 DOT"), Ok(15_000_000_000));
+        assert_eq!(polkadot.parse_amount(" 1.5dot "), Ok(15_000_000_000));
+        assert_eq!(polkadot.parse_amount("2"), Ok(20_000_000_000));
+        assert!(matches!(
+            polkadot.parse_amount("1 KSM"),
+            Err(ValidationError::InvalidAmount(_))
+        ));
+        assert!(polkadot.parse_amount("DOT").is_err());
+        assert!(polkadot.parse_amount("1 D-T").is_err());
+
+        assert_eq!(registry.parse_amount("0.01 WND"), Ok(10_000_000_000));
+        assert_eq!(
+            registry.parse_amount("1 ETH"),
+            Ok(1_000_000_000_000_000_000)
+        );
+        assert!(registry.parse_amount("1 XYZ").is_err());
+        assert!(registry.parse_amount("1").is_err());
+    }
+
+    #[test]
+    fn test_chain_properties() {
+        let properties = serde_json::json!({
+            "ss58Format": 0,
+            "tokenDecimals": 10,
+            "tokenSymbol": "DOT"
+        });
+        let parsed = ChainProperties::from_system_properties(properties.as_object().unwrap());
+        assert_eq!(parsed.token_symbol, "DOT");
+        assert_eq!(parsed.token_decimals, 10);
+        assert_eq!(parsed.ss58_format, Some(0));
+        assert_eq!(parsed.format_amount(15_000_000_000), "1.5 DOT");
+
+        let multi = serde_json::json!({
+            "tokenDecimals": [12, 12],
+            "tokenSymbol": ["ACA", "AUSD"]
+        });
+        let parsed = ChainProperties::from_system_properties(multi.as_object().unwrap());
+        assert_eq!(parsed.token_symbol, "ACA");
+        assert_eq!(parsed.token_decimals, 12);
+        assert_eq!(parsed.ss58_format, None);
+
+        let empty = ChainProperties::from_system_properties(&serde_json::Map::new());
+        assert_eq!(empty, ChainProperties::default());
+
+        let symbol_only = serde_json::json!({ "tokenSymbol": "WND" });
+        let merged = ChainProperties {
+            token_symbol: "DOT".to_string(),
+            token_decimals: 10,
+            ss58_format: Some(0),
+        }
+        .with_system_properties(symbol_only.as_object().unwrap());
+        assert_eq!(merged.token_symbol, "WND");
+        assert_eq!(merged.token_decimals, 10);
+        assert_eq!(merged.ss58_format, Some(0));
+
+        let registry = ChainRegistry::new();
+        let kusama = registry.get("kusama").unwrap().properties();
+        assert_eq!(kusama.token_symbol, "KSM");
+        assert_eq!(kusama.token_decimals, 12);
+        assert_eq!(kusama.ss58_format, Some(2));
+    }
+
+    #[test]
+    fn test_pagination() {
+        let items: Vec<u32> = (0..25).collect();
+
+        let mut request = PageRequest::new(10);
+        let mut seen = Vec::new();
+        let mut pages = 0;
+        loop {
+            let page = Page::from_slice(&items, &request).unwrap();
+            pages += 1;
+            seen.extend(page.items.iter().copied());
+            match page.next_request(10) {
+                Some(next) => request = next,
+                None => break,
+            }
+        }
+        assert_eq!(pages, 3);
+        assert_eq!(seen, items);
+
+        // Limits are clamped to 1..=MAX_LIMIT
+        assert_eq!(PageRequest::new(0).limit, 1);
+        assert_eq!(PageRequest::new(u32::MAX).limit, PageRequest::MAX_LIMIT);
+        assert_eq!(PageRequest::default().limit, PageRequest::DEFAULT_LIMIT);
+
+        // Cursors round-trip through strings and serde
+        let cursor = Cursor::from_offset(20);
+        assert_eq!(cursor.to_string().parse::<Cursor>().unwrap(), cursor);
+        let json = serde_json::to_string(&cursor).unwrap();
+        assert_eq!(serde_json::from_str::<Cursor>(&json).unwrap(), cursor);
+        assert!(Cursor::parse("20").is_err());
+
+        // Cursors from elsewhere or past the end are rejected
+        let foreign = PageRequest::new(10).with_cursor(Cursor::from_bytes(vec![1, 2, 3]));
+        assert!(Page::from_slice(&items, &foreign).is_err());
+        let past_end = PageRequest::new(10).with_cursor(Cursor::from_offset(26));
+        assert!(Page::from_slice(&items, &past_end).is_err());
+
+        let page = Page::from_slice(&items, &PageRequest::new(5))
+            .unwrap()
+            .map(|i| i * 2);
+        assert_eq!(page.items, vec![0, 2, 4, 6, 8]);
+        assert!(page.has_more());
+    }
 }
@@ -367,6 +367,15 @@ fn test_transaction_status_failed() {
     assert_eq!(status.block_number, None);
 }
 
+#[test]
+fn test_transaction_status_timed_out() {
+    let status = TransactionStatus::timed_out("0x789".to_string(), "Timed out".to_string());
+
+    assert_eq!(status.status, TxStatus::TimedOut);
+    assert_eq!(status.error, Some("Timed out".to_string()));
+    assert!(!status.is_confirmed());
+}
+
 #[test]
 fn test_transaction_status_unknown() {
     let status = TransactionStatus::unknown("0xabc".to_string());
@@ -1,5 +1,7 @@
 //! Advanced features and utilities.
 
+use crate::error::Error;
+use crate::error_recovery::{with_retry_if, RetryConfig};
 use crate::sdk::ApexSDK;
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -138,13 +140,35 @@ impl TransactionBatch {
     }
 }
 
+/// Outcome of one transaction in a batch
+#[derive(Debug, Clone)]
+pub struct TransactionOutcome {
+    /// Position of the transaction in the batch
+    pub index: usize,
+    /// Number of execution attempts made
+    pub attempts: usize,
+    /// Source transaction hash, if execution succeeded
+    pub tx_hash: Option<String>,
+    /// Error of the last attempt, if every attempt failed
+    pub error: Option<String>,
+}
+
+impl TransactionOutcome {
+    /// Check if the transaction was executed
+    pub fn is_success(&self) -> bool {
+        self.tx_hash.is_some()
+    }
+}
+
 /// Result of a parallel batch execution
 #[derive(Debug)]
 pub struct BatchExecutionResult {
-    /// Successfully executed transactions
+    /// Successfully executed transactions, in batch order
     pub successes: Vec<crate::transaction::TransactionResult>,
-    /// Failed transactions with their errors
+    /// Failed transactions with their errors, in batch order
     pub failures: Vec<(crate::transaction::Transaction, crate::error::Error)>,
+    /// Per-transaction outcomes, in batch order
+    pub outcomes: Vec<TransactionOutcome>,
     /// Total execution time in milliseconds
     pub execution_time_ms: u128,
 }
@@ -172,18 +196,35 @@ impl BatchExecutionResult {
         }
         (self.success_count() as f64 / self.total() as f64) * 100.0
     }
+
+    /// Check if some, but not all, transactions failed
+    pub fn is_partial_failure(&self) -> bool {
+        !self.successes.is_empty() && !self.failures.is_empty()
+    }
+
+    /// Batch positions of the failed transactions
+    pub fn failed_indices(&self) -> Vec<usize> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| !outcome.is_success())
+            .map(|outcome| outcome.index)
+            .collect()
+    }
 }
 
 /// Parallel executor for high-throughput transaction execution
 ///
 /// Executes multiple transactions concurrently using tokio tasks,
 /// with configurable concurrency limits to prevent overwhelming the network.
+/// Transactions failing with a connection error before submission are
+/// retried according to the retry policy; other errors are final, since the
+/// transaction may already be on chain.
 ///
 /// # Example
 ///
 /// ```rust,no_run
 /// use apex_sdk::advanced::{ParallelExecutor, TransactionBatch};
-/// use apex_sdk::ApexSDK;
+/// use apex_sdk::{ApexSDK, RetryConfig};
 /// use std::sync::Arc;
 ///
 /// # async fn example(sdk: ApexSDK) -> Result<(), Box<dyn std::error::Error>> {
@@ -191,35 +232,55 @@ impl BatchExecutionResult {
 /// // Add transactions to batch...
 ///
 /// let sdk = Arc::new(sdk);
-/// let executor = ParallelExecutor::new(sdk, 10); // Max 10 concurrent transactions
+/// let executor = ParallelExecutor::new(sdk, 10) // Max 10 concurrent transactions
+///     .with_retry_policy(RetryConfig::builder().max_attempts(3).build());
 /// let result = executor.execute_batch(batch).await;
 ///
 /// println!("Executed {} transactions", result.total());
 /// println!("Success rate: {:.2}%", result.success_rate());
+/// if result.is_partial_failure() {
+///     println!("Failed: {:?}", result.failed_indices());
+/// }
 /// # Ok(())
 /// # }
 /// ```
 pub struct ParallelExecutor {
     sdk: Arc<crate::sdk::ApexSDK>,
     concurrency: usize,
+    retry: RetryConfig,
 }
 
 impl ParallelExecutor {
     /// Create a new parallel executor with the specified concurrency limit
     ///
+    /// Transactions are attempted once; see
+    /// [`with_retry_policy`](Self::with_retry_policy).
+    ///
     /// # Arguments
     ///
     /// * `sdk` - The Apex SDK instance to use for executing transactions
     /// * `concurrency` - Maximum number of concurrent transactions (recommended: 5-20)
     pub fn new(sdk: Arc<crate::sdk::ApexSDK>, concurrency: usize) -> Self {
         let concurrency = if concurrency == 0 { 1 } else { concurrency };
-        Self { sdk, concurrency }
+        Self {
+            sdk,
+            concurrency,
+            retry: RetryConfig::builder().max_attempts(1).build(),
+        }
+    }
+
+    /// Set the retry policy applied to each transaction
+    pub fn with_retry_policy(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
     }
 
     /// Execute a batch of transactions in parallel
     ///
     /// Transactions are executed concurrently up to the configured concurrency limit.
-    /// Results include both successful and failed transactions, along with timing metrics.
+    /// Results include both successful and failed transactions in batch order,
+    /// along with per-transaction outcomes and timing metrics. A failing
+    /// transaction does not stop the rest of the batch.
     ///
     /// # Arguments
     ///
@@ -236,6 +297,7 @@ impl ParallelExecutor {
             return BatchExecutionResult {
                 successes: vec![],
                 failures: vec![],
+                outcomes: vec![],
                 execution_time_ms: 0,
             };
         }
@@ -247,11 +309,13 @@ impl ParallelExecutor {
         );
 
         let semaphore = Arc::new(tokio::sync::Semaphore::new(self.concurrency));
-        let mut tasks = Vec::new();
+        let mut tasks = Vec::with_capacity(transactions.len());
 
-        for tx in transactions {
+        for tx in &transactions {
+            let tx = tx.clone();
             let sdk: Arc<ApexSDK> = Arc::clone(&self.sdk);
             let semaphore = Arc::clone(&semaphore);
+            let retry = self.retry.clone();
 
             let task = tokio::spawn(async move {
                 let _permit = semaphore
@@ -259,12 +323,12 @@ impl ParallelExecutor {
                     .await
                     .expect("Semaphore should not be closed during batch execution");
 
-                let result = sdk.execute(tx.clone()).await;
-
-                match result {
-                    Ok(tx_result) => Ok(tx_result),
-                    Err(e) => Err((tx, e)),
-                }
+                with_retry_if(
+                    || sdk.execute(tx.clone()),
+                    |e| matches!(e, Error::Connection(_)),
+                    retry,
+                )
+                .await
             });
 
             tasks.push(task);
@@ -272,13 +336,48 @@ impl ParallelExecutor {
 
         let mut successes = Vec::new();
         let mut failures = Vec::new();
+        let mut outcomes = Vec::with_capacity(tasks.len());
 
-        for task in tasks {
-            match task.await {
-                Ok(Ok(tx_result)) => successes.push(tx_result),
-                Ok(Err((tx, error))) => failures.push((tx, error)),
+        // Awaiting in spawn order keeps results in batch order
+        for (index, (task, tx)) in tasks.into_iter().zip(transactions).enumerate() {
+            let (result, attempts) = match task.await {
+                Ok(result) => result,
                 Err(join_error) => {
                     tracing::error!("Task join error: {}", join_error);
+                    (
+                        Err(Error::Other(format!(
+                            "Transaction execution task failed: {}",
+                            join_error
+                        ))),
+                        1,
+                    )
+                }
+            };
+
+            match result {
+                Ok(tx_result) => {
+                    outcomes.push(TransactionOutcome {
+                        index,
+                        attempts,
+                        tx_hash: Some(tx_result.source_tx_hash.clone()),
+                        error: None,
+                    });
+                    successes.push(tx_result);
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        "Transaction {} of batch failed after {} attempt(s): {}",
+                        index,
+                        attempts,
+                        error
+                    );
+                    outcomes.push(TransactionOutcome {
+                        index,
+                        attempts,
+                        tx_hash: None,
+                        error: Some(error.to_string()),
+                    });
+                    failures.push((tx, error));
                 }
             }
         }
@@ -295,6 +394,7 @@ impl ParallelExecutor {
         BatchExecutionResult {
             successes,
             failures,
+            outcomes,
             execution_time_ms,
         }
     }
@@ -312,8 +412,8 @@ mod tests {
         assert_eq!(batch.len(), 0);
 
         let tx = Transaction::builder()
-            .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbD")
-            .to_evm_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
+            .from_address("0x742d35cc6634c0532925a3b844bc9e7595f0bebd")
+            .to_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
             .amount(1000)
             .build()
             .unwrap();
@@ -331,8 +431,8 @@ mod tests {
         let mut batch = TransactionBatch::new();
 
         let tx = Transaction::builder()
-            .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbD")
-            .to_evm_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
+            .from_address("0x742d35cc6634c0532925a3b844bc9e7595f0bebd")
+            .to_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
             .amount(1000)
             .build()
             .unwrap();
@@ -536,6 +636,7 @@ mod tests {
         let result = BatchExecutionResult {
             successes: vec![],
             failures: vec![],
+            outcomes: vec![],
             execution_time_ms: 0,
         };
 
@@ -557,13 +658,14 @@ mod tests {
             ],
             failures: vec![(
                 Transaction::builder()
-                    .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbD")
-                    .to_evm_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
+                    .from_address("0x742d35cc6634c0532925a3b844bc9e7595f0bebd")
+                    .to_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
                     .amount(1000)
                     .build()
                     .unwrap(),
                 crate::error::Error::Transaction("Test error".to_string()),
             )],
+            outcomes: vec![],
             execution_time_ms: 1000,
         };
 
@@ -634,6 +736,113 @@ mod tests {
         std::mem::forget(sdk);
     }
 
+    #[tokio::test]
+    async fn test_parallel_executor_reports_failures_in_order() {
+        let executor = ParallelExecutor::new(Arc::new(ApexSDK::without_adapters()), 2)
+            .with_retry_policy(RetryConfig::builder().max_attempts(3).build());
+
+        let mut batch = TransactionBatch::new();
+        for amount in [1, 2, 3] {
+            batch.add_transaction(
+                Transaction::builder()
+                    .from_address("0x742d35cc6634c0532925a3b844bc9e7595f0bebd")
+                    .to_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
+                    .amount(amount)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        let result = executor.execute_batch(batch).await;
+
+        assert_eq!(result.failure_count(), 3);
+        assert!(!result.is_partial_failure());
+        assert_eq!(result.failed_indices(), vec![0, 1, 2]);
+        let amounts: Vec<u128> = result.failures.iter().map(|(tx, _)| tx.amount).collect();
+        assert_eq!(amounts, vec![1, 2, 3]);
+        // Missing adapters are not retried
+        assert!(result.outcomes.iter().all(|outcome| outcome.attempts == 1));
+        assert!(result.outcomes[0].error.is_some());
+    }
+
+    #[cfg(feature = "evm")]
+    #[tokio::test(start_paused = true)]
+    async fn test_parallel_executor_retries_connection_failures() {
+        use alloy::primitives::{B256, U64};
+        use alloy::providers::ProviderBuilder;
+        use alloy::rpc::types::FeeHistory;
+        use alloy::transports::mock::Asserter;
+        use std::time::Duration;
+
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(1));
+        let adapter = apex_sdk_evm::EvmAdapter::from_provider(provider)
+            .await
+            .unwrap()
+            .with_signer(
+                "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                    .parse()
+                    .unwrap(),
+            );
+        let sdk = ApexSDK::new(
+            #[cfg(feature = "substrate")]
+            None,
+            #[cfg(feature = "substrate")]
+            None,
+            #[cfg(feature = "revive")]
+            None,
+            Some(adapter),
+            Duration::from_secs(30),
+            crate::SdkConfig {
+                confirmation_strategy: crate::ConfirmationStrategy::Immediate,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let executor = ParallelExecutor::new(Arc::new(sdk), 1).with_retry_policy(
+            RetryConfig::builder()
+                .max_attempts(3)
+                .initial_delay(Duration::from_secs(60))
+                .build(),
+        );
+
+        let mut batch = TransactionBatch::new();
+        batch.add_transaction(
+            Transaction::builder()
+                .from_address("0x742d35cc6634c0532925a3b844bc9e7595f0bebd")
+                .to_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
+                .amount(1000)
+                .chain(apex_sdk_types::Chain::Ethereum)
+                .build()
+                .unwrap(),
+        );
+        let batch = tokio::spawn(async move { executor.execute_batch(batch).await });
+
+        // The first attempt finds no response queued, a transport failure
+        // before anything is signed; the node comes back before the retry
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let tx_hash = B256::repeat_byte(0x42);
+        asserter.push_success(&FeeHistory {
+            base_fee_per_gas: vec![10, 20],
+            reward: Some(vec![vec![3]]),
+            ..Default::default()
+        });
+        asserter.push_success(&U64::from(21_000));
+        asserter.push_success(&U64::from(4));
+        asserter.push_success(&tx_hash);
+
+        let result = batch.await.unwrap();
+        assert_eq!(result.success_count(), 1);
+        assert_eq!(result.outcomes[0].attempts, 2);
+        assert_eq!(
+            result.successes[0].source_tx_hash,
+            format!("{:#x}", tx_hash)
+        );
+    }
+
     #[test]
     fn test_batch_execution_result_metrics() {
         use crate::transaction::{TransactionResult, TransactionStatus};
@@ -644,6 +853,7 @@ mod tests {
                 TransactionResult::new("0x2".to_string()).with_status(TransactionStatus::Success),
             ],
             failures: vec![],
+            outcomes: vec![],
             execution_time_ms: 500,
         };
 
@@ -656,8 +866,8 @@ mod tests {
             failures: vec![
                 (
                     Transaction::builder()
-                        .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbD")
-                        .to_evm_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
+                        .from_address("0x742d35cc6634c0532925a3b844bc9e7595f0bebd")
+                        .to_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
                         .amount(1000)
                         .build()
                         .unwrap(),
@@ -665,14 +875,15 @@ mod tests {
                 ),
                 (
                     Transaction::builder()
-                        .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbD")
-                        .to_evm_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
+                        .from_address("0x742d35cc6634c0532925a3b844bc9e7595f0bebd")
+                        .to_address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
                         .amount(2000)
                         .build()
                         .unwrap(),
                     crate::error::Error::Transaction("Error 2".to_string()),
                 ),
             ],
+            outcomes: vec![],
             execution_time_ms: 750,
         };
 
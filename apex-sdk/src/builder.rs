@@ -2,9 +2,14 @@
 
 use crate::{
     error::{Error, Result},
+    journal::IntentJournal,
     sdk::ApexSDK,
 };
-use std::time::Duration;
+use apex_sdk_core::readiness::{Readiness, ADAPTER_CONNECTED};
+use std::{sync::Arc, time::Duration};
+
+#[cfg(feature = "substrate")]
+use apex_sdk_core::readiness::{KEYSTORE_UNLOCKED, METADATA_LOADED};
 
 #[cfg(feature = "substrate")]
 use apex_sdk_substrate::SubstrateAdapter;
@@ -12,6 +17,9 @@ use apex_sdk_substrate::SubstrateAdapter;
 #[cfg(feature = "revive")]
 use apex_sdk_revive::ReviveAdapter;
 
+#[cfg(feature = "evm")]
+use apex_sdk_evm::{EvmAdapter, PrivateKeySigner};
+
 /// Builder for creating an ApexSDK instance with configuration.
 ///
 /// # Example
@@ -43,8 +51,19 @@ pub struct ApexSDKBuilder {
     #[cfg(feature = "revive")]
     revive_endpoint: Option<String>,
 
+    #[cfg(feature = "evm")]
+    evm_endpoint: Option<String>,
+
+    #[cfg(feature = "evm")]
+    evm_wallet: Option<PrivateKeySigner>,
+
+    #[cfg(feature = "substrate")]
+    spending_limiter: Option<apex_sdk_substrate::SpendingLimiter>,
+
     timeout: Option<Duration>,
     config: Option<crate::sdk::SdkConfig>,
+    readiness: Option<Readiness>,
+    intent_journal: Option<IntentJournal>,
 }
 
 impl ApexSDKBuilder {
@@ -93,6 +112,40 @@ impl ApexSDKBuilder {
         self
     }
 
+    /// Configure the Ethereum JSON-RPC endpoint (HTTP or WebSocket).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use apex_sdk::ApexSDKBuilder;
+    ///
+    /// let builder = ApexSDKBuilder::new()
+    ///     .with_evm_endpoint("https://ethereum-sepolia-rpc.publicnode.com");
+    /// ```
+    #[cfg(feature = "evm")]
+    pub fn with_evm_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.evm_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Configure a private key for signing EVM transactions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use apex_sdk::ApexSDKBuilder;
+    /// use apex_sdk_evm::PrivateKeySigner;
+    ///
+    /// let builder = ApexSDKBuilder::new()
+    ///     .with_evm_endpoint("https://ethereum-sepolia-rpc.publicnode.com")
+    ///     .with_evm_wallet(PrivateKeySigner::random());
+    /// ```
+    #[cfg(feature = "evm")]
+    pub fn with_evm_wallet(mut self, wallet: PrivateKeySigner) -> Self {
+        self.evm_wallet = Some(wallet);
+        self
+    }
+
     /// Configure a Substrate wallet for signing transactions.
     ///
     /// # Example
@@ -140,11 +193,10 @@ impl ApexSDKBuilder {
     /// ```rust
     /// use apex_sdk::{ApexSDKBuilder, SdkConfig, ConfirmationStrategy};
     ///
-    /// let config = SdkConfig {
-    ///     confirmation_strategy: ConfirmationStrategy::WaitForFinality,
-    ///     confirmation_blocks: 3,
-    ///     timeout_seconds: 120,
-    /// };
+    /// let mut config = SdkConfig::default();
+    /// config.confirmation_strategy = ConfirmationStrategy::WaitForFinality;
+    /// config.confirmation_blocks = 3;
+    /// config.timeout_seconds = 120;
     /// let builder = ApexSDKBuilder::new().with_config(config);
     /// ```
     pub fn with_config(mut self, config: crate::sdk::SdkConfig) -> Self {
@@ -171,6 +223,100 @@ impl ApexSDKBuilder {
         self.config = Some(config);
         self
     }
+
+    /// Cap the final fee of Substrate transactions executed by the SDK.
+    ///
+    /// The cap is in the chain's smallest unit and covers the estimated fee
+    /// scaled by the safety and congestion multipliers, plus tip.
+    /// Transactions above it fail with [`Error::FeeCapExceeded`] before they
+    /// are submitted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use apex_sdk::ApexSDKBuilder;
+    ///
+    /// // 0.1 DOT
+    /// let builder = ApexSDKBuilder::new().with_max_fee(1_000_000_000);
+    /// ```
+    ///
+    /// [`Error::FeeCapExceeded`]: crate::Error::FeeCapExceeded
+    pub fn with_max_fee(mut self, max_fee: u128) -> Self {
+        let mut config = self.config.unwrap_or_default();
+        config.max_fee = Some(max_fee);
+        self.config = Some(config);
+        self
+    }
+
+    /// Hold the fees of Substrate transactions executed by the SDK to a
+    /// budget per account.
+    ///
+    /// Each transaction's estimated fee, including tip, is reserved before
+    /// it is submitted. Transactions that do not fit in the remaining budget
+    /// fail with [`Error::SpendingLimitExceeded`]. The limiter's clones share
+    /// its ledger, so keep one to check what has been spent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use apex_sdk::ApexSDKBuilder;
+    /// use apex_sdk_substrate::SpendingLimiter;
+    /// use std::time::Duration;
+    ///
+    /// // At most 1 DOT in fees per account per hour
+    /// let limiter = SpendingLimiter::new(10_000_000_000, Duration::from_secs(3600));
+    /// let builder = ApexSDKBuilder::new().with_spending_limiter(limiter);
+    /// ```
+    ///
+    /// [`Error::SpendingLimitExceeded`]: crate::Error::SpendingLimitExceeded
+    #[cfg(feature = "substrate")]
+    pub fn with_spending_limiter(mut self, limiter: apex_sdk_substrate::SpendingLimiter) -> Self {
+        self.spending_limiter = Some(limiter);
+        self
+    }
+
+    /// Report warm-up progress to readiness conditions.
+    ///
+    /// While building, the SDK marks [`ADAPTER_CONNECTED`] once an adapter
+    /// connects (or not ready with the error if connecting fails),
+    /// `METADATA_LOADED` once the Substrate runtime metadata is fetched and
+    /// `KEYSTORE_UNLOCKED` when a Substrate wallet is configured. Which of
+    /// them gate readiness is up to the caller.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use apex_sdk::ApexSDKBuilder;
+    /// use apex_sdk_core::readiness::{Readiness, ADAPTER_CONNECTED, METADATA_LOADED};
+    ///
+    /// let readiness = Readiness::new()
+    ///     .with_requirement(ADAPTER_CONNECTED)
+    ///     .with_requirement(METADATA_LOADED);
+    /// let builder = ApexSDKBuilder::new().with_readiness(readiness.clone());
+    /// ```
+    pub fn with_readiness(mut self, readiness: Readiness) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
+    /// Journal transactions before submission so they can be reconciled
+    /// after a crash with [`ApexSDK::resume_pending`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use apex_sdk::{ApexSDKBuilder, IntentJournal};
+    ///
+    /// # fn example() -> apex_sdk::Result<()> {
+    /// let builder = ApexSDKBuilder::new()
+    ///     .with_intent_journal(IntentJournal::open("intents.json")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_intent_journal(mut self, journal: IntentJournal) -> Self {
+        self.intent_journal = Some(journal);
+        self
+    }
     /// Build the ApexSDK instance.
     ///
     /// # Errors
@@ -195,32 +341,70 @@ impl ApexSDKBuilder {
     /// ```
     pub async fn build(self) -> Result<ApexSDK> {
         let timeout = self.timeout.unwrap_or(Duration::from_secs(30));
+        let readiness = self.readiness;
+        let connection_failed = |e: String| {
+            if let Some(readiness) = &readiness {
+                readiness.mark_not_ready(ADAPTER_CONNECTED, e.clone());
+            }
+            Error::Connection(e)
+        };
 
         #[cfg(feature = "substrate")]
         let substrate_adapter = if let Some(endpoint) = self.substrate_endpoint {
-            Some(
-                SubstrateAdapter::connect(&endpoint)
-                    .await
-                    .map_err(|e| Error::Connection(e.to_string()))?,
-            )
+            let adapter = SubstrateAdapter::connect(&endpoint)
+                .await
+                .map_err(|e| connection_failed(e.to_string()))?;
+            if let Some(readiness) = &readiness {
+                readiness.mark_ready(ADAPTER_CONNECTED);
+                readiness.mark_ready(METADATA_LOADED);
+            }
+            Some(adapter)
         } else {
             None
         };
 
+        #[cfg(feature = "substrate")]
+        if let (Some(readiness), Some(_)) = (&readiness, &self.substrate_wallet) {
+            readiness.mark_ready(KEYSTORE_UNLOCKED);
+        }
+
         #[cfg(feature = "revive")]
         let revive_adapter = if let Some(endpoint) = self.revive_endpoint {
-            Some(
-                ReviveAdapter::connect(&endpoint)
-                    .await
-                    .map_err(|e| Error::Connection(e.to_string()))?,
-            )
+            let adapter = ReviveAdapter::connect(&endpoint)
+                .await
+                .map_err(|e| connection_failed(e.to_string()))?;
+            if let Some(readiness) = &readiness {
+                readiness.mark_ready(ADAPTER_CONNECTED);
+            }
+            Some(adapter)
+        } else {
+            None
+        };
+
+        #[cfg(feature = "evm")]
+        let evm_adapter = if let Some(endpoint) = self.evm_endpoint {
+            let mut adapter = EvmAdapter::connect(&endpoint)
+                .await
+                .map_err(|e| connection_failed(e.to_string()))?;
+            if let Some(wallet) = self.evm_wallet {
+                adapter = adapter.with_signer(wallet);
+            }
+            if let Some(readiness) = &readiness {
+                readiness.mark_ready(ADAPTER_CONNECTED);
+            }
+            Some(adapter)
         } else {
             None
         };
 
+        #[cfg(feature = "evm")]
+        let evm_configured = evm_adapter.is_some();
+        #[cfg(not(feature = "evm"))]
+        let evm_configured = false;
+
         #[cfg(all(feature = "substrate", feature = "revive"))]
         {
-            if substrate_adapter.is_none() && revive_adapter.is_none() {
+            if !evm_configured && substrate_adapter.is_none() && revive_adapter.is_none() {
                 return Err(Error::Config(
                     "At least one blockchain adapter must be configured".to_string(),
                 ));
@@ -229,7 +413,7 @@ impl ApexSDKBuilder {
 
         #[cfg(all(feature = "substrate", not(feature = "revive")))]
         {
-            if substrate_adapter.is_none() {
+            if !evm_configured && substrate_adapter.is_none() {
                 return Err(Error::Config(
                     "Substrate adapter must be configured when Revive feature is disabled"
                         .to_string(),
@@ -239,7 +423,7 @@ impl ApexSDKBuilder {
 
         #[cfg(all(not(feature = "substrate"), feature = "revive"))]
         {
-            if revive_adapter.is_none() {
+            if !evm_configured && revive_adapter.is_none() {
                 return Err(Error::Config(
                     "Revive adapter must be configured when Substrate feature is disabled"
                         .to_string(),
@@ -247,16 +431,29 @@ impl ApexSDKBuilder {
             }
         }
 
-        ApexSDK::new(
+        let sdk = ApexSDK::new(
             #[cfg(feature = "substrate")]
             substrate_adapter,
             #[cfg(feature = "substrate")]
             self.substrate_wallet,
             #[cfg(feature = "revive")]
             revive_adapter,
+            #[cfg(feature = "evm")]
+            evm_adapter,
             timeout,
             self.config.unwrap_or_default(),
-        )
+        )?;
+
+        #[cfg(feature = "substrate")]
+        let sdk = match self.spending_limiter {
+            Some(limiter) => sdk.with_spending_limiter(limiter),
+            None => sdk,
+        };
+
+        Ok(match self.intent_journal {
+            Some(journal) => sdk.with_intent_journal(Arc::new(journal)),
+            None => sdk,
+        })
     }
 }
 
@@ -307,6 +504,20 @@ mod tests {
         assert_eq!(builder.revive_endpoint, Some(endpoint.to_string()));
     }
 
+    #[cfg(feature = "evm")]
+    #[tokio::test]
+    async fn test_builder_marks_failed_evm_connection_not_ready() {
+        let readiness = Readiness::new().with_requirement(ADAPTER_CONNECTED);
+        let result = ApexSDKBuilder::new()
+            .with_evm_endpoint("ws://127.0.0.1:1")
+            .with_readiness(readiness.clone())
+            .build()
+            .await;
+
+        assert!(matches!(result, Err(Error::Connection(_))));
+        assert!(!readiness.report().ready);
+    }
+
     #[test]
     fn test_builder_with_timeout() {
         let timeout = Duration::from_secs(45);
@@ -315,6 +526,34 @@ mod tests {
         assert_eq!(builder.timeout, Some(timeout));
     }
 
+    #[cfg(feature = "substrate")]
+    #[test]
+    fn test_builder_with_spending_limiter() {
+        let limiter = apex_sdk_substrate::SpendingLimiter::session(1_000);
+        let builder = ApexSDKBuilder::new().with_spending_limiter(limiter.clone());
+
+        // The SDK's executors share the builder's ledger
+        let shared = builder.spending_limiter.as_ref().unwrap();
+        let _reserved = shared.try_spend("alice", 600).unwrap();
+        assert_eq!(limiter.remaining("alice"), 400);
+    }
+
+    #[cfg(feature = "substrate")]
+    #[tokio::test]
+    async fn test_builder_marks_failed_connection_not_ready() {
+        let readiness = Readiness::new().with_requirement(ADAPTER_CONNECTED);
+        let result = ApexSDKBuilder::new()
+            .with_substrate_endpoint("ws://127.0.0.1:1")
+            .with_readiness(readiness.clone())
+            .build()
+            .await;
+
+        assert!(result.is_err());
+        let report = readiness.report();
+        assert!(!report.ready);
+        assert!(report.conditions[0].message.is_some());
+    }
+
     #[tokio::test]
     async fn test_builder_requires_at_least_one_adapter() {
         let result = ApexSDKBuilder::new().build().await;
@@ -0,0 +1,13 @@
+//! Deprecated names kept for source compatibility
+//!
+//! When a public item is renamed or retyped, its old name lives on here as a
+//! `#[deprecated]` alias pointing at the replacement, and is re-exported from
+//! the crate root, so existing code keeps compiling with a warning. Shims are
+//! removed in the next breaking release.
+
+/// Pool of reusable connections
+#[deprecated(
+    since = "0.1.7",
+    note = "use `apex_sdk::performance::ConnectionPool`; the root name clashes with `apex_sdk::substrate::ConnectionPool`"
+)]
+pub type ConnectionPool<T> = crate::performance::ConnectionPool<T>;
@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+pub use apex_sdk_types::{ErrorCode, HasErrorCode};
+
 /// Result type alias for Apex SDK operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -28,15 +30,80 @@ pub enum Error {
     #[error("Invalid address: {0}")]
     InvalidAddress(String),
 
+    /// Amount is malformed, in the wrong unit or finer than the token allows
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
     /// Unsupported chain error
     #[error("Unsupported chain: {0}")]
     UnsupportedChain(String),
 
+    /// No route between the source and destination chains
+    #[error("Unsupported route: {0}")]
+    UnsupportedRoute(String),
+
+    /// Estimated fee above the configured cap; nothing was submitted
+    #[error("Fee {fee} exceeds cap {max_fee}: {breakdown}")]
+    FeeCapExceeded {
+        /// Final estimated fee, tip included
+        fee: u128,
+        /// Configured cap
+        max_fee: u128,
+        /// How the fee was computed
+        breakdown: String,
+    },
+
+    /// Failed to read or write persisted SDK state
+    #[error("Persistence error: {0}")]
+    Persistence(String),
+
+    /// Fee does not fit in the sending account's budget; nothing was
+    /// submitted
+    #[error("Fee {fee} for {account} would exceed budget {limit} ({spent} already spent)")]
+    SpendingLimitExceeded {
+        /// Account the fee would be charged to
+        account: String,
+        /// Final estimated fee, tip included
+        fee: u128,
+        /// Fees already spent or reserved against the budget
+        spent: u128,
+        /// Budget of the account
+        limit: u128,
+    },
+
     /// Generic error
     #[error("Error: {0}")]
     Other(String),
 }
 
+impl HasErrorCode for Error {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::new(match self {
+            Error::Config(_) => 1000,
+            Error::Connection(_) => 1001,
+            Error::Transaction(_) => 1002,
+            Error::Serialization(_) => 1003,
+            Error::InvalidAddress(_) => 1004,
+            Error::InvalidAmount(_) => 1005,
+            Error::UnsupportedChain(_) => 1006,
+            Error::UnsupportedRoute(_) => 1007,
+            Error::FeeCapExceeded { .. } => 1008,
+            Error::Persistence(_) => 1009,
+            Error::SpendingLimitExceeded { .. } => 1010,
+            Error::Other(_) => 1099,
+        })
+    }
+}
+
+impl From<apex_sdk_types::ValidationError> for Error {
+    fn from(err: apex_sdk_types::ValidationError) -> Self {
+        match err {
+            apex_sdk_types::ValidationError::InvalidAmount(amount) => Error::InvalidAmount(amount),
+            err => Error::InvalidAddress(err.to_string()),
+        }
+    }
+}
+
 impl From<anyhow::Error> for Error {
     fn from(err: anyhow::Error) -> Self {
         Error::Other(err.to_string())
@@ -53,6 +120,12 @@ mod tests {
         assert_send_sync::<Error>();
     }
 
+    #[test]
+    fn test_persistence_error_display() {
+        let error = Error::Persistence("disk full".to_string());
+        assert_eq!(error.to_string(), "Persistence error: disk full");
+    }
+
     #[test]
     fn test_config_error_display() {
         let error = Error::Config("test config error".to_string());
@@ -98,12 +171,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unsupported_route_error_display() {
+        let error = Error::UnsupportedRoute("test unsupported route".to_string());
+        assert_eq!(
+            error.to_string(),
+            "Unsupported route: test unsupported route"
+        );
+    }
+
     #[test]
     fn test_other_error_display() {
         let error = Error::Other("test other error".to_string());
         assert_eq!(error.to_string(), "Error: test other error");
     }
 
+    #[test]
+    fn test_error_codes_are_stable_and_unique() {
+        let errors = [
+            Error::Config(String::new()),
+            Error::Connection(String::new()),
+            Error::Transaction(String::new()),
+            Error::Serialization(String::new()),
+            Error::InvalidAddress(String::new()),
+            Error::InvalidAmount(String::new()),
+            Error::UnsupportedChain(String::new()),
+            Error::UnsupportedRoute(String::new()),
+            Error::FeeCapExceeded {
+                fee: 2,
+                max_fee: 1,
+                breakdown: String::new(),
+            },
+            Error::Persistence(String::new()),
+            Error::SpendingLimitExceeded {
+                account: String::new(),
+                fee: 2,
+                spent: 0,
+                limit: 1,
+            },
+            Error::Other(String::new()),
+        ];
+        let codes: std::collections::HashSet<_> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(codes.len(), errors.len());
+        assert!(codes.iter().all(|code| (1000..1100).contains(&code.get())));
+        assert_eq!(
+            Error::Connection(String::new()).code().to_string(),
+            "APEX-1001"
+        );
+
+        let amount: Error = apex_sdk_types::ValidationError::InvalidAmount("x".into()).into();
+        assert_eq!(amount.code(), ErrorCode::new(1005));
+    }
+
     #[test]
     fn test_from_anyhow_error() {
         let anyhow_err = anyhow::anyhow!("test anyhow error");
@@ -1,5 +1,6 @@
 //! Error recovery and retry mechanisms.
 
+use apex_sdk_core::ThrottleHint;
 use std::time::Duration;
 use thiserror::Error;
 
@@ -71,39 +72,62 @@ impl RetryConfigBuilder {
 }
 
 /// Execute a function with retry logic
-pub async fn with_retry<F, Fut, T, E>(mut f: F, config: RetryConfig) -> Result<T, E>
+pub async fn with_retry<F, Fut, T, E>(f: F, config: RetryConfig) -> Result<T, E>
 where
     F: FnMut() -> Fut,
     Fut: std::future::Future<Output = Result<T, E>>,
     E: std::fmt::Display,
 {
-    let mut last_error = None;
+    with_retry_if(f, is_retryable, config).await.0
+}
+
+/// Execute a function with retry logic, retrying only errors accepted by
+/// `retryable`
+///
+/// When an error is a provider throttle response with a wait (see
+/// [`ThrottleHint`]), the next attempt comes after exactly that wait instead
+/// of the exponential delay. Returns the result together with the number of
+/// attempts made.
+pub async fn with_retry_if<F, Fut, T, E, P>(
+    mut f: F,
+    retryable: P,
+    config: RetryConfig,
+) -> (Result<T, E>, usize)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+    P: Fn(&E) -> bool,
+{
+    let max_attempts = config.max_attempts.max(1);
     let mut delay = config.initial_delay;
+    let mut attempt = 1;
 
-    for attempt in 1..=config.max_attempts {
+    loop {
         match f().await {
-            Ok(result) => return Ok(result),
+            Ok(result) => return (Ok(result), attempt),
             Err(err) => {
-                if !is_retryable(&err) {
-                    return Err(err);
+                if attempt >= max_attempts || !retryable(&err) {
+                    return (Err(err), attempt);
                 }
 
-                last_error = Some(err);
-
-                if attempt < config.max_attempts {
-                    tokio::time::sleep(delay).await;
-                    delay = std::cmp::min(
-                        Duration::from_millis(
-                            (delay.as_millis() as f64 * config.multiplier) as u64,
-                        ),
-                        config.max_delay,
-                    );
+                if let Some(wait) = ThrottleHint::parse(&err.to_string())
+                    .and_then(|hint| hint.retry_after_capped(config.max_delay))
+                {
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                    continue;
                 }
+
+                tokio::time::sleep(delay).await;
+                delay = std::cmp::min(
+                    Duration::from_millis((delay.as_millis() as f64 * config.multiplier) as u64),
+                    config.max_delay,
+                );
+                attempt += 1;
             }
         }
     }
-
-    Err(last_error.unwrap())
 }
 
 /// Check if an error is retryable
@@ -212,6 +236,99 @@ mod tests {
         assert_eq!(call_count, 1);
     }
 
+    #[tokio::test]
+    async fn test_with_retry_if_stops_on_permanent_error() {
+        let config = RetryConfig::builder()
+            .max_attempts(5)
+            .initial_delay(Duration::from_millis(1))
+            .build();
+
+        let mut call_count = 0;
+        let (result, attempts) = with_retry_if(
+            || {
+                call_count += 1;
+                let err = if call_count < 3 {
+                    "transient"
+                } else {
+                    "permanent"
+                };
+                async move { Err::<i32, &'static str>(err) }
+            },
+            |err| *err == "transient",
+            config,
+        )
+        .await;
+
+        assert_eq!(result, Err("permanent"));
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_if_waits_for_throttle_hint() {
+        let config = RetryConfig::builder()
+            .max_attempts(2)
+            .initial_delay(Duration::from_secs(5))
+            .build();
+
+        let start = std::time::Instant::now();
+        let mut call_count = 0;
+        let (result, attempts) = with_retry_if(
+            || {
+                call_count += 1;
+                let first = call_count == 1;
+                async move {
+                    if first {
+                        Err("429 Too Many Requests, retry after 20ms")
+                    } else {
+                        Ok(7)
+                    }
+                }
+            },
+            |_| true,
+            config,
+        )
+        .await;
+
+        assert_eq!(result, Ok(7));
+        assert_eq!(attempts, 2);
+        // The hinted 20ms, not the configured 5s
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(20) && elapsed < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_if_caps_oversized_throttle_hint() {
+        let config = RetryConfig::builder()
+            .max_attempts(2)
+            .max_delay(Duration::from_millis(20))
+            .build();
+
+        let start = std::time::Instant::now();
+        let mut call_count = 0;
+        let (result, attempts) = with_retry_if(
+            || {
+                call_count += 1;
+                let first = call_count == 1;
+                async move {
+                    if first {
+                        Err("HTTP error 429 with body: Too Many Requests\nRetry-After: 3600")
+                    } else {
+                        Ok(7)
+                    }
+                }
+            },
+            |_| true,
+            config,
+        )
+        .await;
+
+        assert_eq!(result, Ok(7));
+        assert_eq!(attempts, 2);
+        // The configured 20ms maximum, not the hinted hour
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(20) && elapsed < Duration::from_secs(1));
+    }
+
     #[test]
     fn test_circuit_breaker_opens_after_failures() {
         let mut breaker = CircuitBreaker::new(3, Duration::from_secs(10));
@@ -0,0 +1,544 @@
+//! Write-ahead journal of transaction intents
+//!
+//! When an [`IntentJournal`] is configured, [`ApexSDK::execute`] records each
+//! transaction before handing it to an adapter, together with the sender's
+//! nonce at that point. Substrate and EVM transactions also get their hash
+//! journaled once signed, before they are broadcast. The intent is removed
+//! once `execute` settles it: the transaction reached a final status, or
+//! failed before it could have been broadcast. Intents still in the journal
+//! when the process starts again were interrupted, or failed in a way that
+//! leaves their fate open; [`ApexSDK::resume_pending`] reconciles them
+//! against chain state.
+//!
+//! ```rust,no_run
+//! use apex_sdk::{ApexSDK, IntentJournal, IntentOutcome};
+//!
+//! # async fn example() -> apex_sdk::Result<()> {
+//! let sdk = ApexSDK::builder()
+//!     .with_substrate_endpoint("wss://polkadot.api.onfinality.io/public-ws")
+//!     .with_intent_journal(IntentJournal::open("intents.json")?)
+//!     .build()
+//!     .await?;
+//!
+//! for resumed in sdk.resume_pending().await? {
+//!     if resumed.outcome == IntentOutcome::NotExecuted {
+//!         sdk.execute(resumed.intent.transaction).await?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`ApexSDK::execute`]: crate::ApexSDK::execute
+//! [`ApexSDK::resume_pending`]: crate::ApexSDK::resume_pending
+
+use crate::{
+    error::{Error, Result},
+    transaction::{Transaction, TransactionResult, TransactionStatus},
+    types::Chain,
+};
+use apex_sdk_core::retention::{PurgeReport, RetentionError, RetentionPolicy, RetentionStore};
+use apex_sdk_core::{BroadcastHook, SdkError};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A transaction recorded before submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Intent {
+    /// Journal-assigned identifier
+    pub id: u64,
+    /// The transaction passed to `execute`
+    pub transaction: Transaction,
+    /// Chain the transaction is submitted on
+    pub chain: Chain,
+    /// Sender's next nonce when the intent was recorded, if it could be read
+    pub nonce: Option<u64>,
+    /// Transaction hash, recorded before broadcast when the adapter reports
+    /// it, otherwise once `execute` returns a pending result
+    pub tx_hash: Option<String>,
+    /// Unix timestamp in seconds
+    pub created_at: u64,
+}
+
+/// What chain state says about a journaled intent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntentOutcome {
+    /// The transaction was included in a block
+    Confirmed { tx_hash: String },
+    /// The transaction was included but failed
+    Failed { tx_hash: String },
+    /// The sender's nonce moved past the intent's nonce, so this transaction
+    /// or another one with the same nonce was included
+    NonceUsed,
+    /// The intent's nonce is still unused and the transaction was not
+    /// included. A signed copy still waiting in the pool can be included
+    /// until it expires.
+    NotExecuted,
+    /// Chain state does not settle the intent yet; it stays in the journal
+    Unresolved,
+}
+
+impl IntentOutcome {
+    /// Whether the intent is removed from the journal
+    pub fn is_resolved(&self) -> bool {
+        !matches!(self, IntentOutcome::Unresolved)
+    }
+}
+
+/// A journaled intent and its reconciled outcome
+#[derive(Debug, Clone)]
+pub struct ResumedIntent {
+    /// The intent as recorded
+    pub intent: Intent,
+    /// What chain state says about it
+    pub outcome: IntentOutcome,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct JournalState {
+    next_id: u64,
+    intents: BTreeMap<u64, Intent>,
+}
+
+/// Journal of transaction intents, in memory or persisted to a JSON file
+///
+/// A persisted journal is rewritten on every change, before the transaction
+/// it records is submitted.
+#[derive(Debug, Default)]
+pub struct IntentJournal {
+    path: Option<PathBuf>,
+    state: Mutex<JournalState>,
+}
+
+impl IntentJournal {
+    /// Create an in-memory journal
+    ///
+    /// Intents do not survive a restart; useful for tests.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a journal persisted to a JSON file
+    ///
+    /// Intents already stored in the file are loaded; the file is created on
+    /// the first write.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let state = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                Error::Persistence(format!("Invalid intent journal {}: {}", path.display(), e))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => JournalState::default(),
+            Err(e) => {
+                return Err(Error::Persistence(format!(
+                    "Failed to read {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+
+        Ok(Self {
+            path: Some(path),
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Record a transaction about to be submitted and return its intent ID
+    pub fn record(&self, transaction: &Transaction, nonce: Option<u64>) -> Result<u64> {
+        let mut state = self.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.intents.insert(
+            id,
+            Intent {
+                id,
+                transaction: transaction.clone(),
+                chain: transaction.source_chain(),
+                nonce,
+                tx_hash: None,
+                created_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0),
+            },
+        );
+        self.save(&state)?;
+        Ok(id)
+    }
+
+    /// Attach the transaction hash to an intent
+    pub fn set_tx_hash(&self, id: u64, tx_hash: impl Into<String>) -> Result<()> {
+        let mut state = self.lock();
+        if let Some(intent) = state.intents.get_mut(&id) {
+            intent.tx_hash = Some(tx_hash.into());
+            self.save(&state)?;
+        }
+        Ok(())
+    }
+
+    /// Remove an intent
+    pub fn complete(&self, id: u64) -> Result<()> {
+        let mut state = self.lock();
+        if state.intents.remove(&id).is_some() {
+            self.save(&state)?;
+        }
+        Ok(())
+    }
+
+    /// Intents still in the journal, oldest first
+    pub fn pending(&self) -> Vec<Intent> {
+        self.lock().intents.values().cloned().collect()
+    }
+
+    /// Number of intents in the journal
+    pub fn len(&self) -> usize {
+        self.lock().intents.len()
+    }
+
+    /// Whether the journal is empty
+    pub fn is_empty(&self) -> bool {
+        self.lock().intents.is_empty()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, JournalState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn save(&self, state: &JournalState) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let json = serde_json::to_vec(state).map_err(|e| Error::Serialization(e.to_string()))?;
+
+        // Write to a sibling file first so a crash cannot truncate the journal
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| Error::Persistence(format!("Failed to write {}: {}", path.display(), e)))
+    }
+}
+
+/// A journaled intent whose transaction is being executed
+///
+/// Adapters that can report the hash of a signed transaction before
+/// broadcasting it get a [`broadcast_hook`](Self::broadcast_hook), which
+/// journals the hash and tells [`settle`](Self::settle) whether the
+/// transaction may have reached the network.
+pub(crate) struct Submission {
+    journal: Arc<IntentJournal>,
+    id: u64,
+    hooked: AtomicBool,
+    broadcast: Arc<AtomicBool>,
+}
+
+impl Submission {
+    pub(crate) fn new(journal: Arc<IntentJournal>, id: u64) -> Self {
+        Self {
+            journal,
+            id,
+            hooked: AtomicBool::new(false),
+            broadcast: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Hook journaling the hash of the signed transaction before broadcast
+    ///
+    /// Take it before anything that can fail, so errors raised before the
+    /// hook runs count as raised before submission.
+    pub(crate) fn broadcast_hook(&self) -> BroadcastHook {
+        self.hooked.store(true, Ordering::SeqCst);
+        let journal = Arc::clone(&self.journal);
+        let broadcast = Arc::clone(&self.broadcast);
+        let id = self.id;
+        Arc::new(move |tx_hash: &str| {
+            journal
+                .set_tx_hash(id, tx_hash)
+                .map_err(|e| SdkError::TransactionError(e.to_string()))?;
+            broadcast.store(true, Ordering::SeqCst);
+            Ok(())
+        })
+    }
+
+    /// Remove the intent if `result` settles it, and keep it otherwise
+    ///
+    /// A final status settles it, and so does an error raised before the
+    /// transaction could have been broadcast. Pending results keep their
+    /// hash in the journal for [`ApexSDK::resume_pending`].
+    ///
+    /// [`ApexSDK::resume_pending`]: crate::ApexSDK::resume_pending
+    pub(crate) fn settle(&self, result: &Result<TransactionResult>) -> Result<()> {
+        match result {
+            Ok(result) if matches!(result.status, TransactionStatus::Pending) => self
+                .journal
+                .set_tx_hash(self.id, result.source_tx_hash.clone()),
+            Ok(_) => self.journal.complete(self.id),
+            Err(e) if self.before_submission(e) => self.journal.complete(self.id),
+            Err(e) => {
+                tracing::warn!(
+                    intent = self.id,
+                    error = %e,
+                    "Transaction may have been broadcast; keeping its intent"
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Whether `error` was raised before the transaction could be broadcast
+    fn before_submission(&self, error: &Error) -> bool {
+        if self.broadcast.load(Ordering::SeqCst) {
+            return false;
+        }
+        // With a hook, anything before it ran happened before broadcast;
+        // without one, only errors that refuse the transaction up front tell
+        self.hooked.load(Ordering::SeqCst)
+            || matches!(
+                error,
+                Error::Config(_)
+                    | Error::InvalidAddress(_)
+                    | Error::InvalidAmount(_)
+                    | Error::UnsupportedChain(_)
+                    | Error::UnsupportedRoute(_)
+                    | Error::FeeCapExceeded { .. }
+                    | Error::SpendingLimitExceeded { .. }
+            )
+    }
+}
+
+/// Intents that outlive the policy are dropped oldest first and are no longer
+/// offered by [`ApexSDK::resume_pending`](crate::ApexSDK::resume_pending).
+/// The size limit applies to the serialized journal.
+#[async_trait]
+impl RetentionStore for IntentJournal {
+    fn name(&self) -> &str {
+        "intent_journal"
+    }
+
+    async fn purge(
+        &self,
+        policy: &RetentionPolicy,
+    ) -> std::result::Result<PurgeReport, RetentionError> {
+        let mut state = self.lock();
+        let before = state.intents.len();
+
+        if let Some(cutoff) = policy.cutoff(SystemTime::now()) {
+            state
+                .intents
+                .retain(|_, intent| intent.created_at >= cutoff);
+        }
+        let mut size = serialized_size(&state);
+        while policy.exceeds_size(size) && state.intents.pop_first().is_some() {
+            size = serialized_size(&state);
+        }
+
+        let purged = (before - state.intents.len()) as u64;
+        if purged > 0 {
+            self.save(&state)
+                .map_err(|e| RetentionError::new(self.name(), e))?;
+        }
+        Ok(PurgeReport {
+            purged,
+            size_bytes: size,
+        })
+    }
+}
+
+fn serialized_size(state: &JournalState) -> u64 {
+    serde_json::to_vec(state)
+        .map(|json| json.len() as u64)
+        .unwrap_or(0)
+}
+
+/// Reconcile an intent from the transaction status its hash reports, if
+/// known, and the sender's current nonce
+pub(crate) fn reconcile(
+    intent: &Intent,
+    status: Option<apex_sdk_types::TxStatus>,
+    current_nonce: Option<u64>,
+) -> IntentOutcome {
+    use apex_sdk_types::TxStatus;
+
+    if let Some(tx_hash) = &intent.tx_hash {
+        match status {
+            Some(TxStatus::Confirmed | TxStatus::Finalized) => {
+                return IntentOutcome::Confirmed {
+                    tx_hash: tx_hash.clone(),
+                }
+            }
+            Some(TxStatus::Failed) => {
+                return IntentOutcome::Failed {
+                    tx_hash: tx_hash.clone(),
+                }
+            }
+            Some(TxStatus::Pending | TxStatus::InMempool | TxStatus::TimedOut) => {
+                return IntentOutcome::Unresolved
+            }
+            Some(TxStatus::Unknown) | None => {}
+        }
+    }
+
+    match (intent.nonce, current_nonce) {
+        (Some(nonce), Some(current)) if current > nonce => IntentOutcome::NonceUsed,
+        (Some(_), Some(_)) => IntentOutcome::NotExecuted,
+        _ => IntentOutcome::Unresolved,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Address;
+
+    fn transfer() -> Transaction {
+        Transaction::builder()
+            .from(Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"))
+            .to(Address::evm("0x1234567890123456789012345678901234567890"))
+            .amount(1000)
+            .chain(Chain::Ethereum)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_journal_persists_across_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("intents.json");
+
+        let journal = IntentJournal::open(&path).unwrap();
+        let first = journal.record(&transfer(), Some(7)).unwrap();
+        let second = journal.record(&transfer(), Some(8)).unwrap();
+        journal.set_tx_hash(first, "0xabc").unwrap();
+        journal.complete(second).unwrap();
+
+        let reopened = IntentJournal::open(&path).unwrap();
+        let pending = reopened.pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, first);
+        assert_eq!(pending[0].nonce, Some(7));
+        assert_eq!(pending[0].tx_hash.as_deref(), Some("0xabc"));
+        assert_eq!(pending[0].chain, Chain::Ethereum);
+
+        // IDs keep increasing after a restart
+        assert!(reopened.record(&transfer(), None).unwrap() > second);
+    }
+
+    #[tokio::test]
+    async fn test_retention_purges_oldest_intents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("intents.json");
+        let journal = IntentJournal::open(&path).unwrap();
+        let stale = journal.record(&transfer(), Some(1)).unwrap();
+        journal.lock().intents.get_mut(&stale).unwrap().created_at -= 7200;
+        let ids: Vec<u64> = (2..5)
+            .map(|nonce| journal.record(&transfer(), Some(nonce)).unwrap())
+            .collect();
+
+        let report = journal
+            .purge(&RetentionPolicy::new().with_max_age(std::time::Duration::from_secs(3600)))
+            .await
+            .unwrap();
+        assert_eq!(report.purged, 1);
+        assert_eq!(report.size_bytes, std::fs::metadata(&path).unwrap().len());
+
+        // Shrink to fit two intents
+        let one = serialized_size(&journal.lock()) / 3;
+        let report = journal
+            .purge(&RetentionPolicy::new().with_max_size(one * 2 + one / 2))
+            .await
+            .unwrap();
+        assert_eq!(report.purged, 1);
+
+        let pending: Vec<u64> = IntentJournal::open(&path)
+            .unwrap()
+            .pending()
+            .iter()
+            .map(|intent| intent.id)
+            .collect();
+        assert_eq!(pending, ids[1..]);
+    }
+
+    #[test]
+    fn test_submission_settles_only_definite_outcomes() {
+        let journal = Arc::new(IntentJournal::new());
+        let error = || Err(Error::Transaction("connection dropped".to_string()));
+
+        // Failed after the hash was journaled: may have been broadcast
+        let id = journal.record(&transfer(), Some(1)).unwrap();
+        let submission = Submission::new(journal.clone(), id);
+        let hook = submission.broadcast_hook();
+        hook("0xabc").unwrap();
+        submission.settle(&error()).unwrap();
+        assert_eq!(journal.pending()[0].tx_hash.as_deref(), Some("0xabc"));
+        journal.complete(id).unwrap();
+
+        // Failed before the hook ran
+        let id = journal.record(&transfer(), Some(1)).unwrap();
+        let submission = Submission::new(journal.clone(), id);
+        let _hook = submission.broadcast_hook();
+        submission.settle(&error()).unwrap();
+        assert!(journal.is_empty());
+
+        // Without a hook, only errors that refuse the transaction settle it
+        let id = journal.record(&transfer(), Some(1)).unwrap();
+        let submission = Submission::new(journal.clone(), id);
+        submission.settle(&error()).unwrap();
+        assert_eq!(journal.len(), 1);
+        submission
+            .settle(&Err(Error::UnsupportedRoute("no route".to_string())))
+            .unwrap();
+        assert!(journal.is_empty());
+
+        // Pending results keep their hash, final ones are removed
+        let id = journal.record(&transfer(), Some(1)).unwrap();
+        let submission = Submission::new(journal.clone(), id);
+        let pending =
+            TransactionResult::new("0xdef".to_string()).with_status(TransactionStatus::Pending);
+        submission.settle(&Ok(pending)).unwrap();
+        assert_eq!(journal.pending()[0].tx_hash.as_deref(), Some("0xdef"));
+        let finalized =
+            TransactionResult::new("0xdef".to_string()).with_status(TransactionStatus::Finalized);
+        submission.settle(&Ok(finalized)).unwrap();
+        assert!(journal.is_empty());
+    }
+
+    #[test]
+    fn test_reconcile() {
+        use apex_sdk_types::TxStatus;
+
+        let journal = IntentJournal::new();
+        let id = journal.record(&transfer(), Some(5)).unwrap();
+        let mut intent = journal.pending().remove(0);
+
+        assert_eq!(reconcile(&intent, None, Some(6)), IntentOutcome::NonceUsed);
+        assert_eq!(
+            reconcile(&intent, None, Some(5)),
+            IntentOutcome::NotExecuted
+        );
+        assert_eq!(reconcile(&intent, None, None), IntentOutcome::Unresolved);
+
+        journal.set_tx_hash(id, "0xabc").unwrap();
+        intent = journal.pending().remove(0);
+        assert_eq!(
+            reconcile(&intent, Some(TxStatus::Finalized), Some(6)),
+            IntentOutcome::Confirmed {
+                tx_hash: "0xabc".to_string()
+            }
+        );
+        assert_eq!(
+            reconcile(&intent, Some(TxStatus::InMempool), Some(5)),
+            IntentOutcome::Unresolved
+        );
+        // A hash the chain doesn't know falls back to the nonce
+        assert_eq!(
+            reconcile(&intent, Some(TxStatus::Unknown), Some(5)),
+            IntentOutcome::NotExecuted
+        );
+    }
+}
@@ -28,42 +28,35 @@
 
 pub mod advanced;
 pub mod builder;
+pub mod compat;
 pub mod error;
 pub mod error_recovery;
+pub mod journal;
+pub mod payload;
 pub mod performance;
+pub mod prelude;
 pub mod sdk;
 pub mod transaction;
 
 pub use apex_sdk_core as core;
+#[cfg(feature = "evm")]
+pub use apex_sdk_evm as evm;
 pub use apex_sdk_revive as revive;
 pub use apex_sdk_substrate as substrate;
 pub use apex_sdk_types as types;
 
 pub use advanced::{
     BlockInfo, BlockSubscription, EventSubscription, ParallelExecutor, TransactionBatch,
+    TransactionOutcome,
 };
 pub use builder::ApexSDKBuilder;
-pub use error::{Error, Result};
-pub use error_recovery::{with_retry, CircuitBreaker, RetryConfig};
-pub use performance::{
-    batch_execute, parallel_execute, AsyncMemo, BatchConfig, ConnectionPool, RateLimiter,
-};
+pub use error::{Error, ErrorCode, HasErrorCode, Result};
+pub use error_recovery::{with_retry, with_retry_if, CircuitBreaker, RetryConfig};
+pub use journal::{Intent, IntentJournal, IntentOutcome, ResumedIntent};
+pub use payload::ChainPayload;
+pub use performance::{batch_execute, parallel_execute, AsyncMemo, BatchConfig, RateLimiter};
 pub use sdk::{ApexSDK, ConfirmationStrategy, SdkConfig};
-pub use transaction::{Transaction, TransactionBuilder, TransactionResult};
-
-/// Prelude module for common imports
-pub mod prelude {
-    pub use crate::{
-        builder::ApexSDKBuilder,
-        error::{Error, Result},
-        sdk::{ApexSDK, ConfirmationStrategy, SdkConfig},
-        transaction::{Transaction, TransactionBuilder, TransactionResult},
-        types::{Address, Chain, ChainType},
-    };
-
-    #[cfg(feature = "substrate")]
-    pub use crate::substrate::{SubstrateAdapter, Wallet as SubstrateWallet};
+pub use transaction::{CrossChainResult, Transaction, TransactionBuilder, TransactionResult};
 
-    #[cfg(feature = "revive")]
-    pub use crate::revive::ReviveAdapter;
-}
+#[allow(deprecated)]
+pub use compat::*;
@@ -0,0 +1,444 @@
+//! Conversion of core transactions into chain-specific payloads.
+//!
+//! A [`Transaction`] is chain agnostic: it carries a sender, a recipient, an
+//! amount and optional opaque `data`. [`ChainPayload`] decides what that data
+//! means on the destination chain before anything is signed:
+//!
+//! - on Substrate chains `data` is a SCALE-encoded runtime call
+//!   (`pallet_index ++ call_index ++ args`) submitted as-is, otherwise the
+//!   transaction is a `Balances::transfer_keep_alive`
+//! - on EVM chains, and on the EVM side of hybrid chains, `data` is call data,
+//!   or creation bytecode for deployments, submitted through the EVM adapter
+//! - on Substrate chains running pallet-revive (PolkaVM), [`ChainPayload::for_revive`]
+//!   treats `data` as contract code for deployments or ABI-encoded call data
+//!   for contract calls
+//!
+//! Whether a Substrate chain runs pallet-revive is not known from its
+//! [`Chain`](crate::types::Chain), so Revive payloads are only built on request.
+
+use crate::{
+    error::{Error, Result},
+    transaction::Transaction,
+    types::{Address, ChainType},
+};
+
+/// A transaction translated for a specific chain family
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainPayload {
+    /// Native balance transfer on a Substrate chain
+    SubstrateTransfer {
+        /// SS58 destination address
+        to: String,
+        /// Amount in the chain's smallest unit
+        amount: u128,
+    },
+    /// Pre-encoded runtime call on a Substrate chain
+    SubstrateCall {
+        /// SCALE-encoded `RuntimeCall`
+        call_data: Vec<u8>,
+    },
+    /// Contract deployment through pallet-revive
+    ReviveDeploy {
+        /// Contract code blob
+        code: Vec<u8>,
+        /// Deployment salt
+        salt: [u8; 32],
+        /// Value endowed to the new contract
+        value: u128,
+        /// Optional gas limit
+        gas_limit: Option<u64>,
+    },
+    /// Contract call through pallet-revive
+    ReviveCall {
+        /// Contract address
+        to: Address,
+        /// ABI-encoded call data
+        data: Vec<u8>,
+        /// Value sent with the call
+        value: u128,
+        /// Optional gas limit
+        gas_limit: Option<u64>,
+    },
+    /// Transaction on an Ethereum JSON-RPC chain
+    EvmTransaction {
+        /// Recipient address, `None` for contract deployments
+        to: Option<Address>,
+        /// Call data or creation bytecode
+        data: Vec<u8>,
+        /// Value in wei
+        value: u128,
+        /// Optional gas limit, estimated by the node otherwise
+        gas_limit: Option<u64>,
+    },
+}
+
+impl ChainPayload {
+    /// Translate a core transaction for its destination chain
+    ///
+    /// Contract transactions on Substrate chains are rejected; use
+    /// [`ChainPayload::for_revive`] when the chain runs pallet-revive.
+    pub fn from_transaction(transaction: &Transaction) -> Result<Self> {
+        match transaction.destination_chain().chain_type() {
+            ChainType::Substrate => Self::substrate(transaction),
+            ChainType::Evm => Self::for_evm(transaction),
+            // Hybrid chains accept both; the destination address picks the side
+            ChainType::Hybrid => match transaction.to {
+                Address::Substrate(_) if !transaction.is_deploy => Self::substrate(transaction),
+                _ => Self::for_evm(transaction),
+            },
+        }
+    }
+
+    fn substrate(transaction: &Transaction) -> Result<Self> {
+        if transaction.is_deploy {
+            return Err(Error::Transaction(
+                "Contract deployment is not supported by the Substrate payload; \
+                 use ChainPayload::for_revive on chains running pallet-revive"
+                    .to_string(),
+            ));
+        }
+
+        match &transaction.data {
+            Some(call_data) => {
+                if transaction.amount != 0 {
+                    return Err(Error::Transaction(
+                        "Amount must be encoded in the call data when submitting a raw call"
+                            .to_string(),
+                    ));
+                }
+                if call_data.len() < 2 {
+                    return Err(Error::Transaction(
+                        "Call data must contain at least a pallet and call index".to_string(),
+                    ));
+                }
+                Ok(Self::SubstrateCall {
+                    call_data: call_data.clone(),
+                })
+            }
+            None => match &transaction.to {
+                Address::Substrate(to) => Ok(Self::SubstrateTransfer {
+                    to: to.clone(),
+                    amount: transaction.amount,
+                }),
+                other => Err(Error::InvalidAddress(format!(
+                    "Destination address must be Substrate address for Substrate transactions, got {}",
+                    other
+                ))),
+            },
+        }
+    }
+
+    /// Translate a core transaction for a Substrate chain running pallet-revive
+    ///
+    /// EVM chains do not run pallet-revive and are rejected; their
+    /// transactions go through [`ChainPayload::for_evm`].
+    pub fn for_revive(transaction: &Transaction) -> Result<Self> {
+        let chain = transaction.destination_chain();
+        if chain.chain_type() == ChainType::Evm {
+            return Err(Error::UnsupportedChain(format!(
+                "{} is an EVM chain without pallet-revive; submit the transaction \
+                 through the EVM adapter instead",
+                chain.name()
+            )));
+        }
+
+        if transaction.is_deploy {
+            let code = transaction.data.clone().ok_or_else(|| {
+                Error::Transaction("Contract code is required for deployment".to_string())
+            })?;
+
+            return Ok(Self::ReviveDeploy {
+                code,
+                salt: transaction.salt.unwrap_or([0u8; 32]),
+                value: transaction.amount,
+                gas_limit: transaction.gas_limit,
+            });
+        }
+
+        Ok(Self::ReviveCall {
+            to: transaction.to.clone(),
+            data: transaction.data.clone().unwrap_or_default(),
+            value: transaction.amount,
+            gas_limit: transaction.gas_limit,
+        })
+    }
+
+    /// Translate a core transaction for an Ethereum JSON-RPC chain
+    pub fn for_evm(transaction: &Transaction) -> Result<Self> {
+        let data = transaction.data.clone().unwrap_or_default();
+
+        if transaction.is_deploy {
+            if data.is_empty() {
+                return Err(Error::Transaction(
+                    "Contract code is required for deployment".to_string(),
+                ));
+            }
+            return Ok(Self::EvmTransaction {
+                to: None,
+                data,
+                value: transaction.amount,
+                gas_limit: transaction.gas_limit,
+            });
+        }
+
+        match &transaction.to {
+            Address::Evm(_) => Ok(Self::EvmTransaction {
+                to: Some(transaction.to.clone()),
+                data,
+                value: transaction.amount,
+                gas_limit: transaction.gas_limit,
+            }),
+            other => Err(Error::InvalidAddress(format!(
+                "Destination address must be EVM address for EVM transactions, got {}",
+                other
+            ))),
+        }
+    }
+
+    /// Check whether this payload targets a Substrate chain
+    pub fn is_substrate(&self) -> bool {
+        matches!(
+            self,
+            Self::SubstrateTransfer { .. } | Self::SubstrateCall { .. }
+        )
+    }
+
+    /// Check whether this payload targets a Revive chain
+    pub fn is_revive(&self) -> bool {
+        matches!(self, Self::ReviveDeploy { .. } | Self::ReviveCall { .. })
+    }
+
+    /// Check whether this payload targets an Ethereum JSON-RPC chain
+    pub fn is_evm(&self) -> bool {
+        matches!(self, Self::EvmTransaction { .. })
+    }
+}
+
+impl TryFrom<&Transaction> for ChainPayload {
+    type Error = Error;
+
+    fn try_from(transaction: &Transaction) -> Result<Self> {
+        Self::from_transaction(transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::TransactionBuilder;
+    use crate::types::Chain;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+    const EVM: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+    #[test]
+    fn test_substrate_transfer() {
+        let tx = TransactionBuilder::new()
+            .from_address(ALICE)
+            .to_address(BOB)
+            .amount(1_000)
+            .build()
+            .unwrap();
+
+        let payload = ChainPayload::from_transaction(&tx).unwrap();
+        assert_eq!(
+            payload,
+            ChainPayload::SubstrateTransfer {
+                to: BOB.to_string(),
+                amount: 1_000
+            }
+        );
+        assert!(payload.is_substrate());
+    }
+
+    #[test]
+    fn test_substrate_raw_call() {
+        let tx = TransactionBuilder::new()
+            .from_address(ALICE)
+            .to_address(BOB)
+            .data(vec![0, 7, 1, 2, 3])
+            .build()
+            .unwrap();
+
+        let payload = ChainPayload::from_transaction(&tx).unwrap();
+        assert_eq!(
+            payload,
+            ChainPayload::SubstrateCall {
+                call_data: vec![0, 7, 1, 2, 3]
+            }
+        );
+    }
+
+    #[test]
+    fn test_substrate_call_rejects_amount() {
+        let tx = TransactionBuilder::new()
+            .from_address(ALICE)
+            .to_address(BOB)
+            .amount(5)
+            .data(vec![0, 7])
+            .build()
+            .unwrap();
+
+        assert!(ChainPayload::from_transaction(&tx).is_err());
+    }
+
+    #[test]
+    fn test_substrate_call_rejects_short_data() {
+        let tx = TransactionBuilder::new()
+            .from_address(ALICE)
+            .to_address(BOB)
+            .data(vec![0])
+            .build()
+            .unwrap();
+
+        assert!(ChainPayload::from_transaction(&tx).is_err());
+    }
+
+    #[test]
+    fn test_revive_call() {
+        let tx = TransactionBuilder::new()
+            .from_address(ALICE)
+            .to_address(EVM)
+            .amount(10)
+            .data(vec![0xaa, 0xbb])
+            .gas_limit(50_000)
+            .chain(Chain::Paseo)
+            .build()
+            .unwrap();
+
+        let payload = ChainPayload::for_revive(&tx).unwrap();
+        assert_eq!(
+            payload,
+            ChainPayload::ReviveCall {
+                to: Address::evm(EVM),
+                data: vec![0xaa, 0xbb],
+                value: 10,
+                gas_limit: Some(50_000)
+            }
+        );
+        assert!(payload.is_revive());
+    }
+
+    #[test]
+    fn test_revive_deploy() {
+        let tx = TransactionBuilder::new()
+            .from_address(ALICE)
+            .deploy(true)
+            .data(vec![0x50, 0x56, 0x4d])
+            .salt([1u8; 32])
+            .chain(Chain::Paseo)
+            .build()
+            .unwrap();
+
+        let payload = ChainPayload::for_revive(&tx).unwrap();
+        assert!(matches!(
+            payload,
+            ChainPayload::ReviveDeploy { salt, .. } if salt == [1u8; 32]
+        ));
+    }
+
+    #[test]
+    fn test_revive_deploy_requires_code() {
+        let tx = TransactionBuilder::new()
+            .from_address(ALICE)
+            .deploy(true)
+            .chain(Chain::Paseo)
+            .build()
+            .unwrap();
+
+        assert!(ChainPayload::for_revive(&tx).is_err());
+    }
+
+    #[test]
+    fn test_evm_chain_never_uses_revive() {
+        let tx = TransactionBuilder::new()
+            .from_address(EVM)
+            .to_address(EVM)
+            .amount(10)
+            .data(vec![0xaa])
+            .chain(Chain::Ethereum)
+            .build()
+            .unwrap();
+
+        assert!(ChainPayload::from_transaction(&tx).unwrap().is_evm());
+        assert!(matches!(
+            ChainPayload::for_revive(&tx),
+            Err(Error::UnsupportedChain(_))
+        ));
+    }
+
+    #[test]
+    fn test_hybrid_contract_call_uses_evm() {
+        let tx = TransactionBuilder::new()
+            .from_address(EVM)
+            .to_address(EVM)
+            .data(vec![0xaa])
+            .chain(Chain::Moonbeam)
+            .build()
+            .unwrap();
+
+        assert!(ChainPayload::from_transaction(&tx).unwrap().is_evm());
+    }
+
+    #[test]
+    fn test_hybrid_routes_by_address() {
+        let tx = TransactionBuilder::new()
+            .from_address(ALICE)
+            .to_address(BOB)
+            .amount(1)
+            .chain(Chain::Moonbeam)
+            .build()
+            .unwrap();
+
+        assert!(ChainPayload::from_transaction(&tx).unwrap().is_substrate());
+    }
+
+    #[test]
+    fn test_evm_transaction() {
+        let tx = TransactionBuilder::new()
+            .from_address(EVM)
+            .to_address(EVM)
+            .amount(10)
+            .data(vec![0xaa])
+            .build()
+            .unwrap();
+
+        let payload = ChainPayload::for_evm(&tx).unwrap();
+        assert_eq!(
+            payload,
+            ChainPayload::EvmTransaction {
+                to: Some(Address::evm(EVM)),
+                data: vec![0xaa],
+                value: 10,
+                gas_limit: None
+            }
+        );
+        assert!(payload.is_evm());
+
+        let deploy = TransactionBuilder::new()
+            .from_address(EVM)
+            .deploy(true)
+            .data(vec![0x60, 0x80])
+            .chain(Chain::Ethereum)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            ChainPayload::for_evm(&deploy).unwrap(),
+            ChainPayload::EvmTransaction { to: None, .. }
+        ));
+    }
+
+    #[test]
+    fn test_substrate_rejects_deploy() {
+        let tx = TransactionBuilder::new()
+            .from_address(ALICE)
+            .deploy(true)
+            .data(vec![1, 2, 3])
+            .chain(Chain::Polkadot)
+            .build()
+            .unwrap();
+
+        assert!(ChainPayload::from_transaction(&tx).is_err());
+    }
+}
@@ -191,11 +191,19 @@ pub struct ConnectionGuard<'a, T> {
     pool: &'a ConnectionPool<T>,
 }
 
+/// Longest a [`RateLimiter`] pauses by default, whatever a provider hints
+pub const DEFAULT_MAX_PAUSE: Duration = Duration::from_secs(60);
+
+/// Pause end used when `now + pause` does not fit in an `Instant`
+const FAR_FUTURE: Duration = Duration::from_secs(86400 * 365 * 30);
+
 /// Rate limiter for controlling request rates
 #[derive(Debug)]
 pub struct RateLimiter {
     semaphore: Arc<Semaphore>,
     interval: Duration,
+    max_pause: Duration,
+    paused_until: Mutex<Option<tokio::time::Instant>>,
 }
 
 impl RateLimiter {
@@ -203,10 +211,58 @@ impl RateLimiter {
         Self {
             semaphore: Arc::new(Semaphore::new(max_requests)),
             interval,
+            max_pause: DEFAULT_MAX_PAUSE,
+            paused_until: Mutex::new(None),
+        }
+    }
+
+    /// Longest single pause, [`DEFAULT_MAX_PAUSE`] unless set
+    pub fn with_max_pause(mut self, max_pause: Duration) -> Self {
+        self.max_pause = max_pause;
+        self
+    }
+
+    /// Hold back every acquisition for `duration`, capped at the max pause
+    ///
+    /// Overlapping pauses end at the later of their ends.
+    pub fn pause(&self, duration: Duration) {
+        let duration = duration.min(self.max_pause);
+        let now = tokio::time::Instant::now();
+        let until = now
+            .checked_add(duration)
+            .unwrap_or_else(|| now + FAR_FUTURE);
+        let mut paused_until = self
+            .paused_until
+            .lock()
+            .expect("Rate limiter pause lock should not be poisoned");
+        *paused_until = Some(paused_until.map_or(until, |current| current.max(until)));
+    }
+
+    /// Pause for the wait a throttled provider asked for, capped at the max
+    /// pause
+    ///
+    /// Returns whether the error was a throttle response with a wait.
+    pub fn pause_for(&self, error: &impl std::fmt::Display) -> bool {
+        match apex_sdk_core::ThrottleHint::parse(&error.to_string())
+            .and_then(|hint| hint.retry_after_capped(self.max_pause))
+        {
+            Some(wait) => {
+                self.pause(wait);
+                true
+            }
+            None => false,
         }
     }
 
     pub async fn acquire(&self) -> RateLimitGuard {
+        let paused_until = *self
+            .paused_until
+            .lock()
+            .expect("Rate limiter pause lock should not be poisoned");
+        if let Some(until) = paused_until {
+            tokio::time::sleep_until(until).await;
+        }
+
         let permit = self
             .semaphore
             .clone()
@@ -287,4 +343,45 @@ mod tests {
         // Should have waited at least some time (reduced tolerance for CI stability)
         assert!(elapsed >= Duration::from_millis(50)); // More lenient tolerance for CI
     }
+
+    #[tokio::test]
+    async fn test_rate_limiter_pauses_for_throttle_hint() {
+        let limiter = RateLimiter::new(10, Duration::from_millis(1));
+
+        assert!(!limiter.pause_for(&"connection reset"));
+        assert!(limiter.pause_for(&"HTTP 429 Too Many Requests\nRetry-After: 0"));
+        assert!(limiter.pause_for(&r#"{"code":-32005,"data":{"retryAfterMs":50}}"#));
+
+        let start = Instant::now();
+        let _guard = limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_caps_oversized_hints() {
+        let limiter = RateLimiter::new(10, Duration::from_millis(1))
+            .with_max_pause(Duration::from_millis(50));
+
+        // Waits too long to add to an Instant
+        let huge = format!("HTTP 429\nRetry-After: {}", "9".repeat(19));
+        assert!(limiter.pause_for(&huge));
+        assert!(limiter.pause_for(&r#"{"code":-32005,"data":{"backoff_seconds":1e300}}"#));
+        limiter.pause(Duration::MAX);
+
+        let start = Instant::now();
+        let _guard = limiter.acquire().await;
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(40));
+        assert!(elapsed < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_rate_limiter_pause_does_not_overflow() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let _enter = rt.enter();
+        let limiter = RateLimiter::new(1, Duration::from_millis(1)).with_max_pause(Duration::MAX);
+
+        limiter.pause(Duration::MAX);
+        assert!(limiter.paused_until.lock().unwrap().is_some());
+    }
 }
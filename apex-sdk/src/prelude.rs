@@ -0,0 +1,48 @@
+//! Prelude module for common imports
+//!
+//! The prelude is versioned so that growing the public surface doesn't
+//! change what an existing glob import brings into scope:
+//!
+//! - [`v1`] is the original prelude and is frozen. `use apex_sdk::prelude::*`
+//!   keeps importing exactly these items.
+//! - [`v2`] adds the cross-chain, batch execution and retry APIs. Opt in with
+//!   `use apex_sdk::prelude::v2::*`.
+//!
+//! Items renamed or retyped between versions keep their old name in
+//! [`compat`](crate::compat), marked `#[deprecated]`, for at least one minor
+//! release.
+
+pub use v1::*;
+
+/// The original prelude
+pub mod v1 {
+    pub use crate::{
+        builder::ApexSDKBuilder,
+        error::{Error, Result},
+        sdk::{ApexSDK, ConfirmationStrategy, SdkConfig},
+        transaction::{Transaction, TransactionBuilder, TransactionResult},
+        types::{Address, Chain, ChainType},
+    };
+
+    #[cfg(feature = "substrate")]
+    pub use crate::substrate::{SubstrateAdapter, Wallet as SubstrateWallet};
+
+    #[cfg(feature = "revive")]
+    pub use crate::revive::ReviveAdapter;
+
+    #[cfg(feature = "evm")]
+    pub use crate::evm::EvmAdapter;
+}
+
+/// The current prelude
+pub mod v2 {
+    pub use super::v1::*;
+
+    pub use crate::{
+        advanced::{BatchExecutionResult, ParallelExecutor, TransactionBatch, TransactionOutcome},
+        error::HasErrorCode,
+        error_recovery::{with_retry, with_retry_if, RetryConfig},
+        transaction::{CrossChainResult, TransactionStatus},
+        types::IntoAddress,
+    };
+}
@@ -1,12 +1,15 @@
 //! Main SDK interface providing unified access to both Substrate and EVM blockchains.
 
+#[cfg(any(feature = "substrate", feature = "revive", feature = "evm"))]
+use crate::payload::ChainPayload;
 use crate::{
     error::{Error, Result},
-    transaction::{Transaction, TransactionResult},
+    journal::{IntentJournal, ResumedIntent, Submission},
+    transaction::{CrossChainResult, Transaction, TransactionResult},
     types::{Address, Chain},
 };
 use apex_sdk_core::ChainAdapter;
-use apex_sdk_types::TxStatus;
+use apex_sdk_types::{TxHash, TxStatus};
 use std::{sync::Arc, time::Duration};
 
 /// Transaction confirmation strategy
@@ -21,7 +24,12 @@ pub enum ConfirmationStrategy {
 }
 
 /// SDK configuration for transaction handling
+///
+/// Start from [`SdkConfig::default`] and set the fields you need, or use the
+/// [`ApexSDKBuilder`](crate::ApexSDKBuilder) methods; fields may be added in
+/// minor releases.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct SdkConfig {
     /// Strategy for transaction confirmation
     pub confirmation_strategy: ConfirmationStrategy,
@@ -29,6 +37,10 @@ pub struct SdkConfig {
     pub confirmation_blocks: u32,
     /// Maximum time to wait for confirmations
     pub timeout_seconds: u64,
+    /// Default cap on the final fee of Substrate transactions, tip included,
+    /// in the chain's smallest unit. Transactions estimated above it fail
+    /// with [`Error::FeeCapExceeded`] before submission.
+    pub max_fee: Option<u128>,
 }
 
 impl Default for SdkConfig {
@@ -37,6 +49,7 @@ impl Default for SdkConfig {
             confirmation_strategy: ConfirmationStrategy::WaitForInclusion,
             confirmation_blocks: 1,
             timeout_seconds: 60,
+            max_fee: None,
         }
     }
 }
@@ -47,6 +60,9 @@ use apex_sdk_substrate::SubstrateAdapter;
 #[cfg(feature = "revive")]
 use apex_sdk_revive::ReviveAdapter;
 
+#[cfg(feature = "evm")]
+use apex_sdk_evm::EvmAdapter;
+
 /// The main Apex SDK providing unified access to multiple blockchain types.
 ///
 /// # Example
@@ -78,8 +94,15 @@ pub struct ApexSDK {
     #[cfg(feature = "revive")]
     revive_adapter: Option<Arc<ReviveAdapter>>,
 
+    #[cfg(feature = "evm")]
+    evm_adapter: Option<Arc<EvmAdapter>>,
+
+    #[cfg(feature = "substrate")]
+    spending_limiter: Option<apex_sdk_substrate::SpendingLimiter>,
+
     timeout: Duration,
     config: SdkConfig,
+    journal: Option<Arc<IntentJournal>>,
 }
 
 impl ApexSDK {
@@ -95,20 +118,26 @@ impl ApexSDK {
         #[cfg(feature = "substrate")] substrate_adapter: Option<SubstrateAdapter>,
         #[cfg(feature = "substrate")] substrate_wallet: Option<apex_sdk_substrate::Wallet>,
         #[cfg(feature = "revive")] revive_adapter: Option<ReviveAdapter>,
+        #[cfg(feature = "evm")] evm_adapter: Option<EvmAdapter>,
         timeout: Duration,
         config: SdkConfig,
     ) -> Result<Self> {
-        #[cfg(not(any(feature = "substrate", feature = "revive")))]
+        #[cfg(not(any(feature = "substrate", feature = "revive", feature = "evm")))]
         {
             return Err(Error::Config(
-                "No blockchain adapters enabled. Enable 'substrate' or 'revive' features."
+                "No blockchain adapters enabled. Enable 'substrate', 'revive' or 'evm' features."
                     .to_string(),
             ));
         }
 
+        #[cfg(feature = "evm")]
+        let evm_configured = evm_adapter.is_some();
+        #[cfg(not(feature = "evm"))]
+        let evm_configured = false;
+
         #[cfg(all(feature = "substrate", feature = "revive"))]
         {
-            if substrate_adapter.is_none() && revive_adapter.is_none() {
+            if !evm_configured && substrate_adapter.is_none() && revive_adapter.is_none() {
                 return Err(Error::Config(
                     "At least one adapter must be configured".to_string(),
                 ));
@@ -125,13 +154,197 @@ impl ApexSDK {
             #[cfg(feature = "revive")]
             revive_adapter: revive_adapter.map(Arc::new),
 
+            #[cfg(feature = "evm")]
+            evm_adapter: evm_adapter.map(Arc::new),
+
+            #[cfg(feature = "substrate")]
+            spending_limiter: None,
+
             timeout,
             config,
+            journal: None,
         })
     }
 
+    /// Hold Substrate transactions passed to [`execute`](Self::execute) to a
+    /// fee budget
+    #[cfg(feature = "substrate")]
+    pub(crate) fn with_spending_limiter(
+        mut self,
+        limiter: apex_sdk_substrate::SpendingLimiter,
+    ) -> Self {
+        self.spending_limiter = Some(limiter);
+        self
+    }
+
+    /// Journal transactions passed to [`execute`](Self::execute)
+    pub(crate) fn with_intent_journal(mut self, journal: Arc<IntentJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// SDK without adapters, for tests of code paths that fail before
+    /// reaching a chain
+    #[cfg(test)]
+    pub(crate) fn without_adapters() -> Self {
+        Self {
+            #[cfg(feature = "substrate")]
+            substrate_adapter: None,
+            #[cfg(feature = "substrate")]
+            substrate_wallet: None,
+            #[cfg(feature = "revive")]
+            revive_adapter: None,
+            #[cfg(feature = "evm")]
+            evm_adapter: None,
+            #[cfg(feature = "substrate")]
+            spending_limiter: None,
+            timeout: Duration::from_secs(30),
+            config: SdkConfig::default(),
+            journal: None,
+        }
+    }
+
     /// Execute a transaction on the appropriate blockchain.
+    ///
+    /// Transactions for EVM chains go through the EVM adapter, and contract
+    /// calls on pallet-revive chains through the Revive adapter. Cross-chain
+    /// transfers are routed by [`execute_cross_chain`](Self::execute_cross_chain).
+    ///
+    /// With an [`IntentJournal`] configured, the transaction and the sender's
+    /// nonce are journaled before submission, and Substrate and EVM
+    /// transactions get their hash journaled before broadcast. The intent is
+    /// removed when the transaction reaches a final status or fails before it
+    /// could have been broadcast. Pending results and failures after
+    /// broadcast, such as a dropped connection, keep the intent for
+    /// [`resume_pending`](Self::resume_pending).
     pub async fn execute(&self, transaction: Transaction) -> Result<TransactionResult> {
+        let Some(journal) = &self.journal else {
+            return self.dispatch(transaction, None).await;
+        };
+
+        let nonce = self.sender_nonce(&transaction).await;
+        let id = journal.record(&transaction, nonce)?;
+        let submission = Submission::new(Arc::clone(journal), id);
+        let result = self.dispatch(transaction, Some(&submission)).await;
+
+        submission.settle(&result)?;
+        result
+    }
+
+    /// Reconcile intents left in the journal by an earlier run
+    ///
+    /// Call at startup, before executing new transactions. An intent with a
+    /// known hash is looked up on its chain; otherwise, or if the chain does
+    /// not know the hash, the sender's current nonce is compared with the
+    /// journaled one. Resolved intents are removed from the journal and
+    /// [`Unresolved`](crate::journal::IntentOutcome::Unresolved) ones are kept for the next call. Returns
+    /// an empty list when no journal is configured.
+    pub async fn resume_pending(&self) -> Result<Vec<ResumedIntent>> {
+        let Some(journal) = &self.journal else {
+            return Ok(Vec::new());
+        };
+
+        let mut resumed = Vec::new();
+        for intent in journal.pending() {
+            let status = match &intent.tx_hash {
+                Some(tx_hash) => self
+                    .get_transaction_status(tx_hash, &intent.chain)
+                    .await
+                    .ok()
+                    .map(|status| status.status),
+                None => None,
+            };
+            let current_nonce = self.sender_nonce(&intent.transaction).await;
+            let outcome = crate::journal::reconcile(&intent, status, current_nonce);
+
+            tracing::info!(
+                intent = intent.id,
+                chain = intent.chain.name(),
+                ?outcome,
+                "Reconciled journaled transaction"
+            );
+            if outcome.is_resolved() {
+                journal.complete(intent.id)?;
+            }
+            resumed.push(ResumedIntent { intent, outcome });
+        }
+
+        Ok(resumed)
+    }
+
+    /// Get the intent journal (if configured).
+    pub fn intent_journal(&self) -> Option<Arc<IntentJournal>> {
+        self.journal.clone()
+    }
+
+    /// Next nonce of the account that signs `transaction`, if it can be read
+    ///
+    /// EVM transactions are signed by their sender; Substrate and XCM
+    /// transactions by the SDK's Substrate wallet.
+    #[allow(unused_variables)]
+    async fn sender_nonce(&self, transaction: &Transaction) -> Option<u64> {
+        let chain_type = transaction.source_chain().chain_type();
+
+        #[cfg(feature = "evm")]
+        if chain_type == apex_sdk_types::ChainType::Evm {
+            if let Some(adapter) = &self.evm_adapter {
+                return apex_sdk_core::Provider::get_transaction_count(
+                    adapter.as_ref(),
+                    &transaction.from,
+                )
+                .await
+                .ok();
+            }
+        }
+
+        #[cfg(feature = "substrate")]
+        if chain_type != apex_sdk_types::ChainType::Evm {
+            if let (Some(adapter), Some(wallet)) = (&self.substrate_adapter, &self.substrate_wallet)
+            {
+                return apex_sdk_core::Provider::get_transaction_count(
+                    adapter.as_ref(),
+                    &Address::substrate(wallet.address()),
+                )
+                .await
+                .ok();
+            }
+        }
+
+        None
+    }
+
+    /// Route a transaction to the adapter for its chain
+    ///
+    /// Executors that can report the signed hash before broadcast journal it
+    /// through `submission`.
+    async fn dispatch(
+        &self,
+        transaction: Transaction,
+        submission: Option<&Submission>,
+    ) -> Result<TransactionResult> {
+        if transaction.is_cross_chain() {
+            return self.execute_cross_chain(transaction).await.map(Into::into);
+        }
+
+        #[cfg(feature = "evm")]
+        if transaction.destination_chain().chain_type() == apex_sdk_types::ChainType::Evm {
+            if let Some(adapter) = &self.evm_adapter {
+                return self
+                    .execute_evm_transaction(adapter, transaction, submission)
+                    .await;
+            }
+        }
+
+        // Contract transactions on a Substrate chain go through pallet-revive
+        #[cfg(feature = "revive")]
+        if transaction.destination_chain().chain_type() != apex_sdk_types::ChainType::Evm
+            && (transaction.is_deploy || matches!(transaction.to, Address::Evm(_)))
+        {
+            if let Some(adapter) = &self.revive_adapter {
+                return self.execute_revive_transaction(adapter, transaction).await;
+            }
+        }
+
         match transaction.destination_chain() {
             #[cfg(feature = "substrate")]
             chain if chain.chain_type() == apex_sdk_types::ChainType::Substrate => {
@@ -142,21 +355,13 @@ impl ApexSDK {
                     ))
                 })?;
 
-                self.execute_substrate_transaction(adapter, transaction)
+                self.execute_substrate_transaction(adapter, transaction, submission)
                     .await
             }
 
-            #[cfg(feature = "revive")]
-            chain if chain.chain_type() == apex_sdk_types::ChainType::Evm => {
-                let adapter = self.revive_adapter.as_ref().ok_or_else(|| {
-                    Error::UnsupportedChain(format!(
-                        "Revive adapter not configured for {}",
-                        chain.name()
-                    ))
-                })?;
-
-                self.execute_revive_transaction(adapter, transaction).await
-            }
+            chain if chain.chain_type() == apex_sdk_types::ChainType::Evm => Err(
+                Error::UnsupportedChain(format!("EVM adapter not configured for {}", chain.name())),
+            ),
 
             chain => Err(Error::UnsupportedChain(format!(
                 "Chain {} not supported",
@@ -165,12 +370,56 @@ impl ApexSDK {
         }
     }
 
+    /// Execute a transfer between two chains.
+    ///
+    /// Transfers between a relay chain and its parachains, or between sibling
+    /// parachains, are sent as XCM reserve transfers through the Substrate
+    /// adapter, which must be connected to the source chain. Other routes
+    /// return [`Error::UnsupportedRoute`].
+    pub async fn execute_cross_chain(&self, transaction: Transaction) -> Result<CrossChainResult> {
+        let source = transaction.source_chain();
+        let destination = transaction.destination_chain();
+
+        if transaction.is_deploy || transaction.data.is_some() {
+            return Err(Error::UnsupportedRoute(format!(
+                "Only transfers can be sent from {} to {}",
+                source.name(),
+                destination.name()
+            )));
+        }
+
+        #[cfg(feature = "substrate")]
+        if let Some(hop) = XcmHop::between(&source, &destination) {
+            return self
+                .execute_xcm_transfer(transaction, source, destination, hop)
+                .await;
+        }
+
+        Err(Error::UnsupportedRoute(format!(
+            "No route from {} to {}; only XCM transfers between a relay chain and its parachains are supported",
+            source.name(),
+            destination.name()
+        )))
+    }
+
     /// Get the status of a transaction.
     pub async fn get_transaction_status(
         &self,
         tx_hash: &str,
         chain: &Chain,
     ) -> Result<apex_sdk_types::TransactionStatus> {
+        let tx_hash = &parse_tx_hash(tx_hash)?;
+
+        #[cfg(feature = "evm")]
+        if chain.chain_type() == apex_sdk_types::ChainType::Evm {
+            if let Some(adapter) = &self.evm_adapter {
+                return adapter
+                    .get_transaction_status(tx_hash)
+                    .await
+                    .map_err(|e| Error::Transaction(e.to_string()));
+            }
+        }
+
         match chain.chain_type() {
             #[cfg(feature = "substrate")]
             apex_sdk_types::ChainType::Substrate => {
@@ -249,12 +498,18 @@ impl ApexSDK {
 
     /// Check if a chain is supported by the current configuration.
     pub fn is_chain_supported(&self, chain: &Chain) -> bool {
+        #[cfg(feature = "evm")]
+        if chain.chain_type() == apex_sdk_types::ChainType::Evm && self.evm_adapter.is_some() {
+            return true;
+        }
+
         match chain.chain_type() {
             #[cfg(feature = "substrate")]
             apex_sdk_types::ChainType::Substrate => self.substrate_adapter.is_some(),
 
+            // EVM chains are only served by the EVM adapter, checked above
             #[cfg(feature = "revive")]
-            apex_sdk_types::ChainType::Evm => self.revive_adapter.is_some(),
+            apex_sdk_types::ChainType::Evm => false,
 
             #[cfg(all(feature = "substrate", feature = "revive"))]
             apex_sdk_types::ChainType::Hybrid => {
@@ -281,6 +536,14 @@ impl ApexSDK {
             .ok_or_else(|| Error::Config("Substrate adapter not configured".to_string()))
     }
 
+    /// Get the shared finalized-block event bus of the Substrate adapter.
+    ///
+    /// Consumers subscribed here share a single block subscription.
+    #[cfg(feature = "substrate")]
+    pub fn event_bus(&self) -> Result<Arc<apex_sdk_substrate::EventBus>> {
+        Ok(self.substrate()?.event_bus())
+    }
+
     /// Get access to the Revive adapter (if configured).
     #[cfg(feature = "revive")]
     pub fn revive(&self) -> Result<Arc<ReviveAdapter>> {
@@ -290,6 +553,15 @@ impl ApexSDK {
             .ok_or_else(|| Error::Config("Revive adapter not configured".to_string()))
     }
 
+    /// Get access to the EVM adapter (if configured).
+    #[cfg(feature = "evm")]
+    pub fn evm(&self) -> Result<Arc<EvmAdapter>> {
+        self.evm_adapter
+            .as_ref()
+            .cloned()
+            .ok_or_else(|| Error::Config("EVM adapter not configured".to_string()))
+    }
+
     /// Get the configured timeout duration.
     pub fn timeout(&self) -> Duration {
         self.timeout
@@ -321,8 +593,8 @@ impl ApexSDK {
     /// # use apex_sdk::{ApexSDK, types::Chain};
     /// # async fn example(sdk: ApexSDK) -> Result<(), Box<dyn std::error::Error>> {
     /// let tx = sdk.transaction()
-    ///     .from_evm_address("0x...")
-    ///     .to_evm_address("0x...")
+    ///     .from_address("0x...")
+    ///     .to_address("0x...")
     ///     .amount(1000)
     ///     .build()?;
     ///
@@ -340,6 +612,7 @@ impl ApexSDK {
         chain: &Chain,
         max_wait: Option<Duration>,
     ) -> Result<()> {
+        parse_tx_hash(tx_hash)?;
         let max_wait = max_wait.unwrap_or(Duration::from_secs(60));
         let start = std::time::Instant::now();
 
@@ -371,7 +644,7 @@ impl ApexSDK {
                         let error_msg = format!("Transaction {} failed", tx_hash);
                         return Err(Error::Transaction(error_msg));
                     }
-                    TxStatus::Pending | TxStatus::InMempool => {
+                    TxStatus::Pending | TxStatus::InMempool | TxStatus::TimedOut => {
                         tracing::debug!("Transaction {} still pending, waiting...", tx_hash);
                         tokio::time::sleep(Duration::from_secs(2)).await;
                     }
@@ -393,7 +666,9 @@ impl ApexSDK {
         &self,
         adapter: &SubstrateAdapter,
         transaction: Transaction,
+        submission: Option<&Submission>,
     ) -> Result<TransactionResult> {
+        let broadcast_hook = submission.map(Submission::broadcast_hook);
         let wallet = self.substrate_wallet.as_ref().ok_or_else(|| {
             Error::Transaction(
                 "Substrate wallet not configured. Transaction execution requires signing.\n\
@@ -412,58 +687,64 @@ impl ApexSDK {
                 \n\
                 Alternatively, use the adapter API directly:\n\
                 let executor = sdk.substrate()?.transaction_executor();\n\
-                let tx_hash = executor.transfer(&wallet, &to_address, amount).await?;"
+                let receipt = executor.transfer(&wallet, &to_address, amount).await?;"
                     .to_string(),
             )
         })?;
 
-        let to_address = match &transaction.to {
-            Address::Substrate(addr) => addr.clone(),
-            _ => {
-                return Err(Error::Transaction(
-                    "Destination address must be Substrate address for Substrate transactions"
-                        .to_string(),
-                ))
-            }
-        };
-
-        let amount = transaction.amount;
-
-        if transaction.data.is_some() {
-            return Err(Error::Transaction(
-                "Contract calls require using the adapter API directly for proper SCALE encoding.\n\
-                \n\
-                For contract calls, use:\n\
-                let executor = sdk.substrate()?.transaction_executor();\n\
-                // Use subxt dynamic API or typed metadata for contract calls\n\
-                // Example: executor.submit_extrinsic(call_data).await"
-                    .to_string(),
-            ));
+        let mut executor = adapter.transaction_executor();
+        if let Some(hook) = broadcast_hook {
+            executor = executor.with_broadcast_hook(hook);
+        }
+        if let Some(max_fee) = self.config.max_fee {
+            let fee_config = executor.fee_config().clone().with_max_fee(max_fee);
+            executor = executor.with_fee_config(fee_config);
+        }
+        if let Some(limiter) = &self.spending_limiter {
+            executor = executor.with_spending_limiter(limiter.clone());
         }
 
-        tracing::debug!(
-            "Preparing Substrate transaction: to={}, amount={}",
-            to_address,
-            amount
-        );
+        let tx_hash = match ChainPayload::from_transaction(&transaction)? {
+            ChainPayload::SubstrateTransfer { to, amount } => {
+                tracing::debug!(to = %to, amount, "Preparing Substrate transaction");
 
-        let executor = adapter.transaction_executor();
+                let tx_hash = executor
+                    .transfer(wallet.as_ref(), &to, amount)
+                    .await
+                    .map_err(|e| substrate_error("Substrate transaction failed", e))?
+                    .tx_hash;
+
+                tracing::info!(
+                    from = %transaction.from,
+                    to = %to,
+                    amount,
+                    tx_hash = %tx_hash,
+                    "Substrate transaction submitted"
+                );
+
+                tx_hash
+            }
+            ChainPayload::SubstrateCall { call_data } => {
+                tracing::debug!(
+                    "Preparing Substrate call: {} bytes of call data",
+                    call_data.len()
+                );
+
+                let tx_hash = executor
+                    .execute_call(wallet.as_ref(), call_data)
+                    .await
+                    .map_err(|e| substrate_error("Substrate call failed", e))?;
 
-        let tx_hash = executor
-            .transfer(wallet.as_ref(), &to_address, amount)
-            .await
-            .map_err(|e| Error::Transaction(format!("Substrate transaction failed: {}", e)))?;
+                tracing::info!("Substrate call submitted, hash: {}", tx_hash);
 
-        tracing::info!(
-            "Substrate transaction submitted: {} → {}, amount: {}, hash: {}",
-            match &transaction.from {
-                Address::Substrate(a) => a.as_str(),
-                _ => "unknown",
-            },
-            to_address,
-            amount,
-            tx_hash
-        );
+                tx_hash
+            }
+            _ => {
+                return Err(Error::Transaction(
+                    "Transaction does not translate to a Substrate payload".to_string(),
+                ))
+            }
+        };
 
         tracing::debug!("Waiting for transaction inclusion in block...");
 
@@ -489,6 +770,106 @@ impl ApexSDK {
         Ok(result)
     }
 
+    #[cfg(feature = "substrate")]
+    async fn execute_xcm_transfer(
+        &self,
+        transaction: Transaction,
+        source: Chain,
+        destination: Chain,
+        hop: XcmHop,
+    ) -> Result<CrossChainResult> {
+        use apex_sdk_substrate::{AssetId, MultiLocation, XcmAsset, XcmTransfer};
+
+        let route_error = |reason: &str| {
+            Error::UnsupportedRoute(format!(
+                "XCM transfer from {} to {}: {}",
+                source.name(),
+                destination.name(),
+                reason
+            ))
+        };
+        let xcm_error = |e: apex_sdk_substrate::Error| Error::Transaction(e.to_string());
+
+        let adapter = self
+            .substrate_adapter
+            .as_ref()
+            .ok_or_else(|| route_error("Substrate adapter not configured"))?;
+        let wallet = self.substrate_wallet.as_ref().ok_or_else(|| {
+            Error::Transaction(
+                "Substrate wallet not configured. XCM transfers require signing.".to_string(),
+            )
+        })?;
+
+        let xcm = adapter.xcm();
+        if xcm.is_relay_chain() != source.is_relay_chain() {
+            return Err(route_error(&format!(
+                "the Substrate adapter is not connected to {}",
+                source.name()
+            )));
+        }
+
+        let (dest, asset) = match hop {
+            XcmHop::ToRelay => (
+                xcm.relay_destination().map_err(xcm_error)?,
+                XcmAsset::fungible(
+                    AssetId::Concrete(MultiLocation::parent()),
+                    transaction.amount,
+                ),
+            ),
+            XcmHop::ToParachain(para_id) => (
+                xcm.parachain_destination(para_id).map_err(xcm_error)?,
+                XcmAsset::native(transaction.amount),
+            ),
+        };
+
+        let transfer = match &transaction.to {
+            Address::Substrate(_) => {
+                XcmTransfer::reserve(dest, transaction.to.account_id()?, vec![asset])
+            }
+            Address::Evm(to) if destination.chain_type() == apex_sdk_types::ChainType::Hybrid => {
+                let key: [u8; 20] = hex::decode(to.trim_start_matches("0x"))
+                    .ok()
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(|| Error::InvalidAddress(to.clone()))?;
+                let beneficiary = MultiLocation::builder()
+                    .account_key20(key)
+                    .build()
+                    .map_err(xcm_error)?;
+                // EVM accounts are AccountKey20 locations on the destination
+                XcmTransfer::reserve(dest, [0u8; 32], vec![asset]).with_beneficiary(beneficiary)
+            }
+            Address::Evm(_) => {
+                return Err(route_error(
+                    "EVM recipients are only supported on chains with EVM accounts",
+                ))
+            }
+        };
+
+        let receipt = xcm
+            .transfer(wallet, &transfer)
+            .await
+            .map_err(|e| Error::Transaction(format!("XCM transfer failed: {}", e)))?;
+
+        tracing::info!(
+            source = source.name(),
+            destination = destination.name(),
+            amount = transaction.amount,
+            tx_hash = %receipt.tx_hash,
+            "XCM transfer submitted"
+        );
+
+        Ok(CrossChainResult {
+            source_chain: source,
+            destination_chain: destination,
+            source_tx_hash: receipt.tx_hash,
+            destination_tx_hash: None,
+            message_id: receipt
+                .message_id
+                .map(|id| format!("0x{}", hex::encode(id))),
+            status: crate::transaction::TransactionStatus::Finalized,
+        })
+    }
+
     /// Execute a Revive transaction
     #[cfg(feature = "revive")]
     async fn execute_revive_transaction(
@@ -497,9 +878,9 @@ impl ApexSDK {
         transaction: Transaction,
     ) -> Result<TransactionResult> {
         tracing::debug!(
-            "Executing Revive transaction: {} -> {}",
-            transaction.from,
-            transaction.to
+            from = %transaction.from,
+            to = %transaction.to,
+            "Executing Revive transaction"
         );
 
         let signer = self.substrate_wallet.as_ref().ok_or_else(|| {
@@ -509,33 +890,139 @@ impl ApexSDK {
         let subxt_signer = signer.to_subxt_signer();
         let contract_manager = crate::revive::ContractManager::new(adapter, subxt_signer);
 
-        let result = if transaction.is_deploy {
-            let code = transaction.data.ok_or_else(|| {
-                Error::Transaction("Contract code is required for deployment".into())
-            })?;
-            let salt = transaction.salt.unwrap_or([0u8; 32]);
-            let value = transaction.amount;
+        let result = match ChainPayload::for_revive(&transaction)? {
+            ChainPayload::ReviveDeploy {
+                code,
+                salt,
+                value,
+                gas_limit,
+            } => {
+                let addr = contract_manager
+                    .deploy(code, vec![], salt, value, gas_limit)
+                    .await
+                    .map_err(|e| Error::Transaction(e.to_string()))?;
 
-            let addr = contract_manager
-                .deploy(code, vec![], salt, value, transaction.gas_limit)
-                .await
-                .map_err(|e| Error::Transaction(e.to_string()))?;
+                TransactionResult::new(format!("deploy:{}", addr))
+                    .with_status(crate::transaction::TransactionStatus::Success)
+            }
+            ChainPayload::ReviveCall {
+                to,
+                data,
+                value,
+                gas_limit,
+            } => {
+                let _return_data = contract_manager
+                    .call(&to, data, value, gas_limit)
+                    .await
+                    .map_err(|e| Error::Transaction(e.to_string()))?;
 
-            TransactionResult::new(format!("deploy:{}", addr))
-                .with_status(crate::transaction::TransactionStatus::Success)
-        } else {
-            let data = transaction.data.clone().unwrap_or_default();
-            let value = transaction.amount;
+                TransactionResult::new(transaction.hash())
+                    .with_status(crate::transaction::TransactionStatus::Success)
+            }
+            _ => {
+                return Err(Error::Transaction(
+                    "Transaction does not translate to a Revive payload".to_string(),
+                ))
+            }
+        };
 
-            let _return_data = contract_manager
-                .call(&transaction.to, data, value, transaction.gas_limit)
-                .await
-                .map_err(|e| Error::Transaction(e.to_string()))?;
+        Ok(result)
+    }
+
+    /// Execute a transaction through the EVM adapter
+    #[cfg(feature = "evm")]
+    async fn execute_evm_transaction(
+        &self,
+        adapter: &EvmAdapter,
+        transaction: Transaction,
+        submission: Option<&Submission>,
+    ) -> Result<TransactionResult> {
+        use apex_sdk_core::ReceiptWatcher;
+
+        let broadcast_hook = submission.map(Submission::broadcast_hook);
+
+        tracing::debug!(
+            from = %transaction.from,
+            to = %transaction.to,
+            "Executing EVM transaction"
+        );
 
-            TransactionResult::new(transaction.hash())
-                .with_status(crate::transaction::TransactionStatus::Success)
+        let mut executor = adapter.transaction_executor().map_err(|_| {
+            Error::Config(
+                "EVM wallet required for EVM transactions; use ApexSDKBuilder::with_evm_wallet"
+                    .to_string(),
+            )
+        })?;
+        if let Some(hook) = broadcast_hook {
+            executor = executor.with_broadcast_hook(hook);
+        }
+
+        let tx_hash = match ChainPayload::for_evm(&transaction)? {
+            ChainPayload::EvmTransaction {
+                to: Some(to),
+                data,
+                value,
+                gas_limit,
+            } => {
+                if data.is_empty() && gas_limit.is_none() {
+                    executor.transfer(&to, value).await
+                } else {
+                    executor.call(&to, data, value, gas_limit).await
+                }
+            }
+            ChainPayload::EvmTransaction {
+                to: None,
+                data,
+                value,
+                gas_limit,
+            } => executor.deploy(data, value, gas_limit).await,
+            _ => {
+                return Err(Error::Transaction(
+                    "Transaction does not translate to an EVM payload".to_string(),
+                ))
+            }
+        }
+        .map_err(|e| evm_error("EVM transaction failed", e))?;
+
+        tracing::info!("EVM transaction submitted, hash: {}", tx_hash);
+
+        let strategy = match self.config.confirmation_strategy {
+            ConfirmationStrategy::Immediate => {
+                return Ok(TransactionResult::new(tx_hash.to_string())
+                    .with_status(crate::transaction::TransactionStatus::Pending))
+            }
+            ConfirmationStrategy::WaitForInclusion => {
+                apex_sdk_core::ConfirmationStrategy::BlockConfirmations {
+                    confirmations: self.config.confirmation_blocks,
+                    timeout_secs: self.config.timeout_seconds,
+                }
+            }
+            ConfirmationStrategy::WaitForFinality => {
+                apex_sdk_core::ConfirmationStrategy::Finalized {
+                    timeout_secs: self.config.timeout_seconds,
+                }
+            }
         };
 
+        let status = adapter
+            .wait_for_receipt_with_strategy(&tx_hash, &strategy)
+            .await
+            .map_err(|e| Error::Transaction(e.to_string()))?;
+
+        let mut result =
+            TransactionResult::new(tx_hash.to_string()).with_status(match status.status {
+                TxStatus::Failed => crate::transaction::TransactionStatus::Failed,
+                TxStatus::Finalized => crate::transaction::TransactionStatus::Finalized,
+                TxStatus::TimedOut => crate::transaction::TransactionStatus::Pending,
+                _ => crate::transaction::TransactionStatus::Success,
+            });
+        if let Some(block_number) = status.block_number {
+            result = result.with_block_number(block_number);
+        }
+        if let Some(gas_used) = status.gas_used {
+            result = result.with_gas_used(gas_used);
+        }
+
         Ok(result)
     }
 
@@ -548,15 +1035,7 @@ impl ApexSDK {
     ) -> Result<()> {
         tracing::debug!("Monitoring transaction {} for finality", tx_hash);
 
-        // Parse the transaction hash
-        let hash_bytes = hex::decode(tx_hash.strip_prefix("0x").unwrap_or(tx_hash))
-            .map_err(|e| Error::Transaction(format!("Invalid transaction hash: {}", e)))?;
-
-        if hash_bytes.len() != 32 {
-            return Err(Error::Transaction(
-                "Transaction hash must be 32 bytes".to_string(),
-            ));
-        }
+        let tx_hash = &parse_tx_hash(tx_hash)?;
 
         // Poll for finalized blocks containing our transaction
         let timeout_deadline = tokio::time::Instant::now() + self.timeout;
@@ -588,9 +1067,85 @@ impl ApexSDK {
     }
 }
 
+/// Direction of an XCM transfer within a relay chain ecosystem
+#[cfg(feature = "substrate")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum XcmHop {
+    /// From a parachain up to its relay chain
+    ToRelay,
+    /// From the relay chain or a sibling to a parachain
+    ToParachain(u32),
+}
+
+#[cfg(feature = "substrate")]
+impl XcmHop {
+    fn between(source: &Chain, destination: &Chain) -> Option<Self> {
+        if source == destination {
+            return None;
+        }
+        let relay = if source.is_relay_chain() {
+            source.clone()
+        } else {
+            source.relay_chain()?
+        };
+
+        if *destination == relay {
+            Some(Self::ToRelay)
+        } else if destination.relay_chain().as_ref() == Some(&relay) {
+            destination.parachain_id().map(Self::ToParachain)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse a transaction hash returned by an earlier call
+fn parse_tx_hash(tx_hash: &str) -> Result<TxHash> {
+    tx_hash
+        .parse()
+        .map_err(|e: apex_sdk_types::ValidationError| Error::Transaction(e.to_string()))
+}
+
+/// Convert a Substrate adapter error, keeping fee cap and budget refusals typed
+/// and connection failures retryable
+#[cfg(feature = "substrate")]
+fn substrate_error(context: &str, err: apex_sdk_substrate::Error) -> Error {
+    match err {
+        apex_sdk_substrate::Error::FeeCapExceeded(breakdown) => Error::FeeCapExceeded {
+            fee: breakdown.total_fee,
+            max_fee: breakdown.max_fee,
+            breakdown: breakdown.to_string(),
+        },
+        apex_sdk_substrate::Error::SpendingLimitExceeded(refusal) => Error::SpendingLimitExceeded {
+            account: refusal.account,
+            fee: refusal.fee,
+            spent: refusal.spent,
+            limit: refusal.limit,
+        },
+        // Raised before the transaction was broadcast, so safe to retry
+        apex_sdk_substrate::Error::Connection(msg) => {
+            Error::Connection(format!("{}: {}", context, msg))
+        }
+        other => Error::Transaction(format!("{}: {}", context, other)),
+    }
+}
+
+/// Convert an EVM adapter error, keeping connection failures retryable
+///
+/// The executor reports a failed broadcast as a transaction error, so a
+/// connection error here was raised before the transaction left the SDK.
+#[cfg(feature = "evm")]
+fn evm_error(context: &str, err: apex_sdk_evm::Error) -> Error {
+    match err {
+        apex_sdk_evm::Error::Connection(msg) => Error::Connection(format!("{}: {}", context, msg)),
+        other => Error::Transaction(format!("{}: {}", context, other)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::{ErrorCode, HasErrorCode};
     use apex_sdk_types::{Address, Chain};
 
     #[test]
@@ -602,6 +1157,8 @@ mod tests {
             None,
             #[cfg(feature = "revive")]
             None,
+            #[cfg(feature = "evm")]
+            None,
             Duration::from_secs(30),
             SdkConfig::default(),
         );
@@ -637,7 +1194,12 @@ mod tests {
             substrate_wallet: None,
             #[cfg(feature = "revive")]
             revive_adapter: None,
+            #[cfg(feature = "evm")]
+            evm_adapter: None,
+            #[cfg(feature = "substrate")]
+            spending_limiter: None,
             timeout: Duration::from_secs(30),
+            journal: None,
         };
 
         assert!(!sdk.is_chain_supported(&Chain::Polkadot));
@@ -786,11 +1348,17 @@ mod tests {
             substrate_wallet: None,
             #[cfg(feature = "revive")]
             revive_adapter: None,
+            #[cfg(feature = "evm")]
+            evm_adapter: None,
+            #[cfg(feature = "substrate")]
+            spending_limiter: None,
             timeout: Duration::from_secs(30),
+            journal: None,
         };
 
         let rt = tokio::runtime::Runtime::new().unwrap();
-        let result = rt.block_on(sdk.get_transaction_status("0x123", &Chain::Polkadot));
+        let tx_hash = format!("0x{}", "12".repeat(32));
+        let result = rt.block_on(sdk.get_transaction_status(&tx_hash, &Chain::Polkadot));
         assert!(result.is_err());
         if let Err(Error::UnsupportedChain(msg)) = result {
             assert!(msg.contains("Substrate adapter not configured"));
@@ -809,7 +1377,12 @@ mod tests {
             substrate_wallet: None,
             #[cfg(feature = "revive")]
             revive_adapter: None,
+            #[cfg(feature = "evm")]
+            evm_adapter: None,
+            #[cfg(feature = "substrate")]
+            spending_limiter: None,
             timeout: Duration::from_secs(30),
+            journal: None,
         };
 
         let from_addr = Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbD".to_string());
@@ -827,6 +1400,184 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_execute_journals_and_completes_intent() {
+        let journal = Arc::new(IntentJournal::new());
+        let sdk = ApexSDK::without_adapters().with_intent_journal(journal.clone());
+        let transfer = || {
+            Transaction::builder()
+                .from(Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbD"))
+                .to(Address::evm("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"))
+                .amount(100)
+                .build()
+                .unwrap()
+        };
+
+        // The intent is removed once execute returns a final result
+        assert!(sdk.execute(transfer()).await.is_err());
+        assert!(journal.is_empty());
+
+        // Without adapters, a nonce-less intent cannot be reconciled
+        journal.record(&transfer(), None).unwrap();
+        let resumed = sdk.resume_pending().await.unwrap();
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(
+            resumed[0].outcome,
+            crate::journal::IntentOutcome::Unresolved
+        );
+        assert_eq!(journal.len(), 1);
+    }
+
+    #[cfg(feature = "evm")]
+    #[tokio::test]
+    async fn test_execute_logs_redact_addresses() {
+        use alloy::primitives::U64;
+        use alloy::providers::ProviderBuilder;
+        use alloy::transports::mock::Asserter;
+        use apex_sdk_core::privacy::{PrivacyMode, Redactor};
+        use apex_sdk_metrics::RedactingFields;
+        use std::io;
+        use std::sync::Mutex;
+
+        #[derive(Clone, Default)]
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+        impl io::Write for Buffer {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        const FROM: &str = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEbD";
+        const TO: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(1));
+        let mut sdk = ApexSDK::without_adapters();
+        sdk.evm_adapter = Some(Arc::new(EvmAdapter::from_provider(provider).await.unwrap()));
+
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .fmt_fields(
+                RedactingFields::default().with_redactor(Redactor::new(PrivacyMode::Truncate)),
+            )
+            .without_time()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let transaction = Transaction::builder()
+            .from(Address::evm(FROM))
+            .to(Address::evm(TO))
+            .amount(100)
+            .chain(Chain::Ethereum)
+            .build()
+            .unwrap();
+        // No EVM wallet is configured, so execution stops after logging
+        assert!(matches!(
+            sdk.execute(transaction).await,
+            Err(Error::Config(_))
+        ));
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("Executing EVM transaction"), "{}", output);
+        assert!(output.contains("from=0x742d…bEbD"), "{}", output);
+        assert!(!output.contains(FROM), "{}", output);
+        assert!(!output.contains(TO), "{}", output);
+    }
+
+    #[test]
+    fn test_execute_unsupported_route() {
+        let sdk = ApexSDK {
+            config: SdkConfig::default(),
+            #[cfg(feature = "substrate")]
+            substrate_adapter: None,
+            #[cfg(feature = "substrate")]
+            substrate_wallet: None,
+            #[cfg(feature = "revive")]
+            revive_adapter: None,
+            #[cfg(feature = "evm")]
+            evm_adapter: None,
+            #[cfg(feature = "substrate")]
+            spending_limiter: None,
+            timeout: Duration::from_secs(30),
+            journal: None,
+        };
+
+        // Alice on Polkadot sending to herself on Kusama
+        let transaction = Transaction::builder()
+            .from_address("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5")
+            .to_address("HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F")
+            .source_chain(Chain::Polkadot)
+            .amount(100)
+            .build()
+            .expect("Failed to build test transaction");
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let result = rt.block_on(sdk.execute(transaction));
+        assert!(matches!(result, Err(Error::UnsupportedRoute(_))));
+    }
+
+    #[cfg(feature = "substrate")]
+    #[test]
+    fn test_xcm_hop_between_chains() {
+        assert_eq!(
+            XcmHop::between(&Chain::Polkadot, &Chain::Acala),
+            Some(XcmHop::ToParachain(2000))
+        );
+        assert_eq!(
+            XcmHop::between(&Chain::Moonbeam, &Chain::Polkadot),
+            Some(XcmHop::ToRelay)
+        );
+        assert_eq!(
+            XcmHop::between(&Chain::Astar, &Chain::Moonbeam),
+            Some(XcmHop::ToParachain(2004))
+        );
+        assert_eq!(XcmHop::between(&Chain::Polkadot, &Chain::Kusama), None);
+        assert_eq!(XcmHop::between(&Chain::Acala, &Chain::Ethereum), None);
+    }
+
+    #[cfg(feature = "substrate")]
+    #[test]
+    fn test_spending_limit_refusal_stays_distinct() {
+        let refusal = apex_sdk_substrate::SpendingLimitExceeded {
+            account: "5Grw".to_string(),
+            fee: 30,
+            spent: 80,
+            limit: 100,
+            window: None,
+        };
+        let error = substrate_error(
+            "Substrate transaction failed",
+            apex_sdk_substrate::Error::SpendingLimitExceeded(Box::new(refusal)),
+        );
+        assert!(matches!(
+            &error,
+            Error::SpendingLimitExceeded {
+                fee: 30,
+                spent: 80,
+                limit: 100,
+                ..
+            }
+        ));
+        assert_eq!(error.code(), ErrorCode::new(1010));
+        assert_eq!(
+            error.to_string(),
+            "Fee 30 for 5Grw would exceed budget 100 (80 already spent)"
+        );
+    }
+
     #[test]
     fn test_chain_defaults() {
         let polkadot = Chain::Polkadot;
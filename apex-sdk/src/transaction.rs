@@ -1,8 +1,8 @@
 //! Transaction types and builders for the Apex SDK.
 
 use crate::{
-    error::Result,
-    types::{Address, Chain},
+    error::{Error, Result},
+    types::{Address, Chain, ChainRegistry, ChainType, IntoAddress, ValidationError},
 };
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
@@ -13,12 +13,16 @@ pub struct TransactionBuilder {
     from: Option<Address>,
     to: Option<Address>,
     amount: Option<u128>,
+    amount_input: Option<String>,
+    registry: Option<ChainRegistry>,
     gas_limit: Option<u64>,
     gas_price: Option<u64>,
     data: Option<Vec<u8>>,
     chain: Option<Chain>,
+    source_chain: Option<Chain>,
     salt: Option<[u8; 32]>,
     is_deploy: bool,
+    address_error: Option<ValidationError>,
 }
 
 impl TransactionBuilder {
@@ -33,12 +37,32 @@ impl TransactionBuilder {
         self
     }
 
+    /// Set the sender address from any address input
+    ///
+    /// The input is validated and normalized; an invalid address is reported
+    /// by [`build`](Self::build).
+    pub fn from_address(mut self, address: impl IntoAddress) -> Self {
+        match address.into_address() {
+            Ok(address) => self.from = Some(address),
+            Err(e) => self.address_error = Some(e),
+        }
+        self
+    }
+
     /// Set the sender address from an EVM address string
+    #[deprecated(
+        since = "0.1.7",
+        note = "use `from_address`, which validates the input"
+    )]
     pub fn from_evm_address(self, address: &str) -> Self {
         self.from(Address::evm(address))
     }
 
     /// Set the sender address from a Substrate account string
+    #[deprecated(
+        since = "0.1.7",
+        note = "use `from_address`, which validates the input"
+    )]
     pub fn from_substrate_account(self, address: &str) -> Self {
         self.from(Address::substrate(address))
     }
@@ -49,12 +73,26 @@ impl TransactionBuilder {
         self
     }
 
+    /// Set the recipient address from any address input
+    ///
+    /// The input is validated and normalized; an invalid address is reported
+    /// by [`build`](Self::build).
+    pub fn to_address(mut self, address: impl IntoAddress) -> Self {
+        match address.into_address() {
+            Ok(address) => self.to = Some(address),
+            Err(e) => self.address_error = Some(e),
+        }
+        self
+    }
+
     /// Set the recipient address from an EVM address string
+    #[deprecated(since = "0.1.7", note = "use `to_address`, which validates the input")]
     pub fn to_evm_address(self, address: &str) -> Self {
         self.to(Address::evm(address))
     }
 
     /// Set the recipient address from a Substrate account string
+    #[deprecated(since = "0.1.7", note = "use `to_address`, which validates the input")]
     pub fn to_substrate_account(self, address: &str) -> Self {
         self.to(Address::substrate(address))
     }
@@ -62,6 +100,30 @@ impl TransactionBuilder {
     /// Set the transfer amount
     pub fn amount(mut self, amount: u128) -> Self {
         self.amount = Some(amount);
+        self.amount_input = None;
+        self
+    }
+
+    /// Set the transfer amount from a human amount such as `1.5 DOT` or
+    /// `0.01 WND`
+    ///
+    /// The amount is converted with the decimals of the native token of the
+    /// chain it is spent on, taken from the [`ChainRegistry`]: the source
+    /// chain, the sender's network, the target chain or the recipient's
+    /// network, whichever is known first. Without any of them the unit suffix
+    /// selects the token. A unit that is not the chain's token, or more
+    /// decimal places than the token has, is reported by
+    /// [`build`](Self::build).
+    pub fn amount_str(mut self, amount: impl Into<String>) -> Self {
+        self.amount_input = Some(amount.into());
+        self.amount = None;
+        self
+    }
+
+    /// Look up token decimals for [`amount_str`](Self::amount_str) in
+    /// `registry` instead of [`ChainRegistry::new`]
+    pub fn chain_registry(mut self, registry: ChainRegistry) -> Self {
+        self.registry = Some(registry);
         self
     }
 
@@ -111,8 +173,21 @@ impl TransactionBuilder {
         self
     }
 
+    /// Set the chain the transaction is submitted on
+    ///
+    /// Only needed for cross-chain transfers; defaults to the target chain
+    /// otherwise.
+    pub fn source_chain(mut self, chain: Chain) -> Self {
+        self.source_chain = Some(chain);
+        self
+    }
+
     /// Build the transaction
     pub fn build(self) -> Result<Transaction> {
+        if let Some(e) = self.address_error {
+            return Err(e.into());
+        }
+
         let from = self
             .from
             .ok_or_else(|| crate::error::Error::Config("From address is required".to_string()))?;
@@ -125,7 +200,30 @@ impl TransactionBuilder {
                 .ok_or_else(|| crate::error::Error::Config("To address is required".to_string()))?
         };
 
-        let amount = self.amount.unwrap_or(0);
+        let amount = match &self.amount_input {
+            Some(input) => {
+                // The chain the amount is spent on, as in
+                // `Transaction::source_chain`, falling back to the network
+                // the sender address is encoded for instead of Polkadot
+                let chain = self
+                    .source_chain
+                    .clone()
+                    .or_else(|| self.chain.clone())
+                    .or_else(|| Transaction::address_network(&to))
+                    .or_else(|| Transaction::address_network(&from));
+                parse_amount(input, chain.as_ref(), &self.registry.unwrap_or_default())?
+            }
+            None => self.amount.unwrap_or(0),
+        };
+
+        if let Some(chain) = &self.source_chain {
+            check_address_on_chain("Sender", &from, chain)?;
+        }
+        if let Some(chain) = &self.chain {
+            if !self.is_deploy {
+                check_address_on_chain("Recipient", &to, chain)?;
+            }
+        }
 
         Ok(Transaction {
             from,
@@ -135,6 +233,7 @@ impl TransactionBuilder {
             gas_price: self.gas_price,
             data: self.data,
             chain: self.chain,
+            source_chain: self.source_chain,
             nonce: None,
             salt: self.salt,
             is_deploy: self.is_deploy,
@@ -142,6 +241,57 @@ impl TransactionBuilder {
     }
 }
 
+/// Convert a human amount to raw units of `chain`'s native token, or of the
+/// token named by its unit if the chain is unknown
+fn parse_amount(input: &str, chain: Option<&Chain>, registry: &ChainRegistry) -> Result<u128> {
+    let amount = match chain {
+        Some(chain) => registry
+            .for_chain(chain)
+            .ok_or_else(|| {
+                Error::InvalidAmount(format!(
+                    "{} (no token decimals registered for {})",
+                    input,
+                    chain.name()
+                ))
+            })?
+            .parse_amount(input)?,
+        None => registry.parse_amount(input)?,
+    };
+    Ok(amount)
+}
+
+/// Reject addresses that cannot exist on `chain`
+///
+/// Substrate addresses cannot receive on pure EVM chains, and an address
+/// encoded for a specific network belongs to that network only. EVM
+/// addresses are accepted everywhere, since Substrate chains map them for
+/// pallet-revive.
+fn check_address_on_chain(role: &str, address: &Address, chain: &Chain) -> Result<()> {
+    if !address.is_substrate() {
+        return Ok(());
+    }
+
+    if chain.chain_type() == ChainType::Evm {
+        return Err(Error::InvalidAddress(format!(
+            "{} {} is a Substrate address, but {} is an EVM chain",
+            role,
+            address,
+            chain.name()
+        )));
+    }
+
+    match address.ss58_prefix().and_then(Chain::from_ss58_prefix) {
+        Some(network) if network != *chain => Err(Error::InvalidAddress(format!(
+            "{} {} is a {} address, but the transaction targets {}",
+            role,
+            address,
+            network.name(),
+            chain.name()
+        ))),
+        _ => Ok(()),
+    }
+}
+
 /// Represents a blockchain transaction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
@@ -152,6 +302,9 @@ pub struct Transaction {
     pub gas_price: Option<u64>,
     pub data: Option<Vec<u8>>,
     pub chain: Option<Chain>,
+    /// Chain the transaction is submitted on, when it differs from `chain`
+    #[serde(default)]
+    pub source_chain: Option<Chain>,
     pub nonce: Option<u64>,
     pub salt: Option<[u8; 32]>,
     pub is_deploy: bool,
@@ -164,31 +317,42 @@ impl Transaction {
     }
 
     /// Get the destination chain for this transaction
+    ///
+    /// Uses the target chain when set, then the network the recipient
+    /// address is encoded for, and defaults to Polkadot.
     pub fn destination_chain(&self) -> Chain {
-        self.chain.as_ref().unwrap_or(&Chain::Polkadot).clone()
+        self.chain
+            .clone()
+            .or_else(|| Self::address_network(&self.to))
+            .unwrap_or(Chain::Polkadot)
+    }
+
+    /// Get the chain this transaction is submitted on
+    ///
+    /// Uses the source chain when set and defaults to the destination chain.
+    /// The sender address format is not a hint: an account keeps its
+    /// encoding on every chain it uses.
+    pub fn source_chain(&self) -> Chain {
+        self.source_chain
+            .clone()
+            .unwrap_or_else(|| self.destination_chain())
     }
 
     /// Check if this is a cross-chain transaction
+    ///
+    /// Only a source chain set explicitly and different from the
+    /// destination makes a transaction cross-chain.
     pub fn is_cross_chain(&self) -> bool {
-        // Check if from and to addresses indicate different chain types
-        match (&self.from, &self.to) {
-            (Address::Substrate(_), Address::Evm(_)) => true,
-            (Address::Evm(_), Address::Substrate(_)) => true,
-            (Address::Substrate(from_addr), Address::Substrate(to_addr)) => {
-                !Self::same_substrate_network(from_addr, to_addr)
-            }
-            (Address::Evm(_), Address::Evm(_)) => false,
-        }
+        self.source_chain
+            .as_ref()
+            .is_some_and(|source| *source != self.destination_chain())
     }
 
-    /// Check if two Substrate addresses are on the same network
-    fn same_substrate_network(addr1: &str, addr2: &str) -> bool {
-        use apex_sdk_types::extract_ss58_prefix;
-
-        match (extract_ss58_prefix(addr1), extract_ss58_prefix(addr2)) {
-            (Some(prefix1), Some(prefix2)) => prefix1 == prefix2,
-            _ => true, // If we can't determine, assume same network
-        }
+    /// Network a Substrate address is encoded for
+    ///
+    /// `None` for EVM addresses and the generic format shared by testnets.
+    fn address_network(address: &Address) -> Option<Chain> {
+        address.ss58_prefix().and_then(Chain::from_ss58_prefix)
     }
 
     /// Calculate transaction hash
@@ -251,6 +415,38 @@ impl TransactionResult {
     }
 }
 
+/// Result of a transfer between two chains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossChainResult {
+    /// Chain the transfer was submitted on
+    pub source_chain: Chain,
+    /// Chain receiving the transfer
+    pub destination_chain: Chain,
+    /// Transaction hash on the source chain
+    pub source_tx_hash: String,
+    /// Transaction hash on the destination chain
+    ///
+    /// `None` for XCM transfers, which the destination executes as an
+    /// incoming message rather than a transaction.
+    pub destination_tx_hash: Option<String>,
+    /// Hex-encoded ID of the cross-chain message, used to track delivery
+    pub message_id: Option<String>,
+    /// Status of the transfer on the source chain
+    pub status: TransactionStatus,
+}
+
+impl From<CrossChainResult> for TransactionResult {
+    fn from(result: CrossChainResult) -> Self {
+        Self {
+            source_tx_hash: result.source_tx_hash,
+            destination_tx_hash: result.destination_tx_hash,
+            status: result.status,
+            block_number: None,
+            gas_used: None,
+        }
+    }
+}
+
 /// Transaction status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TransactionStatus {
@@ -289,6 +485,33 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_transaction_builder_normalizes_addresses() {
+        let tx = Transaction::builder()
+            .from_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .to_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            tx.to,
+            Address::evm("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")
+        );
+    }
+
+    #[test]
+    fn test_transaction_builder_rejects_invalid_address() {
+        let result = Transaction::builder()
+            .from_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .to_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD")
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidAddress(_))
+        ));
+    }
+
     #[test]
     fn test_transaction_builder_missing_to() {
         let result = Transaction::builder()
@@ -310,6 +533,72 @@ mod tests {
         assert_eq!(result.unwrap().amount, 0);
     }
 
+    #[test]
+    fn test_transaction_builder_amount_str() {
+        let tx = Transaction::builder()
+            .from_address(ALICE_POLKADOT)
+            .to_address(ALICE_POLKADOT)
+            .amount_str("1.5 DOT")
+            .build()
+            .unwrap();
+        assert_eq!(tx.amount, 15_000_000_000);
+
+        // Decimals follow the chain, not just the symbol
+        let tx = Transaction::builder()
+            .from_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .to_address("5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty")
+            .chain(Chain::Westend)
+            .amount_str("0.01")
+            .build()
+            .unwrap();
+        assert_eq!(tx.amount, 10_000_000_000);
+
+        let tx = Transaction::builder()
+            .from(Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"))
+            .to(Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"))
+            .amount_str("0.01 WND")
+            .build()
+            .unwrap();
+        assert_eq!(tx.amount, 10_000_000_000);
+
+        for (chain, amount) in [
+            (Chain::Polkadot, "1 KSM"),
+            (Chain::Polkadot, "0.00000000001 DOT"),
+            (Chain::Polkadot, "one DOT"),
+        ] {
+            let result = Transaction::builder()
+                .from_address(ALICE_POLKADOT)
+                .to_address(ALICE_POLKADOT)
+                .chain(chain)
+                .amount_str(amount)
+                .build();
+            assert!(matches!(result, Err(crate::error::Error::InvalidAmount(_))));
+        }
+
+        // A raw amount set later replaces the human one
+        let tx = Transaction::builder()
+            .from(Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"))
+            .to(Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"))
+            .amount_str("1")
+            .amount(7)
+            .build()
+            .unwrap();
+        assert_eq!(tx.amount, 7);
+
+        let registry = ChainRegistry::new().with_chain(
+            crate::types::ChainInfo::new("local", "Local", ChainType::Substrate)
+                .with_token("DEV", 18),
+        );
+        let tx = Transaction::builder()
+            .from(Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"))
+            .to(Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"))
+            .chain_registry(registry)
+            .amount_str("2 DEV")
+            .build()
+            .unwrap();
+        assert_eq!(tx.amount, 2_000_000_000_000_000_000);
+    }
+
     #[test]
     fn test_transaction_clone() {
         let tx = Transaction::builder()
@@ -361,6 +650,92 @@ mod tests {
         assert!(!tx.is_cross_chain()); // Same chain type should return false
     }
 
+    // Alice encoded for Polkadot and for Kusama
+    const ALICE_POLKADOT: &str = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+    const ALICE_KUSAMA: &str = "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F";
+
+    #[test]
+    fn test_sender_address_format_does_not_make_cross_chain() {
+        let tx = Transaction::builder()
+            .from_address(ALICE_POLKADOT)
+            .to_address(ALICE_KUSAMA)
+            .amount(1000)
+            .build()
+            .unwrap();
+
+        assert_eq!(tx.source_chain(), Chain::Kusama);
+        assert_eq!(tx.destination_chain(), Chain::Kusama);
+        assert!(!tx.is_cross_chain());
+
+        // A Polkadot-format sender transferring locally on a parachain
+        let tx = Transaction::builder()
+            .from_address(ALICE_POLKADOT)
+            .to_address("5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty")
+            .chain(Chain::Acala)
+            .amount(1000)
+            .build()
+            .unwrap();
+        assert_eq!(tx.source_chain(), Chain::Acala);
+        assert!(!tx.is_cross_chain());
+
+        // Generic addresses and mapped EVM accounts stay on the target chain
+        let tx = Transaction::builder()
+            .from_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .to_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")
+            .chain(Chain::Moonbeam)
+            .build()
+            .unwrap();
+        assert!(!tx.is_cross_chain());
+    }
+
+    #[test]
+    fn test_cross_chain_with_explicit_source() {
+        let tx = Transaction::builder()
+            .from_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .to_address("5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty")
+            .source_chain(Chain::Polkadot)
+            .chain(Chain::Acala)
+            .build()
+            .unwrap();
+
+        assert_eq!(tx.source_chain(), Chain::Polkadot);
+        assert_eq!(tx.destination_chain(), Chain::Acala);
+        assert!(tx.is_cross_chain());
+    }
+
+    #[test]
+    fn test_builder_rejects_address_on_other_chain() {
+        let result = Transaction::builder()
+            .from_address(ALICE_POLKADOT)
+            .to_address(ALICE_KUSAMA)
+            .chain(Chain::Polkadot)
+            .build();
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidAddress(_))
+        ));
+
+        let result = Transaction::builder()
+            .from_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")
+            .to_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .chain(Chain::Ethereum)
+            .build();
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidAddress(_))
+        ));
+
+        let result = Transaction::builder()
+            .from_address(ALICE_KUSAMA)
+            .to_address(ALICE_POLKADOT)
+            .source_chain(Chain::Polkadot)
+            .build();
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidAddress(_))
+        ));
+    }
+
     #[test]
     fn test_transaction_hash_determinism() {
         let tx1 = Transaction::builder()
@@ -0,0 +1,791 @@
+//! Public API snapshots
+//!
+//! `prelude_v2_matches_snapshot` fails when an item is removed from, renamed
+//! in or retyped in a prelude version. Removals and renames break the
+//! imports below; retyped items and changed signatures show up as a diff
+//! against `tests/snapshots/prelude_v2.txt`. After an intentional change,
+//! add a deprecation shim in `apex_sdk::compat`.
+//!
+//! `public_api_matches_snapshot` renders the crate's whole public surface
+//! from rustdoc JSON: every public item, struct field, enum variant, method
+//! signature and trait implementation, one per line, compared against
+//! `tests/snapshots/public_api.txt`. Additions show up as well as breaking
+//! changes. Rustdoc JSON needs a nightly toolchain, so the test is ignored by
+//! default; run it with
+//! `cargo test -p apex-sdk --test public_api -- --include-ignored`.
+//!
+//! Regenerate both snapshots after an intentional change with
+//! `APEX_UPDATE_SNAPSHOTS=1 cargo test -p apex-sdk --test public_api -- --include-ignored`.
+
+use serde_json::Value;
+use std::any::type_name;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[cfg(all(feature = "substrate", feature = "revive", feature = "evm"))]
+fn name_of<T: ?Sized>(_: &T) -> &'static str {
+    type_name::<T>()
+}
+
+#[cfg(all(feature = "substrate", feature = "revive", feature = "evm"))]
+/// Paths and signatures exported by `prelude::v2`, one per line
+fn prelude_v2_surface() -> Vec<String> {
+    use apex_sdk::prelude::v2::*;
+
+    let mut surface = vec![
+        type_name::<ApexSDK>().to_string(),
+        type_name::<ApexSDKBuilder>().to_string(),
+        type_name::<ConfirmationStrategy>().to_string(),
+        type_name::<SdkConfig>().to_string(),
+        type_name::<Error>().to_string(),
+        type_name::<Result<()>>().to_string(),
+        type_name::<Transaction>().to_string(),
+        type_name::<TransactionBuilder>().to_string(),
+        type_name::<TransactionResult>().to_string(),
+        type_name::<TransactionStatus>().to_string(),
+        type_name::<CrossChainResult>().to_string(),
+        type_name::<Address>().to_string(),
+        type_name::<Chain>().to_string(),
+        type_name::<ChainType>().to_string(),
+        type_name::<BatchExecutionResult>().to_string(),
+        type_name::<ParallelExecutor>().to_string(),
+        type_name::<TransactionBatch>().to_string(),
+        type_name::<TransactionOutcome>().to_string(),
+        type_name::<RetryConfig>().to_string(),
+        type_name::<dyn IntoAddress>().to_string(),
+        type_name::<SubstrateAdapter>().to_string(),
+        type_name::<SubstrateWallet>().to_string(),
+        type_name::<ReviveAdapter>().to_string(),
+        type_name::<EvmAdapter>().to_string(),
+    ];
+
+    // Signatures of the entry points most code depends on
+    let build: fn(TransactionBuilder) -> Result<Transaction> = TransactionBuilder::build;
+    let amount: fn(TransactionBuilder, u128) -> TransactionBuilder = TransactionBuilder::amount;
+    let chain: fn(TransactionBuilder, Chain) -> TransactionBuilder = TransactionBuilder::chain;
+    let is_cross_chain: fn(&Transaction) -> bool = Transaction::is_cross_chain;
+    let new_result: fn(String) -> TransactionResult = TransactionResult::new;
+    let builder: fn() -> ApexSDKBuilder = ApexSDK::builder;
+    let is_success: fn(&TransactionOutcome) -> bool = TransactionOutcome::is_success;
+    let failed_indices: fn(&BatchExecutionResult) -> Vec<usize> =
+        BatchExecutionResult::failed_indices;
+    surface.extend(
+        [
+            name_of(&build),
+            name_of(&amount),
+            name_of(&chain),
+            name_of(&is_cross_chain),
+            name_of(&new_result),
+            name_of(&builder),
+            name_of(&is_success),
+            name_of(&failed_indices),
+        ]
+        .map(str::to_string),
+    );
+
+    surface
+}
+
+#[test]
+fn prelude_v1_is_a_subset_of_v2() {
+    // Anything importable from v1 must stay importable from v2
+    #[allow(unused_imports)]
+    use apex_sdk::prelude::v1::{
+        Address, ApexSDK, ApexSDKBuilder, Chain, ChainType, ConfirmationStrategy, Error, Result,
+        SdkConfig, Transaction, TransactionBuilder, TransactionResult,
+    };
+
+    assert_eq!(
+        type_name::<apex_sdk::prelude::v1::Transaction>(),
+        type_name::<apex_sdk::prelude::v2::Transaction>()
+    );
+    assert_eq!(
+        type_name::<apex_sdk::prelude::Transaction>(),
+        type_name::<apex_sdk::prelude::v1::Transaction>()
+    );
+}
+
+// The snapshot is taken with the default features
+#[cfg(all(feature = "substrate", feature = "revive", feature = "evm"))]
+#[test]
+fn prelude_v2_matches_snapshot() {
+    let mut surface = prelude_v2_surface();
+    surface.sort();
+    let actual = surface.join("\n") + "\n";
+
+    assert_snapshot(
+        "prelude_v2.txt",
+        &actual,
+        "prelude::v2 changed; if intentional, add a shim in apex_sdk::compat and regenerate the snapshot with APEX_UPDATE_SNAPSHOTS=1",
+    );
+}
+
+#[test]
+#[ignore = "needs a nightly toolchain for rustdoc JSON"]
+fn public_api_matches_snapshot() {
+    let krate = rustdoc_json();
+    let actual = PublicApi::new(&krate).render().join("\n") + "\n";
+
+    assert_snapshot(
+        "public_api.txt",
+        &actual,
+        "the public API changed; if intentional, regenerate the snapshot with APEX_UPDATE_SNAPSHOTS=1 and note breaking changes in CHANGELOG.md",
+    );
+}
+
+/// Compare `actual` with a snapshot, or overwrite the snapshot when
+/// `APEX_UPDATE_SNAPSHOTS` is set
+fn assert_snapshot(name: &str, actual: &str, message: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(name);
+    if std::env::var_os("APEX_UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, actual).expect("failed to write snapshot");
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).expect("missing snapshot");
+    if expected != actual {
+        let expected: BTreeSet<&str> = expected.lines().collect();
+        let actual: BTreeSet<&str> = actual.lines().collect();
+        let removed: Vec<_> = expected
+            .difference(&actual)
+            .map(|l| format!("- {}", l))
+            .collect();
+        let added: Vec<_> = actual
+            .difference(&expected)
+            .map(|l| format!("+ {}", l))
+            .collect();
+        panic!("{}\n{}\n{}", message, removed.join("\n"), added.join("\n"));
+    }
+}
+
+/// Rustdoc JSON for this crate, built with the nightly toolchain in a
+/// separate target directory
+fn rustdoc_json() -> Value {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("../target/public-api");
+    let status = Command::new("cargo")
+        .args(["+nightly", "rustdoc", "--lib", "--manifest-path"])
+        .arg(manifest_dir.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .args(["--", "-Z", "unstable-options", "--output-format", "json"])
+        .status()
+        .expect("failed to run cargo +nightly rustdoc");
+    assert!(status.success(), "cargo +nightly rustdoc failed");
+
+    let path: PathBuf = target_dir.join("doc/apex_sdk.json");
+    let json = std::fs::read_to_string(&path).expect("missing rustdoc JSON");
+    serde_json::from_str(&json).expect("invalid rustdoc JSON")
+}
+
+/// Public surface of a crate, rendered from its rustdoc JSON
+struct PublicApi<'a> {
+    index: &'a Value,
+    paths: &'a Value,
+    lines: BTreeSet<String>,
+    rendered: BTreeSet<String>,
+}
+
+impl<'a> PublicApi<'a> {
+    fn new(krate: &'a Value) -> Self {
+        Self {
+            index: &krate["index"],
+            paths: &krate["paths"],
+            lines: BTreeSet::new(),
+            rendered: BTreeSet::new(),
+        }
+    }
+
+    fn render(mut self) -> Vec<String> {
+        let root = self.index[id_key(&self.index_root())].clone();
+        let name = root["name"].as_str().unwrap_or_default().to_string();
+        self.module(&root, &name);
+        self.lines.into_iter().collect()
+    }
+
+    fn index_root(&self) -> Value {
+        self.index
+            .as_object()
+            .and_then(|items| {
+                items
+                    .values()
+                    .find(|item| item["inner"]["module"]["is_crate"] == true)
+            })
+            .map(|item| item["id"].clone())
+            .expect("crate root module")
+    }
+
+    fn item(&self, id: &Value) -> Option<&'a Value> {
+        self.index.get(id_key(id))
+    }
+
+    fn module(&mut self, module: &Value, path: &str) {
+        for id in ids(&module["inner"]["module"]["items"]) {
+            let Some(item) = self.item(&id) else {
+                continue;
+            };
+            if item["visibility"] != "public" {
+                continue;
+            }
+            let name = item["name"].as_str().unwrap_or_default();
+            let (kind, inner) = kind(item);
+            match kind {
+                "module" => {
+                    let path = format!("{}::{}", path, name);
+                    self.lines.insert(format!("pub mod {}", path));
+                    self.module(item, &path);
+                }
+                "use" => {
+                    let name = inner["name"].as_str().unwrap_or_default();
+                    let source = inner["source"].as_str().unwrap_or_default();
+                    if inner["is_glob"] == true {
+                        self.lines
+                            .insert(format!("pub use {}::* = {}::*", path, source));
+                    } else {
+                        self.lines
+                            .insert(format!("pub use {}::{} = {}", path, name, source));
+                    }
+                    // Items re-exported from private modules are only
+                    // reachable through the re-export
+                    if let Some(target) = self.item(&inner["id"]) {
+                        match kind_name(target) {
+                            "module" => self.module(target, path),
+                            _ => self.definition(target, &self.path_of(target, path)),
+                        }
+                    }
+                }
+                _ => self.definition(item, &self.path_of(item, path)),
+            }
+        }
+    }
+
+    /// Canonical path of an item, or `{fallback}::{name}`
+    fn path_of(&self, item: &Value, fallback: &str) -> String {
+        match self.paths[id_key(&item["id"])]["path"].as_array() {
+            Some(path) => path
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join("::"),
+            None => format!(
+                "{}::{}",
+                fallback,
+                item["name"].as_str().unwrap_or_default()
+            ),
+        }
+    }
+
+    fn definition(&mut self, item: &Value, path: &str) {
+        if !self.rendered.insert(id_key(&item["id"])) {
+            return;
+        }
+        let (kind, inner) = kind(item);
+        let attrs = attributes(item);
+        match kind {
+            "struct" => {
+                let generics = self.generics(&inner["generics"]);
+                self.lines
+                    .insert(format!("{}pub struct {}{}", attrs, path, generics));
+                self.fields(&inner["kind"], path, "pub ");
+                self.impls(&inner["impls"], path);
+            }
+            "enum" => {
+                let generics = self.generics(&inner["generics"]);
+                self.lines
+                    .insert(format!("{}pub enum {}{}", attrs, path, generics));
+                for id in ids(&inner["variants"]) {
+                    let Some(variant) = self.item(&id) else {
+                        continue;
+                    };
+                    let name =
+                        format!("{}::{}", path, variant["name"].as_str().unwrap_or_default());
+                    let line = format!("{}{}", attributes(variant), name);
+                    let variant_kind = &variant["inner"]["variant"]["kind"];
+                    match variant_kind.as_str() {
+                        Some("plain") => self.lines.insert(line),
+                        _ => self.lines.insert(format!(
+                            "{}{}",
+                            line,
+                            self.variant_shape(variant_kind)
+                        )),
+                    };
+                    self.fields(variant_kind, &name, "");
+                }
+                self.impls(&inner["impls"], path);
+            }
+            "union" => {
+                self.lines.insert(format!("{}pub union {}", attrs, path));
+                self.impls(&inner["impls"], path);
+            }
+            "trait" => {
+                let generics = self.generics(&inner["generics"]);
+                let bounds = self.bounds(&inner["bounds"]);
+                let bounds = if bounds.is_empty() {
+                    String::new()
+                } else {
+                    format!(": {}", bounds)
+                };
+                self.lines
+                    .insert(format!("{}pub trait {}{}{}", attrs, path, generics, bounds));
+                for id in ids(&inner["items"]) {
+                    if let Some(member) = self.item(&id) {
+                        self.member(member, path);
+                    }
+                }
+            }
+            "function" => {
+                let line = self.function(item, path);
+                self.lines.insert(format!("{}{}", attrs, line));
+            }
+            "constant" => {
+                let ty = self.ty(&inner["type"]);
+                self.lines.insert(format!("pub const {}: {}", path, ty));
+            }
+            "static" => {
+                let ty = self.ty(&inner["type"]);
+                self.lines.insert(format!("pub static {}: {}", path, ty));
+            }
+            "type_alias" => {
+                let generics = self.generics(&inner["generics"]);
+                let ty = self.ty(&inner["type"]);
+                self.lines
+                    .insert(format!("pub type {}{} = {}", path, generics, ty));
+            }
+            "macro" | "proc_macro" => {
+                self.lines.insert(format!("pub macro {}!", path));
+            }
+            _ => {}
+        }
+    }
+
+    /// `(..)` or `{ .. }` after a tuple or struct variant
+    fn variant_shape(&self, kind: &Value) -> String {
+        if kind.get("tuple").is_some() {
+            "(..)".to_string()
+        } else {
+            " { .. }".to_string()
+        }
+    }
+
+    /// Public fields of a struct or variant, tuple fields by position
+    fn fields(&mut self, kind: &Value, path: &str, visibility: &str) {
+        let (fields, stripped) = match (kind.get("plain"), kind.get("struct"), kind.get("tuple")) {
+            (Some(plain), _, _) | (_, Some(plain), _) => {
+                (ids(&plain["fields"]), plain["has_stripped_fields"] == true)
+            }
+            (_, _, Some(tuple)) => {
+                let fields: Vec<Value> = tuple.as_array().cloned().unwrap_or_default();
+                let stripped = fields.iter().any(Value::is_null);
+                (
+                    fields.into_iter().filter(|f| !f.is_null()).collect(),
+                    stripped,
+                )
+            }
+            _ => return,
+        };
+        for id in fields {
+            let Some(field) = self.item(&id) else {
+                continue;
+            };
+            let ty = self.ty(&field["inner"]["struct_field"]);
+            self.lines.insert(format!(
+                "{}{}{}::{}: {}",
+                attributes(field),
+                visibility,
+                path,
+                field["name"].as_str().unwrap_or_default(),
+                ty
+            ));
+        }
+        if stripped {
+            self.lines.insert(format!("{} has private fields", path));
+        }
+    }
+
+    /// Inherent methods and non-blanket trait implementations
+    fn impls(&mut self, impls: &Value, path: &str) {
+        for id in ids(impls) {
+            let Some(item) = self.item(&id) else {
+                continue;
+            };
+            let inner = &item["inner"]["impl"];
+            if !inner["blanket_impl"].is_null() {
+                continue;
+            }
+            if inner["trait"].is_null() {
+                for id in ids(&inner["items"]) {
+                    if let Some(member) = self.item(&id) {
+                        if member["visibility"] == "public" {
+                            self.member(member, path);
+                        }
+                    }
+                }
+            } else {
+                let negative = if inner["is_negative"] == true {
+                    "!"
+                } else {
+                    ""
+                };
+                let unsafety = if inner["is_unsafe"] == true {
+                    "unsafe "
+                } else {
+                    ""
+                };
+                let generics = self.generics(&inner["generics"]);
+                self.lines.insert(format!(
+                    "{}impl{} {}{} for {}",
+                    unsafety,
+                    generics,
+                    negative,
+                    self.path(&inner["trait"]),
+                    self.ty(&inner["for"])
+                ));
+            }
+        }
+    }
+
+    /// Method, associated constant or associated type
+    fn member(&mut self, member: &Value, owner: &str) {
+        let name = member["name"].as_str().unwrap_or_default();
+        let path = format!("{}::{}", owner, name);
+        let (kind, inner) = kind(member);
+        let line = match kind {
+            "function" => self.function(member, &path),
+            "assoc_const" => format!("pub const {}: {}", path, self.ty(&inner["type"])),
+            "assoc_type" => {
+                let bounds = self.bounds(&inner["bounds"]);
+                match bounds.is_empty() {
+                    true => format!("pub type {}", path),
+                    false => format!("pub type {}: {}", path, bounds),
+                }
+            }
+            _ => return,
+        };
+        self.lines.insert(format!("{}{}", attributes(member), line));
+    }
+
+    fn function(&self, item: &Value, path: &str) -> String {
+        let inner = &item["inner"]["function"];
+        let header = &inner["header"];
+        let mut qualifiers = String::new();
+        for (flag, keyword) in [
+            ("is_const", "const "),
+            ("is_async", "async "),
+            ("is_unsafe", "unsafe "),
+        ] {
+            if header[flag] == true {
+                qualifiers.push_str(keyword);
+            }
+        }
+        let generics = self.generics(&inner["generics"]);
+        let inputs: Vec<String> = inner["sig"]["inputs"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|input| self.input(&input[0], &input[1]))
+            .collect();
+        let output = match &inner["sig"]["output"] {
+            Value::Null => String::new(),
+            ty => format!(" -> {}", self.ty(ty)),
+        };
+        format!(
+            "pub {}fn {}{}({}){}{}",
+            qualifiers,
+            path,
+            generics,
+            inputs.join(", "),
+            output,
+            self.where_clause(&inner["generics"])
+        )
+    }
+
+    fn input(&self, name: &Value, ty: &Value) -> String {
+        let name = name.as_str().unwrap_or("_");
+        if name == "self" {
+            if ty["generic"] == "Self" {
+                return "self".to_string();
+            }
+            if let Some(reference) = ty.get("borrowed_ref") {
+                if reference["type"]["generic"] == "Self" {
+                    let mutability = if reference["is_mutable"] == true {
+                        "mut "
+                    } else {
+                        ""
+                    };
+                    return format!("&{}self", mutability);
+                }
+            }
+        }
+        format!("{}: {}", name, self.ty(ty))
+    }
+
+    /// `<T: Bound, 'a>`, leaving out the parameters of `impl Trait` arguments
+    fn generics(&self, generics: &Value) -> String {
+        let params: Vec<String> = generics["params"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|param| {
+                let name = param["name"].as_str()?;
+                let kind = &param["kind"];
+                if let Some(ty) = kind.get("type") {
+                    if ty["is_synthetic"] == true {
+                        return None;
+                    }
+                    let bounds = self.bounds(&ty["bounds"]);
+                    return Some(match bounds.is_empty() {
+                        true => name.to_string(),
+                        false => format!("{}: {}", name, bounds),
+                    });
+                }
+                if let Some(constant) = kind.get("const") {
+                    return Some(format!("const {}: {}", name, self.ty(&constant["type"])));
+                }
+                Some(name.to_string())
+            })
+            .collect();
+        match params.is_empty() {
+            true => String::new(),
+            false => format!("<{}>", params.join(", ")),
+        }
+    }
+
+    fn where_clause(&self, generics: &Value) -> String {
+        let predicates: Vec<String> = generics["where_predicates"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|predicate| {
+                let bound = predicate.get("bound_predicate")?;
+                Some(format!(
+                    "{}: {}",
+                    self.ty(&bound["type"]),
+                    self.bounds(&bound["bounds"])
+                ))
+            })
+            .collect();
+        match predicates.is_empty() {
+            true => String::new(),
+            false => format!(" where {}", predicates.join(", ")),
+        }
+    }
+
+    fn bounds(&self, bounds: &Value) -> String {
+        bounds
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|bound| {
+                if let Some(bound) = bound.get("trait_bound") {
+                    let maybe = if bound["modifier"] == "maybe" {
+                        "?"
+                    } else {
+                        ""
+                    };
+                    return Some(format!("{}{}", maybe, self.path(&bound["trait"])));
+                }
+                bound
+                    .get("outlives")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            })
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+
+    /// Canonical path of a resolved path, with its generic arguments
+    fn path(&self, path: &Value) -> String {
+        let name = match self.paths[id_key(&path["id"])]["path"].as_array() {
+            Some(segments) => segments
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join("::"),
+            None => path["path"].as_str().unwrap_or_default().to_string(),
+        };
+        format!("{}{}", name, self.args(&path["args"]))
+    }
+
+    fn args(&self, args: &Value) -> String {
+        if let Some(angle) = args.get("angle_bracketed") {
+            let mut rendered: Vec<String> = angle["args"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(
+                    |arg| match (arg.get("type"), arg.get("lifetime"), arg.get("const")) {
+                        (Some(ty), _, _) => self.ty(ty),
+                        (_, Some(lifetime), _) => lifetime.as_str().unwrap_or_default().to_string(),
+                        (_, _, Some(constant)) => {
+                            constant["expr"].as_str().unwrap_or("_").to_string()
+                        }
+                        _ => "_".to_string(),
+                    },
+                )
+                .collect();
+            for constraint in angle["constraints"].as_array().into_iter().flatten() {
+                let name = constraint["name"].as_str().unwrap_or_default();
+                let binding = &constraint["binding"];
+                match binding.get("equality") {
+                    Some(equality) => {
+                        rendered.push(format!("{} = {}", name, self.ty(&equality["type"])))
+                    }
+                    None => {
+                        rendered.push(format!("{}: {}", name, self.bounds(&binding["constraint"])))
+                    }
+                }
+            }
+            return match rendered.is_empty() {
+                true => String::new(),
+                false => format!("<{}>", rendered.join(", ")),
+            };
+        }
+        if let Some(parenthesized) = args.get("parenthesized") {
+            let inputs: Vec<String> = parenthesized["inputs"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|ty| self.ty(ty))
+                .collect();
+            let output = match &parenthesized["output"] {
+                Value::Null => String::new(),
+                ty => format!(" -> {}", self.ty(ty)),
+            };
+            return format!("({}){}", inputs.join(", "), output);
+        }
+        String::new()
+    }
+
+    fn ty(&self, ty: &Value) -> String {
+        let Some((kind, inner)) = ty.as_object().and_then(|ty| ty.iter().next()) else {
+            return "_".to_string();
+        };
+        match kind.as_str() {
+            "resolved_path" => self.path(inner),
+            "generic" | "primitive" => inner.as_str().unwrap_or_default().to_string(),
+            "borrowed_ref" => {
+                let lifetime = inner["lifetime"]
+                    .as_str()
+                    .map(|l| format!("{} ", l))
+                    .unwrap_or_default();
+                let mutability = if inner["is_mutable"] == true {
+                    "mut "
+                } else {
+                    ""
+                };
+                format!("&{}{}{}", lifetime, mutability, self.ty(&inner["type"]))
+            }
+            "raw_pointer" => {
+                let mutability = if inner["is_mutable"] == true {
+                    "mut"
+                } else {
+                    "const"
+                };
+                format!("*{} {}", mutability, self.ty(&inner["type"]))
+            }
+            "slice" => format!("[{}]", self.ty(inner)),
+            "array" => format!(
+                "[{}; {}]",
+                self.ty(&inner["type"]),
+                inner["len"].as_str().unwrap_or("_")
+            ),
+            "tuple" => {
+                let types: Vec<String> = inner
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|ty| self.ty(ty))
+                    .collect();
+                match types.len() {
+                    1 => format!("({},)", types[0]),
+                    _ => format!("({})", types.join(", ")),
+                }
+            }
+            "impl_trait" => format!("impl {}", self.bounds(inner)),
+            "dyn_trait" => {
+                let mut traits: Vec<String> = inner["traits"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|bound| self.path(&bound["trait"]))
+                    .collect();
+                if let Some(lifetime) = inner["lifetime"].as_str() {
+                    traits.push(lifetime.to_string());
+                }
+                format!("dyn {}", traits.join(" + "))
+            }
+            "function_pointer" => {
+                let inputs: Vec<String> = inner["sig"]["inputs"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|input| self.ty(&input[1]))
+                    .collect();
+                let output = match &inner["sig"]["output"] {
+                    Value::Null => String::new(),
+                    ty => format!(" -> {}", self.ty(ty)),
+                };
+                format!("fn({}){}", inputs.join(", "), output)
+            }
+            "qualified_path" => {
+                let self_type = self.ty(&inner["self_type"]);
+                let name = inner["name"].as_str().unwrap_or_default();
+                match inner["trait"].is_null() {
+                    true => format!("{}::{}", self_type, name),
+                    false => format!(
+                        "<{} as {}>::{}",
+                        self_type,
+                        self.path(&inner["trait"]),
+                        name
+                    ),
+                }
+            }
+            "infer" => "_".to_string(),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// Kind of an index item and its details, e.g. `("struct", {..})`
+fn kind(item: &Value) -> (&str, &Value) {
+    item["inner"]
+        .as_object()
+        .and_then(|inner| inner.iter().next())
+        .map(|(kind, inner)| (kind.as_str(), inner))
+        .unwrap_or(("", &Value::Null))
+}
+
+fn kind_name(item: &Value) -> &str {
+    kind(item).0
+}
+
+/// Attributes that change how an item may be used, as a line prefix
+fn attributes(item: &Value) -> String {
+    let mut prefix = String::new();
+    let non_exhaustive = item["attrs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|attr| attr.to_string().contains("non_exhaustive"));
+    if non_exhaustive {
+        prefix.push_str("#[non_exhaustive] ");
+    }
+    if !item["deprecation"].is_null() {
+        prefix.push_str("#[deprecated] ");
+    }
+    prefix
+}
+
+fn ids(value: &Value) -> Vec<Value> {
+    value.as_array().cloned().unwrap_or_default()
+}
+
+fn id_key(id: &Value) -> String {
+    match id {
+        Value::String(id) => id.clone(),
+        id => id.to_string(),
+    }
+}
@@ -6,6 +6,7 @@ use sp_core::{crypto::Ss58Codec, sr25519, Pair};
 use std::io::Write;
 
 use crate::keystore::{AccountType, Keystore};
+use crate::messages::{t, tf};
 
 /// Generate a new account
 pub fn generate_account(account_type: &str, name: Option<String>) -> Result<()> {
@@ -159,18 +160,18 @@ fn save_account_interactive(
     println!("\n{}", "Saving Account to Keystore".cyan().bold());
     println!("{}", "═══════════════════════════════════════".dimmed());
 
-    let password = rpassword::prompt_password("Enter password to encrypt account: ")
-        .context("Failed to read password")?;
+    let password = rpassword::prompt_password(t("prompt.new_account_password"))
+        .context(t("error.read_password"))?;
 
     if password.len() < 8 {
-        anyhow::bail!("Password must be at least 8 characters long");
+        anyhow::bail!(t("error.password_too_short"));
     }
 
-    let password_confirm = rpassword::prompt_password("Confirm password: ")
-        .context("Failed to read password confirmation")?;
+    let password_confirm = rpassword::prompt_password(t("prompt.confirm_password"))
+        .context(t("error.read_password"))?;
 
     if password != password_confirm {
-        anyhow::bail!("Passwords do not match");
+        anyhow::bail!(t("error.password_mismatch"));
     }
 
     let keystore_path = crate::keystore::get_keystore_path()?;
@@ -241,7 +242,7 @@ pub fn export_account(name: &str) -> Result<()> {
     let mut keystore = Keystore::load(&keystore_path)?;
 
     if !keystore.has_account(name) {
-        anyhow::bail!("Account '{}' not found", name);
+        anyhow::bail!(tf("account.not_found", &[("name", &name)]));
     }
 
     println!("\n{}", "Export Account".yellow().bold());
@@ -252,7 +253,7 @@ pub fn export_account(name: &str) -> Result<()> {
     );
 
     let password =
-        rpassword::prompt_password("Enter password: ").context("Failed to read password")?;
+        rpassword::prompt_password(t("prompt.password")).context(t("error.read_password"))?;
 
     let mnemonic_bytes = keystore.get_account(name, &password)?;
     let mnemonic = String::from_utf8(mnemonic_bytes).context("Failed to decode mnemonic")?;
@@ -272,7 +273,7 @@ pub fn remove_account(name: &str) -> Result<()> {
     let mut keystore = Keystore::load(&keystore_path)?;
 
     if !keystore.has_account(name) {
-        anyhow::bail!("Account '{}' not found", name);
+        anyhow::bail!(tf("account.not_found", &[("name", &name)]));
     }
 
     println!("\n{}", "Remove Account".red().bold());
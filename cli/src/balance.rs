@@ -1,97 +1,71 @@
 //! Balance checking functionality for Substrate and Revive chains
 
 use anyhow::{Context, Result};
+use apex_sdk_substrate::{ChainConfig, SubstrateAdapter};
+use apex_sdk_types::{ChainProperties, ChainRegistry, ChainType};
 use colored::Colorize;
-use subxt::ext::scale_value::At;
 
 /// Get account balance for Substrate chains
-pub async fn get_substrate_balance(address: &str, endpoint: &str) -> Result<()> {
-    use subxt::{OnlineClient, PolkadotConfig};
-
+///
+/// The token symbol and decimals come from the node's `system_properties`,
+/// falling back to the chain registry when `chain` or `endpoint` is a known
+/// chain.
+pub async fn get_substrate_balance(address: &str, chain: &str, endpoint: &str) -> Result<()> {
     println!("\n{}", "Fetching Substrate Balance".cyan().bold());
     println!("{}", "═══════════════════════════════════════".dimmed());
     println!("{}: {}", "Endpoint".dimmed(), endpoint);
     println!("{}: {}", "Address".dimmed(), address);
     println!();
 
+    apex_sdk_substrate::storage::StorageQuery::parse_address(address)
+        .context("Invalid Substrate address")?;
+
+    let registry = ChainRegistry::new();
+    let known = registry
+        .get(chain)
+        .or_else(|| registry.by_endpoint(endpoint))
+        .filter(|info| info.chain_type != ChainType::Evm);
+    let config = match known {
+        Some(info) => ChainConfig {
+            endpoint: endpoint.to_string(),
+            ..ChainConfig::from(info)
+        },
+        None => ChainConfig::custom(chain, endpoint, apex_sdk_types::GENERIC_SS58_PREFIX),
+    };
+
     // Show progress
     let spinner = indicatif::ProgressBar::new_spinner();
     spinner.set_message("Connecting to chain...");
     spinner.enable_steady_tick(std::time::Duration::from_millis(100));
 
     // Connect to the chain
-    let api = OnlineClient::<PolkadotConfig>::from_url(endpoint)
+    let adapter = SubstrateAdapter::connect_with_config(config)
         .await
-        .context("Failed to connect to Substrate endpoint")?;
+        .map_err(|e| anyhow::anyhow!("Failed to connect to Substrate endpoint: {}", e))?;
 
     spinner.set_message("Fetching balance...");
 
-    let address_val = apex_sdk_substrate::storage::StorageQuery::parse_address(address)
-        .context("Invalid Substrate address")?;
-
-    let account_query = subxt::dynamic::storage("System", "Account", vec![address_val.clone()]);
-
-    let account_data = api
-        .storage()
-        .at_latest()
-        .await?
-        .fetch(&account_query)
-        .await?
-        .context("Account not found on chain")?;
-
-    fn extract_u128<T>(value: &subxt::dynamic::Value<T>, path: &[&str]) -> Option<u128> {
-        let mut current = value;
-        for &key in path {
-            current = current.at(key)?;
-        }
-        current.as_u128()
-    }
-
-    let value = account_data
-        .to_value()
-        .map_err(|e| anyhow::anyhow!("Failed to decode account data: {}", e))?;
-    let free_balance = extract_u128(&value, &["data", "free"])
-        .context("Failed to parse free balance from storage")?;
-
-    // Attempt to fetch constants from System pallet if available
-    let token_symbol = "UNIT";
-    let token_decimals = 12;
+    let free_balance = adapter
+        .get_balance(address)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to fetch balance: {}", e))?;
+    let properties = match adapter.chain_properties().await {
+        Ok(properties) => properties,
+        Err(_) => ChainProperties {
+            token_symbol: adapter.config().token_symbol.clone(),
+            token_decimals: adapter.config().token_decimals,
+            ss58_format: Some(adapter.config().ss58_prefix),
+        },
+    };
     spinner.finish_and_clear();
 
-    // Try to fetch chain name from runtime metadata (fallback to static value)
-    let chain_name = api
-        .metadata()
-        .pallet_by_name_err("System")
-        .ok()
-        .and_then(|pallet| {
-            pallet
-                .constants()
-                .find(|c| c.name() == "ChainName")
-                .and_then(|constant| {
-                    std::str::from_utf8(constant.value())
-                        .map(|s| s.to_owned())
-                        .ok()
-                })
-        })
-        .unwrap_or_else(|| "Substrate Chain".to_string());
-
     println!("\n{}", "Balance Retrieved".green().bold());
     println!("{}", "═══════════════════════════════════════".dimmed());
     println!("{}: {}", "Address".cyan(), address);
-    println!("{}: {}", "Network".dimmed(), chain_name);
+    println!("{}: {}", "Network".dimmed(), adapter.config().name);
     println!();
 
-    // Format balance with decimals
-    let divisor = 10u128.pow(token_decimals as u32);
-    let balance_formatted = format_balance(free_balance, divisor);
-
-    println!(
-        "{}: {} {}",
-        "Free Balance".green().bold(),
-        balance_formatted,
-        token_symbol
-    );
-    println!("{}: {} raw units", "Raw".dimmed(), free_balance);
+    print_free_balance(free_balance, &properties);
 
     // Show existential deposit if possible
     println!("\n{}", "Tip:".yellow());
@@ -104,6 +78,9 @@ pub async fn get_substrate_balance(address: &str, endpoint: &str) -> Result<()>
 }
 
 /// Get account balance for Revive chains
+///
+/// Revive balances are read from `System::Account`, so they are formatted
+/// with the native token's decimals from `system_properties`.
 pub async fn get_revive_balance(address: &str, endpoint: &str) -> Result<()> {
     use apex_sdk::core::Provider;
     use apex_sdk::prelude::*;
@@ -130,6 +107,13 @@ pub async fn get_revive_balance(address: &str, endpoint: &str) -> Result<()> {
         .get_balance(&addr)
         .await
         .map_err(|e| anyhow::anyhow!("Failed to fetch Revive balance: {}", e))?;
+    let properties = match adapter.chain_properties().await {
+        Ok(properties) => properties,
+        Err(_) => ChainRegistry::new()
+            .by_endpoint(endpoint)
+            .map(|info| info.properties())
+            .unwrap_or_default(),
+    };
 
     spinner.finish_and_clear();
 
@@ -137,38 +121,25 @@ pub async fn get_revive_balance(address: &str, endpoint: &str) -> Result<()> {
     println!("{}", "═══════════════════════════════════════".dimmed());
     println!("{}: {}", "Address".cyan(), address);
 
-    // Revive usually uses 18 decimals like Ethereum
-    let token_decimals = 18;
-    let token_symbol = "ETH";
+    print_free_balance(balance, &properties);
 
-    // Format balance with decimals
-    let divisor = 10u128.pow(token_decimals as u32);
-    let balance_formatted = format_balance(balance, divisor);
+    Ok(())
+}
 
+/// Print a free balance in whole tokens and raw units
+fn print_free_balance(balance: u128, properties: &ChainProperties) {
     println!(
         "{}: {} {}",
         "Free Balance".green().bold(),
-        balance_formatted,
-        token_symbol
+        format_balance(balance, properties.token_decimals),
+        properties.token_symbol
     );
     println!("{}: {} raw units", "Raw".dimmed(), balance);
-
-    Ok(())
 }
 
 /// Format balance with decimal places
-fn format_balance(balance: u128, divisor: u128) -> String {
-    let whole = balance / divisor;
-    let frac = balance % divisor;
-
-    if frac == 0 {
-        whole.to_string()
-    } else {
-        let decimal_places = (divisor as f64).log10() as usize;
-        let frac_str = format!("{:0width$}", frac, width = decimal_places);
-        let trimmed = frac_str.trim_end_matches('0');
-        format!("{}.{}", whole, trimmed)
-    }
+fn format_balance(balance: u128, decimals: u8) -> String {
+    apex_sdk_types::format_units(balance, decimals)
 }
 
 /// Auto-detect chain type and get balance
@@ -179,7 +150,7 @@ pub async fn get_balance(address: &str, chain: &str, endpoint: &str) -> Result<(
             .unwrap_or(false);
 
     if is_substrate {
-        get_substrate_balance(address, endpoint).await
+        get_substrate_balance(address, chain, endpoint).await
     } else {
         get_revive_balance(address, endpoint).await
     }
@@ -204,7 +175,7 @@ mod tests {
         ];
 
         for (balance, expected) in &test_cases {
-            let result = format_balance(*balance, divisor);
+            let result = format_balance(*balance, 10);
             assert_eq!(
                 result, *expected,
                 "Failed for {} balance, expected {}, got {}",
@@ -218,14 +189,14 @@ mod tests {
         let divisor = 10u128.pow(12); // 12 decimals
 
         // Very small amounts
-        assert_eq!(format_balance(1, divisor), "0.000000000001");
-        assert_eq!(format_balance(10, divisor), "0.00000000001");
+        assert_eq!(format_balance(1, 12), "0.000000000001");
+        assert_eq!(format_balance(10, 12), "0.00000000001");
 
         // Zero
-        assert_eq!(format_balance(0, divisor), "0");
+        assert_eq!(format_balance(0, 12), "0");
 
         // Large amounts
-        assert_eq!(format_balance(1_000_000 * divisor, divisor), "1000000");
+        assert_eq!(format_balance(1_000_000 * divisor, 12), "1000000");
     }
 
     #[tokio::test]
@@ -248,6 +219,7 @@ mod tests {
         // Test with Westend testnet
         let result = get_substrate_balance(
             "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+            "westend",
             "wss://westend-rpc.polkadot.io",
         )
         .await;
@@ -0,0 +1,365 @@
+//! Apex SDK load-test harness
+//!
+//! Funds a set of throwaway wallets from a dev account, fires transfers
+//! between them at a fixed rate and reports throughput, latency percentiles
+//! and a failure breakdown. Intended for local dev nodes.
+//!
+//! `apex-bench soak` instead runs balance queries and block subscriptions for
+//! hours and fails if memory, file descriptors, spans or tasks keep growing.
+
+use anyhow::Context;
+use apex_sdk_core::{Broadcaster, NonceManager};
+use apex_sdk_substrate::{
+    transaction::BatchMode, KeyPairType, SubstrateAdapter, SubstrateNonceManager, Wallet,
+};
+use apex_sdk_types::Address;
+use clap::{Args, Parser, Subcommand};
+use colored::Colorize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use subxt::events::Phase;
+use tokio::task::JoinSet;
+
+mod soak;
+mod stats;
+
+use stats::{Recorder, RunConfig, Stage};
+
+/// Secret phrase of the well-known development accounts
+const DEV_PHRASE: &str = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+#[derive(Parser)]
+#[command(name = "apex-bench")]
+#[command(about = "Transaction throughput benchmark for Substrate nodes", long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Mode>,
+    #[command(flatten)]
+    load: LoadArgs,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Run balance queries and subscriptions for hours, failing on resource leaks
+    Soak(soak::SoakArgs),
+}
+
+#[derive(Args)]
+struct LoadArgs {
+    /// WebSocket endpoint of the node under test
+    #[arg(short, long, default_value = "ws://127.0.0.1:9944")]
+    endpoint: String,
+    /// Funding account: a dev URI like //Alice, or a mnemonic with an optional //path
+    #[arg(long, default_value = "//Alice")]
+    funder: String,
+    /// Number of test wallets to generate and fund
+    #[arg(short, long, default_value_t = 10)]
+    wallets: usize,
+    /// Amount to fund each test wallet with, in raw units
+    #[arg(long, default_value_t = 10_000_000_000_000_000)]
+    fund: u128,
+    /// Amount of each benchmark transfer, in raw units
+    #[arg(short, long, default_value_t = 10_000_000_000)]
+    amount: u128,
+    /// Target submission rate, in transfers per second
+    #[arg(short, long, default_value_t = 10.0)]
+    rate: f64,
+    /// How long to fire transfers for, in seconds
+    #[arg(short, long, default_value_t = 30)]
+    duration: u64,
+    /// Stop after this many transfers instead of after --duration
+    #[arg(short, long)]
+    count: Option<u64>,
+    /// Seconds to wait for outstanding transfers to finalize
+    #[arg(long, default_value_t = 60)]
+    timeout: u64,
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// Transfers awaiting finalization, keyed by extrinsic hash
+type Pending = Arc<Mutex<HashMap<[u8; 32], Instant>>>;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Mode::Soak(args)) => soak::run(args).await,
+        None => run_load(cli.load).await,
+    }
+}
+
+/// Fund test wallets, fire transfers at the target rate and report
+async fn run_load(args: LoadArgs) -> anyhow::Result<()> {
+    if args.wallets < 2 {
+        anyhow::bail!("At least 2 wallets are needed to transfer between");
+    }
+    if !args.rate.is_finite() || args.rate <= 0.0 {
+        anyhow::bail!("Rate must be positive");
+    }
+
+    let funder = parse_funder(&args.funder)?;
+
+    if !args.json {
+        println!("\n{}", "Apex Load Test".cyan().bold());
+        println!("{}", "═══════════════════════════════════════".dimmed());
+        println!("{}: {}", "Endpoint".dimmed(), args.endpoint);
+        println!("{}: {}", "Funder".dimmed(), funder.address());
+        println!("{}: {}", "Wallets".dimmed(), args.wallets);
+        println!("{}: {:.1} tx/s", "Target Rate".dimmed(), args.rate);
+        match args.count {
+            Some(count) => println!("{}: {} transfers", "Load".dimmed(), count),
+            None => println!("{}: {}s", "Load".dimmed(), args.duration),
+        }
+        println!();
+    }
+
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.enable_steady_tick(Duration::from_millis(100));
+    if args.json {
+        spinner.set_draw_target(indicatif::ProgressDrawTarget::hidden());
+    }
+    spinner.set_message("Connecting to chain...");
+
+    let adapter = Arc::new(
+        SubstrateAdapter::connect(&args.endpoint)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to connect to Substrate endpoint: {}", e))?,
+    );
+    let executor = Arc::new(adapter.transaction_executor());
+
+    spinner.set_message(format!("Funding {} wallets...", args.wallets));
+    let wallets: Arc<Vec<Wallet>> =
+        Arc::new((0..args.wallets).map(|_| Wallet::new_random()).collect());
+    let funding = wallets
+        .iter()
+        .map(|wallet| (wallet.address(), args.fund))
+        .collect();
+    executor
+        .execute_batch_transfers(funding, &funder, BatchMode::AllOrNothing)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to fund test wallets: {}", e))
+        .context("Check that the funder has enough balance")?;
+
+    let recorder = Arc::new(Mutex::new(Recorder::new()));
+    let pending: Pending = Arc::new(Mutex::new(HashMap::new()));
+    let tracker = tokio::spawn(track_finalized(
+        adapter.clone(),
+        pending.clone(),
+        recorder.clone(),
+    ));
+
+    let nonces = SubstrateNonceManager::new(adapter.storage());
+    let mut ticker = tokio::time::interval(Duration::from_secs_f64(1.0 / args.rate));
+    let mut tasks = JoinSet::new();
+    let started = Instant::now();
+    let deadline = started + Duration::from_secs(args.duration);
+    let mut sent: u64 = 0;
+
+    loop {
+        match args.count {
+            Some(count) if sent >= count => break,
+            None if Instant::now() >= deadline => break,
+            _ => {}
+        }
+        ticker.tick().await;
+
+        let from_index = (sent as usize) % wallets.len();
+        let to = wallets[(from_index + 1) % wallets.len()].address();
+        let from_address = wallets[from_index].address();
+        sent += 1;
+
+        let nonce = match nonces
+            .get_next_nonce(&Address::substrate(from_address.clone()))
+            .await
+        {
+            Ok(nonce) => nonce,
+            Err(e) => {
+                recorder
+                    .lock()
+                    .unwrap()
+                    .record_failure(Stage::Sign, &e.to_string());
+                continue;
+            }
+        };
+
+        if sent.is_multiple_of(10) {
+            let recorder = recorder.lock().unwrap();
+            spinner.set_message(format!(
+                "Sent {} | submitted {} | finalized {} | failed {}",
+                sent,
+                recorder.submitted(),
+                recorder.finalized(),
+                recorder.failed()
+            ));
+        }
+
+        let (adapter, executor, wallets, nonces) = (
+            adapter.clone(),
+            executor.clone(),
+            wallets.clone(),
+            nonces.clone(),
+        );
+        let (pending, recorder) = (pending.clone(), recorder.clone());
+        let amount = args.amount;
+        tasks.spawn(async move {
+            let from = &wallets[from_index];
+            let signed = match executor
+                .sign_transfer(from, to.as_str(), amount, nonce)
+                .await
+            {
+                Ok(signed) => signed,
+                Err(e) => {
+                    recorder
+                        .lock()
+                        .unwrap()
+                        .record_failure(Stage::Sign, &e.to_string());
+                    let _ = nonces.reset_nonce(&from_address).await;
+                    return;
+                }
+            };
+
+            let hash = sp_core::blake2_256(&signed);
+            let submitted = Instant::now();
+            pending.lock().unwrap().insert(hash, submitted);
+            match adapter.broadcast(&signed).await {
+                Ok(_) => recorder
+                    .lock()
+                    .unwrap()
+                    .record_submitted(submitted.elapsed()),
+                Err(e) => {
+                    pending.lock().unwrap().remove(&hash);
+                    recorder
+                        .lock()
+                        .unwrap()
+                        .record_failure(Stage::Submit, &e.to_string());
+                    let _ = nonces.reset_nonce(&from_address).await;
+                }
+            }
+        });
+    }
+    while tasks.join_next().await.is_some() {}
+    let load_duration = started.elapsed();
+
+    let wait_until = Instant::now() + Duration::from_secs(args.timeout);
+    while Instant::now() < wait_until {
+        let outstanding = pending.lock().unwrap().len();
+        if outstanding == 0 {
+            break;
+        }
+        spinner.set_message(format!(
+            "Waiting for {} transfers to finalize...",
+            outstanding
+        ));
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    tracker.abort();
+    spinner.finish_and_clear();
+
+    let mut recorder = recorder.lock().unwrap();
+    for _ in pending.lock().unwrap().drain() {
+        recorder.record_failure(Stage::Timeout, "not finalized before timeout");
+    }
+
+    let report = recorder.report(
+        RunConfig {
+            endpoint: args.endpoint,
+            wallets: args.wallets,
+            target_rate: args.rate,
+            amount: args.amount,
+        },
+        load_duration,
+    );
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        report.print();
+    }
+
+    Ok(())
+}
+
+/// Match finalized extrinsics against pending transfers
+async fn track_finalized(
+    adapter: Arc<SubstrateAdapter>,
+    pending: Pending,
+    recorder: Arc<Mutex<Recorder>>,
+) {
+    let mut blocks = adapter.event_bus().subscribe("apex-bench");
+    while let Some(block) = blocks.recv().await {
+        let Ok(extrinsics) = block.block.extrinsics().await else {
+            continue;
+        };
+
+        let failed: HashSet<u32> = block
+            .events
+            .iter()
+            .filter_map(|event| event.ok())
+            .filter(|event| {
+                event.pallet_name() == "System" && event.variant_name() == "ExtrinsicFailed"
+            })
+            .filter_map(|event| match event.phase() {
+                Phase::ApplyExtrinsic(index) => Some(index),
+                _ => None,
+            })
+            .collect();
+
+        for ext in extrinsics.iter() {
+            let hash = sp_core::blake2_256(ext.bytes());
+            let Some(submitted) = pending.lock().unwrap().remove(&hash) else {
+                continue;
+            };
+
+            let mut recorder = recorder.lock().unwrap();
+            if failed.contains(&ext.index()) {
+                recorder.record_failure(
+                    Stage::Dispatch,
+                    &format!("Extrinsic failed at block {}", block.number),
+                );
+            } else {
+                recorder.record_finalized(submitted.elapsed());
+            }
+        }
+    }
+}
+
+/// Wallet for a dev URI (`//Alice`) or a mnemonic with an optional `//path`
+fn parse_funder(funder: &str) -> anyhow::Result<Wallet> {
+    let (phrase, path) = match funder.split_once("//") {
+        Some((phrase, path)) => (phrase.trim(), Some(path)),
+        None => (funder.trim(), None),
+    };
+    let phrase = if phrase.is_empty() {
+        DEV_PHRASE
+    } else {
+        phrase
+    };
+
+    Wallet::from_mnemonic_with_path(phrase, path, KeyPairType::Sr25519)
+        .map_err(|e| anyhow::anyhow!("Invalid funder '{}': {}", funder, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_funder() {
+        let alice = parse_funder("//Alice").unwrap();
+        assert_eq!(
+            alice.address(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+
+        let explicit = parse_funder(&format!("{}//Alice", DEV_PHRASE)).unwrap();
+        assert_eq!(explicit.address(), alice.address());
+
+        let root = parse_funder(DEV_PHRASE).unwrap();
+        assert_ne!(root.address(), alice.address());
+
+        assert!(parse_funder("not a mnemonic").is_err());
+    }
+}
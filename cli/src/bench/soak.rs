@@ -0,0 +1,253 @@
+//! Soak mode: hours of balance queries and subscriptions with leak detection
+
+use apex_sdk_metrics::{
+    LeakThresholds, OperationType, PerformanceProfiler, SoakMonitor, SoakReport, SoakResource,
+};
+use apex_sdk_substrate::SubstrateAdapter;
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// `//Alice` on the development chains
+const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+#[derive(Args)]
+pub struct SoakArgs {
+    /// WebSocket endpoint of the node under test
+    #[arg(short, long, default_value = "ws://127.0.0.1:9944")]
+    endpoint: String,
+    /// Accounts whose balances are queried, in turn
+    #[arg(short, long = "address", default_value = ALICE)]
+    addresses: Vec<String>,
+    /// How long to run, in seconds
+    #[arg(short, long, default_value_t = 3600)]
+    duration: u64,
+    /// Milliseconds between balance queries
+    #[arg(long, default_value_t = 500)]
+    query_interval: u64,
+    /// Seconds between resource samples
+    #[arg(long, default_value_t = 30)]
+    sample_interval: u64,
+    /// Seconds of samples ignored while caches and pools fill up
+    #[arg(long, default_value_t = 300)]
+    warmup: u64,
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// Workload counters, shared with the workload tasks
+#[derive(Debug, Default)]
+struct Counters {
+    queries: AtomicU64,
+    query_errors: AtomicU64,
+    blocks: AtomicU64,
+    resubscriptions: AtomicU64,
+}
+
+#[derive(Serialize)]
+struct Output<'a> {
+    endpoint: &'a str,
+    queries: u64,
+    query_errors: u64,
+    blocks: u64,
+    resubscriptions: u64,
+    #[serde(flatten)]
+    report: &'a SoakReport,
+}
+
+/// Run the soak workload, failing if any resource grows without bound
+pub async fn run(args: SoakArgs) -> anyhow::Result<()> {
+    let thresholds = LeakThresholds::default().with_warmup(Duration::from_secs(args.warmup));
+    let sample_interval = Duration::from_secs(args.sample_interval.max(1));
+    let expected = args.duration.saturating_sub(args.warmup) / sample_interval.as_secs();
+    if (expected as usize) < thresholds.min_samples {
+        anyhow::bail!(
+            "Only {} samples fit in a {}s run after a {}s warm-up; at least {} are needed",
+            expected,
+            args.duration,
+            args.warmup,
+            thresholds.min_samples
+        );
+    }
+
+    if !args.json {
+        println!("\n{}", "Apex Soak Test".cyan().bold());
+        println!("{}", "═══════════════════════════════════════".dimmed());
+        println!("{}: {}", "Endpoint".dimmed(), args.endpoint);
+        println!("{}: {}", "Accounts".dimmed(), args.addresses.len());
+        println!("{}: {}s", "Duration".dimmed(), args.duration);
+        println!("{}: {}s", "Warm-up".dimmed(), args.warmup);
+        println!();
+    }
+
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.enable_steady_tick(Duration::from_millis(100));
+    if args.json {
+        spinner.set_draw_target(indicatif::ProgressDrawTarget::hidden());
+    }
+    spinner.set_message("Connecting to chain...");
+
+    let adapter = Arc::new(
+        SubstrateAdapter::connect(&args.endpoint)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to connect to Substrate endpoint: {}", e))?,
+    );
+    let profiler = PerformanceProfiler::new();
+    let counters = Arc::new(Counters::default());
+    let mut monitor = SoakMonitor::new(thresholds).with_profiler(profiler.clone());
+
+    let workload = [
+        tokio::spawn(query_balances(
+            adapter.clone(),
+            args.addresses.clone(),
+            Duration::from_millis(args.query_interval.max(1)),
+            profiler,
+            counters.clone(),
+        )),
+        tokio::spawn(follow_blocks(adapter.clone(), counters.clone())),
+        tokio::spawn(churn_subscriptions(adapter.clone(), counters.clone())),
+    ];
+
+    let deadline = Instant::now() + Duration::from_secs(args.duration);
+    let mut ticker = tokio::time::interval(sample_interval);
+    while Instant::now() < deadline {
+        ticker.tick().await;
+        let sample = monitor.sample().await;
+        spinner.set_message(format!(
+            "{:.0}s | rss {} | fds {} | tasks {} | queries {}",
+            sample.elapsed_secs,
+            format_bytes(sample.rss_bytes as f64),
+            sample.open_fds.map_or("-".to_string(), |v| v.to_string()),
+            sample.tasks.map_or("-".to_string(), |v| v.to_string()),
+            counters.queries.load(Ordering::Relaxed)
+        ));
+    }
+    for task in workload {
+        task.abort();
+    }
+    spinner.finish_and_clear();
+
+    let report = monitor.report();
+    let output = Output {
+        endpoint: &args.endpoint,
+        queries: counters.queries.load(Ordering::Relaxed),
+        query_errors: counters.query_errors.load(Ordering::Relaxed),
+        blocks: counters.blocks.load(Ordering::Relaxed),
+        resubscriptions: counters.resubscriptions.load(Ordering::Relaxed),
+        report: &report,
+    };
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print_report(&output);
+    }
+
+    let leaks = report.leaks();
+    if !leaks.is_empty() {
+        let names: Vec<String> = leaks.iter().map(|t| t.resource.to_string()).collect();
+        anyhow::bail!("Resource leak detected: {}", names.join(", "));
+    }
+    Ok(())
+}
+
+/// Query balances round-robin, recording each query as a profiler span
+async fn query_balances(
+    adapter: Arc<SubstrateAdapter>,
+    addresses: Vec<String>,
+    interval: Duration,
+    profiler: PerformanceProfiler,
+    counters: Arc<Counters>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    for address in addresses.iter().cycle() {
+        ticker.tick().await;
+        let span = profiler.start_span(OperationType::BalanceQuery);
+        counters.queries.fetch_add(1, Ordering::Relaxed);
+        match adapter.get_balance(address.as_str()).await {
+            Ok(_) => span.success(),
+            Err(e) => {
+                counters.query_errors.fetch_add(1, Ordering::Relaxed);
+                span.error(e.to_string());
+            }
+        }
+    }
+}
+
+/// Hold one finalized-block subscription for the whole run
+async fn follow_blocks(adapter: Arc<SubstrateAdapter>, counters: Arc<Counters>) {
+    let mut blocks = adapter.event_bus().subscribe("apex-soak");
+    while blocks.recv().await.is_some() {
+        counters.blocks.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Repeatedly subscribe, wait for one block and unsubscribe
+async fn churn_subscriptions(adapter: Arc<SubstrateAdapter>, counters: Arc<Counters>) {
+    loop {
+        let mut blocks = adapter.event_bus().subscribe("apex-soak-churn");
+        let _ = tokio::time::timeout(Duration::from_secs(60), blocks.recv()).await;
+        drop(blocks);
+        counters.resubscriptions.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn print_report(output: &Output<'_>) {
+    let report = output.report;
+
+    println!("\n{}", "Soak Test Results".cyan().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    println!("{}: {}", "Endpoint".dimmed(), output.endpoint);
+    println!("{}: {:.0}s", "Duration".dimmed(), report.duration_secs);
+    println!("{}: {}", "Samples".dimmed(), report.samples);
+    println!(
+        "{}: {} ({} failed)",
+        "Balance Queries".dimmed(),
+        output.queries,
+        output.query_errors
+    );
+    println!("{}: {}", "Blocks Received".dimmed(), output.blocks);
+    println!("{}: {}", "Resubscriptions".dimmed(), output.resubscriptions);
+
+    println!("\n{}", "Resources".cyan().bold());
+    for trend in &report.trends {
+        let format = |value: f64| match trend.resource {
+            SoakResource::Rss => format_bytes(value),
+            _ => format!("{:.0}", value),
+        };
+        let verdict = if trend.leaking {
+            "LEAK".red().bold()
+        } else if trend.evaluated {
+            "ok".green()
+        } else {
+            "too few samples".yellow()
+        };
+        println!(
+            "  {}: {} → {} (peak {}) {}",
+            trend.resource.to_string().dimmed(),
+            format(trend.start),
+            format(trend.end),
+            format(trend.peak),
+            verdict
+        );
+    }
+}
+
+/// Byte count in MiB, e.g. `12.5 MiB`
+fn format_bytes(bytes: f64) -> String {
+    format!("{:.1} MiB", bytes / (1024.0 * 1024.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0.0), "0.0 MiB");
+        assert_eq!(format_bytes(12.5 * 1024.0 * 1024.0), "12.5 MiB");
+    }
+}
@@ -0,0 +1,316 @@
+//! Latency, throughput and failure accounting for load runs
+
+use apex_sdk_metrics::{categorize_error, ErrorCategory};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Stage of a transfer's life at which it failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// Fetching a nonce or signing the extrinsic
+    Sign,
+    /// Submitting the extrinsic to the node
+    Submit,
+    /// Included in a block but the dispatch failed
+    Dispatch,
+    /// Not finalized before the run's timeout
+    Timeout,
+}
+
+impl Stage {
+    fn as_str(self) -> &'static str {
+        match self {
+            Stage::Sign => "sign",
+            Stage::Submit => "submit",
+            Stage::Dispatch => "dispatch",
+            Stage::Timeout => "timeout",
+        }
+    }
+}
+
+/// Percentile summary of a set of latency samples, in milliseconds
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub samples: usize,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl LatencySummary {
+    /// Summarize `samples`, or `None` if there are none
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let total: Duration = sorted.iter().sum();
+
+        Some(Self {
+            samples: sorted.len(),
+            mean_ms: millis(total) / sorted.len() as f64,
+            p50_ms: millis(percentile(&sorted, 50.0)),
+            p90_ms: millis(percentile(&sorted, 90.0)),
+            p99_ms: millis(percentile(&sorted, 99.0)),
+            max_ms: millis(sorted[sorted.len() - 1]),
+        })
+    }
+}
+
+/// Nearest-rank percentile of non-empty, ascending `sorted`
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Accumulates outcomes while a run is in progress
+#[derive(Debug)]
+pub struct Recorder {
+    started: Instant,
+    submitted: u64,
+    finalized: u64,
+    last_finalized: Option<Instant>,
+    submit_latency: Vec<Duration>,
+    finality_latency: Vec<Duration>,
+    failures: BTreeMap<String, u64>,
+}
+
+impl Recorder {
+    /// Start a run now
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            submitted: 0,
+            finalized: 0,
+            last_finalized: None,
+            submit_latency: Vec::new(),
+            finality_latency: Vec::new(),
+            failures: BTreeMap::new(),
+        }
+    }
+
+    /// The node accepted a transfer after `latency`
+    pub fn record_submitted(&mut self, latency: Duration) {
+        self.submitted += 1;
+        self.submit_latency.push(latency);
+    }
+
+    /// A transfer was finalized `latency` after it was submitted
+    pub fn record_finalized(&mut self, latency: Duration) {
+        self.finalized += 1;
+        self.finality_latency.push(latency);
+        self.last_finalized = Some(Instant::now());
+    }
+
+    /// A transfer failed at `stage`, categorized by its error message
+    pub fn record_failure(&mut self, stage: Stage, message: &str) {
+        let category = match stage {
+            Stage::Timeout => ErrorCategory::Timeout,
+            _ => categorize_error(message, None).category,
+        };
+        *self
+            .failures
+            .entry(format!("{}/{:?}", stage.as_str(), category))
+            .or_default() += 1;
+    }
+
+    /// Number of transfers accepted by the node so far
+    pub fn submitted(&self) -> u64 {
+        self.submitted
+    }
+
+    /// Number of transfers finalized so far
+    pub fn finalized(&self) -> u64 {
+        self.finalized
+    }
+
+    /// Number of failed transfers so far
+    pub fn failed(&self) -> u64 {
+        self.failures.values().sum()
+    }
+
+    /// Build the final report; `load_duration` is how long transfers were fired for
+    pub fn report(&self, config: RunConfig, load_duration: Duration) -> BenchReport {
+        let load_secs = load_duration.as_secs_f64();
+        let finality_secs = self
+            .last_finalized
+            .map(|last| last.duration_since(self.started).as_secs_f64())
+            .unwrap_or_default();
+
+        BenchReport {
+            config,
+            duration_secs: load_secs,
+            submitted: self.submitted,
+            finalized: self.finalized,
+            failed: self.failed(),
+            submit_tps: rate(self.submitted, load_secs),
+            finalized_tps: rate(self.finalized, finality_secs),
+            submit_latency: LatencySummary::from_samples(&self.submit_latency),
+            finality_latency: LatencySummary::from_samples(&self.finality_latency),
+            failures: self.failures.clone(),
+        }
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn rate(count: u64, secs: f64) -> f64 {
+    if secs > 0.0 {
+        count as f64 / secs
+    } else {
+        0.0
+    }
+}
+
+/// Parameters of a run, echoed in its report
+#[derive(Debug, Clone, Serialize)]
+pub struct RunConfig {
+    pub endpoint: String,
+    pub wallets: usize,
+    pub target_rate: f64,
+    pub amount: u128,
+}
+
+/// Results of a load run
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub config: RunConfig,
+    pub duration_secs: f64,
+    pub submitted: u64,
+    pub finalized: u64,
+    pub failed: u64,
+    pub submit_tps: f64,
+    pub finalized_tps: f64,
+    pub submit_latency: Option<LatencySummary>,
+    pub finality_latency: Option<LatencySummary>,
+    /// Failure counts keyed by `stage/category`
+    pub failures: BTreeMap<String, u64>,
+}
+
+impl BenchReport {
+    /// Print the report in the CLI's table style
+    pub fn print(&self) {
+        println!("\n{}", "Load Test Results".cyan().bold());
+        println!("{}", "═══════════════════════════════════════".dimmed());
+        println!("{}: {}", "Endpoint".dimmed(), self.config.endpoint);
+        println!("{}: {}", "Wallets".dimmed(), self.config.wallets);
+        println!(
+            "{}: {:.1} tx/s",
+            "Target Rate".dimmed(),
+            self.config.target_rate
+        );
+        println!("{}: {:.1}s", "Duration".dimmed(), self.duration_secs);
+        println!();
+        println!("{}: {}", "Submitted".dimmed(), self.submitted);
+        println!(
+            "{}: {}",
+            "Finalized".dimmed(),
+            self.finalized.to_string().green()
+        );
+        let failed = self.failed.to_string();
+        if self.failed > 0 {
+            println!("{}: {}", "Failed".dimmed(), failed.red());
+        } else {
+            println!("{}: {}", "Failed".dimmed(), failed);
+        }
+        println!(
+            "{}: {:.2} tx/s",
+            "Submit Throughput".dimmed(),
+            self.submit_tps
+        );
+        println!(
+            "{}: {:.2} tx/s",
+            "Finalized Throughput".dimmed(),
+            self.finalized_tps
+        );
+
+        print_latency("Submit Latency", self.submit_latency.as_ref());
+        print_latency("Finality Latency", self.finality_latency.as_ref());
+
+        if !self.failures.is_empty() {
+            println!("\n{}", "Failures".cyan().bold());
+            for (kind, count) in &self.failures {
+                println!("  {}: {}", kind.dimmed(), count);
+            }
+        }
+    }
+}
+
+fn print_latency(title: &str, summary: Option<&LatencySummary>) {
+    println!("\n{}", title.cyan().bold());
+    match summary {
+        Some(s) => {
+            println!("  {}: {:.1} ms", "mean".dimmed(), s.mean_ms);
+            println!("  {}: {:.1} ms", "p50".dimmed(), s.p50_ms);
+            println!("  {}: {:.1} ms", "p90".dimmed(), s.p90_ms);
+            println!("  {}: {:.1} ms", "p99".dimmed(), s.p99_ms);
+            println!("  {}: {:.1} ms", "max".dimmed(), s.max_ms);
+        }
+        None => println!("  {}", "no samples".dimmed()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_percentiles() {
+        let samples: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+        let summary = LatencySummary::from_samples(&samples).unwrap();
+
+        assert_eq!(summary.samples, 100);
+        assert_eq!(summary.p50_ms, 50.0);
+        assert_eq!(summary.p90_ms, 90.0);
+        assert_eq!(summary.p99_ms, 99.0);
+        assert_eq!(summary.max_ms, 100.0);
+        assert!((summary.mean_ms - 50.5).abs() < 1e-9);
+
+        let single = LatencySummary::from_samples(&[Duration::from_millis(7)]).unwrap();
+        assert_eq!(single.p50_ms, 7.0);
+        assert_eq!(single.p99_ms, 7.0);
+
+        assert!(LatencySummary::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_recorder_report() {
+        let mut recorder = Recorder::new();
+        recorder.record_submitted(Duration::from_millis(4));
+        recorder.record_submitted(Duration::from_millis(6));
+        recorder.record_finalized(Duration::from_secs(12));
+        recorder.record_failure(Stage::Submit, "connection refused");
+        recorder.record_failure(Stage::Timeout, "not finalized");
+        recorder.record_failure(Stage::Timeout, "not finalized");
+
+        let config = RunConfig {
+            endpoint: "ws://127.0.0.1:9944".to_string(),
+            wallets: 2,
+            target_rate: 10.0,
+            amount: 1,
+        };
+        let report = recorder.report(config, Duration::from_secs(2));
+
+        assert_eq!(report.submitted, 2);
+        assert_eq!(report.finalized, 1);
+        assert_eq!(report.failed, 3);
+        assert_eq!(report.submit_tps, 1.0);
+        assert_eq!(report.submit_latency.unwrap().p50_ms, 4.0);
+        assert_eq!(report.failures["timeout/Timeout"], 2);
+        assert_eq!(report.failures["submit/Network"], 1);
+    }
+}
@@ -1,6 +1,7 @@
 //! Configuration management for Apex SDK CLI
 
 use anyhow::{Context, Result};
+use apex_sdk_core::codec::Codec;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -23,6 +24,12 @@ pub struct Preferences {
     pub progress_bars: bool,
     #[serde(default)]
     pub log_level: String,
+    /// Encoding of interchange files such as wallet backups
+    #[serde(default)]
+    pub interchange_format: Codec,
+    /// Locale of CLI messages, e.g. `pt-BR`; unset follows the environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
 }
 
 fn default_true() -> bool {
@@ -35,6 +42,8 @@ impl Default for Preferences {
             color_output: true,
             progress_bars: true,
             log_level: "info".to_string(),
+            interchange_format: Codec::default(),
+            locale: None,
         }
     }
 }
@@ -196,6 +205,13 @@ impl Config {
                 }
                 self.preferences.log_level = value.to_string();
             }
+            "preferences.interchange_format" => {
+                self.preferences.interchange_format =
+                    value.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+            }
+            "preferences.locale" => {
+                self.preferences.locale = Some(value.to_string());
+            }
             key if key.starts_with("endpoints.") => {
                 let chain = key
                     .strip_prefix("endpoints.")
@@ -221,6 +237,12 @@ impl Config {
             "preferences.color_output" => Ok(self.preferences.color_output.to_string()),
             "preferences.progress_bars" => Ok(self.preferences.progress_bars.to_string()),
             "preferences.log_level" => Ok(self.preferences.log_level.clone()),
+            "preferences.interchange_format" => Ok(self.preferences.interchange_format.to_string()),
+            "preferences.locale" => Ok(self
+                .preferences
+                .locale
+                .clone()
+                .unwrap_or_else(|| "auto".to_string())),
             key if key.starts_with("endpoints.") => {
                 let chain = key
                     .strip_prefix("endpoints.")
@@ -401,5 +423,24 @@ mod tests {
         assert!(prefs.color_output);
         assert!(prefs.progress_bars);
         assert_eq!(prefs.log_level, "info");
+        assert_eq!(prefs.interchange_format, Codec::Json);
+    }
+
+    #[test]
+    fn test_config_interchange_format() {
+        let mut config = Config::default();
+        config
+            .set("preferences.interchange_format", "cbor")
+            .unwrap();
+        assert_eq!(config.preferences.interchange_format, Codec::Cbor);
+        assert_eq!(
+            config.get("preferences.interchange_format").unwrap(),
+            "cbor"
+        );
+        assert!(config.set("preferences.interchange_format", "xml").is_err());
+
+        let json = serde_json::to_string(&config).unwrap();
+        let loaded: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.preferences.interchange_format, Codec::Cbor);
     }
 }
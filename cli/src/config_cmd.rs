@@ -4,6 +4,7 @@ use anyhow::Result;
 use colored::Colorize;
 
 use crate::config::{get_config_path, get_legacy_config_path, Config, Preferences};
+use crate::messages::t;
 
 /// Show current configuration
 pub fn show_config() -> Result<()> {
@@ -43,6 +44,16 @@ pub fn show_config() -> Result<()> {
         config.preferences.progress_bars
     );
     println!("  {}: {}", "log_level".cyan(), config.preferences.log_level);
+    println!(
+        "  {}: {}",
+        "interchange_format".cyan(),
+        config.preferences.interchange_format
+    );
+    println!(
+        "  {}: {}",
+        "locale".cyan(),
+        config.preferences.locale.as_deref().unwrap_or("auto")
+    );
 
     if !config.endpoints.is_empty() {
         println!("\n{}", "Configured Endpoints:".yellow().bold());
@@ -185,7 +196,7 @@ pub async fn init_config_interactive() -> Result<()> {
     ];
 
     let default_chain_idx = Select::new()
-        .with_prompt("Select your default chain")
+        .with_prompt(t("config.select_chain"))
         .items(&chains)
         .default(0)
         .interact()?;
@@ -197,7 +208,7 @@ pub async fn init_config_interactive() -> Result<()> {
         .to_string();
 
     let default_endpoint: String = Input::new()
-        .with_prompt("Enter the default RPC endpoint (or press Enter for default)")
+        .with_prompt(t("config.endpoint"))
         .allow_empty(true)
         .interact_text()?;
 
@@ -219,18 +230,18 @@ pub async fn init_config_interactive() -> Result<()> {
     };
 
     let color_output = Confirm::new()
-        .with_prompt("Enable colored output?")
+        .with_prompt(t("config.color_output"))
         .default(true)
         .interact()?;
 
     let progress_bars = Confirm::new()
-        .with_prompt("Enable progress bars?")
+        .with_prompt(t("config.progress_bars"))
         .default(true)
         .interact()?;
 
     let log_levels = vec!["error", "warn", "info", "debug", "trace"];
     let log_level_idx = Select::new()
-        .with_prompt("Select log level")
+        .with_prompt(t("config.log_level"))
         .items(&log_levels)
         .default(2)
         .interact()?;
@@ -247,6 +258,7 @@ pub async fn init_config_interactive() -> Result<()> {
             color_output,
             progress_bars,
             log_level,
+            ..Preferences::default()
         },
     };
 
@@ -308,6 +320,7 @@ mod tests {
                 color_output: true,
                 progress_bars: false,
                 log_level: "debug".to_string(),
+                ..Preferences::default()
             },
             endpoints: std::collections::HashMap::new(),
         };
@@ -408,6 +421,7 @@ mod tests {
             color_output: false,
             progress_bars: true,
             log_level: "trace".to_string(),
+            ..Preferences::default()
         };
 
         assert!(!preferences.color_output);
@@ -436,6 +450,7 @@ mod tests {
                 color_output: false,
                 progress_bars: true,
                 log_level: "warn".to_string(),
+                ..Preferences::default()
             },
             endpoints,
         };
@@ -0,0 +1,627 @@
+//! Solidity contract deployment and interaction on pallet-revive
+
+use crate::transfer::{load_wallet, SignerSource};
+use anyhow::{Context, Result};
+use apex_sdk_revive::abi::{Abi, AbiParam, AbiType, AbiValue, Function};
+use apex_sdk_revive::{ContractEvent, ContractManager, ReviveAdapter};
+use apex_sdk_types::Address;
+use colored::Colorize;
+use std::fs;
+
+/// Options shared by the submitting contract commands
+pub struct SubmitOptions {
+    /// Native value sent with the transaction
+    pub value: u128,
+    /// Weight `ref_time` limit overriding the dry-run estimate
+    pub gas_limit: Option<u64>,
+}
+
+/// Read PolkaVM bytecode from a hex text file or a raw binary file
+fn read_code(path: &str) -> Result<Vec<u8>> {
+    let raw = fs::read(path).with_context(|| format!("Failed to read bytecode '{}'", path))?;
+    match std::str::from_utf8(&raw) {
+        Ok(text) if text.trim().starts_with("0x") => {
+            hex::decode(text.trim().trim_start_matches("0x"))
+                .context("Failed to decode hex bytecode")
+        }
+        _ => Ok(raw),
+    }
+}
+
+/// Load and parse an ABI JSON file
+///
+/// Accepts either a bare ABI array or a compiler artifact with an `abi` field.
+fn read_abi(path: &str) -> Result<Abi> {
+    let json =
+        fs::read_to_string(path).with_context(|| format!("Failed to read ABI '{}'", path))?;
+    let artifact: serde_json::Value =
+        serde_json::from_str(&json).context("ABI file is not valid JSON")?;
+    let abi = match artifact.get("abi") {
+        Some(abi) => abi.to_string(),
+        None => json,
+    };
+    Abi::from_json(&abi).map_err(|e| anyhow::anyhow!("{}", e))
+}
+
+/// Find a function by name, or by canonical signature to pick an overload
+fn find_function<'a>(abi: &'a Abi, function: &str) -> Result<&'a Function> {
+    let found = if function.contains('(') {
+        abi.function_by_signature(function)
+    } else {
+        abi.function(function)
+    };
+    found.ok_or_else(|| anyhow::anyhow!("Function '{}' not found in ABI", function))
+}
+
+/// Parse a `0x`-prefixed 20-byte contract address
+fn parse_contract_address(address: &str) -> Result<Address> {
+    let bytes = parse_hex(address, Some(20)).context("Invalid contract address")?;
+    Ok(Address::evm(format!("0x{}", hex::encode(bytes))))
+}
+
+/// Parse command-line values for `params`
+pub fn parse_args(params: &[AbiParam], args: &[String]) -> Result<Vec<AbiValue>> {
+    if params.len() != args.len() {
+        anyhow::bail!(
+            "Expected {} argument(s) ({}), got {}",
+            params.len(),
+            params
+                .iter()
+                .map(|p| p.kind.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            args.len()
+        );
+    }
+
+    params
+        .iter()
+        .zip(args)
+        .map(|(param, arg)| {
+            let kind = param.abi_type().map_err(|e| anyhow::anyhow!("{}", e))?;
+            parse_value(&kind, arg).with_context(|| {
+                format!("Invalid value '{}' for {} {}", arg, param.kind, param.name)
+            })
+        })
+        .collect()
+}
+
+/// Parse one command-line value of type `kind`
+///
+/// Integers are decimal or `0x` hex, `bytes`/`address` values are hex, and
+/// arrays and tuples are JSON arrays such as `[1,2,3]` or `["0x..",true]`.
+pub fn parse_value(kind: &AbiType, text: &str) -> Result<AbiValue> {
+    let text = text.trim();
+    match kind {
+        AbiType::Uint(bits) => Ok(AbiValue::Uint(parse_uint(text, *bits)?)),
+        AbiType::Int(bits) => Ok(AbiValue::Int(parse_int(text, *bits)?)),
+        AbiType::Address => {
+            let bytes = parse_hex(text, Some(20))?;
+            Ok(AbiValue::Address(bytes.try_into().expect("length checked")))
+        }
+        AbiType::Bool => match text {
+            "true" => Ok(AbiValue::Bool(true)),
+            "false" => Ok(AbiValue::Bool(false)),
+            _ => anyhow::bail!("Expected true or false"),
+        },
+        AbiType::FixedBytes(len) => Ok(AbiValue::FixedBytes(parse_hex(text, Some(*len))?)),
+        AbiType::Bytes => Ok(AbiValue::Bytes(parse_hex(text, None)?)),
+        AbiType::String => Ok(AbiValue::String(text.to_string())),
+        AbiType::Array(_) | AbiType::FixedArray(..) | AbiType::Tuple(_) => {
+            let json: serde_json::Value =
+                serde_json::from_str(text).context("Expected a JSON array")?;
+            parse_json(kind, &json)
+        }
+    }
+}
+
+fn parse_json(kind: &AbiType, json: &serde_json::Value) -> Result<AbiValue> {
+    let items = |expected: Option<usize>| -> Result<&Vec<serde_json::Value>> {
+        let items = json
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Expected a JSON array for {}", kind.canonical()))?;
+        if let Some(expected) = expected.filter(|e| *e != items.len()) {
+            anyhow::bail!(
+                "Expected {} elements for {}, got {}",
+                expected,
+                kind.canonical(),
+                items.len()
+            );
+        }
+        Ok(items)
+    };
+
+    match kind {
+        AbiType::Array(inner) => Ok(AbiValue::Array(
+            items(None)?
+                .iter()
+                .map(|item| parse_json(inner, item))
+                .collect::<Result<_>>()?,
+        )),
+        AbiType::FixedArray(inner, len) => Ok(AbiValue::Array(
+            items(Some(*len))?
+                .iter()
+                .map(|item| parse_json(inner, item))
+                .collect::<Result<_>>()?,
+        )),
+        AbiType::Tuple(types) => Ok(AbiValue::Tuple(
+            types
+                .iter()
+                .zip(items(Some(types.len()))?)
+                .map(|(kind, item)| parse_json(kind, item))
+                .collect::<Result<_>>()?,
+        )),
+        _ => match json {
+            serde_json::Value::String(text) => parse_value(kind, text),
+            serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {
+                parse_value(kind, &json.to_string())
+            }
+            _ => anyhow::bail!("Unexpected JSON value for {}", kind.canonical()),
+        },
+    }
+}
+
+/// Decode `0x`-prefixed hex, optionally requiring an exact byte length
+fn parse_hex(text: &str, len: Option<usize>) -> Result<Vec<u8>> {
+    let digits = text
+        .strip_prefix("0x")
+        .ok_or_else(|| anyhow::anyhow!("Expected 0x-prefixed hex"))?;
+    let bytes = hex::decode(digits).context("Invalid hex")?;
+    if let Some(len) = len.filter(|len| *len != bytes.len()) {
+        anyhow::bail!("Expected {} bytes, got {}", len, bytes.len());
+    }
+    Ok(bytes)
+}
+
+/// Parse an unsigned decimal or `0x` hex integer into a 32-byte word
+fn parse_uint(text: &str, bits: usize) -> Result<[u8; 32]> {
+    let mut word = [0u8; 32];
+    if let Some(digits) = text.strip_prefix("0x") {
+        let padded = if digits.len() % 2 == 1 {
+            format!("0{}", digits)
+        } else {
+            digits.to_string()
+        };
+        let bytes = hex::decode(&padded).context("Invalid hex integer")?;
+        let bytes = &bytes[bytes.iter().take_while(|b| **b == 0).count()..];
+        if bytes.len() > 32 {
+            anyhow::bail!("Value does not fit in uint{}", bits);
+        }
+        word[32 - bytes.len()..].copy_from_slice(bytes);
+    } else {
+        if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+            anyhow::bail!("Expected a decimal or 0x-prefixed integer");
+        }
+        for digit in text.bytes().map(|b| b - b'0') {
+            let mut carry = digit as u16;
+            for byte in word.iter_mut().rev() {
+                let next = *byte as u16 * 10 + carry;
+                *byte = next as u8;
+                carry = next >> 8;
+            }
+            if carry != 0 {
+                anyhow::bail!("Value does not fit in uint{}", bits);
+            }
+        }
+    }
+
+    if word[..32 - bits / 8].iter().any(|b| *b != 0) {
+        anyhow::bail!("Value does not fit in uint{}", bits);
+    }
+    Ok(word)
+}
+
+/// Parse a signed integer into a two's complement 32-byte word
+fn parse_int(text: &str, bits: usize) -> Result<[u8; 32]> {
+    let (negative, magnitude) = match text.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, text),
+    };
+    let mut word = parse_uint(magnitude, 256)?;
+    let negative = negative && word.iter().any(|b| *b != 0);
+    if negative {
+        negate(&mut word);
+    }
+
+    // Everything above the low `bits` must be a sign extension of bit `bits - 1`
+    let width = bits / 8;
+    let fill = if negative { 0xff } else { 0 };
+    let sign_set = word[32 - width] & 0x80 != 0;
+    if word[..32 - width].iter().any(|b| *b != fill) || sign_set != negative {
+        anyhow::bail!("Value does not fit in int{}", bits);
+    }
+    Ok(word)
+}
+
+/// Two's complement negation of a 32-byte word
+fn negate(word: &mut [u8; 32]) {
+    let mut carry = 1u16;
+    for byte in word.iter_mut().rev() {
+        let next = (!*byte) as u16 + carry;
+        *byte = next as u8;
+        carry = next >> 8;
+    }
+}
+
+/// Decimal representation of an unsigned 32-byte word
+fn word_to_decimal(mut word: [u8; 32]) -> String {
+    let mut digits = Vec::new();
+    while word.iter().any(|b| *b != 0) {
+        let mut remainder = 0u16;
+        for byte in word.iter_mut() {
+            let current = (remainder << 8) | *byte as u16;
+            *byte = (current / 10) as u8;
+            remainder = current % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+    }
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.reverse();
+    String::from_utf8(digits).expect("ASCII digits")
+}
+
+/// Human-readable rendering of a decoded value
+pub fn format_value(value: &AbiValue) -> String {
+    let join = |values: &[AbiValue]| {
+        values
+            .iter()
+            .map(format_value)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match value {
+        AbiValue::Uint(word) => word_to_decimal(*word),
+        AbiValue::Int(word) if word[0] & 0x80 != 0 => {
+            let mut magnitude = *word;
+            negate(&mut magnitude);
+            format!("-{}", word_to_decimal(magnitude))
+        }
+        AbiValue::Int(word) => word_to_decimal(*word),
+        AbiValue::Address(address) => format!("0x{}", hex::encode(address)),
+        AbiValue::Bool(value) => value.to_string(),
+        AbiValue::FixedBytes(bytes) | AbiValue::Bytes(bytes) => {
+            format!("0x{}", hex::encode(bytes))
+        }
+        AbiValue::String(value) => format!("{:?}", value),
+        AbiValue::Array(values) => format!("[{}]", join(values)),
+        AbiValue::Tuple(values) => format!("({})", join(values)),
+    }
+}
+
+/// Print decoded values next to the parameters they belong to
+fn print_values(params: &[AbiParam], values: &[AbiValue]) {
+    for (index, (param, value)) in params.iter().zip(values).enumerate() {
+        let label = if param.name.is_empty() {
+            format!("[{}] {}", index, param.kind)
+        } else {
+            format!("{} {}", param.name, param.kind)
+        };
+        println!("  {}: {}", label.dimmed(), format_value(value));
+    }
+}
+
+/// Print emitted logs, decoding those whose signature is in the ABI
+fn print_events(abi: &Abi, events: &[ContractEvent]) {
+    if events.is_empty() {
+        return;
+    }
+
+    println!("\n{}", "Events".cyan().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    for event in events {
+        let known = event.topics.first().and_then(|t| abi.event_by_topic(t));
+        match known.map(|e| (e, e.decode_log(&event.topics, &event.data))) {
+            Some((definition, Ok(values))) => {
+                println!("{} ({})", definition.name.green(), event.contract);
+                print_values(&definition.inputs, &values);
+            }
+            _ => {
+                println!("{} ({})", "Unknown event".yellow(), event.contract);
+                for (index, topic) in event.topics.iter().enumerate() {
+                    println!(
+                        "  {}: 0x{}",
+                        format!("topic{}", index).dimmed(),
+                        hex::encode(topic)
+                    );
+                }
+                println!("  {}: 0x{}", "data".dimmed(), hex::encode(&event.data));
+            }
+        }
+    }
+}
+
+fn spinner(message: &'static str) -> indicatif::ProgressBar {
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.set_message(message);
+    spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+    spinner
+}
+
+async fn connect(endpoint: &str) -> Result<ReviveAdapter> {
+    ReviveAdapter::connect(endpoint)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to connect to endpoint: {}", e))
+}
+
+/// Deploy PolkaVM bytecode with ABI-encoded constructor arguments
+pub async fn deploy(
+    bytecode: &str,
+    abi: &str,
+    args: &[String],
+    endpoint: &str,
+    signer: SignerSource,
+    salt: Option<&str>,
+    options: SubmitOptions,
+) -> Result<()> {
+    let code = read_code(bytecode)?;
+    let abi = read_abi(abi)?;
+    let params = abi
+        .constructor()
+        .map(|c| c.inputs.as_slice())
+        .unwrap_or_default();
+    let constructor_data = abi
+        .encode_constructor(&parse_args(params, args)?)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let salt: [u8; 32] = match salt {
+        Some(salt) => parse_hex(salt, Some(32))
+            .context("Invalid salt")?
+            .try_into()
+            .expect("length checked"),
+        None => {
+            use ::rand::RngCore;
+            let mut salt = [0u8; 32];
+            ::rand::rng().fill_bytes(&mut salt);
+            salt
+        }
+    };
+
+    let wallet = load_wallet(signer)?;
+
+    println!("\n{}", "Deploying Contract".cyan().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    println!("{}: {}", "Bytecode".dimmed(), bytecode);
+    println!("{}: {} bytes", "Code Size".dimmed(), code.len());
+    println!("{}: {}", "Endpoint".dimmed(), endpoint);
+    println!("{}: {}", "Deployer".dimmed(), wallet.address());
+    println!("{}: 0x{}", "Salt".dimmed(), hex::encode(salt));
+    println!();
+
+    let spinner = spinner("Connecting to chain...");
+    let adapter = connect(endpoint).await?;
+    let manager = ContractManager::new(&adapter, wallet.to_subxt_signer());
+
+    spinner.set_message("Deploying and waiting for finalization...");
+    let deployed = manager
+        .deploy_with_events(
+            code,
+            constructor_data,
+            salt,
+            options.value,
+            options.gas_limit,
+        )
+        .await;
+    spinner.finish_and_clear();
+    let (address, events) = deployed.map_err(|e| anyhow::anyhow!("Deployment failed: {}", e))?;
+
+    println!("\n{}", "Contract Deployed".green().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    println!("{}: {}", "Address".cyan(), address);
+    print_events(&abi, &events);
+
+    Ok(())
+}
+
+/// Call a contract function in a transaction
+pub async fn call(
+    address: &str,
+    function: &str,
+    args: &[String],
+    abi: &str,
+    endpoint: &str,
+    signer: SignerSource,
+    options: SubmitOptions,
+) -> Result<()> {
+    let contract = parse_contract_address(address)?;
+    let abi = read_abi(abi)?;
+    let function = find_function(&abi, function)?;
+    let data = function
+        .encode_input(&parse_args(&function.inputs, args)?)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let wallet = load_wallet(signer)?;
+
+    println!("\n{}", "Calling Contract".cyan().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    println!("{}: {}", "Contract".dimmed(), contract);
+    println!("{}: {}", "Function".dimmed(), function.signature());
+    println!("{}: {}", "Endpoint".dimmed(), endpoint);
+    println!("{}: {}", "Caller".dimmed(), wallet.address());
+    println!();
+
+    let spinner = spinner("Connecting to chain...");
+    let adapter = connect(endpoint).await?;
+    let manager = ContractManager::new(&adapter, wallet.to_subxt_signer());
+
+    spinner.set_message("Submitting call and waiting for finalization...");
+    let called = manager
+        .call_with_events(&contract, data, options.value, options.gas_limit)
+        .await;
+    spinner.finish_and_clear();
+    let (return_data, events) = called.map_err(|e| anyhow::anyhow!("Call failed: {}", e))?;
+
+    println!("\n{}", "Call Finalized".green().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    if return_data.is_empty() {
+        println!("{}", "No return data".dimmed());
+    } else {
+        let values = function
+            .decode_output(&return_data)
+            .map_err(|e| anyhow::anyhow!("Failed to decode return data: {}", e))?;
+        println!("{}:", "Returned".cyan());
+        print_values(&function.outputs, &values);
+    }
+    print_events(&abi, &events);
+
+    Ok(())
+}
+
+/// Read contract state through a function and print the decoded result
+pub async fn read(
+    address: &str,
+    function: &str,
+    args: &[String],
+    abi: &str,
+    endpoint: &str,
+    signer: SignerSource,
+) -> Result<()> {
+    let contract = parse_contract_address(address)?;
+    let abi = read_abi(abi)?;
+    let function = find_function(&abi, function)?;
+    let args = parse_args(&function.inputs, args)?;
+
+    let wallet = load_wallet(signer)?;
+
+    let spinner = spinner("Reading contract state...");
+    let adapter = connect(endpoint).await?;
+    let manager = ContractManager::new(&adapter, wallet.to_subxt_signer());
+    let values = manager.read_function(&contract, function, &args).await;
+    spinner.finish_and_clear();
+    let values = values.map_err(|e| anyhow::anyhow!("Read failed: {}", e))?;
+
+    println!("\n{}", function.signature().cyan().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    println!("{}: {}", "Contract".dimmed(), contract);
+    print_values(&function.outputs, &values);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABI: &str = r#"[
+        {"type": "constructor", "inputs": [{"name": "supply", "type": "uint256"}]},
+        {
+            "type": "function",
+            "name": "balanceOf",
+            "inputs": [{"name": "owner", "type": "address"}],
+            "outputs": [{"name": "", "type": "uint256"}],
+            "stateMutability": "view"
+        },
+        {
+            "type": "event",
+            "name": "Transfer",
+            "anonymous": false,
+            "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ]
+        }
+    ]"#;
+
+    #[test]
+    fn test_parse_value_integers() {
+        assert_eq!(
+            parse_value(&AbiType::Uint(256), "1000").unwrap(),
+            AbiValue::uint(1000)
+        );
+        assert_eq!(
+            parse_value(&AbiType::Uint(256), "0x3e8").unwrap(),
+            AbiValue::uint(1000)
+        );
+        assert_eq!(
+            parse_value(&AbiType::Int(256), "-5").unwrap(),
+            AbiValue::int(-5)
+        );
+        assert_eq!(
+            parse_value(&AbiType::Int(8), "-128").unwrap(),
+            AbiValue::int(-128)
+        );
+        assert_eq!(
+            parse_value(&AbiType::Int(8), "-0").unwrap(),
+            AbiValue::int(0)
+        );
+
+        assert!(parse_value(&AbiType::Uint(8), "256").is_err());
+        assert!(parse_value(&AbiType::Int(8), "128").is_err());
+        assert!(parse_value(&AbiType::Int(8), "-129").is_err());
+        assert!(parse_value(&AbiType::Uint(256), "-1").is_err());
+        assert!(parse_value(&AbiType::Uint(256), &"9".repeat(80)).is_err());
+
+        let max = format!("0x{}", "ff".repeat(32));
+        let value = parse_value(&AbiType::Uint(256), &max).unwrap();
+        assert_eq!(
+            format_value(&value),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+    }
+
+    #[test]
+    fn test_parse_value_composites() {
+        let kind = AbiType::Tuple(vec![
+            AbiType::Address,
+            AbiType::Array(Box::new(AbiType::Uint(64))),
+            AbiType::Bool,
+        ]);
+        let address = format!("0x{}", "11".repeat(20));
+        let value = parse_value(&kind, &format!(r#"["{}", [1, "0x02"], true]"#, address)).unwrap();
+        assert_eq!(
+            value,
+            AbiValue::Tuple(vec![
+                AbiValue::Address([0x11; 20]),
+                AbiValue::Array(vec![AbiValue::uint(1), AbiValue::uint(2)]),
+                AbiValue::Bool(true),
+            ])
+        );
+        assert_eq!(format_value(&value), format!("({}, [1, 2], true)", address));
+
+        let fixed = AbiType::FixedArray(Box::new(AbiType::Bool), 2);
+        assert!(parse_value(&fixed, "[true]").is_err());
+        assert!(parse_value(&AbiType::FixedBytes(4), "0x0102").is_err());
+        assert!(parse_value(&AbiType::Address, "0x1234").is_err());
+    }
+
+    #[test]
+    fn test_parse_args_and_event_decoding() {
+        let abi = Abi::from_json(ABI).unwrap();
+        let params = &abi.constructor().unwrap().inputs;
+        assert!(parse_args(params, &[]).is_err());
+        assert_eq!(
+            parse_args(params, &["42".to_string()]).unwrap(),
+            vec![AbiValue::uint(42)]
+        );
+
+        let function = find_function(&abi, "balanceOf(address)").unwrap();
+        assert_eq!(function.name, "balanceOf");
+        assert!(find_function(&abi, "transfer").is_err());
+
+        let transfer = &abi.events()[0];
+        let mut from = [0u8; 32];
+        from[12..].copy_from_slice(&[0xaa; 20]);
+        let mut to = [0u8; 32];
+        to[12..].copy_from_slice(&[0xbb; 20]);
+        let topics = [transfer.topic(), from, to];
+        assert_eq!(
+            abi.event_by_topic(&topics[0]).map(|e| e.name.as_str()),
+            Some("Transfer")
+        );
+
+        let mut data = [0u8; 32];
+        data[31] = 7;
+        let values = transfer.decode_log(&topics, &data).unwrap();
+        assert_eq!(
+            values,
+            vec![
+                AbiValue::Address([0xaa; 20]),
+                AbiValue::Address([0xbb; 20]),
+                AbiValue::uint(7),
+            ]
+        );
+        assert!(transfer.decode_log(&topics[..2], &data).is_err());
+    }
+}
@@ -5,6 +5,8 @@ use apex_sdk_types::Chain;
 use colored::Colorize;
 use std::path::Path;
 
+use crate::messages::{t, tf};
+
 /// Deploy a contract
 pub async fn deploy_contract(
     contract_path: &str,
@@ -103,8 +105,8 @@ async fn deploy_substrate_contract(
     let (signer_name, mnemonic) = if let Some(name) = account_name {
         spinner.set_message(format!("Loading account '{}'...", name));
 
-        let password = rpassword::prompt_password("Enter account password: ")
-            .context("Failed to read password")?;
+        let password = rpassword::prompt_password(t("prompt.account_password"))
+            .context(t("error.read_password"))?;
 
         let keystore_path = crate::keystore::get_keystore_path()?;
         let mut keystore = crate::keystore::Keystore::load(&keystore_path)?;
@@ -336,8 +338,8 @@ async fn deploy_revive_contract(
 
     let mnemonic = if let Some(name) = account_name {
         let password =
-            rpassword::prompt_password(format!("Enter password for account '{}': ", name))
-                .context("Failed to read password")?;
+            rpassword::prompt_password(tf("prompt.account_password_for", &[("name", &name)]))
+                .context(t("error.read_password"))?;
 
         let keystore_path = crate::keystore::get_keystore_path()?;
         let mut keystore = crate::keystore::Keystore::load(&keystore_path)?;
@@ -38,6 +38,8 @@ use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use zeroize::Zeroize;
 
+use crate::messages::tf;
+
 const NONCE_SIZE: usize = 12;
 const KEYSTORE_VERSION: u32 = 1;
 
@@ -145,7 +147,7 @@ impl Keystore {
     }
 
     /// Validate password strength
-    fn validate_password(password: &str) -> Result<()> {
+    pub fn validate_password(password: &str) -> Result<()> {
         if password.len() < MIN_PASSWORD_LENGTH {
             anyhow::bail!(
                 "Password must be at least {} characters long (current: {})",
@@ -269,7 +271,7 @@ impl Keystore {
             .accounts
             .iter()
             .find(|a| a.name == name)
-            .ok_or_else(|| anyhow::anyhow!("Account '{}' not found", name))?;
+            .ok_or_else(|| anyhow::anyhow!(tf("account.not_found", &[("name", &name)])))?;
 
         // Attempt decryption
         match decrypt_data(
@@ -318,7 +320,7 @@ impl Keystore {
         self.accounts.retain(|a| a.name != name);
 
         if self.accounts.len() == initial_len {
-            anyhow::bail!("Account '{}' not found", name);
+            anyhow::bail!(tf("account.not_found", &[("name", &name)]));
         }
 
         Ok(())
@@ -1,7 +1,7 @@
 //! Apex SDK CLI tool
 
 use anyhow::Context;
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, Parser, Subcommand};
 use std::path::{Path, PathBuf};
 
 mod account;
@@ -9,13 +9,22 @@ mod balance;
 mod completions;
 mod config;
 mod config_cmd;
+mod contract;
 mod deploy;
 mod keystore;
+mod messages;
+mod metrics;
+mod transfer;
+mod wallet;
 
 #[derive(Parser)]
 #[command(name = "apex")]
 #[command(about = "Apex SDK CLI - Unified Rust SDK for Substrate & EVM", long_about = None)]
 pub struct Cli {
+    /// Language of prompts and messages, e.g. pt-BR (default: APEX_LOCALE,
+    /// preferences.locale, then LANG)
+    #[arg(long, global = true)]
+    locale: Option<String>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -59,11 +68,50 @@ enum Commands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Transfer balance to another account on a Substrate chain
+    #[command(group(ArgGroup::new("signer").required(true)))]
+    Transfer {
+        /// Recipient SS58 address
+        to: String,
+        /// Amount in the chain's smallest unit (e.g. Planck)
+        amount: u128,
+        /// RPC endpoint URL
+        #[arg(short, long)]
+        endpoint: String,
+        /// Keystore account to send from
+        #[arg(short, long, group = "signer")]
+        account: Option<String>,
+        /// Wallet from 'apex wallet' to send from
+        #[arg(short, long, group = "signer")]
+        wallet: Option<String>,
+        /// Mnemonic phrase to send from
+        #[arg(long, group = "signer")]
+        mnemonic: Option<String>,
+        /// Hex-encoded 32-byte seed to send from
+        #[arg(long, group = "signer")]
+        seed: Option<String>,
+        /// Fee strategy (fast, normal, slow)
+        #[arg(short, long, default_value = "normal")]
+        strategy: String,
+        /// Submit without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Deploy, call and read Solidity contracts on pallet-revive
+    Contract {
+        #[command(subcommand)]
+        action: ContractCommands,
+    },
     /// Manage accounts and wallets
     Account {
         #[command(subcommand)]
         action: AccountCommands,
     },
+    /// Manage Substrate wallets in the encrypted wallet keystore
+    Wallet {
+        #[command(subcommand)]
+        action: WalletCommands,
+    },
     /// Manage configuration
     Config {
         #[command(subcommand)]
@@ -74,6 +122,11 @@ enum Commands {
         #[command(subcommand)]
         action: ChainCommands,
     },
+    /// Export metrics snapshots
+    Metrics {
+        #[command(subcommand)]
+        action: MetricsCommands,
+    },
     /// Generate shell completions
     Completions {
         /// Shell to generate completions for (bash, zsh, fish, powershell, elvish)
@@ -142,6 +195,191 @@ enum AccountCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum ContractCommands {
+    /// Deploy PolkaVM bytecode with constructor arguments
+    #[command(group(ArgGroup::new("signer").required(true)))]
+    Deploy {
+        /// Path to the PolkaVM bytecode (hex text or binary)
+        bytecode: String,
+        /// Path to the ABI JSON (bare array or compiler artifact)
+        #[arg(long)]
+        abi: String,
+        /// Constructor arguments in ABI order
+        args: Vec<String>,
+        /// RPC endpoint URL
+        #[arg(short, long)]
+        endpoint: String,
+        /// Keystore account to deploy from
+        #[arg(short, long, group = "signer")]
+        account: Option<String>,
+        /// Wallet from 'apex wallet' to deploy from
+        #[arg(short, long, group = "signer")]
+        wallet: Option<String>,
+        /// Mnemonic phrase to deploy from
+        #[arg(long, group = "signer")]
+        mnemonic: Option<String>,
+        /// Hex-encoded 32-byte seed to deploy from
+        #[arg(long, group = "signer")]
+        seed: Option<String>,
+        /// Hex-encoded 32-byte salt (random if omitted)
+        #[arg(long)]
+        salt: Option<String>,
+        /// Value to endow the contract with, in the smallest unit
+        #[arg(long, default_value = "0")]
+        value: u128,
+        /// Weight limit overriding the dry-run estimate
+        #[arg(long)]
+        gas_limit: Option<u64>,
+    },
+    /// Call a contract function in a transaction
+    #[command(group(ArgGroup::new("signer").required(true)))]
+    Call {
+        /// Contract address (0x-prefixed, 20 bytes)
+        address: String,
+        /// Function name or canonical signature, e.g. transfer(address,uint256)
+        function: String,
+        /// Function arguments in ABI order
+        args: Vec<String>,
+        /// Path to the ABI JSON (bare array or compiler artifact)
+        #[arg(long)]
+        abi: String,
+        /// RPC endpoint URL
+        #[arg(short, long)]
+        endpoint: String,
+        /// Keystore account to call from
+        #[arg(short, long, group = "signer")]
+        account: Option<String>,
+        /// Wallet from 'apex wallet' to call from
+        #[arg(short, long, group = "signer")]
+        wallet: Option<String>,
+        /// Mnemonic phrase to call from
+        #[arg(long, group = "signer")]
+        mnemonic: Option<String>,
+        /// Hex-encoded 32-byte seed to call from
+        #[arg(long, group = "signer")]
+        seed: Option<String>,
+        /// Value to send with the call, in the smallest unit
+        #[arg(long, default_value = "0")]
+        value: u128,
+        /// Weight limit overriding the dry-run estimate
+        #[arg(long)]
+        gas_limit: Option<u64>,
+    },
+    /// Read contract state through a function
+    #[command(group(ArgGroup::new("signer").required(true)))]
+    Read {
+        /// Contract address (0x-prefixed, 20 bytes)
+        address: String,
+        /// Function name or canonical signature, e.g. balanceOf(address)
+        function: String,
+        /// Function arguments in ABI order
+        args: Vec<String>,
+        /// Path to the ABI JSON (bare array or compiler artifact)
+        #[arg(long)]
+        abi: String,
+        /// RPC endpoint URL
+        #[arg(short, long)]
+        endpoint: String,
+        /// Keystore account to read as
+        #[arg(short, long, group = "signer")]
+        account: Option<String>,
+        /// Wallet from 'apex wallet' to read as
+        #[arg(short, long, group = "signer")]
+        wallet: Option<String>,
+        /// Mnemonic phrase to read as
+        #[arg(long, group = "signer")]
+        mnemonic: Option<String>,
+        /// Hex-encoded 32-byte seed to read as
+        #[arg(long, group = "signer")]
+        seed: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum WalletCommands {
+    /// Generate a wallet and show its mnemonic
+    New {
+        /// Store the wallet under this name
+        #[arg(short, long)]
+        name: Option<String>,
+        /// Key type (sr25519, ed25519)
+        #[arg(short = 't', long, default_value = "sr25519")]
+        key_type: String,
+        /// Network for the SS58 address: chain name or SS58 prefix
+        #[arg(long, default_value = "substrate")]
+        network: String,
+        /// Derivation path, e.g. //polkadot//0
+        #[arg(long)]
+        path: Option<String>,
+        /// Mnemonic length (12 or 24)
+        #[arg(long, default_value = "12")]
+        words: usize,
+        /// Show the address as a QR code
+        #[arg(long)]
+        qr: bool,
+    },
+    /// Import a wallet from a mnemonic or a Polkadot-JS JSON file
+    #[command(group(ArgGroup::new("source")))]
+    Import {
+        /// Wallet name
+        name: String,
+        /// Mnemonic phrase (prompted for if neither --mnemonic nor --json is given)
+        #[arg(long, group = "source")]
+        mnemonic: Option<String>,
+        /// Polkadot-JS JSON keystore file
+        #[arg(long, group = "source")]
+        json: Option<String>,
+        /// Key type (sr25519, ed25519)
+        #[arg(short = 't', long, default_value = "sr25519")]
+        key_type: String,
+        /// Network for the SS58 address: chain name or SS58 prefix
+        #[arg(long)]
+        network: Option<String>,
+        /// Derivation path, e.g. //polkadot//0
+        #[arg(long)]
+        path: Option<String>,
+    },
+    /// List stored wallets
+    List,
+    /// Export a wallet as a Polkadot-JS JSON file
+    Export {
+        /// Wallet name
+        name: String,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Back up all wallets to one file, still encrypted
+    Backup {
+        /// Output file (default: apex-wallets.<format>)
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Encoding: scale, json, cbor (default: preferences.interchange_format)
+        #[arg(long)]
+        format: Option<String>,
+    },
+    /// Restore wallets from a backup file
+    Restore {
+        /// Backup file
+        file: String,
+        /// Encoding: scale, json, cbor (default: preferences.interchange_format)
+        #[arg(long)]
+        format: Option<String>,
+    },
+    /// Show a wallet's address, public key and QR code
+    Inspect {
+        /// Wallet name or SS58 address
+        target: String,
+        /// Show the address for this network: chain name or SS58 prefix
+        #[arg(long)]
+        network: Option<String>,
+        /// Show the address as a QR code
+        #[arg(long)]
+        qr: bool,
+    },
+}
+
 #[derive(Subcommand)]
 enum ConfigCommands {
     /// Show current configuration
@@ -189,12 +427,68 @@ enum ChainCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum MetricsCommands {
+    /// Write the current metrics in Prometheus text format to a file
+    Dump {
+        /// Output file for the Prometheus text exposition
+        #[arg(short, long, default_value = "metrics.prom")]
+        out: PathBuf,
+        /// Also write profiler spans and aggregates to this JSON file
+        #[arg(long)]
+        json: Option<PathBuf>,
+        /// Endpoint to probe before the snapshot (repeatable)
+        #[arg(short, long)]
+        endpoint: Vec<String>,
+        /// Uptime history file to update, for rolling uptime across dumps
+        #[arg(long)]
+        uptime_state: Option<PathBuf>,
+    },
+    /// Generate a Grafana dashboard and Prometheus rules for the SDK metrics
+    Dashboards {
+        /// Directory to write the dashboard and rule files to
+        #[arg(short, long, default_value = ".")]
+        out_dir: PathBuf,
+        /// Dashboard title
+        #[arg(long, default_value = "Apex SDK")]
+        title: String,
+    },
+}
+
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> std::process::ExitCode {
     tracing_subscriber::fmt::init();
 
     let cli = Cli::parse();
+    init_messages(cli.locale.as_deref());
+
+    match run(cli).await {
+        Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(e) => {
+            messages::report_error(&e);
+            std::process::ExitCode::FAILURE
+        }
+    }
+}
+
+/// Select the message catalog for `--locale` or the configured locale
+fn init_messages(flag: Option<&str>) {
+    let preference = config::get_config_path()
+        .and_then(|path| config::Config::load(&path))
+        .ok()
+        .and_then(|config| config.preferences.locale);
+    let locale = messages::select_locale(flag, preference.as_deref());
+
+    match messages::Catalog::load(&locale) {
+        Ok(catalog) => {
+            tracing::debug!("Using {} messages", catalog.locale());
+            messages::init(catalog);
+        }
+        Err(e) => tracing::warn!("Using English messages: {:#}", e),
+    }
+}
 
+async fn run(cli: Cli) -> anyhow::Result<()> {
     match cli.command {
         Commands::New { name, template } => {
             print_apex_banner();
@@ -226,6 +520,80 @@ async fn main() -> anyhow::Result<()> {
             }
             deploy::deploy_contract(&contract, &chain, &endpoint, account, dry_run).await?;
         }
+        Commands::Transfer {
+            to,
+            amount,
+            endpoint,
+            account,
+            wallet,
+            mnemonic,
+            seed,
+            strategy,
+            yes,
+        } => {
+            let signer = signer_source(account, wallet, mnemonic, seed);
+            transfer::transfer(&to, amount, &endpoint, signer, &strategy, yes).await?;
+        }
+        Commands::Contract { action } => match action {
+            ContractCommands::Deploy {
+                bytecode,
+                abi,
+                args,
+                endpoint,
+                account,
+                wallet,
+                mnemonic,
+                seed,
+                salt,
+                value,
+                gas_limit,
+            } => {
+                let signer = signer_source(account, wallet, mnemonic, seed);
+                let options = contract::SubmitOptions { value, gas_limit };
+                contract::deploy(
+                    &bytecode,
+                    &abi,
+                    &args,
+                    &endpoint,
+                    signer,
+                    salt.as_deref(),
+                    options,
+                )
+                .await?;
+            }
+            ContractCommands::Call {
+                address,
+                function,
+                args,
+                abi,
+                endpoint,
+                account,
+                wallet,
+                mnemonic,
+                seed,
+                value,
+                gas_limit,
+            } => {
+                let signer = signer_source(account, wallet, mnemonic, seed);
+                let options = contract::SubmitOptions { value, gas_limit };
+                contract::call(&address, &function, &args, &abi, &endpoint, signer, options)
+                    .await?;
+            }
+            ContractCommands::Read {
+                address,
+                function,
+                args,
+                abi,
+                endpoint,
+                account,
+                wallet,
+                mnemonic,
+                seed,
+            } => {
+                let signer = signer_source(account, wallet, mnemonic, seed);
+                contract::read(&address, &function, &args, &abi, &endpoint, signer).await?;
+            }
+        },
         Commands::Account { action } => match action {
             AccountCommands::Generate { account_type, name } => {
                 println!("🔑 Generating new {} account...", account_type);
@@ -256,6 +624,54 @@ async fn main() -> anyhow::Result<()> {
                 balance::get_balance(&address, &chain, &endpoint).await?;
             }
         },
+        Commands::Wallet { action } => match action {
+            WalletCommands::New {
+                name,
+                key_type,
+                network,
+                path,
+                words,
+                qr,
+            } => {
+                wallet::new_wallet(name, &key_type, &network, path.as_deref(), words, qr)?;
+            }
+            WalletCommands::Import {
+                name,
+                mnemonic,
+                json,
+                key_type,
+                network,
+                path,
+            } => {
+                wallet::import_wallet(
+                    &name,
+                    mnemonic,
+                    json.as_deref(),
+                    &key_type,
+                    network.as_deref(),
+                    path.as_deref(),
+                )?;
+            }
+            WalletCommands::List => {
+                wallet::list_wallets()?;
+            }
+            WalletCommands::Export { name, output } => {
+                wallet::export_wallet(&name, output.as_deref())?;
+            }
+            WalletCommands::Backup { output, format } => {
+                wallet::backup_wallets(output.as_deref(), format.as_deref())?;
+            }
+            WalletCommands::Restore { file, format } => {
+                wallet::restore_wallets(&file, format.as_deref())?;
+            }
+            WalletCommands::Inspect {
+                target,
+                network,
+                qr,
+            } => {
+                wallet::inspect_wallet(&target, network.as_deref(), qr)?;
+            }
+        },
         Commands::Config { action } => match action {
             ConfigCommands::Show => {
                 config_cmd::show_config()?;
@@ -290,6 +706,19 @@ async fn main() -> anyhow::Result<()> {
                 check_chain_health(&endpoint).await?;
             }
         },
+        Commands::Metrics { action } => match action {
+            MetricsCommands::Dump {
+                out,
+                json,
+                endpoint,
+                uptime_state,
+            } => {
+                metrics::dump(&out, json.as_deref(), &endpoint, uptime_state.as_deref()).await?;
+            }
+            MetricsCommands::Dashboards { out_dir, title } => {
+                metrics::dashboards(&out_dir, &title)?;
+            }
+        },
         Commands::Completions { shell } => {
             completions::generate_completions(&shell)?;
             eprintln!("\n# Installation instructions:");
@@ -324,6 +753,22 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Signer selected by the mutually exclusive `--account`/`--wallet`/`--mnemonic`/`--seed` flags
+fn signer_source(
+    account: Option<String>,
+    wallet: Option<String>,
+    mnemonic: Option<String>,
+    seed: Option<String>,
+) -> transfer::SignerSource {
+    match (account, wallet, mnemonic, seed) {
+        (Some(name), ..) => transfer::SignerSource::Keystore(name),
+        (_, Some(name), ..) => transfer::SignerSource::Wallet(name),
+        (_, _, Some(mnemonic), _) => transfer::SignerSource::Mnemonic(mnemonic),
+        (_, _, _, Some(seed)) => transfer::SignerSource::Seed(seed),
+        (None, None, None, None) => unreachable!("clap requires a signer"),
+    }
+}
+
 fn print_apex_banner() {
     println!(
         r#"
@@ -776,32 +1221,25 @@ async fn run_tests(filter: Option<String>) -> anyhow::Result<()> {
 }
 
 fn list_chains() {
-    println!("\n   Substrate Mainnets:");
-    println!("     • polkadot    - Polkadot Relay Chain");
-    println!("     • kusama      - Kusama Relay Chain");
-
-    println!("\n   Substrate Testnets:");
-    println!("     • paseo       - Paseo Testnet (Default)");
-    println!("     • westend     - Westend Testnet");
-
-    println!("\n   Parachains:");
-    println!("     • moonbeam    - Moonbeam (EVM-compatible)");
-    println!("     • astar       - Astar Multi-VM");
-    println!("     • acala       - Acala DeFi Hub");
-    println!("     • phala       - Phala Privacy Cloud");
-    println!("     • bifrost     - Bifrost Liquid Staking");
-
-    println!("\n   EVM Mainnets:");
-    println!("     • ethereum    - Ethereum Mainnet");
-    println!("     • bsc         - Binance Smart Chain");
-    println!("     • polygon     - Polygon (Matic)");
-    println!("     • avalanche   - Avalanche C-Chain");
-    println!("     • arbitrum    - Arbitrum One (L2)");
-    println!("     • optimism    - Optimism (L2)");
-    println!("     • zksync      - zkSync Era (L2)");
-
-    println!("\n   EVM Testnets:");
-    println!("     • sepolia     - Ethereum Sepolia");
+    let registry = apex_sdk_types::ChainRegistry::new();
+    let groups = [
+        ("Substrate Mainnets", false, false),
+        ("Substrate Testnets", false, true),
+        ("EVM Mainnets", true, false),
+        ("EVM Testnets", true, true),
+    ];
+
+    for (title, evm, testnet) in groups {
+        println!("\n   {}:", title);
+        for info in registry.chains().iter().filter(|info| {
+            (info.chain_type == apex_sdk_types::ChainType::Evm) == evm && info.testnet == testnet
+        }) {
+            println!(
+                "     • {:<20} - {} ({}, {} decimals)",
+                info.id, info.name, info.token_symbol, info.token_decimals
+            );
+        }
+    }
 
     println!("\n   Use 'apex config show' to see configured endpoints");
 }
@@ -813,6 +1251,24 @@ async fn get_chain_info(chain: &str, endpoint: &str) -> anyhow::Result<()> {
     println!("{}", "═══════════════════════════════════════".dimmed());
     println!("{}: {}", "Chain".dimmed(), chain);
     println!("{}: {}", "Endpoint".dimmed(), endpoint);
+
+    let registry = apex_sdk_types::ChainRegistry::new();
+    if let Some(info) = registry.get(chain) {
+        println!(
+            "{}: {} ({} decimals)",
+            "Token".dimmed(),
+            info.token_symbol,
+            info.token_decimals
+        );
+        if let Some(prefix) = info.ss58_prefix {
+            println!("{}: {}", "SS58 Prefix".dimmed(), prefix);
+        }
+        println!(
+            "{}: {}s",
+            "Block Time".dimmed(),
+            info.block_time().as_secs_f64()
+        );
+    }
     println!();
 
     let spinner = indicatif::ProgressBar::new_spinner();
@@ -899,17 +1355,15 @@ async fn get_chain_info(chain: &str, endpoint: &str) -> anyhow::Result<()> {
         );
 
         // Determine network name from chain ID
-        let network_name = match chain_id {
-            1 => "Ethereum Mainnet",
-            5 => "Goerli Testnet",
-            11155111 => "Sepolia Testnet",
-            137 => "Polygon Mainnet",
-            80001 => "Polygon Mumbai",
-            56 => "BSC Mainnet",
-            97 => "BSC Testnet",
-            43114 => "Avalanche C-Chain",
-            43113 => "Avalanche Fuji",
-            _ => "Unknown Network",
+        let network_name = match registry.by_evm_chain_id(chain_id) {
+            Some(info) => info.name.as_str(),
+            None => match chain_id {
+                5 => "Goerli Testnet",
+                80001 => "Polygon Mumbai",
+                97 => "BSC Testnet",
+                43113 => "Avalanche Fuji",
+                _ => "Unknown Network",
+            },
         };
 
         println!("\n{}: {}", "Network".green().bold(), network_name);
@@ -919,8 +1373,9 @@ async fn get_chain_info(chain: &str, endpoint: &str) -> anyhow::Result<()> {
 }
 
 async fn check_chain_health(endpoint: &str) -> anyhow::Result<()> {
+    use apex_sdk_metrics::probe::{META_BEST_BLOCK, META_CHAIN, META_FINALIZED_BLOCK, META_PEERS};
+    use apex_sdk_metrics::{EndpointProbe, HealthStatus};
     use colored::Colorize;
-    use std::time::Instant;
 
     println!("\n{}", "🏥 Chain Health Check".cyan().bold());
     println!("{}", "═══════════════════════════════════════".dimmed());
@@ -933,57 +1388,48 @@ async fn check_chain_health(endpoint: &str) -> anyhow::Result<()> {
 
     // Determine chain type
     let is_substrate = endpoint.starts_with("ws://") || endpoint.starts_with("wss://");
-
-    if is_substrate {
-        // Substrate health check
-        use subxt::{OnlineClient, PolkadotConfig};
-
-        let start = Instant::now();
-        let api = OnlineClient::<PolkadotConfig>::from_url(endpoint)
-            .await
-            .context("Failed to connect to Substrate endpoint")?;
-
-        spinner.set_message("Fetching latest block...");
-
-        // Try to get latest block as a health check
-        let block = api.blocks().at_latest().await?;
-        let latency = start.elapsed();
-
-        spinner.finish_and_clear();
-
-        println!("{}", "Connection Successful".green().bold());
-        println!();
-        println!("{}", "Health Metrics:".yellow().bold());
-        println!("  {}: {}ms", "Latency".cyan(), latency.as_millis());
-        println!("  {}: {}", "Latest Block".dimmed(), block.number());
-        println!("  {}: Healthy", "Status".green().bold());
+    let probe = if is_substrate {
+        EndpointProbe::substrate(endpoint)
     } else {
-        // EVM health check
-        use alloy::providers::{Provider, ProviderBuilder};
-
-        let start = Instant::now();
-        let provider =
-            ProviderBuilder::new().connect_http(endpoint.parse().context("Invalid endpoint URL")?);
-
-        spinner.set_message("Fetching chain data...");
+        EndpointProbe::evm(endpoint)
+    };
 
-        // Get block number as a health check
-        let block_number = provider.get_block_number().await?;
+    let health = probe.check().await;
+    spinner.finish_and_clear();
 
-        // Try to get chain ID
-        let chain_id = provider.get_chain_id().await?;
+    if health.status == HealthStatus::Unhealthy {
+        anyhow::bail!(
+            "Endpoint is unhealthy: {}",
+            health.message.unwrap_or_default()
+        );
+    }
 
-        let latency = start.elapsed();
+    let chain_label = if is_substrate { "Chain" } else { "Chain ID" };
 
-        spinner.finish_and_clear();
+    println!("{}", "Connection Successful".green().bold());
+    println!();
+    println!("{}", "Health Metrics:".yellow().bold());
+    if let Some(latency) = health.response_time_ms {
+        println!("  {}: {}ms", "Latency".cyan(), latency);
+    }
+    for (label, key) in [
+        (chain_label, META_CHAIN),
+        ("Latest Block", META_BEST_BLOCK),
+        ("Finalized Block", META_FINALIZED_BLOCK),
+        ("Peers", META_PEERS),
+    ] {
+        if let Some(value) = health.metadata.get(key) {
+            println!("  {}: {}", label.dimmed(), value);
+        }
+    }
 
-        println!("{}", "Connection Successful".green().bold());
-        println!();
-        println!("{}", "Health Metrics:".yellow().bold());
-        println!("  {}: {}ms", "Latency".cyan(), latency.as_millis());
-        println!("  {}: {}", "Latest Block".dimmed(), block_number);
-        println!("  {}: {}", "Chain ID".dimmed(), chain_id);
-        println!("  {}: Healthy", "Status".green().bold());
+    match health.status {
+        HealthStatus::Healthy => println!("  {}: Healthy", "Status".green().bold()),
+        _ => println!(
+            "  {}: Degraded ({})",
+            "Status".yellow().bold(),
+            health.message.unwrap_or_default()
+        ),
     }
 
     Ok(())
@@ -0,0 +1,421 @@
+//! User-facing CLI messages
+//!
+//! Prompts, summaries and remediation hints are looked up by key in a
+//! [`Catalog`] for the selected locale instead of being written inline, so
+//! operator tooling built on the CLI can ship translations. Keys are dotted
+//! names such as `transfer.confirm`; remediation hints are keyed by the SDK
+//! error code (`APEX-1210`) of the error that ended the command.
+//!
+//! English is built in. Other locales are JSON objects of key to template in
+//! `<config dir>/apex-sdk/locales/<locale>.json`; keys they leave out fall
+//! back to English. Templates take `{name}` placeholders.
+//!
+//! Transaction summaries shown before signing use the SDK's `summary.*`
+//! keys (see [`apex_sdk_substrate::summary::MESSAGES`]), which locale files
+//! can translate like any other key.
+
+use anyhow::{Context, Result};
+use apex_sdk_substrate::TransactionSummary;
+use apex_sdk_types::{ErrorCode, HasErrorCode};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Locale used when none is selected, and for keys a catalog lacks
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Built-in English messages
+const EN: &[(&str, &str)] = &[
+    ("common.cancelled", "Cancelled."),
+    ("common.unknown", "unknown"),
+    ("common.raw_units", "{amount} raw units"),
+    ("progress.connecting", "Connecting to chain..."),
+    ("progress.estimating_fee", "Estimating fee..."),
+    ("prompt.password", "Enter password: "),
+    ("prompt.account_password", "Enter account password: "),
+    (
+        "prompt.account_password_for",
+        "Enter password for account '{name}': ",
+    ),
+    ("prompt.wallet_password", "Enter wallet password: "),
+    ("prompt.new_account_password", "Enter password to encrypt account: "),
+    ("prompt.new_wallet_password", "Enter password to encrypt wallet: "),
+    ("prompt.confirm_password", "Confirm password: "),
+    ("prompt.json_password", "Enter JSON file password: "),
+    ("prompt.mnemonic", "Enter mnemonic phrase: "),
+    ("error.prefix", "Error"),
+    ("error.hint", "Hint"),
+    ("error.read_password", "Failed to read password"),
+    ("error.read_mnemonic", "Failed to read mnemonic"),
+    ("error.password_mismatch", "Passwords do not match"),
+    (
+        "error.password_too_short",
+        "Password must be at least 8 characters long",
+    ),
+    ("account.not_found", "Account '{name}' not found"),
+    ("wallet.not_found", "Wallet '{name}' not found"),
+    ("wallet.exists", "Wallet '{name}' already exists"),
+    ("config.select_chain", "Select your default chain"),
+    (
+        "config.endpoint",
+        "Enter the default RPC endpoint (or press Enter for default)",
+    ),
+    ("config.color_output", "Enable colored output?"),
+    ("config.progress_bars", "Enable progress bars?"),
+    ("config.log_level", "Select log level"),
+    ("label.endpoint", "Endpoint"),
+    ("label.from", "From"),
+    ("label.to", "To"),
+    ("label.amount", "Amount"),
+    ("label.transaction", "Transaction"),
+    ("label.block", "Block"),
+    ("fee.title", "Fee Estimate"),
+    ("fee.strategy", "Strategy"),
+    ("fee.base_fee", "Base Fee"),
+    ("fee.length_fee", "Length Fee"),
+    ("fee.weight_fee", "Weight Fee"),
+    ("fee.fee_multiplier", "Fee Multiplier"),
+    ("fee.margin", "Margin"),
+    ("fee.tip", "Tip"),
+    ("fee.congestion", "Congestion"),
+    (
+        "fee.congestion_value",
+        "{level} ({fullness}% full, x{multiplier})",
+    ),
+    ("fee.total", "Total Fee"),
+    ("fee.paid", "Fee Paid"),
+    ("fee.refunded", "Fee Refunded"),
+    ("transfer.title", "Balance Transfer"),
+    (
+        "transfer.unknown_strategy",
+        "Unknown fee strategy '{strategy}'. Expected one of: fast, normal, slow",
+    ),
+    ("transfer.invalid_recipient", "Invalid recipient address"),
+    (
+        "transfer.connect_failed",
+        "Failed to connect to Substrate endpoint",
+    ),
+    ("transfer.estimate_failed", "Failed to estimate fee"),
+    ("transfer.balance_failed", "Failed to fetch sender balance"),
+    ("transfer.properties_failed", "Failed to fetch chain properties"),
+    ("transfer.summary_failed", "Failed to describe the transfer"),
+    (
+        "transfer.insufficient_balance",
+        "Insufficient balance: {available} raw units available, {required} needed for amount and fee",
+    ),
+    ("transfer.confirm", "{summary}. Sign and submit?"),
+    ("transfer.submitting", "Submitting transfer..."),
+    (
+        "transfer.waiting",
+        "Waiting for finalization... (finalized #{block})",
+    ),
+    ("transfer.failed", "Transfer failed"),
+    ("transfer.finalized", "Transfer Finalized"),
+    (
+        "APEX-1001",
+        "Check that the endpoint is reachable and starts with ws://, wss://, http:// or https://.",
+    ),
+    (
+        "APEX-1004",
+        "Check the address format expected by the target chain.",
+    ),
+    (
+        "APEX-1005",
+        "Amounts may not have more decimal places than the token.",
+    ),
+    (
+        "APEX-1008",
+        "Raise the fee cap, or retry with a slower --strategy when the network is less busy.",
+    ),
+    (
+        "APEX-1010",
+        "The account's fee budget is used up; wait for the window to pass or raise the limit.",
+    ),
+    (
+        "APEX-1200",
+        "Check that the endpoint is reachable and starts with ws:// or wss://.",
+    ),
+    (
+        "APEX-1201",
+        "Check that the sender can pay the amount and fee, then retry.",
+    ),
+    (
+        "APEX-1202",
+        "The runtime may have been upgraded; retry to fetch fresh metadata.",
+    ),
+    (
+        "APEX-1204",
+        "Check the wallet name and password; 'apex wallet list' shows stored wallets.",
+    ),
+    (
+        "APEX-1207",
+        "Check the account name and password; 'apex account list' shows stored accounts.",
+    ),
+    (
+        "APEX-1208",
+        "Substrate chains expect an SS58 address with the chain's prefix.",
+    ),
+    (
+        "APEX-1209",
+        "Run with RUST_LOG=debug to see the underlying RPC error.",
+    ),
+    (
+        "APEX-1210",
+        "Raise the fee cap, or retry with a slower --strategy when the network is less busy.",
+    ),
+    (
+        "APEX-1212",
+        "The account's fee budget is used up; wait for the window to pass or raise the limit.",
+    ),
+    (
+        "APEX-1604",
+        "Substrate chains expect an SS58 address with the chain's prefix.",
+    ),
+    (
+        "APEX-1605",
+        "The address checksum does not match; check for a mistyped character.",
+    ),
+    (
+        "APEX-1606",
+        "The address belongs to another network; re-encode it with this chain's SS58 prefix.",
+    ),
+    (
+        "APEX-1611",
+        "Amounts may not have more decimal places than the token.",
+    ),
+];
+
+/// Messages for one locale, with English for keys the locale lacks
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    locale: String,
+    messages: HashMap<String, String>,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self {
+            locale: DEFAULT_LOCALE.to_string(),
+            messages: EN
+                .iter()
+                .chain(apex_sdk_substrate::summary::MESSAGES)
+                .map(|(key, message)| (key.to_string(), message.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Catalog {
+    /// Catalog for `locale`, read from [`locales_dir`]
+    ///
+    /// Tries the full locale (`pt-BR`), then its language (`pt`). A locale
+    /// without a file gets the English catalog.
+    pub fn load(locale: &str) -> Result<Self> {
+        match locales_dir() {
+            Ok(dir) => Self::load_from(&dir, locale),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Catalog for `locale`, read from `<dir>/<locale>.json`
+    pub fn load_from(dir: &Path, locale: &str) -> Result<Self> {
+        let language = locale.split('-').next().unwrap_or(locale);
+        for candidate in [locale, language] {
+            let path = dir.join(format!("{}.json", candidate));
+            if !path.exists() {
+                continue;
+            }
+
+            let data = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let messages: HashMap<String, String> = serde_json::from_str(&data)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            return Ok(Self::default().with_messages(candidate, messages));
+        }
+        Ok(Self::default())
+    }
+
+    /// Override messages by key, and report them as `locale`
+    pub fn with_messages(
+        mut self,
+        locale: impl Into<String>,
+        messages: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        self.locale = locale.into();
+        self.messages.extend(messages);
+        self
+    }
+
+    /// Locale the catalog was loaded for
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Message for `key`, or the key itself if no catalog defines it
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages.get(key).map_or(key, String::as_str)
+    }
+
+    /// Message for `key` with each `{name}` placeholder replaced by its value
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        args.iter()
+            .fold(self.get(key).to_string(), |message, (name, value)| {
+                message.replace(&format!("{{{}}}", name), &value.to_string())
+            })
+    }
+
+    /// Transaction summary rendered with this catalog's `summary.*` templates
+    pub fn summary(&self, summary: &TransactionSummary) -> String {
+        summary.render_with(|key| self.messages.get(key).cloned())
+    }
+
+    /// Remediation hint for an SDK error code
+    pub fn hint(&self, code: ErrorCode) -> Option<&str> {
+        self.messages.get(&code.to_string()).map(String::as_str)
+    }
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Use `catalog` for the rest of the process
+///
+/// Only the first call has an effect; messages looked up before it use
+/// English.
+pub fn init(catalog: Catalog) {
+    let _ = CATALOG.set(catalog);
+}
+
+/// Catalog selected with [`init`]
+pub fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(Catalog::default)
+}
+
+/// Message for `key` from the selected catalog
+pub fn t(key: &'static str) -> &'static str {
+    catalog().get(key)
+}
+
+/// Message for `key` from the selected catalog, with placeholders filled in
+pub fn tf(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    catalog().format(key, args)
+}
+
+/// Directory operator catalogs are read from
+pub fn locales_dir() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
+    Ok(config_dir.join("apex-sdk").join("locales"))
+}
+
+/// Locale to show messages in
+///
+/// The first of `--locale`, `APEX_LOCALE`, `preferences.locale` and the
+/// POSIX `LC_ALL`/`LC_MESSAGES`/`LANG` variables that is set, normalized to
+/// a tag such as `pt-BR`. `C` and `POSIX` mean English.
+pub fn select_locale(flag: Option<&str>, preference: Option<&str>) -> String {
+    let env = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+    let chosen = flag
+        .map(str::to_string)
+        .or_else(|| env("APEX_LOCALE"))
+        .or_else(|| preference.map(str::to_string))
+        .or_else(|| env("LC_ALL"))
+        .or_else(|| env("LC_MESSAGES"))
+        .or_else(|| env("LANG"));
+
+    chosen
+        .as_deref()
+        .map(normalize_locale)
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+/// `pt_BR.UTF-8` → `pt-BR`
+fn normalize_locale(locale: &str) -> String {
+    let tag = locale.split(['.', '@']).next().unwrap_or_default().trim();
+    match tag {
+        "" | "C" | "POSIX" => DEFAULT_LOCALE.to_string(),
+        tag => tag.replace('_', "-"),
+    }
+}
+
+/// Code of the first SDK error in `error`'s chain
+pub fn error_code(error: &anyhow::Error) -> Option<ErrorCode> {
+    error.chain().find_map(|cause| {
+        if let Some(e) = cause.downcast_ref::<apex_sdk_substrate::Error>() {
+            Some(e.code())
+        } else if let Some(e) = cause.downcast_ref::<apex_sdk_revive::Error>() {
+            Some(e.code())
+        } else if let Some(e) = cause.downcast_ref::<apex_sdk::error::Error>() {
+            Some(e.code())
+        } else if let Some(e) = cause.downcast_ref::<apex_sdk_core::SdkError>() {
+            Some(e.code())
+        } else {
+            cause
+                .downcast_ref::<apex_sdk_types::ValidationError>()
+                .map(HasErrorCode::code)
+        }
+    })
+}
+
+/// Print an error that ended a command, with its code and remediation hint
+pub fn report_error(error: &anyhow::Error) {
+    let catalog = catalog();
+    eprintln!("{}: {:#}", catalog.get("error.prefix"), error);
+
+    if let Some(code) = error_code(error) {
+        match catalog.hint(code) {
+            Some(hint) => eprintln!("{} ({}): {}", catalog.get("error.hint"), code, hint),
+            None => eprintln!("[{}]", code),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_catalog_lookup_and_fallback() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("pt.json"),
+            r#"{"transfer.confirm": "Enviar {amount} unidades para {to}?"}"#,
+        )
+        .unwrap();
+
+        let catalog = Catalog::load_from(dir.path(), "pt-BR").unwrap();
+        assert_eq!(catalog.locale(), "pt");
+        assert_eq!(
+            catalog.format("transfer.confirm", &[("amount", &5), ("to", &"5Grw")]),
+            "Enviar 5 unidades para 5Grw?"
+        );
+        assert_eq!(catalog.get("transfer.title"), "Balance Transfer");
+        assert_eq!(catalog.get("no.such.key"), "no.such.key");
+        assert!(catalog.hint(ErrorCode::new(1210)).is_some());
+        assert!(catalog.hint(ErrorCode::new(1099)).is_none());
+        assert_eq!(catalog.get("summary.fee"), "fee ≈ {fee}");
+
+        let english = Catalog::load_from(dir.path(), "de").unwrap();
+        assert_eq!(english.locale(), DEFAULT_LOCALE);
+
+        std::fs::write(dir.path().join("fr.json"), "not json").unwrap();
+        assert!(Catalog::load_from(dir.path(), "fr").is_err());
+    }
+
+    #[test]
+    fn test_locale_selection() {
+        assert_eq!(select_locale(Some("de_DE.UTF-8"), None), "de-DE");
+        assert_eq!(normalize_locale("C"), "en");
+        assert_eq!(normalize_locale("sr_RS@latin"), "sr-RS");
+        assert_eq!(normalize_locale("ja"), "ja");
+    }
+
+    #[test]
+    fn test_error_code_from_chain() {
+        let error = anyhow::Error::new(apex_sdk_substrate::Error::Connection("down".into()))
+            .context("Failed to connect");
+        assert_eq!(error_code(&error), Some(ErrorCode::new(1200)));
+        assert_eq!(error_code(&anyhow::anyhow!("plain")), None);
+    }
+}
@@ -0,0 +1,127 @@
+//! Metrics snapshots for environments that cannot be scraped
+//!
+//! `apex metrics dump` probes the given endpoints, collects the same series
+//! the metrics server would export and writes them to a file in the
+//! Prometheus text exposition format, ready to be shipped by hand to a
+//! node_exporter textfile collector. `apex metrics dashboards` writes a
+//! Grafana dashboard and Prometheus rules for the same series.
+
+use anyhow::Result;
+use apex_sdk_core::metrics::MetricsCollector;
+use apex_sdk_metrics::{
+    DashboardGenerator, EndpointProbe, HealthChecker, HealthStatus, OperationType,
+    PerformanceProfiler, PrometheusRegistry, SnapshotExporter, UptimeTracker,
+};
+use colored::Colorize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Probe `endpoints` and write a metrics snapshot to `out`, plus spans and
+/// aggregates to `json` if given
+///
+/// With `uptime_state`, probe results are added to the persisted uptime
+/// history so repeated dumps report rolling uptime.
+pub async fn dump(
+    out: &Path,
+    json: Option<&Path>,
+    endpoints: &[String],
+    uptime_state: Option<&Path>,
+) -> Result<()> {
+    println!("\n{}", "Metrics Snapshot".cyan().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+
+    let health = Arc::new(HealthChecker::new().with_sample_interval(Duration::ZERO));
+    let profiler = PerformanceProfiler::new();
+    let uptime = Arc::new(match uptime_state {
+        Some(path) => UptimeTracker::with_persistence(path)
+            .map_err(|e| anyhow::anyhow!("Failed to load uptime state: {}", e))?,
+        None => UptimeTracker::new(),
+    });
+
+    if !endpoints.is_empty() {
+        let spinner = indicatif::ProgressBar::new_spinner();
+        spinner.enable_steady_tick(Duration::from_millis(100));
+        for endpoint in endpoints {
+            spinner.set_message(format!("Probing {}...", endpoint));
+            let probe = if endpoint.starts_with("ws://") || endpoint.starts_with("wss://") {
+                EndpointProbe::substrate(endpoint)
+            } else {
+                EndpointProbe::evm(endpoint)
+            };
+
+            let mut span = profiler.start_span(OperationType::RpcRequest);
+            span.set_attribute("endpoint", endpoint.as_str());
+            let component = probe.check().await;
+            if component.status == HealthStatus::Unhealthy {
+                span.error(component.message.clone().unwrap_or_default());
+            } else {
+                span.success();
+            }
+            uptime.record_health(&component);
+            health.update_component(component);
+        }
+        spinner.finish_and_clear();
+
+        if uptime_state.is_some() {
+            uptime
+                .save()
+                .map_err(|e| anyhow::anyhow!("Failed to save uptime state: {}", e))?;
+        }
+    }
+
+    let dump = SnapshotExporter::new(MetricsCollector::new())
+        .map_err(|e| anyhow::anyhow!("Failed to create exporter: {}", e))?
+        .with_health_checker(health)
+        .with_uptime_tracker(uptime)
+        .with_profiler(profiler)
+        .capture()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to capture metrics: {}", e))?;
+
+    dump.write_prometheus(out)
+        .map_err(|e| anyhow::anyhow!("Failed to write snapshot: {}", e))?;
+    if let Some(json) = json {
+        dump.write_json(json)
+            .map_err(|e| anyhow::anyhow!("Failed to write JSON dump: {}", e))?;
+    }
+
+    let samples = dump
+        .prometheus
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .count();
+    println!("{}: {}", "Endpoints".dimmed(), endpoints.len());
+    println!("{}: {}", "Samples".dimmed(), samples);
+    println!("{}: {}", "Prometheus".dimmed(), out.display());
+    if let Some(json) = json {
+        println!("{}: {}", "JSON".dimmed(), json.display());
+    }
+    println!("\n{}", "Snapshot written".green().bold());
+
+    Ok(())
+}
+
+/// Write a Grafana dashboard and a Prometheus rule file for the SDK metrics
+/// to `out_dir`
+pub fn dashboards(out_dir: &Path, title: &str) -> Result<()> {
+    let registry = PrometheusRegistry::new()
+        .map_err(|e| anyhow::anyhow!("Failed to create registry: {}", e))?;
+    let generator = DashboardGenerator::new(&registry).with_title(title);
+
+    std::fs::create_dir_all(out_dir)?;
+    let dashboard = out_dir.join("apex-sdk-dashboard.json");
+    let rules = out_dir.join("apex-sdk-rules.yml");
+    std::fs::write(
+        &dashboard,
+        generator
+            .dashboard_json()
+            .map_err(|e| anyhow::anyhow!("Failed to render dashboard: {}", e))?,
+    )?;
+    std::fs::write(&rules, generator.rules_yaml())?;
+
+    println!("{}: {}", "Families".dimmed(), generator.families().len());
+    println!("{}: {}", "Dashboard".dimmed(), dashboard.display());
+    println!("{}: {}", "Rules".dimmed(), rules.display());
+    Ok(())
+}
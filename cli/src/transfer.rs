@@ -0,0 +1,286 @@
+//! Balance transfers on Substrate chains
+
+use crate::messages::{catalog, t, tf};
+use anyhow::{Context, Result};
+use apex_sdk_substrate::{
+    FeeConfig, FeeEstimate, FeeStrategy, KeyPairType, SubstrateAdapter, Wallet,
+};
+use colored::Colorize;
+use zeroize::Zeroize;
+
+/// Where the sending wallet comes from
+pub enum SignerSource {
+    /// Account in the encrypted keystore
+    Keystore(String),
+    /// Wallet in the wallet keystore (`apex wallet`)
+    Wallet(String),
+    /// BIP-39 mnemonic phrase
+    Mnemonic(String),
+    /// Hex-encoded 32-byte seed
+    Seed(String),
+}
+
+/// Parse a `--strategy` value
+fn parse_strategy(strategy: &str) -> Result<FeeStrategy> {
+    match strategy.to_lowercase().as_str() {
+        "fast" => Ok(FeeStrategy::Fast),
+        "normal" => Ok(FeeStrategy::Normal),
+        "slow" => Ok(FeeStrategy::Slow),
+        other => anyhow::bail!(tf("transfer.unknown_strategy", &[("strategy", &other)])),
+    }
+}
+
+/// Build the sending wallet, prompting for the keystore password if needed
+pub fn load_wallet(source: SignerSource) -> Result<Wallet> {
+    match source {
+        SignerSource::Keystore(name) => {
+            let keystore_path = crate::keystore::get_keystore_path()?;
+            let mut keystore = crate::keystore::Keystore::load(&keystore_path)?;
+            if !keystore.has_account(&name) {
+                anyhow::bail!(tf("account.not_found", &[("name", &name)]));
+            }
+
+            let password = rpassword::prompt_password(t("prompt.account_password"))
+                .context(t("error.read_password"))?;
+            let mnemonic_bytes = keystore.get_account(&name, &password)?;
+            let mut mnemonic =
+                String::from_utf8(mnemonic_bytes).context("Failed to decode mnemonic")?;
+
+            let wallet = Wallet::from_mnemonic(&mnemonic, KeyPairType::Sr25519);
+            mnemonic.zeroize();
+            wallet.map_err(|e| anyhow::anyhow!("Failed to load account '{}': {}", name, e))
+        }
+        SignerSource::Wallet(name) => {
+            let keystore = apex_sdk_substrate::Keystore::open(crate::wallet::get_wallet_dir()?)
+                .map_err(|e| anyhow::anyhow!("{}", e))?;
+            if !keystore.contains(&name) {
+                anyhow::bail!(tf("wallet.not_found", &[("name", &name)]));
+            }
+
+            let mut password = rpassword::prompt_password(t("prompt.wallet_password"))
+                .context(t("error.read_password"))?;
+            let wallet = keystore.load(&name, &password);
+            password.zeroize();
+            wallet.map_err(|e| anyhow::anyhow!("Failed to load wallet '{}': {}", name, e))
+        }
+        SignerSource::Mnemonic(mut mnemonic) => {
+            let wallet = Wallet::from_mnemonic(mnemonic.trim(), KeyPairType::Sr25519);
+            mnemonic.zeroize();
+            wallet.map_err(|e| anyhow::anyhow!("Invalid mnemonic: {}", e))
+        }
+        SignerSource::Seed(mut seed) => {
+            let bytes = hex::decode(seed.trim().trim_start_matches("0x"));
+            seed.zeroize();
+            let mut bytes = bytes.context("Seed must be hex-encoded")?;
+            let wallet = Wallet::from_seed(&bytes, KeyPairType::Sr25519);
+            bytes.zeroize();
+            wallet.map_err(|e| anyhow::anyhow!("Invalid seed: {}", e))
+        }
+    }
+}
+
+/// Print the fee breakdown of an estimate
+fn print_fee_estimate(estimate: &FeeEstimate) {
+    println!("\n{}", t("fee.title").cyan().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    println!(
+        "{}: {}",
+        t("fee.strategy").dimmed(),
+        estimate.strategy.description()
+    );
+    println!("{}: {}", t("fee.base_fee").dimmed(), estimate.base_fee);
+    println!("{}: {}", t("fee.length_fee").dimmed(), estimate.length_fee);
+    println!("{}: {}", t("fee.weight_fee").dimmed(), estimate.weight_fee);
+    if let Some(multiplier) = estimate.fee_multiplier {
+        println!("{}: x{:.4}", t("fee.fee_multiplier").dimmed(), multiplier);
+    }
+    println!("{}: {}", t("fee.margin").dimmed(), estimate.margin);
+    println!("{}: {}", t("fee.tip").dimmed(), estimate.tip);
+    println!(
+        "{}: {}",
+        t("fee.congestion").dimmed(),
+        tf(
+            "fee.congestion_value",
+            &[
+                ("level", &estimate.congestion.level.as_str()),
+                (
+                    "fullness",
+                    &format!("{:.0}", estimate.congestion.avg_block_fullness * 100.0),
+                ),
+                (
+                    "multiplier",
+                    &format!("{:.1}", estimate.congestion.multiplier()),
+                ),
+            ],
+        )
+    );
+    println!(
+        "{}: {}",
+        t("fee.total").green().bold(),
+        tf("common.raw_units", &[("amount", &estimate.total_fee)])
+    );
+}
+
+/// Transfer `amount` raw units to `to`
+///
+/// Shows the fee estimate for the chosen strategy and asks for confirmation
+/// unless `yes` is set, then submits the transfer with the estimated tip and
+/// waits for finalization.
+pub async fn transfer(
+    to: &str,
+    amount: u128,
+    endpoint: &str,
+    signer: SignerSource,
+    strategy: &str,
+    yes: bool,
+) -> Result<()> {
+    let strategy = parse_strategy(strategy)?;
+    apex_sdk_substrate::storage::StorageQuery::parse_address(to)
+        .context(t("transfer.invalid_recipient"))?;
+
+    let wallet = load_wallet(signer)?;
+
+    println!("\n{}", t("transfer.title").cyan().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    println!("{}: {}", t("label.endpoint").dimmed(), endpoint);
+    println!("{}: {}", t("label.from").dimmed(), wallet.address());
+    println!("{}: {}", t("label.to").dimmed(), to);
+    println!(
+        "{}: {}",
+        t("label.amount").dimmed(),
+        tf("common.raw_units", &[("amount", &amount)])
+    );
+    println!();
+
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.set_message(t("progress.connecting"));
+    spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let adapter = SubstrateAdapter::connect(endpoint)
+        .await
+        .context(t("transfer.connect_failed"))?;
+    let executor = adapter.transaction_executor();
+
+    spinner.set_message(t("progress.estimating_fee"));
+
+    let estimate = executor
+        .estimate_transfer_fee_with_strategy(to, amount, &wallet, strategy)
+        .await
+        .context(t("transfer.estimate_failed"))?;
+    let balance = adapter
+        .get_balance(wallet.address())
+        .await
+        .context(t("transfer.balance_failed"))?;
+
+    spinner.finish_and_clear();
+    print_fee_estimate(&estimate);
+
+    let required = amount.saturating_add(estimate.total_fee);
+    if balance < required {
+        anyhow::bail!(tf(
+            "transfer.insufficient_balance",
+            &[("available", &balance), ("required", &required)],
+        ));
+    }
+
+    if !yes {
+        let properties = adapter
+            .chain_properties()
+            .await
+            .context(t("transfer.properties_failed"))?;
+        let summary = executor
+            .summarize_transfer(to, amount)
+            .context(t("transfer.summary_failed"))?
+            .with_properties(properties)
+            .with_fee(estimate.total_fee);
+        println!();
+        let confirmed = dialoguer::Confirm::new()
+            .with_prompt(tf(
+                "transfer.confirm",
+                &[
+                    ("summary", &catalog().summary(&summary)),
+                    ("amount", &amount),
+                    ("to", &to),
+                ],
+            ))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            println!("{}", t("common.cancelled"));
+            return Ok(());
+        }
+    }
+
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.set_message(t("transfer.submitting"));
+    spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let executor = executor.with_fee_config(FeeConfig::new().with_tip(estimate.tip));
+    let submission = executor.transfer(&wallet, to, amount);
+    tokio::pin!(submission);
+
+    let mut blocks = adapter.event_bus().subscribe("cli-transfer");
+    let receipt = loop {
+        tokio::select! {
+            result = &mut submission => break result,
+            Some(block) = blocks.recv() => {
+                spinner.set_message(tf("transfer.waiting", &[("block", &block.number)]));
+            }
+        }
+    };
+
+    spinner.finish_and_clear();
+    let receipt = receipt.context(t("transfer.failed"))?;
+
+    println!("\n{}", t("transfer.finalized").green().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    println!("{}: {}", t("label.transaction").cyan(), receipt.tx_hash);
+    println!(
+        "{}: #{} ({})",
+        t("label.block").dimmed(),
+        receipt.block_number,
+        receipt.block_hash
+    );
+    match receipt.actual_fee {
+        Some(fee) => println!(
+            "{}: {}",
+            t("fee.paid").dimmed(),
+            tf("common.raw_units", &[("amount", &fee)])
+        ),
+        None => println!("{}: {}", t("fee.paid").dimmed(), t("common.unknown")),
+    }
+    if let Some(refunded) = receipt.refunded_fee.filter(|refunded| *refunded > 0) {
+        println!(
+            "{}: {}",
+            t("fee.refunded").dimmed(),
+            tf("common.raw_units", &[("amount", &refunded)])
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_strategy() {
+        assert_eq!(parse_strategy("fast").unwrap(), FeeStrategy::Fast);
+        assert_eq!(parse_strategy("Normal").unwrap(), FeeStrategy::Normal);
+        assert_eq!(parse_strategy("SLOW").unwrap(), FeeStrategy::Slow);
+        assert!(parse_strategy("instant").is_err());
+    }
+
+    #[test]
+    fn test_load_wallet_from_seed() {
+        let seed = format!("0x{}", "01".repeat(32));
+        let wallet = load_wallet(SignerSource::Seed(seed)).unwrap();
+        let expected = Wallet::from_seed(&[1u8; 32], KeyPairType::Sr25519).unwrap();
+        assert_eq!(wallet.address(), expected.address());
+
+        assert!(load_wallet(SignerSource::Seed("0x0102".to_string())).is_err());
+        assert!(load_wallet(SignerSource::Seed("not hex".to_string())).is_err());
+        assert!(load_wallet(SignerSource::Mnemonic("not a mnemonic".to_string())).is_err());
+    }
+}
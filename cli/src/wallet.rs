@@ -0,0 +1,524 @@
+//! Wallet management backed by the encrypted Substrate keystore
+//!
+//! Wallets are stored one file per wallet in the SDK's [`Keystore`] format
+//! (scrypt + AES-256-GCM) and can be used to sign with `--wallet <NAME>`.
+//! Backups bundle the encrypted files in the configured interchange format.
+
+use crate::messages::{t, tf};
+use anyhow::{Context, Result};
+use apex_sdk_core::codec::Codec;
+use apex_sdk_substrate::{KeyPairType, Keystore, Wallet};
+use apex_sdk_types::{Address, Chain, GENERIC_SS58_PREFIX};
+use colored::Colorize;
+use std::path::PathBuf;
+use zeroize::Zeroize;
+
+/// Directory of the wallet keystore
+pub fn get_wallet_dir() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
+    Ok(config_dir.join("apex-sdk").join("wallets"))
+}
+
+fn open_keystore() -> Result<Keystore> {
+    Keystore::open(get_wallet_dir()?).map_err(|e| anyhow::anyhow!("{}", e))
+}
+
+/// Parse a `--key-type` value
+fn parse_key_type(key_type: &str) -> Result<KeyPairType> {
+    match key_type.to_lowercase().as_str() {
+        "sr25519" | "sr" => Ok(KeyPairType::Sr25519),
+        "ed25519" | "ed" => Ok(KeyPairType::Ed25519),
+        other => anyhow::bail!(
+            "Unknown key type '{}'. Expected one of: sr25519, ed25519",
+            other
+        ),
+    }
+}
+
+/// SS58 prefix of a `--network` value: a chain name or a numeric prefix
+pub fn parse_network(network: &str) -> Result<u16> {
+    if let Ok(prefix) = network.parse::<u16>() {
+        if prefix > 16383 {
+            anyhow::bail!("SS58 prefix {} is out of range (0-16383)", prefix);
+        }
+        return Ok(prefix);
+    }
+    if network.eq_ignore_ascii_case("substrate") {
+        return Ok(GENERIC_SS58_PREFIX);
+    }
+    Chain::from_str_case_insensitive(network)
+        .and_then(|chain| chain.ss58_prefix())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown network '{}'. Use a Substrate chain name or an SS58 prefix",
+                network
+            )
+        })
+}
+
+/// Derivation path in `//hard/soft` form, without the leading `//`, as
+/// [`Wallet::from_mnemonic_with_path`] expects it
+fn parse_path(path: &str) -> Result<&str> {
+    path.strip_prefix("//")
+        .filter(|rest| !rest.is_empty())
+        .ok_or_else(|| {
+            anyhow::anyhow!("Derivation path must start with a hard junction, e.g. //polkadot//0")
+        })
+}
+
+/// Build a wallet from a mnemonic, derivation path and network
+fn derive_wallet(
+    mnemonic: &str,
+    path: Option<&str>,
+    key_type: KeyPairType,
+    ss58_prefix: u16,
+) -> Result<Wallet> {
+    let path = path.map(parse_path).transpose()?;
+    Wallet::from_mnemonic_with_path(mnemonic, path, key_type)
+        .map(|wallet| wallet.with_ss58_format(ss58_prefix))
+        .map_err(|e| anyhow::anyhow!("{}", e))
+}
+
+/// Generate a BIP-39 mnemonic of 12 or 24 words
+fn generate_mnemonic(words: usize) -> Result<String> {
+    use ::rand::RngCore;
+
+    let mut entropy = match words {
+        12 => vec![0u8; 16],
+        24 => vec![0u8; 32],
+        _ => anyhow::bail!("Mnemonic length must be 12 or 24 words"),
+    };
+    ::rand::rng().fill_bytes(&mut entropy);
+    let mnemonic = bip39::Mnemonic::from_entropy(&entropy).context("Failed to generate mnemonic");
+    entropy.zeroize();
+    Ok(mnemonic?.to_string())
+}
+
+/// Prompt for a new keystore password twice
+fn prompt_new_password() -> Result<String> {
+    let password = rpassword::prompt_password(t("prompt.new_wallet_password"))
+        .context(t("error.read_password"))?;
+    crate::keystore::Keystore::validate_password(&password)?;
+
+    let confirm = rpassword::prompt_password(t("prompt.confirm_password"))
+        .context(t("error.read_password"))?;
+    if password != confirm {
+        anyhow::bail!(t("error.password_mismatch"));
+    }
+    Ok(password)
+}
+
+/// Encrypt `wallet` into the keystore under `name`
+fn save_wallet(keystore: &Keystore, name: &str, wallet: &Wallet) -> Result<()> {
+    if keystore.contains(name) {
+        anyhow::bail!(tf("wallet.exists", &[("name", &name)]));
+    }
+
+    let mut password = prompt_new_password()?;
+    let saved = keystore.save(name, wallet, &password);
+    password.zeroize();
+    saved.map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    println!("\n{}", "Wallet Saved".green().bold());
+    println!("{}: {}", "Name".cyan(), name);
+    println!("{}: {}", "Address".cyan(), wallet.address());
+    println!("{}: {}", "Keystore".cyan(), keystore.path().display());
+    Ok(())
+}
+
+/// Render `data` as a terminal QR code
+fn render_qr(data: &str) -> Result<String> {
+    use qrcode::render::unicode;
+
+    let code = qrcode::QrCode::new(data.as_bytes()).context("Failed to encode QR code")?;
+    Ok(code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build())
+}
+
+fn key_type_name(key_type: KeyPairType) -> &'static str {
+    match key_type {
+        KeyPairType::Sr25519 => "sr25519",
+        KeyPairType::Ed25519 => "ed25519",
+    }
+}
+
+/// Generate a wallet, print its mnemonic and optionally store it
+pub fn new_wallet(
+    name: Option<String>,
+    key_type: &str,
+    network: &str,
+    path: Option<&str>,
+    words: usize,
+    qr: bool,
+) -> Result<()> {
+    let key_type = parse_key_type(key_type)?;
+    let ss58_prefix = parse_network(network)?;
+    let keystore = open_keystore()?;
+    if let Some(name) = &name {
+        if keystore.contains(name) {
+            anyhow::bail!(tf("wallet.exists", &[("name", &name)]));
+        }
+    }
+
+    let mut mnemonic = generate_mnemonic(words)?;
+    let wallet = derive_wallet(&mnemonic, path, key_type, ss58_prefix)?;
+
+    println!("\n{}", "Wallet Generated".green().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    println!("{}: {}", "Address".cyan().bold(), wallet.address());
+    println!("{}: {}", "Key Type".dimmed(), key_type_name(key_type));
+    println!("{}: {}", "SS58 Prefix".dimmed(), ss58_prefix);
+    if let Some(path) = path {
+        println!("{}: {}", "Derivation Path".dimmed(), path);
+    }
+    println!("\n{}: {}", "Mnemonic".yellow().bold(), mnemonic);
+    mnemonic.zeroize();
+    println!("\n{}", "IMPORTANT SECURITY NOTICE".red().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    println!("• Write down your mnemonic phrase in a secure location");
+    println!("• Never share your mnemonic with anyone");
+    println!("• The keystore stores the derived key, not the mnemonic");
+
+    if qr {
+        println!("\n{}", render_qr(&wallet.address())?);
+    }
+
+    match name {
+        Some(name) => save_wallet(&keystore, &name, &wallet)?,
+        None => {
+            println!("\n{}", "Tip:".cyan());
+            println!(
+                "Use {} to store this wallet",
+                "apex wallet import <name>".yellow()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Import a wallet from a mnemonic or a Polkadot-JS JSON file
+///
+/// Prompts for the mnemonic if neither `mnemonic` nor `json` is given, so the
+/// phrase does not end up in shell history.
+pub fn import_wallet(
+    name: &str,
+    mnemonic: Option<String>,
+    json: Option<&str>,
+    key_type: &str,
+    network: Option<&str>,
+    path: Option<&str>,
+) -> Result<()> {
+    let key_type = parse_key_type(key_type)?;
+    let ss58_prefix = network.map(parse_network).transpose()?;
+    let keystore = open_keystore()?;
+    if keystore.contains(name) {
+        anyhow::bail!(tf("wallet.exists", &[("name", &name)]));
+    }
+
+    let wallet = match json {
+        Some(file) => {
+            if path.is_some() {
+                anyhow::bail!("--path only applies to mnemonic imports");
+            }
+            let data = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read '{}'", file))?;
+            let mut password = rpassword::prompt_password(t("prompt.json_password"))
+                .context(t("error.read_password"))?;
+            let wallet = apex_sdk_substrate::keystore::decode_polkadot_js(&data, &password);
+            password.zeroize();
+            let wallet = wallet.map_err(|e| anyhow::anyhow!("{}", e))?;
+            match ss58_prefix {
+                Some(prefix) => wallet.with_ss58_format(prefix),
+                None => wallet,
+            }
+        }
+        None => {
+            let mut mnemonic = match mnemonic {
+                Some(mnemonic) => mnemonic,
+                None => rpassword::prompt_password(t("prompt.mnemonic"))
+                    .context(t("error.read_mnemonic"))?,
+            };
+            let wallet = derive_wallet(
+                mnemonic.trim(),
+                path,
+                key_type,
+                ss58_prefix.unwrap_or(GENERIC_SS58_PREFIX),
+            );
+            mnemonic.zeroize();
+            wallet?
+        }
+    };
+
+    save_wallet(&keystore, name, &wallet)
+}
+
+/// List stored wallets
+pub fn list_wallets() -> Result<()> {
+    let keystore = open_keystore()?;
+    let wallets = keystore.list().map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    if wallets.is_empty() {
+        println!("\n{}", "No wallets found".yellow());
+        println!("\n{}", "Create a wallet:".cyan());
+        println!("  apex wallet new --name <name>");
+        return Ok(());
+    }
+
+    println!("\n{}", "Wallets".cyan().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    for (idx, wallet) in wallets.iter().enumerate() {
+        println!("\n{}. {}", idx + 1, wallet.name.green().bold());
+        println!("   {}: {}", "Address".dimmed(), wallet.address);
+        println!(
+            "   {}: {} (SS58 {})",
+            "Key Type".dimmed(),
+            key_type_name(wallet.key_type),
+            wallet.ss58_format
+        );
+        let created =
+            chrono::DateTime::from_timestamp(wallet.created_at as i64, 0).unwrap_or_default();
+        println!(
+            "   {}: {}",
+            "Created".dimmed(),
+            created.format("%Y-%m-%d %H:%M:%S")
+        );
+    }
+
+    println!("\n{}: {}", "Total".cyan(), wallets.len());
+    println!("{}: {}", "Keystore".dimmed(), keystore.path().display());
+    Ok(())
+}
+
+/// Export a stored wallet as a Polkadot-JS JSON file, encrypted with the
+/// wallet's password
+pub fn export_wallet(name: &str, output: Option<&str>) -> Result<()> {
+    let keystore = open_keystore()?;
+    if !keystore.contains(name) {
+        anyhow::bail!(tf("wallet.not_found", &[("name", &name)]));
+    }
+
+    let mut password = rpassword::prompt_password(t("prompt.wallet_password"))
+        .context(t("error.read_password"))?;
+    let json = keystore.export_polkadot_js(name, &password);
+    password.zeroize();
+    let json = json.map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    match output {
+        Some(file) => {
+            create_private_file(file, json.as_bytes())?;
+            println!("\n{}", "Wallet Exported".green().bold());
+            println!("{}: {}", "File".cyan(), file);
+            println!("Import it in Polkadot-JS or with 'apex wallet import --json'.");
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// Create a file readable only by the current user
+///
+/// The file is created with its final permissions, and an existing file is
+/// never replaced.
+fn create_private_file(path: &str, data: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = match options.open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            anyhow::bail!("'{}' already exists", path)
+        }
+        Err(e) => return Err(e).with_context(|| format!("Failed to write '{}'", path)),
+    };
+    file.write_all(data)
+        .with_context(|| format!("Failed to write '{}'", path))
+}
+
+/// Resolve a `--format` value, falling back to `preferences.interchange_format`
+fn backup_codec(format: Option<&str>) -> Result<Codec> {
+    match format {
+        Some(format) => format.parse().map_err(|e: String| anyhow::anyhow!(e)),
+        None => {
+            let config = crate::config::Config::load(&crate::config::get_config_path()?)?;
+            Ok(config.preferences.interchange_format)
+        }
+    }
+}
+
+/// Write all stored wallets to a single backup file
+///
+/// Wallets stay encrypted with their own passwords, so no password is asked.
+pub fn backup_wallets(output: Option<&str>, format: Option<&str>) -> Result<()> {
+    let codec = backup_codec(format)?;
+    let keystore = open_keystore()?;
+    let count = keystore.list().map_err(|e| anyhow::anyhow!("{}", e))?.len();
+    if count == 0 {
+        anyhow::bail!("No wallets to back up");
+    }
+
+    let data = keystore
+        .export_backup(codec)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let file = output
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("apex-wallets.{}", codec.extension()));
+    create_private_file(&file, &data)?;
+
+    println!("\n{}", "Wallets Backed Up".green().bold());
+    println!("{}: {}", "File".cyan(), file);
+    println!("{}: {}", "Format".dimmed(), codec);
+    println!("{}: {}", "Wallets".dimmed(), count);
+    Ok(())
+}
+
+/// Restore wallets from a backup file, skipping names already stored
+pub fn restore_wallets(file: &str, format: Option<&str>) -> Result<()> {
+    let codec = backup_codec(format)?;
+    let data = std::fs::read(file).with_context(|| format!("Failed to read '{}'", file))?;
+
+    let keystore = open_keystore()?;
+    let restored = keystore
+        .import_backup(&data, codec)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    println!("\n{}", "Wallets Restored".green().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    for entry in &restored {
+        println!("{}: {}", entry.name.green(), entry.address);
+    }
+    println!("\n{}: {}", "Restored".cyan(), restored.len());
+    println!("{}: {}", "Keystore".dimmed(), keystore.path().display());
+    Ok(())
+}
+
+/// Show the address of a stored wallet or an SS58 address on a network
+pub fn inspect_wallet(target: &str, network: Option<&str>, qr: bool) -> Result<()> {
+    let keystore = open_keystore()?;
+    let (name, key_type, address) = if keystore.contains(target) {
+        let entry = keystore
+            .list()
+            .map_err(|e| anyhow::anyhow!("{}", e))?
+            .into_iter()
+            .find(|entry| entry.name == target)
+            .ok_or_else(|| anyhow::anyhow!("Wallet '{}' is unreadable", target))?;
+        (Some(entry.name), Some(entry.key_type), entry.address)
+    } else {
+        (None, None, target.to_string())
+    };
+
+    let address = Address::parse(&address)
+        .ok()
+        .filter(|address| matches!(address, Address::Substrate(_)))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "'{}' is neither a stored wallet nor an SS58 address",
+                target
+            )
+        })?;
+    let address = match network {
+        Some(network) => address.with_ss58_prefix(parse_network(network)?)?,
+        None => address,
+    };
+
+    println!("\n{}", "Wallet".cyan().bold());
+    println!("{}", "═══════════════════════════════════════".dimmed());
+    if let Some(name) = name {
+        println!("{}: {}", "Name".dimmed(), name);
+    }
+    if let Some(key_type) = key_type {
+        println!("{}: {}", "Key Type".dimmed(), key_type_name(key_type));
+    }
+    println!("{}: {}", "Address".cyan().bold(), address);
+    println!(
+        "{}: {}",
+        "SS58 Prefix".dimmed(),
+        address.ss58_prefix().unwrap_or(GENERIC_SS58_PREFIX)
+    );
+    println!(
+        "{}: 0x{}",
+        "Public Key".dimmed(),
+        hex::encode(address.account_id()?)
+    );
+
+    if qr {
+        println!("\n{}", render_qr(&address.to_string())?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC: &str = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+    #[test]
+    fn test_create_private_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wallet.json");
+        let path = path.to_str().unwrap();
+
+        create_private_file(path, b"first").unwrap();
+        assert!(create_private_file(path, b"second").is_err());
+        assert_eq!(std::fs::read(path).unwrap(), b"first");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_parse_network() {
+        assert_eq!(parse_network("polkadot").unwrap(), 0);
+        assert_eq!(parse_network("Kusama").unwrap(), 2);
+        assert_eq!(parse_network("substrate").unwrap(), 42);
+        assert_eq!(parse_network("7").unwrap(), 7);
+        assert!(parse_network("16384").is_err());
+        assert!(parse_network("ethereum").is_err());
+        assert!(parse_network("nowhere").is_err());
+    }
+
+    #[test]
+    fn test_derive_wallet() {
+        // Alice's well-known dev account
+        let alice = derive_wallet(MNEMONIC, Some("//Alice"), KeyPairType::Sr25519, 42).unwrap();
+        assert_eq!(
+            alice.address(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+
+        let polkadot = derive_wallet(MNEMONIC, Some("//Alice"), KeyPairType::Sr25519, 0).unwrap();
+        assert_eq!(polkadot.ss58_prefix(), 0);
+        assert_eq!(polkadot.public_key(), alice.public_key());
+
+        assert!(derive_wallet(MNEMONIC, Some("Alice"), KeyPairType::Sr25519, 42).is_err());
+        assert!(derive_wallet("not a mnemonic", None, KeyPairType::Sr25519, 42).is_err());
+    }
+
+    #[test]
+    fn test_generate_mnemonic() {
+        assert_eq!(generate_mnemonic(12).unwrap().split(' ').count(), 12);
+        assert_eq!(generate_mnemonic(24).unwrap().split(' ').count(), 24);
+        assert!(generate_mnemonic(15).is_err());
+    }
+
+    #[test]
+    fn test_render_qr() {
+        let qr = render_qr("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY").unwrap();
+        assert!(qr.lines().count() > 10);
+    }
+}
@@ -109,7 +109,7 @@ async fn main() -> Result<()> {
     println!("      Network: Polkadot (prefix: 0)");
 
     // EVM address (ECDSA/secp256k1)
-    let evm_address = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7";
+    let evm_address = "0x742d35cc6634c0532925a3b844bc9e7595f0beb7";
     println!("\n    EVM (secp256k1):     {}", evm_address);
     println!("      Format: Hexadecimal (0x-prefixed)");
     println!("      Compatible: Ethereum, BSC, Polygon, etc.");
@@ -187,8 +187,8 @@ async fn main() -> Result<()> {
 
     let identity_tx = sdk
         .transaction()
-        .from_substrate_account(substrate_address)
-        .to_substrate_account("5C4hrfjw9DjXZTzV3MwzrrAr9P1MJhSrvWGWqi1eSuyUpnhM") // Identity pallet
+        .from_address(substrate_address)
+        .to_address("5C4hrfjw9DjXZTzV3MwzrrAr9P1MJhSrvWGWqi1eSuyUpnhM") // Identity pallet
         .amount(0)
         .with_data(encode_set_identity(&identity))
         .build()?;
@@ -215,8 +215,8 @@ async fn main() -> Result<()> {
 
     let substrate_tx = sdk
         .transaction()
-        .from_substrate_account(substrate_address)
-        .to_substrate_account(recipient_substrate)
+        .from_address(substrate_address)
+        .to_address(recipient_substrate)
         .amount(substrate_transfer_amount)
         .build()?;
 
@@ -234,8 +234,8 @@ async fn main() -> Result<()> {
 
     let evm_tx = sdk
         .transaction()
-        .from_evm_address(evm_address)
-        .to_evm_address(recipient_evm)
+        .from_address(evm_address)
+        .to_address(recipient_evm)
         .amount(evm_transfer_amount)
         .with_gas_limit(21000)
         .build()?;
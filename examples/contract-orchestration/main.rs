@@ -91,7 +91,7 @@ async fn main() -> Result<()> {
 
     // User's accounts on both chains
     let substrate_account = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
-    let evm_account = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7";
+    let evm_account = "0x742d35cc6634c0532925a3b844bc9e7595f0beb7";
 
     println!("User Accounts:");
     println!("  Substrate (SS58): {}", substrate_account);
@@ -160,8 +160,8 @@ async fn main() -> Result<()> {
 
     let swap_tx = sdk
         .transaction()
-        .from_evm_address(evm_account)
-        .to_evm_address(dex_contract)
+        .from_address(evm_account)
+        .to_address(dex_contract)
         .amount(0) // No ETH sent, just contract call
         .with_data(swap_data)
         .with_gas_limit(200000)
@@ -192,8 +192,8 @@ async fn main() -> Result<()> {
 
     let bridge_tx = sdk
         .transaction()
-        .from_evm_address(evm_account)
-        .to_substrate_account(substrate_account)
+        .from_address(evm_account)
+        .to_address(substrate_account)
         .amount(bridge_amount)
         .build()?;
 
@@ -229,8 +229,8 @@ async fn main() -> Result<()> {
 
     let stake_tx = sdk
         .transaction()
-        .from_substrate_account(substrate_account)
-        .to_substrate_account(staking_contract)
+        .from_address(substrate_account)
+        .to_address(staking_contract)
         .amount(0)
         .with_data(stake_data)
         .build()?;
@@ -240,8 +240,8 @@ async fn main() -> Result<()> {
 
     let substrate_publish_tx = sdk
         .transaction()
-        .from_substrate_account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
-        .to_substrate_account(substrate_oracle)
+        .from_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+        .to_address(substrate_oracle)
         .amount(0)
         .with_data(encode_oracle_update(asset, aggregated.median_price_usd))
         .build()?;
@@ -256,8 +256,8 @@ async fn main() -> Result<()> {
 
     let eth_publish_tx = sdk
         .transaction()
-        .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
-        .to_evm_address(eth_oracle)
+        .from_address("0x742d35cc6634c0532925a3b844bc9e7595f0beb7")
+        .to_address(eth_oracle)
         .amount(0)
         .with_data(encode_oracle_update_evm(asset, aggregated.vwap_price_usd))
         .with_gas_limit(100000)
@@ -33,6 +33,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let congestion = estimator.get_congestion().await;
     println!("Network congestion level: {:?}", congestion.level);
     println!("Average block fullness: {:.2}%", congestion.avg_block_fullness * 100.0);
+    println!(
+        "Normal / operational class utilization: {:.2}% / {:.2}%",
+        congestion.utilization.normal * 100.0,
+        congestion.utilization.operational * 100.0
+    );
     println!("Average fee: {} Planck", congestion.avg_fee);
     println!("Blocks analyzed: {}", congestion.blocks_analyzed);
     println!("Congestion multiplier: {:.2}x\n", congestion.multiplier());
@@ -67,7 +72,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("  Base fee: {} Planck", estimate.base_fee);
                 println!("  Length fee: {} Planck", estimate.length_fee);
                 println!("  Weight fee: {} Planck", estimate.weight_fee);
+                println!("  Margin: {} Planck", estimate.margin);
                 println!("  Tip: {} Planck", estimate.tip);
+                if let Some(multiplier) = estimate.fee_multiplier {
+                    println!("  Fee multiplier: {:.4}", multiplier);
+                }
                 println!("  Strategy multiplier: {:.2}x", strategy.multiplier());
 
                 if let Some(weight) = estimate.weight {
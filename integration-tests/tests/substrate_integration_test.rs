@@ -153,12 +153,15 @@ async fn test_substrate_transfer_transaction() {
     println!("\nExecuting transfer of {} units...", transfer_amount);
 
     let executor = adapter.transaction_executor();
-    let tx_hash = executor
+    let receipt = executor
         .transfer(&alice_wallet, bob_address, transfer_amount)
         .await
         .expect("Transfer should succeed");
 
-    println!("Transaction hash: {}", tx_hash);
+    println!(
+        "Transaction hash: {} (block #{}, fee {:?})",
+        receipt.tx_hash, receipt.block_number, receipt.actual_fee
+    );
 
     // Wait for transaction to be included in a block
     tokio::time::sleep(tokio::time::Duration::from_secs(12)).await;
@@ -80,8 +80,8 @@ async fn test_substrate_real_transfer_on_westend() {
     let amount = 10_000_000_000u128;
     let tx = sdk
         .transaction()
-        .from_substrate_account(&from_address)
-        .to_substrate_account(to_address)
+        .from_address(&from_address)
+        .to_address(to_address)
         .amount(amount)
         .build()
         .expect("Failed to build transaction");